@@ -1 +1 @@
-"--workspace --features slow_tests,build_wasms,sqlite-encrypted,chc --lib --tests --bins"
+"--workspace --features slow_tests,build_wasms,sqlite-encrypted,chc,op_replay --lib --tests --bins"
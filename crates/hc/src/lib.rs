@@ -93,6 +93,16 @@ pub enum CliSubcommand {
     Sandbox(hc_sandbox::HcSandbox),
     /// Run a local bootstrap and WebRTC signalling server.
     RunLocalServices(hc_run_local_services::HcRunLocalServices),
+    /// Drive synthetic load against a running conductor and report latencies.
+    DhtLoad(hc_sandbox::HcDhtLoad),
+    /// Decode and pretty-print bundles, hashes, msgpack payloads and state dumps.
+    Inspect(hc_sandbox::HcInspect),
+    /// Generate deterministic agents, chains and DHT ops for integration tests.
+    Fixt(hc_sandbox::HcFixt),
+    /// Import a source chain from the LMDB databases of a legacy conductor into a running conductor.
+    MigrateLegacy(hc_sandbox::HcMigrateLegacy),
+    /// Verify the signatures, hashes and chain linkage of exported records, offline.
+    Verify(hc_sandbox::HcVerify),
     /// Allow redirect of external subcommands (like `hc-scaffold` and `hc-launch`).
     #[command(external_subcommand)]
     External(Vec<String>),
@@ -107,6 +117,11 @@ impl CliSubcommand {
             CliSubcommand::WebApp(cmd) => cmd.run().await?,
             CliSubcommand::Sandbox(cmd) => cmd.run().await?,
             CliSubcommand::RunLocalServices(cmd) => cmd.run().await,
+            CliSubcommand::DhtLoad(cmd) => cmd.run().await?,
+            CliSubcommand::Inspect(cmd) => cmd.run().await?,
+            CliSubcommand::Fixt(cmd) => cmd.run().await?,
+            CliSubcommand::MigrateLegacy(cmd) => cmd.run().await?,
+            CliSubcommand::Verify(cmd) => cmd.run().await?,
             CliSubcommand::External(args) => {
                 let command_suffix = args.first().expect("Missing subcommand name");
                 Command::new(format!("hc-{}", command_suffix))
@@ -63,6 +63,11 @@ pub enum HcDnaBundleSubcommand {
         /// that can be used to run this happ on iOS
         #[arg(long)]
         dylib_ios: bool,
+
+        /// Pack the bundle a second time and check that both packs hash to
+        /// the same value, failing if the output is not reproducible.
+        #[arg(long)]
+        verify_reproducible: bool,
     },
 
     /// Unpack parts of the `.dna` bundle file into a specific directory.
@@ -145,6 +150,11 @@ pub enum HcAppBundleSubcommand {
         /// as each of the DNA files specified in the manifest.
         #[arg(short, long)]
         recursive: bool,
+
+        /// Pack the bundle a second time and check that both packs hash to
+        /// the same value, failing if the output is not reproducible.
+        #[arg(long)]
+        verify_reproducible: bool,
     },
 
     /// Unpack parts of the `.happ` bundle file into a specific directory.
@@ -229,6 +239,11 @@ pub enum HcWebAppBundleSubcommand {
         /// as each of the DNA files specified in the hApps' manifests.
         #[arg(short, long)]
         recursive: bool,
+
+        /// Pack the bundle a second time and check that both packs hash to
+        /// the same value, failing if the output is not reproducible.
+        #[arg(long)]
+        verify_reproducible: bool,
     },
 
     /// Unpack parts of the `.webhapp` bundle file into a specific directory.
@@ -305,12 +320,21 @@ impl HcDnaBundleSubcommand {
                 path,
                 output,
                 dylib_ios,
+                verify_reproducible,
             } => {
                 let name = get_dna_name(&path).await?;
                 let (bundle_path, _) =
                     crate::packing::pack::<ValidatedDnaManifest>(&path, output, name, dylib_ios)
                         .await?;
                 println!("Wrote bundle {}", bundle_path.to_string_lossy());
+                if verify_reproducible {
+                    let hash = crate::packing::verify_reproducible::<ValidatedDnaManifest>(
+                        &path,
+                        &bundle_path,
+                    )
+                    .await?;
+                    println!("Verified reproducible bundle with hash {}", hash);
+                }
             }
             Self::Unpack {
                 path,
@@ -357,6 +381,7 @@ impl HcAppBundleSubcommand {
                 path,
                 output,
                 recursive,
+                verify_reproducible,
             } => {
                 let name = get_app_name(&path).await?;
 
@@ -367,6 +392,12 @@ impl HcAppBundleSubcommand {
                 let (bundle_path, _) =
                     crate::packing::pack::<AppManifest>(&path, output, name, false).await?;
                 println!("Wrote bundle {}", bundle_path.to_string_lossy());
+                if verify_reproducible {
+                    let hash =
+                        crate::packing::verify_reproducible::<AppManifest>(&path, &bundle_path)
+                            .await?;
+                    println!("Verified reproducible bundle with hash {}", hash);
+                }
             }
             Self::Unpack {
                 path,
@@ -408,6 +439,7 @@ impl HcWebAppBundleSubcommand {
                 path,
                 output,
                 recursive,
+                verify_reproducible,
             } => {
                 let name = get_web_app_name(&path).await?;
 
@@ -418,6 +450,12 @@ impl HcWebAppBundleSubcommand {
                 let (bundle_path, _) =
                     crate::packing::pack::<WebAppManifest>(&path, output, name, false).await?;
                 println!("Wrote bundle {}", bundle_path.to_string_lossy());
+                if verify_reproducible {
+                    let hash =
+                        crate::packing::verify_reproducible::<WebAppManifest>(&path, &bundle_path)
+                            .await?;
+                    println!("Verified reproducible bundle with hash {}", hash);
+                }
             }
             Self::Unpack {
                 path,
@@ -508,6 +546,7 @@ pub async fn web_app_pack_recursive(web_app_workdir_path: &PathBuf) -> anyhow::R
             path: ffs::canonicalize(app_workdir_location).await?,
             output: None,
             recursive: true,
+            verify_reproducible: false,
         }
         .run()
         .await?;
@@ -533,6 +572,7 @@ pub async fn app_pack_recursive(app_workdir_path: &PathBuf) -> anyhow::Result<()
             path: dna_workdir_location,
             output: None,
             dylib_ios: false,
+            verify_reproducible: false,
         }
         .run()
         .await?;
@@ -42,6 +42,13 @@ pub enum HcBundleError {
 
     #[error(transparent)]
     ModuleCompileError(#[from] CompileError),
+
+    #[error("Packing is not reproducible, {path:?} hashed to {expected} but repacking hashed to {actual}")]
+    NotReproducible {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
 }
 
 /// HcBundle Result type.
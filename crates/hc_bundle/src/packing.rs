@@ -138,6 +138,31 @@ pub async fn pack<M: Manifest>(
     Ok((target_path, bundle))
 }
 
+/// Pack the working directory again and check that the result is identical to
+/// the bundle previously written to `bundle_path`, returning the bundle's hash.
+pub async fn verify_reproducible<M: Manifest>(
+    dir_path: &Path,
+    bundle_path: &Path,
+) -> HcBundleResult<String> {
+    let dir_path = ffs::canonicalize(dir_path).await?;
+    let repacked: Bundle<M> = Bundle::pack_yaml(&dir_path.join(M::path())).await?;
+    let expected = bundle_hash(&ffs::read(bundle_path).await?);
+    let actual = bundle_hash(&repacked.encode()?);
+    if expected != actual {
+        return Err(HcBundleError::NotReproducible {
+            path: bundle_path.to_owned(),
+            expected,
+            actual,
+        });
+    }
+    Ok(expected)
+}
+
+fn bundle_hash(bytes: &[u8]) -> String {
+    use holochain_types::dna::encode::{blake2b_256, holo_hash_encode};
+    holo_hash_encode(&blake2b_256(bytes))
+}
+
 fn dir_to_bundle_path(dir_path: &Path, name: String, extension: &str) -> HcBundleResult<PathBuf> {
     Ok(dir_path.join(format!("{}.{}", name, extension)))
 }
@@ -85,6 +85,20 @@ async fn test_packed_hash_consistency() {
     }
 }
 
+#[tokio::test]
+async fn test_verify_reproducible() {
+    let mut cmd = Command::cargo_bin("hc-app").unwrap();
+    let cmd = cmd.args([
+        "pack",
+        "--recursive",
+        "--verify-reproducible",
+        "tests/fixtures/my-app/",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("Verified reproducible bundle"));
+}
+
 #[tokio::test]
 async fn test_integrity() {
     let pack_dna = |path| async move {
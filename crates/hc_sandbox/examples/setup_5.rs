@@ -55,6 +55,11 @@ async fn main() -> anyhow::Result<()> {
             source: AppBundleSource::Bundle(bundle),
             membrane_proofs: Default::default(),
             network_seed: None,
+            validation_queue_orders: Default::default(),
+            missed_signals_buffer_size: None,
+            zome_call_timeout_ms: None,
+            warranted_authors: None,
+            egress_policy: None,
             #[cfg(feature = "chc")]
             ignore_genesis_failure: false,
         };
@@ -447,6 +447,7 @@ pub async fn add_admin_interface(cmd: &mut CmdRunner, args: AddAdminWs) -> anyho
                     port,
                     allowed_origins: AllowedOrigins::Any,
                 },
+                require_token: false,
             },
         ]))
         .await?;
@@ -516,6 +517,11 @@ pub async fn install_app_bundle(cmd: &mut CmdRunner, args: InstallApp) -> anyhow
         source: AppBundleSource::Path(path),
         membrane_proofs: Default::default(),
         network_seed,
+        validation_queue_orders: Default::default(),
+        missed_signals_buffer_size: None,
+        zome_call_timeout_ms: None,
+        warranted_authors: None,
+        egress_policy: None,
         #[cfg(feature = "chc")]
         ignore_genesis_failure: false,
     };
@@ -679,7 +685,7 @@ fn parse_agent_key(arg: &str) -> anyhow::Result<AgentPubKey> {
     AgentPubKey::try_from(arg).map_err(|e| anyhow::anyhow!("{:?}", e))
 }
 
-fn parse_dna_hash(arg: &str) -> anyhow::Result<DnaHash> {
+pub(crate) fn parse_dna_hash(arg: &str) -> anyhow::Result<DnaHash> {
     DnaHash::try_from(arg).map_err(|e| anyhow::anyhow!("{:?}", e))
 }
 
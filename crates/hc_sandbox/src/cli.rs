@@ -5,9 +5,11 @@ use clap::{ArgAction, Parser};
 use holochain_conductor_api::conductor::paths::ConfigRootPath;
 use holochain_trace::Output;
 use holochain_types::prelude::InstalledAppId;
+use holochain_util::pw::PwSource;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
 
 const DEFAULT_APP_ID: &str = "test-app";
 
@@ -28,6 +30,16 @@ pub struct HcSandbox {
     #[arg(long)]
     piped: bool,
 
+    /// Instead of the normal "interactive" passphrase mode, read the passphrase
+    /// from the first line of this file. The file must only be accessible by its owner.
+    #[arg(long, conflicts_with_all = &["piped", "passphrase_credential"])]
+    passphrase_file: Option<PathBuf>,
+
+    /// Instead of the normal "interactive" passphrase mode, read the passphrase
+    /// from the systemd credential with this name.
+    #[arg(long, conflicts_with = "piped")]
+    passphrase_credential: Option<String>,
+
     /// The log output option to use for Holochain.
     #[arg(long, default_value_t = Output::Log)]
     structured: Output,
@@ -116,6 +128,17 @@ pub struct Run {
     #[arg(short, long, value_delimiter = ',')]
     ports: Vec<u16>,
 
+    /// Supervise the conductor(s) while they run.
+    /// Each conductor's admin interface is checked periodically and any
+    /// conductor which has crashed or stopped responding is restarted
+    /// with the same config and admin port.
+    #[arg(long)]
+    supervise: bool,
+
+    /// The number of seconds between health checks when supervising.
+    #[arg(long, default_value_t = 5, requires = "supervise")]
+    health_check_interval: u64,
+
     /// (flattened)
     #[command(flatten)]
     existing: Existing,
@@ -124,7 +147,15 @@ pub struct Run {
 impl HcSandbox {
     /// Run this command
     pub async fn run(self) -> anyhow::Result<()> {
-        holochain_util::pw::pw_set_piped(self.piped);
+        holochain_util::pw::pw_set_source(if let Some(path) = &self.passphrase_file {
+            PwSource::Keyfile(path.clone())
+        } else if let Some(name) = &self.passphrase_credential {
+            PwSource::SystemdCredential(name.clone())
+        } else if self.piped {
+            PwSource::Piped
+        } else {
+            PwSource::Interactive
+        });
         match self.subcommand {
             HcSandboxSubcommand::Generate {
                 app_id,
@@ -157,13 +188,18 @@ impl HcSandbox {
 
                     let result = tokio::select! {
                         result = tokio::signal::ctrl_c() => result.map_err(anyhow::Error::from),
-                        result = run_n(&holochain_path, paths, ports, force_admin_ports, structured) => result,
+                        result = run_n(&holochain_path, paths, ports, force_admin_ports, structured, None) => result,
                     };
                     crate::save::release_ports(std::env::current_dir()?).await?;
                     return result;
                 }
             }
-            HcSandboxSubcommand::Run(Run { ports, existing }) => {
+            HcSandboxSubcommand::Run(Run {
+                ports,
+                supervise,
+                health_check_interval,
+                existing,
+            }) => {
                 let paths = existing.load()?;
                 if paths.is_empty() {
                     tracing::warn!("no paths available, exiting.");
@@ -171,10 +207,11 @@ impl HcSandbox {
                 }
                 let holochain_path = self.holochain_path.clone();
                 let force_admin_ports = self.force_admin_ports.clone();
+                let supervise = supervise.then(|| Duration::from_secs(health_check_interval));
 
                 let result = tokio::select! {
                     result = tokio::signal::ctrl_c() => result.map_err(anyhow::Error::from),
-                    result = run_n(&holochain_path, paths.into_iter().map(ConfigRootPath::from).collect(), ports, force_admin_ports, self.structured) => result,
+                    result = run_n(&holochain_path, paths.into_iter().map(ConfigRootPath::from).collect(), ports, force_admin_ports, self.structured, supervise) => result,
                 };
                 crate::save::release_ports(std::env::current_dir()?).await?;
                 return result;
@@ -242,13 +279,16 @@ impl LaunchInfo {
     }
 }
 
-/// Run a conductor for each path
+/// Run a conductor for each path.
+/// If `supervise` is set, each conductor is health checked at that interval
+/// and restarted if it crashes or stops responding.
 pub async fn run_n(
     holochain_path: &Path,
     paths: Vec<ConfigRootPath>,
     app_ports: Vec<u16>,
     force_admin_ports: Vec<u16>,
     structured: Output,
+    supervise: Option<Duration>,
 ) -> anyhow::Result<()> {
     let run_holochain = |holochain_path: PathBuf,
                          path: ConfigRootPath,
@@ -263,6 +303,7 @@ pub async fn run_n(
             ports,
             force_admin_port,
             structured,
+            supervise,
         )
         .await?;
         Result::<_, anyhow::Error>::Ok(())
@@ -65,16 +65,31 @@ pub struct Network {
     /// prior knowledge of each other.
     #[arg(short, long, value_parser = try_parse_url2)]
     pub bootstrap: Option<Url2>,
+
+    /// Discover peers on the local network with mDNS instead of
+    /// a bootstrap service, so that no infrastructure is needed
+    /// for conductors on the same LAN to find each other.
+    #[arg(long, conflicts_with = "bootstrap")]
+    pub mdns: bool,
 }
 
 #[derive(Debug, Parser, Clone)]
 pub enum NetworkType {
     /// A transport that uses the local memory transport protocol.
     Mem,
+    /// A transport over unix domain sockets, which can only reach
+    /// conductors on the same host.
+    #[cfg(unix)]
+    Local {
+        /// The directory to create endpoint sockets in, which must only be
+        /// accessible by the current user. Conductors using the same
+        /// directory can reach each other.
+        /// Defaults to a directory private to the user.
+        #[arg(long)]
+        socket_dir: Option<PathBuf>,
+    },
     // /// A transport that uses the QUIC protocol.
     // Quic(Quic),
-    // /// A transport that uses the MDNS protocol.
-    // Mdns,
     /// A transport that uses the WebRTC protocol.
     #[command(name = "webrtc")]
     WebRTC {
@@ -194,21 +209,21 @@ impl From<Network> for KitsuneP2pConfig {
         let Network {
             transport,
             bootstrap,
+            mdns,
         } = n;
         let mut kit = KitsuneP2pConfig::default();
         kit.bootstrap_service = bootstrap;
+        if mdns {
+            kit.network_type = kitsune_p2p_types::config::NetworkType::QuicMdns;
+        }
 
         match transport {
             NetworkType::Mem => (),
-            /*
-            NetworkType::Mdns => {
-                kit.network_type = kitsune_p2p_types::config::NetworkType::QuicMdns;
-                kit.transport_pool = vec![TransportConfig::Quic {
-                    bind_to: None,
-                    override_host: None,
-                    override_port: None,
-                }];
+            #[cfg(unix)]
+            NetworkType::Local { socket_dir } => {
+                kit.transport_pool = vec![TransportConfig::Local { socket_dir }];
             }
+            /*
             NetworkType::Quic(Quic {
                 bind_to,
                 override_host,
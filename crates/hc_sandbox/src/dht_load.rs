@@ -0,0 +1,619 @@
+//! A traffic generator which drives synthetic load against the DHT of a running conductor.
+//!
+//! Load is generated by calling zome functions of an installed app, so the app
+//! needs to expose functions with the following signatures in a single zome:
+//!
+//! - create: takes a `String` entry content and returns the `ActionHash` of the created entry.
+//! - link: takes the `ActionHash` returned by create and creates one link from it.
+//! - read: takes an `ActionHash` returned by create and reads it back, with any return type.
+//!
+//! The names of these functions can be configured, see [`HcDhtLoad`].
+
+use std::collections::BTreeSet;
+use std::net::ToSocketAddrs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::anyhow;
+use anyhow::bail;
+use anyhow::Context;
+use clap::Parser;
+use holochain_conductor_api::AdminRequest;
+use holochain_conductor_api::AdminResponse;
+use holochain_conductor_api::AppAuthenticationRequest;
+use holochain_conductor_api::AppRequest;
+use holochain_conductor_api::AppResponse;
+use holochain_conductor_api::CellInfo;
+use holochain_conductor_api::ZomeCall;
+use holochain_types::prelude::*;
+use holochain_types::websocket::AllowedOrigins;
+use holochain_websocket::ConnectRequest;
+use holochain_websocket::WebsocketConfig;
+use holochain_websocket::WebsocketSender;
+
+use crate::calls::attach_app_interface;
+use crate::calls::list_app_ws;
+use crate::calls::AddAppWs;
+use crate::CmdRunner;
+
+const DHT_LOAD_ORIGIN: &str = "hc-dht-load";
+
+/// Drive configurable synthetic load against a running conductor and report
+/// latency percentiles and failure counts.
+///
+/// Every create is followed by `--link-fanout` link calls on the created entry,
+/// and reads of previously created entries are mixed in at `--read-ratio` reads per create.
+#[derive(Debug, Parser, Clone)]
+pub struct HcDhtLoad {
+    /// Admin port of the running conductor.
+    #[arg(short, long)]
+    pub running: u16,
+
+    /// The installed app to call.
+    #[arg(short, long)]
+    pub app_id: InstalledAppId,
+
+    /// The role of the cell to call.
+    /// Defaults to the first provisioned cell of the app.
+    #[arg(long)]
+    pub role: Option<RoleName>,
+
+    /// The zome which exposes the create, link and read functions.
+    #[arg(short, long)]
+    pub zome: String,
+
+    /// The zome function which creates an entry.
+    #[arg(long, default_value = "create_entry")]
+    pub create_fn: String,
+
+    /// The zome function which creates a link from an entry.
+    #[arg(long, default_value = "create_link")]
+    pub link_fn: String,
+
+    /// The zome function which reads an entry.
+    #[arg(long, default_value = "get_entry")]
+    pub read_fn: String,
+
+    /// How many entries to create per second.
+    #[arg(long, default_value_t = 10.0)]
+    pub creates_per_sec: f64,
+
+    /// The size in bytes of the content of each created entry.
+    #[arg(long, default_value_t = 1024)]
+    pub entry_size: usize,
+
+    /// How many links to create from each created entry.
+    #[arg(long, default_value_t = 0)]
+    pub link_fanout: u32,
+
+    /// How many reads of previously created entries to make per create.
+    #[arg(long, default_value_t = 1.0)]
+    pub read_ratio: f64,
+
+    /// How long to generate load for, in seconds.
+    #[arg(short, long, default_value_t = 60)]
+    pub duration: u64,
+
+    /// The maximum number of creates which are in flight at once,
+    /// including the links and reads which follow them.
+    #[arg(long, default_value_t = 32)]
+    pub concurrency: usize,
+
+    /// The file which keeps the signing key and capability secret of the load client.
+    /// Later runs against the same cell and functions reuse them, and so the
+    /// capability grant, instead of committing a new grant each time.
+    #[arg(long, default_value = ".hc_dht_load")]
+    pub credentials: PathBuf,
+}
+
+impl HcDhtLoad {
+    /// Run this command
+    pub async fn run(self) -> anyhow::Result<()> {
+        if self.creates_per_sec <= 0.0 {
+            bail!("--creates-per-sec must be greater than zero");
+        }
+        if self.read_ratio < 0.0 {
+            bail!("--read-ratio must not be negative");
+        }
+
+        let mut cmd = CmdRunner::try_new(self.running).await?;
+        let client = LoadClient::connect(&mut cmd, &self).await?;
+        msg!(
+            "Generating load against {:?} for {}s at {} creates/s",
+            client.cell_id,
+            self.duration,
+            self.creates_per_sec
+        );
+
+        let report = generate_load(Arc::new(client), &self).await;
+        msg!("{}", report);
+        Ok(())
+    }
+}
+
+/// The kinds of zome call which make up the load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LoadOp {
+    Create,
+    Link,
+    Read,
+}
+
+/// Latencies and failures of the calls of a single [`LoadOp`].
+#[derive(Debug, Default)]
+struct OpStats {
+    latencies: Vec<Duration>,
+    failures: usize,
+}
+
+impl OpStats {
+    fn record(&mut self, latency: Duration, ok: bool) {
+        if ok {
+            self.latencies.push(latency);
+        } else {
+            self.failures += 1;
+        }
+    }
+
+    /// The latency which `p` percent of successful calls were faster than or equal to.
+    fn percentile(&self, p: f64) -> Option<Duration> {
+        if self.latencies.is_empty() {
+            return None;
+        }
+        let mut sorted = self.latencies.clone();
+        sorted.sort_unstable();
+        let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.clamp(1, sorted.len()) - 1])
+    }
+}
+
+/// The outcome of a load run.
+#[derive(Debug, Default)]
+struct LoadReport {
+    elapsed: Duration,
+    create: OpStats,
+    link: OpStats,
+    read: OpStats,
+}
+
+impl LoadReport {
+    fn stats_mut(&mut self, op: LoadOp) -> &mut OpStats {
+        match op {
+            LoadOp::Create => &mut self.create,
+            LoadOp::Link => &mut self.link,
+            LoadOp::Read => &mut self.read,
+        }
+    }
+}
+
+impl std::fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Load report after {:.1}s", self.elapsed.as_secs_f64())?;
+        writeln!(
+            f,
+            "{:<8}{:>8}{:>10}{:>10}{:>10}{:>10}{:>10}{:>10}",
+            "op", "ok", "failed", "ops/s", "p50 ms", "p90 ms", "p99 ms", "max ms"
+        )?;
+        for (name, stats) in [
+            ("create", &self.create),
+            ("link", &self.link),
+            ("read", &self.read),
+        ] {
+            let ms = |p| {
+                stats
+                    .percentile(p)
+                    .map(|d| format!("{:.1}", d.as_secs_f64() * 1000.0))
+                    .unwrap_or_else(|| "-".to_string())
+            };
+            let secs = self.elapsed.as_secs_f64();
+            let rate = if secs > 0.0 {
+                stats.latencies.len() as f64 / secs
+            } else {
+                0.0
+            };
+            writeln!(
+                f,
+                "{:<8}{:>8}{:>10}{:>10.1}{:>10}{:>10}{:>10}{:>10}",
+                name,
+                stats.latencies.len(),
+                stats.failures,
+                rate,
+                ms(50.0),
+                ms(90.0),
+                ms(99.0),
+                ms(100.0)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Shared state of a load run.
+#[derive(Default)]
+struct LoadState {
+    report: LoadReport,
+    created: Vec<ActionHash>,
+    read_credit: f64,
+    next_read: usize,
+}
+
+impl LoadState {
+    /// Take the entries to read after a create, spreading the reads over all created entries.
+    fn take_reads(&mut self, read_ratio: f64) -> Vec<ActionHash> {
+        self.read_credit += read_ratio;
+        let mut reads = Vec::new();
+        while self.read_credit >= 1.0 && !self.created.is_empty() {
+            self.read_credit -= 1.0;
+            reads.push(self.created[self.next_read % self.created.len()].clone());
+            self.next_read = self.next_read.wrapping_add(1);
+        }
+        reads
+    }
+}
+
+async fn generate_load(client: Arc<LoadClient>, args: &HcDhtLoad) -> LoadReport {
+    let state = Arc::new(Mutex::default());
+    let permits = Arc::new(tokio::sync::Semaphore::new(args.concurrency.max(1)));
+    let content = "x".repeat(args.entry_size);
+    let deadline = Instant::now() + Duration::from_secs(args.duration);
+    let mut interval = tokio::time::interval(Duration::from_secs_f64(1.0 / args.creates_per_sec));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    let started = Instant::now();
+    let mut tasks = tokio::task::JoinSet::new();
+    while Instant::now() < deadline {
+        interval.tick().await;
+        let Ok(permit) = permits.clone().acquire_owned().await else {
+            break;
+        };
+        let client = client.clone();
+        let state = state.clone();
+        let content = content.clone();
+        let args = args.clone();
+        tasks.spawn(async move {
+            let _permit = permit;
+            let (result, latency) = client.call::<_, ActionHash>(&args.create_fn, content).await;
+            record(&state, LoadOp::Create, latency, &result);
+            let Ok(hash) = result else {
+                return;
+            };
+
+            for _ in 0..args.link_fanout {
+                let (result, latency) = client
+                    .call::<_, serde::de::IgnoredAny>(&args.link_fn, hash.clone())
+                    .await;
+                record(&state, LoadOp::Link, latency, &result);
+            }
+
+            let reads = {
+                let mut state = state.lock().expect("load state poisoned");
+                state.created.push(hash);
+                state.take_reads(args.read_ratio)
+            };
+            for read in reads {
+                let (result, latency) = client
+                    .call::<_, serde::de::IgnoredAny>(&args.read_fn, read)
+                    .await;
+                record(&state, LoadOp::Read, latency, &result);
+            }
+        });
+    }
+    while tasks.join_next().await.is_some() {}
+
+    let mut state = state.lock().expect("load state poisoned");
+    let mut report = std::mem::take(&mut state.report);
+    report.elapsed = started.elapsed();
+    report
+}
+
+fn record<T>(state: &Mutex<LoadState>, op: LoadOp, latency: Duration, result: &anyhow::Result<T>) {
+    if let Err(e) = result {
+        tracing::debug!(?op, ?e, "load call failed");
+    }
+    state
+        .lock()
+        .expect("load state poisoned")
+        .report
+        .stats_mut(op)
+        .record(latency, result.is_ok());
+}
+
+/// The signing key and capability secret of a load client, kept between runs
+/// so that its capability grant is only committed once.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct LoadCredentials {
+    cell_id: CellId,
+    functions: BTreeSet<(ZomeName, FunctionName)>,
+    signing_seed: [u8; sodoken::sign::SEEDBYTES],
+    cap_secret: CapSecret,
+}
+
+impl LoadCredentials {
+    /// Read the credentials from `path`, if there are any for the cell and functions.
+    fn load(
+        path: &Path,
+        cell_id: &CellId,
+        functions: &BTreeSet<(ZomeName, FunctionName)>,
+    ) -> anyhow::Result<Option<Self>> {
+        let json = match std::fs::read(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).context("Failed to read the load client credentials"),
+        };
+        let credentials: Self = serde_json::from_slice(&json)
+            .with_context(|| format!("Invalid load client credentials in {}", path.display()))?;
+        let matches = credentials.cell_id == *cell_id && credentials.functions == *functions;
+        Ok(matches.then_some(credentials))
+    }
+
+    /// Write the credentials to `path`, readable only by the current user.
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        use std::io::Write;
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options
+            .open(path)
+            .and_then(|mut file| file.write_all(&serde_json::to_vec(self)?))
+            .context("Failed to write the load client credentials")
+    }
+}
+
+/// An authenticated app connection which signs zome calls with its own key.
+struct LoadClient {
+    app_ws: WebsocketSender,
+    recv_task: tokio::task::JoinHandle<()>,
+    cell_id: CellId,
+    zome: ZomeName,
+    cap_secret: CapSecret,
+    signing_key: AgentPubKey,
+    signing_secret: sodoken::BufReadSized<{ sodoken::sign::SECRETKEYBYTES }>,
+}
+
+impl Drop for LoadClient {
+    fn drop(&mut self) {
+        self.recv_task.abort();
+    }
+}
+
+impl LoadClient {
+    /// Connect to the app interface of an earlier run, or attach a new one, and
+    /// authorize a signing key for the load functions of the cell.
+    ///
+    /// The signing key is reused from the credentials file when it was
+    /// authorized for the same cell and functions, otherwise a fresh key is
+    /// generated and granted access.
+    async fn connect(cmd: &mut CmdRunner, args: &HcDhtLoad) -> anyhow::Result<Self> {
+        let allowed_origins = AllowedOrigins::from(DHT_LOAD_ORIGIN.to_string());
+        let existing = list_app_ws(cmd).await?.into_iter().find(|info| {
+            info.allowed_origins == allowed_origins
+                && info.installed_app_id.as_ref() == Some(&args.app_id)
+        });
+        let port = match existing {
+            Some(info) => info.port,
+            None => {
+                attach_app_interface(
+                    cmd,
+                    AddAppWs {
+                        port: None,
+                        allowed_origins,
+                        installed_app_id: Some(args.app_id.clone()),
+                    },
+                )
+                .await?
+            }
+        };
+
+        let resp = cmd
+            .command(AdminRequest::IssueAppAuthenticationToken(
+                args.app_id.clone().into(),
+            ))
+            .await?;
+        let token = match resp {
+            AdminResponse::AppAuthenticationTokenIssued(issued) => issued.token,
+            _ => bail!("Failed to issue app authentication token, got: {:?}", resp),
+        };
+
+        let addr = format!("localhost:{port}")
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| anyhow!("Could not resolve localhost"))?;
+        let (app_ws, mut recv) = holochain_websocket::connect(
+            Arc::new(WebsocketConfig::CLIENT_DEFAULT),
+            ConnectRequest::new(addr).try_set_header("Origin", DHT_LOAD_ORIGIN)?,
+        )
+        .await?;
+        let recv_task =
+            tokio::task::spawn(async move { while recv.recv::<AppResponse>().await.is_ok() {} });
+        app_ws
+            .authenticate(AppAuthenticationRequest { token })
+            .await
+            .context("Failed to authenticate app connection")?;
+
+        let resp: AppResponse = app_ws.request(AppRequest::AppInfo).await?;
+        let app_info = match resp {
+            AppResponse::AppInfo(Some(app_info)) => app_info,
+            _ => bail!("App {} not found, got: {:?}", args.app_id, resp),
+        };
+        let cell_id = app_info
+            .cell_info
+            .iter()
+            .filter(|(role, _)| args.role.as_ref().map_or(true, |r| r == *role))
+            .flat_map(|(_, cells)| cells.iter())
+            .find_map(|cell| match cell {
+                CellInfo::Provisioned(cell) => Some(cell.cell_id.clone()),
+                _ => None,
+            })
+            .ok_or_else(|| anyhow!("No provisioned cell found for the requested role"))?;
+
+        let zome = ZomeName::from(args.zome.clone());
+        let functions: BTreeSet<_> = [&args.create_fn, &args.link_fn, &args.read_fn]
+            .into_iter()
+            .map(|f| (zome.clone(), FunctionName::from(f.clone())))
+            .collect();
+        let credentials = LoadCredentials::load(&args.credentials, &cell_id, &functions)?;
+        let granted = credentials.is_some();
+        let credentials = match credentials {
+            Some(credentials) => credentials,
+            None => {
+                let seed: sodoken::BufWriteSized<{ sodoken::sign::SEEDBYTES }> =
+                    sodoken::BufWriteSized::new_no_lock();
+                sodoken::random::bytes_buf(seed.clone()).await?;
+                let secret: sodoken::BufWriteSized<CAP_SECRET_BYTES> =
+                    sodoken::BufWriteSized::new_no_lock();
+                sodoken::random::bytes_buf(secret.clone()).await?;
+                LoadCredentials {
+                    cell_id: cell_id.clone(),
+                    functions: functions.clone(),
+                    signing_seed: *seed.read_lock_sized(),
+                    cap_secret: CapSecret::from(*secret.read_lock_sized()),
+                }
+            }
+        };
+
+        let signing_public: sodoken::BufWriteSized<{ sodoken::sign::PUBLICKEYBYTES }> =
+            sodoken::BufWriteSized::new_no_lock();
+        let signing_secret: sodoken::BufWriteSized<{ sodoken::sign::SECRETKEYBYTES }> =
+            sodoken::BufWriteSized::new_mem_locked()?;
+        sodoken::sign::seed_keypair(
+            signing_public.clone(),
+            signing_secret.clone(),
+            sodoken::BufReadSized::from(credentials.signing_seed),
+        )
+        .await?;
+        let signing_key = AgentPubKey::from_raw_32(signing_public.read_lock_sized().to_vec());
+        let cap_secret = credentials.cap_secret;
+
+        if !granted {
+            let resp = cmd
+                .command(AdminRequest::GrantZomeCallCapability(Box::new(
+                    GrantZomeCallCapabilityPayload {
+                        cell_id: cell_id.clone(),
+                        cap_grant: ZomeCallCapGrant {
+                            tag: DHT_LOAD_ORIGIN.to_string(),
+                            access: CapAccess::Assigned {
+                                secret: cap_secret,
+                                assignees: BTreeSet::from([signing_key.clone()]),
+                            },
+                            functions: GrantedFunctions::Listed(functions),
+                        },
+                    },
+                )))
+                .await?;
+            if !matches!(resp, AdminResponse::ZomeCallCapabilityGranted) {
+                bail!("Failed to grant zome call capability, got: {:?}", resp);
+            }
+            credentials.save(&args.credentials)?;
+        }
+
+        Ok(Self {
+            app_ws,
+            recv_task,
+            cell_id,
+            zome,
+            cap_secret,
+            signing_key,
+            signing_secret: signing_secret.to_read_sized(),
+        })
+    }
+
+    /// Call a zome function, returning the decoded result and how long the call took.
+    async fn call<I, O>(&self, fn_name: &str, payload: I) -> (anyhow::Result<O>, Duration)
+    where
+        I: serde::Serialize + std::fmt::Debug,
+        O: serde::de::DeserializeOwned + std::fmt::Debug,
+    {
+        let started = Instant::now();
+        let result = self.call_inner(fn_name, payload).await;
+        (result, started.elapsed())
+    }
+
+    async fn call_inner<I, O>(&self, fn_name: &str, payload: I) -> anyhow::Result<O>
+    where
+        I: serde::Serialize + std::fmt::Debug,
+        O: serde::de::DeserializeOwned + std::fmt::Debug,
+    {
+        let (nonce, expires_at) =
+            holochain_nonce::fresh_nonce(Timestamp::now()).map_err(|e| anyhow!(e))?;
+        let unsigned = ZomeCallUnsigned {
+            provenance: self.signing_key.clone(),
+            cell_id: self.cell_id.clone(),
+            zome_name: self.zome.clone(),
+            fn_name: FunctionName::from(fn_name.to_string()),
+            cap_secret: Some(self.cap_secret),
+            payload: ExternIO::encode(payload)?,
+            nonce,
+            expires_at,
+            wait_for_publish: None,
+        };
+
+        let signature: sodoken::BufWriteSized<{ sodoken::sign::BYTES }> =
+            sodoken::BufWriteSized::new_no_lock();
+        sodoken::sign::detached(
+            signature.clone(),
+            unsigned.data_to_sign()?.to_vec(),
+            self.signing_secret.clone(),
+        )
+        .await?;
+        let signature = Signature::from(*signature.read_lock_sized());
+
+        let resp: AppResponse = self
+            .app_ws
+            .request(AppRequest::CallZome(Box::new(ZomeCall {
+                cell_id: unsigned.cell_id,
+                zome_name: unsigned.zome_name,
+                fn_name: unsigned.fn_name,
+                payload: unsigned.payload,
+                cap_secret: unsigned.cap_secret,
+                provenance: unsigned.provenance,
+                signature,
+                nonce: unsigned.nonce,
+                expires_at: unsigned.expires_at,
+                wait_for_publish: None,
+                timeout_ms: None,
+            })))
+            .await?;
+        match resp {
+            AppResponse::ZomeCalled(output) => Ok(output.decode()?),
+            _ => bail!("Zome call to {} failed: {:?}", fn_name, resp),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let mut stats = OpStats::default();
+        for ms in (1..=100).rev() {
+            stats.record(Duration::from_millis(ms), true);
+        }
+        stats.record(Duration::from_secs(10), false);
+
+        assert_eq!(stats.failures, 1);
+        assert_eq!(stats.percentile(50.0), Some(Duration::from_millis(50)));
+        assert_eq!(stats.percentile(99.0), Some(Duration::from_millis(99)));
+        assert_eq!(stats.percentile(100.0), Some(Duration::from_millis(100)));
+        assert_eq!(OpStats::default().percentile(50.0), None);
+    }
+
+    #[test]
+    fn reads_follow_the_read_ratio() {
+        let mut state = LoadState::default();
+        // Nothing to read yet, so the credit builds up.
+        assert!(state.take_reads(0.5).is_empty());
+
+        state.created.push(ActionHash::from_raw_36(vec![1; 36]));
+        assert_eq!(state.take_reads(0.5).len(), 1);
+        assert!(state.take_reads(0.5).is_empty());
+        assert_eq!(state.take_reads(2.0).len(), 2);
+    }
+}
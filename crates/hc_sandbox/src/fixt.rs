@@ -0,0 +1,100 @@
+//! Generate deterministic fixture data for integration tests.
+//!
+//! The same seed and sizes always produce the same agents, chains, entries, links
+//! and countersigned entries, so tests can share fixtures without checking in
+//! large database files.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+use clap::Parser;
+use clap::ValueEnum;
+use holochain_state::fixture_data::insert_fixture_data;
+use holochain_state::prelude::DbKindDht;
+use holochain_state::prelude::DbWrite;
+use holochain_types::fixture_data::FixtureConfig;
+use holochain_types::fixture_data::FixtureData;
+
+/// Generate deterministic agents, source chains and DHT ops from a seed.
+#[derive(Debug, Parser)]
+pub struct HcFixt {
+    /// The seed to generate the data from.
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+
+    /// The number of agents to generate a source chain for.
+    #[arg(long, default_value_t = 3)]
+    pub agents: usize,
+
+    /// The number of app entries each agent creates.
+    #[arg(long, default_value_t = 10)]
+    pub entries_per_agent: usize,
+
+    /// The number of links each agent creates from each of its entries.
+    #[arg(long, default_value_t = 1)]
+    pub links_per_entry: usize,
+
+    /// The number of countersigned entries created by pairs of agents.
+    #[arg(long, default_value_t = 1)]
+    pub countersigned_entries: usize,
+
+    /// The size in bytes of each entry.
+    #[arg(long, default_value_t = 64)]
+    pub entry_size: usize,
+
+    /// What to write the generated data as.
+    #[arg(long, value_enum, default_value_t = FixtFormat::Archive)]
+    pub format: FixtFormat,
+
+    /// Where to write the data. A file for an archive, or a directory
+    /// to create the DHT database in for sqlite.
+    pub out: PathBuf,
+}
+
+/// The formats fixture data can be written as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FixtFormat {
+    /// A DHT op archive, as read by the `ImportDhtArchive` admin call.
+    Archive,
+    /// A DHT sqlite database with every op integrated and valid.
+    Sqlite,
+}
+
+impl HcFixt {
+    /// Run this command.
+    pub async fn run(self) -> anyhow::Result<()> {
+        let config = FixtureConfig {
+            seed: self.seed,
+            agents: self.agents,
+            entries_per_agent: self.entries_per_agent,
+            links_per_entry: self.links_per_entry,
+            countersigned_entries: self.countersigned_entries,
+            entry_size: self.entry_size,
+        };
+        let data = FixtureData::generate(&config);
+        let ops = match self.format {
+            FixtFormat::Archive => {
+                let archive = data.to_dht_archive()?;
+                std::fs::write(&self.out, archive)
+                    .with_context(|| format!("Failed to write {}", self.out.display()))?;
+                data.ops().len()
+            }
+            FixtFormat::Sqlite => {
+                std::fs::create_dir_all(&self.out)?;
+                let db = DbWrite::open(&self.out, DbKindDht(Arc::new(data.dna_hash.clone())))?;
+                insert_fixture_data(&db, &data).await?
+            }
+        };
+        msg!(
+            "Wrote {} ops for DNA {} to {}",
+            ops,
+            data.dna_hash,
+            self.out.display()
+        );
+        for agent in &data.agents {
+            msg!("Agent {}", agent);
+        }
+        Ok(())
+    }
+}
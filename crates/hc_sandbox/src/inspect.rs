@@ -0,0 +1,424 @@
+//! Decode and pretty-print Holochain artifacts, for debugging.
+//!
+//! Supports `.dna`, `.happ` and `.webhapp` bundle files, base64 encoded
+//! HoloHashes, msgpack encoded payloads like the bytes of an [`ExternIO`],
+//! and JSON state dump files.
+
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::bail;
+use anyhow::Context;
+use base64::Engine;
+use clap::Parser;
+use clap::Subcommand;
+use holo_hash::hash_type;
+use holo_hash::PrimitiveHashType;
+use holo_hash::HOLO_HASH_CORE_LEN;
+use holo_hash::HOLO_HASH_FULL_LEN;
+use holo_hash::HOLO_HASH_PREFIX_LEN;
+use holochain_conductor_api::FullStateDump;
+use holochain_conductor_api::JsonDump;
+use holochain_conductor_api::StateDumpItems;
+use holochain_conductor_api::StateDumpPage;
+use holochain_types::prelude::*;
+use holochain_types::web_app::WebAppBundle;
+use holochain_util::hex::bytes_to_hex;
+use serde::de::MapAccess;
+use serde::de::SeqAccess;
+use serde::de::Visitor;
+use serde::Deserializer;
+
+/// Decode and pretty-print a Holochain artifact.
+#[derive(Debug, Parser)]
+pub struct HcInspect {
+    /// The kind of artifact to inspect.
+    #[command(subcommand)]
+    pub subcommand: HcInspectSubcommand,
+}
+
+/// The kinds of artifact which can be inspected.
+#[derive(Debug, Subcommand)]
+pub enum HcInspectSubcommand {
+    /// Print the manifest and bundled resources of a `.dna`, `.happ` or `.webhapp` file,
+    /// along with the hashes of the DNAs it contains.
+    Bundle {
+        /// The path to the bundle file.
+        path: PathBuf,
+    },
+
+    /// Print the type, core bytes and DHT location of a base64 encoded HoloHash,
+    /// e.g. `uhCAk...`.
+    Hash {
+        /// The hash to inspect.
+        hash: String,
+    },
+
+    /// Decode a msgpack encoded payload, like the bytes of an `ExternIO`, and print it as JSON.
+    ///
+    /// Byte arrays which are valid HoloHashes are printed in their base64 form,
+    /// any other byte arrays are printed as hex.
+    Payload {
+        /// The payload, base64 encoded unless `--hex` is given.
+        #[arg(required_unless_present = "file")]
+        payload: Option<String>,
+
+        /// The payload is hex encoded rather than base64 encoded.
+        #[arg(long)]
+        hex: bool,
+
+        /// Read the raw payload bytes from a file instead.
+        #[arg(short, long, conflicts_with = "payload")]
+        file: Option<PathBuf>,
+    },
+
+    /// Print a summary and the contents of a JSON state dump file, as returned by
+    /// the `DumpState`, `DumpFullState` and `DumpStatePage` admin calls.
+    StateDump {
+        /// The path to the state dump file.
+        path: PathBuf,
+    },
+}
+
+impl HcInspect {
+    /// Run this command
+    pub async fn run(self) -> anyhow::Result<()> {
+        match self.subcommand {
+            HcInspectSubcommand::Bundle { path } => inspect_bundle(&path).await,
+            HcInspectSubcommand::Hash { hash } => {
+                println!("{}", inspect_hash(&hash)?);
+                Ok(())
+            }
+            HcInspectSubcommand::Payload { payload, hex, file } => {
+                let bytes = match (payload, file) {
+                    (_, Some(file)) => std::fs::read(&file)
+                        .with_context(|| format!("Failed to read {}", file.display()))?,
+                    (Some(payload), None) if hex => hex_to_bytes(&payload)?,
+                    (Some(payload), None) => base64::engine::general_purpose::STANDARD
+                        .decode(payload.trim())
+                        .context("The payload is not valid base64")?,
+                    (None, None) => bail!("Either a payload or --file must be given"),
+                };
+                println!("{}", serde_json::to_string_pretty(&decode_payload(bytes)?)?);
+                Ok(())
+            }
+            HcInspectSubcommand::StateDump { path } => {
+                let json = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                println!("{}", inspect_state_dump(&json)?);
+                Ok(())
+            }
+        }
+    }
+}
+
+async fn inspect_bundle(path: &Path) -> anyhow::Result<()> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("dna") => {
+            let bundle = DnaBundle::decode(&bytes)?;
+            println!(
+                "--- DNA manifest ---\n{}",
+                serde_yaml::to_string(bundle.manifest())?
+            );
+            print_resources(bundle.bundled_resources());
+            let (dna_file, _) = bundle.into_dna_file(DnaModifiersOpt::none()).await?;
+            println!("DNA hash: {}", dna_file.dna_hash());
+        }
+        Some("happ") => {
+            let bundle = AppBundle::decode(&bytes)?;
+            println!(
+                "--- hApp manifest ---\n{}",
+                serde_yaml::to_string(bundle.manifest())?
+            );
+            print_resources(bundle.bundled_resources());
+            for (resource_path, resource) in bundle.bundled_resources() {
+                let (dna_file, _) = DnaBundle::decode(resource.inner())?
+                    .into_dna_file(DnaModifiersOpt::none())
+                    .await?;
+                println!(
+                    "DNA hash of {}: {}",
+                    resource_path.display(),
+                    dna_file.dna_hash()
+                );
+            }
+        }
+        Some("webhapp") => {
+            let bundle = WebAppBundle::decode(&bytes)?;
+            println!(
+                "--- Web hApp manifest ---\n{}",
+                serde_yaml::to_string(bundle.manifest())?
+            );
+            print_resources(bundle.bundled_resources());
+        }
+        _ => bail!(
+            "Expected a .dna, .happ or .webhapp bundle file: {}",
+            path.display()
+        ),
+    }
+    Ok(())
+}
+
+fn print_resources<'a, R>(resources: impl IntoIterator<Item = (&'a PathBuf, &'a R)>)
+where
+    R: std::ops::Deref<Target = Vec<u8>> + 'a,
+{
+    println!("--- Bundled resources ---");
+    for (path, bytes) in resources {
+        println!("{} ({} bytes)", path.display(), bytes.len());
+    }
+}
+
+/// The parts of a decoded HoloHash.
+#[derive(Debug, PartialEq, Eq)]
+pub struct HashInspection {
+    /// The name of the hash type, if the prefix is a known one.
+    pub hash_type: Option<&'static str>,
+    /// The 3 prefix bytes.
+    pub prefix: Vec<u8>,
+    /// The 32 core bytes.
+    pub core: Vec<u8>,
+    /// The 4 location bytes.
+    pub loc_bytes: Vec<u8>,
+    /// The DHT location the location bytes encode.
+    pub loc: u32,
+}
+
+impl fmt::Display for HashInspection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Type:     {}", self.hash_type.unwrap_or("unknown"))?;
+        writeln!(f, "Prefix:   0x{}", bytes_to_hex(&self.prefix, false))?;
+        writeln!(f, "Core:     0x{}", bytes_to_hex(&self.core, false))?;
+        write!(
+            f,
+            "Location: 0x{} ({})",
+            bytes_to_hex(&self.loc_bytes, false),
+            self.loc
+        )
+    }
+}
+
+/// Decode a base64 encoded HoloHash of any type into its parts.
+pub fn inspect_hash(hash: &str) -> anyhow::Result<HashInspection> {
+    let hash = hash.trim();
+    if hash.is_empty() || !hash.is_ascii() {
+        bail!("Not a valid HoloHash: {}", hash);
+    }
+    let raw = holo_hash::holo_hash_decode_unchecked(hash)
+        .map_err(|e| anyhow::anyhow!("Not a valid HoloHash: {}", e))?;
+    let (prefix, rest) = raw.split_at(HOLO_HASH_PREFIX_LEN);
+    let (core, loc_bytes) = rest.split_at(HOLO_HASH_CORE_LEN);
+    Ok(HashInspection {
+        hash_type: hash_type_name(prefix),
+        prefix: prefix.to_vec(),
+        core: core.to_vec(),
+        loc_bytes: loc_bytes.to_vec(),
+        loc: u32::from_le_bytes(loc_bytes.try_into()?),
+    })
+}
+
+fn hash_type_name(prefix: &[u8]) -> Option<&'static str> {
+    fn name_if<T: PrimitiveHashType>(prefix: &[u8]) -> Option<&'static str> {
+        (prefix == T::static_prefix()).then(|| PrimitiveHashType::hash_name(T::new()))
+    }
+    name_if::<hash_type::Agent>(prefix)
+        .or_else(|| name_if::<hash_type::Entry>(prefix))
+        .or_else(|| name_if::<hash_type::Action>(prefix))
+        .or_else(|| name_if::<hash_type::DhtOp>(prefix))
+        .or_else(|| name_if::<hash_type::Dna>(prefix))
+        .or_else(|| name_if::<hash_type::NetId>(prefix))
+        .or_else(|| name_if::<hash_type::Wasm>(prefix))
+        .or_else(|| name_if::<hash_type::External>(prefix))
+}
+
+fn hex_to_bytes(hex: &str) -> anyhow::Result<Vec<u8>> {
+    let hex = hex.trim();
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        bail!("The payload is not valid hex");
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).context("The payload is not valid hex"))
+        .collect()
+}
+
+/// Decode msgpack encoded bytes into JSON for display.
+pub fn decode_payload(bytes: Vec<u8>) -> anyhow::Result<serde_json::Value> {
+    Ok(ExternIO(bytes)
+        .decode::<DisplayValue>()
+        .context("The payload is not valid msgpack")?
+        .0)
+}
+
+/// Any msgpack value, with byte arrays rendered as strings so that
+/// it can be displayed as JSON.
+#[derive(Debug)]
+struct DisplayValue(serde_json::Value);
+
+impl<'de> serde::Deserialize<'de> for DisplayValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(DisplayValueVisitor)
+    }
+}
+
+struct DisplayValueVisitor;
+
+impl<'de> Visitor<'de> for DisplayValueVisitor {
+    type Value = DisplayValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any msgpack value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(DisplayValue(v.into()))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(DisplayValue(v.into()))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(DisplayValue(v.into()))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(DisplayValue(v.into()))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(DisplayValue(v.into()))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+        let s = if v.len() == HOLO_HASH_FULL_LEN
+            && hash_type_name(&v[..HOLO_HASH_PREFIX_LEN]).is_some()
+        {
+            holo_hash::holo_hash_encode(v)
+        } else {
+            format!("0x{}", bytes_to_hex(v, false))
+        };
+        Ok(DisplayValue(s.into()))
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(DisplayValue(serde_json::Value::Null))
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        serde::Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(DisplayValue(serde_json::Value::Null))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::new();
+        while let Some(DisplayValue(item)) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(DisplayValue(items.into()))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut object = serde_json::Map::new();
+        while let Some((DisplayValue(key), DisplayValue(value))) = map.next_entry()? {
+            let key = match key {
+                serde_json::Value::String(key) => key,
+                key => key.to_string(),
+            };
+            object.insert(key, value);
+        }
+        Ok(DisplayValue(object.into()))
+    }
+}
+
+/// Summarise and pretty-print a JSON state dump.
+pub fn inspect_state_dump(json: &str) -> anyhow::Result<String> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).context("The state dump is not valid JSON")?;
+    let summary = if let Ok(dump) = serde_json::from_value::<FullStateDump>(value.clone()) {
+        format!(
+            "--- Full State Dump Summary ---\n\
+            Number of other peers in p2p store: {},\n\
+            Records authored: {}, Ops published: {}\n\
+            Ops in validation limbo: {}, in integration limbo: {}, integrated: {}",
+            dump.peer_dump.peers.len(),
+            dump.source_chain_dump.records.len(),
+            dump.source_chain_dump.published_ops_count,
+            dump.integration_dump.validation_limbo.len(),
+            dump.integration_dump.integration_limbo.len(),
+            dump.integration_dump.integrated.len(),
+        )
+    } else if let Ok(dump) = serde_json::from_value::<JsonDump>(value.clone()) {
+        dump.to_string().trim_end().to_string()
+    } else if let Ok(page) = serde_json::from_value::<StateDumpPage>(value.clone()) {
+        let (store, count) = match &page.items {
+            StateDumpItems::SourceChain(items) => ("source chain records", items.len()),
+            StateDumpItems::DhtOps(items) => ("DHT ops", items.len()),
+            StateDumpItems::Cache(items) => ("cached ops", items.len()),
+            StateDumpItems::PeerStore(items) => ("agent infos", items.len()),
+        };
+        format!(
+            "--- State Dump Page Summary ---\n{} {}, next cursor: {:?}",
+            count, store, page.next_cursor
+        )
+    } else {
+        "--- Unrecognised state dump ---".to_string()
+    };
+    Ok(format!(
+        "{}\n{}",
+        summary,
+        serde_json::to_string_pretty(&value)?
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_are_split_into_their_parts() {
+        let hash = AgentPubKey::from_raw_32(vec![0xdb; 32]);
+        let inspection = inspect_hash(&hash.to_string()).unwrap();
+
+        assert_eq!(inspection.hash_type, Some("AgentPubKey"));
+        assert_eq!(inspection.prefix, hash.get_raw_39()[..3].to_vec());
+        assert_eq!(inspection.core, vec![0xdb; 32]);
+        assert_eq!(inspection.loc, hash.get_loc().as_u32());
+
+        assert!(inspect_hash("not a hash").is_err());
+    }
+
+    #[test]
+    fn payloads_render_hashes_and_bytes() {
+        #[derive(Debug, serde::Serialize)]
+        struct Payload {
+            author: AgentPubKey,
+            data: ExternIO,
+            count: u32,
+        }
+        let author = AgentPubKey::from_raw_32(vec![1; 32]);
+        let bytes = ExternIO::encode(Payload {
+            author: author.clone(),
+            data: ExternIO(vec![0xab, 0xcd]),
+            count: 3,
+        })
+        .unwrap();
+
+        assert_eq!(
+            decode_payload(bytes.0).unwrap(),
+            serde_json::json!({
+                "author": author.to_string(),
+                "data": "0xabcd",
+                "count": 3,
+            })
+        );
+        assert_eq!(hex_to_bytes("0xabcd").unwrap(), vec![0xab, 0xcd]);
+        assert!(hex_to_bytes("abc").is_err());
+    }
+}
@@ -22,7 +22,7 @@ use std::path::Path;
 
 use holochain_conductor_api::conductor::paths::ConfigRootPath;
 use holochain_conductor_api::{AdminRequest, AdminResponse};
-use holochain_websocket::WebsocketSender;
+use holochain_websocket::ReconnectingWebsocketSender;
 use ports::get_admin_api;
 
 pub use ports::force_admin_port;
@@ -43,25 +43,29 @@ pub mod cli;
 #[doc(hidden)]
 pub mod cmds;
 pub mod config;
+pub mod dht_load;
+pub mod fixt;
 pub mod generate;
+pub mod inspect;
+pub mod migrate_legacy;
 pub mod run;
 pub mod sandbox;
 pub mod save;
+pub mod verify;
 pub use cli::HcSandbox;
+pub use dht_load::HcDhtLoad;
+pub use fixt::HcFixt;
 use holochain_trace::Output;
+pub use inspect::HcInspect;
+pub use migrate_legacy::HcMigrateLegacy;
+pub use verify::HcVerify;
 
 mod ports;
 
-/// An active connection to a running conductor.
+/// An active connection to a running conductor,
+/// which reconnects if the conductor is restarted.
 pub struct CmdRunner {
-    client: WebsocketSender,
-    task: tokio::task::JoinHandle<()>,
-}
-
-impl Drop for CmdRunner {
-    fn drop(&mut self) {
-        self.task.abort();
-    }
+    client: ReconnectingWebsocketSender,
 }
 
 impl CmdRunner {
@@ -76,8 +80,8 @@ impl CmdRunner {
 
     /// Create a new connection for calling admin interface commands.
     pub async fn try_new(port: u16) -> std::io::Result<Self> {
-        let (client, task) = get_admin_api(port).await?;
-        Ok(Self { client, task })
+        let client = get_admin_api(port).await?;
+        Ok(Self { client })
     }
 
     /// Create a command runner from a sandbox path.
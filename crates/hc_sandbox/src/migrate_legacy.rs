@@ -0,0 +1,237 @@
+//! Migrate a source chain from the LMDB databases of the legacy conductor into
+//! a cell of a running conductor.
+//!
+//! The legacy conductor kept the source chain of each cell in its own LMDB
+//! environment, written through `rkv`. The signed headers are read from the
+//! header table and the entries from the public and private entry tables, then
+//! sent to the running conductor with [`AdminRequest::ImportLegacyChain`], which
+//! checks them against their hashes and signatures, converts them and validates
+//! the result before writing it to the cell's source chain.
+//!
+//! The legacy chain brings its own genesis, so it is imported before the app is
+//! installed: register the DNA, run this command, then install the app with the
+//! agent key the legacy chain was written with.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use anyhow::bail;
+use anyhow::Context;
+use clap::Parser;
+use heed::types::Bytes;
+use holochain_conductor_api::AdminRequest;
+use holochain_conductor_api::AdminResponse;
+use holochain_types::prelude::*;
+
+use crate::calls::parse_dna_hash;
+use crate::CmdRunner;
+
+/// The table of the legacy LMDB environment holding the signed headers.
+pub const LEGACY_HEADERS_DB: &str = "ElementVaultHeaders";
+
+/// The table of the legacy LMDB environment holding the public entries.
+pub const LEGACY_PUBLIC_ENTRIES_DB: &str = "ElementVaultPublicEntries";
+
+/// The table of the legacy LMDB environment holding the private entries.
+pub const LEGACY_PRIVATE_ENTRIES_DB: &str = "ElementVaultPrivateEntries";
+
+/// The tag `rkv` wrote before each value it stored as a blob.
+const RKV_BLOB_TAG: u8 = 9;
+
+/// Import a source chain from the LMDB databases of the legacy conductor
+/// into a cell of a running conductor.
+///
+/// The cell is the one of the given DNA for the agent who wrote the legacy
+/// chain. The DNA must be registered and the cell must not have run genesis,
+/// so install the app for that agent after the chain is imported.
+#[derive(Debug, Parser, Clone)]
+pub struct HcMigrateLegacy {
+    /// The directory of the legacy LMDB environment of the cell.
+    pub path: PathBuf,
+
+    /// Admin port of the running conductor.
+    #[arg(short, long)]
+    pub running: u16,
+
+    /// The registered DNA to import the chain into.
+    #[arg(short, long, value_parser = parse_dna_hash, required_unless_present = "dry_run")]
+    pub dna: Option<DnaHash>,
+
+    /// The link type to give the links of a zome, as `<zome index>=<link type>`.
+    /// Legacy links had no type, so every zome the chain created links in
+    /// needs one.
+    #[arg(long = "link-type", value_parser = parse_link_type)]
+    pub link_types: Vec<(ZomeIndex, LinkType)>,
+
+    /// Only read the legacy chain and report its size, without importing it.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl HcMigrateLegacy {
+    /// Run this command
+    pub async fn run(self) -> anyhow::Result<()> {
+        let chain = read_legacy_chain(&self.path)?;
+        let author = chain.author().cloned().ok_or_else(|| {
+            anyhow!(
+                "The legacy chain at {} has no Dna header",
+                self.path.display()
+            )
+        })?;
+        msg!(
+            "Read {} headers and {} entries by {} from {}",
+            chain.headers.len(),
+            chain.entries.len(),
+            author,
+            self.path.display()
+        );
+        if self.dry_run {
+            return Ok(());
+        }
+        let dna = self
+            .dna
+            .ok_or_else(|| anyhow!("The DNA to import the chain into is required"))?;
+
+        let mut cmd = CmdRunner::try_new(self.running).await?;
+        let cell_id = CellId::new(dna, author);
+        let resp = cmd
+            .command(AdminRequest::ImportLegacyChain {
+                cell_id: cell_id.clone(),
+                chain,
+                link_types: self.link_types.into_iter().collect::<HashMap<_, _>>(),
+            })
+            .await?;
+        match resp {
+            AdminResponse::LegacyChainImported(imported) => {
+                msg!("Imported {} records into {:?}", imported, cell_id);
+                Ok(())
+            }
+            _ => bail!("Failed to import the legacy chain, got: {:?}", resp),
+        }
+    }
+}
+
+/// Read the source chain held in a legacy LMDB environment.
+pub fn read_legacy_chain(path: &Path) -> anyhow::Result<LegacyChain> {
+    // Safety: the environment must not be opened by another process while it
+    // is read, which holds because the legacy conductor is no longer running.
+    let env = unsafe { heed::EnvOpenOptions::new().max_dbs(32).open(path) }
+        .with_context(|| format!("Failed to open LMDB environment at {}", path.display()))?;
+    let txn = env.read_txn()?;
+
+    let headers = read_table(&env, &txn, LEGACY_HEADERS_DB)?
+        .ok_or_else(|| anyhow!("No {} table at {}", LEGACY_HEADERS_DB, path.display()))?;
+    let mut entries = read_table(&env, &txn, LEGACY_PUBLIC_ENTRIES_DB)?.unwrap_or_default();
+    entries.extend(read_table(&env, &txn, LEGACY_PRIVATE_ENTRIES_DB)?.unwrap_or_default());
+
+    Ok(LegacyChain { headers, entries })
+}
+
+/// Decode every value of a table, or `None` if the table doesn't exist.
+fn read_table<T: TryFrom<SerializedBytes, Error = SerializedBytesError>>(
+    env: &heed::Env,
+    txn: &heed::RoTxn,
+    name: &str,
+) -> anyhow::Result<Option<Vec<T>>> {
+    let Some(db) = env.open_database::<Bytes, Bytes>(txn, Some(name))? else {
+        return Ok(None);
+    };
+    db.iter(txn)?
+        .map(|item| {
+            let (_, value) = item?;
+            let value = rkv_blob(value)
+                .with_context(|| format!("Failed to read a value of the {} table", name))?;
+            T::try_from(SerializedBytes::from(UnsafeBytes::from(value.to_vec())))
+                .with_context(|| format!("Failed to decode a value of the {} table", name))
+        })
+        .collect::<anyhow::Result<_>>()
+        .map(Some)
+}
+
+/// The bytes of a value `rkv` stored as a blob: its tag, followed by the
+/// length of the blob as a little-endian `u64` and then the blob itself.
+fn rkv_blob(value: &[u8]) -> anyhow::Result<&[u8]> {
+    match value {
+        [RKV_BLOB_TAG, len @ ..] if len.len() >= 8 => {
+            let (len, blob) = len.split_at(8);
+            let len = u64::from_le_bytes(len.try_into().expect("split at 8 bytes"));
+            if blob.len() as u64 != len {
+                bail!("Blob of {} bytes has a length of {}", blob.len(), len);
+            }
+            Ok(blob)
+        }
+        _ => bail!("Not an rkv blob"),
+    }
+}
+
+fn parse_link_type(arg: &str) -> anyhow::Result<(ZomeIndex, LinkType)> {
+    let (zome, link_type) = arg
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Expected <zome index>=<link type>, got {}", arg))?;
+    Ok((ZomeIndex(zome.parse()?), LinkType(link_type.parse()?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_headers_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = read_legacy_chain(dir.path()).unwrap_err();
+        assert!(err.to_string().contains(LEGACY_HEADERS_DB));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn read_legacy_lmdb_fixture() {
+        // Opening the environment creates its lock file, so read a copy.
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::copy(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/legacy-lmdb/data.mdb"),
+            dir.path().join("data.mdb"),
+        )
+        .unwrap();
+
+        let chain = read_legacy_chain(dir.path()).unwrap();
+        assert_eq!(chain.headers.len(), 6);
+        assert_eq!(chain.entries.len(), 2);
+        let author = chain.author().unwrap().clone();
+        chain.verify(&author).await.unwrap();
+
+        let link_types = [(ZomeIndex(0), LinkType(1))].into_iter().collect();
+        let actions = chain
+            .into_actions(DnaHash::from_raw_36(vec![0; 36]), &link_types)
+            .unwrap();
+        let types: Vec<_> = actions
+            .iter()
+            .map(|(action, _)| action.action_type())
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ActionType::Dna,
+                ActionType::AgentValidationPkg,
+                ActionType::Create,
+                ActionType::InitZomesComplete,
+                ActionType::Create,
+                ActionType::CreateLink,
+            ]
+        );
+        assert!(matches!(
+            &actions[5].0,
+            Action::CreateLink(link) if link.link_type == LinkType(1) && link.tag == LinkTag::new("legacy")
+        ));
+    }
+
+    #[test]
+    fn rkv_blob_length_must_match() {
+        let mut value = vec![RKV_BLOB_TAG];
+        value.extend_from_slice(&3u64.to_le_bytes());
+        value.extend_from_slice(b"abc");
+        assert_eq!(rkv_blob(&value).unwrap(), b"abc");
+        assert!(rkv_blob(&value[..value.len() - 1]).is_err());
+        assert!(rkv_blob(b"abc").is_err());
+    }
+}
@@ -3,13 +3,14 @@
 use std::net::ToSocketAddrs;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use holochain_conductor_api::conductor::paths::ConfigRootPath;
 use holochain_conductor_api::{
     config::conductor::ConductorConfig, AdminInterfaceConfig, InterfaceDriver,
 };
 use holochain_types::websocket::AllowedOrigins;
-use holochain_websocket::{self as ws, WebsocketConfig, WebsocketSender};
+use holochain_websocket::{ReconnectConfig, ReconnectingWebsocketSender, WebsocketConfig};
 
 use crate::config::read_config;
 use crate::config::write_config;
@@ -36,6 +37,7 @@ pub async fn get_admin_ports(paths: Vec<PathBuf>) -> anyhow::Result<Vec<u16>> {
             if let Some(ai) = config.admin_interfaces {
                 if let Some(AdminInterfaceConfig {
                     driver: InterfaceDriver::Websocket { port, .. },
+                    ..
                 }) = ai.first()
                 {
                     ports.push(*port)
@@ -46,18 +48,23 @@ pub async fn get_admin_ports(paths: Vec<PathBuf>) -> anyhow::Result<Vec<u16>> {
     Ok(ports)
 }
 
-/// Creates a [`WebsocketSender`] along with a task which simply consumes and discards
-/// all messages on the receiving side
-pub(crate) async fn get_admin_api(
-    port: u16,
-) -> std::io::Result<(WebsocketSender, tokio::task::JoinHandle<()>)> {
+/// How an admin client reconnects when its connection drops, for example because
+/// the conductor was restarted. A conductor which isn't running is reported after
+/// a few quick attempts rather than waited for.
+const ADMIN_RECONNECT: ReconnectConfig = ReconnectConfig {
+    initial_backoff: Duration::from_millis(100),
+    max_backoff: Duration::from_secs(2),
+    max_attempts: Some(3),
+};
+
+/// Creates a [`ReconnectingWebsocketSender`] to the admin interface on this port,
+/// which reconnects if the connection drops.
+pub(crate) async fn get_admin_api(port: u16) -> std::io::Result<ReconnectingWebsocketSender> {
     tracing::debug!(port);
     websocket_client_by_port(port).await
 }
 
-async fn websocket_client_by_port(
-    port: u16,
-) -> std::io::Result<(WebsocketSender, tokio::task::JoinHandle<()>)> {
+async fn websocket_client_by_port(port: u16) -> std::io::Result<ReconnectingWebsocketSender> {
     let req = holochain_websocket::ConnectRequest::new(
         format!("localhost:{port}")
             .to_socket_addrs()?
@@ -67,21 +74,19 @@ async fn websocket_client_by_port(
     .try_set_header("Origin", "hc_sandbox")
     .expect("Failed to set `Origin` header for websocket connection request");
 
-    let (send, mut recv) = ws::connect(Arc::new(WebsocketConfig::CLIENT_DEFAULT), req).await?;
-    let task = tokio::task::spawn(async move {
-        while recv
-            .recv::<holochain_conductor_api::AdminResponse>()
-            .await
-            .is_ok()
-        {}
-    });
-    Ok((send, task))
+    ReconnectingWebsocketSender::connect(
+        Arc::new(WebsocketConfig::CLIENT_DEFAULT),
+        ADMIN_RECONNECT,
+        req,
+    )
+    .await
 }
 
 pub(crate) fn random_admin_port(config: &mut ConductorConfig) {
     match config.admin_interfaces.as_mut().and_then(|i| i.first_mut()) {
         Some(AdminInterfaceConfig {
             driver: InterfaceDriver::Websocket { port, .. },
+            ..
         }) => {
             if *port != 0 {
                 *port = 0;
@@ -94,6 +99,7 @@ pub(crate) fn random_admin_port(config: &mut ConductorConfig) {
                     port,
                     allowed_origins: AllowedOrigins::Any,
                 },
+                require_token: false,
             }]);
         }
     }
@@ -106,6 +112,7 @@ pub(crate) fn set_admin_port(config: &mut ConductorConfig, port: u16) {
             port,
             allowed_origins: AllowedOrigins::Any,
         },
+        require_token: false,
     };
     match config
         .admin_interfaces
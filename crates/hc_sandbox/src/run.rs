@@ -3,11 +3,13 @@
 use anyhow::anyhow;
 use std::path::Path;
 use std::process::Stdio;
+use std::time::Duration;
 
 use holochain_conductor_api::conductor::paths::ConfigFilePath;
 use holochain_conductor_api::conductor::paths::ConfigRootPath;
 use holochain_conductor_api::conductor::paths::KeystorePath;
 use holochain_conductor_api::conductor::{ConductorConfig, KeystoreConfig};
+use holochain_conductor_api::AdminRequest;
 use holochain_trace::Output;
 use holochain_types::websocket::AllowedOrigins;
 use tokio::io::AsyncBufReadExt;
@@ -29,6 +31,13 @@ const LAIR_START: &str = "# lair-keystore running #";
 const HC_START_1: &str = "HOLOCHAIN_SANDBOX";
 const HC_START_2: &str = "HOLOCHAIN_SANDBOX_END";
 
+/// The number of consecutive failed health checks after which
+/// a supervised conductor is restarted.
+const MAX_FAILED_HEALTH_CHECKS: u32 = 3;
+
+/// How long a health check may take before it counts as failed.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Run a conductor and wait for it to finish.
 /// Use [`run_async`] to run in the background.
 /// Requires the holochain binary to be available
@@ -38,6 +47,8 @@ const HC_START_2: &str = "HOLOCHAIN_SANDBOX_END";
 /// Can optionally force the admin port used. Otherwise
 /// the port in the config will be used if it's free or
 /// a random free port will be chosen.
+/// If `supervise` is set the conductor is health checked at that
+/// interval and restarted if it crashes or stops responding.
 pub async fn run(
     holochain_path: &Path,
     sandbox_path: ConfigRootPath,
@@ -45,12 +56,13 @@ pub async fn run(
     app_ports: Vec<u16>,
     force_admin_port: Option<u16>,
     structured: Output,
+    supervise: Option<Duration>,
 ) -> anyhow::Result<()> {
     let (admin_port, mut holochain, lair) = run_async(
         holochain_path,
         sandbox_path.clone(),
         force_admin_port,
-        structured,
+        structured.clone(),
     )
     .await?;
     let mut launch_info = LaunchInfo::from_admin_port(admin_port);
@@ -76,6 +88,20 @@ pub async fn run(
 
     crate::save::lock_live(std::env::current_dir()?, &sandbox_path, admin_port).await?;
     msg!("Connected successfully to a running holochain");
+
+    if let Some(health_check_interval) = supervise {
+        return supervise_conductor(
+            holochain_path,
+            sandbox_path,
+            conductor_index,
+            admin_port,
+            (holochain, lair),
+            structured,
+            health_check_interval,
+        )
+        .await;
+    }
+
     let e = format!("Failed to run holochain at {}", sandbox_path.display());
 
     holochain.wait().await.expect(&e);
@@ -87,6 +113,102 @@ pub async fn run(
     Ok(())
 }
 
+/// Keep a conductor running, restarting it with the same config and
+/// admin port whenever it exits or fails [`MAX_FAILED_HEALTH_CHECKS`]
+/// health checks in a row. App interfaces are persisted by the conductor
+/// so they are rebound on restart.
+/// This only returns if the conductor can't be restarted.
+async fn supervise_conductor(
+    holochain_path: &Path,
+    sandbox_path: ConfigRootPath,
+    conductor_index: usize,
+    admin_port: u16,
+    conductor: (Child, Option<Child>),
+    structured: Output,
+    health_check_interval: Duration,
+) -> anyhow::Result<()> {
+    let mut interval = tokio::time::interval(health_check_interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // The first tick completes immediately and the conductor has just started.
+    interval.tick().await;
+
+    let (mut holochain, mut lair) = conductor;
+    let mut failed_checks = 0;
+    let mut restarts = 0;
+    loop {
+        tokio::select! {
+            status = holochain.wait() => {
+                match status {
+                    Ok(status) => msg!("Conductor #{} exited with {}", conductor_index, status),
+                    Err(e) => msg!("Conductor #{} exited: {:?}", conductor_index, e),
+                }
+            }
+            _ = interval.tick() => {
+                if health_check(admin_port).await {
+                    failed_checks = 0;
+                    continue;
+                }
+                failed_checks += 1;
+                msg!(
+                    "Conductor #{} failed health check ({}/{})",
+                    conductor_index,
+                    failed_checks,
+                    MAX_FAILED_HEALTH_CHECKS
+                );
+                if failed_checks < MAX_FAILED_HEALTH_CHECKS {
+                    continue;
+                }
+                let _ = holochain.kill().await;
+            }
+        }
+
+        failed_checks = 0;
+        if let Some(mut lair) = lair.take() {
+            let _ = lair.kill().await;
+            let _ = lair.wait().await;
+        }
+
+        restarts += 1;
+        msg!(
+            "Restarting conductor #{} at {} (restart {})",
+            conductor_index,
+            sandbox_path.display(),
+            restarts
+        );
+        (_, holochain, lair) = run_async(
+            holochain_path,
+            sandbox_path.clone(),
+            Some(admin_port),
+            structured.clone(),
+        )
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "Failed to restart holochain at {}: {:?}",
+                sandbox_path.display(),
+                e
+            )
+        })?;
+        msg!(
+            "Conductor #{} restarted on admin port {}",
+            conductor_index,
+            admin_port
+        );
+    }
+}
+
+/// Check that the conductor's admin interface is accepting requests.
+async fn health_check(admin_port: u16) -> bool {
+    let check = async {
+        let mut cmd = crate::CmdRunner::try_new(admin_port).await?;
+        cmd.command(AdminRequest::ListDnas).await
+    };
+    matches!(
+        tokio::time::timeout(HEALTH_CHECK_TIMEOUT, check).await,
+        Ok(Ok(_))
+    )
+}
+
 /// Run a conductor in the background.
 /// Requires the holochain binary to be available
 /// on the `holochain_path`.
@@ -0,0 +1,105 @@
+//! Verify an exported set of records offline.
+//!
+//! Checks the signatures, hashes and chain linkage of the records without a
+//! running conductor, so that third parties can audit exported data.
+
+use std::path::PathBuf;
+
+use anyhow::bail;
+use anyhow::Context;
+use clap::Parser;
+use holochain_types::prelude::*;
+
+/// Verify the signatures, hashes and chain linkage of a file of records.
+#[derive(Debug, Parser)]
+pub struct HcVerify {
+    /// The path to the file of records: a list of records encoded as JSON or msgpack.
+    pub path: PathBuf,
+
+    /// Print the full report as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl HcVerify {
+    /// Run this command.
+    ///
+    /// Fails if any problem is found with the records.
+    pub async fn run(self) -> anyhow::Result<()> {
+        let bytes = std::fs::read(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        let records = decode_records(&bytes)?;
+        let report = verify_record_bundle(&records).await;
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            for issue in &report.issues {
+                msg!("{}: {}", issue.action_hash, describe(&issue.problem));
+            }
+            msg!(
+                "Verified {} records by {} authors, {} problems found",
+                report.records,
+                report.authors,
+                report.issues.len()
+            );
+        }
+        if !report.is_valid() {
+            bail!("The records in {} are not valid", self.path.display());
+        }
+        Ok(())
+    }
+}
+
+/// Decode a list of records, as JSON if it looks like JSON or as msgpack otherwise.
+fn decode_records(bytes: &[u8]) -> anyhow::Result<Vec<Record>> {
+    match bytes.iter().find(|b| !b.is_ascii_whitespace()) {
+        Some(b'[') => serde_json::from_slice(bytes).context("The records are not valid JSON"),
+        _ => ExternIO(bytes.to_vec())
+            .decode()
+            .context("The records are not valid msgpack"),
+    }
+}
+
+fn describe(problem: &RecordBundleProblem) -> String {
+    match problem {
+        RecordBundleProblem::ActionHashMismatch { computed } => {
+            format!("the action hashes to {}", computed)
+        }
+        RecordBundleProblem::InvalidSignature => "the signature is not valid".to_string(),
+        RecordBundleProblem::EntryHashMismatch { expected, computed } => {
+            format!("the entry hashes to {} rather than {}", computed, expected)
+        }
+        RecordBundleProblem::UnexpectedEntry => {
+            "the record holds an entry its action doesn't create".to_string()
+        }
+        RecordBundleProblem::BrokenPrevAction { prev_action } => format!(
+            "the previous action {} is not the one before it in the chain",
+            prev_action
+        ),
+        RecordBundleProblem::MissingPrevAction { prev_action } => {
+            format!("the previous action {} is missing", prev_action)
+        }
+        RecordBundleProblem::ChainFork { other } => {
+            format!("the chain forks, {} is at the same position", other)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_decode_from_json_and_msgpack() {
+        let records: Vec<Record> = Vec::new();
+
+        let json = serde_json::to_vec(&records).unwrap();
+        assert!(decode_records(&json).unwrap().is_empty());
+
+        let msgpack = ExternIO::encode(&records).unwrap();
+        assert!(decode_records(&msgpack.0).unwrap().is_empty());
+
+        assert!(decode_records(b"[1, 2").is_err());
+    }
+}
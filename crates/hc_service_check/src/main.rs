@@ -80,6 +80,7 @@ async fn bootstrap(url: String) -> Result<()> {
     println!("boostrap check of {url}");
     println!("checking 'now' command");
     let now = kitsune_p2p_bootstrap_client::now(
+        &kitsune_p2p_bootstrap_client::BootstrapClient::default(),
         Some(url2::Url2::parse(url)),
         kitsune_p2p_bootstrap_client::BootstrapNet::Tx5,
     )
@@ -0,0 +1,86 @@
+//! The manifest entry type for storing large payloads as content addressed chunks.
+//!
+//! A blob is split into chunks of at most [`BLOB_CHUNK_SIZE`] bytes, which are
+//! published to the DHT as their own ops addressed by the hash of their bytes,
+//! and a small [`BlobManifest`] entry listing the hashes of the chunks in order.
+//! Identical chunks have the same hash, so they are only stored once, and readers
+//! only fetch the chunks they actually read. The chunks are checked by sys
+//! validation, so they never reach app validation.
+//!
+//! To use blobs add the manifest to the entry types of an integrity zome and call
+//! [`validate_blob_manifest`] from its validation:
+//!
+//! ```ignore
+//! #[hdk_entry_types]
+//! #[unit_enum(UnitEntryTypes)]
+//! pub enum EntryTypes {
+//!     BlobManifest(BlobManifest),
+//! }
+//! ```
+//!
+//! The HDK `create_blob`, `read_blob_stream` and `delete_blob` functions create,
+//! read and release blobs stored with this type.
+
+use crate::prelude::*;
+
+/// The size of a blob and the hashes of its chunks, in order.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq, Eq)]
+pub struct BlobManifest {
+    /// The total size of the blob in bytes.
+    pub size: u64,
+    /// The hashes of the chunks of the blob.
+    pub chunks: Vec<EntryHash>,
+}
+
+crate::app_entry!(BlobManifest);
+
+impl BlobManifest {
+    /// The number of chunks a blob of `size` bytes is split into.
+    pub fn chunk_count(size: u64) -> usize {
+        size.div_ceil(BLOB_CHUNK_SIZE as u64) as usize
+    }
+}
+
+/// Check that a manifest lists as many chunks as its size needs.
+///
+/// The chunks themselves aren't fetched, so validating a manifest is cheap
+/// however large the blob is.
+pub fn validate_blob_manifest(manifest: &BlobManifest) -> ExternResult<ValidateCallbackResult> {
+    let expected = BlobManifest::chunk_count(manifest.size);
+    Ok(if manifest.chunks.len() == expected {
+        ValidateCallbackResult::Valid
+    } else {
+        ValidateCallbackResult::Invalid(format!(
+            "Blob manifest of {} bytes lists {} chunks but needs {}",
+            manifest.size,
+            manifest.chunks.len(),
+            expected
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_chunk_count_matches_size() {
+        let chunk = EntryHash::from_raw_36(vec![0; 36]);
+        let manifest = |size, chunks| BlobManifest {
+            size,
+            chunks: vec![chunk.clone(); chunks],
+        };
+        assert_eq!(
+            ValidateCallbackResult::Valid,
+            validate_blob_manifest(&manifest(0, 0)).unwrap()
+        );
+        assert_eq!(
+            ValidateCallbackResult::Valid,
+            validate_blob_manifest(&manifest(BLOB_CHUNK_SIZE as u64 + 1, 2)).unwrap()
+        );
+        assert!(matches!(
+            validate_blob_manifest(&manifest(BLOB_CHUNK_SIZE as u64, 2)).unwrap(),
+            ValidateCallbackResult::Invalid(_)
+        ));
+    }
+}
@@ -24,3 +24,64 @@ pub fn must_get_agent_activity(
             })
     })
 }
+
+/// The membrane proof that is current at the head of some chain activity.
+///
+/// This is the proof of the latest [`RenewMembraneProof`] in the activity, or
+/// the proof of the [`AgentValidationPkg`] if it was never renewed. Returns
+/// `None` if the activity holds neither, e.g. because the filter stopped
+/// before genesis, or if the agent joined without a proof.
+pub fn current_membrane_proof(activity: &[RegisterAgentActivity]) -> Option<MembraneProof> {
+    activity
+        .iter()
+        .map(|activity| activity.action.action())
+        .filter(|action| {
+            matches!(
+                action,
+                Action::AgentValidationPkg(_) | Action::RenewMembraneProof(_)
+            )
+        })
+        .max_by_key(|action| action.action_seq())
+        .and_then(|action| match action {
+            Action::AgentValidationPkg(pkg) => pkg.membrane_proof.clone(),
+            Action::RenewMembraneProof(renewal) => Some(renewal.membrane_proof.clone()),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::short_hand::*;
+    use std::sync::Arc;
+
+    fn activity(action: impl Into<Action>) -> RegisterAgentActivity {
+        RegisterAgentActivity {
+            action: SignedHashed::new_unchecked(action.into(), Signature([0; 64])),
+            cached_entry: None,
+        }
+    }
+
+    #[test]
+    fn latest_membrane_proof_is_current() {
+        let original = mp();
+        let renewed: MembraneProof = Arc::new(SerializedBytes::from(UnsafeBytes::from(vec![1])));
+        let mut later = rmp(ah(1), renewed.clone());
+        later.action_seq = 7;
+
+        assert_eq!(current_membrane_proof(&[]), None);
+        assert_eq!(current_membrane_proof(&[activity(avp(None))]), None);
+        assert_eq!(
+            current_membrane_proof(&[activity(avp(Some(original.clone())))]),
+            Some(original.clone())
+        );
+        assert_eq!(
+            current_membrane_proof(&[
+                activity(later),
+                activity(rmp(ah(1), original.clone())),
+                activity(avp(None)),
+            ]),
+            Some(renewed)
+        );
+    }
+}
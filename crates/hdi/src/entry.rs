@@ -2,6 +2,7 @@ use crate::prelude::*;
 
 pub use hdk_derive::hdk_entry_helper;
 pub use hdk_derive::hdk_entry_types;
+pub use hdk_derive::hdk_entry_versioned;
 
 #[cfg(doc)]
 pub mod examples;
@@ -109,6 +110,49 @@ pub fn must_get_valid_record(action_hash: ActionHash) -> ExternResult<Record> {
     })
 }
 
+/// The number of bytes an app entry will take up once serialized.
+///
+/// The size is computed without serializing the entry. App entries larger than
+/// [`ENTRY_SIZE_LIMIT`] can't be created, so this can be used to check an entry
+/// before trying to commit it.
+pub fn app_entry_size<T>(entry: &T) -> ExternResult<usize>
+where
+    T: serde::Serialize + ?Sized,
+{
+    serialized_size(entry).map_err(|e| wasm_error!(WasmErrorInner::Serialize(e)))
+}
+
+/// Whether an app entry is small enough to be created,
+/// i.e. it is no larger than [`ENTRY_SIZE_LIMIT`] once serialized.
+pub fn fits_entry_size_limit<T>(entry: &T) -> ExternResult<bool>
+where
+    T: serde::Serialize + ?Sized,
+{
+    Ok(app_entry_size(entry)? <= ENTRY_SIZE_LIMIT)
+}
+
+/// An app entry which keeps every version of its schema, so that entries
+/// committed with an old version can still be read.
+///
+/// Implemented by [`hdk_entry_versioned`] for an enum with a variant per version.
+pub trait VersionedEntry: Sized {
+    /// The type of the newest version of the entry.
+    type Latest;
+
+    /// The newest version, counting from 1 for the oldest.
+    const LATEST_VERSION: u32;
+
+    /// The version of this entry, counting from 1 for the oldest.
+    fn version(&self) -> u32;
+
+    /// Upgrade the entry to the next version.
+    /// The latest version is returned unchanged.
+    fn upgrade(self) -> Self;
+
+    /// Upgrade the entry through each newer version to the latest.
+    fn into_latest(self) -> Self::Latest;
+}
+
 /// Implements conversion traits to allow a struct to be handled as an app entry.
 /// If you have some need to implement custom serialization logic or metadata injection
 /// you can do so by implementing these traits manually instead.
@@ -129,8 +173,11 @@ macro_rules! app_entry {
                     $crate::prelude::Entry::CounterSign(_, eb) => Ok(Self::try_from(
                         $crate::prelude::SerializedBytes::from(eb.to_owned()),
                     ).map_err(|e| $crate::prelude::wasm_error!(e))?),
+                    $crate::prelude::Entry::DualSign(_, eb) => Ok(Self::try_from(
+                        $crate::prelude::SerializedBytes::from(eb.to_owned()),
+                    ).map_err(|e| $crate::prelude::wasm_error!(e))?),
                     _ => Err($crate::prelude::wasm_error!(
-                        "{:?} is not an Entry::App or Entry::CounterSign so has no serialized bytes",
+                        "{:?} is not an Entry::App, Entry::CounterSign or Entry::DualSign so has no serialized bytes",
                         entry
                     )),
                 }
@@ -4,13 +4,15 @@
 use holo_hash::{ActionHash, AgentPubKey, AnyLinkableHash, DnaHash, EntryHash};
 use holochain_integrity_types::{
     AgentValidationPkg, CloseChain, Create, CreateLink, Delete, DeleteLink, Dna, InitZomesComplete,
-    LinkTag, MembraneProof, OpenChain, UnitEnum, Update,
+    LinkTag, MembraneProof, OpenChain, RenewMembraneProof, UnitEnum, Update,
 };
 
 mod flat_op_activity;
+mod flat_op_dispatch;
 mod flat_op_entry;
 mod flat_op_record;
 pub use flat_op_activity::*;
+pub use flat_op_dispatch::*;
 pub use flat_op_entry::*;
 pub use flat_op_record::*;
 
@@ -182,4 +182,16 @@ pub enum OpActivity<UnitType, LT> {
         /// The [`InitZomesComplete`] action
         action: InitZomesComplete,
     },
+    /// This operation registers the [`Action`] for an
+    /// [`Action::RenewMembraneProof`] to the author's chain
+    /// and contains the renewed membrane proof.
+    RenewMembraneProof {
+        /// The membrane proof which replaces the agent's previous proof
+        membrane_proof: MembraneProof,
+        /// The [`AgentValidationPkg`] or earlier [`RenewMembraneProof`] action
+        /// whose proof is replaced
+        supersedes: ActionHash,
+        /// The [`RenewMembraneProof`] action
+        action: RenewMembraneProof,
+    },
 }
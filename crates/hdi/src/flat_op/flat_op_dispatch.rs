@@ -0,0 +1,112 @@
+use crate::prelude::*;
+
+/// Validation of each variant of an app's entry types enum.
+///
+/// This is usually implemented with
+/// [`hdk_validation_dispatch`](hdk_derive::hdk_validation_dispatch),
+/// which calls a `validate_create_<variant>`, `validate_update_<variant>` and
+/// `validate_delete_<variant>` function for every variant.
+pub trait EntryTypesValidation: Sized {
+    /// Validate a new entry of this type.
+    fn validate_create(self, action: EntryCreationAction) -> ExternResult<ValidateCallbackResult>;
+
+    /// Validate an update which creates an entry of this type.
+    fn validate_update(self, action: Update) -> ExternResult<ValidateCallbackResult>;
+
+    /// Validate the deletion of an entry of this type.
+    /// `self` is the deleted entry and `original_action` the action that created it.
+    fn validate_delete(
+        self,
+        action: Delete,
+        original_action: EntryCreationAction,
+    ) -> ExternResult<ValidateCallbackResult>;
+}
+
+/// Validation of each variant of an app's link types enum.
+///
+/// This is usually implemented with
+/// [`hdk_validation_dispatch`](hdk_derive::hdk_validation_dispatch),
+/// which calls a `validate_create_link_<variant>` and
+/// `validate_delete_link_<variant>` function for every variant.
+pub trait LinkTypesValidation: Sized {
+    /// Validate a new link of this type.
+    fn validate_create_link(self, action: CreateLink) -> ExternResult<ValidateCallbackResult>;
+
+    /// Validate the deletion of a link of this type.
+    fn validate_delete_link(
+        self,
+        action: DeleteLink,
+        original_action: CreateLink,
+    ) -> ExternResult<ValidateCallbackResult>;
+}
+
+/// Validate an [`Op`] by calling the handler for its entry or link type.
+///
+/// - Creates and updates are validated by the [`Op::StoreEntry`] authority.
+///   Private entries are never stored on the DHT, so their handlers are not called.
+/// - Deletes are validated by the [`Op::RegisterDelete`] authority,
+///   which fetches the deleted entry with [`must_get_valid_record`].
+/// - Links are validated by the [`Op::RegisterCreateLink`] and
+///   [`Op::RegisterDeleteLink`] authorities.
+///
+/// All other ops are valid.
+///
+/// # Examples
+/// ```ignore
+/// #[hdk_extern]
+/// pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+///     dispatch_validation::<EntryTypes, LinkTypes>(&op)
+/// }
+/// ```
+pub fn dispatch_validation<ET, LT>(op: &Op) -> ExternResult<ValidateCallbackResult>
+where
+    ET: EntryTypesValidation + EntryTypesHelper + UnitEnum,
+    <ET as UnitEnum>::Unit: Into<ZomeEntryTypesKey>,
+    LT: LinkTypesValidation + LinkTypesHelper,
+    WasmError: From<<ET as EntryTypesHelper>::Error>,
+    WasmError: From<<LT as LinkTypesHelper>::Error>,
+{
+    match op.flattened::<ET, LT>()? {
+        FlatOp::StoreEntry(OpEntry::CreateEntry { app_entry, action }) => {
+            app_entry.validate_create(EntryCreationAction::Create(action))
+        }
+        FlatOp::StoreEntry(OpEntry::UpdateEntry {
+            app_entry, action, ..
+        }) => app_entry.validate_update(action),
+        FlatOp::RegisterDelete(OpDelete { action }) => {
+            let original_record = must_get_valid_record(action.deletes_address.clone())?;
+            let original_action =
+                match EntryCreationAction::try_from(original_record.action().clone()) {
+                    Ok(original_action) => original_action,
+                    Err(_) => {
+                        return Ok(ValidateCallbackResult::Invalid(
+                            "The deleted action does not create an entry".to_string(),
+                        ))
+                    }
+                };
+            let app_entry = match (
+                original_action.app_entry_def(),
+                original_record.entry().as_option(),
+            ) {
+                (Some(entry_def), Some(entry)) => {
+                    ET::deserialize_from_type(entry_def.zome_index, entry_def.entry_index, entry)?
+                }
+                _ => None,
+            };
+            match app_entry {
+                Some(app_entry) => app_entry.validate_delete(action, original_action),
+                None => Ok(ValidateCallbackResult::Valid),
+            }
+        }
+        FlatOp::RegisterCreateLink {
+            link_type, action, ..
+        } => link_type.validate_create_link(action),
+        FlatOp::RegisterDeleteLink {
+            link_type,
+            action,
+            original_action,
+            ..
+        } => link_type.validate_delete_link(action, original_action),
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
@@ -180,4 +180,16 @@ pub enum OpRecord<ET: UnitEnum, LT> {
         /// The [`InitZomesComplete`] action
         action: InitZomesComplete,
     },
+    /// This operation stores the [`Record`] for an
+    /// [`Action::RenewMembraneProof`] and contains
+    /// the renewed membrane proof.
+    RenewMembraneProof {
+        /// The membrane proof which replaces the agent's previous proof
+        membrane_proof: MembraneProof,
+        /// The [`AgentValidationPkg`] or earlier [`RenewMembraneProof`] action
+        /// whose proof is replaced
+        supersedes: ActionHash,
+        /// The [`RenewMembraneProof`] action
+        action: RenewMembraneProof,
+    },
 }
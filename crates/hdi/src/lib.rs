@@ -141,8 +141,10 @@ pub const HDI_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub use hdk_derive::hdk_entry_helper;
 pub use hdk_derive::hdk_entry_types;
+pub use hdk_derive::hdk_entry_versioned;
 pub use hdk_derive::hdk_extern;
 pub use hdk_derive::hdk_link_types;
+pub use hdk_derive::hdk_validation_dispatch;
 
 /// Working with app and system entries.
 ///
@@ -331,6 +333,8 @@ pub mod link;
 
 pub mod chain;
 
+pub mod blob;
+
 #[deny(missing_docs)]
 pub mod op;
 
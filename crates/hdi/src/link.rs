@@ -96,3 +96,49 @@ where
             .try_into_filter()
     }
 }
+
+/// Check that the author of a new link also authored its base, which is the
+/// usual rule for "only the owner of the base may link from it".
+///
+/// The link's author owns the base if the base is:
+/// - the author's own agent key, or
+/// - the hash of an action written by the author, which is fetched with [`must_get_action`].
+///
+/// Links from any other base, such as an entry or an external hash, are invalid
+/// because their ownership can't be proven.
+/// The result only depends on content addressed data so it is deterministic,
+/// and the conductor caches fetched actions across validation runs so repeated
+/// checks against the same base are cheap.
+///
+/// # Examples
+/// ```ignore
+/// #[hdk_extern]
+/// pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+///     match op.flattened::<EntryTypes, LinkTypes>()? {
+///         FlatOp::RegisterCreateLink { action, .. } => must_be_base_author(&action),
+///         _ => Ok(ValidateCallbackResult::Valid),
+///     }
+/// }
+/// ```
+pub fn must_be_base_author(create_link: &CreateLink) -> ExternResult<ValidateCallbackResult> {
+    let base = create_link.base_address.clone();
+    if base.clone().into_agent_pub_key().as_ref() == Some(&create_link.author) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let base_action = match base.into_action_hash() {
+        Some(action_hash) => must_get_action(action_hash)?,
+        None => {
+            return Ok(ValidateCallbackResult::Invalid(
+                "The base of the link is not an action or the agent key of the link's author"
+                    .to_string(),
+            ))
+        }
+    };
+    if *base_action.action().author() == create_link.author {
+        Ok(ValidateCallbackResult::Valid)
+    } else {
+        Ok(ValidateCallbackResult::Invalid(
+            "The author of the link is not the author of its base".to_string(),
+        ))
+    }
+}
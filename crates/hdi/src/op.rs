@@ -79,6 +79,18 @@ impl OpHelper for Op {
                     Action::InitZomesComplete(action) => OpRecord::InitZomesComplete {
                         action: action.clone(),
                     },
+                    Action::RenewMembraneProof(action) => {
+                        let RenewMembraneProof {
+                            membrane_proof,
+                            supersedes,
+                            ..
+                        } = action;
+                        OpRecord::RenewMembraneProof {
+                            membrane_proof: membrane_proof.clone(),
+                            supersedes: supersedes.clone(),
+                            action: action.clone(),
+                        }
+                    }
                     Action::CreateLink(action) => {
                         let CreateLink {
                             zome_index,
@@ -364,6 +376,18 @@ impl OpHelper for Op {
                     Action::InitZomesComplete(action) => OpActivity::InitZomesComplete {
                         action: action.clone(),
                     },
+                    Action::RenewMembraneProof(action) => {
+                        let RenewMembraneProof {
+                            membrane_proof,
+                            supersedes,
+                            ..
+                        } = action;
+                        OpActivity::RenewMembraneProof {
+                            membrane_proof: membrane_proof.clone(),
+                            supersedes: supersedes.clone(),
+                            action: action.clone(),
+                        }
+                    }
                     Action::OpenChain(action) => {
                         let OpenChain { prev_dna_hash, .. } = action;
                         OpActivity::OpenChain {
@@ -581,7 +605,7 @@ where
         ..
     } = entry_def;
     match (entry, visibility) {
-        (Some(entry), EntryVisibility::Public) => {
+        (Some(entry), EntryVisibility::Public | EntryVisibility::Unlisted) => {
             get_app_entry_type_for_store_entry_authority(entry_def, entry).map(UnitEnumEither::Enum)
         }
 
@@ -596,9 +620,11 @@ where
             "Entry visibility is private but an entry was provided! entry_def: {entry_def:?}"
         )))),
 
-        (None, EntryVisibility::Public) => Err(wasm_error!(WasmErrorInner::Guest(format!(
-            "Entry visibility is public but no entry is available. entry_def: {entry_def:?}"
-        )))),
+        (None, EntryVisibility::Public | EntryVisibility::Unlisted) => {
+            Err(wasm_error!(WasmErrorInner::Guest(format!(
+                "Entry visibility is public but no entry is available. entry_def: {entry_def:?}"
+            ))))
+        }
     }
 }
 
@@ -621,7 +647,9 @@ where
         }) => {
             let unit = get_unit_entry_type::<ET>(*zome_index, *entry_def_index)?;
             match visibility {
-                EntryVisibility::Public => Ok(ActivityEntry::App { entry_type: unit }),
+                EntryVisibility::Public | EntryVisibility::Unlisted => {
+                    Ok(ActivityEntry::App { entry_type: unit })
+                }
                 EntryVisibility::Private => Ok(ActivityEntry::PrivateApp { entry_type: unit }),
             }
         }
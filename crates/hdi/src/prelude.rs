@@ -1,10 +1,15 @@
 pub use crate::app_entry;
+pub use crate::blob::*;
+pub use crate::chain::current_membrane_proof;
 pub use crate::chain::must_get_agent_activity;
 pub use crate::ed25519::verify_signature;
 pub use crate::ed25519::verify_signature_raw;
+pub use crate::entry::app_entry_size;
+pub use crate::entry::fits_entry_size_limit;
 pub use crate::entry::must_get_action;
 pub use crate::entry::must_get_entry;
 pub use crate::entry::must_get_valid_record;
+pub use crate::entry::VersionedEntry;
 pub use crate::entry_types;
 pub use crate::flat_op::*;
 pub use crate::hash::*;
@@ -16,6 +21,7 @@ pub use crate::hash_path::shard::ShardStrategy;
 pub use crate::hdi::*;
 pub use crate::info::dna_info;
 pub use crate::info::zome_info;
+pub use crate::link::must_be_base_author;
 pub use crate::link::LinkTypeFilterExt;
 pub use crate::map_extern;
 pub use crate::map_extern::ExternResult;
@@ -30,9 +36,11 @@ pub use hdk_derive::dna_properties;
 pub use hdk_derive::hdk_entry_helper;
 pub use hdk_derive::hdk_entry_types;
 pub use hdk_derive::hdk_entry_types_conversions;
+pub use hdk_derive::hdk_entry_versioned;
 pub use hdk_derive::hdk_extern;
 pub use hdk_derive::hdk_link_types;
 pub use hdk_derive::hdk_to_coordinates;
+pub use hdk_derive::hdk_validation_dispatch;
 pub use hdk_derive::EntryDefRegistration;
 pub use hdk_derive::UnitEnum;
 pub use holo_hash;
@@ -44,9 +52,11 @@ pub use holo_hash::DnaHash;
 pub use holo_hash::EntryHash;
 pub use holo_hash::EntryHashes;
 pub use holo_hash::ExternalHash;
+pub use holo_hash::ExternalNamespace;
 pub use holo_hash::HasHash;
 pub use holo_hash::HoloHash;
 pub use holo_hash::HoloHashed;
+pub use holo_hash::NamespacedExternalHash;
 pub use holochain_integrity_types;
 pub use holochain_integrity_types::prelude::*;
 pub use holochain_wasmer_guest::*;
@@ -1,5 +1,7 @@
 use crate::prelude::*;
 
+#[cfg(feature = "test_utils")]
+pub mod op_gen;
 pub mod short_hand;
 
 pub fn set_zome_types(entries: &[(u8, u8)], links: &[(u8, u8)]) {
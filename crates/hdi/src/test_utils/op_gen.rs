@@ -0,0 +1,520 @@
+//! [`proptest`] strategies which generate [`Op`]s for fuzzing the `validate`
+//! callback of an integrity zome.
+//!
+//! The strategies are built from the zome's own entry and link types and its
+//! [`ZomeIndex`] in the DNA, so every generated op refers to a type the zome
+//! defines. Valid ops are consistent in
+//! every way a zome can check: hashes match their content and the action follows
+//! on from [`OpCase::prev_action`], which should be returned by a
+//! [`must_get_action`] of the op's `prev_action`. Invalid ops break exactly one
+//! of these relationships, which is recorded as an [`OpMutation`].
+//!
+//! Signatures are not real because there is no keystore to sign with, so they
+//! are left to system validation.
+//!
+//! Only the ops for [`Action::Create`] and [`Action::CreateLink`] are generated.
+//! Updates, deletes and link deletes are not covered, nor are genesis actions.
+//!
+//! Validation callbacks look up the zome types, so they must be set with
+//! [`set_zome_types`](super::set_zome_types) before validating a generated op.
+//!
+//! The entry and link types are produced by a strategy the zome provides, because
+//! the types generated by `hdk_entry_types` don't implement `Arbitrary`:
+//!
+//! ```ignore
+//! fn entry_types() -> impl Strategy<Value = EntryTypes> {
+//!     "[a-z]{1,20}".prop_map(|title| EntryTypes::Post(Post { title }))
+//! }
+//!
+//! proptest! {
+//!     #[test]
+//!     fn invalid_ops_are_rejected(case in invalid_entry_ops(0.into(), entry_types())) {
+//!         set_zome_types(&[(0, 3)], &[(0, 3)]);
+//!         prop_assert!(matches!(
+//!             validate(case.op),
+//!             Ok(ValidateCallbackResult::Invalid(_))
+//!         ));
+//!     }
+//! }
+//! ```
+
+use crate::prelude::*;
+use proptest::prelude::*;
+
+/// The inconsistency introduced into an invalid [`OpCase`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OpMutation {
+    /// The action is authored by a different agent than the action before it.
+    BadAuthor,
+    /// The `prev_action` of the action is not the hash of the action before it.
+    BrokenPrevAction,
+    /// The entry hash of the action does not match the entry in the op.
+    MismatchedEntryHash,
+}
+
+/// An op generated for validation along with the chain context it refers to.
+#[derive(Clone, Debug)]
+pub struct OpCase {
+    /// The op to validate.
+    pub op: Op,
+    /// The action before the op's action on its author's chain.
+    pub prev_action: SignedActionHashed,
+    /// How the op was made invalid, or `None` if it is valid.
+    pub mutation: Option<OpMutation>,
+}
+
+/// Generate valid ops for the creation of entries of the types produced by `entry_types`.
+///
+/// The ops are [`Op::StoreRecord`], [`Op::StoreEntry`] and [`Op::RegisterAgentActivity`]
+/// for an [`Action::Create`]. Private entries are never stored as an [`Op::StoreEntry`]
+/// and are hidden from an [`Op::StoreRecord`].
+pub fn valid_entry_ops<ET>(
+    zome_index: ZomeIndex,
+    entry_types: impl Strategy<Value = ET>,
+) -> impl Strategy<Value = OpCase>
+where
+    ET: std::fmt::Debug,
+    for<'a> ZomeEntryTypesKey: From<&'a ET>,
+    for<'a> EntryVisibility: From<&'a ET>,
+    Entry: TryFrom<ET, Error = WasmError>,
+{
+    entry_drafts(zome_index, entry_types).prop_map(|draft| draft.into_case(None))
+}
+
+/// Generate ops for the creation of entries like [`valid_entry_ops`], which are each
+/// made invalid by one [`OpMutation`].
+pub fn invalid_entry_ops<ET>(
+    zome_index: ZomeIndex,
+    entry_types: impl Strategy<Value = ET>,
+) -> impl Strategy<Value = OpCase>
+where
+    ET: std::fmt::Debug,
+    for<'a> ZomeEntryTypesKey: From<&'a ET>,
+    for<'a> EntryVisibility: From<&'a ET>,
+    Entry: TryFrom<ET, Error = WasmError>,
+{
+    mutated(entry_drafts(zome_index, entry_types))
+}
+
+/// Generate valid ops for the creation of links of the types produced by `link_types`.
+///
+/// The ops are [`Op::RegisterCreateLink`], [`Op::StoreRecord`] and
+/// [`Op::RegisterAgentActivity`] for an [`Action::CreateLink`].
+pub fn valid_link_ops<LT>(
+    zome_index: ZomeIndex,
+    link_types: impl Strategy<Value = LT>,
+) -> impl Strategy<Value = OpCase>
+where
+    LT: std::fmt::Debug,
+    ZomeLinkTypesKey: From<LT>,
+{
+    link_drafts(zome_index, link_types).prop_map(|draft| draft.into_case(None))
+}
+
+/// Generate ops for the creation of links like [`valid_link_ops`], which are each
+/// made invalid by one [`OpMutation`].
+pub fn invalid_link_ops<LT>(
+    zome_index: ZomeIndex,
+    link_types: impl Strategy<Value = LT>,
+) -> impl Strategy<Value = OpCase>
+where
+    LT: std::fmt::Debug,
+    ZomeLinkTypesKey: From<LT>,
+{
+    mutated(link_drafts(zome_index, link_types))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum OpKind {
+    StoreRecord,
+    StoreEntry,
+    RegisterAgentActivity,
+    RegisterCreateLink,
+}
+
+/// An action and the op it will be published as, before it is signed and hashed.
+#[derive(Clone, Debug)]
+struct Draft {
+    kind: OpKind,
+    action: Action,
+    entry: Option<Entry>,
+    prev_action: SignedActionHashed,
+}
+
+/// Where the generated action is on its author's chain.
+#[derive(Clone, Debug)]
+struct Position {
+    author: AgentPubKey,
+    action_seq: u32,
+    timestamp: Timestamp,
+    prev_action: SignedActionHashed,
+}
+
+impl Draft {
+    /// The mutations which can be detected from the op and its chain context.
+    fn mutations(&self) -> Vec<OpMutation> {
+        let mut mutations = vec![OpMutation::BadAuthor, OpMutation::BrokenPrevAction];
+        if self.entry_in_op() {
+            mutations.push(OpMutation::MismatchedEntryHash);
+        }
+        mutations
+    }
+
+    fn entry_in_op(&self) -> bool {
+        match self.kind {
+            OpKind::StoreEntry => true,
+            OpKind::StoreRecord => self
+                .action
+                .entry_type()
+                .map_or(false, |t| *t.visibility() == EntryVisibility::Public),
+            OpKind::RegisterAgentActivity | OpKind::RegisterCreateLink => false,
+        }
+    }
+
+    fn mutate(
+        mut self,
+        mutation: OpMutation,
+        author: AgentPubKey,
+        prev_action: ActionHash,
+        entry_hash: EntryHash,
+    ) -> Self {
+        match (mutation, &mut self.action) {
+            (OpMutation::BadAuthor, Action::Create(Create { author: a, .. }))
+            | (OpMutation::BadAuthor, Action::CreateLink(CreateLink { author: a, .. })) => {
+                *a = author
+            }
+            (OpMutation::BrokenPrevAction, Action::Create(Create { prev_action: p, .. }))
+            | (
+                OpMutation::BrokenPrevAction,
+                Action::CreateLink(CreateLink { prev_action: p, .. }),
+            ) => *p = prev_action,
+            (OpMutation::MismatchedEntryHash, Action::Create(Create { entry_hash: e, .. })) => {
+                *e = entry_hash
+            }
+            (mutation, action) => {
+                unreachable!("{mutation:?} can't be applied to {action:?}")
+            }
+        }
+        self
+    }
+
+    fn into_case(self, mutation: Option<OpMutation>) -> OpCase {
+        let Draft {
+            kind,
+            action,
+            entry,
+            prev_action,
+        } = self;
+        let op = match (kind, action) {
+            (OpKind::StoreEntry, Action::Create(create)) => Op::StoreEntry(StoreEntry {
+                action: signed(EntryCreationAction::Create(create)),
+                entry: entry.expect("a created entry is generated with its action"),
+            }),
+            (OpKind::RegisterCreateLink, Action::CreateLink(create_link)) => {
+                Op::RegisterCreateLink(RegisterCreateLink {
+                    create_link: signed(create_link),
+                })
+            }
+            (OpKind::StoreRecord, action) => {
+                let entry = match (action.entry_type().map(|t| t.visibility()), entry) {
+                    (Some(EntryVisibility::Public), Some(entry)) => RecordEntry::Present(entry),
+                    (Some(_), _) => RecordEntry::Hidden,
+                    (None, _) => RecordEntry::NA,
+                };
+                Op::StoreRecord(StoreRecord {
+                    record: Record {
+                        signed_action: signed(action),
+                        entry,
+                    },
+                })
+            }
+            (OpKind::RegisterAgentActivity, action) => {
+                Op::RegisterAgentActivity(RegisterAgentActivity {
+                    action: signed(action),
+                    cached_entry: None,
+                })
+            }
+            (kind, action) => unreachable!("{kind:?} is never generated for {action:?}"),
+        };
+        OpCase {
+            op,
+            prev_action,
+            mutation,
+        }
+    }
+}
+
+fn entry_drafts<ET>(
+    zome_index: ZomeIndex,
+    entry_types: impl Strategy<Value = ET>,
+) -> impl Strategy<Value = Draft>
+where
+    ET: std::fmt::Debug,
+    for<'a> ZomeEntryTypesKey: From<&'a ET>,
+    for<'a> EntryVisibility: From<&'a ET>,
+    Entry: TryFrom<ET, Error = WasmError>,
+{
+    let kinds = prop::sample::select(vec![
+        OpKind::StoreRecord,
+        OpKind::StoreEntry,
+        OpKind::RegisterAgentActivity,
+    ]);
+    (position(), entry_types, kinds).prop_map(move |(position, entry_type, kind)| {
+        let entry_index = ZomeEntryTypesKey::from(&entry_type).type_index;
+        let visibility = EntryVisibility::from(&entry_type);
+        let entry = Entry::try_from(entry_type).expect("generated entries must serialize");
+        let action = Action::Create(Create {
+            author: position.author,
+            timestamp: position.timestamp,
+            action_seq: position.action_seq,
+            prev_action: position.prev_action.as_hash().clone(),
+            entry_type: EntryType::App(AppEntryDef::new(entry_index, zome_index, visibility)),
+            entry_hash: EntryHash::with_data_sync(&entry),
+            weight: Default::default(),
+        });
+        // Private entries are not published to entry authorities.
+        let kind = match (kind, visibility) {
+            (OpKind::StoreEntry, EntryVisibility::Private) => OpKind::StoreRecord,
+            (kind, _) => kind,
+        };
+        Draft {
+            kind,
+            action,
+            entry: Some(entry),
+            prev_action: position.prev_action,
+        }
+    })
+}
+
+fn link_drafts<LT>(
+    zome_index: ZomeIndex,
+    link_types: impl Strategy<Value = LT>,
+) -> impl Strategy<Value = Draft>
+where
+    LT: std::fmt::Debug,
+    ZomeLinkTypesKey: From<LT>,
+{
+    let kinds = prop::sample::select(vec![
+        OpKind::RegisterCreateLink,
+        OpKind::StoreRecord,
+        OpKind::RegisterAgentActivity,
+    ]);
+    (
+        position(),
+        link_types,
+        kinds,
+        entry_hash(),
+        entry_hash(),
+        prop::collection::vec(any::<u8>(), 0..64),
+    )
+        .prop_map(
+            move |(position, link_type, kind, base, target, tag)| Draft {
+                kind,
+                action: Action::CreateLink(CreateLink {
+                    author: position.author,
+                    timestamp: position.timestamp,
+                    action_seq: position.action_seq,
+                    prev_action: position.prev_action.as_hash().clone(),
+                    base_address: base.into(),
+                    target_address: target.into(),
+                    zome_index,
+                    link_type: ZomeLinkTypesKey::from(link_type).type_index,
+                    tag: LinkTag::new(tag),
+                    weight: Default::default(),
+                }),
+                entry: None,
+                prev_action: position.prev_action,
+            },
+        )
+}
+
+fn mutated(drafts: impl Strategy<Value = Draft>) -> impl Strategy<Value = OpCase> {
+    drafts
+        .prop_flat_map(|draft| {
+            let mutation = prop::sample::select(draft.mutations());
+            (Just(draft), mutation, agent(), action_hash(), entry_hash())
+        })
+        .prop_map(|(draft, mutation, author, prev_action, entry_hash)| {
+            draft
+                .mutate(mutation, author, prev_action, entry_hash)
+                .into_case(Some(mutation))
+        })
+}
+
+/// A position after genesis on a random agent's chain.
+fn position() -> impl Strategy<Value = Position> {
+    (
+        agent(),
+        4..u32::from(u16::MAX),
+        // Between 2020 and 2033, in microseconds.
+        1_600_000_000_000_000..2_000_000_000_000_000i64,
+        // Up to an hour between the previous action and this one.
+        0..3_600_000_000i64,
+        action_hash(),
+        entry_hash(),
+    )
+        .prop_map(
+            |(author, action_seq, prev_time, gap, prev_prev_action, claim_hash)| {
+                let prev_action = if action_seq == 4 {
+                    Action::InitZomesComplete(InitZomesComplete {
+                        author: author.clone(),
+                        timestamp: Timestamp(prev_time),
+                        action_seq: 3,
+                        prev_action: prev_prev_action,
+                    })
+                } else {
+                    Action::Create(Create {
+                        author: author.clone(),
+                        timestamp: Timestamp(prev_time),
+                        action_seq: action_seq - 1,
+                        prev_action: prev_prev_action,
+                        entry_type: EntryType::CapClaim,
+                        entry_hash: claim_hash,
+                        weight: Default::default(),
+                    })
+                };
+                Position {
+                    author,
+                    action_seq,
+                    timestamp: Timestamp(prev_time + gap),
+                    prev_action: signed(prev_action),
+                }
+            },
+        )
+}
+
+fn signed<T: HashableContent<HashType = holo_hash::hash_type::Action>>(
+    content: T,
+) -> SignedHashed<T> {
+    SignedHashed {
+        hashed: HoloHashed::from_content_sync(content),
+        signature: Signature([0u8; 64]),
+    }
+}
+
+fn agent() -> impl Strategy<Value = AgentPubKey> {
+    any::<[u8; 32]>().prop_map(|b| AgentPubKey::from_raw_32(b.to_vec()))
+}
+
+fn action_hash() -> impl Strategy<Value = ActionHash> {
+    any::<[u8; 32]>().prop_map(|b| ActionHash::from_raw_32(b.to_vec()))
+}
+
+fn entry_hash() -> impl Strategy<Value = EntryHash> {
+    any::<[u8; 32]>().prop_map(|b| EntryHash::from_raw_32(b.to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as hdi;
+    use crate::test_utils::set_zome_types;
+
+    #[hdk_entry_helper]
+    #[derive(Clone, PartialEq, Eq)]
+    pub struct Post(String);
+
+    #[hdk_entry_helper]
+    #[derive(Clone, PartialEq, Eq)]
+    pub struct Secret(u32);
+
+    #[hdk_entry_types(skip_hdk_extern = true)]
+    #[unit_enum(UnitEntryTypes)]
+    #[derive(Clone, PartialEq, Eq)]
+    pub enum EntryTypes {
+        Post(Post),
+        #[entry_type(visibility = "private")]
+        Secret(Secret),
+    }
+
+    #[hdk_link_types(skip_no_mangle = true)]
+    pub enum LinkTypes {
+        PostToPost,
+        SecretToPost,
+    }
+
+    fn entry_types() -> impl Strategy<Value = EntryTypes> {
+        prop_oneof![
+            any::<String>().prop_map(|s| EntryTypes::Post(Post(s))),
+            any::<u32>().prop_map(|n| EntryTypes::Secret(Secret(n))),
+        ]
+    }
+
+    fn link_types() -> impl Strategy<Value = LinkTypes> {
+        prop::sample::select(LinkTypes::iter().collect::<Vec<_>>())
+    }
+
+    /// The actions and entry of an op, which should agree with each other.
+    fn contents(op: &Op) -> (Action, ActionHash, Option<Entry>) {
+        match op {
+            Op::StoreRecord(StoreRecord { record }) => (
+                record.action().clone(),
+                record.action_address().clone(),
+                record.entry().as_option().cloned(),
+            ),
+            Op::StoreEntry(StoreEntry { action, entry }) => (
+                action.hashed.content.clone().into(),
+                action.hashed.hash.clone(),
+                Some(entry.clone()),
+            ),
+            Op::RegisterAgentActivity(RegisterAgentActivity { action, .. }) => {
+                (action.action().clone(), action.as_hash().clone(), None)
+            }
+            Op::RegisterCreateLink(RegisterCreateLink { create_link }) => (
+                create_link.hashed.content.clone().into(),
+                create_link.hashed.hash.clone(),
+                None,
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Find the mutations of an op, as a zome could.
+    fn find_mutations(case: &OpCase) -> Vec<OpMutation> {
+        let (action, hash, entry) = contents(&case.op);
+        assert_eq!(hash, ActionHash::with_data_sync(&action));
+        assert_eq!(
+            action.action_seq(),
+            case.prev_action.action().action_seq() + 1
+        );
+
+        let mut found = Vec::new();
+        if action.author() != case.prev_action.action().author() {
+            found.push(OpMutation::BadAuthor);
+        }
+        if action.prev_action() != Some(case.prev_action.as_hash()) {
+            found.push(OpMutation::BrokenPrevAction);
+        }
+        if let Some(entry) = entry {
+            if action.entry_hash() != Some(&EntryHash::with_data_sync(&entry)) {
+                found.push(OpMutation::MismatchedEntryHash);
+            }
+        }
+        found
+    }
+
+    proptest! {
+        #[test]
+        fn valid_ops_are_consistent(
+            case in prop_oneof![
+                valid_entry_ops(0.into(), entry_types()),
+                valid_link_ops(0.into(), link_types()),
+            ]
+        ) {
+            set_zome_types(&[(0, 2)], &[(0, 2)]);
+            prop_assert_eq!(case.mutation, None);
+            prop_assert_eq!(find_mutations(&case), vec![]);
+            prop_assert!(case.op.flattened::<EntryTypes, LinkTypes>().is_ok());
+        }
+
+        #[test]
+        fn invalid_ops_have_one_mutation(
+            case in prop_oneof![
+                invalid_entry_ops(0.into(), entry_types()),
+                invalid_link_ops(0.into(), link_types()),
+            ]
+        ) {
+            prop_assert_eq!(find_mutations(&case), case.mutation.into_iter().collect::<Vec<_>>());
+        }
+    }
+}
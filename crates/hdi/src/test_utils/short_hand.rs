@@ -254,6 +254,18 @@ pub fn avp(membrane_proof: Option<MembraneProof>) -> AgentValidationPkg {
     }
 }
 
+/// Create [`RenewMembraneProof`].
+pub fn rmp(supersedes: ActionHash, membrane_proof: MembraneProof) -> RenewMembraneProof {
+    RenewMembraneProof {
+        author: ak(0),
+        timestamp: Timestamp(0),
+        action_seq: 5,
+        prev_action: ah(0),
+        supersedes,
+        membrane_proof,
+    }
+}
+
 /// Create [`InitZomesComplete`].
 pub fn izc() -> InitZomesComplete {
     InitZomesComplete {
@@ -0,0 +1,115 @@
+use hdi::prelude::*;
+use hdi::test_utils::short_hand::*;
+use test_case::test_case;
+
+/// An HDI which can only fetch the actions it was created with.
+struct ActionsHdi(Vec<SignedActionHashed>);
+
+#[allow(unused_variables)]
+impl HdiT for ActionsHdi {
+    fn verify_signature(&self, verify_signature: VerifySignature) -> ExternResult<bool> {
+        todo!()
+    }
+
+    fn hash(&self, hash_input: HashInput) -> ExternResult<HashOutput> {
+        todo!()
+    }
+
+    fn must_get_entry(&self, must_get_entry_input: MustGetEntryInput) -> ExternResult<EntryHashed> {
+        todo!()
+    }
+
+    fn must_get_action(
+        &self,
+        must_get_action_input: MustGetActionInput,
+    ) -> ExternResult<SignedActionHashed> {
+        let hash = must_get_action_input.into_inner();
+        self.0
+            .iter()
+            .find(|action| *action.action_address() == hash)
+            .cloned()
+            .ok_or_else(|| wasm_error!(WasmErrorInner::Guest(format!("Missing action {hash}"))))
+    }
+
+    fn must_get_valid_record(
+        &self,
+        must_get_valid_record_input: MustGetValidRecordInput,
+    ) -> ExternResult<Record> {
+        todo!()
+    }
+
+    fn dna_info(&self, dna_info_input: ()) -> ExternResult<DnaInfo> {
+        todo!()
+    }
+
+    fn zome_info(&self, zome_info_input: ()) -> ExternResult<ZomeInfo> {
+        todo!()
+    }
+
+    fn x_salsa20_poly1305_decrypt(
+        &self,
+        x_salsa20_poly1305_decrypt: XSalsa20Poly1305Decrypt,
+    ) -> ExternResult<Option<XSalsa20Poly1305Data>> {
+        todo!()
+    }
+
+    fn x_25519_x_salsa20_poly1305_decrypt(
+        &self,
+        x_25519_x_salsa20_poly1305_decrypt: X25519XSalsa20Poly1305Decrypt,
+    ) -> ExternResult<Option<XSalsa20Poly1305Data>> {
+        todo!()
+    }
+
+    fn ed_25519_x_salsa20_poly1305_decrypt(
+        &self,
+        ed_25519_x_salsa20_poly1305_decrypt: Ed25519XSalsa20Poly1305Decrypt,
+    ) -> ExternResult<XSalsa20Poly1305Data> {
+        todo!()
+    }
+
+    fn trace(&self, trace_msg: TraceMsg) -> ExternResult<()> {
+        todo!()
+    }
+
+    fn must_get_agent_activity(
+        &self,
+        must_get_agent_activity_input: MustGetAgentActivityInput,
+    ) -> ExternResult<Vec<RegisterAgentActivity>> {
+        todo!()
+    }
+}
+
+/// A create action at `ah(hash)` written by `ak(author)`.
+fn base_action(hash: u8, author: u8) -> SignedActionHashed {
+    let mut create = c(EntryType::App(public_app_entry_def(0, 0)));
+    create.author = ak(author);
+    SignedActionHashed::with_presigned(
+        HoloHashed::with_pre_hashed(Action::Create(create), ah(hash)),
+        Signature([0; 64]),
+    )
+}
+
+#[test_case(ak(0).into() => true ; "own agent key")]
+#[test_case(ak(1).into() => false ; "other agent key")]
+#[test_case(ah(1).into() => true ; "own action")]
+#[test_case(ah(2).into() => false ; "other agent's action")]
+#[test_case(eh(1).into() => false ; "entry")]
+#[test_case(ExternalHash::from_raw_36(vec![1; 36]).into() => false ; "external hash")]
+fn base_author(base: AnyLinkableHash) -> bool {
+    set_hdi(ActionsHdi(vec![base_action(1, 0), base_action(2, 1)]));
+    let mut create_link = cl(0, 0);
+    create_link.base_address = base;
+    match must_be_base_author(&create_link).unwrap() {
+        ValidateCallbackResult::Valid => true,
+        ValidateCallbackResult::Invalid(_) => false,
+        r => panic!("Unexpected result {r:?}"),
+    }
+}
+
+#[test]
+fn missing_base_action_is_an_error() {
+    set_hdi(ActionsHdi(vec![]));
+    let mut create_link = cl(0, 0);
+    create_link.base_address = ah(1).into();
+    assert!(must_be_base_author(&create_link).is_err());
+}
@@ -0,0 +1,87 @@
+use hdi::prelude::*;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct PostV1(pub String);
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct PostV2 {
+    pub body: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct PostV3 {
+    pub title: String,
+    pub body: String,
+}
+
+impl From<PostV1> for PostV2 {
+    fn from(v1: PostV1) -> Self {
+        Self { body: v1.0 }
+    }
+}
+
+impl From<PostV2> for PostV3 {
+    fn from(v2: PostV2) -> Self {
+        Self {
+            title: "untitled".to_string(),
+            body: v2.body,
+        }
+    }
+}
+
+#[hdk_entry_versioned]
+pub enum Post {
+    V1(PostV1),
+    V2(PostV2),
+    V3(PostV3),
+}
+
+#[test]
+fn upgrades_one_version_at_a_time() {
+    let post = Post::V1(PostV1("hello".to_string()));
+    assert_eq!(post.version(), 1);
+    let post = post.upgrade();
+    assert_eq!(post.version(), 2);
+    let post = post.upgrade();
+    assert_eq!(post.version(), Post::LATEST_VERSION);
+    assert_eq!(post.upgrade().version(), Post::LATEST_VERSION);
+}
+
+#[test]
+fn old_entries_are_read_as_the_latest_version() {
+    let old = Entry::try_from(Post::V1(PostV1("hello".to_string()))).unwrap();
+    let latest = PostV3::try_from(&old).unwrap();
+    assert_eq!(
+        latest,
+        PostV3 {
+            title: "untitled".to_string(),
+            body: "hello".to_string(),
+        }
+    );
+
+    // The version tag is kept, so the entry still reads as the version it was committed with.
+    assert_eq!(Post::try_from(&old).unwrap().version(), 1);
+
+    let new = Entry::try_from(Post::from(PostV3 {
+        title: "hi".to_string(),
+        body: "there".to_string(),
+    }))
+    .unwrap();
+    assert_eq!(PostV3::try_from(new).unwrap().title, "hi");
+}
+
+#[test]
+fn entries_committed_before_versioning_are_read_as_the_first_version() {
+    // An entry committed as the bare first version, before the type was versioned.
+    let bytes = holochain_serialized_bytes::encode(&PostV1("hello".to_string())).unwrap();
+    let old = Entry::app(SerializedBytes::from(UnsafeBytes::from(bytes))).unwrap();
+
+    let post = Post::try_from(&old).unwrap();
+    assert_eq!(post.version(), 1);
+    assert_eq!(PostV3::try_from(&old).unwrap().body, "hello");
+
+    // Once rewritten it carries the version tag and still round trips.
+    let rewritten = Entry::try_from(post).unwrap();
+    assert_ne!(rewritten, old);
+    assert_eq!(Post::try_from(&rewritten).unwrap().version(), 1);
+}
@@ -5,7 +5,10 @@
 use hdi::prelude::*;
 use hdi::test_utils::set_zome_types;
 
+mod base_author;
+mod entry_versioned;
 mod op;
+mod validation_dispatch;
 
 fn to_coords(t: impl Into<ZomeLinkTypesKey>) -> (u8, u8) {
     let t = t.into();
@@ -213,6 +216,10 @@ mod entry_defs_overrides_mod {
         B(A),
         #[entry_type(required_validations = 10, cache_at_agent_activity = true)]
         C(A),
+        #[entry_type(purge_after_secs = 60)]
+        D(A),
+        #[entry_type(max_size = 1024)]
+        E(A),
     }
 }
 
@@ -224,20 +231,29 @@ fn entry_defs_overrides() {
             EntryDef {
                 id: "hey".into(),
                 visibility: Default::default(),
-                required_validations: Default::default(),
                 ..Default::default()
             },
             EntryDef {
                 id: "b".into(),
                 visibility: EntryVisibility::Private,
-                required_validations: Default::default(),
                 ..Default::default()
             },
             EntryDef {
                 id: "c".into(),
                 visibility: Default::default(),
-                required_validations: RequiredValidations(10),
                 cache_at_agent_activity: true,
+                policies: EntryDefPolicies::default()
+                    .with_required_validations(RequiredValidations(10)),
+            },
+            EntryDef {
+                id: "d".into(),
+                policies: EntryDefPolicies::default().with_purge(PurgePolicy::after_secs(60)),
+                ..Default::default()
+            },
+            EntryDef {
+                id: "e".into(),
+                policies: EntryDefPolicies::default().with_max_size(1024),
+                ..Default::default()
             },
         ]))
     );
@@ -264,19 +280,16 @@ fn entry_defs_default() {
             EntryDef {
                 id: "a".into(),
                 visibility: Default::default(),
-                required_validations: Default::default(),
                 ..Default::default()
             },
             EntryDef {
                 id: "b".into(),
                 visibility: Default::default(),
-                required_validations: Default::default(),
                 ..Default::default()
             },
             EntryDef {
                 id: "c".into(),
                 visibility: Default::default(),
-                required_validations: Default::default(),
                 ..Default::default()
             },
         ]))
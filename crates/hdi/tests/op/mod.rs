@@ -112,6 +112,7 @@ fn op_errors(op: Op) -> WasmErrorInner {
 #[test_case(FlatOp::RegisterAgentActivity(OpActivity::InitZomesComplete { action: izc()}))]
 #[test_case(FlatOp::RegisterAgentActivity(OpActivity::AgentValidationPkg{ membrane_proof: None, action: avp(None) }))]
 #[test_case(FlatOp::RegisterAgentActivity(OpActivity::AgentValidationPkg{ membrane_proof: Some(mp()), action: avp(Some(mp())) }))]
+#[test_case(FlatOp::RegisterAgentActivity(OpActivity::RenewMembraneProof{ membrane_proof: mp(), supersedes: ah(1), action: rmp(ah(1), mp()) }))]
 // Store Record
 // Entries
 // App Entries
@@ -141,6 +142,7 @@ fn op_errors(op: Op) -> WasmErrorInner {
 #[test_case(FlatOp::StoreRecord(OpRecord::InitZomesComplete { action: izc() }))]
 #[test_case(FlatOp::StoreRecord(OpRecord::AgentValidationPkg { action: avp(None), membrane_proof: None}))]
 #[test_case(FlatOp::StoreRecord(OpRecord::AgentValidationPkg { action: avp(Some(mp())), membrane_proof: Some(mp())}))]
+#[test_case(FlatOp::StoreRecord(OpRecord::RenewMembraneProof { action: rmp(ah(1), mp()), supersedes: ah(1), membrane_proof: mp()}))]
 // Store Entry
 #[test_case(FlatOp::StoreEntry(OpEntry::CreateEntry { action: c(EntryType::App(public_app_entry_def(0, 0))), app_entry: EntryTypes::A(A{}) }))]
 #[test_case(FlatOp::StoreEntry(OpEntry::UpdateEntry { action: u(EntryType::App(public_app_entry_def(0, 0))), original_action_hash: ah(1), original_entry_hash: eh(1), app_entry: EntryTypes::A(A{}) }))]
@@ -179,6 +181,10 @@ fn op_flattened(op: FlatOp<EntryTypes, LinkTypes>) {
             let d = Action::InitZomesComplete(action);
             store_record_entry(d, RecordEntry::NA)
         }
+        FlatOp::StoreRecord(OpRecord::RenewMembraneProof { action, .. }) => {
+            let d = Action::RenewMembraneProof(action);
+            store_record_entry(d, RecordEntry::NA)
+        }
         FlatOp::StoreRecord(OpRecord::OpenChain { action, .. }) => {
             let d = Action::OpenChain(action);
             store_record_entry(d, RecordEntry::NA)
@@ -400,6 +406,7 @@ fn op_flattened(op: FlatOp<EntryTypes, LinkTypes>) {
                 OpActivity::CloseChain { action, .. } => Action::CloseChain(action),
                 OpActivity::AgentValidationPkg { action, .. } => Action::AgentValidationPkg(action),
                 OpActivity::InitZomesComplete { action } => Action::InitZomesComplete(action),
+                OpActivity::RenewMembraneProof { action, .. } => Action::RenewMembraneProof(action),
             };
             let r = RegisterAgentActivity {
                 cached_entry: None,
@@ -569,6 +576,7 @@ fn op_match_sanity() {
             OpRecord::CloseChain { .. } => (),
             OpRecord::AgentValidationPkg { .. } => (),
             OpRecord::InitZomesComplete { .. } => (),
+            OpRecord::RenewMembraneProof { .. } => (),
         },
         FlatOp::StoreEntry(_) => (),
         FlatOp::RegisterAgentActivity(_) => (),
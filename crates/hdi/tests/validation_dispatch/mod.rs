@@ -0,0 +1,80 @@
+use super::set_zome_types;
+use hdi::prelude::*;
+use hdi::test_utils::short_hand::*;
+use test_case::test_case;
+
+#[hdk_entry_helper]
+pub struct A;
+#[hdk_entry_helper]
+pub struct B;
+
+#[hdk_validation_dispatch]
+#[hdk_entry_types(skip_hdk_extern = true)]
+#[unit_enum(UnitEntryTypes)]
+pub enum EntryTypes {
+    A(A),
+    #[entry_type(visibility = "private")]
+    B(B),
+}
+
+#[hdk_validation_dispatch]
+#[hdk_link_types(skip_no_mangle = true)]
+pub enum LinkTypes {
+    A,
+    B,
+}
+
+fn handled(name: &str) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(name.to_string()))
+}
+
+fn validate_create_a(_: EntryCreationAction, _: A) -> ExternResult<ValidateCallbackResult> {
+    handled("create a")
+}
+fn validate_update_a(_: Update, _: A) -> ExternResult<ValidateCallbackResult> {
+    handled("update a")
+}
+fn validate_delete_a(
+    _: Delete,
+    _: EntryCreationAction,
+    _: A,
+) -> ExternResult<ValidateCallbackResult> {
+    handled("delete a")
+}
+fn validate_create_b(_: EntryCreationAction, _: B) -> ExternResult<ValidateCallbackResult> {
+    handled("create b")
+}
+fn validate_update_b(_: Update, _: B) -> ExternResult<ValidateCallbackResult> {
+    handled("update b")
+}
+fn validate_delete_b(
+    _: Delete,
+    _: EntryCreationAction,
+    _: B,
+) -> ExternResult<ValidateCallbackResult> {
+    handled("delete b")
+}
+fn validate_create_link_a(_: CreateLink) -> ExternResult<ValidateCallbackResult> {
+    handled("create link a")
+}
+fn validate_delete_link_a(_: DeleteLink, _: CreateLink) -> ExternResult<ValidateCallbackResult> {
+    handled("delete link a")
+}
+fn validate_create_link_b(_: CreateLink) -> ExternResult<ValidateCallbackResult> {
+    handled("create link b")
+}
+fn validate_delete_link_b(_: DeleteLink, _: CreateLink) -> ExternResult<ValidateCallbackResult> {
+    handled("delete link b")
+}
+
+#[test_case(s_entry(c(EntryType::App(public_app_entry_def(0, 0))).into(), e(A)) => handled("create a").unwrap())]
+#[test_case(s_entry(u(EntryType::App(public_app_entry_def(0, 0))).into(), e(A)) => handled("update a").unwrap())]
+#[test_case(s_record(create_entry(0, 0), RecordEntry::Present(e(A))) => ValidateCallbackResult::Valid ; "entries are validated by the store entry authority")]
+#[test_case(s_record(create_hidden_entry(0, 1), RecordEntry::Hidden) => ValidateCallbackResult::Valid ; "private entries are not validated")]
+#[test_case(r_create_link(0, 0) => handled("create link a").unwrap())]
+#[test_case(r_delete_link(0, 1) => handled("delete link b").unwrap())]
+#[test_case(r_activity(create_entry(0, 0)) => ValidateCallbackResult::Valid)]
+fn dispatches_to_handler(op: Op) -> ValidateCallbackResult {
+    set_zome_types(&[(0, 2)], &[(0, 2)]);
+    dispatch_validation::<EntryTypes, LinkTypes>(&op).unwrap()
+}
@@ -0,0 +1,168 @@
+use crate::prelude::*;
+
+/// Store a large payload as a blob of chunks and a [`BlobManifest`].
+///
+/// Each chunk is published to the DHT as its own op, addressed by the hash of its bytes, with
+/// [`create_blob_chunk`]. The `manifest_entry` function wraps the manifest in the zome's entry
+/// types, which must include it, see [`hdi::blob`]. The chunks are then held on this node for
+/// as long as the manifest isn't deleted or released.
+///
+/// Returns the [`ActionHash`] of the manifest. The manifest's entry hash is what readers pass
+/// to [`read_blob_stream`].
+///
+/// ```ignore
+/// let manifest = create_blob(&bytes, EntryTypes::BlobManifest)?;
+/// ```
+pub fn create_blob<I, E, E2>(
+    bytes: &[u8],
+    manifest_entry: impl FnOnce(BlobManifest) -> I,
+) -> ExternResult<ActionHash>
+where
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a I, Error = E2>,
+    EntryVisibility: for<'a> From<&'a I>,
+    Entry: TryFrom<I, Error = E>,
+    WasmError: From<E>,
+    WasmError: From<E2>,
+{
+    let chunks = bytes
+        .chunks(BLOB_CHUNK_SIZE)
+        .map(|chunk| create_blob_chunk(chunk.to_vec()))
+        .collect::<ExternResult<Vec<_>>>()?;
+    let manifest = create_entry(manifest_entry(BlobManifest {
+        size: bytes.len() as u64,
+        chunks: chunks.clone(),
+    }))?;
+    hold_blob_chunks(manifest.clone(), chunks)?;
+    Ok(manifest)
+}
+
+/// Publish one chunk of a blob to the DHT and return the hash of its bytes.
+///
+/// The chunk must be no larger than [`BLOB_CHUNK_SIZE`] and not empty. Publishing the same
+/// bytes again returns the same hash and stores nothing new.
+///
+/// The chunk is stored with the rest of the call's writes and must be held by a manifest
+/// created in the same call with [`hold_blob_chunks`], otherwise the call fails. A call which
+/// fails leaves none of its chunks behind.
+pub fn create_blob_chunk(bytes: Vec<u8>) -> ExternResult<EntryHash> {
+    HDK.with(|h| h.borrow().create_blob_chunk(BlobChunk(bytes)))
+}
+
+/// Read the blob chunk with this hash, or `None` if no authority for it could be reached.
+///
+/// A chunk which isn't held on this node is fetched from its authorities and kept. Chunks
+/// created earlier in the same call are read back before they are stored.
+pub fn get_blob_chunk(chunk: EntryHash) -> ExternResult<Option<Vec<u8>>> {
+    HDK.with(|h| h.borrow().get_blob_chunk(chunk))
+        .map(|chunk| chunk.map(|chunk| chunk.0))
+}
+
+/// Keep these chunks on this node until the manifest created by the action `manifest` is
+/// deleted or released with [`release_blob_chunks`].
+pub fn hold_blob_chunks(manifest: ActionHash, chunks: Vec<EntryHash>) -> ExternResult<()> {
+    HDK.with(|h| {
+        h.borrow()
+            .hold_blob_chunks(HoldBlobChunksInput { manifest, chunks })
+    })
+}
+
+/// Release the chunks held for the manifest created by the action `manifest`.
+///
+/// Chunks which no other manifest holds are removed from this node when the call's writes are
+/// stored, unless it is an authority for them. Deleting the manifest releases its chunks too.
+pub fn release_blob_chunks(manifest: ActionHash) -> ExternResult<()> {
+    HDK.with(|h| h.borrow().release_blob_chunks(manifest))
+}
+
+/// Read the [`BlobManifest`] with this entry hash, or `None` if it can't be found.
+pub fn get_blob_manifest(manifest: EntryHash) -> ExternResult<Option<BlobManifest>> {
+    get(manifest, GetOptions::default())?
+        .map(BlobManifest::try_from)
+        .transpose()
+}
+
+/// Read the blob with this manifest entry hash a chunk at a time.
+///
+/// Only the manifest is fetched up front. Each chunk is fetched when the stream reaches it,
+/// so a reader which stops early doesn't fetch the rest of the blob. Returns `None` if the
+/// manifest can't be found.
+///
+/// ```ignore
+/// if let Some(stream) = read_blob_stream(manifest_hash)? {
+///     for chunk in stream {
+///         process(&chunk?);
+///     }
+/// }
+/// ```
+pub fn read_blob_stream(manifest: EntryHash) -> ExternResult<Option<BlobStream>> {
+    Ok(get_blob_manifest(manifest)?.map(|manifest| BlobStream {
+        chunks: manifest.chunks.into_iter(),
+    }))
+}
+
+/// Read the whole blob with this manifest entry hash into memory.
+///
+/// Returns `None` if the manifest can't be found. Prefer [`read_blob_stream`] for blobs which
+/// don't need to be held in memory at once.
+pub fn read_blob(manifest: EntryHash) -> ExternResult<Option<Vec<u8>>> {
+    let Some(manifest) = get_blob_manifest(manifest)? else {
+        return Ok(None);
+    };
+    let mut bytes = Vec::with_capacity(manifest.size as usize);
+    for chunk in (BlobStream {
+        chunks: manifest.chunks.into_iter(),
+    }) {
+        bytes.extend(chunk?);
+    }
+    Ok(Some(bytes))
+}
+
+/// The chunks of a blob, fetched one at a time as the stream is iterated.
+///
+/// Returned by [`read_blob_stream`]. Yields an error if a chunk can't be found.
+pub struct BlobStream {
+    chunks: std::vec::IntoIter<EntryHash>,
+}
+
+impl BlobStream {
+    /// The number of chunks left to read.
+    pub fn remaining(&self) -> usize {
+        self.chunks.len()
+    }
+}
+
+impl Iterator for BlobStream {
+    type Item = ExternResult<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let hash = self.chunks.next()?;
+        Some(get_blob_chunk(hash.clone()).and_then(|chunk| {
+            chunk.ok_or_else(|| {
+                wasm_error!(WasmErrorInner::Guest(format!(
+                    "Blob chunk {} could not be found",
+                    hash
+                )))
+            })
+        }))
+    }
+}
+
+/// Delete a blob this agent created with [`create_blob`] and release its chunks.
+///
+/// Deletes the manifest created by the action `manifest`, which releases the chunks held for
+/// it. Chunks are held per manifest, so a chunk which is also part
+/// of another of this agent's blobs stays on this node, and nothing else on the chain is read.
+///
+/// Returns the [`ActionHash`] of the delete of the manifest.
+pub fn delete_blob(manifest: ActionHash) -> ExternResult<ActionHash> {
+    let author = agent_info()?.agent_latest_pubkey;
+    get(manifest.clone(), GetOptions::local())?
+        .filter(|record| *record.action().author() == author)
+        .ok_or_else(|| {
+            wasm_error!(WasmErrorInner::Guest(format!(
+                "Blob manifest {} is not on this agent's chain",
+                manifest
+            )))
+        })?;
+    delete_entry(manifest)
+}
@@ -18,6 +18,42 @@ pub fn get_agent_activity(
     })
 }
 
+/// Query the _actions_ of a remote agent's chain, like [`get_agent_activity`],
+/// choosing where the activity is read from.
+///
+/// [`ReadSource::LocalOnly`] only returns the activity held locally, so it
+/// never waits on the network.
+pub fn get_agent_activity_from(
+    agent: AgentPubKey,
+    query: ChainQueryFilter,
+    request: ActivityRequest,
+    read_source: ReadSource,
+) -> ExternResult<AgentActivity> {
+    HDK.with(|h| {
+        h.borrow().get_agent_activity(
+            GetAgentActivityInput::new(agent, query, request).with_read_source(read_source),
+        )
+    })
+}
+
+/// Get the action at position `action_seq` of an agent's chain.
+///
+/// Authorities look the action up by author and sequence number, so this is
+/// cheaper than fetching the agent activity for a range of the chain.
+/// Returns `None` if the action is not held by the agent activity authorities
+/// that were asked, or locally if the `read_source` is [`ReadSource::LocalOnly`].
+pub fn get_action_by_seq(
+    author: AgentPubKey,
+    action_seq: u32,
+    read_source: ReadSource,
+) -> ExternResult<Option<SignedActionHashed>> {
+    HDK.with(|h| {
+        h.borrow().get_action_by_seq(
+            GetActionBySeqInput::new(author, action_seq).with_read_source(read_source),
+        )
+    })
+}
+
 /// Walks the source chain in ascending order (oldest to latest) filtering by action and/or entry type
 ///
 /// Given an action and entry type, returns an [ `Vec<Record>` ]
@@ -31,3 +67,16 @@ pub fn get_agent_activity(
 pub fn query(filter: ChainQueryFilter) -> ExternResult<Vec<Record>> {
     HDK.with(|h| h.borrow().query(filter))
 }
+
+/// Renew your membrane proof, e.g. because the one you joined the network with has expired.
+///
+/// Commits a [`RenewMembraneProof`] action which supersedes your current membrane proof. That is
+/// the proof of your latest renewal, or the proof from your [`AgentValidationPkg`] if you never
+/// renewed it. The new proof is validated by your app's validation rules like the original, and
+/// [`current_membrane_proof`] finds it in your chain activity from then on.
+pub fn renew_membrane_proof(membrane_proof: MembraneProof) -> ExternResult<ActionHash> {
+    HDK.with(|h| {
+        h.borrow()
+            .renew_membrane_proof(RenewMembraneProofInput { membrane_proof })
+    })
+}
@@ -0,0 +1,107 @@
+use crate::prelude::*;
+
+/// Propose an app entry to a counterparty, who can accept it later with
+/// [`accept_dual_signed_entry`].
+///
+/// Takes the same input as [`create_entry`], plus the agent which may accept the entry.
+/// Unlike a countersigning session, the counterparty doesn't have to be online: the
+/// proposal is committed straight away, and stays open until the counterparty accepts it.
+///
+/// The entry should be public, so that the counterparty can get it to accept it.
+///
+/// ```ignore
+/// let proposal = propose_dual_signed_entry(bob, EntryTypes::Trade(trade))?;
+/// ```
+pub fn propose_dual_signed_entry<I, E, E2>(
+    counterparty: AgentPubKey,
+    input: I,
+) -> ExternResult<ActionHash>
+where
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a I, Error = E2>,
+    EntryVisibility: for<'a> From<&'a I>,
+    Entry: TryFrom<I, Error = E>,
+    WasmError: From<E>,
+    WasmError: From<E2>,
+{
+    let ScopedEntryDefIndex {
+        zome_index,
+        zome_type: entry_def_index,
+    } = (&input).try_into()?;
+    let visibility = EntryVisibility::from(&input);
+    let entry_bytes = match input.try_into()? {
+        Entry::App(entry_bytes) => entry_bytes,
+        entry => {
+            return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                "{:?} is not an app entry so can't be dual signed",
+                entry
+            ))))
+        }
+    };
+    create(CreateInput::new(
+        EntryDefLocation::app(zome_index, entry_def_index),
+        visibility,
+        dual_sign_proposal(counterparty, entry_bytes),
+        ChainTopOrdering::default(),
+    ))
+}
+
+/// Accept a dual signed entry which was proposed to this agent with
+/// [`propose_dual_signed_entry`].
+///
+/// The acceptance is committed as an update of the proposal, with the same app entry.
+/// It is only valid if this agent is the counterparty which the proposal names.
+pub fn accept_dual_signed_entry(proposal: ActionHash) -> ExternResult<ActionHash> {
+    let record = get(proposal.clone(), GetOptions::network())?.ok_or_else(|| {
+        wasm_error!(WasmErrorInner::Guest(format!(
+            "The proposal {} could not be found",
+            proposal
+        )))
+    })?;
+    let entry_bytes = match record.entry().as_option() {
+        Some(Entry::DualSign(data, entry_bytes)) => match data.as_ref() {
+            DualSignData::Proposal { counterparty }
+                if *counterparty == agent_info()?.agent_initial_pubkey =>
+            {
+                entry_bytes.clone()
+            }
+            DualSignData::Proposal { .. } => {
+                return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                    "The proposal {} was made to another agent",
+                    proposal
+                ))))
+            }
+            DualSignData::Acceptance { .. } => {
+                return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                    "{} is an acceptance, not a proposal",
+                    proposal
+                ))))
+            }
+        },
+        _ => {
+            return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                "{} is not a dual signed entry proposal",
+                proposal
+            ))))
+        }
+    };
+    update(UpdateInput {
+        original_action_address: proposal,
+        entry: dual_sign_acceptance(record.action().author().clone(), entry_bytes),
+        chain_top_ordering: ChainTopOrdering::default(),
+    })
+}
+
+/// Get the counterparty's acceptance of a proposed dual signed entry.
+///
+/// Returns `None` if the proposal can't be found or hasn't been accepted yet.
+/// If the counterparty accepted the proposal more than once, the oldest acceptance
+/// is returned.
+pub fn get_dual_signed_acceptance(
+    proposal: ActionHash,
+    options: GetOptions,
+) -> ExternResult<Option<Record>> {
+    HDK.with(|h| {
+        h.borrow()
+            .get_dual_sign_acceptance(GetInput::new(proposal.into(), options))
+    })
+}
@@ -93,6 +93,45 @@ where
     create(create_input)
 }
 
+/// Create an app entry unless this agent has already created an identical one.
+///
+/// Takes the same input as [`create_entry`]. If a `Create` of the same entry, with the same
+/// entry type, is already on this agent's source chain and hasn't been deleted, no new record is
+/// written and the existing action hash is returned. Writes made earlier in the same zome call
+/// are checked too, so calling this twice with the same entry only creates it once.
+///
+/// This replaces the racy pattern of calling [`get`] and then [`create_entry`] when the entry
+/// isn't found. As with [`create_entry`] the write uses [`ChainTopOrdering::Strict`], so if two
+/// zome calls try to create the same entry at the same time, one of them fails with a
+/// `HeadMoved` error and, when retried, returns the other's action hash.
+///
+/// e.g.
+/// ```ignore
+/// let action_hash = ensure_entry(EntryTypes::Foo(Foo(50)))?;
+/// assert_eq!(action_hash, ensure_entry(EntryTypes::Foo(Foo(50)))?);
+/// ```
+pub fn ensure_entry<I, E, E2>(input: I) -> ExternResult<ActionHash>
+where
+    ScopedEntryDefIndex: for<'a> TryFrom<&'a I, Error = E2>,
+    EntryVisibility: for<'a> From<&'a I>,
+    Entry: TryFrom<I, Error = E>,
+    WasmError: From<E>,
+    WasmError: From<E2>,
+{
+    let ScopedEntryDefIndex {
+        zome_index,
+        zome_type: entry_def_index,
+    } = (&input).try_into()?;
+    let visibility = EntryVisibility::from(&input);
+    let create_input = CreateInput::new(
+        EntryDefLocation::app(zome_index, entry_def_index),
+        visibility,
+        input.try_into()?,
+        ChainTopOrdering::default(),
+    );
+    HDK.with(|h| h.borrow().ensure_entry(create_input))
+}
+
 /// Delete an app entry. Also see [`delete`].
 ///
 /// This function accepts the [`ActionHash`] of the record to delete and optionally an argument to
@@ -294,6 +333,35 @@ pub fn get_details<H: Into<AnyDhtHash>>(
         .unwrap())
 }
 
+/// Get a page of the public entries of one type created by an author, in the order they were
+/// created.
+///
+/// The author's agent activity authorities list the actions which created the entries from an
+/// index by author and entry type, so this is cheaper than walking the author's agent activity.
+/// The entries are then fetched from their own authorities, and an entry which can't be fetched
+/// is left out of the page.
+///
+/// Get the next page with [`EntriesPage::after`] and the sequence number of the last record's action.
+///
+/// e.g.
+/// ```ignore
+/// let posts = get_entries_by_author(
+///     author,
+///     UnitEntryTypes::Post.try_into()?,
+///     EntriesPage::first(20),
+/// )?;
+/// ```
+pub fn get_entries_by_author(
+    author: AgentPubKey,
+    entry_type: EntryType,
+    page: EntriesPage,
+) -> ExternResult<Vec<Record>> {
+    HDK.with(|h| {
+        h.borrow()
+            .get_entries_by_author(GetEntriesByAuthorInput::new(author, entry_type, page))
+    })
+}
+
 /// Implements a whole lot of sane defaults for a struct or enum that should behave as an entry.
 /// All the entry def fields are available as dedicated methods on the type and matching From impls
 /// are provided for each. This allows for both Foo::entry_types() and EntryDef::from(Foo::new())
@@ -26,16 +26,26 @@ pub trait HdkT: HdiT {
         &self,
         get_agent_activity_input: GetAgentActivityInput,
     ) -> ExternResult<AgentActivity>;
+    fn get_action_by_seq(
+        &self,
+        get_action_by_seq_input: GetActionBySeqInput,
+    ) -> ExternResult<Option<SignedActionHashed>>;
     fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>>;
     // Ed25519
     fn sign(&self, sign: Sign) -> ExternResult<Signature>;
     fn sign_ephemeral(&self, sign_ephemeral: SignEphemeral) -> ExternResult<EphemeralSignatures>;
     // Entry
     fn create(&self, create_input: CreateInput) -> ExternResult<ActionHash>;
+    fn ensure_entry(&self, create_input: CreateInput) -> ExternResult<ActionHash>;
     fn update(&self, update_input: UpdateInput) -> ExternResult<ActionHash>;
     fn delete(&self, delete_input: DeleteInput) -> ExternResult<ActionHash>;
     fn get(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Record>>>;
     fn get_details(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>>;
+    fn get_dual_sign_acceptance(&self, get_input: GetInput) -> ExternResult<Option<Record>>;
+    fn get_entries_by_author(
+        &self,
+        get_entries_by_author_input: GetEntriesByAuthorInput,
+    ) -> ExternResult<Vec<Record>>;
     // CounterSigning
     fn accept_countersigning_preflight_request(
         &self,
@@ -44,14 +54,29 @@ pub trait HdkT: HdiT {
     // Info
     fn agent_info(&self, agent_info_input: ()) -> ExternResult<AgentInfo>;
     fn call_info(&self, call_info_input: ()) -> ExternResult<CallInfo>;
+    fn entry_type_metrics(
+        &self,
+        entry_type_metrics_input: (),
+    ) -> ExternResult<Vec<EntryTypeMetrics>>;
     // Link
     fn create_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash>;
+    fn create_links(
+        &self,
+        create_link_inputs: Vec<CreateLinkInput>,
+    ) -> ExternResult<Vec<ActionHash>>;
     fn delete_link(&self, delete_link_input: DeleteLinkInput) -> ExternResult<ActionHash>;
+    fn delete_links(
+        &self,
+        delete_link_inputs: Vec<DeleteLinkInput>,
+    ) -> ExternResult<Vec<ActionHash>>;
+    fn ensure_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash>;
     fn get_links(&self, get_links_input: Vec<GetLinksInput>) -> ExternResult<Vec<Vec<Link>>>;
     fn get_link_details(
         &self,
         get_links_input: Vec<GetLinksInput>,
     ) -> ExternResult<Vec<LinkDetails>>;
+    fn get_links_delta(&self, get_links_input: Vec<GetLinksInput>)
+        -> ExternResult<Vec<LinksDelta>>;
     fn count_links(&self, query: LinkQuery) -> ExternResult<usize>;
     // P2P
     fn block_agent(&self, block_agent_input: BlockAgentInput) -> ExternResult<()>;
@@ -59,10 +84,16 @@ pub trait HdkT: HdiT {
     fn call(&self, call: Vec<Call>) -> ExternResult<Vec<ZomeCallResponse>>;
     fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()>;
     fn send_remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()>;
+    fn remote_query(&self, remote_query: RemoteQuery) -> ExternResult<ZomeCallResponse>;
+    fn call_remote_durable(
+        &self,
+        durable_call_remote: DurableCallRemote,
+    ) -> ExternResult<DurableCallId>;
     // Random
     fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes>;
     // Time
     fn sys_time(&self, sys_time_input: ()) -> ExternResult<Timestamp>;
+    fn network_time(&self, network_time_input: ()) -> ExternResult<NetworkTime>;
     fn schedule(&self, scheduled_fn: String) -> ExternResult<()>;
     fn sleep(&self, wake_after: std::time::Duration) -> ExternResult<()>;
     // XSalsa20Poly1305
@@ -99,6 +130,13 @@ pub trait HdkT: HdiT {
     // Migrate DNA
     fn close_chain(&self, input: CloseChainInput) -> ExternResult<ActionHash>;
     fn open_chain(&self, input: OpenChainInput) -> ExternResult<ActionHash>;
+    // Membrane proof
+    fn renew_membrane_proof(&self, input: RenewMembraneProofInput) -> ExternResult<ActionHash>;
+    // Blobs
+    fn create_blob_chunk(&self, input: BlobChunk) -> ExternResult<EntryHash>;
+    fn get_blob_chunk(&self, input: EntryHash) -> ExternResult<Option<BlobChunk>>;
+    fn hold_blob_chunks(&self, input: HoldBlobChunksInput) -> ExternResult<()>;
+    fn release_blob_chunks(&self, input: ActionHash) -> ExternResult<()>;
 }
 
 #[cfg(feature = "mock")]
@@ -111,16 +149,26 @@ mockall::mock! {
             &self,
             get_agent_activity_input: GetAgentActivityInput,
         ) -> ExternResult<AgentActivity>;
+        fn get_action_by_seq(
+            &self,
+            get_action_by_seq_input: GetActionBySeqInput,
+        ) -> ExternResult<Option<SignedActionHashed>>;
         fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>>;
         // Ed25519
         fn sign(&self, sign: Sign) -> ExternResult<Signature>;
         fn sign_ephemeral(&self, sign_ephemeral: SignEphemeral) -> ExternResult<EphemeralSignatures>;
         // Entry
         fn create(&self, create_input: CreateInput) -> ExternResult<ActionHash>;
+        fn ensure_entry(&self, create_input: CreateInput) -> ExternResult<ActionHash>;
         fn update(&self, update_input: UpdateInput) -> ExternResult<ActionHash>;
         fn delete(&self, delete_input: DeleteInput) -> ExternResult<ActionHash>;
         fn get(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Record>>>;
         fn get_details(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>>;
+        fn get_dual_sign_acceptance(&self, get_input: GetInput) -> ExternResult<Option<Record>>;
+        fn get_entries_by_author(
+            &self,
+            get_entries_by_author_input: GetEntriesByAuthorInput,
+        ) -> ExternResult<Vec<Record>>;
         // CounterSigning
         fn accept_countersigning_preflight_request(
             &self,
@@ -129,14 +177,31 @@ mockall::mock! {
         // Info
         fn agent_info(&self, agent_info_input: ()) -> ExternResult<AgentInfo>;
         fn call_info(&self, call_info_input: ()) -> ExternResult<CallInfo>;
+        fn entry_type_metrics(
+            &self,
+            entry_type_metrics_input: (),
+        ) -> ExternResult<Vec<EntryTypeMetrics>>;
         // Link
         fn create_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash>;
+        fn create_links(
+            &self,
+            create_link_inputs: Vec<CreateLinkInput>,
+        ) -> ExternResult<Vec<ActionHash>>;
         fn delete_link(&self, delete_link_input: DeleteLinkInput) -> ExternResult<ActionHash>;
+        fn delete_links(
+            &self,
+            delete_link_inputs: Vec<DeleteLinkInput>,
+        ) -> ExternResult<Vec<ActionHash>>;
+        fn ensure_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash>;
         fn get_links(&self, get_links_input: Vec<GetLinksInput>) -> ExternResult<Vec<Vec<Link>>>;
         fn get_link_details(
             &self,
             get_links_input: Vec<GetLinksInput>,
         ) -> ExternResult<Vec<LinkDetails>>;
+        fn get_links_delta(
+            &self,
+            get_links_input: Vec<GetLinksInput>,
+        ) -> ExternResult<Vec<LinksDelta>>;
         fn count_links(&self, query: LinkQuery) -> ExternResult<usize>;
         // P2P
         fn block_agent(&self, block_agent_input: BlockAgentInput) -> ExternResult<()>;
@@ -144,10 +209,16 @@ mockall::mock! {
         fn call(&self, call: Vec<Call>) -> ExternResult<Vec<ZomeCallResponse>>;
         fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()>;
         fn send_remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()>;
+        fn remote_query(&self, remote_query: RemoteQuery) -> ExternResult<ZomeCallResponse>;
+        fn call_remote_durable(
+            &self,
+            durable_call_remote: DurableCallRemote,
+        ) -> ExternResult<DurableCallId>;
         // Random
         fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes>;
         // Time
         fn sys_time(&self, sys_time_input: ()) -> ExternResult<Timestamp>;
+        fn network_time(&self, network_time_input: ()) -> ExternResult<NetworkTime>;
         fn schedule(&self, scheduled_fn: String) -> ExternResult<()>;
         fn sleep(&self, wake_after: std::time::Duration) -> ExternResult<()>;
         // XSalsa20Poly1305
@@ -182,6 +253,14 @@ mockall::mock! {
         fn delete_clone_cell(&self, input: DeleteCloneCellInput) -> ExternResult<()>;
         fn close_chain(&self, input: CloseChainInput) -> ExternResult<ActionHash>;
         fn open_chain(&self, input: OpenChainInput) -> ExternResult<ActionHash>;
+        fn renew_membrane_proof(
+            &self,
+            input: RenewMembraneProofInput,
+        ) -> ExternResult<ActionHash>;
+        fn create_blob_chunk(&self, input: BlobChunk) -> ExternResult<EntryHash>;
+        fn get_blob_chunk(&self, input: EntryHash) -> ExternResult<Option<BlobChunk>>;
+        fn hold_blob_chunks(&self, input: HoldBlobChunksInput) -> ExternResult<()>;
+        fn release_blob_chunks(&self, input: ActionHash) -> ExternResult<()>;
     }
 
     impl HdiT for HdkT {
@@ -311,6 +390,12 @@ impl HdkT for ErrHdk {
     fn get_agent_activity(&self, _: GetAgentActivityInput) -> ExternResult<AgentActivity> {
         Self::err()
     }
+    fn get_action_by_seq(
+        &self,
+        _: GetActionBySeqInput,
+    ) -> ExternResult<Option<SignedActionHashed>> {
+        Self::err()
+    }
     fn query(&self, _: ChainQueryFilter) -> ExternResult<Vec<Record>> {
         Self::err()
     }
@@ -323,6 +408,9 @@ impl HdkT for ErrHdk {
     fn create(&self, _: CreateInput) -> ExternResult<ActionHash> {
         Self::err()
     }
+    fn ensure_entry(&self, _: CreateInput) -> ExternResult<ActionHash> {
+        Self::err()
+    }
     fn update(&self, _: UpdateInput) -> ExternResult<ActionHash> {
         Self::err()
     }
@@ -335,6 +423,12 @@ impl HdkT for ErrHdk {
     fn get_details(&self, _: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>> {
         Self::err()
     }
+    fn get_dual_sign_acceptance(&self, _: GetInput) -> ExternResult<Option<Record>> {
+        Self::err()
+    }
+    fn get_entries_by_author(&self, _: GetEntriesByAuthorInput) -> ExternResult<Vec<Record>> {
+        Self::err()
+    }
     // CounterSigning
     fn accept_countersigning_preflight_request(
         &self,
@@ -348,19 +442,34 @@ impl HdkT for ErrHdk {
     fn call_info(&self, _: ()) -> ExternResult<CallInfo> {
         Self::err()
     }
+    fn entry_type_metrics(&self, _: ()) -> ExternResult<Vec<EntryTypeMetrics>> {
+        Self::err()
+    }
     // Link
     fn create_link(&self, _: CreateLinkInput) -> ExternResult<ActionHash> {
         Self::err()
     }
+    fn create_links(&self, _: Vec<CreateLinkInput>) -> ExternResult<Vec<ActionHash>> {
+        Self::err()
+    }
     fn delete_link(&self, _: DeleteLinkInput) -> ExternResult<ActionHash> {
         Self::err()
     }
+    fn delete_links(&self, _: Vec<DeleteLinkInput>) -> ExternResult<Vec<ActionHash>> {
+        Self::err()
+    }
+    fn ensure_link(&self, _: CreateLinkInput) -> ExternResult<ActionHash> {
+        Self::err()
+    }
     fn get_links(&self, _: Vec<GetLinksInput>) -> ExternResult<Vec<Vec<Link>>> {
         Self::err()
     }
     fn get_link_details(&self, _: Vec<GetLinksInput>) -> ExternResult<Vec<LinkDetails>> {
         Self::err()
     }
+    fn get_links_delta(&self, _: Vec<GetLinksInput>) -> ExternResult<Vec<LinksDelta>> {
+        Self::err()
+    }
     fn count_links(&self, _: LinkQuery) -> ExternResult<usize> {
         Self::err()
     }
@@ -380,6 +489,12 @@ impl HdkT for ErrHdk {
     fn send_remote_signal(&self, _: RemoteSignal) -> ExternResult<()> {
         Self::err()
     }
+    fn remote_query(&self, _: RemoteQuery) -> ExternResult<ZomeCallResponse> {
+        Self::err()
+    }
+    fn call_remote_durable(&self, _: DurableCallRemote) -> ExternResult<DurableCallId> {
+        Self::err()
+    }
     // Random
     fn random_bytes(&self, _: u32) -> ExternResult<Bytes> {
         Self::err()
@@ -388,6 +503,9 @@ impl HdkT for ErrHdk {
     fn sys_time(&self, _: ()) -> ExternResult<Timestamp> {
         Self::err()
     }
+    fn network_time(&self, _: ()) -> ExternResult<NetworkTime> {
+        Self::err()
+    }
     fn schedule(&self, _: String) -> ExternResult<()> {
         Self::err()
     }
@@ -469,6 +587,28 @@ impl HdkT for ErrHdk {
     fn open_chain(&self, _input: OpenChainInput) -> ExternResult<ActionHash> {
         Self::err()
     }
+
+    // Membrane proof
+    fn renew_membrane_proof(&self, _input: RenewMembraneProofInput) -> ExternResult<ActionHash> {
+        Self::err()
+    }
+
+    // Blobs
+    fn create_blob_chunk(&self, _input: BlobChunk) -> ExternResult<EntryHash> {
+        Self::err()
+    }
+
+    fn get_blob_chunk(&self, _input: EntryHash) -> ExternResult<Option<BlobChunk>> {
+        Self::err()
+    }
+
+    fn hold_blob_chunks(&self, _input: HoldBlobChunksInput) -> ExternResult<()> {
+        Self::err()
+    }
+
+    fn release_blob_chunks(&self, _input: ActionHash) -> ExternResult<()> {
+        Self::err()
+    }
 }
 
 /// The HDK implemented as externs provided by the host.
@@ -550,6 +690,15 @@ impl HdkT for HostHdk {
             get_agent_activity_input,
         )
     }
+    fn get_action_by_seq(
+        &self,
+        get_action_by_seq_input: GetActionBySeqInput,
+    ) -> ExternResult<Option<SignedActionHashed>> {
+        host_call::<GetActionBySeqInput, Option<SignedActionHashed>>(
+            __hc__get_action_by_seq_1,
+            get_action_by_seq_input,
+        )
+    }
     fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>> {
         host_call::<ChainQueryFilter, Vec<Record>>(__hc__query_1, filter)
     }
@@ -562,6 +711,9 @@ impl HdkT for HostHdk {
     fn create(&self, create_input: CreateInput) -> ExternResult<ActionHash> {
         host_call::<CreateInput, ActionHash>(__hc__create_1, create_input)
     }
+    fn ensure_entry(&self, create_input: CreateInput) -> ExternResult<ActionHash> {
+        host_call::<CreateInput, ActionHash>(__hc__ensure_entry_1, create_input)
+    }
     fn update(&self, update_input: UpdateInput) -> ExternResult<ActionHash> {
         host_call::<UpdateInput, ActionHash>(__hc__update_1, update_input)
     }
@@ -574,6 +726,18 @@ impl HdkT for HostHdk {
     fn get_details(&self, get_inputs: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>> {
         host_call::<Vec<GetInput>, Vec<Option<Details>>>(__hc__get_details_1, get_inputs)
     }
+    fn get_dual_sign_acceptance(&self, get_input: GetInput) -> ExternResult<Option<Record>> {
+        host_call::<GetInput, Option<Record>>(__hc__get_dual_sign_acceptance_1, get_input)
+    }
+    fn get_entries_by_author(
+        &self,
+        get_entries_by_author_input: GetEntriesByAuthorInput,
+    ) -> ExternResult<Vec<Record>> {
+        host_call::<GetEntriesByAuthorInput, Vec<Record>>(
+            __hc__get_entries_by_author_1,
+            get_entries_by_author_input,
+        )
+    }
     // CounterSigning
     fn accept_countersigning_preflight_request(
         &self,
@@ -585,17 +749,35 @@ impl HdkT for HostHdk {
         )
     }
     fn agent_info(&self, _: ()) -> ExternResult<AgentInfo> {
-        host_call::<(), AgentInfo>(__hc__agent_info_1, ())
+        host_call::<(), AgentInfo>(__hc__agent_info_2, ())
     }
     fn call_info(&self, _: ()) -> ExternResult<CallInfo> {
         host_call::<(), CallInfo>(__hc__call_info_1, ())
     }
+    fn entry_type_metrics(&self, _: ()) -> ExternResult<Vec<EntryTypeMetrics>> {
+        host_call::<(), Vec<EntryTypeMetrics>>(__hc__entry_type_metrics_1, ())
+    }
     fn create_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash> {
         host_call::<CreateLinkInput, ActionHash>(__hc__create_link_1, create_link_input)
     }
+    fn create_links(
+        &self,
+        create_link_inputs: Vec<CreateLinkInput>,
+    ) -> ExternResult<Vec<ActionHash>> {
+        host_call::<Vec<CreateLinkInput>, Vec<ActionHash>>(__hc__create_links_1, create_link_inputs)
+    }
     fn delete_link(&self, delete_link_input: DeleteLinkInput) -> ExternResult<ActionHash> {
         host_call::<DeleteLinkInput, ActionHash>(__hc__delete_link_1, delete_link_input)
     }
+    fn delete_links(
+        &self,
+        delete_link_inputs: Vec<DeleteLinkInput>,
+    ) -> ExternResult<Vec<ActionHash>> {
+        host_call::<Vec<DeleteLinkInput>, Vec<ActionHash>>(__hc__delete_links_1, delete_link_inputs)
+    }
+    fn ensure_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash> {
+        host_call::<CreateLinkInput, ActionHash>(__hc__ensure_link_1, create_link_input)
+    }
     fn get_links(&self, get_links_input: Vec<GetLinksInput>) -> ExternResult<Vec<Vec<Link>>> {
         host_call::<Vec<GetLinksInput>, Vec<Vec<Link>>>(__hc__get_links_1, get_links_input)
     }
@@ -605,6 +787,12 @@ impl HdkT for HostHdk {
     ) -> ExternResult<Vec<LinkDetails>> {
         host_call::<Vec<GetLinksInput>, Vec<LinkDetails>>(__hc__get_link_details_1, get_links_input)
     }
+    fn get_links_delta(
+        &self,
+        get_links_input: Vec<GetLinksInput>,
+    ) -> ExternResult<Vec<LinksDelta>> {
+        host_call::<Vec<GetLinksInput>, Vec<LinksDelta>>(__hc__get_links_delta_1, get_links_input)
+    }
     fn count_links(&self, query: LinkQuery) -> ExternResult<usize> {
         host_call::<LinkQuery, usize>(__hc__count_links_1, query)
     }
@@ -623,12 +811,27 @@ impl HdkT for HostHdk {
     fn send_remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()> {
         host_call::<RemoteSignal, ()>(__hc__send_remote_signal_1, remote_signal)
     }
+    fn remote_query(&self, remote_query: RemoteQuery) -> ExternResult<ZomeCallResponse> {
+        host_call::<RemoteQuery, ZomeCallResponse>(__hc__remote_query_1, remote_query)
+    }
+    fn call_remote_durable(
+        &self,
+        durable_call_remote: DurableCallRemote,
+    ) -> ExternResult<DurableCallId> {
+        host_call::<DurableCallRemote, DurableCallId>(
+            __hc__call_remote_durable_1,
+            durable_call_remote,
+        )
+    }
     fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes> {
         host_call::<u32, Bytes>(__hc__random_bytes_1, number_of_bytes)
     }
     fn sys_time(&self, _: ()) -> ExternResult<Timestamp> {
         host_call::<(), Timestamp>(__hc__sys_time_1, ())
     }
+    fn network_time(&self, _: ()) -> ExternResult<NetworkTime> {
+        host_call::<(), NetworkTime>(__hc__network_time_1, ())
+    }
     fn schedule(&self, scheduled_fn: String) -> ExternResult<()> {
         host_call::<String, ()>(__hc__schedule_1, scheduled_fn)
     }
@@ -723,6 +926,26 @@ impl HdkT for HostHdk {
     fn open_chain(&self, input: OpenChainInput) -> ExternResult<ActionHash> {
         host_call::<OpenChainInput, ActionHash>(__hc__open_chain_1, input)
     }
+
+    fn renew_membrane_proof(&self, input: RenewMembraneProofInput) -> ExternResult<ActionHash> {
+        host_call::<RenewMembraneProofInput, ActionHash>(__hc__renew_membrane_proof_1, input)
+    }
+
+    fn create_blob_chunk(&self, input: BlobChunk) -> ExternResult<EntryHash> {
+        host_call::<BlobChunk, EntryHash>(__hc__create_blob_chunk_1, input)
+    }
+
+    fn get_blob_chunk(&self, input: EntryHash) -> ExternResult<Option<BlobChunk>> {
+        host_call::<EntryHash, Option<BlobChunk>>(__hc__get_blob_chunk_1, input)
+    }
+
+    fn hold_blob_chunks(&self, input: HoldBlobChunksInput) -> ExternResult<()> {
+        host_call::<HoldBlobChunksInput, ()>(__hc__hold_blob_chunks_1, input)
+    }
+
+    fn release_blob_chunks(&self, input: ActionHash) -> ExternResult<()> {
+        host_call::<ActionHash, ()>(__hc__release_blob_chunks_1, input)
+    }
 }
 
 /// At any time the global HDK can be set to a different hdk.
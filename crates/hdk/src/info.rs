@@ -9,7 +9,8 @@ pub use hdi::info::*;
 /// ```
 ///
 /// the [AgentInfo] is the current agent's original pubkey/address that they joined the network with
-/// and their most recent pubkey/address.
+/// and their most recent pubkey/address, along with the current chain head and length and the
+/// DHT arc the agent is currently storing data for.
 pub fn agent_info() -> ExternResult<AgentInfo> {
     HDK.with(|h| h.borrow().agent_info(()))
 }
@@ -26,3 +27,24 @@ pub fn agent_info() -> ExternResult<AgentInfo> {
 pub fn call_info() -> ExternResult<CallInfo> {
     HDK.with(|h| h.borrow().call_info(()))
 }
+
+/// Get the counts of the creates, updates and deletes of each app entry type of
+/// this DNA which passed app validation on this conductor, and of the actions which
+/// failed it, since the conductor started.
+///
+/// Only the actions this conductor validates as an authority are counted, which are
+/// those in the storage arcs of its agents, not every action on the DHT. The counts
+/// are only held in memory and start again from zero when the conductor restarts.
+///
+/// Deletes are counted against the entry type of the entry they delete.
+/// Only entry types which have had any actions are included.
+///
+/// The counts depend on what the conductor happens to have validated, so this can't
+/// be called from validation or other deterministic callbacks.
+///
+/// ```ignore
+/// let metrics = entry_type_metrics()?;
+/// ```
+pub fn entry_type_metrics() -> ExternResult<Vec<EntryTypeMetrics>> {
+    HDK.with(|h| h.borrow().entry_type_metrics(()))
+}
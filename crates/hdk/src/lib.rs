@@ -361,6 +361,13 @@ pub mod capability;
 /// participants, which are NOT included in this crate.
 pub mod countersigning;
 
+/// Dual signed entries let two agents agree on an app entry without a countersigning session.
+///
+/// One agent proposes the entry to a counterparty, who accepts it whenever they are
+/// next online by committing an update of the proposal. System validation only accepts
+/// an acceptance authored by the counterparty the proposal names, for the same app entry.
+pub mod dual_signing;
+
 /// Working with app and system entries.
 ///
 /// Most Holochain applications will define their own app entry types.
@@ -581,3 +588,8 @@ pub mod clone;
 
 /// Tools for working with migrations from one DNA to another.
 mod migrate;
+
+/// Create, read and delete large payloads stored as blobs of content addressed chunks.
+///
+/// See [`hdi::blob`] for the entry type a zome needs to store blobs.
+pub mod blob;
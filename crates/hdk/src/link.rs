@@ -84,6 +84,78 @@ where
     })
 }
 
+/// Create a link unless this agent has already created an identical one.
+///
+/// Takes the same arguments as [ `create_link` ]. If a `CreateLink` with the same base, target,
+/// link type and tag is already on this agent's source chain and hasn't been deleted, no new
+/// record is written and the existing action hash is returned. Writes made earlier in the same
+/// zome call are checked too.
+///
+/// As with [ `create_link` ] the write uses [ `ChainTopOrdering::Strict` ], so if two zome calls
+/// try to create the same link at the same time, one of them fails with a `HeadMoved` error
+/// and, when retried, returns the other's action hash.
+pub fn ensure_link<T, E>(
+    base_address: impl Into<AnyLinkableHash>,
+    target_address: impl Into<AnyLinkableHash>,
+    link_type: T,
+    tag: impl Into<LinkTag>,
+) -> ExternResult<ActionHash>
+where
+    ScopedLinkType: TryFrom<T, Error = E>,
+    WasmError: From<E>,
+{
+    let ScopedLinkType {
+        zome_index,
+        zome_type: link_type,
+    } = link_type.try_into()?;
+    HDK.with(|h| {
+        h.borrow().ensure_link(CreateLinkInput::new(
+            base_address.into(),
+            target_address.into(),
+            zome_index,
+            link_type,
+            tag.into(),
+            ChainTopOrdering::default(),
+        ))
+    })
+}
+
+/// Create many links in one call, e.g. when rebuilding an index.
+///
+/// Each item is the base, target, link type and tag of one link, as for [ `create_link` ].
+/// All the links are written to the source chain and signed in a single pass, which is much
+/// faster than calling [ `create_link` ] once per link.
+/// The action hashes of the new links are returned in the same order as the items.
+pub fn create_links<B, T, L, G, E>(
+    links: impl IntoIterator<Item = (B, T, L, G)>,
+) -> ExternResult<Vec<ActionHash>>
+where
+    B: Into<AnyLinkableHash>,
+    T: Into<AnyLinkableHash>,
+    G: Into<LinkTag>,
+    ScopedLinkType: TryFrom<L, Error = E>,
+    WasmError: From<E>,
+{
+    let inputs = links
+        .into_iter()
+        .map(|(base_address, target_address, link_type, tag)| {
+            let ScopedLinkType {
+                zome_index,
+                zome_type: link_type,
+            } = link_type.try_into()?;
+            Ok(CreateLinkInput::new(
+                base_address.into(),
+                target_address.into(),
+                zome_index,
+                link_type,
+                tag.into(),
+                ChainTopOrdering::default(),
+            ))
+        })
+        .collect::<ExternResult<Vec<_>>>()?;
+    HDK.with(|h| h.borrow().create_links(inputs))
+}
+
 /// Delete a specific link creation record.
 ///
 /// Links are defined by a [OR-Set CRDT](https://en.wikipedia.org/wiki/Conflict-free_replicated_data_type#OR-Set_(Observed-Remove_Set))
@@ -114,6 +186,24 @@ pub fn delete_link(address: ActionHash) -> ExternResult<ActionHash> {
     })
 }
 
+/// Delete many link creation records in one call.
+///
+/// As for [ `create_links` ], all the deletes are written to the source chain and signed
+/// in a single pass. See [ `delete_link` ] for how deleting links works.
+/// The action hashes of the deletes are returned in the same order as the addresses.
+pub fn delete_links(
+    addresses: impl IntoIterator<Item = ActionHash>,
+) -> ExternResult<Vec<ActionHash>> {
+    HDK.with(|h| {
+        h.borrow().delete_links(
+            addresses
+                .into_iter()
+                .map(|address| DeleteLinkInput::new(address, ChainTopOrdering::default()))
+                .collect(),
+        )
+    })
+}
+
 /// Returns all links that reference a base hash, filtered by link type and other criteria.
 /// Use a [ `GetLinksInputBuilder` ] to create the [ `GetLinksInput` ] and optionally filter links further.
 ///
@@ -184,7 +274,7 @@ pub fn get_link_details(
             if let Some(link_tag) = link_tag {
                 input = input.tag_prefix(link_tag);
             }
-            input = input.get_options(get_options.strategy);
+            input = input.read_source(get_options.read_source());
             h.borrow().get_link_details(vec![input.build()])
         })?
         .into_iter()
@@ -192,6 +282,35 @@ pub fn get_link_details(
         .unwrap())
 }
 
+/// Get the links created and deleted at or after a watermark, for keeping a copy of
+/// a collection of links up to date without fetching all of it again.
+///
+/// The links are filtered like in [ `get_links` ]. Only the link ops which were integrated
+/// since [ `GetLinksInputBuilder::since` ] are read and fetched from authorities, or all
+/// of them if it isn't set. Pass the returned [ `LinksDelta::watermark` ] as `since` to get
+/// the next delta.
+///
+/// e.g.
+/// ```ignore
+/// let delta = get_links_delta(
+///     GetLinksInputBuilder::try_new(base, LinkTypes::Post)?
+///         .since(watermark)
+///         .build(),
+/// )?;
+/// posts.retain(|link| !delta.deleted.contains(&link.create_link_hash));
+/// posts.extend(delta.created);
+/// watermark = delta.watermark;
+/// ```
+///
+/// See [ `get_links` ].
+pub fn get_links_delta(input: GetLinksInput) -> ExternResult<LinksDelta> {
+    Ok(HDK
+        .with(|h| h.borrow().get_links_delta(vec![input]))?
+        .into_iter()
+        .next()
+        .unwrap())
+}
+
 pub fn count_links(query: LinkQuery) -> ExternResult<usize> {
     HDK.with(|h| h.borrow().count_links(query))
 }
@@ -56,15 +56,29 @@ impl GetLinksInputBuilder {
             link_type: link_type.try_into_filter()?,
             get_options: GetOptions::default(),
             tag_prefix: None,
+            tag_range: None,
+            tag_fields: Vec::new(),
             before: None,
             after: None,
             author: None,
+            order: LinkOrder::default(),
+            since: None,
         }))
     }
 
     /// Fetch links from network or local only.
     pub fn get_options(mut self, get_strategy: GetStrategy) -> Self {
-        self.0.get_options.strategy = get_strategy;
+        self.0.get_options = match get_strategy {
+            GetStrategy::Network => GetOptions::network(),
+            GetStrategy::Local => GetOptions::local(),
+        };
+        self
+    }
+
+    /// Set where links are read from, e.g. from the cache before going to
+    /// the network.
+    pub fn read_source(mut self, read_source: ReadSource) -> Self {
+        self.0.get_options = read_source.into();
         self
     }
 
@@ -74,6 +88,22 @@ impl GetLinksInputBuilder {
         self
     }
 
+    /// Filter for links with a tag from `start` inclusive to `end` exclusive.
+    ///
+    /// Tags are compared byte by byte, so numbers used as tags should be
+    /// encoded big-endian for the range to match their numeric order.
+    pub fn tag_range(mut self, start: LinkTag, end: LinkTag) -> Self {
+        self.0.tag_range = Some((start, end));
+        self
+    }
+
+    /// Filter for links whose tag carries a [`LinkTagPayload`] matching `filter`.
+    /// Can be called more than once, and links must match every filter.
+    pub fn tag_field(mut self, filter: LinkTagFieldFilter) -> Self {
+        self.0.tag_fields.push(filter);
+        self
+    }
+
     /// Filter for links created before `before`.
     pub fn before(mut self, before: Timestamp) -> Self {
         self.0.before = Some(before);
@@ -92,6 +122,19 @@ impl GetLinksInputBuilder {
         self
     }
 
+    /// Return the links newest first, or oldest first which is the default.
+    pub fn order(mut self, order: LinkOrder) -> Self {
+        self.0.order = order;
+        self
+    }
+
+    /// Only get the links created or deleted since `since`, a watermark returned
+    /// by a previous [`get_links_delta`](crate::prelude::get_links_delta).
+    pub fn since(mut self, since: Timestamp) -> Self {
+        self.0.since = Some(since);
+        self
+    }
+
     /// Construct the result of the builder.
     pub fn build(self) -> GetLinksInput {
         self.0
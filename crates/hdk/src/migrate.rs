@@ -1,12 +1,11 @@
 use crate::hdk::HDK;
-use crate::prelude::CloseChainInput;
-use hdi::prelude::{ExternResult, OpenChainInput};
-use holo_hash::{ActionHash, DnaHash};
+use crate::prelude::*;
 
 /// Close your current source chain to indicate that you are planning to migrate to a new DNA.
 ///
 /// This must be the last entry you try to make in your source chain. Holochain's sytem validation
-/// will reject any actions that come after this one.
+/// will reject any actions that come after this one, and will reject the close if `new_dna_hash`
+/// is the hash of the DNA it is committed in.
 pub fn close_chain(new_dna_hash: DnaHash) -> ExternResult<ActionHash> {
     HDK.with(|h| h.borrow().close_chain(CloseChainInput { new_dna_hash }))
 }
@@ -17,6 +16,27 @@ pub fn close_chain(new_dna_hash: DnaHash) -> ExternResult<ActionHash> {
 /// Holochain does not enforce an order for this action, or even that you must use it at all. It is
 /// the only way that your app validation rules can know which DNA you have migrated from. So if
 /// your app needs to know this to validate imported data then you will need to call this function.
+/// Holochain's system validation will reject the open if `prev_dna_hash` is the hash of the DNA it
+/// is committed in.
 pub fn open_chain(prev_dna_hash: DnaHash) -> ExternResult<ActionHash> {
     HDK.with(|h| h.borrow().open_chain(OpenChainInput { prev_dna_hash }))
 }
+
+/// Get the DNA that an agent's chain in this DNA was migrated to, if the agent has closed it.
+///
+/// A [`CloseChain`] action is always the last action of a chain, so only the head of the agent's
+/// chain is fetched. Returns `None` if the head is not a [`CloseChain`] action, or if the agent has
+/// forked their chain at its head.
+pub fn get_migrated_to(agent: AgentPubKey) -> ExternResult<Option<DnaHash>> {
+    let activity = get_agent_activity(agent, ChainQueryFilter::new(), ActivityRequest::Status)?;
+    let head = match activity.highest_observed {
+        Some(HighestObserved { hash, .. }) if hash.len() == 1 => hash[0].clone(),
+        _ => return Ok(None),
+    };
+    Ok(
+        get(head, GetOptions::default())?.and_then(|record| match record.action() {
+            Action::CloseChain(close) => Some(close.new_dna_hash.clone()),
+            _ => None,
+        }),
+    )
+}
@@ -95,6 +95,102 @@ where
         .unwrap())
 }
 
+/// Queue a call to a zome function of a remote agent which may not be online
+/// right now.
+///
+/// Unlike [ `call_remote` ], this doesn't wait for a response. The conductor
+/// persists the call and keeps retrying it, backing off between attempts, until
+/// the remote agent responds or `ttl` runs out, even across restarts of the
+/// conductor. This suits agents on devices which are only online now and then.
+///
+/// The response is delivered to the app of the calling cell as a
+/// [ `SystemSignal::DurableCallRemoteDelivered` ] carrying the returned
+/// [ `DurableCallId` ] and the [ `ZomeCallResponse` ]. If the call is given up
+/// on, a [ `SystemSignal::DurableCallRemoteExpired` ] is sent instead, or a
+/// [ `SystemSignal::DurableCallRemoteBlocked` ] if the egress policy of the
+/// calling cell doesn't allow the call. Responses other than network errors,
+/// such as [ `ZomeCallResponse::Unauthorized` ], are delivered rather than retried.
+///
+/// The call is only queued once the writes of the calling zome function have
+/// been committed, so nothing is sent if the zome call fails.
+///
+/// Calls are delivered at least once: the call may reach the remote agent more
+/// than once, if a response gets lost or the conductor stops before it arrives,
+/// so the remote function should be idempotent.
+///
+/// ```ignore
+/// ...
+/// let call_id = call_remote_durable(
+///     bob,
+///     "foo_zome",
+///     "do_it".into(),
+///     secret,
+///     serializable_payload,
+///     std::time::Duration::from_secs(24 * 60 * 60),
+/// )?;
+/// ...
+/// ```
+pub fn call_remote_durable<I, Z>(
+    agent: AgentPubKey,
+    zome: Z,
+    fn_name: FunctionName,
+    cap_secret: Option<CapSecret>,
+    payload: I,
+    ttl: std::time::Duration,
+) -> ExternResult<DurableCallId>
+where
+    I: serde::Serialize + std::fmt::Debug,
+    Z: Into<ZomeName>,
+{
+    HDK.with(|h| {
+        h.borrow().call_remote_durable(DurableCallRemote::new(
+            agent,
+            zome.into(),
+            fn_name,
+            cap_secret,
+            ExternIO::encode(payload).map_err(|e| wasm_error!(e))?,
+            ttl,
+        ))
+    })
+}
+
+/// Call a read-only zome function on a remote agent's source chain.
+///
+/// Unlike [ `call_remote` ], the remote agent must have granted the function
+/// with [ `GrantedFunctions::Queries` ], and a grant for regular calls is not
+/// enough. The remote function runs without access to host functions that
+/// write to the source chain or the network, so any attempt to do so fails
+/// the query.
+///
+/// The responses are the same as for [ `call_remote` ].
+///
+/// ```ignore
+/// ...
+/// let foo: Foo = remote_query(bob, "foo_zome", "read_it", secret, serializable_payload)?;
+/// ...
+/// ```
+pub fn remote_query<I, Z>(
+    agent: AgentPubKey,
+    zome: Z,
+    fn_name: FunctionName,
+    cap_secret: Option<CapSecret>,
+    payload: I,
+) -> ExternResult<ZomeCallResponse>
+where
+    I: serde::Serialize + std::fmt::Debug,
+    Z: Into<ZomeName>,
+{
+    HDK.with(|h| {
+        h.borrow().remote_query(RemoteQuery::new(
+            agent,
+            zome.into(),
+            fn_name,
+            cap_secret,
+            ExternIO::encode(payload).map_err(|e| wasm_error!(e))?,
+        ))
+    })
+}
+
 /// Emit an app-defined Signal.
 ///
 /// Only clients who have subscribed to signals from this Cell with the proper
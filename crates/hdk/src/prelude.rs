@@ -1,13 +1,30 @@
+pub use crate::blob::create_blob;
+pub use crate::blob::create_blob_chunk;
+pub use crate::blob::delete_blob;
+pub use crate::blob::get_blob_chunk;
+pub use crate::blob::get_blob_manifest;
+pub use crate::blob::hold_blob_chunks;
+pub use crate::blob::read_blob;
+pub use crate::blob::read_blob_stream;
+pub use crate::blob::release_blob_chunks;
+pub use crate::blob::BlobStream;
 pub use crate::capability::create_cap_claim;
 pub use crate::capability::create_cap_grant;
 pub use crate::capability::delete_cap_grant;
 pub use crate::capability::generate_cap_secret;
 pub use crate::capability::update_cap_grant;
+pub use crate::chain::current_membrane_proof;
+pub use crate::chain::get_action_by_seq;
 pub use crate::chain::get_agent_activity;
+pub use crate::chain::get_agent_activity_from;
 pub use crate::chain::must_get_agent_activity;
 pub use crate::chain::query;
+pub use crate::chain::renew_membrane_proof;
 pub use crate::countersigning::accept_countersigning_preflight_request;
 pub use crate::countersigning::session_times_from_millis;
+pub use crate::dual_signing::accept_dual_signed_entry;
+pub use crate::dual_signing::get_dual_signed_acceptance;
+pub use crate::dual_signing::propose_dual_signed_entry;
 pub use crate::ed25519::sign;
 pub use crate::ed25519::sign_ephemeral;
 pub use crate::ed25519::sign_ephemeral_raw;
@@ -18,8 +35,10 @@ pub use crate::entry::create;
 pub use crate::entry::create_entry;
 pub use crate::entry::delete;
 pub use crate::entry::delete_entry;
+pub use crate::entry::ensure_entry;
 pub use crate::entry::get;
 pub use crate::entry::get_details;
+pub use crate::entry::get_entries_by_author;
 pub use crate::entry::must_get_action;
 pub use crate::entry::must_get_entry;
 pub use crate::entry::must_get_valid_record;
@@ -38,21 +57,29 @@ pub use crate::hdk::*;
 pub use crate::info::agent_info;
 pub use crate::info::call_info;
 pub use crate::info::dna_info;
+pub use crate::info::entry_type_metrics;
 pub use crate::info::zome_info;
 pub use crate::link::count_links;
 pub use crate::link::create_link;
+pub use crate::link::create_links;
 pub use crate::link::delete_link;
+pub use crate::link::delete_links;
+pub use crate::link::ensure_link;
 pub use crate::link::get_link_details;
 pub use crate::link::get_links;
+pub use crate::link::get_links_delta;
 pub use crate::link::GetLinksInputBuilder;
 pub use crate::link::LinkTypeFilterExt;
 pub use crate::map_extern;
 pub use crate::map_extern::ExternResult;
 pub use crate::p2p::call;
 pub use crate::p2p::call_remote;
+pub use crate::p2p::call_remote_durable;
 pub use crate::p2p::emit_signal;
+pub use crate::p2p::remote_query;
 pub use crate::p2p::send_remote_signal;
 pub use crate::random::*;
+pub use crate::time::network_time;
 pub use crate::time::schedule;
 pub use crate::time::sleep;
 pub use crate::time::sys_time;
@@ -73,11 +100,15 @@ pub use hdi;
 pub use hdi::map_extern_infallible;
 pub use hdi::op::OpHelper;
 pub use hdi::prelude::app_entry;
+pub use hdi::prelude::must_be_base_author;
+pub use hdi::prelude::validate_blob_manifest;
 pub use hdi::prelude::Anchor;
+pub use hdi::prelude::BlobManifest;
 pub use hdi::prelude::Component;
 pub use hdi::prelude::Path;
 pub use hdi::prelude::ShardStrategy;
 pub use hdi::prelude::TypedPath;
+pub use hdi::prelude::VersionedEntry;
 pub use hdk_derive;
 pub use hdk_derive::dna_properties;
 pub use hdk_derive::hdk_dependent_entry_types;
@@ -85,9 +116,11 @@ pub use hdk_derive::hdk_dependent_link_types;
 pub use hdk_derive::hdk_entry_helper;
 pub use hdk_derive::hdk_entry_types;
 pub use hdk_derive::hdk_entry_types_conversions;
+pub use hdk_derive::hdk_entry_versioned;
 pub use hdk_derive::hdk_extern;
 pub use hdk_derive::hdk_link_types;
 pub use hdk_derive::hdk_to_coordinates;
+pub use hdk_derive::hdk_validation_dispatch;
 pub use hdk_derive::EntryDefRegistration;
 pub use hdk_derive::UnitEnum;
 pub use holo_hash;
@@ -98,9 +131,11 @@ pub use holo_hash::AnyLinkableHash;
 pub use holo_hash::EntryHash;
 pub use holo_hash::EntryHashes;
 pub use holo_hash::ExternalHash;
+pub use holo_hash::ExternalNamespace;
 pub use holo_hash::HasHash;
 pub use holo_hash::HoloHash;
 pub use holo_hash::HoloHashed;
+pub use holo_hash::NamespacedExternalHash;
 pub use holochain_wasmer_guest::*;
 pub use holochain_zome_types;
 pub use holochain_zome_types::prelude::*;
@@ -132,16 +167,23 @@ macro_rules! holochain_externs {
             dna_info:1,
             random_bytes:1,
             sys_time:1,
+            network_time:1,
+            entry_type_metrics:1,
             agent_info:1,
+            agent_info:2,
             capability_claims:1,
             capability_grants:1,
             capability_info:1,
             get:1,
             get_details:1,
+            get_dual_sign_acceptance:1,
+            get_entries_by_author:1,
             get_links:1,
             get_link_details:1,
+            get_links_delta:1,
             count_links:1,
             get_agent_activity:1,
+            get_action_by_seq:1,
             must_get_entry:1,
             must_get_valid_record:1,
             must_get_action:1,
@@ -154,8 +196,14 @@ macro_rules! holochain_externs {
             create:1,
             emit_signal:1,
             send_remote_signal:1,
+            remote_query:1,
+            call_remote_durable:1,
             create_link:1,
+            create_links:1,
             delete_link:1,
+            delete_links:1,
+            ensure_entry:1,
+            ensure_link:1,
             update:1,
             delete:1,
             schedule:1,
@@ -175,7 +223,12 @@ macro_rules! holochain_externs {
             enable_clone_cell:1,
             delete_clone_cell:1,
             close_chain:1,
-            open_chain:1
+            open_chain:1,
+            renew_membrane_proof:1,
+            create_blob_chunk:1,
+            get_blob_chunk:1,
+            hold_blob_chunks:1,
+            release_blob_chunks:1
         );
     };
 }
@@ -65,6 +65,27 @@ pub fn sys_time() -> ExternResult<Timestamp> {
     HDK.with(|h| h.borrow().sys_time(()))
 }
 
+/// Current system time from the host, along with an estimate of how far the
+/// clocks of the network are from it.
+///
+/// The host measures the clocks of the peers it talks to for this DNA. The estimate
+/// is the median offset of those peers, with an uncertainty that grows with the
+/// round trip times of the measurements and how much the peers disagree.
+/// The offset is `None` until some peers have been measured, e.g. when offline.
+///
+/// ```ignore
+/// let time = network_time()?;
+/// let (earliest, latest) = time.network_now_bounds();
+/// ```
+///
+/// This is useful when an app needs to pick times that other agents will accept,
+/// for example the start of a countersigning session. As with [`sys_time`] the
+/// estimate comes from the host and is not suitable for validation, and a majority
+/// of peers can agree on any time they like.
+pub fn network_time() -> ExternResult<NetworkTime> {
+    HDK.with(|h| h.borrow().network_time(()))
+}
+
 /// Adds a function from the current zome to the scheduler.
 ///
 /// Any schedulable function from the current zome can be added to the scheduler
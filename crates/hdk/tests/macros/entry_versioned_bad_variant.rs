@@ -0,0 +1,12 @@
+use hdk::prelude::*;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PostV1(pub String);
+
+#[hdk_entry_versioned]
+pub enum Post {
+    V1(PostV1),
+    V2 { body: String },
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use hdk::prelude::*;
+
+#[hdk_validation_dispatch]
+#[hdk_link_types(skip_no_mangle = true)]
+pub enum LinkTypes {
+    Handled,
+    Missing,
+}
+
+fn validate_create_link_handled(_: CreateLink) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_delete_link_handled(_: DeleteLink, _: CreateLink) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_create_link_missing(_: CreateLink) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn main() {}
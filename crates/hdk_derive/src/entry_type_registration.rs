@@ -19,6 +19,10 @@ struct VarOpts {
     required_validations: Option<u8>,
     #[darling(default)]
     cache_at_agent_activity: Option<bool>,
+    #[darling(default)]
+    max_size: Option<u32>,
+    #[darling(default)]
+    purge_after_secs: Option<u64>,
 }
 
 #[derive(FromDeriveInput)]
@@ -45,6 +49,8 @@ pub fn derive(input: TokenStream) -> TokenStream {
                      visibility,
                      required_validations,
                      cache_at_agent_activity,
+                     max_size,
+                     purge_after_secs,
                      ..
                  }| {
                     let id = crate::util::to_snake_case(name, &v_ident);
@@ -52,12 +58,24 @@ pub fn derive(input: TokenStream) -> TokenStream {
                     let required_validations =
                         required_validations.unwrap_or_else(|| RequiredValidations::default().0);
                     let cache_at_agent_activity = cache_at_agent_activity.unwrap_or(false);
+                    let max_size = match max_size {
+                        Some(bytes) => quote::quote! {Some(#bytes)},
+                        None => quote::quote! {None},
+                    };
+                    let purge = match purge_after_secs {
+                        Some(secs) => quote::quote! {Some(PurgePolicy::after_secs(#secs))},
+                        None => quote::quote! {None},
+                    };
                     quote::quote! {
                         EntryDef {
                             id: EntryDefId::App(AppEntryName::from_str(#id)),
                             visibility: #visibility,
-                            required_validations: RequiredValidations(#required_validations),
                             cache_at_agent_activity: #cache_at_agent_activity,
+                            policies: EntryDefPolicies {
+                                required_validations: RequiredValidations(#required_validations),
+                                max_size: #max_size,
+                                purge: #purge,
+                            },
                         },
                     }
                 },
@@ -85,7 +103,11 @@ fn parse_visibility(ident: &syn::Ident, variant: Option<String>) -> proc_macro2:
     match variant.as_str() {
         "public" => quote::quote! {EntryVisibility::Public},
         "private" => quote::quote! {EntryVisibility::Private},
-        _ => abort!(ident, "EntryVisibility can only be `public` or `private`"),
+        "unlisted" => quote::quote! {EntryVisibility::Unlisted},
+        _ => abort!(
+            ident,
+            "EntryVisibility can only be `public`, `private` or `unlisted`"
+        ),
     }
 }
 
@@ -93,5 +115,6 @@ fn default_visibility() -> proc_macro2::TokenStream {
     match EntryVisibility::default() {
         EntryVisibility::Public => quote::quote! {EntryVisibility::Public},
         EntryVisibility::Private => quote::quote! {EntryVisibility::Private},
+        EntryVisibility::Unlisted => quote::quote! {EntryVisibility::Unlisted},
     }
 }
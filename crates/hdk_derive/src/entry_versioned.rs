@@ -0,0 +1,151 @@
+use proc_macro::TokenStream;
+use proc_macro_error::abort;
+use syn::parse_macro_input;
+use syn::Item;
+use syn::ItemEnum;
+
+pub fn build(_attrs: TokenStream, input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as Item);
+
+    // Extract the enums ident and variants.
+    let (ident, variants) = match &input {
+        Item::Enum(ItemEnum {
+            ident, variants, ..
+        }) => (ident, variants),
+        _ => abort!(input, "hdk_entry_versioned can only be used on Enums"),
+    };
+    if variants.is_empty() {
+        abort!(ident, "hdk_entry_versioned needs at least one version");
+    }
+
+    // Each variant is a version, holding the entry as it was at that version.
+    let versions = variants
+        .iter()
+        .map(|v| match &v.fields {
+            syn::Fields::Unnamed(f) if f.unnamed.len() == 1 => (&v.ident, &f.unnamed[0].ty),
+            _ => abort!(
+                v,
+                "hdk_entry_versioned variants must hold exactly one unnamed field"
+            ),
+        })
+        .collect::<Vec<_>>();
+    let (first_ident, first_ty) = versions[0];
+    let (latest_ident, latest_ty) = versions[versions.len() - 1];
+    let latest_version = versions.len() as u32;
+
+    let mut version_arms = proc_macro2::TokenStream::new();
+    let mut upgrade_arms = proc_macro2::TokenStream::new();
+    for (i, (v_ident, _)) in versions.iter().enumerate() {
+        let version = i as u32 + 1;
+        version_arms.extend(quote::quote! {#ident::#v_ident(_) => #version,});
+        // Each version is upgraded to the next one with its `From` impl.
+        match versions.get(i + 1) {
+            Some((next_ident, _)) => upgrade_arms
+                .extend(quote::quote! {#ident::#v_ident(v) => #ident::#next_ident(v.into()),}),
+            None => {
+                upgrade_arms.extend(quote::quote! {#ident::#v_ident(v) => #ident::#v_ident(v),})
+            }
+        }
+    }
+
+    let output = quote::quote! {
+        #[derive(Serialize, Deserialize, Debug)]
+        #input
+
+        impl TryFrom<&#ident> for hdi::prelude::SerializedBytes {
+            type Error = hdi::prelude::SerializedBytesError;
+            fn try_from(entry: &#ident) -> Result<Self, Self::Error> {
+                hdi::prelude::holochain_serialized_bytes::encode(entry).map(|bytes| {
+                    hdi::prelude::SerializedBytes::from(hdi::prelude::UnsafeBytes::from(bytes))
+                })
+            }
+        }
+
+        impl TryFrom<#ident> for hdi::prelude::SerializedBytes {
+            type Error = hdi::prelude::SerializedBytesError;
+            fn try_from(entry: #ident) -> Result<Self, Self::Error> {
+                Self::try_from(&entry)
+            }
+        }
+
+        // Entries committed before the type was versioned hold the first version
+        // without a version tag, so they are read as the first version.
+        impl TryFrom<hdi::prelude::SerializedBytes> for #ident {
+            type Error = hdi::prelude::SerializedBytesError;
+            fn try_from(sb: hdi::prelude::SerializedBytes) -> Result<Self, Self::Error> {
+                use hdi::prelude::holochain_serialized_bytes::decode;
+                decode(sb.bytes()).or_else(|e| {
+                    let first: #first_ty = decode(sb.bytes()).map_err(|_| e)?;
+                    Ok(#ident::#first_ident(first))
+                })
+            }
+        }
+
+        hdi::app_entry!(#ident);
+
+        impl hdi::prelude::VersionedEntry for #ident {
+            type Latest = #latest_ty;
+
+            const LATEST_VERSION: u32 = #latest_version;
+
+            fn version(&self) -> u32 {
+                match self {
+                    #version_arms
+                }
+            }
+
+            fn upgrade(self) -> Self {
+                match self {
+                    #upgrade_arms
+                }
+            }
+
+            fn into_latest(self) -> Self::Latest {
+                let mut entry = self;
+                loop {
+                    #[allow(unreachable_patterns)]
+                    match entry {
+                        #ident::#latest_ident(latest) => return latest,
+                        older => entry = <Self as hdi::prelude::VersionedEntry>::upgrade(older),
+                    }
+                }
+            }
+        }
+
+        impl From<#latest_ty> for #ident {
+            fn from(latest: #latest_ty) -> Self {
+                #ident::#latest_ident(latest)
+            }
+        }
+
+        // Entries of any version are read as the latest version.
+        impl TryFrom<&hdi::prelude::Entry> for #latest_ty {
+            type Error = hdi::prelude::WasmError;
+            fn try_from(entry: &hdi::prelude::Entry) -> Result<Self, Self::Error> {
+                Ok(hdi::prelude::VersionedEntry::into_latest(#ident::try_from(entry)?))
+            }
+        }
+
+        impl TryFrom<hdi::prelude::Entry> for #latest_ty {
+            type Error = hdi::prelude::WasmError;
+            fn try_from(entry: hdi::prelude::Entry) -> Result<Self, Self::Error> {
+                Self::try_from(&entry)
+            }
+        }
+
+        impl TryFrom<&hdi::prelude::Record> for #latest_ty {
+            type Error = hdi::prelude::WasmError;
+            fn try_from(record: &hdi::prelude::Record) -> Result<Self, Self::Error> {
+                Ok(hdi::prelude::VersionedEntry::into_latest(#ident::try_from(record)?))
+            }
+        }
+
+        impl TryFrom<hdi::prelude::Record> for #latest_ty {
+            type Error = hdi::prelude::WasmError;
+            fn try_from(record: hdi::prelude::Record) -> Result<Self, Self::Error> {
+                Self::try_from(&record)
+            }
+        }
+    };
+    output.into()
+}
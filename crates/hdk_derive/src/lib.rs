@@ -15,12 +15,14 @@ mod entry_type_registration;
 mod entry_types;
 mod entry_types_conversions;
 mod entry_types_name_registration;
+mod entry_versioned;
 mod entry_zomes;
 mod link_types;
 mod link_zomes;
 mod to_coordinates;
 mod unit_enum;
 mod util;
+mod validation_dispatch;
 
 struct EntryDef(holochain_integrity_types::entry_def::EntryDef);
 struct EntryDefId(holochain_integrity_types::entry_def::EntryDefId);
@@ -66,6 +68,9 @@ impl Parse for EntryDef {
                                 "private" => {
                                     holochain_integrity_types::entry_def::EntryVisibility::Private
                                 }
+                                "unlisted" => {
+                                    holochain_integrity_types::entry_def::EntryVisibility::Unlisted
+                                }
                                 _ => unreachable!(),
                             },
                             _ => unreachable!(),
@@ -78,8 +83,9 @@ impl Parse for EntryDef {
         Ok(EntryDef(holochain_integrity_types::entry_def::EntryDef {
             id,
             visibility,
-            required_validations,
             cache_at_agent_activity: false,
+            policies: holochain_integrity_types::entry_def::EntryDefPolicies::default()
+                .with_required_validations(required_validations),
         }))
     }
 }
@@ -113,6 +119,7 @@ impl quote::ToTokens for EntryVisibility {
             match self.0 {
                 holochain_integrity_types::entry_def::EntryVisibility::Public => "Public",
                 holochain_integrity_types::entry_def::EntryVisibility::Private => "Private",
+                holochain_integrity_types::entry_def::EntryVisibility::Unlisted => "Unlisted",
             },
             proc_macro2::Span::call_site(),
         );
@@ -142,13 +149,15 @@ impl quote::ToTokens for EntryDef {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let id = EntryDefId(self.0.id.clone());
         let visibility = EntryVisibility(self.0.visibility);
-        let required_validations = RequiredValidations(self.0.required_validations);
+        let required_validations = RequiredValidations(self.0.policies.required_validations);
 
         tokens.append_all(quote::quote! {
             hdi::prelude::EntryDef {
                 id: #id,
                 visibility: #visibility,
-                required_validations: #required_validations,
+                cache_at_agent_activity: false,
+                policies: hdi::prelude::EntryDefPolicies::default()
+                    .with_required_validations(#required_validations),
             }
         });
     }
@@ -233,6 +242,10 @@ pub fn derive_to_unit_enum(input: TokenStream) -> TokenStream {
 ///     will not be published anymore (optional). Defaults to 5.
 ///   - visibility: The visibility of this entry. [`public` | `private`].
 ///     Default is `public`.
+///   - max_size: The largest the entry may be in bytes, which the host enforces
+///     during validation (optional). Defaults to the host's maximum entry size.
+///   - purge_after_secs: Lets authorities purge the entry once it has been deleted
+///     for this many seconds (optional). Defaults to never purging.
 ///
 /// # Examples
 /// ```ignore
@@ -244,6 +257,10 @@ pub fn derive_to_unit_enum(input: TokenStream) -> TokenStream {
 ///     Msg(Msg),
 ///     #[entry_type(name = "hidden_msg", required_validations = 5, visibility = "private")]
 ///     PrivMsg(PrivMsg),
+///     #[entry_type(purge_after_secs = 86400)]
+///     Ephemeral(Ephemeral),
+///     #[entry_type(max_size = 1024)]
+///     Note(Note),
 /// }
 /// ```
 #[proc_macro_error]
@@ -259,6 +276,44 @@ pub fn hdk_link_types(attrs: TokenStream, code: TokenStream) -> TokenStream {
     link_types::build(attrs, code)
 }
 
+/// Implements validation dispatch for an `EntryTypes` or `LinkTypes` enum.
+///
+/// Each variant is validated by a function named after it, which must be in scope:
+/// - Entry types call `validate_create_<variant>(EntryCreationAction, entry)`,
+///   `validate_update_<variant>(Update, entry)` and
+///   `validate_delete_<variant>(Delete, EntryCreationAction, original_entry)`.
+/// - Link types call `validate_create_link_<variant>(CreateLink)` and
+///   `validate_delete_link_<variant>(DeleteLink, CreateLink)`.
+///
+/// Compilation fails if any of these functions is missing.
+/// Use `hdi::prelude::dispatch_validation` in the `validate` callback to call them.
+///
+/// # Examples
+/// ```ignore
+/// #[hdk_validation_dispatch]
+/// #[hdk_entry_types]
+/// #[unit_enum(UnitEntryTypes)]
+/// pub enum EntryTypes {
+///     Post(Post),
+/// }
+///
+/// #[hdk_validation_dispatch]
+/// #[hdk_link_types]
+/// pub enum LinkTypes {
+///     AllPosts,
+/// }
+///
+/// #[hdk_extern]
+/// pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+///     dispatch_validation::<EntryTypes, LinkTypes>(&op)
+/// }
+/// ```
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn hdk_validation_dispatch(attrs: TokenStream, code: TokenStream) -> TokenStream {
+    validation_dispatch::build(attrs, code)
+}
+
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn hdk_to_coordinates(attrs: TokenStream, code: TokenStream) -> TokenStream {
@@ -306,6 +361,60 @@ pub fn hdk_entry_helper(attrs: TokenStream, code: TokenStream) -> TokenStream {
     entry_helper::build(attrs, code)
 }
 
+/// Helper for entry data types whose schema changes over time.
+///
+/// Each variant of the enum is a version of the entry, from oldest to newest,
+/// holding the entry as it was at that version. The entry is serialized with its
+/// variant as a version tag, so entries committed with an old version can still
+/// be deserialized after a new version is added. Entries committed before the
+/// type was versioned have no tag and are deserialized as the first version.
+///
+/// Every version but the latest must implement `From` for the next version.
+/// Reading an entry or record as the latest version's type upgrades it through
+/// each newer version in turn.
+///
+/// # Implements
+/// - `#[derive(Serialize, Deserialize, Debug)]`
+/// - `TryFrom` between the enum and `SerializedBytes`
+/// - `hdi::app_entry!`
+/// - `hdi::prelude::VersionedEntry`
+/// - `From<Latest>` for the enum
+/// - `TryFrom<Entry>` and `TryFrom<Record>` for the latest version's type
+///
+/// # Examples
+/// ```ignore
+/// #[derive(Serialize, Deserialize, Debug)]
+/// pub struct PostV1(pub String);
+///
+/// #[derive(Serialize, Deserialize, Debug)]
+/// pub struct PostV2 {
+///     pub title: String,
+///     pub body: String,
+/// }
+///
+/// impl From<PostV1> for PostV2 {
+///     fn from(v1: PostV1) -> Self {
+///         Self {
+///             title: String::new(),
+///             body: v1.0,
+///         }
+///     }
+/// }
+///
+/// #[hdk_entry_versioned]
+/// pub enum Post {
+///     V1(PostV1),
+///     V2(PostV2),
+/// }
+///
+/// let post: PostV2 = record.try_into()?;
+/// ```
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn hdk_entry_versioned(attrs: TokenStream, code: TokenStream) -> TokenStream {
+    entry_versioned::build(attrs, code)
+}
+
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn dna_properties(attrs: TokenStream, code: TokenStream) -> TokenStream {
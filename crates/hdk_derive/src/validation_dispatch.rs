@@ -0,0 +1,127 @@
+use proc_macro::TokenStream;
+use proc_macro_error::abort;
+use syn::parse_macro_input;
+use syn::Item;
+use syn::ItemEnum;
+
+pub fn build(_attrs: TokenStream, input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as Item);
+
+    // Extract the enums ident and variants.
+    let (ident, variants) = match &input {
+        Item::Enum(ItemEnum {
+            ident, variants, ..
+        }) => (ident, variants),
+        _ => abort!(input, "hdk_validation_dispatch can only be used on Enums"),
+    };
+    if variants.is_empty() {
+        abort!(ident, "hdk_validation_dispatch needs at least one variant");
+    }
+
+    // Unit enums are link types and tuple enums are entry types.
+    let is_link_types = variants
+        .iter()
+        .all(|v| matches!(v.fields, syn::Fields::Unit));
+    let is_entry_types = variants
+        .iter()
+        .all(|v| matches!(&v.fields, syn::Fields::Unnamed(f) if f.unnamed.len() == 1));
+
+    // The handler for each variant is named after the variant
+    // and spanned to it, so a missing handler is reported on the variant.
+    let handler = |prefix: &str, v_ident: &syn::Ident| {
+        syn::Ident::new(
+            &format!("{}_{}", prefix, crate::util::to_snake_case(None, v_ident)),
+            v_ident.span(),
+        )
+    };
+
+    let output = if is_link_types {
+        let mut create_arms = proc_macro2::TokenStream::new();
+        let mut delete_arms = proc_macro2::TokenStream::new();
+        for syn::Variant { ident: v_ident, .. } in variants {
+            let create = handler("validate_create_link", v_ident);
+            let delete = handler("validate_delete_link", v_ident);
+            create_arms.extend(quote::quote! {#ident::#v_ident => #create(action),});
+            delete_arms
+                .extend(quote::quote! {#ident::#v_ident => #delete(action, original_action),});
+        }
+        quote::quote! {
+            #input
+
+            impl hdi::prelude::LinkTypesValidation for #ident {
+                fn validate_create_link(
+                    self,
+                    action: hdi::prelude::CreateLink,
+                ) -> hdi::prelude::ExternResult<hdi::prelude::ValidateCallbackResult> {
+                    match self {
+                        #create_arms
+                    }
+                }
+
+                fn validate_delete_link(
+                    self,
+                    action: hdi::prelude::DeleteLink,
+                    original_action: hdi::prelude::CreateLink,
+                ) -> hdi::prelude::ExternResult<hdi::prelude::ValidateCallbackResult> {
+                    match self {
+                        #delete_arms
+                    }
+                }
+            }
+        }
+    } else if is_entry_types {
+        let mut create_arms = proc_macro2::TokenStream::new();
+        let mut update_arms = proc_macro2::TokenStream::new();
+        let mut delete_arms = proc_macro2::TokenStream::new();
+        for syn::Variant { ident: v_ident, .. } in variants {
+            let create = handler("validate_create", v_ident);
+            let update = handler("validate_update", v_ident);
+            let delete = handler("validate_delete", v_ident);
+            create_arms.extend(quote::quote! {#ident::#v_ident(entry) => #create(action, entry),});
+            update_arms.extend(quote::quote! {#ident::#v_ident(entry) => #update(action, entry),});
+            delete_arms.extend(
+                quote::quote! {#ident::#v_ident(entry) => #delete(action, original_action, entry),},
+            );
+        }
+        quote::quote! {
+            #input
+
+            impl hdi::prelude::EntryTypesValidation for #ident {
+                fn validate_create(
+                    self,
+                    action: hdi::prelude::EntryCreationAction,
+                ) -> hdi::prelude::ExternResult<hdi::prelude::ValidateCallbackResult> {
+                    match self {
+                        #create_arms
+                    }
+                }
+
+                fn validate_update(
+                    self,
+                    action: hdi::prelude::Update,
+                ) -> hdi::prelude::ExternResult<hdi::prelude::ValidateCallbackResult> {
+                    match self {
+                        #update_arms
+                    }
+                }
+
+                fn validate_delete(
+                    self,
+                    action: hdi::prelude::Delete,
+                    original_action: hdi::prelude::EntryCreationAction,
+                ) -> hdi::prelude::ExternResult<hdi::prelude::ValidateCallbackResult> {
+                    match self {
+                        #delete_arms
+                    }
+                }
+            }
+        }
+    } else {
+        abort!(
+            ident,
+            "hdk_validation_dispatch can only be used on entry types enums with a single \
+            tuple field per variant or on unit link types enums"
+        )
+    };
+    output.into()
+}
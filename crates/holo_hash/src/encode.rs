@@ -35,7 +35,7 @@ impl<P: PrimitiveHashType> TryFrom<String> for HoloHash<P> {
 
 impl<T: HashType> std::fmt::Display for HoloHash<T> {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-        write!(f, "{}", holo_hash_encode(self.get_raw_39()))
+        crate::redact::write_maybe_redacted(f, &holo_hash_encode(self.get_raw_39()))
     }
 }
 
@@ -28,6 +28,19 @@ pub enum HoloHashError {
     /// this hash size is too large for blake2b
     #[error("Bad Blake2B hash size.")]
     BadHashSize,
+
+    /// this external hash namespace is neither registered nor app defined
+    #[error("External hash namespace {0:#04x} is not registered or app defined")]
+    BadExternalNamespace(u8),
+
+    /// this external hash refers to a different system than expected
+    #[error("Expected an external hash in namespace {expected:#04x} but found {found:#04x}")]
+    ExternalNamespaceMismatch {
+        /// The namespace that was expected.
+        expected: u8,
+        /// The namespace of the hash.
+        found: u8,
+    },
 }
 
 /// HoloHash Result type
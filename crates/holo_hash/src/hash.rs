@@ -184,6 +184,16 @@ impl<T: HashType> HoloHash<T> {
         self.hash
     }
 
+    /// Compare two hashes in constant time, so that the time taken doesn't
+    /// reveal how many leading bytes match.
+    ///
+    /// Use this rather than `==` when checking an agent against a key
+    /// it must match, such as in authorization checks.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.get_raw_39().ct_eq(other.get_raw_39()).into()
+    }
+
     /// Get the hex representation of the hash bytes
     pub fn to_hex(&self) -> String {
         holochain_util::hex::bytes_to_hex(&self.hash, false)
@@ -263,6 +273,7 @@ impl<T: HashType> HasHash<T> for HoloHash<T> {
 // NB: See encode/encode_raw module for Display impl
 impl<T: HashType> std::fmt::Debug for HoloHash<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Display is redacted itself when redaction is on
         f.write_fmt(format_args!("{}({})", self.hash_type().hash_name(), self))?;
         Ok(())
     }
@@ -289,6 +300,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ct_eq() {
+        let a = AgentPubKey::from_raw_36(vec![0xdb; HOLO_HASH_UNTYPED_LEN]);
+        let mut bytes = vec![0xdb; HOLO_HASH_UNTYPED_LEN];
+        bytes[HOLO_HASH_UNTYPED_LEN - 1] = 0;
+        let b = AgentPubKey::from_raw_36(bytes);
+        assert!(a.ct_eq(&a.clone()));
+        assert!(!a.ct_eq(&b));
+    }
+
     #[test]
     fn test_enum_types() {
         assert_type(
@@ -7,6 +7,8 @@ mod error;
 mod has_hash;
 mod hash;
 pub mod hash_type;
+mod namespaced;
+mod redact;
 
 pub use aliases::*;
 pub use error::*;
@@ -14,6 +16,8 @@ pub use has_hash::HasHash;
 pub use hash::*;
 pub use hash_type::HashType;
 pub use hash_type::PrimitiveHashType;
+pub use namespaced::*;
+pub use redact::*;
 
 // feature: serialization (enabled by default)
 // (serde, SerializedBytes)
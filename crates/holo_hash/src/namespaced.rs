@@ -0,0 +1,295 @@
+//! External hashes tagged with the external system they refer to.
+//!
+//! An [`ExternalHash`](crate::ExternalHash) is 32 opaque bytes, so nothing
+//! says whether they are an IPFS digest, a git object id or something else.
+//! A [`NamespacedExternalHash`] is a namespace byte followed by a 32 byte
+//! digest, which lets integrity zomes reference content in other systems
+//! unambiguously and validate that a reference is to the expected system.
+
+use crate::HoloHashError;
+use crate::HoloHashResult;
+use crate::HOLO_HASH_CORE_LEN;
+
+/// The length of a [`NamespacedExternalHash`] as bytes.
+pub const NAMESPACED_EXTERNAL_HASH_LEN: usize = 1 + HOLO_HASH_CORE_LEN;
+
+/// The external system a [`NamespacedExternalHash`] refers to.
+///
+/// Namespaces below [`ExternalNamespace::APP_DEFINED_START`] are registered
+/// here and have a fixed meaning. Namespaces from it upward are left for
+/// apps to define for themselves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct ExternalNamespace(u8);
+
+impl ExternalNamespace {
+    /// The sha2-256 digest of an IPFS CID.
+    pub const IPFS_SHA2_256: Self = Self(0x01);
+    /// A git object id in a repository using the sha256 object format.
+    pub const GIT_SHA256: Self = Self(0x02);
+    /// The sha2-256 digest of arbitrary content.
+    pub const SHA2_256: Self = Self(0x03);
+    /// A keccak256 digest, as used by Ethereum.
+    pub const KECCAK256: Self = Self(0x04);
+    /// The 32 byte core of a hash in another Holochain DHT.
+    pub const HOLOCHAIN: Self = Self(0x05);
+
+    /// The first namespace which is defined by apps rather than registered here.
+    pub const APP_DEFINED_START: u8 = 0x80;
+
+    const REGISTRY: &'static [(Self, &'static str)] = &[
+        (Self::IPFS_SHA2_256, "ipfs-sha2-256"),
+        (Self::GIT_SHA256, "git-sha256"),
+        (Self::SHA2_256, "sha2-256"),
+        (Self::KECCAK256, "keccak256"),
+        (Self::HOLOCHAIN, "holochain"),
+    ];
+
+    /// The namespace for a byte, if it is registered or app defined.
+    pub fn try_from_u8(namespace: u8) -> HoloHashResult<Self> {
+        let namespace = Self(namespace);
+        if namespace.is_registered() || namespace.is_app_defined() {
+            Ok(namespace)
+        } else {
+            Err(HoloHashError::BadExternalNamespace(namespace.0))
+        }
+    }
+
+    /// An app defined namespace, `index` from [`Self::APP_DEFINED_START`].
+    pub fn app_defined(index: u8) -> HoloHashResult<Self> {
+        Self::APP_DEFINED_START
+            .checked_add(index)
+            .map(Self)
+            .ok_or(HoloHashError::BadExternalNamespace(index))
+    }
+
+    /// The byte this namespace is encoded as.
+    pub fn as_u8(&self) -> u8 {
+        self.0
+    }
+
+    /// Is this one of the namespaces registered here?
+    pub fn is_registered(&self) -> bool {
+        self.name().is_some()
+    }
+
+    /// Is this a namespace defined by an app?
+    pub fn is_app_defined(&self) -> bool {
+        self.0 >= Self::APP_DEFINED_START
+    }
+
+    /// The name of a registered namespace.
+    pub fn name(&self) -> Option<&'static str> {
+        Self::REGISTRY
+            .iter()
+            .find(|(namespace, _)| namespace == self)
+            .map(|(_, name)| *name)
+    }
+}
+
+impl std::fmt::Display for ExternalNamespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "app-defined-{:#04x}", self.0),
+        }
+    }
+}
+
+/// A 32 byte digest from an external system, tagged with the [`ExternalNamespace`]
+/// of that system.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NamespacedExternalHash {
+    namespace: ExternalNamespace,
+    digest: [u8; HOLO_HASH_CORE_LEN],
+}
+
+impl NamespacedExternalHash {
+    /// Tag a digest with the namespace of the system it is from.
+    pub fn new(namespace: ExternalNamespace, digest: [u8; HOLO_HASH_CORE_LEN]) -> Self {
+        Self { namespace, digest }
+    }
+
+    /// Decode a namespace byte followed by a 32 byte digest.
+    pub fn from_bytes(bytes: &[u8]) -> HoloHashResult<Self> {
+        if bytes.len() != NAMESPACED_EXTERNAL_HASH_LEN {
+            return Err(HoloHashError::BadSize);
+        }
+        let namespace = ExternalNamespace::try_from_u8(bytes[0])?;
+        let mut digest = [0; HOLO_HASH_CORE_LEN];
+        digest.copy_from_slice(&bytes[1..]);
+        Ok(Self::new(namespace, digest))
+    }
+
+    /// Encode as the namespace byte followed by the 32 byte digest.
+    pub fn to_bytes(&self) -> [u8; NAMESPACED_EXTERNAL_HASH_LEN] {
+        let mut bytes = [0; NAMESPACED_EXTERNAL_HASH_LEN];
+        bytes[0] = self.namespace.as_u8();
+        bytes[1..].copy_from_slice(&self.digest);
+        bytes
+    }
+
+    /// The system this hash refers to.
+    pub fn namespace(&self) -> ExternalNamespace {
+        self.namespace
+    }
+
+    /// The digest in the external system.
+    pub fn digest(&self) -> &[u8; HOLO_HASH_CORE_LEN] {
+        &self.digest
+    }
+
+    /// `Ok(())` if this hash refers to the `expected` system,
+    /// otherwise [`HoloHashError::ExternalNamespaceMismatch`].
+    pub fn check_namespace(&self, expected: ExternalNamespace) -> HoloHashResult<()> {
+        if self.namespace == expected {
+            Ok(())
+        } else {
+            Err(HoloHashError::ExternalNamespaceMismatch {
+                expected: expected.as_u8(),
+                found: self.namespace.as_u8(),
+            })
+        }
+    }
+
+    /// The [`ExternalHash`](crate::ExternalHash) of this hash, which can be used as
+    /// a link base. The namespace is digested along with the external digest, so the
+    /// same digest in different namespaces gives different external hashes.
+    #[cfg(feature = "hashing")]
+    pub fn to_external_hash(&self) -> crate::ExternalHash {
+        crate::ExternalHash::from_raw_32(crate::encode::blake2b_256(&self.to_bytes()))
+    }
+}
+
+impl std::fmt::Debug for NamespacedExternalHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "NamespacedExternalHash({}:{})",
+            self.namespace,
+            holochain_util::hex::bytes_to_hex(&self.digest, false)
+        )
+    }
+}
+
+#[cfg(feature = "encoding")]
+impl std::fmt::Display for NamespacedExternalHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&crate::holo_hash_encode(&self.to_bytes()))
+    }
+}
+
+#[cfg(feature = "encoding")]
+impl std::str::FromStr for NamespacedExternalHash {
+    type Err = HoloHashError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use base64::Engine;
+        let encoded = s.strip_prefix('u').ok_or(HoloHashError::NoU)?;
+        let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|_| HoloHashError::BadBase64)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl serde::Serialize for NamespacedExternalHash {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+#[cfg(feature = "serialization")]
+impl<'de> serde::Deserialize<'de> for NamespacedExternalHash {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes = serde_bytes::ByteBuf::deserialize(deserializer)?;
+        Self::from_bytes(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn namespaced_external_hash_round_trips_as_bytes() {
+        let hash = NamespacedExternalHash::new(ExternalNamespace::IPFS_SHA2_256, [0xdb; 32]);
+        let bytes = hash.to_bytes();
+        assert_eq!(0x01, bytes[0]);
+        assert_eq!(hash, NamespacedExternalHash::from_bytes(&bytes).unwrap());
+        assert_eq!(
+            Err(HoloHashError::BadSize),
+            NamespacedExternalHash::from_bytes(&bytes[1..])
+        );
+    }
+
+    #[test]
+    fn external_namespaces_are_validated() {
+        assert_eq!(
+            Err(HoloHashError::BadExternalNamespace(0)),
+            ExternalNamespace::try_from_u8(0)
+        );
+        assert_eq!(
+            Err(HoloHashError::BadExternalNamespace(0x7f)),
+            NamespacedExternalHash::from_bytes(&[&[0x7f][..], &[0; 32]].concat())
+        );
+
+        let app = ExternalNamespace::app_defined(3).unwrap();
+        assert!(app.is_app_defined() && !app.is_registered());
+        assert_eq!(app, ExternalNamespace::try_from_u8(0x83).unwrap());
+        assert!(ExternalNamespace::app_defined(0x80).is_err());
+
+        let hash = NamespacedExternalHash::new(ExternalNamespace::GIT_SHA256, [1; 32]);
+        assert!(hash.check_namespace(ExternalNamespace::GIT_SHA256).is_ok());
+        assert_eq!(
+            Err(HoloHashError::ExternalNamespaceMismatch {
+                expected: 0x01,
+                found: 0x02
+            }),
+            hash.check_namespace(ExternalNamespace::IPFS_SHA2_256)
+        );
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn namespaced_external_hash_round_trips_as_string() {
+        let hash = NamespacedExternalHash::new(ExternalNamespace::KECCAK256, [7; 32]);
+        let s = hash.to_string();
+        assert!(s.starts_with('u'));
+        assert_eq!(hash, s.parse().unwrap());
+        assert_eq!(
+            Err(HoloHashError::NoU),
+            s[1..].parse::<NamespacedExternalHash>()
+        );
+    }
+
+    #[cfg(feature = "hashing")]
+    #[test]
+    fn namespace_is_part_of_the_external_hash() {
+        let ipfs = NamespacedExternalHash::new(ExternalNamespace::IPFS_SHA2_256, [9; 32]);
+        let git = NamespacedExternalHash::new(ExternalNamespace::GIT_SHA256, [9; 32]);
+        assert_eq!(ipfs.to_external_hash(), ipfs.to_external_hash());
+        assert_ne!(ipfs.to_external_hash(), git.to_external_hash());
+    }
+
+    #[cfg(feature = "serialization")]
+    #[test]
+    fn namespaced_external_hash_serializes_as_bytes() {
+        let hash = NamespacedExternalHash::new(ExternalNamespace::HOLOCHAIN, [2; 32]);
+        let encoded = holochain_serialized_bytes::encode(&hash).unwrap();
+        assert_eq!(
+            hash,
+            holochain_serialized_bytes::decode::<_, NamespacedExternalHash>(&encoded).unwrap()
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! Redacted formatting of hashes, so that full agent keys and other hashes
+//! don't end up in logs which are shared with others.
+//!
+//! When redaction is on, both the `Display` and the `Debug` impl of
+//! [`HoloHash`](crate::HoloHash) print only the type prefix and the first 6
+//! characters of the hash, followed by `…`. This covers `%hash` as well as
+//! `?hash` fields in tracing.
+//! Serialization and [`holo_hash_encode`](crate::holo_hash_encode) are never
+//! redacted, so hashes which are stored or sent somewhere must be encoded
+//! with those rather than with `to_string`.
+//!
+//! Redaction is set for the whole process, usually once at startup from the
+//! conductor config. It is off by default, unless the `redacted-display`
+//! feature is enabled.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+/// The number of characters of the hash, after the type prefix, shown when redacted.
+pub const REDACTED_LEN: usize = 6;
+
+static REDACTED: AtomicBool = AtomicBool::new(cfg!(feature = "redacted-display"));
+
+/// Turn redacted formatting of hashes on or off for the whole process.
+///
+/// Returns whether redaction was on before the call, so it can be restored.
+pub fn set_redacted(on: bool) -> bool {
+    REDACTED.swap(on, Ordering::Relaxed)
+}
+
+/// Whether hashes are formatted redacted.
+pub fn is_redacted() -> bool {
+    REDACTED.load(Ordering::Relaxed)
+}
+
+/// The length of the redacted form of a displayed hash: the characters
+/// which encode the 3 type prefix bytes plus [`REDACTED_LEN`].
+pub(crate) fn redacted_len() -> usize {
+    // "u" and the 4 characters which encode the 3 prefix bytes
+    #[cfg(feature = "encoding")]
+    let prefix_len = 5;
+    // "0x" and the 6 hex characters for the 3 prefix bytes
+    #[cfg(not(feature = "encoding"))]
+    let prefix_len = 8;
+    prefix_len + REDACTED_LEN
+}
+
+/// Write the full displayed form of a hash, or its redacted form if redaction is on.
+pub(crate) fn write_maybe_redacted(
+    f: &mut std::fmt::Formatter<'_>,
+    full: &str,
+) -> std::fmt::Result {
+    if is_redacted() {
+        write!(f, "{}…", &full[..redacted_len()])
+    } else {
+        f.write_str(full)
+    }
+}
@@ -0,0 +1,31 @@
+//! Redaction is set for the whole process, so it is tested in its own test
+//! binary where it can't change how hashes look to other tests.
+
+use holo_hash::*;
+
+#[test]
+fn redaction_hides_the_hash_on_every_thread() {
+    let agent = AgentPubKey::from_raw_36(vec![0xdb; HOLO_HASH_UNTYPED_LEN]);
+
+    let was = set_redacted(false);
+    let full = agent.to_string();
+    let full_debug = format!("{:?}", agent);
+    set_redacted(true);
+    let display = agent.to_string();
+    let debug = format!("{:?}", agent);
+    let from_other_thread = std::thread::spawn({
+        let agent = agent.clone();
+        move || agent.to_string()
+    })
+    .join()
+    .unwrap();
+    set_redacted(was);
+
+    assert_eq!(format!("AgentPubKey({})", full), full_debug);
+
+    let shown = display.strip_suffix('…').unwrap();
+    assert!(full.starts_with(shown));
+    assert!(shown.len() < full.len() / 2);
+    assert_eq!(format!("AgentPubKey({})", display), debug);
+    assert_eq!(display, from_other_thread);
+}
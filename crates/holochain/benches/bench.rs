@@ -84,6 +84,7 @@ pub fn wasm_call_n(c: &mut Criterion) {
                     expires_at: Timestamp::now(),
                     nonce: [0; 32].into(),
                     signature: [0; 64].into(),
+                    wait_for_publish: None,
                 };
                 let ribosome = REAL_RIBOSOME.lock().unwrap().clone();
                 let fut = ribosome.maybe_call(ha.clone().into(), &i, &zome, &i.fn_name);
@@ -8,6 +8,7 @@ use holochain_conductor_api::conductor::ConductorConfigError;
 use holochain_conductor_api::config::conductor::paths::ConfigRootPath;
 use holochain_conductor_api::config::conductor::KeystoreConfig;
 use holochain_trace::Output;
+use holochain_util::pw::PwSource;
 use holochain_util::tokio_helper;
 #[cfg(unix)]
 use sd_notify::{notify, NotifyState};
@@ -47,11 +48,30 @@ struct Opt {
     #[structopt(short = "p", long)]
     pub piped: bool,
 
+    /// Read the passphrase from the first line of this file instead of
+    /// prompting for it. The file must only be accessible by its owner.
+    #[structopt(
+        long,
+        conflicts_with_all = &["piped", "passphrase-credential"]
+    )]
+    pub passphrase_file: Option<PathBuf>,
+
+    /// Read the passphrase from the systemd credential with this name instead
+    /// of prompting for it, e.g. one loaded with
+    /// `LoadCredentialEncrypted=<name>:<path>` in the holochain unit file.
+    #[structopt(long, conflicts_with = "piped")]
+    pub passphrase_credential: Option<String>,
+
     #[structopt(
         long,
         help = "Display version information such as git revision and HDK version"
     )]
     build_info: bool,
+
+    /// Before starting, replace the conductor's databases with those in a
+    /// snapshot directory written by the `Snapshot` admin request.
+    #[structopt(long)]
+    restore_snapshot: Option<PathBuf>,
 }
 
 fn main() {
@@ -122,12 +142,19 @@ async fn conductor_handle_from_config(opt: &Opt, config: ConductorConfig) -> Con
     // read the passphrase to prepare for usage
     let passphrase = match &config.keystore {
         KeystoreConfig::DangerTestKeystore => None,
-        KeystoreConfig::LairServer { .. } | KeystoreConfig::LairServerInProc { .. } => {
-            if opt.piped {
-                holochain_util::pw::pw_set_piped(true);
+        KeystoreConfig::LairServer { .. }
+        | KeystoreConfig::LairServerInProc { .. }
+        | KeystoreConfig::InProcSoftware { .. }
+        | KeystoreConfig::OsKeychain { .. } => {
+            holochain_util::pw::pw_set_source(passphrase_source(opt));
+
+            match holochain_util::pw::pw_get() {
+                Ok(passphrase) => Some(passphrase),
+                Err(err) => {
+                    println!("Couldn't read the keystore passphrase: {}", err);
+                    std::process::exit(ERROR_CODE);
+                }
             }
-
-            Some(holochain_util::pw::pw_get().unwrap())
         }
     };
 
@@ -146,12 +173,11 @@ async fn conductor_handle_from_config(opt: &Opt, config: ConductorConfig) -> Con
     }
 
     // Initialize the Conductor
-    match Conductor::builder()
-        .config(config)
-        .passphrase(passphrase)
-        .build()
-        .await
-    {
+    let mut builder = Conductor::builder().config(config).passphrase(passphrase);
+    if let Some(snapshot_dir) = &opt.restore_snapshot {
+        builder = builder.restore_snapshot(snapshot_dir.clone());
+    }
+    match builder.build().await {
         Err(err) => panic!(
             "Could not initialize Conductor from configuration: {:?}",
             err
@@ -160,6 +186,18 @@ async fn conductor_handle_from_config(opt: &Opt, config: ConductorConfig) -> Con
     }
 }
 
+fn passphrase_source(opt: &Opt) -> PwSource {
+    if let Some(path) = &opt.passphrase_file {
+        PwSource::Keyfile(path.clone())
+    } else if let Some(name) = &opt.passphrase_credential {
+        PwSource::SystemdCredential(name.clone())
+    } else if opt.piped {
+        PwSource::Piped
+    } else {
+        PwSource::Interactive
+    }
+}
+
 /// Load config, throw friendly error on failure
 fn load_config(maybe_config_root_path: Option<ConfigRootPath>) -> ConductorConfig {
     if let Some(ref config_root_path) = maybe_config_root_path {
@@ -12,6 +12,7 @@ use crate::core::ribosome::real_ribosome::RealRibosome;
 use crate::core::workflow::ZomeCallResult;
 use async_trait::async_trait;
 use holo_hash::DnaHash;
+use holochain_conductor_api::FirehoseOp;
 use holochain_conductor_api::ZomeCall;
 use holochain_keystore::MetaLairClient;
 use holochain_state::host_fn_workspace::SourceChainWorkspace;
@@ -20,6 +21,7 @@ use holochain_state::prelude::DatabaseResult;
 use holochain_types::prelude::*;
 use holochain_zome_types::block::Block;
 use holochain_zome_types::block::BlockTargetId;
+use tokio::sync::broadcast;
 use tokio::sync::mpsc::error::SendError;
 use tokio::sync::mpsc::OwnedPermit;
 
@@ -192,13 +194,21 @@ pub trait CellConductorReadHandleT: Send + Sync {
         expires: Timestamp,
     ) -> ConductorApiResult<WitnessNonceResult>;
 
-    /// Find the first cell ID across all apps the given cell id is in that
-    /// is assigned to the given role.
+    /// Find the cell provisioned for a role in the running app which the
+    /// given cell is in.
     async fn find_cell_with_role_alongside_cell(
         &self,
         cell_id: &CellId,
         role_name: &RoleName,
-    ) -> ConductorResult<Option<CellId>>;
+    ) -> ConductorResult<CellId>;
+
+    /// Find the enabled clone cell with a clone id in the running app which
+    /// the given cell is in.
+    async fn find_clone_cell_alongside_cell(
+        &self,
+        cell_id: &CellId,
+        clone_id: &CloneId,
+    ) -> ConductorResult<CellId>;
 
     /// Expose block functionality to zomes.
     async fn block(&self, input: Block) -> DatabaseResult<()>;
@@ -215,6 +225,13 @@ pub trait CellConductorReadHandleT: Send + Sync {
         cell_id: &CellId,
     ) -> ConductorResult<Option<InstalledApp>>;
 
+    /// What gets do with data by warranted authors, by default, for the app
+    /// which this cell is in.
+    async fn warranted_authors(&self) -> ConductorResult<WarrantedAuthors>;
+
+    /// Subscribe to the ops integrated into this cell's space.
+    fn subscribe_integrated_ops(&self) -> broadcast::Receiver<Arc<FirehoseOp>>;
+
     /// Expose create_clone_cell functionality to zomes.
     async fn create_clone_cell(
         &self,
@@ -284,12 +301,22 @@ impl CellConductorReadHandleT for CellConductorApi {
         &self,
         cell_id: &CellId,
         role_name: &RoleName,
-    ) -> ConductorResult<Option<CellId>> {
+    ) -> ConductorResult<CellId> {
         self.conductor_handle
             .find_cell_with_role_alongside_cell(cell_id, role_name)
             .await
     }
 
+    async fn find_clone_cell_alongside_cell(
+        &self,
+        cell_id: &CellId,
+        clone_id: &CloneId,
+    ) -> ConductorResult<CellId> {
+        self.conductor_handle
+            .find_clone_cell_alongside_cell(cell_id, clone_id)
+            .await
+    }
+
     async fn block(&self, input: Block) -> DatabaseResult<()> {
         self.conductor_handle.block(input).await
     }
@@ -311,6 +338,18 @@ impl CellConductorReadHandleT for CellConductorApi {
             .await
     }
 
+    async fn warranted_authors(&self) -> ConductorResult<WarrantedAuthors> {
+        self.conductor_handle
+            .warranted_authors_for_cell(&self.cell_id)
+            .await
+    }
+
+    fn subscribe_integrated_ops(&self) -> broadcast::Receiver<Arc<FirehoseOp>> {
+        self.conductor_handle
+            .firehose()
+            .subscribe(self.cell_id.dna_hash().clone())
+    }
+
     async fn create_clone_cell(
         &self,
         installed_app_id: &InstalledAppId,
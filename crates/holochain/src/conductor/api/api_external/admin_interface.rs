@@ -31,6 +31,48 @@ impl AdminInterfaceApi {
         AdminInterfaceApi { conductor_handle }
     }
 
+    /// Check that `token` is a valid admin authentication token, for a connection to an admin
+    /// interface which requires one.
+    pub fn auth(&self, token: &AdminAuthenticationToken) -> InterfaceResult<()> {
+        self.conductor_handle
+            .authenticate_admin_token(token)
+            .map(|_| ())
+            .map_err(Box::new)
+            .map_err(InterfaceError::RequestHandler)
+    }
+
+    /// Derive a DNA from a registered one by overriding its modifiers, and register it
+    /// unless it already is. `requested_by` names the request in the error returned when
+    /// no modifier is set.
+    async fn register_derived_dna(
+        &self,
+        requested_by: &str,
+        base_dna_hash: &DnaHash,
+        modifiers: DnaModifiersOpt,
+    ) -> ConductorApiResult<DnaHash> {
+        if !modifiers.has_some_option_set() {
+            return Err(ConductorApiError::DnaReadError(format!(
+                "{} requires `properties` or `network_seed` or `origin_time` to create a derived Dna",
+                requested_by
+            )));
+        }
+        let dna = self
+            .conductor_handle
+            .get_dna_file(base_dna_hash)
+            .ok_or_else(|| {
+                ConductorApiError::DnaReadError(format!(
+                    "Unable to create derived Dna: {} not registered",
+                    base_dna_hash
+                ))
+            })?
+            .update_modifiers(modifiers);
+        let hash = dna.dna_hash().clone();
+        if !self.conductor_handle.list_dnas().contains(&hash) {
+            self.conductor_handle.register_dna(dna).await?;
+        }
+        Ok(hash)
+    }
+
     /// Handle an [AdminRequest] and return an [AdminResponse].
     pub async fn handle_request(
         &self,
@@ -49,6 +91,30 @@ impl AdminInterfaceApi {
         }
     }
 
+    /// Handle an [AdminRequest] made over a connection which authenticated with the admin
+    /// `token`, refusing it unless the scope of the token permits it.
+    pub async fn handle_scoped_request(
+        &self,
+        token: &AdminAuthenticationToken,
+        request: Result<AdminRequest, SerializedBytesError>,
+    ) -> InterfaceResult<AdminResponse> {
+        let request = match request {
+            Ok(request) => request,
+            Err(e) => return Ok(AdminResponse::Error(SerializationError::from(e).into())),
+        };
+        match self
+            .conductor_handle
+            .authorize_admin_request(token, &request)
+            .await
+        {
+            Ok(()) => self.handle_request(Ok(request)).await,
+            Err(ConductorError::FailedAuthenticationError(e)) => Ok(AdminResponse::Error(
+                ExternalApiWireError::AdminCallUnauthorized(e),
+            )),
+            Err(e) => Ok(AdminResponse::Error(ConductorApiError::from(e).into())),
+        }
+    }
+
     /// Deal with error cases produced by `handle_admin_request_inner`
     pub(crate) async fn handle_admin_request(&self, request: AdminRequest) -> AdminResponse {
         debug!("admin request: {:?}", request);
@@ -81,21 +147,10 @@ impl AdminInterfaceApi {
                 // network seed and properties from the register call will override any in the bundle
                 let dna = match source {
                     DnaSource::Hash(ref hash) => {
-                        if !modifiers.has_some_option_set() {
-                            return Err(ConductorApiError::DnaReadError(
-                                "DnaSource::Hash requires `properties` or `network_seed` or `origin_time` to create a derived Dna"
-                                    .to_string(),
-                            ));
-                        }
-                        self.conductor_handle
-                            .get_dna_file(hash)
-                            .ok_or_else(|| {
-                                ConductorApiError::DnaReadError(format!(
-                                    "Unable to create derived Dna: {} not registered",
-                                    hash
-                                ))
-                            })?
-                            .update_modifiers(modifiers)
+                        let hash = self
+                            .register_derived_dna("DnaSource::Hash", hash, modifiers)
+                            .await?;
+                        return Ok(AdminResponse::DnaRegistered(hash));
                     }
                     DnaSource::Path(ref path) => {
                         let bundle = Bundle::read_from_file(path).await?;
@@ -116,6 +171,16 @@ impl AdminInterfaceApi {
                 }
                 Ok(AdminResponse::DnaRegistered(hash))
             }
+            RegisterDnaVariant {
+                base_dna_hash,
+                modifiers,
+            } => {
+                let modifiers = modifiers.serialized().map_err(SerializationError::Bytes)?;
+                Ok(AdminResponse::DnaVariantRegistered(
+                    self.register_derived_dna("RegisterDnaVariant", &base_dna_hash, modifiers)
+                        .await?,
+                ))
+            }
             GetDnaDefinition(dna_hash) => {
                 let dna_def = self
                     .conductor_handle
@@ -140,6 +205,12 @@ impl AdminInterfaceApi {
 
                 Ok(AdminResponse::CoordinatorsUpdated)
             }
+            RollbackCoordinators { dna_hash } => {
+                self.conductor_handle
+                    .rollback_coordinators(&dna_hash)
+                    .await?;
+                Ok(AdminResponse::CoordinatorsRolledBack)
+            }
             InstallApp(payload) => {
                 let app: InstalledApp = self
                     .conductor_handle
@@ -220,6 +291,23 @@ impl AdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::AppDisabled)
             }
+            SetAppNetworkMode {
+                installed_app_id,
+                mode,
+            } => {
+                self.conductor_handle
+                    .set_app_network_mode(&installed_app_id, mode)
+                    .await?;
+                Ok(AdminResponse::AppNetworkModeSet)
+            }
+            FreezeCell { cell_id } => {
+                self.conductor_handle.freeze_cell(&cell_id).await?;
+                Ok(AdminResponse::CellFrozen)
+            }
+            UnfreezeCell { cell_id } => {
+                self.conductor_handle.unfreeze_cell(&cell_id).await?;
+                Ok(AdminResponse::CellUnfrozen)
+            }
             AttachAppInterface {
                 port,
                 allowed_origins,
@@ -259,6 +347,18 @@ impl AdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::FullStateDumped(state))
             }
+            DumpStatePage {
+                cell_id,
+                store,
+                cursor,
+                limit,
+            } => {
+                let page = self
+                    .conductor_handle
+                    .dump_cell_state_page(&cell_id, store, cursor, limit)
+                    .await?;
+                Ok(AdminResponse::StateDumpPage(page))
+            }
             DumpNetworkMetrics { dna_hash } => {
                 let dump = self.conductor_handle.dump_network_metrics(dna_hash).await?;
                 Ok(AdminResponse::NetworkMetricsDumped(dump))
@@ -275,6 +375,14 @@ impl AdminInterfaceApi {
                 let r = self.conductor_handle.get_agent_infos(cell_id).await?;
                 Ok(AdminResponse::AgentInfo(r))
             }
+            ListPeers { dna_hash } => {
+                let peers = self.conductor_handle.list_peers(&dna_hash).await?;
+                Ok(AdminResponse::PeersListed(peers))
+            }
+            PrunePeers { expired_by } => {
+                let pruned = self.conductor_handle.prune_peers(expired_by).await?;
+                Ok(AdminResponse::PeersPruned(pruned))
+            }
             GraftRecords {
                 cell_id,
                 validate,
@@ -286,6 +394,18 @@ impl AdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::RecordsGrafted)
             }
+            ImportLegacyChain {
+                cell_id,
+                chain,
+                link_types,
+            } => {
+                let imported = self
+                    .conductor_handle
+                    .clone()
+                    .import_legacy_chain(cell_id, chain, link_types)
+                    .await?;
+                Ok(AdminResponse::LegacyChainImported(imported))
+            }
             GrantZomeCallCapability(payload) => {
                 self.conductor_handle
                     .clone()
@@ -293,6 +413,17 @@ impl AdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::ZomeCallCapabilityGranted)
             }
+            RenewMembraneProof {
+                cell_id,
+                membrane_proof,
+            } => {
+                let action_hash = self
+                    .conductor_handle
+                    .clone()
+                    .renew_membrane_proof(&cell_id, membrane_proof)
+                    .await?;
+                Ok(AdminResponse::MembraneProofRenewed(action_hash))
+            }
             DeleteCloneCell(payload) => {
                 self.conductor_handle
                     .clone()
@@ -303,6 +434,12 @@ impl AdminInterfaceApi {
             StorageInfo => Ok(AdminResponse::StorageInfo(
                 self.conductor_handle.storage_info().await?,
             )),
+            PendingDbMigrations => Ok(AdminResponse::PendingDbMigrations(
+                self.conductor_handle.pending_db_migrations().await?,
+            )),
+            Snapshot { path } => Ok(AdminResponse::SnapshotTaken(
+                self.conductor_handle.snapshot_databases(path).await?,
+            )),
             IssueAppAuthenticationToken(payload) => {
                 Ok(AdminResponse::AppAuthenticationTokenIssued(
                     self.conductor_handle
@@ -314,6 +451,116 @@ impl AdminInterfaceApi {
                     .revoke_app_authentication_token(token)?;
                 Ok(AdminResponse::AppAuthenticationTokenRevoked)
             }
+            IssueAdminAuthenticationToken(payload) => {
+                let issued = self
+                    .conductor_handle
+                    .issue_admin_authentication_token(payload)?;
+                Ok(AdminResponse::AdminAuthenticationTokenIssued(issued))
+            }
+            RevokeAdminAuthenticationToken(token) => {
+                self.conductor_handle
+                    .revoke_admin_authentication_token(token)?;
+                Ok(AdminResponse::AdminAuthenticationTokenRevoked)
+            }
+            // The metrics stream itself is attached to the connection by the
+            // websocket interface once this request has succeeded.
+            SubscribeMetrics { interval } => {
+                if interval == 0 {
+                    return Err(ConductorApiError::other(
+                        "Metrics interval must be greater than zero",
+                    ));
+                }
+                Ok(AdminResponse::MetricsSubscribed)
+            }
+            // The encoding itself is attached to the connection by the
+            // websocket interface once this request has succeeded.
+            SetWireEncoding { .. } => Ok(AdminResponse::WireEncodingSet),
+            SetTracingFilter { filter } => {
+                holochain_trace::set_filter(&filter).map_err(ConductorApiError::other)?;
+                info!(%filter, "Tracing filter changed");
+                Ok(AdminResponse::TracingFilterSet)
+            }
+            GetTracingFilter => Ok(AdminResponse::TracingFilter(
+                holochain_trace::current_filter(),
+            )),
+            ProbeBasis { dna_hash, basis } => Ok(AdminResponse::BasisProbed(
+                self.conductor_handle.probe_basis(&dna_hash, basis).await?,
+            )),
+            EventJournal { since } => Ok(AdminResponse::EventJournal(
+                self.conductor_handle.event_journal(since).await?,
+            )),
+            QueryRegionSet { dna_hash } => Ok(AdminResponse::RegionSetQueried(
+                self.conductor_handle.query_region_set(&dna_hash).await?,
+            )),
+            FetchOpDataByRegions { dna_hash, regions } => {
+                Ok(AdminResponse::OpDataByRegionsFetched(
+                    self.conductor_handle
+                        .fetch_op_data_by_regions(&dna_hash, regions)
+                        .await?,
+                ))
+            }
+            WorkflowStats { dna_hash } => Ok(AdminResponse::WorkflowStats(
+                self.conductor_handle.workflow_stats(&dna_hash).await?,
+            )),
+            ListValidationLimbo { dna_hash } => Ok(AdminResponse::ValidationLimboListed(
+                self.conductor_handle
+                    .list_validation_limbo(&dna_hash)
+                    .await?,
+            )),
+            GetOpDependencyGraph {
+                dna_hash,
+                op_hashes,
+            } => Ok(AdminResponse::OpDependencyGraph(
+                self.conductor_handle
+                    .op_dependency_graph(&dna_hash, op_hashes)
+                    .await?,
+            )),
+            RetryValidation {
+                dna_hash,
+                op_hashes,
+            } => Ok(AdminResponse::ValidationRetried(
+                self.conductor_handle
+                    .retry_validation(&dna_hash, op_hashes)
+                    .await?,
+            )),
+            AdminRequest::LoadSheddingStats => Ok(AdminResponse::LoadSheddingStats(
+                self.conductor_handle.load_shedding_stats(),
+            )),
+            AdminRequest::NonceAudit { agent, since } => Ok(AdminResponse::NonceAudit(
+                self.conductor_handle.nonce_audit(agent, since).await?,
+            )),
+            ExportDhtArchive { dna_hash, offset } => Ok(AdminResponse::DhtArchiveExported(
+                self.conductor_handle
+                    .export_dht_archive_chunk(&dna_hash, offset)
+                    .await?,
+            )),
+            ImportDhtArchive {
+                dna_hash,
+                offset,
+                chunk,
+                last,
+            } => {
+                let received = self
+                    .conductor_handle
+                    .receive_dht_archive_chunk(&dna_hash, offset, chunk.into_vec())
+                    .await?;
+                if last {
+                    Ok(AdminResponse::DhtArchiveImported(
+                        self.conductor_handle
+                            .import_received_dht_archive(&dna_hash, received)
+                            .await?,
+                    ))
+                } else {
+                    Ok(AdminResponse::DhtArchiveChunkImported(received))
+                }
+            }
+            #[cfg(feature = "op_replay")]
+            ReplayValidation { dna_hash, archive } => Ok(AdminResponse::ValidationReplayed(
+                self.conductor_handle
+                    .clone()
+                    .replay_validation(&dna_hash, archive.into_vec())
+                    .await?,
+            )),
         }
     }
 }
@@ -460,6 +707,76 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn register_dna_variant() -> Result<()> {
+        use ::fixt::prelude::*;
+        use holo_hash::fixt::DnaHashFixturator;
+
+        holochain_trace::test_run();
+        let env_dir = test_db_dir();
+        let handle = Conductor::builder()
+            .with_data_root_path(env_dir.path().to_path_buf().into())
+            .test(&[])
+            .await?;
+
+        let admin_api = AdminInterfaceApi::new(handle.clone());
+        let dna = fake_dna_zomes(
+            &Uuid::new_v4().to_string(),
+            vec![(TestWasm::Foo.into(), TestWasm::Foo.into())],
+        );
+        let dna_hash = dna.dna_hash().clone();
+        handle.register_dna(dna).await?;
+
+        let variant = |base_dna_hash: DnaHash, modifiers| {
+            admin_api.handle_admin_request(AdminRequest::RegisterDnaVariant {
+                base_dna_hash,
+                modifiers,
+            })
+        };
+
+        // without modifiers the variant would be the base DNA
+        assert_matches!(
+            variant(dna_hash.clone(), DnaModifiersOpt::none()).await,
+            AdminResponse::Error(ExternalApiWireError::DnaReadError(_))
+        );
+
+        let modifiers =
+            DnaModifiersOpt::none().with_network_seed(String::from("12345678900000000000000"));
+        let variant_hash = match variant(dna_hash.clone(), modifiers.clone()).await {
+            AdminResponse::DnaVariantRegistered(hash) => hash,
+            other => panic!("unexpected response {:?}", other),
+        };
+        assert_ne!(variant_hash, dna_hash);
+        let variant_dna = handle.get_dna_file(&variant_hash).unwrap();
+        assert_eq!(
+            variant_dna.dna_def().modifiers.network_seed,
+            "12345678900000000000000"
+        );
+        assert_eq!(
+            variant_dna.dna_def().integrity_zomes,
+            handle.get_dna_def(&dna_hash).unwrap().integrity_zomes
+        );
+
+        // registering the same variant again is idempotent
+        assert_matches!(
+            variant(dna_hash.clone(), modifiers.clone()).await,
+            AdminResponse::DnaVariantRegistered(hash) if hash == variant_hash
+        );
+        let dnas = handle.list_dnas();
+        assert!(dnas.contains(&dna_hash) && dnas.contains(&variant_hash));
+
+        // the base DNA must be registered
+        assert_matches!(
+            variant(fixt!(DnaHash), modifiers).await,
+            AdminResponse::Error(ExternalApiWireError::DnaReadError(_))
+        );
+
+        tokio::time::timeout(std::time::Duration::from_secs(1), handle.shutdown())
+            .await
+            .ok();
+        Ok(())
+    }
+
     // @todo fix test by using new InstallApp call
     // #[tokio::test(flavor = "multi_thread")]
     // async fn install_list_dna_app() {
@@ -1,3 +1,4 @@
+use crate::conductor::api::error::ConductorApiError;
 use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::api::error::SerializationError;
 use crate::conductor::interface::error::InterfaceError;
@@ -130,9 +131,47 @@ impl AppInterfaceApi {
                     .await?;
                 Ok(AppResponse::NetworkInfo(info))
             }
+            AppRequest::PublicationProof(payload) => {
+                let proof = self
+                    .conductor_handle
+                    .publication_proof(&installed_app_id, &payload)
+                    .await?;
+                Ok(AppResponse::PublicationProof(proof))
+            }
+            AppRequest::Neighborhood { cell_id } => {
+                let neighborhood = self
+                    .conductor_handle
+                    .neighborhood(&installed_app_id, &cell_id)
+                    .await?;
+                Ok(AppResponse::Neighborhood(neighborhood))
+            }
+            AppRequest::ListZomeFunctions { cell_id } => {
+                let zome_fns = self
+                    .conductor_handle
+                    .list_zome_functions(&installed_app_id, &cell_id)
+                    .await?;
+                Ok(AppResponse::ZomeFunctionsListed(zome_fns))
+            }
             AppRequest::ListWasmHostFunctions => Ok(AppResponse::ListWasmHostFunctions(
                 self.conductor_handle.list_wasm_host_functions().await?,
             )),
+            AppRequest::FetchMissedSignals { since_token } => {
+                let signals = self
+                    .conductor_handle
+                    .missed_signals(&installed_app_id, since_token)
+                    .await?;
+                Ok(AppResponse::MissedSignalsFetched(signals))
+            }
+            // The filters themselves are attached to the connection by the
+            // websocket interface once these requests have succeeded.
+            AppRequest::SubscribeSignals { pattern, .. } => {
+                if pattern.as_deref() == Some("") {
+                    return Err(ConductorApiError::other("Signal pattern must not be empty"));
+                }
+                Ok(AppResponse::SignalsSubscribed)
+            }
+            AppRequest::UnsubscribeSignals => Ok(AppResponse::SignalsUnsubscribed),
+            AppRequest::SetWireEncoding { .. } => Ok(AppResponse::WireEncodingSet),
         }
     }
 }
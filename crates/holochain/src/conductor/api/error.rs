@@ -163,7 +163,13 @@ impl From<SerializationError> for ExternalApiWireError {
 
 impl From<RibosomeError> for ExternalApiWireError {
     fn from(e: RibosomeError) -> Self {
-        ExternalApiWireError::RibosomeError(e.to_string())
+        match e {
+            RibosomeError::ZomeCallTimeout(..) => {
+                ExternalApiWireError::ZomeCallTimeout(e.to_string())
+            }
+            RibosomeError::CellFrozen(..) => ExternalApiWireError::CellFrozen(e.to_string()),
+            e => ExternalApiWireError::RibosomeError(e.to_string()),
+        }
     }
 }
 
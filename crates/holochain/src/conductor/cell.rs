@@ -23,18 +23,25 @@ use holochain_conductor_api::ZomeCall;
 use holochain_nonce::fresh_nonce;
 use holochain_p2p::event::CountersigningSessionNegotiationMessage;
 use holochain_p2p::ChcImpl;
+use holochain_p2p::DhtOpHashExt;
 use holochain_p2p::HolochainP2pDna;
+use holochain_p2p::ProbeBasisResponse;
 use holochain_sqlite::prelude::*;
 use holochain_state::host_fn_workspace::SourceChainWorkspace;
 use holochain_state::prelude::*;
 use holochain_state::schedule::live_scheduled_fns;
 use holochain_types::db_cache::DhtDbQueryCache;
+use kitsune_p2p::dependencies::kitsune_p2p_fetch::OpHashSized;
+use kitsune_p2p_types::agent_info::AgentInfoSigned;
 
 use crate::conductor::api::CellConductorApi;
 use crate::conductor::cell::error::CellResult;
+use crate::core::metrics::THROUGHPUT;
 use crate::core::queue_consumer::spawn_queue_consumer_tasks;
 use crate::core::queue_consumer::InitialQueueTriggers;
 use crate::core::queue_consumer::QueueTriggers;
+use crate::core::ribosome::guest_callback::init::InitHostAccess;
+use crate::core::ribosome::guest_callback::init::InitInvocation;
 use crate::core::ribosome::guest_callback::init::InitResult;
 use crate::core::ribosome::real_ribosome::RealRibosome;
 use crate::core::ribosome::ZomeCallInvocation;
@@ -56,6 +63,10 @@ use super::ConductorHandle;
 
 pub const INIT_MUTEX_TIMEOUT_SECS: u64 = 30;
 
+/// The number of accepted actions a zome call waiting for publication may fall
+/// behind on before it misses some of them.
+const PUBLISH_ACCEPTED_BUFFER_SIZE: usize = 64;
+
 #[allow(missing_docs)]
 pub mod error;
 
@@ -106,6 +117,9 @@ pub struct Cell {
     holochain_p2p_cell: HolochainP2pDna,
     queue_triggers: QueueTriggers,
     signal_tx: broadcast::Sender<Signal>,
+    /// Announces each authored action the first time an authority accepts it,
+    /// for zome calls which wait for their actions to be published.
+    publish_accepted_tx: broadcast::Sender<ActionHash>,
     init_mutex: tokio::sync::Mutex<()>,
 }
 
@@ -154,6 +168,7 @@ impl Cell {
                     holochain_p2p_cell,
                     queue_triggers,
                     signal_tx,
+                    publish_accepted_tx: broadcast::channel(PUBLISH_ACCEPTED_BUFFER_SIZE).0,
                     init_mutex: Default::default(),
                 },
                 initial_queue_triggers,
@@ -227,7 +242,6 @@ impl Cell {
         self.id.dna_hash()
     }
 
-    #[allow(unused)]
     fn agent_pubkey(&self) -> &AgentPubKey {
         self.id.agent_pubkey()
     }
@@ -310,6 +324,7 @@ impl Cell {
                         payload,
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     };
 
                     tasks.push(
@@ -393,6 +408,9 @@ impl Cell {
             | QueryOpHashes { .. }
             | QueryAgentInfoSignedNearBasis { .. }
             | QueryPeerDensity { .. }
+            | EgressBlocked { .. }
+            | QueueRemoteSignal { .. }
+            | TakeQueuedRemoteSignals { .. }
             | Publish { .. }
             | FetchOpData { .. } => {
                 // These events are aggregated over a set of cells, so need to be handled at the conductor level.
@@ -426,6 +444,33 @@ impl Cell {
                 .await;
             }
 
+            RemoteQuery {
+                span_context: _,
+                from_agent,
+                signature,
+                zome_name,
+                fn_name,
+                cap_secret,
+                respond,
+                payload,
+                nonce,
+                expires_at,
+                ..
+            } => {
+                async {
+                    let res = self
+                        .handle_remote_query(
+                            from_agent, signature, zome_name, fn_name, cap_secret, payload, nonce,
+                            expires_at,
+                        )
+                        .await
+                        .map_err(holochain_p2p::HolochainP2pError::other);
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
+                .instrument(debug_span!("remote_query"))
+                .await;
+            }
+
             Get {
                 span_context: _,
                 respond,
@@ -497,6 +542,23 @@ impl Cell {
                 .await;
             }
 
+            ProbeBasis {
+                span_context: _,
+                respond,
+                basis,
+                ..
+            } => {
+                async {
+                    let res = self
+                        .handle_probe_basis(basis)
+                        .await
+                        .map_err(holochain_p2p::HolochainP2pError::other);
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
+                .instrument(debug_span!("cell_handle_probe_basis"))
+                .await;
+            }
+
             GetAgentActivity {
                 span_context: _,
                 respond,
@@ -534,6 +596,43 @@ impl Cell {
                 .await;
             }
 
+            GetActionBySeq {
+                span_context: _,
+                respond,
+                author,
+                action_seq,
+                ..
+            } => {
+                async {
+                    let res = self
+                        .handle_get_action_by_seq(author, action_seq)
+                        .await
+                        .map_err(holochain_p2p::HolochainP2pError::other);
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
+                .instrument(debug_span!("cell_handle_get_action_by_seq"))
+                .await;
+            }
+
+            GetEntriesByAuthor {
+                span_context: _,
+                respond,
+                author,
+                entry_type,
+                page,
+                ..
+            } => {
+                async {
+                    let res = self
+                        .handle_get_entries_by_author(author, entry_type, page)
+                        .await
+                        .map_err(holochain_p2p::HolochainP2pError::other);
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
+                .instrument(debug_span!("cell_handle_get_entries_by_author"))
+                .await;
+            }
+
             ValidationReceiptsReceived {
                 span_context: _,
                 respond,
@@ -717,6 +816,25 @@ impl Cell {
         ))
     }
 
+    /// a remote node is asking how many ops we hold for a basis
+    #[instrument(skip(self))]
+    async fn handle_probe_basis(&self, basis: OpBasis) -> CellResult<ProbeBasisResponse> {
+        let op_count = self
+            .space
+            .dht_db
+            .read_async(move |txn| -> DatabaseResult<u32> {
+                Ok(txn.query_row(
+                    "SELECT COUNT(hash) FROM DhtOp WHERE basis_hash = :basis_hash AND when_integrated IS NOT NULL",
+                    named_params! {
+                        ":basis_hash": basis,
+                    },
+                    |row| row.get(0),
+                )?)
+            })
+            .await?;
+        Ok(ProbeBasisResponse { op_count })
+    }
+
     #[instrument(skip(self, options))]
     async fn handle_get_agent_activity(
         &self,
@@ -742,6 +860,31 @@ impl Cell {
             .map_err(Into::into)
     }
 
+    #[instrument(skip(self))]
+    async fn handle_get_action_by_seq(
+        &self,
+        author: AgentPubKey,
+        action_seq: u32,
+    ) -> CellResult<ActionBySeqResponse> {
+        let db = self.space.dht_db.clone();
+        authority::handle_get_action_by_seq(db.into(), author, action_seq)
+            .await
+            .map_err(Into::into)
+    }
+
+    #[instrument(skip(self))]
+    async fn handle_get_entries_by_author(
+        &self,
+        author: AgentPubKey,
+        entry_type: EntryType,
+        page: EntriesPage,
+    ) -> CellResult<EntriesByAuthorResponse> {
+        let db = self.space.dht_db.clone();
+        authority::handle_get_entries_by_author(db.into(), author, entry_type, page)
+            .await
+            .map_err(Into::into)
+    }
+
     /// A remote agent is sending us a validation receipt bundle.
     #[tracing::instrument(skip(self, receipts))]
     async fn handle_validation_receipts(
@@ -751,26 +894,38 @@ impl Cell {
         for receipt in receipts.into_iter() {
             debug!(from = ?receipt.receipt.validators, to = ?self.id.agent_pubkey(), hash = ?receipt.receipt.dht_op_hash);
 
-            // Get the action for this op so we can check the entry type.
+            // Get the action for this op so we can check the entry type,
+            // along with the hashes of all ops produced by that action.
             let hash = receipt.receipt.dht_op_hash.clone();
-            let action: Option<SignedAction> = self
+            let (action, action_op_hashes): (Option<SignedAction>, Vec<DhtOpHash>) = self
                 .get_or_create_authored_db()?
-                .read_async(move |txn| {
-                    let h: Option<Vec<u8>> = txn
+                .read_async(move |txn| -> StateQueryResult<_> {
+                    let h: Option<(Vec<u8>, ActionHash)> = txn
                         .query_row(
-                            "SELECT Action.blob as action_blob
+                            "SELECT Action.blob as action_blob, DhtOp.action_hash as action_hash
                     FROM DhtOp
                     LEFT JOIN Action ON Action.hash = DhtOp.action_hash
                     WHERE DhtOp.hash = :hash",
                             named_params! {
                                 ":hash": hash,
                             },
-                            |row| row.get("action_blob"),
+                            |row| Ok((row.get("action_blob")?, row.get("action_hash")?)),
                         )
                         .optional()?;
                     match h {
-                        Some(h) => from_blob(h),
-                        None => Ok(None),
+                        Some((blob, action_hash)) => {
+                            let op_hashes = txn
+                                .prepare("SELECT hash FROM DhtOp WHERE action_hash = :action_hash")?
+                                .query_map(
+                                    named_params! {
+                                        ":action_hash": action_hash,
+                                    },
+                                    |row| row.get(0),
+                                )?
+                                .collect::<Result<Vec<_>, _>>()?;
+                            Ok((Some(from_blob(blob)?), op_hashes))
+                        }
+                        None => Ok((None, Vec::new())),
                     }
                 })
                 .await?;
@@ -792,7 +947,7 @@ impl Cell {
                                 zome.into_inner().1,
                                 *entry_index,
                             ))
-                            .map(|e| u8::from(e.required_validations)),
+                            .map(|e| u8::from(e.policies.required_validations)),
                         None => None,
                     }
                 }
@@ -805,13 +960,16 @@ impl Cell {
             );
 
             let receipt_op_hash = receipt.receipt.dht_op_hash.clone();
+            let validators = receipt.receipt.validators.clone();
 
-            let receipt_count = self
+            let (receipt_count, first_for_action) = self
                 .space
                 .dht_db
                 .write_async({
                     let receipt_op_hash = receipt_op_hash.clone();
-                    move |txn| -> StateMutationResult<usize> {
+                    move |txn| -> StateMutationResult<(usize, bool)> {
+                        let action_receipts_before = count_valid_for_ops(txn, &action_op_hashes)?;
+
                         // Add the new receipts to the db
                         add_if_unique(txn, receipt)?;
 
@@ -824,11 +982,35 @@ impl Cell {
                             |row| row.get(0),
                         )?;
 
-                        Ok(receipt_count)
+                        let first_for_action = action_receipts_before == 0
+                            && count_valid_for_ops(txn, &action_op_hashes)? > 0;
+
+                        Ok((receipt_count, first_for_action))
                     }
                 })
                 .await?;
 
+            // Let waiting zome calls and the app know that the action has reached
+            // at least one authority.
+            if first_for_action {
+                if let Some(action) = &action {
+                    let action_hash = ActionHash::with_data_sync(action.action());
+                    // It's fine if nobody is waiting or listening.
+                    let _ = self.publish_accepted_tx.send(action_hash.clone());
+                    let signal = ExternIO::encode(PublishAccepted {
+                        action_hash,
+                        validators,
+                    })?;
+                    for zome_name in self.coordinators_of_action(action.action()).await? {
+                        let _ = self.signal_tx.send(Signal::App {
+                            cell_id: self.id.clone(),
+                            zome_name,
+                            signal: AppSignal::new(signal.clone()),
+                        });
+                    }
+                }
+            }
+
             // If we have enough receipts then set receipts to complete.
             if receipt_count >= required_validation_count as usize {
                 // Note that the flag is set in the authored db because that's what the publish workflow checks to decide
@@ -844,6 +1026,66 @@ impl Cell {
         Ok(())
     }
 
+    /// The coordinator zomes which depend on the integrity zome that defines
+    /// the entry or link type of an action, or of the action it deletes.
+    async fn coordinators_of_action(&self, action: &Action) -> CellResult<Vec<ZomeName>> {
+        let deleted = match action {
+            Action::Delete(delete) => Some(delete.deletes_address.clone()),
+            Action::DeleteLink(delete_link) => Some(delete_link.link_add_address.clone()),
+            _ => None,
+        };
+        let deleted = match deleted {
+            Some(hash) => {
+                let get_action = |hash: ActionHash| {
+                    move |txn: Transaction| -> StateQueryResult<Option<SignedAction>> {
+                        txn.query_row(
+                            "SELECT blob FROM Action WHERE hash = :hash",
+                            named_params! {
+                                ":hash": hash,
+                            },
+                            |row| row.get::<_, Vec<u8>>(0),
+                        )
+                        .optional()?
+                        .map(from_blob)
+                        .transpose()
+                    }
+                };
+                // Our own actions are in the authored database, and the actions of
+                // others which we deleted were fetched while validating the delete.
+                let authored = self
+                    .get_or_create_authored_db()?
+                    .read_async(get_action(hash.clone()))
+                    .await?;
+                match authored {
+                    Some(action) => Some(action),
+                    None => match self.dht_db().read_async(get_action(hash)).await? {
+                        Some(action) => Some(action),
+                        None => return Ok(Vec::new()),
+                    },
+                }
+            }
+            None => None,
+        };
+        let action = deleted.as_ref().map_or(action, |deleted| deleted.action());
+        let zome_index = match (action, action.entry_type()) {
+            (_, Some(EntryType::App(AppEntryDef { zome_index, .. }))) => *zome_index,
+            (Action::CreateLink(create_link), _) => create_link.zome_index,
+            _ => return Ok(Vec::new()),
+        };
+        let ribosome = self.conductor_api.get_this_ribosome().map_err(Box::new)?;
+        Ok(ribosome
+            .dna_def()
+            .coordinator_zomes
+            .iter()
+            .filter(|(zome_name, _)| {
+                ribosome
+                    .get_zome_dependencies(zome_name)
+                    .map_or(false, |dependencies| dependencies.contains(&zome_index))
+            })
+            .map(|(zome_name, _)| zome_name.clone())
+            .collect())
+    }
+
     /// the network module would like this cell/agent to sign some data
     #[tracing::instrument(skip(self))]
     async fn handle_sign_network_data(&self) -> CellResult<Signature> {
@@ -874,6 +1116,8 @@ impl Cell {
             fn_name,
             nonce,
             expires_at,
+            wait_for_publish: None,
+            timeout_ms: None,
         };
         // double ? because
         // - ConductorApiResult
@@ -881,6 +1125,39 @@ impl Cell {
         Ok(self.call_zome(invocation, None).await??.try_into()?)
     }
 
+    #[instrument(skip(self, from_agent, fn_name, cap_secret, payload))]
+    #[allow(clippy::too_many_arguments)]
+    /// a remote agent is attempting a read-only "remote_query" on this cell.
+    async fn handle_remote_query(
+        &self,
+        from_agent: AgentPubKey,
+        from_signature: Signature,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap_secret: Option<CapSecret>,
+        payload: ExternIO,
+        nonce: Nonce256Bits,
+        expires_at: Timestamp,
+    ) -> CellResult<SerializedBytes> {
+        let invocation = ZomeCall {
+            cell_id: self.id.clone(),
+            zome_name,
+            cap_secret,
+            payload,
+            provenance: from_agent,
+            signature: from_signature,
+            fn_name,
+            nonce,
+            expires_at,
+            wait_for_publish: None,
+            timeout_ms: None,
+        };
+        Ok(self
+            .run_zome_call(invocation, None, true)
+            .await??
+            .try_into()?)
+    }
+
     /// Function called by the Conductor
     //
     // TODO: when we had CellStatus to track whether a cell had joined the network or not,
@@ -892,6 +1169,17 @@ impl Cell {
         call: ZomeCall,
         workspace_lock: Option<SourceChainWorkspace>,
     ) -> CellResult<ZomeCallResult> {
+        self.run_zome_call(call, workspace_lock, false).await
+    }
+
+    async fn run_zome_call(
+        &self,
+        call: ZomeCall,
+        workspace_lock: Option<SourceChainWorkspace>,
+        read_only: bool,
+    ) -> CellResult<ZomeCallResult> {
+        THROUGHPUT.record_zome_call();
+
         // Only check if init has run if this call is not coming from
         // an already running init call.
         if workspace_lock
@@ -906,6 +1194,15 @@ impl Cell {
 
         let conductor_handle = self.conductor_handle.clone();
         let ribosome = self.get_ribosome()?;
+        let max_wait_for_publish = self
+            .conductor_handle
+            .get_config()
+            .conductor_tuning_params()
+            .max_wait_for_publish();
+        let wait_for_publish = call
+            .wait_for_publish
+            .map(|ms| std::time::Duration::from_millis(ms).min(max_wait_for_publish));
+        let timeout = call.timeout_ms.map(std::time::Duration::from_millis);
         let invocation =
             ZomeCallInvocation::try_from_interface_call(self.conductor_api.clone(), call).await?;
 
@@ -915,18 +1212,17 @@ impl Cell {
         let is_root_zome_call = workspace_lock.is_none();
         let workspace_lock = match workspace_lock {
             Some(l) => l,
-            None => {
-                SourceChainWorkspace::new(
-                    self.get_or_create_authored_db()?,
-                    self.dht_db().clone(),
-                    self.space.dht_query_cache.clone(),
-                    self.cache().clone(),
-                    keystore.clone(),
-                    self.id.agent_pubkey().clone(),
-                    Arc::new(dna_def),
-                )
-                .await?
-            }
+            None => SourceChainWorkspace::new(
+                self.get_or_create_authored_db()?,
+                self.dht_db().clone(),
+                self.space.dht_query_cache.clone(),
+                self.cache().clone(),
+                keystore.clone(),
+                self.id.agent_pubkey().clone(),
+                Arc::new(dna_def),
+            )
+            .await?
+            .with_negative_cache(self.space.negative_cache.clone()),
         };
 
         let args = CallZomeWorkflowArgs {
@@ -936,6 +1232,10 @@ impl Cell {
             signal_tx: self.signal_tx.clone(),
             conductor_handle,
             is_root_zome_call,
+            wait_for_publish,
+            publish_accepted_tx: self.publish_accepted_tx.clone(),
+            read_only,
+            timeout,
         };
         Ok(call_zome_workflow(
             workspace_lock,
@@ -980,12 +1280,17 @@ impl Cell {
             id.agent_pubkey().clone(),
             Arc::new(dna_def.into_content()),
         )
-        .await?;
+        .await?
+        .with_negative_cache(self.space.negative_cache.clone());
 
         // Check if initialization has run
         if workspace.source_chain().zomes_initialized().await? {
             return Ok(());
         }
+        // Init commits to the chain, which a frozen cell can't do.
+        if workspace.source_chain().is_chain_frozen().await? {
+            return Err(CellError::CellFrozen(id));
+        }
         trace!("running init");
 
         // Run the workflow
@@ -1008,6 +1313,39 @@ impl Cell {
         Ok(())
     }
 
+    /// Run the init callbacks of a ribosome against this cell's source chain without
+    /// committing anything, to check new coordinator zomes before they are put to use.
+    ///
+    /// Signals emitted by the callbacks are dropped.
+    pub(crate) async fn smoke_test_init(&self, ribosome: RealRibosome) -> CellResult<InitResult> {
+        use crate::conductor::api::CellConductorApiT;
+
+        let keystore = self.conductor_api.keystore().clone();
+        let dna_def = ribosome.dna_def().as_content().clone();
+        let workspace = SourceChainWorkspace::init_as_root(
+            self.get_or_create_authored_db()?,
+            self.dht_db().clone(),
+            self.space.dht_query_cache.clone(),
+            self.cache().clone(),
+            keystore.clone(),
+            self.id.agent_pubkey().clone(),
+            Arc::new(dna_def.clone()),
+        )
+        .await?;
+        let call_zome_handle =
+            CellConductorApi::new(self.conductor_handle.clone(), self.id.clone())
+                .into_call_zome_handle();
+        let host_access = InitHostAccess::new(
+            workspace.into(),
+            keystore,
+            self.holochain_p2p_cell.clone(),
+            broadcast::channel(1).0,
+            call_zome_handle,
+        );
+        // The workspace is never flushed, so whatever init writes is discarded.
+        Ok(ribosome.run_init(host_access, InitInvocation::new(dna_def))?)
+    }
+
     /// Clean up long-running managed tasks.
     pub async fn cleanup(&self) -> CellResult<()> {
         use holochain_p2p::HolochainP2pDnaT;
@@ -1040,6 +1378,44 @@ impl Cell {
         &self.space.p2p_agents_db
     }
 
+    /// Publish the signed agent info of this cell's agent to the authorities
+    /// for the agent, so peers can find it through the DHT when they can't
+    /// reach a bootstrap service. The info was signed locally, so this node
+    /// holds the op as integrated and valid straight away.
+    pub(crate) async fn publish_agent_info(&self, info: &AgentInfoSigned) -> CellResult<()> {
+        use holochain_p2p::HolochainP2pDnaT;
+
+        let op = holochain_p2p::agent_info_to_op(info)?;
+        let op_size = op.agent_info.len();
+        let op = DhtOpHashed::from_content_sync(DhtOp::from(op));
+        let hash = op.as_hash().clone();
+        self.dht_db()
+            .write_async({
+                let op = op.clone();
+                move |txn| -> StateMutationResult<()> {
+                    insert_op(txn, &op)?;
+                    set_validation_status(txn, op.as_hash(), ValidationStatus::Valid)?;
+                    set_when_integrated(txn, op.as_hash(), Timestamp::now())?;
+                    Ok(())
+                }
+            })
+            .await?;
+
+        let op = op.into_content();
+        self.holochain_p2p_dna()
+            .publish(
+                false,
+                false,
+                op.dht_basis(),
+                self.agent_pubkey().clone(),
+                vec![OpHashSized::new(hash.to_kitsune(), Some(op_size.into()))],
+                None,
+                Some(vec![op]),
+            )
+            .await?;
+        Ok(())
+    }
+
     /// Accessor for the authored database backing this Cell
     pub(crate) fn get_or_create_authored_db(&self) -> CellResult<DbWrite<DbKindAuthored>> {
         Ok(self
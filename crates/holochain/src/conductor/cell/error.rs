@@ -53,6 +53,8 @@ pub enum CellError {
         INIT_MUTEX_TIMEOUT_SECS
     )]
     InitTimeout,
+    #[error("The cell {0:?} is frozen, so its zomes can't be initialized")]
+    CellFrozen(CellId),
     #[error("Failed to get or create the cache for this dna {0:?}")]
     FailedToCreateCache(Box<ConductorError>),
     #[error("Failed to get or create the authored db for this dna {0:?}")]
@@ -31,7 +31,7 @@
 //! ```
 //!
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
@@ -52,14 +52,25 @@ pub use builder::*;
 use holo_hash::DnaHash;
 use holochain_conductor_api::conductor::KeystoreConfig;
 use holochain_conductor_api::AppInfo;
+use holochain_conductor_api::AppNetworkMode;
 use holochain_conductor_api::AppStatusFilter;
+use holochain_conductor_api::DhtArchiveChunk;
+use holochain_conductor_api::DhtOpDump;
 use holochain_conductor_api::FullIntegrationStateDump;
 use holochain_conductor_api::FullStateDump;
 use holochain_conductor_api::IntegrationStateDump;
 use holochain_conductor_api::JsonDump;
+use holochain_conductor_api::LoadSheddingStats;
+use holochain_conductor_api::NonceAudit;
+use holochain_conductor_api::NonceWindow;
+use holochain_conductor_api::PeerInfo;
+use holochain_conductor_api::RejectedNonce;
+use holochain_conductor_api::StateDumpItems;
+use holochain_conductor_api::StateDumpPage;
+use holochain_conductor_api::StateDumpStore;
+use holochain_conductor_api::WorkflowStats;
+use holochain_conductor_api::MAX_STATE_DUMP_PAGE_LIMIT;
 pub use holochain_conductor_services::*;
-use holochain_keystore::lair_keystore::spawn_lair_keystore;
-use holochain_keystore::lair_keystore::spawn_lair_keystore_in_proc;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::actor::HolochainP2pRefToDna;
 use holochain_p2p::event::HolochainP2pEvent;
@@ -76,14 +87,20 @@ use holochain_zome_types::prelude::ClonedCell;
 use kitsune_p2p::agent_store::AgentInfoSigned;
 
 use crate::conductor::cell::Cell;
+use crate::conductor::conductor::admin_auth_token_store::AdminAuthTokenStore;
 use crate::conductor::conductor::app_auth_token_store::AppAuthTokenStore;
 use crate::conductor::conductor::app_broadcast::AppBroadcast;
+use crate::conductor::conductor::dht_archive_transfer::*;
+use crate::conductor::conductor::firehose::Firehose;
+use crate::conductor::conductor::load_shedding::{LoadShedder, SheddableRequest};
+use crate::conductor::conductor::nonce_rejections::NonceRejections;
 use crate::conductor::config::ConductorConfig;
 use crate::conductor::error::ConductorResult;
 use crate::conductor::metrics::create_p2p_event_duration_metric;
 use crate::conductor::p2p_agent_store::get_single_agent_info;
 use crate::conductor::p2p_agent_store::list_all_agent_info;
 use crate::conductor::p2p_agent_store::query_peer_density;
+use crate::core::metrics::WorkflowQueueRegistry;
 use crate::core::queue_consumer::InitialQueueTriggers;
 use crate::core::queue_consumer::QueueConsumerMap;
 #[cfg(any(test, feature = "test_utils"))]
@@ -101,12 +118,14 @@ use crate::{
 use super::api::AppInterfaceApi;
 use super::api::ZomeCall;
 use super::config::AdminInterfaceConfig;
+use super::config::FirehoseInterfaceConfig;
 use super::config::InterfaceDriver;
 use super::entry_def_store::get_entry_defs;
 use super::error::ConductorError;
 use super::interface::error::InterfaceResult;
 use super::interface::websocket::spawn_admin_interface_tasks;
 use super::interface::websocket::spawn_app_interface_task;
+use super::interface::websocket::spawn_firehose_interface_task;
 use super::interface::websocket::spawn_websocket_listener;
 use super::manager::TaskManagerResult;
 use super::p2p_agent_store;
@@ -129,10 +148,20 @@ mod chc;
 
 mod graft_records_onto_source_chain;
 
+mod dht_archive_transfer;
+
+mod admin_auth_token_store;
+
 mod app_auth_token_store;
 
 pub(crate) mod app_broadcast;
 
+pub(crate) mod firehose;
+
+pub(crate) mod load_shedding;
+
+mod nonce_rejections;
+
 #[cfg(test)]
 pub mod tests;
 
@@ -143,6 +172,31 @@ pub mod tests;
 /// of an app having full network access as soon as its UI begins making requests.
 pub const JOIN_NETWORK_WAITING_PERIOD: std::time::Duration = std::time::Duration::from_secs(5);
 
+/// How often the conductor checks for deleted entries whose purge policy
+/// retention has expired, and drops them from the DHT databases.
+pub const ENTRY_PURGE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// How often the conductor evicts data which has been cached for longer than
+/// the startup profiles of their DNAs allow.
+pub const CACHE_EVICTION_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// How often the conductor checks for clone cells which the clone archival
+/// policies of their apps consider inactive, and archives them.
+pub const CLONE_ARCHIVAL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// How often the conductor checks for durable remote calls which are due to be
+/// attempted again.
+pub const DURABLE_CALL_OUTBOX_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// The longest the conductor waits between two attempts of a durable remote call.
+pub const MAX_DURABLE_CALL_BACKOFF: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// The most durable remote calls the conductor attempts at once.
+pub const MAX_CONCURRENT_DURABLE_CALLS: usize = 16;
+
+/// The most ops an op dependency graph covers before it is cut short.
+pub const MAX_OP_DEPENDENCY_GRAPH_OPS: usize = 1000;
+
 /// A list of Cells which failed to start, and why
 pub type CellStartupErrors = Vec<(CellId, CellError)>;
 
@@ -220,6 +274,9 @@ pub struct Conductor {
     /// the dynamically allocated port later.
     admin_websocket_ports: RwShare<Vec<u16>>,
 
+    /// The port of the firehose interface, if one is configured.
+    firehose_websocket_port: RwShare<Option<u16>>,
+
     /// The interface to the task manager
     task_manager: TaskManagerClient,
 
@@ -249,8 +306,46 @@ pub struct Conductor {
 
     app_auth_token_store: RwShare<AppAuthTokenStore>,
 
+    admin_auth_token_store: RwShare<AdminAuthTokenStore>,
+
     /// Container to connect app signals to app interfaces, by installed app id.
     app_broadcast: AppBroadcast,
+
+    /// Container to connect integrated ops to the firehose interface, by DNA hash.
+    firehose: Firehose,
+
+    /// The network mode of each cell whose app has been taken out of
+    /// [`AppNetworkMode::Normal`]. Not persisted.
+    cell_network_modes: RwShare<HashMap<CellId, AppNetworkMode>>,
+
+    /// What gets do with data by warranted authors, for each cell they have
+    /// been looked up for, so that gets don't read the conductor state.
+    /// Cleared whenever an app is installed or removed.
+    warranted_authors: RwShare<HashMap<CellId, WarrantedAuthors>>,
+
+    /// The zome call timeout of the app each cell belongs to, in milliseconds,
+    /// for each cell it has been looked up for, so that zome calls don't read
+    /// the conductor state. Cleared whenever an app is installed or removed.
+    zome_call_timeouts: RwShare<HashMap<CellId, Option<u64>>>,
+
+    /// The DHT archives being exported over the admin API in chunks. Not persisted.
+    dht_archive_exports: DhtArchiveTransfers,
+
+    /// The DHT archives being imported over the admin API in chunks. Not persisted.
+    dht_archive_imports: DhtArchiveTransfers,
+
+    /// The state of this conductor's instrumented work queues. Not persisted.
+    workflow_queues: Arc<WorkflowQueueRegistry>,
+
+    /// Refuses low priority requests from other peers while the workflows are lagging.
+    load_shedder: LoadShedder,
+
+    /// Decides when the rejected zome call nonces are recorded in the event journal.
+    nonce_rejections: NonceRejections,
+
+    /// Wakes the durable call outbox task when a call is queued, so that the
+    /// call is attempted straight away.
+    durable_call_wakeup: tokio::sync::Notify,
 }
 
 impl Conductor {
@@ -289,6 +384,13 @@ mod startup_shutdown_impls {
                 .data_root_path
                 .clone()
                 .map(|path| PathBuf::from(path.deref()));
+            let workflow_queues = Arc::new(WorkflowQueueRegistry::new());
+            let load_shedder = LoadShedder::new(
+                config
+                    .conductor_tuning_params()
+                    .load_shedding_lag_threshold(),
+                workflow_queues.clone(),
+            );
 
             Self {
                 spaces,
@@ -299,6 +401,7 @@ mod startup_shutdown_impls {
                 // Must be initialized later, since it requires an Arc<Conductor>
                 outcomes_task: RwShare::new(None),
                 admin_websocket_ports: RwShare::new(Vec::new()),
+                firehose_websocket_port: RwShare::new(None),
                 scheduler: Arc::new(parking_lot::Mutex::new(None)),
                 ribosome_store,
                 keystore,
@@ -309,7 +412,18 @@ mod startup_shutdown_impls {
                     maybe_data_root_path,
                 ))),
                 app_auth_token_store: RwShare::default(),
+                admin_auth_token_store: RwShare::default(),
                 app_broadcast: AppBroadcast::default(),
+                firehose: Firehose::default(),
+                cell_network_modes: RwShare::new(HashMap::new()),
+                warranted_authors: RwShare::new(HashMap::new()),
+                zome_call_timeouts: RwShare::new(HashMap::new()),
+                dht_archive_exports: DhtArchiveTransfers::default(),
+                dht_archive_imports: DhtArchiveTransfers::default(),
+                workflow_queues,
+                load_shedder,
+                nonce_rejections: NonceRejections::default(),
+                durable_call_wakeup: tokio::sync::Notify::new(),
             }
         }
 
@@ -356,6 +470,8 @@ mod startup_shutdown_impls {
             admin_configs: Vec<AdminInterfaceConfig>,
         ) -> ConductorResult<CellStartupErrors> {
             self.load_dnas().await?;
+            self.update_host_fn_allowlists().await?;
+            self.update_egress_policies().await?;
 
             info!("Conductor startup: DNAs loaded.");
 
@@ -387,10 +503,18 @@ mod startup_shutdown_impls {
 
             info!("Conductor startup: admin interface(s) added.");
 
+            if let Some(config) = self.config.firehose_interface.clone() {
+                self.clone().add_firehose_interface(config).await?;
+
+                info!("Conductor startup: firehose interface added.");
+            }
+
             self.clone().startup_app_interfaces().await?;
 
             info!("Conductor startup: app interfaces started.");
 
+            self.start_signal_persistence().await?;
+
             // We don't care what fx are returned here, since all cells need to
             // be spun up
             let _ = self.start_paused_apps().await?;
@@ -421,11 +545,11 @@ mod interface_impls {
             let tm = self.task_manager();
 
             // Closure to process each admin config item
-            let spawn_from_config = |AdminInterfaceConfig { driver, .. }| {
+            let spawn_from_config = |config: AdminInterfaceConfig| {
                 let admin_api = admin_api.clone();
                 let tm = tm.clone();
                 async move {
-                    match driver {
+                    match config.driver {
                         InterfaceDriver::Websocket {
                             port,
                             allowed_origins,
@@ -437,6 +561,7 @@ mod interface_impls {
                                 listener,
                                 admin_api.clone(),
                                 port,
+                                config.require_token,
                             );
 
                             InterfaceResult::Ok(port)
@@ -473,6 +598,8 @@ mod interface_impls {
             allowed_origins: AllowedOrigins,
             installed_app_id: Option<InstalledAppId>,
         ) -> ConductorResult<u16> {
+            // Interfaces which already exist are re-attached by id on startup.
+            let newly_attached = port.is_left();
             let interface_id = match port {
                 either::Either::Left(port) => AppInterfaceId::new(port),
                 either::Either::Right(id) => id,
@@ -491,21 +618,69 @@ mod interface_impls {
                 installed_app_id.clone(),
                 app_api,
                 self.app_broadcast.clone(),
+                (&self.get_config().conductor_tuning_params()).into(),
             )
             .await
             .map_err(Box::new)?;
 
-            let config = AppInterfaceConfig::websocket(port, allowed_origins, installed_app_id);
+            let config =
+                AppInterfaceConfig::websocket(port, allowed_origins, installed_app_id.clone());
             self.update_state(|mut state| {
                 state.app_interfaces.insert(interface_id, config);
 
                 Ok(state)
             })
             .await?;
+            if newly_attached {
+                self.record_event(ConductorEvent::AppInterfaceAttached {
+                    port,
+                    installed_app_id,
+                })
+                .await;
+            }
             debug!("App interface added at port: {}", port);
             Ok(port)
         }
 
+        /// Spawn the firehose interface task, register it with the TaskManager,
+        /// and modify the conductor accordingly, based on the config passed in.
+        ///
+        /// Returns the given or auto-chosen port number if giving an Ok Result
+        #[tracing::instrument(skip_all)]
+        pub async fn add_firehose_interface(
+            self: Arc<Self>,
+            config: FirehoseInterfaceConfig,
+        ) -> ConductorResult<u16> {
+            let port = async {
+                match config.driver {
+                    InterfaceDriver::Websocket {
+                        port,
+                        allowed_origins,
+                    } => {
+                        let listener = spawn_websocket_listener(port, allowed_origins).await?;
+                        let port = listener.local_addrs()?[0].port();
+                        spawn_firehose_interface_task(
+                            self.task_manager(),
+                            listener,
+                            self.clone(),
+                            port,
+                        );
+                        InterfaceResult::Ok(port)
+                    }
+                }
+            }
+            .await
+            .map_err(Box::new)?;
+            self.firehose_websocket_port.share_mut(|p| *p = Some(port));
+            debug!("Firehose interface added at port: {}", port);
+            Ok(port)
+        }
+
+        /// The port of the firehose interface, if one has been added.
+        pub fn get_firehose_websocket_port(&self) -> Option<u16> {
+            self.firehose_websocket_port.share_ref(|p| *p)
+        }
+
         /// Returns a port which is guaranteed to have a websocket listener with an Admin interface
         /// on it. Useful for specifying port 0 and letting the OS choose a free port.
         pub fn get_arbitrary_admin_websocket_port(&self) -> Option<u16> {
@@ -809,33 +984,102 @@ mod dna_impls {
 
             Ok(())
         }
+
+        /// Apply the host function allowlists which installed apps declare
+        /// in their manifests to the ribosomes of their DNAs, including clones.
+        ///
+        /// If more than one app declares an allowlist for the same DNA, a zome
+        /// may only use the classes of host functions which all of them allow.
+        pub(crate) async fn update_host_fn_allowlists(&self) -> ConductorResult<()> {
+            let state = self.get_state().await?;
+            let mut allowlists: HashMap<DnaHash, HostFnAllowlist> = HashMap::new();
+            for app in state.installed_apps().values() {
+                let AppManifest::V1(manifest) = app.manifest();
+                for role in &manifest.roles {
+                    let Some(host_fns) = &role.dna.host_fns else {
+                        continue;
+                    };
+                    let Ok(assignment) = app.role(&role.name) else {
+                        continue;
+                    };
+                    let clones = app
+                        .clone_cells_for_role_name(&role.name)
+                        .into_iter()
+                        .chain(app.disabled_clone_cells_for_role_name(&role.name))
+                        .flat_map(|clones| clones.values());
+                    for cell_id in std::iter::once(assignment.cell_id()).chain(clones) {
+                        allowlists
+                            .entry(cell_id.dna_hash().clone())
+                            .and_modify(|allowlist| *allowlist = allowlist.intersection(host_fns))
+                            .or_insert_with(|| host_fns.clone());
+                    }
+                }
+            }
+            self.ribosome_store
+                .share_mut(|d| d.set_host_fn_allowlists(allowlists));
+            Ok(())
+        }
     }
 }
 
 /// Network-related methods
 mod network_impls {
+    use std::io::{Read, Seek, Write};
     use std::time::Duration;
 
     use futures::future::join_all;
     use rusqlite::params;
 
+    #[cfg(feature = "op_replay")]
+    use holochain_conductor_api::ValidationReplayReport;
     use holochain_conductor_api::{
-        CellInfo, DnaStorageInfo, NetworkInfo, StorageBlob, StorageInfo,
+        AuthorityProbe, CellInfo, DnaStorageInfo, Neighbor, Neighborhood, NetworkInfo,
+        PendingDbMigration, StorageBlob, StorageInfo,
     };
+    use holochain_p2p::actor::EgressPolicyMap;
+    use holochain_p2p::dht::prelude::{
+        Arq, ArqSet, ArqStart, RegionCoords, RegionSetLtcs, SpaceOffset, Topology,
+    };
+    use holochain_p2p::dht_arc::DhtArc;
     use holochain_p2p::HolochainP2pSender;
+    use holochain_p2p::ProbeBasisResponse;
     use holochain_sqlite::stats::{get_size_on_disk, get_used_size};
     use holochain_zome_types::block::Block;
     use holochain_zome_types::block::BlockTargetId;
+    use kitsune_p2p::metrics::ProtocolViolation;
     use kitsune_p2p::KitsuneAgent;
     use kitsune_p2p::KitsuneBinType;
 
     use crate::conductor::api::error::{
         zome_call_response_to_conductor_api_result, ConductorApiError,
     };
+    use crate::conductor::kitsune_host_impl;
+    use crate::core::sys_validate::SysValidationError;
+    #[cfg(feature = "op_replay")]
+    use crate::core::workflow::replay_validation_workflow::*;
+    use crate::core::workflow::WorkflowError;
 
     use super::*;
 
     impl Conductor {
+        /// Apply the egress policies which apps were installed with to their
+        /// cells, including clones. Other cells in the same spaces, such as
+        /// those of other apps, aren't restricted by them.
+        pub(crate) async fn update_egress_policies(&self) -> ConductorResult<()> {
+            let state = self.get_state().await?;
+            let mut policies = EgressPolicyMap::new();
+            for app in state.installed_apps().values() {
+                let Some(policy) = state.egress_policy(app.id()) else {
+                    continue;
+                };
+                for cell_id in app.all_cells() {
+                    policies.insert(cell_id.clone(), policy.clone());
+                }
+            }
+            self.holochain_p2p.set_egress_policies(policies).await?;
+            Ok(())
+        }
+
         /// Get signed agent info from the conductor
         pub async fn get_agent_infos(
             &self,
@@ -862,20 +1106,166 @@ mod network_impls {
             }
         }
 
+        /// List every agent info in the peer store of a DNA, along with whether
+        /// each peer is still live.
+        pub async fn list_peers(&self, dna_hash: &DnaHash) -> ConductorApiResult<Vec<PeerInfo>> {
+            let db: DbRead<DbKindP2pAgents> = self.p2p_agents_db(dna_hash).into();
+            let now = Timestamp::now();
+            Ok(db
+                .p2p_list_all_agents()
+                .await?
+                .into_iter()
+                .map(|agent_info| PeerInfo::new(agent_info, now))
+                .collect())
+        }
+
+        /// Remove the agent infos which had expired by `expired_by` from the
+        /// peer stores of all DNAs. Unexpired agent infos are never removed,
+        /// including those of peers which left and advertise no urls.
+        ///
+        /// Returns the number of agent infos which were removed.
+        pub async fn prune_peers(&self, expired_by: Timestamp) -> ConductorApiResult<usize> {
+            let expired_by_ms = expired_by.as_millis().max(0) as u64;
+            let mut pruned = 0;
+            for db in self.spaces.get_from_spaces(|s| s.p2p_agents_db.clone()) {
+                pruned += p2p_prune_stale(&db, expired_by_ms).await?.len();
+            }
+            Ok(pruned)
+        }
+
+        /// Change how the cells of an app take part in the network.
+        ///
+        /// Gossip is turned off or on for the app's cells straight away, and cells
+        /// which haven't joined the network yet pick up the mode when they do. Publishing
+        /// is checked by each cell's publish workflow, which is triggered when
+        /// publishing is turned back on so that ops authored meanwhile go out.
+        pub async fn set_app_network_mode(
+            &self,
+            installed_app_id: &InstalledAppId,
+            mode: AppNetworkMode,
+        ) -> ConductorResult<()> {
+            let state = self.get_state().await?;
+            let cell_ids: Vec<CellId> = state
+                .get_app(installed_app_id)?
+                .all_cells()
+                .cloned()
+                .collect();
+            self.cell_network_modes.share_mut(|modes| {
+                for cell_id in &cell_ids {
+                    if mode == AppNetworkMode::Normal {
+                        modes.remove(cell_id);
+                    } else {
+                        modes.insert(cell_id.clone(), mode);
+                    }
+                }
+            });
+
+            for cell_id in cell_ids {
+                match self
+                    .holochain_p2p
+                    .set_agent_gossip(
+                        cell_id.dna_hash().clone(),
+                        cell_id.agent_pubkey().clone(),
+                        mode.gossip(),
+                    )
+                    .await
+                {
+                    // The cell has never joined, it will pick up the mode when it does.
+                    Err(holochain_p2p::HolochainP2pError::RoutingDnaError(_)) => continue,
+                    r => r?,
+                }
+                if mode.publish() {
+                    if let Some(cell) = self
+                        .running_cells
+                        .share_ref(|c| c.get(&cell_id).map(|item| item.cell.clone()))
+                    {
+                        cell.triggers()
+                            .publish_dht_ops
+                            .trigger(&"set_app_network_mode");
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        /// The network mode of the app a cell belongs to.
+        pub fn cell_network_mode(&self, cell_id: &CellId) -> AppNetworkMode {
+            self.cell_network_modes
+                .share_ref(|modes| modes.get(cell_id).copied())
+                .unwrap_or_default()
+        }
+
+        /// Freeze a running cell, so that its state can be investigated without
+        /// racing ongoing mutations.
+        ///
+        /// The freeze is recorded in the cell's authored database and checked
+        /// whenever its source chain is written, so zome calls which would commit
+        /// anything fail with
+        /// [`RibosomeError::CellFrozen`](crate::core::ribosome::error::RibosomeError::CellFrozen),
+        /// and countersigning sessions and grafting are refused. The cell stops publishing, while
+        /// reads, gossip and incoming ops carry on as usual. A frozen cell stays
+        /// frozen across restarts until it is unfrozen.
+        pub async fn freeze_cell(&self, cell_id: &CellId) -> ConductorResult<()> {
+            self.cell_by_id(cell_id).await?;
+            let author = cell_id.agent_pubkey().clone();
+            self.get_or_create_authored_db(cell_id.dna_hash(), author.clone())?
+                .write_async(move |txn| holochain_state::mutations::freeze_chain(txn, &author))
+                .await?;
+            tracing::warn!(?cell_id, "Cell frozen");
+            Ok(())
+        }
+
+        /// Unfreeze a cell, and publish anything it authored before it was frozen
+        /// which hasn't been published yet.
+        pub async fn unfreeze_cell(&self, cell_id: &CellId) -> ConductorResult<()> {
+            let cell = self.cell_by_id(cell_id).await?;
+            let author = cell_id.agent_pubkey().clone();
+            self.get_or_create_authored_db(cell_id.dna_hash(), author.clone())?
+                .write_async(move |txn| holochain_state::mutations::unfreeze_chain(txn, &author))
+                .await?;
+            tracing::info!(?cell_id, "Cell unfrozen");
+            cell.triggers().publish_dht_ops.trigger(&"unfreeze_cell");
+            Ok(())
+        }
+
+        /// Whether a cell has been frozen with [`Conductor::freeze_cell`].
+        pub async fn is_cell_frozen(&self, cell_id: &CellId) -> ConductorResult<bool> {
+            let author = cell_id.agent_pubkey().clone();
+            Ok(self
+                .get_or_create_authored_db(cell_id.dna_hash(), author.clone())?
+                .read_async(move |txn| holochain_state::chain_lock::is_chain_frozen(&txn, &author))
+                .await?)
+        }
+
         pub(crate) async fn witness_nonce_from_calling_agent(
             &self,
             agent: AgentPubKey,
             nonce: Nonce256Bits,
             expires: Timestamp,
         ) -> ConductorResult<WitnessNonceResult> {
-            Ok(witness_nonce(
+            let result = witness_nonce(
                 &self.spaces.conductor_db,
-                agent,
+                agent.clone(),
                 nonce,
                 Timestamp::now(),
                 expires,
             )
-            .await?)
+            .await?;
+            let reason = match result {
+                WitnessNonceResult::Fresh => None,
+                WitnessNonceResult::Duplicate => Some(NonceRejection::Duplicate),
+                WitnessNonceResult::Expired => Some(NonceRejection::Expired),
+                WitnessNonceResult::Future => Some(NonceRejection::Future),
+            };
+            if let Some(reason) = reason {
+                let events = self
+                    .nonce_rejections
+                    .reject(agent, reason, std::time::Instant::now());
+                for event in events {
+                    self.record_event(event).await;
+                }
+            }
+            Ok(result)
         }
 
         /// Block some target.
@@ -962,18 +1352,20 @@ mod network_impls {
                 // query number of agents from peer db
                 let db = { self.p2p_agents_db(dna) };
 
-                let (current_number_of_peers, arc_size, total_network_peers) = db
+                let (current_number_of_peers, arc_size, total_network_peers, storage_arc) = db
                     .read_async({
                         let agent_pub_key = agent_pub_key.clone();
                         let space = dna.clone().into_kitsune();
-                        move |txn| -> DatabaseResult<(u32, f64, u32)> {
+                        move |txn| -> DatabaseResult<(u32, f64, u32, Option<DhtArc>)> {
                             let current_number_of_peers = txn.p2p_count_agents(space.clone())?;
 
                             // query arc size and extrapolated coverage and estimate total peers
-                            let (arc_size, total_network_peers) = match txn.p2p_get_agent(
+                            let agent = txn.p2p_get_agent(
                                 space.clone(),
                                 &KitsuneAgent::new(agent_pub_key.get_raw_36().to_vec()),
-                            )? {
+                            )?;
+                            let storage_arc = agent.as_ref().map(|agent| agent.storage_arc());
+                            let (arc_size, total_network_peers) = match agent {
                                 None => (0.0, 0),
                                 Some(agent) => {
                                     let arc_size = agent.storage_arc().coverage();
@@ -993,7 +1385,12 @@ mod network_impls {
                                 }
                             };
 
-                            Ok((current_number_of_peers, arc_size, total_network_peers))
+                            Ok((
+                                current_number_of_peers,
+                                arc_size,
+                                total_network_peers,
+                                storage_arc,
+                            ))
                         }
                     })
                     .await?;
@@ -1038,6 +1435,14 @@ mod network_impls {
                     .await?;
                 let bytes_since_last_time_queried = dht_bytes_received + cache_bytes_received;
 
+                // the size of the data held within the agent's storage arc
+                let arc_data_size = match storage_arc {
+                    Some(arc) => {
+                        kitsune_host_impl::query_arc_storage_size(dht_db.into(), arc).await?
+                    }
+                    None => 0,
+                };
+
                 // calculate open peer connections based on current gossip sessions
                 let completed_rounds_since_last_time_queried = diagnostics
                     .metrics
@@ -1061,6 +1466,7 @@ mod network_impls {
                     total_network_peers,
                     bytes_since_last_time_queried,
                     completed_rounds_since_last_time_queried,
+                    arc_data_size,
                 })
             }))
             .await
@@ -1068,100 +1474,563 @@ mod network_impls {
             .collect::<Result<Vec<_>, _>>()
         }
 
-        #[tracing::instrument(skip_all)]
-        pub(crate) async fn storage_info(&self) -> ConductorResult<StorageInfo> {
-            let state = self.get_state().await?;
+        /// Aggregate the validation receipts received for each op of an
+        /// action authored by one of the app's cells.
+        pub(crate) async fn publication_proof(
+            &self,
+            installed_app_id: &InstalledAppId,
+            payload: &PublicationProofRequestPayload,
+        ) -> ConductorResult<PublicationProof> {
+            let PublicationProofRequestPayload {
+                cell_id,
+                action_hash,
+            } = payload;
 
-            let all_dna: HashMap<DnaHash, Vec<InstalledAppId>> = HashMap::new();
-            let all_dna =
-                state
-                    .installed_apps()
-                    .iter()
-                    .fold(all_dna, |mut acc, (installed_app_id, app)| {
-                        for dna_hash in app.all_cells().map(|cell_id| cell_id.dna_hash()) {
-                            acc.entry(dna_hash.clone())
-                                .or_default()
-                                .push(installed_app_id.clone());
-                        }
+            let in_app = self
+                .find_app_containing_cell(cell_id)
+                .await?
+                .is_some_and(|app| app.id() == installed_app_id);
+            if !in_app {
+                return Err(ConductorError::AppAccessError(
+                    installed_app_id.clone(),
+                    Box::new(cell_id.clone()),
+                ));
+            }
 
-                        acc
-                    });
+            // The ops are in the author's db, while the receipts the author
+            // has received are stored in the dht db.
+            let action_ops = self
+                .get_or_create_authored_db(cell_id.dna_hash(), cell_id.agent_pubkey().clone())?
+                .read_async({
+                    let action_hash = action_hash.clone();
+                    move |txn| list_action_ops(&txn, &action_hash)
+                })
+                .await?;
 
-            let app_data_blobs =
-                futures::future::join_all(all_dna.iter().map(|(dna_hash, used_by)| async {
-                    self.storage_info_for_dna(dna_hash, used_by).await
-                }))
-                .await
-                .into_iter()
-                .collect::<Result<Vec<StorageBlob>, ConductorError>>()?;
+            let ops = self
+                .get_or_create_dht_db(cell_id.dna_hash())?
+                .read_async(move |txn| {
+                    action_ops
+                        .into_iter()
+                        .map(|(op_hash, op_type)| {
+                            let receipts = list_receipts(&txn, &op_hash)?
+                                .into_iter()
+                                .map(PublicationReceipt::from)
+                                .collect();
+                            StateQueryResult::Ok(OpPublicationProof {
+                                op_hash,
+                                op_type,
+                                receipts,
+                            })
+                        })
+                        .collect::<StateQueryResult<Vec<_>>>()
+                })
+                .await?;
 
-            Ok(StorageInfo {
-                blobs: app_data_blobs,
+            Ok(PublicationProof {
+                action_hash: action_hash.clone(),
+                ops,
             })
         }
 
-        async fn storage_info_for_dna(
+        /// List the peers in the peer store whose storage arcs cover the location
+        /// of one of the app's cells.
+        pub(crate) async fn neighborhood(
+            &self,
+            installed_app_id: &InstalledAppId,
+            cell_id: &CellId,
+        ) -> ConductorResult<Neighborhood> {
+            use holochain_p2p::AgentPubKeyExt;
+
+            let in_app = self
+                .find_app_containing_cell(cell_id)
+                .await?
+                .is_some_and(|app| app.id() == installed_app_id);
+            if !in_app {
+                return Err(ConductorError::AppAccessError(
+                    installed_app_id.clone(),
+                    Box::new(cell_id.clone()),
+                ));
+            }
+
+            let agent_location = cell_id.agent_pubkey().get_loc().as_u32();
+            let now = Timestamp::now();
+            let db: DbRead<DbKindP2pAgents> = self.p2p_agents_db(cell_id.dna_hash()).into();
+
+            let mut own_arc = None;
+            let mut known_peers = 0;
+            let mut neighbors = Vec::new();
+            for info in db.p2p_list_all_agents().await? {
+                let agent = AgentPubKey::from_kitsune(&info.agent);
+                let arc = info.storage_arc();
+                if &agent == cell_id.agent_pubkey() {
+                    own_arc = Some(arc);
+                    continue;
+                }
+                known_peers += 1;
+                if arc.dist(agent_location) == 0 {
+                    let peer = PeerInfo::new(info, now);
+                    neighbors.push(Neighbor {
+                        agent,
+                        arc_size: arc.coverage(),
+                        expires_at: peer.expires_at,
+                        expired: peer.expired,
+                        reachable: peer.reachable,
+                    });
+                }
+            }
+
+            Ok(Neighborhood::new(
+                agent_location,
+                own_arc.as_ref().map_or(0.0, |arc| arc.coverage()),
+                own_arc.is_some_and(|arc| arc.dist(agent_location) == 0),
+                neighbors,
+                known_peers,
+            ))
+        }
+
+        /// List the functions each coordinator zome of one of the app's cells
+        /// exposes to zome calls.
+        pub(crate) async fn list_zome_functions(
+            &self,
+            installed_app_id: &InstalledAppId,
+            cell_id: &CellId,
+        ) -> ConductorResult<BTreeMap<ZomeName, Vec<FunctionName>>> {
+            let in_app = self
+                .find_app_containing_cell(cell_id)
+                .await?
+                .is_some_and(|app| app.id() == installed_app_id);
+            if !in_app {
+                return Err(ConductorError::AppAccessError(
+                    installed_app_id.clone(),
+                    Box::new(cell_id.clone()),
+                ));
+            }
+
+            let ribosome = self.get_ribosome(cell_id.dna_hash())?;
+            Ok((*ribosome.zome_fns).clone())
+        }
+
+        /// Ask each authority for a basis, as known to the local peer store,
+        /// how many ops it holds for the basis.
+        /// The probes aren't made for a cell, so no egress policy applies to them.
+        pub(crate) async fn probe_basis(
             &self,
             dna_hash: &DnaHash,
-            used_by: &Vec<InstalledAppId>,
-        ) -> ConductorResult<StorageBlob> {
-            let authored_dbs = self.spaces.get_all_authored_dbs(dna_hash)?;
-            let dht_db = self.spaces.dht_db(dna_hash)?;
-            let cache_db = self.spaces.cache(dna_hash)?;
+            basis: OpBasis,
+        ) -> ConductorResult<Vec<AuthorityProbe>> {
+            use holochain_p2p::AgentPubKeyExt;
 
-            Ok(StorageBlob::Dna(DnaStorageInfo {
-                authored_data_size_on_disk: join_all(
-                    authored_dbs
-                        .iter()
-                        .map(|db| db.read_async(get_size_on_disk)),
-                )
-                .await
-                .into_iter()
-                .map(|r| r.map_err(ConductorError::DatabaseError))
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .sum(),
-                authored_data_size: join_all(
-                    authored_dbs.iter().map(|db| db.read_async(get_used_size)),
-                )
-                .await
-                .into_iter()
-                .map(|r| r.map_err(ConductorError::DatabaseError))
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .sum(),
-                dht_data_size_on_disk: dht_db
-                    .read_async(get_size_on_disk)
-                    .map_err(ConductorError::DatabaseError)
-                    .await?,
-                dht_data_size: dht_db
-                    .read_async(get_used_size)
-                    .map_err(ConductorError::DatabaseError)
-                    .await?,
-                cache_data_size_on_disk: cache_db
-                    .read_async(get_size_on_disk)
-                    .map_err(ConductorError::DatabaseError)
-                    .await?,
-                cache_data_size: cache_db
-                    .read_async(get_used_size)
-                    .map_err(ConductorError::DatabaseError)
-                    .await?,
-                used_by: used_by.clone(),
+            let basis_loc = basis.get_loc().as_u32();
+            let authorities: Vec<AgentPubKey> = list_all_agent_info_signed_near_basis(
+                self.p2p_agents_db(dna_hash).into(),
+                dna_hash.to_kitsune(),
+                basis_loc,
+                u32::MAX,
+            )
+            .await?
+            .into_iter()
+            // Agents are sorted by distance, and only those at zero distance hold the basis.
+            .take_while(|info| info.storage_arc().dist(basis_loc) == 0)
+            .map(|info| AgentPubKey::from_kitsune(&info.agent))
+            .collect();
+
+            Ok(join_all(authorities.into_iter().map(|agent| {
+                let basis = basis.clone();
+                async move {
+                    let start = std::time::Instant::now();
+                    let res = self
+                        .holochain_p2p
+                        .probe_basis(dna_hash.clone(), None, agent.clone(), basis, None)
+                        .await;
+                    let round_trip_ms = start.elapsed().as_millis() as u64;
+                    match res {
+                        Ok(ProbeBasisResponse { op_count }) => AuthorityProbe {
+                            agent,
+                            op_count: Some(op_count),
+                            round_trip_ms: Some(round_trip_ms),
+                            error: None,
+                        },
+                        Err(err) => AuthorityProbe {
+                            agent,
+                            op_count: None,
+                            round_trip_ms: None,
+                            error: Some(err.to_string()),
+                        },
+                    }
+                }
             }))
+            .await)
         }
 
-        #[instrument(skip(self))]
-        pub(crate) async fn dispatch_holochain_p2p_event(
+        /// Summarise all the ops held for a DNA as a set of regions covering the full DHT arc,
+        /// so that external tools can diff it against the region set of another conductor.
+        pub(crate) async fn query_region_set(
             &self,
-            event: holochain_p2p::event::HolochainP2pEvent,
-        ) -> ConductorApiResult<()> {
+            dna_hash: &DnaHash,
+        ) -> ConductorResult<RegionSetLtcs> {
+            let topology = self.dna_topology(dna_hash)?;
+            let strat = self.get_config().dna_tuning_params(dna_hash).to_arq_strat();
+            let arq_set = ArqSet::single(Arq::new_full_max(
+                topology.space,
+                &strat,
+                SpaceOffset::zero(),
+            ));
+            kitsune_host_impl::query_region_set(
+                self.spaces.dht_db(dna_hash)?,
+                topology,
+                &strat,
+                Arc::new(arq_set),
+            )
+            .await
+        }
+
+        /// Fetch the integrated ops held for a DNA within the given regions.
+        pub(crate) async fn fetch_op_data_by_regions(
+            &self,
+            dna_hash: &DnaHash,
+            regions: Vec<RegionCoords>,
+        ) -> ConductorResult<Vec<(DhtOpHash, DhtOp)>> {
+            let topology = self.dna_topology(dna_hash)?;
+            let bounds = regions
+                .iter()
+                .map(|coords| coords.to_bounds(&topology))
+                .collect();
+            self.spaces
+                .handle_fetch_op_data_by_regions(dna_hash, bounds)
+                .await
+        }
+
+        /// Return a chunk of a DHT archive of the integrated ops held for a DNA.
+        ///
+        /// A chunk at offset 0 writes a new archive into a temporary file, and each following
+        /// chunk must be asked for at the offset where the previous one ended. The file is
+        /// removed once its last chunk has been returned.
+        pub(crate) async fn export_dht_archive_chunk(
+            &self,
+            dna_hash: &DnaHash,
+            offset: u64,
+        ) -> ConductorResult<DhtArchiveChunk> {
+            let file = if offset == 0 {
+                self.get_dna_def(dna_hash)
+                    .ok_or_else(|| DnaError::DnaMissing(dna_hash.clone()))?;
+                let file = new_transfer_file(&self.spaces.db_dir)?;
+                self.spaces
+                    .export_dht_archive(dna_hash, file.reopen()?)
+                    .await?;
+                file
+            } else {
+                self.dht_archive_exports.resume(dna_hash, offset)?
+            };
+
+            let (bytes, archive_len) = tokio::task::spawn_blocking({
+                let file = file.clone();
+                move || -> std::io::Result<_> {
+                    let mut reader = file.reopen()?;
+                    let archive_len = reader.metadata()?.len();
+                    reader.seek(std::io::SeekFrom::Start(offset))?;
+                    let mut bytes = Vec::new();
+                    reader
+                        .take(DHT_ARCHIVE_CHUNK_SIZE as u64)
+                        .read_to_end(&mut bytes)?;
+                    Ok((bytes, archive_len))
+                }
+            })
+            .await??;
+
+            let next_offset = offset + bytes.len() as u64;
+            if next_offset < archive_len {
+                self.dht_archive_exports
+                    .advance(dna_hash, file, next_offset);
+            } else {
+                self.dht_archive_exports.finish(dna_hash);
+            }
+
+            Ok(DhtArchiveChunk {
+                offset,
+                archive_len,
+                bytes: Bytes::from(bytes),
+            })
+        }
+
+        /// Receive a chunk of a DHT archive to import for a DNA, writing it into a temporary
+        /// file. A chunk at offset 0 starts a new import, and each following chunk must be
+        /// sent at the offset where the previous one ended. Returns the number of bytes of
+        /// the archive received so far.
+        pub(crate) async fn receive_dht_archive_chunk(
+            &self,
+            dna_hash: &DnaHash,
+            offset: u64,
+            chunk: Vec<u8>,
+        ) -> ConductorResult<u64> {
+            self.get_dna_def(dna_hash)
+                .ok_or_else(|| DnaError::DnaMissing(dna_hash.clone()))?;
+            let file = if offset == 0 {
+                new_transfer_file(&self.spaces.db_dir)?
+            } else {
+                self.dht_archive_imports.resume(dna_hash, offset)?
+            };
+
+            // Claim the next offset before writing, so that a chunk sent twice is rejected.
+            let received = offset + chunk.len() as u64;
+            self.dht_archive_imports
+                .advance(dna_hash, file.clone(), received);
+
+            let written = tokio::task::spawn_blocking(move || -> std::io::Result<()> {
+                let mut writer = std::fs::OpenOptions::new().append(true).open(file.path())?;
+                writer.write_all(&chunk)?;
+                writer.flush()
+            })
+            .await?;
+            if let Err(err) = written {
+                self.dht_archive_imports.finish(dna_hash);
+                return Err(err.into());
+            }
+            Ok(received)
+        }
+
+        /// Import the ops in the DHT archive received for a DNA with
+        /// [`Self::receive_dht_archive_chunk`], once all `archive_len` bytes of it have been received.
+        ///
+        /// Every op is checked against its hash before anything is imported. The ops are
+        /// then read from the archive in batches and handled as if they had been published
+        /// to this conductor, so they are validated before they are integrated. Returns the
+        /// number of ops in the archive.
+        pub(crate) async fn import_received_dht_archive(
+            &self,
+            dna_hash: &DnaHash,
+            archive_len: u64,
+        ) -> ConductorResult<usize> {
+            let file = self.dht_archive_imports.resume(dna_hash, archive_len)?;
+            self.dht_archive_imports.finish(dna_hash);
+            let archive = tokio::task::spawn_blocking(move || -> DhtArchiveResult<_> {
+                let archive = DhtArchive::open_file(file.reopen()?)?;
+                for op in archive.iter() {
+                    op?;
+                }
+                Ok(archive)
+            })
+            .await??;
+            if archive.header().dna_hash != *dna_hash {
+                return Err(DhtArchiveError::DnaMismatch {
+                    expected: dna_hash.clone(),
+                    found: archive.header().dna_hash.clone(),
+                }
+                .into());
+            }
+
+            let archive = Arc::new(archive);
+            let hashes: Vec<DhtOpHash> = archive.hashes().cloned().collect();
+            for batch in hashes.chunks(DHT_ARCHIVE_IMPORT_BATCH) {
+                let ops = tokio::task::spawn_blocking({
+                    let archive = archive.clone();
+                    let batch = batch.to_vec();
+                    move || -> DhtArchiveResult<Vec<DhtOp>> {
+                        batch
+                            .iter()
+                            .filter_map(|hash| archive.get(hash).transpose())
+                            .map(|op| op.map(|op| op.into_content()))
+                            .collect()
+                    }
+                })
+                .await??;
+                self.spaces
+                    .handle_publish(dna_hash, false, false, ops)
+                    .await?;
+            }
+            Ok(hashes.len())
+        }
+
+        /// Validate the ops in a DHT archive again against a DNA, in an isolated workspace,
+        /// and compare each outcome with the validation status recorded for the op in the
+        /// archive's DNA. Ops are only recorded if this conductor holds the archive's DNA.
+        #[cfg(feature = "op_replay")]
+        pub(crate) async fn replay_validation(
+            self: Arc<Self>,
+            dna_hash: &DnaHash,
+            archive: Vec<u8>,
+        ) -> ConductorResult<ValidationReplayReport> {
+            let dna_def = self
+                .get_dna_def(dna_hash)
+                .ok_or_else(|| DnaError::DnaMissing(dna_hash.clone()))?;
+            let archive = DhtArchive::open(archive)?;
+            let archive_dna_hash = archive.header().dna_hash.clone();
+            let ops = archive.ops()?;
+            let op_hashes: Vec<DhtOpHash> = ops.iter().map(|op| op.as_hash().clone()).collect();
+
+            let dir = tempfile::Builder::new()
+                .prefix("holochain-validation-replay")
+                .tempdir()?;
+            let workspace = ReplayValidationWorkspace::new(dna_def, dir)?;
+            let network = self.holochain_p2p().to_dna(dna_hash.clone(), None);
+            let replayed =
+                replay_validation_workflow(workspace, ops, self.clone(), network).await?;
+
+            let recorded = if self.get_dna_def(&archive_dna_hash).is_some() {
+                validation_statuses(
+                    self.spaces.dht_db(&archive_dna_hash)?.into(),
+                    op_hashes.clone(),
+                )
+                .await?
+            } else {
+                Default::default()
+            };
+
+            Ok(compare_validation_statuses(
+                dna_hash.clone(),
+                archive_dna_hash,
+                &op_hashes,
+                &recorded,
+                &replayed,
+            ))
+        }
+
+        fn dna_topology(&self, dna_hash: &DnaHash) -> ConductorResult<Topology> {
+            let cutoff = self
+                .get_config()
+                .network
+                .tuning_params
+                .danger_gossip_recent_threshold();
+            Ok(self
+                .get_dna_def(dna_hash)
+                .ok_or_else(|| DnaError::DnaMissing(dna_hash.clone()))?
+                .topology(cutoff))
+        }
+
+        #[tracing::instrument(skip_all)]
+        pub(crate) async fn storage_info(&self) -> ConductorResult<StorageInfo> {
+            let state = self.get_state().await?;
+
+            let all_dna: HashMap<DnaHash, Vec<InstalledAppId>> = HashMap::new();
+            let all_dna =
+                state
+                    .installed_apps()
+                    .iter()
+                    .fold(all_dna, |mut acc, (installed_app_id, app)| {
+                        for dna_hash in app.all_cells().map(|cell_id| cell_id.dna_hash()) {
+                            acc.entry(dna_hash.clone())
+                                .or_default()
+                                .push(installed_app_id.clone());
+                        }
+
+                        acc
+                    });
+
+            let app_data_blobs =
+                futures::future::join_all(all_dna.iter().map(|(dna_hash, used_by)| async {
+                    self.storage_info_for_dna(dna_hash, used_by).await
+                }))
+                .await
+                .into_iter()
+                .collect::<Result<Vec<StorageBlob>, ConductorError>>()?;
+
+            Ok(StorageInfo {
+                blobs: app_data_blobs,
+            })
+        }
+
+        /// Report the databases which will be migrated when they are next opened.
+        pub(crate) async fn pending_db_migrations(
+            &self,
+        ) -> ConductorResult<Vec<PendingDbMigration>> {
+            let root = self.root_db_dir().clone();
+            let pending = tokio::task::spawn_blocking(move || {
+                holochain_sqlite::db::pending_migrations_in(&root)
+            })
+            .await
+            .map_err(DatabaseError::from)??;
+
+            Ok(pending
+                .into_iter()
+                .map(|(path, pending)| PendingDbMigration {
+                    path,
+                    current_version: pending.current_version,
+                    latest_version: pending.latest_version,
+                })
+                .collect())
+        }
+
+        /// Take a snapshot of every database this conductor has open into a directory,
+        /// without stopping any workflows. The directory can be restored from
+        /// when the conductor is next started.
+        pub(crate) async fn snapshot_databases(
+            &self,
+            dir: PathBuf,
+        ) -> ConductorResult<Vec<PathBuf>> {
+            if dir.exists() && std::fs::read_dir(&dir)?.next().is_some() {
+                return Err(ConductorError::SnapshotDirNotEmpty(dir));
+            }
+            let paths = self.spaces.snapshot_databases(&dir).await?;
+            tracing::info!(?dir, "snapshotted {} databases", paths.len());
+            Ok(paths)
+        }
+
+        async fn storage_info_for_dna(
+            &self,
+            dna_hash: &DnaHash,
+            used_by: &Vec<InstalledAppId>,
+        ) -> ConductorResult<StorageBlob> {
+            let authored_dbs = self.spaces.get_all_authored_dbs(dna_hash)?;
+            let dht_db = self.spaces.dht_db(dna_hash)?;
+            let cache_db = self.spaces.cache(dna_hash)?;
+
+            Ok(StorageBlob::Dna(DnaStorageInfo {
+                authored_data_size_on_disk: join_all(
+                    authored_dbs
+                        .iter()
+                        .map(|db| db.read_async(get_size_on_disk)),
+                )
+                .await
+                .into_iter()
+                .map(|r| r.map_err(ConductorError::DatabaseError))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .sum(),
+                authored_data_size: join_all(
+                    authored_dbs.iter().map(|db| db.read_async(get_used_size)),
+                )
+                .await
+                .into_iter()
+                .map(|r| r.map_err(ConductorError::DatabaseError))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .sum(),
+                dht_data_size_on_disk: dht_db
+                    .read_async(get_size_on_disk)
+                    .map_err(ConductorError::DatabaseError)
+                    .await?,
+                dht_data_size: dht_db
+                    .read_async(get_used_size)
+                    .map_err(ConductorError::DatabaseError)
+                    .await?,
+                cache_data_size_on_disk: cache_db
+                    .read_async(get_size_on_disk)
+                    .map_err(ConductorError::DatabaseError)
+                    .await?,
+                cache_data_size: cache_db
+                    .read_async(get_used_size)
+                    .map_err(ConductorError::DatabaseError)
+                    .await?,
+                used_by: used_by.clone(),
+            }))
+        }
+
+        #[instrument(skip(self))]
+        pub(crate) async fn dispatch_holochain_p2p_event(
+            &self,
+            event: holochain_p2p::event::HolochainP2pEvent,
+        ) -> ConductorApiResult<()> {
             use HolochainP2pEvent::*;
             let dna_hash = event.dna_hash().clone();
             trace!(dispatch_event = ?event);
+            let busy = |request: &str| holochain_p2p::HolochainP2pError::Busy {
+                request: request.to_string(),
+            };
             match event {
                 PutAgentInfoSigned {
                     peer_data, respond, ..
                 } => {
+                    let local_cells = self.local_cells_for_agent_infos(&dna_hash, &peer_data);
                     let sender = self.p2p_batch_sender(&dna_hash);
                     let (result_sender, response) = tokio::sync::oneshot::channel();
                     let _ = sender
@@ -1177,6 +2046,16 @@ mod network_impls {
                         Ok(r) => r.map_err(holochain_p2p::HolochainP2pError::other),
                         Err(e) => Err(holochain_p2p::HolochainP2pError::other(e)),
                     };
+                    if res.is_ok() {
+                        // The publish goes out over the network, so it mustn't hold up the dispatch.
+                        for (cell, info) in local_cells {
+                            tokio::spawn(async move {
+                                if let Err(err) = cell.publish_agent_info(&info).await {
+                                    tracing::warn!(?err, "Failed to publish agent info to the DHT");
+                                }
+                            });
+                        }
+                    }
                     respond.respond(Ok(async move { res }.boxed().into()));
                 }
                 QueryAgentInfoSigned {
@@ -1246,7 +2125,7 @@ mod network_impls {
                         .get_dna_def(&dna_hash)
                         .ok_or_else(|| DnaError::DnaMissing(dna_hash.clone()))?
                         .topology(cutoff);
-                    let tuning = self.get_config().kitsune_tuning_params();
+                    let tuning = self.get_config().dna_tuning_params(&dna_hash);
                     let db = { self.p2p_agents_db(&dna_hash) };
                     let res = query_peer_density(
                         db.into(),
@@ -1268,14 +2147,52 @@ mod network_impls {
                     let signature = to_agent.sign_raw(self.keystore(), data.into()).await?;
                     respond.respond(Ok(async move { Ok(signature) }.boxed().into()));
                 }
+                // While the conductor is overloaded, gets which scan an index are
+                // refused so that the requesting peer tries another authority, and
+                // gossip doesn't pull in more ops for the lagging workflows to
+                // validate. Gets of a single hash are cheap, so they're still served.
+                GetLinks { respond, .. }
+                    if self.load_shedder.shed(SheddableRequest::ScanningGet) =>
+                {
+                    let res = Err(busy("get_links"));
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
+                CountLinks { respond, .. }
+                    if self.load_shedder.shed(SheddableRequest::ScanningGet) =>
+                {
+                    let res = Err(busy("count_links"));
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
+                GetAgentActivity { respond, .. }
+                    if self.load_shedder.shed(SheddableRequest::ScanningGet) =>
+                {
+                    let res = Err(busy("get_agent_activity"));
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
+                GetEntriesByAuthor { respond, .. }
+                    if self.load_shedder.shed(SheddableRequest::ScanningGet) =>
+                {
+                    let res = Err(busy("get_entries_by_author"));
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
+                QueryOpHashes { respond, .. }
+                    if self.load_shedder.shed(SheddableRequest::GossipBackfill) =>
+                {
+                    let res = Err(busy("query_op_hashes"));
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
                 HolochainP2pEvent::CallRemote { .. }
+                | RemoteQuery { .. }
                 | CountersigningSessionNegotiation { .. }
                 | Get { .. }
                 | GetMeta { .. }
                 | GetLinks { .. }
                 | CountLinks { .. }
+                | ProbeBasis { .. }
                 | GetAgentActivity { .. }
                 | MustGetAgentActivity { .. }
+                | GetActionBySeq { .. }
+                | GetEntriesByAuthor { .. }
                 | ValidationReceiptsReceived { .. } => {
                     let cell_id =
                         CellId::new(event.dna_hash().clone(), event.target_agents().clone());
@@ -1300,7 +2217,19 @@ mod network_impls {
                                 ops,
                             )
                             .await
-                            .map_err(holochain_p2p::HolochainP2pError::other);
+                            .map_err(|err| match err {
+                                // Let the network penalize the peer that sent us forged data.
+                                ConductorError::WorkflowError(
+                                    WorkflowError::SysValidationError(
+                                        SysValidationError::ValidationOutcome(outcome),
+                                    ),
+                                ) if outcome.is_counterfeit() => {
+                                    holochain_p2p::HolochainP2pError::ProtocolViolation(
+                                        ProtocolViolation::BadSignature,
+                                    )
+                                }
+                                err => holochain_p2p::HolochainP2pError::other(err),
+                            });
                         respond.respond(Ok(async move { res }.boxed().into()));
                     }
                     .instrument(debug_span!("handle_publish"))
@@ -1324,6 +2253,61 @@ mod network_impls {
                     .await;
                 }
 
+                EgressBlocked {
+                    dna_hash,
+                    from_agent,
+                    to_agent,
+                    request,
+                    respond,
+                    ..
+                } => {
+                    self.record_event(ConductorEvent::EgressBlocked {
+                        cell_id: CellId::new(dna_hash, from_agent),
+                        to_agent,
+                        request,
+                    })
+                    .await;
+                    respond.respond(Ok(async move { Ok(()) }.boxed().into()));
+                }
+
+                QueueRemoteSignal {
+                    dna_hash,
+                    to_agent,
+                    signal,
+                    max_len,
+                    respond,
+                    ..
+                } => {
+                    let res = holochain_state::remote_signal_queue::push(
+                        &self.spaces.conductor_db,
+                        dna_hash,
+                        to_agent,
+                        signal,
+                        max_len,
+                        Timestamp::now(),
+                    )
+                    .await
+                    .map_err(holochain_p2p::HolochainP2pError::other);
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
+
+                TakeQueuedRemoteSignals {
+                    dna_hash,
+                    to_agent,
+                    respond,
+                    ..
+                } => {
+                    let res = holochain_state::remote_signal_queue::take(
+                        &self.spaces.conductor_db,
+                        dna_hash,
+                        to_agent,
+                        Timestamp::now(),
+                    )
+                    .await
+                    .map_err(holochain_p2p::HolochainP2pError::other);
+                    respond.respond(Ok(async move { res }.boxed().into()));
+                }
+
                 HolochainP2pEvent::QueryOpHashes {
                     dna_hash,
                     window,
@@ -1351,8 +2335,12 @@ mod network_impls {
         }
 
         /// Invoke a zome function on a Cell
-        pub async fn call_zome(&self, call: ZomeCall) -> ConductorApiResult<ZomeCallResult> {
+        pub async fn call_zome(&self, mut call: ZomeCall) -> ConductorApiResult<ZomeCallResult> {
             let cell = self.cell_by_id(&call.cell_id).await?;
+            if call.timeout_ms.is_none() {
+                // Fall back to the timeout of the app the cell belongs to.
+                call.timeout_ms = self.zome_call_timeout_for_cell(&call.cell_id).await?;
+            }
             Ok(cell.call_zome(call, None).await?)
         }
 
@@ -1394,6 +2382,7 @@ mod network_impls {
                 payload,
                 nonce,
                 expires_at,
+                wait_for_publish: None,
             };
             let call =
                 ZomeCall::try_from_unsigned_zome_call(self.keystore(), call_unsigned).await?;
@@ -1451,6 +2440,11 @@ mod app_impls {
                 installed_app_id,
                 membrane_proofs,
                 network_seed,
+                validation_queue_orders,
+                missed_signals_buffer_size,
+                zome_call_timeout_ms,
+                warranted_authors,
+                egress_policy,
                 ..
             } = payload;
 
@@ -1495,6 +2489,21 @@ mod app_impls {
                 ));
             };
 
+            let validation_queue_orders = validation_queue_orders
+                .into_iter()
+                .map(|(role_name, order)| {
+                    let dna_hash = ops
+                        .role_assignments
+                        .iter()
+                        .find(|(name, _)| *name == role_name)
+                        .ok_or(AppError::RoleNameMissing(role_name))?
+                        .1
+                        .dna_hash()
+                        .clone();
+                    Ok((dna_hash, order))
+                })
+                .collect::<ConductorResult<Vec<_>>>()?;
+
             for (dna, _) in ops.dnas_to_register {
                 self.clone().register_dna(dna).await?;
             }
@@ -1509,10 +2518,64 @@ mod app_impls {
 
             if genesis_result.is_ok() || ignore_genesis_failure {
                 let roles = ops.role_assignments;
-                let app = InstalledAppCommon::new(installed_app_id, agent_key, roles, manifest)?;
+                let app =
+                    InstalledAppCommon::new(installed_app_id.clone(), agent_key, roles, manifest)?;
 
                 // Update the db
                 let stopped_app = self.add_disabled_app_to_db(app).await?;
+                self.update_host_fn_allowlists().await?;
+                if !validation_queue_orders.is_empty() {
+                    self.update_state(move |mut state| {
+                        for (dna_hash, order) in validation_queue_orders {
+                            state.set_validation_queue_order(dna_hash, order);
+                        }
+                        Ok(state)
+                    })
+                    .await?;
+                }
+                if let Some(buffer_size) = missed_signals_buffer_size {
+                    let id = installed_app_id.clone();
+                    self.update_state(move |mut state| {
+                        state.set_missed_signals_buffer_size(id, buffer_size);
+                        Ok(state)
+                    })
+                    .await?;
+                    self.spawn_signal_persistence(installed_app_id.clone(), buffer_size);
+                }
+                if let Some(timeout_ms) = zome_call_timeout_ms {
+                    let id = installed_app_id.clone();
+                    self.update_state(move |mut state| {
+                        state.set_zome_call_timeout_ms(id, timeout_ms);
+                        Ok(state)
+                    })
+                    .await?;
+                    self.zome_call_timeouts.share_mut(|cached| cached.clear());
+                }
+                if let Some(warranted_authors) = warranted_authors {
+                    let id = installed_app_id.clone();
+                    self.update_state(move |mut state| {
+                        state.set_warranted_authors(id, warranted_authors);
+                        Ok(state)
+                    })
+                    .await?;
+                    self.warranted_authors.share_mut(|cached| cached.clear());
+                }
+                if let Some(egress_policy) = egress_policy {
+                    let id = installed_app_id.clone();
+                    self.update_state(move |mut state| {
+                        state.set_egress_policy(id, egress_policy);
+                        Ok(state)
+                    })
+                    .await?;
+                    self.update_egress_policies().await?;
+                }
+
+                for cell_id in cell_ids {
+                    self.record_event(ConductorEvent::CellCreated { cell_id })
+                        .await;
+                }
+                self.record_event(ConductorEvent::AppInstalled { installed_app_id })
+                    .await;
 
                 // Return the result, which be may an error if no_rollback was specified
                 genesis_result.map(|()| stopped_app)
@@ -1534,6 +2597,12 @@ mod app_impls {
             let self_clone = self.clone();
             let app = self.remove_app_from_db(installed_app_id).await?;
             tracing::debug!(msg = "Removed app from db.", app = ?app);
+            self.update_host_fn_allowlists().await?;
+            self.update_egress_policies().await?;
+            self.record_event(ConductorEvent::AppUninstalled {
+                installed_app_id: installed_app_id.clone(),
+            })
+            .await;
 
             // Remove cells which may now be dangling due to the removed app
             self_clone
@@ -1549,6 +2618,12 @@ mod app_impls {
                 .collect::<HashSet<_>>();
             self.app_broadcast.retain(installed_app_ids);
 
+            holochain_state::missed_signals::clear(
+                &self.spaces.conductor_db,
+                installed_app_id.clone(),
+            )
+            .await?;
+
             Ok(())
         }
 
@@ -1603,26 +2678,43 @@ mod app_impls {
                 .collect())
         }
 
-        /// Find the ID of the first active installed App which uses this Cell
+        /// Find the cell provisioned for a role in the running app which the given cell is in.
         #[tracing::instrument(skip_all)]
         pub async fn find_cell_with_role_alongside_cell(
             &self,
             cell_id: &CellId,
             role_name: &RoleName,
-        ) -> ConductorResult<Option<CellId>> {
-            Ok(self
-                .get_state()
-                .await?
+        ) -> ConductorResult<CellId> {
+            let state = self.get_state().await?;
+            let app = Self::running_app_containing_cell(&state, cell_id)?;
+            let role = app.role(role_name)?;
+            Ok(role
+                .provisioned_cell()
+                .ok_or_else(|| AppError::RoleNotProvisioned(role_name.clone()))?
+                .clone())
+        }
+
+        /// Find the enabled clone cell with the given clone id in the running app
+        /// which the given cell is in.
+        pub async fn find_clone_cell_alongside_cell(
+            &self,
+            cell_id: &CellId,
+            clone_id: &CloneId,
+        ) -> ConductorResult<CellId> {
+            let state = self.get_state().await?;
+            let app = Self::running_app_containing_cell(&state, cell_id)?;
+            Ok(app.get_clone_cell_id(&CloneCellId::CloneId(clone_id.clone()))?)
+        }
+
+        fn running_app_containing_cell(
+            state: &ConductorState,
+            cell_id: &CellId,
+        ) -> ConductorResult<InstalledAppCommon> {
+            state
                 .running_apps()
                 .find(|(_, running_app)| running_app.all_cells().any(|i| i == cell_id))
-                .and_then(|(_, running_app)| {
-                    running_app
-                        .into_common()
-                        .role(role_name)
-                        .ok()
-                        .map(|role| role.cell_id())
-                        .cloned()
-                }))
+                .map(|(_, running_app)| running_app.into_common())
+                .ok_or_else(|| ConductorError::CellMissing(cell_id.clone()))
         }
 
         /// Get the IDs of all active installed Apps which use this Dna
@@ -1672,6 +2764,32 @@ mod cell_impls {
     use super::*;
 
     impl Conductor {
+        /// The running cells of the local agents which the agent infos are for,
+        /// if agent infos are published to the DHT.
+        fn local_cells_for_agent_infos(
+            &self,
+            dna_hash: &DnaHash,
+            infos: &[AgentInfoSigned],
+        ) -> Vec<(Arc<Cell>, AgentInfoSigned)> {
+            use holochain_p2p::AgentPubKeyExt;
+
+            if !self.config.conductor_tuning_params().publish_agent_infos() {
+                return Vec::new();
+            }
+            self.running_cells.share_ref(|cells| {
+                infos
+                    .iter()
+                    .filter_map(|info| {
+                        let cell_id =
+                            CellId::new(dna_hash.clone(), AgentPubKey::from_kitsune(&info.agent));
+                        cells
+                            .get(&cell_id)
+                            .map(|item| (item.cell.clone(), info.clone()))
+                    })
+                    .collect()
+            })
+        }
+
         pub(crate) async fn cell_by_id(&self, cell_id: &CellId) -> ConductorResult<Arc<Cell>> {
             // Can only get a cell from the running_cells list
             if let Some(cell) = self.running_cells.share_ref(|c| c.get(cell_id).cloned()) {
@@ -1746,10 +2864,16 @@ mod clone_cell_impls {
                     name,
                 )
                 .await?;
+            self.update_host_fn_allowlists().await?;
+            self.update_egress_policies().await?;
 
             // run genesis on cloned cell
             let cells = vec![(clone_cell.cell_id.clone(), membrane_proof)];
             crate::conductor::conductor::genesis_cells(self.clone(), cells).await?;
+            self.record_event(ConductorEvent::CellCreated {
+                cell_id: clone_cell.cell_id.clone(),
+            })
+            .await;
             self.create_and_add_initialized_cells_for_running_apps(Some(installed_app_id))
                 .await?;
             Ok(clone_cell)
@@ -1877,6 +3001,10 @@ mod app_status_impls {
             let (app, delta) = self
                 .transition_app_status(app_id.clone(), AppStatusTransition::Enable)
                 .await?;
+            self.record_event(ConductorEvent::AppEnabled {
+                installed_app_id: app_id.clone(),
+            })
+            .await;
             let errors = self
                 .process_app_status_fx(delta, Some(vec![app_id.to_owned()].into_iter().collect()))
                 .await?;
@@ -1891,8 +3019,13 @@ mod app_status_impls {
             reason: DisabledAppReason,
         ) -> ConductorResult<InstalledApp> {
             let (app, delta) = self
-                .transition_app_status(app_id.clone(), AppStatusTransition::Disable(reason))
+                .transition_app_status(app_id.clone(), AppStatusTransition::Disable(reason.clone()))
                 .await?;
+            self.record_event(ConductorEvent::AppDisabled {
+                installed_app_id: app_id.clone(),
+                reason,
+            })
+            .await;
             self.process_app_status_fx(delta, Some(vec![app_id.to_owned()].into_iter().collect()))
                 .await?;
             Ok(app)
@@ -1989,6 +3122,7 @@ mod app_status_impls {
 
             future::join_all(new_cells.iter().map(|(cell, _)| {
                 let sleuth_id = self.config.sleuth_id();
+                let conductor = self.clone();
                 async move {
                     let p2p_agents_db = cell.p2p_agents_db().clone();
                     let cell_id = cell.id().clone();
@@ -2018,6 +3152,22 @@ mod app_status_impls {
                                         node: sleuth_id,
                                         agent: cell_id.agent_pubkey().clone()
                                     });
+                                    if !conductor.cell_network_mode(&cell_id).gossip() {
+                                        use holochain_p2p::HolochainP2pSender;
+                                        if let Err(e) = conductor
+                                            .holochain_p2p
+                                            .set_agent_gossip(
+                                                cell_id.dna_hash().clone(),
+                                                cell_id.agent_pubkey().clone(),
+                                                false,
+                                            )
+                                            .await
+                                        {
+                                            tracing::error!(
+                                                "Failed to turn off gossip for {cell_id}: {e:?}"
+                                            );
+                                        }
+                                    }
                                 }
                                 Err(e) => {
                                     tracing::error!(
@@ -2142,21 +3292,435 @@ mod state_impls {
             self.spaces.update_state(f).await
         }
 
-        /// Update the internal state with a pure function mapping old state to new,
-        /// which may also produce an output value which will be the output of
-        /// this function
-        #[tracing::instrument(skip_all)]
-        pub(crate) async fn update_state_prime<F, O>(
-            &self,
-            f: F,
-        ) -> ConductorResult<(ConductorState, O)>
-        where
-            F: FnOnce(ConductorState) -> ConductorResult<(ConductorState, O)> + Send + 'static,
-            O: Send + 'static,
-        {
-            self.check_running()?;
-            self.spaces.update_state_prime(f).await
-        }
+        /// Update the internal state with a pure function mapping old state to new,
+        /// which may also produce an output value which will be the output of
+        /// this function
+        #[tracing::instrument(skip_all)]
+        pub(crate) async fn update_state_prime<F, O>(
+            &self,
+            f: F,
+        ) -> ConductorResult<(ConductorState, O)>
+        where
+            F: FnOnce(ConductorState) -> ConductorResult<(ConductorState, O)> + Send + 'static,
+            O: Send + 'static,
+        {
+            self.check_running()?;
+            self.spaces.update_state_prime(f).await
+        }
+    }
+}
+
+/// Methods related to the conductor's event journal
+mod event_journal_impls {
+    use super::*;
+    use holochain_sqlite::schema::PendingMigrations;
+
+    impl Conductor {
+        /// Append an event to the conductor's event journal.
+        ///
+        /// The journal is an audit trail, so failing to write to it is logged
+        /// rather than failing the operation which is being recorded.
+        pub(crate) async fn record_event(&self, event: ConductorEvent) {
+            if let Err(err) =
+                holochain_state::event_journal::record(&self.spaces.conductor_db, event.clone())
+                    .await
+            {
+                error!(?err, ?event, "Failed to record event in the event journal");
+            }
+        }
+
+        /// Get the events in the conductor's event journal recorded at or after `since`.
+        pub async fn event_journal(
+            &self,
+            since: Timestamp,
+        ) -> ConductorResult<Vec<EventJournalEntry>> {
+            Ok(self
+                .spaces
+                .conductor_db
+                .read_async(move |txn| holochain_state::event_journal::query_since(&txn, since))
+                .await?)
+        }
+
+        /// Audit the conductor's protection against replayed zome calls: the unexpired
+        /// nonce windows, and the nonces rejected at or after `since`, either for one
+        /// agent or for every agent.
+        pub async fn nonce_audit(
+            &self,
+            agent: Option<AgentPubKey>,
+            since: Timestamp,
+        ) -> ConductorResult<NonceAudit> {
+            let audited = |a: &AgentPubKey| agent.as_ref().map_or(true, |agent| agent == a);
+            let windows =
+                holochain_state::nonce::nonce_windows(&self.spaces.conductor_db, Timestamp::now())
+                    .await?
+                    .into_iter()
+                    .filter(|(agent, _)| audited(agent))
+                    .map(|(agent, expires)| NonceWindow { agent, expires })
+                    .collect();
+            let rejected = self
+                .event_journal(since)
+                .await?
+                .into_iter()
+                .filter_map(|entry| match entry.event {
+                    ConductorEvent::ZomeCallNonceRejected {
+                        agent,
+                        reason,
+                        count,
+                    } if audited(&agent) => Some(RejectedNonce {
+                        agent,
+                        timestamp: entry.timestamp,
+                        reason,
+                        count,
+                    }),
+                    _ => None,
+                })
+                .collect();
+            Ok(NonceAudit {
+                persistent: self
+                    .get_config()
+                    .conductor_tuning_params()
+                    .persist_nonce_windows(),
+                windows,
+                rejected,
+            })
+        }
+
+        /// Record the databases which were migrated while the conductor started up,
+        /// given the migrations which were pending before startup.
+        pub(crate) async fn record_db_migrations(
+            &self,
+            pending_before: Vec<(PathBuf, PendingMigrations)>,
+        ) -> ConductorResult<()> {
+            let root = self.root_db_dir().clone();
+            let pending_after: HashMap<_, _> = tokio::task::spawn_blocking(move || {
+                holochain_sqlite::db::pending_migrations_in(&root)
+            })
+            .await
+            .map_err(DatabaseError::from)??
+            .into_iter()
+            .collect();
+
+            for (path, before) in pending_before {
+                let to_version = match pending_after.get(&path) {
+                    Some(after) => after.current_version,
+                    None => before.latest_version,
+                };
+                if to_version != before.current_version {
+                    self.record_event(ConductorEvent::DbMigrated {
+                        path,
+                        from_version: before.current_version,
+                        to_version,
+                    })
+                    .await;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Methods related to persisting the signals emitted by apps
+mod missed_signal_impls {
+    use super::*;
+    use tokio::sync::broadcast::error::RecvError;
+
+    impl Conductor {
+        /// Start persisting the signals of every app which has
+        /// missed signal persistence enabled.
+        pub(crate) async fn start_signal_persistence(&self) -> ConductorResult<()> {
+            let state = self.get_state().await?;
+            for (installed_app_id, buffer_size) in state.missed_signals_buffer_sizes() {
+                self.spawn_signal_persistence(installed_app_id.clone(), *buffer_size);
+            }
+            Ok(())
+        }
+
+        /// Start the task which persists every signal emitted by an app,
+        /// keeping at most `buffer_size` of them.
+        pub(crate) fn spawn_signal_persistence(
+            &self,
+            installed_app_id: InstalledAppId,
+            buffer_size: u32,
+        ) {
+            let mut signals = self.app_broadcast.subscribe(installed_app_id.clone());
+            let db = self.spaces.conductor_db.clone();
+            self.task_manager()
+                .add_conductor_task_ignored("signal_persistence", move || async move {
+                    loop {
+                        match signals.recv().await {
+                            Ok(signal) => {
+                                if let Err(err) = holochain_state::missed_signals::record(
+                                    &db,
+                                    installed_app_id.clone(),
+                                    signal,
+                                    buffer_size,
+                                )
+                                .await
+                                {
+                                    error!(?err, %installed_app_id, "Failed to persist signal");
+                                }
+                            }
+                            Err(RecvError::Lagged(skipped)) => {
+                                warn!(
+                                    %installed_app_id,
+                                    skipped, "Signals were emitted faster than they could be persisted"
+                                );
+                            }
+                            Err(RecvError::Closed) => break,
+                        }
+                    }
+                    Ok(())
+                });
+        }
+
+        /// Get the signals persisted for an app after `since_token`.
+        pub async fn missed_signals(
+            &self,
+            installed_app_id: &InstalledAppId,
+            since_token: Option<u64>,
+        ) -> ConductorResult<MissedSignals> {
+            let state = self.get_state().await?;
+            state.get_app(installed_app_id)?;
+            if state.missed_signals_buffer_size(installed_app_id).is_none() {
+                return Err(ConductorError::MissedSignalsNotPersisted(
+                    installed_app_id.clone(),
+                ));
+            }
+            let installed_app_id = installed_app_id.clone();
+            Ok(self
+                .spaces
+                .conductor_db
+                .read_async(move |txn| {
+                    holochain_state::missed_signals::query_since(
+                        &txn,
+                        &installed_app_id,
+                        since_token,
+                    )
+                })
+                .await?)
+        }
+    }
+}
+
+/// Methods related to the outbox of durable remote calls
+mod durable_call_impls {
+    use super::*;
+    use holochain_nonce::fresh_nonce;
+    use holochain_state::durable_call_outbox;
+    use holochain_state::durable_call_outbox::DurableCall;
+
+    /// How long to wait before attempting a durable remote call again after it
+    /// has failed `attempts` times, doubling from a second up to
+    /// [`MAX_DURABLE_CALL_BACKOFF`].
+    pub(crate) fn durable_call_backoff(attempts: u32) -> std::time::Duration {
+        std::time::Duration::from_secs(1)
+            .saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)))
+            .min(MAX_DURABLE_CALL_BACKOFF)
+    }
+
+    impl Conductor {
+        /// Queue the durable remote calls made by a cell, to be attempted straight away.
+        ///
+        /// This is done once the writes of the zome call which made them have been
+        /// flushed. The outbox is in the conductor database rather than the cell's,
+        /// so if the conductor stops in between, the calls are lost.
+        pub(crate) async fn queue_durable_calls_remote(
+            &self,
+            cell_id: CellId,
+            calls: Vec<(DurableCallId, DurableCallRemote)>,
+        ) -> ConductorResult<()> {
+            if calls.is_empty() {
+                return Ok(());
+            }
+            durable_call_outbox::queue(&self.spaces.conductor_db, cell_id, calls, Timestamp::now())
+                .await?;
+            self.durable_call_wakeup.notify_one();
+            Ok(())
+        }
+
+        /// Start the task which attempts the durable remote calls which are due,
+        /// periodically and whenever a call is queued.
+        pub(crate) fn start_durable_call_outbox(
+            self: Arc<Self>,
+            interval_period: std::time::Duration,
+        ) {
+            let conductor = self.clone();
+            self.task_manager().add_conductor_task_ignored(
+                "durable_call_outbox",
+                move || async move {
+                    let mut interval = tokio::time::interval(interval_period);
+                    loop {
+                        tokio::select! {
+                            _ = interval.tick() => (),
+                            _ = conductor.durable_call_wakeup.notified() => (),
+                        }
+                        let now = Timestamp::now();
+                        if let Err(e) = conductor.dispatch_durable_calls(now).await {
+                            tracing::error!(?e, "Failed to dispatch durable remote calls");
+                        }
+                    }
+                },
+            );
+        }
+
+        /// Attempt every durable remote call which is due at `now`, and give up on
+        /// the calls which have expired. The app of the calling cell is sent a
+        /// signal for each call which is delivered or given up on.
+        ///
+        /// At most [`MAX_CONCURRENT_DURABLE_CALLS`] calls are attempted at once.
+        ///
+        /// Calls are delivered at least once: a call is only removed from the outbox
+        /// after its response has arrived, so if the conductor stops in between, or
+        /// the response is lost on the way back, the call is made again.
+        pub(crate) async fn dispatch_durable_calls(&self, now: Timestamp) -> ConductorResult<()> {
+            let calls = self
+                .spaces
+                .conductor_db
+                .read_async(move |txn| durable_call_outbox::due(&txn, now))
+                .await?;
+            let results: Vec<ConductorResult<()>> = futures::stream::iter(calls)
+                .map(|call| self.dispatch_durable_call(call, now))
+                .buffer_unordered(MAX_CONCURRENT_DURABLE_CALLS)
+                .collect()
+                .await;
+            results.into_iter().collect()
+        }
+
+        async fn dispatch_durable_call(
+            &self,
+            call: DurableCall,
+            now: Timestamp,
+        ) -> ConductorResult<()> {
+            let db = &self.spaces.conductor_db;
+            let call_id = call.id;
+            let Ok(signal_tx) = self.get_signal_tx(&call.cell_id).await else {
+                // The app of the calling cell has been uninstalled,
+                // so nobody is waiting for the response.
+                db.write_async(move |txn| durable_call_outbox::remove(txn, call_id))
+                    .await?;
+                return Ok(());
+            };
+
+            let signal = if call.expires_at <= now {
+                SystemSignal::DurableCallRemoteExpired {
+                    cell_id: call.cell_id,
+                    call_id,
+                    attempts: call.attempts,
+                }
+            } else {
+                match self.attempt_durable_call(&call).await {
+                    DurableCallAttempt::Failed(e) => {
+                        let attempts = call.attempts + 1;
+                        // Make sure the expiry is noticed on time.
+                        let next_attempt_at = now
+                            .saturating_add(&durable_call_backoff(attempts))
+                            .min(call.expires_at);
+                        tracing::debug!(?call_id, attempts, ?e, "Durable remote call failed");
+                        db.write_async(move |txn| {
+                            durable_call_outbox::reschedule(txn, call_id, attempts, next_attempt_at)
+                        })
+                        .await?;
+                        return Ok(());
+                    }
+                    DurableCallAttempt::Responded(response) => {
+                        SystemSignal::DurableCallRemoteDelivered {
+                            cell_id: call.cell_id,
+                            call_id,
+                            response,
+                        }
+                    }
+                    // Retrying won't help until the egress policy changes,
+                    // which may be never.
+                    DurableCallAttempt::Blocked => SystemSignal::DurableCallRemoteBlocked {
+                        cell_id: call.cell_id,
+                        call_id,
+                        to_agent: call.call.agent,
+                    },
+                }
+            };
+
+            db.write_async(move |txn| durable_call_outbox::remove(txn, call_id))
+                .await?;
+            // Nobody may be listening, which is fine.
+            let _ = signal_tx.send(Signal::System(signal));
+            Ok(())
+        }
+
+        /// Make a durable remote call, freshly signed, from the cell which
+        /// queued it, so that the cell's egress policy applies.
+        async fn attempt_durable_call(&self, call: &DurableCall) -> DurableCallAttempt {
+            let provenance = call.cell_id.agent_pubkey().clone();
+            let dna_hash = call.cell_id.dna_hash().clone();
+            let DurableCallRemote {
+                agent,
+                zome_name,
+                fn_name,
+                cap_secret,
+                payload,
+                ..
+            } = call.call.clone();
+
+            let response = async {
+                let (nonce, expires_at) = fresh_nonce(Timestamp::now())
+                    .map_err(|e| DurableCallAttempt::Failed(e.to_string()))?;
+                let zome_call_unsigned = ZomeCallUnsigned {
+                    provenance: provenance.clone(),
+                    cell_id: CellId::new(dna_hash.clone(), agent.clone()),
+                    zome_name,
+                    fn_name,
+                    cap_secret,
+                    payload,
+                    nonce,
+                    expires_at,
+                    wait_for_publish: None,
+                };
+                let signature = provenance
+                    .sign_raw(
+                        self.keystore(),
+                        zome_call_unsigned
+                            .data_to_sign()
+                            .map_err(|e| DurableCallAttempt::Failed(e.to_string()))?,
+                    )
+                    .await
+                    .map_err(|e| DurableCallAttempt::Failed(e.to_string()))?;
+                let response = self
+                    .holochain_p2p()
+                    .to_cell(call.cell_id.clone(), None)
+                    .call_remote(
+                        provenance,
+                        signature,
+                        agent,
+                        zome_call_unsigned.zome_name,
+                        zome_call_unsigned.fn_name,
+                        zome_call_unsigned.cap_secret,
+                        zome_call_unsigned.payload,
+                        zome_call_unsigned.nonce,
+                        zome_call_unsigned.expires_at,
+                    )
+                    .await
+                    .map_err(|e| match e {
+                        holochain_p2p::HolochainP2pError::EgressBlocked { .. } => {
+                            DurableCallAttempt::Blocked
+                        }
+                        e => DurableCallAttempt::Failed(e.to_string()),
+                    })?;
+                ZomeCallResponse::try_from(response)
+                    .map(DurableCallAttempt::Responded)
+                    .map_err(|e| DurableCallAttempt::Failed(e.to_string()))
+            }
+            .await;
+            response.unwrap_or_else(|attempt| attempt)
+        }
+    }
+
+    /// How an attempt to make a durable remote call turned out.
+    enum DurableCallAttempt {
+        /// The remote agent responded, with anything but a network error.
+        Responded(ZomeCallResponse),
+        /// The remote agent couldn't be reached, so the call may be retried.
+        Failed(String),
+        /// The egress policy of the calling cell doesn't allow the call.
+        Blocked,
     }
 }
 
@@ -2230,6 +3794,192 @@ mod scheduler_impls {
                 .map(|cell_arc| cell_arc.dispatch_scheduled_fns(now));
             futures::future::join_all(tasks).await;
         }
+
+        /// Start the task which periodically purges deleted entries
+        /// from the DHT databases of all running cells.
+        pub(crate) fn start_entry_purge(self: Arc<Self>, interval_period: std::time::Duration) {
+            let conductor = self.clone();
+            self.task_manager()
+                .add_conductor_task_ignored("entry_purge", move || async move {
+                    let mut interval = tokio::time::interval(interval_period);
+                    loop {
+                        interval.tick().await;
+                        conductor
+                            .clone()
+                            .purge_deleted_entries(Timestamp::now())
+                            .await;
+                    }
+                });
+        }
+
+        /// Purge the deleted entries whose retention has expired
+        /// from the DHT database of each running DNA.
+        pub(crate) async fn purge_deleted_entries(self: Arc<Self>, now: Timestamp) {
+            let dna_hashes: HashSet<DnaHash> = self
+                .running_cell_ids()
+                .into_iter()
+                .map(|cell_id| cell_id.dna_hash().clone())
+                .collect();
+
+            for dna_hash in dna_hashes {
+                let result = match self.spaces.dht_db(&dna_hash) {
+                    Ok(db) => {
+                        crate::core::workflow::purge_entries_workflow::purge_entries_workflow(
+                            &dna_hash,
+                            db,
+                            self.clone(),
+                            now,
+                        )
+                        .await
+                        .map_err(|e| e.to_string())
+                    }
+                    Err(e) => Err(e.to_string()),
+                };
+                match result {
+                    Ok(0) => (),
+                    Ok(purged) => tracing::debug!(?dna_hash, purged, "Purged deleted entries"),
+                    Err(e) => tracing::error!(?dna_hash, ?e, "Failed to purge deleted entries"),
+                }
+            }
+        }
+
+        /// Start the task which periodically evicts old data
+        /// from the cache databases of all running cells.
+        pub(crate) fn start_cache_eviction(self: Arc<Self>, interval_period: std::time::Duration) {
+            let conductor = self.clone();
+            self.task_manager()
+                .add_conductor_task_ignored("cache_eviction", move || async move {
+                    let mut interval = tokio::time::interval(interval_period);
+                    loop {
+                        interval.tick().await;
+                        conductor.evict_caches(Timestamp::now()).await;
+                    }
+                });
+        }
+
+        /// Evict the data which has been cached for longer than the startup
+        /// profile allows from the cache database of each running DNA.
+        pub(crate) async fn evict_caches(&self, now: Timestamp) {
+            let dna_hashes: HashSet<DnaHash> = self
+                .running_cell_ids()
+                .into_iter()
+                .map(|cell_id| cell_id.dna_hash().clone())
+                .collect();
+
+            for dna_hash in dna_hashes {
+                let Some(age) = self
+                    .get_config()
+                    .startup_profile(&dna_hash)
+                    .cache_eviction_age()
+                else {
+                    continue;
+                };
+                let before = now.saturating_sub(&age);
+                let result = match self.spaces.cache(&dna_hash) {
+                    Ok(db) => db
+                        .write_async(move |txn| evict_cached_ops(txn, before))
+                        .await
+                        .map_err(|e| e.to_string()),
+                    Err(e) => Err(e.to_string()),
+                };
+                match result {
+                    Ok(0) => (),
+                    Ok(evicted) => tracing::debug!(?dna_hash, evicted, "Evicted cached ops"),
+                    Err(e) => tracing::error!(?dna_hash, ?e, "Failed to evict cached ops"),
+                }
+            }
+        }
+
+        /// Start the task which periodically archives the clone cells
+        /// which the clone archival policies of their apps consider inactive.
+        pub(crate) fn start_clone_archival(self: Arc<Self>, interval_period: std::time::Duration) {
+            let conductor = self.clone();
+            self.task_manager()
+                .add_conductor_task_ignored("clone_archival", move || async move {
+                    let mut interval = tokio::time::interval(interval_period);
+                    loop {
+                        interval.tick().await;
+                        if let Err(e) = conductor
+                            .archive_inactive_clone_cells(Timestamp::now())
+                            .await
+                        {
+                            tracing::error!(?e, "Failed to archive inactive clone cells");
+                        }
+                    }
+                });
+        }
+
+        /// Archive (disable) the clone cells of every enabled app whose source chain
+        /// hasn't been written to for longer than the clone archival policy of their
+        /// role allows, and emit a [`SystemSignal::CloneCellArchived`] to the app for each.
+        ///
+        /// Returns the archived cells.
+        pub(crate) async fn archive_inactive_clone_cells(
+            &self,
+            now: Timestamp,
+        ) -> ConductorResult<Vec<CellId>> {
+            let state = self.get_state().await?;
+            let mut candidates = Vec::new();
+            for (installed_app_id, app) in state.enabled_apps() {
+                let AppManifest::V1(manifest) = app.manifest();
+                for role in &manifest.roles {
+                    let Some(policy) = &role.dna.clone_archival else {
+                        continue;
+                    };
+                    let Some(clones) = app.clone_cells_for_role_name(&role.name) else {
+                        continue;
+                    };
+                    for (clone_id, cell_id) in clones {
+                        candidates.push((
+                            installed_app_id.clone(),
+                            clone_id.clone(),
+                            cell_id.clone(),
+                            policy.clone(),
+                        ));
+                    }
+                }
+            }
+
+            let mut archived = Vec::new();
+            for (installed_app_id, clone_id, cell_id, policy) in candidates {
+                let authored_db = self.get_or_create_authored_db(
+                    cell_id.dna_hash(),
+                    cell_id.agent_pubkey().clone(),
+                )?;
+                let author = Arc::new(cell_id.agent_pubkey().clone());
+                let head = authored_db
+                    .read_async(move |txn| chain_head_db(&txn, author))
+                    .await?;
+                // A clone without a chain yet has nothing to go by.
+                let Some(head) = head else {
+                    continue;
+                };
+                if !policy.should_archive(head.timestamp, now) {
+                    continue;
+                }
+
+                self.disable_clone_cell(
+                    &installed_app_id,
+                    &DisableCloneCellPayload {
+                        clone_cell_id: CloneCellId::CloneId(clone_id.clone()),
+                    },
+                )
+                .await?;
+                tracing::info!(?cell_id, %installed_app_id, "Archived inactive clone cell");
+
+                // Nobody may be listening, which is fine.
+                let _ = self
+                    .app_broadcast
+                    .create_send_handle(installed_app_id)
+                    .send(Signal::System(SystemSignal::CloneCellArchived {
+                        cell_id: cell_id.clone(),
+                        clone_id,
+                        last_write: head.timestamp,
+                    }));
+                archived.push(cell_id);
+            }
+            Ok(archived)
+        }
     }
 }
 
@@ -2287,6 +4037,33 @@ mod misc_impls {
             Ok(action_hash)
         }
 
+        /// Renew the membrane proof of a cell's agent, superseding the agent's
+        /// current proof, and publish the renewal.
+        pub async fn renew_membrane_proof(
+            &self,
+            cell_id: &CellId,
+            membrane_proof: MembraneProof,
+        ) -> ConductorApiResult<ActionHash> {
+            let cell = self.cell_by_id(cell_id).await?;
+            let source_chain = SourceChain::new(
+                self.get_or_create_authored_db(cell_id.dna_hash(), cell_id.agent_pubkey().clone())?,
+                self.get_or_create_dht_db(cell_id.dna_hash())?,
+                self.get_or_create_space(cell_id.dna_hash())?
+                    .dht_query_cache,
+                self.keystore.clone(),
+                cell_id.agent_pubkey().clone(),
+            )
+            .await?;
+
+            let action_hash = source_chain
+                .put_membrane_proof_renewal(membrane_proof, ChainTopOrdering::default())
+                .await?;
+            source_chain.flush(cell.holochain_p2p_dna()).await?;
+            cell.notify_authored_ops_moved_to_limbo();
+
+            Ok(action_hash)
+        }
+
         /// Create a JSON dump of the cell's state
         #[tracing::instrument(skip_all)]
         pub async fn dump_cell_state(&self, cell_id: &CellId) -> ConductorApiResult<String> {
@@ -2384,6 +4161,60 @@ mod misc_impls {
             Ok(out)
         }
 
+        /// Dump one page of a single store of a cell's state
+        pub async fn dump_cell_state_page(
+            &self,
+            cell_id: &CellId,
+            store: StateDumpStore,
+            cursor: Option<u64>,
+            limit: u32,
+        ) -> ConductorApiResult<StateDumpPage> {
+            let limit = limit.clamp(1, MAX_STATE_DUMP_PAGE_LIMIT);
+            let dna_hash = cell_id.dna_hash();
+
+            let (len, last, items) = match store {
+                StateDumpStore::SourceChain => {
+                    let authored_db =
+                        self.get_or_create_authored_db(dna_hash, cell_id.agent_pubkey().clone())?;
+                    let after_seq = cursor.map(|c| u32::try_from(c).unwrap_or(u32::MAX));
+                    let records = source_chain::dump_state_page(
+                        authored_db.into(),
+                        cell_id.agent_pubkey().clone(),
+                        after_seq,
+                        limit,
+                    )
+                    .await?;
+                    let last = records.last().map(|r| r.action.action_seq() as u64);
+                    (records.len(), last, StateDumpItems::SourceChain(records))
+                }
+                StateDumpStore::DhtOps => {
+                    let dht_db = self.get_or_create_dht_db(dna_hash)?;
+                    let (ops, last) = dht_ops_dump_page(&dht_db.into(), cursor, limit).await?;
+                    (ops.len(), last, StateDumpItems::DhtOps(ops))
+                }
+                StateDumpStore::Cache => {
+                    let cache_db = self.get_or_create_cache_db(dna_hash)?;
+                    let (ops, last) = dht_ops_dump_page(&cache_db.into(), cursor, limit).await?;
+                    (ops.len(), last, StateDumpItems::Cache(ops))
+                }
+                StateDumpStore::PeerStore => {
+                    let p2p_agents_db: DbRead<_> = self.spaces.p2p_agents_db(dna_hash)?.into();
+                    let page = p2p_agents_db
+                        .p2p_list_agents_page(cursor.unwrap_or(0), limit)
+                        .await?;
+                    let last = page.last().map(|(rowid, _)| *rowid);
+                    let len = page.len();
+                    let infos = page.into_iter().map(|(_, info)| info).collect();
+                    (len, last, StateDumpItems::PeerStore(infos))
+                }
+            };
+
+            // A short page means the end of the store was reached.
+            let next_cursor = if len == limit as usize { last } else { None };
+
+            Ok(StateDumpPage { items, next_cursor })
+        }
+
         /// JSON dump of network metrics
         pub async fn dump_network_metrics(
             &self,
@@ -2405,6 +4236,113 @@ mod misc_impls {
                 .map_err(crate::conductor::api::error::ConductorApiError::other)
         }
 
+        /// Snapshot the work queues of a DNA, along with the run durations of the
+        /// workflows which consume them.
+        pub async fn workflow_stats(
+            &self,
+            dna_hash: &DnaHash,
+        ) -> ConductorApiResult<WorkflowStats> {
+            use crate::core::metrics::{QueueSample, WorkflowQueueKind};
+            use crate::core::queue_consumer::InstrumentedQueue;
+
+            self.get_dna_def(dna_hash)
+                .ok_or_else(|| DnaError::DnaMissing(dna_hash.clone()))?;
+            let dht_db: DbRead<DbKindDht> = self.spaces.dht_db(dna_hash)?.into();
+            let sys_validation = InstrumentedQueue::SysValidation(dht_db.clone())
+                .sample()
+                .await?;
+            let app_validation = InstrumentedQueue::AppValidation(dht_db.clone())
+                .sample()
+                .await?;
+            let integration = InstrumentedQueue::Integration(dht_db).sample().await?;
+            let mut publish = QueueSample::default();
+            for db in self.spaces.get_all_authored_dbs(dna_hash)? {
+                let sample = InstrumentedQueue::Publish(db.into()).sample().await?;
+                publish.depth += sample.depth;
+                publish.oldest = publish.oldest.into_iter().chain(sample.oldest).min();
+            }
+
+            let stats = |kind, sample| self.workflow_queues.stats(dna_hash, kind, sample);
+            Ok(WorkflowStats {
+                sys_validation: stats(WorkflowQueueKind::SysValidation, sys_validation),
+                app_validation: stats(WorkflowQueueKind::AppValidation, app_validation),
+                integration: stats(WorkflowQueueKind::Integration, integration),
+                publish: stats(WorkflowQueueKind::Publish, publish),
+            })
+        }
+
+        /// List the ops of a DNA which haven't been integrated yet, and why.
+        pub async fn list_validation_limbo(
+            &self,
+            dna_hash: &DnaHash,
+        ) -> ConductorApiResult<Vec<ValidationLimboOp>> {
+            self.get_dna_def(dna_hash)
+                .ok_or_else(|| DnaError::DnaMissing(dna_hash.clone()))?;
+            let dht_db = self.spaces.dht_db(dna_hash)?;
+            Ok(dht_db
+                .read_async(|txn| holochain_state::validation_db::list_validation_limbo(&txn))
+                .await?)
+        }
+
+        /// Build the graph of what the given ops are waiting for, following the ops which
+        /// hold each dependency in turn, to debug ops which are stuck in validation limbo.
+        pub async fn op_dependency_graph(
+            &self,
+            dna_hash: &DnaHash,
+            op_hashes: Vec<DhtOpHash>,
+        ) -> ConductorApiResult<OpDependencyGraph> {
+            self.get_dna_def(dna_hash)
+                .ok_or_else(|| DnaError::DnaMissing(dna_hash.clone()))?;
+            let dht_db = self.spaces.dht_db(dna_hash)?;
+            Ok(dht_db
+                .read_async(move |txn| {
+                    holochain_state::validation_db::op_dependency_graph(
+                        &txn,
+                        &op_hashes,
+                        MAX_OP_DEPENDENCY_GRAPH_OPS,
+                    )
+                })
+                .await?)
+        }
+
+        /// Reset the validation attempts of the given ops in limbo and trigger validation,
+        /// so they are revalidated before the other ops awaiting validation.
+        pub async fn retry_validation(
+            &self,
+            dna_hash: &DnaHash,
+            op_hashes: Vec<DhtOpHash>,
+        ) -> ConductorApiResult<Vec<DhtOpHash>> {
+            self.get_dna_def(dna_hash)
+                .ok_or_else(|| DnaError::DnaMissing(dna_hash.clone()))?;
+            let dht_db = self.spaces.dht_db(dna_hash)?;
+            let reset = dht_db
+                .write_async(move |txn| {
+                    holochain_state::validation_db::reset_validation_attempts(txn, &op_hashes)
+                })
+                .await?;
+
+            if !reset.is_empty() {
+                let dna_hash = Arc::new(dna_hash.clone());
+                let queue_consumer_map = &self.spaces.queue_consumer_map;
+                for trigger in [
+                    queue_consumer_map.sys_validation_trigger(dna_hash.clone()),
+                    queue_consumer_map.app_validation_trigger(dna_hash),
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    trigger.trigger(&"retry_validation");
+                }
+            }
+            Ok(reset)
+        }
+
+        /// The state of load shedding, and how many requests from other peers have been
+        /// refused because the workflows were lagging.
+        pub fn load_shedding_stats(&self) -> LoadSheddingStats {
+            self.load_shedder.stats()
+        }
+
         /// Add signed agent info to the conductor
         pub async fn add_agent_infos(
             &self,
@@ -2440,41 +4378,172 @@ mod misc_impls {
             .await
         }
 
+        /// Import a source chain written by the LMDB-era conductor into a cell.
+        ///
+        /// The legacy chain starts with its own genesis, so the cell must not have
+        /// run genesis yet: its DNA is registered, the chain imported, and then the
+        /// app is installed for the chain's agent, which skips genesis for a chain
+        /// that already has it.
+        ///
+        /// The legacy chain is checked against its hashes and signatures, converted to
+        /// current actions with the link types in `link_types`, signed again by the
+        /// cell's agent and then written to the cell's source chain with validation.
+        /// Returns the number of records imported.
+        pub async fn import_legacy_chain(
+            self: Arc<Self>,
+            cell_id: CellId,
+            chain: LegacyChain,
+            link_types: HashMap<ZomeIndex, LinkType>,
+        ) -> ConductorApiResult<usize> {
+            let space = self.get_or_create_space(cell_id.dna_hash())?;
+            if space
+                .source_chain(self.keystore().clone(), cell_id.agent_pubkey().clone())
+                .await?
+                .chain_head()?
+                .is_some()
+            {
+                return Err(ConductorError::CellAlreadyHasGenesis(cell_id).into());
+            }
+
+            chain
+                .verify(cell_id.agent_pubkey())
+                .await
+                .map_err(ConductorError::from)?;
+            let actions = chain
+                .into_actions(cell_id.dna_hash().clone(), &link_types)
+                .map_err(ConductorError::from)?;
+
+            let keystore = self.keystore();
+            let mut records = Vec::with_capacity(actions.len());
+            for (action, entry) in actions {
+                let action =
+                    SignedActionHashed::sign(keystore, ActionHashed::from_content_sync(action))
+                        .await
+                        .map_err(holochain_keystore::KeystoreError::LairError)?;
+                records.push(Record::new(action, entry));
+            }
+
+            let imported = records.len();
+            graft_records_onto_source_chain::graft_records(self, cell_id, true, records).await?;
+            Ok(imported)
+        }
+
         /// Update coordinator zomes on an existing dna.
+        ///
+        /// The updated coordinators are first staged in a new ribosome and smoke tested,
+        /// see [`RealRibosome::smoke_test_zomes`]. Only if that succeeds is the ribosome
+        /// of the dna swapped for the staged one, so a failed update leaves the dna
+        /// running its previous coordinators. The previous coordinators are kept so the
+        /// update can be undone with [`Conductor::rollback_coordinators`].
         pub async fn update_coordinators(
             &self,
             hash: &DnaHash,
             coordinator_zomes: CoordinatorZomes,
             wasms: Vec<wasm::DnaWasm>,
         ) -> ConductorResult<()> {
-            // Note this isn't really concurrent safe. It would be a race condition to update the
-            // same dna concurrently.
-            let mut ribosome = self
-                .ribosome_store()
-                .share_ref(|d| match d.get_ribosome(hash) {
-                    Some(dna) => Ok(dna),
-                    None => Err(DnaError::DnaMissing(hash.to_owned())),
-                })?;
-            let _old_wasms = ribosome
-                .dna_file
+            let current = self.get_ribosome(hash)?;
+            let mut dna_file = current.dna_file().clone();
+            let _old_wasms = dna_file
                 .update_coordinators(coordinator_zomes.clone(), wasms.clone())
                 .await?;
 
+            let zome_names: Vec<_> = coordinator_zomes
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect();
+            let staged = self.stage_coordinators(hash, dna_file, &zome_names).await?;
+
             // Add new wasm code to db.
             self.put_wasm_code(
-                ribosome.dna_def().clone(),
+                staged.dna_def().clone(),
                 wasms.into_iter(),
                 Vec::with_capacity(0),
             )
             .await?;
 
-            // Update RibosomeStore.
-            self.ribosome_store()
-                .share_mut(|d| d.add_ribosome(ribosome));
-
             // TODO: Remove old wasm code? (Maybe this needs to be done on restart as it could be in use).
+            self.swap_coordinators(&current, staged)
+        }
 
-            Ok(())
+        /// Go back to the coordinator zomes a dna had before its last
+        /// [`Conductor::update_coordinators`].
+        ///
+        /// The coordinators which are rolled back from become the previous coordinators,
+        /// so rolling back twice restores the update. Previous coordinators are only
+        /// kept in memory, so there is nothing to roll back to after a restart.
+        pub async fn rollback_coordinators(&self, hash: &DnaHash) -> ConductorResult<()> {
+            let current = self.get_ribosome(hash)?;
+            let previous = self
+                .ribosome_store()
+                .share_ref(|d| d.get_previous_coordinators(hash))
+                .ok_or_else(|| ConductorError::NoPreviousCoordinators(hash.clone()))?;
+            let zome_names: Vec<_> = previous
+                .dna_def()
+                .coordinator_zomes
+                .iter()
+                .map(|(name, _)| name.clone())
+                .collect();
+            let staged = self.stage_coordinators(hash, previous, &zome_names).await?;
+            self.swap_coordinators(&current, staged)
+        }
+
+        /// Build and smoke test a ribosome for a dna file with new coordinators.
+        ///
+        /// Besides [`RealRibosome::smoke_test_zomes`], the init callbacks are run against
+        /// the source chain of one of the running cells of the dna, if there is one,
+        /// without committing anything.
+        async fn stage_coordinators(
+            &self,
+            hash: &DnaHash,
+            dna_file: DnaFile,
+            zome_names: &[ZomeName],
+        ) -> ConductorResult<RealRibosome> {
+            use crate::conductor::cell::error::CellError;
+            use crate::core::ribosome::error::RibosomeError;
+            use crate::core::ribosome::guest_callback::init::InitResult;
+
+            let failed = |err: RibosomeError| {
+                tracing::warn!(?hash, ?err, "Coordinator update failed its smoke test");
+                ConductorError::CoordinatorSmokeTestFailed(hash.clone(), Box::new(err))
+            };
+
+            let staged = RealRibosome::new(dna_file, self.wasmer_module_cache.clone())
+                .await
+                .map_err(failed)?;
+            staged.smoke_test_zomes(zome_names).await.map_err(failed)?;
+
+            let cell = self.running_cells.share_ref(|cells| {
+                cells
+                    .iter()
+                    .find(|(cell_id, _)| cell_id.dna_hash() == hash)
+                    .map(|(_, item)| item.cell.clone())
+            });
+            if let Some(cell) = cell {
+                match cell.smoke_test_init(staged.clone()).await {
+                    Ok(InitResult::Fail(zome_name, reason)) => {
+                        return Err(failed(RibosomeError::InitFailed(zome_name, reason)));
+                    }
+                    // Dependencies may only be missing for now, init is retried
+                    // when the cell is next called.
+                    Ok(InitResult::Pass | InitResult::UnresolvedDependencies(..)) => {}
+                    Err(CellError::RibosomeError(err)) => return Err(failed(err)),
+                    Err(err) => return Err(err.into()),
+                }
+            }
+            Ok(staged)
+        }
+
+        /// Atomically replace the `current` ribosome of a dna with a `staged` one.
+        fn swap_coordinators(
+            &self,
+            current: &RealRibosome,
+            staged: RealRibosome,
+        ) -> ConductorResult<()> {
+            self.ribosome_store()
+                .share_mut(|d| d.swap_coordinators(current, staged))
+                .map_err(|_| {
+                    ConductorError::ConcurrentCoordinatorUpdate(current.dna_hash().clone())
+                })
         }
     }
 }
@@ -2493,6 +4562,15 @@ mod accessor_impls {
             self.spaces.queue_consumer_map.clone()
         }
 
+        pub(crate) fn firehose(&self) -> &Firehose {
+            &self.firehose
+        }
+
+        /// The registry the queue consumers of this conductor record their queues in.
+        pub(crate) fn workflow_queues(&self) -> Arc<WorkflowQueueRegistry> {
+            self.workflow_queues.clone()
+        }
+
         /// Get a signal broadcast sender for a cell.
         pub async fn get_signal_tx(
             &self,
@@ -2593,13 +4671,60 @@ mod accessor_impls {
                 .find_app_containing_cell(cell_id)
                 .cloned())
         }
+
+        /// What gets do with data by warranted authors, by default, for the
+        /// app which contains the given cell.
+        pub async fn warranted_authors_for_cell(
+            &self,
+            cell_id: &CellId,
+        ) -> ConductorResult<WarrantedAuthors> {
+            if let Some(warranted_authors) = self
+                .warranted_authors
+                .share_ref(|cached| cached.get(cell_id).copied())
+            {
+                return Ok(warranted_authors);
+            }
+            let state = self.get_state().await?;
+            let warranted_authors = state
+                .find_app_containing_cell(cell_id)
+                .and_then(|app| state.warranted_authors(app.id()))
+                .unwrap_or_default();
+            self.warranted_authors.share_mut(|cached| {
+                cached.insert(cell_id.clone(), warranted_authors);
+            });
+            Ok(warranted_authors)
+        }
+
+        /// The zome call timeout, in milliseconds, of the app which contains
+        /// the given cell, if it was installed with one.
+        pub async fn zome_call_timeout_for_cell(
+            &self,
+            cell_id: &CellId,
+        ) -> ConductorResult<Option<u64>> {
+            if let Some(timeout_ms) = self
+                .zome_call_timeouts
+                .share_ref(|cached| cached.get(cell_id).copied())
+            {
+                return Ok(timeout_ms);
+            }
+            let state = self.get_state().await?;
+            let timeout_ms = state
+                .find_app_containing_cell(cell_id)
+                .and_then(|app| state.zome_call_timeout_ms(app.id()));
+            self.zome_call_timeouts.share_mut(|cached| {
+                cached.insert(cell_id.clone(), timeout_ms);
+            });
+            Ok(timeout_ms)
+        }
     }
 }
 
 mod authenticate_token_impls {
     use super::*;
     use holochain_conductor_api::{
-        AppAuthenticationToken, AppAuthenticationTokenIssued, IssueAppAuthenticationTokenPayload,
+        AdminAuthenticationToken, AdminAuthenticationTokenIssued, AdminRequest, AdminScope,
+        AppAuthenticationToken, AppAuthenticationTokenIssued, IssueAdminAuthenticationTokenPayload,
+        IssueAppAuthenticationTokenPayload,
     };
 
     impl Conductor {
@@ -2648,6 +4773,75 @@ mod authenticate_token_impls {
                 app_connection_auth.authenticate_token(token, app_id)
             })
         }
+
+        /// Issue a new token for connecting to admin interfaces which require one.
+        pub fn issue_admin_authentication_token(
+            &self,
+            payload: IssueAdminAuthenticationTokenPayload,
+        ) -> ConductorResult<AdminAuthenticationTokenIssued> {
+            let (token, expires_at) = self.admin_auth_token_store.share_mut(|admin_auth| {
+                admin_auth.issue_token(payload.scope, payload.expiry_seconds)
+            });
+
+            Ok(AdminAuthenticationTokenIssued {
+                token,
+                expires_at: expires_at
+                    .and_then(|i| i.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|d| Timestamp::saturating_from_dur(&d)),
+            })
+        }
+
+        /// Revoke an admin interface authentication token.
+        pub fn revoke_admin_authentication_token(
+            &self,
+            token: AdminAuthenticationToken,
+        ) -> ConductorResult<()> {
+            self.admin_auth_token_store
+                .share_mut(|admin_auth| admin_auth.revoke_token(&token));
+
+            Ok(())
+        }
+
+        /// Authenticate the admin interface authentication `token`.
+        ///
+        /// Returns the [AdminScope] that the token was issued for.
+        pub fn authenticate_admin_token(
+            &self,
+            token: &AdminAuthenticationToken,
+        ) -> ConductorResult<AdminScope> {
+            self.admin_auth_token_store
+                .share_mut(|admin_auth| admin_auth.authenticate_token(token))
+        }
+
+        /// Check that the scope of the admin `token` permits the `request`.
+        ///
+        /// The token is authenticated again, so that revoked and expired tokens are refused
+        /// on connections which authenticated with them earlier.
+        pub async fn authorize_admin_request(
+            &self,
+            token: &AdminAuthenticationToken,
+            request: &AdminRequest,
+        ) -> ConductorResult<()> {
+            let scope = self.authenticate_admin_token(token)?;
+            let app_cells = match &scope {
+                AdminScope::ReadOnly => HashSet::new(),
+                AdminScope::App { installed_app_id } => self
+                    .get_state()
+                    .await?
+                    .get_app(installed_app_id)
+                    .map(|app| app.all_cells().cloned().collect())
+                    .unwrap_or_default(),
+            };
+
+            if scope.permits(request, |cell_id| app_cells.contains(cell_id)) {
+                Ok(())
+            } else {
+                Err(ConductorError::FailedAuthenticationError(format!(
+                    "Request is not permitted by the token scope {:?}",
+                    scope
+                )))
+            }
+        }
     }
 }
 
@@ -2820,17 +5014,19 @@ impl Conductor {
             .running_cells
             .share_ref(|c| c.keys().cloned().collect());
 
+        let state = &state;
         let tasks = app_cells.difference(&on_cells).map(|cell_id| {
             let handle = self.clone();
             let chc = handle.chc(self.keystore().clone(), cell_id);
             async move {
-                let holochain_p2p_cell =
-                    handle.holochain_p2p.to_dna(cell_id.dna_hash().clone(), chc);
+                let holochain_p2p_cell = handle.holochain_p2p.to_cell(cell_id.clone(), chc);
 
                 let space = handle
                     .get_or_create_space(cell_id.dna_hash())
                     .map_err(|e| CellError::FailedToCreateDnaSpace(ConductorError::from(e).into()))
                     .map_err(|err| (cell_id.clone(), err))?;
+                *space.validation_queue_order.write() =
+                    state.validation_queue_order(cell_id.dna_hash());
 
                 let signal_tx = handle
                     .get_signal_tx(cell_id)
@@ -2913,6 +5109,8 @@ impl Conductor {
                 }
             })
             .await?;
+        self.warranted_authors.share_mut(|cached| cached.clear());
+        self.zome_call_timeouts.share_mut(|cached| cached.clear());
         Ok(app)
     }
 
@@ -3206,6 +5404,46 @@ pub async fn full_integration_dump(
         .await
 }
 
+/// Dump up to `limit` ops of a DHT or cache database which come after the
+/// `cursor` RowId, and return the RowId of the last one.
+async fn dht_ops_dump_page<Kind: DbKindT>(
+    db: &DbRead<Kind>,
+    cursor: Option<u64>,
+    limit: u32,
+) -> ConductorApiResult<(Vec<DhtOpDump>, Option<u64>)> {
+    db.read_async(move |txn| {
+        let mut stmt = txn.prepare(state_dump::DHT_OPS_PAGE)?;
+        let page = stmt
+            .query_and_then(
+                named_params! {
+                    ":cursor": cursor.unwrap_or(0),
+                    ":limit": limit,
+                },
+                |row| {
+                    let op = holochain_state::query::map_sql_dht_op(false, "dht_type", row)?;
+                    let rowid: u64 = row.get("rowid")?;
+                    StateQueryResult::Ok((
+                        rowid,
+                        DhtOpDump {
+                            op,
+                            validation_status: row.get("validation_status")?,
+                            when_integrated: row.get("when_integrated")?,
+                            validation_provenance: row
+                                .get::<_, Option<Vec<u8>>>("validation_provenance")?
+                                .map(from_blob)
+                                .transpose()?,
+                        },
+                    ))
+                },
+            )?
+            .collect::<StateQueryResult<Vec<_>>>()?;
+        let last = page.last().map(|(rowid, _)| *rowid);
+        let ops = page.into_iter().map(|(_, op)| op).collect();
+        ConductorApiResult::Ok((ops, last))
+    })
+    .await
+}
+
 fn query_dht_ops_from_statement(
     txn: &Transaction,
     stmt_str: &str,
@@ -0,0 +1,121 @@
+use holochain_conductor_api::{AdminAuthenticationToken, AdminScope};
+use rand::RngCore;
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use crate::conductor::error::{ConductorError, ConductorResult};
+
+/// The tokens issued for connecting to admin interfaces which require one.
+///
+/// Unlike app tokens, admin tokens are never single use. A connection keeps presenting
+/// its token with every request, so that revoking or expiring a token takes effect on
+/// connections which are already open.
+pub struct AdminAuthTokenStore {
+    issued_tokens: HashMap<AdminAuthenticationToken, TokenMeta>,
+}
+
+impl AdminAuthTokenStore {
+    pub fn new() -> Self {
+        Self {
+            issued_tokens: HashMap::new(),
+        }
+    }
+
+    /// Issue a token limited to `scope`, which expires after `expiry_seconds` or never if that is 0.
+    pub fn issue_token(
+        &mut self,
+        scope: AdminScope,
+        expiry_seconds: u64,
+    ) -> (AdminAuthenticationToken, Option<SystemTime>) {
+        let mut token = [0u8; 64];
+        rand::thread_rng().fill_bytes(&mut token);
+        let token = token.to_vec();
+
+        let expires_at = if expiry_seconds > 0 {
+            SystemTime::now().checked_add(std::time::Duration::from_secs(expiry_seconds))
+        } else {
+            None
+        };
+
+        self.issued_tokens
+            .insert(token.clone(), TokenMeta { scope, expires_at });
+        self.remove_expired_tokens();
+
+        (token, expires_at)
+    }
+
+    /// Revoke a token. Revoking a token which doesn't exist is not an error.
+    pub fn revoke_token(&mut self, token: &AdminAuthenticationToken) {
+        self.issued_tokens.remove(token);
+    }
+
+    /// Authenticate a token and return the scope it was issued for.
+    pub fn authenticate_token(
+        &mut self,
+        token: &AdminAuthenticationToken,
+    ) -> ConductorResult<AdminScope> {
+        self.remove_expired_tokens();
+
+        self.issued_tokens
+            .get(token)
+            .map(|meta| meta.scope.clone())
+            .ok_or_else(|| ConductorError::FailedAuthenticationError("Invalid token".to_string()))
+    }
+
+    fn remove_expired_tokens(&mut self) {
+        let current_time = SystemTime::now();
+
+        self.issued_tokens.retain(|_, meta| {
+            meta.expires_at
+                .map_or(true, |expires_at| expires_at > current_time)
+        });
+    }
+}
+
+impl Default for AdminAuthTokenStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TokenMeta {
+    scope: AdminScope,
+    expires_at: Option<SystemTime>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_is_reusable_until_revoked() {
+        let mut auth = AdminAuthTokenStore::new();
+        let (token, expiry) = auth.issue_token(AdminScope::ReadOnly, 0);
+        assert!(expiry.is_none());
+
+        for _ in 0..3 {
+            assert_eq!(
+                AdminScope::ReadOnly,
+                auth.authenticate_token(&token).unwrap()
+            );
+        }
+
+        auth.revoke_token(&token);
+        assert!(auth.authenticate_token(&token).is_err());
+    }
+
+    #[test]
+    fn expired_token_is_refused() {
+        let mut auth = AdminAuthTokenStore::new();
+        let (token, _) = auth.issue_token(AdminScope::ReadOnly, 10);
+        auth.issued_tokens.get_mut(&token).unwrap().expires_at = Some(
+            SystemTime::now()
+                .checked_sub(std::time::Duration::from_secs(10))
+                .unwrap(),
+        );
+
+        assert!(auth.authenticate_token(&token).is_err());
+        assert!(auth.issued_tokens.is_empty());
+    }
+}
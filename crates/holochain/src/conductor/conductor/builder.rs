@@ -2,10 +2,15 @@ use super::*;
 use crate::conductor::kitsune_host_impl::KitsuneHostImpl;
 use crate::conductor::manager::OutcomeReceiver;
 use crate::conductor::metrics::{create_post_commit_duration_metric, PostCommitDurationMetric};
-use crate::conductor::paths::DataRootPath;
+use crate::conductor::paths::{DataRootPath, DatabasesRootPath};
 use crate::conductor::ribosome_store::RibosomeStore;
 use crate::conductor::ConductorHandle;
 use holochain_conductor_api::conductor::paths::KeystorePath;
+use holochain_keystore::keychain_store::os_keychain;
+use holochain_keystore::{
+    DangerTestKeystore, Keystore, LairInProcKeystore, LairServerKeystore, SoftwareKeystore,
+    SoftwareKeystoreStore,
+};
 
 /// A configurable Builder for Conductor and sometimes ConductorHandle
 #[derive(Default)]
@@ -23,6 +28,8 @@ pub struct ConductorBuilder {
     pub state: Option<ConductorState>,
     /// Skip printing setup info to stdout
     pub no_print_setup: bool,
+    /// A snapshot directory to restore the databases from before they are opened
+    pub restore_snapshot: Option<PathBuf>,
 }
 
 impl ConductorBuilder {
@@ -51,6 +58,14 @@ impl ConductorBuilder {
         self
     }
 
+    /// Restore the databases from a snapshot taken with
+    /// [`AdminRequest::Snapshot`](holochain_conductor_api::AdminRequest::Snapshot)
+    /// before the conductor opens them.
+    pub fn restore_snapshot(mut self, snapshot_dir: PathBuf) -> Self {
+        self.restore_snapshot = Some(snapshot_dir);
+        self
+    }
+
     /// Set the data root path for the conductor that will be built.
     pub fn with_data_root_path(mut self, data_root_path: DataRootPath) -> Self {
         self.config.data_root_path = Some(data_root_path);
@@ -60,6 +75,11 @@ impl ConductorBuilder {
     /// Initialize a "production" Conductor
     #[tracing::instrument(skip_all, fields(scope = self.config.network.tracing_scope))]
     pub async fn build(self) -> ConductorResult<ConductorHandle> {
+        let redact_hashes = self
+            .config
+            .conductor_tuning_params()
+            .redact_hashes_in_logs();
+        holo_hash::set_redacted(redact_hashes);
         tracing::debug!(?self.config);
 
         let keystore = if let Some(keystore) = self.keystore {
@@ -74,34 +94,10 @@ impl ConductorBuilder {
                     tracing::warn!("{}", MSG);
                 }
             }
-            let get_passphrase = || -> ConductorResult<sodoken::BufRead> {
-                match self.passphrase {
-                    None => Err(
-                        one_err::OneErr::new("passphrase required for lair keystore api").into(),
-                    ),
-                    Some(p) => Ok(p),
-                }
-            };
-            match &self.config.keystore {
-                KeystoreConfig::DangerTestKeystore => {
-                    holochain_keystore::spawn_test_keystore().await?
-                }
-                KeystoreConfig::LairServer { connection_url } => {
-                    warn_no_encryption();
-                    let passphrase = get_passphrase()?;
-                    match spawn_lair_keystore(connection_url.clone(), passphrase).await {
-                        Ok(keystore) => keystore,
-                        Err(err) => {
-                            tracing::error!(?err, "Failed to spawn Lair keystore");
-                            return Err(err.into());
-                        }
-                    }
-                }
-                KeystoreConfig::LairServerInProc { lair_root } => {
-                    warn_no_encryption();
-
-                    let keystore_root_path: KeystorePath = match lair_root {
-                        Some(lair_root) => lair_root.clone(),
+            let keystore_root_path =
+                |root: &Option<KeystorePath>| -> ConductorResult<KeystorePath> {
+                    Ok(match root {
+                        Some(root) => root.clone(),
                         None => self
                             .config
                             .data_root_path
@@ -109,19 +105,41 @@ impl ConductorBuilder {
                             .ok_or(ConductorError::NoDataRootPath)?
                             .clone()
                             .try_into()?,
-                    };
-                    let keystore_config_path = keystore_root_path
-                        .as_ref()
-                        .join("lair-keystore-config.yaml");
-                    let passphrase = get_passphrase()?;
-
-                    match spawn_lair_keystore_in_proc(&keystore_config_path, passphrase).await {
-                        Ok(keystore) => keystore,
-                        Err(err) => {
-                            tracing::error!(?err, "Failed to spawn Lair keystore in process");
-                            return Err(err.into());
-                        }
-                    }
+                    })
+                };
+            let keystore_config_path = |root: &Option<KeystorePath>| -> ConductorResult<PathBuf> {
+                Ok(keystore_root_path(root)?
+                    .as_ref()
+                    .join("lair-keystore-config.yaml"))
+            };
+            let backend: Box<dyn Keystore> = match &self.config.keystore {
+                KeystoreConfig::DangerTestKeystore => Box::new(DangerTestKeystore),
+                KeystoreConfig::LairServer { connection_url } => Box::new(LairServerKeystore {
+                    connection_url: connection_url.clone(),
+                }),
+                KeystoreConfig::LairServerInProc { lair_root } => Box::new(LairInProcKeystore {
+                    config_path: keystore_config_path(lair_root)?,
+                }),
+                KeystoreConfig::InProcSoftware { keystore_root } => Box::new(SoftwareKeystore {
+                    config_path: keystore_config_path(keystore_root)?,
+                    store: SoftwareKeystoreStore::Sqlite,
+                }),
+                KeystoreConfig::OsKeychain {
+                    service,
+                    keystore_root,
+                } => Box::new(SoftwareKeystore {
+                    config_path: keystore_config_path(keystore_root)?,
+                    store: SoftwareKeystoreStore::Keychain(os_keychain(service.clone())?),
+                }),
+            };
+            if backend.requires_passphrase() {
+                warn_no_encryption();
+            }
+            match backend.spawn(self.passphrase.clone()).await {
+                Ok(keystore) => keystore,
+                Err(err) => {
+                    tracing::error!(?err, keystore = ?self.config.keystore, "Failed to spawn keystore");
+                    return Err(err.into());
                 }
             }
         };
@@ -131,6 +149,7 @@ impl ConductorBuilder {
         let Self {
             ribosome_store,
             config,
+            restore_snapshot,
             ..
         } = self;
 
@@ -138,6 +157,29 @@ impl ConductorBuilder {
 
         let ribosome_store = RwShare::new(ribosome_store);
 
+        // Databases are migrated as they are opened, so note which ones need
+        // migrating now to find out which were migrated once startup is done.
+        let pending_migrations = {
+            let root_db_dir: DatabasesRootPath = config
+                .data_root_path
+                .clone()
+                .ok_or(ConductorError::NoDataRootPath)?
+                .try_into()?;
+            if let Some(snapshot_dir) = restore_snapshot {
+                holochain_sqlite::db::restore_databases_from_snapshot(
+                    &snapshot_dir,
+                    root_db_dir.as_ref(),
+                )?;
+                info!("Conductor startup: databases restored from snapshot.");
+            }
+            holochain_sqlite::db::pending_migrations_in(root_db_dir.as_ref()).unwrap_or_else(
+                |err| {
+                    warn!(?err, "Could not check databases for pending migrations");
+                    Vec::new()
+                },
+            )
+        };
+
         let spaces = Spaces::new(config.clone())?;
         let tag = spaces.get_state().await?.tag().clone();
 
@@ -171,6 +213,11 @@ impl ConductorBuilder {
             Some(keystore.lair_client()),
         );
 
+        if !config.conductor_tuning_params().persist_nonce_windows() {
+            holochain_state::nonce::forget_nonces(&spaces.conductor_db).await?;
+            info!("Conductor startup: nonce windows forgotten.");
+        }
+
         let network_compat = crate::conductor::space::query_conductor_state(&spaces.conductor_db)
             .await?
             .map(|s| s.get_network_compat())
@@ -228,7 +275,7 @@ impl ConductorBuilder {
             });
         }
 
-        Self::finish(
+        let handle = Self::finish(
             handle,
             config,
             p2p_evt,
@@ -236,7 +283,11 @@ impl ConductorBuilder {
             outcome_rx,
             self.no_print_setup,
         )
-        .await
+        .await?;
+
+        handle.record_db_migrations(pending_migrations).await?;
+
+        Ok(handle)
     }
 
     pub(crate) async fn spawn_post_commit(
@@ -307,6 +358,28 @@ impl ConductorBuilder {
 
         info!("Conductor startup: scheduler task started.");
 
+        conductor.clone().start_entry_purge(ENTRY_PURGE_INTERVAL);
+
+        info!("Conductor startup: entry purge task started.");
+
+        conductor
+            .clone()
+            .start_cache_eviction(CACHE_EVICTION_INTERVAL);
+
+        info!("Conductor startup: cache eviction task started.");
+
+        conductor
+            .clone()
+            .start_clone_archival(CLONE_ARCHIVAL_INTERVAL);
+
+        info!("Conductor startup: clone archival task started.");
+
+        conductor
+            .clone()
+            .start_durable_call_outbox(DURABLE_CALL_OUTBOX_INTERVAL);
+
+        info!("Conductor startup: durable call outbox task started.");
+
         tokio::task::spawn(p2p_event_task(p2p_evt, conductor.clone()).in_current_span());
 
         info!("Conductor startup: p2p event task started.");
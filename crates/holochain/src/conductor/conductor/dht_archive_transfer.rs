@@ -0,0 +1,62 @@
+//! Tracks the DHT archives being exported or imported over the admin API in
+//! chunks. Each archive is staged in a temporary file in the databases root,
+//! which is removed once the transfer is finished or replaced.
+
+use holochain_types::prelude::*;
+use holochain_types::share::RwShare;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+
+/// The number of ops of an imported DHT archive which are read into memory and
+/// published at a time.
+pub(crate) const DHT_ARCHIVE_IMPORT_BATCH: usize = 1000;
+
+/// A DHT archive being transferred in chunks.
+#[derive(Clone)]
+struct DhtArchiveTransfer {
+    file: Arc<NamedTempFile>,
+    /// The offset of the next chunk of the transfer.
+    offset: u64,
+}
+
+/// The DHT archives being transferred in one direction, by DNA.
+/// Each DNA has at most one transfer at a time, and starting a new one replaces it.
+#[derive(Clone, Default)]
+pub(crate) struct DhtArchiveTransfers(RwShare<HashMap<DnaHash, DhtArchiveTransfer>>);
+
+impl DhtArchiveTransfers {
+    /// The file of the transfer in progress for a DNA, if its next chunk is at `offset`.
+    pub fn resume(&self, dna_hash: &DnaHash, offset: u64) -> DhtArchiveResult<Arc<NamedTempFile>> {
+        let transfer = self
+            .0
+            .share_ref(|transfers| transfers.get(dna_hash).cloned());
+        match transfer {
+            Some(transfer) if transfer.offset == offset => Ok(transfer.file),
+            transfer => Err(DhtArchiveError::UnexpectedOffset {
+                expected: transfer.map(|t| t.offset).unwrap_or(0),
+                found: offset,
+            }),
+        }
+    }
+
+    /// Record that the next chunk of the transfer for a DNA is at `offset`.
+    pub fn advance(&self, dna_hash: &DnaHash, file: Arc<NamedTempFile>, offset: u64) {
+        self.0.share_mut(|transfers| {
+            transfers.insert(dna_hash.clone(), DhtArchiveTransfer { file, offset });
+        });
+    }
+
+    /// Stop tracking the transfer for a DNA. Its file is removed once it is no longer in use.
+    pub fn finish(&self, dna_hash: &DnaHash) {
+        self.0.share_mut(|transfers| {
+            transfers.remove(dna_hash);
+        });
+    }
+}
+
+/// Create the file to stage a new transfer in, in the databases root.
+pub(crate) fn new_transfer_file(dir: &Path) -> std::io::Result<Arc<NamedTempFile>> {
+    Ok(Arc::new(NamedTempFile::new_in(dir)?))
+}
@@ -0,0 +1,103 @@
+//! Streams the ops integrated into each space to the clients connected to
+//! the conductor's firehose interface.
+
+use holochain_conductor_api::FirehoseOp;
+use holochain_types::prelude::*;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+// Number of ops in buffer before we start dropping them for a slow client.
+// Integration happens in batches, so this needs to hold a few batches for
+// a busy space, clients which fall further behind are told how many ops
+// they missed.
+const FIREHOSE_BUFFER_SIZE: usize = 1024;
+
+/// Container to connect the integration workflow of each space to the
+/// firehose interface, by DNA hash.
+#[derive(Debug, Clone, Default)]
+pub struct Firehose {
+    channels: Arc<parking_lot::Mutex<HashMap<DnaHash, broadcast::Sender<Arc<FirehoseOp>>>>>,
+}
+
+impl Firehose {
+    /// Create the sender the integration workflow of a space uses to
+    /// stream its integrated ops.
+    pub(crate) fn create_send_handle(&self, dna_hash: DnaHash) -> FirehoseSender {
+        FirehoseSender(self.channel(dna_hash))
+    }
+
+    /// Subscribe to the ops integrated into a space.
+    ///
+    /// The space does not need to exist, and the sender does not need to be
+    /// created before subscribing.
+    pub(crate) fn subscribe(&self, dna_hash: DnaHash) -> broadcast::Receiver<Arc<FirehoseOp>> {
+        self.channel(dna_hash).subscribe()
+    }
+
+    fn channel(&self, dna_hash: DnaHash) -> broadcast::Sender<Arc<FirehoseOp>> {
+        match self.channels.lock().entry(dna_hash) {
+            Entry::Occupied(e) => e.get().clone(),
+            Entry::Vacant(e) => e.insert(broadcast::channel(FIREHOSE_BUFFER_SIZE).0).clone(),
+        }
+    }
+}
+
+/// Sends the ops integrated into one space to its firehose subscribers.
+#[derive(Debug, Clone)]
+pub struct FirehoseSender(broadcast::Sender<Arc<FirehoseOp>>);
+
+impl FirehoseSender {
+    /// Create a sender which isn't connected to a firehose interface.
+    pub fn disconnected() -> Self {
+        Self(broadcast::channel(1).0)
+    }
+
+    /// Whether any client is subscribed to this space, so the integrated
+    /// ops are only read back from the database when they will be sent.
+    pub fn has_subscribers(&self) -> bool {
+        self.0.receiver_count() > 0
+    }
+
+    /// Send an integrated op to all subscribers.
+    pub fn send(&self, op: FirehoseOp) {
+        // Only fails if there are no subscribers left, which is fine.
+        let _ = self.0.send(Arc::new(op));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+
+    #[tokio::test]
+    async fn ops_are_only_sent_to_subscribers_of_the_space() {
+        let firehose = Firehose::default();
+        let dna_hash = fixt!(DnaHash);
+        let tx = firehose.create_send_handle(dna_hash.clone());
+        assert!(!tx.has_subscribers());
+
+        let mut rx = firehose.subscribe(dna_hash.clone());
+        let mut other_rx = firehose.subscribe(fixt!(DnaHash));
+        assert!(tx.has_subscribers());
+
+        let op: DhtOp =
+            ChainOp::RegisterAgentActivity(fixt!(Signature), Action::Dna(fixt!(Dna))).into();
+        let op = FirehoseOp {
+            dna_hash,
+            op_hash: DhtOpHash::with_data_sync(&op),
+            op_type: op.get_type(),
+            basis: op.dht_basis(),
+            author: op.author(),
+            authored_timestamp: op.timestamp(),
+            when_integrated: Timestamp::now(),
+            op,
+        };
+        tx.send(op.clone());
+
+        assert_eq!(*rx.recv().await.unwrap(), op);
+        assert!(other_rx.try_recv().is_err());
+    }
+}
@@ -15,13 +15,22 @@ pub(crate) async fn graft_records_onto_source_chain(
         let _ = err?;
     }
 
+    graft_records(conductor, cell_id, validate, records).await
+}
+
+/// Graft records onto the source chain of a cell, which doesn't need to be
+/// installed yet. The DNA of the cell must be registered.
+pub(crate) async fn graft_records(
+    conductor: ConductorHandle,
+    cell_id: CellId,
+    validate: bool,
+    records: Vec<Record>,
+) -> ConductorApiResult<()> {
     // Get or create the space for this cell.
     let space = conductor.get_or_create_space(cell_id.dna_hash())?;
 
     let chc = None;
-    let network = conductor
-        .holochain_p2p()
-        .to_dna(cell_id.dna_hash().clone(), chc);
+    let network = conductor.holochain_p2p().to_cell(cell_id.clone(), chc);
 
     let source_chain: SourceChain = space
         .source_chain(conductor.keystore().clone(), cell_id.agent_pubkey().clone())
@@ -62,6 +71,9 @@ pub(crate) async fn graft_records_onto_source_chain(
         .write_async({
             let cell_id = cell_id.clone();
             move |txn| {
+                if holochain_state::chain_lock::is_chain_frozen(txn, cell_id.agent_pubkey())? {
+                    return Err(SourceChainError::ChainFrozen);
+                }
                 if let Some((_, seq)) = chain_top {
                     // Remove records above the grafting position.
                     //
@@ -135,9 +147,7 @@ async fn validate_records(
     let space = handle.get_or_create_space(cell_id.dna_hash())?;
     let ribosome = handle.get_ribosome(cell_id.dna_hash())?;
     let chc = None;
-    let network = handle
-        .holochain_p2p()
-        .to_dna(cell_id.dna_hash().clone(), chc);
+    let network = handle.holochain_p2p().to_cell(cell_id.clone(), chc);
 
     // Create a raw source chain to validate against because
     // genesis may not have been run yet.
@@ -0,0 +1,150 @@
+//! Refuses low priority requests from other peers while the conductor's
+//! workflows are lagging, so that an overloaded conductor keeps capacity for
+//! zome calls instead of timing out everything.
+
+use crate::core::metrics::WorkflowQueueRegistry;
+use holochain_conductor_api::LoadSheddingStats;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The kinds of request which are shed while the conductor is overloaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SheddableRequest {
+    /// A gossip request for the op hashes this conductor holds.
+    GossipBackfill,
+    /// A get from another peer which scans an index, like a get of links or of agent
+    /// activity, rather than looking up a single hash. The peer can retry it against
+    /// another authority.
+    ScanningGet,
+}
+
+/// Decides whether to shed each low priority request, and counts the ones it sheds.
+#[derive(Debug)]
+pub(crate) struct LoadShedder {
+    /// The workflow lag above which requests are shed. Zero disables shedding.
+    lag_threshold: Duration,
+    /// The queues of the conductor whose workflows' lag is measured.
+    workflow_queues: Arc<WorkflowQueueRegistry>,
+    shedding: AtomicBool,
+    shed_gossip_backfill: AtomicU64,
+    shed_gets: AtomicU64,
+}
+
+impl LoadShedder {
+    pub fn new(lag_threshold: Duration, workflow_queues: Arc<WorkflowQueueRegistry>) -> Self {
+        Self {
+            lag_threshold,
+            workflow_queues,
+            shedding: AtomicBool::new(false),
+            shed_gossip_backfill: AtomicU64::new(0),
+            shed_gets: AtomicU64::new(0),
+        }
+    }
+
+    /// Whether a request should be refused because the conductor is overloaded.
+    pub fn shed(&self, request: SheddableRequest) -> bool {
+        self.shed_with_lag(request, self.workflow_queues.validation_lag())
+    }
+
+    fn shed_with_lag(&self, request: SheddableRequest, lag: Duration) -> bool {
+        let overloaded = !self.lag_threshold.is_zero() && lag > self.lag_threshold;
+        if self.shedding.swap(overloaded, Ordering::Relaxed) != overloaded {
+            if overloaded {
+                tracing::warn!(
+                    ?lag,
+                    threshold = ?self.lag_threshold,
+                    "Workflows are lagging, shedding gossip backfill and scanning gets from other peers"
+                );
+            } else {
+                tracing::info!(
+                    ?lag,
+                    "Workflows have caught up, no longer shedding requests"
+                );
+            }
+        }
+        if overloaded {
+            let counter = match request {
+                SheddableRequest::GossipBackfill => &self.shed_gossip_backfill,
+                SheddableRequest::ScanningGet => &self.shed_gets,
+            };
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+        overloaded
+    }
+
+    /// The current state of load shedding and the number of requests shed so far.
+    pub fn stats(&self) -> LoadSheddingStats {
+        LoadSheddingStats {
+            shedding: self.shedding.load(Ordering::Relaxed),
+            lag_ms: self.workflow_queues.validation_lag().as_millis() as u64,
+            lag_threshold_ms: self.lag_threshold.as_millis() as u64,
+            shed_gossip_backfill: self.shed_gossip_backfill.load(Ordering::Relaxed),
+            shed_gets: self.shed_gets.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::metrics::{WorkflowQueueKey, WorkflowQueueKind};
+    use holo_hash::DnaHash;
+
+    fn shedder(lag_threshold: Duration) -> LoadShedder {
+        LoadShedder::new(lag_threshold, Arc::new(WorkflowQueueRegistry::new()))
+    }
+
+    #[test]
+    fn sheds_only_while_lagging() {
+        let shedder = shedder(Duration::from_secs(10));
+        let lagging = Duration::from_secs(11);
+
+        assert!(!shedder.shed_with_lag(SheddableRequest::ScanningGet, Duration::from_secs(10)));
+        assert!(shedder.shed_with_lag(SheddableRequest::ScanningGet, lagging));
+        assert!(shedder.shed_with_lag(SheddableRequest::GossipBackfill, lagging));
+        assert!(shedder.shed_with_lag(SheddableRequest::ScanningGet, lagging));
+        assert!(shedder.shedding.load(Ordering::Relaxed));
+
+        assert!(!shedder.shed_with_lag(SheddableRequest::ScanningGet, Duration::ZERO));
+        let stats = shedder.stats();
+        assert!(!stats.shedding);
+        assert_eq!(10_000, stats.lag_threshold_ms);
+        assert_eq!(1, stats.shed_gossip_backfill);
+        assert_eq!(2, stats.shed_gets);
+    }
+
+    #[test]
+    fn zero_threshold_disables_shedding() {
+        let shedder = shedder(Duration::ZERO);
+        assert!(!shedder.shed_with_lag(SheddableRequest::ScanningGet, Duration::from_secs(3600)));
+        assert_eq!(0, shedder.stats().shed_gets);
+    }
+
+    #[test]
+    fn sheds_while_a_workflow_of_its_own_conductor_lags() {
+        let workflow_queues = Arc::new(WorkflowQueueRegistry::new());
+        let other_conductor_queues = WorkflowQueueRegistry::new();
+        let shedder = LoadShedder::new(Duration::from_millis(50), workflow_queues.clone());
+        // Both conductors run the same DNA.
+        let key = WorkflowQueueKey {
+            dna_hash: Arc::new(DnaHash::from_raw_36(vec![0; 36])),
+            agent: None,
+            kind: WorkflowQueueKind::AppValidation,
+        };
+
+        // Another conductor in the process lagging doesn't make this one shed.
+        other_conductor_queues.record_run_started(&key);
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(!shedder.shed(SheddableRequest::ScanningGet));
+
+        workflow_queues.record_run_started(&key);
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(shedder.shed(SheddableRequest::ScanningGet));
+        assert!(shedder.stats().lag_ms >= 60);
+
+        workflow_queues.record_caught_up(&key);
+        assert!(!shedder.shed(SheddableRequest::ScanningGet));
+        assert_eq!(1, shedder.stats().shed_gets);
+    }
+}
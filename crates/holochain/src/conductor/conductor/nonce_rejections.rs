@@ -0,0 +1,122 @@
+//! Rate limits the records of rejected zome call nonces in the event journal.
+//!
+//! A client replaying zome calls can have every one of them rejected, so rather
+//! than writing each rejection to the journal, the rejections of an agent's nonces
+//! for the same reason are recorded at most once per [`RECORD_INTERVAL`], as one
+//! event which counts them.
+
+use holo_hash::AgentPubKey;
+use holochain_types::prelude::{ConductorEvent, NonceRejection};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How often the rejections of an agent's nonces for the same reason are recorded.
+const RECORD_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The rejections of an agent's nonces for one reason since they were last recorded.
+#[derive(Debug)]
+struct Rejections {
+    recorded_at: Instant,
+    unrecorded: u32,
+}
+
+/// Counts rejected nonces, and decides when they are recorded.
+#[derive(Debug, Default)]
+pub(crate) struct NonceRejections(
+    parking_lot::Mutex<HashMap<(AgentPubKey, NonceRejection), Rejections>>,
+);
+
+impl NonceRejections {
+    /// Count a rejected nonce, and return the events which are due to be recorded.
+    ///
+    /// The first rejection of an agent's nonces for a reason is recorded straight
+    /// away. The ones which follow within [`RECORD_INTERVAL`] are counted, and
+    /// recorded as one event once the interval has passed and another nonce of
+    /// any agent is rejected.
+    pub fn reject(
+        &self,
+        agent: AgentPubKey,
+        reason: NonceRejection,
+        now: Instant,
+    ) -> Vec<ConductorEvent> {
+        let mut due = Vec::new();
+        let mut rejections = self.0.lock();
+        rejections.retain(|(agent, reason), rejections| {
+            if now.duration_since(rejections.recorded_at) < RECORD_INTERVAL {
+                return true;
+            }
+            if rejections.unrecorded > 0 {
+                due.push(ConductorEvent::ZomeCallNonceRejected {
+                    agent: agent.clone(),
+                    reason: *reason,
+                    count: rejections.unrecorded,
+                });
+            }
+            false
+        });
+        match rejections.entry((agent.clone(), reason)) {
+            Entry::Occupied(mut entry) => entry.get_mut().unrecorded += 1,
+            Entry::Vacant(entry) => {
+                entry.insert(Rejections {
+                    recorded_at: now,
+                    unrecorded: 0,
+                });
+                due.push(ConductorEvent::ZomeCallNonceRejected {
+                    agent,
+                    reason,
+                    count: 1,
+                });
+            }
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rejected(agent: &AgentPubKey, reason: NonceRejection, count: u32) -> ConductorEvent {
+        ConductorEvent::ZomeCallNonceRejected {
+            agent: agent.clone(),
+            reason,
+            count,
+        }
+    }
+
+    #[test]
+    fn repeated_rejections_are_counted_in_one_event_per_interval() {
+        let rejections = NonceRejections::default();
+        let alice = AgentPubKey::from_raw_36(vec![0; 36]);
+        let bob = AgentPubKey::from_raw_36(vec![1; 36]);
+        let start = Instant::now();
+        let duplicate = NonceRejection::Duplicate;
+
+        assert_eq!(
+            vec![rejected(&alice, duplicate, 1)],
+            rejections.reject(alice.clone(), duplicate, start)
+        );
+        for _ in 0..3 {
+            assert!(rejections
+                .reject(alice.clone(), duplicate, start)
+                .is_empty());
+        }
+        // Other agents and reasons are recorded separately.
+        assert_eq!(
+            vec![rejected(&alice, NonceRejection::Expired, 1)],
+            rejections.reject(alice.clone(), NonceRejection::Expired, start)
+        );
+
+        // Once the interval has passed, the rejections which weren't recorded are.
+        let later = start + RECORD_INTERVAL;
+        let due = rejections.reject(bob.clone(), duplicate, later);
+        assert_eq!(2, due.len());
+        assert!(due.contains(&rejected(&alice, duplicate, 3)));
+        assert!(due.contains(&rejected(&bob, duplicate, 1)));
+        assert_eq!(
+            vec![rejected(&alice, duplicate, 1)],
+            rejections.reject(alice, duplicate, later)
+        );
+    }
+}
@@ -5,6 +5,7 @@ use super::Conductor;
 use super::ConductorState;
 use super::*;
 use crate::conductor::api::error::ConductorApiError;
+use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::guest_callback::validate::ValidateResult;
 use crate::sweettest::*;
 use crate::test_utils::inline_zomes::simple_crud_zome;
@@ -253,6 +254,702 @@ async fn common_genesis_test_app(
         .await
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_event_journal_records_app_lifecycle() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let since = Timestamp::now();
+
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let cell_id = app.cells()[0].cell_id().clone();
+    let port = conductor
+        .raw_handle()
+        .add_app_interface(
+            either::Either::Left(0),
+            holochain_types::websocket::AllowedOrigins::Any,
+            None,
+        )
+        .await
+        .unwrap();
+    conductor
+        .disable_app("app".to_string(), DisabledAppReason::User)
+        .await
+        .unwrap();
+    conductor
+        .raw_handle()
+        .uninstall_app(&"app".to_string())
+        .await
+        .unwrap();
+
+    let events: Vec<_> = conductor
+        .event_journal(since)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.event)
+        .collect();
+    let installed_app_id = "app".to_string();
+    assert_eq!(
+        events,
+        vec![
+            ConductorEvent::CellCreated { cell_id },
+            ConductorEvent::AppInstalled {
+                installed_app_id: installed_app_id.clone()
+            },
+            ConductorEvent::AppEnabled {
+                installed_app_id: installed_app_id.clone()
+            },
+            ConductorEvent::AppInterfaceAttached {
+                port,
+                installed_app_id: None
+            },
+            ConductorEvent::AppDisabled {
+                installed_app_id: installed_app_id.clone(),
+                reason: DisabledAppReason::User
+            },
+            ConductorEvent::AppUninstalled { installed_app_id },
+        ]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_missed_signals_are_persisted_per_app() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let zome = app.cells()[0].zome(SweetInlineZomes::COORDINATOR);
+    let installed_app_id = "app".to_string();
+
+    // Persistence is opt-in.
+    assert_matches!(
+        conductor.missed_signals(&installed_app_id, None).await,
+        Err(ConductorError::MissedSignalsNotPersisted(_))
+    );
+
+    let id = installed_app_id.clone();
+    conductor
+        .update_state(move |mut state| {
+            state.set_missed_signals_buffer_size(id, 2);
+            Ok(state)
+        })
+        .await
+        .unwrap();
+    conductor.spawn_signal_persistence(installed_app_id.clone(), 2);
+
+    for _ in 0..3 {
+        let _: () = conductor.call(&zome, "emit_signal", ()).await;
+    }
+
+    assert_eq_retry_10s!(
+        conductor
+            .missed_signals(&installed_app_id, None)
+            .await
+            .unwrap()
+            .latest_token,
+        Some(3)
+    );
+    let missed = conductor
+        .missed_signals(&installed_app_id, Some(0))
+        .await
+        .unwrap();
+    assert_eq!(
+        missed.signals.iter().map(|s| s.token).collect::<Vec<_>>(),
+        vec![2, 3]
+    );
+    assert!(missed.truncated);
+
+    conductor
+        .disable_app(installed_app_id.clone(), DisabledAppReason::User)
+        .await
+        .unwrap();
+    conductor
+        .raw_handle()
+        .uninstall_app(&installed_app_id)
+        .await
+        .unwrap();
+    assert_matches!(
+        conductor.missed_signals(&installed_app_id, None).await,
+        Err(ConductorError::AppNotInstalled(_))
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_app_zome_call_timeout_cancels_calls() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let zome = app.cells()[0].zome(SweetInlineZomes::COORDINATOR);
+
+    // A zero timeout has always elapsed by the time the call returns.
+    conductor
+        .update_state(|mut state| {
+            state.set_zome_call_timeout_ms("app".to_string(), 0);
+            Ok(state)
+        })
+        .await
+        .unwrap();
+    let err = conductor
+        .call_fallible::<_, ActionHash>(&zome, "create_unit", ())
+        .await
+        .unwrap_err();
+    assert_matches!(
+        err,
+        ConductorApiError::Other(ref e) if e.to_string().contains("was cancelled after")
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_zome_call_timeout_aborts_a_running_call() {
+    holochain_trace::test_run();
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::TheIncredibleHalt]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    conductor
+        .update_state(|mut state| {
+            state.set_zome_call_timeout_ms("app".to_string(), 500);
+            Ok(state)
+        })
+        .await
+        .unwrap();
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let zome = app.cells()[0].zome(TestWasm::TheIncredibleHalt);
+
+    // `smash` loops forever without calling a host function, so only running
+    // out of metering points stops it, well before the metering limit.
+    let err = tokio::time::timeout(
+        std::time::Duration::from_secs(30),
+        conductor.call_fallible::<_, ()>(&zome, "smash", ()),
+    )
+    .await
+    .expect("The call should have been aborted at its deadline")
+    .unwrap_err();
+    assert_matches!(
+        err,
+        ConductorApiError::Other(ref e) if e.to_string().contains("was cancelled after")
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_set_app_network_mode() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+    let cell_id = cell.cell_id();
+    assert_eq!(AppNetworkMode::Normal, conductor.cell_network_mode(cell_id));
+
+    for mode in [AppNetworkMode::Isolated, AppNetworkMode::PublishOnly] {
+        conductor
+            .set_app_network_mode(&"app".to_string(), mode)
+            .await
+            .unwrap();
+        assert_eq!(mode, conductor.cell_network_mode(cell_id));
+    }
+
+    // Local writes and reads still work while the app is isolated.
+    conductor
+        .set_app_network_mode(&"app".to_string(), AppNetworkMode::Isolated)
+        .await
+        .unwrap();
+    let zome = cell.zome(SweetInlineZomes::COORDINATOR);
+    let hash: ActionHash = conductor.call(&zome, "create_unit", ()).await;
+    let record: Option<Record> = conductor.call(&zome, "read", hash).await;
+    assert!(record.is_some());
+
+    conductor
+        .set_app_network_mode(&"app".to_string(), AppNetworkMode::Normal)
+        .await
+        .unwrap();
+    assert_eq!(AppNetworkMode::Normal, conductor.cell_network_mode(cell_id));
+
+    assert_matches!(
+        conductor
+            .set_app_network_mode(&"nope".to_string(), AppNetworkMode::Isolated)
+            .await,
+        Err(ConductorError::AppNotInstalled(_))
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_freeze_cell() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+    let cell_id = cell.cell_id();
+    let zome = cell.zome(SweetInlineZomes::COORDINATOR);
+    let hash: ActionHash = conductor.call(&zome, "create_unit", ()).await;
+
+    conductor.freeze_cell(cell_id).await.unwrap();
+    assert!(conductor.is_cell_frozen(cell_id).await.unwrap());
+
+    // Reads still work, but writes are refused.
+    let record: Option<Record> = conductor.call(&zome, "read", hash).await;
+    let record = record.unwrap();
+    let err = conductor
+        .call_fallible::<_, ActionHash>(&zome, "create_unit", ())
+        .await
+        .unwrap_err();
+    let ConductorApiError::Other(err) = err else {
+        panic!("expected a ribosome error, got {err:?}");
+    };
+    assert_matches!(
+        err.downcast_ref::<RibosomeError>(),
+        Some(RibosomeError::CellFrozen(frozen)) if frozen == cell_id
+    );
+
+    // Grafting writes to the source chain directly, and is refused too.
+    let err = conductor
+        .raw_handle()
+        .graft_records_onto_source_chain(cell_id.clone(), false, vec![record])
+        .await
+        .unwrap_err();
+    assert_matches!(
+        err,
+        ConductorApiError::SourceChainError(SourceChainError::ChainFrozen)
+    );
+
+    conductor.unfreeze_cell(cell_id).await.unwrap();
+    assert!(!conductor.is_cell_frozen(cell_id).await.unwrap());
+    let _: ActionHash = conductor.call(&zome, "create_unit", ()).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_list_zome_functions() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+
+    let zome_fns = conductor
+        .list_zome_functions(&"app".to_string(), cell.cell_id())
+        .await
+        .unwrap();
+    assert_eq!(
+        vec![ZomeName::from(SweetInlineZomes::COORDINATOR)],
+        zome_fns.keys().cloned().collect::<Vec<_>>()
+    );
+    let fns = &zome_fns[&ZomeName::from(SweetInlineZomes::COORDINATOR)];
+    assert!(fns.contains(&"create_unit".into()));
+    assert!(fns.contains(&"read".into()));
+    assert!(fns.windows(2).all(|pair| pair[0] < pair[1]));
+
+    assert_matches!(
+        conductor
+            .list_zome_functions(&"other app".to_string(), cell.cell_id())
+            .await,
+        Err(ConductorError::AppAccessError(_, _))
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_renew_membrane_proof() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+
+    let proof: MembraneProof = Arc::new(SerializedBytes::from(UnsafeBytes::from(vec![1, 2, 3])));
+    let first = conductor
+        .renew_membrane_proof(cell.cell_id(), proof.clone())
+        .await
+        .unwrap();
+    let second = conductor
+        .renew_membrane_proof(cell.cell_id(), proof)
+        .await
+        .unwrap();
+    await_consistency(10, [&cell]).await.unwrap();
+
+    // The store record and agent activity ops of both renewals are valid.
+    let num_valid_ops: usize = cell
+        .dht_db()
+        .read_async(move |txn| -> DatabaseResult<usize> {
+            Ok(txn.query_row(
+                "SELECT COUNT(hash) FROM DhtOp WHERE action_hash IN (:first, :second)
+                AND when_integrated IS NOT NULL AND validation_status = :status",
+                rusqlite::named_params! {
+                    ":first": first,
+                    ":second": second,
+                    ":status": ValidationStatus::Valid,
+                },
+                |row| row.get(0),
+            )?)
+        })
+        .await
+        .unwrap();
+    assert_eq!(num_valid_ops, 4);
+}
+
+#[test]
+fn test_durable_call_backoff_doubles_up_to_a_limit() {
+    use super::durable_call_impls::durable_call_backoff;
+    use std::time::Duration;
+
+    assert_eq!(Duration::from_secs(1), durable_call_backoff(1));
+    assert_eq!(Duration::from_secs(2), durable_call_backoff(2));
+    assert_eq!(Duration::from_secs(16), durable_call_backoff(5));
+    assert_eq!(MAX_DURABLE_CALL_BACKOFF, durable_call_backoff(20));
+    assert_eq!(MAX_DURABLE_CALL_BACKOFF, durable_call_backoff(u32::MAX));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_durable_call_remote_expires_when_the_agent_is_unreachable() {
+    holochain_trace::test_run();
+    let zomes = SweetInlineZomes::new(vec![EntryDef::default_from_id("string")], 0)
+        .integrity_function("validate", |_api, op: Op| match op {
+            Op::StoreEntry(StoreEntry { action, .. })
+                if action.hashed.content.app_entry_def().is_some() =>
+            {
+                Ok(ValidateResult::Invalid("no app entries".into()))
+            }
+            _ => Ok(ValidateResult::Valid),
+        })
+        // Queue a durable call, after committing an invalid entry if asked to.
+        .function("queue", |api, commit_invalid: bool| {
+            if commit_invalid {
+                api.create(CreateInput::new(
+                    InlineZomeSet::get_entry_location(&api, EntryDefIndex(0)),
+                    EntryVisibility::Public,
+                    Entry::app(().try_into().unwrap()).unwrap(),
+                    ChainTopOrdering::default(),
+                ))?;
+            }
+            Ok(api.call_remote_durable(DurableCallRemote::new(
+                fixt!(AgentPubKey),
+                SweetInlineZomes::COORDINATOR.into(),
+                "read".into(),
+                None,
+                ExternIO::encode(()).unwrap(),
+                std::time::Duration::from_secs(60),
+            ))?)
+        });
+    let (dna, _, _) = mk_dna(zomes).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let cell_id = app.cells()[0].cell_id().clone();
+    let zome = app.cells()[0].zome(SweetInlineZomes::COORDINATOR);
+    let mut signals = conductor.subscribe_to_app_signals("app".to_string());
+    let due = |at| {
+        conductor
+            .spaces
+            .conductor_db
+            .read_async(move |txn| holochain_state::durable_call_outbox::due(&txn, at))
+    };
+
+    // A call made alongside writes which fail validation is dropped with them.
+    let result: ConductorApiResult<DurableCallId> =
+        conductor.call_fallible(&zome, "queue", true).await;
+    assert!(result.is_err());
+    assert!(due(Timestamp::max()).await.unwrap().is_empty());
+
+    let call_id: DurableCallId = conductor.call(&zome, "queue", false).await;
+    assert_eq!(
+        vec![call_id],
+        due(Timestamp::max())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|call| call.id)
+            .collect::<Vec<_>>()
+    );
+
+    // Nobody has the target agent's key, so the call can't be delivered
+    // and is given up on once its time to live has run out.
+    let later = (Timestamp::now() + std::time::Duration::from_secs(120)).unwrap();
+    conductor.dispatch_durable_calls(later).await.unwrap();
+
+    assert_matches!(
+        signals.recv().await.unwrap(),
+        Signal::System(SystemSignal::DurableCallRemoteExpired { cell_id: expired_cell_id, call_id: expired_call_id, .. })
+            if expired_cell_id == cell_id && expired_call_id == call_id
+    );
+    assert!(due(later).await.unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fetch_op_data_by_queried_regions() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+    await_consistency(10, [&cell]).await.unwrap();
+
+    let dna_hash = cell.cell_id().dna_hash();
+    let regions: Vec<_> = conductor
+        .query_region_set(dna_hash)
+        .await
+        .unwrap()
+        .regions()
+        .filter(|region| region.data.count > 0)
+        .collect();
+    let op_count: u32 = regions.iter().map(|region| region.data.count).sum();
+    assert!(op_count > 0);
+
+    let ops = conductor
+        .fetch_op_data_by_regions(
+            dna_hash,
+            regions.into_iter().map(|region| region.coords).collect(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(ops.len(), op_count as usize);
+}
+
+/// Export a DHT archive chunk by chunk, as an admin client would.
+async fn export_dht_archive(conductor: &SweetConductor, dna_hash: &DnaHash) -> Vec<u8> {
+    let mut archive = Vec::new();
+    loop {
+        let chunk = conductor
+            .export_dht_archive_chunk(dna_hash, archive.len() as u64)
+            .await
+            .unwrap();
+        assert_eq!(chunk.offset, archive.len() as u64);
+        archive.extend_from_slice(&chunk.bytes);
+        if archive.len() as u64 >= chunk.archive_len {
+            return archive;
+        }
+    }
+}
+
+/// Import a DHT archive in small chunks, as an admin client would.
+async fn import_dht_archive(
+    conductor: &SweetConductor,
+    dna_hash: &DnaHash,
+    archive: &[u8],
+) -> ConductorResult<usize> {
+    let mut received = 0;
+    for chunk in archive.chunks(100) {
+        received = conductor
+            .receive_dht_archive_chunk(dna_hash, received, chunk.to_vec())
+            .await?;
+    }
+    conductor
+        .import_received_dht_archive(dna_hash, received)
+        .await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dht_archive_export_and_import() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let dna_hash = dna.dna_hash().clone();
+    let mut exporter = SweetConductor::from_standard_config().await;
+    let (cell,) = exporter
+        .setup_app("app", [&dna])
+        .await
+        .unwrap()
+        .into_tuple();
+    await_consistency(10, [&cell]).await.unwrap();
+
+    let bytes = export_dht_archive(&exporter, &dna_hash).await;
+    let archive = DhtArchive::open(bytes.clone()).unwrap();
+    assert_eq!(archive.header().dna_hash, dna_hash);
+    assert!(!archive.is_empty());
+    let exported: HashSet<DhtOpHash> = archive.hashes().cloned().collect();
+
+    // An export which isn't in progress can't be continued.
+    assert_matches!(
+        exporter.export_dht_archive_chunk(&dna_hash, 100).await,
+        Err(ConductorError::DhtArchiveError(
+            DhtArchiveError::UnexpectedOffset {
+                expected: 0,
+                found: 100
+            }
+        ))
+    );
+
+    let mut importer = SweetConductor::from_standard_config().await;
+    importer.setup_app("app", [&dna]).await.unwrap();
+
+    // Chunks must be sent in order.
+    importer
+        .receive_dht_archive_chunk(&dna_hash, 0, bytes[..100].to_vec())
+        .await
+        .unwrap();
+    assert_matches!(
+        importer
+            .receive_dht_archive_chunk(&dna_hash, 50, bytes[50..].to_vec())
+            .await,
+        Err(ConductorError::DhtArchiveError(
+            DhtArchiveError::UnexpectedOffset {
+                expected: 100,
+                found: 50
+            }
+        ))
+    );
+
+    let imported = import_dht_archive(&importer, &dna_hash, &bytes)
+        .await
+        .unwrap();
+    assert_eq!(imported, exported.len());
+
+    // The imported ops are validated and integrated.
+    assert_eq_retry_10s!(
+        {
+            let archive = DhtArchive::open(export_dht_archive(&importer, &dna_hash).await).unwrap();
+            let integrated: HashSet<DhtOpHash> = archive.hashes().cloned().collect();
+            exported.is_subset(&integrated)
+        },
+        true
+    );
+
+    // An archive can't be imported for another DNA.
+    let mut other_archive = DhtArchiveWriter::new(fixt!(DnaHash)).unwrap();
+    other_archive.push(&archive.ops().unwrap()[0]).unwrap();
+    assert_matches!(
+        import_dht_archive(&importer, &dna_hash, &other_archive.finish().unwrap()).await,
+        Err(ConductorError::DhtArchiveError(
+            DhtArchiveError::DnaMismatch { .. }
+        ))
+    );
+}
+
+#[cfg(feature = "op_replay")]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_replay_validation_through_admin_api() {
+    use crate::conductor::api::AdminInterfaceApi;
+    use holochain_conductor_api::{AdminRequest, AdminResponse, ValidationReplayDifference};
+
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let dna_hash = dna.dna_hash().clone();
+    // A later version of the DNA which rejects every app entry.
+    let (strict_dna, _, _) = mk_dna(
+        SweetInlineZomes::new(vec![EntryDef::default_from_id("string")], 0).integrity_function(
+            "validate",
+            |_api, op: Op| match op {
+                Op::StoreEntry(StoreEntry { action, .. })
+                    if action.hashed.content.app_entry_def().is_some() =>
+                {
+                    Ok(ValidateResult::Invalid("no app entries".into()))
+                }
+                _ => Ok(ValidateResult::Valid),
+            },
+        ),
+    )
+    .await;
+    let strict_dna_hash = strict_dna.dna_hash().clone();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let (cell,) = conductor
+        .setup_app("app", [&dna])
+        .await
+        .unwrap()
+        .into_tuple();
+    conductor.setup_app("strict", [&strict_dna]).await.unwrap();
+    let action_hash: ActionHash = conductor
+        .call(&cell.zome("coordinator"), "create_string", "1".to_string())
+        .await;
+    await_consistency(10, [&cell]).await.unwrap();
+
+    let bytes = export_dht_archive(&conductor, &dna_hash).await;
+    let archive = DhtArchive::open(bytes.clone()).unwrap();
+    let store_entry_op_hash = archive
+        .ops()
+        .unwrap()
+        .into_iter()
+        .find(|op| match op.as_content() {
+            DhtOp::ChainOp(op) => {
+                op.get_type() == ChainOpType::StoreEntry
+                    && ActionHash::with_data_sync(&op.action()) == action_hash
+            }
+            _ => false,
+        })
+        .unwrap()
+        .into_hash();
+
+    let admin_api = AdminInterfaceApi::new(conductor.raw_handle());
+    let replay = |dna_hash: DnaHash| {
+        let admin_api = admin_api.clone();
+        let archive = bytes.clone();
+        async move {
+            let response = admin_api
+                .handle_request(Ok(AdminRequest::ReplayValidation {
+                    dna_hash,
+                    archive: archive.into(),
+                }))
+                .await
+                .unwrap();
+            match response {
+                AdminResponse::ValidationReplayed(report) => report,
+                other => panic!("unexpected response: {other:?}"),
+            }
+        }
+    };
+
+    // Replaying against the DNA the ops were validated with changes nothing.
+    let report = replay(dna_hash.clone()).await;
+    assert_eq!(report.archive_dna_hash, dna_hash);
+    assert_eq!(report.ops_replayed, archive.len());
+    assert_eq!(report.ops_unrecorded, 0);
+    assert_eq!(report.ops_undetermined, 0);
+    assert!(report.differences.is_empty());
+
+    // The stricter DNA rejects the app entry which was recorded as valid.
+    let report = replay(strict_dna_hash.clone()).await;
+    assert_eq!(report.dna_hash, strict_dna_hash);
+    assert_eq!(report.archive_dna_hash, dna_hash);
+    assert!(report.differences.contains(&ValidationReplayDifference {
+        op_hash: store_entry_op_hash,
+        recorded: ValidationStatus::Valid,
+        replayed: ValidationStatus::Rejected,
+    }));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_snapshot_databases() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let (cell,) = conductor
+        .setup_app("app", [&dna])
+        .await
+        .unwrap()
+        .into_tuple();
+
+    let snapshot_dir = tempfile::tempdir().unwrap();
+    let paths = conductor
+        .snapshot_databases(snapshot_dir.path().to_owned())
+        .await
+        .unwrap();
+    assert!(paths
+        .iter()
+        .all(|path| path.starts_with(snapshot_dir.path())));
+    assert!(paths.iter().all(|path| path.is_file()));
+    let authored_path = snapshot_dir.path().join(
+        conductor
+            .get_or_create_authored_db(cell.dna_hash(), cell.agent_pubkey().clone())
+            .unwrap()
+            .kind()
+            .filename(),
+    );
+    assert!(paths.contains(&authored_path));
+
+    // The snapshot can be restored into an empty databases root.
+    let restore_root = tempfile::tempdir().unwrap();
+    let restored = holochain_sqlite::db::restore_databases_from_snapshot(
+        snapshot_dir.path(),
+        restore_root.path(),
+    )
+    .unwrap();
+    assert_eq!(restored.len(), paths.len());
+
+    // A snapshot is never written over another one.
+    assert_matches!(
+        conductor
+            .snapshot_databases(snapshot_dir.path().to_owned())
+            .await,
+        Err(ConductorError::SnapshotDirNotEmpty(_))
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_uninstall_app() {
     holochain_trace::test_run();
@@ -136,13 +136,11 @@ mod tests {
         let post_def = EntryDef {
             id: "post".into(),
             visibility: EntryVisibility::Public,
-            required_validations: 5.into(),
             ..Default::default()
         };
         let comment_def = EntryDef {
             id: "comment".into(),
             visibility: EntryVisibility::Private,
-            required_validations: 5.into(),
             ..Default::default()
         };
         let dna_wasm = DnaWasmHashed::from_content(TestWasm::EntryDefs.into())
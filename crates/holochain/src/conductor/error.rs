@@ -31,6 +31,9 @@ pub enum ConductorError {
     #[error("Cell is not initialized.")]
     CellNotInitialized,
 
+    #[error("Cell has already run genesis. CellId: {0:?}")]
+    CellAlreadyHasGenesis(CellId),
+
     #[error("Cell was referenced, but is currently disabled. CellId: {0:?}")]
     CellDisabled(CellId),
 
@@ -125,6 +128,12 @@ pub enum ConductorError {
     #[error(transparent)]
     RibosomeError(#[from] crate::core::ribosome::error::RibosomeError),
 
+    #[error(transparent)]
+    DhtArchiveError(#[from] holochain_types::dht_archive::DhtArchiveError),
+
+    #[error(transparent)]
+    LegacyChainError(#[from] holochain_types::legacy_chain::LegacyChainError),
+
     #[error("Authentication failed with reason: {0}")]
     FailedAuthenticationError(String),
 
@@ -140,6 +149,23 @@ pub enum ConductorError {
 
     #[error("The conductor has no config directory.")]
     NoConfigPath,
+
+    #[error("Missed signals are not persisted for app: {0}")]
+    MissedSignalsNotPersisted(InstalledAppId),
+
+    #[error("Cannot write a database snapshot into {0} because it is not empty")]
+    SnapshotDirNotEmpty(std::path::PathBuf),
+
+    #[error(
+        "The new coordinator zomes for DNA {0} failed their smoke test and were not installed: {1}"
+    )]
+    CoordinatorSmokeTestFailed(DnaHash, Box<crate::core::ribosome::error::RibosomeError>),
+
+    #[error("The coordinator zomes of DNA {0} have not been updated since the conductor started, so there are none to roll back to")]
+    NoPreviousCoordinators(DnaHash),
+
+    #[error("The coordinator zomes of DNA {0} were changed while they were being updated")]
+    ConcurrentCoordinatorUpdate(DnaHash),
 }
 
 impl ConductorError {
@@ -4,6 +4,8 @@
 use super::error::InterfaceResult;
 use crate::conductor::conductor::app_broadcast::AppBroadcast;
 use crate::conductor::manager::TaskManagerClient;
+use crate::conductor::ConductorHandle;
+use crate::core::metrics::THROUGHPUT;
 use holochain_serialized_bytes::SerializedBytes;
 use holochain_types::signal::Signal;
 use holochain_websocket::ReceiveMessage;
@@ -15,14 +17,19 @@ use std::io::ErrorKind;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
 
 use crate::conductor::api::{AdminInterfaceApi, AppAuthentication, AppInterfaceApi};
+use holochain_conductor_api::conductor::ConductorTuningParams;
+use holochain_conductor_api::signal_subscription::SignalFilter;
+use holochain_conductor_api::wire_encoding::{compact_encode, WireEncoding};
 use holochain_conductor_api::{
-    AdminRequest, AdminResponse, AppAuthenticationRequest, AppRequest, AppResponse,
+    AdminAuthenticationRequest, AdminAuthenticationToken, AdminRequest, AdminResponse, AdminSignal,
+    AppAuthenticationRequest, AppRequest, AppResponse, FirehoseSignal,
 };
 use holochain_types::app::InstalledAppId;
 use holochain_types::websocket::AllowedOrigins;
 use std::sync::Arc;
 use tokio::pin;
 use tokio::sync::broadcast;
+use tokio::sync::Semaphore;
 use tokio::task::JoinHandle;
 use tracing::*;
 
@@ -35,6 +42,75 @@ const CONCURRENCY_COUNT: usize = 128;
 /// The maximum number of connections allowed to the admin interface
 pub const MAX_CONNECTIONS: usize = 400;
 
+/// Limits on how many requests an app interface will process at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppInterfaceLimits {
+    /// The maximum number of requests in flight for a single connection.
+    pub max_in_flight_per_connection: usize,
+    /// The maximum number of requests in flight across all connections to the interface.
+    pub max_in_flight: usize,
+}
+
+impl From<&ConductorTuningParams> for AppInterfaceLimits {
+    fn from(params: &ConductorTuningParams) -> Self {
+        Self {
+            max_in_flight_per_connection: params.app_interface_max_in_flight_per_connection(),
+            max_in_flight: params.app_interface_max_in_flight(),
+        }
+    }
+}
+
+/// Shares the request capacity of an app interface between its connections.
+///
+/// The shared capacity is handed out first-come first-served, but each connection
+/// only has one request at a time waiting for it. So connections waiting for capacity
+/// take turns, and a client flooding the interface waits behind the other clients
+/// rather than monopolizing zome call execution.
+#[derive(Clone)]
+struct AppCallScheduler {
+    max_in_flight_per_connection: usize,
+    in_flight: Arc<Semaphore>,
+}
+
+impl AppCallScheduler {
+    fn new(limits: AppInterfaceLimits) -> Self {
+        Self {
+            max_in_flight_per_connection: limits.max_in_flight_per_connection.max(1),
+            in_flight: Arc::new(Semaphore::new(limits.max_in_flight.max(1))),
+        }
+    }
+
+    /// The scheduler for the requests of a single connection.
+    fn connection(&self) -> ConnectionScheduler {
+        ConnectionScheduler {
+            in_flight: self.in_flight.clone(),
+            turn: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+}
+
+/// Schedules the requests of a single connection to an app interface.
+#[derive(Clone)]
+struct ConnectionScheduler {
+    in_flight: Arc<Semaphore>,
+    /// Held by the request of this connection which is waiting for capacity.
+    turn: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl ConnectionScheduler {
+    /// Wait for capacity to process a request of this connection.
+    async fn acquire(&self) -> tokio::sync::OwnedSemaphorePermit {
+        // The mutex is fair, so the requests of this connection wait in order.
+        let _turn = self.turn.lock().await;
+        self.in_flight
+            .clone()
+            .acquire_owned()
+            .await
+            // The semaphore is never closed.
+            .expect("app interface semaphore closed")
+    }
+}
+
 /// Create a WebsocketListener to be used in interfaces
 pub async fn spawn_websocket_listener(
     port: u16,
@@ -77,12 +153,16 @@ impl TaskList {
 }
 
 /// Create an Admin Interface, which only receives AdminRequest messages
-/// from the external client
+/// from the external client.
+///
+/// If `require_token` is set, connections must authenticate with an admin token
+/// and their requests are limited to the scope of the token.
 pub fn spawn_admin_interface_tasks(
     tm: TaskManagerClient,
     listener: WebsocketListener,
     api: AdminInterfaceApi,
     port: u16,
+    require_token: bool,
 ) {
     tm.add_conductor_task_ignored(&format!("admin interface, port {}", port), move || {
         async move {
@@ -90,7 +170,7 @@ pub fn spawn_admin_interface_tasks(
             // establish a new connection to a client
             loop {
                 match listener.accept().await {
-                    Ok((_, rx_from_iface)) => {
+                    Ok((tx_to_iface, rx_from_iface)) => {
                         task_list.prune();
                         let conn_count = task_list.0.lock().len();
                         if conn_count >= MAX_CONNECTIONS {
@@ -100,10 +180,25 @@ pub fn spawn_admin_interface_tasks(
                             continue;
                         };
                         debug!("Accepting new connection with number of existing connections {}", conn_count);
-                        task_list.0.lock().push(tokio::task::spawn(recv_incoming_admin_msgs(
-                            api.clone(),
-                            rx_from_iface,
-                        )));
+                        let api = api.clone();
+                        task_list.0.lock().push(tokio::task::spawn(async move {
+                            let mut rx_from_iface = rx_from_iface;
+                            let token = if require_token {
+                                match authenticate_incoming_admin_connection(
+                                    &api,
+                                    &mut rx_from_iface,
+                                    port,
+                                )
+                                .await
+                                {
+                                    Some(token) => Some(token),
+                                    None => return,
+                                }
+                            } else {
+                                None
+                            };
+                            recv_incoming_admin_msgs(api, tx_to_iface, rx_from_iface, token).await
+                        }));
                     }
                     Err(err) => {
                         warn!("Admin socket connection failed: {}", err);
@@ -123,6 +218,7 @@ pub async fn spawn_app_interface_task(
     installed_app_id: Option<InstalledAppId>,
     api: AppInterfaceApi,
     app_broadcast: AppBroadcast,
+    limits: AppInterfaceLimits,
 ) -> InterfaceResult<u16> {
     trace!("Initializing App interface");
 
@@ -142,6 +238,7 @@ pub async fn spawn_app_interface_task(
     tm.add_conductor_task_ignored("app interface new connection handler", move || {
         async move {
             let task_list = TaskList::default();
+            let scheduler = AppCallScheduler::new(limits);
             // establish a new connection to a client
             loop {
                 match listener.accept().await {
@@ -154,6 +251,7 @@ pub async fn spawn_app_interface_task(
                             tx_to_iface,
                             installed_app_id.clone(),
                             port,
+                            scheduler.clone(),
                         );
                     }
                     Err(err) => {
@@ -166,13 +264,54 @@ pub async fn spawn_app_interface_task(
     Ok(port)
 }
 
+/// Create a Firehose Interface, which streams every op integrated into the
+/// spaces of an app to clients authenticated for that app
+pub fn spawn_firehose_interface_task(
+    tm: TaskManagerClient,
+    listener: WebsocketListener,
+    conductor: ConductorHandle,
+    port: u16,
+) {
+    tm.add_conductor_task_ignored(&format!("firehose interface, port {}", port), move || {
+        async move {
+            let task_list = TaskList::default();
+            // establish a new connection to a client
+            loop {
+                match listener.accept().await {
+                    Ok((tx_to_iface, rx_from_iface)) => {
+                        authenticate_incoming_firehose_connection(
+                            task_list.0.clone(),
+                            conductor.clone(),
+                            rx_from_iface,
+                            tx_to_iface,
+                            port,
+                        );
+                    }
+                    Err(err) => {
+                        warn!("Firehose socket connection failed: {}", err);
+                    }
+                }
+            }
+        }
+    });
+}
+
 /// Polls for messages coming in from the external client.
 /// Used by Admin interface.
-async fn recv_incoming_admin_msgs(api: AdminInterfaceApi, rx_from_iface: WebsocketReceiver) {
+async fn recv_incoming_admin_msgs(
+    api: AdminInterfaceApi,
+    tx_to_iface: WebsocketSender,
+    rx_from_iface: WebsocketReceiver,
+    token: Option<AdminAuthenticationToken>,
+) {
     use futures::stream::StreamExt;
 
     tracing::info!("Starting admin listener");
 
+    // Tasks which push signals to this connection, aborted when it closes.
+    let signal_tasks = TaskList::default();
+    let encoding = ConnectionEncoding::default();
+
     let rx_from_iface =
         futures::stream::unfold(rx_from_iface, move |mut rx_from_iface| async move {
             match rx_from_iface.recv().await {
@@ -186,10 +325,23 @@ async fn recv_incoming_admin_msgs(api: AdminInterfaceApi, rx_from_iface: Websock
 
     // TODO - metrics to indicate if we're getting overloaded here.
     rx_from_iface
-        .for_each_concurrent(CONCURRENCY_COUNT, move |msg| {
+        .for_each_concurrent(CONCURRENCY_COUNT, |msg| {
             let api = api.clone();
+            let tx_to_iface = tx_to_iface.clone();
+            let signal_tasks = signal_tasks.0.clone();
+            let encoding = encoding.clone();
+            let token = token.clone();
             async move {
-                if let Err(e) = handle_incoming_admin_message(msg, api.clone()).await {
+                if let Err(e) = handle_incoming_admin_message(
+                    msg,
+                    api,
+                    tx_to_iface,
+                    signal_tasks,
+                    encoding,
+                    token,
+                )
+                .await
+                {
                     error!(error = &e as &dyn std::error::Error)
                 }
             }
@@ -199,6 +351,55 @@ async fn recv_incoming_admin_msgs(api: AdminInterfaceApi, rx_from_iface: Websock
     info!("Admin listener finished");
 }
 
+/// Waits for an admin authentication message on an open connection to an admin interface which
+/// requires a token.
+/// Returns the token once it has been authenticated, or `None` if the connection should be
+/// dropped because it didn't authenticate within 10s or sent anything else first.
+async fn authenticate_incoming_admin_connection(
+    api: &AdminInterfaceApi,
+    rx_from_iface: &mut WebsocketReceiver,
+    port: u16,
+) -> Option<AdminAuthenticationToken> {
+    let auth_payload = tokio::time::timeout(
+        std::time::Duration::from_secs(10),
+        rx_from_iface.recv::<AdminRequest>(),
+    )
+    .await;
+    let auth_payload = match auth_payload {
+        Ok(Ok(ReceiveMessage::Authenticate(auth_payload))) => auth_payload,
+        Ok(Ok(_)) => {
+            warn!("Connection to Holochain admin port {port} tried to send a message before authenticating. Dropping connection.");
+            return None;
+        }
+        Ok(Err(_)) => {
+            warn!("Could not receive authentication message on Holochain admin port {port}. Dropping connection.");
+            return None;
+        }
+        Err(_) => {
+            warn!("Connection to Holochain admin port {port} timed out while awaiting authentication. Dropping connection.");
+            return None;
+        }
+    };
+    let payload: AdminAuthenticationRequest = match SerializedBytes::from(
+        holochain_serialized_bytes::UnsafeBytes::from(auth_payload),
+    )
+    .try_into()
+    {
+        Ok(payload) => payload,
+        Err(e) => {
+            warn!("Holochain admin port {port} received a payload that failed to decode into an authentication payload: {e}. Dropping connection.");
+            return None;
+        }
+    };
+    match api.auth(&payload.token) {
+        Ok(()) => Some(payload.token),
+        Err(e) => {
+            warn!("Connection to Holochain admin port {port} failed to authenticate: {e}. Dropping connection.");
+            None
+        }
+    }
+}
+
 /// Takes an open connection and waits for an authentication message to complete the connection
 /// registration.
 /// If the connection is not authenticated within 10s or any other content is sent, then the
@@ -213,6 +414,7 @@ fn authenticate_incoming_app_connection(
     tx_to_iface: WebsocketSender,
     installed_app_id: Option<InstalledAppId>,
     port: u16,
+    scheduler: AppCallScheduler,
 ) {
     let join_handle = tokio::task::spawn({
         let task_list = task_list.clone();
@@ -265,6 +467,7 @@ fn authenticate_incoming_app_connection(
                             // Once authentication passes we know which app this connection is for,
                             // so we can subscribe to app signals now.
                             let rx_from_cell = app_broadcast.subscribe(installed_app_id.clone());
+                            let signal_filters = SignalFilters::default();
 
                             spawn_app_signals_handler(
                                 task_list.clone(),
@@ -272,12 +475,16 @@ fn authenticate_incoming_app_connection(
                                 tx_to_iface.clone(),
                                 port,
                                 installed_app_id.clone(),
+                                signal_filters.clone(),
                             );
                             spawn_recv_incoming_app_msgs(
                                 task_list,
                                 api,
                                 rx_from_iface,
                                 installed_app_id,
+                                scheduler,
+                                signal_filters,
+                                ConnectionEncoding::default(),
                             );
                         }
                         Err(e) => {
@@ -293,14 +500,141 @@ fn authenticate_incoming_app_connection(
     task_list_lock.push(join_handle);
 }
 
+/// Takes an open firehose connection and waits for an app authentication message, in the same
+/// way as [`authenticate_incoming_app_connection`].
+/// If the authentication succeeds, then the ops integrated into the spaces of the app the token
+/// was issued for are streamed to the client.
+fn authenticate_incoming_firehose_connection(
+    task_list: TaskListInner,
+    conductor: ConductorHandle,
+    mut rx_from_iface: WebsocketReceiver,
+    tx_to_iface: WebsocketSender,
+    port: u16,
+) {
+    let join_handle = tokio::task::spawn({
+        let task_list = task_list.clone();
+        async move {
+            let auth_payload = tokio::time::timeout(
+                std::time::Duration::from_secs(10),
+                rx_from_iface.recv::<AppRequest>(),
+            )
+            .await;
+            let auth_payload = match auth_payload {
+                Ok(Ok(ReceiveMessage::Authenticate(auth_payload))) => auth_payload,
+                Ok(Ok(_)) => {
+                    warn!("Connection to Holochain firehose port {port} tried to send a message before authenticating. Dropping connection.");
+                    return;
+                }
+                Ok(Err(_)) => {
+                    warn!("Could not receive authentication message on Holochain firehose port {port}. Dropping connection.");
+                    return;
+                }
+                Err(_) => {
+                    warn!("Connection to Holochain firehose port {port} timed out while awaiting authentication. Dropping connection.");
+                    return;
+                }
+            };
+            let payload: AppAuthenticationRequest = match SerializedBytes::from(
+                holochain_serialized_bytes::UnsafeBytes::from(auth_payload),
+            )
+            .try_into()
+            {
+                Ok(payload) => payload,
+                Err(e) => {
+                    warn!("Holochain firehose port {port} received a payload that failed to decode into an authentication payload: {e}. Dropping connection.");
+                    return;
+                }
+            };
+            let installed_app_id = match conductor.authenticate_app_token(payload.token, None) {
+                Ok(installed_app_id) => installed_app_id,
+                Err(e) => {
+                    warn!("Connection to Holochain firehose port {port} failed to authenticate: {e}. Dropping connection.");
+                    return;
+                }
+            };
+            let dna_hashes = match conductor.get_state().await.and_then(|state| {
+                Ok(state
+                    .get_app(&installed_app_id)?
+                    .all_cells()
+                    .map(|cell_id| cell_id.dna_hash().clone())
+                    .collect::<std::collections::HashSet<_>>())
+            }) {
+                Ok(dna_hashes) => dna_hashes,
+                Err(e) => {
+                    warn!("Could not find the spaces of app '{installed_app_id}' for Holochain firehose port {port}: {e}. Dropping connection.");
+                    return;
+                }
+            };
+            let receivers = dna_hashes
+                .into_iter()
+                .map(|dna_hash| conductor.firehose().subscribe(dna_hash))
+                .collect();
+            spawn_firehose_stream(task_list.clone(), receivers, tx_to_iface, port);
+
+            // Clients don't send anything once authenticated, but the connection is kept
+            // open for as long as the client keeps reading.
+            while rx_from_iface.recv::<AppRequest>().await.is_ok() {
+                debug!("Holochain firehose port {port} ignored a message from a client");
+            }
+        }
+    });
+
+    task_list.lock().push(join_handle);
+}
+
+/// Starts a task that listens for the ops integrated into the spaces of an app with `receivers`
+/// and sends them to the connected client via `tx_to_iface`.
+fn spawn_firehose_stream(
+    task_list: TaskListInner,
+    receivers: Vec<broadcast::Receiver<Arc<holochain_conductor_api::FirehoseOp>>>,
+    tx_to_iface: WebsocketSender,
+    port: u16,
+) {
+    use futures::stream::StreamExt;
+
+    let ops = futures::stream::select_all(receivers.into_iter().map(|rx| {
+        futures::stream::unfold(rx, |mut rx| async move {
+            match rx.recv().await {
+                Ok(op) => Some((FirehoseSignal::Op(Box::new((*op).clone())), rx)),
+                Err(broadcast::error::RecvError::Lagged(dropped)) => {
+                    Some((FirehoseSignal::Lagged { dropped }, rx))
+                }
+                Err(broadcast::error::RecvError::Closed) => None,
+            }
+        })
+        .boxed()
+    }));
+
+    task_list.lock().push(tokio::task::spawn(async move {
+        pin!(ops);
+        while let Some(signal) = ops.next().await {
+            if let FirehoseSignal::Lagged { dropped } = &signal {
+                warn!("Holochain firehose port {port} dropped {dropped} ops. The client is not reading them fast enough.");
+            }
+            if let Err(err) = tx_to_iface.signal(signal).await {
+                debug!(?err, "Failed to send firehose op, closing firehose stream");
+                break;
+            }
+        }
+    }));
+}
+
+/// The signal filters an app connection has subscribed to.
+/// Without any filters the connection receives every signal.
+type SignalFilters = Arc<parking_lot::Mutex<Vec<SignalFilter>>>;
+
+/// The encoding a connection has chosen for its responses.
+type ConnectionEncoding = Arc<parking_lot::Mutex<WireEncoding>>;
+
 /// Starts a task that listens for signals coming from apps with `rx_from_cell` and sends them to
-/// the connected client via `tx_to_iface`.
+/// the connected client via `tx_to_iface`, if they pass the connection's `signal_filters`.
 fn spawn_app_signals_handler(
     task_list: TaskListInner,
     rx_from_cell: broadcast::Receiver<Signal>,
     tx_to_iface: WebsocketSender,
     port: u16,
     installed_app_id: InstalledAppId,
+    signal_filters: SignalFilters,
 ) {
     use futures::stream::StreamExt;
 
@@ -328,6 +662,13 @@ fn spawn_app_signals_handler(
         pin!(rx_from_cell);
         loop {
             if let Some(signal) = rx_from_cell.next().await {
+                {
+                    let filters = signal_filters.lock();
+                    if !filters.is_empty() && !filters.iter().any(|f| f.matches(&signal)) {
+                        trace!(msg = "Signal filtered out", ?signal);
+                        continue;
+                    }
+                }
                 trace!(msg = "Sending signal!", ?signal);
                 if let Err(err) = tx_to_iface.signal(signal).await {
                     if err.kind() == ErrorKind::Other && err.to_string() == "WebsocketClosed" {
@@ -355,6 +696,9 @@ fn spawn_recv_incoming_app_msgs(
     api: AppInterfaceApi,
     rx_from_iface: WebsocketReceiver,
     installed_app_id: InstalledAppId,
+    scheduler: AppCallScheduler,
+    signal_filters: SignalFilters,
+    encoding: ConnectionEncoding,
 ) {
     use futures::stream::StreamExt;
 
@@ -371,16 +715,29 @@ fn spawn_recv_incoming_app_msgs(
             }
         });
 
+    let connection = scheduler.connection();
+
     // TODO - metrics to indicate if we're getting overloaded here.
     task_list
         .lock()
         .push(tokio::task::spawn(rx_from_iface.for_each_concurrent(
-            CONCURRENCY_COUNT,
+            scheduler.max_in_flight_per_connection,
             move |msg| {
                 let installed_app_id = installed_app_id.clone();
                 let api = api.clone();
+                let connection = connection.clone();
+                let signal_filters = signal_filters.clone();
+                let encoding = encoding.clone();
                 async move {
-                    if let Err(err) = handle_incoming_app_message(msg, installed_app_id, api).await
+                    let _permit = connection.acquire().await;
+                    if let Err(err) = handle_incoming_app_message(
+                        msg,
+                        installed_app_id,
+                        api,
+                        signal_filters,
+                        encoding,
+                    )
+                    .await
                     {
                         error!(?err, "error handling app websocket message");
                     }
@@ -393,6 +750,10 @@ fn spawn_recv_incoming_app_msgs(
 async fn handle_incoming_admin_message(
     ws_msg: ReceiveMessage<AdminRequest>,
     api: AdminInterfaceApi,
+    tx_to_iface: WebsocketSender,
+    signal_tasks: TaskListInner,
+    encoding: ConnectionEncoding,
+    token: Option<AdminAuthenticationToken>,
 ) -> InterfaceResult<()> {
     match ws_msg {
         ReceiveMessage::Signal(_) => {
@@ -405,10 +766,35 @@ async fn handle_incoming_admin_message(
         }
         ReceiveMessage::Request(data, respond) => {
             use holochain_serialized_bytes::SerializedBytesError;
-            let result: AdminResponse = api.handle_request(Ok(data)).await?;
+            let metrics_interval = match &data {
+                AdminRequest::SubscribeMetrics { interval } => Some(*interval),
+                _ => None,
+            };
+            let new_encoding = match &data {
+                AdminRequest::SetWireEncoding { encoding } => Some(*encoding),
+                _ => None,
+            };
+            let response_encoding = *encoding.lock();
+            let result: AdminResponse = match &token {
+                Some(token) => api.handle_scoped_request(token, Ok(data)).await?,
+                None => api.handle_request(Ok(data)).await?,
+            };
+            if let (Some(interval), AdminResponse::MetricsSubscribed) = (metrics_interval, &result)
+            {
+                spawn_metrics_stream(
+                    signal_tasks,
+                    tx_to_iface,
+                    std::time::Duration::from_millis(interval),
+                );
+            }
+            if let (Some(new_encoding), AdminResponse::WireEncodingSet) = (new_encoding, &result) {
+                *encoding.lock() = new_encoding;
+            }
             // Have to jump through some hoops, because our response type
             // only implements try_into, but the responder needs try_from.
-            let result = result.try_into();
+            let result = result
+                .try_into()
+                .and_then(|bytes| encode_response(bytes, response_encoding));
             #[derive(Debug)]
             struct Cnv(Result<SerializedBytes, SerializedBytesError>);
             impl std::convert::TryFrom<Cnv> for SerializedBytes {
@@ -424,11 +810,41 @@ async fn handle_incoming_admin_message(
     }
 }
 
+/// Starts a task that periodically samples the conductor's throughput counters
+/// and sends them to the connected admin client via `tx_to_iface`.
+fn spawn_metrics_stream(
+    task_list: TaskListInner,
+    tx_to_iface: WebsocketSender,
+    interval: std::time::Duration,
+) {
+    let join_handle = tokio::task::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        // The first tick completes immediately.
+        ticker.tick().await;
+        let mut last = THROUGHPUT.snapshot();
+        loop {
+            ticker.tick().await;
+            let now = THROUGHPUT.snapshot();
+            let metrics = now.rates_since(&last);
+            last = now;
+            if let Err(err) = tx_to_iface.signal(AdminSignal::Metrics(metrics)).await {
+                debug!(?err, "Failed to send metrics, closing metrics stream");
+                break;
+            }
+        }
+    });
+
+    task_list.lock().push(join_handle);
+}
+
 /// Handles messages on app interfaces
 async fn handle_incoming_app_message(
     ws_msg: ReceiveMessage<AppRequest>,
     installed_app_id: InstalledAppId,
     api: AppInterfaceApi,
+    signal_filters: SignalFilters,
+    encoding: ConnectionEncoding,
 ) -> InterfaceResult<()> {
     match ws_msg {
         ReceiveMessage::Signal(_) => {
@@ -441,10 +857,34 @@ async fn handle_incoming_app_message(
         }
         ReceiveMessage::Request(data, respond) => {
             use holochain_serialized_bytes::SerializedBytesError;
+            let signal_filter = match &data {
+                AppRequest::SubscribeSignals { zome, pattern } => Some(SignalFilter {
+                    zome: zome.clone(),
+                    pattern: pattern.clone(),
+                }),
+                _ => None,
+            };
+            let new_encoding = match &data {
+                AppRequest::SetWireEncoding { encoding } => Some(*encoding),
+                _ => None,
+            };
+            let response_encoding = *encoding.lock();
             let result: AppResponse = api.handle_request(installed_app_id, Ok(data)).await?;
+            match (signal_filter, &result) {
+                (Some(filter), AppResponse::SignalsSubscribed) => {
+                    signal_filters.lock().push(filter)
+                }
+                (None, AppResponse::SignalsUnsubscribed) => signal_filters.lock().clear(),
+                _ => (),
+            }
+            if let (Some(new_encoding), AppResponse::WireEncodingSet) = (new_encoding, &result) {
+                *encoding.lock() = new_encoding;
+            }
             // Have to jump through some hoops, because our response type
             // only implements try_into, but the responder needs try_from.
-            let result = result.try_into();
+            let result = result
+                .try_into()
+                .and_then(|bytes| encode_response(bytes, response_encoding));
             #[derive(Debug)]
             struct Cnv(Result<SerializedBytes, SerializedBytesError>);
             impl std::convert::TryFrom<Cnv> for SerializedBytes {
@@ -460,6 +900,19 @@ async fn handle_incoming_app_message(
     }
 }
 
+/// Re-encode a msgpack encoded response in the encoding chosen by the connection.
+fn encode_response(
+    bytes: SerializedBytes,
+    encoding: WireEncoding,
+) -> Result<SerializedBytes, holochain_serialized_bytes::SerializedBytesError> {
+    match encoding {
+        WireEncoding::Msgpack => Ok(bytes),
+        WireEncoding::Compact => Ok(SerializedBytes::from(
+            holochain_serialized_bytes::UnsafeBytes::from(compact_encode(bytes.bytes())?),
+        )),
+    }
+}
+
 /// Test items needed by other crates
 #[cfg(any(test, feature = "test_utils"))]
 pub use crate::test_utils::setup_app_in_new_conductor;
@@ -479,9 +932,12 @@ pub mod test {
     use crate::fixt::RealRibosomeFixturator;
     use crate::sweettest::websocket_client_by_port;
     use crate::sweettest::SweetConductor;
+    use crate::sweettest::SweetConductorConfig;
     use crate::sweettest::SweetDnaFile;
+    use crate::sweettest::SweetInlineZomes;
     use crate::sweettest::WsPollRecv;
     use crate::sweettest::{app_bundle_from_dnas, authenticate_app_ws_client};
+    use crate::test_utils::inline_zomes::simple_crud_zome;
     use crate::test_utils::install_app_in_conductor;
     use ::fixt::prelude::*;
     use holochain_conductor_api::*;
@@ -545,6 +1001,7 @@ pub mod test {
                     port: 0,
                     allowed_origins: AllowedOrigins::Any,
                 },
+                require_token: false,
             }])
             .await
             .unwrap()[0];
@@ -567,6 +1024,11 @@ pub mod test {
             installed_app_id: None,
             membrane_proofs: HashMap::new(),
             network_seed: None,
+            validation_queue_orders: Default::default(),
+            missed_signals_buffer_size: None,
+            zome_call_timeout_ms: None,
+            warranted_authors: None,
+            egress_policy: None,
             #[cfg(feature = "chc")]
             ignore_genesis_failure: false,
         }));
@@ -633,6 +1095,7 @@ pub mod test {
                     payload: ExternIO::encode(()).unwrap(),
                     nonce,
                     expires_at,
+                    wait_for_publish: None,
                 },
             )
             .await
@@ -658,6 +1121,121 @@ pub mod test {
         app_rx_task.abort();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn admin_wire_encoding_negotiation() {
+        holochain_trace::test_run();
+        let db_dir = test_db_dir();
+        let conductor_handle = ConductorBuilder::new()
+            .with_data_root_path(db_dir.path().to_path_buf().into())
+            .test(&[])
+            .await
+            .unwrap();
+
+        let admin_port = conductor_handle
+            .clone()
+            .add_admin_interfaces(vec![AdminInterfaceConfig {
+                driver: InterfaceDriver::Websocket {
+                    port: 0,
+                    allowed_origins: AllowedOrigins::Any,
+                },
+                require_token: false,
+            }])
+            .await
+            .unwrap()[0];
+
+        let (admin_tx, mut rx) = websocket_client_by_port(admin_port).await.unwrap();
+        let admin_rx_task =
+            tokio::task::spawn(async move { while rx.recv::<AdminResponse>().await.is_ok() {} });
+
+        // The response to the negotiation is still plain msgpack.
+        let response: AdminResponse = admin_tx
+            .request(AdminRequest::SetWireEncoding {
+                encoding: WireEncoding::Compact,
+            })
+            .await
+            .unwrap();
+        assert_matches!(response, AdminResponse::WireEncodingSet);
+
+        // Later responses are a table of repeated values followed by the response.
+        let (table, response): (Vec<String>, AdminResponse) =
+            admin_tx.request(AdminRequest::ListDnas).await.unwrap();
+        assert!(table.is_empty());
+        assert_matches!(response, AdminResponse::DnasListed(dnas) if dnas.is_empty());
+
+        // And the connection can switch back.
+        let (_, response): (Vec<String>, AdminResponse) = admin_tx
+            .request(AdminRequest::SetWireEncoding {
+                encoding: WireEncoding::Msgpack,
+            })
+            .await
+            .unwrap();
+        assert_matches!(response, AdminResponse::WireEncodingSet);
+        let response: AdminResponse = admin_tx.request(AdminRequest::ListDnas).await.unwrap();
+        assert_matches!(response, AdminResponse::DnasListed(_));
+
+        admin_rx_task.abort();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn admin_metrics_subscription() {
+        holochain_trace::test_run();
+        let db_dir = test_db_dir();
+        let conductor_handle = ConductorBuilder::new()
+            .with_data_root_path(db_dir.path().to_path_buf().into())
+            .test(&[])
+            .await
+            .unwrap();
+
+        let admin_port = conductor_handle
+            .clone()
+            .add_admin_interfaces(vec![AdminInterfaceConfig {
+                driver: InterfaceDriver::Websocket {
+                    port: 0,
+                    allowed_origins: AllowedOrigins::Any,
+                },
+                require_token: false,
+            }])
+            .await
+            .unwrap()[0];
+
+        let (admin_tx, mut rx) = websocket_client_by_port(admin_port).await.unwrap();
+        let (s_send, mut s_recv) = tokio::sync::mpsc::unbounded_channel();
+        let admin_rx_task = tokio::task::spawn(async move {
+            while let Ok(msg) = rx.recv::<AdminResponse>().await {
+                if let ReceiveMessage::Signal(s) = msg {
+                    s_send.send(s).unwrap();
+                }
+            }
+        });
+
+        // A zero interval is rejected.
+        let response: AdminResponse = admin_tx
+            .request(AdminRequest::SubscribeMetrics { interval: 0 })
+            .await
+            .unwrap();
+        assert_matches!(response, AdminResponse::Error(_));
+
+        let response: AdminResponse = admin_tx
+            .request(AdminRequest::SubscribeMetrics { interval: 100 })
+            .await
+            .unwrap();
+        assert_matches!(response, AdminResponse::MetricsSubscribed);
+
+        // Samples keep arriving on the connection.
+        for _ in 0..2 {
+            let signal = tokio::time::timeout(std::time::Duration::from_secs(5), s_recv.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            let signal: AdminSignal = SerializedBytes::from(UnsafeBytes::from(signal))
+                .try_into()
+                .unwrap();
+            assert_matches!(signal, AdminSignal::Metrics(m) if m.interval_ms > 0);
+        }
+
+        admin_rx_task.abort();
+    }
+
     async fn setup_admin() -> (Arc<TempDir>, ConductorHandle) {
         let db_dir = test_db_dir();
         let conductor_handle = Conductor::builder()
@@ -846,7 +1424,10 @@ pub mod test {
 
         let msg = AppRequest::NetworkInfo(Box::new(request));
         let respond = |response: AppResponse| match response {
-            AppResponse::NetworkInfo(info) => {
+            AppResponse::NetworkInfo(mut info) => {
+                // The genesis ops are held within the full arc.
+                assert!(info[0].arc_data_size > 0);
+                info[0].arc_data_size = 0;
                 assert_eq!(
                     info,
                     vec![NetworkInfo {
@@ -856,6 +1437,7 @@ pub mod test {
                         total_network_peers: 1,
                         bytes_since_last_time_queried: 1842,
                         completed_rounds_since_last_time_queried: 0,
+                        arc_data_size: 0,
                     }]
                 )
             }
@@ -1200,6 +1782,75 @@ pub mod test {
         conductor_handle.shutdown().await.unwrap().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn dump_state_page() {
+        holochain_trace::test_run();
+        let uuid = Uuid::new_v4();
+        let dna = fake_dna_zomes(
+            &uuid.to_string(),
+            vec![("zomey".into(), TestWasm::Foo.into())],
+        );
+        let agent_pubkey = fake_agent_pubkey_1();
+        let cell_id = CellId::from((dna.dna_hash().clone(), agent_pubkey.clone()));
+
+        let (_tmpdir, conductor_handle) =
+            setup_admin_fake_cells(agent_pubkey, vec![(dna, None)]).await;
+        let conductor_handle = activate(conductor_handle).await;
+
+        let full = conductor_handle
+            .dump_full_cell_state(&cell_id, None)
+            .await
+            .unwrap();
+
+        // Page through the source chain two records at a time.
+        let mut records = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = conductor_handle
+                .dump_cell_state_page(&cell_id, StateDumpStore::SourceChain, cursor, 2)
+                .await
+                .unwrap();
+            match page.items {
+                StateDumpItems::SourceChain(r) => records.extend(r),
+                other => panic!("unexpected items {:?}", other),
+            }
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(full.source_chain_dump.records, records);
+
+        // A page of the DHT holds the ops of the full dump.
+        let admin_api = AdminInterfaceApi::new(conductor_handle.clone());
+        let msg = AdminRequest::DumpStatePage {
+            cell_id: Box::new(cell_id),
+            store: StateDumpStore::DhtOps,
+            cursor: None,
+            limit: MAX_STATE_DUMP_PAGE_LIMIT,
+        };
+        let msg = msg.try_into().unwrap();
+        let integration_dump = full.integration_dump;
+        let respond = move |response: AdminResponse| {
+            let (ops, next_cursor) = match response {
+                AdminResponse::StateDumpPage(StateDumpPage {
+                    items: StateDumpItems::DhtOps(ops),
+                    next_cursor,
+                }) => (ops, next_cursor),
+                other => panic!("unexpected response {:?}", other),
+            };
+            assert_eq!(next_cursor, None);
+            let expected = integration_dump.validation_limbo.len()
+                + integration_dump.integration_limbo.len()
+                + integration_dump.integrated.len();
+            assert_eq!(ops.len(), expected);
+        };
+        test_handle_incoming_admin_message(msg, respond, admin_api)
+            .await
+            .unwrap();
+        conductor_handle.shutdown().await.unwrap().unwrap();
+    }
+
     async fn make_dna(network_seed: &str, zomes: Vec<TestWasm>) -> DnaFile {
         DnaFile::new(
             DnaDef {
@@ -1228,6 +1879,65 @@ pub mod test {
         .await
     }
 
+    /// Check that a connection flooding an app interface can't starve other connections.
+    #[tokio::test(flavor = "current_thread")]
+    async fn app_call_scheduler_does_not_starve_quiet_connections() {
+        use futures::stream::StreamExt;
+
+        let scheduler = AppCallScheduler::new(AppInterfaceLimits {
+            max_in_flight_per_connection: 20,
+            max_in_flight: 1,
+        });
+        let (queued_tx, mut queued_rx) = tokio::sync::mpsc::unbounded_channel();
+        let started = Arc::new(parking_lot::Mutex::new(Vec::new()));
+
+        // Runs requests through the scheduler the same way a connection does.
+        let connection = |name: &'static str, count: usize| {
+            let max_in_flight_per_connection = scheduler.max_in_flight_per_connection;
+            let connection = scheduler.connection();
+            let queued_tx = queued_tx.clone();
+            let started = started.clone();
+            futures::stream::iter(0..count).for_each_concurrent(
+                max_in_flight_per_connection,
+                move |_| {
+                    let connection = connection.clone();
+                    let queued_tx = queued_tx.clone();
+                    let started = started.clone();
+                    async move {
+                        queued_tx.send(()).unwrap();
+                        let _permit = connection.acquire().await;
+                        started.lock().push(name);
+                    }
+                },
+            )
+        };
+
+        // Hold all the capacity until every request is waiting for it. The runtime
+        // is single threaded, so a request has started waiting by the time its
+        // message is received.
+        let held = scheduler.in_flight.clone().acquire_owned().await.unwrap();
+        let greedy = tokio::spawn(connection("greedy", 20));
+        for _ in 0..20 {
+            queued_rx.recv().await.unwrap();
+        }
+        let quiet = tokio::spawn(connection("quiet", 1));
+        queued_rx.recv().await.unwrap();
+        drop(held);
+
+        greedy.await.unwrap();
+        quiet.await.unwrap();
+
+        // The quiet connection only waits behind the one request the greedy
+        // connection had waiting, not behind all of them.
+        let started = started.lock();
+        assert_eq!(
+            Some(1),
+            started.iter().position(|n| *n == "quiet"),
+            "{:?}",
+            *started
+        );
+    }
+
     /// Check that we can add and get agent info for a conductor
     /// across the admin websocket.
     #[tokio::test(flavor = "multi_thread")]
@@ -1307,6 +2017,211 @@ pub mod test {
         assert_eq!(vec![k1], results);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn app_signals_are_filtered_by_subscription() {
+        holochain_trace::test_run();
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        enum TestSignal {
+            Wanted(u32),
+            Unwanted(u32),
+        }
+
+        let zomes = SweetInlineZomes::new(vec![], 0).function("emit", |api, signal: TestSignal| {
+            api.emit_signal(AppSignal::new(ExternIO::encode(signal).unwrap()))
+                .map_err(Into::into)
+        });
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(zomes.0).await;
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let zome = app.cells()[0].zome(SweetInlineZomes::COORDINATOR);
+
+        let app_port = conductor
+            .raw_handle()
+            .add_app_interface(either::Either::Left(0), AllowedOrigins::Any, None)
+            .await
+            .unwrap();
+        let (app_tx, mut rx) = websocket_client_by_port(app_port).await.unwrap();
+        let (s_send, mut s_recv) = tokio::sync::mpsc::unbounded_channel();
+        let app_rx_task = tokio::task::spawn(async move {
+            while let Ok(ReceiveMessage::Signal(s)) = rx.recv::<AppResponse>().await {
+                s_send.send(s).unwrap();
+            }
+        });
+        authenticate_app_ws_client(
+            app_tx.clone(),
+            conductor.get_arbitrary_admin_websocket_port().unwrap(),
+            "app".to_string(),
+        )
+        .await;
+
+        let decode = |s: Vec<u8>| match Signal::try_from_vec(s).unwrap() {
+            Signal::App { signal, .. } => signal.into_inner().decode::<TestSignal>().unwrap(),
+            oth => panic!("unexpected: {oth:?}"),
+        };
+
+        // - Only subscribe to the wanted signals.
+        let response: AppResponse = app_tx
+            .request(AppRequest::SubscribeSignals {
+                zome: Some(SweetInlineZomes::COORDINATOR.into()),
+                pattern: Some("Want*".to_string()),
+            })
+            .await
+            .unwrap();
+        assert_matches!(response, AppResponse::SignalsSubscribed);
+
+        let _: () = conductor.call(&zome, "emit", TestSignal::Unwanted(1)).await;
+        let _: () = conductor.call(&zome, "emit", TestSignal::Wanted(2)).await;
+
+        // - The unwanted signal, which was emitted first, is filtered out.
+        assert_eq!(TestSignal::Wanted(2), decode(s_recv.recv().await.unwrap()));
+
+        // - After unsubscribing every signal is received again.
+        let response: AppResponse = app_tx
+            .request(AppRequest::UnsubscribeSignals)
+            .await
+            .unwrap();
+        assert_matches!(response, AppResponse::SignalsUnsubscribed);
+
+        let _: () = conductor.call(&zome, "emit", TestSignal::Unwanted(3)).await;
+        assert_eq!(
+            TestSignal::Unwanted(3),
+            decode(s_recv.recv().await.unwrap())
+        );
+
+        app_rx_task.abort();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn firehose_streams_integrated_ops() {
+        holochain_trace::test_run();
+        let mut config = SweetConductorConfig::standard();
+        config.firehose_interface = Some(FirehoseInterfaceConfig {
+            driver: InterfaceDriver::Websocket {
+                port: 0,
+                allowed_origins: AllowedOrigins::Any,
+            },
+        });
+        let mut conductor = SweetConductor::from_config(config).await;
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(simple_crud_zome()).await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let zome = app.cells()[0].zome(SweetInlineZomes::COORDINATOR);
+
+        let firehose_port = conductor.get_firehose_websocket_port().unwrap();
+        let (firehose_tx, mut rx) = websocket_client_by_port(firehose_port).await.unwrap();
+        let (s_send, mut s_recv) = tokio::sync::mpsc::unbounded_channel();
+        let firehose_rx_task = tokio::task::spawn(async move {
+            while let Ok(ReceiveMessage::Signal(s)) = rx.recv::<AppResponse>().await {
+                s_send.send(s).unwrap();
+            }
+        });
+        authenticate_app_ws_client(
+            firehose_tx,
+            conductor.get_arbitrary_admin_websocket_port().unwrap(),
+            "app".to_string(),
+        )
+        .await;
+
+        let action_hash: ActionHash = conductor.call(&zome, "create_unit", ()).await;
+
+        // - The ops of the new record are streamed once they are integrated.
+        let mut op_types = HashSet::new();
+        while op_types.len() < 3 {
+            let signal = tokio::time::timeout(std::time::Duration::from_secs(10), s_recv.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            let signal: FirehoseSignal = SerializedBytes::from(UnsafeBytes::from(signal))
+                .try_into()
+                .unwrap();
+            let op = match signal {
+                FirehoseSignal::Op(op) => op,
+                oth => panic!("unexpected: {oth:?}"),
+            };
+            assert_eq!(&op.dna_hash, dna_file.dna_hash());
+            assert_eq!(&op.author, app.cells()[0].agent_pubkey());
+            if ActionHash::with_data_sync(&op.op.as_chain_op().unwrap().action()) == action_hash {
+                op_types.insert(op.op_type);
+            }
+        }
+        assert!(op_types.contains(&DhtOpType::Chain(ChainOpType::StoreRecord)));
+        assert!(op_types.contains(&DhtOpType::Chain(ChainOpType::StoreEntry)));
+        assert!(op_types.contains(&DhtOpType::Chain(ChainOpType::RegisterAgentActivity)));
+
+        firehose_rx_task.abort();
+    }
+
+    /// Check that expired agent infos can be listed and pruned across the
+    /// admin websocket, while live ones, like the conductor's own agent, are kept.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn list_and_prune_peers_via_admin() {
+        holochain_trace::test_run();
+        let dna = make_dna("1", vec![TestWasm::Anchor]).await;
+
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let agent = conductor.setup_app("app", [&dna]).await.unwrap().cells()[0]
+            .agent_pubkey()
+            .clone();
+        let handle = conductor.raw_handle();
+        let admin_api = AdminInterfaceApi::new(handle.clone());
+
+        // - Give time for the agent to join the network.
+        crate::assert_eq_retry_10s!(
+            {
+                let req = AdminRequest::ListPeers {
+                    dna_hash: dna.dna_hash().clone(),
+                };
+                let r = make_req(admin_api.clone(), req).await.await.unwrap();
+                unwrap_to::unwrap_to!(r => AdminResponse::PeersListed).len()
+            },
+            1
+        );
+
+        // - The local agent is live.
+        let req = AdminRequest::ListPeers {
+            dna_hash: dna.dna_hash().clone(),
+        };
+        let r = make_req(admin_api.clone(), req).await.await.unwrap();
+        let peers = unwrap_to::unwrap_to!(r => AdminResponse::PeersListed).clone();
+        assert_eq!(peers[0].agent_info.agent, agent.to_kitsune());
+        assert!(!peers[0].expired);
+        assert!(peers[0].reachable);
+
+        // - Add agent infos which expired long ago.
+        let agent_infos = AgentInfoSignedFixturator::new(Unpredictable)
+            .take(5)
+            .collect::<Vec<_>>();
+        let stale_space = agent_infos[0].space.clone();
+        let req = AdminRequest::AddAgentInfo {
+            agent_infos: agent_infos.clone(),
+        };
+        let r = make_req(admin_api.clone(), req).await.await.unwrap();
+        assert_matches!(r, AdminResponse::AgentInfoAdded);
+
+        let req = AdminRequest::ListPeers {
+            dna_hash: DnaHash::from_kitsune(&stale_space),
+        };
+        let r = make_req(admin_api.clone(), req).await.await.unwrap();
+        let peers = unwrap_to::unwrap_to!(r => AdminResponse::PeersListed).clone();
+        assert_eq!(peers.len(), 1);
+        assert!(peers[0].expired);
+
+        // - Pruning removes the expired infos but keeps the local agent, whose info is live.
+        let req = AdminRequest::PrunePeers {
+            expired_by: Timestamp::now(),
+        };
+        let r = make_req(admin_api.clone(), req).await.await.unwrap();
+        assert_matches!(r, AdminResponse::PeersPruned(5));
+
+        let req = AdminRequest::ListPeers {
+            dna_hash: dna.dna_hash().clone(),
+        };
+        let r = make_req(admin_api.clone(), req).await.await.unwrap();
+        let peers = unwrap_to::unwrap_to!(r => AdminResponse::PeersListed).clone();
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].agent_info.agent, agent.to_kitsune());
+    }
+
     async fn make_req(
         admin_api: AdminInterfaceApi,
         req: AdminRequest,
@@ -1,10 +1,12 @@
 //! Implementation of the Kitsune Host API
 
+mod query_arc_storage_size;
 mod query_region_op_hashes;
 mod query_region_set;
 mod query_size_limited_regions;
 use holochain_conductor_api::conductor::ConductorConfig;
 use kitsune_p2p_bin_data::KitsuneSpace;
+pub use query_arc_storage_size::query_arc_storage_size;
 pub use query_region_op_hashes::query_region_op_hashes;
 pub use query_region_set::query_region_set;
 pub use query_size_limited_regions::query_size_limited_regions;
@@ -16,14 +18,17 @@ use futures::FutureExt;
 use holo_hash::DnaHash;
 use holochain_p2p::{
     dht::{spacetime::Topology, ArqStrat},
-    DhtOpHashExt, DnaHashExt, FetchContextExt,
+    AgentPubKeyExt, DhtOpHashExt, DnaHashExt, FetchContextExt,
 };
-use holochain_sqlite::prelude::{AsP2pMetricStoreTxExt, AsP2pStateReadExt, AsP2pStateWriteExt};
+use holochain_sqlite::prelude::{
+    p2p_prune_stale, AsP2pMetricStoreTxExt, AsP2pStateReadExt, AsP2pStateWriteExt,
+};
+use holochain_state::query::{get_agent_info_from_db, get_agent_infos_from_db};
 use holochain_types::{
     prelude::{DhtOpHash, DnaError},
     share::RwShare,
 };
-use holochain_zome_types::prelude::Timestamp;
+use holochain_zome_types::prelude::{AgentPubKey, Timestamp};
 use kitsune_p2p::{
     agent_store::AgentInfoSigned,
     dependencies::kitsune_p2p_fetch::{OpHashSized, RoughSized, TransferMethod},
@@ -31,6 +36,8 @@ use kitsune_p2p::{
     event::GetAgentInfoSignedEvt,
     KitsuneHost, KitsuneHostResult,
 };
+use kitsune_p2p_types::config::KitsuneP2pTuningParams;
+use kitsune_p2p_types::dht_arc::DhtLocation;
 use kitsune_p2p_types::metrics::MetricRecord;
 use kitsune_p2p_types::{dependencies::lair_keystore_api, KOpData, KOpHash};
 use rusqlite::ToSql;
@@ -145,8 +152,24 @@ impl KitsuneHost for KitsuneHostImpl {
     ) -> KitsuneHostResult<Option<AgentInfoSigned>> {
         let dna_hash = DnaHash::from_kitsune(&space);
         let db = self.spaces.p2p_agents_db(&dna_hash);
+        let dht_db = self.spaces.dht_db(&dna_hash);
         async move {
-            Ok(super::p2p_agent_store::get_agent_info_signed(db?.into(), space, agent).await?)
+            let info =
+                super::p2p_agent_store::get_agent_info_signed(db?.into(), space, agent.clone())
+                    .await?;
+            if info.is_some() {
+                return Ok(info);
+            }
+
+            // Fall back to the info published for the agent to the DHT, so this node
+            // can answer lookups for the agents it is an authority for.
+            let agent = AgentPubKey::from_kitsune(&agent);
+            let op = dht_db?
+                .read_async(move |txn| get_agent_info_from_db(&txn, &agent, Timestamp::now()))
+                .await?;
+            Ok(op
+                .map(|op| holochain_p2p::agent_info_from_op(&op))
+                .transpose()?)
         }
         .boxed()
         .into()
@@ -163,6 +186,47 @@ impl KitsuneHost for KitsuneHostImpl {
             .into()
     }
 
+    fn query_dht_agent_infos(
+        &self,
+        space: Arc<kitsune_p2p::KitsuneSpace>,
+        basis_loc: DhtLocation,
+    ) -> KitsuneHostResult<Vec<AgentInfoSigned>> {
+        let dna_hash = DnaHash::from_kitsune(&space);
+        let db = self.spaces.dht_db(&dna_hash);
+        async move {
+            let ops = db?
+                .read_async(|txn| get_agent_infos_from_db(&txn, Timestamp::now()))
+                .await?;
+            let mut infos = ops
+                .iter()
+                .map(holochain_p2p::agent_info_from_op)
+                .collect::<Result<Vec<_>, _>>()?;
+            infos.sort_by_key(|info| {
+                let dist = info
+                    .agent
+                    .get_loc()
+                    .as_u32()
+                    .wrapping_sub(basis_loc.as_u32());
+                dist.min(dist.wrapping_neg())
+            });
+            Ok(infos)
+        }
+        .boxed()
+        .into()
+    }
+
+    fn prune_agent_info_signed(
+        &self,
+        space: Arc<kitsune_p2p::KitsuneSpace>,
+        expired_by_ms: u64,
+    ) -> KitsuneHostResult<Vec<Arc<kitsune_p2p::KitsuneAgent>>> {
+        let dna_hash = DnaHash::from_kitsune(&space);
+        let db = self.spaces.p2p_agents_db(&dna_hash);
+        async move { Ok(p2p_prune_stale(&db?, expired_by_ms).await?) }
+            .boxed()
+            .into()
+    }
+
     fn query_region_set(
         &self,
         space: Arc<kitsune_p2p::KitsuneSpace>,
@@ -199,6 +263,19 @@ impl KitsuneHost for KitsuneHostImpl {
         .into()
     }
 
+    fn storage_disk_usage(&self, space: Arc<kitsune_p2p::KitsuneSpace>) -> KitsuneHostResult<u64> {
+        let dna_hash = DnaHash::from_kitsune(&space);
+        async move {
+            let db = self.spaces.dht_db(&dna_hash)?;
+            let size = db
+                .read_async(holochain_sqlite::stats::get_file_size)
+                .await?;
+            Ok(size as u64)
+        }
+        .boxed()
+        .into()
+    }
+
     fn query_op_hashes_by_region(
         &self,
         space: Arc<kitsune_p2p::KitsuneSpace>,
@@ -249,7 +326,12 @@ impl KitsuneHost for KitsuneHostImpl {
         context: Option<kitsune_p2p::dependencies::kitsune_p2p_fetch::FetchContext>,
     ) -> KitsuneHostResult<Vec<bool>> {
         async move {
-            let db = self.spaces.dht_db(&DnaHash::from_kitsune(&space))?;
+            let dna_hash = DnaHash::from_kitsune(&space);
+            let send_validation_receipts = self
+                .config
+                .startup_profile(&dna_hash)
+                .send_validation_receipts();
+            let db = self.spaces.dht_db(&dna_hash)?;
             let results = db
                 .write_async(move |txn| {
                     let mut out = Vec::new();
@@ -266,7 +348,9 @@ impl KitsuneHost for KitsuneHostImpl {
                                 // but we still need this for the delegate
                                 // broadcast case.
                                 if let Some(context) = context {
-                                    if context.has_request_validation_receipt() {
+                                    if send_validation_receipts
+                                        && context.has_request_validation_receipt()
+                                    {
                                         txn.execute(
                                             "UPDATE DhtOp SET require_receipt = ? WHERE DhtOp.hash = ?",
                                             [&true as &dyn ToSql, &op_hash as &dyn ToSql],
@@ -316,6 +400,17 @@ impl KitsuneHost for KitsuneHostImpl {
         });
     }
 
+    fn space_tuning_params(
+        &self,
+        space: Arc<KitsuneSpace>,
+        tuning_params: KitsuneP2pTuningParams,
+    ) -> KitsuneP2pTuningParams {
+        let dna_hash = DnaHash::from_kitsune(&space);
+        self.config
+            .startup_profile(&dna_hash)
+            .apply_tuning_params(tuning_params)
+    }
+
     fn lair_tag(&self) -> Option<Arc<str>> {
         self.lair_tag.clone()
     }
@@ -0,0 +1,89 @@
+use holochain_p2p::dht_arc::DhtArc;
+use holochain_sqlite::prelude::*;
+use rusqlite::named_params;
+
+use crate::conductor::error::ConductorResult;
+
+/// The number of bytes of op data stored within an arc.
+///
+/// Like region data, this counts the action of every op, and the entry only
+/// for ops which hold the entry itself. It is read from the running totals the
+/// database keeps for each 1/4096th of the location space, so the arc is
+/// rounded out to whole parts.
+pub async fn query_arc_storage_size(db: DbRead<DbKindDht>, arc: DhtArc) -> ConductorResult<u64> {
+    let Some((start, end)) = arc.to_bounds_grouped() else {
+        return Ok(0);
+    };
+    Ok(db
+        .read_async(move |txn| {
+            let sql = holochain_sqlite::sql::sql_cell::ARC_STORAGE_SIZE;
+            txn.query_row(
+                sql,
+                named_params! {
+                    ":storage_start_loc": start.as_u32(),
+                    ":storage_end_loc": end.as_u32(),
+                },
+                |row| Ok(row.get::<_, f64>("total_size")? as u64),
+            )
+            .map_err(DatabaseError::from)
+        })
+        .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_p2p::dht_arc::DhtLocation;
+    use holochain_state::prelude::*;
+    use holochain_state::test_utils::test_dht_db;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn arc_storage_size_keeps_a_running_total_of_ops_within_the_arc() {
+        let db = test_dht_db();
+        let op = DhtOpHashed::from_content_sync(DhtOp::from(ChainOp::RegisterAgentActivity(
+            ::fixt::fixt!(Signature),
+            Action::Dna(::fixt::fixt!(Dna)),
+        )));
+        let loc = op.dht_basis().get_loc();
+        db.test_write(move |txn| insert_op(txn, &op).unwrap());
+
+        let full = query_arc_storage_size(db.to_db().into(), DhtArc::full(loc))
+            .await
+            .unwrap();
+        assert!(full > 0);
+
+        let around = DhtArc::from_start_and_half_len(loc, 16);
+        assert_eq!(
+            full,
+            query_arc_storage_size(db.to_db().into(), around)
+                .await
+                .unwrap()
+        );
+
+        let elsewhere = DhtArc::from_start_and_half_len(
+            DhtLocation::new(loc.as_u32().wrapping_add(u32::MAX / 2)),
+            16,
+        );
+        assert_eq!(
+            0,
+            query_arc_storage_size(db.to_db().into(), elsewhere)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            0,
+            query_arc_storage_size(db.to_db().into(), DhtArc::empty(loc))
+                .await
+                .unwrap()
+        );
+
+        // The running total drops when ops are removed.
+        db.test_write(|txn| txn.execute("DELETE FROM DhtOp", []).unwrap());
+        assert_eq!(
+            0,
+            query_arc_storage_size(db.to_db().into(), DhtArc::full(loc))
+                .await
+                .unwrap()
+        );
+    }
+}
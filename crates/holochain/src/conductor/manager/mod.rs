@@ -92,6 +92,12 @@ pub fn spawn_task_outcome_handler(
                         "Shutting down conductor due to unrecoverable error: {:?}\nContext: {}",
                         error, context
                     );
+                    conductor
+                        .record_event(ConductorEvent::Crashed {
+                            error: format!("{:?}", error),
+                            context,
+                        })
+                        .await;
                     return Err(TaskManagerError::Unrecoverable(Box::new(error)));
                 }
                 TaskOutcome::StopApps(cell_id, error, context) => {
@@ -1,6 +1,7 @@
 use holochain_types::{prelude::*, share::RwShare};
 use holochain_zome_types::entry_def::EntryDef;
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::*;
 
 use crate::core::ribosome::{real_ribosome::RealRibosome, RibosomeT};
@@ -9,6 +10,10 @@ use crate::core::ribosome::{real_ribosome::RealRibosome, RibosomeT};
 pub struct RibosomeStore {
     ribosomes: HashMap<DnaHash, RealRibosome>,
     entry_defs: HashMap<EntryDefBufferKey, EntryDef>,
+    host_fn_allowlists: HashMap<DnaHash, Arc<HostFnAllowlist>>,
+    /// The DNA files from before the last coordinator update of each DNA,
+    /// which can be rolled back to.
+    previous_coordinators: HashMap<DnaHash, DnaFile>,
 }
 
 impl RibosomeStore {
@@ -16,6 +21,8 @@ impl RibosomeStore {
         RwShare::new(RibosomeStore {
             ribosomes: HashMap::new(),
             entry_defs: HashMap::new(),
+            host_fn_allowlists: HashMap::new(),
+            previous_coordinators: HashMap::new(),
         })
     }
 
@@ -48,8 +55,50 @@ impl RibosomeStore {
         self.ribosomes.get(hash).map(|r| r.dna_file().clone())
     }
 
+    /// Replace the ribosome of a DNA with one with updated coordinators,
+    /// keeping the DNA file of the `current` ribosome so it can be rolled back to.
+    ///
+    /// Fails and leaves the store untouched if the ribosome in the store
+    /// is no longer the `current` one.
+    pub fn swap_coordinators(
+        &mut self,
+        current: &RealRibosome,
+        updated: RealRibosome,
+    ) -> Result<(), RealRibosome> {
+        let hash = current.dna_hash();
+        match self.ribosomes.get(hash) {
+            Some(ribosome)
+                if ribosome.dna_def().coordinator_zomes == current.dna_def().coordinator_zomes =>
+            {
+                self.previous_coordinators
+                    .insert(hash.clone(), current.dna_file().clone());
+                self.ribosomes.insert(hash.clone(), updated);
+                Ok(())
+            }
+            _ => Err(updated),
+        }
+    }
+
+    /// The DNA file from before the last coordinator update of a DNA.
+    pub fn get_previous_coordinators(&self, hash: &DnaHash) -> Option<DnaFile> {
+        self.previous_coordinators.get(hash).cloned()
+    }
+
     pub fn get_ribosome(&self, hash: &DnaHash) -> Option<RealRibosome> {
-        self.ribosomes.get(hash).cloned()
+        self.ribosomes.get(hash).map(|ribosome| {
+            let mut ribosome = ribosome.clone();
+            ribosome.host_fn_allowlist = self.host_fn_allowlists.get(hash).cloned();
+            ribosome
+        })
+    }
+
+    /// Replace the host function allowlists of all DNAs.
+    /// DNAs without an allowlist may use every host function.
+    pub fn set_host_fn_allowlists(&mut self, allowlists: HashMap<DnaHash, HostFnAllowlist>) {
+        self.host_fn_allowlists = allowlists
+            .into_iter()
+            .map(|(hash, allowlist)| (hash, Arc::new(allowlist)))
+            .collect();
     }
 
     pub fn add_entry_def(&mut self, k: EntryDefBufferKey, entry_def: EntryDef) {
@@ -35,13 +35,14 @@ use holochain_p2p::{
 };
 use holochain_sqlite::prelude::{
     DatabaseResult, DbKindAuthored, DbKindCache, DbKindConductor, DbKindDht, DbKindP2pAgents,
-    DbKindP2pMetrics, DbKindWasm, DbSyncLevel, DbSyncStrategy, DbWrite, ReadAccess,
+    DbKindP2pMetrics, DbKindWasm, DbPoolSizes, DbSyncLevel, DbSyncStrategy, DbWrite, ReadAccess,
 };
 use holochain_state::{
     host_fn_workspace::SourceChainWorkspace,
     mutations,
+    negative_cache::NegativeCache,
     prelude::*,
-    query::{map_sql_dht_op_common, StateQueryError},
+    query::{map_sql_dht_op, map_sql_dht_op_common, StateQueryError},
 };
 use holochain_util::timed;
 use kitsune_p2p::event::{TimeWindow, TimeWindowInclusive};
@@ -49,6 +50,7 @@ use kitsune_p2p_block::NodeId;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
 use rusqlite::{named_params, OptionalExtension};
 use std::convert::TryInto;
+use std::path::Path;
 use std::path::PathBuf;
 use tracing::instrument;
 
@@ -105,6 +107,9 @@ pub struct Space {
     /// A cache for slow database queries.
     pub dht_query_cache: DhtDbQueryCache,
 
+    /// The hashes which authorities recently reported they hold no data for.
+    pub negative_cache: NegativeCache,
+
     /// Countersigning workspace that is shared across this cell.
     pub countersigning_workspace: CountersigningWorkspace,
 
@@ -114,7 +119,12 @@ pub struct Space {
     /// Incoming ops batch for this space.
     pub incoming_ops_batch: IncomingOpsBatch,
 
+    /// The order in which the validation queues of this space process ops.
+    pub validation_queue_order: Arc<parking_lot::RwLock<ValidationQueueOrder>>,
+
     root_db_dir: Arc<PathBuf>,
+
+    db_pool_sizes: DbPoolSizes,
 }
 
 #[cfg(test)]
@@ -143,10 +153,20 @@ impl Spaces {
             DbSyncStrategy::Resilient => DbSyncLevel::Normal,
         };
 
-        let conductor_db =
-            DbWrite::open_with_sync_level(root_db_dir.as_ref(), DbKindConductor, db_sync_level)?;
-        let wasm_db =
-            DbWrite::open_with_sync_level(root_db_dir.as_ref(), DbKindWasm, db_sync_level)?;
+        let db_pool_sizes = config.db_pool_sizes;
+
+        let conductor_db = DbWrite::open_with_pool_size(
+            root_db_dir.as_ref(),
+            DbKindConductor,
+            db_sync_level,
+            db_pool_sizes.conductor,
+        )?;
+        let wasm_db = DbWrite::open_with_pool_size(
+            root_db_dir.as_ref(),
+            DbKindWasm,
+            db_sync_level,
+            db_pool_sizes.wasm,
+        )?;
         Ok(Spaces {
             map: RwShare::new(HashMap::new()),
             db_dir: Arc::new(root_db_dir),
@@ -334,6 +354,10 @@ impl Spaces {
                             Arc::new(dna_hash.clone()),
                             self.db_dir.to_path_buf(),
                             self.config.db_sync_strategy,
+                            self.config.db_pool_sizes,
+                            self.config
+                                .conductor_tuning_params()
+                                .cascade_negative_cache_ttl(),
                         )?;
 
                         let r = f(&space);
@@ -554,6 +578,69 @@ impl Spaces {
             .await?)
     }
 
+    /// Write every integrated op held for a DNA into a DHT archive in `out`.
+    ///
+    /// Each op is written into the archive as it is read from the database, so the
+    /// ops held for the DNA are never all decoded in memory at once.
+    pub async fn export_dht_archive(
+        &self,
+        dna_hash: &DnaHash,
+        out: std::fs::File,
+    ) -> ConductorResult<()> {
+        let sql = holochain_sqlite::sql::sql_cell::state_dump::DHT_OPS_INTEGRATED;
+        let mut writer = DhtArchiveWriter::with_writer(
+            std::io::BufWriter::new(out),
+            dna_hash.clone(),
+            Timestamp::now(),
+        )?;
+        self.dht_db(dna_hash)?
+            .read_async(move |txn| -> ConductorResult<()> {
+                let mut stmt = txn.prepare(sql)?;
+                let mut rows = stmt.query([])?;
+                while let Some(row) = rows.next()? {
+                    let hash: DhtOpHash = row.get("dht_hash")?;
+                    let op = map_sql_dht_op(false, "dht_type", row)?;
+                    writer.push(&DhtOpHashed::with_pre_hashed(op, hash))?;
+                }
+                writer.finish()?;
+                Ok(())
+            })
+            .await
+    }
+
+    /// Take a snapshot of every database this conductor has open, writing them into
+    /// a directory with the same layout as the databases root.
+    /// Returns the paths of the database files which were written.
+    pub async fn snapshot_databases(&self, dir: &Path) -> ConductorResult<Vec<PathBuf>> {
+        async fn backup<Kind: DbKindT>(
+            db: &DbWrite<Kind>,
+            dir: &Path,
+            paths: &mut Vec<PathBuf>,
+        ) -> ConductorResult<()> {
+            let path = dir.join(db.kind().filename());
+            db.backup_to(&path).await?;
+            paths.push(path);
+            Ok(())
+        }
+
+        let mut paths = Vec::new();
+        backup(&self.conductor_db, dir, &mut paths).await?;
+        backup(&self.wasm_db, dir, &mut paths).await?;
+        let spaces: Vec<Space> = self
+            .map
+            .share_ref(|spaces| spaces.values().cloned().collect());
+        for space in spaces {
+            backup(&space.cache_db, dir, &mut paths).await?;
+            backup(&space.dht_db, dir, &mut paths).await?;
+            backup(&space.p2p_agents_db, dir, &mut paths).await?;
+            backup(&space.p2p_metrics_db, dir, &mut paths).await?;
+            for authored_db in space.get_all_authored_dbs() {
+                backup(&authored_db, dir, &mut paths).await?;
+            }
+        }
+        Ok(paths)
+    }
+
     #[instrument(skip(self, op_hashes))]
     /// The network module is requesting the content for dht ops
     pub async fn handle_fetch_op_data_by_hashes(
@@ -564,10 +651,13 @@ impl Spaces {
     ) -> ConductorResult<Vec<(holo_hash::DhtOpHash, holochain_types::dht_op::DhtOp)>> {
         let mut sql = "
             SELECT DhtOp.hash, DhtOp.type AS dht_type,
-            Action.blob AS action_blob, Entry.blob AS entry_blob
+            Action.blob AS action_blob, Entry.blob AS entry_blob,
+            AgentInfo.blob AS agent_info_blob, BlobChunk.blob AS blob_chunk_blob
             FROM DHtOp
             LEFT JOIN Action ON DhtOp.action_hash = Action.hash
             LEFT JOIN Entry ON Action.entry_hash = Entry.hash
+            LEFT JOIN AgentInfo ON DhtOp.hash = AgentInfo.hash
+            LEFT JOIN BlobChunk ON DhtOp.hash = BlobChunk.hash
             WHERE
             DhtOp.hash = ?
         "
@@ -590,9 +680,12 @@ impl Spaces {
                     let mut stmt = txn.prepare_cached(&sql)?;
                     let mut rows = stmt.query([hash])?;
                     if let Some(row) = rows.next()? {
-                        let op = holochain_state::query::map_sql_dht_op(false, "dht_type", row)?;
+                        let op = map_sql_dht_op_common(false, false, "dht_type", row)?;
                         let hash: DhtOpHash = row.get("hash")?;
-                        out.push((hash, op));
+                        // Skip ops which can't be sent, such as those whose entry has been purged.
+                        if let Some(op) = op {
+                            out.push((hash, op));
+                        }
                     } else {
                         return Err(holochain_state::query::StateQueryError::Sql(
                             rusqlite::Error::QueryReturnedNoRows,
@@ -662,6 +755,11 @@ impl Spaces {
                     return Ok(());
                 }
             };
+            let request_validation_receipt = request_validation_receipt
+                && self
+                    .config
+                    .startup_profile(dna_hash)
+                    .send_validation_receipts();
             incoming_dht_ops_workflow(space, trigger, ops, request_validation_receipt).await?;
         }
         Ok(())
@@ -681,34 +779,44 @@ impl Space {
         dna_hash: Arc<DnaHash>,
         root_db_dir: PathBuf,
         db_sync_strategy: DbSyncStrategy,
+        db_pool_sizes: DbPoolSizes,
+        negative_cache_ttl: std::time::Duration,
     ) -> DatabaseResult<Self> {
         let space = dna_hash.to_kitsune();
         let db_sync_level = match db_sync_strategy {
             DbSyncStrategy::Fast => DbSyncLevel::Off,
             DbSyncStrategy::Resilient => DbSyncLevel::Normal,
         };
-        let cache = DbWrite::open_with_sync_level(
+        let cache = DbWrite::open_with_pool_size(
             root_db_dir.as_ref(),
             DbKindCache(dna_hash.clone()),
             db_sync_level,
+            db_pool_sizes.cache,
         )?;
-        let dht_db = DbWrite::open_with_sync_level(
+        let dht_db = DbWrite::open_with_pool_size(
             root_db_dir.as_ref(),
             DbKindDht(dna_hash.clone()),
             db_sync_level,
+            db_pool_sizes.dht,
         )?;
-        let p2p_agents_db = DbWrite::open_with_sync_level(
+        let p2p_agents_db = DbWrite::open_with_pool_size(
             root_db_dir.as_ref(),
             DbKindP2pAgents(space.clone()),
             db_sync_level,
+            db_pool_sizes.p2p_agent_store,
         )?;
-        let p2p_metrics_db = DbWrite::open_with_sync_level(
+        let p2p_metrics_db = DbWrite::open_with_pool_size(
             root_db_dir.as_ref(),
             DbKindP2pMetrics(space),
             db_sync_level,
+            db_pool_sizes.p2p_metrics,
+        )?;
+        let conductor_db: DbWrite<DbKindConductor> = DbWrite::open_with_pool_size(
+            root_db_dir.as_ref(),
+            DbKindConductor,
+            db_sync_level,
+            db_pool_sizes.conductor,
         )?;
-        let conductor_db: DbWrite<DbKindConductor> =
-            DbWrite::open_with_sync_level(root_db_dir.as_ref(), DbKindConductor, db_sync_level)?;
 
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         tokio::spawn(p2p_agent_store::p2p_put_all_batch(
@@ -732,9 +840,12 @@ impl Space {
             countersigning_workspace,
             incoming_op_hashes,
             incoming_ops_batch,
+            validation_queue_order: Default::default(),
             dht_query_cache,
+            negative_cache: NegativeCache::new(negative_cache_ttl),
             conductor_db,
             root_db_dir: Arc::new(root_db_dir),
+            db_pool_sizes,
         };
         Ok(r)
     }
@@ -771,7 +882,8 @@ impl Space {
             author,
             dna_def,
         )
-        .await?)
+        .await?
+        .with_negative_cache(self.negative_cache.clone()))
     }
 
     /// Get or create the authored database for an agent in this space
@@ -782,10 +894,11 @@ impl Space {
         match self.authored_dbs.lock().entry(author.clone()) {
             hash_map::Entry::Occupied(entry) => Ok(entry.get().clone()),
             hash_map::Entry::Vacant(entry) => {
-                let db = DbWrite::open_with_sync_level(
+                let db = DbWrite::open_with_pool_size(
                     self.root_db_dir.as_ref(),
                     DbKindAuthored(Arc::new(CellId::new((*self.dna_hash).clone(), author))),
                     DbSyncLevel::Normal,
+                    self.db_pool_sizes.authored,
                 )?;
 
                 entry.insert(db.clone());
@@ -864,6 +977,11 @@ impl TestSpaces {
 #[cfg(test)]
 impl TestSpace {
     pub fn new(dna_hash: DnaHash) -> Self {
+        Self::with_negative_cache_ttl(dna_hash, std::time::Duration::ZERO)
+    }
+
+    /// A test space whose cascades remember authoritative misses for `ttl`.
+    pub fn with_negative_cache_ttl(dna_hash: DnaHash, ttl: std::time::Duration) -> Self {
         let temp_dir = tempfile::Builder::new()
             .prefix("holochain-test-environments")
             .tempdir()
@@ -874,6 +992,8 @@ impl TestSpace {
                 Arc::new(dna_hash),
                 temp_dir.path().to_path_buf(),
                 Default::default(),
+                Default::default(),
+                ttl,
             )
             .unwrap(),
             _temp_dir: temp_dir,
@@ -9,6 +9,7 @@ use holochain_types::websocket::AllowedOrigins;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use super::error::{ConductorError, ConductorResult};
@@ -42,6 +43,26 @@ pub struct ConductorState {
     /// List of interfaces any UI can use to access zome functions.
     #[serde(default)]
     pub(crate) app_interfaces: HashMap<AppInterfaceId, AppInterfaceConfig>,
+    /// The order in which each DNA's validation queues process ops,
+    /// for DNAs which were installed with an order other than the default.
+    #[serde(default)]
+    validation_queue_orders: HashMap<DnaHash, ValidationQueueOrder>,
+    /// The number of signals persisted for each app which was installed
+    /// with missed signal persistence enabled.
+    #[serde(default)]
+    missed_signals_buffer_sizes: HashMap<InstalledAppId, u32>,
+    /// The default zome call timeout, in milliseconds, for each app which
+    /// was installed with one.
+    #[serde(default)]
+    zome_call_timeouts: HashMap<InstalledAppId, u64>,
+    /// What gets do with data by warranted authors, for each app which
+    /// was installed with a default other than [`WarrantedAuthors::Include`].
+    #[serde(default)]
+    warranted_authors: HashMap<InstalledAppId, WarrantedAuthors>,
+    /// The peers the cells of each app may send requests to, for each app
+    /// which was installed with an egress policy.
+    #[serde(default)]
+    egress_policies: HashMap<InstalledAppId, EgressPolicy>,
 }
 
 /// A unique identifier used to refer to an App Interface internally.
@@ -159,9 +180,90 @@ impl ConductorState {
 
     /// Getter for a single app. Returns error if app missing.
     pub fn remove_app(&mut self, id: &InstalledAppId) -> ConductorResult<InstalledApp> {
-        self.installed_apps
+        self.missed_signals_buffer_sizes.remove(id);
+        self.zome_call_timeouts.remove(id);
+        self.warranted_authors.remove(id);
+        self.egress_policies.remove(id);
+        let app = self
+            .installed_apps
             .remove(id)
-            .ok_or_else(|| ConductorError::AppNotInstalled(id.clone()))
+            .ok_or_else(|| ConductorError::AppNotInstalled(id.clone()))?;
+
+        // Forget the validation queue orders of DNAs which no other app uses.
+        let dnas_in_use: HashSet<&DnaHash> = self
+            .installed_apps
+            .values()
+            .flat_map(|app| app.all_cells().map(|cell_id| cell_id.dna_hash()))
+            .collect();
+        for cell_id in app.all_cells() {
+            if !dnas_in_use.contains(cell_id.dna_hash()) {
+                self.validation_queue_orders.remove(cell_id.dna_hash());
+            }
+        }
+        Ok(app)
+    }
+
+    /// The order in which the validation queues of a DNA process ops.
+    pub fn validation_queue_order(&self, dna_hash: &DnaHash) -> ValidationQueueOrder {
+        self.validation_queue_orders
+            .get(dna_hash)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Set the order in which the validation queues of a DNA process ops.
+    pub fn set_validation_queue_order(&mut self, dna_hash: DnaHash, order: ValidationQueueOrder) {
+        self.validation_queue_orders.insert(dna_hash, order);
+    }
+
+    /// The number of missed signals persisted for an app, if persistence
+    /// is enabled for it.
+    pub fn missed_signals_buffer_size(&self, id: &InstalledAppId) -> Option<u32> {
+        self.missed_signals_buffer_sizes.get(id).copied()
+    }
+
+    /// Enable persistence of missed signals for an app.
+    pub fn set_missed_signals_buffer_size(&mut self, id: InstalledAppId, buffer_size: u32) {
+        self.missed_signals_buffer_sizes.insert(id, buffer_size);
+    }
+
+    /// All apps with missed signal persistence enabled, with their buffer sizes.
+    pub fn missed_signals_buffer_sizes(&self) -> &HashMap<InstalledAppId, u32> {
+        &self.missed_signals_buffer_sizes
+    }
+
+    /// The default zome call timeout for an app, in milliseconds, if it has one.
+    pub fn zome_call_timeout_ms(&self, id: &InstalledAppId) -> Option<u64> {
+        self.zome_call_timeouts.get(id).copied()
+    }
+
+    /// Set the default zome call timeout for an app, in milliseconds.
+    pub fn set_zome_call_timeout_ms(&mut self, id: InstalledAppId, timeout_ms: u64) {
+        self.zome_call_timeouts.insert(id, timeout_ms);
+    }
+
+    /// What gets do with data by warranted authors, by default, for an app.
+    pub fn warranted_authors(&self, id: &InstalledAppId) -> Option<WarrantedAuthors> {
+        self.warranted_authors.get(id).copied()
+    }
+
+    /// Set what gets do with data by warranted authors, by default, for an app.
+    pub fn set_warranted_authors(
+        &mut self,
+        id: InstalledAppId,
+        warranted_authors: WarrantedAuthors,
+    ) {
+        self.warranted_authors.insert(id, warranted_authors);
+    }
+
+    /// The peers the cells of an app may send requests to, if restricted.
+    pub fn egress_policy(&self, id: &InstalledAppId) -> Option<&EgressPolicy> {
+        self.egress_policies.get(id)
+    }
+
+    /// Restrict the peers the cells of an app may send requests to.
+    pub fn set_egress_policy(&mut self, id: InstalledAppId, egress_policy: EgressPolicy) {
+        self.egress_policies.insert(id, egress_policy);
     }
 
     /// Add an app in the Deactivated state. Returns an error if an app is already
@@ -212,6 +314,7 @@ impl ConductorState {
                 tracing::warn!("Using default NetworkCompatParams");
                 None
             },
+            ..Default::default()
         }
     }
 }
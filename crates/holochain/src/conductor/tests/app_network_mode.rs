@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use holochain_conductor_api::AppNetworkMode;
+use holochain_sqlite::db::DbKindT;
+use holochain_sqlite::prelude::DatabaseResult;
+use holochain_types::db::DbWrite;
+use holochain_types::prelude::*;
+use holochain_wasm_test_utils::TestWasm;
+
+use crate::sweettest::*;
+
+/// The number of ops for an action which are held in a database, and how many of them
+/// have been published.
+async fn op_counts<Kind: DbKindT>(db: &DbWrite<Kind>, action_hash: &ActionHash) -> (u32, u32) {
+    let action_hash = action_hash.clone();
+    db.read_async(move |txn| -> DatabaseResult<(u32, u32)> {
+        Ok(txn.query_row(
+            "SELECT COUNT(*), COUNT(last_publish_time) FROM DhtOp WHERE action_hash = ?",
+            [action_hash],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?)
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn isolated_app_neither_publishes_nor_gossips_until_it_is_back_to_normal() {
+    holochain_trace::test_run();
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let mut conductors = SweetConductorBatch::from_standard_config(2).await;
+    let apps = conductors.setup_app("app", &[dna.clone()]).await.unwrap();
+    let ((alice,), (bob,)) = apps.into_tuples();
+    conductors.exchange_peer_info().await;
+    await_consistency(30, [&alice, &bob]).await.unwrap();
+
+    conductors[0]
+        .set_app_network_mode(&"app".to_string(), AppNetworkMode::Isolated)
+        .await
+        .unwrap();
+    let action_hash: ActionHash = conductors[0]
+        .call(
+            &alice.zome(TestWasm::Create.coordinator_zome_name()),
+            "create_entry",
+            (),
+        )
+        .await;
+
+    // The publish workflow runs after the commit, but publishes nothing and
+    // bob can't gossip for the ops either.
+    tokio::time::sleep(Duration::from_secs(5)).await;
+    let (authored, published) = op_counts(alice.authored_db(), &action_hash).await;
+    assert!(authored > 0);
+    assert_eq!(0, published);
+    assert_eq!((0, 0), op_counts(bob.dht_db(), &action_hash).await);
+
+    // Local reads still work while the app is isolated.
+    let record: Option<Record> = conductors[0]
+        .call(
+            &alice.zome(TestWasm::Create.coordinator_zome_name()),
+            "get_entry",
+            (),
+        )
+        .await;
+    assert!(record.is_some());
+
+    // Going back to normal publishes the ops authored meanwhile.
+    conductors[0]
+        .set_app_network_mode(&"app".to_string(), AppNetworkMode::Normal)
+        .await
+        .unwrap();
+    await_consistency(60, [&alice, &bob]).await.unwrap();
+    let (authored, published) = op_counts(alice.authored_db(), &action_hash).await;
+    assert_eq!(authored, published);
+    assert!(op_counts(bob.dht_db(), &action_hash).await.0 > 0);
+}
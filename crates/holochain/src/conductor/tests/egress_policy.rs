@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use holochain_p2p::HolochainP2pError;
+use holochain_p2p::HolochainP2pSender;
+use holochain_types::prelude::*;
+use holochain_wasm_test_utils::TestWasm;
+
+use crate::sweettest::*;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn egress_policy_blocks_and_records_requests_of_its_cells_to_denied_peers() {
+    holochain_trace::test_run();
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let mut conductors = SweetConductorBatch::from_standard_config(2).await;
+    let apps = conductors.setup_app("app", &[dna.clone()]).await.unwrap();
+    let ((alice,), (bob,)) = apps.into_tuples();
+    conductors.exchange_peer_info().await;
+
+    // Another app with the same DNA restricts its own cell on alice's conductor.
+    let since = Timestamp::now();
+    let carol = SweetAgents::one(conductors[0].keystore()).await;
+    conductors[0]
+        .clone()
+        .install_app_bundle(InstallAppPayload {
+            source: AppBundleSource::Bundle(app_bundle_from_dnas([&dna]).await),
+            agent_key: carol.clone(),
+            installed_app_id: Some("restricted".into()),
+            membrane_proofs: HashMap::new(),
+            network_seed: None,
+            validation_queue_orders: Default::default(),
+            missed_signals_buffer_size: None,
+            zome_call_timeout_ms: None,
+            warranted_authors: None,
+            egress_policy: Some(EgressPolicy {
+                allow: vec![],
+                deny: vec![EgressPeer::Agent(bob.agent_pubkey().clone())],
+            }),
+            #[cfg(feature = "chc")]
+            ignore_genesis_failure: false,
+        })
+        .await
+        .unwrap();
+    let carol_cell_id = CellId::new(dna.dna_hash().clone(), carol.clone());
+
+    let probe = |from_agent: &AgentPubKey| {
+        conductors[0].holochain_p2p().probe_basis(
+            dna.dna_hash().clone(),
+            Some(from_agent.clone()),
+            bob.agent_pubkey().clone(),
+            alice.agent_pubkey().clone().into(),
+            None,
+        )
+    };
+
+    let err = probe(&carol).await.unwrap_err();
+    assert!(matches!(err, HolochainP2pError::EgressBlocked { .. }));
+
+    // The policy doesn't restrict the cells of other apps in the same space.
+    probe(alice.agent_pubkey()).await.unwrap();
+
+    // The blocked request is recorded in the event journal.
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let events = conductors[0].event_journal(since).await.unwrap();
+            if events.iter().any(|entry| {
+                matches!(
+                    &entry.event,
+                    ConductorEvent::EgressBlocked { cell_id, to_agent, request }
+                        if cell_id == &carol_cell_id
+                            && to_agent == bob.agent_pubkey()
+                            && request == "probe_basis"
+                )
+            }) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    // Uninstalling the app lifts the restriction.
+    conductors[0]
+        .clone()
+        .uninstall_app(&"restricted".to_string())
+        .await
+        .unwrap();
+    probe(&carol).await.unwrap();
+}
@@ -0,0 +1,65 @@
+use hdk::prelude::*;
+use holochain_types::prelude::InstalledAppId;
+use holochain_wasm_test_utils::TestWasm;
+
+use crate::sweettest::*;
+
+#[derive(Serialize, Deserialize, SerializedBytes, Debug)]
+struct Post(String);
+
+#[tokio::test(flavor = "multi_thread")]
+async fn get_entries_by_author_from_another_conductor() {
+    holochain_trace::test_run();
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let config = SweetConductorConfig::standard();
+    let mut conductors = SweetConductorBatch::from_config(2, config).await;
+    let app_id: InstalledAppId = "app".into();
+    let apps = conductors.setup_app(&app_id, &[dna]).await.unwrap();
+    let cells = apps.cells_flattened();
+    let alice = SweetZome::new(
+        cells[0].cell_id().clone(),
+        TestWasm::Create.coordinator_zome_name(),
+    );
+    let bob = SweetZome::new(
+        cells[1].cell_id().clone(),
+        TestWasm::Create.coordinator_zome_name(),
+    );
+
+    conductors.exchange_peer_info().await;
+
+    let first: ActionHash = conductors[0]
+        .call(&alice, "create_post", Post("first".into()))
+        .await;
+    let _: ActionHash = conductors[0].call(&alice, "create_msg", ()).await;
+    let second: ActionHash = conductors[0]
+        .call(&alice, "create_post", Post("second".into()))
+        .await;
+
+    await_consistency(10, &cells).await.unwrap();
+
+    // Only alice's posts are listed, in the order she created them, with their entries.
+    let posts: Vec<Record> = conductors[1]
+        .call(&bob, "get_posts_by_author", cells[0].agent_pubkey().clone())
+        .await;
+    assert_eq!(
+        posts
+            .iter()
+            .map(|record| record.action_address().clone())
+            .collect::<Vec<_>>(),
+        vec![first, second]
+    );
+    assert!(posts
+        .iter()
+        .all(|record| record.entry().as_option().is_some()));
+
+    // Bob hasn't created any posts.
+    let posts: Vec<Record> = conductors[0]
+        .call(
+            &alice,
+            "get_posts_by_author",
+            cells[1].agent_pubkey().clone(),
+        )
+        .await;
+    assert!(posts.is_empty());
+}
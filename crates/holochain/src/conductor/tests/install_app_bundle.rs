@@ -35,6 +35,8 @@ async fn clone_only_provisioning_creates_no_cell_and_allows_cloning() {
                 modifiers: modifiers.clone(),
                 installed_hash: Some(installed_dna_hash.into()),
                 clone_limit,
+                host_fns: None,
+                clone_archival: None,
             },
             provisioning: Some(CellProvisioning::CloneOnly),
         }];
@@ -57,6 +59,11 @@ async fn clone_only_provisioning_creates_no_cell_and_allows_cloning() {
             installed_app_id: Some("app_1".into()),
             network_seed: None,
             membrane_proofs: HashMap::new(),
+            validation_queue_orders: Default::default(),
+            missed_signals_buffer_size: None,
+            zome_call_timeout_ms: None,
+            warranted_authors: None,
+            egress_policy: None,
             #[cfg(feature = "chc")]
             ignore_genesis_failure: false,
         }
@@ -161,6 +168,8 @@ async fn reject_duplicate_app_for_same_agent() {
             modifiers: modifiers.clone(),
             installed_hash: Some(installed_dna_hash.into()),
             clone_limit: 0,
+            host_fns: None,
+            clone_archival: None,
         },
         provisioning: Some(CellProvisioning::Create { deferred: false }),
     }];
@@ -184,6 +193,11 @@ async fn reject_duplicate_app_for_same_agent() {
             installed_app_id: Some("app_1".into()),
             network_seed: None,
             membrane_proofs: HashMap::new(),
+            validation_queue_orders: Default::default(),
+            missed_signals_buffer_size: None,
+            zome_call_timeout_ms: None,
+            warranted_authors: None,
+            egress_policy: None,
             #[cfg(feature = "chc")]
             ignore_genesis_failure: false,
         })
@@ -201,6 +215,11 @@ async fn reject_duplicate_app_for_same_agent() {
             agent_key: alice.clone(),
             installed_app_id: Some("app_2".into()),
             membrane_proofs: HashMap::new(),
+            validation_queue_orders: Default::default(),
+            missed_signals_buffer_size: None,
+            zome_call_timeout_ms: None,
+            warranted_authors: None,
+            egress_policy: None,
             #[cfg(feature = "chc")]
             ignore_genesis_failure: false,
             network_seed: None,
@@ -225,6 +244,11 @@ async fn reject_duplicate_app_for_same_agent() {
             agent_key: alice.clone(),
             installed_app_id: Some("app_2".into()),
             membrane_proofs: HashMap::new(),
+            validation_queue_orders: Default::default(),
+            missed_signals_buffer_size: None,
+            zome_call_timeout_ms: None,
+            warranted_authors: None,
+            egress_policy: None,
             #[cfg(feature = "chc")]
             ignore_genesis_failure: false,
             network_seed: None,
@@ -246,6 +270,11 @@ async fn reject_duplicate_app_for_same_agent() {
             agent_key: alice.clone(),
             installed_app_id: Some("app_2".into()),
             membrane_proofs: HashMap::new(),
+            validation_queue_orders: Default::default(),
+            missed_signals_buffer_size: None,
+            zome_call_timeout_ms: None,
+            warranted_authors: None,
+            egress_policy: None,
             #[cfg(feature = "chc")]
             ignore_genesis_failure: false,
             network_seed: Some("network".into()),
@@ -273,6 +302,8 @@ async fn can_install_app_a_second_time_using_nothing_but_the_manifest_from_app_i
             // Note that there is no installed hash provided. We'll check that this changes later.
             installed_hash: None,
             clone_limit: 0,
+            host_fns: None,
+            clone_archival: None,
         },
         provisioning: Some(CellProvisioning::Create { deferred: false }),
     }];
@@ -298,6 +329,11 @@ async fn can_install_app_a_second_time_using_nothing_but_the_manifest_from_app_i
             installed_app_id: Some("app_1".into()),
             network_seed: Some("final seed".into()),
             membrane_proofs: HashMap::new(),
+            validation_queue_orders: Default::default(),
+            missed_signals_buffer_size: None,
+            zome_call_timeout_ms: None,
+            warranted_authors: None,
+            egress_policy: None,
             #[cfg(feature = "chc")]
             ignore_genesis_failure: false,
         })
@@ -342,6 +378,11 @@ async fn can_install_app_a_second_time_using_nothing_but_the_manifest_from_app_i
             installed_app_id: Some("app_2".into()),
             network_seed: None,
             membrane_proofs: HashMap::new(),
+            validation_queue_orders: Default::default(),
+            missed_signals_buffer_size: None,
+            zome_call_timeout_ms: None,
+            warranted_authors: None,
+            egress_policy: None,
             #[cfg(feature = "chc")]
             ignore_genesis_failure: false,
         })
@@ -376,6 +417,8 @@ async fn network_seed_regression() {
                 modifiers: DnaModifiersOpt::default(),
                 installed_hash: None,
                 clone_limit: 0,
+                host_fns: None,
+                clone_archival: None,
             },
             provisioning: None,
         }];
@@ -407,6 +450,11 @@ async fn network_seed_regression() {
             installed_app_id: Some("no-seed".into()),
             network_seed: None,
             membrane_proofs: HashMap::new(),
+            validation_queue_orders: Default::default(),
+            missed_signals_buffer_size: None,
+            zome_call_timeout_ms: None,
+            warranted_authors: None,
+            egress_policy: None,
             #[cfg(feature = "chc")]
             ignore_genesis_failure: false,
         })
@@ -421,6 +469,11 @@ async fn network_seed_regression() {
             installed_app_id: Some("yes-seed".into()),
             network_seed: Some("seed".into()),
             membrane_proofs: HashMap::new(),
+            validation_queue_orders: Default::default(),
+            missed_signals_buffer_size: None,
+            zome_call_timeout_ms: None,
+            warranted_authors: None,
+            egress_policy: None,
             #[cfg(feature = "chc")]
             ignore_genesis_failure: false,
         })
@@ -607,6 +660,8 @@ impl TestCase {
                         modifiers: dna_modifiers.clone(),
                         installed_hash: None,
                         clone_limit: 10,
+                        host_fns: None,
+                        clone_archival: None,
                     },
                     provisioning: Some(CellProvisioning::Create { deferred: false }),
                 }];
@@ -631,6 +686,8 @@ impl TestCase {
                         modifiers: dna_modifiers.clone(),
                         installed_hash: Some(dna_hash.clone().into()),
                         clone_limit: 0,
+                        host_fns: None,
+                        clone_archival: None,
                     },
                     provisioning: None,
                 }];
@@ -672,6 +729,11 @@ impl TestCase {
                 installed_app_id: Some(case_str.clone()),
                 network_seed,
                 membrane_proofs: HashMap::new(),
+                validation_queue_orders: Default::default(),
+                missed_signals_buffer_size: None,
+                zome_call_timeout_ms: None,
+                warranted_authors: None,
+                egress_policy: None,
                 #[cfg(feature = "chc")]
                 ignore_genesis_failure: false,
             })
@@ -682,3 +744,219 @@ impl TestCase {
         (installed_hash, case)
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn host_fn_allowlist_is_enforced() {
+    holochain_trace::test_run();
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let path = PathBuf::from(format!("{}", dna.dna_hash()));
+    let zome_name = TestWasm::Create.coordinator_zome_name();
+
+    // - The zome may write to its chain, as its init does, but not read from the DHT.
+    let host_fns = HostFnAllowlist([(zome_name.clone(), [HostFnClass::ChainWrites].into())].into());
+    let roles = vec![AppRoleManifest {
+        name: "name".into(),
+        dna: AppRoleDnaManifest {
+            location: Some(DnaLocation::Bundled(path.clone())),
+            modifiers: DnaModifiersOpt::none(),
+            installed_hash: None,
+            clone_limit: 0,
+            host_fns: Some(host_fns),
+            clone_archival: None,
+        },
+        provisioning: Some(CellProvisioning::Create { deferred: false }),
+    }];
+    let manifest = AppManifestCurrentBuilder::default()
+        .name("test_app".into())
+        .description(None)
+        .roles(roles)
+        .build()
+        .unwrap();
+    let resources = vec![(path, DnaBundle::from_dna_file(dna.clone()).unwrap())];
+    let bundle = AppBundle::new(manifest.into(), resources, PathBuf::from("."))
+        .await
+        .unwrap();
+
+    conductor
+        .clone()
+        .install_app_bundle(InstallAppPayload {
+            agent_key: alice.clone(),
+            source: AppBundleSource::Bundle(bundle),
+            installed_app_id: Some("app".into()),
+            network_seed: None,
+            membrane_proofs: HashMap::new(),
+            validation_queue_orders: Default::default(),
+            missed_signals_buffer_size: None,
+            zome_call_timeout_ms: None,
+            warranted_authors: None,
+            egress_policy: None,
+            #[cfg(feature = "chc")]
+            ignore_genesis_failure: false,
+        })
+        .await
+        .unwrap();
+    conductor.enable_app("app".into()).await.unwrap();
+
+    let cell_id = CellId::new(dna.dna_hash().clone(), alice);
+    let zome = SweetZome::new(cell_id, zome_name);
+
+    let hash: ActionHash = conductor.call(&zome, "create_entry", ()).await;
+    let denied: Result<Option<Record>, _> = conductor.call_fallible(&zome, "get_entry", ()).await;
+    assert!(
+        format!("{:?}", denied.unwrap_err()).contains("cannot be called from zome"),
+        "getting an entry should have been denied"
+    );
+
+    // - Unrestricted host functions, such as `must_get_*`, can still be used.
+    let _: Record = conductor.call(&zome, "must_get_valid_record", hash).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn inactive_clone_cells_are_archived() {
+    holochain_trace::test_run();
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let path = PathBuf::from(format!("{}", dna.dna_hash()));
+    let role_name: RoleName = "name".into();
+
+    let roles = vec![AppRoleManifest {
+        name: role_name.clone(),
+        dna: AppRoleDnaManifest {
+            location: Some(DnaLocation::Bundled(path.clone())),
+            modifiers: DnaModifiersOpt::none(),
+            installed_hash: None,
+            clone_limit: 1,
+            host_fns: None,
+            clone_archival: Some(CloneArchivalPolicy {
+                archive_after_inactive_secs: 60 * 60,
+            }),
+        },
+        provisioning: Some(CellProvisioning::Create { deferred: false }),
+    }];
+    let manifest = AppManifestCurrentBuilder::default()
+        .name("test_app".into())
+        .description(None)
+        .roles(roles)
+        .build()
+        .unwrap();
+    let resources = vec![(path, DnaBundle::from_dna_file(dna.clone()).unwrap())];
+    let bundle = AppBundle::new(manifest.into(), resources, PathBuf::from("."))
+        .await
+        .unwrap();
+
+    let installed_app_id: InstalledAppId = "app".into();
+    conductor
+        .clone()
+        .install_app_bundle(InstallAppPayload {
+            agent_key: alice.clone(),
+            source: AppBundleSource::Bundle(bundle),
+            installed_app_id: Some(installed_app_id.clone()),
+            network_seed: None,
+            membrane_proofs: HashMap::new(),
+            validation_queue_orders: Default::default(),
+            missed_signals_buffer_size: None,
+            zome_call_timeout_ms: None,
+            warranted_authors: None,
+            egress_policy: None,
+            #[cfg(feature = "chc")]
+            ignore_genesis_failure: false,
+        })
+        .await
+        .unwrap();
+    conductor
+        .enable_app(installed_app_id.clone())
+        .await
+        .unwrap();
+
+    let clone = conductor
+        .create_clone_cell(
+            &installed_app_id,
+            CreateCloneCellPayload {
+                role_name: role_name.clone(),
+                modifiers: DnaModifiersOpt::none().with_network_seed("clone".to_string()),
+                membrane_proof: None,
+                name: None,
+            },
+        )
+        .await
+        .unwrap();
+    let mut signals = conductor.subscribe_to_app_signals(installed_app_id.clone());
+
+    // - The clone has just been created, so it's still active.
+    let now = Timestamp::now();
+    let archived = conductor.archive_inactive_clone_cells(now).await.unwrap();
+    assert!(archived.is_empty());
+
+    // - Once the policy's period has passed, only the clone is archived.
+    let later = (now + std::time::Duration::from_secs(2 * 60 * 60)).unwrap();
+    let archived = conductor.archive_inactive_clone_cells(later).await.unwrap();
+    assert_eq!(vec![clone.cell_id.clone()], archived);
+
+    let state = conductor.get_state_from_handle().await.unwrap();
+    let app = state.get_app(&installed_app_id).unwrap();
+    assert_eq!(0, app.clone_cells().count());
+    assert_eq!(
+        vec![&clone.cell_id],
+        app.disabled_clone_cells()
+            .map(|(_, cell_id)| cell_id)
+            .collect::<Vec<_>>()
+    );
+
+    assert_matches!(
+        signals.recv().await.unwrap(),
+        Signal::System(SystemSignal::CloneCellArchived { cell_id, clone_id, .. })
+            if cell_id == clone.cell_id && clone_id == clone.clone_id
+    );
+
+    // - Archived clones aren't archived again.
+    let archived = conductor.archive_inactive_clone_cells(later).await.unwrap();
+    assert!(archived.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn uninstalling_the_last_app_of_a_dna_forgets_its_validation_queue_order() {
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let dna_hash = dna.dna_hash().clone();
+    conductor.setup_app("app_1", [&dna]).await.unwrap();
+    conductor.setup_app("app_2", [&dna]).await.unwrap();
+    conductor
+        .update_state({
+            let dna_hash = dna_hash.clone();
+            move |mut state| {
+                state.set_validation_queue_order(dna_hash, ValidationQueueOrder::NewestFirst);
+                Ok(state)
+            }
+        })
+        .await
+        .unwrap();
+
+    // - The DNA is still used by the other app, so its order is kept.
+    conductor
+        .clone()
+        .uninstall_app(&"app_1".to_string())
+        .await
+        .unwrap();
+    let state = conductor.get_state_from_handle().await.unwrap();
+    assert_eq!(
+        ValidationQueueOrder::NewestFirst,
+        state.validation_queue_order(&dna_hash)
+    );
+
+    // - Once the last app using the DNA is gone, so is its order.
+    conductor
+        .clone()
+        .uninstall_app(&"app_2".to_string())
+        .await
+        .unwrap();
+    let state = conductor.get_state_from_handle().await.unwrap();
+    assert_eq!(
+        ValidationQueueOrder::OpType,
+        state.validation_queue_order(&dna_hash)
+    );
+}
@@ -1,6 +1,15 @@
 mod app_info;
+mod app_network_mode;
 mod cell_cloning;
+mod egress_policy;
+mod get_entries_by_author;
 mod install_app_bundle;
+mod neighborhood;
 mod network_info;
+mod nonce_audit;
+mod probe_basis;
+#[cfg(feature = "slow_tests")]
+mod remote_signal_queue;
 mod request_dna_def;
 mod signed_zome_call;
+mod validation_limbo;
@@ -0,0 +1,49 @@
+use holochain_types::prelude::InstalledAppId;
+use holochain_wasm_test_utils::TestWasm;
+use matches::assert_matches;
+
+use crate::conductor::error::ConductorError;
+use crate::sweettest::*;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn neighborhood() {
+    holochain_trace::test_run();
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let config = SweetConductorConfig::standard();
+    let mut conductors = SweetConductorBatch::from_config(2, config).await;
+    let app_id: InstalledAppId = "app".into();
+    let app_batch = conductors.setup_app(&app_id, &[dna.clone()]).await.unwrap();
+    let cells = app_batch.cells_flattened();
+
+    conductors.exchange_peer_info().await;
+
+    // Both agents have full arcs, so each is the other's neighbor.
+    let neighborhood = conductors[0]
+        .neighborhood(&app_id, cells[0].cell_id())
+        .await
+        .unwrap();
+    assert_eq!(
+        neighborhood.agent_location,
+        cells[0].agent_pubkey().get_loc().as_u32()
+    );
+    assert_eq!(neighborhood.arc_size, 1.0);
+    assert_eq!(
+        neighborhood
+            .neighbors
+            .iter()
+            .map(|n| n.agent.clone())
+            .collect::<Vec<_>>(),
+        vec![cells[1].agent_pubkey().clone()]
+    );
+    assert_eq!(neighborhood.coverage.known_peers, 1);
+    assert_eq!(neighborhood.coverage.live_neighbors, 1);
+    assert!(neighborhood.coverage.self_covered);
+
+    assert_matches!(
+        conductors[0]
+            .neighborhood(&"other app".to_string(), cells[0].cell_id())
+            .await,
+        Err(ConductorError::AppAccessError(_, _))
+    );
+}
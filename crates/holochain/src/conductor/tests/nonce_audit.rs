@@ -0,0 +1,109 @@
+use ::fixt::prelude::*;
+use holochain_conductor_api::NonceWindow;
+use holochain_nonce::fresh_nonce;
+use holochain_state::nonce::WitnessNonceResult;
+use holochain_types::prelude::*;
+
+use crate::fixt::AgentPubKeyFixturator;
+use crate::sweettest::*;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn nonce_audit_lists_windows_and_rejected_replays() {
+    holochain_trace::test_run();
+
+    let conductor = SweetConductor::from_standard_config().await;
+    let since = Timestamp::now();
+    let alice = fixt!(AgentPubKey, Predictable, 0);
+    let bob = fixt!(AgentPubKey, Predictable, 1);
+    let (nonce, expires) = fresh_nonce(Timestamp::now()).unwrap();
+
+    for agent in [&alice, &bob] {
+        assert_eq!(
+            WitnessNonceResult::Fresh,
+            conductor
+                .witness_nonce_from_calling_agent(agent.clone(), nonce, expires)
+                .await
+                .unwrap()
+        );
+    }
+    // Alice's call is replayed, again and again.
+    for _ in 0..3 {
+        assert_eq!(
+            WitnessNonceResult::Duplicate,
+            conductor
+                .witness_nonce_from_calling_agent(alice.clone(), nonce, expires)
+                .await
+                .unwrap()
+        );
+    }
+
+    let audit = conductor
+        .nonce_audit(Some(alice.clone()), since)
+        .await
+        .unwrap();
+    assert!(audit.persistent);
+    assert_eq!(
+        audit.windows,
+        vec![NonceWindow {
+            agent: alice.clone(),
+            expires,
+        }]
+    );
+    // Only the first replay is recorded straight away. The others are counted
+    // in the next record, a minute later.
+    assert_eq!(audit.rejected.len(), 1);
+    assert_eq!(audit.rejected[0].agent, alice);
+    assert_eq!(audit.rejected[0].reason, NonceRejection::Duplicate);
+    assert_eq!(audit.rejected[0].count, 1);
+
+    // Bob's nonce wasn't replayed.
+    let audit = conductor.nonce_audit(Some(bob), since).await.unwrap();
+    assert_eq!(audit.windows.len(), 1);
+    assert!(audit.rejected.is_empty());
+
+    let audit = conductor.nonce_audit(None, since).await.unwrap();
+    assert_eq!(audit.windows.len(), 2);
+    assert_eq!(audit.rejected.len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn nonce_windows_are_forgotten_on_restart_unless_persisted() {
+    holochain_trace::test_run();
+
+    for persist in [true, false] {
+        let mut conductor = SweetConductor::from_config(
+            SweetConductorConfig::standard()
+                .tune_conductor(|params| params.persist_nonce_windows = Some(persist)),
+        )
+        .await;
+        let agent = fixt!(AgentPubKey);
+        let (nonce, expires) = fresh_nonce(Timestamp::now()).unwrap();
+        conductor
+            .witness_nonce_from_calling_agent(agent.clone(), nonce, expires)
+            .await
+            .unwrap();
+
+        conductor.shutdown().await;
+        conductor.startup().await;
+
+        let audit = conductor
+            .nonce_audit(Some(agent.clone()), Timestamp::now())
+            .await
+            .unwrap();
+        assert_eq!(audit.persistent, persist);
+        assert_eq!(audit.windows.len(), persist as usize);
+
+        let expected = if persist {
+            WitnessNonceResult::Duplicate
+        } else {
+            WitnessNonceResult::Fresh
+        };
+        assert_eq!(
+            expected,
+            conductor
+                .witness_nonce_from_calling_agent(agent, nonce, expires)
+                .await
+                .unwrap()
+        );
+    }
+}
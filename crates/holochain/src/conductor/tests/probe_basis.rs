@@ -0,0 +1,43 @@
+use holo_hash::ActionHash;
+use holochain_types::prelude::InstalledAppId;
+use holochain_wasm_test_utils::TestWasm;
+
+use crate::sweettest::*;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn probe_basis() {
+    holochain_trace::test_run();
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let config = SweetConductorConfig::standard();
+    let mut conductors = SweetConductorBatch::from_config(2, config).await;
+    let app_id: InstalledAppId = "app".into();
+    let app_batch = conductors.setup_app(&app_id, &[dna.clone()]).await.unwrap();
+    let cells = app_batch.cells_flattened();
+
+    conductors.exchange_peer_info().await;
+
+    let zome = SweetZome::new(
+        cells[0].cell_id().clone(),
+        TestWasm::Create.coordinator_zome_name(),
+    );
+    let action_hash: ActionHash = conductors[0].call(&zome, "create_entry", ()).await;
+
+    await_consistency(10, &cells).await.unwrap();
+
+    // Both agents have full arcs, so both are authorities for any basis.
+    let probes = conductors[0]
+        .probe_basis(dna.dna_hash(), action_hash.into())
+        .await
+        .unwrap();
+    assert_eq!(probes.len(), 2);
+    for cell in &cells {
+        let probe = probes
+            .iter()
+            .find(|probe| &probe.agent == cell.agent_pubkey())
+            .unwrap();
+        assert!(probe.error.is_none(), "{:?}", probe.error);
+        assert!(probe.round_trip_ms.is_some());
+        assert!(probe.op_count.unwrap() > 0);
+    }
+}
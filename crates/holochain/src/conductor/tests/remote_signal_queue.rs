@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use holochain_types::prelude::*;
+use holochain_wasm_test_utils::TestWasm;
+
+use crate::sweettest::*;
+
+/// A signal to an agent which isn't online yet is held by the sending conductor,
+/// across a restart, and delivered once the agent comes online.
+#[tokio::test(flavor = "multi_thread")]
+async fn remote_signal_to_an_offline_agent_is_delivered_when_it_comes_online() {
+    holochain_trace::test_run();
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::EmitSignal]).await;
+    let mut conductors =
+        SweetConductorBatch::from_config_rendezvous(2, SweetConductorConfig::rendezvous(true))
+            .await;
+    let (alice,) = conductors[0]
+        .setup_app("app", &[dna.clone()])
+        .await
+        .unwrap()
+        .into_tuple();
+    // Bob hasn't joined the network, so alice's conductor has no agent info for him.
+    let bob = SweetAgents::one(conductors[1].keystore()).await;
+
+    let _: () = conductors[0]
+        .call(
+            &alice.zome(TestWasm::EmitSignal),
+            "signal_others",
+            RemoteSignal {
+                agents: vec![bob.clone()],
+                signal: ExternIO::encode("held".to_string()).unwrap(),
+            },
+        )
+        .await;
+
+    // The signal is persisted, so it's still held after a restart.
+    conductors[0].shutdown().await;
+    conductors[0].startup().await;
+
+    let mut signals = conductors[1].subscribe_to_app_signals("app".to_string());
+    conductors[1]
+        .setup_app_for_agent("app", bob.clone(), &[dna])
+        .await
+        .unwrap();
+
+    let signal = tokio::time::timeout(Duration::from_secs(60), signals.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    match signal {
+        Signal::App {
+            cell_id, signal, ..
+        } => {
+            assert_eq!(&bob, cell_id.agent_pubkey());
+            assert_eq!("held", signal.into_inner().decode::<String>().unwrap());
+        }
+        signal => panic!("Expected an app signal, got {signal:?}"),
+    }
+}
@@ -129,6 +129,7 @@ async fn signed_zome_call() {
                     payload: ExternIO::encode(()).unwrap(),
                     nonce: Nonce256Bits::from([0; 32]),
                     expires_at: Timestamp(Timestamp::now().as_micros() + 100000),
+                    wait_for_publish: None,
                 },
             )
             .await
@@ -154,6 +155,7 @@ async fn signed_zome_call() {
                     payload: ExternIO::encode(()).unwrap(),
                     nonce,
                     expires_at,
+                    wait_for_publish: None,
                 },
             )
             .await
@@ -264,6 +266,7 @@ async fn signed_zome_call_wildcard() {
                     payload: ExternIO::encode(()).unwrap(),
                     nonce,
                     expires_at,
+                    wait_for_publish: None,
                 },
             )
             .await
@@ -0,0 +1,80 @@
+use ::fixt::prelude::*;
+use holo_hash::fixt::*;
+use holochain_sqlite::prelude::DatabaseResult;
+use holochain_types::prelude::*;
+use holochain_wasm_test_utils::TestWasm;
+
+use crate::sweettest::*;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn validation_limbo_is_empty_once_ops_are_integrated() {
+    holochain_trace::test_run();
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+
+    await_consistency(10, [&cell]).await.unwrap();
+
+    let limbo = conductor
+        .list_validation_limbo(dna.dna_hash())
+        .await
+        .unwrap();
+    assert!(limbo.is_empty(), "{:?}", limbo);
+
+    // Ops which aren't in limbo are not retried.
+    let retried = conductor
+        .retry_validation(dna.dna_hash(), vec![fixt!(DhtOpHash)])
+        .await
+        .unwrap();
+    assert!(retried.is_empty());
+
+    assert!(conductor
+        .list_validation_limbo(&fixt!(DnaHash))
+        .await
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn op_dependency_graph_ends_at_integrated_ops() {
+    holochain_trace::test_run();
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+
+    await_consistency(10, [&cell]).await.unwrap();
+
+    let mut op_hashes = cell
+        .dht_db()
+        .read_async(|txn| -> DatabaseResult<Vec<DhtOpHash>> {
+            let mut stmt = txn.prepare("SELECT hash FROM DhtOp")?;
+            let hashes = stmt
+                .query_map([], |row| row.get("hash"))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(hashes)
+        })
+        .await
+        .unwrap();
+    assert!(!op_hashes.is_empty());
+    let missing = fixt!(DhtOpHash);
+    op_hashes.push(missing.clone());
+
+    let graph = conductor
+        .op_dependency_graph(dna.dna_hash(), op_hashes.clone())
+        .await
+        .unwrap();
+    assert!(!graph.truncated);
+    // Integrated ops aren't waiting for anything.
+    assert!(graph.edges.is_empty(), "{:?}", graph.edges);
+    assert_eq!(graph.nodes.len(), op_hashes.len());
+    for node in graph.nodes {
+        match node {
+            OpDependencyNode::Op { stage, .. } => assert_eq!(stage, None),
+            OpDependencyNode::MissingOp { op_hash } => assert_eq!(op_hash, missing),
+            other => panic!("unexpected node {:?}", other),
+        }
+    }
+}
@@ -9,7 +9,7 @@ mod validation;
 #[allow(missing_docs)]
 pub mod workflow;
 
-mod metrics;
+pub(crate) mod metrics;
 mod sys_validate;
 
 pub use sys_validate::*;
@@ -1,7 +1,16 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use holo_hash::{AgentPubKey, DnaHash};
+use holochain_conductor_api::{ConductorMetrics, WorkflowQueueStats};
+use holochain_types::prelude::{
+    AppEntryDef, EntryDefIndex, EntryTypeMetrics, Timestamp, ZomeIndex,
+};
+use once_cell::sync::Lazy;
 use opentelemetry_api::{global::meter_with_version, metrics::*, KeyValue};
+use parking_lot::Mutex;
 
 pub type WorkflowDurationMetric = Histogram<f64>;
 
@@ -30,3 +39,595 @@ pub fn create_workflow_duration_metric(
     .with_description("The time spent running a workflow")
     .init()
 }
+
+/// The work queues which are instrumented with depth and lag metrics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum WorkflowQueueKind {
+    SysValidation,
+    AppValidation,
+    Integration,
+    Publish,
+}
+
+impl WorkflowQueueKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            WorkflowQueueKind::SysValidation => "sys_validation",
+            WorkflowQueueKind::AppValidation => "app_validation",
+            WorkflowQueueKind::Integration => "integration",
+            WorkflowQueueKind::Publish => "publish",
+        }
+    }
+}
+
+/// The number of ops in a queue, and when the oldest of them was received.
+///
+/// Authored ops are received when they're authored, and ops stored before the
+/// receive time was recorded count from when they were authored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct QueueSample {
+    pub depth: u64,
+    pub oldest: Option<Timestamp>,
+}
+
+impl QueueSample {
+    /// The time since the oldest op in the queue was received.
+    pub fn oldest_item_age(&self, now: Timestamp) -> Option<Duration> {
+        self.oldest.map(|oldest| {
+            Duration::from_micros(now.as_micros().saturating_sub(oldest.as_micros()).max(0) as u64)
+        })
+    }
+}
+
+/// Identifies one queue: queues are per DNA, except for publishing which is per cell.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct WorkflowQueueKey {
+    pub dna_hash: Arc<DnaHash>,
+    pub agent: Option<AgentPubKey>,
+    pub kind: WorkflowQueueKind,
+}
+
+impl WorkflowQueueKey {
+    fn attributes(&self) -> Vec<KeyValue> {
+        let mut attr = vec![
+            KeyValue::new("workflow", self.kind.as_str()),
+            KeyValue::new("dna_hash", format!("{:?}", self.dna_hash)),
+        ];
+        if let Some(agent) = &self.agent {
+            attr.push(KeyValue::new("agent", format!("{:?}", agent)));
+        }
+        attr
+    }
+}
+
+#[derive(Default)]
+struct WorkflowQueueRecord {
+    sample: QueueSample,
+    runs: u64,
+    last_run: Option<Duration>,
+    total_run: Duration,
+    /// When the workflow started the run of back to back runs it's still working through.
+    busy_since: Option<Instant>,
+}
+
+/// The latest sample and the run durations of every instrumented queue of a
+/// conductor, observed by the queue gauges, by admin workflow stats snapshots and
+/// by load shedding.
+///
+/// Each conductor has its own registry, so that conductors running in the same
+/// process, which may share DNAs, don't see each other's queues.
+pub(crate) struct WorkflowQueueRegistry {
+    queues: Arc<Mutex<HashMap<WorkflowQueueKey, WorkflowQueueRecord>>>,
+    _depth_gauge: ObservableGauge<u64>,
+    _oldest_item_age_gauge: ObservableGauge<f64>,
+}
+
+impl WorkflowQueueRegistry {
+    pub fn new() -> Self {
+        let queues: Arc<Mutex<HashMap<WorkflowQueueKey, WorkflowQueueRecord>>> = Default::default();
+        let meter = meter_with_version(
+            "hc.conductor",
+            None::<&'static str>,
+            None::<&'static str>,
+            Some(vec![]),
+        );
+        let depth_gauge = meter
+            .u64_observable_gauge("hc.conductor.workflow.queue_depth")
+            .with_description("The number of ops waiting in the queue of a workflow")
+            .with_callback({
+                let queues = queues.clone();
+                move |gauge| {
+                    for (key, record) in queues.lock().iter() {
+                        gauge.observe(record.sample.depth, &key.attributes());
+                    }
+                }
+            })
+            .init();
+        // The age is worked out when the gauge is observed rather than when the queue is
+        // sampled, so that it keeps growing while a stalled workflow isn't running.
+        let oldest_item_age_gauge = meter
+            .f64_observable_gauge("hc.conductor.workflow.oldest_item_age")
+            .with_unit(Unit::new("s"))
+            .with_description(
+                "The time since the oldest op in the queue of a workflow was received",
+            )
+            .with_callback({
+                let queues = queues.clone();
+                move |gauge| {
+                    let now = Timestamp::now();
+                    for (key, record) in queues.lock().iter() {
+                        let age = record.sample.oldest_item_age(now).unwrap_or_default();
+                        gauge.observe(age.as_secs_f64(), &key.attributes());
+                    }
+                }
+            })
+            .init();
+        Self {
+            queues,
+            _depth_gauge: depth_gauge,
+            _oldest_item_age_gauge: oldest_item_age_gauge,
+        }
+    }
+
+    /// Record that the workflow which consumes a queue started a run.
+    pub fn record_run_started(&self, key: &WorkflowQueueKey) {
+        self.queues
+            .lock()
+            .entry(key.clone())
+            .or_default()
+            .busy_since
+            .get_or_insert_with(Instant::now);
+    }
+
+    /// Record that the workflow which consumes a queue finished a run without having to
+    /// run again straight away, either because it got through its queue or because it
+    /// is waiting for something other than itself.
+    pub fn record_caught_up(&self, key: &WorkflowQueueKey) {
+        if let Some(record) = self.queues.lock().get_mut(key) {
+            record.busy_since = None;
+        }
+    }
+
+    /// How long the most lagging validation or integration workflow of any DNA of the
+    /// conductor has been working without catching up with its queue.
+    ///
+    /// This is measured from when the workflow started working rather than from when the
+    /// oldest op in its queue was authored, so that neither old data arriving by gossip
+    /// nor ops waiting for missing dependencies count as lag.
+    pub fn validation_lag(&self) -> Duration {
+        let now = Instant::now();
+        self.queues
+            .lock()
+            .iter()
+            .filter(|(key, _)| key.kind != WorkflowQueueKind::Publish)
+            .filter_map(|(_, record)| record.busy_since)
+            .map(|since| now.saturating_duration_since(since))
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Record a run of the workflow which consumes a queue.
+    pub fn record_run(&self, key: &WorkflowQueueKey, duration: Duration) {
+        let mut queues = self.queues.lock();
+        let record = queues.entry(key.clone()).or_default();
+        record.runs += 1;
+        record.last_run = Some(duration);
+        record.total_run += duration;
+    }
+
+    /// Forget a queue whose consumer has stopped, so that it's no longer reported.
+    pub fn remove_queue(&self, key: &WorkflowQueueKey) {
+        self.queues.lock().remove(key);
+    }
+
+    /// Record a fresh sample of a queue.
+    pub fn record_sample(&self, key: &WorkflowQueueKey, sample: QueueSample) {
+        self.queues.lock().entry(key.clone()).or_default().sample = sample;
+    }
+
+    /// Combine freshly sampled queues with the recorded runs of the workflows which
+    /// consume them. The runs of all the cells of the DNA are added together.
+    pub fn stats(
+        &self,
+        dna_hash: &DnaHash,
+        kind: WorkflowQueueKind,
+        sample: QueueSample,
+    ) -> WorkflowQueueStats {
+        let queues = self.queues.lock();
+        let records = queues
+            .iter()
+            .filter(|(key, _)| key.kind == kind && *key.dna_hash == *dna_hash)
+            .map(|(_, record)| record);
+        let mut runs = 0;
+        let mut total_run = Duration::ZERO;
+        let mut last_run = None;
+        for record in records {
+            runs += record.runs;
+            total_run += record.total_run;
+            last_run = last_run.max(record.last_run);
+        }
+        WorkflowQueueStats {
+            depth: sample.depth,
+            oldest_item_age_ms: sample
+                .oldest_item_age(Timestamp::now())
+                .map(|age| age.as_millis() as u64),
+            runs,
+            last_run_duration_ms: last_run.map(|run| run.as_millis() as u64),
+            mean_run_duration_ms: (runs > 0).then(|| (total_run.as_millis() / runs as u128) as u64),
+        }
+    }
+}
+
+/// Process-wide throughput counters, sampled by admin metrics subscriptions.
+pub(crate) static THROUGHPUT: ThroughputCounters = ThroughputCounters {
+    ops_received: AtomicU64::new(0),
+    ops_validated: AtomicU64::new(0),
+    ops_integrated: AtomicU64::new(0),
+    zome_calls: AtomicU64::new(0),
+};
+
+/// Monotonic counters of work done by this conductor.
+pub(crate) struct ThroughputCounters {
+    ops_received: AtomicU64,
+    ops_validated: AtomicU64,
+    ops_integrated: AtomicU64,
+    zome_calls: AtomicU64,
+}
+
+impl ThroughputCounters {
+    /// Record ops received from the network and queued for validation.
+    pub fn record_ops_received(&self, n: usize) {
+        self.ops_received.fetch_add(n as u64, Ordering::Relaxed);
+    }
+
+    /// Record ops which finished app validation.
+    pub fn record_ops_validated(&self, n: usize) {
+        self.ops_validated.fetch_add(n as u64, Ordering::Relaxed);
+    }
+
+    /// Record ops integrated into the DHT database.
+    pub fn record_ops_integrated(&self, n: usize) {
+        self.ops_integrated.fetch_add(n as u64, Ordering::Relaxed);
+    }
+
+    /// Record a zome call.
+    pub fn record_zome_call(&self) {
+        self.zome_calls.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Take a snapshot of the current counter values.
+    pub fn snapshot(&self) -> ThroughputSnapshot {
+        let (gossip_bytes_in, gossip_bytes_out) =
+            kitsune_p2p::gossip::sharded_gossip::gossip_bytes_total();
+        ThroughputSnapshot {
+            at: Instant::now(),
+            ops_received: self.ops_received.load(Ordering::Relaxed),
+            ops_validated: self.ops_validated.load(Ordering::Relaxed),
+            ops_integrated: self.ops_integrated.load(Ordering::Relaxed),
+            zome_calls: self.zome_calls.load(Ordering::Relaxed),
+            gossip_bytes_in,
+            gossip_bytes_out,
+        }
+    }
+}
+
+/// The values of the [`ThroughputCounters`] at a point in time.
+#[derive(Clone, Debug)]
+pub(crate) struct ThroughputSnapshot {
+    at: Instant,
+    ops_received: u64,
+    ops_validated: u64,
+    ops_integrated: u64,
+    zome_calls: u64,
+    gossip_bytes_in: u64,
+    gossip_bytes_out: u64,
+}
+
+impl ThroughputSnapshot {
+    /// The per second rates between an earlier snapshot and this one.
+    pub fn rates_since(&self, earlier: &ThroughputSnapshot) -> ConductorMetrics {
+        let elapsed = self.at.saturating_duration_since(earlier.at);
+        let secs = elapsed.as_secs_f64();
+        let rate = |now: u64, then: u64| {
+            if secs > 0.0 {
+                now.saturating_sub(then) as f64 / secs
+            } else {
+                0.0
+            }
+        };
+        ConductorMetrics {
+            interval_ms: elapsed.as_millis() as u64,
+            ops_received_per_second: rate(self.ops_received, earlier.ops_received),
+            ops_validated_per_second: rate(self.ops_validated, earlier.ops_validated),
+            ops_integrated_per_second: rate(self.ops_integrated, earlier.ops_integrated),
+            gossip_bytes_in_per_second: rate(self.gossip_bytes_in, earlier.gossip_bytes_in),
+            gossip_bytes_out_per_second: rate(self.gossip_bytes_out, earlier.gossip_bytes_out),
+            zome_calls_per_second: rate(self.zome_calls, earlier.zome_calls),
+        }
+    }
+}
+
+/// Counts of the actions on each app entry type which passed or failed app
+/// validation, observed by the entry type counter and read by zomes.
+///
+/// The counts are global to the process, so every conductor in it adds to the
+/// same counts per DNA, and they're only held in memory, so they start again from
+/// zero when the process restarts. Only store record ops are counted, so only the
+/// actions in the storage arc of an agent on this node are seen, not every action
+/// on the DHT.
+pub(crate) static ENTRY_TYPE_COUNTERS: Lazy<EntryTypeCounters> = Lazy::new(EntryTypeCounters::new);
+
+/// What happened to an action on an app entry type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EntryTypeEvent {
+    Create,
+    Update,
+    Delete,
+    ValidationFailure,
+}
+
+impl EntryTypeEvent {
+    fn as_str(&self) -> &'static str {
+        match self {
+            EntryTypeEvent::Create => "create",
+            EntryTypeEvent::Update => "update",
+            EntryTypeEvent::Delete => "delete",
+            EntryTypeEvent::ValidationFailure => "validation_failure",
+        }
+    }
+}
+
+/// The counts of actions on app entry types per DNA.
+pub(crate) struct EntryTypeCounters {
+    counts: Mutex<HashMap<(DnaHash, ZomeIndex, EntryDefIndex), EntryTypeMetrics>>,
+    counter: Counter<u64>,
+}
+
+impl EntryTypeCounters {
+    fn new() -> Self {
+        let counter = meter_with_version(
+            "hc.conductor",
+            None::<&'static str>,
+            None::<&'static str>,
+            Some(vec![]),
+        )
+        .u64_counter("hc.conductor.entry_type.actions")
+        .with_description(
+            "The number of actions on an app entry type which passed or failed app validation",
+        )
+        .init();
+        Self {
+            counts: Default::default(),
+            counter,
+        }
+    }
+
+    /// Record an action on an app entry type of a DNA.
+    pub fn record(&self, dna_hash: &DnaHash, entry_def: &AppEntryDef, event: EntryTypeEvent) {
+        let zome_index = entry_def.zome_index();
+        let entry_index = entry_def.entry_index();
+        self.counter.add(
+            1,
+            &[
+                KeyValue::new("dna_hash", format!("{:?}", dna_hash)),
+                KeyValue::new("zome_index", zome_index.0 as i64),
+                KeyValue::new("entry_index", entry_index.0 as i64),
+                KeyValue::new("event", event.as_str()),
+            ],
+        );
+        let mut counts = self.counts.lock();
+        let key = (dna_hash.clone(), zome_index, entry_index);
+        let metrics = counts.entry(key).or_insert_with(|| EntryTypeMetrics {
+            zome_index,
+            entry_index,
+            creates: 0,
+            updates: 0,
+            deletes: 0,
+            validation_failures: 0,
+        });
+        match event {
+            EntryTypeEvent::Create => metrics.creates += 1,
+            EntryTypeEvent::Update => metrics.updates += 1,
+            EntryTypeEvent::Delete => metrics.deletes += 1,
+            EntryTypeEvent::ValidationFailure => metrics.validation_failures += 1,
+        }
+    }
+
+    /// The counts for every entry type of a DNA which has had any actions, ordered by
+    /// zome and entry index.
+    pub fn metrics(&self, dna_hash: &DnaHash) -> Vec<EntryTypeMetrics> {
+        let mut metrics: Vec<_> = self
+            .counts
+            .lock()
+            .iter()
+            .filter(|((hash, _, _), _)| hash == dna_hash)
+            .map(|(_, metrics)| metrics.clone())
+            .collect();
+        metrics.sort_by_key(|m| (m.zome_index, m.entry_index));
+        metrics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_types::prelude::EntryVisibility;
+    use std::time::Duration;
+
+    #[test]
+    fn rates_are_per_second() {
+        let earlier = THROUGHPUT.snapshot();
+        let mut later = earlier.clone();
+        later.at += Duration::from_millis(500);
+        later.ops_received += 10;
+        later.gossip_bytes_out += 2_000;
+
+        let metrics = later.rates_since(&earlier);
+        assert_eq!(500, metrics.interval_ms);
+        assert_eq!(20.0, metrics.ops_received_per_second);
+        assert_eq!(4_000.0, metrics.gossip_bytes_out_per_second);
+        assert_eq!(0.0, metrics.zome_calls_per_second);
+
+        // A zero length interval doesn't divide by zero.
+        assert_eq!(0.0, earlier.rates_since(&earlier).ops_received_per_second);
+    }
+
+    #[test]
+    fn workflow_stats_combine_the_runs_of_all_cells() {
+        let registry = WorkflowQueueRegistry::new();
+        let dna_hash = Arc::new(DnaHash::from_raw_36(vec![0; 36]));
+        let key = |agent: u8| WorkflowQueueKey {
+            dna_hash: dna_hash.clone(),
+            agent: Some(AgentPubKey::from_raw_36(vec![agent; 36])),
+            kind: WorkflowQueueKind::Publish,
+        };
+        registry.record_run(&key(1), Duration::from_millis(10));
+        registry.record_run(&key(2), Duration::from_millis(30));
+        // Runs of other queues are not included.
+        registry.record_run(
+            &WorkflowQueueKey {
+                kind: WorkflowQueueKind::Integration,
+                ..key(1)
+            },
+            Duration::from_millis(500),
+        );
+
+        let oldest = (Timestamp::now() - Duration::from_secs(2)).unwrap();
+        let stats = registry.stats(
+            &dna_hash,
+            WorkflowQueueKind::Publish,
+            QueueSample {
+                depth: 3,
+                oldest: Some(oldest),
+            },
+        );
+        assert_eq!(3, stats.depth);
+        assert!(stats.oldest_item_age_ms.unwrap() >= 2_000);
+        assert_eq!(2, stats.runs);
+        assert_eq!(Some(30), stats.last_run_duration_ms);
+        assert_eq!(Some(20), stats.mean_run_duration_ms);
+
+        let empty = registry.stats(
+            &dna_hash,
+            WorkflowQueueKind::SysValidation,
+            QueueSample::default(),
+        );
+        assert_eq!(None, empty.oldest_item_age_ms);
+        assert_eq!(None, empty.mean_run_duration_ms);
+    }
+
+    #[test]
+    fn removed_queues_are_no_longer_reported() {
+        let registry = WorkflowQueueRegistry::new();
+        let dna_hash = Arc::new(DnaHash::from_raw_36(vec![0; 36]));
+        let key = WorkflowQueueKey {
+            dna_hash: dna_hash.clone(),
+            agent: None,
+            kind: WorkflowQueueKind::SysValidation,
+        };
+        registry.record_run_started(&key);
+        registry.record_run(&key, Duration::from_millis(10));
+
+        registry.remove_queue(&key);
+        assert_eq!(Duration::ZERO, registry.validation_lag());
+        let stats = registry.stats(
+            &dna_hash,
+            WorkflowQueueKind::SysValidation,
+            QueueSample::default(),
+        );
+        assert_eq!(0, stats.runs);
+        assert!(registry.queues.lock().is_empty());
+    }
+
+    #[test]
+    fn validation_lag_lasts_until_the_workflow_catches_up() {
+        let registry = WorkflowQueueRegistry::new();
+        let key = |kind| WorkflowQueueKey {
+            dna_hash: Arc::new(DnaHash::from_raw_36(vec![0; 36])),
+            agent: None,
+            kind,
+        };
+        let sys_validation = key(WorkflowQueueKind::SysValidation);
+        assert_eq!(Duration::ZERO, registry.validation_lag());
+
+        // A busy publish workflow isn't validation lag.
+        registry.record_run_started(&key(WorkflowQueueKind::Publish));
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(Duration::ZERO, registry.validation_lag());
+
+        registry.record_run_started(&sys_validation);
+        std::thread::sleep(Duration::from_millis(10));
+        let lag = registry.validation_lag();
+        assert!(lag >= Duration::from_millis(10));
+
+        // Runs which follow on straight away don't reset the lag.
+        registry.record_run(&sys_validation, lag);
+        registry.record_run_started(&sys_validation);
+        assert!(registry.validation_lag() >= lag);
+
+        registry.record_caught_up(&sys_validation);
+        assert_eq!(Duration::ZERO, registry.validation_lag());
+    }
+
+    #[test]
+    fn entry_type_counters_are_per_dna_and_entry_type() {
+        let counters = EntryTypeCounters::new();
+        let dna_hash = DnaHash::from_raw_36(vec![0; 36]);
+        let other_dna_hash = DnaHash::from_raw_36(vec![1; 36]);
+        let entry_def = |zome: u8, entry: u8, visibility| {
+            AppEntryDef::new(entry.into(), zome.into(), visibility)
+        };
+        counters.record(
+            &dna_hash,
+            &entry_def(1, 0, EntryVisibility::Public),
+            EntryTypeEvent::Create,
+        );
+        counters.record(
+            &dna_hash,
+            &entry_def(0, 2, EntryVisibility::Private),
+            EntryTypeEvent::Create,
+        );
+        counters.record(
+            &dna_hash,
+            &entry_def(0, 2, EntryVisibility::Public),
+            EntryTypeEvent::Update,
+        );
+        counters.record(
+            &dna_hash,
+            &entry_def(0, 2, EntryVisibility::Private),
+            EntryTypeEvent::ValidationFailure,
+        );
+        counters.record(
+            &other_dna_hash,
+            &entry_def(0, 2, EntryVisibility::Private),
+            EntryTypeEvent::Delete,
+        );
+
+        let metrics = counters.metrics(&dna_hash);
+        assert_eq!(
+            metrics,
+            vec![
+                EntryTypeMetrics {
+                    zome_index: 0.into(),
+                    entry_index: 2.into(),
+                    creates: 1,
+                    updates: 1,
+                    deletes: 0,
+                    validation_failures: 1,
+                },
+                EntryTypeMetrics {
+                    zome_index: 1.into(),
+                    entry_index: 0.into(),
+                    creates: 1,
+                    updates: 0,
+                    deletes: 0,
+                    validation_failures: 0,
+                },
+            ]
+        );
+        assert_eq!(counters.metrics(&other_dna_hash)[0].deletes, 1);
+        assert!(counters
+            .metrics(&DnaHash::from_raw_36(vec![2; 36]))
+            .is_empty());
+    }
+}
@@ -55,7 +55,7 @@ use crate::conductor::ConductorHandle;
 use crate::conductor::{error::ConductorError, manager::ManagedTaskResult};
 use holochain_p2p::HolochainP2pDna;
 use holochain_p2p::*;
-use holochain_sqlite::prelude::DatabaseResult;
+use holochain_sqlite::prelude::{DatabaseResult, DbKindAuthored, DbKindDht, DbKindT, DbRead};
 use publish_dht_ops_consumer::*;
 
 mod countersigning_consumer;
@@ -65,6 +65,7 @@ use countersigning_consumer::*;
 mod tests;
 
 use super::metrics::create_workflow_duration_metric;
+use super::metrics::{QueueSample, WorkflowQueueKey, WorkflowQueueKind, WorkflowQueueRegistry};
 use super::workflow::app_validation_workflow::AppValidationWorkspace;
 use super::workflow::sys_validation_workflow::SysValidationWorkspace;
 use super::workflow::{WorkflowError, WorkflowResult};
@@ -123,6 +124,8 @@ pub async fn spawn_queue_consumer_tasks(
             conductor.task_manager(),
             tx_receipt.clone(),
             network.clone(),
+            conductor.firehose().create_send_handle((*dna_hash).clone()),
+            conductor.workflow_queues(),
         )
     });
 
@@ -142,7 +145,8 @@ pub async fn spawn_queue_consumer_tasks(
                 cache.clone(),
                 keystore.clone(),
                 Arc::new(dna_def),
-            ),
+            )
+            .with_validation_queue_order(space.validation_queue_order.clone()),
             conductor.clone(),
             tx_integration.clone(),
             network.clone(),
@@ -168,7 +172,9 @@ pub async fn spawn_queue_consumer_tasks(
                     .get_config()
                     .conductor_tuning_params()
                     .sys_validation_retry_delay(),
-            ),
+            )
+            .with_validation_queue_order(space.validation_queue_order.clone())
+            .with_firehose(conductor.firehose().create_send_handle((*dna_hash).clone())),
             space.clone(),
             conductor.clone(),
             tx_app.clone(),
@@ -645,6 +651,75 @@ pub enum WorkComplete {
 #[derive(Debug, Display, thiserror::Error)]
 pub struct QueueTriggerClosedError;
 
+/// A work queue which is instrumented with depth and lag metrics.
+///
+/// The queue is sampled when its consumer starts and after every run of the consumer,
+/// and the samples and runs are recorded in the conductor's [`WorkflowQueueRegistry`].
+#[derive(Clone)]
+pub(crate) enum InstrumentedQueue {
+    /// Ops waiting for system validation.
+    SysValidation(DbRead<DbKindDht>),
+    /// Ops waiting for app validation.
+    AppValidation(DbRead<DbKindDht>),
+    /// Validated ops waiting for integration.
+    Integration(DbRead<DbKindDht>),
+    /// Authored ops which are still being published.
+    Publish(DbRead<DbKindAuthored>),
+}
+
+impl InstrumentedQueue {
+    /// The kind of queue this is.
+    pub fn kind(&self) -> WorkflowQueueKind {
+        match self {
+            InstrumentedQueue::SysValidation(_) => WorkflowQueueKind::SysValidation,
+            InstrumentedQueue::AppValidation(_) => WorkflowQueueKind::AppValidation,
+            InstrumentedQueue::Integration(_) => WorkflowQueueKind::Integration,
+            InstrumentedQueue::Publish(_) => WorkflowQueueKind::Publish,
+        }
+    }
+
+    /// Count the ops in the queue and find when the oldest of them was received.
+    pub async fn sample(&self) -> DatabaseResult<QueueSample> {
+        use holochain_sqlite::sql::sql_cell::queue_stats;
+        match self {
+            InstrumentedQueue::SysValidation(db) => {
+                sample_queue(db, queue_stats::SYS_VALIDATION).await
+            }
+            InstrumentedQueue::AppValidation(db) => {
+                sample_queue(db, queue_stats::APP_VALIDATION).await
+            }
+            InstrumentedQueue::Integration(db) => sample_queue(db, queue_stats::INTEGRATION).await,
+            InstrumentedQueue::Publish(db) => sample_queue(db, queue_stats::PUBLISH).await,
+        }
+    }
+}
+
+async fn sample_queue<Kind: DbKindT>(
+    db: &DbRead<Kind>,
+    sql: &'static str,
+) -> DatabaseResult<QueueSample> {
+    db.read_async(move |txn| {
+        Ok(txn.query_row(sql, [], |row| {
+            Ok(QueueSample {
+                depth: row.get::<_, i64>("depth")? as u64,
+                oldest: row.get("oldest")?,
+            })
+        })?)
+    })
+    .await
+}
+
+async fn record_queue_sample(
+    registry: &WorkflowQueueRegistry,
+    key: &WorkflowQueueKey,
+    queue: &InstrumentedQueue,
+) {
+    match queue.sample().await {
+        Ok(sample) => registry.record_sample(key, sample),
+        Err(err) => tracing::warn!(?err, ?key, "Failed to sample workflow queue"),
+    }
+}
+
 /// Get a stream of triggers which can be terminated by a received Stop
 pub(super) fn trigger_stream(rx: TriggerReceiver, stop: StopReceiver) -> impl Stream<Item = ()> {
     stop.fuse_with(Box::pin(futures::stream::unfold(rx, |mut rx| async move {
@@ -661,17 +736,35 @@ async fn queue_consumer_main_task_impl<
     name: String,
     dna_hash: Arc<DnaHash>,
     agent: Option<AgentPubKey>,
+    queue: Option<(InstrumentedQueue, Arc<WorkflowQueueRegistry>)>,
     (tx, rx): (TriggerSender, TriggerReceiver),
     stop: StopReceiver,
     mut fut: impl 'static + Send + FnMut() -> Fut,
 ) -> ManagedTaskResult {
     let mut triggers = trigger_stream(rx, stop);
+    let queue = queue.map(|(queue, registry)| {
+        let key = WorkflowQueueKey {
+            dna_hash: dna_hash.clone(),
+            agent: agent.clone(),
+            kind: queue.kind(),
+        };
+        (key, queue, registry)
+    });
     let duration_metric = create_workflow_duration_metric(name.clone(), dna_hash, agent);
-    loop {
+    if let Some((key, queue, registry)) = &queue {
+        record_queue_sample(registry, key, queue).await;
+    }
+    let result = loop {
         if let Some(()) = triggers.next().await {
+            if let Some((key, _, registry)) = &queue {
+                registry.record_run_started(key);
+            }
             let start = Instant::now();
+            // Whether the workflow has to run again straight away to get through its queue.
+            let mut behind = false;
             match fut().await {
                 Ok(WorkComplete::Incomplete(delay)) => {
+                    behind = delay.is_none();
                     tracing::debug!("Work incomplete, re-triggering workflow - {}.", name);
                     if let Some(dly) = delay {
                         tracing::debug!(
@@ -683,23 +776,40 @@ async fn queue_consumer_main_task_impl<
                     }
                     tx.trigger(&"retrigger")
                 }
-                Err(err) => handle_workflow_error(&name, err)?,
+                Err(err) => {
+                    if let Err(err) = handle_workflow_error(&name, err) {
+                        break Err(err);
+                    }
+                }
                 _ => (),
             }
 
-            duration_metric.record(start.elapsed().as_secs_f64(), &[]);
+            let elapsed = start.elapsed();
+            duration_metric.record(elapsed.as_secs_f64(), &[]);
+            if let Some((key, queue, registry)) = &queue {
+                registry.record_run(key, elapsed);
+                if !behind {
+                    registry.record_caught_up(key);
+                }
+                record_queue_sample(registry, key, queue).await;
+            }
         } else {
             tracing::info!("Cell is shutting down: stopping queue consumer '{}'", name);
-            break;
+            break Ok(());
         }
+    };
+    // The queue goes away with its cell or DNA, so stop reporting it.
+    if let Some((key, _, registry)) = &queue {
+        registry.remove_queue(key);
     }
-    ManagedTaskResult::Ok(())
+    result
 }
 
 fn queue_consumer_dna_bound<Fut: 'static + Send + Future<Output = WorkflowResult<WorkComplete>>>(
     name: &str,
     dna_hash: Arc<DnaHash>,
     tm: TaskManagerClient,
+    queue: Option<(InstrumentedQueue, Arc<WorkflowQueueRegistry>)>,
     (tx, rx): (TriggerSender, TriggerReceiver),
     fut: impl 'static + Send + FnMut() -> Fut,
 ) {
@@ -707,7 +817,15 @@ fn queue_consumer_dna_bound<Fut: 'static + Send + Future<Output = WorkflowResult
     let task_dna_hash = dna_hash.clone();
     tm.add_dna_task_critical(name, dna_hash, {
         move |stop| {
-            queue_consumer_main_task_impl(workflow_name, task_dna_hash, None, (tx, rx), stop, fut)
+            queue_consumer_main_task_impl(
+                workflow_name,
+                task_dna_hash,
+                None,
+                queue,
+                (tx, rx),
+                stop,
+                fut,
+            )
         }
     });
 }
@@ -718,6 +836,7 @@ fn queue_consumer_cell_bound<
     name: &str,
     cell_id: CellId,
     tm: TaskManagerClient,
+    queue: Option<(InstrumentedQueue, Arc<WorkflowQueueRegistry>)>,
     (tx, rx): (TriggerSender, TriggerReceiver),
     fut: impl 'static + Send + FnMut() -> Fut,
 ) {
@@ -730,6 +849,7 @@ fn queue_consumer_cell_bound<
                 workflow_name,
                 Arc::new(dna_hash),
                 Some(agent),
+                queue,
                 (tx, rx),
                 stop,
                 fut,
@@ -20,6 +20,7 @@ pub fn spawn_app_validation_consumer(
     dht_query_cache: DhtDbQueryCache,
 ) -> TriggerSender {
     let (tx, rx) = TriggerSender::new();
+    let queue = InstrumentedQueue::AppValidation(workspace.dht_db().clone().into());
     let workspace = Arc::new(workspace);
     let validation_dependencies = Arc::new(Mutex::new(ValidationDependencies::new()));
 
@@ -27,6 +28,7 @@ pub fn spawn_app_validation_consumer(
         "app_validation_consumer",
         dna_hash.clone(),
         conductor.task_manager(),
+        Some((queue, conductor.workflow_queues())),
         (tx.clone(), rx),
         move || {
             app_validation_workflow(
@@ -19,6 +19,7 @@ pub(crate) fn spawn_countersigning_consumer(
         "countersigning_consumer",
         space.dna_hash.clone(),
         tm,
+        None,
         (tx.clone(), rx),
         move || countersigning_workflow(space.clone(), dna_network.clone(), trigger_sys.clone()),
     );
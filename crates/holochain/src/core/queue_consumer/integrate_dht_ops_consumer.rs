@@ -1,6 +1,7 @@
 //! The workflow and queue consumer for DhtOp integration
 
 use super::*;
+use crate::conductor::conductor::firehose::FirehoseSender;
 use crate::conductor::manager::TaskManagerClient;
 use crate::core::workflow::integrate_dht_ops_workflow::integrate_dht_ops_workflow;
 use holochain_types::db_cache::DhtDbQueryCache;
@@ -8,7 +9,15 @@ use holochain_types::db_cache::DhtDbQueryCache;
 use tracing::*;
 
 /// Spawn the QueueConsumer for DhtOpIntegration workflow
-#[instrument(skip(env, trigger_receipt, tm, network, dht_query_cache))]
+#[instrument(skip(
+    env,
+    trigger_receipt,
+    tm,
+    network,
+    dht_query_cache,
+    firehose,
+    workflow_queues
+))]
 pub fn spawn_integrate_dht_ops_consumer(
     dna_hash: Arc<DnaHash>,
     env: DbWrite<DbKindDht>,
@@ -16,13 +25,17 @@ pub fn spawn_integrate_dht_ops_consumer(
     tm: TaskManagerClient,
     trigger_receipt: TriggerSender,
     network: HolochainP2pDna,
+    firehose: FirehoseSender,
+    workflow_queues: Arc<WorkflowQueueRegistry>,
 ) -> TriggerSender {
     let (tx, rx) = TriggerSender::new();
+    let queue = InstrumentedQueue::Integration(env.clone().into());
 
     super::queue_consumer_dna_bound(
         "integrate_dht_ops_consumer",
         dna_hash,
         tm,
+        Some((queue, workflow_queues)),
         (tx.clone(), rx),
         move || {
             integrate_dht_ops_workflow(
@@ -30,6 +43,7 @@ pub fn spawn_integrate_dht_ops_consumer(
                 dht_query_cache.clone(),
                 trigger_receipt.clone(),
                 network.clone(),
+                firehose.clone(),
             )
         },
     );
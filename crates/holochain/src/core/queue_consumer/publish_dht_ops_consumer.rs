@@ -19,19 +19,34 @@ pub fn spawn_publish_dht_ops_consumer(
     let (tx, rx) =
         TriggerSender::new_with_loop(Duration::from_secs(60)..Duration::from_secs(60 * 5), true);
     let sender = tx.clone();
+    let queue = InstrumentedQueue::Publish(env.clone().into());
     super::queue_consumer_cell_bound(
         "publish_dht_ops_consumer",
         cell_id.clone(),
         conductor.task_manager(),
+        Some((queue, conductor.workflow_queues())),
         (tx.clone(), rx),
         move || {
             let conductor = conductor.clone();
             let tx = tx.clone();
             let env = env.clone();
             let agent = cell_id.agent_pubkey().clone();
+            let cell_id = cell_id.clone();
             let network = network.clone();
             async move {
-                if conductor.get_config().network.tuning_params.disable_publish {
+                if conductor.get_config().network.tuning_params.disable_publish
+                    || !conductor.cell_network_mode(&cell_id).publish()
+                {
+                    return Ok(WorkComplete::Complete);
+                }
+                // A frozen cell publishes nothing until it is unfrozen.
+                let frozen = env
+                    .read_async({
+                        let agent = agent.clone();
+                        move |txn| holochain_state::chain_lock::is_chain_frozen(&txn, &agent)
+                    })
+                    .await?;
+                if frozen {
                     Ok(WorkComplete::Complete)
                 } else {
                     publish_dht_ops_workflow(env, Arc::new(network), tx, agent).await
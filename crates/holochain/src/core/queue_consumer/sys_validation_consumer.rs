@@ -21,6 +21,7 @@ pub fn spawn_sys_validation_consumer(
     let workspace = Arc::new(workspace);
     let space = Arc::new(space);
     let config = conductor.config.clone();
+    let queue = InstrumentedQueue::SysValidation(space.dht_db.clone().into());
 
     let current_validation_dependencies = Arc::new(Mutex::new(ValidationDependencies::new()));
 
@@ -28,6 +29,7 @@ pub fn spawn_sys_validation_consumer(
         "sys_validation_consumer",
         space.dna_hash.clone(),
         conductor.task_manager(),
+        Some((queue, conductor.workflow_queues())),
         (tx.clone(), rx),
         move || {
             sys_validation_workflow(
@@ -221,6 +221,36 @@ async fn test_concurrency() {
     assert!(timer.elapsed() < Duration::from_millis(20));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn queue_age_is_measured_from_when_ops_are_received() {
+    let mut u = arbitrary::Unstructured::new(&[0; 1000]);
+    let kind = DbKindDht(Arc::new(DnaHash::arbitrary(&mut u).unwrap()));
+    let tmpdir = tempfile::Builder::new()
+        .prefix("holochain-test-environments")
+        .tempdir()
+        .unwrap();
+    let db = DbWrite::test(tmpdir.path(), kind).expect("Couldn't create test database");
+    let action = Action::arbitrary(&mut u).unwrap();
+    let signature = Signature::arbitrary(&mut u).unwrap();
+    let op = ChainOp::RegisterAgentActivity(signature, action);
+    let op = DhtOpHashed::from_content_sync(op);
+    // - The op was authored long ago, like old data arriving by gossip.
+    let long_ago = (Timestamp::now() - Duration::from_secs(60 * 60)).unwrap();
+    assert!(op.timestamp() < long_ago);
+    db.write_async(move |txn| -> StateMutationResult<()> { mutations::insert_op(txn, &op) })
+        .await
+        .unwrap();
+
+    let sample = InstrumentedQueue::SysValidation(db.into())
+        .sample()
+        .await
+        .unwrap();
+    assert_eq!(1, sample.depth);
+    // - But it has only just been received.
+    let age = sample.oldest_item_age(Timestamp::now()).unwrap();
+    assert!(age < Duration::from_secs(60), "{age:?}");
+}
+
 #[tokio::test(flavor = "current_thread", start_paused = true)]
 async fn publish_loop() {
     let mut u = arbitrary::Unstructured::new(&[0; 1000]);
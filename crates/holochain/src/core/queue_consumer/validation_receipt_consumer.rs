@@ -20,6 +20,7 @@ pub fn spawn_validation_receipt_consumer(
         "validation_receipt_consumer",
         dna_hash.clone(),
         conductor.task_manager(),
+        None,
         (tx.clone(), rx),
         move || {
             validation_receipt_workflow(
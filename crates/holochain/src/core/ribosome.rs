@@ -59,6 +59,8 @@ mod check_clone_access;
 use crate::conductor::api::CellConductorHandle;
 use crate::conductor::api::CellConductorReadHandle;
 use crate::conductor::api::ZomeCall;
+use crate::core::ribosome::guest_callback::dependency_hints::DependencyHintsInvocation;
+use crate::core::ribosome::guest_callback::dependency_hints::DependencyHintsResult;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsResult;
 use crate::core::ribosome::guest_callback::genesis_self_check::v1::GenesisSelfCheckHostAccessV1;
 use crate::core::ribosome::guest_callback::genesis_self_check::v2::GenesisSelfCheckHostAccessV2;
@@ -72,6 +74,7 @@ use crate::core::ribosome::guest_callback::validate::ValidateResult;
 use crate::core::ribosome::guest_callback::CallIterator;
 use derive_more::Constructor;
 use error::RibosomeResult;
+use guest_callback::dependency_hints::DependencyHintsHostAccess;
 use guest_callback::entry_defs::EntryDefsHostAccess;
 use guest_callback::init::InitHostAccess;
 use guest_callback::migrate_agent::MigrateAgentHostAccess;
@@ -144,6 +147,7 @@ impl CallContext {
 
 #[derive(Clone, Debug)]
 pub enum HostContext {
+    DependencyHints(DependencyHintsHostAccess),
     EntryDefs(EntryDefsHostAccess),
     GenesisSelfCheckV1(GenesisSelfCheckHostAccessV1),
     GenesisSelfCheckV2(GenesisSelfCheckHostAccessV2),
@@ -163,6 +167,7 @@ impl From<&HostContext> for HostFnAccess {
             HostContext::Validate(access) => access.into(),
             HostContext::Init(access) => access.into(),
             HostContext::EntryDefs(access) => access.into(),
+            HostContext::DependencyHints(access) => access.into(),
             HostContext::MigrateAgent(access) => access.into(),
             HostContext::PostCommit(access) => access.into(),
         }
@@ -240,6 +245,14 @@ impl HostContext {
         }
     }
 
+    /// The time after which the call is cancelled, if it has a timeout.
+    pub fn deadline(&self) -> Option<std::time::Instant> {
+        match self {
+            Self::ZomeCall(ZomeCallHostAccess { deadline, .. }) => *deadline,
+            _ => None,
+        }
+    }
+
     /// Get the call zome handle, panics if none was provided
     pub fn call_zome_handle(&self) -> &CellConductorReadHandle {
         match self {
@@ -253,6 +266,19 @@ impl HostContext {
             ),
         }
     }
+
+    /// Get the call zome handle if this context has one
+    pub fn maybe_call_zome_handle(&self) -> Option<&CellConductorReadHandle> {
+        match self {
+            Self::ZomeCall(ZomeCallHostAccess {
+                call_zome_handle, ..
+            })
+            | Self::Init(InitHostAccess {
+                call_zome_handle, ..
+            }) => Some(call_zome_handle),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -383,6 +409,7 @@ impl ZomeCallInvocation {
     /// - we need to find a live (committed and not deleted) cap grant that matches the secret
     /// - if the live cap grant is for the current author the call is ALWAYS authorized ELSE
     /// - the live cap grant needs to include the invocation's provenance AND zome/function name
+    /// - for a read-only query the live cap grant needs to be a query grant
     pub async fn verify_grant(
         &self,
         host_access: &ZomeCallHostAccess,
@@ -390,13 +417,20 @@ impl ZomeCallInvocation {
         let check_function = (self.zome.zome_name().clone(), self.fn_name.clone());
         let check_agent = self.provenance.clone();
         let check_secret = self.cap_secret;
-        let maybe_grant: Option<CapGrant> = host_access
+        let source_chain = host_access
             .workspace
             .source_chain()
             .as_ref()
-            .expect("Must have source chain to make zome calls")
-            .valid_cap_grant(check_function, check_agent, check_secret)
-            .await?;
+            .expect("Must have source chain to make zome calls");
+        let maybe_grant: Option<CapGrant> = if host_access.read_only {
+            source_chain
+                .valid_query_cap_grant(check_function, check_agent, check_secret)
+                .await?
+        } else {
+            source_chain
+                .valid_cap_grant(check_function, check_agent, check_secret)
+                .await?
+        };
         Ok(if maybe_grant.is_some() {
             ZomeCallAuthorization::Authorized
         } else {
@@ -514,6 +548,9 @@ pub struct ZomeCallInvocation {
     pub nonce: Nonce256Bits,
     /// This call MUST NOT be respected after this time, in the opinion of the callee.
     pub expires_at: Timestamp,
+    /// How many milliseconds the caller asked to wait for the data committed by
+    /// the call to be published. It is signed along with the rest of the call.
+    pub wait_for_publish: Option<u64>,
 }
 
 impl Invocation for ZomeCallInvocation {
@@ -546,6 +583,8 @@ impl ZomeCallInvocation {
             signature,
             nonce,
             expires_at,
+            wait_for_publish,
+            ..
         } = call;
         let zome = conductor_api
             .get_zome(cell_id.dna_hash(), &zome_name)
@@ -560,6 +599,7 @@ impl ZomeCallInvocation {
             signature,
             nonce,
             expires_at,
+            wait_for_publish,
         })
     }
 }
@@ -576,6 +616,7 @@ impl From<ZomeCallInvocation> for ZomeCall {
             signature,
             nonce,
             expires_at,
+            wait_for_publish,
         } = inv;
         Self {
             cell_id,
@@ -587,6 +628,8 @@ impl From<ZomeCallInvocation> for ZomeCall {
             signature,
             nonce,
             expires_at,
+            wait_for_publish,
+            timeout_ms: None,
         }
     }
 }
@@ -598,6 +641,13 @@ pub struct ZomeCallHostAccess {
     pub network: HolochainP2pDna,
     pub signal_tx: broadcast::Sender<Signal>,
     pub call_zome_handle: CellConductorReadHandle,
+    /// Set for remote queries, which must be granted as queries and may not
+    /// write to the source chain or the network.
+    pub read_only: bool,
+    /// The call is cancelled at this time. Wasm is only given the metering
+    /// points it would use until then, any host function called after it fails,
+    /// and calls it makes to other zomes on this conductor share the deadline.
+    pub deadline: Option<std::time::Instant>,
 }
 
 impl std::fmt::Debug for ZomeCallHostAccess {
@@ -613,8 +663,16 @@ impl From<ZomeCallHostAccess> for HostContext {
 }
 
 impl From<&ZomeCallHostAccess> for HostFnAccess {
-    fn from(_: &ZomeCallHostAccess) -> Self {
-        Self::all()
+    fn from(access: &ZomeCallHostAccess) -> Self {
+        if access.read_only {
+            Self {
+                write_workspace: Permission::Deny,
+                write_network: Permission::Deny,
+                ..Self::all()
+            }
+        } else {
+            Self::all()
+        }
     }
 }
 
@@ -740,6 +798,14 @@ pub trait RibosomeT: Sized + std::fmt::Debug + Send + Sync {
         invocation: ValidateInvocation,
     ) -> RibosomeResult<ValidateResult>;
 
+    /// Runs the optional dependency hints callback for an op, to find out
+    /// which dependencies validating it is expected to need.
+    fn run_dependency_hints(
+        &self,
+        access: DependencyHintsHostAccess,
+        invocation: DependencyHintsInvocation,
+    ) -> RibosomeResult<DependencyHintsResult>;
+
     /// Runs the specified zome fn. Returns the cursor used by HDK,
     /// so that it can be passed on to source chain manager for transactional writes
     fn call_zome_function(
@@ -802,6 +868,7 @@ pub mod wasm_test {
             payload: ExternIO::encode(()).unwrap(),
             nonce,
             expires_at,
+            wait_for_publish: None,
         };
         let alice_signed_zome_call = ZomeCall::try_from_unsigned_zome_call(
             &conductor.keystore(),
@@ -35,10 +35,22 @@ pub enum RibosomeError {
     #[error("Attempted to call a zome function that doesn't exist: Zome: {0} Fn {1}")]
     ZomeFnNotExists(ZomeName, FunctionName),
 
+    /// A zome call was cancelled because it didn't finish within its timeout.
+    #[error("The zome call to {0}::{1} was cancelled after {2:?}")]
+    ZomeCallTimeout(ZomeName, FunctionName, std::time::Duration),
+
+    /// A zome call tried to commit to a cell which is frozen.
+    #[error("The cell {0:?} is frozen, so the zome call's writes were not committed")]
+    CellFrozen(CellId),
+
     /// a problem with entry defs
     #[error("An error with entry defs in zome '{0}': {1}")]
     EntryDefs(ZomeName, String),
 
+    /// an init callback failed
+    #[error("The init callback of zome '{0}' failed: {1}")]
+    InitFailed(ZomeName, String),
+
     /// a mandatory dependency for a record doesn't exist
     /// for example a remove link ribosome call needs to find the add link in order to infer the
     /// correct base and this dependent relationship exists before even subconscious validation
@@ -94,6 +106,10 @@ pub enum RibosomeError {
     #[error("Host function {2} cannot be called from zome function {1} in zome {0}")]
     HostFnPermissions(ZomeName, FunctionName, String),
 
+    /// The app manifest doesn't allow a zome to use a class of host functions.
+    #[error("Host function {2} cannot be called from zome {0}, because it is not allowed {1:?} host functions")]
+    HostFnNotAllowed(ZomeName, HostFnClass, String),
+
     /// An attempt to was made to perform a clone operation on a cell that is not provisioned or belongs to another app.
     #[error("Invalid request to modify a cell which belongs to another app")]
     InvalidCloneTarget,
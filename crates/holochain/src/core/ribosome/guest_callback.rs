@@ -1,3 +1,4 @@
+pub mod dependency_hints;
 pub mod entry_defs;
 pub mod genesis_self_check;
 pub mod init;
@@ -0,0 +1,112 @@
+use crate::core::ribosome::FnComponents;
+use crate::core::ribosome::HostContext;
+use crate::core::ribosome::Invocation;
+use crate::core::ribosome::InvocationAuth;
+use crate::core::ribosome::ZomesToInvoke;
+use derive_more::Constructor;
+use holochain_serialized_bytes::prelude::*;
+use holochain_types::prelude::*;
+use holochain_zome_types::op::Op;
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+/// An invocation of the dependency hints callback function.
+pub struct DependencyHintsInvocation {
+    /// The zomes this invocation will invoke.
+    zomes_to_invoke: ZomesToInvoke,
+    /// The serialized op to get hints for.
+    data: Arc<ExternIO>,
+}
+
+impl DependencyHintsInvocation {
+    pub fn new(zomes_to_invoke: ZomesToInvoke, data: &Op) -> Result<Self, SerializedBytesError> {
+        let data = Arc::new(ExternIO::encode(data)?);
+        Ok(Self {
+            zomes_to_invoke,
+            data,
+        })
+    }
+}
+
+/// Hints are derived from the op alone, so no host functions beyond the
+/// deterministic bindings are available.
+#[derive(Clone, Constructor, Debug)]
+pub struct DependencyHintsHostAccess;
+
+impl From<DependencyHintsHostAccess> for HostContext {
+    fn from(dependency_hints_host_access: DependencyHintsHostAccess) -> Self {
+        Self::DependencyHints(dependency_hints_host_access)
+    }
+}
+
+impl From<&DependencyHintsHostAccess> for HostFnAccess {
+    fn from(_: &DependencyHintsHostAccess) -> Self {
+        let mut access = Self::none();
+        access.bindings_deterministic = Permission::Allow;
+        access
+    }
+}
+
+impl Invocation for DependencyHintsInvocation {
+    fn zomes(&self) -> ZomesToInvoke {
+        self.zomes_to_invoke.clone()
+    }
+    fn fn_components(&self) -> FnComponents {
+        vec!["dependency_hints".to_string()].into()
+    }
+    fn host_input(self) -> Result<ExternIO, SerializedBytesError> {
+        Ok((*self.data).clone())
+    }
+    fn auth(&self) -> InvocationAuth {
+        InvocationAuth::LocalCallback
+    }
+}
+
+/// The combined dependency hints of all invoked zomes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DependencyHintsResult(pub Vec<AnyDhtHash>);
+
+impl From<Vec<(ZomeName, DependencyHintsCallbackResult)>> for DependencyHintsResult {
+    fn from(callback_results: Vec<(ZomeName, DependencyHintsCallbackResult)>) -> Self {
+        let mut hints = Vec::new();
+        for (_, DependencyHintsCallbackResult(hashes)) in callback_results {
+            for hash in hashes {
+                if !hints.contains(&hash) {
+                    hints.push(hash);
+                }
+            }
+        }
+        Self(hints)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DependencyHintsResult;
+    use ::fixt::prelude::*;
+    use holo_hash::fixt::AnyDhtHashFixturator;
+    use holochain_types::prelude::*;
+
+    #[test]
+    fn dependency_hints_result_merges_zomes() {
+        let a = fixt!(AnyDhtHash);
+        let b = fixt!(AnyDhtHash);
+        let results = vec![
+            (
+                ZomeName::from("foo"),
+                DependencyHintsCallbackResult(vec![a.clone(), b.clone()]),
+            ),
+            (ZomeName::from("bar"), DependencyHintsCallbackResult(vec![])),
+            (
+                ZomeName::from("baz"),
+                DependencyHintsCallbackResult(vec![b.clone()]),
+            ),
+        ];
+
+        assert_eq!(DependencyHintsResult(vec![a, b]), results.into());
+        assert_eq!(
+            DependencyHintsResult::default(),
+            Vec::<(ZomeName, DependencyHintsCallbackResult)>::new().into()
+        );
+    }
+}
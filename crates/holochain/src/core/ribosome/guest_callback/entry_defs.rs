@@ -234,13 +234,11 @@ mod slow_tests {
                     EntryDef {
                         id: "post".into(),
                         visibility: EntryVisibility::Public,
-                        required_validations: 5.into(),
                         ..Default::default()
                     },
                     EntryDef {
                         id: "comment".into(),
                         visibility: EntryVisibility::Private,
-                        required_validations: 5.into(),
                         ..Default::default()
                     },
                 ]
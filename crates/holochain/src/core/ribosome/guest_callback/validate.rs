@@ -9,6 +9,8 @@ use holochain_serialized_bytes::prelude::*;
 use holochain_state::host_fn_workspace::HostFnWorkspaceRead;
 use holochain_types::prelude::*;
 use holochain_zome_types::op::Op;
+use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[derive(Clone, Debug)]
@@ -34,6 +36,34 @@ impl ValidateInvocation {
 pub struct ValidateHostAccess {
     pub workspace: HostFnWorkspaceRead,
     pub network: GenericNetwork,
+    pub action_cache: ValidateActionCache,
+}
+
+/// Actions which have been fetched with `must_get_action` during validation.
+///
+/// An action is content addressed so once it has been found it can be reused
+/// by every later validation run, which makes repeated checks against the same
+/// action, like a link base, cheap.
+#[derive(Clone, Default)]
+pub struct ValidateActionCache(Arc<Mutex<HashMap<ActionHash, SignedActionHashed>>>);
+
+impl ValidateActionCache {
+    /// The cache is cleared once it holds this many actions.
+    const MAX_ACTIONS: usize = 10_000;
+
+    /// Get a cached action.
+    pub fn get(&self, action_hash: &ActionHash) -> Option<SignedActionHashed> {
+        self.0.lock().get(action_hash).cloned()
+    }
+
+    /// Cache an action.
+    pub fn insert(&self, action: SignedActionHashed) {
+        let mut actions = self.0.lock();
+        if actions.len() >= Self::MAX_ACTIONS {
+            actions.clear();
+        }
+        actions.insert(action.action_address().clone(), action);
+    }
 }
 
 impl std::fmt::Debug for ValidateHostAccess {
@@ -25,7 +25,7 @@ pub fn accept_countersigning_preflight_request<'a>(
             if let Err(e) = input.check_integrity() {
                 return Ok(PreflightRequestAcceptance::Invalid(e.to_string()));
             }
-            let author = super::agent_info::agent_info(_ribosome, call_context.clone(), ())?
+            let author = super::agent_info_1::agent_info_1(_ribosome, call_context.clone(), ())?
                 .agent_latest_pubkey;
             tokio_helper::block_forever_on(async move {
                 if (holochain_zome_types::prelude::Timestamp::now() + SESSION_TIME_FUTURE_MAX)
@@ -180,6 +180,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -191,6 +192,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -202,6 +204,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -213,6 +216,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -287,6 +291,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -298,6 +303,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -309,6 +315,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -320,6 +327,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -470,6 +478,7 @@ pub mod wasm_test {
                         payload: ExternIO::encode(()).unwrap(),
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     },
                 )
                 .await
@@ -501,6 +510,7 @@ pub mod wasm_test {
                         .unwrap(),
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     },
                 )
                 .await
@@ -589,6 +599,7 @@ pub mod wasm_test {
                         payload: ExternIO::encode(&preflight_request_2).unwrap(),
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     },
                 )
                 .await
@@ -632,6 +643,7 @@ pub mod wasm_test {
                         payload: ExternIO::encode(()).unwrap(),
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     },
                 )
                 .await
@@ -656,6 +668,7 @@ pub mod wasm_test {
                         payload: ExternIO::encode(()).unwrap(),
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     },
                 )
                 .await
@@ -690,6 +703,7 @@ pub mod wasm_test {
                         payload: ExternIO::encode(()).unwrap(),
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     },
                 )
                 .await
@@ -710,6 +724,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -732,6 +747,7 @@ pub mod wasm_test {
                         payload: ExternIO::encode(()).unwrap(),
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     },
                 )
                 .await
@@ -791,6 +807,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -813,6 +830,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -900,6 +918,7 @@ pub mod wasm_test {
                         payload: ExternIO::encode(&preflight_request_2).unwrap(),
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     },
                 )
                 .await
@@ -943,6 +962,7 @@ pub mod wasm_test {
                         payload: ExternIO::encode(()).unwrap(),
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     },
                 )
                 .await
@@ -967,6 +987,7 @@ pub mod wasm_test {
                         payload: ExternIO::encode(()).unwrap(),
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     },
                 )
                 .await
@@ -1001,6 +1022,7 @@ pub mod wasm_test {
                         payload: ExternIO::encode(()).unwrap(),
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     },
                 )
                 .await
@@ -1021,6 +1043,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -1043,6 +1066,7 @@ pub mod wasm_test {
                         payload: ExternIO::encode(()).unwrap(),
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     },
                 )
                 .await
@@ -1102,6 +1126,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -1124,6 +1149,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -1175,6 +1201,7 @@ pub mod wasm_test {
                         agent_pubkey: bob_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        read_source: Default::default(),
                     },
                 )
                 .await;
@@ -1241,6 +1268,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -1253,6 +1281,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -1279,6 +1308,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -1291,6 +1321,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    read_source: Default::default(),
                 },
             )
             .await;
@@ -1351,6 +1382,7 @@ pub mod wasm_test {
                         agent_pubkey: alice_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        read_source: Default::default(),
                     },
                 )
                 .await;
@@ -1363,6 +1395,7 @@ pub mod wasm_test {
                         agent_pubkey: bob_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        read_source: Default::default(),
                     },
                 )
                 .await;
@@ -1445,6 +1478,7 @@ pub mod wasm_test {
                         agent_pubkey: alice_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        read_source: Default::default(),
                     },
                 )
                 .await;
@@ -1458,6 +1492,7 @@ pub mod wasm_test {
                         agent_pubkey: bob_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        read_source: Default::default(),
                     },
                 )
                 .await;
@@ -1544,6 +1579,7 @@ pub mod wasm_test {
                         agent_pubkey: alice_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        read_source: Default::default(),
                     },
                 )
                 .await;
@@ -1556,6 +1592,7 @@ pub mod wasm_test {
                         agent_pubkey: bob_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        read_source: Default::default(),
                     },
                 )
                 .await;
@@ -1579,6 +1616,7 @@ pub mod wasm_test {
                         agent_pubkey: alice_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        read_source: Default::default(),
                     },
                 )
                 .await;
@@ -1591,6 +1629,7 @@ pub mod wasm_test {
                         agent_pubkey: bob_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        read_source: Default::default(),
                     },
                 )
                 .await;
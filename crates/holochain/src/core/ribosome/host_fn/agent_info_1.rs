@@ -0,0 +1,54 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use holochain_zome_types::info::AgentInfoV1;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn agent_info_1<'a>(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    _input: (),
+) -> Result<AgentInfoV1, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            agent_info: Permission::Allow,
+            ..
+        } => {
+            let agent_pubkey = call_context
+                .host_context
+                .workspace()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain if agent_info access is given")
+                .agent_pubkey()
+                .clone();
+            let head = call_context
+                .host_context
+                .workspace()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain if agent_info access is given")
+                .chain_head_nonempty()
+                .map_err(|e| wasm_error!(WasmErrorInner::Host(e.to_string())))?;
+            Ok(AgentInfoV1 {
+                agent_initial_pubkey: agent_pubkey.clone(),
+                agent_latest_pubkey: agent_pubkey,
+                chain_head: head.into_tuple(),
+            })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "agent_info".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
@@ -4,40 +4,44 @@ use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
 use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::*;
+use holochain_zome_types::info::AgentInfoV2;
 use std::sync::Arc;
 use wasmer::RuntimeError;
 
-#[allow(clippy::extra_unused_lifetimes)]
-pub fn agent_info<'a>(
+pub fn agent_info_2(
     _ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
     _input: (),
-) -> Result<AgentInfo, RuntimeError> {
+) -> Result<AgentInfoV2, RuntimeError> {
     match HostFnAccess::from(&call_context.host_context()) {
         HostFnAccess {
             agent_info: Permission::Allow,
             ..
         } => {
-            let agent_pubkey = call_context
-                .host_context
-                .workspace()
+            let workspace = call_context.host_context.workspace();
+            let source_chain = workspace
                 .source_chain()
                 .as_ref()
-                .expect("Must have source chain if agent_info access is given")
-                .agent_pubkey()
-                .clone();
-            let head = call_context
-                .host_context
-                .workspace()
-                .source_chain()
-                .as_ref()
-                .expect("Must have source chain if agent_info access is given")
+                .expect("Must have source chain if agent_info access is given");
+            let agent_pubkey = source_chain.agent_pubkey().clone();
+            let head = source_chain
                 .chain_head_nonempty()
                 .map_err(|e| wasm_error!(WasmErrorInner::Host(e.to_string())))?;
-            Ok(AgentInfo {
+            let network = call_context.host_context.network();
+            let storage_arc = tokio_helper::block_forever_on({
+                let agent_pubkey = agent_pubkey.clone();
+                async move { network.storage_arc(agent_pubkey).await }
+            })
+            .map_err(|e| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(e.to_string())).into()
+            })?;
+            let chain_head = head.into_tuple();
+            Ok(AgentInfoV2 {
                 agent_initial_pubkey: agent_pubkey.clone(),
                 agent_latest_pubkey: agent_pubkey,
-                chain_head: head.into_tuple(),
+                chain_length: chain_head.1 + 1,
+                chain_head,
+                storage_arc,
             })
         }
         _ => Err(wasm_error!(WasmErrorInner::Host(
@@ -75,9 +79,17 @@ pub mod test {
         assert_eq!(agent_info.agent_latest_pubkey, alice_pubkey);
 
         assert_eq!(agent_info.chain_head.1, call_info.as_at.1 + 1,);
+        assert_eq!(agent_info.chain_length, agent_info.chain_head.1 + 1);
 
         let call_info_1: CallInfo = conductor.call(&alice, "call_info", ()).await;
         let agent_info_1: AgentInfo = conductor.call(&alice, "agent_info", ()).await;
         assert_eq!(agent_info_1.chain_head.1, call_info_1.as_at.1 + 1,);
+        assert_eq!(agent_info_1.chain_length, agent_info.chain_length + 1);
+
+        // The arc is only known once the agent info has been published.
+        if let Some(storage_arc) = agent_info_1.storage_arc {
+            assert!((0.0..=1.0).contains(&storage_arc.coverage));
+            assert_eq!(storage_arc.bounds.is_none(), storage_arc.coverage == 0.0);
+        }
     }
 }
@@ -73,6 +73,7 @@ pub fn call(
                                     payload,
                                     nonce,
                                     expires_at,
+                                    wait_for_publish: None,
                                 };
                                 match call_context
                                     .host_context()
@@ -115,28 +116,29 @@ pub fn call(
                                 let cell_id_result: Result<CellId, RuntimeError> = match target_cell
                                 {
                                     CallTargetCell::OtherRole(role_name) => {
-                                        let this_cell_id = call_context
-                                            .host_context()
-                                            .call_zome_handle()
-                                            .cell_id()
-                                            .clone();
-                                        call_context
-                                            .host_context()
-                                            .call_zome_handle()
+                                        let handle = call_context.host_context.call_zome_handle();
+                                        handle
                                             .find_cell_with_role_alongside_cell(
-                                                &this_cell_id,
+                                                handle.cell_id(),
                                                 &role_name,
                                             )
                                             .await
-                                            .map_err(|e| -> RuntimeError { wasm_error!(e).into() })
-                                            .and_then(|c| {
-                                                c.ok_or_else(|| {
-                                                    wasmer::RuntimeError::from(wasm_error!(
-                                                        WasmErrorInner::Host(format!(
-                                                            "Role not found: {role_name}"
-                                                        ))
-                                                    ))
-                                                })
+                                            .map_err(|e| -> RuntimeError {
+                                                wasm_error!(WasmErrorInner::Host(e.to_string()))
+                                                    .into()
+                                            })
+                                    }
+                                    CallTargetCell::OtherClone(clone_id) => {
+                                        let handle = call_context.host_context.call_zome_handle();
+                                        handle
+                                            .find_clone_cell_alongside_cell(
+                                                handle.cell_id(),
+                                                &clone_id,
+                                            )
+                                            .await
+                                            .map_err(|e| -> RuntimeError {
+                                                wasm_error!(WasmErrorInner::Host(e.to_string()))
+                                                    .into()
                                             })
                                     }
                                     CallTargetCell::OtherCell(cell_id) => Ok(cell_id),
@@ -157,8 +159,9 @@ pub fn call(
                                             provenance,
                                             nonce,
                                             expires_at,
+                                            wait_for_publish: None,
                                         };
-                                        let call = ZomeCall::try_from_unsigned_zome_call(
+                                        let mut call = ZomeCall::try_from_unsigned_zome_call(
                                             call_context.host_context.keystore(),
                                             zome_call_unsigned,
                                         )
@@ -166,6 +169,19 @@ pub fn call(
                                         .map_err(|e| -> RuntimeError {
                                             wasm_error!(WasmErrorInner::Host(e.to_string())).into()
                                         })?;
+                                        // A call can't outlive the call that made it.
+                                        if let Some(deadline) =
+                                            call_context.host_context().deadline()
+                                        {
+                                            call.timeout_ms = Some(
+                                                deadline
+                                                    .saturating_duration_since(
+                                                        std::time::Instant::now(),
+                                                    )
+                                                    .as_millis()
+                                                    as u64,
+                                            );
+                                        }
                                         match call_context
                                             .host_context()
                                             .call_zome_handle()
@@ -261,6 +277,7 @@ pub mod wasm_test {
             .await
             .unwrap();
 
+        let installed_app_id = apps[0].installed_app_id().clone();
         let ((cell1, cell2),) = apps.into_tuples();
 
         let zome1 = cell1.zome(test_wasm);
@@ -280,6 +297,42 @@ pub mod wasm_test {
             assert_eq!(agent_info.agent_initial_pubkey, alice_pubkey);
             assert_eq!(agent_info.agent_latest_pubkey, alice_pubkey);
         }
+        {
+            let err = conductor
+                .call_fallible::<_, AgentInfo>(&zome1, "who_are_they_role", "role3")
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("missing role name: 'role3'"));
+        }
+        {
+            let clone = conductor
+                .raw_handle()
+                .create_clone_cell(
+                    &installed_app_id,
+                    CreateCloneCellPayload {
+                        role_name: "role2".to_string(),
+                        modifiers: DnaModifiersOpt::default().with_network_seed("clone".into()),
+                        membrane_proof: None,
+                        name: None,
+                    },
+                )
+                .await
+                .unwrap();
+            let agent_info: AgentInfo = conductor
+                .call(&zome1, "who_are_they_clone", clone.clone_id.clone())
+                .await;
+            assert_eq!(agent_info.agent_initial_pubkey, alice_pubkey);
+
+            let err = conductor
+                .call_fallible::<_, AgentInfo>(
+                    &zome1,
+                    "who_are_they_clone",
+                    CloneId::new(&"role2".to_string(), 5),
+                )
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("Could not find clone cell"));
+        }
     }
 
     /// When calling the same cell we need to make sure
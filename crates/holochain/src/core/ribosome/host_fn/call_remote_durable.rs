@@ -0,0 +1,52 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+/// Queue a call to a remote agent, which the conductor persists and retries
+/// until it is delivered or its time to live runs out. The response is sent to
+/// the app as a [`SystemSignal::DurableCallRemoteDelivered`].
+///
+/// The call is staged in the scratch like the zome call's writes, and only
+/// queued in the conductor's outbox once they have been flushed, so a zome
+/// call which fails doesn't make any durable remote calls.
+pub fn call_remote_durable(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: DurableCallRemote,
+) -> Result<DurableCallId, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            write_workspace: Permission::Allow,
+            write_network: Permission::Allow,
+            agent_info: Permission::Allow,
+            ..
+        } => {
+            // The id is stored as an sqlite integer, so it must fit in an i64.
+            let id = DurableCallId(rand::random::<u64>() >> 1);
+            call_context
+                .host_context()
+                .workspace_write()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain if write_workspace access is given")
+                .scratch()
+                .apply(|scratch| scratch.add_durable_call(id, input))
+                .map_err(|e| wasm_error!(WasmErrorInner::Host(e.to_string())))?;
+            Ok(id)
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "call_remote_durable".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
@@ -26,6 +26,7 @@ pub fn count_links<'a>(
                 base: query.base,
                 link_type: query.link_type,
                 tag_prefix: query.tag_prefix,
+                tag_range: query.tag_range,
                 before: query.before,
                 after: query.after,
                 author: query.author,
@@ -0,0 +1,92 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_wasmer_host::prelude::*;
+
+use holochain_types::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn create_blob_chunk(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: BlobChunk,
+) -> Result<EntryHash, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            write_workspace: Permission::Allow,
+            ..
+        } => {
+            let chunk = input.0;
+            if chunk.is_empty() || chunk.len() > BLOB_CHUNK_SIZE {
+                return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                    "Blob chunk of {} bytes must be between 1 and {} bytes",
+                    chunk.len(),
+                    BLOB_CHUNK_SIZE
+                )))
+                .into());
+            }
+            // The chunk is only stored when the call's writes are flushed, as a
+            // blob chunk op accounted for by the manifest which holds it, so a
+            // failed call leaves no chunks behind. It is published with the
+            // rest of the authored ops.
+            call_context
+                .host_context
+                .workspace_write()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain if write_workspace access is given")
+                .put_blob_chunk(chunk)
+                .map_err(|source_chain_error| -> RuntimeError {
+                    wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+                })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "create_blob_chunk".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use hdk::prelude::*;
+    use holochain_types::prelude::BlobChunkOp;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_blob_chunks_are_shared_and_released() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::Create).await;
+
+        let bytes = vec![1_u8; 16];
+        let chunk_hash = BlobChunkOp::chunk_hash_of(&bytes);
+        let first: ActionHash = conductor.call(&alice, "create_blob", bytes.clone()).await;
+        let second: ActionHash = conductor.call(&alice, "create_blob", bytes.clone()).await;
+        assert_ne!(first, second);
+
+        let read: Option<Vec<u8>> = conductor.call(&alice, "read_blob", first.clone()).await;
+        assert_eq!(read, Some(bytes.clone()));
+
+        // The chunk is shared with the second blob, so it outlives the first.
+        let _: ActionHash = conductor.call(&alice, "delete_blob", first).await;
+        let read: Option<Vec<u8>> = conductor.call(&alice, "read_blob", second.clone()).await;
+        assert_eq!(read, Some(bytes.clone()));
+
+        // Once no blob holds it, the chunk is still kept by its authority,
+        // which is alice as the only agent.
+        let _: ActionHash = conductor.call(&alice, "delete_blob", second).await;
+        let chunk: Option<Vec<u8>> = conductor.call(&alice, "get_blob_chunk", chunk_hash).await;
+        assert_eq!(chunk, Some(bytes));
+    }
+}
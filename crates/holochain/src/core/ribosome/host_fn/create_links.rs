@@ -0,0 +1,108 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_wasmer_host::prelude::*;
+
+use holochain_types::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn create_links<'a>(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    inputs: Vec<CreateLinkInput>,
+) -> Result<Vec<ActionHash>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            write_workspace: Permission::Allow,
+            ..
+        } => {
+            // The scratch is strict if any of its writes are.
+            let chain_top_ordering = if inputs
+                .iter()
+                .any(|input| input.chain_top_ordering == ChainTopOrdering::Strict)
+            {
+                ChainTopOrdering::Strict
+            } else {
+                ChainTopOrdering::Relaxed
+            };
+
+            let action_builders: Vec<_> = inputs
+                .into_iter()
+                .map(|input| {
+                    builder::CreateLink::new(
+                        input.base_address,
+                        input.target_address,
+                        input.zome_index,
+                        input.link_type,
+                        input.tag,
+                    )
+                })
+                .collect();
+
+            let action_hashes = tokio_helper::block_forever_on(tokio::task::spawn(async move {
+                // push all the actions into the source chain
+                let action_hashes = call_context
+                    .host_context
+                    .workspace_write()
+                    .source_chain()
+                    .as_ref()
+                    .expect("Must have source chain if write_workspace access is given")
+                    .put_weightless_many(action_builders, chain_top_ordering)
+                    .await?;
+                Ok::<Vec<ActionHash>, RibosomeError>(action_hashes)
+            }))
+            .map_err(|join_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(join_error.to_string())).into()
+            })?
+            .map_err(|ribosome_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(ribosome_error.to_string())).into()
+            })?;
+
+            // as with create_link, validation is handled by the workflow
+            // and the whole commit is rolled back if any of the links are invalid
+            Ok(action_hashes)
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "create_links".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_create_and_delete_links_in_bulk() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::Link).await;
+
+        let link_actions: Vec<ActionHash> = conductor.call(&alice, "create_many_links", 50).await;
+        assert_eq!(50, link_actions.len());
+
+        let links: Vec<Link> = conductor.call(&alice, "get_many_links", ()).await;
+        assert_eq!(50, links.len());
+
+        let delete_actions: Vec<ActionHash> = conductor
+            .call(&alice, "delete_many_links", link_actions)
+            .await;
+        assert_eq!(50, delete_actions.len());
+
+        let links: Vec<Link> = conductor.call(&alice, "get_many_links", ()).await;
+        assert!(links.is_empty());
+    }
+}
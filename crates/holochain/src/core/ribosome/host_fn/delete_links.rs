@@ -0,0 +1,93 @@
+use crate::core::ribosome::error::RibosomeError;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeT;
+use holochain_cascade::CascadeImpl;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn delete_links<'a>(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    inputs: Vec<DeleteLinkInput>,
+) -> Result<Vec<ActionHash>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            write_workspace: Permission::Allow,
+            ..
+        } => {
+            // The scratch is strict if any of its writes are.
+            let chain_top_ordering = if inputs
+                .iter()
+                .any(|input| input.chain_top_ordering == ChainTopOrdering::Strict)
+            {
+                ChainTopOrdering::Strict
+            } else {
+                ChainTopOrdering::Relaxed
+            };
+
+            let network = call_context.host_context.network().clone();
+            let call_context_2 = call_context.clone();
+
+            // get the base address from each add link action, as delete_link does
+            let action_builders = tokio_helper::block_forever_on(async move {
+                let workspace = call_context_2.host_context.workspace();
+                let cascade = CascadeImpl::from_workspace_and_network(&workspace, network);
+                let mut action_builders = Vec::with_capacity(inputs.len());
+                for DeleteLinkInput { address, .. } in inputs {
+                    let maybe_add_link = cascade
+                        .dht_get(address.clone().into(), GetOptions::local())
+                        .await?
+                        .map(|el| el.into_inner().0);
+                    let base_address = match maybe_add_link.as_ref().map(|a| a.action()) {
+                        Some(Action::CreateLink(link_add_action)) => {
+                            link_add_action.base_address.clone()
+                        }
+                        // the add link action hash was either not found
+                        // or didn't point to an AddLink action
+                        _ => return Err(RibosomeError::RecordDeps(address.into())),
+                    };
+                    action_builders.push(builder::DeleteLink {
+                        link_add_address: address,
+                        base_address,
+                    });
+                }
+                Ok(action_builders)
+            })
+            .map_err(|ribosome_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(ribosome_error.to_string())).into()
+            })?;
+
+            let source_chain = call_context
+                .host_context
+                .workspace_write()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain if write_workspace access is given");
+
+            // add all the DeleteLinks to the source chain
+            tokio_helper::block_forever_on(async move {
+                source_chain
+                    .put_weightless_many(action_builders, chain_top_ordering)
+                    .await
+                    .map_err(|source_chain_error| -> RuntimeError {
+                        wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+                    })
+            })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "delete_links".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+// see create_links for the tests of bulk link creation and deletion
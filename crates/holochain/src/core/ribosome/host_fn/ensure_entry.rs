@@ -0,0 +1,115 @@
+use super::create::create;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+/// Create an entry unless an identical one has already been created on this
+/// agent's chain and not deleted, in which case return the existing action hash.
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn ensure_entry<'a>(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: CreateInput,
+) -> Result<ActionHash, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            write_workspace: Permission::Allow,
+            ..
+        } => {
+            // Countersigned entries are only ever written as part of a session.
+            if let Entry::CounterSign(_, _) = input.entry {
+                return create(ribosome, call_context, input);
+            }
+
+            let entry_type = match &input.entry_location {
+                EntryDefLocation::App(AppEntryDefLocation {
+                    zome_index,
+                    entry_def_index,
+                }) => EntryType::App(AppEntryDef::new(
+                    *entry_def_index,
+                    *zome_index,
+                    input.entry_visibility,
+                )),
+                EntryDefLocation::CapGrant => EntryType::CapGrant,
+                EntryDefLocation::CapClaim => EntryType::CapClaim,
+            };
+            let entry_hash = EntryHash::with_data_sync(&input.entry);
+
+            let existing = tokio_helper::block_forever_on({
+                let call_context = call_context.clone();
+                async move {
+                    call_context
+                        .host_context
+                        .workspace_write()
+                        .source_chain()
+                        .as_ref()
+                        .expect("Must have source chain if write_workspace access is given")
+                        .find_live_create(entry_type, entry_hash)
+                        .await
+                }
+            })
+            .map_err(|source_chain_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+            })?;
+
+            match existing {
+                Some(action_hash) => Ok(action_hash),
+                None => create(ribosome, call_context, input),
+            }
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "ensure_entry".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[derive(Serialize, Deserialize, SerializedBytes, Debug)]
+    struct Post(String);
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_ensure_entry_only_creates_once() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::Create).await;
+
+        let first: ActionHash = conductor
+            .call(&alice, "ensure_post", Post("foo".into()))
+            .await;
+        let second: ActionHash = conductor
+            .call(&alice, "ensure_post", Post("foo".into()))
+            .await;
+        assert_eq!(first, second);
+
+        // A different entry is created.
+        let other: ActionHash = conductor
+            .call(&alice, "ensure_post", Post("bar".into()))
+            .await;
+        assert_ne!(first, other);
+
+        // Once deleted, the entry is created again.
+        let _: ActionHash = conductor.call(&alice, "delete_post", first.clone()).await;
+        let recreated: ActionHash = conductor
+            .call(&alice, "ensure_post", Post("foo".into()))
+            .await;
+        assert_ne!(first, recreated);
+    }
+}
@@ -0,0 +1,95 @@
+use super::create_link::create_link;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+/// Create a link unless an identical one has already been created on this
+/// agent's chain and not deleted, in which case return the existing action hash.
+#[allow(clippy::extra_unused_lifetimes)]
+pub fn ensure_link<'a>(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: CreateLinkInput,
+) -> Result<ActionHash, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            write_workspace: Permission::Allow,
+            ..
+        } => {
+            let existing = tokio_helper::block_forever_on({
+                let call_context = call_context.clone();
+                let input = input.clone();
+                async move {
+                    call_context
+                        .host_context
+                        .workspace_write()
+                        .source_chain()
+                        .as_ref()
+                        .expect("Must have source chain if write_workspace access is given")
+                        .find_live_create_link(
+                            &input.base_address,
+                            &input.target_address,
+                            input.zome_index,
+                            input.link_type,
+                            &input.tag,
+                        )
+                        .await
+                }
+            })
+            .map_err(|source_chain_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+            })?;
+
+            match existing {
+                Some(action_hash) => Ok(action_hash),
+                None => create_link(ribosome, call_context, input),
+            }
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "ensure_link".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_ensure_link_only_creates_once() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::Link).await;
+
+        let first: ActionHash = conductor.call(&alice, "ensure_link", ()).await;
+        let second: ActionHash = conductor.call(&alice, "ensure_link", ()).await;
+        assert_eq!(first, second);
+
+        // A link made with create_link is found too.
+        let _: ActionHash = conductor.call(&alice, "delete_link", first.clone()).await;
+        let created: ActionHash = conductor.call(&alice, "create_link", ()).await;
+        let ensured: ActionHash = conductor.call(&alice, "ensure_link", ()).await;
+        assert_eq!(created, ensured);
+
+        // Once deleted, the link is created again.
+        let _: ActionHash = conductor.call(&alice, "delete_link", created.clone()).await;
+        let recreated: ActionHash = conductor.call(&alice, "ensure_link", ()).await;
+        assert_ne!(created, recreated);
+        assert_ne!(first, recreated);
+    }
+}
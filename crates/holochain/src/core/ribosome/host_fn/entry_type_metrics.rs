@@ -0,0 +1,34 @@
+use crate::core::metrics::ENTRY_TYPE_COUNTERS;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::access::Permission;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+/// The counts of the actions on each app entry type of the calling DNA which passed
+/// or failed app validation on this conductor.
+pub fn entry_type_metrics(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    _input: (),
+) -> Result<Vec<EntryTypeMetrics>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            non_determinism: Permission::Allow,
+            ..
+        } => Ok(ENTRY_TYPE_COUNTERS.metrics(ribosome.dna_hash())),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "entry_type_metrics".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
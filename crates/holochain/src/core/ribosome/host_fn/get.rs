@@ -14,7 +14,7 @@ use wasmer::RuntimeError;
 pub fn get<'a>(
     _ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
-    inputs: Vec<GetInput>,
+    mut inputs: Vec<GetInput>,
 ) -> Result<Vec<Option<Record>>, RuntimeError> {
     let num_requests = inputs.len();
     tracing::debug!("Starting with {} requests.", num_requests);
@@ -23,6 +23,7 @@ pub fn get<'a>(
             read_workspace: Permission::Allow,
             ..
         } => {
+            tokio_helper::block_forever_on(fill_warranted_authors(&call_context, &mut inputs))?;
             let results: Vec<Result<Option<Record>, _>> =
                 tokio_helper::block_forever_on(async move {
                     futures::stream::iter(inputs.into_iter().map(|input| async {
@@ -73,6 +74,36 @@ pub fn get<'a>(
     }
 }
 
+/// Fill in the app's default for gets which don't choose what to do with data
+/// by warranted authors. Only zome calls and init have an app to take the
+/// default from, so callbacks such as validation always include this data.
+pub(crate) async fn fill_warranted_authors(
+    call_context: &CallContext,
+    inputs: &mut [GetInput],
+) -> Result<(), RuntimeError> {
+    let handle = match call_context.host_context.maybe_call_zome_handle() {
+        Some(handle) => handle,
+        None => return Ok(()),
+    };
+    if inputs
+        .iter()
+        .all(|input| input.get_options.warranted_authors.is_some())
+    {
+        return Ok(());
+    }
+    let warranted_authors = handle
+        .warranted_authors()
+        .await
+        .map_err(|e| -> RuntimeError { wasm_error!(WasmErrorInner::Host(e.to_string())).into() })?;
+    for input in inputs {
+        input
+            .get_options
+            .warranted_authors
+            .get_or_insert(warranted_authors);
+    }
+    Ok(())
+}
+
 // we are relying on the create tests to show the commit/get round trip
 // See create.rs
 
@@ -0,0 +1,52 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_cascade::CascadeImpl;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn get_action_by_seq(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: GetActionBySeqInput,
+) -> Result<Option<SignedActionHashed>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => {
+            let GetActionBySeqInput {
+                author,
+                action_seq,
+                read_source,
+            } = input;
+
+            // Get the network from the context
+            let network = call_context.host_context.network().clone();
+
+            // timeouts must be handled by the network
+            tokio_helper::block_forever_on(async move {
+                let workspace = call_context.host_context.workspace();
+                let cascade = CascadeImpl::from_workspace_and_network(&workspace, network);
+                cascade
+                    .get_action_by_seq(author, action_seq, read_source)
+                    .await
+                    .map_err(|cascade_error| {
+                        wasm_error!(WasmErrorInner::Host(cascade_error.to_string())).into()
+                    })
+            })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "get_action_by_seq".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
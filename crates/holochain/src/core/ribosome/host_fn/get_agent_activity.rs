@@ -23,16 +23,19 @@ pub fn get_agent_activity(
                 agent_pubkey,
                 chain_query_filter,
                 activity_request,
+                read_source,
             } = input;
             let options = match activity_request {
                 ActivityRequest::Status => GetActivityOptions {
                     include_valid_activity: false,
                     include_rejected_activity: false,
+                    read_source,
                     ..Default::default()
                 },
                 ActivityRequest::Full => GetActivityOptions {
                     include_valid_activity: true,
                     include_rejected_activity: true,
+                    read_source,
                     ..Default::default()
                 },
             };
@@ -0,0 +1,121 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_p2p::DhtOpHashExt;
+use holochain_state::query::get_blob_chunk_from_db;
+use holochain_wasmer_host::prelude::*;
+use kitsune_p2p::dependencies::kitsune_p2p_fetch::OpHashSized;
+
+use holochain_types::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use wasmer::RuntimeError;
+
+/// How long to wait for a chunk to be fetched before giving up on it.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn get_blob_chunk(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    chunk_hash: EntryHash,
+) -> Result<Option<BlobChunk>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => {
+            let workspace = call_context.host_context.workspace();
+            // A chunk created earlier in this call isn't stored until the call's
+            // writes are flushed.
+            if let Some(source_chain) = workspace.source_chain().as_ref() {
+                if let Some(chunk) = source_chain.staged_blob_chunk(&chunk_hash).map_err(
+                    |source_chain_error| -> RuntimeError {
+                        wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+                    },
+                )? {
+                    return Ok(Some(BlobChunk(chunk)));
+                }
+            }
+            let (authored, dht, _) = workspace.databases();
+            let network = call_context.host_context.network();
+            // Subscribe before looking in the database, so a chunk which is
+            // integrated in between isn't missed.
+            let integrated_ops = call_context
+                .host_context
+                .maybe_call_zome_handle()
+                .map(|handle| handle.subscribe_integrated_ops());
+            tokio_helper::block_forever_on(async move {
+                let held = || {
+                    let chunk_hash = chunk_hash.clone();
+                    dht.read_async(move |txn| get_blob_chunk_from_db(&txn, &chunk_hash))
+                };
+                if let Some(op) = held().await? {
+                    return Ok(Some(BlobChunk(op.chunk)));
+                }
+                // The chunks this agent created are kept with its chain, even
+                // when this node isn't an authority for them.
+                let authored_chunk = authored
+                    .read_async({
+                        let chunk_hash = chunk_hash.clone();
+                        move |txn| get_blob_chunk_from_db(&txn, &chunk_hash)
+                    })
+                    .await?;
+                if let Some(op) = authored_chunk {
+                    return Ok(Some(BlobChunk(op.chunk)));
+                }
+                // Without a conductor to hear from, such as in a validation
+                // callback, only the chunks held on this node can be read.
+                let Some(mut integrated_ops) = integrated_ops else {
+                    return Ok(None);
+                };
+
+                // Chunks are only fetched when they are read. The op hash is derived
+                // from the chunk hash, so the fetch pool can ask the authorities for
+                // the chunk by hash and the op is validated like any other it receives.
+                let op_hash = BlobChunkOp::op_hash(&chunk_hash);
+                network
+                    .fetch_from_authorities(
+                        chunk_hash.clone().into(),
+                        vec![OpHashSized::new(op_hash.to_kitsune(), None)],
+                    )
+                    .await?;
+                let fetched = async {
+                    loop {
+                        match integrated_ops.recv().await {
+                            Ok(op) if op.op_hash == op_hash => {
+                                if let DhtOp::BlobChunkOp(op) = &op.op {
+                                    return Ok(Some(BlobChunk(op.chunk.clone())));
+                                }
+                            }
+                            Ok(_) => (),
+                            // The op may have been among the ones which were missed.
+                            Err(broadcast::error::RecvError::Lagged(_)) => {
+                                if let Some(op) = held().await? {
+                                    return Ok(Some(BlobChunk(op.chunk)));
+                                }
+                            }
+                            Err(broadcast::error::RecvError::Closed) => return Ok(None),
+                        }
+                    }
+                };
+                tokio::time::timeout(FETCH_TIMEOUT, fetched)
+                    .await
+                    .unwrap_or(Ok::<_, RibosomeError>(None))
+            })
+            .map_err(|ribosome_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(ribosome_error.to_string())).into()
+            })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "get_blob_chunk".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
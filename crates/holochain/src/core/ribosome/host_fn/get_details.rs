@@ -1,3 +1,4 @@
+use super::get::fill_warranted_authors;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::HostFnAccess;
 use crate::core::ribosome::RibosomeError;
@@ -13,13 +14,14 @@ use wasmer::RuntimeError;
 pub fn get_details<'a>(
     _ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
-    inputs: Vec<GetInput>,
+    mut inputs: Vec<GetInput>,
 ) -> Result<Vec<Option<Details>>, RuntimeError> {
     match HostFnAccess::from(&call_context.host_context()) {
         HostFnAccess {
             read_workspace: Permission::Allow,
             ..
         } => {
+            tokio_helper::block_forever_on(fill_warranted_authors(&call_context, &mut inputs))?;
             let results: Vec<Result<Option<Details>, _>> =
                 tokio_helper::block_forever_on(async move {
                     join_all(inputs.into_iter().map(|input| async {
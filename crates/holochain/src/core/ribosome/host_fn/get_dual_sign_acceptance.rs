@@ -0,0 +1,59 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_cascade::CascadeImpl;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn get_dual_sign_acceptance(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: GetInput,
+) -> Result<Option<Record>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => {
+            let GetInput {
+                any_dht_hash,
+                get_options,
+            } = input;
+            let proposal = any_dht_hash
+                .into_action_hash()
+                .ok_or_else(|| -> RuntimeError {
+                    wasm_error!(WasmErrorInner::Guest(
+                        "A dual signed entry proposal must be given by its action hash".to_string()
+                    ))
+                    .into()
+                })?;
+
+            // Get the network from the context
+            let network = call_context.host_context.network().clone();
+
+            // timeouts must be handled by the network
+            tokio_helper::block_forever_on(async move {
+                let workspace = call_context.host_context.workspace();
+                let cascade = CascadeImpl::from_workspace_and_network(&workspace, network);
+                cascade
+                    .get_dual_sign_acceptance(proposal, get_options)
+                    .await
+                    .map_err(|cascade_error| {
+                        wasm_error!(WasmErrorInner::Host(cascade_error.to_string())).into()
+                    })
+            })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "get_dual_sign_acceptance".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
@@ -0,0 +1,100 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_cascade::CascadeImpl;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn get_entries_by_author(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: GetEntriesByAuthorInput,
+) -> Result<Vec<Record>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => {
+            let GetEntriesByAuthorInput {
+                author,
+                entry_type,
+                page,
+                read_source,
+            } = input;
+
+            // Get the network from the context
+            let network = call_context.host_context.network().clone();
+
+            // timeouts must be handled by the network
+            tokio_helper::block_forever_on(async move {
+                let workspace = call_context.host_context.workspace();
+                let cascade = CascadeImpl::from_workspace_and_network(&workspace, network);
+                cascade
+                    .get_entries_by_author(author, entry_type, page, read_source)
+                    .await
+                    .map_err(|cascade_error| {
+                        wasm_error!(WasmErrorInner::Host(cascade_error.to_string())).into()
+                    })
+            })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "get_entries_by_author".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[derive(Serialize, Deserialize, SerializedBytes, Debug)]
+    struct Post(String);
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_get_entries_by_author() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            alice_pubkey,
+            bob_pubkey,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Create).await;
+
+        let first: ActionHash = conductor
+            .call(&alice, "create_post", Post("first".into()))
+            .await;
+        let _: ActionHash = conductor.call(&alice, "create_msg", ()).await;
+        let second: ActionHash = conductor
+            .call(&alice, "create_post", Post("second".into()))
+            .await;
+
+        let posts: Vec<Record> = conductor
+            .call(&alice, "get_posts_by_author", alice_pubkey)
+            .await;
+        assert_eq!(
+            posts
+                .iter()
+                .map(|record| record.action_address().clone())
+                .collect::<Vec<_>>(),
+            vec![first, second]
+        );
+
+        let posts: Vec<Record> = conductor
+            .call(&alice, "get_posts_by_author", bob_pubkey)
+            .await;
+        assert!(posts.is_empty());
+    }
+}
@@ -36,9 +36,12 @@ pub fn get_link_details<'a>(
                             base: base_address,
                             type_query: link_type,
                             tag: tag_prefix,
+                            tag_range: None,
+                            tag_fields: Vec::new(),
                             after: None,
                             before: None,
                             author: None,
+                            since: None,
                         };
                         Ok(CascadeImpl::from_workspace_and_network(
                             &call_context.host_context.workspace(),
@@ -35,20 +35,28 @@ pub fn get_links<'a>(
                                     link_type,
                                     get_options,
                                     tag_prefix,
+                                    tag_range,
+                                    tag_fields,
                                     after,
                                     before,
                                     author,
+                                    order,
+                                    since,
                                 } = input;
 
                                 let key = WireLinkKey {
                                     base: base_address,
                                     type_query: link_type,
                                     tag: tag_prefix,
+                                    tag_range,
+                                    tag_fields,
                                     after,
                                     before,
                                     author,
+                                    since,
                                 };
-                                Ok(CascadeImpl::from_workspace_and_network(
+                                // Links come back from the cascade in ascending order.
+                                let mut links = CascadeImpl::from_workspace_and_network(
                                     &call_context.host_context.workspace(),
                                     call_context.host_context.network().to_owned(),
                                 )
@@ -59,7 +67,11 @@ pub fn get_links<'a>(
                                         ..Default::default()
                                     },
                                 )
-                                .await?)
+                                .await?;
+                                if order == LinkOrder::Descending {
+                                    links.reverse();
+                                }
+                                Ok(links)
                             },
                         ),
                     )
@@ -449,6 +461,126 @@ pub mod slow_tests {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_links_filtered_by_tag_range() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::Link).await;
+
+        let mut hashes = Vec::new();
+        for tag in ["a", "b", "b.a", "c", "d"] {
+            let hash: ActionHash = conductor
+                .call(&alice, "create_tagged_link", tag.to_string())
+                .await;
+            hashes.push(hash);
+        }
+
+        // Get the base all the links are attached from
+        let base: AnyLinkableHash = conductor.call(&alice, "get_base_hash", ()).await;
+
+        let get_range = |start: &str, end: &str| {
+            GetLinksInputBuilder::try_new(
+                base.clone(),
+                LinkTypeFilter::Dependencies(vec![ZomeIndex(0)]),
+            )
+            .unwrap()
+            .tag_range(LinkTag::new(start), LinkTag::new(end))
+            .build()
+        };
+
+        // The start of the range is inclusive and the end is exclusive.
+        let links: Vec<Link> = conductor
+            .call(&alice, "get_links_with_query", get_range("b", "d"))
+            .await;
+        assert_eq!(
+            hashes[1..4].to_vec(),
+            links
+                .into_iter()
+                .map(|l| l.create_link_hash)
+                .collect::<Vec<ActionHash>>()
+        );
+
+        let links: Vec<Link> = conductor
+            .call(&alice, "get_links_with_query", get_range("e", "z"))
+            .await;
+        assert!(links.is_empty());
+
+        let count: usize = conductor
+            .call(
+                &alice,
+                "get_count",
+                LinkQuery::new(
+                    base.clone(),
+                    LinkTypeFilter::Dependencies(vec![ZomeIndex(0)]),
+                )
+                .tag_range(LinkTag::new("a"), LinkTag::new("b.a")),
+            )
+            .await;
+        assert_eq!(2, count);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_links_filtered_by_tag_payload_fields() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::Link).await;
+
+        let _: ActionHash = conductor
+            .call(&alice, "create_tagged_link", "weight".to_string())
+            .await;
+        let mut hashes = Vec::new();
+        for (weight, kind) in [(1, "up"), (5, "up"), (3, "down")] {
+            let payload = LinkTagPayload::new()
+                .with("weight", weight as i64)
+                .with("kind", kind);
+            let hash: ActionHash = conductor.call(&alice, "create_payload_link", payload).await;
+            hashes.push(hash);
+        }
+
+        let base: AnyLinkableHash = conductor.call(&alice, "get_base_hash", ()).await;
+        let get_fields = |filters: Vec<LinkTagFieldFilter>| {
+            filters
+                .into_iter()
+                .fold(
+                    GetLinksInputBuilder::try_new(
+                        base.clone(),
+                        LinkTypeFilter::Dependencies(vec![ZomeIndex(0)]),
+                    )
+                    .unwrap(),
+                    |builder, filter| builder.tag_field(filter),
+                )
+                .build()
+        };
+
+        let links: Vec<Link> = conductor
+            .call(
+                &alice,
+                "get_links_with_query",
+                get_fields(vec![
+                    LinkTagFieldFilter::Eq("kind".into(), "up".into()),
+                    LinkTagFieldFilter::Range("weight".into(), 2i64.into(), 10i64.into()),
+                ]),
+            )
+            .await;
+        assert_eq!(
+            vec![hashes[1].clone()],
+            links
+                .into_iter()
+                .map(|l| l.create_link_hash)
+                .collect::<Vec<ActionHash>>()
+        );
+
+        // The plain tag has no payload so it's only returned without field filters.
+        let links: Vec<Link> = conductor
+            .call(&alice, "get_links_with_query", get_fields(vec![]))
+            .await;
+        assert_eq!(4, links.len());
+        let payload = links[3].tag.payload().unwrap();
+        assert_eq!(Some(&LinkTagValue::Int(3)), payload.get("weight"));
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn get_links_filtered_by_timestamp_and_author() {
         holochain_trace::test_run();
@@ -0,0 +1,172 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use futures::future::join_all;
+use holochain_cascade::CascadeImpl;
+use holochain_p2p::actor::GetLinksOptions;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn get_links_delta(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    inputs: Vec<GetLinksInput>,
+) -> Result<Vec<LinksDelta>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => {
+            let results: Vec<Result<LinksDelta, RibosomeError>> =
+                tokio_helper::block_forever_on(async move {
+                    let call_context_iter = std::iter::from_fn(|| Some(call_context.clone()));
+                    join_all(std::iter::zip(inputs.into_iter(), call_context_iter).map(
+                        |(input, call_context)| async move {
+                            let GetLinksInput {
+                                base_address,
+                                link_type,
+                                get_options,
+                                tag_prefix,
+                                tag_range,
+                                tag_fields,
+                                after,
+                                before,
+                                author,
+                                since,
+                                // The links of a delta are always oldest first.
+                                order: _,
+                            } = input;
+
+                            let key = WireLinkKey {
+                                base: base_address,
+                                type_query: link_type,
+                                tag: tag_prefix,
+                                tag_range,
+                                tag_fields,
+                                after,
+                                before,
+                                author,
+                                since,
+                            };
+                            Ok(CascadeImpl::from_workspace_and_network(
+                                &call_context.host_context.workspace(),
+                                call_context.host_context.network().to_owned(),
+                            )
+                            .dht_get_links_delta(
+                                key,
+                                GetLinksOptions {
+                                    get_options,
+                                    ..Default::default()
+                                },
+                            )
+                            .await?)
+                        },
+                    ))
+                    .await
+                });
+            let results: Result<Vec<_>, RuntimeError> = results
+                .into_iter()
+                .map(|result| match result {
+                    Ok(delta) => Ok(delta),
+                    Err(cascade_error) => {
+                        Err(wasm_error!(WasmErrorInner::Host(cascade_error.to_string())).into())
+                    }
+                })
+                .collect();
+            Ok(results?)
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "get_links_delta".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod slow_tests {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use crate::sweettest::await_consistency;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_links_delta_only_returns_changes_since_watermark() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            alice_cell,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Link).await;
+
+        let hash_a: ActionHash = conductor
+            .call(&alice, "create_tagged_link", "a".to_string())
+            .await;
+        let hash_b: ActionHash = conductor
+            .call(&alice, "create_tagged_link", "b".to_string())
+            .await;
+        await_consistency(10, [&alice_cell]).await.unwrap();
+
+        let base: AnyLinkableHash = conductor.call(&alice, "get_base_hash", ()).await;
+        let get_delta = |since: Option<Timestamp>| {
+            let builder = GetLinksInputBuilder::try_new(
+                base.clone(),
+                LinkTypeFilter::Dependencies(vec![ZomeIndex(0)]),
+            )
+            .unwrap();
+            match since {
+                Some(since) => builder.since(since),
+                None => builder,
+            }
+            .build()
+        };
+        let created = |delta: &LinksDelta| {
+            delta
+                .created
+                .iter()
+                .map(|l| l.create_link_hash.clone())
+                .collect::<Vec<ActionHash>>()
+        };
+
+        let delta: LinksDelta = conductor
+            .call(&alice, "get_links_delta_with_query", get_delta(None))
+            .await;
+        assert_eq!(vec![hash_a.clone(), hash_b], created(&delta));
+        assert!(delta.deleted.is_empty());
+
+        let _: ActionHash = conductor.call(&alice, "delete_link", hash_a.clone()).await;
+        let hash_c: ActionHash = conductor
+            .call(&alice, "create_tagged_link", "c".to_string())
+            .await;
+        await_consistency(10, [&alice_cell]).await.unwrap();
+
+        let delta: LinksDelta = conductor
+            .call(
+                &alice,
+                "get_links_delta_with_query",
+                get_delta(Some(delta.watermark)),
+            )
+            .await;
+        assert_eq!(vec![hash_c], created(&delta));
+        assert_eq!(vec![hash_a], delta.deleted);
+
+        let delta: LinksDelta = conductor
+            .call(
+                &alice,
+                "get_links_delta_with_query",
+                get_delta(Some(delta.watermark)),
+            )
+            .await;
+        assert!(delta.created.is_empty());
+        assert!(delta.deleted.is_empty());
+    }
+}
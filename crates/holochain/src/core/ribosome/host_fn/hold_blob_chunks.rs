@@ -0,0 +1,40 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_wasmer_host::prelude::*;
+
+use holochain_types::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn hold_blob_chunks(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: HoldBlobChunksInput,
+) -> Result<(), RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            write_workspace: Permission::Allow,
+            ..
+        } => call_context
+            .host_context
+            .workspace_write()
+            .source_chain()
+            .as_ref()
+            .expect("Must have source chain if write_workspace access is given")
+            .hold_blob_chunks(input.manifest, input.chunks)
+            .map_err(|source_chain_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+            }),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "hold_blob_chunks".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
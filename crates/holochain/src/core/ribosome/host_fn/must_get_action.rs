@@ -1,3 +1,4 @@
+use crate::core::ribosome::guest_callback::validate::ValidateHostAccess;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::HostContext;
 use crate::core::ribosome::RibosomeError;
@@ -24,6 +25,14 @@ pub fn must_get_action<'a>(
         } => {
             let action_hash = input.into_inner();
 
+            if let HostContext::Validate(ValidateHostAccess { action_cache, .. }) =
+                &call_context.host_context
+            {
+                if let Some(action) = action_cache.get(&action_hash) {
+                    return Ok(action);
+                }
+            }
+
             // timeouts must be handled by the network
             tokio_helper::block_forever_on(async move {
                 let workspace = call_context.host_context.workspace();
@@ -42,9 +51,17 @@ pub fn must_get_action<'a>(
                     .map_err(|cascade_error| -> RuntimeError {
                         wasm_error!(WasmErrorInner::Host(cascade_error.to_string())).into()
                     })? {
-                    Some((action, _)) => Ok(action),
+                    Some((action, _)) => {
+                        if let HostContext::Validate(ValidateHostAccess { action_cache, .. }) =
+                            &call_context.host_context
+                        {
+                            action_cache.insert(action.clone());
+                        }
+                        Ok(action)
+                    }
                     None => match call_context.host_context {
-                        HostContext::EntryDefs(_)
+                        HostContext::DependencyHints(_)
+                        | HostContext::EntryDefs(_)
                         | HostContext::GenesisSelfCheckV1(_)
                         | HostContext::GenesisSelfCheckV2(_)
                         | HostContext::MigrateAgent(_)
@@ -43,7 +43,8 @@ pub fn must_get_entry<'a>(
                     })? {
                     Some((entry, _)) => Ok(entry),
                     None => match call_context.host_context {
-                        HostContext::EntryDefs(_)
+                        HostContext::DependencyHints(_)
+                        | HostContext::EntryDefs(_)
                         | HostContext::GenesisSelfCheckV1(_)
                         | HostContext::GenesisSelfCheckV2(_)
                         | HostContext::MigrateAgent(_)
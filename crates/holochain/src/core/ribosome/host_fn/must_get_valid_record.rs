@@ -47,7 +47,8 @@ pub fn must_get_valid_record<'a>(
                         ..
                     }) => Ok(record),
                     _ => match call_context.host_context {
-                        HostContext::EntryDefs(_)
+                        HostContext::DependencyHints(_)
+                        | HostContext::EntryDefs(_)
                         | HostContext::GenesisSelfCheckV1(_)
                         | HostContext::GenesisSelfCheckV2(_)
                         | HostContext::MigrateAgent(_)
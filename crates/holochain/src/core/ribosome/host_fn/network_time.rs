@@ -0,0 +1,59 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::access::Permission;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn network_time(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    _input: (),
+) -> Result<NetworkTime, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            non_determinism: Permission::Allow,
+            ..
+        } => {
+            let local = Timestamp::now();
+            let network = call_context.host_context.network();
+            let offset = tokio_helper::block_forever_on(async move {
+                network.network_time_offset().await
+            })
+            .map_err(|e| -> RuntimeError { wasm_error!(WasmErrorInner::Host(e.to_string())).into() })?;
+            Ok(NetworkTime { local, offset })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "network_time".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use holochain_wasm_test_utils::TestWasm;
+    use holochain_zome_types::prelude::NetworkTime;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn invoke_import_network_time_test() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::SysTime).await;
+        let network_time: NetworkTime = conductor.call(&alice, "network_time", ()).await;
+        // A lone conductor has no peers to estimate the offset from.
+        assert_eq!(None, network_time.offset);
+        assert_eq!(network_time.local, network_time.network_now());
+    }
+}
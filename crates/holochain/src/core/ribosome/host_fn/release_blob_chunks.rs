@@ -0,0 +1,42 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_wasmer_host::prelude::*;
+
+use holochain_types::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn release_blob_chunks(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    manifest: ActionHash,
+) -> Result<(), RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            write_workspace: Permission::Allow,
+            ..
+        } => call_context
+            .host_context
+            .workspace_write()
+            .source_chain()
+            .as_ref()
+            .expect("Must have source chain if write_workspace access is given")
+            // Chunks which no manifest holds any more are purged when the
+            // call's writes are flushed.
+            .release_blob_chunks(manifest)
+            .map_err(|source_chain_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+            }),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "release_blob_chunks".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
@@ -0,0 +1,157 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_nonce::fresh_nonce;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn remote_query(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: RemoteQuery,
+) -> Result<ZomeCallResponse, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            write_network: Permission::Allow,
+            agent_info: Permission::Allow,
+            ..
+        } => {
+            let RemoteQuery {
+                agent,
+                zome_name,
+                fn_name,
+                cap_secret,
+                payload,
+            } = input;
+            let provenance = call_context
+                .host_context
+                .workspace()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain to know provenance")
+                .agent_pubkey()
+                .clone();
+            let (nonce, expires_at) = fresh_nonce(Timestamp::now()).map_err(|e| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(e.to_string())).into()
+            })?;
+            let zome_call_unsigned = ZomeCallUnsigned {
+                provenance: provenance.clone(),
+                cell_id: CellId::new(ribosome.dna_def().as_hash().clone(), agent.clone()),
+                zome_name,
+                fn_name,
+                cap_secret,
+                payload,
+                nonce,
+                expires_at,
+                wait_for_publish: None,
+            };
+            tokio_helper::block_forever_on(async move {
+                let signature = zome_call_unsigned
+                    .provenance
+                    .sign_raw(
+                        call_context.host_context.keystore(),
+                        zome_call_unsigned
+                            .data_to_sign()
+                            .map_err(|e| -> RuntimeError { wasm_error!(e.to_string()).into() })?,
+                    )
+                    .await
+                    .map_err(|e| -> RuntimeError {
+                        wasm_error!(WasmErrorInner::Host(e.to_string())).into()
+                    })?;
+                match call_context
+                    .host_context()
+                    .network()
+                    .remote_query(
+                        provenance,
+                        signature,
+                        agent,
+                        zome_call_unsigned.zome_name,
+                        zome_call_unsigned.fn_name,
+                        zome_call_unsigned.cap_secret,
+                        zome_call_unsigned.payload,
+                        zome_call_unsigned.nonce,
+                        zome_call_unsigned.expires_at,
+                    )
+                    .await
+                {
+                    Ok(serialized_bytes) => ZomeCallResponse::try_from(serialized_bytes)
+                        .map_err(|e| -> RuntimeError { wasm_error!(e).into() }),
+                    Err(e) => Ok(ZomeCallResponse::NetworkError(e.to_string())),
+                }
+            })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "remote_query".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+    use matches::assert_matches;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn remote_query_requires_a_query_grant() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            bob,
+            bob_pubkey,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::WhoAmI).await;
+
+        // A grant for regular calls does not make a function queryable.
+        let _: () = conductor.call(&bob, "set_access", ()).await;
+        let response: ZomeCallResponse = conductor
+            .call(&alice, "query_whoami", bob_pubkey.clone())
+            .await;
+        assert_matches!(response, ZomeCallResponse::Unauthorized(..));
+
+        let _: () = conductor.call(&bob, "set_query_access", ()).await;
+        let response: ZomeCallResponse = conductor
+            .call(&alice, "query_whoami", bob_pubkey.clone())
+            .await;
+        let agent_info: AgentInfo = match response {
+            ZomeCallResponse::Ok(io) => io.decode().unwrap(),
+            other => panic!("unexpected response {:?}", other),
+        };
+        assert_eq!(agent_info.agent_initial_pubkey, bob_pubkey);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn remote_query_cannot_write() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            bob,
+            bob_pubkey,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::WhoAmI).await;
+
+        let _: () = conductor.call(&bob, "set_query_access", ()).await;
+        let before: Vec<Record> = conductor.call(&bob, "query_own_chain", ()).await;
+
+        let response: ZomeCallResponse = conductor
+            .call(&alice, "query_set_access", bob_pubkey.clone())
+            .await;
+        assert_matches!(response, ZomeCallResponse::NetworkError(..));
+
+        let after: Vec<Record> = conductor.call(&bob, "query_own_chain", ()).await;
+        assert_eq!(before.len(), after.len());
+    }
+}
@@ -0,0 +1,107 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_wasmer_host::prelude::*;
+
+use holochain_types::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn renew_membrane_proof(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: RenewMembraneProofInput,
+) -> Result<ActionHash, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            write_workspace: Permission::Allow,
+            ..
+        } => {
+            let action_hash = tokio_helper::block_forever_on(tokio::task::spawn(async move {
+                // push the renewal into the source chain, superseding the current proof
+                let action_hash = call_context
+                    .host_context
+                    .workspace_write()
+                    .source_chain()
+                    .as_ref()
+                    .expect("Must have source chain if write_workspace access is given")
+                    .put_membrane_proof_renewal(input.membrane_proof, ChainTopOrdering::Strict)
+                    .await?;
+                Ok::<ActionHash, RibosomeError>(action_hash)
+            }))
+            .map_err(|join_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(join_error.to_string())).into()
+            })?
+            .map_err(|ribosome_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(ribosome_error.to_string())).into()
+            })?;
+
+            // Return the hash of the renewal
+            Ok(action_hash)
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "renew_membrane_proof".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::renew_membrane_proof;
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use crate::fixt::{CallContextFixturator, RealRibosomeFixturator};
+    use ::fixt::prelude::*;
+    use holochain_util::tokio_helper;
+    use holochain_wasm_test_utils::{TestWasm, TestWasmPair};
+    use holochain_zome_types::prelude::*;
+    use matches::assert_matches;
+    use std::sync::Arc;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn call_renew_membrane_proof() {
+        // Note that any zome will do here, we're not calling its functions!
+        let ribosome =
+            RealRibosomeFixturator::new(crate::fixt::curve::Zomes(vec![TestWasm::Create]))
+                .next()
+                .unwrap();
+        let mut call_context = CallContextFixturator::new(Unpredictable).next().unwrap();
+        call_context.zome = TestWasmPair::<IntegrityZome, CoordinatorZome>::from(TestWasm::Create)
+            .coordinator
+            .erase_type();
+        let host_access = fixt!(ZomeCallHostAccess, Predictable);
+        let host_access_2 = host_access.clone();
+        call_context.host_context = host_access.into();
+        let input = RenewMembraneProofInput {
+            membrane_proof: Arc::new(fixt!(SerializedBytes)),
+        };
+
+        let output =
+            renew_membrane_proof(Arc::new(ribosome), Arc::new(call_context), input).unwrap();
+
+        // the renewal should supersede the agent validation package from genesis
+        let (chain_head, pkg) = tokio_helper::block_forever_on(async move {
+            let source_chain = host_access_2.workspace.source_chain().clone().unwrap();
+            let pkg = source_chain
+                .query(ChainQueryFilter::new().action_type(ActionType::AgentValidationPkg))
+                .await
+                .unwrap()[0]
+                .action_address()
+                .clone();
+            let chain_head = source_chain.query(ChainQueryFilter::new()).await.unwrap();
+            (chain_head.last().unwrap().action().clone(), pkg)
+        });
+
+        assert_eq!(chain_head.to_hash(), output);
+        assert_matches!(
+            chain_head,
+            Action::RenewMembraneProof(RenewMembraneProof { supersedes, .. }) if supersedes == pkg
+        );
+    }
+}
@@ -195,6 +195,60 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "mock_clock")]
+    async fn schedule_test_advance_time() -> anyhow::Result<()> {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            alice_pubkey,
+            alice_host_fn_caller,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Schedule).await;
+
+        // Only dispatch scheduled functions when the clock is advanced.
+        conductor
+            .raw_handle()
+            .start_scheduler(std::time::Duration::from_millis(1000_000_000))
+            .await?;
+
+        // Running a zome call runs init, which schedules the tock.
+        let query_tock_init: Vec<Record> = conductor.call(&alice, "query_tock_init", ()).await;
+        assert!(query_tock_init.is_empty());
+        let persisted_scheduled_fn =
+            ScheduledFn::new(TestWasm::Schedule.into(), "cron_scheduled_fn_init".into());
+        while !alice_host_fn_caller
+            .authored_db
+            .read_async({
+                let alice_pubkey = alice_pubkey.clone();
+                let persisted_scheduled_fn = persisted_scheduled_fn.clone();
+                move |txn| fn_is_scheduled(&txn, persisted_scheduled_fn, &alice_pubkey)
+            })
+            .await?
+        {
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        }
+
+        // Moving the clock forward runs the tock without waiting for it.
+        conductor
+            .advance_time(std::time::Duration::from_secs(2))
+            .await;
+        tokio::time::timeout(std::time::Duration::from_secs(10), async {
+            loop {
+                let query_tock_init: Vec<Record> =
+                    conductor.call(&alice, "query_tock_init", ()).await;
+                if !query_tock_init.is_empty() {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+            }
+        })
+        .await?;
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     #[cfg(feature = "test_utils")]
     async fn schedule_test_wasm() -> anyhow::Result<()> {
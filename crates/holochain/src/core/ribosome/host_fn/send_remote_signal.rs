@@ -30,8 +30,9 @@ pub fn send_remote_signal(
             ..
         } => {
             const FN_NAME: &str = "recv_remote_signal";
-            let from_agent = super::agent_info::agent_info(_ribosome, call_context.clone(), ())?
-                .agent_latest_pubkey;
+            let from_agent =
+                super::agent_info_1::agent_info_1(_ribosome, call_context.clone(), ())?
+                    .agent_latest_pubkey;
             // Timeouts and errors are ignored,
             // this is a send and forget operation.
             let network = call_context.host_context().network().clone();
@@ -61,6 +62,7 @@ pub fn send_remote_signal(
                             payload: signal.clone(),
                             nonce,
                             expires_at,
+                            wait_for_publish: None,
                         };
                         let potentially_signature = zome_call_unsigned.provenance.sign_raw(call_context.host_context.keystore(), match zome_call_unsigned.data_to_sign() {
                             Ok(to_sign) => to_sign,
@@ -57,13 +57,11 @@ pub mod test {
                 EntryDef {
                     id: "post".into(),
                     visibility: Default::default(),
-                    required_validations: Default::default(),
                     ..Default::default()
                 },
                 EntryDef {
                     id: "comment".into(),
                     visibility: EntryVisibility::Private,
-                    required_validations: Default::default(),
                     ..Default::default()
                 }
             ]
@@ -1,3 +1,4 @@
+use super::guest_callback::dependency_hints::DependencyHintsHostAccess;
 use super::guest_callback::entry_defs::EntryDefsHostAccess;
 use super::guest_callback::init::InitHostAccess;
 use super::guest_callback::migrate_agent::MigrateAgentHostAccess;
@@ -6,12 +7,16 @@ use super::guest_callback::validate::ValidateHostAccess;
 use super::host_fn::delete_clone_cell::delete_clone_cell;
 use super::host_fn::disable_clone_cell::disable_clone_cell;
 use super::host_fn::enable_clone_cell::enable_clone_cell;
+use super::host_fn::get_action_by_seq::get_action_by_seq;
 use super::host_fn::get_agent_activity::get_agent_activity;
+use super::host_fn::get_entries_by_author::get_entries_by_author;
 use super::host_fn::HostFnApi;
 use super::HostContext;
 use super::ZomeCallHostAccess;
 use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::error::RibosomeResult;
+use crate::core::ribosome::guest_callback::dependency_hints::DependencyHintsInvocation;
+use crate::core::ribosome::guest_callback::dependency_hints::DependencyHintsResult;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsInvocation;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsResult;
 use crate::core::ribosome::guest_callback::genesis_self_check::v1::GenesisSelfCheckInvocationV1;
@@ -29,35 +34,50 @@ use crate::core::ribosome::guest_callback::validate::ValidateInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateResult;
 use crate::core::ribosome::guest_callback::CallIterator;
 use crate::core::ribosome::host_fn::accept_countersigning_preflight_request::accept_countersigning_preflight_request;
-use crate::core::ribosome::host_fn::agent_info::agent_info;
+use crate::core::ribosome::host_fn::agent_info_1::agent_info_1;
+use crate::core::ribosome::host_fn::agent_info_2::agent_info_2;
 use crate::core::ribosome::host_fn::block_agent::block_agent;
 use crate::core::ribosome::host_fn::call::call;
 use crate::core::ribosome::host_fn::call_info::call_info;
+use crate::core::ribosome::host_fn::call_remote_durable::call_remote_durable;
 use crate::core::ribosome::host_fn::capability_claims::capability_claims;
 use crate::core::ribosome::host_fn::capability_grants::capability_grants;
 use crate::core::ribosome::host_fn::capability_info::capability_info;
 use crate::core::ribosome::host_fn::create::create;
+use crate::core::ribosome::host_fn::create_blob_chunk::create_blob_chunk;
 use crate::core::ribosome::host_fn::create_clone_cell::create_clone_cell;
 use crate::core::ribosome::host_fn::create_link::create_link;
+use crate::core::ribosome::host_fn::create_links::create_links;
 use crate::core::ribosome::host_fn::create_x25519_keypair::create_x25519_keypair;
 use crate::core::ribosome::host_fn::delete::delete;
 use crate::core::ribosome::host_fn::delete_link::delete_link;
+use crate::core::ribosome::host_fn::delete_links::delete_links;
 use crate::core::ribosome::host_fn::dna_info_1::dna_info_1;
 use crate::core::ribosome::host_fn::dna_info_2::dna_info_2;
 use crate::core::ribosome::host_fn::ed_25519_x_salsa20_poly1305_decrypt::ed_25519_x_salsa20_poly1305_decrypt;
 use crate::core::ribosome::host_fn::ed_25519_x_salsa20_poly1305_encrypt::ed_25519_x_salsa20_poly1305_encrypt;
 use crate::core::ribosome::host_fn::emit_signal::emit_signal;
+use crate::core::ribosome::host_fn::ensure_entry::ensure_entry;
+use crate::core::ribosome::host_fn::ensure_link::ensure_link;
+use crate::core::ribosome::host_fn::entry_type_metrics::entry_type_metrics;
 use crate::core::ribosome::host_fn::get::get;
+use crate::core::ribosome::host_fn::get_blob_chunk::get_blob_chunk;
 use crate::core::ribosome::host_fn::get_details::get_details;
+use crate::core::ribosome::host_fn::get_dual_sign_acceptance::get_dual_sign_acceptance;
 use crate::core::ribosome::host_fn::get_link_details::get_link_details;
 use crate::core::ribosome::host_fn::get_links::get_links;
+use crate::core::ribosome::host_fn::get_links_delta::get_links_delta;
 use crate::core::ribosome::host_fn::hash::hash;
+use crate::core::ribosome::host_fn::hold_blob_chunks::hold_blob_chunks;
 use crate::core::ribosome::host_fn::must_get_action::must_get_action;
 use crate::core::ribosome::host_fn::must_get_agent_activity::must_get_agent_activity;
 use crate::core::ribosome::host_fn::must_get_entry::must_get_entry;
 use crate::core::ribosome::host_fn::must_get_valid_record::must_get_valid_record;
+use crate::core::ribosome::host_fn::network_time::network_time;
 use crate::core::ribosome::host_fn::query::query;
 use crate::core::ribosome::host_fn::random_bytes::random_bytes;
+use crate::core::ribosome::host_fn::release_blob_chunks::release_blob_chunks;
+use crate::core::ribosome::host_fn::remote_query::remote_query;
 use crate::core::ribosome::host_fn::schedule::schedule;
 use crate::core::ribosome::host_fn::send_remote_signal::send_remote_signal;
 use crate::core::ribosome::host_fn::sign::sign;
@@ -104,13 +124,16 @@ use wasmer::Type;
 use crate::core::ribosome::host_fn::close_chain::close_chain;
 use crate::core::ribosome::host_fn::count_links::count_links;
 use crate::core::ribosome::host_fn::open_chain::open_chain;
+use crate::core::ribosome::host_fn::renew_membrane_proof::renew_membrane_proof;
 use crate::holochain_wasmer_host::module::WASM_METERING_LIMIT;
+use holochain_types::zome_types::resolve_zome_dependencies;
 use holochain_types::zome_types::GlobalZomeTypes;
 use holochain_types::zome_types::ZomeTypesError;
 use holochain_wasmer_host::prelude::*;
 use once_cell::sync::Lazy;
 use opentelemetry_api::global::meter_with_version;
 use opentelemetry_api::metrics::Counter;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
@@ -140,6 +163,14 @@ pub struct RealRibosome {
 
     /// File system and in-memory cache for wasm modules.
     pub wasmer_module_cache: Arc<ModuleCacheLock>,
+
+    /// The classes of host functions each zome may use, if the apps
+    /// using this DNA restrict them.
+    pub host_fn_allowlist: Option<Arc<HostFnAllowlist>>,
+
+    /// The functions each coordinator zome exposes to zome calls,
+    /// read from the zome's exports when the ribosome is created.
+    pub zome_fns: Arc<BTreeMap<ZomeName, Vec<FunctionName>>>,
 }
 
 type ContextMap = Lazy<Arc<Mutex<HashMap<u64, Arc<CallContext>>>>>;
@@ -151,6 +182,32 @@ static CONTEXT_MAP: ContextMap = Lazy::new(Default::default);
 // host fn calls.
 static CONTEXT_KEY: AtomicU64 = AtomicU64::new(0);
 
+/// Roughly how many metering points a wasm uses in a millisecond.
+///
+/// Wasmer can't interrupt a running instance from outside, so a zome call
+/// with a deadline is given only as many points as it would use before the
+/// deadline. A call which spins without calling a host function then traps
+/// when it runs out of points, near its deadline, rather than running on
+/// until the metering limit.
+const METERING_POINTS_PER_MS: u64 = 1_000_000;
+
+/// The metering points a call may use, and the time it was given them for
+/// if that is less than the metering limit.
+fn metering_limit(deadline: Option<std::time::Instant>) -> (u64, Option<std::time::Duration>) {
+    let Some(deadline) = deadline else {
+        return (WASM_METERING_LIMIT, None);
+    };
+    let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+    let points = u64::try_from(remaining.as_millis())
+        .unwrap_or(u64::MAX)
+        .saturating_mul(METERING_POINTS_PER_MS);
+    if points < WASM_METERING_LIMIT {
+        (points, Some(remaining))
+    } else {
+        (WASM_METERING_LIMIT, None)
+    }
+}
+
 struct HostFnBuilder {
     store: Arc<Mutex<Store>>,
     function_env: FunctionEnv<Env>,
@@ -171,6 +228,11 @@ impl HostFnBuilder {
     {
         let ribosome_arc = Arc::clone(&self.ribosome_arc);
         let context_key = self.context_key;
+        // Every host function must be classified, so that an allowlist can't be
+        // bypassed by a host function which was added without a class.
+        let host_fn_class = HostFnClass::of_host_fn(host_function_name)
+            .unwrap_or_else(|| panic!("Host function {host_function_name} has no class"));
+        let host_fn_name = host_function_name.to_string();
         {
             let mut store_lock = self.store.lock();
             let mut store_mut = store_lock.as_store_mut();
@@ -192,8 +254,34 @@ impl HostFnBuilder {
                                 })
                                 .clone()
                         };
+                        // Cancel the call once its deadline has passed by trapping
+                        // on the next host function call.
+                        if context_arc
+                            .host_context()
+                            .deadline()
+                            .is_some_and(|deadline| std::time::Instant::now() >= deadline)
+                        {
+                            return Err(wasm_error!(WasmErrorInner::Host(
+                                "The zome call timed out".into()
+                            ))
+                            .into());
+                        }
                         let (env, mut store_mut) = function_env_mut.data_and_store_mut();
+                        let zome_name = context_arc.zome.zome_name();
                         let result = match env.consume_bytes_from_guest(&mut store_mut, guest_ptr, len) {
+                            // The allowlist is enforced for every host function of a class,
+                            // before the host function itself gets to run.
+                            Ok(_) if !ribosome_arc.allows_host_fn(zome_name, host_fn_class) => {
+                                Err(wasm_error!(WasmErrorInner::Host(
+                                    RibosomeError::HostFnNotAllowed(
+                                        zome_name.clone(),
+                                        host_fn_class,
+                                        host_fn_name.clone(),
+                                    )
+                                    .to_string()
+                                ))
+                                .into())
+                            }
                             Ok(input) => host_function(Arc::clone(&ribosome_arc), context_arc, input),
                             Err(runtime_error) => Result::<_, RuntimeError>::Err(runtime_error),
                         };
@@ -248,6 +336,8 @@ impl RealRibosome {
             zome_dependencies: Default::default(),
             usage_meter: Self::standard_usage_meter(),
             wasmer_module_cache,
+            host_fn_allowlist: None,
+            zome_fns: Default::default(),
         };
 
         // Collect the number of entry and link types
@@ -288,52 +378,57 @@ impl RealRibosome {
 
         ribosome.zome_types = Arc::new(map?);
 
-        // Create a map of integrity zome names to ZomeIndexes.
-        let integrity_zomes: HashMap<_, _> = ribosome
-            .dna_def()
-            .integrity_zomes
-            .iter()
-            .enumerate()
-            .map(|(i, (n, _))| Some((n.clone(), ZomeIndex(i.try_into().ok()?))))
-            .collect::<Option<_>>()
-            .ok_or(ZomeTypesError::ZomeIndexOverflow)?;
-
-        // Collect the dependencies for each zome.
-        ribosome.zome_dependencies = ribosome
-            .dna_def()
-            .all_zomes()
-            .map(|(zome_name, def)| {
-                let mut dependencies = Vec::new();
-
-                if integrity_zomes.len() == 1 {
-                    // If there's only one integrity zome we add it to this zome and are done.
-                    dependencies.push(ZomeIndex(0));
-                } else {
-                    // Integrity zomes need to have themselves as a dependency.
-                    if ribosome.dna_def().is_integrity_zome(zome_name) {
-                        // Get the ZomeIndex for this zome.
-                        let id = integrity_zomes.get(zome_name).copied().ok_or_else(|| {
-                            ZomeTypesError::MissingDependenciesForZome(zome_name.clone())
-                        })?;
-                        dependencies.push(id);
-                    }
-                    for name in def.dependencies() {
-                        // Get the ZomeIndex for this dependency.
-                        let id = integrity_zomes.get(name).copied().ok_or_else(|| {
-                            ZomeTypesError::MissingDependenciesForZome(zome_name.clone())
-                        })?;
-                        dependencies.push(id);
-                    }
+        // Collect the dependencies for each zome, including the types re-exported
+        // by the integrity zomes they depend on.
+        ribosome.zome_dependencies = resolve_zome_dependencies(ribosome.dna_def())?.into();
+
+        // Collect the functions each coordinator zome exposes, so they can be
+        // listed without compiling the zomes again.
+        let mut zome_fns = BTreeMap::new();
+        for (zome_name, def) in ribosome.dna_def().coordinator_zomes.iter() {
+            let zome = Zome::new(zome_name.clone(), def.clone().erase_type());
+            let fns = match zome.zome_def() {
+                ZomeDef::Wasm(_) => {
+                    let module = ribosome.get_module_for_zome(&zome).await?;
+                    module
+                        .exports()
+                        .functions()
+                        .map(|f| FunctionName::new(f.name()))
+                        .collect()
                 }
-
-                Ok((zome_name.clone(), dependencies))
-            })
-            .collect::<RibosomeResult<HashMap<_, _>>>()?
-            .into();
+                ZomeDef::Inline { inline_zome, .. } => inline_zome.0.functions(),
+            };
+            let mut fns: Vec<_> = fns
+                .into_iter()
+                .filter(|f| !Self::is_callback_fn(f))
+                .collect();
+            fns.sort();
+            zome_fns.insert(zome_name.clone(), fns);
+        }
+        ribosome.zome_fns = Arc::new(zome_fns);
 
         Ok(ribosome)
     }
 
+    /// Whether a zome export is a callback or an export needed by the host
+    /// rather than a function which can be called with a zome call.
+    fn is_callback_fn(name: &FunctionName) -> bool {
+        const CALLBACKS: &[&str] = &[
+            "init",
+            "post_commit",
+            "validate",
+            "entry_defs",
+            "dependency_hints",
+            "migrate_agent",
+            "migrate_agent_open",
+            "migrate_agent_close",
+            "genesis_self_check",
+            "genesis_self_check_1",
+            "genesis_self_check_2",
+        ];
+        name.0.starts_with("__") || CALLBACKS.contains(&name.0.as_str())
+    }
+
     #[cfg(any(test, feature = "test_utils"))]
     pub fn empty(dna_file: DnaFile) -> Self {
         Self {
@@ -342,6 +437,8 @@ impl RealRibosome {
             zome_dependencies: Default::default(),
             usage_meter: Self::standard_usage_meter(),
             wasmer_module_cache: Arc::new(ModuleCacheLock::new(ModuleCache::new(None))),
+            host_fn_allowlist: None,
+            zome_fns: Default::default(),
         }
     }
 
@@ -371,6 +468,30 @@ impl RealRibosome {
         Ok(key)
     }
 
+    /// Check that the given zomes can be run before this ribosome is put to use.
+    ///
+    /// Each wasm zome is compiled and instantiated, which fails if the wasm is
+    /// invalid or imports host functions this conductor doesn't provide, and then
+    /// the `entry_defs` callback is run. No zome functions or other callbacks are
+    /// called, as they need a cell to run in, see `Cell::smoke_test_init`.
+    pub async fn smoke_test_zomes(&self, zome_names: &[ZomeName]) -> RibosomeResult<()> {
+        for zome_name in zome_names {
+            let zome = self.dna_def().get_zome(zome_name).map_err(DnaError::from)?;
+            if let ZomeDef::Wasm(_) = zome.zome_def() {
+                let module = self.get_module_for_zome(&zome).await?;
+                // Nothing is called on the instance, so it doesn't need a call context.
+                self.build_instance_with_store(module, Self::next_context_key())?;
+            }
+        }
+
+        let ribosome = self.clone();
+        tokio::task::spawn_blocking(move || {
+            ribosome.run_entry_defs(EntryDefsHostAccess, EntryDefsInvocation)
+        })
+        .await??;
+        Ok(())
+    }
+
     #[tracing::instrument(skip_all)]
     pub async fn get_module_for_zome(&self, zome: &Zome<ZomeDef>) -> RibosomeResult<Arc<Module>> {
         match &zome.def {
@@ -502,7 +623,8 @@ impl RealRibosome {
                 "__hc__accept_countersigning_preflight_request_1",
                 accept_countersigning_preflight_request,
             )
-            .with_host_function(&mut ns, "__hc__agent_info_1", agent_info)
+            .with_host_function(&mut ns, "__hc__agent_info_1", agent_info_1)
+            .with_host_function(&mut ns, "__hc__agent_info_2", agent_info_2)
             .with_host_function(&mut ns, "__hc__block_agent_1", block_agent)
             .with_host_function(&mut ns, "__hc__unblock_agent_1", unblock_agent)
             .with_host_function(&mut ns, "__hc__trace_1", trace)
@@ -567,16 +689,30 @@ impl RealRibosome {
             .with_host_function(&mut ns, "__hc__call_info_1", call_info)
             .with_host_function(&mut ns, "__hc__random_bytes_1", random_bytes)
             .with_host_function(&mut ns, "__hc__sys_time_1", sys_time)
+            .with_host_function(&mut ns, "__hc__network_time_1", network_time)
+            .with_host_function(&mut ns, "__hc__entry_type_metrics_1", entry_type_metrics)
             .with_host_function(&mut ns, "__hc__sleep_1", sleep)
             .with_host_function(&mut ns, "__hc__capability_claims_1", capability_claims)
             .with_host_function(&mut ns, "__hc__capability_grants_1", capability_grants)
             .with_host_function(&mut ns, "__hc__capability_info_1", capability_info)
             .with_host_function(&mut ns, "__hc__get_1", get)
             .with_host_function(&mut ns, "__hc__get_details_1", get_details)
+            .with_host_function(
+                &mut ns,
+                "__hc__get_dual_sign_acceptance_1",
+                get_dual_sign_acceptance,
+            )
             .with_host_function(&mut ns, "__hc__get_links_1", get_links)
             .with_host_function(&mut ns, "__hc__get_link_details_1", get_link_details)
+            .with_host_function(&mut ns, "__hc__get_links_delta_1", get_links_delta)
             .with_host_function(&mut ns, "__hc__count_links_1", count_links)
             .with_host_function(&mut ns, "__hc__get_agent_activity_1", get_agent_activity)
+            .with_host_function(&mut ns, "__hc__get_action_by_seq_1", get_action_by_seq)
+            .with_host_function(
+                &mut ns,
+                "__hc__get_entries_by_author_1",
+                get_entries_by_author,
+            )
             .with_host_function(&mut ns, "__hc__must_get_entry_1", must_get_entry)
             .with_host_function(&mut ns, "__hc__must_get_action_1", must_get_action)
             .with_host_function(
@@ -591,11 +727,17 @@ impl RealRibosome {
             )
             .with_host_function(&mut ns, "__hc__query_1", query)
             .with_host_function(&mut ns, "__hc__send_remote_signal_1", send_remote_signal)
+            .with_host_function(&mut ns, "__hc__remote_query_1", remote_query)
+            .with_host_function(&mut ns, "__hc__call_remote_durable_1", call_remote_durable)
             .with_host_function(&mut ns, "__hc__call_1", call)
             .with_host_function(&mut ns, "__hc__create_1", create)
             .with_host_function(&mut ns, "__hc__emit_signal_1", emit_signal)
             .with_host_function(&mut ns, "__hc__create_link_1", create_link)
+            .with_host_function(&mut ns, "__hc__create_links_1", create_links)
             .with_host_function(&mut ns, "__hc__delete_link_1", delete_link)
+            .with_host_function(&mut ns, "__hc__delete_links_1", delete_links)
+            .with_host_function(&mut ns, "__hc__ensure_entry_1", ensure_entry)
+            .with_host_function(&mut ns, "__hc__ensure_link_1", ensure_link)
             .with_host_function(&mut ns, "__hc__update_1", update)
             .with_host_function(&mut ns, "__hc__delete_1", delete)
             .with_host_function(&mut ns, "__hc__schedule_1", schedule)
@@ -605,13 +747,29 @@ impl RealRibosome {
             .with_host_function(&mut ns, "__hc__enable_clone_cell_1", enable_clone_cell)
             .with_host_function(&mut ns, "__hc__delete_clone_cell_1", delete_clone_cell)
             .with_host_function(&mut ns, "__hc__close_chain_1", close_chain)
-            .with_host_function(&mut ns, "__hc__open_chain_1", open_chain);
+            .with_host_function(&mut ns, "__hc__open_chain_1", open_chain)
+            .with_host_function(
+                &mut ns,
+                "__hc__renew_membrane_proof_1",
+                renew_membrane_proof,
+            )
+            .with_host_function(&mut ns, "__hc__create_blob_chunk_1", create_blob_chunk)
+            .with_host_function(&mut ns, "__hc__get_blob_chunk_1", get_blob_chunk)
+            .with_host_function(&mut ns, "__hc__hold_blob_chunks_1", hold_blob_chunks)
+            .with_host_function(&mut ns, "__hc__release_blob_chunks_1", release_blob_chunks);
 
         imports.register_namespace("env", ns);
 
         (host_fn_builder.function_env, imports)
     }
 
+    /// Whether the zome may call a host function of this class.
+    pub fn allows_host_fn(&self, zome_name: &ZomeName, class: HostFnClass) -> bool {
+        self.host_fn_allowlist
+            .as_ref()
+            .map_or(true, |allowlist| allowlist.allows(zome_name, class))
+    }
+
     pub fn get_zome_dependencies(&self, zome_name: &ZomeName) -> RibosomeResult<&[ZomeIndex]> {
         Ok(self
             .zome_dependencies
@@ -834,6 +992,7 @@ impl RibosomeT for RealRibosome {
             otel_info.push(opentelemetry_api::KeyValue::new("agent", agent_pubkey));
         }
 
+        let deadline = host_context.deadline();
         let call_context = CallContext {
             zome: zome.clone(),
             function_name: fn_name.clone(),
@@ -857,17 +1016,14 @@ impl RibosomeT for RealRibosome {
                     }
 
                     let instance = instance_with_store.instance.clone();
+                    let (points_limit, deadline_budget) = metering_limit(deadline);
                     {
                         let mut store_lock = instance_with_store.store.lock();
                         let mut store_mut = store_lock.as_store_mut();
-                        set_remaining_points(
-                            &mut store_mut,
-                            instance.as_ref(),
-                            WASM_METERING_LIMIT,
-                        );
+                        set_remaining_points(&mut store_mut, instance.as_ref(), points_limit);
                     }
 
-                    let result = self
+                    let mut result = self
                         .call_zome_fn::<I>(invocation, zome, fn_name, instance_with_store.clone())
                         .map(Some);
 
@@ -876,8 +1032,18 @@ impl RibosomeT for RealRibosome {
                         let mut store_mut = store_lock.as_store_mut();
                         let points_used =
                             match get_remaining_points(&mut store_mut, instance.as_ref()) {
-                                MeteringPoints::Remaining(points) => WASM_METERING_LIMIT - points,
-                                MeteringPoints::Exhausted => WASM_METERING_LIMIT,
+                                MeteringPoints::Remaining(points) => points_limit - points,
+                                MeteringPoints::Exhausted => {
+                                    // The points ran out because the deadline came.
+                                    if let Some(budget) = deadline_budget {
+                                        result = Err(RibosomeError::ZomeCallTimeout(
+                                            zome.zome_name().clone(),
+                                            fn_name.clone(),
+                                            budget,
+                                        ));
+                                    }
+                                    points_limit
+                                }
                             };
                         self.usage_meter.add(points_used, &otel_info);
                     }
@@ -1025,6 +1191,14 @@ impl RibosomeT for RealRibosome {
         do_callback!(self, host_access, invocation, ValidateCallbackResult)
     }
 
+    fn run_dependency_hints(
+        &self,
+        host_access: DependencyHintsHostAccess,
+        invocation: DependencyHintsInvocation,
+    ) -> RibosomeResult<DependencyHintsResult> {
+        do_callback!(self, host_access, invocation, DependencyHintsCallbackResult)
+    }
+
     fn run_init(
         &self,
         host_access: InitHostAccess,
@@ -1084,6 +1258,7 @@ pub mod wasm_test {
     use ::fixt::prelude::*;
     use hdk::prelude::*;
     use holochain_nonce::fresh_nonce;
+    use holochain_types::access::HostFnClass;
     use holochain_types::prelude::AgentPubKeyFixturator;
     use holochain_wasm_test_utils::TestWasm;
     use holochain_zome_types::zome_io::ZomeCallUnsigned;
@@ -1228,6 +1403,7 @@ pub mod wasm_test {
                         payload: ExternIO::encode(()).unwrap(),
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     },
                 )
                 .await
@@ -1252,21 +1428,26 @@ pub mod wasm_test {
             vec![
                 "__hc__accept_countersigning_preflight_request_1",
                 "__hc__agent_info_1",
+                "__hc__agent_info_2",
                 "__hc__block_agent_1",
                 "__hc__call_1",
                 "__hc__call_info_1",
+                "__hc__call_remote_durable_1",
                 "__hc__capability_claims_1",
                 "__hc__capability_grants_1",
                 "__hc__capability_info_1",
                 "__hc__close_chain_1",
                 "__hc__count_links_1",
                 "__hc__create_1",
+                "__hc__create_blob_chunk_1",
                 "__hc__create_clone_cell_1",
                 "__hc__create_link_1",
+                "__hc__create_links_1",
                 "__hc__create_x25519_keypair_1",
                 "__hc__delete_1",
                 "__hc__delete_clone_cell_1",
                 "__hc__delete_link_1",
+                "__hc__delete_links_1",
                 "__hc__disable_clone_cell_1",
                 "__hc__dna_info_1",
                 "__hc__dna_info_2",
@@ -1274,19 +1455,32 @@ pub mod wasm_test {
                 "__hc__ed_25519_x_salsa20_poly1305_encrypt_1",
                 "__hc__emit_signal_1",
                 "__hc__enable_clone_cell_1",
+                "__hc__ensure_entry_1",
+                "__hc__ensure_link_1",
+                "__hc__entry_type_metrics_1",
                 "__hc__get_1",
+                "__hc__get_action_by_seq_1",
                 "__hc__get_agent_activity_1",
+                "__hc__get_blob_chunk_1",
                 "__hc__get_details_1",
+                "__hc__get_dual_sign_acceptance_1",
+                "__hc__get_entries_by_author_1",
                 "__hc__get_link_details_1",
                 "__hc__get_links_1",
+                "__hc__get_links_delta_1",
                 "__hc__hash_1",
+                "__hc__hold_blob_chunks_1",
                 "__hc__must_get_action_1",
                 "__hc__must_get_agent_activity_1",
                 "__hc__must_get_entry_1",
                 "__hc__must_get_valid_record_1",
+                "__hc__network_time_1",
                 "__hc__open_chain_1",
                 "__hc__query_1",
                 "__hc__random_bytes_1",
+                "__hc__release_blob_chunks_1",
+                "__hc__remote_query_1",
+                "__hc__renew_membrane_proof_1",
                 "__hc__schedule_1",
                 "__hc__send_remote_signal_1",
                 "__hc__sign_1",
@@ -1314,6 +1508,16 @@ pub mod wasm_test {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn every_host_fn_has_a_class() {
+        for host_fn_name in super::RealRibosome::tooling_imports().await.unwrap() {
+            assert!(
+                HostFnClass::of_host_fn(&host_fn_name).is_some(),
+                "{host_fn_name} has no host function class"
+            );
+        }
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     #[ignore]
     async fn the_incredible_halt_test() {
@@ -31,6 +31,29 @@ pub const MAX_ENTRY_SIZE: usize = ENTRY_SIZE_LIMIT;
 /// fast lookup so they should be small.
 pub const MAX_TAG_SIZE: usize = 1000;
 
+/// 4kb limit on LinkTags which carry a [`LinkTagPayload`],
+/// so small metadata can live on the link instead of in a separate entry.
+pub const MAX_PAYLOAD_TAG_SIZE: usize = 4000;
+
+/// DNAs with an origin time from Nov 1, 2026, 12:00:00 AM UTC accept tags of up to
+/// [`MAX_PAYLOAD_TAG_SIZE`] which carry a [`LinkTagPayload`], and reject tags which
+/// start with the [`LINK_TAG_PAYLOAD_PREFIX`] but don't carry one. Conductors which
+/// predate payloads reject the larger tags, so older DNAs keep [`MAX_TAG_SIZE`].
+pub const LINK_TAG_PAYLOAD_SINCE: Timestamp = Timestamp(1793491200000000);
+
+/// DNAs with an origin time from Nov 1, 2026, 12:00:00 AM UTC reject chain migration
+/// actions which reference the DNA they are committed in.
+pub const CHAIN_MIGRATION_DNA_SINCE: Timestamp = Timestamp(1793491200000000);
+
+/// Whether a sys validation rule introduced at `since` applies to a DNA.
+///
+/// Rules which change the outcome of validating an op only apply to DNAs with an
+/// origin time at or after the rule was introduced, so that every authority in an
+/// existing network keeps validating its ops the same way across an upgrade.
+pub fn rule_applies(dna_def: &DnaDef, since: Timestamp) -> bool {
+    dna_def.modifiers.origin_time >= since
+}
+
 /// Verify the signature for this action
 pub async fn verify_action_signature(sig: &Signature, action: &Action) -> SysValidationResult<()> {
     if action.author().verify_signature(sig, action).await? {
@@ -59,6 +82,31 @@ pub async fn verify_warrant_signature(warrant_op: &WarrantOp) -> SysValidationRe
     }
 }
 
+/// Verify the signature of the agent over the agent info held by this op
+pub async fn verify_agent_info_signature(op: &AgentInfoOp) -> SysValidationResult<()> {
+    let data: Arc<[u8]> = op.agent_info.clone().into();
+    if op.agent.verify_signature_raw(&op.signature, data).await? {
+        Ok(())
+    } else {
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::CounterfeitAgentInfo(op.clone()),
+        ))
+    }
+}
+
+/// Verify the signature of the author over the chunk hash held by this op
+/// and the manifest action which accounts for it
+pub async fn verify_blob_chunk_signature(op: &BlobChunkOp) -> SysValidationResult<()> {
+    let data: Arc<[u8]> = BlobChunkOp::signed_data(&op.chunk_hash, &op.manifest).into();
+    if op.author.verify_signature_raw(&op.signature, data).await? {
+        Ok(())
+    } else {
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::CounterfeitBlobChunk(op.author.clone(), op.chunk_hash.clone()),
+        ))
+    }
+}
+
 /// Verify the author key was valid at the time
 /// of signing with dpki
 /// TODO: This is just a stub until we have dpki.
@@ -168,6 +216,54 @@ pub async fn check_countersigning_session_data(
     }
 }
 
+/// Check that a dual signed entry is committed by the right agent with the right action.
+///
+/// A proposal must be created, and must not name its own author as the counterparty.
+/// An acceptance must update a proposal authored by the initiator it names, which in
+/// turn named the author of the acceptance as the counterparty for the same app entry.
+/// The `original_action` is the action being updated, if this is an update.
+pub fn check_dual_sign_data(
+    data: &DualSignData,
+    entry_bytes: &AppEntryBytes,
+    action: NewEntryActionRef<'_>,
+    original_action: Option<&Action>,
+) -> SysValidationResult<()> {
+    let invalid = |reason: &str| -> SysValidationResult<()> {
+        Err(ValidationOutcome::InvalidDualSign(reason.to_string()).into())
+    };
+    let author = action.author();
+    if data.other_party() == author {
+        return invalid("an agent can't agree with itself");
+    }
+    match (data, &action, original_action) {
+        (DualSignData::Proposal { .. }, NewEntryActionRef::Create(_), _) => Ok(()),
+        (DualSignData::Proposal { .. }, NewEntryActionRef::Update(_), _) => {
+            invalid("a proposal must be a create")
+        }
+        (
+            DualSignData::Acceptance { initiator },
+            NewEntryActionRef::Update(update),
+            Some(original),
+        ) => {
+            if original.author() != initiator {
+                return invalid("the accepted proposal was not authored by the initiator");
+            }
+            let proposal_hash = dual_sign_proposal_hash(author.clone(), entry_bytes.clone());
+            if update.original_entry_address != proposal_hash {
+                return invalid("the accepted proposal doesn't name this author for this entry");
+            }
+            Ok(())
+        }
+        (DualSignData::Acceptance { .. }, NewEntryActionRef::Update(update), None) => Err(
+            ValidationOutcome::DepMissingFromDht(update.original_action_address.clone().into())
+                .into(),
+        ),
+        (DualSignData::Acceptance { .. }, NewEntryActionRef::Create(_), _) => {
+            invalid("an acceptance must be an update of the proposal")
+        }
+    }
+}
+
 /// Check that the correct actions have the correct setting for prev_action:
 /// - Dna can never have a prev_action, and must have seq == 0.
 /// - All other actions must have prev_action, and seq > 0.
@@ -215,6 +311,66 @@ pub fn check_valid_if_dna(action: &Action, dna_def: &DnaDefHashed) -> SysValidat
     }
 }
 
+/// Check that a membrane proof renewal supersedes the current membrane proof
+/// on its author's own chain.
+///
+/// The superseded action must be the [`AgentValidationPkg`] or a previous
+/// [`RenewMembraneProof`], by the same author and earlier in the chain.
+/// `intervening` are the actions of the chain between the superseded action
+/// and the renewal, none of which may hold a membrane proof, so the superseded
+/// proof is the latest one and hasn't already been superseded.
+pub fn check_membrane_proof_renewal<'a>(
+    renewal: &RenewMembraneProof,
+    superseded: &Action,
+    intervening: impl IntoIterator<Item = &'a Action>,
+) -> SysValidationResult<()> {
+    let invalid = |reason: &str| -> SysValidationResult<()> {
+        Err(ValidationOutcome::InvalidMembraneProofRenewal(reason.to_string()).into())
+    };
+    if !matches!(
+        superseded,
+        Action::AgentValidationPkg(_) | Action::RenewMembraneProof(_)
+    ) {
+        return invalid("the superseded action doesn't hold a membrane proof");
+    }
+    if *superseded.author() != renewal.author {
+        return invalid("the superseded membrane proof is on another agent's chain");
+    }
+    if superseded.action_seq() >= renewal.action_seq {
+        return invalid("the superseded membrane proof is not earlier in the chain");
+    }
+    for action in intervening {
+        match action {
+            Action::RenewMembraneProof(earlier) if earlier.supersedes == renewal.supersedes => {
+                return invalid("the superseded membrane proof was already superseded");
+            }
+            Action::AgentValidationPkg(_) | Action::RenewMembraneProof(_) => {
+                return invalid("the superseded membrane proof is not the latest one");
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+/// Check that chain migration actions reference a DNA other than the one
+/// they are committed in, if the DNA applies this rule
+pub fn check_chain_migration(action: &Action, dna_def: &DnaDefHashed) -> SysValidationResult<()> {
+    if !rule_applies(dna_def, CHAIN_MIGRATION_DNA_SINCE) {
+        return Ok(());
+    }
+    let migration_dna_hash = match action {
+        Action::CloseChain(a) => &a.new_dna_hash,
+        Action::OpenChain(a) => &a.prev_dna_hash,
+        _ => return Ok(()),
+    };
+    if migration_dna_hash == dna_def.as_hash() {
+        Err(ValidationOutcome::MigrationToSameDna(migration_dna_hash.clone()).into())
+    } else {
+        Ok(())
+    }
+}
+
 /// Check if there are other actions at this
 /// sequence number
 pub async fn check_chain_rollback(
@@ -369,14 +525,16 @@ pub fn check_entry_visibility(op: &ChainOp) -> SysValidationResult<()> {
     };
 
     match (op.action().entry_type().map(|t| t.visibility()), op.entry()) {
-        (Some(Public), Present(_)) => Ok(()),
+        (Some(Public | Unlisted), Present(_)) => Ok(()),
         (Some(Private), Hidden) => Ok(()),
         (Some(Private), NotStored) => Ok(()),
 
-        (Some(Public), Hidden) => err("RecordEntry::Hidden is only for Private entry type"),
+        (Some(Public | Unlisted), Hidden) => {
+            err("RecordEntry::Hidden is only for Private entry type")
+        }
         (Some(_), NA) => err("There is action entry data but the entry itself is N/A"),
         (Some(Private), Present(_)) => Err(ValidationOutcome::PrivateEntryLeaked.into()),
-        (Some(Public), NotStored) => {
+        (Some(Public | Unlisted), NotStored) => {
             if op.get_type() == ChainOpType::RegisterAgentActivity
                 || op.action().entry_type() == Some(&EntryType::AgentPubKey)
             {
@@ -414,7 +572,7 @@ pub fn check_new_entry_action(action: &Action) -> SysValidationResult<()> {
 /// Check the entry size is under the MAX_ENTRY_SIZE
 pub fn check_entry_size(entry: &Entry) -> SysValidationResult<()> {
     match entry {
-        Entry::App(bytes) | Entry::CounterSign(_, bytes) => {
+        Entry::App(bytes) | Entry::CounterSign(_, bytes) | Entry::DualSign(_, bytes) => {
             let size = std::mem::size_of_val(&bytes.bytes()[..]);
             if size <= MAX_ENTRY_SIZE {
                 Ok(())
@@ -429,16 +587,35 @@ pub fn check_entry_size(entry: &Entry) -> SysValidationResult<()> {
     }
 }
 
-/// Check the link tag size is under the MAX_TAG_SIZE
-pub fn check_tag_size(tag: &LinkTag) -> SysValidationResult<()> {
+/// Check the link tag size is under the MAX_TAG_SIZE, or the MAX_PAYLOAD_TAG_SIZE
+/// if the tag carries a payload and the DNA accepts the larger limit.
+pub fn check_tag_size(tag: &LinkTag, dna_def: &DnaDef) -> SysValidationResult<()> {
     let size = std::mem::size_of_val(&tag.0[..]);
-    if size <= MAX_TAG_SIZE {
+    let payload_size_allowed = || {
+        size <= MAX_PAYLOAD_TAG_SIZE
+            && rule_applies(dna_def, LINK_TAG_PAYLOAD_SINCE)
+            && tag.payload().is_some()
+    };
+    if size <= MAX_TAG_SIZE || payload_size_allowed() {
         Ok(())
     } else {
         Err(ValidationOutcome::TagTooLarge(size).into())
     }
 }
 
+/// Check that a link tag which starts with the [`LINK_TAG_PAYLOAD_PREFIX`] carries a
+/// [`LinkTagPayload`], so that the fields of every such tag can be indexed.
+pub fn check_tag_payload(tag: &LinkTag, dna_def: &DnaDef) -> SysValidationResult<()> {
+    if rule_applies(dna_def, LINK_TAG_PAYLOAD_SINCE)
+        && tag.0.starts_with(LINK_TAG_PAYLOAD_PREFIX)
+        && tag.payload().is_none()
+    {
+        Err(ValidationOutcome::MalformedTagPayload.into())
+    } else {
+        Ok(())
+    }
+}
+
 /// Check a Update's entry type is the same for
 /// original and new entry.
 pub fn check_update_reference(
@@ -579,9 +756,10 @@ impl DhtOpSender for IncomingDhtOpSender {
 
     async fn send_store_entry(&self, record: Record) -> SysValidationResult<()> {
         // TODO: MD: isn't it already too late if we've received a private entry from the network at this point?
-        let is_public_entry = record.action().entry_type().map_or(false, |et| {
-            matches!(et.visibility(), EntryVisibility::Public)
-        });
+        let is_public_entry = record
+            .action()
+            .entry_type()
+            .map_or(false, |et| et.visibility().is_published());
         if is_public_entry {
             if let Some(op) = make_store_entry(record) {
                 self.send_op(op.into()).await?;
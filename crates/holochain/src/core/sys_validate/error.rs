@@ -107,10 +107,22 @@ pub enum ValidationOutcome {
     CounterfeitWarrant(WarrantOp),
     #[error("A warrant op was found to be invalid. Reason: {1}, Warrant: {0:?}")]
     InvalidWarrantOp(WarrantOp, String),
+    #[error("An agent info op was found to be counterfeit. Agent info: {0:?}")]
+    CounterfeitAgentInfo(AgentInfoOp),
+    #[error("An agent info op was found to be invalid. Reason: {1}, Agent info: {0:?}")]
+    InvalidAgentInfoOp(AgentInfoOp, String),
+    #[error("A blob chunk op by {0} was found to be counterfeit. Chunk: {1}")]
+    CounterfeitBlobChunk(AgentPubKey, EntryHash),
+    #[error("A blob chunk op was found to be invalid. Reason: {1}, Chunk: {0}")]
+    InvalidBlobChunkOp(EntryHash, String),
     #[error("The action {1:?} is not found in the countersigning session data {0:?}")]
     ActionNotInCounterSigningSession(CounterSigningSessionData, NewEntryAction),
     #[error(transparent)]
     CounterSigningError(#[from] CounterSigningError),
+    #[error("The dual signed entry is invalid: {0}")]
+    InvalidDualSign(String),
+    #[error("The membrane proof renewal is invalid: {0}")]
+    InvalidMembraneProofRenewal(String),
     #[error("The dependency {0:?} was not found on the DHT")]
     DepMissingFromDht(AnyDhtHash),
     #[error("The entry def index for {0:?} was out of range")]
@@ -122,17 +134,24 @@ pub enum ValidationOutcome {
         MAX_ENTRY_SIZE
     )]
     EntryTooLarge(usize),
+    #[error("The entry size {1} was larger than the max size {2} of its entry type {0:?}")]
+    EntryTooLargeForType(AppEntryDef, usize, u32),
     #[error("The entry has a different type to the action's entry type")]
     EntryTypeMismatch,
     #[error("The visibility for {0:?} didn't match the zome")]
     EntryVisibility(AppEntryDef),
     #[error(
-        "The link tag size {0} was larger than the MAX_TAG_SIZE {}",
-        super::MAX_TAG_SIZE
+        "The link tag size {0} was larger than the MAX_TAG_SIZE {}, or the MAX_PAYLOAD_TAG_SIZE {} for a tag with a payload",
+        super::MAX_TAG_SIZE,
+        super::MAX_PAYLOAD_TAG_SIZE
     )]
     TagTooLarge(usize),
     #[error("An op with non-private entry type is missing its entry data. Action: {0:?}, Op type: {1:?} Reason: {2}")]
     MalformedDhtOp(Box<Action>, ChainOpType, String),
+    #[error("The link tag starts like a tag with a payload but doesn't carry one")]
+    MalformedTagPayload,
+    #[error("A chain migration can't reference the DNA it is committed in: {0:?}")]
+    MigrationToSameDna(DnaHash),
     #[error("The action with {0:?} was expected to be a link add action")]
     NotCreateLink(ActionHash),
     #[error("The action was expected to be a new entry action but was {0:?}")]
@@ -165,6 +184,17 @@ impl ValidationOutcome {
     pub fn into_outcome<T>(self) -> SysValidationOutcome<T> {
         Err(OutcomeOrError::Outcome(self))
     }
+
+    /// Was the data signed by someone other than its claimed author?
+    pub fn is_counterfeit(&self) -> bool {
+        matches!(
+            self,
+            Self::CounterfeitAction(..)
+                | Self::CounterfeitWarrant(_)
+                | Self::CounterfeitAgentInfo(_)
+                | Self::CounterfeitBlobChunk(..)
+        )
+    }
 }
 
 /// Context information for an invalid action to make it easier to trace in errors.
@@ -13,9 +13,13 @@
 //! - Hash integrity check. The hash of an entry always matches what's in the action.
 //! - The size of an entry does not exceed the max.
 //! - Check that updates can't switch the entry type
+//! - Dual signed entries are proposed and accepted by the right agents with the right actions
 //! - The link tag size is bounded
+//! - Link tags which start like a tag with a payload carry one
 //! - Check the AppEntryDef is valid for the zome and the EntryDefId and ZomeIndex are in range.
 //! - Check that StoreEntry never contains a private entry type
+//! - Chain migration actions don't reference the DNA they are committed in
+//! - Membrane proof renewals supersede an earlier membrane proof on the same chain
 //! - Test that a given sequence of actions constitutes a valid chain w.r.t. its backlinks
 //!
 //! TO TEST:
@@ -140,6 +144,13 @@ async fn record_with_deps_fixup(
                         entry_type: EntryType::AgentPubKey,
                         ..
                     }) => matching_record(&mut g, is_pkg_record),
+                    // A renewal must supersede the latest membrane proof, so
+                    // the action before it can't hold one.
+                    Action::RenewMembraneProof(_) => matching_record(&mut g, |r| {
+                        !is_dna_record(r)
+                            && !is_pkg_record(r)
+                            && !matches!(r.action(), Action::RenewMembraneProof(_))
+                    }),
                     _ => matching_record(&mut g, |r| !is_dna_record(r) && !is_pkg_record(r)),
                 };
                 *prev.as_action_mut().action_seq_mut().unwrap() = action.action_seq() - 1;
@@ -195,6 +206,16 @@ async fn record_with_deps_fixup(
                     deps.push(base);
                     deps.push(create);
                 }
+                Action::RenewMembraneProof(renewal) => {
+                    // Supersede the proof from genesis, right before the previous action
+                    let mut pkg = matching_record(&mut g, is_pkg_record);
+                    *pkg.as_action_mut().author_mut() = renewal.author.clone();
+                    *pkg.as_action_mut().action_seq_mut().unwrap() = renewal.action_seq - 2;
+                    renewal.supersedes = pkg.action_address().clone();
+                    let prev = deps.last_mut().unwrap();
+                    *prev.as_action_mut().prev_action_mut().unwrap() = pkg.action_address().clone();
+                    deps.push(pkg);
+                }
                 Action::AgentValidationPkg(_)
                 | Action::CloseChain(_)
                 | Action::InitZomesComplete(_)
@@ -223,10 +244,17 @@ async fn record_with_cascade(
     (record, Arc::new(MockCascade::with_records(deps)))
 }
 
+/// A DNA which every sys validation rule applies to.
+fn test_dna_def() -> DnaDefHashed {
+    let mut dna_def = DnaDef::arbitrary(&mut random_generator()).unwrap();
+    dna_def.modifiers.origin_time = LINK_TAG_PAYLOAD_SINCE.max(CHAIN_MIGRATION_DNA_SINCE);
+    DnaDefHashed::from_content_sync(dna_def)
+}
+
 async fn assert_valid_action(keystore: &MetaLairClient, action: Action) {
     let (record, deps) = record_with_deps(keystore, action).await;
     let cascade = Arc::new(MockCascade::with_records(deps.clone()));
-    let result = sys_validate_record(&record, cascade).await;
+    let result = sys_validate_record(&record, &test_dna_def(), cascade).await;
     if result.is_err() {
         dbg!(&deps, &record);
         result.unwrap();
@@ -262,10 +290,10 @@ async fn verify_action_signature_test() {
         SignedActionHashed::new_unchecked(record_valid.action().clone(), wrong_signature);
     let record_invalid = Record::new(action_invalid, None);
 
-    sys_validate_record(&record_valid, cascade.clone())
+    sys_validate_record(&record_valid, &test_dna_def(), cascade.clone())
         .await
         .unwrap();
-    sys_validate_record(&record_invalid, cascade)
+    sys_validate_record(&record_invalid, &test_dna_def(), cascade)
         .await
         .unwrap_err();
 }
@@ -297,6 +325,7 @@ async fn check_previous_action() {
 
         let actual = sys_validate_record(
             &sign_record(&keystore, action, None).await,
+            &test_dna_def(),
             Arc::new(cascade),
         )
         .await
@@ -419,15 +448,23 @@ async fn check_previous_timestamp() {
     let (record, mut deps) = record_with_deps(&keystore, action).await;
     *deps[0].as_action_mut().timestamp_mut() = before;
 
-    sys_validate_record(&record, Arc::new(MockCascade::with_records(deps.clone())))
-        .await
-        .unwrap();
+    sys_validate_record(
+        &record,
+        &test_dna_def(),
+        Arc::new(MockCascade::with_records(deps.clone())),
+    )
+    .await
+    .unwrap();
 
     *deps[0].as_action_mut().timestamp_mut() = after;
-    let r = sys_validate_record(&record, Arc::new(MockCascade::with_records(deps.clone())))
-        .await
-        .unwrap_err()
-        .into_outcome();
+    let r = sys_validate_record(
+        &record,
+        &test_dna_def(),
+        Arc::new(MockCascade::with_records(deps.clone())),
+    )
+    .await
+    .unwrap_err()
+    .into_outcome();
 
     assert_matches!(
         r,
@@ -451,18 +488,24 @@ async fn check_previous_seq() {
     // *record.as_action_mut().action_seq_mut().unwrap() = 2;
     *deps[0].as_action_mut().action_seq_mut().unwrap() = 1;
 
-    assert!(
-        sys_validate_record(&record, Arc::new(MockCascade::with_records(deps.clone())))
-            .await
-            .is_ok()
-    );
+    assert!(sys_validate_record(
+        &record,
+        &test_dna_def(),
+        Arc::new(MockCascade::with_records(deps.clone()))
+    )
+    .await
+    .is_ok());
 
     *deps[0].as_action_mut().action_seq_mut().unwrap() = 2;
     assert_matches!(
-        sys_validate_record(&record, Arc::new(MockCascade::with_records(deps.clone())))
-            .await
-            .unwrap_err()
-            .into_outcome(),
+        sys_validate_record(
+            &record,
+            &test_dna_def(),
+            Arc::new(MockCascade::with_records(deps.clone()))
+        )
+        .await
+        .unwrap_err()
+        .into_outcome(),
         Some(ValidationOutcome::PrevActionError(PrevActionError {
             source: PrevActionErrorKind::InvalidSeq(2, 2),
             ..
@@ -471,10 +514,14 @@ async fn check_previous_seq() {
 
     *deps[0].as_action_mut().action_seq_mut().unwrap() = 3;
     assert_matches!(
-        sys_validate_record(&record, Arc::new(MockCascade::with_records(deps.clone())))
-            .await
-            .unwrap_err()
-            .into_outcome(),
+        sys_validate_record(
+            &record,
+            &test_dna_def(),
+            Arc::new(MockCascade::with_records(deps.clone()))
+        )
+        .await
+        .unwrap_err()
+        .into_outcome(),
         Some(ValidationOutcome::PrevActionError(PrevActionError {
             source: PrevActionErrorKind::InvalidSeq(2, 3),
             ..
@@ -485,10 +532,14 @@ async fn check_previous_seq() {
     let record = rebuild_record(record, &keystore).await;
     *deps[0].as_action_mut().action_seq_mut().unwrap() = 0;
     assert_matches!(
-        sys_validate_record(&record, Arc::new(MockCascade::with_records(deps.clone())))
-            .await
-            .unwrap_err()
-            .into_outcome(),
+        sys_validate_record(
+            &record,
+            &test_dna_def(),
+            Arc::new(MockCascade::with_records(deps.clone()))
+        )
+        .await
+        .unwrap_err()
+        .into_outcome(),
         Some(ValidationOutcome::PrevActionError(PrevActionError {
             source: PrevActionErrorKind::InvalidRoot,
             ..
@@ -496,6 +547,177 @@ async fn check_previous_seq() {
     );
 }
 
+/// Chain migration actions never reference the DNA they are committed in
+#[test]
+fn check_chain_migration_test() {
+    let mut g = random_generator();
+    let dna_def = test_dna_def();
+    let other_dna_hash = fixt!(DnaHash);
+
+    let mut close = CloseChain::arbitrary(&mut g).unwrap();
+    close.new_dna_hash = other_dna_hash.clone();
+    assert_matches!(
+        check_chain_migration(&close.clone().into(), &dna_def),
+        Ok(())
+    );
+    close.new_dna_hash = dna_def.as_hash().clone();
+    assert_matches!(
+        check_chain_migration(&close.into(), &dna_def),
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::MigrationToSameDna(_)
+        ))
+    );
+
+    let mut open = OpenChain::arbitrary(&mut g).unwrap();
+    open.prev_dna_hash = other_dna_hash;
+    assert_matches!(
+        check_chain_migration(&open.clone().into(), &dna_def),
+        Ok(())
+    );
+    open.prev_dna_hash = dna_def.as_hash().clone();
+    assert_matches!(
+        check_chain_migration(&open.clone().into(), &dna_def),
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::MigrationToSameDna(_)
+        ))
+    );
+
+    // DNAs which predate the rule keep accepting a migration to the same DNA.
+    let mut old_dna_def = dna_def.into_content();
+    old_dna_def.modifiers.origin_time = Timestamp(CHAIN_MIGRATION_DNA_SINCE.0 - 1);
+    let old_dna_def = DnaDefHashed::from_content_sync(old_dna_def);
+    open.prev_dna_hash = old_dna_def.as_hash().clone();
+    assert_matches!(check_chain_migration(&open.into(), &old_dna_def), Ok(()));
+}
+
+/// Membrane proof renewals supersede an earlier membrane proof on the same chain
+#[test]
+fn check_membrane_proof_renewal_test() {
+    let mut g = random_generator();
+
+    let mut pkg = AgentValidationPkg::arbitrary(&mut g).unwrap();
+    pkg.author = fake_agent_pubkey_1();
+    pkg.action_seq = 1;
+
+    let mut renewal = RenewMembraneProof::arbitrary(&mut g).unwrap();
+    renewal.author = fake_agent_pubkey_1();
+    renewal.action_seq = 5;
+
+    let is_invalid = |r: SysValidationResult<()>| {
+        matches!(
+            r,
+            Err(SysValidationError::ValidationOutcome(
+                ValidationOutcome::InvalidMembraneProofRenewal(_)
+            ))
+        )
+    };
+
+    // - The proof from genesis and earlier renewals can be superseded.
+    check_membrane_proof_renewal(&renewal, &pkg.clone().into(), []).unwrap();
+    let mut earlier = renewal.clone();
+    earlier.action_seq = 4;
+    check_membrane_proof_renewal(&renewal, &earlier.clone().into(), []).unwrap();
+
+    // - Actions which don't hold a membrane proof can come in between.
+    let mut init = InitZomesComplete::arbitrary(&mut g).unwrap();
+    init.author = fake_agent_pubkey_1();
+    init.action_seq = 3;
+    let init: Action = init.into();
+    check_membrane_proof_renewal(&renewal, &pkg.clone().into(), [&init]).unwrap();
+
+    // - A later renewal can't be superseded.
+    let mut later = earlier.clone();
+    later.action_seq = 6;
+    assert!(is_invalid(check_membrane_proof_renewal(
+        &renewal,
+        &later.into(),
+        []
+    )));
+
+    // - Another agent's proof can't be superseded.
+    let mut other = pkg.clone();
+    other.author = fake_agent_pubkey_2();
+    assert!(is_invalid(check_membrane_proof_renewal(
+        &renewal,
+        &other.into(),
+        []
+    )));
+
+    // - Only actions holding a membrane proof can be superseded.
+    assert!(is_invalid(check_membrane_proof_renewal(
+        &renewal,
+        &init,
+        []
+    )));
+}
+
+/// A membrane proof can't be superseded twice
+#[test]
+fn check_membrane_proof_renewal_double_supersede_test() {
+    let mut g = random_generator();
+
+    let mut pkg = AgentValidationPkg::arbitrary(&mut g).unwrap();
+    pkg.author = fake_agent_pubkey_1();
+    pkg.action_seq = 1;
+    let pkg: Action = pkg.into();
+
+    let mut first = RenewMembraneProof::arbitrary(&mut g).unwrap();
+    first.author = fake_agent_pubkey_1();
+    first.action_seq = 4;
+    first.supersedes = pkg.to_hash();
+
+    let mut second = first.clone();
+    second.action_seq = 5;
+
+    let first: Action = first.into();
+    assert!(matches!(
+        check_membrane_proof_renewal(&second, &pkg, [&first]),
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::InvalidMembraneProofRenewal(reason)
+        )) if reason.contains("already superseded")
+    ));
+}
+
+/// A renewal must supersede the latest membrane proof, not an older one
+#[test]
+fn check_membrane_proof_renewal_stale_target_test() {
+    let mut g = random_generator();
+
+    let mut pkg = AgentValidationPkg::arbitrary(&mut g).unwrap();
+    pkg.author = fake_agent_pubkey_1();
+    pkg.action_seq = 1;
+    let pkg: Action = pkg.into();
+
+    let mut first = RenewMembraneProof::arbitrary(&mut g).unwrap();
+    first.author = fake_agent_pubkey_1();
+    first.action_seq = 4;
+    first.supersedes = pkg.to_hash();
+    let first: Action = first.into();
+
+    let mut second = RenewMembraneProof::arbitrary(&mut g).unwrap();
+    second.author = fake_agent_pubkey_1();
+    second.action_seq = 6;
+    second.supersedes = first.to_hash();
+    let second_action: Action = second.clone().into();
+
+    // The second renewal supersedes the first one, so a third renewal which
+    // targets the first one targets a stale proof.
+    let mut third = RenewMembraneProof::arbitrary(&mut g).unwrap();
+    third.author = fake_agent_pubkey_1();
+    third.action_seq = 8;
+    third.supersedes = first.to_hash();
+    assert!(matches!(
+        check_membrane_proof_renewal(&third, &first, [&second_action]),
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::InvalidMembraneProofRenewal(reason)
+        )) if reason.contains("not the latest")
+    ));
+
+    // Targeting the second renewal is fine.
+    third.supersedes = second_action.to_hash();
+    check_membrane_proof_renewal(&third, &second_action, []).unwrap();
+}
+
 /// Entry type in the action matches the entry variant
 #[test]
 fn check_entry_type_test() {
@@ -576,7 +798,9 @@ async fn check_entry_size_test() {
         EntryType::App(AppEntryDef::arbitrary(&mut g).unwrap());
     *record.as_entry_mut() = RecordEntry::Present(tiny_entry);
     let mut record = rebuild_record(record, &keystore).await;
-    sys_validate_record(&record, cascade.clone()).await.unwrap();
+    sys_validate_record(&record, &test_dna_def(), cascade.clone())
+        .await
+        .unwrap();
 
     let huge_entry = Entry::App(AppEntryBytes(SerializedBytes::from(UnsafeBytes::from(
         (0..5_000_000).map(|_| 0u8).into_iter().collect::<Vec<_>>(),
@@ -585,7 +809,7 @@ async fn check_entry_size_test() {
     let record = rebuild_record(record, &keystore).await;
 
     assert_eq!(
-        sys_validate_record(&record, cascade)
+        sys_validate_record(&record, &test_dna_def(), cascade)
             .await
             .unwrap_err()
             .into_outcome(),
@@ -633,7 +857,7 @@ async fn check_update_reference_test() {
     let record = rebuild_record(record, &keystore).await;
 
     assert_eq!(
-        sys_validate_record(&record, cascade)
+        sys_validate_record(&record, &test_dna_def(), cascade)
             .await
             .unwrap_err()
             .into_outcome(),
@@ -644,6 +868,101 @@ async fn check_update_reference_test() {
     );
 }
 
+/// Dual signed entries are proposed and accepted by the right agents with the right actions
+#[test]
+fn check_dual_sign_data_test() {
+    let alice = fake_agent_pubkey_1();
+    let bob = fake_agent_pubkey_2();
+    let bytes = AppEntryBytes(SerializedBytes::from(UnsafeBytes::from(vec![1, 2, 3])));
+    let proposal = DualSignData::Proposal {
+        counterparty: bob.clone(),
+    };
+    let acceptance = DualSignData::Acceptance {
+        initiator: alice.clone(),
+    };
+
+    let mut create = fixt!(Create);
+    create.author = alice.clone();
+    create.entry_hash = dual_sign_proposal_hash(bob.clone(), bytes.clone());
+    let original = Action::Create(create.clone());
+
+    let mut update = fixt!(Update);
+    update.author = bob.clone();
+    update.original_entry_address = create.entry_hash.clone();
+
+    let is_invalid = |r: SysValidationResult<()>| {
+        matches!(
+            r,
+            Err(SysValidationError::ValidationOutcome(
+                ValidationOutcome::InvalidDualSign(_)
+            ))
+        )
+    };
+
+    // - Alice proposes to Bob and Bob accepts.
+    check_dual_sign_data(&proposal, &bytes, NewEntryActionRef::Create(&create), None).unwrap();
+    check_dual_sign_data(
+        &acceptance,
+        &bytes,
+        NewEntryActionRef::Update(&update),
+        Some(&original),
+    )
+    .unwrap();
+
+    // - Bob can't propose to himself.
+    let mut by_bob = create.clone();
+    by_bob.author = bob.clone();
+    assert!(is_invalid(check_dual_sign_data(
+        &proposal,
+        &bytes,
+        NewEntryActionRef::Create(&by_bob),
+        None
+    )));
+
+    // - Only Bob can accept.
+    let mut by_carol = update.clone();
+    by_carol.author = fixt!(AgentPubKey);
+    assert!(is_invalid(check_dual_sign_data(
+        &acceptance,
+        &bytes,
+        NewEntryActionRef::Update(&by_carol),
+        Some(&original)
+    )));
+
+    // - Bob can only accept the entry that was proposed.
+    let other_bytes = AppEntryBytes(SerializedBytes::from(UnsafeBytes::from(vec![4, 5, 6])));
+    assert!(is_invalid(check_dual_sign_data(
+        &acceptance,
+        &other_bytes,
+        NewEntryActionRef::Update(&update),
+        Some(&original)
+    )));
+
+    // - The proposal must be authored by the initiator.
+    let mut by_someone_else = create.clone();
+    by_someone_else.author = fixt!(AgentPubKey);
+    assert!(is_invalid(check_dual_sign_data(
+        &acceptance,
+        &bytes,
+        NewEntryActionRef::Update(&update),
+        Some(&Action::Create(by_someone_else))
+    )));
+
+    // - An acceptance must be an update and a proposal must be a create.
+    assert!(is_invalid(check_dual_sign_data(
+        &acceptance,
+        &bytes,
+        NewEntryActionRef::Create(&by_bob),
+        None
+    )));
+    assert!(is_invalid(check_dual_sign_data(
+        &proposal,
+        &bytes,
+        NewEntryActionRef::Update(&update),
+        Some(&original)
+    )));
+}
+
 /// The link tag size is bounded
 #[tokio::test(flavor = "multi_thread")]
 async fn check_link_tag_size_test() {
@@ -662,7 +981,7 @@ async fn check_link_tag_size_test() {
     let (record, cascade) = record_with_cascade(&keystore, action.into()).await;
 
     assert_eq!(
-        sys_validate_record(&record, cascade)
+        sys_validate_record(&record, &test_dna_def(), cascade)
             .await
             .unwrap_err()
             .into_outcome(),
@@ -670,6 +989,63 @@ async fn check_link_tag_size_test() {
     );
 }
 
+/// A link tag which carries a payload may be larger than a plain tag, in DNAs
+/// which accept the larger limit
+#[test]
+fn check_payload_link_tag_size_test() {
+    let payload = |len: usize| {
+        LinkTag::try_from(&LinkTagPayload::new().with("data", vec![0u8; len])).unwrap()
+    };
+    let dna_def = test_dna_def();
+    let tag = payload(super::MAX_TAG_SIZE);
+    assert!(tag.0.len() > super::MAX_TAG_SIZE);
+    assert!(super::check_tag_size(&tag, &dna_def).is_ok());
+
+    let tag = payload(super::MAX_PAYLOAD_TAG_SIZE);
+    assert_matches!(
+        super::check_tag_size(&tag, &dna_def),
+        Err(SysValidationError::ValidationOutcome(ValidationOutcome::TagTooLarge(size)))
+            if size == tag.0.len()
+    );
+
+    // DNAs which predate the larger limit keep the plain limit for every tag.
+    let mut dna_def = dna_def.into_content();
+    dna_def.modifiers.origin_time = Timestamp(LINK_TAG_PAYLOAD_SINCE.0 - 1);
+    let tag = payload(super::MAX_TAG_SIZE);
+    assert_matches!(
+        super::check_tag_size(&tag, &dna_def),
+        Err(SysValidationError::ValidationOutcome(ValidationOutcome::TagTooLarge(size)))
+            if size == tag.0.len()
+    );
+}
+
+/// A link tag which starts like a tag with a payload must carry one, in DNAs
+/// which accept payloads
+#[test]
+fn check_link_tag_payload_test() {
+    let dna_def = test_dna_def();
+    let tag = LinkTag::try_from(&LinkTagPayload::new().with("weight", 3u32)).unwrap();
+    assert_matches!(super::check_tag_payload(&tag, &dna_def), Ok(()));
+    assert_matches!(
+        super::check_tag_payload(&LinkTag::from("weight"), &dna_def),
+        Ok(())
+    );
+
+    let mut malformed = LINK_TAG_PAYLOAD_PREFIX.to_vec();
+    malformed.extend([0xc1, 0xc1]);
+    let malformed = LinkTag(malformed);
+    assert_matches!(
+        super::check_tag_payload(&malformed, &dna_def),
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::MalformedTagPayload
+        ))
+    );
+
+    let mut dna_def = dna_def.into_content();
+    dna_def.modifiers.origin_time = Timestamp(LINK_TAG_PAYLOAD_SINCE.0 - 1);
+    assert_matches!(super::check_tag_payload(&malformed, &dna_def), Ok(()));
+}
+
 /// Check that StoreEntry does not have a private entry type
 #[tokio::test(flavor = "multi_thread")]
 async fn incoming_ops_filters_private_entry() {
@@ -1030,5 +1406,7 @@ async fn valid_chain_fact_test() {
 
     let cascade = MockCascade::with_records(chain);
 
-    sys_validate_record(&last, Arc::new(cascade)).await.unwrap();
+    sys_validate_record(&last, &test_dna_def(), Arc::new(cascade))
+        .await
+        .unwrap();
 }
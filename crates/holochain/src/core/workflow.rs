@@ -32,6 +32,9 @@ pub mod incoming_dht_ops_workflow;
 pub mod initialize_zomes_workflow;
 pub mod integrate_dht_ops_workflow;
 pub mod publish_dht_ops_workflow;
+pub mod purge_entries_workflow;
+#[cfg(feature = "op_replay")]
+pub mod replay_validation_workflow;
 pub mod sys_validation_workflow;
 pub mod validation_receipt_workflow;
 
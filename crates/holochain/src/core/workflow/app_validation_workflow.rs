@@ -33,6 +33,10 @@
 //! or non-app entries like [`EntryType::CapGrant`] are validated with all
 //! validation functions of the DNA's integrity zomes.
 //!
+//! Before any callback is invoked, ops with an app entry type are checked against
+//! the entry def of their type. The visibility must match, and the entry must be
+//! within the type's size limit if it has one (see [`EntryDefPolicies`]).
+//!
 //! Having established the relevant integrity zomes for validating an op, each
 //! zome's validation callback is invoked.
 //!
@@ -85,6 +89,15 @@
 //! dependencies have all been fetched, these ops will be validated the next
 //! time the workflow runs.
 //!
+//! ### Dependency hints
+//!
+//! Integrity zomes may implement an optional `dependency_hints` callback,
+//! which returns the hashes that validating an op is expected to `must_get`.
+//! Before validating a batch of ops, the hints for all of them are collected
+//! and any hinted dependencies that aren't held locally are fetched in
+//! parallel. This way validation doesn't discover missing dependencies one op
+//! at a time and wait for a re-trigger to fetch each of them.
+//!
 //! ### Integration workflow
 //!
 
@@ -100,8 +113,13 @@ use super::sys_validation_workflow::validation_query;
 use crate::conductor::entry_def_store::get_entry_def;
 use crate::conductor::Conductor;
 use crate::conductor::ConductorHandle;
+use crate::core::metrics::EntryTypeEvent;
+use crate::core::metrics::ENTRY_TYPE_COUNTERS;
+use crate::core::metrics::THROUGHPUT;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
+use crate::core::ribosome::guest_callback::dependency_hints::DependencyHintsHostAccess;
+use crate::core::ribosome::guest_callback::dependency_hints::DependencyHintsInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateHostAccess;
 use crate::core::ribosome::guest_callback::validate::ValidateInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateResult;
@@ -184,6 +202,8 @@ pub async fn app_validation_workflow(
     .await?;
     // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
 
+    THROUGHPUT.record_ops_validated(outcome_summary.validated);
+
     // If ops have been accepted or rejected, trigger integration.
     if outcome_summary.validated > 0 {
         trigger_integration.trigger(&"app_validation_workflow");
@@ -206,6 +226,29 @@ pub async fn app_validation_workflow(
     )
 }
 
+/// App validate the ops in the workspace once, without the triggers and retries
+/// of the workflow. Returns the number of ops which were accepted or rejected.
+#[cfg(feature = "op_replay")]
+pub(crate) async fn app_validate_ops(
+    dna_hash: Arc<DnaHash>,
+    workspace: Arc<AppValidationWorkspace>,
+    conductor: ConductorHandle,
+    network: &HolochainP2pDna,
+    dht_query_cache: DhtDbQueryCache,
+    validation_dependencies: Arc<Mutex<ValidationDependencies>>,
+) -> WorkflowResult<usize> {
+    let outcome_summary = app_validation_workflow_inner(
+        dna_hash,
+        workspace,
+        conductor,
+        network,
+        dht_query_cache,
+        validation_dependencies,
+    )
+    .await?;
+    Ok(outcome_summary.validated)
+}
+
 async fn app_validation_workflow_inner(
     dna_hash: Arc<DnaHash>,
     workspace: Arc<AppValidationWorkspace>,
@@ -215,7 +258,8 @@ async fn app_validation_workflow_inner(
     validation_dependencies: Arc<Mutex<ValidationDependencies>>,
 ) -> WorkflowResult<OutcomeSummary> {
     let db = workspace.dht_db.clone().into();
-    let sorted_dht_ops = validation_query::get_ops_to_app_validate(&db).await?;
+    let order = *workspace.validation_queue_order.read();
+    let sorted_dht_ops = validation_query::get_ops_to_app_validate(&db, order).await?;
     // filter out ops that have missing dependencies
     tracing::debug!("number of ops to validate {:?}", sorted_dht_ops.len());
     let sorted_dht_ops = validation_dependencies
@@ -232,6 +276,19 @@ async fn app_validation_workflow_inner(
     let sleuth_id = conductor.config.sleuth_id();
 
     let cascade = Arc::new(workspace.full_cascade(network.clone()));
+    let local_cascade = workspace.local_cascade();
+
+    prefetch_dependency_hints(
+        &sorted_dht_ops,
+        dna_hash.clone(),
+        &conductor,
+        &workspace,
+        network,
+        cascade.clone(),
+        &validation_dependencies,
+    )
+    .await;
+
     let validation_dependencies = validation_dependencies.clone();
     let accepted_ops = Arc::new(AtomicUsize::new(0));
     let awaiting_ops = Arc::new(AtomicUsize::new(0));
@@ -276,16 +333,17 @@ async fn app_validation_workflow_inner(
             }
             Err(e) => Err(e),
         };
-        // Flatten nested app validation outcome to either ok or error
+        // Flatten nested app validation outcome to either ok or error.
+        // Outcomes reached before any validation callback ran have no provenance.
         let validation_outcome = match validation_outcome {
-            Ok(outcome) => AppValidationResult::Ok(outcome),
-            Err(OutcomeOrError::Outcome(outcome)) => AppValidationResult::Ok(outcome),
+            Ok((outcome, provenance)) => AppValidationResult::Ok((outcome, Some(provenance))),
+            Err(OutcomeOrError::Outcome(outcome)) => AppValidationResult::Ok((outcome, None)),
             Err(OutcomeOrError::Err(err)) => AppValidationResult::Err(err),
         };
 
         let sleuth_id = sleuth_id.clone();
         match validation_outcome {
-            Ok(outcome) => {
+            Ok((outcome, provenance)) => {
                 // Collect all agent activity.
                 if let Some(activity) = activity {
                     // If the activity is accepted or rejected then it's ready to integrate.
@@ -297,6 +355,15 @@ async fn app_validation_workflow_inner(
                     warn!(?outcome, ?dht_op_lite, "DhtOp has failed app validation");
                 }
 
+                // Work out which entry type metric to count before the outcome is written.
+                let entry_type_event = match &outcome {
+                    Outcome::Accepted => record_op_entry_type(&chain_op, &local_cascade).await,
+                    Outcome::Rejected(_) => record_op_entry_type(&chain_op, &local_cascade)
+                        .await
+                        .map(|(entry_def, _)| (entry_def, EntryTypeEvent::ValidationFailure)),
+                    Outcome::AwaitingDeps(_) => None,
+                };
+
                 let accepted_ops = accepted_ops.clone();
                 let awaiting_ops = awaiting_ops.clone();
                 let rejected_ops = rejected_ops.clone();
@@ -306,6 +373,9 @@ async fn app_validation_workflow_inner(
                     .write_async(move|txn| match outcome {
                         Outcome::Accepted => {
                             accepted_ops.fetch_add(1, Ordering::SeqCst);
+                            if let Some(provenance) = &provenance {
+                                set_validation_provenance(txn, &dht_op_hash, provenance)?;
+                            }
                             aitia::trace!(&hc_sleuth::Event::AppValidated {
                                 by: sleuth_id.clone(),
                                 op: dht_op_hash.clone()
@@ -332,6 +402,9 @@ async fn app_validation_workflow_inner(
                         }
                         Outcome::Rejected(_) => {
                             rejected_ops.fetch_add(1, Ordering::SeqCst);
+                            if let Some(provenance) = &provenance {
+                                set_validation_provenance(txn, &dht_op_hash, provenance)?;
+                            }
                             tracing::info!(
                             "Received invalid op. The op author will be blocked. Op: {dht_op_lite:?}"
                         );
@@ -345,6 +418,8 @@ async fn app_validation_workflow_inner(
                     .await;
                 if let Err(err) = write_result {
                     tracing::error!(?chain_op, ?err, "Error updating dht op in database.");
+                } else if let Some((entry_def, event)) = entry_type_event {
+                    ENTRY_TYPE_COUNTERS.record(&dna_hash, &entry_def, event);
                 }
             }
             Err(err) => {
@@ -394,6 +469,91 @@ async fn app_validation_workflow_inner(
     Ok(outcome_summary)
 }
 
+/// The number of hinted dependencies fetched concurrently.
+const DEPENDENCY_PREFETCH_CONCURRENCY: usize = 32;
+
+/// Run the dependency hints callback for every op about to be validated and
+/// fetch the hinted dependencies in parallel.
+///
+/// This is only an optimisation, so failures are logged and otherwise ignored.
+/// Any dependency that is still missing will be found by the validation
+/// callback as usual.
+async fn prefetch_dependency_hints(
+    sorted_dht_ops: &[DhtOpHashed],
+    dna_hash: Arc<DnaHash>,
+    conductor: &ConductorHandle,
+    workspace: &AppValidationWorkspace,
+    network: &HolochainP2pDna,
+    cascade: Arc<CascadeImpl>,
+    validation_dependencies: &Mutex<ValidationDependencies>,
+) {
+    use futures::StreamExt;
+
+    let Ok(ribosome) = conductor.get_ribosome(dna_hash.as_ref()) else {
+        return;
+    };
+    let host_fn_workspace = match workspace.validation_workspace().await {
+        Ok(w) => w,
+        Err(err) => {
+            tracing::warn!(?err, "Could not get workspace to collect dependency hints");
+            return;
+        }
+    };
+    let generic_network: GenericNetwork = Arc::new(network.clone());
+
+    let mut hints = HashSet::new();
+    for dht_op in sorted_dht_ops {
+        let DhtOp::ChainOp(chain_op) = &dht_op.content else {
+            continue;
+        };
+        let Ok(op) = chain_op_to_op(*chain_op.clone(), cascade.clone()).await else {
+            continue;
+        };
+        let Ok(zomes_to_invoke) =
+            get_zomes_to_invoke(&op, &host_fn_workspace, generic_network.clone(), &ribosome).await
+        else {
+            continue;
+        };
+        let invocation = match DependencyHintsInvocation::new(zomes_to_invoke, &op) {
+            Ok(invocation) => invocation,
+            Err(err) => {
+                tracing::warn!(?err, "Could not encode op for dependency hints");
+                continue;
+            }
+        };
+        match ribosome.run_dependency_hints(DependencyHintsHostAccess, invocation) {
+            Ok(result) => hints.extend(result.0),
+            Err(err) => tracing::warn!(?err, "Dependency hints callback failed"),
+        }
+    }
+
+    // Skip anything which is already being fetched because an earlier
+    // validation found it missing.
+    {
+        let validation_dependencies = validation_dependencies.lock();
+        hints.retain(|hash| !validation_dependencies.missing_hashes.contains_key(hash));
+    }
+    if hints.is_empty() {
+        return;
+    }
+    tracing::debug!("prefetching {} hinted validation dependencies", hints.len());
+
+    // Local data is returned straight away, anything else is fetched from the network.
+    futures::stream::iter(hints)
+        .for_each_concurrent(DEPENDENCY_PREFETCH_CONCURRENCY, |hash| {
+            let cascade = cascade.clone();
+            async move {
+                if let Err(err) = cascade
+                    .retrieve(hash.clone(), NetworkGetOptions::must_get_options())
+                    .await
+                {
+                    tracing::debug!(?hash, ?err, "Failed to prefetch hinted dependency");
+                }
+            }
+        })
+        .await;
+}
+
 // This fn is only used in the zome call workflow's inline validation.
 pub async fn record_to_op(
     record: Record,
@@ -459,7 +619,7 @@ async fn chain_op_to_op(chain_op: ChainOp, cascade: Arc<impl Cascade>) -> AppVal
         ChainOp::RegisterUpdatedContent(signature, update, entry)
         | ChainOp::RegisterUpdatedRecord(signature, update, entry) => {
             let new_entry = match update.entry_type.visibility() {
-                EntryVisibility::Public => match entry.into_option() {
+                EntryVisibility::Public | EntryVisibility::Unlisted => match entry.into_option() {
                     Some(entry) => Some(entry),
                     None => Some(
                         cascade
@@ -510,7 +670,7 @@ async fn validate_op_outer(
     workspace: &AppValidationWorkspace,
     network: &HolochainP2pDna,
     validation_dependencies: Arc<Mutex<ValidationDependencies>>,
-) -> AppValidationOutcome<Outcome> {
+) -> AppValidationOutcome<(Outcome, ValidationProvenance)> {
     // Get the workspace for the validation calls
     let host_fn_workspace = workspace.validation_workspace().await?;
 
@@ -519,7 +679,7 @@ async fn validate_op_outer(
         .get_ribosome(dna_hash.as_ref())
         .map_err(|_| AppValidationError::DnaMissing((*dna_hash).clone()))?;
 
-    validate_op(
+    validate_op_with_provenance(
         op,
         dht_op_hash,
         host_fn_workspace,
@@ -540,6 +700,30 @@ pub async fn validate_op(
     conductor_handle: &ConductorHandle,
     validation_dependencies: Arc<Mutex<ValidationDependencies>>,
 ) -> AppValidationOutcome<Outcome> {
+    validate_op_with_provenance(
+        op,
+        dht_op_hash,
+        workspace,
+        network,
+        ribosome,
+        conductor_handle,
+        validation_dependencies,
+    )
+    .await
+    .map(|(outcome, _)| outcome)
+}
+
+/// Validate an op, also returning the integrity zomes and conductor version
+/// it was validated with.
+async fn validate_op_with_provenance(
+    op: &Op,
+    dht_op_hash: &DhtOpHash,
+    workspace: HostFnWorkspaceRead,
+    network: &HolochainP2pDna,
+    ribosome: &impl RibosomeT,
+    conductor_handle: &ConductorHandle,
+    validation_dependencies: Arc<Mutex<ValidationDependencies>>,
+) -> AppValidationOutcome<(Outcome, ValidationProvenance)> {
     check_entry_def(op, &network.dna_hash(), conductor_handle)
         .await
         .map_err(AppValidationError::SysValidationError)?;
@@ -551,6 +735,7 @@ pub async fn validate_op(
         tracing::error!(?op, ?err, "Error getting zomes to invoke to validate op.");
     };
     let zomes_to_invoke = zomes_to_invoke?;
+    let provenance = validation_provenance(&zomes_to_invoke, ribosome);
     let invocation = ValidateInvocation::new(zomes_to_invoke, op)
         .map_err(|e| AppValidationError::RibosomeError(e.into()))?;
 
@@ -564,18 +749,50 @@ pub async fn validate_op(
     )
     .await?;
 
-    Ok(outcome)
+    Ok((outcome, provenance))
+}
+
+/// The wasm hashes of the integrity zomes which are invoked to validate an op,
+/// along with the version of this conductor.
+fn validation_provenance(
+    zomes_to_invoke: &ZomesToInvoke,
+    ribosome: &impl RibosomeT,
+) -> ValidationProvenance {
+    let integrity_wasm_hashes = match zomes_to_invoke {
+        ZomesToInvoke::OneIntegrity(zome) => zome
+            .zome_def()
+            .wasm_hash(zome.zome_name())
+            .into_iter()
+            .collect(),
+        _ => ribosome
+            .dna_def()
+            .integrity_zomes
+            .iter()
+            .filter_map(|(zome_name, def)| def.wasm_hash(zome_name).ok())
+            .collect(),
+    };
+    ValidationProvenance {
+        integrity_wasm_hashes,
+        conductor_version: crate::HOLOCHAIN_VERSION.to_string(),
+    }
 }
 
 /// Check the AppEntryDef is valid for the zome.
 /// Check the EntryDefId and ZomeIndex are in range.
+/// Check the entry is within the size limit of its entry type.
 async fn check_entry_def(
     op: &Op,
     dna_hash: &DnaHash,
     conductor: &Conductor,
 ) -> SysValidationResult<()> {
     if let Some((_, EntryType::App(app_entry_def))) = op.entry_data() {
-        check_app_entry_def(app_entry_def, dna_hash, conductor).await
+        let entry = match op {
+            Op::StoreRecord(StoreRecord { record }) => record.entry().as_option(),
+            Op::StoreEntry(StoreEntry { entry, .. }) => Some(entry),
+            Op::RegisterUpdate(RegisterUpdate { new_entry, .. }) => new_entry.as_ref(),
+            _ => None,
+        };
+        check_app_entry_def(app_entry_def, entry, dna_hash, conductor).await
     } else {
         Ok(())
     }
@@ -583,8 +800,10 @@ async fn check_entry_def(
 
 /// Check the AppEntryDef is valid for the zome.
 /// Check the EntryDefId and ZomeIndex are in range.
+/// Check the entry, if there is one, is within the size limit of its entry type.
 async fn check_app_entry_def(
     app_entry_def: &AppEntryDef,
+    entry: Option<&Entry>,
     dna_hash: &DnaHash,
     conductor: &Conductor,
 ) -> SysValidationResult<()> {
@@ -603,16 +822,30 @@ async fn check_app_entry_def(
 
     let entry_def = get_entry_def(app_entry_def.entry_index(), zome, dna_hash, conductor).await?;
 
-    // Check the visibility and return
-    match entry_def {
-        Some(entry_def) => {
-            if entry_def.visibility == *app_entry_def.visibility() {
+    // Check the visibility
+    let entry_def =
+        entry_def.ok_or_else(|| ValidationOutcome::EntryDefId(app_entry_def.clone()))?;
+    if entry_def.visibility != *app_entry_def.visibility() {
+        return Err(ValidationOutcome::EntryVisibility(app_entry_def.clone()).into());
+    }
+
+    // Check the size limit of the entry type and return
+    match (entry, entry_def.policies.max_size) {
+        (
+            Some(Entry::App(bytes) | Entry::CounterSign(_, bytes) | Entry::DualSign(_, bytes)),
+            Some(max_size),
+        ) => {
+            let size = bytes.bytes().len();
+            if entry_def.policies.allows_size(size) {
                 Ok(())
             } else {
-                Err(ValidationOutcome::EntryVisibility(app_entry_def.clone()).into())
+                Err(
+                    ValidationOutcome::EntryTooLargeForType(app_entry_def.clone(), size, max_size)
+                        .into(),
+                )
             }
         }
-        None => Err(ValidationOutcome::EntryDefId(app_entry_def.clone()).into()),
+        _ => Ok(()),
     }
 }
 
@@ -733,6 +966,43 @@ async fn retrieve_deleted_action(
     Ok(deleted_action)
 }
 
+/// The app entry type of the action of a store record op and what the action does
+/// to it. A delete is counted against the type of the entry it deletes.
+///
+/// The cascade must be local only, so that counting never goes to the network.
+/// A delete whose deleted action isn't held locally isn't counted.
+async fn record_op_entry_type(
+    chain_op: &ChainOp,
+    cascade: &CascadeImpl,
+) -> Option<(AppEntryDef, EntryTypeEvent)> {
+    let ChainOp::StoreRecord(_, action, _) = chain_op else {
+        return None;
+    };
+    let (entry_type, event) = match action {
+        Action::Create(Create { entry_type, .. }) => (entry_type.clone(), EntryTypeEvent::Create),
+        Action::Update(Update { entry_type, .. }) => (entry_type.clone(), EntryTypeEvent::Update),
+        Action::Delete(Delete {
+            deletes_address, ..
+        }) => {
+            // The deleted action was already retrieved to validate the delete,
+            // so it's usually in the cache by now.
+            let (deleted_action, _) = cascade
+                .retrieve_action(deletes_address.clone(), NetworkGetOptions::default())
+                .await
+                .ok()??;
+            (
+                deleted_action.action().entry_type()?.clone(),
+                EntryTypeEvent::Delete,
+            )
+        }
+        _ => return None,
+    };
+    match entry_type {
+        EntryType::App(entry_def) => Some((entry_def, event)),
+        _ => None,
+    }
+}
+
 fn get_integrity_zome_from_ribosome(
     zome_index: &ZomeIndex,
     ribosome: &impl RibosomeT,
@@ -751,8 +1021,9 @@ async fn run_validation_callback(
     network: GenericNetwork,
     validation_dependencies: Arc<Mutex<ValidationDependencies>>,
 ) -> AppValidationResult<Outcome> {
+    let action_cache = validation_dependencies.lock().action_cache();
     let validate_result = ribosome.run_validate(
-        ValidateHostAccess::new(workspace.clone(), network.clone()),
+        ValidateHostAccess::new(workspace.clone(), network.clone(), action_cache),
         invocation.clone(),
     )?;
     match validate_result {
@@ -885,6 +1156,7 @@ pub struct AppValidationWorkspace {
     cache: DbWrite<DbKindCache>,
     keystore: MetaLairClient,
     dna_def: Arc<DnaDef>,
+    validation_queue_order: Arc<parking_lot::RwLock<ValidationQueueOrder>>,
 }
 
 impl AppValidationWorkspace {
@@ -903,9 +1175,25 @@ impl AppValidationWorkspace {
             cache,
             keystore,
             dna_def,
+            validation_queue_order: Default::default(),
         }
     }
 
+    /// Process ops in the order held by this shared setting,
+    /// which can be changed while the workflow is running.
+    pub fn with_validation_queue_order(
+        mut self,
+        validation_queue_order: Arc<parking_lot::RwLock<ValidationQueueOrder>>,
+    ) -> Self {
+        self.validation_queue_order = validation_queue_order;
+        self
+    }
+
+    /// The DHT database whose ops are validated.
+    pub fn dht_db(&self) -> &DbWrite<DbKindDht> {
+        &self.dht_db
+    }
+
     pub async fn validation_workspace(&self) -> AppValidationResult<HostFnWorkspaceRead> {
         Ok(HostFnWorkspace::new(
             self.authored_db.clone(),
@@ -925,6 +1213,14 @@ impl AppValidationWorkspace {
             .with_dht(self.dht_db.clone().into())
             .with_network(Arc::new(network), self.cache.clone())
     }
+
+    /// A cascade over the local databases only, which never goes to the network.
+    pub fn local_cascade(&self) -> CascadeImpl {
+        CascadeImpl::empty()
+            .with_authored(self.authored_db.clone())
+            .with_dht(self.dht_db.clone().into())
+            .with_cache(self.cache.clone())
+    }
 }
 
 pub fn put_validation_limbo(
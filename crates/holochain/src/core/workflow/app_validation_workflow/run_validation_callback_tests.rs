@@ -270,6 +270,7 @@ async fn validation_callback_awaiting_deps_agent_activity() {
                     chain_top: delete.as_hash().clone(),
                     filters: ChainFilters::Until(filter_hashes),
                     include_cached_entries: false,
+                    until_timestamp: None,
                 };
                 let result = api.must_get_agent_activity(MustGetAgentActivityInput {
                     author: delete.hashed.author.clone(),
@@ -545,6 +546,7 @@ async fn validation_callback_prevent_multiple_identical_agent_activity_fetches()
                     chain_top: delete.as_hash().clone(),
                     filters: ChainFilters::Until(filter_hashes),
                     include_cached_entries: false,
+                    until_timestamp: None,
                 };
                 let result = api.must_get_agent_activity(MustGetAgentActivityInput {
                     author: delete.hashed.author.clone(),
@@ -84,11 +84,13 @@ async fn main_workflow() {
 
     // check there are no ops to app validate
     // genesis entries have already been validated at this stage
-    let ops_to_validate =
-        validation_query::get_ops_to_app_validate(&app_validation_workspace.dht_db)
-            .await
-            .unwrap()
-            .len();
+    let ops_to_validate = validation_query::get_ops_to_app_validate(
+        &app_validation_workspace.dht_db,
+        ValidationQueueOrder::OpType,
+    )
+    .await
+    .unwrap()
+    .len();
     assert_eq!(ops_to_validate, 0);
 
     // create op that following delete op depends on
@@ -109,6 +111,7 @@ async fn main_workflow() {
     let dht_delete_op = ChainOp::RegisterDeletedEntryAction(fixt!(Signature), delete);
     let dht_delete_op_hash = DhtOpHash::with_data_sync(&dht_delete_op);
     let dht_delete_op_hashed = DhtOpHashed::from_content_sync(dht_delete_op);
+    let validated_op_hash = dht_delete_op_hash.clone();
 
     // insert op to validate in dht db and mark ready for app validation
     app_validation_workspace.dht_db.test_write(move |txn| {
@@ -117,11 +120,13 @@ async fn main_workflow() {
     });
 
     // check delete op is now counted as op to validate
-    let ops_to_validate =
-        validation_query::get_ops_to_app_validate(&app_validation_workspace.dht_db)
-            .await
-            .unwrap()
-            .len();
+    let ops_to_validate = validation_query::get_ops_to_app_validate(
+        &app_validation_workspace.dht_db,
+        ValidationQueueOrder::OpType,
+    )
+    .await
+    .unwrap()
+    .len();
     assert_eq!(ops_to_validate, 1);
 
     let validation_dependencies = Arc::new(Mutex::new(ValidationDependencies::new()));
@@ -161,11 +166,13 @@ async fn main_workflow() {
     });
 
     // there is still the 1 delete op to be validated
-    let ops_to_validate =
-        validation_query::get_ops_to_app_validate(&app_validation_workspace.dht_db)
-            .await
-            .unwrap()
-            .len();
+    let ops_to_validate = validation_query::get_ops_to_app_validate(
+        &app_validation_workspace.dht_db,
+        ValidationQueueOrder::OpType,
+    )
+    .await
+    .unwrap()
+    .len();
     assert_eq!(ops_to_validate, 1);
 
     // run validation workflow
@@ -196,12 +203,36 @@ async fn main_workflow() {
     );
 
     // check ops to validate is 0 now after having been validated
-    let ops_to_validate =
-        validation_query::get_ops_to_app_validate(&app_validation_workspace.dht_db)
-            .await
-            .unwrap()
-            .len();
+    let ops_to_validate = validation_query::get_ops_to_app_validate(
+        &app_validation_workspace.dht_db,
+        ValidationQueueOrder::OpType,
+    )
+    .await
+    .unwrap()
+    .len();
     assert_eq!(ops_to_validate, 0);
+
+    // the validated op records what it was validated with,
+    // and inline zomes have no wasm hash
+    let provenance = app_validation_workspace.dht_db.test_read(move |txn| {
+        let blob: Option<Vec<u8>> = txn
+            .query_row(
+                "SELECT ValidationProvenance.blob FROM DhtOp
+                LEFT JOIN ValidationProvenance ON DhtOp.validation_provenance_id = ValidationProvenance.id
+                WHERE DhtOp.hash = :hash",
+                named_params! { ":hash": validated_op_hash },
+                |row| row.get(0),
+            )
+            .unwrap();
+        blob.map(|blob| from_blob::<ValidationProvenance>(blob).unwrap())
+    });
+    assert_eq!(
+        provenance,
+        Some(ValidationProvenance {
+            integrity_wasm_hashes: vec![],
+            conductor_version: crate::HOLOCHAIN_VERSION.to_string(),
+        })
+    );
 }
 
 // test that app validation validates multiple ops in one workflow run where
@@ -292,11 +323,13 @@ async fn validate_ops_in_sequence_must_get_agent_activity() {
 
     // check there are no ops to app validate
     // genesis entries have already been validated at this stage
-    let ops_to_validate =
-        validation_query::get_ops_to_app_validate(&app_validation_workspace.dht_db)
-            .await
-            .unwrap()
-            .len();
+    let ops_to_validate = validation_query::get_ops_to_app_validate(
+        &app_validation_workspace.dht_db,
+        ValidationQueueOrder::OpType,
+    )
+    .await
+    .unwrap()
+    .len();
     assert_eq!(ops_to_validate, 0);
 
     // insert create and delete op in dht db and mark ready for app validation
@@ -313,11 +346,13 @@ async fn validate_ops_in_sequence_must_get_agent_activity() {
     });
 
     // check create and delete op are now counted as ops to validate
-    let ops_to_validate =
-        validation_query::get_ops_to_app_validate(&app_validation_workspace.dht_db)
-            .await
-            .unwrap()
-            .len();
+    let ops_to_validate = validation_query::get_ops_to_app_validate(
+        &app_validation_workspace.dht_db,
+        ValidationQueueOrder::OpType,
+    )
+    .await
+    .unwrap()
+    .len();
     assert_eq!(ops_to_validate, 2);
 
     let validation_dependencies = Arc::new(Mutex::new(ValidationDependencies::new()));
@@ -350,11 +385,13 @@ async fn validate_ops_in_sequence_must_get_agent_activity() {
     );
 
     // check ops to validate is also 0
-    let ops_to_validate =
-        validation_query::get_ops_to_app_validate(&app_validation_workspace.dht_db)
-            .await
-            .unwrap()
-            .len();
+    let ops_to_validate = validation_query::get_ops_to_app_validate(
+        &app_validation_workspace.dht_db,
+        ValidationQueueOrder::OpType,
+    )
+    .await
+    .unwrap()
+    .len();
     assert_eq!(ops_to_validate, 0);
 }
 
@@ -414,11 +451,13 @@ async fn validate_ops_in_sequence_must_get_action() {
 
     // check there are no ops to app validate
     // genesis entries have already been validated at this stage
-    let ops_to_validate =
-        validation_query::get_ops_to_app_validate(&app_validation_workspace.dht_db)
-            .await
-            .unwrap()
-            .len();
+    let ops_to_validate = validation_query::get_ops_to_app_validate(
+        &app_validation_workspace.dht_db,
+        ValidationQueueOrder::OpType,
+    )
+    .await
+    .unwrap()
+    .len();
     assert_eq!(ops_to_validate, 0);
 
     // create op that following delete op depends on
@@ -455,11 +494,13 @@ async fn validate_ops_in_sequence_must_get_action() {
     });
 
     // check create and delete op are now counted as ops to validate
-    let ops_to_validate =
-        validation_query::get_ops_to_app_validate(&app_validation_workspace.dht_db)
-            .await
-            .unwrap()
-            .len();
+    let ops_to_validate = validation_query::get_ops_to_app_validate(
+        &app_validation_workspace.dht_db,
+        ValidationQueueOrder::OpType,
+    )
+    .await
+    .unwrap()
+    .len();
     assert_eq!(ops_to_validate, 2);
 
     let validation_dependencies = Arc::new(Mutex::new(ValidationDependencies::new()));
@@ -492,11 +533,13 @@ async fn validate_ops_in_sequence_must_get_action() {
     );
 
     // check ops to validate is also 0
-    let ops_to_validate =
-        validation_query::get_ops_to_app_validate(&app_validation_workspace.dht_db)
-            .await
-            .unwrap()
-            .len();
+    let ops_to_validate = validation_query::get_ops_to_app_validate(
+        &app_validation_workspace.dht_db,
+        ValidationQueueOrder::OpType,
+    )
+    .await
+    .unwrap()
+    .len();
     assert_eq!(ops_to_validate, 0);
 }
 
@@ -613,11 +656,13 @@ async fn handle_error_in_op_validation() {
     });
 
     // check ops are now counted as ops to validate
-    let ops_to_validate =
-        validation_query::get_ops_to_app_validate(&app_validation_workspace.dht_db)
-            .await
-            .unwrap()
-            .len();
+    let ops_to_validate = validation_query::get_ops_to_app_validate(
+        &app_validation_workspace.dht_db,
+        ValidationQueueOrder::OpType,
+    )
+    .await
+    .unwrap()
+    .len();
     assert_eq!(ops_to_validate, 2);
 
     let validation_dependencies = Arc::new(Mutex::new(ValidationDependencies::new()));
@@ -652,11 +697,13 @@ async fn handle_error_in_op_validation() {
         } if actual_failed == expected_failed
     );
 
-    let ops_to_validate =
-        validation_query::get_ops_to_app_validate(&app_validation_workspace.dht_db)
-            .await
-            .unwrap()
-            .len();
+    let ops_to_validate = validation_query::get_ops_to_app_validate(
+        &app_validation_workspace.dht_db,
+        ValidationQueueOrder::OpType,
+    )
+    .await
+    .unwrap()
+    .len();
     assert_eq!(ops_to_validate, 1);
 }
 
@@ -891,7 +938,7 @@ async fn check_app_entry_def_test() {
     // ## Dna is missing
     let app_entry_def_0 = AppEntryDef::new(0.into(), 0.into(), EntryVisibility::Public);
     assert_matches!(
-        check_app_entry_def(&app_entry_def_0, &dna_hash, &conductor_handle).await,
+        check_app_entry_def(&app_entry_def_0, None, &dna_hash, &conductor_handle).await,
         Err(SysValidationError::DnaMissing(_))
     );
 
@@ -902,7 +949,7 @@ async fn check_app_entry_def_test() {
     // ## EntryId is out of range
     let app_entry_def_1 = AppEntryDef::new(10.into(), 0.into(), EntryVisibility::Public);
     assert_matches!(
-        check_app_entry_def(&app_entry_def_1, &dna_hash, &conductor_handle).await,
+        check_app_entry_def(&app_entry_def_1, None, &dna_hash, &conductor_handle).await,
         Err(SysValidationError::ValidationOutcome(
             ValidationOutcome::EntryDefId(_)
         ))
@@ -910,7 +957,7 @@ async fn check_app_entry_def_test() {
 
     let app_entry_def_2 = AppEntryDef::new(0.into(), 100.into(), EntryVisibility::Public);
     assert_matches!(
-        check_app_entry_def(&app_entry_def_2, &dna_hash, &conductor_handle).await,
+        check_app_entry_def(&app_entry_def_2, None, &dna_hash, &conductor_handle).await,
         Err(SysValidationError::ValidationOutcome(
             ValidationOutcome::ZomeIndex(_)
         ))
@@ -919,12 +966,12 @@ async fn check_app_entry_def_test() {
     // ## EntryId is in range for dna
     let app_entry_def_3 = AppEntryDef::new(0.into(), 0.into(), EntryVisibility::Public);
     assert_matches!(
-        check_app_entry_def(&app_entry_def_3, &dna_hash, &conductor_handle).await,
+        check_app_entry_def(&app_entry_def_3, None, &dna_hash, &conductor_handle).await,
         Ok(_)
     );
     let app_entry_def_4 = AppEntryDef::new(0.into(), 0.into(), EntryVisibility::Private);
     assert_matches!(
-        check_app_entry_def(&app_entry_def_4, &dna_hash, &conductor_handle).await,
+        check_app_entry_def(&app_entry_def_4, None, &dna_hash, &conductor_handle).await,
         Err(SysValidationError::ValidationOutcome(
             ValidationOutcome::EntryVisibility(_)
         ))
@@ -933,7 +980,67 @@ async fn check_app_entry_def_test() {
     // ## Can get the entry from the entry def
     let app_entry_def_5 = AppEntryDef::new(0.into(), 0.into(), EntryVisibility::Public);
     assert_matches!(
-        check_app_entry_def(&app_entry_def_5, &dna_hash, &conductor_handle).await,
+        check_app_entry_def(&app_entry_def_5, None, &dna_hash, &conductor_handle).await,
+        Ok(_)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn check_app_entry_def_max_size_test() {
+    holochain_trace::test_run();
+    let entry_def = EntryDef {
+        id: "note".into(),
+        policies: EntryDefPolicies::default().with_max_size(4),
+        ..Default::default()
+    };
+    let zomeset = InlineZomeSet::new_unique([("integrity", vec![entry_def], 0)], ["coordinator"]);
+    let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(zomeset).await;
+    let dna_hash = dna_file.dna_hash().clone();
+
+    let db_dir = test_db_dir();
+    let conductor_handle = Conductor::builder()
+        .with_data_root_path(db_dir.path().to_path_buf().into())
+        .test(&[])
+        .await
+        .unwrap();
+    conductor_handle.register_dna(dna_file).await.unwrap();
+
+    let app_entry_def = AppEntryDef::new(0.into(), 0.into(), EntryVisibility::Public);
+    let entry = |len| {
+        Entry::App(AppEntryBytes(SerializedBytes::from(UnsafeBytes::from(
+            vec![0; len],
+        ))))
+    };
+
+    // ## Entry is within the max size of its type
+    assert_matches!(
+        check_app_entry_def(
+            &app_entry_def,
+            Some(&entry(4)),
+            &dna_hash,
+            &conductor_handle
+        )
+        .await,
+        Ok(_)
+    );
+
+    // ## Entry is larger than the max size of its type
+    assert_matches!(
+        check_app_entry_def(
+            &app_entry_def,
+            Some(&entry(5)),
+            &dna_hash,
+            &conductor_handle
+        )
+        .await,
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::EntryTooLargeForType(_, 5, 4)
+        ))
+    );
+
+    // ## Ops without the entry have nothing to check
+    assert_matches!(
+        check_app_entry_def(&app_entry_def, None, &dna_hash, &conductor_handle).await,
         Ok(_)
     );
 }
@@ -1045,6 +1152,8 @@ fn show_limbo(txn: &Transaction) -> Vec<DhtOpLite> {
                 let ((warrant, timestamp), signature) = warrant.into();
                 Ok(WarrantOp::new(warrant, author, signature, timestamp).into())
             }
+            DhtOpType::AgentInfo => unreachable!("agent infos aren't published in these tests"),
+            DhtOpType::BlobChunk => unreachable!("blob chunks aren't published in these tests"),
         }
     })
     .unwrap()
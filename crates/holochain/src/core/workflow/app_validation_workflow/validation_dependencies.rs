@@ -3,6 +3,7 @@ use std::{
     time::{Duration, Instant},
 };
 
+use crate::core::ribosome::guest_callback::validate::ValidateActionCache;
 use holo_hash::{AnyDhtHash, DhtOpHash};
 use holochain_types::dht_op::DhtOpHashed;
 
@@ -18,6 +19,8 @@ pub struct ValidationDependencies {
     /// Missing hashes that are being fetched, along with a set of DhtOps that depend
     /// on the hash and the timestamp a fetch was attempted.
     pub(super) missing_hashes: HashMap<AnyDhtHash, MissingHashProperties>,
+    /// Actions fetched by validation callbacks, shared between validation runs.
+    action_cache: ValidateActionCache,
 }
 
 impl Default for ValidationDependencies {
@@ -32,9 +35,15 @@ impl ValidationDependencies {
     pub fn new() -> Self {
         Self {
             missing_hashes: HashMap::new(),
+            action_cache: ValidateActionCache::default(),
         }
     }
 
+    /// The cache of actions fetched by validation callbacks.
+    pub fn action_cache(&self) -> ValidateActionCache {
+        self.action_cache.clone()
+    }
+
     /// Returns true if this is a new missing hash.
     pub fn insert_missing_hash_for_op(&mut self, hash: AnyDhtHash, dht_op_hash: DhtOpHash) -> bool {
         match self.missing_hashes.entry(hash.clone()) {
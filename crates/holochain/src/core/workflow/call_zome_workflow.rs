@@ -4,10 +4,12 @@ use super::app_validation_workflow::Outcome;
 use super::app_validation_workflow::ValidationDependencies;
 use super::error::WorkflowResult;
 use super::sys_validation_workflow::sys_validate_record;
+use crate::conductor::api::error::ConductorApiError;
 use crate::conductor::api::CellConductorApi;
 use crate::conductor::api::CellConductorApiT;
 use crate::conductor::ConductorHandle;
 use crate::core::queue_consumer::TriggerSender;
+use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::error::RibosomeResult;
 use crate::core::ribosome::guest_callback::post_commit::send_post_commit;
 use crate::core::ribosome::RibosomeT;
@@ -21,6 +23,7 @@ use holochain_state::source_chain::SourceChainError;
 use holochain_types::prelude::*;
 use holochain_zome_types::record::Record;
 use parking_lot::Mutex;
+use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::broadcast;
 use tracing::instrument;
@@ -38,6 +41,17 @@ pub struct CallZomeWorkflowArgs<RibosomeT> {
     pub conductor_handle: ConductorHandle,
     pub is_root_zome_call: bool,
     pub cell_id: CellId,
+    /// If set, wait up to this long for the committed actions to be accepted
+    /// by at least one authority before returning.
+    pub wait_for_publish: Option<std::time::Duration>,
+    /// Announces the actions of the cell which have been accepted by an authority.
+    pub publish_accepted_tx: broadcast::Sender<ActionHash>,
+    /// A read-only call is authorized against query grants, can't use host
+    /// functions that write, and never flushes the workspace.
+    pub read_only: bool,
+    /// If set, the call is cancelled once it has run for this long.
+    /// Nothing it wrote is committed.
+    pub timeout: Option<std::time::Duration>,
 }
 
 #[instrument(skip(
@@ -64,12 +78,36 @@ where
         .dna_def()
         .get_coordinator_zome(args.invocation.zome.zome_name())
         .ok();
-    let should_write = args.is_root_zome_call;
+    let should_write = args.is_root_zome_call && !args.read_only;
     let conductor_handle = args.conductor_handle.clone();
     let signal_tx = args.signal_tx.clone();
-    let result =
-        call_zome_workflow_inner(workspace.clone(), network.clone(), keystore.clone(), args)
-            .await?;
+    let wait_for_publish = args.wait_for_publish;
+    let publish_accepted_tx = args.publish_accepted_tx.clone();
+    let cell_id = args.cell_id.clone();
+    let timeout = args.timeout;
+    let deadline = timeout.map(|timeout| std::time::Instant::now() + timeout);
+    let zome_name = args.invocation.zome.zome_name().clone();
+    let fn_name = args.invocation.fn_name.clone();
+    let result = call_zome_workflow_inner(
+        workspace.clone(),
+        network.clone(),
+        keystore.clone(),
+        args,
+        deadline,
+    )
+    .await?;
+
+    // A call which ran past its deadline was cancelled, possibly part way
+    // through, so its writes are dropped rather than flushed.
+    if let (Some(timeout), Some(deadline)) = (timeout, deadline) {
+        if std::time::Instant::now() >= deadline
+            || matches!(result, Err(RibosomeError::ZomeCallTimeout(..)))
+        {
+            return Ok(Err(RibosomeError::ZomeCallTimeout(
+                zome_name, fn_name, timeout,
+            )));
+        }
+    }
 
     // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
 
@@ -78,8 +116,19 @@ where
         let countersigning_op = workspace.source_chain().countersigning_op()?;
         match workspace.source_chain().flush(&network).await {
             Ok(flushed_actions) => {
+                queue_durable_calls(&conductor_handle, &workspace, &cell_id).await?;
+
                 // Skip if nothing was written
                 if !flushed_actions.is_empty() {
+                    // Subscribe before triggering publish so that no acceptance is missed.
+                    let publish_accepted = wait_for_publish.map(|timeout| {
+                        let action_hashes = flushed_actions
+                            .iter()
+                            .map(|a| a.as_hash().clone())
+                            .collect();
+                        (timeout, action_hashes, publish_accepted_tx.subscribe())
+                    });
+
                     match countersigning_op {
                         Some(op) => {
                             if let Err(error_response) =
@@ -115,8 +164,16 @@ where
                         )
                         .await?;
                     }
+
+                    if let Some((timeout, action_hashes, accepted)) = publish_accepted {
+                        await_publish_accepted(&cell_id, action_hashes, accepted, timeout).await;
+                    }
                 }
             }
+            // A frozen cell still serves reads, but refuses calls which would commit.
+            Err(SourceChainError::ChainFrozen) => {
+                return Ok(Err(RibosomeError::CellFrozen(cell_id)));
+            }
             err => {
                 err?;
             }
@@ -126,11 +183,65 @@ where
     Ok(result)
 }
 
+/// Hand the durable remote calls staged in the scratch to the conductor's
+/// outbox, once the writes made alongside them have been flushed.
+pub(crate) async fn queue_durable_calls(
+    conductor_handle: &ConductorHandle,
+    workspace: &SourceChainWorkspace,
+    cell_id: &CellId,
+) -> WorkflowResult<()> {
+    let calls = workspace
+        .source_chain()
+        .scratch()
+        .apply(|scratch| scratch.drain_durable_calls().collect::<Vec<_>>())
+        .map_err(SourceChainError::from)?;
+    conductor_handle
+        .queue_durable_calls_remote(cell_id.clone(), calls)
+        .await
+        .map_err(|e| Box::new(ConductorApiError::from(e)))?;
+    Ok(())
+}
+
+/// Wait until every one of the given actions has been accepted by at least
+/// one authority, as announced by the cell which received the receipts, or
+/// until the timeout elapses.
+async fn await_publish_accepted(
+    cell_id: &CellId,
+    mut action_hashes: HashSet<ActionHash>,
+    mut accepted: broadcast::Receiver<ActionHash>,
+    timeout: std::time::Duration,
+) {
+    let wait = async {
+        while !action_hashes.is_empty() {
+            match accepted.recv().await {
+                Ok(action_hash) => {
+                    action_hashes.remove(&action_hash);
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!(
+                        ?skipped,
+                        "Missed accepted actions while waiting for publish, acceptance may go unnoticed"
+                    );
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    };
+    if tokio::time::timeout(timeout, wait).await.is_err() {
+        tracing::info!(
+            ?cell_id,
+            ?timeout,
+            "Timed out waiting for committed actions to be accepted by an authority"
+        );
+    }
+}
+
 async fn call_zome_workflow_inner<Ribosome>(
     workspace: SourceChainWorkspace,
     network: HolochainP2pDna,
     keystore: MetaLairClient,
     args: CallZomeWorkflowArgs<Ribosome>,
+    deadline: Option<std::time::Instant>,
 ) -> WorkflowResult<ZomeCallResult>
 where
     Ribosome: RibosomeT + 'static,
@@ -141,6 +252,7 @@ where
         signal_tx,
         conductor_handle,
         cell_id,
+        read_only,
         ..
     } = args;
 
@@ -154,6 +266,8 @@ where
         network.clone(),
         signal_tx,
         call_zome_handle,
+        read_only,
+        deadline,
     );
     let (ribosome, result) =
         call_zome_function_authorized(ribosome, host_access, invocation).await?;
@@ -246,7 +360,7 @@ where
         let mut to_app_validate: Vec<Record> = Vec::with_capacity(scratch_records.len());
         // Loop forwards through all the new records
         for record in scratch_records {
-            sys_validate_record(&record, cascade.clone())
+            sys_validate_record(&record, ribosome.dna_def(), cascade.clone())
                 .await
                 // If the was en error exit
                 // If the validation failed, exit with an InvalidCommit
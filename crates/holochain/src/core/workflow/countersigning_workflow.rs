@@ -252,6 +252,10 @@ pub(crate) async fn countersigning_success(
             let author = author.clone();
             let entry_hash = entry_hash.clone();
             move |txn| {
+            // A frozen chain neither completes the session nor publishes it.
+            if holochain_state::chain_lock::is_chain_frozen(txn, &author)? {
+                return Err(SourceChainError::ChainFrozen);
+            }
             if let Some((cs_entry_hash, cs)) = current_countersigning_session(txn, Arc::new(author.clone()))? {
                 // Check we have the right session.
                 if cs_entry_hash == entry_hash {
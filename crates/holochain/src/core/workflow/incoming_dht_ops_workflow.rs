@@ -1,7 +1,10 @@
 //! The workflow and queue consumer for DhtOp integration
 
 use super::sys_validation_workflow::counterfeit_check_action;
+use super::sys_validation_workflow::counterfeit_check_agent_info;
+use super::sys_validation_workflow::counterfeit_check_blob_chunk;
 use super::{error::WorkflowResult, sys_validation_workflow::counterfeit_check_warrant};
+use crate::core::metrics::THROUGHPUT;
 use crate::{conductor::space::Space, core::queue_consumer::TriggerSender};
 use holo_hash::DhtOpHash;
 use holochain_sqlite::error::DatabaseResult;
@@ -104,6 +107,7 @@ pub async fn incoming_dht_ops_workflow(
         incoming_op_hashes,
         incoming_ops_batch,
         dht_db,
+        negative_cache,
         ..
     } = space;
 
@@ -133,12 +137,24 @@ pub async fn incoming_dht_ops_workflow(
                     "Dropping batch of {} ops because the current op failed counterfeit checks",
                     num_ops,
                 );
-                // TODO we are returning here without blocking this author?
+                // The conductor reports this to the network, which penalizes the sending peer.
                 return Err(e);
             }
         }
     }
 
+    // Gets for the hashes of these ops shouldn't be skipped any more.
+    if !negative_cache.is_empty() {
+        let hashes = filter_ops.iter().filter_map(|op| match &op.content {
+            DhtOp::ChainOp(op) => Some(op.action()),
+            DhtOp::WarrantOp(_) | DhtOp::AgentInfoOp(_) | DhtOp::BlobChunkOp(_) => None,
+        });
+        negative_cache.invalidate(hashes.flat_map(|action| {
+            let entry_hash = action.entry_hash().cloned().map(AnyDhtHash::from);
+            std::iter::once(ActionHash::with_data_sync(&action).into()).chain(entry_hash)
+        }));
+    }
+
     if !request_validation_receipt {
         // Filter the list of ops to only include those that are not already in the database.
         filter_ops = filter_existing_ops(&dht_db, filter_ops).await?;
@@ -152,6 +168,8 @@ pub async fn incoming_dht_ops_workflow(
         return Ok(());
     }
 
+    THROUGHPUT.record_ops_received(filter_ops.len());
+
     let (mut maybe_batch, rcv) =
         incoming_ops_batch.check_insert(request_validation_receipt, filter_ops);
 
@@ -216,6 +234,8 @@ async fn should_keep(op: &DhtOp) -> WorkflowResult<()> {
             counterfeit_check_action(signature, &action).await?;
         }
         DhtOp::WarrantOp(op) => counterfeit_check_warrant(op).await?,
+        DhtOp::AgentInfoOp(op) => counterfeit_check_agent_info(op).await?,
+        DhtOp::BlobChunkOp(op) => counterfeit_check_blob_chunk(op).await?,
     }
     Ok(())
 }
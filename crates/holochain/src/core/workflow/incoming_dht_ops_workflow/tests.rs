@@ -101,6 +101,42 @@ async fn can_retry_failed_op() {
     assert!(space.space.incoming_op_hashes.0.lock().is_empty());
 }
 
+// Gets which the authorities recently missed go to the network again once an op
+// for the action or its entry arrives by publish or gossip.
+#[tokio::test(flavor = "multi_thread")]
+async fn incoming_ops_invalidate_negative_cache() {
+    holochain_trace::test_run();
+
+    let space =
+        TestSpace::with_negative_cache_ttl(fixt!(DnaHash), std::time::Duration::from_secs(60));
+    let negative_cache = space.space.negative_cache.clone();
+    let keystore = test_keystore();
+    let (sys_validation_trigger, _sys_validation_rx) = TriggerSender::new();
+
+    let author = keystore.new_sign_keypair_random().await.unwrap();
+
+    let mut action = fixt!(Create);
+    action.author = author.clone();
+    let entry_hash: AnyDhtHash = action.entry_hash.clone().into();
+    let action = Action::Create(action);
+    let action_hash: AnyDhtHash = ActionHash::with_data_sync(&action).into();
+    let other: AnyDhtHash = fixt!(ActionHash).into();
+    let signature = author.sign(&keystore, &action).await.unwrap();
+    let op: DhtOp = ChainOp::RegisterAgentActivity(signature, action).into();
+
+    negative_cache.insert(action_hash.clone());
+    negative_cache.insert(entry_hash.clone());
+    negative_cache.insert(other.clone());
+
+    incoming_dht_ops_workflow(space.space.clone(), sys_validation_trigger, vec![op], false)
+        .await
+        .unwrap();
+
+    assert!(!negative_cache.contains(&action_hash));
+    assert!(!negative_cache.contains(&entry_hash));
+    assert!(negative_cache.contains(&other));
+}
+
 // Verifies that an op which has been republished will allow a new validation receipt to be requested.
 #[tokio::test(flavor = "multi_thread")]
 async fn republish_to_request_validation_receipt() {
@@ -51,6 +51,7 @@ where
     let coordinators = args.ribosome.dna_def().get_all_coordinators();
     let integrate_dht_ops_trigger = args.integrate_dht_ops_trigger.clone();
     let signal_tx = args.signal_tx.clone();
+    let cell_id = args.cell_id.clone();
     let result =
         initialize_zomes_workflow_inner(workspace.clone(), network.clone(), keystore.clone(), args)
             .await?;
@@ -60,6 +61,8 @@ where
     // only commit if the result was successful
     if result == InitResult::Pass {
         let flushed_actions = workspace.source_chain().flush(&network).await?;
+        super::call_zome_workflow::queue_durable_calls(&conductor_handle, &workspace, &cell_id)
+            .await?;
 
         send_post_commit(
             conductor_handle,
@@ -1,8 +1,11 @@
 //! The workflow and queue consumer for DhtOp integration
 
 use super::*;
+use crate::conductor::conductor::firehose::FirehoseSender;
+use crate::core::metrics::THROUGHPUT;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
+use holochain_conductor_api::FirehoseOp;
 use holochain_p2p::HolochainP2pDna;
 use holochain_p2p::HolochainP2pDnaT;
 use holochain_state::prelude::*;
@@ -14,18 +17,21 @@ mod query_tests;
 #[cfg(feature = "test_utils")]
 mod tests;
 
-#[instrument(skip(vault, trigger_receipt, network, dht_query_cache))]
+#[instrument(skip(vault, trigger_receipt, network, dht_query_cache, firehose))]
 pub async fn integrate_dht_ops_workflow(
     vault: DbWrite<DbKindDht>,
     dht_query_cache: DhtDbQueryCache,
     trigger_receipt: TriggerSender,
     network: HolochainP2pDna,
+    firehose: FirehoseSender,
 ) -> WorkflowResult<WorkComplete> {
     let start = std::time::Instant::now();
     let time = holochain_zome_types::prelude::Timestamp::now();
     // Get any activity from the cache that is ready to be integrated.
     let activity_to_integrate = dht_query_cache.get_activity_to_integrate().await?;
-    let (changed, activity_integrated) = vault
+    let dna_hash = network.dna_hash();
+    let stream_to_firehose = firehose.has_subscribers();
+    let (changed, activity_integrated, integrated_ops) = vault
         .write_async(move |txn| {
             let mut total = 0;
             if !activity_to_integrate.is_empty() {
@@ -82,7 +88,14 @@ pub async fn integrate_dht_ops_workflow(
 
                 })?;
             total += changed;
-            WorkflowResult::Ok((total, activity_to_integrate))
+            // Read back the ops integrated in this run for the firehose, only
+            // if a client is listening.
+            let integrated_ops = if stream_to_firehose && total > 0 {
+                integrated_ops(txn, &dna_hash, time)?
+            } else {
+                Vec::with_capacity(0)
+            };
+            WorkflowResult::Ok((total, activity_to_integrate, integrated_ops))
         })
         .await?;
     // Once the database transaction is committed, update the cache with the
@@ -92,6 +105,10 @@ pub async fn integrate_dht_ops_workflow(
         .await?;
     let ops_ps = changed as f64 / start.elapsed().as_micros() as f64 * 1_000_000.0;
     tracing::debug!(?changed, %ops_ps);
+    THROUGHPUT.record_ops_integrated(changed);
+    for op in integrated_ops {
+        firehose.send(op);
+    }
     if changed > 0 {
         trigger_receipt.trigger(&"integrate_dht_ops_workflow");
         network.new_integrated_data().await?;
@@ -100,3 +117,42 @@ pub async fn integrate_dht_ops_workflow(
         Ok(WorkComplete::Complete)
     }
 }
+
+/// Get the chain ops which were integrated at `when_integrated`, to stream
+/// them to the firehose interface.
+fn integrated_ops(
+    txn: &Transaction,
+    dna_hash: &DnaHash,
+    when_integrated: Timestamp,
+) -> StateQueryResult<Vec<FirehoseOp>> {
+    let mut stmt = txn.prepare_cached(holochain_sqlite::sql::sql_cell::INTEGRATED_OPS)?;
+    let ops = stmt
+        .query_and_then(
+            named_params! {
+                ":when_integrated": when_integrated,
+            },
+            |row| {
+                // Store entry ops for private entries, or for entries which
+                // have been purged, can't be sent.
+                let op = match holochain_state::query::map_sql_dht_op_common(
+                    false, false, "dht_type", row,
+                )? {
+                    Some(op) => op,
+                    None => return Ok(None),
+                };
+                StateQueryResult::Ok(Some(FirehoseOp {
+                    dna_hash: dna_hash.clone(),
+                    op_hash: row.get("dht_hash")?,
+                    op_type: op.get_type(),
+                    basis: row.get("basis_hash")?,
+                    author: row.get("author")?,
+                    authored_timestamp: row.get("authored_timestamp")?,
+                    when_integrated,
+                    op,
+                }))
+            },
+        )?
+        .filter_map(Result::transpose)
+        .collect::<StateQueryResult<Vec<_>>>()?;
+    Ok(ops)
+}
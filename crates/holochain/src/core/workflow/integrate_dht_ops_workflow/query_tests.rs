@@ -7,6 +7,7 @@ use holochain_sqlite::prelude::DatabaseResult;
 use holochain_state::prelude::*;
 use holochain_types::dht_op::DhtOpHashed;
 
+use crate::conductor::conductor::firehose::Firehose;
 use crate::test_utils::test_network;
 
 use super::*;
@@ -99,9 +100,18 @@ async fn integrate_query() {
     let (qt, _rx) = TriggerSender::new();
     let test_network = test_network(None, None).await;
     let holochain_p2p_cell = test_network.dna_network();
-    integrate_dht_ops_workflow(db.to_db().into(), db.to_db().into(), qt, holochain_p2p_cell)
-        .await
-        .unwrap();
+    let firehose = Firehose::default();
+    let mut firehose_rx = firehose.subscribe(holochain_p2p_cell.dna_hash());
+    let firehose_tx = firehose.create_send_handle(holochain_p2p_cell.dna_hash());
+    integrate_dht_ops_workflow(
+        db.to_db().into(),
+        db.to_db().into(),
+        qt,
+        holochain_p2p_cell,
+        firehose_tx,
+    )
+    .await
+    .unwrap();
     let hashes = db
         .write_async(move |txn| -> DatabaseResult<HashSet<DhtOpHash>> {
             let mut stmt =
@@ -123,6 +133,17 @@ async fn integrate_query() {
         debug!(?d, missing = ?expected.ops.get(d));
     }
     assert_eq!(hashes, expected.hashes);
+
+    // The ops integrated by this run were streamed to the firehose.
+    let mut streamed = Vec::new();
+    while let Ok(op) = firehose_rx.try_recv() {
+        streamed.push(op);
+    }
+    assert!(!streamed.is_empty());
+    assert!(streamed.iter().all(|op| hashes.contains(&op.op_hash)));
+    assert!(streamed
+        .iter()
+        .all(|op| op.when_integrated == streamed[0].when_integrated));
 }
 
 async fn create_and_insert_op(
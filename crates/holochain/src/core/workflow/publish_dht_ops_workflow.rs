@@ -23,7 +23,9 @@ use std::time;
 use tracing::*;
 
 mod publish_query;
-pub use publish_query::{get_ops_to_publish, num_still_needing_publish};
+pub use publish_query::{
+    get_blob_chunks_to_publish, get_ops_to_publish, num_still_needing_publish,
+};
 
 #[cfg(test)]
 mod unit_tests;
@@ -85,6 +87,31 @@ pub async fn publish_dht_ops_workflow(
         info!("published {}/{} ops", success.len(), to_publish_count);
     }
 
+    // Blob chunks are only announced by hash, the authorities fetch them.
+    let mut blob_chunks_published = Vec::new();
+    for (basis, op_hash) in get_blob_chunks_to_publish(agent.clone(), &db).await? {
+        match network
+            .publish(
+                false,
+                false,
+                basis,
+                agent.clone(),
+                vec![op_hash.clone()],
+                None,
+                None,
+            )
+            .await
+        {
+            Err(e) => {
+                if let holochain_p2p::HolochainP2pError::RoutingDnaError(_) = e {
+                    complete = WorkComplete::Incomplete(None);
+                }
+                warn!(failed_to_send_publish = ?e);
+            }
+            Ok(()) => blob_chunks_published.push(op_hash),
+        }
+    }
+
     let now = time::SystemTime::now().duration_since(time::UNIX_EPOCH)?;
     let continue_publish = db
         .write_async(move |txn| {
@@ -93,6 +120,12 @@ pub async fn publish_dht_ops_workflow(
                 let hash = DhtOpHash::from_kitsune(hash.data_ref());
                 set_last_publish_time(txn, &hash, now)?;
             }
+            for hash in blob_chunks_published {
+                use holochain_p2p::DhtOpHashExt;
+                let hash = DhtOpHash::from_kitsune(hash.data_ref());
+                set_last_publish_time(txn, &hash, now)?;
+                set_receipts_complete(txn, &hash, true)?;
+            }
             WorkflowResult::Ok(publish_query::num_still_needing_publish(txn, agent)? > 0)
         })
         .await?;
@@ -90,6 +90,65 @@ where
     results
 }
 
+/// Get the blob chunks an agent created which haven't been published yet.
+///
+/// Only the hashes are published, the authorities fetch the chunks. Chunks
+/// aren't part of the chain, so they are published once rather than until
+/// enough validation receipts come back.
+pub async fn get_blob_chunks_to_publish<AuthorDb>(
+    agent: AgentPubKey,
+    db: &AuthorDb,
+) -> WorkflowResult<Vec<(OpBasis, OpHashSized)>>
+where
+    AuthorDb: ReadAccess<DbKindAuthored>,
+{
+    let recency_threshold = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|epoch| epoch.checked_sub(MIN_PUBLISH_INTERVAL))
+        .map(|t| t.as_secs())
+        .unwrap_or(0);
+
+    db.read_async(move |txn| {
+        let mut stmt = txn.prepare_cached(
+            "
+            SELECT
+            DhtOp.hash as dht_hash,
+            DhtOp.basis_hash as basis_hash,
+            LENGTH(BlobChunk.blob) AS chunk_size
+            FROM BlobChunk
+            JOIN
+            DhtOp ON DhtOp.hash = BlobChunk.hash
+            WHERE
+            BlobChunk.author = :author
+            AND
+            DhtOp.withhold_publish IS NULL
+            AND
+            (DhtOp.last_publish_time IS NULL OR DhtOp.last_publish_time <= :recency_threshold)
+            AND
+            DhtOp.receipts_complete IS NULL
+            ",
+        )?;
+        let r = stmt.query_and_then(
+            named_params! {
+                ":author": agent,
+                ":recency_threshold": recency_threshold,
+            },
+            |row| {
+                let hash: DhtOpHash = row.get("dht_hash")?;
+                let chunk_size: usize = row.get("chunk_size")?;
+                let basis: OpBasis = row.get("basis_hash")?;
+                WorkflowResult::Ok((
+                    basis,
+                    OpHashSized::new(hash.to_kitsune(), Some(chunk_size.into())),
+                ))
+            },
+        )?;
+        r.collect()
+    })
+    .await
+}
+
 /// Get the number of ops that might need to publish again in the future.
 pub fn num_still_needing_publish(txn: &Transaction, agent: AgentPubKey) -> WorkflowResult<usize> {
     let count = txn.query_row(
@@ -112,9 +171,28 @@ pub fn num_still_needing_publish(txn: &Transaction, agent: AgentPubKey) -> Workf
             ":author": agent,
             ":store_entry": ChainOpType::StoreEntry,
         },
-        |row| row.get("num_ops"),
+        |row| row.get::<_, usize>("num_ops"),
+    )?;
+    let blob_chunks = txn.query_row(
+        "
+        SELECT
+        COUNT(DhtOp.rowid) as num_ops
+        FROM BlobChunk
+        JOIN
+        DhtOp ON DhtOp.hash = BlobChunk.hash
+        WHERE
+        BlobChunk.author = :author
+        AND
+        DhtOp.withhold_publish IS NULL
+        AND
+        DhtOp.receipts_complete IS NULL
+        ",
+        named_params! {
+            ":author": agent,
+        },
+        |row| row.get::<_, usize>("num_ops"),
     )?;
-    Ok(count)
+    Ok(count + blob_chunks)
 }
 
 #[cfg(test)]
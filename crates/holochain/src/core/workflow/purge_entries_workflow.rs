@@ -0,0 +1,129 @@
+//! The workflow which purges the bytes of deleted entries whose entry type has a [`PurgePolicy`].
+
+use super::*;
+use crate::conductor::entry_def_store::get_entry_def;
+use crate::conductor::ConductorHandle;
+use holochain_sqlite::sql::sql_cell::DELETED_ENTRY_ACTIONS;
+use holochain_state::prelude::*;
+use std::collections::HashMap;
+use tracing::*;
+
+#[cfg(test)]
+mod tests;
+
+/// An action which created a deleted entry, and when it was deleted
+/// if it has been.
+type DeletedEntryAction = (EntryHash, Action, Option<Timestamp>);
+
+/// Purge the deleted entries of this DNA whose retention period has passed.
+///
+/// Only the entry bytes are removed. The ops stay in the database so gossip
+/// still sees them as held and won't fetch the entries again, but ops which
+/// can't be sent without their entry are no longer served to other nodes.
+/// Returns the number of purged entries.
+#[instrument(skip(vault, conductor))]
+pub async fn purge_entries_workflow(
+    dna_hash: &DnaHash,
+    vault: DbWrite<DbKindDht>,
+    conductor: ConductorHandle,
+    now: Timestamp,
+) -> WorkflowResult<usize> {
+    let deleted_entry_actions: Vec<DeletedEntryAction> = vault
+        .read_async(|txn| {
+            let mut stmt = txn.prepare_cached(DELETED_ENTRY_ACTIONS)?;
+            let rows = stmt.query_and_then(
+                named_params! {
+                    ":deleted_entry_action": ChainOpType::RegisterDeletedEntryAction,
+                    ":status": ValidationStatus::Valid,
+                },
+                |row| {
+                    let action = from_blob::<SignedAction>(row.get("action_blob")?)?;
+                    StateQueryResult::Ok((
+                        row.get("entry_hash")?,
+                        action.into_data(),
+                        row.get("deleted_at")?,
+                    ))
+                },
+            )?;
+            rows.collect::<StateQueryResult<Vec<_>>>()
+        })
+        .await?;
+    if deleted_entry_actions.is_empty() {
+        return Ok(0);
+    }
+
+    let dna_def = match conductor.get_dna_def(dna_hash) {
+        Some(dna_def) => dna_def,
+        None => return Ok(0),
+    };
+    let mut policies = HashMap::new();
+    for (_, action, _) in &deleted_entry_actions {
+        let app_entry_def = match action.entry_type() {
+            Some(EntryType::App(app_entry_def)) => app_entry_def,
+            _ => continue,
+        };
+        if policies.contains_key(app_entry_def) {
+            continue;
+        }
+        let policy = match dna_def
+            .integrity_zomes
+            .get(app_entry_def.zome_index().0 as usize)
+        {
+            Some((_, zome)) => get_entry_def(
+                app_entry_def.entry_index(),
+                zome.clone(),
+                dna_hash,
+                &conductor,
+            )
+            .await
+            .map_err(WorkflowError::other)?
+            .and_then(|entry_def| entry_def.policies.purge),
+            None => None,
+        };
+        policies.insert(app_entry_def.clone(), policy);
+    }
+
+    let purgeable = purgeable_entries(deleted_entry_actions, &policies, now);
+    if purgeable.is_empty() {
+        return Ok(0);
+    }
+    let purged = vault
+        .write_async(move |txn| {
+            let mut stmt = txn.prepare_cached("DELETE FROM Entry WHERE hash = ?")?;
+            for entry_hash in &purgeable {
+                stmt.execute([entry_hash])?;
+            }
+            DatabaseResult::Ok(purgeable.len())
+        })
+        .await?;
+    debug!(?purged, "Purged deleted entries");
+    Ok(purged)
+}
+
+/// Find the entries which can be purged, which are those where every action
+/// that created the entry has a purge policy and was deleted longer ago than
+/// the policy's retention period.
+fn purgeable_entries(
+    deleted_entry_actions: Vec<DeletedEntryAction>,
+    policies: &HashMap<AppEntryDef, Option<PurgePolicy>>,
+    now: Timestamp,
+) -> Vec<EntryHash> {
+    let mut entries: HashMap<EntryHash, bool> = HashMap::new();
+    for (entry_hash, action, deleted_at) in deleted_entry_actions {
+        let policy = match action.entry_type() {
+            Some(EntryType::App(app_entry_def)) => policies.get(app_entry_def).copied().flatten(),
+            _ => None,
+        };
+        let expired = match (policy, deleted_at) {
+            (Some(policy), Some(deleted_at)) => (deleted_at + policy.retention())
+                .map(|expires_at| expires_at <= now)
+                .unwrap_or(false),
+            _ => false,
+        };
+        *entries.entry(entry_hash).or_insert(true) &= expired;
+    }
+    entries
+        .into_iter()
+        .filter_map(|(entry_hash, expired)| expired.then_some(entry_hash))
+        .collect()
+}
@@ -0,0 +1,84 @@
+use super::*;
+use ::fixt::prelude::*;
+use std::time::Duration;
+
+const RETENTION: Duration = Duration::from_secs(60);
+
+fn create(entry_hash: &EntryHash, entry_index: u8) -> Action {
+    let mut create = fixt!(Create);
+    create.entry_hash = entry_hash.clone();
+    create.entry_type = EntryType::App(AppEntryDef::new(
+        entry_index.into(),
+        0.into(),
+        EntryVisibility::Public,
+    ));
+    Action::Create(create)
+}
+
+fn policies() -> HashMap<AppEntryDef, Option<PurgePolicy>> {
+    [
+        (
+            AppEntryDef::new(0.into(), 0.into(), EntryVisibility::Public),
+            Some(PurgePolicy::after_secs(RETENTION.as_secs())),
+        ),
+        (
+            AppEntryDef::new(1.into(), 0.into(), EntryVisibility::Public),
+            None,
+        ),
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[test]
+fn purges_entries_deleted_longer_ago_than_retention() {
+    let now = Timestamp::now();
+    let long_ago = (now - RETENTION * 2).unwrap();
+    let recently = (now - RETENTION / 2).unwrap();
+    let expired = fixt!(EntryHash);
+    let deleted_recently = fixt!(EntryHash);
+    let not_deleted = fixt!(EntryHash);
+
+    let purgeable = purgeable_entries(
+        vec![
+            (expired.clone(), create(&expired, 0), Some(long_ago)),
+            (
+                deleted_recently.clone(),
+                create(&deleted_recently, 0),
+                Some(recently),
+            ),
+            (not_deleted.clone(), create(&not_deleted, 0), None),
+        ],
+        &policies(),
+        now,
+    );
+
+    assert_eq!(purgeable, vec![expired]);
+}
+
+#[test]
+fn keeps_entries_with_any_live_or_unpurgeable_action() {
+    let now = Timestamp::now();
+    let long_ago = (now - RETENTION * 2).unwrap();
+    let still_created = fixt!(EntryHash);
+    let no_policy = fixt!(EntryHash);
+
+    let purgeable = purgeable_entries(
+        vec![
+            // Created twice but only one of the creates has been deleted.
+            (
+                still_created.clone(),
+                create(&still_created, 0),
+                Some(long_ago),
+            ),
+            (still_created.clone(), create(&still_created, 0), None),
+            // Created again with an entry type that has no purge policy.
+            (no_policy.clone(), create(&no_policy, 0), Some(long_ago)),
+            (no_policy.clone(), create(&no_policy, 1), Some(long_ago)),
+        ],
+        &policies(),
+        now,
+    );
+
+    assert!(purgeable.is_empty());
+}
@@ -0,0 +1,261 @@
+//! Replays sys and app validation of archived ops in an isolated workspace,
+//! to find ops whose validation outcome has changed since they were recorded,
+//! for example between two versions of a DNA.
+
+use super::app_validation_workflow;
+use super::app_validation_workflow::AppValidationWorkspace;
+use super::error::WorkflowResult;
+use super::integrate_dht_ops_workflow::integrate_dht_ops_workflow;
+use super::sys_validation_workflow;
+use super::sys_validation_workflow::SysValidationWorkspace;
+use crate::conductor::conductor::firehose::FirehoseSender;
+use crate::conductor::ConductorHandle;
+use crate::core::queue_consumer::TriggerSender;
+use holo_hash::DhtOpHash;
+use holochain_conductor_api::ValidationReplayDifference;
+use holochain_conductor_api::ValidationReplayReport;
+use holochain_p2p::HolochainP2pDna;
+use holochain_sqlite::prelude::*;
+use holochain_state::prelude::*;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The most times the ops are run through validation and integration. Ops which
+/// depend on other ops in the archive are only validated once those are integrated,
+/// so each round can validate another layer of the archive.
+const MAX_REPLAY_ROUNDS: usize = 16;
+
+/// The databases the ops are replayed in. They are created in a temporary directory
+/// which is removed when the workspace is dropped, so nothing is written to the
+/// conductor's own databases.
+pub struct ReplayValidationWorkspace {
+    dna_hash: Arc<DnaHash>,
+    dna_def: Arc<DnaDef>,
+    authored_db: DbWrite<DbKindAuthored>,
+    dht_db: DbWrite<DbKindDht>,
+    cache: DbWrite<DbKindCache>,
+    dht_query_cache: DhtDbQueryCache,
+    _dir: tempfile::TempDir,
+}
+
+impl ReplayValidationWorkspace {
+    /// Create empty databases for the DNA in the given directory.
+    pub fn new(dna_def: DnaDef, dir: tempfile::TempDir) -> WorkflowResult<Self> {
+        let dna_hash = Arc::new(DnaHash::with_data_sync(&dna_def));
+        // Nothing is authored during a replay, but the workspaces need an authored database.
+        let cell_id = CellId::new((*dna_hash).clone(), AgentPubKey::from_raw_36(vec![0; 36]));
+        let authored_db = open_db(&dir, DbKindAuthored(Arc::new(cell_id)))?;
+        let dht_db = open_db(&dir, DbKindDht(dna_hash.clone()))?;
+        let cache = open_db(&dir, DbKindCache(dna_hash.clone()))?;
+        Ok(Self {
+            dna_hash,
+            dna_def: Arc::new(dna_def),
+            authored_db,
+            dht_query_cache: DhtDbQueryCache::new(dht_db.clone().into()),
+            dht_db,
+            cache,
+            _dir: dir,
+        })
+    }
+}
+
+fn open_db<Kind: DbKindT>(dir: &tempfile::TempDir, kind: Kind) -> DatabaseResult<DbWrite<Kind>> {
+    // Not opened through the shared database handles, so the databases are
+    // closed along with the workspace.
+    DbWrite::new(
+        Some(dir.path()),
+        kind,
+        DbSyncLevel::Off,
+        DbPoolSize::default(),
+        None,
+    )
+}
+
+/// Validate the ops in the workspace's DNA as if they had just been published to it,
+/// and return the validation status each op ended up with. Ops which couldn't be
+/// validated, usually because their dependencies are missing, have no status.
+pub async fn replay_validation_workflow(
+    workspace: ReplayValidationWorkspace,
+    ops: Vec<DhtOpHashed>,
+    conductor: ConductorHandle,
+    network: HolochainP2pDna,
+) -> WorkflowResult<HashMap<DhtOpHash, ValidationStatus>> {
+    let op_hashes: Vec<DhtOpHash> = ops.iter().map(|op| op.as_hash().clone()).collect();
+    workspace
+        .dht_db
+        .write_async(move |txn| -> StateMutationResult<()> {
+            for op in &ops {
+                insert_op(txn, op)?;
+            }
+            Ok(())
+        })
+        .await?;
+
+    let sys_workspace = Arc::new(SysValidationWorkspace::new(
+        workspace.authored_db.clone().into(),
+        workspace.dht_db.clone(),
+        workspace.dht_query_cache.clone(),
+        workspace.cache.clone(),
+        workspace.dna_def.clone(),
+        Duration::ZERO,
+    ));
+    let app_workspace = Arc::new(AppValidationWorkspace::new(
+        workspace.authored_db.clone().into(),
+        workspace.dht_db.clone(),
+        workspace.dht_query_cache.clone(),
+        workspace.cache.clone(),
+        conductor.keystore().clone(),
+        workspace.dna_def.clone(),
+    ));
+    let sys_validation_dependencies = Arc::new(Mutex::new(
+        sys_validation_workflow::validation_deps::ValidationDependencies::new(),
+    ));
+    let app_validation_dependencies = Arc::new(Mutex::new(
+        app_validation_workflow::ValidationDependencies::new(),
+    ));
+    // No receipts are sent for replayed ops.
+    let (trigger_receipt, _) = TriggerSender::new();
+
+    for round in 0..MAX_REPLAY_ROUNDS {
+        let sys_validated = sys_validation_workflow::sys_validate_local_ops(
+            sys_workspace.clone(),
+            sys_validation_dependencies.clone(),
+            conductor.config.clone(),
+        )
+        .await?;
+        let app_validated = app_validation_workflow::app_validate_ops(
+            workspace.dna_hash.clone(),
+            app_workspace.clone(),
+            conductor.clone(),
+            &network,
+            workspace.dht_query_cache.clone(),
+            app_validation_dependencies.clone(),
+        )
+        .await?;
+        integrate_dht_ops_workflow(
+            workspace.dht_db.clone(),
+            workspace.dht_query_cache.clone(),
+            trigger_receipt.clone(),
+            network.clone(),
+            FirehoseSender::disconnected(),
+        )
+        .await?;
+        tracing::debug!(round, sys_validated, app_validated, "Replayed validation");
+        if sys_validated + app_validated == 0 {
+            break;
+        }
+    }
+
+    validation_statuses(workspace.dht_db.into(), op_hashes).await
+}
+
+/// The validation status of each of the ops which has one in the database.
+pub async fn validation_statuses(
+    db: DbRead<DbKindDht>,
+    op_hashes: Vec<DhtOpHash>,
+) -> WorkflowResult<HashMap<DhtOpHash, ValidationStatus>> {
+    Ok(db
+        .read_async(move |txn| -> DatabaseResult<_> {
+            let mut stmt =
+                txn.prepare_cached("SELECT validation_status FROM DhtOp WHERE hash = ?")?;
+            let mut statuses = HashMap::new();
+            for hash in op_hashes {
+                let status: Option<Option<ValidationStatus>> =
+                    stmt.query_row([&hash], |row| row.get(0)).optional()?;
+                if let Some(Some(status)) = status {
+                    statuses.insert(hash, status);
+                }
+            }
+            Ok(statuses)
+        })
+        .await?)
+}
+
+/// Compare the replayed validation status of each op with the recorded one.
+pub fn compare_validation_statuses(
+    dna_hash: DnaHash,
+    archive_dna_hash: DnaHash,
+    op_hashes: &[DhtOpHash],
+    recorded: &HashMap<DhtOpHash, ValidationStatus>,
+    replayed: &HashMap<DhtOpHash, ValidationStatus>,
+) -> ValidationReplayReport {
+    let mut report = ValidationReplayReport {
+        dna_hash,
+        archive_dna_hash,
+        ops_replayed: op_hashes.len(),
+        ops_unrecorded: 0,
+        ops_undetermined: 0,
+        differences: Vec::new(),
+    };
+    for op_hash in op_hashes {
+        match (recorded.get(op_hash), replayed.get(op_hash)) {
+            (Some(recorded), Some(replayed)) => {
+                if recorded != replayed {
+                    report.differences.push(ValidationReplayDifference {
+                        op_hash: op_hash.clone(),
+                        recorded: *recorded,
+                        replayed: *replayed,
+                    });
+                }
+            }
+            (recorded, replayed) => {
+                if recorded.is_none() {
+                    report.ops_unrecorded += 1;
+                }
+                if replayed.is_none() {
+                    report.ops_undetermined += 1;
+                }
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use holo_hash::fixt::DhtOpHashFixturator;
+    use holo_hash::fixt::DnaHashFixturator;
+
+    #[test]
+    fn reports_only_ops_whose_outcome_changed() {
+        let op_hashes: Vec<DhtOpHash> = DhtOpHashFixturator::new(Unpredictable).take(5).collect();
+        let recorded: HashMap<_, _> = [
+            (op_hashes[0].clone(), ValidationStatus::Valid),
+            (op_hashes[1].clone(), ValidationStatus::Valid),
+            (op_hashes[2].clone(), ValidationStatus::Rejected),
+            (op_hashes[3].clone(), ValidationStatus::Valid),
+        ]
+        .into();
+        let replayed: HashMap<_, _> = [
+            (op_hashes[0].clone(), ValidationStatus::Valid),
+            (op_hashes[1].clone(), ValidationStatus::Rejected),
+            (op_hashes[2].clone(), ValidationStatus::Rejected),
+            (op_hashes[4].clone(), ValidationStatus::Valid),
+        ]
+        .into();
+
+        let report = compare_validation_statuses(
+            fixt!(DnaHash),
+            fixt!(DnaHash),
+            &op_hashes,
+            &recorded,
+            &replayed,
+        );
+
+        assert_eq!(5, report.ops_replayed);
+        assert_eq!(1, report.ops_unrecorded);
+        assert_eq!(1, report.ops_undetermined);
+        assert_eq!(
+            vec![ValidationReplayDifference {
+                op_hash: op_hashes[1].clone(),
+                recorded: ValidationStatus::Valid,
+                replayed: ValidationStatus::Rejected,
+            }],
+            report.differences
+        );
+    }
+}
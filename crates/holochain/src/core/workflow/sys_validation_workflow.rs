@@ -23,6 +23,8 @@
 //!    - Check that the [`Action`] is either a [`Action::Dna`] at sequence number 0, or has a previous action with sequence number strictly greater than 0.
 //!    - If the [`Action`] is a [`Action::Dna`], then verify the contained DNA hash matches the DNA hash that sys validation is being run for.
 //!    - Check that the previous action is never a [`Action::CloseChain`], since this is always required to be the last action in a chain.
+//!    - If the [`Action`] is a [`Action::CloseChain`] or an [`Action::OpenChain`], then verify that the DNA hash it migrates to or from is not the DNA hash that sys validation is being run for, in a DNA which applies this rule.
+//!    - If the [`Action`] is an [`Action::RenewMembraneProof`], then the [`RenewMembraneProof::supersedes`] reference must point to an [`Action::AgentValidationPkg`] or [`Action::RenewMembraneProof`] that can be found locally, by the same author and earlier in the chain. It must be the latest membrane proof on the chain, so none of the actions between it and the renewal may hold a membrane proof, which also means a proof can't be superseded twice.
 //!    - Run the [store record checks](#store-record-checks).
 //! - For a [`ChainOp::RegisterUpdatedContent`]
 //!    - The [`Update::original_action_address`] reference to the [`Action`] being updated must point to an [`Action`] that can be found locally. Once the [`Action`] address has been resolved, the [`Update::original_entry_address`] is checked against the entry address that the referenced [`Action`] specified.
@@ -35,11 +37,23 @@
 //! - For a [`ChainOp::RegisterDeletedEntryAction`]
 //!    - The [`Delete::deletes_address`] reference to the [`Action`] being deleted must point to an [`Action`] that can be found locally. The action being deleted must be a [`Action::Create`] or [`Action::Update`].
 //! - For a [`ChainOp::RegisterAddLink`]
-//!   - The size of the [`CreateLink::tag`] must be less than or equal to the maximum size that is accepted for this link tag. This is specified in the constant [`MAX_TAG_SIZE`].
+//!   - The size of the [`CreateLink::tag`] must be less than or equal to the maximum size that is accepted for this link tag. This is specified in the constant [`MAX_TAG_SIZE`], or [`MAX_PAYLOAD_TAG_SIZE`] for a tag which carries a [`LinkTagPayload`] in a DNA which accepts payloads.
+//!   - A [`CreateLink::tag`] which starts with the [`LINK_TAG_PAYLOAD_PREFIX`] must carry a [`LinkTagPayload`], in a DNA which accepts payloads.
 //! - For a [`ChainOp::RegisterRemoveLink`]
 //!   - The [`DeleteLink::link_add_address`] reference to the [`Action`] of the link being deleted must point to an [`Action`] that can be found locally. That action being deleted must also
 //!     be a [`Action::CreateLink`].
 //!
+//! - For an [`AgentInfoOp`]
+//!   - The agent info must decode, be for the agent which published it and be for the space of the DNA that sys validation is being run for.
+//!   - The signing and expiry times of the op must match the ones in the agent info.
+//!   - Agent infos are not app validated, so an accepted op is integrated straight away.
+//!
+//! - For a [`BlobChunkOp`]
+//!   - The chunk must not be empty or larger than [`BLOB_CHUNK_SIZE`] bytes.
+//!   - The chunk hash must be the hash of the bytes of the chunk.
+//!   - The manifest [`Action`] which accounts for the chunk must be found, must be an [`Action::Create`] and must be by the author of the chunk.
+//!   - Blob chunks are not app validated, so an accepted op is integrated straight away.
+//!
 //! ##### Store record checks
 //!
 //! These checks are run when storing a new action for a [`DhtOp`].
@@ -56,9 +70,10 @@
 //!
 //! - The entry type specified in the [`Action`] must match the entry type specified in the [`Entry`].
 //! - The entry hash specified in the [`Action`] must match the entry hash specified in the [`Entry`], which will be hashed as part of the check to obtain a value that is deterministic.
-//! - The size of the [`Entry`] must be less than or equal to the maximum size that is accepted for this entry type. This is specified in the constant [`MAX_ENTRY_SIZE`].
+//! - The size of the [`Entry`] must be less than or equal to the maximum size that is accepted for any entry. This is specified in the constant [`MAX_ENTRY_SIZE`]. A lower limit for an entry type is enforced by app validation.
 //! - If the [`Action`] is an [`Action::Update`], then the [`Update::original_action_address`] reference to the [`Action`] being updated must point to an [`Action`] that can be found locally. Once the [`Action`] address has been resolved, the [`Update::original_entry_address`] is checked against the entry address that the referenced [`Action`] specified.
 //! - If the [`Entry`] is an [`Entry::CounterSign`], then the pre-flight response signatures are checked.
+//! - If the [`Entry`] is an [`Entry::DualSign`], then a proposal must be an [`Action::Create`] which names another agent as the counterparty, and an acceptance must be an [`Action::Update`] of a proposal by the initiator it names, which named the author of the acceptance as the counterparty for the same app entry.
 //!
 //! #### Workflow description
 //!
@@ -75,6 +90,7 @@
 //! - Once all ops have an outcome, the workflow is complete and will wait to be triggered again by new incoming ops.
 //!
 
+use crate::conductor::conductor::firehose::FirehoseSender;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
 use crate::core::sys_validate::*;
@@ -85,7 +101,9 @@ use futures::StreamExt;
 use holo_hash::DhtOpHash;
 use holochain_cascade::Cascade;
 use holochain_cascade::CascadeImpl;
+use holochain_cascade::CascadeSource;
 use holochain_conductor_api::conductor::ConductorConfig;
+use holochain_conductor_api::FirehoseOp;
 use holochain_p2p::GenericNetwork;
 use holochain_p2p::HolochainP2pDnaT;
 use holochain_sqlite::prelude::*;
@@ -215,13 +233,28 @@ pub async fn sys_validation_workflow<Network: HolochainP2pDnaT + 'static>(
     }
 }
 
+/// Sys validate the ops in the workspace using only the data held locally,
+/// without going to the network for missing dependencies.
+/// Returns the number of ops which were accepted or rejected.
+#[cfg(feature = "op_replay")]
+pub(crate) async fn sys_validate_local_ops(
+    workspace: Arc<SysValidationWorkspace>,
+    current_validation_dependencies: Arc<Mutex<ValidationDependencies>>,
+    config: Arc<ConductorConfig>,
+) -> WorkflowResult<usize> {
+    let outcome_summary =
+        sys_validation_workflow_inner(workspace, current_validation_dependencies, config).await?;
+    Ok(outcome_summary.accepted + outcome_summary.rejected)
+}
+
 async fn sys_validation_workflow_inner(
     workspace: Arc<SysValidationWorkspace>,
     current_validation_dependencies: Arc<Mutex<ValidationDependencies>>,
     config: Arc<ConductorConfig>,
 ) -> WorkflowResult<OutcomeSummary> {
     let db = workspace.dht_db.clone();
-    let sorted_ops = validation_query::get_ops_to_sys_validate(&db).await?;
+    let order = *workspace.validation_queue_order.read();
+    let sorted_ops = validation_query::get_ops_to_sys_validate(&db, order).await?;
     let sleuth_id = config.sleuth_id();
 
     // Forget what dependencies are currently in use
@@ -254,6 +287,10 @@ async fn sys_validation_workflow_inner(
     // Now drop all the dependencies that we didn't just try to access while searching the current set of ops to validate.
     current_validation_dependencies.lock().purge_held_deps();
 
+    // Blob chunks are integrated here rather than by the integration workflow,
+    // so they are streamed to the firehose here too.
+    let stream_to_firehose = workspace.firehose.has_subscribers();
+    let mut accepted_blob_chunks = Vec::new();
     let mut validation_outcomes = Vec::with_capacity(sorted_ops.len());
     for hashed_op in sorted_ops {
         let (op, op_hash) = hashed_op.into_inner();
@@ -262,12 +299,23 @@ async fn sys_validation_workflow_inner(
         // rejected and don't have dependencies.
         let dependency = op.sys_validation_dependency();
 
+        // Agent infos and blob chunks aren't app validated,
+        // so they are integrated as soon as they're accepted.
+        let skip_app_validation = matches!(op, DhtOp::AgentInfoOp(_) | DhtOp::BlobChunkOp(_));
+
         // Note that this is async only because of the signature checks done during countersigning.
         // In most cases this will be a fast synchronous call.
         let r = validate_op(&op, &dna_def, current_validation_dependencies.clone()).await;
 
         match r {
-            Ok(outcome) => validation_outcomes.push((op_hash, outcome, dependency)),
+            Ok(outcome) => {
+                if let (true, Outcome::Accepted, DhtOp::BlobChunkOp(chunk)) =
+                    (stream_to_firehose, &outcome, &op)
+                {
+                    accepted_blob_chunks.push((op_hash.clone(), chunk.clone()));
+                }
+                validation_outcomes.push((op_hash, outcome, dependency, skip_app_validation))
+            }
             Err(e) => {
                 tracing::error!(error = ?e, "Error validating op");
             }
@@ -278,8 +326,12 @@ async fn sys_validation_workflow_inner(
         .dht_db
         .write_async(move |txn| {
             let mut summary = OutcomeSummary::default();
-            for (op_hash, outcome, dependency) in validation_outcomes {
+            for (op_hash, outcome, dependency, skip_app_validation) in validation_outcomes {
                 match outcome {
+                    Outcome::Accepted if skip_app_validation => {
+                        summary.accepted += 1;
+                        put_integrated(txn, &op_hash, ValidationStatus::Valid)?;
+                    }
                     Outcome::Accepted => {
                         summary.accepted += 1;
                         put_validation_limbo(txn, &op_hash, ValidationStage::SysValidated)?;
@@ -307,6 +359,21 @@ async fn sys_validation_workflow_inner(
         })
         .await?;
 
+    let when_integrated = Timestamp::now();
+    for (op_hash, chunk) in accepted_blob_chunks {
+        let op = DhtOp::from(chunk.clone());
+        workspace.firehose.send(FirehoseOp {
+            dna_hash: dna_def.as_hash().clone(),
+            op_hash,
+            op_type: op.get_type(),
+            basis: op.dht_basis(),
+            author: chunk.author,
+            authored_timestamp: chunk.timestamp,
+            when_integrated,
+            op,
+        });
+    }
+
     tracing::debug!(
         ?summary,
         ?num_ops_to_validate,
@@ -369,6 +436,7 @@ fn get_dependency_hashes_from_actions(actions: impl Iterator<Item = Action>) ->
                     Action::Update(action) => Some(action.original_action_address),
                     Action::Delete(action) => Some(action.deletes_address),
                     Action::DeleteLink(action) => Some(action.link_add_address),
+                    Action::RenewMembraneProof(action) => Some(action.supersedes),
                     _ => None,
                 },
             ]
@@ -385,12 +453,22 @@ async fn fetch_previous_actions(
     cascade: Arc<impl Cascade + Send + Sync>,
     actions: impl Iterator<Item = Action>,
 ) {
+    let actions: Vec<_> = actions.collect();
+    let renewals = actions
+        .iter()
+        .filter_map(|action| match action {
+            Action::RenewMembraneProof(renewal) => Some(renewal.clone()),
+            _ => None,
+        })
+        .collect();
     retrieve_actions(
-        current_validation_dependencies,
-        cascade,
-        get_dependency_hashes_from_actions(actions).into_iter(),
+        current_validation_dependencies.clone(),
+        cascade.clone(),
+        get_dependency_hashes_from_actions(actions.into_iter()).into_iter(),
     )
     .await;
+    retrieve_membrane_proof_renewal_chains(current_validation_dependencies, cascade, renewals)
+        .await;
 }
 
 fn get_dependency_hashes_from_ops(ops: impl Iterator<Item = DhtOpHashed>) -> Vec<ActionHash> {
@@ -449,9 +527,14 @@ fn get_dependency_hashes_from_ops(ops: impl Iterator<Item = DhtOpHashed>) -> Vec
                         }
                         Some(actions)
                     }
-                    ChainOp::RegisterAgentActivity(_, action) => action
-                        .prev_action()
-                        .map(|action| vec![action.as_hash().clone()]),
+                    ChainOp::RegisterAgentActivity(_, action) => {
+                        let mut actions: Vec<_> =
+                            action.prev_action().cloned().into_iter().collect();
+                        if let Action::RenewMembraneProof(renewal) = action {
+                            actions.push(renewal.supersedes.clone());
+                        }
+                        Some(actions)
+                    }
                     ChainOp::RegisterUpdatedContent(_, action, _) => {
                         Some(vec![action.original_action_address.clone()])
                     }
@@ -481,6 +564,8 @@ fn get_dependency_hashes_from_ops(ops: impl Iterator<Item = DhtOpHashed>) -> Vec
                         } => Some(vec![a1.clone(), a2.clone()]),
                     },
                 },
+                DhtOp::BlobChunkOp(op) => Some(vec![op.manifest.clone()]),
+                DhtOp::AgentInfoOp(_) => None,
             }
         })
         .flatten()
@@ -494,12 +579,25 @@ async fn retrieve_previous_actions_for_ops(
     cascade: Arc<impl Cascade + Send + Sync>,
     ops: impl Iterator<Item = DhtOpHashed>,
 ) {
+    let ops: Vec<_> = ops.collect();
+    let renewals = ops
+        .iter()
+        .filter_map(|op| match &op.content {
+            DhtOp::ChainOp(op) => match op.action() {
+                Action::RenewMembraneProof(renewal) => Some(renewal),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
     retrieve_actions(
-        current_validation_dependencies,
-        cascade,
-        get_dependency_hashes_from_ops(ops).into_iter(),
+        current_validation_dependencies.clone(),
+        cascade.clone(),
+        get_dependency_hashes_from_ops(ops.into_iter()).into_iter(),
     )
     .await;
+    retrieve_membrane_proof_renewal_chains(current_validation_dependencies, cascade, renewals)
+        .await;
 }
 
 /// Validate a single DhtOp, using the supplied Cascade to draw dependencies from
@@ -511,6 +609,8 @@ pub(crate) async fn validate_op(
     let result = match op {
         DhtOp::ChainOp(op) => validate_chain_op(op, dna_def, validation_dependencies).await,
         DhtOp::WarrantOp(op) => validate_warrant_op(op, dna_def, validation_dependencies).await,
+        DhtOp::AgentInfoOp(op) => validate_agent_info_op(op, dna_def),
+        DhtOp::BlobChunkOp(op) => validate_blob_chunk_op(op, validation_dependencies),
     };
     match result {
         Ok(_) => Ok(Outcome::Accepted),
@@ -684,7 +784,7 @@ async fn validate_chain_op(
         ChainOp::RegisterDeletedEntryAction(_, action) => {
             register_deleted_entry_action(action, validation_dependencies)
         }
-        ChainOp::RegisterAddLink(_, action) => register_add_link(action),
+        ChainOp::RegisterAddLink(_, action) => register_add_link(action, dna_def),
         ChainOp::RegisterRemoveLink(_, action) => {
             register_delete_link(action, validation_dependencies)
         }
@@ -785,6 +885,59 @@ async fn validate_warrant_op(
     }
 }
 
+fn validate_agent_info_op(op: &AgentInfoOp, dna_def: &DnaDefHashed) -> SysValidationResult<()> {
+    use holochain_p2p::DnaHashExt;
+
+    let invalid = |reason: &str| ValidationOutcome::InvalidAgentInfoOp(op.clone(), reason.into());
+    let info = holochain_p2p::agent_info_from_op(op)
+        .map_err(|e| invalid(&format!("agent info could not be decoded: {}", e)))?;
+
+    if DnaHash::from_kitsune(&info.space) != *dna_def.as_hash() {
+        return Err(invalid("space mismatch").into());
+    }
+    if info.signed_at_ms as i64 * 1000 != op.signed_at.as_micros()
+        || info.expires_at_ms as i64 * 1000 != op.expires_at.as_micros()
+    {
+        return Err(invalid("timestamp mismatch").into());
+    }
+
+    Ok(())
+}
+
+fn validate_blob_chunk_op(
+    op: &BlobChunkOp,
+    validation_dependencies: Arc<Mutex<ValidationDependencies>>,
+) -> SysValidationResult<()> {
+    let invalid =
+        |reason: &str| ValidationOutcome::InvalidBlobChunkOp(op.chunk_hash.clone(), reason.into());
+
+    if op.chunk.is_empty() {
+        return Err(invalid("chunk is empty").into());
+    }
+    if op.chunk.len() > BLOB_CHUNK_SIZE {
+        return Err(invalid("chunk is too large").into());
+    }
+    if BlobChunkOp::chunk_hash_of(&op.chunk) != op.chunk_hash {
+        return Err(invalid("chunk hash mismatch").into());
+    }
+
+    // The chunk must be accounted for on its author's chain by the action
+    // which created its manifest.
+    let deps = validation_dependencies.lock();
+    let manifest = deps
+        .get(&op.manifest)
+        .and_then(|s| s.as_action())
+        .ok_or_else(|| ValidationOutcome::DepMissingFromDht(op.manifest.clone().into()))?;
+    if *manifest.author() != op.author {
+        return Err(invalid("manifest author mismatch").into());
+    }
+    if !matches!(manifest, Action::Create(_)) {
+        return Err(invalid("manifest is not a create").into());
+    }
+
+    Ok(())
+}
+
 /// Run system validation for a single [`Record`] instead of the usual [`DhtOp`] input for the system validation workflow.
 /// It is expected that the provided cascade will include a network so that dependencies which we either do not hold yet, or
 /// should not hold, can be fetched and cached for use in validation.
@@ -793,9 +946,10 @@ async fn validate_warrant_op(
 /// it is intended to be used for validation of records which have been authored locally so we should always be able to check the previous action.
 pub async fn sys_validate_record(
     record: &Record,
+    dna_def: &DnaDefHashed,
     cascade: Arc<impl Cascade + Send + Sync>,
 ) -> SysValidationOutcome<()> {
-    match sys_validate_record_inner(record, cascade).await {
+    match sys_validate_record_inner(record, dna_def, cascade).await {
         // Validation succeeded
         Ok(_) => Ok(()),
         // Validation failed so exit with that outcome
@@ -814,6 +968,7 @@ pub async fn sys_validate_record(
 
 async fn sys_validate_record_inner(
     record: &Record,
+    dna_def: &DnaDefHashed,
     cascade: Arc<impl Cascade + Send + Sync>,
 ) -> SysValidationResult<()> {
     let signature = record.signature();
@@ -824,6 +979,7 @@ async fn sys_validate_record_inner(
     async fn validate(
         action: &Action,
         maybe_entry: Option<&Entry>,
+        dna_def: &DnaDefHashed,
         cascade: Arc<impl Cascade + Send + Sync>,
     ) -> SysValidationResult<()> {
         let validation_dependencies = Arc::new(Mutex::new(ValidationDependencies::new()));
@@ -852,10 +1008,13 @@ async fn sys_validate_record_inner(
             Action::Delete(action) => {
                 register_deleted_entry_action(action, validation_dependencies.clone())
             }
-            Action::CreateLink(action) => register_add_link(action),
+            Action::CreateLink(action) => register_add_link(action, dna_def),
             Action::DeleteLink(action) => {
                 register_delete_link(action, validation_dependencies.clone())
             }
+            Action::RenewMembraneProof(action) => {
+                register_membrane_proof_renewal(action, validation_dependencies.clone())
+            }
             _ => Ok(()),
         }
     }
@@ -865,15 +1024,15 @@ async fn sys_validate_record_inner(
             if let Some(weight) = action.entry_rate_data() {
                 let entry_hash = EntryHash::with_data_sync(maybe_entry.unwrap());
                 for action in session.build_action_set(entry_hash, weight)? {
-                    validate(&action, maybe_entry, cascade.clone()).await?;
+                    validate(&action, maybe_entry, dna_def, cascade.clone()).await?;
                 }
                 Ok(())
             } else {
                 tracing::error!("Got countersigning entry without rate assigned. This should be impossible. But, let's see what happens.");
-                validate(action, maybe_entry, cascade.clone()).await
+                validate(action, maybe_entry, dna_def, cascade.clone()).await
             }
         }
-        _ => validate(action, maybe_entry, cascade).await,
+        _ => validate(action, maybe_entry, dna_def, cascade).await,
     }
 }
 
@@ -895,6 +1054,20 @@ pub async fn counterfeit_check_warrant(warrant_op: &WarrantOp) -> SysValidationR
     Ok(())
 }
 
+/// Check if the agent info op is signed by the agent it is for.
+pub async fn counterfeit_check_agent_info(op: &AgentInfoOp) -> SysValidationResult<()> {
+    verify_agent_info_signature(op).await?;
+    author_key_is_valid(&op.agent).await?;
+    Ok(())
+}
+
+/// Check if the blob chunk op is signed by its author.
+pub async fn counterfeit_check_blob_chunk(op: &BlobChunkOp) -> SysValidationResult<()> {
+    verify_blob_chunk_signature(op).await?;
+    author_key_is_valid(&op.author).await?;
+    Ok(())
+}
+
 fn register_agent_activity(
     action: &Action,
     validation_dependencies: Arc<Mutex<ValidationDependencies>>,
@@ -906,6 +1079,10 @@ fn register_agent_activity(
     // Checks
     check_prev_action(action)?;
     check_valid_if_dna(action, dna_def)?;
+    check_chain_migration(action, dna_def)?;
+    if let Action::RenewMembraneProof(renewal) = action {
+        register_membrane_proof_renewal(renewal, validation_dependencies.clone())?;
+    }
     if let Some(prev_action_hash) = prev_action_hash {
         let validation_dependencies = validation_dependencies.lock();
         let prev_action = validation_dependencies
@@ -964,7 +1141,7 @@ async fn store_entry(
     check_entry_size(entry)?;
 
     // Additional checks if this is an Update
-    if let NewEntryActionRef::Update(entry_update) = action {
+    let original_action = if let NewEntryActionRef::Update(entry_update) = action {
         let original_action_address = &entry_update.original_action_address;
         let validation_dependencies = validation_dependencies.lock();
         let original_action = validation_dependencies
@@ -974,12 +1151,26 @@ async fn store_entry(
                 ValidationOutcome::DepMissingFromDht(original_action_address.clone().into())
             })?;
         update_check(entry_update, original_action)?;
-    }
+        Some(original_action.clone())
+    } else {
+        None
+    };
 
-    // Additional checks if this is a countersigned entry.
-    if let Entry::CounterSign(session_data, _) = entry {
-        check_countersigning_session_data(EntryHash::with_data_sync(entry), session_data, action)
+    match entry {
+        // Additional checks if this is a countersigned entry.
+        Entry::CounterSign(session_data, _) => {
+            check_countersigning_session_data(
+                EntryHash::with_data_sync(entry),
+                session_data,
+                action,
+            )
             .await?;
+        }
+        // Additional checks if this is a dual signed entry.
+        Entry::DualSign(data, entry_bytes) => {
+            check_dual_sign_data(data, entry_bytes, action, original_action.as_ref())?;
+        }
+        _ => (),
     }
     Ok(())
 }
@@ -1056,8 +1247,9 @@ fn register_deleted_entry_action(
     check_new_entry_action(action)
 }
 
-fn register_add_link(link_add: &CreateLink) -> SysValidationResult<()> {
-    check_tag_size(&link_add.tag)
+fn register_add_link(link_add: &CreateLink, dna_def: &DnaDefHashed) -> SysValidationResult<()> {
+    check_tag_size(&link_add.tag, dna_def)?;
+    check_tag_payload(&link_add.tag, dna_def)
 }
 
 fn register_delete_link(
@@ -1080,6 +1272,99 @@ fn register_delete_link(
     }
 }
 
+fn register_membrane_proof_renewal(
+    renewal: &RenewMembraneProof,
+    validation_dependencies: Arc<Mutex<ValidationDependencies>>,
+) -> SysValidationResult<()> {
+    // Get data ready to validate
+    let supersedes = &renewal.supersedes;
+
+    let validation_dependencies = validation_dependencies.lock();
+    let get_action = |hash: &ActionHash| {
+        validation_dependencies
+            .get(hash)
+            .and_then(|s| s.as_action())
+            .ok_or_else(|| ValidationOutcome::DepMissingFromDht(hash.clone().into()))
+    };
+    let superseded = get_action(supersedes)?;
+
+    // Walk the chain back from the renewal to the superseded proof, collecting
+    // the actions in between.
+    let mut intervening = Vec::new();
+    let mut next = &renewal.prev_action;
+    while next != supersedes && superseded.action_seq() < renewal.action_seq {
+        let action = get_action(next)?;
+        if action.action_seq() <= superseded.action_seq() {
+            return Err(ValidationOutcome::InvalidMembraneProofRenewal(
+                "the superseded membrane proof is not on the renewal's chain".to_string(),
+            )
+            .into());
+        }
+        intervening.push(action);
+        match action.prev_action() {
+            Some(prev_action) => next = prev_action,
+            None => break,
+        }
+    }
+
+    check_membrane_proof_renewal(renewal, superseded, intervening)
+}
+
+/// Fetch the actions between each membrane proof renewal and the proof it
+/// supersedes, so the renewal can be checked against its chain prefix.
+///
+/// The actions are fetched with one agent activity request per renewal. If the
+/// author's chain isn't complete back to the superseded proof, none of them are
+/// added, which leaves the renewal awaiting its previous action.
+async fn retrieve_membrane_proof_renewal_chains(
+    current_validation_dependencies: Arc<Mutex<ValidationDependencies>>,
+    cascade: Arc<impl Cascade + Send + Sync>,
+    renewals: Vec<RenewMembraneProof>,
+) {
+    for renewal in renewals {
+        let Some(superseded_seq) = current_validation_dependencies
+            .lock()
+            .get(&renewal.supersedes)
+            .and_then(|s| s.as_action())
+            .map(|a| a.action_seq())
+        else {
+            continue;
+        };
+        if superseded_seq >= renewal.action_seq {
+            continue;
+        }
+        let filter = ChainFilter::new(renewal.prev_action.clone())
+            .until(renewal.supersedes.clone())
+            .take(renewal.action_seq - superseded_seq);
+        let activity = match cascade.retrieve_chain(renewal.author.clone(), filter).await {
+            Ok(MustGetAgentActivityResponse::Activity(activity)) => activity,
+            Ok(response) => {
+                tracing::debug!(?response, renewal = ?renewal.prev_action, "Chain of membrane proof renewal is incomplete");
+                continue;
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, renewal = ?renewal.prev_action, "Error retrieving chain of membrane proof renewal");
+                continue;
+            }
+        };
+        // The actions are only used to check the renewal, so they aren't passed
+        // on to be held like dependencies fetched from the network.
+        let mut deps = current_validation_dependencies.lock();
+        let new_deps: ValidationDependencies = activity
+            .into_iter()
+            .map(|activity| activity.action)
+            .filter(|action| !deps.has(action.as_hash()))
+            .map(|action| {
+                (
+                    action.as_hash().clone(),
+                    (action, CascadeSource::Local).into(),
+                )
+            })
+            .collect();
+        deps.merge(new_deps);
+    }
+}
+
 fn update_check(entry_update: &Update, original_action: &Action) -> SysValidationResult<()> {
     check_new_entry_action(original_action)?;
     // This shouldn't fail due to the above `check_new_entry_action` check
@@ -1094,10 +1379,12 @@ pub struct SysValidationWorkspace {
     scratch: Option<SyncScratch>,
     authored_db: DbRead<DbKindAuthored>,
     dht_db: DbWrite<DbKindDht>,
+    firehose: FirehoseSender,
     dht_query_cache: Option<DhtDbQueryCache>,
     cache: DbWrite<DbKindCache>,
     pub(crate) dna_def: Arc<DnaDef>,
     sys_validation_retry_delay: Duration,
+    validation_queue_order: Arc<parking_lot::RwLock<ValidationQueueOrder>>,
 }
 
 impl SysValidationWorkspace {
@@ -1113,13 +1400,32 @@ impl SysValidationWorkspace {
             scratch: None,
             authored_db,
             dht_db,
+            firehose: FirehoseSender::disconnected(),
             dht_query_cache: Some(dht_query_cache),
             cache,
             dna_def,
             sys_validation_retry_delay,
+            validation_queue_order: Default::default(),
         }
     }
 
+    /// Process ops in the order held by this shared setting,
+    /// which can be changed while the workflow is running.
+    pub fn with_validation_queue_order(
+        mut self,
+        validation_queue_order: Arc<parking_lot::RwLock<ValidationQueueOrder>>,
+    ) -> Self {
+        self.validation_queue_order = validation_queue_order;
+        self
+    }
+
+    /// Stream the blob chunks which are integrated as soon as they are
+    /// accepted to the firehose of their space.
+    pub fn with_firehose(mut self, firehose: FirehoseSender) -> Self {
+        self.firehose = firehose;
+        self
+    }
+
     #[tracing::instrument(skip_all)]
     pub async fn is_chain_empty(&self, author: &AgentPubKey) -> SourceChainResult<bool> {
         // If we have a query cache then this is an authority node and
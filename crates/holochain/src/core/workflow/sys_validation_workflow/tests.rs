@@ -329,6 +329,8 @@ fn show_limbo(txn: &Transaction) -> Vec<DhtOpLite> {
                 let ((warrant, timestamp), signature) = warrant.into();
                 Ok(WarrantOp::new(warrant, author, signature, timestamp).into())
             }
+            DhtOpType::AgentInfo => unreachable!("agent infos aren't published in these tests"),
+            DhtOpType::BlobChunk => unreachable!("blob chunks aren't published in these tests"),
         }
     })
     .unwrap()
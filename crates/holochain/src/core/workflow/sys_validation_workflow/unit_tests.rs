@@ -27,6 +27,7 @@ use holochain_state::mutations::StateMutationResult;
 use holochain_types::dht_op::ChainOp;
 use holochain_types::dht_op::DhtOp;
 use holochain_types::dht_op::DhtOpHashed;
+use holochain_types::dht_op::ValidationQueueOrder;
 use holochain_types::dht_op::WireOps;
 use holochain_types::record::SignedActionHashedExt;
 use holochain_types::record::WireRecordOps;
@@ -404,7 +405,7 @@ impl TestCase {
 
     /// This provides a quick and reliable way to check that ops have been sys validated
     async fn get_ops_pending_app_validation(&self) -> HashSet<DhtOpHash> {
-        get_ops_to_app_validate(&self.dht_db_handle().into())
+        get_ops_to_app_validate(&self.dht_db_handle().into(), ValidationQueueOrder::OpType)
             .await
             .unwrap()
             .into_iter()
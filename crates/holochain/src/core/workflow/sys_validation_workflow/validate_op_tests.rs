@@ -2270,6 +2270,195 @@ async fn crash_case() {
     assert!(matches!(validation_outcome, Outcome::Accepted));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn validate_valid_agent_info_op() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+
+    let info = test_agent_info(&test_case.agent, test_case.dna_def_hash().hash).await;
+    let op = DhtOp::AgentInfoOp(holochain_p2p::agent_info_to_op(&info).unwrap());
+
+    let outcome = test_case.with_op(op).run().await.unwrap();
+
+    assert!(
+        matches!(outcome, Outcome::Accepted),
+        "Expected Accepted but actual outcome was {:?}",
+        outcome
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn validate_agent_info_op_for_another_space() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+
+    // Signed for a different DNA than the one being validated
+    let info = test_agent_info(&test_case.agent, fixt!(DnaHash)).await;
+    let agent_info_op = holochain_p2p::agent_info_to_op(&info).unwrap();
+
+    let outcome = test_case
+        .with_op(DhtOp::AgentInfoOp(agent_info_op.clone()))
+        .run()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        Outcome::Rejected(
+            ValidationOutcome::InvalidAgentInfoOp(agent_info_op, "space mismatch".into())
+                .to_string()
+        ),
+        outcome
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn validate_agent_info_op_with_mismatched_timestamp() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+
+    let info = test_agent_info(&test_case.agent, test_case.dna_def_hash().hash).await;
+    let mut agent_info_op = holochain_p2p::agent_info_to_op(&info).unwrap();
+    // Claim a later expiry than the one that was signed
+    agent_info_op.expires_at =
+        (agent_info_op.expires_at + std::time::Duration::from_secs(60)).unwrap();
+
+    let outcome = test_case
+        .with_op(DhtOp::AgentInfoOp(agent_info_op.clone()))
+        .run()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        Outcome::Rejected(
+            ValidationOutcome::InvalidAgentInfoOp(agent_info_op, "timestamp mismatch".into())
+                .to_string()
+        ),
+        outcome
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn validate_valid_blob_chunk_op() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+    let manifest = test_blob_manifest(&test_case, test_case.agent.clone()).await;
+    let op = test_blob_chunk_op(&test_case.agent, manifest.as_hash(), vec![1; 16]);
+
+    let outcome = test_case
+        .expect_retrieve_records_from_cascade(vec![manifest])
+        .with_op(op.into())
+        .run()
+        .await
+        .unwrap();
+
+    assert!(
+        matches!(outcome, Outcome::Accepted),
+        "Expected Accepted but actual outcome was {:?}",
+        outcome
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn validate_blob_chunk_op_hash_mismatch() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+    let manifest = test_blob_manifest(&test_case, test_case.agent.clone()).await;
+    let mut op = test_blob_chunk_op(&test_case.agent, manifest.as_hash(), vec![1; 16]);
+    op.chunk = vec![2; 16];
+
+    let outcome = test_case
+        .expect_retrieve_records_from_cascade(vec![manifest])
+        .with_op(op.clone().into())
+        .run()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        Outcome::Rejected(
+            ValidationOutcome::InvalidBlobChunkOp(op.chunk_hash, "chunk hash mismatch".into())
+                .to_string()
+        ),
+        outcome
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn validate_blob_chunk_op_too_large() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+    let manifest = test_blob_manifest(&test_case, test_case.agent.clone()).await;
+    let op = test_blob_chunk_op(
+        &test_case.agent,
+        manifest.as_hash(),
+        vec![1; BLOB_CHUNK_SIZE + 1],
+    );
+
+    let outcome = test_case
+        .expect_retrieve_records_from_cascade(vec![manifest])
+        .with_op(op.clone().into())
+        .run()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        Outcome::Rejected(
+            ValidationOutcome::InvalidBlobChunkOp(op.chunk_hash, "chunk is too large".into())
+                .to_string()
+        ),
+        outcome
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn validate_blob_chunk_op_without_manifest() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+    let manifest = fixt!(ActionHash);
+    let op = test_blob_chunk_op(&test_case.agent, &manifest, vec![1; 16]);
+
+    test_case
+        .cascade_mut()
+        .expect_retrieve_action()
+        .times(1)
+        .returning(move |_, _| async move { Ok(None) }.boxed());
+
+    let outcome = test_case.with_op(op.into()).run().await.unwrap();
+
+    assert_eq!(Outcome::MissingDhtDep(manifest.into()), outcome);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn validate_blob_chunk_op_with_manifest_by_another_author() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+    // The chunk claims to be accounted for by someone else's manifest
+    let manifest = test_blob_manifest(&test_case, fixt!(AgentPubKey)).await;
+    let op = test_blob_chunk_op(&test_case.agent, manifest.as_hash(), vec![1; 16]);
+
+    let outcome = test_case
+        .expect_retrieve_records_from_cascade(vec![manifest])
+        .with_op(op.clone().into())
+        .run()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        Outcome::Rejected(
+            ValidationOutcome::InvalidBlobChunkOp(op.chunk_hash, "manifest author mismatch".into())
+                .to_string()
+        ),
+        outcome
+    );
+}
+
 struct TestCase {
     op: Option<DhtOp>,
     keystore: holochain_keystore::MetaLairClient,
@@ -2388,3 +2577,46 @@ fn test_op(previous: SignedHashed<Action>) -> DhtOp {
 
     ChainOp::RegisterAgentActivity(fixt!(Signature), action).into()
 }
+
+async fn test_agent_info(
+    agent: &AgentPubKey,
+    dna_hash: DnaHash,
+) -> kitsune_p2p_types::agent_info::AgentInfoSigned {
+    use holochain_p2p::AgentPubKeyExt;
+    use holochain_p2p::DnaHashExt;
+
+    let signed_at_ms = Timestamp::now().as_millis() as u64;
+    kitsune_p2p_types::agent_info::AgentInfoSigned::sign(
+        dna_hash.to_kitsune(),
+        agent.to_kitsune(),
+        kitsune_p2p::dht::arq::ArqSize::from_half_len(u32::MAX / 2),
+        vec![],
+        signed_at_ms,
+        signed_at_ms + 60_000,
+        |_| async move {
+            Ok(Arc::new(kitsune_p2p_bin_data::KitsuneSignature(vec![
+                0;
+                64
+            ])))
+        },
+    )
+    .await
+    .unwrap()
+}
+
+async fn test_blob_manifest(test_case: &TestCase, author: AgentPubKey) -> SignedActionHashed {
+    let mut create_action = fixt!(Create);
+    create_action.author = author;
+    test_case.sign_action(Action::Create(create_action)).await
+}
+
+fn test_blob_chunk_op(agent: &AgentPubKey, manifest: &ActionHash, chunk: Vec<u8>) -> BlobChunkOp {
+    BlobChunkOp {
+        author: agent.clone(),
+        chunk_hash: BlobChunkOp::chunk_hash_of(&chunk),
+        manifest: manifest.clone(),
+        chunk,
+        signature: fixt!(Signature),
+        timestamp: Timestamp::now(),
+    }
+}
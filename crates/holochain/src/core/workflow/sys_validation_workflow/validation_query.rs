@@ -5,28 +5,39 @@ use crate::core::workflow::WorkflowResult;
 
 /// Get all ops that need to sys or app validated in order.
 /// - Sys validated or awaiting app dependencies.
-/// - Ordered by type then timestamp (See [`OpOrder`])
+/// - Ordered by the number of validation attempts and then by the
+///   given [`ValidationQueueOrder`]
 #[tracing::instrument(skip_all)]
-pub async fn get_ops_to_app_validate(db: &DbRead<DbKindDht>) -> WorkflowResult<Vec<DhtOpHashed>> {
-    get_ops_to_validate(db, false).await
+pub async fn get_ops_to_app_validate(
+    db: &DbRead<DbKindDht>,
+    order: ValidationQueueOrder,
+) -> WorkflowResult<Vec<DhtOpHashed>> {
+    get_ops_to_validate(db, false, order).await
 }
 
 /// Get all ops that need to sys or app validated in order.
 /// - Pending or awaiting sys dependencies.
-/// - Ordered by type then timestamp (See [`OpOrder`])
+/// - Ordered by the number of validation attempts and then by the
+///   given [`ValidationQueueOrder`]
 #[tracing::instrument(skip_all)]
-pub async fn get_ops_to_sys_validate(db: &DbRead<DbKindDht>) -> WorkflowResult<Vec<DhtOpHashed>> {
-    get_ops_to_validate(db, true).await
+pub async fn get_ops_to_sys_validate(
+    db: &DbRead<DbKindDht>,
+    order: ValidationQueueOrder,
+) -> WorkflowResult<Vec<DhtOpHashed>> {
+    get_ops_to_validate(db, true, order).await
 }
 
 async fn get_ops_to_validate(
     db: &DbRead<DbKindDht>,
     system: bool,
+    order: ValidationQueueOrder,
 ) -> WorkflowResult<Vec<DhtOpHashed>> {
     let mut sql = "
         SELECT
         Action.blob as action_blob,
         Entry.blob as entry_blob,
+        AgentInfo.blob as agent_info_blob,
+        BlobChunk.blob as blob_chunk_blob,
         DhtOp.type as dht_type,
         DhtOp.hash as dht_hash
         FROM DhtOp
@@ -34,6 +45,10 @@ async fn get_ops_to_validate(
         Action ON DhtOp.action_hash = Action.hash
         LEFT JOIN
         Entry ON Action.entry_hash = Entry.hash
+        LEFT JOIN
+        AgentInfo ON DhtOp.hash = AgentInfo.hash
+        LEFT JOIN
+        BlobChunk ON DhtOp.hash = BlobChunk.hash
         "
     .to_string();
     if system {
@@ -69,11 +84,21 @@ async fn get_ops_to_validate(
         "
         ORDER BY
         DhtOp.num_validation_attempts ASC,
-        DhtOp.op_order ASC
+        ",
+    );
+    sql.push_str(match order {
+        ValidationQueueOrder::OpType => "DhtOp.op_order ASC",
+        ValidationQueueOrder::OldestFirst => "DhtOp.authored_timestamp ASC",
+        ValidationQueueOrder::NewestFirst => "DhtOp.authored_timestamp DESC",
+    });
+    sql.push_str(
+        "
         LIMIT 10000
         ",
     );
-    db.read_async(move |txn| {
+    // This query runs for every validation round, so it uses the dedicated
+    // query connections to avoid contending with the other reads and writes.
+    db.query_async(move |txn| {
         let mut stmt = txn.prepare(&sql)?;
         let r = stmt.query_and_then([], |row| {
             let op = WorkflowResult::Ok(holochain_state::query::map_sql_dht_op(
@@ -120,7 +145,9 @@ mod tests {
         holochain_trace::test_run();
         let db = test_dht_db();
         let expected = create_test_data(&db.to_db().into()).await;
-        let ops = get_ops_to_sys_validate(&db.to_db().into()).await.unwrap();
+        let ops = get_ops_to_sys_validate(&db.to_db().into(), ValidationQueueOrder::OpType)
+            .await
+            .unwrap();
 
         assert_sorted_by_op_order(&ops).await;
         assert_sorted_by_validation_attempts(&db.to_db().into(), &ops).await;
@@ -136,7 +163,9 @@ mod tests {
         holochain_trace::test_run();
         let db = test_dht_db();
         let expected = create_test_data(&db.to_db().into()).await;
-        let ops = get_ops_to_app_validate(&db.to_db().into()).await.unwrap();
+        let ops = get_ops_to_app_validate(&db.to_db().into(), ValidationQueueOrder::OpType)
+            .await
+            .unwrap();
 
         assert_sorted_by_op_order(&ops).await;
         assert_sorted_by_validation_attempts(&db.to_db().into(), &ops).await;
@@ -147,14 +176,56 @@ mod tests {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn validation_query_honors_queue_order() {
+        holochain_trace::test_run();
+        let db = test_dht_db();
+        create_test_data(&db.to_db().into()).await;
+
+        for system in [true, false] {
+            for order in [
+                ValidationQueueOrder::OldestFirst,
+                ValidationQueueOrder::NewestFirst,
+            ] {
+                let ops = get_ops_to_validate(&db.to_db().into(), system, order)
+                    .await
+                    .unwrap();
+                assert_sorted_by_validation_attempts(&db.to_db().into(), &ops).await;
+
+                // Within the same number of attempts, ops follow the queue order.
+                let attempts = get_num_validation_attempts(
+                    &db.to_db().into(),
+                    ops.iter().map(|op| op.hash.clone()).collect(),
+                )
+                .await;
+                for (i, w) in ops.windows(2).enumerate() {
+                    if attempts[i] == attempts[i + 1] {
+                        match order {
+                            ValidationQueueOrder::NewestFirst => {
+                                assert!(w[0].timestamp() >= w[1].timestamp())
+                            }
+                            _ => assert!(w[0].timestamp() <= w[1].timestamp()),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     /// Make sure both workflows can't pull in the same ops.
     #[tokio::test(flavor = "multi_thread")]
     async fn workflows_are_exclusive() {
         holochain_trace::test_run();
         let db = test_dht_db();
         create_test_data(&db.to_db().into()).await;
-        let app_validation_ops = get_ops_to_app_validate(&db.to_db().into()).await.unwrap();
-        let sys_validation_ops = get_ops_to_sys_validate(&db.to_db().into()).await.unwrap();
+        let app_validation_ops =
+            get_ops_to_app_validate(&db.to_db().into(), ValidationQueueOrder::OpType)
+                .await
+                .unwrap();
+        let sys_validation_ops =
+            get_ops_to_sys_validate(&db.to_db().into(), ValidationQueueOrder::OpType)
+                .await
+                .unwrap();
 
         let app_hashes = app_validation_ops
             .into_iter()
@@ -111,6 +111,104 @@ async fn test_validation_receipt() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn publish_accepted_signal_on_first_receipt() {
+    holochain_trace::test_run();
+
+    let mut conductors = SweetConductorBatch::from_standard_config(2).await;
+
+    let (dna_file, _, coordinator_zomes) =
+        SweetDnaFile::unique_from_inline_zomes(("simple", simple_create_read_zome())).await;
+
+    let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (_bobbo,)) = apps.into_tuples();
+
+    let mut signals = conductors[0].subscribe_to_app_signals("app".to_string());
+
+    let hash: ActionHash = conductors[0]
+        .call(&alice.zome("simple"), "create", ())
+        .await;
+
+    tokio::time::timeout(std::time::Duration::from_secs(60), async {
+        loop {
+            if let Signal::App {
+                cell_id,
+                zome_name,
+                signal,
+            } = signals.recv().await.unwrap()
+            {
+                let Ok(PublishAccepted {
+                    action_hash,
+                    validators,
+                }) = signal.into_inner().decode()
+                else {
+                    continue;
+                };
+                if action_hash == hash {
+                    assert_eq!(cell_id, *alice.cell_id());
+                    assert_eq!(zome_name, *coordinator_zomes[0].zome_name());
+                    assert!(!validators.is_empty());
+                    break;
+                }
+            }
+        }
+    })
+    .await
+    .expect("Timed out waiting for the publish accepted signal");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn publication_proof_aggregates_receipts() {
+    holochain_trace::test_run();
+
+    let mut conductors = SweetConductorBatch::from_standard_config(2).await;
+
+    let (dna_file, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(("simple", simple_create_read_zome())).await;
+
+    let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+
+    let hash: ActionHash = conductors[0]
+        .call(&alice.zome("simple"), "create", ())
+        .await;
+
+    let payload = PublicationProofRequestPayload {
+        cell_id: alice.cell_id().clone(),
+        action_hash: hash.clone(),
+    };
+
+    let proof = tokio::time::timeout(std::time::Duration::from_secs(60), async {
+        loop {
+            let proof = conductors[0]
+                .raw_handle()
+                .publication_proof(&"app".to_string(), &payload)
+                .await
+                .unwrap();
+            if proof.valid_count() > 0 {
+                break proof;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        }
+    })
+    .await
+    .expect("Timed out waiting for a validation receipt");
+    assert_eq!(proof.action_hash, hash);
+    assert!(!proof.ops.is_empty());
+    assert!(proof.validators().contains(bobbo.agent_pubkey()));
+
+    // Cells of other apps can't be queried.
+    assert!(conductors[0]
+        .raw_handle()
+        .publication_proof(&"other app".to_string(), &payload)
+        .await
+        .is_err());
+}
+
 macro_rules! wait_until {
     ($expression:expr; $interval_ms:literal; $timeout_ms:literal; $wait_msg:literal; $timeout_msg:literal;) => {
         let timeout = (Timestamp::now() + std::time::Duration::from_millis($timeout_ms)).unwrap();
@@ -2,6 +2,7 @@ pub mod curve;
 
 use crate::conductor::api::CellConductorReadHandle;
 use crate::conductor::api::MockCellConductorReadHandleT;
+use crate::core::ribosome::guest_callback::dependency_hints::DependencyHintsHostAccess;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsHostAccess;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsInvocation;
 use crate::core::ribosome::guest_callback::init::InitHostAccess;
@@ -10,6 +11,7 @@ use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentHostAccess
 use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentInvocation;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitHostAccess;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitInvocation;
+use crate::core::ribosome::guest_callback::validate::ValidateActionCache;
 use crate::core::ribosome::guest_callback::validate::ValidateHostAccess;
 use crate::core::ribosome::real_ribosome::ModuleCacheLock;
 use crate::core::ribosome::real_ribosome::RealRibosome;
@@ -355,6 +357,8 @@ fixturator!(
         network: HolochainP2pDnaFixturator::new(Empty).next().unwrap(),
         signal_tx: broadcast::channel(50).0,
         call_zome_handle: CellConductorReadHandleFixturator::new(Empty).next().unwrap(),
+        read_only: false,
+        deadline: None,
     };
     curve Unpredictable ZomeCallHostAccess {
         workspace: HostFnWorkspaceFixturator::new(Unpredictable).next().unwrap(),
@@ -362,6 +366,8 @@ fixturator!(
         network: HolochainP2pDnaFixturator::new(Unpredictable).next().unwrap(),
         signal_tx: broadcast::channel(50).0,
         call_zome_handle: CellConductorReadHandleFixturator::new(Unpredictable).next().unwrap(),
+        read_only: false,
+        deadline: None,
     };
     curve Predictable ZomeCallHostAccess {
         workspace: HostFnWorkspaceFixturator::new_indexed(Predictable, get_fixt_index!())
@@ -377,6 +383,8 @@ fixturator!(
         call_zome_handle: CellConductorReadHandleFixturator::new_indexed(Predictable, get_fixt_index!())
             .next()
             .unwrap(),
+        read_only: false,
+        deadline: None,
     };
 );
 
@@ -390,6 +398,11 @@ fixturator!(
     constructor fn new();
 );
 
+fixturator!(
+    DependencyHintsHostAccess;
+    constructor fn new();
+);
+
 fixturator!(
     InitInvocation;
     constructor fn new(DnaDef);
@@ -476,9 +489,14 @@ fixturator!(
     constructor fn one(Zome);
 );
 
+fixturator!(
+    ValidateActionCache;
+    constructor fn default();
+);
+
 fixturator!(
     ValidateHostAccess;
-    constructor fn new(HostFnWorkspace, HolochainP2pDna);
+    constructor fn new(HostFnWorkspace, HolochainP2pDna, ValidateActionCache);
 );
 
 fixturator!(
@@ -488,6 +506,7 @@ fixturator!(
         Validate(ValidateHostAccess)
         Init(InitHostAccess)
         EntryDefs(EntryDefsHostAccess)
+        DependencyHints(DependencyHintsHostAccess)
         MigrateAgent(MigrateAgentHostAccess)
         PostCommit(PostCommitHostAccess)
     ];
@@ -515,6 +534,7 @@ fixturator!(
         signature: SignatureFixturator::new(Empty).next().unwrap(),
         nonce: Nonce256Bits::from(ThirtyTwoBytesFixturator::new(Empty).next().unwrap()),
         expires_at: TimestampFixturator::new(Empty).next().unwrap(),
+        wait_for_publish: None,
     };
     curve Unpredictable ZomeCallInvocation {
         cell_id: CellIdFixturator::new(Unpredictable).next().unwrap(),
@@ -527,6 +547,7 @@ fixturator!(
         nonce: Nonce256Bits::from(ThirtyTwoBytesFixturator::new(Unpredictable).next().unwrap()),
         // @todo should this be less predictable?
         expires_at: (Timestamp::now() + std::time::Duration::from_secs(30)).unwrap(),
+        wait_for_publish: None,
     };
     curve Predictable ZomeCallInvocation {
         cell_id: CellIdFixturator::new_indexed(Predictable, get_fixt_index!())
@@ -551,6 +572,7 @@ fixturator!(
         nonce: Nonce256Bits::from(ThirtyTwoBytesFixturator::new_indexed(Predictable, get_fixt_index!()).next().unwrap()),
         // @todo should this be more predictable?
         expires_at: (Timestamp::now() + std::time::Duration::from_secs(30)).unwrap(),
+        wait_for_publish: None,
     };
 );
 
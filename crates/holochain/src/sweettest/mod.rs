@@ -22,6 +22,7 @@ mod sweet_conductor_config;
 mod sweet_conductor_config_rendezvous;
 mod sweet_conductor_handle;
 pub mod sweet_consistency;
+mod sweet_countersigning;
 mod sweet_dna;
 /// Generation of network topologies.
 pub mod sweet_topos;
@@ -37,6 +38,7 @@ pub use sweet_conductor_config::*;
 pub use sweet_conductor_config_rendezvous::*;
 pub use sweet_conductor_handle::*;
 pub use sweet_consistency::*;
+pub use sweet_countersigning::*;
 pub use sweet_dna::*;
 pub use sweet_topos::*;
 pub use sweet_zome::*;
@@ -23,6 +23,8 @@ pub async fn app_bundle_from_dnas<'a>(
                     modifiers,
                     installed_hash: Some(installed_dna_hash.into()),
                     clone_limit: 255,
+                    host_fns: None,
+                    clone_archival: None,
                 },
                 provisioning: Some(CellProvisioning::Create { deferred: false }),
             };
@@ -61,6 +63,11 @@ pub async fn get_install_app_payload_from_dnas(
         installed_app_id: Some(installed_app_id.into()),
         network_seed: None,
         membrane_proofs,
+        validation_queue_orders: Default::default(),
+        missed_signals_buffer_size: None,
+        zome_call_timeout_ms: None,
+        warranted_authors: None,
+        egress_policy: None,
         #[cfg(feature = "chc")]
         ignore_genesis_failure: false,
     }
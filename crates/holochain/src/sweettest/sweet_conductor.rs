@@ -616,6 +616,20 @@ impl SweetConductor {
             .expect("Tried to use a conductor that is offline")
     }
 
+    /// Move the clock forward by `duration`, then run any scheduled functions
+    /// which have become due, without waiting for the scheduler.
+    ///
+    /// The clock is shared by everything in the test process, so every
+    /// conductor in the test sees the new time.
+    /// See [`holochain_zome_types::timestamp::mock_clock`].
+    #[cfg(feature = "mock_clock")]
+    pub async fn advance_time(&self, duration: std::time::Duration) {
+        holochain_zome_types::timestamp::mock_clock::advance(duration);
+        self.raw_handle()
+            .dispatch_scheduled_fns(Timestamp::now())
+            .await;
+    }
+
     /// Force trigger all dht ops that haven't received
     /// enough validation receipts yet.
     pub async fn force_all_publish_dht_ops(&self) {
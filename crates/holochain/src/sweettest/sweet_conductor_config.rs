@@ -31,9 +31,17 @@ impl From<KitsuneP2pConfig> for SweetConductorConfig {
                     port: 0,
                     allowed_origins: AllowedOrigins::Any,
                 },
+                require_token: false,
             }]),
             tuning_params: Some(ConductorTuningParams {
                 sys_validation_retry_delay: Some(std::time::Duration::from_secs(1)),
+                // Tests often get data straight after it's published to another node,
+                // so don't let an earlier miss hide it.
+                cascade_negative_cache_ttl: Some(std::time::Duration::ZERO),
+                // Tests count the ops held by each node, which the agent infos
+                // of the conductors in the test would add to.
+                publish_agent_infos: Some(false),
+                ..ConductorTuningParams::new()
             }),
             ..Default::default()
         }
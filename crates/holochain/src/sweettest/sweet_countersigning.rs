@@ -0,0 +1,114 @@
+//! Helpers for testing countersigning sessions between cells on a [`SweetConductor`].
+
+use super::*;
+use hdk::prelude::*;
+use holochain_state::prelude::{from_blob, named_params, StateQueryResult};
+use std::time::{Duration, Instant};
+
+/// The zome function which [`SweetConductor::accept_all_preflights`] calls to
+/// accept a preflight request. Every zome taking part in a session must expose
+/// an extern with this name which passes its input on to
+/// `hdk::prelude::accept_countersigning_preflight_request`.
+pub const ACCEPT_PREFLIGHT_FN: &str = "accept_countersigning_preflight_request";
+
+impl SweetConductor {
+    /// Start a countersigning session by calling a zome function which takes
+    /// the signing agents and returns a [`PreflightRequest`] for them.
+    /// The signing agents are the authors of `signers` in order, with no roles.
+    pub async fn initiate_countersigning(
+        &self,
+        zome: &SweetZome,
+        fn_name: impl Into<FunctionName>,
+        signers: &[&SweetZome],
+    ) -> PreflightRequest {
+        let agents: CounterSigningAgents = signers
+            .iter()
+            .map(|signer| (signer.cell_id().agent_pubkey().clone(), vec![]))
+            .collect();
+        self.call(zome, fn_name, agents).await
+    }
+
+    /// Accept the preflight request with every zome, by calling its
+    /// [`ACCEPT_PREFLIGHT_FN`], and return the responses in the same order.
+    /// Panics if any agent rejects the request.
+    pub async fn accept_all_preflights(
+        &self,
+        request: &PreflightRequest,
+        zomes: &[&SweetZome],
+    ) -> Vec<PreflightResponse> {
+        let mut responses = Vec::with_capacity(zomes.len());
+        for zome in zomes {
+            match self.call(zome, ACCEPT_PREFLIGHT_FN, request.clone()).await {
+                PreflightRequestAcceptance::Accepted(response) => responses.push(response),
+                rejected => panic!(
+                    "Agent {} did not accept the preflight request: {:?}",
+                    zome.cell_id().agent_pubkey(),
+                    rejected
+                ),
+            }
+        }
+        responses
+    }
+
+    /// Wait until the countersigning session for this request is complete for
+    /// every cell, meaning that the cell has committed the countersigned entry
+    /// and its chain is no longer locked.
+    /// Panics if this doesn't happen before the timeout.
+    pub async fn await_session_completion(
+        &self,
+        request: &PreflightRequest,
+        cells: &[&SweetCell],
+        timeout: Duration,
+    ) {
+        let wait_start = Instant::now();
+        for cell in cells {
+            while !session_completed(cell, request).await {
+                if wait_start.elapsed() > timeout {
+                    panic!(
+                        "Timed out waiting for the countersigning session to complete for cell {}",
+                        cell.cell_id()
+                    );
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+    }
+}
+
+/// Has this cell committed the countersigned entry for the request
+/// and unlocked its chain?
+async fn session_completed(cell: &SweetCell, request: &PreflightRequest) -> bool {
+    let author = cell.agent_pubkey().clone();
+    let request = request.clone();
+    cell.authored_db()
+        .read_async(move |txn| -> StateQueryResult<bool> {
+            let locked: bool = txn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM ChainLock WHERE author = :author)",
+                named_params! {":author": author},
+                |row| row.get(0),
+            )?;
+            if locked {
+                return Ok(false);
+            }
+            let mut stmt = txn.prepare(
+                "
+                SELECT Entry.blob FROM Action
+                JOIN Entry ON Action.entry_hash = Entry.hash
+                WHERE Action.author = :author
+                ",
+            )?;
+            let entries = stmt.query_and_then(named_params! {":author": author}, |row| {
+                from_blob::<Entry>(row.get("blob")?)
+            })?;
+            for entry in entries {
+                if let Entry::CounterSign(session_data, _) = entry? {
+                    if session_data.preflight_request == request {
+                        return Ok(true);
+                    }
+                }
+            }
+            Ok(false)
+        })
+        .await
+        .unwrap()
+}
@@ -445,6 +445,7 @@ pub async fn setup_app_inner(
                 port: 0,
                 allowed_origins: AllowedOrigins::Any,
             },
+            require_token: false,
         }]),
         network: network.unwrap_or_default(),
         ..Default::default()
@@ -565,6 +566,8 @@ async fn wait_for_integration_diff<Db: ReadAccess<DbKindDht>>(
             DhtOp::WarrantOp(op) => {
                 format!("{} WARRANT ({})", op.author, op.get_type(),)
             }
+            DhtOp::AgentInfoOp(op) => format!("{} AGENT INFO", op.agent),
+            DhtOp::BlobChunkOp(op) => format!("{} BLOB CHUNK ({})", op.author, op.chunk_hash),
         }
     }
 
@@ -737,8 +740,11 @@ pub async fn query_integration<Db: ReadAccess<DbKindDht>>(db: &Db) -> Integratio
 async fn get_integrated_count<Db: ReadAccess<DbKindDht>>(db: &Db) -> usize {
     db.read_async(move |txn| -> DatabaseResult<usize> {
         Ok(txn.query_row(
-            "SELECT COUNT(hash) FROM DhtOp WHERE DhtOp.when_integrated IS NOT NULL",
-            [],
+            "SELECT COUNT(hash) FROM DhtOp WHERE DhtOp.when_integrated IS NOT NULL AND DhtOp.type NOT IN (:agent_info, :blob_chunk)",
+            named_params! {
+                ":agent_info": DhtOpType::AgentInfo,
+                ":blob_chunk": DhtOpType::BlobChunk,
+            },
             |row| row.get(0),
         )?)
     })
@@ -760,11 +766,15 @@ pub async fn get_integrated_ops<Db: ReadAccess<DbKindDht>>(db: &Db) -> Vec<DhtOp
             Entry ON Action.entry_hash = Entry.hash
             WHERE
             DhtOp.when_integrated IS NOT NULL
+            AND DhtOp.type NOT IN (:agent_info, :blob_chunk)
             ORDER BY DhtOp.rowid ASC
         ",
         )
         .unwrap()
-        .query_and_then(named_params! {}, |row| {
+        .query_and_then(named_params! {
+            ":agent_info": DhtOpType::AgentInfo,
+            ":blob_chunk": DhtOpType::BlobChunk,
+        }, |row| {
             Ok(holochain_state::query::map_sql_dht_op(true, "type", row).unwrap())
         })
         .unwrap()
@@ -825,6 +835,7 @@ where
         provenance: cell_id.agent_pubkey().clone(),
         nonce,
         expires_at,
+        wait_for_publish: None,
     })
 }
 
@@ -848,6 +859,7 @@ where
         signature,
         nonce,
         expires_at,
+        wait_for_publish,
         ..
     } = new_zome_call(keystore, cell_id, func, payload, zome.clone().into()).await?;
     Ok(ZomeCallInvocation {
@@ -860,6 +872,7 @@ where
         signature,
         nonce,
         expires_at,
+        wait_for_publish,
     })
 }
 
@@ -122,9 +122,7 @@ impl HostFnCaller {
         let dht_db_cache = handle.get_dht_db_cache(cell_id.dna_hash()).unwrap();
         let cache = handle.get_cache_db(cell_id).await.unwrap();
         let keystore = handle.keystore().clone();
-        let network = handle
-            .holochain_p2p()
-            .to_dna(cell_id.dna_hash().clone(), None);
+        let network = handle.holochain_p2p().to_cell(cell_id.clone(), None);
 
         let zome_path = (
             cell_id.clone(),
@@ -196,6 +194,8 @@ impl HostFnCaller {
             network,
             signal_tx,
             call_zome_handle,
+            false,
+            None,
         );
         let ribosome = Arc::new(ribosome);
         let zome = ribosome.dna_def().get_zome(&zome_name).unwrap();
@@ -263,6 +263,7 @@ async fn many_concurrent_zome_calls_dont_gunk_up_the_works() {
                         payload: ExternIO::encode(n).unwrap(),
                         nonce,
                         expires_at,
+                        wait_for_publish: None,
                     },
                 )
                 .await
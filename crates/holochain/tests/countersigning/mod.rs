@@ -0,0 +1,54 @@
+use holochain::sweettest::*;
+use holochain_types::prelude::*;
+use holochain_wasm_test_utils::TestWasm;
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread")]
+#[cfg(feature = "slow_tests")]
+async fn countersigning_session_with_sweet_helpers() {
+    holochain_trace::test_run();
+
+    let (dna_file, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::CounterSigning]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let (alice_pubkey, bob_pubkey) = SweetAgents::alice_and_bob();
+    let apps = conductor
+        .setup_app_for_agents("app-", [&alice_pubkey, &bob_pubkey], [&dna_file])
+        .await
+        .unwrap();
+    let ((alice_cell,), (bob_cell,)) = apps.into_tuples();
+    let alice = alice_cell.zome(TestWasm::CounterSigning);
+    let bob = bob_cell.zome(TestWasm::CounterSigning);
+
+    // Bob's zome must be initialized for countersigning to work.
+    let _: ActionHash = conductor.call(&bob, "create_a_thing", ()).await;
+
+    let request = conductor
+        .initiate_countersigning(
+            &alice,
+            "generate_countersigning_preflight_request",
+            &[&alice, &bob],
+        )
+        .await;
+    assert_eq!(
+        vec![(alice_pubkey, vec![]), (bob_pubkey, vec![])],
+        request.signing_agents
+    );
+
+    let responses = conductor
+        .accept_all_preflights(&request, &[&alice, &bob])
+        .await;
+    for zome in [&alice, &bob] {
+        let _: ActionHash = conductor
+            .call(zome, "create_a_countersigned_thing", responses.clone())
+            .await;
+    }
+
+    conductor
+        .await_session_completion(&request, &[&alice_cell, &bob_cell], Duration::from_secs(30))
+        .await;
+
+    // Both chains are unlocked again.
+    let _: ActionHash = conductor.call(&alice, "create_a_thing", ()).await;
+    let _: ActionHash = conductor.call(&bob, "create_a_thing", ()).await;
+}
@@ -1,6 +1,7 @@
 mod agent_scaling;
 mod authored_test;
 mod clone_cell;
+mod countersigning;
 mod dht_arc;
 mod dna_properties;
 mod graft_records_onto_source_chain;
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use holo_hash::ActionHash;
 use holo_hash::WasmHash;
 use holochain::conductor::api::AdminInterfaceApi;
+use holochain::conductor::error::ConductorError;
 use holochain::sweettest::*;
 use holochain_conductor_api::AdminRequest;
 use holochain_conductor_api::AdminResponse;
@@ -77,6 +78,120 @@ async fn test_coordinator_zome_update() {
     println!("Success! Success! Success! ");
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_coordinator_zome_update_failure_and_rollback() {
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let (dna, _, _) = SweetDnaFile::unique_from_zomes(
+        vec![TestIntegrityWasm::IntegrityZome],
+        vec![TestCoordinatorWasm::CoordinatorZome],
+        vec![
+            DnaWasm::from(TestIntegrityWasm::IntegrityZome),
+            DnaWasm::from(TestCoordinatorWasm::CoordinatorZome),
+        ],
+    )
+    .await;
+    let dna_hash = dna.dna_hash().clone();
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let cells = app.into_cells();
+    let zome = cells[0].zome(TestCoordinatorWasm::CoordinatorZome);
+
+    let _hash: ActionHash = conductor.call(&zome, "create_entry", ()).await;
+
+    // There is nothing to roll back to before the first update.
+    assert!(conductor.rollback_coordinators(&dna_hash).await.is_err());
+
+    // Replacing the coordinator with wasm that doesn't compile fails the update
+    // and leaves the original coordinator in place.
+    let invalid_wasm = DnaWasm::new_invalid();
+    let invalid_coordinator: CoordinatorZomeDef = ZomeDef::Wasm(WasmZome {
+        wasm_hash: WasmHash::with_data(&invalid_wasm).await,
+        dependencies: vec![TestIntegrityWasm::IntegrityZome.into()],
+        preserialized_path: None,
+    })
+    .into();
+    let result = conductor
+        .update_coordinators(
+            &dna_hash,
+            vec![(
+                TestCoordinatorWasm::CoordinatorZome.into(),
+                invalid_coordinator,
+            )],
+            vec![invalid_wasm],
+        )
+        .await;
+    assert!(result.is_err());
+
+    let record: Option<Record> = conductor.call(&zome, "get_entry", ()).await;
+    assert!(record.is_some());
+
+    // A coordinator whose init fails is refused too.
+    let (_, init_fails) = InlineZomeSet::new_unique(
+        Vec::<(&'static str, Vec<EntryDef>, u8)>::new(),
+        ["init_fails"],
+    )
+    .function("init_fails", "init", |_, ()| {
+        Ok(InitCallbackResult::Fail("refusing to start".into()))
+    })
+    .into_zomes();
+    let result = conductor
+        .update_coordinators(
+            &dna_hash,
+            init_fails
+                .into_iter()
+                .map(|zome| zome.into_inner())
+                .collect(),
+            vec![],
+        )
+        .await;
+    assert!(
+        matches!(
+            &result,
+            Err(ConductorError::CoordinatorSmokeTestFailed(_, err))
+                if err.to_string().contains("refusing to start")
+        ),
+        "{result:?}"
+    );
+    assert_eq!(
+        1,
+        conductor
+            .get_dna_def(&dna_hash)
+            .unwrap()
+            .coordinator_zomes
+            .len()
+    );
+
+    // A valid update can be rolled back to the original coordinators.
+    conductor
+        .update_coordinators(
+            &dna_hash,
+            vec![CoordinatorZome::from(TestCoordinatorWasm::CoordinatorZomeUpdate).into_inner()],
+            vec![TestCoordinatorWasm::CoordinatorZomeUpdate.into()],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        2,
+        conductor
+            .get_dna_def(&dna_hash)
+            .unwrap()
+            .coordinator_zomes
+            .len()
+    );
+
+    conductor.rollback_coordinators(&dna_hash).await.unwrap();
+    let coordinators = conductor.get_dna_def(&dna_hash).unwrap().coordinator_zomes;
+    assert_eq!(
+        vec![ZomeName::from(TestCoordinatorWasm::CoordinatorZome)],
+        coordinators
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>()
+    );
+
+    let record: Option<Record> = conductor.call(&zome, "get_entry", ()).await;
+    assert!(record.is_some());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_coordinator_zome_update_multi_integrity() {
     let mut conductor = SweetConductor::from_standard_config().await;
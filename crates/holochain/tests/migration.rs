@@ -98,3 +98,120 @@ async fn migrate_dna_with_second_app_install() {
     assert_eq!(results[1].value, "test new");
     assert_eq!(results[1].amount, 4);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn import_legacy_chain() {
+    use holochain::conductor::api::error::ConductorApiError;
+    use holochain::conductor::error::ConductorError;
+    use holochain::test_utils::inline_zomes::simple_crud_zome;
+    use holochain_sqlite::error::DatabaseResult;
+    use holochain_state::prelude::{Store, Txn};
+    use holochain_types::prelude::*;
+    use std::collections::HashMap;
+
+    holochain_trace::test_run();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(simple_crud_zome()).await;
+    let author = SweetAgents::one(conductor.keystore()).await;
+    let keystore = conductor.keystore();
+    let cell_id = CellId::new(dna_file.dna_hash().clone(), author.clone());
+
+    // The genesis of a chain written by the LMDB-era conductor, for another DNA.
+    let dna = LegacyHeader::Dna {
+        author: author.clone(),
+        timestamp: LegacyTimestamp(1_600_000_000, 0),
+        hash: holo_hash::DnaHash::from_raw_36(vec![0xdb; 36]),
+    };
+    let avp = LegacyHeader::AgentValidationPkg {
+        author: author.clone(),
+        timestamp: LegacyTimestamp(1_600_000_001, 0),
+        header_seq: 1,
+        prev_header: ActionHash::with_data_sync(&dna),
+        membrane_proof: None,
+    };
+    let agent_entry = LegacyEntry::Agent(author.clone());
+    let create_agent = LegacyHeader::Create {
+        author: author.clone(),
+        timestamp: LegacyTimestamp(1_600_000_002, 0),
+        header_seq: 2,
+        prev_header: ActionHash::with_data_sync(&avp),
+        entry_type: LegacyEntryType::AgentPubKey,
+        entry_hash: EntryHash::with_data_sync(&agent_entry),
+    };
+    let mut headers = Vec::new();
+    for header in [dna, avp, create_agent] {
+        let signature = author.sign(&keystore, &header).await.unwrap();
+        headers.push(LegacySignedHeader(header, signature));
+    }
+    let chain = LegacyChain {
+        headers,
+        entries: vec![agent_entry],
+    };
+
+    // The chain is imported before the app is installed, once its DNA is registered.
+    conductor
+        .raw_handle()
+        .register_dna(dna_file.clone())
+        .await
+        .unwrap();
+
+    // A chain signed by someone else is rejected.
+    let mut forged = chain.clone();
+    forged.headers[2].1 = Signature([0; 64]);
+    assert!(conductor
+        .raw_handle()
+        .import_legacy_chain(cell_id.clone(), forged, HashMap::new())
+        .await
+        .is_err());
+
+    let imported = conductor
+        .raw_handle()
+        .import_legacy_chain(cell_id.clone(), chain.clone(), HashMap::new())
+        .await
+        .unwrap();
+    assert_eq!(imported, 3);
+
+    // Installing the app for the chain's agent keeps the imported genesis.
+    let (alice,) = conductor
+        .setup_app_for_agent("app", author.clone(), [&dna_file])
+        .await
+        .unwrap()
+        .into_tuple();
+    let actions: Vec<Action> = alice
+        .authored_db()
+        .read_async(|txn| -> DatabaseResult<Vec<Action>> {
+            let hashes: Vec<ActionHash> = txn
+                .prepare("SELECT hash FROM Action ORDER BY seq")?
+                .query_map([], |row| row.get(0))?
+                .collect::<Result<_, _>>()?;
+            let txn: Txn = (&txn).into();
+            Ok(hashes
+                .into_iter()
+                .map(|hash| {
+                    let record = txn.get_record(&hash.into()).unwrap().unwrap();
+                    record.action().clone()
+                })
+                .collect())
+        })
+        .await
+        .unwrap();
+    assert_eq!(actions.len(), 3);
+    assert!(matches!(&actions[0], Action::Dna(dna) if &dna.hash == dna_file.dna_hash()));
+    assert_eq!(
+        actions[0].timestamp(),
+        Timestamp::from_micros(1_600_000_000_000_000)
+    );
+    assert_eq!(actions[2].entry_hash(), Some(&author.clone().into()));
+
+    // A cell which has run genesis can't have a chain imported into it.
+    assert!(matches!(
+        conductor
+            .raw_handle()
+            .import_legacy_chain(cell_id, chain, HashMap::new())
+            .await,
+        Err(ConductorApiError::ConductorError(
+            ConductorError::CellAlreadyHasGenesis(_)
+        ))
+    ));
+}
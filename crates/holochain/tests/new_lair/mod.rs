@@ -50,6 +50,7 @@ async fn test_new_lair_conductor_integration() {
             port: ADMIN_PORT,
             allowed_origins: AllowedOrigins::Any,
         },
+        require_token: false,
     }]);
     conductor_config.data_root_path = Some(tmp.path().to_owned().into());
     conductor_config.keystore = KeystoreConfig::LairServer {
@@ -87,6 +87,7 @@ async fn ser_regression_test() {
             provenance: alice.agent_pubkey().clone(),
             nonce,
             expires_at,
+            wait_for_publish: None,
         },
     )
     .await
@@ -135,6 +136,7 @@ async fn ser_regression_test() {
             provenance: alice.agent_pubkey().clone(),
             nonce,
             expires_at,
+            wait_for_publish: None,
         },
     )
     .await
@@ -882,6 +882,7 @@ async fn mock_network_sharded_gossip() {
                             debug!("CallRemoteMulti")
                         }
                         holochain_p2p::WireMessage::CallRemote { .. } => debug!("CallRemote"),
+                        holochain_p2p::WireMessage::RemoteQuery { .. } => debug!("RemoteQuery"),
                         holochain_p2p::WireMessage::PublishCountersign { .. } => {
                             debug!("PublishCountersign")
                         }
@@ -926,12 +927,19 @@ async fn mock_network_sharded_gossip() {
                         holochain_p2p::WireMessage::GetMeta { .. } => debug!("get_meta"),
                         holochain_p2p::WireMessage::GetLinks { .. } => debug!("get_links"),
                         holochain_p2p::WireMessage::CountLinks { .. } => debug!("count_links"),
+                        holochain_p2p::WireMessage::ProbeBasis { .. } => debug!("probe_basis"),
                         holochain_p2p::WireMessage::GetAgentActivity { .. } => {
                             debug!("get_agent_activity")
                         }
                         holochain_p2p::WireMessage::MustGetAgentActivity { .. } => {
                             debug!("must_get_agent_activity")
                         }
+                        holochain_p2p::WireMessage::GetActionBySeq { .. } => {
+                            debug!("get_action_by_seq")
+                        }
+                        holochain_p2p::WireMessage::GetEntriesByAuthor { .. } => {
+                            debug!("get_entries_by_author")
+                        }
                         holochain_p2p::WireMessage::CountersigningSessionNegotiation { .. } => {
                             debug!("countersigning_session_negotiation")
                         }
@@ -1399,6 +1407,7 @@ async fn mock_network_sharding() {
                             debug!("CallRemoteMulti")
                         }
                         holochain_p2p::WireMessage::CallRemote { .. } => debug!("CallRemote"),
+                        holochain_p2p::WireMessage::RemoteQuery { .. } => debug!("RemoteQuery"),
                         holochain_p2p::WireMessage::ValidationReceipts { receipts: _ } => {
                             debug!("Validation Receipt")
                         }
@@ -1442,12 +1451,19 @@ async fn mock_network_sharding() {
                         holochain_p2p::WireMessage::GetMeta { .. } => debug!("get_meta"),
                         holochain_p2p::WireMessage::GetLinks { .. } => debug!("get_links"),
                         holochain_p2p::WireMessage::CountLinks { .. } => debug!("count_links"),
+                        holochain_p2p::WireMessage::ProbeBasis { .. } => debug!("probe_basis"),
                         holochain_p2p::WireMessage::GetAgentActivity { .. } => {
                             debug!("get_agent_activity")
                         }
                         holochain_p2p::WireMessage::MustGetAgentActivity { .. } => {
                             debug!("must_get_agent_activity")
                         }
+                        holochain_p2p::WireMessage::GetActionBySeq { .. } => {
+                            debug!("get_action_by_seq")
+                        }
+                        holochain_p2p::WireMessage::GetEntriesByAuthor { .. } => {
+                            debug!("get_entries_by_author")
+                        }
                         holochain_p2p::WireMessage::CountersigningSessionNegotiation { .. } => {
                             debug!("countersigning_session_negotiation")
                         }
@@ -140,6 +140,7 @@ pub async fn call_zome_fn<S>(
         payload: ExternIO::encode(input).unwrap(),
         nonce,
         expires_at,
+        wait_for_publish: None,
     };
     let signature = signing_keypair.sign(&zome_call_unsigned.data_to_sign().unwrap());
     let call = ZomeCall {
@@ -152,6 +153,8 @@ pub async fn call_zome_fn<S>(
         nonce: zome_call_unsigned.nonce,
         expires_at: zome_call_unsigned.expires_at,
         signature: Signature::from(signature.to_bytes()),
+        wait_for_publish: None,
+        timeout_ms: None,
     };
     let request = AppRequest::CallZome(Box::new(call));
     let response = app_tx.request(request);
@@ -260,6 +263,8 @@ pub async fn register_and_install_dna_named(
             modifiers: mods,
             installed_hash: Some(dna_hash.clone().into()),
             clone_limit: 0,
+            host_fns: None,
+            clone_archival: None,
         },
         provisioning: Some(CellProvisioning::Create { deferred: false }),
     }];
@@ -283,6 +288,11 @@ pub async fn register_and_install_dna_named(
         installed_app_id: Some(name),
         network_seed: None,
         membrane_proofs: std::collections::HashMap::new(),
+        validation_queue_orders: Default::default(),
+        missed_signals_buffer_size: None,
+        zome_call_timeout_ms: None,
+        warranted_authors: None,
+        egress_policy: None,
         #[cfg(feature = "chc")]
         ignore_genesis_failure: false,
     };
@@ -348,6 +358,7 @@ pub fn create_config(port: u16, data_root_path: DataRootPath) -> ConductorConfig
                 port,
                 allowed_origins: AllowedOrigins::Any,
             },
+            require_token: false,
         }]),
         data_root_path: Some(data_root_path),
         keystore: KeystoreConfig::DangerTestKeystore,
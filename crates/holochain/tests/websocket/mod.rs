@@ -18,7 +18,10 @@ use holochain::{
 use std::net::{Ipv4Addr, Ipv6Addr, ToSocketAddrs};
 
 use either::Either;
-use holochain_conductor_api::{AdminInterfaceConfig, AppRequest, InterfaceDriver};
+use holochain_conductor_api::{
+    AdminAuthenticationRequest, AdminInterfaceConfig, AdminScope, AppRequest, ExternalApiWireError,
+    InterfaceDriver, IssueAdminAuthenticationTokenPayload,
+};
 use holochain_types::websocket::AllowedOrigins;
 use holochain_types::{
     prelude::*,
@@ -856,6 +859,7 @@ async fn admin_allowed_origins() {
                 port: 0,
                 allowed_origins: "http://localhost:3000".to_string().into(),
             },
+            require_token: false,
         }])
         .await
         .unwrap();
@@ -896,6 +900,103 @@ async fn admin_allowed_origins() {
     let _: AdminResponse = client.request(request).await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn admin_token_scope_is_enforced() {
+    holochain_trace::test_run();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+
+    let dna_file = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo])
+        .await
+        .0;
+    let app = conductor.setup_app("app", &[dna_file]).await.unwrap();
+    let cell_id = app.into_cells()[0].cell_id().clone();
+
+    let ports = conductor
+        .clone()
+        .add_admin_interfaces(vec![AdminInterfaceConfig {
+            driver: InterfaceDriver::Websocket {
+                port: 0,
+                allowed_origins: AllowedOrigins::Any,
+            },
+            require_token: true,
+        }])
+        .await
+        .unwrap();
+    let port = *ports.first().unwrap();
+
+    let connect_admin = || async move {
+        connect(
+            Arc::new(WebsocketConfig::CLIENT_DEFAULT),
+            ConnectRequest::new((Ipv4Addr::LOCALHOST, port).into()),
+        )
+        .await
+        .unwrap()
+    };
+
+    // A connection which doesn't authenticate is dropped
+    let (client, rx) = connect_admin().await;
+    let _rx = WsPollRecv::new::<AdminResponse>(rx);
+    assert!(client
+        .request::<_, AdminResponse>(AdminRequest::ListDnas)
+        .await
+        .is_err());
+
+    let issued = conductor
+        .issue_admin_authentication_token(IssueAdminAuthenticationTokenPayload::for_scope(
+            AdminScope::App {
+                installed_app_id: "app".to_string(),
+            },
+        ))
+        .unwrap();
+
+    let (client, rx) = connect_admin().await;
+    let _rx = WsPollRecv::new::<AdminResponse>(rx);
+    client
+        .authenticate(AdminAuthenticationRequest {
+            token: issued.token.clone(),
+        })
+        .await
+        .unwrap();
+
+    let response: AdminResponse = client
+        .request(AdminRequest::FreezeCell {
+            cell_id: cell_id.clone(),
+        })
+        .await
+        .unwrap();
+    assert_matches!(response, AdminResponse::CellFrozen);
+
+    for request in [
+        AdminRequest::DisableApp {
+            installed_app_id: "other".to_string(),
+        },
+        AdminRequest::GenerateAgentPubKey,
+        AdminRequest::IssueAdminAuthenticationToken(
+            IssueAdminAuthenticationTokenPayload::for_scope(AdminScope::ReadOnly),
+        ),
+    ] {
+        let response: AdminResponse = client.request(request).await.unwrap();
+        assert_matches!(
+            response,
+            AdminResponse::Error(ExternalApiWireError::AdminCallUnauthorized(_))
+        );
+    }
+
+    // Revoking the token takes effect on the open connection
+    conductor
+        .revoke_admin_authentication_token(issued.token)
+        .unwrap();
+    let response: AdminResponse = client
+        .request(AdminRequest::UnfreezeCell { cell_id })
+        .await
+        .unwrap();
+    assert_matches!(
+        response,
+        AdminResponse::Error(ExternalApiWireError::AdminCallUnauthorized(_))
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn holochain_websockets_listen_on_ipv4_and_ipv6() {
     holochain_trace::test_run();
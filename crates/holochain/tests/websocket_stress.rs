@@ -42,6 +42,7 @@ pub async fn websocket_stress() {
                 port: 0,
                 allowed_origins: AllowedOrigins::Any,
             },
+            require_token: false,
         }]),
         data_root_path: Some(data_root_path.into()),
         keystore: KeystoreConfig::DangerTestKeystore,
@@ -1,7 +1,9 @@
 //! Functions for the various authorities to handle queries
 
+use self::get_agent_activity_query::action_by_seq::GetActionBySeqQuery;
 use self::get_agent_activity_query::hashes::GetAgentActivityQuery;
 use self::get_agent_activity_query::must_get_agent_activity::must_get_agent_activity;
+use self::get_entries_by_author_query::GetEntryActionsByAuthorQuery;
 use self::get_entry_ops_query::GetEntryOpsQuery;
 use self::get_links_ops_query::GetLinksOpsQuery;
 use self::{
@@ -23,6 +25,7 @@ use tracing::*;
 mod test;
 
 pub(crate) mod get_agent_activity_query;
+pub(crate) mod get_entries_by_author_query;
 pub(crate) mod get_entry_ops_query;
 pub(crate) mod get_links_ops_query;
 pub(crate) mod get_record_query;
@@ -68,6 +71,35 @@ pub async fn handle_get_agent_activity(
     Ok(results)
 }
 
+/// Handler for get_action_by_seq query to an Activity authority
+#[instrument(skip(env))]
+pub async fn handle_get_action_by_seq(
+    env: DbRead<DbKindDht>,
+    author: AgentPubKey,
+    action_seq: u32,
+) -> CascadeResult<ActionBySeqResponse> {
+    let query = GetActionBySeqQuery::valid(author, action_seq);
+    let actions = env
+        .read_async(move |txn| query.run(Txn::from(&txn)))
+        .await?;
+    Ok(ActionBySeqResponse { actions })
+}
+
+/// Handler for get_entries_by_author query to an Activity authority
+#[instrument(skip(env))]
+pub async fn handle_get_entries_by_author(
+    env: DbRead<DbKindDht>,
+    author: AgentPubKey,
+    entry_type: EntryType,
+    page: EntriesPage,
+) -> CascadeResult<EntriesByAuthorResponse> {
+    let query = GetEntryActionsByAuthorQuery::new(author, entry_type, page);
+    let actions = env
+        .read_async(move |txn| query.run(Txn::from(&txn)))
+        .await?;
+    Ok(EntriesByAuthorResponse { actions })
+}
+
 /// Handler for must_get_agent_activity query to an Activity authority
 #[instrument(skip(env))]
 pub async fn handle_must_get_agent_activity(
@@ -102,7 +134,11 @@ pub async fn handle_get_links(
 ) -> CascadeResult<WireLinkOps> {
     let query = GetLinksOpsQuery::new(link_key);
     let results = env
-        .read_async(move |txn| query.run(Txn::from(&txn)))
+        .read_async(move |txn| -> CascadeResult<_> {
+            let mut ops = query.run(Txn::from(&txn))?;
+            get_links_ops_query::retain_listed_targets(txn, &mut ops)?;
+            Ok(ops)
+        })
         .await?;
     Ok(results)
 }
@@ -1,3 +1,4 @@
+pub mod action_by_seq;
 pub mod deterministic;
 pub mod hashes;
 pub mod must_get_agent_activity;
@@ -0,0 +1,114 @@
+//! Query for `get_action_by_seq`, which looks up a single position of an
+//! agent's chain by author and sequence number.
+//!
+//! This is answered from the `Action_author_seq_idx` index, so unlike
+//! `get_agent_activity` it doesn't have to walk the whole chain.
+//!
+//! More than one action is returned if the agent has forked their chain at
+//! this position.
+
+use holo_hash::*;
+use holochain_sqlite::rusqlite::*;
+use holochain_state::{
+    prelude::*,
+    query::{row_blob_and_hash_to_action, QueryData},
+};
+use std::{fmt::Debug, sync::Arc};
+
+#[derive(Debug, Clone)]
+pub struct GetActionBySeqQuery {
+    author: Arc<AgentPubKey>,
+    action_seq: u32,
+    valid_only: bool,
+}
+
+impl GetActionBySeqQuery {
+    /// Query for actions which have been validated and integrated,
+    /// as an agent activity authority serves them.
+    pub fn valid(author: AgentPubKey, action_seq: u32) -> Self {
+        Self {
+            author: Arc::new(author),
+            action_seq,
+            valid_only: true,
+        }
+    }
+
+    /// Query for any action which has not been rejected,
+    /// including actions which are still pending validation.
+    pub fn not_rejected(author: AgentPubKey, action_seq: u32) -> Self {
+        Self {
+            author: Arc::new(author),
+            action_seq,
+            valid_only: false,
+        }
+    }
+}
+
+impl Query for GetActionBySeqQuery {
+    type Item = Judged<SignedActionHashed>;
+    type State = Vec<SignedActionHashed>;
+    type Output = Vec<SignedActionHashed>;
+
+    fn query(&self) -> String {
+        "
+            SELECT Action.blob, Action.hash, DhtOp.validation_status
+            FROM Action
+            JOIN DhtOp ON DhtOp.action_hash = Action.hash
+            WHERE Action.author = :author
+            AND Action.seq = :seq
+            AND (
+                :valid_only = 0
+                OR (DhtOp.validation_status = :status AND DhtOp.when_integrated IS NOT NULL)
+            )
+        "
+        .to_string()
+    }
+
+    fn params(&self) -> Vec<holochain_state::query::Params> {
+        (named_params! {
+            ":author": self.author,
+            ":seq": self.action_seq,
+            ":valid_only": self.valid_only,
+            ":status": ValidationStatus::Valid,
+        })
+        .to_vec()
+    }
+
+    fn init_fold(&self) -> StateQueryResult<Self::State> {
+        Ok(Vec::new())
+    }
+
+    fn as_filter(&self) -> Box<dyn Fn(&QueryData<Self>) -> bool> {
+        let author = self.author.clone();
+        let action_seq = self.action_seq;
+        Box::new(move |action: &QueryData<Self>| {
+            *action.action().author() == *author && action.action().action_seq() == action_seq
+        })
+    }
+
+    fn fold(&self, mut state: Self::State, item: Self::Item) -> StateQueryResult<Self::State> {
+        match item.status {
+            Some(ValidationStatus::Valid) | None => state.push(item.data),
+            Some(ValidationStatus::Rejected) | Some(ValidationStatus::Abandoned) => (),
+        }
+        Ok(state)
+    }
+
+    fn render<S>(&self, mut state: Self::State, _stores: S) -> StateQueryResult<Self::Output>
+    where
+        S: Store,
+    {
+        // An action is joined once per op, and may be held by more than one store.
+        state.sort_unstable_by(|a, b| a.as_hash().cmp(b.as_hash()));
+        state.dedup_by(|a, b| a.as_hash() == b.as_hash());
+        Ok(state)
+    }
+
+    fn as_map(&self) -> Arc<dyn Fn(&Row) -> StateQueryResult<Self::Item>> {
+        let f = row_blob_and_hash_to_action("blob", "hash");
+        Arc::new(move |row| {
+            let validation_status: Option<ValidationStatus> = row.get("validation_status")?;
+            Ok(Judged::raw(f(row)?, validation_status))
+        })
+    }
+}
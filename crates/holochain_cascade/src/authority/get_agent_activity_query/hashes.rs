@@ -133,6 +133,8 @@ impl Query for GetAgentActivityQuery {
         let highest_observed = compute_highest_observed(&state);
         let status = compute_chain_status(&state);
 
+        // Actions which create or update unlisted entries are listed like any
+        // other, so the activity has no gaps in its sequence numbers.
         let valid = state.valid;
         let rejected = state.rejected;
         let valid_activity = if self.options.include_valid_activity {
@@ -0,0 +1,246 @@
+//! Queries for `get_entries_by_author`, which lists the public entries of one
+//! type created by an author.
+//!
+//! The actions which created the entries are listed by the author's agent
+//! activity authorities from the register agent activity ops they hold, through
+//! the `Action_author_entry_type_idx` index, so unlike filtering the author's
+//! agent activity they don't have to walk the whole chain. The entries
+//! themselves are then fetched from their own authorities.
+
+use holo_hash::*;
+use holochain_sqlite::rusqlite::*;
+use holochain_state::{
+    prelude::*,
+    query::{row_blob_and_hash_to_action, QueryData},
+};
+use std::{fmt::Debug, sync::Arc};
+
+#[derive(Debug, Clone)]
+pub struct GetEntriesByAuthorQuery {
+    author: Arc<AgentPubKey>,
+    entry_type: Arc<EntryType>,
+    /// The entry type as it is stored in the `Action.entry_type` column.
+    entry_type_sql: String,
+    after_seq: Option<u32>,
+    limit: u32,
+}
+
+impl GetEntriesByAuthorQuery {
+    /// Query for any entry whose action has not been rejected, including
+    /// entries which are still pending validation.
+    pub fn not_rejected(author: AgentPubKey, entry_type: EntryType, page: EntriesPage) -> Self {
+        Self {
+            author: Arc::new(author),
+            entry_type_sql: entry_type.to_string(),
+            entry_type: Arc::new(entry_type),
+            after_seq: page.after_seq,
+            limit: page.limit.min(EntriesPage::MAX_LIMIT),
+        }
+    }
+}
+
+impl Query for GetEntriesByAuthorQuery {
+    type Item = Judged<SignedActionHashed>;
+    type State = Vec<SignedActionHashed>;
+    type Output = Vec<Record>;
+
+    fn query(&self) -> String {
+        "
+            SELECT Action.blob, Action.hash, DhtOp.validation_status
+            FROM Action
+            JOIN DhtOp ON DhtOp.action_hash = Action.hash
+            WHERE Action.author = :author
+            AND Action.entry_type = :entry_type
+            AND (:after_seq IS NULL OR Action.seq > :after_seq)
+            AND Action.private_entry = 0
+            AND DhtOp.type IN (:store_entry, :register_activity)
+            ORDER BY Action.seq
+            -- An action can be joined once for each of the two op types.
+            LIMIT :limit * 2
+        "
+        .to_string()
+    }
+
+    fn params(&self) -> Vec<holochain_state::query::Params> {
+        (named_params! {
+            ":author": self.author,
+            ":entry_type": self.entry_type_sql,
+            ":after_seq": self.after_seq,
+            ":store_entry": ChainOpType::StoreEntry,
+            ":register_activity": ChainOpType::RegisterAgentActivity,
+            ":limit": self.limit,
+        })
+        .to_vec()
+    }
+
+    fn init_fold(&self) -> StateQueryResult<Self::State> {
+        Ok(Vec::new())
+    }
+
+    fn as_filter(&self) -> Box<dyn Fn(&QueryData<Self>) -> bool> {
+        let author = self.author.clone();
+        let entry_type = self.entry_type.clone();
+        let after_seq = self.after_seq;
+        Box::new(move |action: &QueryData<Self>| {
+            let action = action.action();
+            *action.author() == *author
+                && action.entry_type() == Some(&*entry_type)
+                && after_seq.map_or(true, |after_seq| action.action_seq() > after_seq)
+        })
+    }
+
+    fn fold(&self, mut state: Self::State, item: Self::Item) -> StateQueryResult<Self::State> {
+        match item.status {
+            Some(ValidationStatus::Valid) | None => state.push(item.data),
+            Some(ValidationStatus::Rejected) | Some(ValidationStatus::Abandoned) => (),
+        }
+        Ok(state)
+    }
+
+    fn render<S>(&self, mut state: Self::State, stores: S) -> StateQueryResult<Self::Output>
+    where
+        S: Store,
+    {
+        // An action may be held by more than one store.
+        state.sort_unstable_by(|a, b| {
+            (a.action().action_seq(), a.as_hash()).cmp(&(b.action().action_seq(), b.as_hash()))
+        });
+        state.dedup_by(|a, b| a.as_hash() == b.as_hash());
+
+        let mut records = Vec::with_capacity(state.len());
+        for action in state {
+            // Only public entries are listed. Unlisted entries can only be
+            // found by their own hash.
+            if !action
+                .action()
+                .entry_type()
+                .map_or(false, |et| et.visibility().is_public())
+            {
+                continue;
+            }
+            let entry = match action.action().entry_hash() {
+                Some(entry_hash) => stores.get_entry(entry_hash)?,
+                None => None,
+            };
+            // Without its entry the record can't be returned.
+            if let Some(entry) = entry {
+                records.push(Record::new(action, Some(entry)));
+            }
+            if records.len() as u32 >= self.limit {
+                break;
+            }
+        }
+        Ok(records)
+    }
+
+    fn as_map(&self) -> Arc<dyn Fn(&Row) -> StateQueryResult<Self::Item>> {
+        let f = row_blob_and_hash_to_action("blob", "hash");
+        Arc::new(move |row| {
+            let validation_status: Option<ValidationStatus> = row.get("validation_status")?;
+            Ok(Judged::raw(f(row)?, validation_status))
+        })
+    }
+}
+
+/// Query for the actions which created the public entries of one type by an
+/// author, as an agent activity authority serves them.
+///
+/// Only actions whose register agent activity ops have been validated and
+/// integrated are listed.
+#[derive(Debug, Clone)]
+pub struct GetEntryActionsByAuthorQuery(GetEntriesByAuthorQuery);
+
+impl GetEntryActionsByAuthorQuery {
+    /// Query for a page of the actions.
+    pub fn new(author: AgentPubKey, entry_type: EntryType, page: EntriesPage) -> Self {
+        Self(GetEntriesByAuthorQuery::not_rejected(
+            author, entry_type, page,
+        ))
+    }
+}
+
+impl Query for GetEntryActionsByAuthorQuery {
+    type Item = Judged<SignedActionHashed>;
+    type State = Vec<SignedActionHashed>;
+    type Output = Vec<SignedActionHashed>;
+
+    fn query(&self) -> String {
+        "
+            SELECT Action.blob, Action.hash, DhtOp.validation_status
+            FROM Action
+            JOIN DhtOp ON DhtOp.action_hash = Action.hash
+            WHERE Action.author = :author
+            AND Action.entry_type = :entry_type
+            AND (:after_seq IS NULL OR Action.seq > :after_seq)
+            AND Action.private_entry = 0
+            AND DhtOp.type = :register_activity
+            AND DhtOp.validation_status = :status
+            AND DhtOp.when_integrated IS NOT NULL
+            ORDER BY Action.seq
+            LIMIT :limit
+        "
+        .to_string()
+    }
+
+    fn params(&self) -> Vec<holochain_state::query::Params> {
+        (named_params! {
+            ":author": self.0.author,
+            ":entry_type": self.0.entry_type_sql,
+            ":after_seq": self.0.after_seq,
+            ":register_activity": ChainOpType::RegisterAgentActivity,
+            ":status": ValidationStatus::Valid,
+            ":limit": self.0.limit,
+        })
+        .to_vec()
+    }
+
+    fn init_fold(&self) -> StateQueryResult<Self::State> {
+        Ok(Vec::new())
+    }
+
+    fn as_filter(&self) -> Box<dyn Fn(&QueryData<Self>) -> bool> {
+        let author = self.0.author.clone();
+        let entry_type = self.0.entry_type.clone();
+        let after_seq = self.0.after_seq;
+        Box::new(move |action: &QueryData<Self>| {
+            let action = action.action();
+            *action.author() == *author
+                && action.entry_type() == Some(&*entry_type)
+                && after_seq.map_or(true, |after_seq| action.action_seq() > after_seq)
+        })
+    }
+
+    fn fold(&self, mut state: Self::State, item: Self::Item) -> StateQueryResult<Self::State> {
+        if let Some(ValidationStatus::Valid) = item.status {
+            state.push(item.data);
+        }
+        Ok(state)
+    }
+
+    fn render<S>(&self, mut state: Self::State, _stores: S) -> StateQueryResult<Self::Output>
+    where
+        S: Store,
+    {
+        state.sort_unstable_by(|a, b| {
+            (a.action().action_seq(), a.as_hash()).cmp(&(b.action().action_seq(), b.as_hash()))
+        });
+        state.dedup_by(|a, b| a.as_hash() == b.as_hash());
+        // Unlisted entries can only be found by their own hash.
+        state.retain(|action| {
+            action
+                .action()
+                .entry_type()
+                .map_or(false, |et| et.visibility().is_public())
+        });
+        state.truncate(self.0.limit as usize);
+        Ok(state)
+    }
+
+    fn as_map(&self) -> Arc<dyn Fn(&Row) -> StateQueryResult<Self::Item>> {
+        let f = row_blob_and_hash_to_action("blob", "hash");
+        Arc::new(move |row| {
+            let validation_status: Option<ValidationStatus> = row.get("validation_status")?;
+            Ok(Judged::raw(f(row)?, validation_status))
+        })
+    }
+}
@@ -78,7 +78,7 @@ impl Query for GetEntryOpsQuery {
                     .data
                     .action
                     .entry_type()
-                    .filter(|et| *et.visibility() == EntryVisibility::Public)
+                    .filter(|et| et.visibility().is_published())
                     .is_some()
                 {
                     let status = dht_op.validation_status();
@@ -103,6 +103,15 @@ impl Query for GetEntryOpsQuery {
                     .push(Judged::raw(dht_op.data.action.try_into()?, status));
             }
             ChainOpType::RegisterUpdatedContent => {
+                // Updates to unlisted entries can only be found by their own hash.
+                if dht_op
+                    .data
+                    .action
+                    .entry_type()
+                    .map_or(false, |et| et.visibility().is_unlisted())
+                {
+                    return Ok(state);
+                }
                 let status = dht_op.validation_status();
                 let action = dht_op.data.action;
                 state.ops.updates.push(Judged::raw(
@@ -1,9 +1,12 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use holo_hash::AnyLinkableHash;
 use holochain_sqlite::rusqlite::named_params;
 use holochain_sqlite::rusqlite::Row;
+use holochain_sqlite::rusqlite::Transaction;
 use holochain_state::prelude::*;
+use holochain_state::query::link::LinkTagFieldsSql;
 use holochain_state::query::StateQueryError;
 use holochain_types::sql::ToSqlStatement;
 
@@ -14,14 +17,25 @@ pub struct GetLinksOpsQuery {
     base: Arc<AnyLinkableHash>,
     type_query: LinkTypeFilter,
     tag: Option<Arc<LinkTag>>,
+    tag_start: Option<Vec<u8>>,
+    tag_end: Option<Vec<u8>>,
+    tag_fields: LinkTagFieldsSql,
+    since: Option<Timestamp>,
 }
 
 impl GetLinksOpsQuery {
     pub fn new(key: WireLinkKey) -> Self {
+        let (tag_start, tag_end) = key
+            .tag_range
+            .map_or((None, None), |(start, end)| (Some(start.0), Some(end.0)));
         Self {
             base: Arc::new(key.base),
             type_query: key.type_query,
             tag: key.tag.map(Arc::new),
+            tag_start,
+            tag_end,
+            tag_fields: LinkTagFieldsSql::new(&key.tag_fields),
+            since: key.since,
         }
     }
     pub fn tag_to_hex(tag: &LinkTag) -> String {
@@ -69,15 +83,32 @@ impl Query for GetLinksOpsQuery {
                 common_query, tag
             );
         }
+        if self.tag_start.is_some() {
+            common_query = format!(
+                "{} AND Action.tag >= :tag_start AND Action.tag < :tag_end",
+                common_query
+            );
+        } else {
+            common_query = format!(
+                "{} AND :tag_start IS NULL AND :tag_end IS NULL",
+                common_query
+            );
+        }
         common_query = format!(
             "
             {}
             {}
+            {}
             ",
             common_query,
+            self.tag_fields.conditions(),
             self.type_query.to_sql_statement(),
         );
-        let create_query = format!("{}{}", create, common_query);
+        // The watermark is compared with when the ops were integrated here, so
+        // ops authored earlier which only arrived since are included. Deletes
+        // since the watermark can be of links created before it.
+        let since = "AND (:since IS NULL OR DhtOp.when_integrated >= :since)";
+        let create_query = format!("{}{}{}", create, common_query, since);
         let sub_create_query = format!("{}{}", sub_create, common_query);
         let delete_query = format!(
             "
@@ -90,19 +121,25 @@ impl Query for GetLinksOpsQuery {
             DhtOp.when_integrated IS NOT NULL
             AND
             Action.create_link_hash IN ({})
+            {}
             ",
-            sub_create_query
+            sub_create_query, since
         );
         format!("{} UNION ALL {}", create_query, delete_query)
     }
 
     fn params(&self) -> Vec<Params> {
-        named_params! {
+        let mut params = named_params! {
             ":create": ChainOpType::RegisterAddLink,
             ":delete": ChainOpType::RegisterRemoveLink,
             ":base_hash": self.base,
+            ":tag_start": self.tag_start,
+            ":tag_end": self.tag_end,
+            ":since": self.since,
         }
-        .to_vec()
+        .to_vec();
+        params.extend(self.tag_fields.params());
+        params
     }
 
     fn as_map(&self) -> Arc<dyn Fn(&Row) -> StateQueryResult<Self::Item>> {
@@ -160,3 +197,47 @@ impl Query for GetLinksOpsQuery {
         Ok(state)
     }
 }
+
+/// Leave out the links whose target is an unlisted entry, or an action which
+/// creates or updates one, so unlisted entries can't be found by following
+/// links to them. Only targets this authority holds can be checked.
+pub(crate) fn retain_listed_targets(
+    txn: &Transaction,
+    ops: &mut WireLinkOps,
+) -> StateQueryResult<()> {
+    let mut stmt = txn.prepare_cached(
+        "
+        SELECT Action.blob AS action_blob FROM Action
+        WHERE Action.hash = :target OR Action.entry_hash = :target
+        ",
+    )?;
+    let mut unlisted = HashMap::new();
+    let mut is_unlisted = |target: &AnyLinkableHash| -> StateQueryResult<bool> {
+        if let Some(unlisted) = unlisted.get(target) {
+            return Ok(*unlisted);
+        }
+        let mut rows = stmt.query(named_params! { ":target": target })?;
+        let mut target_unlisted = false;
+        while let Some(row) = rows.next()? {
+            let action = from_blob::<SignedAction>(row.get("action_blob")?)?;
+            if action
+                .action()
+                .entry_type()
+                .map_or(false, |et| et.visibility().is_unlisted())
+            {
+                target_unlisted = true;
+                break;
+            }
+        }
+        unlisted.insert(target.clone(), target_unlisted);
+        Ok(target_unlisted)
+    };
+    let mut creates = Vec::with_capacity(ops.creates.len());
+    for create in ops.creates.drain(..) {
+        if !is_unlisted(&create.target_address)? {
+            creates.push(create);
+        }
+    }
+    ops.creates = creates;
+    Ok(())
+}
@@ -112,7 +112,8 @@ impl Query for GetRecordOpsQuery {
                 .entry_data()
                 .map(|(hash, et)| (hash, et.visibility()))
         });
-        if let Some((entry_hash, EntryVisibility::Public)) = entry_hash {
+        if let Some((entry_hash, EntryVisibility::Public | EntryVisibility::Unlisted)) = entry_hash
+        {
             let entry = stores.get_entry(entry_hash)?;
             state.entry = entry;
         }
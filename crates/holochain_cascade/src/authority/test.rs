@@ -1,6 +1,7 @@
 use super::*;
 use crate::authority::handle_get_agent_activity;
 use crate::test_utils::*;
+use ::fixt::prelude::*;
 use holochain_p2p::actor;
 use holochain_p2p::event::GetRequest;
 use holochain_state::prelude::test_dht_db;
@@ -62,6 +63,42 @@ async fn get_entry() {
     assert_eq!(result, expected);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn get_entry_hides_unlisted_updates() {
+    holochain_trace::test_run();
+    let db = test_dht_db();
+
+    let td = EntryTestData::create();
+    fill_db(&db.to_db(), td.store_entry_op.clone()).await;
+
+    let ChainOp::RegisterUpdatedContent(signature, mut update, entry) =
+        td.update_content_op.as_content().clone()
+    else {
+        unreachable!()
+    };
+    update.entry_type = EntryType::App(AppEntryDef::new(
+        0.into(),
+        0.into(),
+        EntryVisibility::Unlisted,
+    ));
+    fill_db(
+        &db.to_db(),
+        ChainOpHashed::from_content_sync(ChainOp::RegisterUpdatedContent(signature, update, entry)),
+    )
+    .await;
+
+    let result = handle_get_entry(db.to_db().into(), td.hash.clone(), options())
+        .await
+        .unwrap();
+    let expected = WireEntryOps {
+        creates: vec![td.wire_create.clone()],
+        deletes: vec![],
+        updates: vec![],
+        entry: Some(td.entry.clone()),
+    };
+    assert_eq!(result, expected);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn get_record() {
     holochain_trace::test_run();
@@ -188,6 +225,75 @@ async fn get_links() {
     assert_eq!(result, expected);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn get_links_hides_links_to_unlisted_entries() {
+    holochain_trace::test_run();
+    let db = test_dht_db();
+
+    let td = EntryTestData::create();
+
+    // An unlisted entry, created by an action held by this authority.
+    let ChainOp::StoreEntry(signature, NewEntryAction::Create(mut create), entry) =
+        td.store_entry_op.as_content().clone()
+    else {
+        unreachable!()
+    };
+    create.entry_type = EntryType::App(AppEntryDef::new(
+        0.into(),
+        0.into(),
+        EntryVisibility::Unlisted,
+    ));
+    let unlisted_action_hash = ActionHash::with_data_sync(&Action::Create(create.clone()));
+    let unlisted_entry_hash = create.entry_hash.clone();
+    fill_db(
+        &db.to_db(),
+        ChainOpHashed::from_content_sync(ChainOp::StoreEntry(
+            signature,
+            NewEntryAction::Create(create),
+            entry,
+        )),
+    )
+    .await;
+
+    let ChainOp::RegisterAddLink(signature, create_link) = td.create_link_op.as_content().clone()
+    else {
+        unreachable!()
+    };
+    let targets: Vec<AnyLinkableHash> = vec![
+        unlisted_entry_hash.into(),
+        unlisted_action_hash.into(),
+        fixt!(EntryHash).into(),
+    ];
+    for target in targets.iter() {
+        let mut create_link = create_link.clone();
+        create_link.target_address = target.clone();
+        fill_db(
+            &db.to_db(),
+            ChainOpHashed::from_content_sync(ChainOp::RegisterAddLink(
+                signature.clone(),
+                create_link,
+            )),
+        )
+        .await;
+    }
+
+    let result = handle_get_links(
+        db.to_db().into(),
+        td.link_key.clone(),
+        (&actor::GetLinksOptions::default()).into(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        result
+            .creates
+            .iter()
+            .map(|c| c.target_address.clone())
+            .collect::<Vec<_>>(),
+        vec![targets[2].clone()]
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn get_agent_activity() {
     holochain_trace::test_run();
@@ -23,10 +23,13 @@
 //!
 #![warn(missing_docs)]
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Instant;
 
+use authority::get_agent_activity_query::action_by_seq::GetActionBySeqQuery;
+use authority::get_entries_by_author_query::GetEntriesByAuthorQuery;
 use error::CascadeResult;
 use holo_hash::ActionHash;
 use holo_hash::AgentPubKey;
@@ -43,9 +46,11 @@ use holochain_state::mutations::insert_action;
 use holochain_state::mutations::insert_entry;
 use holochain_state::mutations::insert_op_lite;
 use holochain_state::mutations::set_validation_status;
+use holochain_state::negative_cache::NegativeCache;
 use holochain_state::prelude::*;
 use holochain_state::query::entry_details::GetEntryDetailsQuery;
 use holochain_state::query::link::{GetLinksFilter, GetLinksQuery};
+use holochain_state::query::link_delta::GetLinksDeltaQuery;
 use holochain_state::query::link_details::GetLinkDetailsQuery;
 use holochain_state::query::live_entry::GetLiveEntryQuery;
 use holochain_state::query::live_record::GetLiveRecordQuery;
@@ -61,8 +66,6 @@ use tracing::*;
 use kitsune_p2p::dependencies::kitsune_p2p_types::box_fut_plain;
 #[cfg(feature = "test_utils")]
 use kitsune_p2p::dependencies::kitsune_p2p_types::tx2::tx2_utils::ShareOpen;
-#[cfg(feature = "test_utils")]
-use std::collections::HashMap;
 
 pub mod authority;
 pub mod error;
@@ -90,6 +93,12 @@ macro_rules! some_or_return {
     };
 }
 
+/// How far before the watermark of a links delta the network is asked for link
+/// ops from, so ops integrated by authorities whose clocks are behind this
+/// node's aren't missed. Ops which are fetched again keep the time they were
+/// first cached at, so they aren't returned by a later delta.
+pub const LINKS_DELTA_OVERLAP: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
 /// Marks whether data came from a local store or another node on the network
 #[derive(Debug, Clone)]
 pub enum CascadeSource {
@@ -110,6 +119,7 @@ pub struct CascadeImpl {
     scratch: Option<SyncScratch>,
     network: Option<GenericNetwork>,
     private_data: Option<Arc<AgentPubKey>>,
+    negative_cache: Option<NegativeCache>,
     duration_metric: &'static CascadeDurationMetric,
 }
 
@@ -154,6 +164,15 @@ impl CascadeImpl {
         }
     }
 
+    /// Skip the network for hashes which authorities recently reported
+    /// they hold no data for, and record new misses in this cache.
+    pub fn with_negative_cache(self, negative_cache: NegativeCache) -> Self {
+        Self {
+            negative_cache: Some(negative_cache),
+            ..self
+        }
+    }
+
     /// Add the network and cache to the cascade.
     pub fn with_network(
         self,
@@ -165,6 +184,7 @@ impl CascadeImpl {
             dht: self.dht,
             scratch: self.scratch,
             private_data: self.private_data,
+            negative_cache: self.negative_cache,
             cache: Some(cache_db),
             network: Some(network),
             duration_metric: create_cascade_duration_metric(),
@@ -180,6 +200,7 @@ impl CascadeImpl {
             cache: None,
             scratch: None,
             private_data: None,
+            negative_cache: None,
             duration_metric: create_cascade_duration_metric(),
         }
     }
@@ -198,6 +219,7 @@ impl CascadeImpl {
             dht,
             cache,
             scratch,
+            negative_cache,
         } = workspace.stores();
         let private_data = workspace.author();
         CascadeImpl {
@@ -206,6 +228,7 @@ impl CascadeImpl {
             cache: Some(cache),
             private_data,
             scratch,
+            negative_cache,
             network: Some(network),
             duration_metric: create_cascade_duration_metric(),
         }
@@ -218,6 +241,7 @@ impl CascadeImpl {
             dht,
             cache,
             scratch,
+            negative_cache,
         } = stores;
         Self {
             authored: Some(authored),
@@ -226,6 +250,7 @@ impl CascadeImpl {
             scratch,
             network: None,
             private_data: author,
+            negative_cache,
             duration_metric: create_cascade_duration_metric(),
         }
     }
@@ -258,6 +283,14 @@ pub trait Cascade {
         hash: AnyDhtHash,
         mut options: NetworkGetOptions,
     ) -> CascadeResult<Option<(Record, CascadeSource)>>;
+
+    /// Retrieve the part of an author's chain selected by the filter, either
+    /// locally or from the author's agent activity authorities.
+    async fn retrieve_chain(
+        &self,
+        author: AgentPubKey,
+        filter: ChainFilter,
+    ) -> CascadeResult<MustGetAgentActivityResponse>;
 }
 
 #[async_trait::async_trait]
@@ -364,11 +397,24 @@ impl Cascade for CascadeImpl {
             .await?;
         Ok(result.map(|r| (r, CascadeSource::Network)))
     }
+
+    async fn retrieve_chain(
+        &self,
+        author: AgentPubKey,
+        filter: ChainFilter,
+    ) -> CascadeResult<MustGetAgentActivityResponse> {
+        self.must_get_agent_activity(author, filter).await
+    }
 }
 
 impl CascadeImpl {
     #[allow(clippy::result_large_err)] // TODO - investigate this lint
-    fn insert_rendered_op(txn: &mut Transaction, op: &RenderedOp) -> CascadeResult<()> {
+    fn insert_rendered_op(
+        txn: &mut Transaction,
+        op: &RenderedOp,
+        when_integrated: Timestamp,
+        keep_integrated: bool,
+    ) -> CascadeResult<()> {
         let RenderedOp {
             op_light,
             op_hash,
@@ -384,18 +430,30 @@ impl CascadeImpl {
         }
         // We set the integrated to for the cache so it can match the
         // same query as the vault. This can also be used for garbage collection.
-        set_when_integrated(txn, op_hash, Timestamp::now())?;
+        if keep_integrated {
+            set_when_integrated_if_unset(txn, op_hash, when_integrated)?;
+        } else {
+            set_when_integrated(txn, op_hash, when_integrated)?;
+        }
         Ok(())
     }
 
+    /// Insert rendered ops into the cache as integrated at `when_integrated`.
+    /// If `keep_integrated` is set, ops which are already cached keep the time
+    /// they were first cached at.
     #[allow(clippy::result_large_err)] // TODO - investigate this lint
-    fn insert_rendered_ops(txn: &mut Transaction, ops: &RenderedOps) -> CascadeResult<()> {
+    fn insert_rendered_ops(
+        txn: &mut Transaction,
+        ops: &RenderedOps,
+        when_integrated: Timestamp,
+        keep_integrated: bool,
+    ) -> CascadeResult<()> {
         let RenderedOps { ops, entry } = ops;
         if let Some(entry) = entry {
             insert_entry(txn, entry.as_hash(), entry.as_content())?;
         }
         for op in ops {
-            Self::insert_rendered_op(txn, op)?;
+            Self::insert_rendered_op(txn, op, when_integrated, keep_integrated)?;
         }
         Ok(())
     }
@@ -425,6 +483,28 @@ impl CascadeImpl {
         Ok(())
     }
 
+    /// Insert actions which an agent activity authority holds as valid into the Cache.
+    #[allow(clippy::result_large_err)] // TODO - investigate this lint
+    fn insert_valid_actions(
+        txn: &mut Transaction,
+        actions: Vec<SignedActionHashed>,
+    ) -> CascadeResult<()> {
+        for action in actions {
+            let SignedHashed {
+                hashed: HoloHashed { content, .. },
+                signature,
+            } = action;
+            let op =
+                DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(signature, content));
+            insert_op(txn, &op)?;
+            set_validation_status(txn, op.as_hash(), ValidationStatus::Valid)?;
+            // We set the integrated to for the cache so it can match the
+            // same query as the vault. This can also be used for garbage collection.
+            set_when_integrated(txn, op.as_hash(), Timestamp::now())?;
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(skip_all)]
     async fn merge_ops_into_cache(&self, responses: Vec<WireOps>) -> CascadeResult<()> {
         let cache = some_or_return!(self.cache.as_ref());
@@ -432,7 +512,7 @@ impl CascadeImpl {
             .write_async(|txn| {
                 for response in responses {
                     let ops = response.render()?;
-                    Self::insert_rendered_ops(txn, &ops)?;
+                    Self::insert_rendered_ops(txn, &ops, Timestamp::now(), false)?;
                 }
                 CascadeResult::Ok(())
             })
@@ -445,13 +525,15 @@ impl CascadeImpl {
         &self,
         responses: Vec<WireLinkOps>,
         key: WireLinkKey,
+        when_integrated: Timestamp,
+        keep_integrated: bool,
     ) -> CascadeResult<()> {
         let cache = some_or_return!(self.cache.as_ref());
         cache
             .write_async(move |txn| {
                 for response in responses {
                     let ops = response.render(&key)?;
-                    Self::insert_rendered_ops(txn, &ops)?;
+                    Self::insert_rendered_ops(txn, &ops, when_integrated, keep_integrated)?;
                 }
                 CascadeResult::Ok(())
             })
@@ -511,6 +593,9 @@ impl CascadeImpl {
     }
 
     /// Fetch a Record from the network, caching and returning the results
+    ///
+    /// If the authorities recently reported they hold no data for the hash,
+    /// the network isn't asked again until that miss expires.
     #[instrument(skip(self, options))]
     pub async fn fetch_record(
         &self,
@@ -518,11 +603,24 @@ impl CascadeImpl {
         options: NetworkGetOptions,
     ) -> CascadeResult<()> {
         let network = some_or_return!(self.network.as_ref());
+        if let Some(negative_cache) = &self.negative_cache {
+            if negative_cache.contains(&hash) {
+                return Ok(());
+            }
+        }
         let results = network
-            .get(hash, options.clone())
+            .get(hash.clone(), options.clone())
             .instrument(debug_span!("fetch_record::network_get"))
             .await?;
 
+        // No responses at all isn't an authoritative miss,
+        // so only record one if every authority had nothing.
+        if let Some(negative_cache) = &self.negative_cache {
+            if !results.is_empty() && results.iter().all(WireOps::is_empty) {
+                negative_cache.insert(hash);
+            }
+        }
+
         self.merge_ops_into_cache(results).await?;
         Ok(())
     }
@@ -532,11 +630,13 @@ impl CascadeImpl {
         &self,
         link_key: WireLinkKey,
         options: GetLinksOptions,
+        when_integrated: Timestamp,
+        keep_integrated: bool,
     ) -> CascadeResult<()> {
         let network = some_or_return!(self.network.as_ref());
         let results = network.get_links(link_key.clone(), options).await?;
 
-        self.merge_link_ops_into_cache(results, link_key.clone())
+        self.merge_link_ops_into_cache(results, link_key.clone(), when_integrated, keep_integrated)
             .await?;
         Ok(())
     }
@@ -568,6 +668,60 @@ impl CascadeImpl {
         self.add_activity_into_cache(results).await
     }
 
+    #[instrument(skip(self))]
+    /// Fetch the actions at one position of an author's chain from the network.
+    async fn fetch_action_by_seq(&self, author: AgentPubKey, action_seq: u32) -> CascadeResult<()> {
+        let network = some_or_return!(self.network.as_ref());
+        let cache = some_or_return!(self.cache.as_ref());
+        let results = network.get_action_by_seq(author, action_seq).await?;
+
+        cache
+            .write_async(move |txn| {
+                for ActionBySeqResponse { actions } in results {
+                    Self::insert_valid_actions(txn, actions)?;
+                }
+                CascadeResult::Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    /// Fetch a page of the entries of one type created by an author from the network.
+    ///
+    /// The actions are listed by the author's agent activity authorities, then each
+    /// entry which isn't already held is fetched from its own authorities.
+    async fn fetch_entries_by_author(
+        &self,
+        author: AgentPubKey,
+        entry_type: EntryType,
+        page: EntriesPage,
+    ) -> CascadeResult<()> {
+        let network = some_or_return!(self.network.as_ref());
+        let cache = some_or_return!(self.cache.as_ref());
+        let results = network
+            .get_entries_by_author(author, entry_type, page)
+            .await?;
+        let actions: Vec<SignedActionHashed> = results
+            .into_iter()
+            .flat_map(|EntriesByAuthorResponse { actions }| actions)
+            .collect();
+        let entry_hashes: HashSet<EntryHash> = actions
+            .iter()
+            .filter_map(|action| action.action().entry_hash().cloned())
+            .collect();
+
+        cache
+            .write_async(move |txn| Self::insert_valid_actions(txn, actions))
+            .await?;
+        self.get_concurrent(
+            entry_hashes.into_iter().map(Into::into),
+            GetOptions::cache_then_network(),
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Get transactions for available databases.
     async fn get_txn_guards(&self) -> CascadeResult<Vec<PTxnGuard>> {
         let mut conns: Vec<_> = Vec::with_capacity(3);
@@ -674,33 +828,59 @@ impl CascadeImpl {
         Ok(None)
     }
 
-    /// Get Entry data along with all CRUD actions associated with it.
+    /// Run a query about a hash, reading from where the options' [`ReadSource`] says.
     ///
-    /// Also returns Rejected actions, which may affect the interpreted validity status of this Entry.
-    #[instrument(skip(self, options))]
-    pub async fn get_entry_details(
+    /// When the network is read from, the record is only fetched if this agent
+    /// is neither authoring the hash nor an authority for it. Otherwise the
+    /// local databases already hold the latest data.
+    async fn read_with_source<Q, O>(
         &self,
-        entry_hash: EntryHash,
+        hash: AnyDhtHash,
+        query: Q,
         options: GetOptions,
-    ) -> CascadeResult<Option<EntryDetails>> {
-        let query: GetEntryDetailsQuery = self.construct_query_with_data_access(entry_hash.clone());
-        if let GetStrategy::Local = options.strategy {
+    ) -> CascadeResult<Option<O>>
+    where
+        Q: Query<Item = Judged<SignedActionHashed>, Output = Option<O>> + Clone + Send + 'static,
+        O: Send + 'static,
+    {
+        match options.read_source() {
             // Only return what is in the database.
-            return self.cascading(query.clone()).await;
+            ReadSource::LocalOnly => return self.cascading(query).await,
+            // Only go to the network if the database doesn't hold the data.
+            ReadSource::CacheThenNetwork => {
+                if let Some(found) = self.cascading(query.clone()).await? {
+                    return Ok(Some(found));
+                }
+            }
+            ReadSource::NetworkOnly => (),
         }
 
         // If we are not in the process of authoring this hash or its
         // authority we need a network call.
-        let authoring = self.am_i_authoring(&entry_hash.clone().into())?;
-        let authority = self.am_i_an_authority(entry_hash.clone().into()).await?;
+        let authoring = self.am_i_authoring(&hash)?;
+        let authority = self.am_i_an_authority(hash.clone().into()).await?;
         if !(authoring || authority) {
-            self.fetch_record(entry_hash.into(), options.into()).await?;
+            self.fetch_record(hash, options.into()).await?;
         }
 
         // Check if we have the data now after the network call.
         self.cascading(query).await
     }
 
+    /// Get Entry data along with all CRUD actions associated with it.
+    ///
+    /// Also returns Rejected actions, which may affect the interpreted validity status of this Entry.
+    #[instrument(skip(self, options))]
+    pub async fn get_entry_details(
+        &self,
+        entry_hash: EntryHash,
+        options: GetOptions,
+    ) -> CascadeResult<Option<EntryDetails>> {
+        let query: GetEntryDetailsQuery = self.construct_query_with_data_access(entry_hash.clone());
+        self.read_with_source(entry_hash.into(), query, options)
+            .await
+    }
+
     /// Get the specified Record along with all Updates and Deletes associated with it.
     ///
     /// Can return a Rejected Record.
@@ -717,22 +897,8 @@ impl CascadeImpl {
         // Is this bad because we will not go back to the network until our
         // cache is cleared. Could someone create an attack based on this fact?
 
-        if let GetStrategy::Local = options.strategy {
-            // Only return what is in the database.
-            return self.cascading(query.clone()).await;
-        }
-
-        // If we are not in the process of authoring this hash or its
-        // authority we need a network call.
-        let authoring = self.am_i_authoring(&action_hash.clone().into())?;
-        let authority = self.am_i_an_authority(action_hash.clone().into()).await?;
-        if !(authoring || authority) {
-            self.fetch_record(action_hash.into(), options.into())
-                .await?;
-        }
-
-        // Check if we have the data now after the network call.
-        self.cascading(query).await
+        self.read_with_source(action_hash.into(), query, options)
+            .await
     }
 
     #[instrument(skip(self, options))]
@@ -751,22 +917,8 @@ impl CascadeImpl {
         // Is this bad because we will not go back to the network until our
         // cache is cleared. Could someone create an attack based on this fact?
 
-        if let GetStrategy::Local = options.strategy {
-            // Only return what is in the database.
-            return self.cascading(query.clone()).await;
-        }
-
-        // If we are not in the process of authoring this hash or its
-        // authority we need a network call.
-        let authoring = self.am_i_authoring(&action_hash.clone().into())?;
-        let authority = self.am_i_an_authority(action_hash.clone().into()).await?;
-        if !(authoring || authority) {
-            self.fetch_record(action_hash.into(), options.into())
-                .await?;
-        }
-
-        // Check if we have the data now after the network call.
-        self.cascading(query).await
+        self.read_with_source(action_hash.into(), query, options)
+            .await
     }
 
     #[instrument(skip(self, options))]
@@ -779,21 +931,8 @@ impl CascadeImpl {
     ) -> CascadeResult<Option<Record>> {
         let query: GetLiveEntryQuery = self.construct_query_with_data_access(entry_hash.clone());
 
-        if let GetStrategy::Local = options.strategy {
-            // Only return what is in the database.
-            return self.cascading(query.clone()).await;
-        }
-
-        // If we are not in the process of authoring this hash or its
-        // authority we need a network call.
-        let authoring = self.am_i_authoring(&entry_hash.clone().into())?;
-        let authority = self.am_i_an_authority(entry_hash.clone().into()).await?;
-        if !(authoring || authority) {
-            self.fetch_record(entry_hash.into(), options.into()).await?;
-        }
-
-        // Check if we have the data now after the network call.
-        self.cascading(query).await
+        self.read_with_source(entry_hash.into(), query, options)
+            .await
     }
 
     /// Perform a concurrent `get` on multiple hashes simultaneously, returning
@@ -824,34 +963,169 @@ impl CascadeImpl {
     /// and returns what is in the cache.
     /// This gives you the latest possible picture of the current dht state.
     /// Data from your zome call is also added to the cache.
+    ///
+    /// With [`WarrantedAuthors::Exclude`] no record is returned if its author
+    /// has a valid warrant against them. A get by entry hash doesn't fall back
+    /// to another author's record for the same entry.
     pub async fn dht_get(
         &self,
         hash: AnyDhtHash,
         options: GetOptions,
     ) -> CascadeResult<Option<Record>> {
-        match hash.into_primitive() {
-            AnyDhtHashPrimitive::Entry(hash) => self.dht_get_entry(hash, options).await,
-            AnyDhtHashPrimitive::Action(hash) => self.dht_get_action(hash, options).await,
+        let warranted_authors = options.warranted_authors.unwrap_or_default();
+        let record = match hash.into_primitive() {
+            AnyDhtHashPrimitive::Entry(hash) => self.dht_get_entry(hash, options).await?,
+            AnyDhtHashPrimitive::Action(hash) => self.dht_get_action(hash, options).await?,
+        };
+        match record {
+            Some(record)
+                if warranted_authors == WarrantedAuthors::Exclude
+                    && !self
+                        .get_warrants_against(record.action().author().clone())
+                        .await?
+                        .is_empty() =>
+            {
+                Ok(None)
+            }
+            record => Ok(record),
         }
     }
 
     /// Get either [`EntryDetails`] or [`RecordDetails`], depending on the hash provided
+    ///
+    /// With [`WarrantedAuthors::Annotate`] the warrants against the author of a
+    /// record are added to its [`RecordDetails`]. With [`WarrantedAuthors::Exclude`]
+    /// records by warranted authors aren't returned, and the actions of warranted
+    /// authors are left out of [`EntryDetails`].
     #[instrument(skip(self))]
     pub async fn get_details(
         &self,
         hash: AnyDhtHash,
         options: GetOptions,
     ) -> CascadeResult<Option<Details>> {
+        let warranted_authors = options.warranted_authors.unwrap_or_default();
         match hash.into_primitive() {
-            AnyDhtHashPrimitive::Entry(hash) => Ok(self
-                .get_entry_details(hash, options)
-                .await?
-                .map(Details::Entry)),
-            AnyDhtHashPrimitive::Action(hash) => Ok(self
-                .get_record_details(hash, options)
-                .await?
-                .map(Details::Record)),
+            AnyDhtHashPrimitive::Entry(hash) => {
+                let mut details = self.get_entry_details(hash, options).await?;
+                if let (WarrantedAuthors::Exclude, Some(details)) =
+                    (warranted_authors, details.as_mut())
+                {
+                    let mut warranted = HashMap::new();
+                    for action in details
+                        .actions
+                        .iter()
+                        .chain(details.rejected_actions.iter())
+                        .chain(details.deletes.iter())
+                        .chain(details.updates.iter())
+                    {
+                        let author = action.action().author();
+                        if !warranted.contains_key(author) {
+                            let is_warranted =
+                                !self.get_warrants_against(author.clone()).await?.is_empty();
+                            warranted.insert(author.clone(), is_warranted);
+                        }
+                    }
+                    let keep = |action: &SignedActionHashed| !warranted[action.action().author()];
+                    details.actions.retain(keep);
+                    details.rejected_actions.retain(keep);
+                    details.deletes.retain(keep);
+                    details.updates.retain(keep);
+                }
+                Ok(details.map(Details::Entry))
+            }
+            AnyDhtHashPrimitive::Action(hash) => {
+                let details = match self.get_record_details(hash, options).await? {
+                    Some(details) => details,
+                    None => return Ok(None),
+                };
+                if warranted_authors == WarrantedAuthors::Include {
+                    return Ok(Some(Details::Record(details)));
+                }
+                let warrants = self
+                    .get_warrants_against(details.record.action().author().clone())
+                    .await?;
+                match warranted_authors {
+                    WarrantedAuthors::Exclude if !warrants.is_empty() => Ok(None),
+                    WarrantedAuthors::Annotate => Ok(Some(Details::Record(RecordDetails {
+                        warrants,
+                        ..details
+                    }))),
+                    _ => Ok(Some(Details::Record(details))),
+                }
+            }
+        }
+    }
+
+    /// Get the counterparty's acceptance of a proposed dual signed entry.
+    ///
+    /// The acceptance is an update of the proposal, so it is found among the
+    /// updates in the proposal's [`RecordDetails`]. Returns `None` if the
+    /// proposal can't be found, isn't a dual signed entry proposal or hasn't
+    /// been accepted yet. If the counterparty accepted the proposal more than
+    /// once, the oldest acceptance is returned.
+    #[instrument(skip(self, options))]
+    pub async fn get_dual_sign_acceptance(
+        &self,
+        proposal: ActionHash,
+        options: GetOptions,
+    ) -> CascadeResult<Option<Record>> {
+        let details = match self.get_record_details(proposal, options.clone()).await? {
+            Some(details) => details,
+            None => return Ok(None),
+        };
+        for candidate in details.dual_sign_acceptance_candidates() {
+            let record = self
+                .dht_get_action(candidate.as_hash().clone(), options.clone())
+                .await?;
+            if let Some(record) = record {
+                if let Some(Entry::DualSign(data, _)) = record.entry().as_option() {
+                    if matches!(data.as_ref(), DualSignData::Acceptance { .. }) {
+                        return Ok(Some(record));
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Get the valid warrants against an agent which are held in the
+    /// local databases. Warrants are held by the agent activity authorities
+    /// for the warranted agent and by the agent which issued them.
+    pub async fn get_warrants_against(
+        &self,
+        agent: AgentPubKey,
+    ) -> CascadeResult<Vec<SignedWarrant>> {
+        let mut warrants = Vec::new();
+        if let Some(cache) = self.cache.clone() {
+            let agent = agent.clone();
+            warrants.extend(
+                cache
+                    .read_async(move |txn| get_warrants_against_from_db(&txn, &agent))
+                    .await?,
+            );
+        }
+        if let Some(dht) = self.dht.clone() {
+            let agent = agent.clone();
+            warrants.extend(
+                dht.read_async(move |txn| get_warrants_against_from_db(&txn, &agent))
+                    .await?,
+            );
+        }
+        if let Some(authored) = self.authored.clone() {
+            let agent = agent.clone();
+            warrants.extend(
+                authored
+                    .read_async(move |txn| get_warrants_against_from_db(&txn, &agent))
+                    .await?,
+            );
         }
+        let mut unique = Vec::with_capacity(warrants.len());
+        for warrant in warrants {
+            if !unique.contains(&warrant) {
+                unique.push(warrant);
+            }
+        }
+        Ok(unique)
     }
 
     #[instrument(skip(self, options))]
@@ -862,26 +1136,33 @@ impl CascadeImpl {
         key: WireLinkKey,
         options: GetLinksOptions,
     ) -> CascadeResult<Vec<Link>> {
-        // only fetch links from network if i am not an authority and
-        // GetStrategy is Latest
-        if let GetStrategy::Network = options.get_options.strategy {
-            let authority = self.am_i_an_authority(key.base.clone()).await?;
-            if !authority {
-                self.fetch_links(key.clone(), options).await?;
-            }
-        }
-
         let query = GetLinksQuery::new(
-            key.base,
-            key.type_query,
-            key.tag,
+            key.base.clone(),
+            key.type_query.clone(),
+            key.tag.clone(),
             GetLinksFilter {
                 after: key.after,
                 before: key.before,
-                author: key.author,
+                author: key.author.clone(),
+                tag_range: key.tag_range.clone(),
+                tag_fields: key.tag_fields.clone(),
+                // Link ops which arrived before the watermark aren't fetched,
+                // so they are excluded locally too.
+                integrated_since: key.since,
+                integrated_before: None,
             },
         );
 
+        // only fetch links from network if i am not an authority and
+        // the read source allows it
+        if self
+            .should_fetch_links(&key, options.get_options.read_source(), query.clone())
+            .await?
+        {
+            self.fetch_links(key, options, Timestamp::now(), false)
+                .await?;
+        }
+
         self.cascading(query).await
     }
 
@@ -894,17 +1175,94 @@ impl CascadeImpl {
         options: GetLinksOptions,
     ) -> CascadeResult<Vec<(SignedActionHashed, Vec<SignedActionHashed>)>> {
         // only fetch link details from network if i am not an authority and
-        // GetStrategy is Network
-        if let GetStrategy::Network = options.get_options.strategy {
-            let authority = self.am_i_an_authority(key.base.clone()).await?;
-            if !authority {
-                self.fetch_links(key.clone(), options).await?;
-            }
+        // the read source allows it
+        let query =
+            GetLinkDetailsQuery::new(key.base.clone(), key.type_query.clone(), key.tag.clone());
+        if self
+            .should_fetch_links(&key, options.get_options.read_source(), query.clone())
+            .await?
+        {
+            self.fetch_links(key, options, Timestamp::now(), false)
+                .await?;
+        }
+        self.cascading(query).await
+    }
+
+    /// Get the links created and deleted since the `since` watermark of the key.
+    ///
+    /// The watermark is compared with when link ops were integrated, or added
+    /// to the cache, so polling with the returned watermark only transfers and
+    /// returns what arrived since the previous delta.
+    #[instrument(skip(self, key, options))]
+    pub async fn dht_get_links_delta(
+        &self,
+        key: WireLinkKey,
+        options: GetLinksOptions,
+    ) -> CascadeResult<LinksDelta> {
+        let query = GetLinksDeltaQuery::new(
+            key.base.clone(),
+            key.type_query.clone(),
+            key.tag.clone(),
+            GetLinksFilter {
+                after: key.after,
+                before: key.before,
+                author: key.author.clone(),
+                tag_range: key.tag_range.clone(),
+                tag_fields: key.tag_fields.clone(),
+                integrated_since: key.since,
+                // Set to the watermark by the query.
+                integrated_before: None,
+            },
+        );
+
+        // A local delta can't show whether the network holds link ops which
+        // haven't arrived here yet, so unless the read is local only the
+        // network is asked for them.
+        let fetch = match options.get_options.read_source() {
+            ReadSource::LocalOnly => false,
+            ReadSource::CacheThenNetwork | ReadSource::NetworkOnly => true,
+        };
+        if fetch && !self.am_i_an_authority(key.base.clone()).await? {
+            // Authorities compare the watermark with their own clocks, so
+            // they are asked for ops from a while before it.
+            let mut network_key = key;
+            network_key.since = network_key
+                .since
+                .map(|since| since.saturating_sub(&LINKS_DELTA_OVERLAP));
+            // Newly fetched ops are cached as integrated just before the
+            // watermark, so they are in this delta and not the next. Ops which
+            // were already cached keep their time, so the overlap doesn't
+            // return them again.
+            let cached_at = query
+                .watermark()
+                .saturating_sub(&std::time::Duration::from_micros(1));
+            self.fetch_links(network_key, options, cached_at, true)
+                .await?;
         }
-        let query = GetLinkDetailsQuery::new(key.base, key.type_query, key.tag);
+
         self.cascading(query).await
     }
 
+    /// Whether links should be fetched from the network before answering
+    /// the `query` from the local databases.
+    async fn should_fetch_links<Q, T>(
+        &self,
+        key: &WireLinkKey,
+        read_source: ReadSource,
+        query: Q,
+    ) -> CascadeResult<bool>
+    where
+        Q: Query<Item = Judged<SignedActionHashed>, Output = Vec<T>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let fetch = match read_source {
+            ReadSource::LocalOnly => false,
+            ReadSource::CacheThenNetwork => self.cascading(query).await?.is_empty(),
+            ReadSource::NetworkOnly => true,
+        };
+        Ok(fetch && !self.am_i_an_authority(key.base.clone()).await?)
+    }
+
     /// Count the number of links matching the `query`.
     #[instrument(skip(self, query))]
     pub async fn dht_count_links(&self, query: WireLinkQuery) -> CascadeResult<usize> {
@@ -999,6 +1357,82 @@ impl CascadeImpl {
         }
     }
 
+    /// Get the action at position `action_seq` of an author's chain.
+    ///
+    /// Agent activity authorities look the action up by author and sequence
+    /// number, so this doesn't need to fetch the activity of the whole chain.
+    /// If the author has forked their chain at this position, the action with
+    /// the lowest hash is returned.
+    pub async fn get_action_by_seq(
+        &self,
+        author: AgentPubKey,
+        action_seq: u32,
+        read_source: ReadSource,
+    ) -> CascadeResult<Option<SignedActionHashed>> {
+        let query = GetActionBySeqQuery::not_rejected(author.clone(), action_seq);
+        let authority = self.am_i_an_authority(author.clone().into()).await?;
+        if authority || read_source != ReadSource::NetworkOnly {
+            let actions = self.cascading(query.clone()).await?;
+            // Only go to the network if the databases hold no action at this position.
+            if authority || read_source == ReadSource::LocalOnly || !actions.is_empty() {
+                return Ok(actions.into_iter().next());
+            }
+        }
+        self.fetch_action_by_seq(author, action_seq).await?;
+        Ok(self.cascading(query).await?.into_iter().next())
+    }
+
+    /// Get a page of the public entries of one type created by an author, in
+    /// the order they were created.
+    ///
+    /// The author's agent activity authorities list the actions which created
+    /// the entries from an index by author and entry type, and the responses of
+    /// all the authorities asked are merged. The entries are then fetched from
+    /// their own authorities.
+    /// With [`ReadSource::CacheThenNetwork`] the network is only asked if the
+    /// local databases don't hold a full page.
+    pub async fn get_entries_by_author(
+        &self,
+        author: AgentPubKey,
+        entry_type: EntryType,
+        page: EntriesPage,
+        read_source: ReadSource,
+    ) -> CascadeResult<Vec<Record>> {
+        let query = GetEntriesByAuthorQuery::not_rejected(author.clone(), entry_type.clone(), page);
+        if read_source != ReadSource::NetworkOnly {
+            let records = self.cascading(query.clone()).await?;
+            if read_source == ReadSource::LocalOnly || records.len() as u32 >= page.limit {
+                return Ok(records);
+            }
+        }
+        self.fetch_entries_by_author(author, entry_type, page)
+            .await?;
+        self.cascading(query).await
+    }
+
+    /// Get the activity of an agent held in the local DHT database.
+    async fn local_agent_activity(
+        &self,
+        agent: &AgentPubKey,
+        query: &ChainQueryFilter,
+        options: &GetActivityOptions,
+    ) -> CascadeResult<AgentActivityResponse<ActionHash>> {
+        match self.dht.clone() {
+            Some(vault) => Ok(authority::handle_get_agent_activity(
+                vault,
+                agent.clone(),
+                query.clone(),
+                options.into(),
+            )
+            .await?),
+            None => Ok(agent_activity::merge_activities(
+                agent.clone(),
+                options,
+                Vec::with_capacity(0),
+            )?),
+        }
+    }
+
     #[instrument(skip(self, agent, query, options))]
     /// Get agent activity from agent activity authorities.
     /// Hashes are requested from the authority and cache for valid chains.
@@ -1019,29 +1453,24 @@ impl CascadeImpl {
         let status_only = !options.include_rejected_activity && !options.include_valid_activity;
         // DESIGN: Evaluate if it's ok to **not** go to another authority for agent activity?
         let authority = self.am_i_an_authority(agent.clone().into()).await?;
-        let merged_response = if !authority {
-            let results = self
-                .fetch_agent_activity(agent.clone(), query.clone(), options.clone())
-                .await?;
-            let merged_response: AgentActivityResponse<ActionHash> =
-                agent_activity::merge_activities(agent.clone(), &options, results)?;
-            merged_response
+        let local_response = if authority || options.read_source == ReadSource::LocalOnly {
+            Some(self.local_agent_activity(&agent, &query, &options).await?)
+        } else if options.read_source == ReadSource::CacheThenNetwork {
+            // Only go to the network if the database holds no activity.
+            Some(self.local_agent_activity(&agent, &query, &options).await?)
+                .filter(|response| !matches!(response.status, ChainStatus::Empty))
         } else {
-            match self.dht.clone() {
-                Some(vault) => {
-                    authority::handle_get_agent_activity(
-                        vault,
-                        agent.clone(),
-                        query.clone(),
-                        (&options).into(),
-                    )
-                    .await?
-                }
-                None => agent_activity::merge_activities(
-                    agent.clone(),
-                    &options,
-                    Vec::with_capacity(0),
-                )?,
+            None
+        };
+        let merged_response = match local_response {
+            Some(local_response) => local_response,
+            None => {
+                let results = self
+                    .fetch_agent_activity(agent.clone(), query.clone(), options.clone())
+                    .await?;
+                let merged_response: AgentActivityResponse<ActionHash> =
+                    agent_activity::merge_activities(agent.clone(), &options, results)?;
+                merged_response
             }
         };
 
@@ -1180,7 +1609,7 @@ impl MockCascade {
             })))
         });
 
-        let map = map0;
+        let map = map0.clone();
         cascade.expect_retrieve_entry().returning(move |hash, _| {
             box_fut_plain(Ok(map.share_ref(|m| {
                 m.get(&hash.into()).map(|r| {
@@ -1192,6 +1621,34 @@ impl MockCascade {
             })))
         });
 
+        let map = map0;
+        cascade.expect_retrieve_chain().returning(move |_, filter| {
+            let (take, until) = match filter.filters {
+                ChainFilters::ToGenesis => (None, HashSet::new()),
+                ChainFilters::Take(n) => (Some(n), HashSet::new()),
+                ChainFilters::Until(until) => (None, until),
+                ChainFilters::Both(n, until) => (Some(n), until),
+            };
+            box_fut_plain(Ok(map.share_ref(|m| {
+                let mut activity = Vec::new();
+                let mut next = Some(filter.chain_top);
+                while let Some(hash) = next {
+                    let Some(record) = m.get(&hash.clone().into()) else {
+                        return MustGetAgentActivityResponse::IncompleteChain;
+                    };
+                    activity.push(RegisterAgentActivity {
+                        action: record.signed_action().clone(),
+                        cached_entry: None,
+                    });
+                    if until.contains(&hash) || take.is_some_and(|n| activity.len() >= n as usize) {
+                        break;
+                    }
+                    next = record.action().prev_action().cloned();
+                }
+                MustGetAgentActivityResponse::Activity(activity)
+            })))
+        });
+
         cascade
     }
 }
@@ -32,10 +32,12 @@ pub use activity_test_data::*;
 pub use entry_test_data::*;
 use holochain_types::validation_receipt::ValidationReceiptBundle;
 pub use record_test_data::*;
+pub use simulated_dht::*;
 
 mod activity_test_data;
 mod entry_test_data;
 mod record_test_data;
+mod simulated_dht;
 
 /// A network implementation which routes to the local databases,
 /// and can declare itself an authority either for all ops, or for no ops.
@@ -177,6 +179,42 @@ impl HolochainP2pDnaT for PassThroughNetwork {
         Ok(out)
     }
 
+    async fn get_action_by_seq(
+        &self,
+        author: AgentPubKey,
+        action_seq: u32,
+    ) -> actor::HolochainP2pResult<Vec<ActionBySeqResponse>> {
+        let mut out = Vec::new();
+        for env in &self.envs {
+            let r = authority::handle_get_action_by_seq(env.clone(), author.clone(), action_seq)
+                .await
+                .map_err(|e| HolochainP2pError::Other(e.into()))?;
+            out.push(r);
+        }
+        Ok(out)
+    }
+
+    async fn get_entries_by_author(
+        &self,
+        author: AgentPubKey,
+        entry_type: EntryType,
+        page: EntriesPage,
+    ) -> actor::HolochainP2pResult<Vec<EntriesByAuthorResponse>> {
+        let mut out = Vec::new();
+        for env in &self.envs {
+            let r = authority::handle_get_entries_by_author(
+                env.clone(),
+                author.clone(),
+                entry_type.clone(),
+                page,
+            )
+            .await
+            .map_err(|e| HolochainP2pError::Other(e.into()))?;
+            out.push(r);
+        }
+        Ok(out)
+    }
+
     async fn authority_for_hash(
         &self,
         _dht_hash: holo_hash::OpBasis,
@@ -184,6 +222,14 @@ impl HolochainP2pDnaT for PassThroughNetwork {
         Ok(self.authority)
     }
 
+    async fn fetch_from_authorities(
+        &self,
+        _basis: holo_hash::OpBasis,
+        _op_hash_list: Vec<OpHashSized>,
+    ) -> actor::HolochainP2pResult<()> {
+        todo!()
+    }
+
     fn dna_hash(&self) -> holo_hash::DnaHash {
         todo!()
     }
@@ -244,6 +290,17 @@ impl HolochainP2pDnaT for PassThroughNetwork {
         todo!()
     }
 
+    async fn network_time_offset(&self) -> actor::HolochainP2pResult<Option<NetworkTimeOffset>> {
+        Ok(None)
+    }
+
+    async fn storage_arc(
+        &self,
+        _agent: AgentPubKey,
+    ) -> actor::HolochainP2pResult<Option<StorageArcSummary>> {
+        Ok(None)
+    }
+
     async fn join(
         &self,
         _agent: AgentPubKey,
@@ -272,6 +329,21 @@ impl HolochainP2pDnaT for PassThroughNetwork {
         todo!()
     }
 
+    async fn remote_query(
+        &self,
+        _from_agent: AgentPubKey,
+        _from_signature: Signature,
+        _to_agent: AgentPubKey,
+        _zome_name: ZomeName,
+        _fn_name: FunctionName,
+        _cap: Option<CapSecret>,
+        _payload: ExternIO,
+        _nonce: Nonce256Bits,
+        _expires_at: Timestamp,
+    ) -> actor::HolochainP2pResult<holochain_serialized_bytes::SerializedBytes> {
+        todo!()
+    }
+
     fn chc(&self) -> Option<ChcImpl> {
         None
     }
@@ -162,17 +162,23 @@ impl EntryTestData {
             base: create_link.base_address.clone(),
             type_query: LinkTypeFilter::single_dep(0.into()),
             tag: None,
+            tag_range: None,
+            tag_fields: Vec::new(),
             after: None,
             before: None,
             author: None,
+            since: None,
         };
         let link_key_tag = WireLinkKey {
             base: create_link.base_address.clone(),
             type_query: LinkTypeFilter::single_dep(0.into()),
             tag: Some(create_link.tag.clone()),
+            tag_range: None,
+            tag_fields: Vec::new(),
             after: None,
             before: None,
             author: None,
+            since: None,
         };
 
         let link = Link {
@@ -190,6 +196,7 @@ impl EntryTestData {
             base: create_link.base_address,
             link_type: LinkTypeFilter::single_dep(0.into()),
             tag_prefix: None,
+            tag_range: None,
             before: None,
             after: None,
             author: None,
@@ -0,0 +1,413 @@
+use crate::authority;
+use holo_hash::ActionHash;
+use holo_hash::AgentPubKey;
+use holo_hash::AnyDhtHash;
+use holo_hash::DnaHash;
+use holo_hash::OpBasis;
+use holochain_nonce::Nonce256Bits;
+use holochain_p2p::actor;
+use holochain_p2p::event::CountersigningSessionNegotiationMessage;
+use holochain_p2p::ChcImpl;
+use holochain_p2p::HolochainP2pDnaT;
+use holochain_p2p::HolochainP2pError;
+use holochain_state::prelude::*;
+use holochain_types::validation_receipt::ValidationReceiptBundle;
+use kitsune_p2p::agent_store::AgentInfoSigned;
+use kitsune_p2p::dependencies::kitsune_p2p_fetch::OpHashSized;
+use kitsune_p2p::dht::Arq;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// An in-process simulation of the DHT for a DNA, for multi-agent tests
+/// which don't need real networking.
+///
+/// Every agent in the simulation is an authority with its own in-memory DHT
+/// database. Each op is held by the `replication_factor` agents whose
+/// locations are nearest to the location of the op's basis, and the
+/// networks handed out by [`SimulatedDht::network`] route cascade calls
+/// straight to the authorities for the requested basis.
+///
+/// Ops which are published are stored as valid and integrated.
+/// No validation is run.
+#[derive(Clone)]
+pub struct SimulatedDht {
+    dna_hash: DnaHash,
+    replication_factor: usize,
+    authorities: Arc<Vec<(AgentPubKey, DbWrite<DbKindDht>)>>,
+}
+
+impl SimulatedDht {
+    /// Create a simulated DHT where every op is held by
+    /// `replication_factor` of the given agents.
+    pub fn new(dna_hash: DnaHash, agents: Vec<AgentPubKey>, replication_factor: usize) -> Self {
+        let authorities = agents
+            .into_iter()
+            .map(|agent| {
+                let db = test_in_mem_db(DbKindDht(Arc::new(dna_hash.clone())));
+                (agent, db)
+            })
+            .collect();
+        Self {
+            dna_hash,
+            replication_factor: replication_factor.max(1),
+            authorities: Arc::new(authorities),
+        }
+    }
+
+    /// The agents in the simulation.
+    pub fn agents(&self) -> impl Iterator<Item = &AgentPubKey> {
+        self.authorities.iter().map(|(agent, _)| agent)
+    }
+
+    /// The DHT database of an agent in the simulation.
+    pub fn db(&self, agent: &AgentPubKey) -> Option<DbWrite<DbKindDht>> {
+        self.authorities
+            .iter()
+            .find(|(a, _)| a == agent)
+            .map(|(_, db)| db.clone())
+    }
+
+    /// The agents which hold the ops for a basis,
+    /// with the nearest agent first.
+    pub fn authorities_for(&self, basis: &OpBasis) -> Vec<AgentPubKey> {
+        self.authority_dbs_for(basis)
+            .into_iter()
+            .map(|(agent, _)| agent)
+            .collect()
+    }
+
+    fn authority_dbs_for(&self, basis: &OpBasis) -> Vec<(AgentPubKey, DbWrite<DbKindDht>)> {
+        let loc = basis.get_loc().as_u32();
+        let mut authorities = self.authorities.as_ref().clone();
+        authorities.sort_by_key(|(agent, _)| {
+            let agent_loc = agent.get_loc().as_u32();
+            let distance = loc.wrapping_sub(agent_loc).min(agent_loc.wrapping_sub(loc));
+            (distance, agent.clone())
+        });
+        authorities.truncate(self.replication_factor);
+        authorities
+    }
+
+    /// Store an op with every authority for its basis, as valid and integrated.
+    pub async fn publish(&self, op: DhtOpHashed) {
+        self.publish_with_status(op, ValidationStatus::Valid).await
+    }
+
+    /// Store an op with every authority for its basis, as rejected and integrated.
+    pub async fn publish_rejected(&self, op: DhtOpHashed) {
+        self.publish_with_status(op, ValidationStatus::Rejected)
+            .await
+    }
+
+    async fn publish_with_status(&self, op: DhtOpHashed, status: ValidationStatus) {
+        for (_, db) in self.authority_dbs_for(&op.dht_basis()) {
+            let op = op.clone();
+            db.write_async(move |txn| -> DatabaseResult<()> {
+                let hash = op.to_hash();
+                insert_op(txn, &op).unwrap();
+                set_validation_status(txn, &hash, status).unwrap();
+                set_when_integrated(txn, &hash, Timestamp::now()).unwrap();
+                Ok(())
+            })
+            .await
+            .unwrap();
+        }
+    }
+
+    /// A network for an agent in the simulation, to hand to a cascade.
+    pub fn network(&self, agent: AgentPubKey) -> Arc<SimulatedDhtNetwork> {
+        Arc::new(SimulatedDhtNetwork {
+            dht: self.clone(),
+            agent,
+        })
+    }
+
+    fn dbs_for(&self, basis: &OpBasis) -> Vec<DbRead<DbKindDht>> {
+        self.authority_dbs_for(basis)
+            .into_iter()
+            .map(|(_, db)| db.into())
+            .collect()
+    }
+}
+
+/// The network of one agent in a [`SimulatedDht`].
+#[derive(Clone)]
+pub struct SimulatedDhtNetwork {
+    dht: SimulatedDht,
+    agent: AgentPubKey,
+}
+
+#[async_trait::async_trait]
+impl HolochainP2pDnaT for SimulatedDhtNetwork {
+    async fn get(
+        &self,
+        dht_hash: AnyDhtHash,
+        options: actor::GetOptions,
+    ) -> actor::HolochainP2pResult<Vec<WireOps>> {
+        let mut out = Vec::new();
+        for env in self.dht.dbs_for(&dht_hash.clone().into()) {
+            let r = match dht_hash.clone().into_primitive() {
+                holo_hash::AnyDhtHashPrimitive::Entry(hash) => WireOps::Entry(
+                    authority::handle_get_entry(env, hash, (&options).into())
+                        .await
+                        .map_err(|e| HolochainP2pError::Other(e.into()))?,
+                ),
+                holo_hash::AnyDhtHashPrimitive::Action(hash) => WireOps::Record(
+                    authority::handle_get_record(env, hash, (&options).into())
+                        .await
+                        .map_err(|e| HolochainP2pError::Other(e.into()))?,
+                ),
+            };
+            out.push(r);
+        }
+        Ok(out)
+    }
+
+    async fn get_meta(
+        &self,
+        _dht_hash: AnyDhtHash,
+        _options: actor::GetMetaOptions,
+    ) -> actor::HolochainP2pResult<Vec<MetadataSet>> {
+        todo!()
+    }
+
+    async fn get_links(
+        &self,
+        link_key: WireLinkKey,
+        options: actor::GetLinksOptions,
+    ) -> actor::HolochainP2pResult<Vec<WireLinkOps>> {
+        let mut out = Vec::new();
+        for env in self.dht.dbs_for(&link_key.base) {
+            let r = authority::handle_get_links(env, link_key.clone(), (&options).into())
+                .await
+                .map_err(|e| HolochainP2pError::Other(e.into()))?;
+            out.push(r);
+        }
+        Ok(out)
+    }
+
+    async fn count_links(
+        &self,
+        query: WireLinkQuery,
+    ) -> actor::HolochainP2pResult<CountLinksResponse> {
+        let mut out = HashSet::new();
+        for env in self.dht.dbs_for(&query.base) {
+            let r = authority::handle_get_links_query(env, query.clone())
+                .await
+                .map_err(|e| HolochainP2pError::Other(e.into()))?;
+            out.extend(r);
+        }
+        Ok(CountLinksResponse::new(
+            out.into_iter()
+                .map(|l| l.create_link_hash)
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    async fn get_agent_activity(
+        &self,
+        agent: AgentPubKey,
+        query: QueryFilter,
+        options: actor::GetActivityOptions,
+    ) -> actor::HolochainP2pResult<Vec<AgentActivityResponse<ActionHash>>> {
+        let mut out = Vec::new();
+        for env in self.dht.dbs_for(&agent.clone().into()) {
+            let r = authority::handle_get_agent_activity(
+                env,
+                agent.clone(),
+                query.clone(),
+                (&options).into(),
+            )
+            .await
+            .map_err(|e| HolochainP2pError::Other(e.into()))?;
+            out.push(r);
+        }
+        Ok(out)
+    }
+
+    async fn must_get_agent_activity(
+        &self,
+        agent: AgentPubKey,
+        filter: ChainFilter,
+    ) -> actor::HolochainP2pResult<Vec<MustGetAgentActivityResponse>> {
+        let mut out = Vec::new();
+        for env in self.dht.dbs_for(&agent.clone().into()) {
+            let r = authority::handle_must_get_agent_activity(env, agent.clone(), filter.clone())
+                .await
+                .map_err(|e| HolochainP2pError::Other(e.into()))?;
+            out.push(r);
+        }
+        Ok(out)
+    }
+
+    async fn get_action_by_seq(
+        &self,
+        author: AgentPubKey,
+        action_seq: u32,
+    ) -> actor::HolochainP2pResult<Vec<ActionBySeqResponse>> {
+        let mut out = Vec::new();
+        for env in self.dht.dbs_for(&author.clone().into()) {
+            let r = authority::handle_get_action_by_seq(env, author.clone(), action_seq)
+                .await
+                .map_err(|e| HolochainP2pError::Other(e.into()))?;
+            out.push(r);
+        }
+        Ok(out)
+    }
+
+    async fn get_entries_by_author(
+        &self,
+        author: AgentPubKey,
+        entry_type: EntryType,
+        page: EntriesPage,
+    ) -> actor::HolochainP2pResult<Vec<EntriesByAuthorResponse>> {
+        let mut out = Vec::new();
+        for env in self.dht.dbs_for(&author.clone().into()) {
+            let r = authority::handle_get_entries_by_author(
+                env,
+                author.clone(),
+                entry_type.clone(),
+                page,
+            )
+            .await
+            .map_err(|e| HolochainP2pError::Other(e.into()))?;
+            out.push(r);
+        }
+        Ok(out)
+    }
+
+    async fn authority_for_hash(&self, basis: OpBasis) -> actor::HolochainP2pResult<bool> {
+        Ok(self.dht.authorities_for(&basis).contains(&self.agent))
+    }
+
+    async fn fetch_from_authorities(
+        &self,
+        _basis: OpBasis,
+        _op_hash_list: Vec<OpHashSized>,
+    ) -> actor::HolochainP2pResult<()> {
+        todo!()
+    }
+
+    fn dna_hash(&self) -> DnaHash {
+        self.dht.dna_hash.clone()
+    }
+
+    async fn send_remote_signal(
+        &self,
+        _from_agent: AgentPubKey,
+        _to_agent_list: Vec<(Signature, AgentPubKey)>,
+        _zome_name: ZomeName,
+        _fn_name: FunctionName,
+        _cap: Option<CapSecret>,
+        _payload: ExternIO,
+        _nonce: Nonce256Bits,
+        _expires_at: Timestamp,
+    ) -> actor::HolochainP2pResult<()> {
+        todo!()
+    }
+
+    async fn publish(
+        &self,
+        _request_validation_receipt: bool,
+        _countersigning_session: bool,
+        _basis_hash: OpBasis,
+        _source: AgentPubKey,
+        _op_hash_list: Vec<OpHashSized>,
+        _timeout_ms: Option<u64>,
+        reflect_ops: Option<Vec<crate::DhtOp>>,
+    ) -> actor::HolochainP2pResult<()> {
+        // Only the ops themselves can be stored, as there is no author to fetch them from.
+        for op in reflect_ops.into_iter().flatten() {
+            self.dht.publish(DhtOpHashed::from_content_sync(op)).await;
+        }
+        Ok(())
+    }
+
+    async fn publish_countersign(
+        &self,
+        _flag: bool,
+        _basis_hash: OpBasis,
+        op: crate::DhtOp,
+    ) -> actor::HolochainP2pResult<()> {
+        self.dht.publish(DhtOpHashed::from_content_sync(op)).await;
+        Ok(())
+    }
+
+    async fn send_validation_receipts(
+        &self,
+        _to_agent: AgentPubKey,
+        _receipts: ValidationReceiptBundle,
+    ) -> actor::HolochainP2pResult<()> {
+        Ok(())
+    }
+
+    async fn countersigning_session_negotiation(
+        &self,
+        _agents: Vec<AgentPubKey>,
+        _message: CountersigningSessionNegotiationMessage,
+    ) -> actor::HolochainP2pResult<()> {
+        todo!()
+    }
+
+    async fn new_integrated_data(&self) -> actor::HolochainP2pResult<()> {
+        Ok(())
+    }
+
+    async fn network_time_offset(&self) -> actor::HolochainP2pResult<Option<NetworkTimeOffset>> {
+        Ok(None)
+    }
+
+    async fn storage_arc(
+        &self,
+        _agent: AgentPubKey,
+    ) -> actor::HolochainP2pResult<Option<StorageArcSummary>> {
+        Ok(None)
+    }
+
+    async fn join(
+        &self,
+        _agent: AgentPubKey,
+        _maybe_agent_info: Option<AgentInfoSigned>,
+        _initial_arq: Option<Arq>,
+    ) -> actor::HolochainP2pResult<()> {
+        Ok(())
+    }
+
+    async fn leave(&self, _agent: AgentPubKey) -> actor::HolochainP2pResult<()> {
+        Ok(())
+    }
+
+    async fn call_remote(
+        &self,
+        _from_agent: AgentPubKey,
+        _from_signature: Signature,
+        _to_agent: AgentPubKey,
+        _zome_name: ZomeName,
+        _fn_name: FunctionName,
+        _cap: Option<CapSecret>,
+        _payload: ExternIO,
+        _nonce: Nonce256Bits,
+        _expires_at: Timestamp,
+    ) -> actor::HolochainP2pResult<holochain_serialized_bytes::SerializedBytes> {
+        todo!()
+    }
+
+    async fn remote_query(
+        &self,
+        _from_agent: AgentPubKey,
+        _from_signature: Signature,
+        _to_agent: AgentPubKey,
+        _zome_name: ZomeName,
+        _fn_name: FunctionName,
+        _cap: Option<CapSecret>,
+        _payload: ExternIO,
+        _nonce: Nonce256Bits,
+        _expires_at: Timestamp,
+    ) -> actor::HolochainP2pResult<holochain_serialized_bytes::SerializedBytes> {
+        todo!()
+    }
+
+    fn chc(&self) -> Option<ChcImpl> {
+        None
+    }
+}
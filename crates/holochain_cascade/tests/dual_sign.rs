@@ -0,0 +1,116 @@
+use ::fixt::prelude::*;
+use holo_hash::ActionHash;
+use holo_hash::AgentPubKey;
+use holo_hash::EntryHash;
+use holochain_cascade::test_utils::*;
+use holochain_cascade::CascadeImpl;
+use holochain_state::prelude::*;
+
+/// Store an update of the proposal, as the DHT would hold it.
+async fn store_update(db: &DbWrite<DbKindDht>, mut update: Update, entry: Entry) -> ActionHash {
+    update.entry_hash = EntryHash::with_data_sync(&entry);
+    let signature = fixt!(Signature);
+    fill_db(
+        db,
+        ChainOpHashed::from_content_sync(ChainOp::RegisterUpdatedRecord(
+            signature.clone(),
+            update.clone(),
+            entry.clone().into(),
+        )),
+    )
+    .await;
+    let action = Action::Update(update);
+    let hash = ActionHash::with_data_sync(&action);
+    fill_db(
+        db,
+        ChainOpHashed::from_content_sync(ChainOp::StoreRecord(signature, action, entry.into())),
+    )
+    .await;
+    hash
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn get_dual_sign_acceptance() {
+    holochain_trace::test_run();
+    let dht = test_dht_db();
+    let cascade = CascadeImpl::empty().with_dht(dht.to_db().into());
+
+    let alice = AgentPubKey::from_raw_36(vec![1; 36]);
+    let bob = AgentPubKey::from_raw_36(vec![2; 36]);
+    let carol = AgentPubKey::from_raw_36(vec![3; 36]);
+    let bytes = fixt!(AppEntryBytes);
+    let entry_type = EntryType::App(
+        AppEntryDefFixturator::new(EntryVisibility::Public)
+            .next()
+            .unwrap(),
+    );
+
+    // Alice proposes the entry to Bob.
+    let proposal = dual_sign_proposal(bob.clone(), bytes.clone());
+    let mut create = fixt!(Create);
+    create.author = alice.clone();
+    create.entry_type = entry_type.clone();
+    create.entry_hash = EntryHash::with_data_sync(&proposal);
+    let create = Action::Create(create);
+    let proposal_hash = ActionHash::with_data_sync(&create);
+    fill_db(
+        &dht.to_db(),
+        ChainOpHashed::from_content_sync(ChainOp::StoreRecord(
+            fixt!(Signature),
+            create,
+            proposal.clone().into(),
+        )),
+    )
+    .await;
+
+    let mut update = fixt!(Update);
+    update.entry_type = entry_type;
+    update.original_action_address = proposal_hash.clone();
+    update.original_entry_address = EntryHash::with_data_sync(&proposal);
+    update.timestamp = Timestamp::from_micros(1_000);
+
+    // - Nothing is returned until Bob accepts.
+    let r = cascade
+        .get_dual_sign_acceptance(proposal_hash.clone(), GetOptions::local())
+        .await
+        .unwrap();
+    assert_eq!(r, None);
+
+    // - An acceptance by anyone but Bob is ignored.
+    let mut by_carol = update.clone();
+    by_carol.author = carol;
+    store_update(
+        &dht.to_db(),
+        by_carol,
+        dual_sign_acceptance(alice.clone(), bytes.clone()),
+    )
+    .await;
+    let r = cascade
+        .get_dual_sign_acceptance(proposal_hash.clone(), GetOptions::local())
+        .await
+        .unwrap();
+    assert_eq!(r, None);
+
+    // - Bob's oldest acceptance is returned.
+    let mut by_bob = update.clone();
+    by_bob.author = bob.clone();
+    let accepted = store_update(
+        &dht.to_db(),
+        by_bob.clone(),
+        dual_sign_acceptance(alice.clone(), bytes.clone()),
+    )
+    .await;
+    by_bob.timestamp = Timestamp::from_micros(2_000);
+    store_update(
+        &dht.to_db(),
+        by_bob,
+        dual_sign_acceptance(alice.clone(), bytes),
+    )
+    .await;
+    let r = cascade
+        .get_dual_sign_acceptance(proposal_hash, GetOptions::local())
+        .await
+        .unwrap()
+        .expect("Failed to get acceptance");
+    assert_eq!(*r.action_address(), accepted);
+}
@@ -135,3 +135,59 @@ async fn test_must_get_agent_activity(
         .await
         .unwrap()
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn get_action_by_seq() {
+    holochain_trace::test_run();
+
+    // Environments
+    let cache = test_cache_db();
+    let authority = test_dht_db();
+
+    // Data
+    let td = ActivityTestData::valid_chain_scenario();
+
+    for hash_op in td.hash_ops.iter().cloned() {
+        fill_db(&authority.to_db(), hash_op).await;
+    }
+    for hash_op in td.noise_ops.iter().cloned() {
+        fill_db(&authority.to_db(), hash_op).await;
+    }
+    let expected = match &td.valid_records {
+        ChainItems::Full(records) => records[10].action_address().clone(),
+        _ => unreachable!(),
+    };
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.to_db().clone().into()]);
+
+    // Cascade
+    let cascade = CascadeImpl::empty().with_network(network, cache.to_db());
+
+    // Nothing is held locally yet.
+    let r = cascade
+        .get_action_by_seq(td.agent.clone(), 10, ReadSource::LocalOnly)
+        .await
+        .unwrap();
+    assert_eq!(r, None);
+
+    let r = cascade
+        .get_action_by_seq(td.agent.clone(), 10, ReadSource::NetworkOnly)
+        .await
+        .unwrap();
+    assert_eq!(r.map(|a| a.as_hash().clone()), Some(expected.clone()));
+
+    // The action is now cached.
+    let r = cascade
+        .get_action_by_seq(td.agent.clone(), 10, ReadSource::LocalOnly)
+        .await
+        .unwrap();
+    assert_eq!(r.map(|a| a.as_hash().clone()), Some(expected));
+
+    // Past the head of the chain.
+    let r = cascade
+        .get_action_by_seq(td.agent.clone(), 1000, ReadSource::NetworkOnly)
+        .await
+        .unwrap();
+    assert_eq!(r, None);
+}
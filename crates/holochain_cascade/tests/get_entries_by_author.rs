@@ -0,0 +1,164 @@
+use ::fixt::prelude::*;
+use holo_hash::AgentPubKey;
+use holo_hash::EntryHash;
+use holochain_cascade::test_utils::*;
+use holochain_cascade::CascadeImpl;
+use holochain_state::prelude::*;
+
+/// Store an entry created by `author` at `action_seq`, as the DHT would hold it.
+/// The action is held by the author's agent activity authority and the entry by
+/// the entry's authority.
+async fn store_entry(
+    activity: &DbWrite<DbKindDht>,
+    entries: &DbWrite<DbKindDht>,
+    author: &AgentPubKey,
+    action_seq: u32,
+    entry_type: EntryType,
+) {
+    // Every entry is distinct, as entries with the same content are sent with
+    // one entry type.
+    let entry: AppEntryBytes =
+        SerializedBytes::from(UnsafeBytes::from(action_seq.to_be_bytes().to_vec()))
+            .try_into()
+            .unwrap();
+    let entry = Entry::App(entry);
+    let mut create = fixt!(Create);
+    create.author = author.clone();
+    create.action_seq = action_seq;
+    create.entry_type = entry_type;
+    create.entry_hash = EntryHash::with_data_sync(&entry);
+    let signature = fixt!(Signature);
+    let op = ChainOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+        signature.clone(),
+        Action::Create(create.clone()),
+    ));
+    fill_db(activity, op).await;
+    let op = ChainOpHashed::from_content_sync(ChainOp::StoreEntry(
+        signature,
+        NewEntryAction::Create(create),
+        entry,
+    ));
+    fill_db(entries, op).await;
+}
+
+fn seqs(records: &[Record]) -> Vec<u32> {
+    records.iter().map(|r| r.action().action_seq()).collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn get_entries_by_author() {
+    holochain_trace::test_run();
+
+    // Environments
+    let cache = test_cache_db();
+    let activity = test_dht_db();
+    let entries = test_dht_db();
+
+    // Data
+    let author = fixt!(AgentPubKey);
+    let post = EntryType::App(AppEntryDef::new(
+        0.into(),
+        0.into(),
+        EntryVisibility::Public,
+    ));
+    let comment = EntryType::App(AppEntryDef::new(
+        1.into(),
+        0.into(),
+        EntryVisibility::Public,
+    ));
+    for action_seq in 3..8 {
+        store_entry(
+            &activity.to_db(),
+            &entries.to_db(),
+            &author,
+            action_seq,
+            post.clone(),
+        )
+        .await;
+    }
+    store_entry(
+        &activity.to_db(),
+        &entries.to_db(),
+        &author,
+        8,
+        comment.clone(),
+    )
+    .await;
+    store_entry(
+        &activity.to_db(),
+        &entries.to_db(),
+        &fixt!(AgentPubKey),
+        9,
+        post.clone(),
+    )
+    .await;
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![
+        activity.to_db().clone().into(),
+        entries.to_db().clone().into(),
+    ]);
+
+    // Cascade
+    let cascade = CascadeImpl::empty().with_network(network, cache.to_db());
+
+    // Nothing is held locally yet.
+    let r = cascade
+        .get_entries_by_author(
+            author.clone(),
+            post.clone(),
+            EntriesPage::default(),
+            ReadSource::LocalOnly,
+        )
+        .await
+        .unwrap();
+    assert!(r.is_empty());
+
+    // Pages are listed in the order the entries were created.
+    let r = cascade
+        .get_entries_by_author(
+            author.clone(),
+            post.clone(),
+            EntriesPage::first(2),
+            ReadSource::NetworkOnly,
+        )
+        .await
+        .unwrap();
+    assert_eq!(seqs(&r), vec![3, 4]);
+    assert!(r.iter().all(|r| r.entry().as_option().is_some()));
+
+    let r = cascade
+        .get_entries_by_author(
+            author.clone(),
+            post.clone(),
+            EntriesPage::after(4, 10),
+            ReadSource::NetworkOnly,
+        )
+        .await
+        .unwrap();
+    assert_eq!(seqs(&r), vec![5, 6, 7]);
+
+    // The listed entries are now cached.
+    let r = cascade
+        .get_entries_by_author(
+            author.clone(),
+            post.clone(),
+            EntriesPage::default(),
+            ReadSource::LocalOnly,
+        )
+        .await
+        .unwrap();
+    assert_eq!(seqs(&r), vec![3, 4, 5, 6, 7]);
+
+    // Other entry types are listed separately.
+    let r = cascade
+        .get_entries_by_author(
+            author.clone(),
+            comment,
+            EntriesPage::default(),
+            ReadSource::NetworkOnly,
+        )
+        .await
+        .unwrap();
+    assert_eq!(seqs(&r), vec![8]);
+}
@@ -5,6 +5,7 @@ use holochain_cascade::test_utils::*;
 use holochain_cascade::{Cascade, CascadeImpl};
 use holochain_p2p::MockHolochainP2pDnaT;
 use holochain_state::mutations::insert_op_scratch;
+use holochain_state::negative_cache::NegativeCache;
 use holochain_state::prelude::*;
 
 async fn assert_can_get(
@@ -67,6 +68,7 @@ async fn assert_can_get(
         validation_status: ValidationStatus::Valid,
         deletes: vec![],
         updates: vec![],
+        warrants: vec![],
     });
     assert_eq!(r, expected);
 }
@@ -164,6 +166,7 @@ async fn assert_rejected(
         validation_status: ValidationStatus::Rejected,
         deletes: vec![],
         updates: vec![],
+        warrants: vec![],
     });
 
     assert_eq!(r, expected);
@@ -326,6 +329,114 @@ async fn content_not_authority_or_authoring() {
     assert_can_get(&td_entry, &td_record, &cascade, GetOptions::local()).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn cache_then_network_reads_from_cache() {
+    holochain_trace::test_run();
+
+    // Environments
+    let cache = test_cache_db();
+
+    // Data
+    let td_entry = EntryTestData::create();
+    let td_record = RecordTestData::create();
+    fill_db(&cache.to_db(), td_entry.store_entry_op.clone()).await;
+    fill_db(&cache.to_db(), td_record.any_store_record_op.clone()).await;
+
+    // Network
+    // - Not expecting any calls to the network.
+    let mock = Arc::new(MockHolochainP2pDnaT::new());
+
+    // Cascade
+    let cascade = CascadeImpl::empty().with_network(mock, cache.to_db());
+
+    assert_can_get(
+        &td_entry,
+        &td_record,
+        &cascade,
+        GetOptions::cache_then_network(),
+    )
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cache_then_network_falls_back_to_network() {
+    holochain_trace::test_run();
+
+    // Environments
+    let cache = test_cache_db();
+    let authority = test_dht_db();
+
+    // Data
+    let td_entry = EntryTestData::create();
+    let td_record = RecordTestData::create();
+    fill_db(&authority.to_db(), td_entry.store_entry_op.clone()).await;
+    fill_db(&authority.to_db(), td_record.any_store_record_op.clone()).await;
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.to_db().clone().into()]);
+
+    // Cascade
+    let cascade = CascadeImpl::empty().with_network(network, cache.to_db());
+
+    assert_can_get(
+        &td_entry,
+        &td_record,
+        &cascade,
+        GetOptions::cache_then_network(),
+    )
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn misses_are_negatively_cached() {
+    holochain_trace::test_run();
+
+    // Environments
+    let cache = test_cache_db();
+    let authority = test_dht_db();
+
+    // Data
+    let td_record = RecordTestData::create();
+    let hash: AnyDhtHash = td_record.any_action_hash.clone().into();
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.to_db().clone().into()]);
+
+    // Cascade
+    let negative_cache = NegativeCache::new(std::time::Duration::from_secs(60));
+    let cascade = CascadeImpl::empty()
+        .with_negative_cache(negative_cache.clone())
+        .with_network(network, cache.to_db());
+
+    // The authority holds nothing, so the miss is recorded.
+    let r = cascade
+        .dht_get(hash.clone(), GetOptions::network())
+        .await
+        .unwrap();
+    assert!(r.is_none());
+    assert!(negative_cache.contains(&hash));
+
+    // Once the authority has the data, the cached miss still skips the network.
+    fill_db(&authority.to_db(), td_record.any_store_record_op.clone()).await;
+    let r = cascade
+        .dht_get(hash.clone(), GetOptions::network())
+        .await
+        .unwrap();
+    assert!(r.is_none());
+
+    // Until it's invalidated.
+    negative_cache.invalidate([hash.clone()]);
+    let r = cascade
+        .dht_get(hash.clone(), GetOptions::network())
+        .await
+        .unwrap();
+    assert_eq!(
+        Some(&td_record.any_action_hash),
+        r.as_ref().map(|r| r.action_address())
+    );
+    assert!(!negative_cache.contains(&hash));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn content_authoring() {
     holochain_trace::test_run();
@@ -494,3 +605,96 @@ async fn test_pending_data_isnt_returned() {
 
     assert_can_retrieve(&td_entry, &cascade, GetOptions::network()).await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn warranted_authors() {
+    holochain_trace::test_run();
+
+    // Environments
+    let cache = test_cache_db();
+    let vault = test_authored_db();
+
+    // Data
+    let td_entry = EntryTestData::create();
+    let td_record = RecordTestData::create();
+    fill_db(&vault.to_db(), td_entry.store_entry_op.clone()).await;
+    fill_db(&vault.to_db(), td_record.any_store_record_op.clone()).await;
+
+    // - A valid warrant against the author of the record.
+    let author = td_record.any_record.action().author().clone();
+    let warrant = Warrant::ChainIntegrity(ChainIntegrityWarrant::InvalidChainOp {
+        action_author: author.clone(),
+        action: (td_record.any_action_hash.clone(), Signature([0; 64])),
+        validation_type: ValidationType::App,
+    });
+    let op = DhtOpHashed::from_content_sync(DhtOp::from(WarrantOp::new(
+        warrant,
+        AgentPubKey::from_raw_36(vec![1; 36]),
+        Signature([0; 64]),
+        Timestamp::now(),
+    )));
+    let signed_warrant = op.as_content().clone();
+    vault
+        .to_db()
+        .write_async(move |txn| -> DatabaseResult<()> {
+            let hash = op.to_hash();
+            insert_op(txn, &op).unwrap();
+            set_validation_status(txn, &hash, ValidationStatus::Valid).unwrap();
+            set_when_integrated(txn, &hash, Timestamp::now()).unwrap();
+            Ok(())
+        })
+        .await
+        .unwrap();
+    let signed_warrant = match signed_warrant {
+        DhtOp::WarrantOp(op) => op.into_signed_warrant(),
+        _ => unreachable!(),
+    };
+
+    // Network
+    // - Not expecting any calls to the network.
+    let mut mock = MockHolochainP2pDnaT::new();
+    mock.expect_authority_for_hash().returning(|_| Ok(true));
+    let mock = Arc::new(mock);
+
+    // Cascade
+    let cascade = CascadeImpl::empty()
+        .with_authored(vault.to_db().into())
+        .with_network(mock, cache.to_db());
+
+    assert_eq!(
+        cascade.get_warrants_against(author).await.unwrap(),
+        vec![signed_warrant.clone()]
+    );
+
+    // - Data by warranted authors is included by default.
+    assert_can_get(&td_entry, &td_record, &cascade, GetOptions::network()).await;
+
+    // - Annotating adds the warrant to the record details.
+    let options = GetOptions::network().with_warranted_authors(WarrantedAuthors::Annotate);
+    let r = cascade
+        .get_details(td_record.any_action_hash.clone().into(), options)
+        .await
+        .unwrap();
+    match r {
+        Some(Details::Record(details)) => assert_eq!(details.warrants, vec![signed_warrant]),
+        r => panic!("Expected record details, got {:?}", r),
+    }
+
+    // - Excluding leaves out the record, but not data by other authors.
+    let options = GetOptions::network().with_warranted_authors(WarrantedAuthors::Exclude);
+    let r = cascade
+        .dht_get(td_record.any_action_hash.clone().into(), options.clone())
+        .await
+        .unwrap();
+    assert!(r.is_none());
+    let r = cascade
+        .get_details(td_record.any_action_hash.clone().into(), options.clone())
+        .await
+        .unwrap();
+    assert!(r.is_none());
+    let r = cascade
+        .dht_get(td_entry.hash.clone().into(), options)
+        .await
+        .unwrap();
+    assert!(r.is_some());
+}
@@ -0,0 +1,261 @@
+use ::fixt::prelude::*;
+use holo_hash::ActionHash;
+use holo_hash::AnyLinkableHash;
+use holochain_cascade::test_utils::*;
+use holochain_cascade::CascadeImpl;
+use holochain_p2p::actor::GetLinksOptions;
+use holochain_state::prelude::*;
+
+fn secs(s: i64) -> Timestamp {
+    Timestamp::from_micros(s * 1_000_000)
+}
+
+/// Store a link on `base` authored at `at` seconds and integrated now.
+async fn create_link<Db: DbKindT + DbKindOp>(
+    db: &DbWrite<Db>,
+    base: &AnyLinkableHash,
+    at: i64,
+) -> ActionHash {
+    let mut create_link = fixt!(CreateLink);
+    create_link.base_address = base.clone();
+    create_link.zome_index = 0.into();
+    create_link.timestamp = secs(at);
+    let hash = ActionHash::with_data_sync(&Action::CreateLink(create_link.clone()));
+    let op =
+        ChainOpHashed::from_content_sync(ChainOp::RegisterAddLink(fixt!(Signature), create_link));
+    fill_db(db, op).await;
+    hash
+}
+
+/// Store a link on `base` authored at `at` seconds and integrated at
+/// `integrated`, as by an authority whose clock is behind.
+async fn create_link_integrated_at(
+    db: &DbWrite<DbKindDht>,
+    base: &AnyLinkableHash,
+    at: i64,
+    integrated: Timestamp,
+) -> ActionHash {
+    let mut create_link = fixt!(CreateLink);
+    create_link.base_address = base.clone();
+    create_link.zome_index = 0.into();
+    create_link.timestamp = secs(at);
+    let hash = ActionHash::with_data_sync(&Action::CreateLink(create_link.clone()));
+    let op =
+        ChainOpHashed::from_content_sync(ChainOp::RegisterAddLink(fixt!(Signature), create_link));
+    db.write_async(move |txn| -> DatabaseResult<()> {
+        let op_hash = op.to_hash();
+        insert_op(txn, &op.downcast()).unwrap();
+        set_validation_status(txn, &op_hash, ValidationStatus::Valid).unwrap();
+        set_when_integrated(txn, &op_hash, integrated).unwrap();
+        Ok(())
+    })
+    .await
+    .unwrap();
+    hash
+}
+
+/// Store a delete of the link created by `link_add_address`, authored at `at`
+/// seconds and integrated now.
+async fn delete_link(
+    db: &DbWrite<DbKindDht>,
+    base: &AnyLinkableHash,
+    link_add_address: ActionHash,
+    at: i64,
+) {
+    let mut delete_link = fixt!(DeleteLink);
+    delete_link.base_address = base.clone();
+    delete_link.link_add_address = link_add_address;
+    delete_link.timestamp = secs(at);
+    let op = ChainOpHashed::from_content_sync(ChainOp::RegisterRemoveLink(
+        fixt!(Signature),
+        delete_link,
+    ));
+    fill_db(db, op).await;
+}
+
+fn link_key(base: &AnyLinkableHash, since: Option<Timestamp>) -> WireLinkKey {
+    WireLinkKey {
+        base: base.clone(),
+        type_query: LinkTypeFilter::single_dep(0.into()),
+        tag: None,
+        tag_range: None,
+        tag_fields: Vec::new(),
+        after: None,
+        before: None,
+        author: None,
+        since,
+    }
+}
+
+fn hashes(links: &[Link]) -> Vec<ActionHash> {
+    links.iter().map(|l| l.create_link_hash.clone()).collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn links_delta_not_authority() {
+    holochain_trace::test_run();
+
+    // Environments
+    let cache = test_cache_db();
+    let authority = test_dht_db();
+
+    // Data
+    let base: AnyLinkableHash = fixt!(EntryHash).into();
+    let a = create_link(&authority.to_db(), &base, 1).await;
+    let b = create_link(&authority.to_db(), &base, 3).await;
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.to_db().clone().into()]);
+
+    // Cascade
+    let cascade = CascadeImpl::empty().with_network(network, cache.to_db());
+
+    let before = Timestamp::now();
+    let delta = cascade
+        .dht_get_links_delta(link_key(&base, None), Default::default())
+        .await
+        .unwrap();
+
+    assert_eq!(hashes(&delta.created), vec![a.clone(), b]);
+    assert!(delta.deleted.is_empty());
+    assert!(delta.watermark >= before);
+    let watermark = delta.watermark;
+
+    delete_link(&authority.to_db(), &base, a.clone(), 4).await;
+    let c = create_link(&authority.to_db(), &base, 5).await;
+    // Authored before the watermark, but only arrived since.
+    let d = create_link(&authority.to_db(), &base, 2).await;
+
+    // The links fetched for the previous delta aren't returned again.
+    let delta = cascade
+        .dht_get_links_delta(link_key(&base, Some(watermark)), Default::default())
+        .await
+        .unwrap();
+
+    assert_eq!(hashes(&delta.created), vec![d.clone(), c.clone()]);
+    assert_eq!(delta.deleted, vec![a]);
+    assert!(delta.watermark > watermark);
+
+    let delta = cascade
+        .dht_get_links_delta(link_key(&base, Some(delta.watermark)), Default::default())
+        .await
+        .unwrap();
+
+    assert!(delta.created.is_empty());
+    assert!(delta.deleted.is_empty());
+
+    // Plain gets only include links which arrived since the watermark.
+    let links = cascade
+        .dht_get_links(link_key(&base, Some(watermark)), Default::default())
+        .await
+        .unwrap();
+
+    assert_eq!(hashes(&links), vec![d, c]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn links_delta_tolerates_authorities_with_clocks_behind() {
+    holochain_trace::test_run();
+
+    let cache = test_cache_db();
+    let authority = test_dht_db();
+
+    let base: AnyLinkableHash = fixt!(EntryHash).into();
+    let a = create_link(&authority.to_db(), &base, 1).await;
+
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.to_db().clone().into()]);
+    let cascade = CascadeImpl::empty().with_network(network, cache.to_db());
+
+    let delta = cascade
+        .dht_get_links_delta(link_key(&base, None), Default::default())
+        .await
+        .unwrap();
+    assert_eq!(hashes(&delta.created), vec![a]);
+    let watermark = delta.watermark;
+
+    // Arrives after the delta, but the authority's clock puts it before the
+    // watermark.
+    let b = create_link_integrated_at(
+        &authority.to_db(),
+        &base,
+        2,
+        watermark.saturating_sub(&std::time::Duration::from_secs(60)),
+    )
+    .await;
+
+    // The overlap fetches `a` again, but it was already returned.
+    let delta = cascade
+        .dht_get_links_delta(link_key(&base, Some(watermark)), Default::default())
+        .await
+        .unwrap();
+    assert_eq!(hashes(&delta.created), vec![b]);
+
+    let delta = cascade
+        .dht_get_links_delta(link_key(&base, Some(delta.watermark)), Default::default())
+        .await
+        .unwrap();
+    assert!(delta.created.is_empty());
+    assert!(delta.deleted.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn links_delta_cache_then_network_fetches_when_cache_has_links() {
+    holochain_trace::test_run();
+
+    let cache = test_cache_db();
+    let authority = test_dht_db();
+
+    let base: AnyLinkableHash = fixt!(EntryHash).into();
+    let watermark = Timestamp::now();
+    let a = create_link(&cache.to_db(), &base, 1).await;
+    let b = create_link(&authority.to_db(), &base, 2).await;
+
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.to_db().clone().into()]);
+    let cascade = CascadeImpl::empty().with_network(network, cache.to_db());
+
+    // The local delta isn't empty, but the network still has links to add to it.
+    let delta = cascade
+        .dht_get_links_delta(
+            link_key(&base, Some(watermark)),
+            GetLinksOptions {
+                get_options: GetOptions::cache_then_network(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(hashes(&delta.created), vec![a, b]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn authority_only_responds_with_ops_integrated_since_watermark() {
+    holochain_trace::test_run();
+
+    let authority = test_dht_db();
+
+    let base: AnyLinkableHash = fixt!(EntryHash).into();
+    let a = create_link(&authority.to_db(), &base, 1).await;
+    create_link(&authority.to_db(), &base, 3).await;
+    let watermark = Timestamp::now();
+    delete_link(&authority.to_db(), &base, a, 4).await;
+    // Authored before the watermark, but only integrated since.
+    create_link(&authority.to_db(), &base, 2).await;
+
+    let ops = holochain_cascade::authority::handle_get_links(
+        authority.to_db().into(),
+        link_key(&base, Some(watermark)),
+        (&GetLinksOptions::default()).into(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        ops.creates.iter().map(|c| c.timestamp).collect::<Vec<_>>(),
+        vec![secs(2)]
+    );
+    assert_eq!(
+        ops.deletes.iter().map(|d| d.timestamp).collect::<Vec<_>>(),
+        vec![secs(4)]
+    );
+}
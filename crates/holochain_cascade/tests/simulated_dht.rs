@@ -0,0 +1,60 @@
+use holo_hash::AgentPubKey;
+use holo_hash::DnaHash;
+use holochain_cascade::test_utils::*;
+use holochain_cascade::CascadeImpl;
+use holochain_state::prelude::*;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn simulated_dht_routes_to_authorities() {
+    holochain_trace::test_run();
+
+    let agents: Vec<AgentPubKey> = (0..5u8)
+        .map(|i| AgentPubKey::from_raw_36(vec![i * 50; 36]))
+        .collect();
+    let dht = SimulatedDht::new(DnaHash::from_raw_36(vec![0; 36]), agents.clone(), 2);
+
+    // Data
+    let td = EntryTestData::create();
+    dht.publish(td.store_entry_op.clone().downcast()).await;
+    dht.publish(td.create_link_op.clone().downcast()).await;
+
+    // - Each op is only held by its authorities.
+    let authorities = dht.authorities_for(&td.hash.clone().into());
+    assert_eq!(authorities.len(), 2);
+    for agent in &agents {
+        let held = dht
+            .db(agent)
+            .unwrap()
+            .read_async(|txn| -> DatabaseResult<usize> {
+                Ok(txn.query_row("SELECT COUNT(*) FROM DhtOp", [], |row| row.get(0))?)
+            })
+            .await
+            .unwrap();
+        let expected = [&td.store_entry_op, &td.create_link_op]
+            .iter()
+            .filter(|op| dht.authorities_for(&op.dht_basis()).contains(agent))
+            .count();
+        assert_eq!(held, expected);
+    }
+
+    // - Every agent can get the data, whether or not they are an authority for it.
+    for agent in agents {
+        let cache = test_cache_db();
+        let cascade = CascadeImpl::empty()
+            .with_dht(dht.db(&agent).unwrap().into())
+            .with_network(dht.network(agent), cache.to_db());
+
+        let r = cascade
+            .dht_get(td.hash.clone().into(), GetOptions::network())
+            .await
+            .unwrap()
+            .expect("Failed to get entry");
+        assert_eq!(*r.action_address(), td.create_hash);
+
+        let r = cascade
+            .dht_get_links(td.link_key_tag.clone(), Default::default())
+            .await
+            .unwrap();
+        assert_eq!(r, td.links);
+    }
+}
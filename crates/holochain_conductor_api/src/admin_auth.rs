@@ -0,0 +1,225 @@
+//! Scoped credentials for admin interfaces.
+//!
+//! An admin interface configured with
+//! [`AdminInterfaceConfig::require_token`](crate::AdminInterfaceConfig::require_token)
+//! only accepts connections which authenticate with a token issued through
+//! [`AdminRequest::IssueAdminAuthenticationToken`]. Every request made over such a
+//! connection is checked against the [`AdminScope`] of its token, which allows
+//! handing a UI control over a single app without giving it the whole conductor.
+
+use crate::AdminRequest;
+use holochain_types::prelude::*;
+
+/// A token issued by the conductor that can be used to authenticate a connection to an
+/// admin interface.
+pub type AdminAuthenticationToken = Vec<u8>;
+
+/// What a connection authenticated with an admin token is allowed to do.
+///
+/// Issuing and revoking admin tokens is never permitted by a scope, so a scoped
+/// connection can't hand out credentials beyond its own.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum AdminScope {
+    /// Read the state of the conductor, its apps and cells, without changing anything.
+    ReadOnly,
+    /// Manage a single installed app: enable, disable and uninstall it, issue app
+    /// tokens for it and inspect or administer the cells which belong to it.
+    App {
+        /// The app the token is scoped to.
+        installed_app_id: InstalledAppId,
+    },
+}
+
+impl AdminScope {
+    /// Whether a request is allowed under this scope.
+    ///
+    /// `cell_in_scope` tells whether a cell belongs to the app of an
+    /// [`AdminScope::App`]. It isn't called for [`AdminScope::ReadOnly`].
+    pub fn permits(&self, request: &AdminRequest, cell_in_scope: impl Fn(&CellId) -> bool) -> bool {
+        use AdminRequest::*;
+        match self {
+            AdminScope::ReadOnly => matches!(
+                request,
+                GetDnaDefinition(_)
+                    | ListDnas
+                    | ListCellIds
+                    | ListApps { .. }
+                    | ListAppInterfaces
+                    | DumpState { .. }
+                    | DumpConductorState
+                    | DumpFullState { .. }
+                    | DumpStatePage { .. }
+                    | DumpNetworkMetrics { .. }
+                    | DumpNetworkStats
+                    | AgentInfo { .. }
+                    | ListPeers { .. }
+                    | StorageInfo
+                    | PendingDbMigrations
+                    | GetTracingFilter
+                    | ProbeBasis { .. }
+                    | EventJournal { .. }
+                    | QueryRegionSet { .. }
+                    | FetchOpDataByRegions { .. }
+                    | WorkflowStats { .. }
+                    | ListValidationLimbo { .. }
+                    | GetOpDependencyGraph { .. }
+                    | LoadSheddingStats
+                    | NonceAudit { .. }
+                    | ExportDhtArchive { .. }
+                    | SubscribeMetrics { .. }
+                    | SetWireEncoding { .. }
+            ),
+            AdminScope::App { installed_app_id } => match request {
+                UninstallApp {
+                    installed_app_id: id,
+                }
+                | EnableApp {
+                    installed_app_id: id,
+                }
+                | DisableApp {
+                    installed_app_id: id,
+                }
+                | SetAppNetworkMode {
+                    installed_app_id: id,
+                    ..
+                } => id == installed_app_id,
+                IssueAppAuthenticationToken(payload) => {
+                    &payload.installed_app_id == installed_app_id
+                }
+                DeleteCloneCell(payload) => &payload.app_id == installed_app_id,
+                FreezeCell { cell_id }
+                | UnfreezeCell { cell_id }
+                | GraftRecords { cell_id, .. }
+                | ImportLegacyChain { cell_id, .. }
+                | RenewMembraneProof { cell_id, .. }
+                | AgentInfo {
+                    cell_id: Some(cell_id),
+                } => cell_in_scope(cell_id),
+                DumpState { cell_id }
+                | DumpFullState { cell_id, .. }
+                | DumpStatePage { cell_id, .. } => cell_in_scope(cell_id),
+                GrantZomeCallCapability(payload) => cell_in_scope(&payload.cell_id),
+                SetWireEncoding { .. } => true,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Request payload for [`AdminRequest::IssueAdminAuthenticationToken`].
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct IssueAdminAuthenticationTokenPayload {
+    /// What connections authenticated with the token are allowed to do.
+    pub scope: AdminScope,
+
+    /// The number of seconds that the token should be valid for. Connections which
+    /// authenticated with the token are refused once it has expired.
+    ///
+    /// This is 0 by default, which creates a token that does not expire and stays
+    /// valid until it is revoked with [`AdminRequest::RevokeAdminAuthenticationToken`].
+    #[serde(default)]
+    pub expiry_seconds: u64,
+}
+
+impl IssueAdminAuthenticationTokenPayload {
+    /// Create a new payload for issuing a token with the given scope, which does not expire.
+    pub fn for_scope(scope: AdminScope) -> Self {
+        Self {
+            scope,
+            expiry_seconds: 0,
+        }
+    }
+
+    /// Set the expiry time for the token.
+    pub fn expiry_seconds(mut self, expiry_seconds: u64) -> Self {
+        self.expiry_seconds = expiry_seconds;
+        self
+    }
+}
+
+/// Response payload for [`AdminResponse::AdminAuthenticationTokenIssued`](crate::AdminResponse::AdminAuthenticationTokenIssued).
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct AdminAuthenticationTokenIssued {
+    /// The token, to be sent in an [`AdminAuthenticationRequest`] when connecting.
+    /// It should be treated as secret and kept from other parties.
+    pub token: AdminAuthenticationToken,
+
+    /// The timestamp after which Holochain will consider the token invalid, or `None`
+    /// if the token was created with an `expiry_seconds` of 0.
+    pub expires_at: Option<Timestamp>,
+}
+
+/// The message a client sends to authenticate a connection to an admin interface which
+/// requires a token.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct AdminAuthenticationRequest {
+    /// The token issued by [`AdminRequest::IssueAdminAuthenticationToken`].
+    pub token: AdminAuthenticationToken,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(n: u8) -> CellId {
+        CellId::new(
+            DnaHash::from_raw_32(vec![n; 32]),
+            AgentPubKey::from_raw_32(vec![n; 32]),
+        )
+    }
+
+    #[test]
+    fn read_only_scope_refuses_mutations() {
+        let scope = AdminScope::ReadOnly;
+        assert!(scope.permits(&AdminRequest::ListDnas, |_| false));
+        assert!(scope.permits(
+            &AdminRequest::DumpState {
+                cell_id: Box::new(cell(1))
+            },
+            |_| false
+        ));
+        assert!(!scope.permits(
+            &AdminRequest::DisableApp {
+                installed_app_id: "app".to_string()
+            },
+            |_| true
+        ));
+        assert!(!scope.permits(&AdminRequest::GenerateAgentPubKey, |_| true));
+    }
+
+    #[test]
+    fn app_scope_is_limited_to_its_app() {
+        let scope = AdminScope::App {
+            installed_app_id: "app".to_string(),
+        };
+        let in_app = |c: &CellId| c == &cell(1);
+
+        assert!(scope.permits(
+            &AdminRequest::DisableApp {
+                installed_app_id: "app".to_string()
+            },
+            in_app
+        ));
+        assert!(!scope.permits(
+            &AdminRequest::DisableApp {
+                installed_app_id: "other".to_string()
+            },
+            in_app
+        ));
+        assert!(scope.permits(&AdminRequest::FreezeCell { cell_id: cell(1) }, in_app));
+        assert!(!scope.permits(&AdminRequest::FreezeCell { cell_id: cell(2) }, in_app));
+        assert!(!scope.permits(
+            &AdminRequest::ListApps {
+                status_filter: None
+            },
+            in_app
+        ));
+        assert!(!scope.permits(
+            &AdminRequest::IssueAdminAuthenticationToken(
+                IssueAdminAuthenticationTokenPayload::for_scope(scope.clone())
+            ),
+            in_app
+        ));
+    }
+}
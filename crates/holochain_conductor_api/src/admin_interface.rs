@@ -3,8 +3,15 @@ use holochain_types::prelude::*;
 use holochain_types::websocket::AllowedOrigins;
 use holochain_zome_types::cell::CellId;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
+use kitsune_p2p_types::dht::prelude::{RegionCoords, RegionSetLtcs};
 
-use crate::{AppInfo, FullStateDump, StorageInfo};
+use crate::wire_encoding::WireEncoding;
+#[cfg(feature = "op_replay")]
+use crate::ValidationReplayReport;
+use crate::{
+    AppInfo, AuthorityProbe, DhtArchiveChunk, FullStateDump, LoadSheddingStats, NonceAudit,
+    PeerInfo, PendingDbMigration, StateDumpPage, StateDumpStore, StorageInfo, WorkflowStats,
+};
 
 /// Represents the available conductor functions to call over an admin interface.
 ///
@@ -37,6 +44,23 @@ pub enum AdminRequest {
     /// [`AdminResponse::DnaRegistered`]
     RegisterDna(Box<RegisterDnaPayload>),
 
+    /// Register a variant of an already registered DNA, derived from it by changing
+    /// its modifiers, such as the network seed or origin time.
+    ///
+    /// The variant reuses the wasm of the base DNA, so nothing needs to be uploaded
+    /// again. Once registered, it can be installed into apps by its hash like any
+    /// other registered DNA. Registering the same variant again returns the same hash.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::DnaVariantRegistered`]
+    RegisterDnaVariant {
+        /// The hash of the registered DNA to derive the variant from.
+        base_dna_hash: DnaHash,
+        /// The modifiers to override. At least one must be set.
+        modifiers: DnaModifiersOpt<YamlProperties>,
+    },
+
     /// Get the definition of a DNA.
     ///
     /// # Returns
@@ -50,11 +74,29 @@ pub enum AdminRequest {
     /// If the zome name doesn't exist then the coordinator zome is appended
     /// to the current list of coordinator zomes.
     ///
+    /// The new coordinators are compiled and smoke tested before they replace
+    /// the installed ones, so if any of them fails to compile or instantiate the
+    /// DNA keeps running its previous coordinators and an error is returned.
+    ///
     /// # Returns
     ///
     /// [`AdminResponse::CoordinatorsUpdated`]
     UpdateCoordinators(Box<UpdateCoordinatorsPayload>),
 
+    /// Go back to the coordinator zomes a DNA had before its last
+    /// [`AdminRequest::UpdateCoordinators`].
+    ///
+    /// Rolling back twice restores the update. The previous coordinators are only
+    /// kept until the conductor restarts.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::CoordinatorsRolledBack`]
+    RollbackCoordinators {
+        /// The DNA to roll back.
+        dna_hash: DnaHash,
+    },
+
     /// Install an app using an [`AppBundle`].
     ///
     /// Triggers genesis to be run on all Cells and to be stored.
@@ -148,6 +190,50 @@ pub enum AdminRequest {
         installed_app_id: InstalledAppId,
     },
 
+    /// Change how the cells of an app take part in the network, see [`AppNetworkMode`].
+    ///
+    /// The app keeps running and local reads and writes are unaffected. The mode
+    /// applies to the cells the app has when the call is made and is not persisted,
+    /// so every app is back in [`AppNetworkMode::Normal`] after the conductor restarts.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AppNetworkModeSet`]
+    SetAppNetworkMode {
+        /// The app ID to change the network mode of
+        installed_app_id: InstalledAppId,
+        /// The mode to put the app's cells in
+        mode: AppNetworkMode,
+    },
+
+    /// Freeze a running cell so that its state can be investigated without racing
+    /// ongoing mutations.
+    ///
+    /// Nothing can be written to the cell's source chain while it is frozen. Zome
+    /// calls which would commit anything fail with [`ExternalApiWireError::CellFrozen`],
+    /// countersigning sessions and grafting are refused, and the cell stops publishing,
+    /// while reads, gossip and incoming ops carry on as usual. The freeze is persisted,
+    /// so the cell stays frozen across conductor restarts until it is unfrozen.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::CellFrozen`]
+    FreezeCell {
+        /// The cell to freeze
+        cell_id: CellId,
+    },
+
+    /// Unfreeze a cell frozen with [`AdminRequest::FreezeCell`]. Anything the cell
+    /// authored before it was frozen and hadn't yet published is published.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::CellUnfrozen`]
+    UnfreezeCell {
+        /// The cell to unfreeze
+        cell_id: CellId,
+    },
+
     /// Open up a new websocket for processing [`AppRequest`]s. Any active app will be
     /// callable via the attached app interface.
     ///
@@ -245,6 +331,30 @@ pub enum AdminRequest {
         dht_ops_cursor: Option<u64>,
     },
 
+    /// Dump one page of a single store of the Cell specified by argument `cell_id`.
+    ///
+    /// Unlike [`AdminRequest::DumpFullState`], this returns at most `limit` items,
+    /// so a store of any size can be inspected incrementally: pass the `next_cursor`
+    /// of each page as the `cursor` of the next request until it is `None`.
+    ///
+    /// **Warning**: this API call is subject to change, and will not be available to hApps.
+    /// This is meant to be used by introspection tooling.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::StateDumpPage`]
+    DumpStatePage {
+        /// The cell ID for which to dump the store
+        cell_id: Box<CellId>,
+        /// The store to dump
+        store: StateDumpStore,
+        /// The cursor returned with the previous page, or `None` to start
+        /// from the beginning of the store.
+        cursor: Option<u64>,
+        /// The most items to return, up to [`MAX_STATE_DUMP_PAGE_LIMIT`](crate::MAX_STATE_DUMP_PAGE_LIMIT).
+        limit: u32,
+    },
+
     /// Dump the network metrics tracked by kitsune.
     ///
     /// # Returns
@@ -292,6 +402,36 @@ pub enum AdminRequest {
         cell_id: Option<CellId>,
     },
 
+    /// List every agent info in the peer store of a DNA, along with whether
+    /// each peer is still live.
+    ///
+    /// Unlike [`AdminRequest::AgentInfo`], this includes agent infos which have
+    /// expired or which advertise no urls to reach the peer at.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::PeersListed`]
+    ListPeers {
+        /// The DNA whose peer store to list.
+        dna_hash: DnaHash,
+    },
+
+    /// Remove expired agent infos from the peer stores of all DNAs.
+    ///
+    /// Agent infos which have not expired are never removed. This includes
+    /// those which advertise no urls to reach the peer at, which are left by
+    /// peers that leave a space and are kept until they expire so the peer's
+    /// older agent infos aren't gossiped back in.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::PeersPruned`]
+    PrunePeers {
+        /// Agent infos which had expired by this time are removed.
+        /// A time in the future is treated as now.
+        expired_by: Timestamp,
+    },
+
     /// "Graft" [`Record`]s onto the source chain of the specified [`CellId`].
     ///
     /// The records must form a valid chain segment (ascending sequence numbers,
@@ -343,6 +483,36 @@ pub enum AdminRequest {
         records: Vec<Record>,
     },
 
+    /// Import a source chain written by the LMDB-era conductor into a cell which
+    /// hasn't run genesis yet.
+    ///
+    /// The legacy chain starts with its own genesis. Register the DNA, import the
+    /// chain into the cell of that DNA and the chain's agent, and then install the
+    /// app for that agent, which skips genesis for the imported chain.
+    ///
+    /// The legacy chain is rejected unless every header matches its hash, is signed by
+    /// the cell's agent and follows the header before it. It is then converted to
+    /// current actions, which the cell's agent signs again, and the records are
+    /// written to the cell's source chain as with [`AdminRequest::GraftRecords`],
+    /// validating them first.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::LegacyChainImported`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cell has already run genesis.
+    ImportLegacyChain {
+        /// The cell that the legacy chain is imported into.
+        cell_id: CellId,
+        /// The legacy chain, as read from the cell's LMDB databases.
+        chain: LegacyChain,
+        /// The link type given to the links of each zome. Legacy links had no
+        /// type, and a link in a zome missing from this map fails the import.
+        link_types: std::collections::HashMap<ZomeIndex, LinkType>,
+    },
+
     /// Request capability grant for making zome calls.
     ///
     /// # Returns
@@ -350,6 +520,22 @@ pub enum AdminRequest {
     /// [`AdminResponse::ZomeCallCapabilityGranted`]
     GrantZomeCallCapability(Box<GrantZomeCallCapabilityPayload>),
 
+    /// Renew the membrane proof of a cell's agent, e.g. because the proof
+    /// the agent joined the network with has expired.
+    ///
+    /// Commits a [`RenewMembraneProof`] action which supersedes the agent's
+    /// current membrane proof, and publishes it to the network.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::MembraneProofRenewed`] with the hash of the renewal.
+    RenewMembraneProof {
+        /// The cell whose agent's membrane proof is renewed.
+        cell_id: CellId,
+        /// The membrane proof which supersedes the current one.
+        membrane_proof: MembraneProof,
+    },
+
     /// Delete a clone cell that was previously disabled.
     ///
     /// # Returns
@@ -360,6 +546,34 @@ pub enum AdminRequest {
     /// Info about storage used by apps
     StorageInfo,
 
+    /// List the databases with schema migrations that haven't run yet, without
+    /// running them. Databases are migrated when they are opened, so this reports
+    /// databases which haven't been opened since the conductor was upgraded,
+    /// such as those of disabled apps.
+    ///
+    /// Before a database is migrated a snapshot of it is saved next to it,
+    /// which is restored automatically if the migration fails.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::PendingDbMigrations`]
+    PendingDbMigrations,
+
+    /// Take a consistent snapshot of every database the conductor has open, using the
+    /// SQLite online backup API. Workflows keep running while the snapshot is taken.
+    ///
+    /// The snapshot is written to a directory with the same layout as the databases
+    /// root, which must not exist or be empty. Start the conductor with
+    /// `--restore-snapshot <path>` to restore from it.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::SnapshotTaken`]
+    Snapshot {
+        /// The directory to write the snapshot to.
+        path: std::path::PathBuf,
+    },
+
     /// Connecting to an app over an app websocket requires an authentication token. This endpoint
     /// is used to issue those tokens for use by app clients.
     ///
@@ -374,6 +588,300 @@ pub enum AdminRequest {
     ///
     /// [`AdminResponse::AppAuthenticationTokenRevoked`]
     RevokeAppAuthenticationToken(AppAuthenticationToken),
+
+    /// Issue a token for connecting to an admin interface which requires one, see
+    /// [`AdminInterfaceConfig::require_token`](crate::AdminInterfaceConfig::require_token).
+    ///
+    /// Requests made over a connection authenticated with the token are limited to its
+    /// [`AdminScope`](crate::AdminScope). This call is only available to connections
+    /// which aren't limited by a scope.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AdminAuthenticationTokenIssued`]
+    IssueAdminAuthenticationToken(crate::IssueAdminAuthenticationTokenPayload),
+
+    /// Revoke an issued admin authentication token. Connections which authenticated with
+    /// the token are refused from their next request on.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AdminAuthenticationTokenRevoked`]
+    RevokeAdminAuthenticationToken(crate::AdminAuthenticationToken),
+
+    /// Subscribe this admin connection to a stream of conductor throughput
+    /// metrics. A sample is pushed as an [`AdminSignal::Metrics`](crate::AdminSignal::Metrics) every
+    /// `interval` until the connection is closed.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::MetricsSubscribed`]
+    SubscribeMetrics {
+        /// How often to push a sample, in milliseconds. Must be greater than zero.
+        interval: u64,
+    },
+
+    /// Choose how the responses to later requests on this connection are encoded,
+    /// see [`WireEncoding`](crate::wire_encoding::WireEncoding).
+    ///
+    /// The response to this request is sent in the previous encoding.
+    /// This is best requested straight after connecting, since responses to requests
+    /// which are already being handled may be sent in either encoding.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::WireEncodingSet`]
+    SetWireEncoding {
+        /// The encoding to use for later responses.
+        encoding: WireEncoding,
+    },
+
+    /// Replace the conductor's log filter without restarting it.
+    ///
+    /// The filter uses the same directives as `RUST_LOG`, e.g. `warn,holochain::core::workflow=debug`,
+    /// and replaces the whole filter. If any directive is invalid the filter is left unchanged
+    /// and an error is returned. The change lasts until the conductor is restarted.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::TracingFilterSet`]
+    SetTracingFilter {
+        /// The new filter directives.
+        filter: String,
+    },
+
+    /// Get the conductor's current log filter.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::TracingFilter`]
+    GetTracingFilter,
+
+    /// Actively probe the authorities for a basis, to debug data which can't be found.
+    ///
+    /// The authorities are the agents in this conductor's peer store whose storage arc
+    /// covers the basis. Each one is asked how many integrated ops it holds for the basis,
+    /// and the round-trip time is measured. Authorities which don't respond within the
+    /// network's `default_rpc_single_timeout_ms` are reported with the error.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::BasisProbed`]
+    ProbeBasis {
+        /// The DNA whose network to probe.
+        dna_hash: DnaHash,
+        /// The basis hash the authorities are found for.
+        basis: AnyLinkableHash,
+    },
+
+    /// Get the conductor's event journal, an audit trail of the changes made to its
+    /// configuration: apps being installed, enabled, disabled and uninstalled, cells being
+    /// created, app interfaces being attached, database migrations and crashes.
+    ///
+    /// The journal is stored in the conductor database, so it persists across restarts.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::EventJournal`]
+    EventJournal {
+        /// Only events recorded at or after this time are returned.
+        since: Timestamp,
+    },
+
+    /// Summarise the ops this conductor holds for a DNA as a set of regions of the DHT,
+    /// grouped by location and time, for external backup and replication tools.
+    ///
+    /// Each region carries a hash of its ops along with their count and total size, so
+    /// the region sets of two conductors can be compared with [`RegionSetLtcs::diff`] to
+    /// find the regions whose ops differ. Those ops can then be fetched with
+    /// [`AdminRequest::FetchOpDataByRegions`] and copied across, rather than gossiping
+    /// the whole space.
+    ///
+    /// The region set covers the full DHT arc, and includes ops which are still being
+    /// validated, in the same way as the region sets used for gossip.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::RegionSetQueried`]
+    QueryRegionSet {
+        /// The DNA whose ops to summarise.
+        dna_hash: DnaHash,
+    },
+
+    /// Fetch the ops this conductor holds for a DNA within the given regions of the DHT,
+    /// typically the regions found by diffing two [`AdminRequest::QueryRegionSet`] results.
+    ///
+    /// Only integrated ops are returned.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::OpDataByRegionsFetched`]
+    FetchOpDataByRegions {
+        /// The DNA whose ops to fetch.
+        dna_hash: DnaHash,
+        /// The regions to fetch ops for.
+        regions: Vec<RegionCoords>,
+    },
+
+    /// Get a snapshot of the work queues of a DNA: how many ops are waiting for system
+    /// validation, app validation, integration and publishing, how long the oldest of them
+    /// has been waiting, and how long the workflows consuming each queue take to run.
+    ///
+    /// The same figures are reported continuously through the metrics subsystem.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::WorkflowStats`]
+    WorkflowStats {
+        /// The DNA whose work queues to inspect.
+        dna_hash: DnaHash,
+    },
+
+    /// List the ops of a DNA which haven't been integrated yet, with the stage of
+    /// validation each has reached, the dependencies it was last found to be missing
+    /// and how many times it has been through a validation workflow.
+    ///
+    /// The ops which have been attempted the most come first, as they are the most
+    /// likely to be stuck.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ValidationLimboListed`]
+    ListValidationLimbo {
+        /// The DNA whose ops to list.
+        dna_hash: DnaHash,
+    },
+
+    /// Get the graph of what ops of a DNA are waiting for, to debug ops which are
+    /// stuck in validation limbo.
+    ///
+    /// Each op points at the actions and entries the validation workflows last found
+    /// it to be missing, and at the action whose ops it needs integrated first. Each
+    /// of those points at the ops which hold it, which are followed in turn, so a
+    /// chain of stalled ops can be traced back to whatever is holding it up.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::OpDependencyGraph`]
+    GetOpDependencyGraph {
+        /// The DNA whose ops to graph.
+        dna_hash: DnaHash,
+        /// The ops to start the graph from.
+        op_hashes: Vec<DhtOpHash>,
+    },
+
+    /// Revalidate ops of a DNA which are stuck in validation limbo as soon as possible,
+    /// for example once their missing dependencies are known to be available.
+    ///
+    /// The validation attempts of each op are reset, which puts it at the front of the
+    /// sys and app validation queues, and both validation workflows are triggered.
+    /// Ops which have been integrated or rejected, or aren't held at all, are ignored.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ValidationRetried`]
+    RetryValidation {
+        /// The DNA whose ops to revalidate.
+        dna_hash: DnaHash,
+        /// The ops to revalidate.
+        op_hashes: Vec<DhtOpHash>,
+    },
+
+    /// Get the state of the conductor's load shedding: whether it's currently refusing
+    /// low priority requests from other peers because its workflows are lagging, and how
+    /// many requests it has refused so far.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::LoadSheddingStats`]
+    LoadSheddingStats,
+
+    /// Audit the conductor's protection against replayed zome calls: the latest nonce
+    /// witnessed for each agent, which is rejected if it's used again before it expires,
+    /// and the zome calls whose nonce was rejected as a possible replay.
+    ///
+    /// Rejected nonces are recorded in the event journal, so they persist across restarts.
+    /// Whether the nonce windows do too is set by the `persist_nonce_windows` tuning param.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::NonceAudit`]
+    NonceAudit {
+        /// Only audit the nonces of this agent, or of every agent if `None`.
+        agent: Option<AgentPubKey>,
+        /// Only nonces rejected at or after this time are returned.
+        since: Timestamp,
+    },
+
+    /// Export the integrated ops this conductor holds for a DNA as a DHT archive, a
+    /// content-addressed archive with an index keyed by op hash, for external archival
+    /// systems or for seeding another conductor offline with [`AdminRequest::ImportDhtArchive`].
+    ///
+    /// The archive is returned in chunks of at most [`DHT_ARCHIVE_CHUNK_SIZE`] bytes.
+    /// A request at offset 0 writes a new archive into a file in the conductor's data
+    /// directory and returns its first chunk, replacing any export of the DNA which is
+    /// in progress. Each following chunk is requested at the offset where the previous
+    /// one ended. The file is removed once the last chunk has been returned.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::DhtArchiveExported`]
+    ExportDhtArchive {
+        /// The DNA whose ops to export.
+        dna_hash: DnaHash,
+        /// The offset of the chunk to return.
+        offset: u64,
+    },
+
+    /// Import the ops in a DHT archive, as exported by [`AdminRequest::ExportDhtArchive`].
+    ///
+    /// The archive is sent in chunks, which are written into a file in the conductor's
+    /// data directory. The first chunk is sent at offset 0, which discards any import of
+    /// the DNA which is in progress, and each following chunk at the offset where the
+    /// previous one ended.
+    ///
+    /// Once the last chunk has been received, the archive is rejected if it was exported
+    /// for another DNA, or if any op in it doesn't match its hash. The ops are then read
+    /// from the file in batches and validated before they are integrated, in the same way
+    /// as ops which are published to this conductor.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::DhtArchiveChunkImported`] for every chunk but the last, and
+    /// [`AdminResponse::DhtArchiveImported`] for the last one.
+    ImportDhtArchive {
+        /// The DNA whose ops the archive holds.
+        dna_hash: DnaHash,
+        /// The offset of this chunk in the archive.
+        offset: u64,
+        /// The bytes of this chunk.
+        chunk: Bytes,
+        /// Whether this is the last chunk of the archive.
+        last: bool,
+    },
+
+    /// Validate the ops in a DHT archive again against a DNA installed on this conductor,
+    /// and report every op whose outcome differs from the validation status recorded
+    /// for it, to track down validation regressions between DNA versions.
+    ///
+    /// The ops are validated in an isolated, in-memory workspace, so nothing is written
+    /// to this conductor's databases. The DNA may differ from the one the archive was
+    /// exported for, in which case the genesis ops of the archive are rejected because
+    /// they name the other DNA. App validation may fetch dependencies which aren't in
+    /// the archive from the network of the DNA being validated against.
+    ///
+    /// Only available when the conductor is built with the `op_replay` feature.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ValidationReplayed`]
+    #[cfg(feature = "op_replay")]
+    ReplayValidation {
+        /// The DNA to validate the ops against.
+        dna_hash: DnaHash,
+        /// The bytes of the archive, as exported by [`AdminRequest::ExportDhtArchive`].
+        archive: Bytes,
+    },
 }
 
 /// Represents the possible responses to an [`AdminRequest`]
@@ -395,12 +903,20 @@ pub enum AdminResponse {
     /// The successful response to an [`AdminRequest::RegisterDna`]
     DnaRegistered(DnaHash),
 
+    /// The successful response to an [`AdminRequest::RegisterDnaVariant`].
+    ///
+    /// Contains the hash of the variant.
+    DnaVariantRegistered(DnaHash),
+
     /// The successful response to an [`AdminRequest::GetDnaDefinition`]
     DnaDefinitionReturned(DnaDef),
 
     /// The successful response to an [`AdminRequest::UpdateCoordinators`]
     CoordinatorsUpdated,
 
+    /// The successful response to an [`AdminRequest::RollbackCoordinators`]
+    CoordinatorsRolledBack,
+
     /// The successful response to an [`AdminRequest::InstallApp`].
     ///
     /// The resulting [`AppInfo`] contains the app ID,
@@ -464,6 +980,15 @@ pub enum AdminResponse {
     /// It means the app was disabled successfully.
     AppDisabled,
 
+    /// The successful response to an [`AdminRequest::SetAppNetworkMode`].
+    AppNetworkModeSet,
+
+    /// The successful response to an [`AdminRequest::FreezeCell`].
+    CellFrozen,
+
+    /// The successful response to an [`AdminRequest::UnfreezeCell`].
+    CellUnfrozen,
+
     /// The successful response to an [`AdminRequest::DumpState`].
     ///
     /// The result contains a string of serialized JSON data which can be deserialized to access the
@@ -478,6 +1003,11 @@ pub enum AdminResponse {
     /// Note that this result can be very big, as it's requesting the full database of the cell.
     FullStateDumped(FullStateDump),
 
+    /// The successful response to an [`AdminRequest::DumpStatePage`].
+    ///
+    /// The page holds the items of the requested store and the cursor for the next page.
+    StateDumpPage(StateDumpPage),
+
     /// The successful response to an [`AdminRequest::DumpConductorState`].
     ///
     /// Simply a JSON serialized snapshot of `Conductor` and `ConductorState` from the `holochain` crate.
@@ -504,23 +1034,133 @@ pub enum AdminResponse {
     /// This is all the agent info that was found for the request.
     AgentInfo(Vec<AgentInfoSigned>),
 
+    /// The successful response to an [`AdminRequest::ListPeers`].
+    ///
+    /// This is every agent info in the peer store of the DNA.
+    PeersListed(Vec<PeerInfo>),
+
+    /// The successful response to an [`AdminRequest::PrunePeers`].
+    ///
+    /// This is the number of agent infos which were removed.
+    PeersPruned(usize),
+
     /// The successful response to an [`AdminRequest::GraftRecords`].
     RecordsGrafted,
 
+    /// The successful response to an [`AdminRequest::ImportLegacyChain`].
+    ///
+    /// Contains the number of records which were imported.
+    LegacyChainImported(usize),
+
     /// The successful response to an [`AdminRequest::GrantZomeCallCapability`].
     ZomeCallCapabilityGranted,
 
+    /// The successful response to an [`AdminRequest::RenewMembraneProof`].
+    ///
+    /// Contains the hash of the [`RenewMembraneProof`] action.
+    MembraneProofRenewed(ActionHash),
+
     /// The successful response to an [`AdminRequest::DeleteCloneCell`].
     CloneCellDeleted,
 
     /// The successful response to an [`AdminRequest::StorageInfo`].
     StorageInfo(StorageInfo),
 
+    /// The successful response to an [`AdminRequest::PendingDbMigrations`].
+    PendingDbMigrations(Vec<PendingDbMigration>),
+
+    /// The successful response to an [`AdminRequest::Snapshot`].
+    ///
+    /// Contains the paths of the database files in the snapshot.
+    SnapshotTaken(Vec<std::path::PathBuf>),
+
     /// The successful response to an [`AdminRequest::IssueAppAuthenticationToken`].
     AppAuthenticationTokenIssued(AppAuthenticationTokenIssued),
 
     /// The successful response to an [`AdminRequest::RevokeAppAuthenticationToken`].
     AppAuthenticationTokenRevoked,
+
+    /// The successful response to an [`AdminRequest::IssueAdminAuthenticationToken`].
+    AdminAuthenticationTokenIssued(crate::AdminAuthenticationTokenIssued),
+
+    /// The successful response to an [`AdminRequest::RevokeAdminAuthenticationToken`].
+    AdminAuthenticationTokenRevoked,
+
+    /// The successful response to an [`AdminRequest::SubscribeMetrics`].
+    ///
+    /// Metrics samples will be sent as [`AdminSignal::Metrics`](crate::AdminSignal::Metrics) signals on this connection.
+    MetricsSubscribed,
+
+    /// The successful response to an [`AdminRequest::SetWireEncoding`].
+    WireEncodingSet,
+
+    /// The successful response to an [`AdminRequest::SetTracingFilter`].
+    TracingFilterSet,
+
+    /// The successful response to an [`AdminRequest::GetTracingFilter`].
+    ///
+    /// `None` if the conductor's logging was not set up with a filter that can be changed.
+    TracingFilter(Option<String>),
+
+    /// The successful response to an [`AdminRequest::ProbeBasis`].
+    ///
+    /// Contains one probe per authority, which is empty if the peer
+    /// store doesn't know of any authorities for the basis.
+    BasisProbed(Vec<AuthorityProbe>),
+
+    /// The successful response to an [`AdminRequest::EventJournal`].
+    ///
+    /// Contains the matching journal entries, oldest first.
+    EventJournal(Vec<EventJournalEntry>),
+
+    /// The successful response to an [`AdminRequest::QueryRegionSet`].
+    RegionSetQueried(RegionSetLtcs),
+
+    /// The successful response to an [`AdminRequest::FetchOpDataByRegions`].
+    ///
+    /// Contains the hash and content of each op found in the regions.
+    OpDataByRegionsFetched(Vec<(DhtOpHash, DhtOp)>),
+
+    /// The successful response to an [`AdminRequest::WorkflowStats`].
+    WorkflowStats(WorkflowStats),
+
+    /// The successful response to an [`AdminRequest::ListValidationLimbo`].
+    ValidationLimboListed(Vec<ValidationLimboOp>),
+
+    /// The successful response to an [`AdminRequest::GetOpDependencyGraph`].
+    OpDependencyGraph(OpDependencyGraph),
+
+    /// The successful response to an [`AdminRequest::RetryValidation`].
+    ///
+    /// Contains the hashes of the ops which will be revalidated.
+    ValidationRetried(Vec<DhtOpHash>),
+
+    /// The successful response to an [`AdminRequest::LoadSheddingStats`].
+    LoadSheddingStats(LoadSheddingStats),
+
+    /// The successful response to an [`AdminRequest::NonceAudit`].
+    NonceAudit(NonceAudit),
+
+    /// The successful response to an [`AdminRequest::ExportDhtArchive`].
+    ///
+    /// Contains the requested chunk of the archive.
+    DhtArchiveExported(DhtArchiveChunk),
+
+    /// The successful response to an [`AdminRequest::ImportDhtArchive`] which isn't
+    /// the last chunk of the archive.
+    ///
+    /// Contains the number of bytes of the archive received so far.
+    DhtArchiveChunkImported(u64),
+
+    /// The successful response to an [`AdminRequest::ImportDhtArchive`] with the last
+    /// chunk of the archive.
+    ///
+    /// Contains the number of ops in the archive which were queued for validation.
+    DhtArchiveImported(usize),
+
+    /// The successful response to an [`AdminRequest::ReplayValidation`].
+    #[cfg(feature = "op_replay")]
+    ValidationReplayed(ValidationReplayReport),
 }
 
 /// Error type that goes over the websocket wire.
@@ -546,6 +1186,13 @@ pub enum ExternalApiWireError {
     ZomeCallUnauthorized(String),
     /// A countersigning session has failed.
     CountersigningSessionError(String),
+    /// The zome call was cancelled because it didn't finish within its timeout.
+    ZomeCallTimeout(String),
+    /// The zome call would have committed to a cell which is frozen.
+    CellFrozen(String),
+    /// The admin request isn't permitted by the scope of the token the connection
+    /// authenticated with.
+    AdminCallUnauthorized(String),
 }
 
 impl ExternalApiWireError {
@@ -567,6 +1214,34 @@ pub enum AppStatusFilter {
     Paused,
 }
 
+/// How the cells of an app take part in the network, set with
+/// [`AdminRequest::SetAppNetworkMode`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppNetworkMode {
+    /// Gossip and publish as usual.
+    #[default]
+    Normal,
+    /// Publish new data but don't gossip.
+    PublishOnly,
+    /// Neither gossip nor publish. Data authored while isolated is published
+    /// once the app goes back to [`AppNetworkMode::Normal`] or
+    /// [`AppNetworkMode::PublishOnly`].
+    Isolated,
+}
+
+impl AppNetworkMode {
+    /// Whether cells in this mode gossip.
+    pub fn gossip(&self) -> bool {
+        matches!(self, AppNetworkMode::Normal)
+    }
+
+    /// Whether cells in this mode publish the data they author.
+    pub fn publish(&self) -> bool {
+        !matches!(self, AppNetworkMode::Isolated)
+    }
+}
+
 /// Informational response for listing app interfaces.
 #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone)]
 pub struct AppInterfaceInfo {
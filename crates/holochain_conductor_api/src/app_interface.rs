@@ -1,9 +1,11 @@
-use crate::{AppAuthenticationToken, ExternalApiWireError};
+use crate::wire_encoding::WireEncoding;
+use crate::{AppAuthenticationToken, ExternalApiWireError, Neighborhood};
 use holo_hash::AgentPubKey;
 use holochain_keystore::LairResult;
 use holochain_keystore::MetaLairClient;
 use holochain_types::prelude::*;
 use kitsune_p2p_types::fetch_pool::FetchPoolInfo;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 
 /// Represents the available conductor functions to call over an app interface
@@ -70,12 +72,97 @@ pub enum AppRequest {
     /// [`AppResponse::NetworkInfo`]
     NetworkInfo(Box<NetworkInfoRequestPayload>),
 
+    /// Get the validation receipts which this conductor has received for
+    /// each op of an action authored by one of the app's cells, so the
+    /// author can see that the action has been published and validated.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::PublicationProof`]
+    PublicationProof(Box<PublicationProofRequestPayload>),
+
+    /// List the peers whose storage arcs cover the location of one of the app's
+    /// cells, so that its agent can tell whether anyone it is connected to holds
+    /// its data.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::Neighborhood`]
+    Neighborhood {
+        /// The cell to list the neighbors of.
+        cell_id: CellId,
+    },
+
+    /// List the functions each coordinator zome of one of the app's cells
+    /// exposes to zome calls, leaving out callbacks like `init`.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::ZomeFunctionsListed`]
+    ListZomeFunctions {
+        /// The cell to list the zome functions of.
+        cell_id: CellId,
+    },
+
     /// List all host functions available to wasm on this conductor.
     ///
     /// # Returns
     ///
     /// [`AppResponse::ListWasmHostFunctions`]
     ListWasmHostFunctions,
+
+    /// Fetch the signals emitted by the app after `since_token`, so that a
+    /// client which was disconnected can catch up on what it missed.
+    ///
+    /// Signals are only persisted if the app was installed with a
+    /// `missed_signals_buffer_size`. If no token is given, every signal
+    /// still persisted is returned.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::MissedSignalsFetched`]
+    FetchMissedSignals { since_token: Option<u64> },
+
+    /// Only forward the app signals which match a filter to this connection,
+    /// rather than every signal the app emits.
+    ///
+    /// Subscribing again adds another filter, and signals matching any of the
+    /// filters are forwarded. A connection without any filters receives every
+    /// signal. System signals are always forwarded.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::SignalsSubscribed`]
+    SubscribeSignals {
+        /// Only forward signals emitted by this zome.
+        zome: Option<ZomeName>,
+        /// Only forward signals whose type matches this pattern,
+        /// see [`SignalFilter::pattern`](crate::signal_subscription::SignalFilter::pattern).
+        pattern: Option<String>,
+    },
+
+    /// Remove all the signal filters of this connection,
+    /// so that it receives every signal again.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::SignalsUnsubscribed`]
+    UnsubscribeSignals,
+
+    /// Choose how the responses to later requests on this connection are encoded,
+    /// see [`WireEncoding`](crate::wire_encoding::WireEncoding).
+    ///
+    /// The response to this request is sent in the previous encoding.
+    /// This is best requested straight after authenticating, since responses to requests
+    /// which are already being handled may be sent in either encoding.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::WireEncodingSet`]
+    SetWireEncoding {
+        /// The encoding to use for later responses.
+        encoding: WireEncoding,
+    },
 }
 
 /// Represents the possible responses to an [`AppRequest`].
@@ -119,8 +206,38 @@ pub enum AppResponse {
     /// NetworkInfo is returned
     NetworkInfo(Vec<NetworkInfo>),
 
+    /// The successful response to an [`AppRequest::PublicationProof`].
+    ///
+    /// The proof is empty of receipts if none have been received yet.
+    PublicationProof(PublicationProof),
+
+    /// The successful response to an [`AppRequest::Neighborhood`].
+    ///
+    /// Live neighbors are listed first, followed by those with the largest arcs.
+    Neighborhood(Neighborhood),
+
+    /// The successful response to an [`AppRequest::ListZomeFunctions`].
+    ///
+    /// The functions are sorted by name for each coordinator zome.
+    ZomeFunctionsListed(BTreeMap<ZomeName, Vec<FunctionName>>),
+
     /// All the wasm host functions supported by this conductor.
     ListWasmHostFunctions(Vec<String>),
+
+    /// The successful response to an [`AppRequest::FetchMissedSignals`].
+    ///
+    /// `truncated` is set if some signals after the requested token have
+    /// already been evicted from the buffer.
+    MissedSignalsFetched(MissedSignals),
+
+    /// The successful response to an [`AppRequest::SubscribeSignals`].
+    SignalsSubscribed,
+
+    /// The successful response to an [`AppRequest::UnsubscribeSignals`].
+    SignalsUnsubscribed,
+
+    /// The successful response to an [`AppRequest::SetWireEncoding`].
+    WireEncodingSet,
 }
 
 /// The data provided over an app interface in order to make a zome call
@@ -146,6 +263,24 @@ pub struct ZomeCall {
     pub signature: Signature,
     pub nonce: Nonce256Bits,
     pub expires_at: Timestamp,
+    /// Opt into waiting for publication of the data committed by this call.
+    ///
+    /// If set, the call will not return until every action it committed has been
+    /// accepted by at least one DHT authority, or until this many milliseconds
+    /// have passed, whichever comes first. The data is committed either way, so
+    /// hitting the timeout does not turn the call into an error. The wait is
+    /// capped by the conductor's `max_wait_for_publish` tuning param.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_for_publish: Option<u64>,
+    /// Cancel the call if it hasn't finished after this many milliseconds.
+    ///
+    /// Overrides the `zome_call_timeout_ms` the app was installed with.
+    /// A call which is cancelled returns [`ExternalApiWireError::ZomeCallTimeout`]
+    /// and commits nothing.
+    ///
+    /// This field is not covered by the signature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
 }
 
 impl From<ZomeCall> for ZomeCallUnsigned {
@@ -159,6 +294,7 @@ impl From<ZomeCall> for ZomeCallUnsigned {
             provenance: zome_call.provenance,
             nonce: zome_call.nonce,
             expires_at: zome_call.expires_at,
+            wait_for_publish: zome_call.wait_for_publish,
         }
     }
 }
@@ -187,6 +323,8 @@ impl ZomeCall {
             nonce: unsigned_zome_call.nonce,
             expires_at: unsigned_zome_call.expires_at,
             signature,
+            wait_for_publish: unsigned_zome_call.wait_for_publish,
+            timeout_ms: None,
         })
     }
 
@@ -204,8 +342,12 @@ impl ZomeCall {
             payload: self.payload,
             nonce: self.nonce,
             expires_at: self.expires_at,
+            wait_for_publish: self.wait_for_publish,
         };
-        ZomeCall::try_from_unsigned_zome_call(keystore, zome_call_unsigned).await
+        let mut zome_call =
+            ZomeCall::try_from_unsigned_zome_call(keystore, zome_call_unsigned).await?;
+        zome_call.timeout_ms = self.timeout_ms;
+        Ok(zome_call)
     }
 }
 
@@ -425,6 +567,9 @@ pub struct NetworkInfo {
     pub total_network_peers: u32,
     pub bytes_since_last_time_queried: u64,
     pub completed_rounds_since_last_time_queried: u32,
+    /// The number of bytes of op data held within the agent's storage arc.
+    #[serde(default)]
+    pub arc_data_size: u64,
 }
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
@@ -0,0 +1,16 @@
+use holochain_types::prelude::*;
+
+/// The result of probing one authority with an [`AdminRequest::ProbeBasis`](crate::AdminRequest::ProbeBasis).
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct AuthorityProbe {
+    /// The authority which was probed.
+    pub agent: AgentPubKey,
+    /// The number of integrated ops the authority holds for the basis,
+    /// or `None` if it didn't respond.
+    pub op_count: Option<u32>,
+    /// How long the authority took to respond, in milliseconds,
+    /// or `None` if it didn't respond.
+    pub round_trip_ms: Option<u64>,
+    /// Why the probe failed, if the authority didn't respond.
+    pub error: Option<String>,
+}
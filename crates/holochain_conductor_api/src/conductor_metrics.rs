@@ -0,0 +1,32 @@
+use holochain_types::prelude::*;
+
+/// Throughput counters for a conductor, averaged over the interval since
+/// the previous sample.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct ConductorMetrics {
+    /// The length of the sampled interval, in milliseconds.
+    pub interval_ms: u64,
+    /// DhtOps received from the network and queued for validation.
+    pub ops_received_per_second: f64,
+    /// DhtOps which finished app validation.
+    pub ops_validated_per_second: f64,
+    /// DhtOps integrated into the DHT database.
+    pub ops_integrated_per_second: f64,
+    /// Gossip bytes received.
+    pub gossip_bytes_in_per_second: f64,
+    /// Gossip bytes sent.
+    pub gossip_bytes_out_per_second: f64,
+    /// Zome calls made into cells running on this conductor.
+    pub zome_calls_per_second: f64,
+}
+
+/// Signals which are pushed to admin interface clients.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum AdminSignal {
+    /// A periodic sample of conductor throughput, sent to clients
+    /// which made an [`AdminRequest::SubscribeMetrics`] request.
+    ///
+    /// [`AdminRequest::SubscribeMetrics`]: crate::AdminRequest::SubscribeMetrics
+    Metrics(ConductorMetrics),
+}
@@ -2,7 +2,8 @@
 //! This module is used to configure the conductor
 
 use crate::conductor::process::ERROR_CODE;
-use holochain_types::prelude::DbSyncStrategy;
+use holo_hash::{DnaHash, DnaHashB64};
+use holochain_types::prelude::{DbPoolSizes, DbSyncStrategy};
 use kitsune_p2p_types::config::{KitsuneP2pConfig, KitsuneP2pTuningParams};
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
@@ -16,6 +17,7 @@ mod keystore_config;
 /// Defines subdirectories of the config directory.
 pub mod paths;
 pub mod process;
+mod startup_profile;
 //mod logger_config;
 //mod signal_config;
 
@@ -25,6 +27,8 @@ pub use dpki_config::DpkiConfig;
 pub use error::*;
 pub use keystore_config::KeystoreConfig;
 //pub use signal_config::SignalConfig;
+pub use startup_profile::StartupProfile;
+use std::collections::HashMap;
 use std::path::Path;
 
 use crate::config::conductor::paths::DataRootPath;
@@ -54,6 +58,12 @@ pub struct ConductorConfig {
     /// Setup admin interfaces to control this conductor through a websocket connection.
     pub admin_interfaces: Option<Vec<AdminInterfaceConfig>>,
 
+    /// Setup a firehose interface, which streams every op integrated into the
+    /// spaces of an app to external indexers through a websocket connection.
+    /// See [`FirehoseInterfaceConfig`].
+    #[serde(default)]
+    pub firehose_interface: Option<FirehoseInterfaceConfig>,
+
     /// Optional config for the network module.
     #[serde(default)]
     pub network: KitsuneP2pConfig,
@@ -76,9 +86,27 @@ pub struct ConductorConfig {
     #[serde(default)]
     pub db_sync_strategy: DbSyncStrategy,
 
+    /// Override the default number of connections in the database pools,
+    /// per kind of database.
+    ///
+    /// Increasing the dedicated query connections of the DHT databases
+    /// can help when reads contend with writes under heavy validation load.
+    /// See [`DbPoolSizes`] for details.
+    #[serde(default)]
+    pub db_pool_sizes: DbPoolSizes,
+
     /// Tuning parameters to adjust the behaviour of the conductor.
     #[serde(default)]
     pub tuning_params: Option<ConductorTuningParams>,
+
+    /// The role this conductor plays on the network, see [`StartupProfile`].
+    /// Defaults to [`StartupProfile::Standard`].
+    #[serde(default)]
+    pub profile: Option<StartupProfile>,
+
+    /// Override the conductor's [`StartupProfile`] for specific DNAs.
+    #[serde(default)]
+    pub dna_profiles: Option<HashMap<DnaHashB64, StartupProfile>>,
 }
 
 /// Helper function to load a config from a YAML string.
@@ -137,6 +165,23 @@ impl ConductorConfig {
     pub fn conductor_tuning_params(&self) -> ConductorTuningParams {
         self.tuning_params.clone().unwrap_or_default()
     }
+
+    /// Get the startup profile for a DNA, which is the DNA's own profile if
+    /// one is set, or otherwise the profile of the conductor.
+    pub fn startup_profile(&self, dna_hash: &DnaHash) -> StartupProfile {
+        self.dna_profiles
+            .as_ref()
+            .and_then(|profiles| profiles.get(&DnaHashB64::from(dna_hash.clone())))
+            .or(self.profile.as_ref())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Get the network tuning params for a DNA, with its startup profile applied.
+    pub fn dna_tuning_params(&self, dna_hash: &DnaHash) -> KitsuneP2pTuningParams {
+        self.startup_profile(dna_hash)
+            .apply_tuning_params(self.kitsune_tuning_params())
+    }
 }
 
 /// Tuning parameters to adjust the behaviour of the conductor.
@@ -145,6 +190,52 @@ pub struct ConductorTuningParams {
     /// The delay between retries of sys validation when there are missing dependencies waiting to be found on the DHT.
     /// Default: 10 seconds
     pub sys_validation_retry_delay: Option<std::time::Duration>,
+    /// The maximum number of requests from a single app interface connection that may be
+    /// in flight at once. Further requests from that connection wait until one completes.
+    /// Default: 128
+    pub app_interface_max_in_flight_per_connection: Option<usize>,
+    /// The maximum number of requests that may be in flight at once across all connections
+    /// to a single app interface. Connections waiting for capacity take turns, so no single
+    /// connection can starve the others.
+    /// Default: 128
+    pub app_interface_max_in_flight: Option<usize>,
+    /// How long a get skips the network for a hash after every authority asked
+    /// reported it holds no data for it. Zero disables this negative caching.
+    /// A miss is forgotten early if an op for the hash arrives at this node by publish or gossip.
+    /// Default: 5 seconds
+    pub cascade_negative_cache_ttl: Option<std::time::Duration>,
+    /// How long a validation or integration workflow may keep working without catching
+    /// up with its queue before the conductor considers itself overloaded and starts
+    /// shedding low priority requests from other peers, so that it keeps capacity for
+    /// zome calls. The requests shed are gossip requests for op hashes and gets which
+    /// scan an index, such as gets of links or of agent activity. Gets of a single hash
+    /// are still served.
+    ///
+    /// The lag is measured from when the workflow started working rather than from when
+    /// the ops in its queue were authored or received, so neither old data arriving by
+    /// gossip nor ops waiting for missing dependencies count as lag.
+    /// Zero disables load shedding.
+    /// Default: 60 seconds
+    pub load_shedding_lag_threshold: Option<std::time::Duration>,
+    /// Whether the nonces witnessed for zome calls are kept across restarts.
+    /// If they are forgotten, a signed zome call can be replayed once after the
+    /// conductor restarts until its nonce expires, at most 50 minutes later.
+    /// Default: true
+    pub persist_nonce_windows: Option<bool>,
+    /// Whether the signed agent infos of local agents are published to the DHT,
+    /// so peers can find them when they can't reach a bootstrap service.
+    /// Default: true
+    pub publish_agent_infos: Option<bool>,
+    /// Whether hashes are printed redacted, as their type prefix and first 6
+    /// characters, wherever they are displayed or debug formatted, so that logs
+    /// don't carry full agent keys. This applies to the whole process.
+    /// Default: false
+    pub redact_hashes_in_logs: Option<bool>,
+    /// The longest a zome call may wait for its actions to be accepted by an
+    /// authority when it asks to with `wait_for_publish`. Longer waits requested
+    /// by a caller are cut down to this.
+    /// Default: 60 seconds
+    pub max_wait_for_publish: Option<std::time::Duration>,
 }
 
 impl ConductorTuningParams {
@@ -152,6 +243,14 @@ impl ConductorTuningParams {
     pub fn new() -> Self {
         Self {
             sys_validation_retry_delay: None,
+            app_interface_max_in_flight_per_connection: None,
+            app_interface_max_in_flight: None,
+            cascade_negative_cache_ttl: None,
+            load_shedding_lag_threshold: None,
+            persist_nonce_windows: None,
+            publish_agent_infos: None,
+            redact_hashes_in_logs: None,
+            max_wait_for_publish: None,
         }
     }
 
@@ -160,6 +259,51 @@ impl ConductorTuningParams {
         self.sys_validation_retry_delay
             .unwrap_or_else(|| std::time::Duration::from_secs(10))
     }
+
+    /// Get the current value of `app_interface_max_in_flight_per_connection` or its default value.
+    pub fn app_interface_max_in_flight_per_connection(&self) -> usize {
+        self.app_interface_max_in_flight_per_connection
+            .unwrap_or(128)
+            .max(1)
+    }
+
+    /// Get the current value of `app_interface_max_in_flight` or its default value.
+    pub fn app_interface_max_in_flight(&self) -> usize {
+        self.app_interface_max_in_flight.unwrap_or(128).max(1)
+    }
+
+    /// Get the current value of `cascade_negative_cache_ttl` or its default value.
+    pub fn cascade_negative_cache_ttl(&self) -> std::time::Duration {
+        self.cascade_negative_cache_ttl
+            .unwrap_or_else(|| std::time::Duration::from_secs(5))
+    }
+
+    /// Get the current value of `load_shedding_lag_threshold` or its default value.
+    pub fn load_shedding_lag_threshold(&self) -> std::time::Duration {
+        self.load_shedding_lag_threshold
+            .unwrap_or_else(|| std::time::Duration::from_secs(60))
+    }
+
+    /// Get the current value of `persist_nonce_windows` or its default value.
+    pub fn persist_nonce_windows(&self) -> bool {
+        self.persist_nonce_windows.unwrap_or(true)
+    }
+
+    /// Get the current value of `publish_agent_infos` or its default value.
+    pub fn publish_agent_infos(&self) -> bool {
+        self.publish_agent_infos.unwrap_or(true)
+    }
+
+    /// Get the current value of `redact_hashes_in_logs` or its default value.
+    pub fn redact_hashes_in_logs(&self) -> bool {
+        self.redact_hashes_in_logs.unwrap_or(false)
+    }
+
+    /// Get the current value of `max_wait_for_publish` or its default value.
+    pub fn max_wait_for_publish(&self) -> std::time::Duration {
+        self.max_wait_for_publish
+            .unwrap_or_else(|| std::time::Duration::from_secs(60))
+    }
 }
 
 impl Default for ConductorTuningParams {
@@ -167,6 +311,16 @@ impl Default for ConductorTuningParams {
         let empty = Self::new();
         Self {
             sys_validation_retry_delay: Some(empty.sys_validation_retry_delay()),
+            app_interface_max_in_flight_per_connection: Some(
+                empty.app_interface_max_in_flight_per_connection(),
+            ),
+            app_interface_max_in_flight: Some(empty.app_interface_max_in_flight()),
+            cascade_negative_cache_ttl: Some(empty.cascade_negative_cache_ttl()),
+            load_shedding_lag_threshold: Some(empty.load_shedding_lag_threshold()),
+            persist_nonce_windows: Some(empty.persist_nonce_windows()),
+            publish_agent_infos: Some(empty.publish_agent_infos()),
+            redact_hashes_in_logs: Some(empty.redact_hashes_in_logs()),
+            max_wait_for_publish: Some(empty.max_wait_for_publish()),
         }
     }
 }
@@ -174,6 +328,7 @@ impl Default for ConductorTuningParams {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use holochain_types::prelude::DbPoolSize;
     use holochain_types::websocket::AllowedOrigins;
     use kitsune_p2p_types::config::TransportConfig;
     use matches::assert_matches;
@@ -216,10 +371,14 @@ mod tests {
                 dpki: None,
                 keystore: KeystoreConfig::DangerTestKeystore,
                 admin_interfaces: None,
+                firehose_interface: None,
                 db_sync_strategy: DbSyncStrategy::default(),
+                db_pool_sizes: DbPoolSizes::default(),
                 #[cfg(feature = "chc")]
                 chc_url: None,
                 tuning_params: None,
+                profile: None,
+                dna_profiles: None,
             }
         );
     }
@@ -247,6 +406,12 @@ mod tests {
           port: 1234
           allowed_origins: "*"
 
+    firehose_interface:
+      driver:
+        type: websocket
+        port: 1235
+        allowed_origins: "*"
+
     network:
       bootstrap_service: https://bootstrap-staging.holo.host
       transport_pool:
@@ -266,6 +431,10 @@ mod tests {
       network_type: quic_bootstrap
 
     db_sync_strategy: Fast
+
+    db_pool_sizes:
+      dht:
+        query_connections: 8
     "#;
         let result: ConductorConfigResult<ConductorConfig> = config_from_yaml(yaml);
         let mut network_config = KitsuneP2pConfig::default();
@@ -300,13 +469,29 @@ mod tests {
                     driver: InterfaceDriver::Websocket {
                         port: 1234,
                         allowed_origins: AllowedOrigins::Any
-                    }
+                    },
+                    require_token: false,
                 }]),
+                firehose_interface: Some(FirehoseInterfaceConfig {
+                    driver: InterfaceDriver::Websocket {
+                        port: 1235,
+                        allowed_origins: AllowedOrigins::Any
+                    }
+                }),
                 network: network_config,
                 db_sync_strategy: DbSyncStrategy::Fast,
+                db_pool_sizes: DbPoolSizes {
+                    dht: DbPoolSize {
+                        query_connections: Some(8),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
                 #[cfg(feature = "chc")]
                 chc_url: None,
                 tuning_params: None,
+                profile: None,
+                dna_profiles: None,
             }
         );
     }
@@ -333,11 +518,39 @@ mod tests {
                     connection_url: url2::url2!("unix:///var/run/lair-keystore/socket?k=EcRDnP3xDIZ9Rk_1E-egPE0mGZi5CcszeRxVkb2QXXQ"),
                 },
                 admin_interfaces: None,
+                firehose_interface: None,
                 db_sync_strategy: DbSyncStrategy::Fast,
+                db_pool_sizes: DbPoolSizes::default(),
                 #[cfg(feature = "chc")]
                 chc_url: None,
                 tuning_params: None,
+                profile: None,
+                dna_profiles: None,
             }
         );
     }
+
+    #[test]
+    fn test_config_startup_profiles() {
+        let archived_dna = DnaHash::from_raw_36(vec![1; 36]);
+        let other_dna = DnaHash::from_raw_36(vec![2; 36]);
+        let yaml = format!(
+            r#"---
+    profile: minimal
+    dna_profiles:
+      {}: archive
+    "#,
+            DnaHashB64::from(archived_dna.clone())
+        );
+        let config: ConductorConfig = config_from_yaml(&yaml).unwrap();
+        assert_eq!(
+            config.startup_profile(&archived_dna),
+            StartupProfile::Archive
+        );
+        assert_eq!(config.startup_profile(&other_dna), StartupProfile::Minimal);
+        assert_eq!(
+            ConductorConfig::default().startup_profile(&other_dna),
+            StartupProfile::Standard
+        );
+    }
 }
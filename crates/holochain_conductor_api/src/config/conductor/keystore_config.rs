@@ -33,6 +33,36 @@ pub enum KeystoreConfig {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         lair_root: Option<KeystorePath>,
     },
+
+    /// Run a keystore in-process without a lair server, persisting keys to
+    /// an encrypted sqlite file. Unlike [`KeystoreConfig::LairServerInProc`]
+    /// this needs no IPC socket or pid file, so it can be used where a lair
+    /// server can't run.
+    /// This keystore type requires a secure passphrase specified
+    /// to the cli binary entrypoint for this Holochain conductor process.
+    InProcSoftware {
+        /// The directory containing the keystore config and store files.
+        /// If not specified, will default to the ConductorConfig
+        /// `[environment_path]/ks`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        keystore_root: Option<KeystorePath>,
+    },
+
+    /// Run a keystore in-process, keeping its entries in the operating
+    /// system keychain. Entries are encrypted before they reach the keychain.
+    /// Supported on macOS (`security`) and Linux (`secret-tool`).
+    /// This keystore type requires a secure passphrase specified
+    /// to the cli binary entrypoint for this Holochain conductor process.
+    OsKeychain {
+        /// The keychain service name that entries are stored under.
+        service: String,
+
+        /// The directory containing the keystore config file.
+        /// If not specified, will default to the ConductorConfig
+        /// `[environment_path]/ks`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        keystore_root: Option<KeystorePath>,
+    },
 }
 
 impl Default for KeystoreConfig {
@@ -0,0 +1,99 @@
+use kitsune_p2p_types::config::KitsuneP2pTuningParams;
+use serde::Deserialize;
+use serde::Serialize;
+use std::time::Duration;
+
+/// A named bundle of settings for running a conductor, or one of its DNAs,
+/// in a particular role on the network.
+///
+/// A profile only overrides the settings it is about. Any other tuning
+/// params are taken from the network config as usual.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupProfile {
+    /// A light node which doesn't hold data for others.
+    ///
+    /// The storage arc is clamped to empty, which also disables gossip,
+    /// and data fetched from the network is only cached for an hour.
+    Minimal,
+
+    /// A regular node, which uses the network tuning params as they are
+    /// and caches data fetched from the network for a day.
+    #[default]
+    Standard,
+
+    /// An archive node which holds all data for the DNA.
+    ///
+    /// The storage arc is clamped to full, the cache is never evicted and no
+    /// validation receipts are sent, so authors keep publishing until regular
+    /// authorities have validated their data.
+    Archive,
+}
+
+impl StartupProfile {
+    /// Apply the settings of this profile to the network tuning params.
+    pub fn apply_tuning_params(
+        &self,
+        tuning_params: KitsuneP2pTuningParams,
+    ) -> KitsuneP2pTuningParams {
+        let arc_clamping = match self {
+            StartupProfile::Minimal => "empty",
+            StartupProfile::Standard => return tuning_params,
+            StartupProfile::Archive => "full",
+        };
+        let mut tuning_params = (*tuning_params).clone();
+        tuning_params.gossip_arc_clamping = arc_clamping.to_string();
+        std::sync::Arc::new(tuning_params)
+    }
+
+    /// Whether validation receipts should be sent to the authors of the data we hold.
+    pub fn send_validation_receipts(&self) -> bool {
+        !matches!(self, StartupProfile::Archive)
+    }
+
+    /// How long data fetched from the network is kept in the cache,
+    /// or `None` if the cache is never evicted.
+    pub fn cache_eviction_age(&self) -> Option<Duration> {
+        match self {
+            StartupProfile::Minimal => Some(Duration::from_secs(60 * 60)),
+            StartupProfile::Standard => Some(Duration::from_secs(24 * 60 * 60)),
+            StartupProfile::Archive => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kitsune_p2p_types::dht::prelude::ArqClamping;
+
+    #[test]
+    fn profiles_clamp_the_arc() {
+        let tuning_params = KitsuneP2pTuningParams::default();
+        assert_eq!(
+            StartupProfile::Minimal
+                .apply_tuning_params(tuning_params.clone())
+                .arc_clamping(),
+            Some(ArqClamping::Empty)
+        );
+        assert_eq!(
+            StartupProfile::Standard.apply_tuning_params(tuning_params.clone()),
+            tuning_params
+        );
+        assert_eq!(
+            StartupProfile::Archive
+                .apply_tuning_params(tuning_params)
+                .arc_clamping(),
+            Some(ArqClamping::Full)
+        );
+    }
+
+    #[test]
+    fn only_archive_keeps_the_cache() {
+        assert!(
+            StartupProfile::Minimal.cache_eviction_age().unwrap()
+                < StartupProfile::Standard.cache_eviction_age().unwrap()
+        );
+        assert_eq!(StartupProfile::Archive.cache_eviction_age(), None);
+    }
+}
@@ -8,6 +8,28 @@ pub struct AdminInterfaceConfig {
     /// By what means the interface will be exposed.
     /// Currently the only option is a local websocket running on a configurable port.
     pub driver: InterfaceDriver,
+
+    /// Whether connections must authenticate with an admin token before making requests.
+    ///
+    /// Tokens are issued with [`AdminRequest::IssueAdminAuthenticationToken`] over an
+    /// interface which doesn't require one, and every request made over the connection
+    /// is limited to the scope of its token.
+    ///
+    /// [`AdminRequest::IssueAdminAuthenticationToken`]: crate::AdminRequest::IssueAdminAuthenticationToken
+    #[serde(default)]
+    pub require_token: bool,
+}
+
+/// Information needed to spawn a firehose interface, which streams every op
+/// integrated into the spaces of an app to clients authenticated for that app.
+///
+/// Clients authenticate with an app authentication token, in the same way as
+/// for app interfaces.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+pub struct FirehoseInterfaceConfig {
+    /// By what means the interface will be exposed.
+    /// Currently the only option is a local websocket running on a configurable port.
+    pub driver: InterfaceDriver,
 }
 
 /// Configuration for interfaces, specifying the means by which an interface
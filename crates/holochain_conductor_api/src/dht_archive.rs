@@ -0,0 +1,14 @@
+use holochain_types::prelude::*;
+
+/// A chunk of a DHT archive, returned by an
+/// [`AdminRequest::ExportDhtArchive`](crate::AdminRequest::ExportDhtArchive).
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct DhtArchiveChunk {
+    /// The offset of this chunk in the archive.
+    pub offset: u64,
+    /// The length of the whole archive. The export is finished once the chunk
+    /// which ends at this length has been returned.
+    pub archive_len: u64,
+    /// The bytes of this chunk, at most [`DHT_ARCHIVE_CHUNK_SIZE`] of them.
+    pub bytes: Bytes,
+}
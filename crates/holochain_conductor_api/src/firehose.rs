@@ -0,0 +1,40 @@
+//! Types for the firehose interface, which streams every op integrated into
+//! a conductor's DHT databases to external indexers.
+
+use holochain_types::prelude::*;
+
+/// A chain op which has been integrated into the DHT database of a space.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct FirehoseOp {
+    /// The space the op was integrated into.
+    pub dna_hash: DnaHash,
+    /// The hash of the op.
+    pub op_hash: DhtOpHash,
+    /// The type of the op.
+    pub op_type: DhtOpType,
+    /// The basis hash the op is stored at.
+    pub basis: OpBasis,
+    /// The author of the action the op was produced from.
+    pub author: AgentPubKey,
+    /// When the op was authored.
+    pub authored_timestamp: Timestamp,
+    /// When the op was integrated.
+    pub when_integrated: Timestamp,
+    /// The op itself. Private entry data is never included.
+    pub op: DhtOp,
+}
+
+/// Signals which are pushed to firehose interface clients.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum FirehoseSignal {
+    /// An op was integrated into one of the spaces of the app the
+    /// connection authenticated for.
+    Op(Box<FirehoseOp>),
+    /// The client fell behind and ops were dropped before they could be sent.
+    /// An indexer which receives this should catch up from a state dump.
+    Lagged {
+        /// The number of ops which were dropped.
+        dropped: u64,
+    },
+}
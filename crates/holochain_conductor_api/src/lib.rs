@@ -23,16 +23,45 @@
 //! Notably the app interface allows calling functions exposed by the hApps'
 //! modules, called DNAs. To discover a particular hApp's structure, its app
 //! info can be requested.
+//!
+//! A conductor can also be configured with a firehose interface, which streams every op
+//! integrated into the spaces of an app to clients which authenticate for that app, see
+//! [`FirehoseSignal`].
 
+pub mod admin_auth;
 mod admin_interface;
 mod app_interface;
+pub mod basis_probe;
+pub mod conductor_metrics;
 pub mod config;
+pub mod dht_archive;
+pub mod firehose;
+pub mod load_shedding;
+pub mod neighborhood;
+pub mod nonce_audit;
+pub mod peer_info;
 pub mod signal_subscription;
 pub mod state_dump;
 pub mod storage_info;
+#[cfg(feature = "op_replay")]
+pub mod validation_replay;
+pub mod wire_encoding;
+pub mod workflow_stats;
 
+pub use admin_auth::*;
 pub use admin_interface::*;
 pub use app_interface::*;
+pub use basis_probe::*;
+pub use conductor_metrics::*;
 pub use config::*;
+pub use dht_archive::*;
+pub use firehose::*;
+pub use load_shedding::*;
+pub use neighborhood::*;
+pub use nonce_audit::*;
+pub use peer_info::*;
 pub use state_dump::*;
 pub use storage_info::*;
+#[cfg(feature = "op_replay")]
+pub use validation_replay::*;
+pub use workflow_stats::*;
@@ -0,0 +1,27 @@
+use holochain_types::prelude::*;
+
+/// The state of the conductor's load shedding, returned by an
+/// [`AdminRequest::LoadSheddingStats`](crate::AdminRequest::LoadSheddingStats).
+///
+/// While one of the validation or integration workflows of the conductor has been
+/// working without catching up with its queue for longer than the threshold, the
+/// conductor refuses low priority requests from other peers with a busy error, so
+/// that it keeps capacity for zome calls.
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+pub struct LoadSheddingStats {
+    /// Whether requests are currently being shed.
+    pub shedding: bool,
+    /// How long the most lagging workflow has been working without catching up,
+    /// in milliseconds.
+    pub lag_ms: u64,
+    /// The lag above which requests are shed, in milliseconds, or zero if load
+    /// shedding is disabled.
+    pub lag_threshold_ms: u64,
+    /// The number of gossip requests for op hashes refused since the conductor started.
+    pub shed_gossip_backfill: u64,
+    /// The number of gets from other peers which scan an index, such as gets of links
+    /// or of agent activity, refused since the conductor started.
+    pub shed_gets: u64,
+}
@@ -0,0 +1,135 @@
+use holochain_types::prelude::*;
+
+/// The peers holding the data of a cell's agent, as returned for an
+/// [`AppRequest::Neighborhood`](crate::AppRequest::Neighborhood).
+///
+/// Peers are known from the conductor's peer store, so a neighborhood is only
+/// as complete as the conductor's view of the network.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct Neighborhood {
+    /// The location of the cell's agent in the DHT.
+    pub agent_location: u32,
+    /// The fraction of the DHT covered by the agent's own storage arc,
+    /// or 0 if the agent hasn't joined the network yet.
+    pub arc_size: f64,
+    /// The other peers whose storage arcs cover the agent's location.
+    pub neighbors: Vec<Neighbor>,
+    /// A summary of how well the agent's location is covered.
+    pub coverage: NeighborhoodCoverage,
+}
+
+/// A peer whose storage arc covers the location of an agent.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct Neighbor {
+    /// The peer.
+    pub agent: AgentPubKey,
+    /// The fraction of the DHT covered by the peer's storage arc.
+    pub arc_size: f64,
+    /// When the peer's agent info expires, according to the peer's own clock.
+    pub expires_at: Timestamp,
+    /// Whether the peer's agent info had expired when the neighborhood was read.
+    pub expired: bool,
+    /// Whether the peer advertises any urls it can be reached at.
+    pub reachable: bool,
+}
+
+impl Neighbor {
+    /// Whether the peer can be expected to serve the agent's data.
+    pub fn is_live(&self) -> bool {
+        !self.expired && self.reachable
+    }
+}
+
+/// Statistics about the peers covering the location of an agent.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct NeighborhoodCoverage {
+    /// The number of other peers in the peer store.
+    pub known_peers: u32,
+    /// The number of neighbors, live or not.
+    pub neighbors: u32,
+    /// The number of neighbors whose agent info hasn't expired and which can be reached.
+    pub live_neighbors: u32,
+    /// Whether the agent's own storage arc covers its location, so that it
+    /// holds its own data as an authority.
+    pub self_covered: bool,
+}
+
+impl Neighborhood {
+    /// Summarise the neighbors of an agent at `agent_location`, out of
+    /// `known_peers` other peers.
+    pub fn new(
+        agent_location: u32,
+        arc_size: f64,
+        self_covered: bool,
+        mut neighbors: Vec<Neighbor>,
+        known_peers: u32,
+    ) -> Self {
+        // The most useful neighbors come first.
+        neighbors.sort_by(|a, b| {
+            b.is_live()
+                .cmp(&a.is_live())
+                .then(b.arc_size.total_cmp(&a.arc_size))
+        });
+        let coverage = NeighborhoodCoverage {
+            known_peers,
+            neighbors: neighbors.len() as u32,
+            live_neighbors: neighbors.iter().filter(|n| n.is_live()).count() as u32,
+            self_covered,
+        };
+        Self {
+            agent_location,
+            arc_size,
+            neighbors,
+            coverage,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn neighbor(n: u8, arc_size: f64, expired: bool, reachable: bool) -> Neighbor {
+        Neighbor {
+            agent: AgentPubKey::from_raw_32(vec![n; 32]),
+            arc_size,
+            expires_at: Timestamp::from_micros(0),
+            expired,
+            reachable,
+        }
+    }
+
+    #[test]
+    fn live_neighbors_are_counted_and_listed_first() {
+        let neighborhood = Neighborhood::new(
+            7,
+            1.0,
+            true,
+            vec![
+                neighbor(1, 1.0, true, true),
+                neighbor(2, 0.25, false, true),
+                neighbor(3, 0.5, false, false),
+                neighbor(4, 0.5, false, true),
+            ],
+            10,
+        );
+
+        assert_eq!(
+            neighborhood.coverage,
+            NeighborhoodCoverage {
+                known_peers: 10,
+                neighbors: 4,
+                live_neighbors: 2,
+                self_covered: true,
+            }
+        );
+        assert_eq!(
+            neighborhood
+                .neighbors
+                .iter()
+                .map(|n| n.agent.get_raw_32()[0])
+                .collect::<Vec<_>>(),
+            vec![4, 2, 1, 3]
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use holochain_types::prelude::*;
+
+/// The state of the conductor's protection against replayed zome calls, returned by
+/// an [`AdminRequest::NonceAudit`](crate::AdminRequest::NonceAudit).
+///
+/// Every signed zome call carries a nonce and an expiry. The conductor keeps the
+/// latest nonce of each agent until it expires, and rejects calls which reuse it.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct NonceAudit {
+    /// Whether the nonce windows are kept across restarts of the conductor.
+    pub persistent: bool,
+    /// The window of each agent whose latest nonce hasn't expired yet.
+    pub windows: Vec<NonceWindow>,
+    /// The zome calls whose nonce was rejected, oldest first.
+    pub rejected: Vec<RejectedNonce>,
+}
+
+/// The latest nonce the conductor has witnessed for an agent.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct NonceWindow {
+    /// The agent the zome calls were made as.
+    pub agent: AgentPubKey,
+    /// When the nonce expires. Until then a zome call reusing it is rejected.
+    pub expires: Timestamp,
+}
+
+/// Zome calls which were refused because their nonce could have been a replay.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RejectedNonce {
+    /// The agent the zome call was made as.
+    pub agent: AgentPubKey,
+    /// When the zome call was rejected.
+    pub timestamp: Timestamp,
+    /// Why the nonce was rejected.
+    pub reason: NonceRejection,
+    /// How many zome calls were rejected. Repeated rejections of the same agent's
+    /// nonces for the same reason are only recorded once a minute, so this counts
+    /// those since the previous record.
+    pub count: u32,
+}
@@ -0,0 +1,35 @@
+//! Types for listing the peers in a conductor's peer stores.
+
+use holochain_types::prelude::*;
+use kitsune_p2p_types::agent_info::AgentInfoSigned;
+
+/// An agent info from a peer store, along with what it says about whether
+/// the peer is still live.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct PeerInfo {
+    /// The agent info as it is stored.
+    pub agent_info: AgentInfoSigned,
+    /// When the agent info was signed, according to the peer's own clock.
+    pub signed_at: Timestamp,
+    /// When the agent info expires, according to the peer's own clock.
+    pub expires_at: Timestamp,
+    /// Whether the agent info had expired when it was listed.
+    pub expired: bool,
+    /// Whether the peer advertises any urls it can be reached at.
+    pub reachable: bool,
+}
+
+impl PeerInfo {
+    /// Describe an agent info as of `now`.
+    pub fn new(agent_info: AgentInfoSigned, now: Timestamp) -> Self {
+        let signed_at = Timestamp::from_micros(agent_info.signed_at_ms as i64 * 1000);
+        let expires_at = Timestamp::from_micros(agent_info.expires_at_ms as i64 * 1000);
+        Self {
+            expired: expires_at <= now,
+            reachable: agent_info.is_active(),
+            signed_at,
+            expires_at,
+            agent_info,
+        }
+    }
+}
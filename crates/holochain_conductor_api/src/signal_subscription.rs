@@ -1,8 +1,11 @@
 use holochain_serialized_bytes::prelude::*;
 use holochain_types::app::InstalledAppId;
+use holochain_types::signal::Signal;
 use holochain_zome_types::cell::CellId;
+use holochain_zome_types::signal::AppSignal;
+use holochain_zome_types::zome::ZomeName;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Declares updated Signal subscription settings for an App.
 /// This message is part of the AppInterfaceApi
@@ -51,7 +54,18 @@ impl SignalFilterSet {
 
 /// Specifies fine-grained filter controls for the signals
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
-pub struct SignalFilter;
+pub struct SignalFilter {
+    /// Only match app signals emitted by this zome.
+    #[serde(default)]
+    pub zome: Option<ZomeName>,
+    /// Only match app signals whose type matches this pattern.
+    ///
+    /// The type of a signal is the name of its enum variant, whether the enum is
+    /// externally tagged or tagged with a `type` field, or the signal itself if it is
+    /// a string. `*` in the pattern matches any sequence of characters.
+    #[serde(default)]
+    pub pattern: Option<String>,
+}
 
 impl Default for SignalFilter {
     fn default() -> Self {
@@ -63,6 +77,151 @@ impl Default for SignalFilter {
 impl SignalFilter {
     /// A passthrough filter which filters nothing
     pub fn empty() -> Self {
-        SignalFilter
+        SignalFilter {
+            zome: None,
+            pattern: None,
+        }
+    }
+
+    /// Check whether a signal passes this filter.
+    /// System signals always pass.
+    pub fn matches(&self, signal: &Signal) -> bool {
+        let (zome_name, signal) = match signal {
+            Signal::App {
+                zome_name, signal, ..
+            } => (zome_name, signal),
+            Signal::System(_) => return true,
+        };
+        if self.zome.as_ref().map_or(false, |zome| zome != zome_name) {
+            return false;
+        }
+        match &self.pattern {
+            Some(pattern) => signal_type(signal)
+                .map_or(false, |signal_type| wildcard_match(pattern, &signal_type)),
+            None => true,
+        }
+    }
+}
+
+/// Read the type name of an app signal, see [`SignalFilter::pattern`].
+fn signal_type(signal: &AppSignal) -> Option<String> {
+    #[derive(Debug, Deserialize)]
+    #[serde(untagged)]
+    enum SignalType {
+        Tagged {
+            #[serde(rename = "type")]
+            signal_type: String,
+        },
+        Unit(String),
+        External(BTreeMap<String, serde::de::IgnoredAny>),
+    }
+
+    match signal.clone().into_inner().decode::<SignalType>().ok()? {
+        SignalType::Tagged { signal_type } | SignalType::Unit(signal_type) => Some(signal_type),
+        SignalType::External(map) if map.len() == 1 => map.into_keys().next(),
+        SignalType::External(_) => None,
+    }
+}
+
+/// Match text against a pattern in which `*` matches any sequence of characters.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    // There is always at least one part.
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts = parts.collect::<Vec<_>>();
+    let Some(last) = parts.pop() else {
+        // No wildcard, so the whole text must match.
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_zome_types::prelude::*;
+
+    fn app_signal(zome_name: &str, signal: impl Serialize + std::fmt::Debug) -> Signal {
+        Signal::App {
+            cell_id: CellId::new(
+                DnaHash::from_raw_32(vec![0; 32]),
+                AgentPubKey::from_raw_32(vec![0; 32]),
+            ),
+            zome_name: zome_name.into(),
+            signal: AppSignal::new(ExternIO::encode(signal).unwrap()),
+        }
+    }
+
+    #[derive(Debug, Serialize)]
+    #[serde(tag = "type")]
+    enum Tagged {
+        PostCreated { id: u32 },
+        PostDeleted,
+    }
+
+    #[derive(Debug, Serialize)]
+    enum External {
+        CommentCreated(u32),
+        Ping,
+    }
+
+    #[test]
+    fn signal_filter_matches() {
+        let filter = SignalFilter::empty();
+        assert!(filter.matches(&app_signal("posts", Tagged::PostDeleted)));
+        assert!(filter.matches(&app_signal("posts", 42)));
+
+        let filter = SignalFilter {
+            zome: Some("posts".into()),
+            pattern: None,
+        };
+        assert!(filter.matches(&app_signal("posts", 42)));
+        assert!(!filter.matches(&app_signal("comments", 42)));
+
+        let filter = SignalFilter {
+            zome: None,
+            pattern: Some("Post*".into()),
+        };
+        assert!(filter.matches(&app_signal("posts", Tagged::PostCreated { id: 1 })));
+        assert!(filter.matches(&app_signal("posts", Tagged::PostDeleted)));
+        assert!(!filter.matches(&app_signal("comments", External::CommentCreated(1))));
+        assert!(!filter.matches(&app_signal("posts", 42)));
+
+        let filter = SignalFilter {
+            zome: Some("comments".into()),
+            pattern: Some("*Created".into()),
+        };
+        assert!(filter.matches(&app_signal("comments", External::CommentCreated(1))));
+        assert!(!filter.matches(&app_signal("comments", External::Ping)));
+        assert!(!filter.matches(&app_signal("posts", Tagged::PostCreated { id: 1 })));
+
+        let filter = SignalFilter {
+            zome: None,
+            pattern: Some("Ping".into()),
+        };
+        assert!(filter.matches(&app_signal("comments", External::Ping)));
+        assert!(!filter.matches(&app_signal("comments", "Pingo")));
+    }
+
+    #[test]
+    fn wildcard_patterns() {
+        assert!(wildcard_match("abc", "abc"));
+        assert!(!wildcard_match("abc", "abcd"));
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("a*", "abc"));
+        assert!(wildcard_match("*c", "abc"));
+        assert!(wildcard_match("a*c", "ac"));
+        assert!(wildcard_match("a*b*c", "axxbyyc"));
+        assert!(!wildcard_match("a*b*c", "axxcyyb"));
+        assert!(!wildcard_match("ab*ba", "aba"));
     }
 }
@@ -1,8 +1,11 @@
 use holo_hash::AgentPubKey;
 use holo_hash::DnaHash;
-use holochain_state_types::SourceChainDump;
+use holochain_state_types::{SourceChainDump, SourceChainDumpRecord};
 use holochain_types::dht_op::DhtOp;
+use holochain_types::dht_op::ValidationProvenance;
+use holochain_zome_types::prelude::{Timestamp, ValidationStatus};
 use kitsune_p2p_bin_data::{KitsuneAgent, KitsuneSpace};
+use kitsune_p2p_types::agent_info::AgentInfoSigned;
 use serde::Deserialize;
 use serde::Serialize;
 use std::sync::Arc;
@@ -87,6 +90,68 @@ pub struct AgentInfoDump {
     pub dump: String,
 }
 
+/// The most items a single [`StateDumpPage`] will hold.
+/// Larger limits are reduced to this.
+pub const MAX_STATE_DUMP_PAGE_LIMIT: u32 = 1000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+/// A store of a cell's state which can be dumped one page at a time.
+pub enum StateDumpStore {
+    /// The records of the cell's source chain, in chain order.
+    /// The cursor is the action sequence number of the last record seen.
+    SourceChain,
+    /// The ops held in the DHT database of the cell's DNA.
+    /// The cursor is the RowId of the last op seen.
+    DhtOps,
+    /// The ops held in the cache database of the cell's DNA.
+    /// The cursor is the RowId of the last op seen.
+    Cache,
+    /// The agent infos held in the peer store of the cell's DNA.
+    /// The cursor is the RowId of the last agent info seen.
+    /// An agent info which is updated is stored again with a new RowId.
+    PeerStore,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+/// A DhtOp along with where it is in validation and integration.
+pub struct DhtOpDump {
+    /// The op. Private entries are left out.
+    pub op: DhtOp,
+    /// The outcome of validating the op, if it has been validated.
+    pub validation_status: Option<ValidationStatus>,
+    /// When the op was integrated, if it has been.
+    pub when_integrated: Option<Timestamp>,
+    /// The integrity zomes and conductor version which validated the op,
+    /// if it has been validated by this conductor's app validation.
+    #[serde(default)]
+    pub validation_provenance: Option<ValidationProvenance>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", content = "items", rename_all = "snake_case")]
+/// The items of one page of a [`StateDumpStore`].
+pub enum StateDumpItems {
+    /// Records from [`StateDumpStore::SourceChain`].
+    SourceChain(Vec<SourceChainDumpRecord>),
+    /// Ops from [`StateDumpStore::DhtOps`].
+    DhtOps(Vec<DhtOpDump>),
+    /// Ops from [`StateDumpStore::Cache`].
+    Cache(Vec<DhtOpDump>),
+    /// Agent infos from [`StateDumpStore::PeerStore`].
+    PeerStore(Vec<AgentInfoSigned>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+/// One page of a dump of a [`StateDumpStore`].
+pub struct StateDumpPage {
+    /// The items in this page.
+    pub items: StateDumpItems,
+    /// The cursor to pass to get the next page,
+    /// or `None` if this page reached the end of the store.
+    pub next_cursor: Option<u64>,
+}
+
 impl std::fmt::Display for JsonDump {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let num_other_peers = self.peer_dump.peers.len();
@@ -25,3 +25,14 @@ pub enum StorageBlob {
 pub struct StorageInfo {
     pub blobs: Vec<StorageBlob>,
 }
+
+/// A database with schema migrations which will run the next time it is opened.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct PendingDbMigration {
+    /// The database file.
+    pub path: std::path::PathBuf,
+    /// The number of migrations already applied to the database.
+    pub current_version: usize,
+    /// The number of migrations the database will have once it is migrated.
+    pub latest_version: usize,
+}
@@ -0,0 +1,34 @@
+use holochain_types::prelude::*;
+
+/// The result of an [`AdminRequest::ReplayValidation`](crate::AdminRequest::ReplayValidation).
+///
+/// The ops in a DHT archive are validated again in an isolated workspace against the
+/// requested DNA, and each outcome is compared with the validation status this
+/// conductor has recorded for the op in the archive's DNA.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct ValidationReplayReport {
+    /// The DNA the ops were validated against.
+    pub dna_hash: DnaHash,
+    /// The DNA the archive was exported for.
+    pub archive_dna_hash: DnaHash,
+    /// The number of ops in the archive.
+    pub ops_replayed: usize,
+    /// The number of ops which this conductor has no recorded validation status for.
+    pub ops_unrecorded: usize,
+    /// The number of ops which couldn't be validated by the replay, usually because
+    /// their dependencies are neither in the archive nor on the network.
+    pub ops_undetermined: usize,
+    /// The ops whose replayed validation status differs from the recorded one.
+    pub differences: Vec<ValidationReplayDifference>,
+}
+
+/// An op whose validation status differs between the recorded and the replayed validation.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct ValidationReplayDifference {
+    /// The hash of the op.
+    pub op_hash: DhtOpHash,
+    /// The validation status recorded by this conductor.
+    pub recorded: ValidationStatus,
+    /// The validation status from the replay.
+    pub replayed: ValidationStatus,
+}
@@ -0,0 +1,216 @@
+//! Types for choosing how responses are encoded on an admin or app interface connection.
+//!
+//! Responses are msgpack encoded by default. A connection can switch to the
+//! [`WireEncoding::Compact`] encoding with an
+//! [`AdminRequest::SetWireEncoding`](crate::AdminRequest::SetWireEncoding) or
+//! [`AppRequest::SetWireEncoding`](crate::AppRequest::SetWireEncoding) request, which is
+//! best made straight after connecting.
+//!
+//! The compact encoding is still msgpack, but every string or binary value of at least
+//! [`COMPACT_MIN_LEN`] bytes which occurs more than once in a response, such as the hashes
+//! in a list of records, is sent only once. The response is encoded as a two element array:
+//!
+//! 1. A table of the repeated strings and binaries, in the order they first occur.
+//! 2. The response itself, with each occurrence of a value from the table replaced by an
+//!    extension value of type [`COMPACT_REF_EXT_TYPE`] whose data is the index of the value
+//!    in the table, as a big-endian `u32`.
+//!
+//! Rust clients can use [`compact_decode`] to get back the original msgpack encoded response.
+
+use holochain_serialized_bytes::SerializedBytesError;
+use rmpv::Value;
+use std::collections::HashMap;
+
+/// The msgpack extension type used for references into the table of a compact response.
+pub const COMPACT_REF_EXT_TYPE: i8 = 1;
+
+/// Strings and binaries shorter than this are never deduplicated,
+/// because a reference to them would not be any smaller.
+pub const COMPACT_MIN_LEN: usize = 8;
+
+/// How responses are encoded on an interface connection.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WireEncoding {
+    /// Plain msgpack, the default.
+    #[default]
+    Msgpack,
+    /// Msgpack with repeated strings and binaries deduplicated,
+    /// see the [module docs](self).
+    Compact,
+}
+
+/// Re-encode a msgpack encoded response with the [`WireEncoding::Compact`] encoding.
+pub fn compact_encode(msgpack: &[u8]) -> Result<Vec<u8>, SerializedBytesError> {
+    let mut body = rmpv::decode::read_value(&mut &msgpack[..])
+        .map_err(|e| SerializedBytesError::Deserialize(e.to_string()))?;
+
+    let mut counts = HashMap::new();
+    count_dedupable(&body, &mut counts);
+
+    let mut table = Vec::new();
+    let mut indexes = HashMap::new();
+    replace_repeated(&mut body, &counts, &mut table, &mut indexes);
+
+    let mut out = Vec::with_capacity(msgpack.len());
+    rmpv::encode::write_value(&mut out, &Value::Array(vec![Value::Array(table), body]))
+        .map_err(|e| SerializedBytesError::Serialize(e.to_string()))?;
+    Ok(out)
+}
+
+/// Decode a response sent with the [`WireEncoding::Compact`] encoding
+/// back to the plain msgpack encoded response.
+pub fn compact_decode(compact: &[u8]) -> Result<Vec<u8>, SerializedBytesError> {
+    let value = rmpv::decode::read_value(&mut &compact[..])
+        .map_err(|e| SerializedBytesError::Deserialize(e.to_string()))?;
+    let (table, mut body) = match value {
+        Value::Array(mut parts) if parts.len() == 2 => match (parts.remove(0), parts.remove(0)) {
+            (Value::Array(table), body) => (table, body),
+            _ => return Err(invalid("the table is not an array")),
+        },
+        _ => return Err(invalid("expected a table and a body")),
+    };
+    restore_refs(&mut body, &table)?;
+
+    let mut out = Vec::with_capacity(compact.len());
+    rmpv::encode::write_value(&mut out, &body)
+        .map_err(|e| SerializedBytesError::Serialize(e.to_string()))?;
+    Ok(out)
+}
+
+fn invalid(reason: &str) -> SerializedBytesError {
+    SerializedBytesError::Deserialize(format!("Invalid compact encoding, {reason}"))
+}
+
+/// Strings and binaries are deduplicated separately, by their bytes.
+type DedupKey = (bool, Vec<u8>);
+
+/// The key a string or binary value is deduplicated by,
+/// or `None` if the value isn't deduplicated.
+fn dedup_key(value: &Value) -> Option<DedupKey> {
+    let (is_str, bytes) = match value {
+        Value::String(s) => (true, s.as_bytes()),
+        Value::Binary(b) => (false, b.as_slice()),
+        _ => return None,
+    };
+    (bytes.len() >= COMPACT_MIN_LEN).then(|| (is_str, bytes.to_vec()))
+}
+
+fn count_dedupable(value: &Value, counts: &mut HashMap<DedupKey, usize>) {
+    if let Some(key) = dedup_key(value) {
+        *counts.entry(key).or_default() += 1;
+        return;
+    }
+    match value {
+        Value::Array(items) => items.iter().for_each(|v| count_dedupable(v, counts)),
+        Value::Map(entries) => entries.iter().for_each(|(k, v)| {
+            count_dedupable(k, counts);
+            count_dedupable(v, counts);
+        }),
+        _ => (),
+    }
+}
+
+fn replace_repeated(
+    value: &mut Value,
+    counts: &HashMap<DedupKey, usize>,
+    table: &mut Vec<Value>,
+    indexes: &mut HashMap<DedupKey, u32>,
+) {
+    if let Some(key) = dedup_key(value) {
+        if counts.get(&key).copied().unwrap_or_default() > 1 {
+            let index = *indexes.entry(key).or_insert_with(|| {
+                table.push(value.clone());
+                (table.len() - 1) as u32
+            });
+            *value = Value::Ext(COMPACT_REF_EXT_TYPE, index.to_be_bytes().to_vec());
+        }
+        return;
+    }
+    match value {
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|v| replace_repeated(v, counts, table, indexes)),
+        Value::Map(entries) => entries.iter_mut().for_each(|(k, v)| {
+            replace_repeated(k, counts, table, indexes);
+            replace_repeated(v, counts, table, indexes);
+        }),
+        _ => (),
+    }
+}
+
+fn restore_refs(value: &mut Value, table: &[Value]) -> Result<(), SerializedBytesError> {
+    match value {
+        Value::Ext(COMPACT_REF_EXT_TYPE, data) => {
+            let index = <[u8; 4]>::try_from(data.as_slice())
+                .map_err(|_| invalid("a reference is not a u32"))?;
+            *value = table
+                .get(u32::from_be_bytes(index) as usize)
+                .ok_or_else(|| invalid("a reference is outside the table"))?
+                .clone();
+        }
+        Value::Array(items) => {
+            for item in items {
+                restore_refs(item, table)?;
+            }
+        }
+        Value::Map(entries) => {
+            for (k, v) in entries {
+                restore_refs(k, table)?;
+                restore_refs(v, table)?;
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AppResponse;
+    use holochain_serialized_bytes::prelude::*;
+
+    #[test]
+    fn compact_encoding_round_trips_and_is_smaller() {
+        let agent = holo_hash::AgentPubKey::from_raw_36(vec![0xdb; 36]);
+        let hashes = vec![agent; 20];
+        let response = AppResponse::ListWasmHostFunctions(
+            std::iter::repeat("a_long_host_fn_name".to_string())
+                .take(20)
+                .collect(),
+        );
+
+        for bytes in [
+            SerializedBytes::try_from(response).unwrap(),
+            holochain_serialized_bytes::encode(&hashes)
+                .map(|b| SerializedBytes::from(UnsafeBytes::from(b)))
+                .unwrap(),
+        ] {
+            let compact = compact_encode(bytes.bytes()).unwrap();
+            assert!(compact.len() < bytes.bytes().len());
+            assert_eq!(bytes.bytes(), &compact_decode(&compact).unwrap());
+        }
+    }
+
+    #[test]
+    fn compact_encoding_leaves_unique_values_in_place() {
+        let bytes =
+            holochain_serialized_bytes::encode(&("unique string one", "short", "short")).unwrap();
+        let compact = compact_encode(&bytes).unwrap();
+        let value = rmpv::decode::read_value(&mut &compact[..]).unwrap();
+        matches::assert_matches!(value, Value::Array(parts) if parts[0] == Value::Array(vec![]));
+        assert_eq!(bytes, compact_decode(&compact).unwrap());
+    }
+
+    #[test]
+    fn compact_decode_rejects_bad_references() {
+        let mut compact = Vec::new();
+        let value = Value::Array(vec![
+            Value::Array(vec![]),
+            Value::Ext(COMPACT_REF_EXT_TYPE, 0u32.to_be_bytes().to_vec()),
+        ]);
+        rmpv::encode::write_value(&mut compact, &value).unwrap();
+        assert!(compact_decode(&compact).is_err());
+    }
+}
@@ -0,0 +1,35 @@
+use holochain_types::prelude::*;
+
+/// A snapshot of the work queues of a DNA, returned by an
+/// [`AdminRequest::WorkflowStats`](crate::AdminRequest::WorkflowStats).
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct WorkflowStats {
+    /// Ops waiting to be system validated.
+    pub sys_validation: WorkflowQueueStats,
+    /// Ops waiting to be app validated.
+    pub app_validation: WorkflowQueueStats,
+    /// Validated ops waiting to be integrated.
+    pub integration: WorkflowQueueStats,
+    /// Authored ops still being published, summed over all the cells of the DNA.
+    pub publish: WorkflowQueueStats,
+}
+
+/// The state of one work queue and of the workflow which consumes it.
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+pub struct WorkflowQueueStats {
+    /// The number of ops in the queue.
+    pub depth: u64,
+    /// The time since the oldest op in the queue was received, in milliseconds,
+    /// or `None` if the queue is empty. Ops from other peers are received when
+    /// they're stored, and this node's own ops when they're authored, so old data
+    /// arriving by gossip doesn't look like a stalled queue.
+    pub oldest_item_age_ms: Option<u64>,
+    /// The number of times the workflow has run since the conductor started.
+    pub runs: u64,
+    /// How long the most recent run took, in milliseconds.
+    pub last_run_duration_ms: Option<u64>,
+    /// The mean duration of a run, in milliseconds.
+    pub mean_run_duration_ms: Option<u64>,
+}
@@ -51,6 +51,7 @@ pub enum Action {
     Create(Create),
     Update(Update),
     Delete(Delete),
+    RenewMembraneProof(RenewMembraneProof),
 }
 
 /// A summary display for communicating the content of an action
@@ -88,6 +89,11 @@ impl Display for Action {
             Action::Create(create) => write!(f, "create=[author={}, timestamp={}, entry_type={:?}, entry_hash={}]", create.author, create.timestamp, create.entry_type, create.entry_hash),
             Action::Update(update) => write!(f, "create=[author={}, timestamp={}, original_action_address={}, original_entry_address={}, entry_type={:?}, entry_hash={}]", update.author, update.timestamp, update.original_action_address, update.original_entry_address, update.entry_type, update.entry_hash),
             Action::Delete(delete) => write!(f, "create=[author={}, timestamp={}, deletes_address={}, deletes_entry_address={}]", delete.author, delete.timestamp, delete.deletes_address, delete.deletes_entry_address),
+            Action::RenewMembraneProof(rmp) => write!(
+                f,
+                "renew_membrane_proof=[author={}, timestamp={}, supersedes={}]",
+                rmp.author, rmp.timestamp, rmp.supersedes
+            ),
         }
     }
 }
@@ -108,6 +114,7 @@ pub(crate) enum ActionRef<'a> {
     Create(&'a Create),
     Update(&'a Update),
     Delete(&'a Delete),
+    RenewMembraneProof(&'a RenewMembraneProof),
 }
 
 pub type ActionHashed = HoloHashed<Action>;
@@ -211,6 +218,8 @@ write_into_action! {
 
     CreateLink<RateWeight>,
     DeleteLink,
+
+    RenewMembraneProof,
 }
 
 /// a utility macro just to not have to type in the match statement everywhere.
@@ -227,6 +236,7 @@ macro_rules! match_action {
             Action::Create($i) => { $($t)* }
             Action::Update($i) => { $($t)* }
             Action::Delete($i) => { $($t)* }
+            Action::RenewMembraneProof($i) => { $($t)* }
         }
     };
 }
@@ -308,7 +318,8 @@ impl Action {
             | Self::CloseChain(CloseChain { action_seq, .. })
             | Self::OpenChain(OpenChain { action_seq, .. })
             | Self::Create(Create { action_seq, .. })
-            | Self::Update(Update { action_seq, .. }) => *action_seq,
+            | Self::Update(Update { action_seq, .. })
+            | Self::RenewMembraneProof(RenewMembraneProof { action_seq, .. }) => *action_seq,
         }
     }
 
@@ -325,6 +336,7 @@ impl Action {
             Self::OpenChain(OpenChain { prev_action, .. }) => prev_action,
             Self::Create(Create { prev_action, .. }) => prev_action,
             Self::Update(Update { prev_action, .. }) => prev_action,
+            Self::RenewMembraneProof(RenewMembraneProof { prev_action, .. }) => prev_action,
         })
     }
 
@@ -345,7 +357,8 @@ impl Action {
             | Self::InitZomesComplete(InitZomesComplete { .. })
             | Self::DeleteLink(DeleteLink { .. })
             | Self::CloseChain(CloseChain { .. })
-            | Self::OpenChain(OpenChain { .. }) => RateWeight::default(),
+            | Self::OpenChain(OpenChain { .. })
+            | Self::RenewMembraneProof(RenewMembraneProof { .. }) => RateWeight::default(),
         }
     }
 
@@ -365,7 +378,10 @@ impl Action {
             | Self::InitZomesComplete(InitZomesComplete { .. })
             | Self::DeleteLink(DeleteLink { .. })
             | Self::CloseChain(CloseChain { .. })
-            | Self::OpenChain(OpenChain { .. }) => Some(EntryRateWeight::default()),
+            | Self::OpenChain(OpenChain { .. })
+            | Self::RenewMembraneProof(RenewMembraneProof { .. }) => {
+                Some(EntryRateWeight::default())
+            }
         }
     }
 }
@@ -406,6 +422,7 @@ impl_hashable_content_for_ref!(OpenChain);
 impl_hashable_content_for_ref!(Create);
 impl_hashable_content_for_ref!(Update);
 impl_hashable_content_for_ref!(Delete);
+impl_hashable_content_for_ref!(RenewMembraneProof);
 
 /// this id is an internal reference, which also serves as a canonical ordering
 /// for zome initialization.  The value should be auto-generated from the Zome Bundle def
@@ -483,6 +500,28 @@ pub struct AgentValidationPkg {
     pub membrane_proof: Option<MembraneProof>,
 }
 
+/// Action for a membrane proof which replaces the one an agent joined the
+/// network with, or a previous renewal, e.g. because that proof expired.
+///
+/// The proof is validated by the app like the proof in the
+/// [`AgentValidationPkg`], and from then on it is the agent's current proof.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, SerializedBytes, Hash)]
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub struct RenewMembraneProof {
+    pub author: AgentPubKey,
+    pub timestamp: Timestamp,
+    pub action_seq: u32,
+    pub prev_action: ActionHash,
+
+    /// The [`AgentValidationPkg`] or earlier [`RenewMembraneProof`] on this
+    /// chain whose proof this one replaces.
+    pub supersedes: ActionHash,
+    pub membrane_proof: MembraneProof,
+}
+
 /// An action which declares that all zome init functions have successfully
 /// completed, and the chain is ready for commits. Contains no explicit data.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, SerializedBytes, Hash)]
@@ -278,6 +278,11 @@ builder_variant!(AgentValidationPkg {
     membrane_proof: Option<MembraneProof>,
 });
 
+builder_variant!(RenewMembraneProof {
+    supersedes: ActionHash,
+    membrane_proof: MembraneProof,
+});
+
 /// The Dna action can't implement ActionBuilder because it lacks a
 /// `prev_action` field, so this helper is provided as a special case
 #[cfg(feature = "test_utils")]
@@ -0,0 +1,5 @@
+//! Constants shared by the host and guests for blobs, large payloads which are
+//! stored outside of source chain entries.
+
+/// The largest number of bytes in one chunk of a blob.
+pub const BLOB_CHUNK_SIZE: usize = 1024 * 1024;
@@ -85,29 +85,65 @@ impl CapGrant {
     /// Given a grant, is it valid in isolation?
     /// In a world of CRUD, some new entry might update or delete an existing one, but we can check
     /// if a grant is valid in a standalone way.
+    ///
+    /// Grants of [`GrantedFunctions::Queries`] are never valid for zome calls,
+    /// see `.is_valid_query()`.
     pub fn is_valid(
         &self,
         check_function: &GrantedFunction,
         check_agent: &AgentPubKey,
         check_secret: Option<&CapSecret>,
+    ) -> bool {
+        self.is_valid_inner(check_function, check_agent, check_secret, false)
+    }
+
+    /// Given a grant, is it valid in isolation for a read-only remote query?
+    ///
+    /// Only grants of [`GrantedFunctions::Queries`] (and the chain author) are
+    /// valid for queries, so a grant for regular zome calls never makes a
+    /// function queryable and vice versa.
+    pub fn is_valid_query(
+        &self,
+        check_function: &GrantedFunction,
+        check_agent: &AgentPubKey,
+        check_secret: Option<&CapSecret>,
+    ) -> bool {
+        self.is_valid_inner(check_function, check_agent, check_secret, true)
+    }
+
+    fn is_valid_inner(
+        &self,
+        check_function: &GrantedFunction,
+        check_agent: &AgentPubKey,
+        check_secret: Option<&CapSecret>,
+        query: bool,
     ) -> bool {
         match self {
             // Grant is always valid if the author matches the check agent.
-            CapGrant::ChainAuthor(author) => author == check_agent,
+            CapGrant::ChainAuthor(author) => author.ct_eq(check_agent),
             // Otherwise we need to do more work…
             CapGrant::RemoteAgent(ZomeCallCapGrant {
                 access, functions, ..
             }) => {
                 // The checked function needs to be in the grant…
-                let granted = match functions {
-                    GrantedFunctions::All => true,
-                    GrantedFunctions::Listed(fns) => fns.contains(check_function),
+                let granted = match (functions, query) {
+                    (GrantedFunctions::All, false) => true,
+                    (GrantedFunctions::Listed(fns), false) => fns.contains(check_function),
+                    (GrantedFunctions::Queries(fns), true) => fns.contains(check_function),
+                    (GrantedFunctions::ListedPatterns(patterns), false)
+                    | (GrantedFunctions::QueryPatterns(patterns), true) => patterns
+                        .iter()
+                        .any(|pattern| pattern_matches(pattern, check_function)),
+                    _ => false,
                 };
                 granted
                 // The agent needs to be valid…
                 && match access {
-                    // The grant is assigned so the agent needs to match…
-                    CapAccess::Assigned { assignees, .. } => assignees.contains(check_agent),
+                    // The grant is assigned so the agent needs to match, checking every
+                    // assignee in constant time so the time taken doesn't reveal which matched…
+                    CapAccess::Assigned { assignees, .. } => assignees
+                        .iter()
+                        .fold(false, |found, a| a.ct_eq(check_agent) | found),
                     // The grant has no assignees so is always valid…
                     _ => true,
                 }
@@ -203,4 +239,133 @@ pub enum GrantedFunctions {
     All,
     /// grant to specified zomes and functions
     Listed(BTreeSet<GrantedFunction>),
+    /// grant read-only access to specified zomes and functions.
+    ///
+    /// These functions can only be reached through a remote query, which is
+    /// not allowed to write to the source chain or the network.
+    Queries(BTreeSet<GrantedFunction>),
+    /// grant to the functions matching any of these patterns,
+    /// see [`FunctionPattern`].
+    ListedPatterns(BTreeSet<GrantedFunctionPattern>),
+    /// grant read-only access to the functions matching any of these patterns,
+    /// as for [`GrantedFunctions::Queries`].
+    QueryPatterns(BTreeSet<GrantedFunctionPattern>),
+}
+
+/// Which functions of a zome a [`GrantedFunctionPattern`] matches.
+///
+/// Patterns can be written as strings: `*` matches every function, a name
+/// ending in `*` such as `get_*` matches every function starting with the rest
+/// of the name, and any other name only matches that function.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub enum FunctionPattern {
+    /// Every function in the zome.
+    Any,
+    /// Every function whose name starts with this prefix.
+    Prefix(String),
+    /// Only this function.
+    Exact(FunctionName),
+}
+
+impl FunctionPattern {
+    /// Does this pattern match the function?
+    pub fn matches(&self, function: &FunctionName) -> bool {
+        match self {
+            FunctionPattern::Any => true,
+            FunctionPattern::Prefix(prefix) => function.0.starts_with(prefix.as_str()),
+            FunctionPattern::Exact(name) => name == function,
+        }
+    }
+}
+
+impl From<&str> for FunctionPattern {
+    fn from(pattern: &str) -> Self {
+        match pattern.strip_suffix('*') {
+            Some("") => FunctionPattern::Any,
+            Some(prefix) => FunctionPattern::Prefix(prefix.to_string()),
+            None => FunctionPattern::Exact(pattern.into()),
+        }
+    }
+}
+
+/// A zome and a pattern for the functions granted in it.
+pub type GrantedFunctionPattern = (ZomeName, FunctionPattern);
+
+fn pattern_matches((zome, function): &GrantedFunctionPattern, check: &GrantedFunction) -> bool {
+    *zome == check.0 && function.matches(&check.1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn query_grants_are_separate_from_call_grants() {
+        let agent = AgentPubKey::from_raw_36(vec![1; 36]);
+        let function: GrantedFunction = ("zome".into(), "fn".into());
+        let fns: BTreeSet<_> = [function.clone()].into_iter().collect();
+        let grant = |functions| {
+            CapGrant::from(ZomeCallCapGrant::new(
+                "tag".into(),
+                CapAccess::Unrestricted,
+                functions,
+            ))
+        };
+
+        let listed = grant(GrantedFunctions::Listed(fns.clone()));
+        assert!(listed.is_valid(&function, &agent, None));
+        assert!(!listed.is_valid_query(&function, &agent, None));
+
+        let all = grant(GrantedFunctions::All);
+        assert!(!all.is_valid_query(&function, &agent, None));
+
+        let queries = grant(GrantedFunctions::Queries(fns));
+        assert!(queries.is_valid_query(&function, &agent, None));
+        assert!(!queries.is_valid(&function, &agent, None));
+
+        let author = CapGrant::ChainAuthor(agent.clone());
+        assert!(author.is_valid_query(&function, &agent, None));
+    }
+
+    #[test]
+    fn pattern_grants_match_functions() {
+        let agent = AgentPubKey::from_raw_36(vec![1; 36]);
+        let get: GrantedFunction = ("zome".into(), "get_thing".into());
+        let create: GrantedFunction = ("zome".into(), "create_thing".into());
+        let other_zome: GrantedFunction = ("other".into(), "get_thing".into());
+        let grant = |functions| {
+            CapGrant::from(ZomeCallCapGrant::new(
+                "tag".into(),
+                CapAccess::Unrestricted,
+                functions,
+            ))
+        };
+
+        assert_eq!(FunctionPattern::Any, "*".into());
+        assert_eq!(FunctionPattern::Prefix("get_".into()), "get_*".into());
+        assert_eq!(FunctionPattern::Exact("get".into()), "get".into());
+
+        let prefix: BTreeSet<_> = [("zome".into(), "get_*".into())].into_iter().collect();
+        let listed = grant(GrantedFunctions::ListedPatterns(prefix.clone()));
+        assert!(listed.is_valid(&get, &agent, None));
+        assert!(!listed.is_valid(&create, &agent, None));
+        assert!(!listed.is_valid(&other_zome, &agent, None));
+        assert!(!listed.is_valid_query(&get, &agent, None));
+
+        let queries = grant(GrantedFunctions::QueryPatterns(prefix));
+        assert!(queries.is_valid_query(&get, &agent, None));
+        assert!(!queries.is_valid(&get, &agent, None));
+
+        let zome: BTreeSet<_> = [("zome".into(), FunctionPattern::Any)]
+            .into_iter()
+            .collect();
+        let whole_zome = grant(GrantedFunctions::QueryPatterns(zome));
+        assert!(whole_zome.is_valid_query(&get, &agent, None));
+        assert!(whole_zome.is_valid_query(&create, &agent, None));
+        assert!(!whole_zome.is_valid_query(&other_zome, &agent, None));
+    }
 }
@@ -3,6 +3,8 @@
 
 use std::collections::HashSet;
 
+use crate::genesis::MembraneProof;
+use crate::Timestamp;
 use holo_hash::AgentPubKey;
 use holo_hash::{ActionHash, DnaHash};
 use holochain_serialized_bytes::prelude::*;
@@ -15,7 +17,8 @@ mod test;
 /// Starting from some chain position given as an [`ActionHash`]
 /// the chain is walked backwards to genesis.
 /// The filter can stop early by specifying the number of
-/// chain items to take and / or an [`ActionHash`] to consume until.
+/// chain items to take, a set of [`ActionHash`]es to consume until
+/// and / or a [`Timestamp`] to consume back to.
 pub struct ChainFilter<H: Eq + Ord + std::hash::Hash = ActionHash> {
     /// The starting position of the filter.
     pub chain_top: H,
@@ -25,6 +28,9 @@ pub struct ChainFilter<H: Eq + Ord + std::hash::Hash = ActionHash> {
     /// Should the query return any entries that are
     /// cached at the agent activity to save network hops.
     pub include_cached_entries: bool,
+    /// Only take actions authored at or after this time.
+    #[serde(default)]
+    pub until_timestamp: Option<Timestamp>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Eq, Clone)]
@@ -107,6 +113,7 @@ impl<H: Eq + Ord + std::hash::Hash> ChainFilter<H> {
             chain_top,
             filters: Default::default(),
             include_cached_entries: false,
+            until_timestamp: None,
         }
     }
 
@@ -150,6 +157,37 @@ impl<H: Eq + Ord + std::hash::Hash> ChainFilter<H> {
         self
     }
 
+    /// Take all actions until any of these action hashes is found,
+    /// as if [`ChainFilter::until`] was called with each of them.
+    pub fn until_any(self, action_hashes: impl IntoIterator<Item = H>) -> Self {
+        action_hashes
+            .into_iter()
+            .fold(self, |filter, action_hash| filter.until(action_hash))
+    }
+
+    /// Take all actions authored at or after this time, walking back from the
+    /// starting position until an action authored before it.
+    ///
+    /// Action timestamps strictly increase along a valid chain, so this is
+    /// deterministic. The authority must hold the action before the earliest
+    /// one returned, to show that nothing later was left out, unless the walk
+    /// reaches genesis or the bottom set by the other filters first.
+    ///
+    /// Combined with `take` and `until`, whichever is hit first ends the walk.
+    /// Setting this more than once keeps the latest timestamp.
+    pub fn until_timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.until_timestamp = Some(
+            self.until_timestamp
+                .map_or(timestamp, |old| old.max(timestamp)),
+        );
+        self
+    }
+
+    /// Get the until timestamp if there is one.
+    pub fn get_until_timestamp(&self) -> Option<Timestamp> {
+        self.until_timestamp
+    }
+
     /// Get the until hashes if there are any.
     pub fn get_until(&self) -> Option<&HashSet<H>> {
         match &self.filters {
@@ -188,3 +226,10 @@ pub struct OpenChainInput {
     /// The hash of the DNA that was migrated from.
     pub prev_dna_hash: DnaHash,
 }
+
+/// Input to renew the membrane proof of a chain.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct RenewMembraneProofInput {
+    /// The membrane proof which supersedes the agent's current one.
+    pub membrane_proof: MembraneProof,
+}
@@ -36,3 +36,25 @@ fn until_hash_is_a_set() {
             .until(hash(1)),
     );
 }
+
+#[test]
+fn until_any_is_a_union() {
+    assert_eq!(
+        ChainFilter::new(hash(0)).until(hash(1)).until(hash(2)),
+        ChainFilter::new(hash(0)).until_any([hash(2), hash(1)]),
+    );
+}
+
+#[test]
+fn until_timestamp_is_max() {
+    assert_eq!(
+        Some(Timestamp(5)),
+        ChainFilter::new(hash(0))
+            .until_timestamp(Timestamp(5))
+            .until_timestamp(Timestamp(2))
+            .get_until_timestamp()
+    );
+    let filter = ChainFilter::new(hash(0)).until_timestamp(Timestamp(5));
+    let sb = SerializedBytes::try_from(&filter).unwrap();
+    assert_eq!(filter, ChainFilter::try_from(sb).unwrap());
+}
@@ -0,0 +1,95 @@
+//! Dual signed entries let two agents agree on an entry without a countersigning session.
+//!
+//! The initiator commits a [`Entry::DualSign`] holding a [`DualSignData::Proposal`]
+//! which names the counterparty. Whenever it suits them, the counterparty accepts the
+//! proposal by committing an [`Action::Update`](crate::Action::Update) of the proposal's
+//! action, with an [`Entry::DualSign`] holding a [`DualSignData::Acceptance`] and the same
+//! app entry bytes. Neither agent has to be online at the same time as the other.
+//!
+//! System validation ties the acceptance to the proposal: it is only valid if it was
+//! authored by the counterparty which the proposal names, for the very same app entry.
+
+use crate::entry::AppEntryBytes;
+use crate::Entry;
+use holo_hash::AgentPubKey;
+#[cfg(feature = "hashing")]
+use holo_hash::EntryHash;
+use holochain_serialized_bytes::prelude::*;
+
+/// The part of a dual signed entry which says which side of the agreement it is.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub enum DualSignData {
+    /// Committed by the initiator, naming the agent which may accept it.
+    Proposal {
+        /// The only agent which may accept the proposal.
+        counterparty: AgentPubKey,
+    },
+    /// Committed by the counterparty as an update of the proposal.
+    Acceptance {
+        /// The author of the accepted proposal.
+        initiator: AgentPubKey,
+    },
+}
+
+impl DualSignData {
+    /// The agent on the other side of the agreement from the author of this data.
+    pub fn other_party(&self) -> &AgentPubKey {
+        match self {
+            DualSignData::Proposal { counterparty } => counterparty,
+            DualSignData::Acceptance { initiator } => initiator,
+        }
+    }
+}
+
+/// Build the entry which proposes `entry_bytes` to `counterparty`.
+pub fn dual_sign_proposal(counterparty: AgentPubKey, entry_bytes: AppEntryBytes) -> Entry {
+    Entry::DualSign(
+        Box::new(DualSignData::Proposal { counterparty }),
+        entry_bytes,
+    )
+}
+
+/// Build the entry which accepts a proposal of `entry_bytes` made by `initiator`.
+pub fn dual_sign_acceptance(initiator: AgentPubKey, entry_bytes: AppEntryBytes) -> Entry {
+    Entry::DualSign(
+        Box::new(DualSignData::Acceptance { initiator }),
+        entry_bytes,
+    )
+}
+
+/// The hash of the proposal which an acceptance by `counterparty` of `entry_bytes` must update.
+#[cfg(feature = "hashing")]
+pub fn dual_sign_proposal_hash(counterparty: AgentPubKey, entry_bytes: AppEntryBytes) -> EntryHash {
+    EntryHash::with_data_sync(&dual_sign_proposal(counterparty, entry_bytes))
+}
+
+#[cfg(all(test, feature = "hashing"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proposal_hash_ties_acceptance_to_counterparty() {
+        let alice = AgentPubKey::from_raw_36(vec![1; 36]);
+        let bob = AgentPubKey::from_raw_36(vec![2; 36]);
+        let bytes = AppEntryBytes(SerializedBytes::from(UnsafeBytes::from(vec![0xdb; 8])));
+
+        let proposal = dual_sign_proposal(bob.clone(), bytes.clone());
+        assert_eq!(
+            EntryHash::with_data_sync(&proposal),
+            dual_sign_proposal_hash(bob.clone(), bytes.clone())
+        );
+        assert_ne!(
+            EntryHash::with_data_sync(&proposal),
+            dual_sign_proposal_hash(alice.clone(), bytes.clone())
+        );
+
+        match dual_sign_acceptance(alice.clone(), bytes) {
+            Entry::DualSign(data, _) => assert_eq!(&alice, data.other_party()),
+            _ => unreachable!(),
+        }
+    }
+}
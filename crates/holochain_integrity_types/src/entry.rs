@@ -9,6 +9,7 @@ use crate::capability::CapClaim;
 use crate::capability::CapGrant;
 use crate::capability::ZomeCallCapGrant;
 use crate::countersigning::CounterSigningSessionData;
+use crate::dual_signing::DualSignData;
 use crate::AppEntryDef;
 use crate::EntryDefIndex;
 use crate::EntryType;
@@ -98,6 +99,9 @@ pub enum Entry {
     App(AppEntryBytes),
     /// Application entry data for entries that need countersigning to move forward multiple chains together.
     CounterSign(Box<CounterSigningSessionData>, AppEntryBytes),
+    /// Application entry data for entries that one agent proposes and another accepts later.
+    /// See [`dual_signing`](crate::dual_signing).
+    DualSign(Box<DualSignData>, AppEntryBytes),
     /// The capability claim system entry which allows committing a granted permission
     /// for later use
     CapClaim(CapClaimEntry),
@@ -151,9 +155,9 @@ impl Entry {
             (Entry::Agent(_), _) => Some(EntryType::AgentPubKey),
             (Entry::CapClaim(_), _) => Some(EntryType::CapClaim),
             (Entry::CapGrant(_), _) => Some(EntryType::CapGrant),
-            (Entry::App(_), Some(aed)) | (Entry::CounterSign(_, _), Some(aed)) => {
-                Some(EntryType::App(aed))
-            }
+            (Entry::App(_), Some(aed))
+            | (Entry::CounterSign(_, _), Some(aed))
+            | (Entry::DualSign(_, _), Some(aed)) => Some(EntryType::App(aed)),
             _ => None,
         }
     }
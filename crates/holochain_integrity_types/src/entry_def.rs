@@ -56,6 +56,23 @@ pub trait EntryDefRegistration {
 )]
 pub struct RequiredValidations(pub u8);
 
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+/// Lets authorities purge the bytes of a deleted entry, while keeping
+/// its actions and their deletes, once the retention period has passed.
+///
+/// An entry is only purged when every action which created it has been deleted,
+/// and the retention period starts when the authority integrated the last delete.
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub struct PurgePolicy {
+    /// How long a deleted entry is kept for, in seconds.
+    pub retention_secs: u64,
+}
+
 #[derive(
     Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
 )]
@@ -68,13 +85,57 @@ pub struct EntryDef {
     pub id: EntryDefId,
     /// Public or Private
     pub visibility: EntryVisibility,
-    /// how many validations to receive before considered "network saturated" (MAX value of 50?)
-    pub required_validations: RequiredValidations,
     /// Should this entry be cached with agent activity authorities
     /// for reduced networked hops when using `must_get_agent_activity`.
     /// Note this will result in more storage being used on the DHT.
     /// Defaults to false.
     pub cache_at_agent_activity: bool,
+    /// The policies the host enforces for entries of this type
+    /// over their lifecycle.
+    ///
+    /// Flattened into the def, so defs serialized before the policies were
+    /// grouped, with `required_validations` and `purge_policy` alongside the
+    /// other fields, still deserialize.
+    #[serde(flatten)]
+    pub policies: EntryDefPolicies,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+/// The policies of an entry type, which the host stores along with
+/// the [`EntryDef`] and enforces for every entry of the type.
+///
+/// Every policy has a default, and policies which are missing when an
+/// [`EntryDef`] is deserialized take their default, so new policies
+/// can be added without breaking existing zomes.
+#[serde(default)]
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub struct EntryDefPolicies {
+    /// How many validation receipts the author collects for an entry
+    /// before it is considered published.
+    pub required_validations: RequiredValidations,
+    /// The largest an app entry of this type may be, in bytes.
+    /// Entries over this size are rejected by app validation.
+    /// Defaults to no limit other than the host's maximum entry size.
+    pub max_size: Option<u32>,
+    /// When authorities may purge this entry after it has been deleted.
+    /// Defaults to never.
+    #[serde(alias = "purge_policy")]
+    pub purge: Option<PurgePolicy>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -101,6 +162,10 @@ pub struct EntryDefs(pub Vec<EntryDef>);
 pub enum EntryVisibility {
     Public,
     Private,
+    /// Published to the DHT like a public entry, but authorities leave it
+    /// out of listings such as entry updates and links to it.
+    /// It can only be fetched by someone who already knows its hash.
+    Unlisted,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
@@ -137,6 +202,18 @@ impl From<RequiredValidations> for u8 {
     }
 }
 
+impl PurgePolicy {
+    /// Purge deleted entries once they have been deleted for this many seconds.
+    pub const fn after_secs(retention_secs: u64) -> Self {
+        Self { retention_secs }
+    }
+
+    /// The retention period as a [`std::time::Duration`].
+    pub fn retention(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.retention_secs)
+    }
+}
+
 impl Default for RequiredValidations {
     fn default() -> Self {
         Self(DEFAULT_REQUIRED_VALIDATIONS)
@@ -148,20 +225,59 @@ impl EntryVisibility {
     pub fn is_public(&self) -> bool {
         *self == EntryVisibility::Public
     }
+
+    /// Whether the entry is published to the DHT, i.e. it is public or unlisted.
+    pub fn is_published(&self) -> bool {
+        *self != EntryVisibility::Private
+    }
+
+    /// Whether authorities should leave the entry out of listings.
+    pub fn is_unlisted(&self) -> bool {
+        *self == EntryVisibility::Unlisted
+    }
+}
+
+impl EntryDefPolicies {
+    /// Require this many validation receipts before an entry is considered published.
+    pub const fn with_required_validations(
+        mut self,
+        required_validations: RequiredValidations,
+    ) -> Self {
+        self.required_validations = required_validations;
+        self
+    }
+
+    /// Reject app entries which are larger than this many bytes.
+    pub const fn with_max_size(mut self, max_size: u32) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Let authorities purge deleted entries as set out by the [`PurgePolicy`].
+    pub const fn with_purge(mut self, purge: PurgePolicy) -> Self {
+        self.purge = Some(purge);
+        self
+    }
+
+    /// Whether an app entry of `size` bytes is within the size limit.
+    pub fn allows_size(&self, size: usize) -> bool {
+        self.max_size
+            .map_or(true, |max_size| size <= max_size as usize)
+    }
 }
 
 impl EntryDef {
     pub fn new(
         id: EntryDefId,
         visibility: EntryVisibility,
-        required_validations: RequiredValidations,
         cache_at_agent_activity: bool,
+        policies: EntryDefPolicies,
     ) -> Self {
         Self {
             id,
             visibility,
-            required_validations,
             cache_at_agent_activity,
+            policies,
         }
     }
 
@@ -249,8 +365,62 @@ impl Default for EntryDef {
         Self {
             id: EntryDefId::App(AppEntryName(Default::default())),
             visibility: Default::default(),
-            required_validations: Default::default(),
             cache_at_agent_activity: false,
+            policies: Default::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An [`EntryDef`] as it was serialized before its policies were grouped.
+    #[derive(serde::Serialize)]
+    struct UngroupedEntryDef {
+        id: EntryDefId,
+        visibility: EntryVisibility,
+        required_validations: RequiredValidations,
+        cache_at_agent_activity: bool,
+        purge_policy: Option<PurgePolicy>,
+    }
+
+    #[test]
+    fn entry_def_deserializes_ungrouped_policies() {
+        let ungrouped = UngroupedEntryDef {
+            id: "post".into(),
+            visibility: EntryVisibility::Private,
+            required_validations: 7.into(),
+            cache_at_agent_activity: true,
+            purge_policy: Some(PurgePolicy { retention_secs: 60 }),
+        };
+        let bytes = holochain_serialized_bytes::encode(&ungrouped).unwrap();
+        let entry_def: EntryDef = holochain_serialized_bytes::decode(&bytes).unwrap();
+        assert_eq!(
+            entry_def,
+            EntryDef::new(
+                "post".into(),
+                EntryVisibility::Private,
+                true,
+                EntryDefPolicies::default()
+                    .with_required_validations(7.into())
+                    .with_purge(PurgePolicy { retention_secs: 60 }),
+            )
+        );
+
+        // Grouped policies survive a round trip, and missing ones take their defaults.
+        let bytes = holochain_serialized_bytes::encode(&entry_def).unwrap();
+        assert_eq!(
+            entry_def,
+            holochain_serialized_bytes::decode::<_, EntryDef>(&bytes).unwrap()
+        );
+        let bytes = holochain_serialized_bytes::encode(&UngroupedEntryDef {
+            purge_policy: None,
+            ..ungrouped
+        })
+        .unwrap();
+        let entry_def: EntryDef = holochain_serialized_bytes::decode(&bytes).unwrap();
+        assert_eq!(entry_def.policies.max_size, None);
+        assert_eq!(entry_def.policies.purge, None);
+    }
+}
@@ -12,10 +12,12 @@
 
 #[allow(missing_docs)]
 pub mod action;
+pub mod blob;
 pub mod capability;
 pub mod chain;
 pub mod countersigning;
 pub mod dna_properties;
+pub mod dual_signing;
 pub mod entry;
 #[allow(missing_docs)]
 pub mod entry_def;
@@ -29,6 +31,7 @@ pub mod op;
 pub mod prelude;
 pub mod rate_limit;
 pub mod record;
+pub mod serialized_size;
 pub mod signature;
 pub use kitsune_p2p_timestamp as timestamp;
 #[allow(missing_docs)]
@@ -62,6 +62,148 @@ impl LinkTag {
     pub fn into_inner(self) -> Vec<u8> {
         self.0
     }
+
+    /// Decode the structured payload carried by this tag, if it has one.
+    pub fn payload(&self) -> Option<LinkTagPayload> {
+        let bytes = self.0.strip_prefix(LINK_TAG_PAYLOAD_PREFIX)?;
+        holochain_serialized_bytes::decode(&bytes).ok()
+    }
+
+    /// Whether this tag carries a structured payload which matches all the filters.
+    /// A tag without a payload only matches when there are no filters.
+    pub fn matches_fields(&self, filters: &[LinkTagFieldFilter]) -> bool {
+        if filters.is_empty() {
+            return true;
+        }
+        self.payload()
+            .map_or(false, |payload| filters.iter().all(|f| f.matches(&payload)))
+    }
+}
+
+/// The bytes which a tag carrying a [`LinkTagPayload`] starts with.
+pub const LINK_TAG_PAYLOAD_PREFIX: &[u8] = b"\0hcltp";
+
+/// A value in a [`LinkTagPayload`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum LinkTagValue {
+    Bool(bool),
+    Int(i64),
+    Text(String),
+    Bytes(#[serde(with = "serde_bytes")] Vec<u8>),
+}
+
+impl LinkTagValue {
+    /// Compare two values of the same kind. Values of different kinds don't compare.
+    pub fn compare(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (Self::Bool(a), Self::Bool(b)) => Some(a.cmp(b)),
+            (Self::Int(a), Self::Int(b)) => Some(a.cmp(b)),
+            (Self::Text(a), Self::Text(b)) => Some(a.cmp(b)),
+            (Self::Bytes(a), Self::Bytes(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+}
+
+impl From<bool> for LinkTagValue {
+    fn from(v: bool) -> Self {
+        Self::Bool(v)
+    }
+}
+
+impl From<i64> for LinkTagValue {
+    fn from(v: i64) -> Self {
+        Self::Int(v)
+    }
+}
+
+impl From<u32> for LinkTagValue {
+    fn from(v: u32) -> Self {
+        Self::Int(v.into())
+    }
+}
+
+impl From<String> for LinkTagValue {
+    fn from(v: String) -> Self {
+        Self::Text(v)
+    }
+}
+
+impl From<&str> for LinkTagValue {
+    fn from(v: &str) -> Self {
+        Self::Text(v.to_string())
+    }
+}
+
+impl From<Vec<u8>> for LinkTagValue {
+    fn from(v: Vec<u8>) -> Self {
+        Self::Bytes(v)
+    }
+}
+
+/// Small named values carried in a link's tag, such as the weight of a vote,
+/// so they can be read and filtered on by `get_links` without fetching an entry.
+///
+/// A tag carrying a payload may be larger than a plain tag, up to the
+/// maximum payload tag size checked by sys validation.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LinkTagPayload(std::collections::BTreeMap<String, LinkTagValue>);
+
+impl LinkTagPayload {
+    /// An empty payload.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a field to the payload, replacing any field with the same name.
+    pub fn with(mut self, name: impl Into<String>, value: impl Into<LinkTagValue>) -> Self {
+        self.0.insert(name.into(), value.into());
+        self
+    }
+
+    /// The value of a field.
+    pub fn get(&self, name: &str) -> Option<&LinkTagValue> {
+        self.0.get(name)
+    }
+
+    /// The fields of the payload, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &LinkTagValue)> {
+        self.0.iter()
+    }
+}
+
+impl TryFrom<&LinkTagPayload> for LinkTag {
+    type Error = SerializedBytesError;
+
+    fn try_from(payload: &LinkTagPayload) -> Result<Self, Self::Error> {
+        let mut bytes = LINK_TAG_PAYLOAD_PREFIX.to_vec();
+        bytes.extend(holochain_serialized_bytes::encode(payload)?);
+        Ok(Self(bytes))
+    }
+}
+
+/// A condition on a field of the [`LinkTagPayload`] of a link's tag.
+/// Links whose tag has no payload, or no such field, don't match.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum LinkTagFieldFilter {
+    /// The field equals the value.
+    Eq(String, LinkTagValue),
+    /// The field is from the start value inclusive to the end value exclusive.
+    Range(String, LinkTagValue, LinkTagValue),
+}
+
+impl LinkTagFieldFilter {
+    /// Whether the payload matches this condition.
+    pub fn matches(&self, payload: &LinkTagPayload) -> bool {
+        use std::cmp::Ordering;
+        match self {
+            Self::Eq(name, value) => payload.get(name) == Some(value),
+            Self::Range(name, start, end) => payload.get(name).map_or(false, |v| {
+                matches!(v.compare(start), Some(Ordering::Greater | Ordering::Equal))
+                    && v.compare(end) == Some(Ordering::Less)
+            }),
+        }
+    }
 }
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug, Serialize, Deserialize)]
@@ -194,3 +336,31 @@ impl TryInto<String> for LinkTag {
         String::from_utf8(self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_tag_payload_filters() {
+        let payload = LinkTagPayload::new()
+            .with("weight", 3u32)
+            .with("kind", "up");
+        let tag = LinkTag::try_from(&payload).unwrap();
+        assert_eq!(Some(payload), tag.payload());
+        assert_eq!(None, LinkTag::from("weight").payload());
+
+        let eq = |name: &str, value: LinkTagValue| LinkTagFieldFilter::Eq(name.into(), value);
+        let range = |start: i64, end: i64| {
+            LinkTagFieldFilter::Range("weight".into(), start.into(), end.into())
+        };
+        assert!(tag.matches_fields(&[]));
+        assert!(tag.matches_fields(&[eq("kind", "up".into()), range(3, 4)]));
+        assert!(!tag.matches_fields(&[range(0, 3)]));
+        assert!(!tag.matches_fields(&[eq("kind", "down".into())]));
+        // Values of a different kind never match.
+        assert!(!tag.matches_fields(&[eq("weight", "3".into())]));
+        assert!(!tag.matches_fields(&[eq("missing", true.into())]));
+        assert!(!LinkTag::from("up").matches_fields(&[eq("kind", "up".into())]));
+    }
+}
@@ -2,10 +2,12 @@
 
 pub use crate::action::conversions::*;
 pub use crate::action::*;
+pub use crate::blob::*;
 pub use crate::capability::*;
 pub use crate::chain::*;
 pub use crate::countersigning::*;
 pub use crate::dna_properties::*;
+pub use crate::dual_signing::*;
 pub use crate::entry::*;
 pub use crate::entry_def::*;
 pub use crate::genesis::*;
@@ -15,6 +17,7 @@ pub use crate::link::*;
 pub use crate::op::*;
 pub use crate::rate_limit::*;
 pub use crate::record::*;
+pub use crate::serialized_size::*;
 pub use crate::signature::*;
 pub use crate::timestamp::*;
 pub use crate::trace::*;
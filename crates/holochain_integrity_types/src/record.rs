@@ -78,7 +78,9 @@ impl<E: Borrow<Entry>> RecordEntry<E> {
                 // TODO this is a problem case but it is reachable
                 unreachable!("Entry is present for an action type which has no entry reference")
             }
-            (None, Some(EntryVisibility::Public)) => RecordEntry::NotStored,
+            (None, Some(EntryVisibility::Public | EntryVisibility::Unlisted)) => {
+                RecordEntry::NotStored
+            }
         }
     }
 
@@ -0,0 +1,88 @@
+//! Computing the size of a value once it is serialized, without building the
+//! serialized bytes.
+//!
+//! The size is exact: it is the length of the bytes which [`SerializedBytes`]
+//! would hold for the same value. This lets apps check a structure against
+//! [`ENTRY_SIZE_LIMIT`](crate::entry::ENTRY_SIZE_LIMIT) before committing it.
+
+use crate::Action;
+use crate::Entry;
+use holochain_serialized_bytes::prelude::*;
+
+/// Counts the bytes written to it and throws them away.
+#[derive(Default)]
+struct ByteCounter(usize);
+
+impl std::io::Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The number of bytes a value takes up once serialized to [`SerializedBytes`].
+pub fn serialized_size<T>(value: &T) -> Result<usize, SerializedBytesError>
+where
+    T: serde::Serialize + ?Sized,
+{
+    // Serialize the same way as `holochain_serialized_bytes::encode`.
+    let mut se = rmp_serde::encode::Serializer::new(ByteCounter::default()).with_struct_map();
+    value
+        .serialize(&mut se)
+        .map_err(|e| SerializedBytesError::Serialize(e.to_string()))?;
+    Ok(se.into_inner().0)
+}
+
+/// Types whose serialized size can be computed without serializing them.
+pub trait SerializedSize {
+    /// The number of bytes this takes up once serialized.
+    fn serialized_size(&self) -> Result<usize, SerializedBytesError>;
+}
+
+impl SerializedSize for Entry {
+    fn serialized_size(&self) -> Result<usize, SerializedBytesError> {
+        serialized_size(self)
+    }
+}
+
+impl SerializedSize for Action {
+    fn serialized_size(&self) -> Result<usize, SerializedBytesError> {
+        serialized_size(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AgentPubKey;
+
+    #[derive(Debug, serde::Serialize)]
+    struct Post {
+        title: String,
+        tags: Vec<String>,
+        likes: Option<u64>,
+    }
+
+    #[test]
+    fn serialized_size_matches_encode() {
+        let post = Post {
+            title: "hello".repeat(100),
+            tags: vec!["a".into(), "bc".into()],
+            likes: Some(u64::MAX),
+        };
+        assert_eq!(
+            holochain_serialized_bytes::encode(&post).unwrap().len(),
+            serialized_size(&post).unwrap()
+        );
+
+        let entry = Entry::Agent(AgentPubKey::from_raw_36(vec![0xdb; 36]));
+        assert_eq!(
+            holochain_serialized_bytes::encode(&entry).unwrap().len(),
+            entry.serialized_size().unwrap()
+        );
+    }
+}
@@ -21,6 +21,17 @@ pub enum UnresolvedDependencies {
     AgentActivity(AgentPubKey, ChainFilter),
 }
 
+/// The result of the optional `dependency_hints` callback.
+///
+/// An integrity zome can declare the hashes which its `validate` callback is
+/// expected to `must_get` for an op, e.g. the base record of a
+/// `RegisterCreateLink`. Holochain fetches these in parallel before running
+/// `validate`, so that validation doesn't have to wait on them one at a time.
+/// Hints are only an optimisation, `validate` must still `must_get` anything
+/// it relies on.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, SerializedBytes)]
+pub struct DependencyHintsCallbackResult(pub Vec<AnyDhtHash>);
+
 /// The level of validation package required by
 /// an entry.
 #[derive(
@@ -0,0 +1,417 @@
+//! A lair store persisted in an operating system keychain.
+//!
+//! Lair already encrypts the secret material of every entry with a key
+//! derived from the keystore passphrase, so the keychain only ever sees
+//! encrypted entries. Each entry is stored under its lair tag, and an
+//! additional index item lists the tags so the store can be reloaded.
+
+use crate::*;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use futures::future::{BoxFuture, FutureExt};
+use kitsune_p2p_types::dependencies::lair_keystore_api;
+use lair_keystore_api::lair_store::traits::*;
+use lair_keystore_api::prelude::*;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The keychain account under which the list of stored tags is kept.
+const INDEX_ACCOUNT: &str = "__holochain_keystore_index";
+
+/// Access to a keychain which stores opaque values by account name.
+///
+/// Implementations are expected to block, they are always called from
+/// a blocking task.
+pub trait KeychainAdapter: 'static + Send + Sync {
+    /// Read the value stored for `account`, if any.
+    fn get(&self, account: &str) -> LairResult<Option<Vec<u8>>>;
+
+    /// Store `value` for `account`, replacing any existing value.
+    fn set(&self, account: &str, value: &[u8]) -> LairResult<()>;
+}
+
+/// Create a [`LairStoreFactory`] which persists entries through the given
+/// [`KeychainAdapter`].
+pub fn create_keychain_store_factory(adapter: Arc<dyn KeychainAdapter>) -> LairStoreFactory {
+    LairStoreFactory(Arc::new(KeychainStoreFactory(adapter)))
+}
+
+/// Create a [`KeychainAdapter`] for the keychain of the current operating
+/// system, storing all items under the given service name.
+///
+/// This uses the `security` tool on macOS and `secret-tool` (libsecret)
+/// on Linux. Other platforms are not supported. Values are always written
+/// through the tool's stdin, never its arguments, so they can't be read from
+/// the process list.
+pub fn os_keychain(service: impl Into<String>) -> LairResult<Arc<dyn KeychainAdapter>> {
+    if cfg!(any(target_os = "macos", target_os = "linux")) {
+        Ok(Arc::new(OsKeychain {
+            service: service.into(),
+        }))
+    } else {
+        Err("OS keychain is not supported on this platform".into())
+    }
+}
+
+struct OsKeychain {
+    service: String,
+}
+
+impl OsKeychain {
+    fn run(
+        &self,
+        mut cmd: std::process::Command,
+        stdin: Option<&str>,
+    ) -> LairResult<Option<String>> {
+        use std::io::Write;
+        use std::process::Stdio;
+
+        let mut child = cmd
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        if let Some(stdin) = stdin {
+            child
+                .stdin
+                .take()
+                .ok_or_else(|| one_err::OneErr::from("keychain stdin unavailable"))?
+                .write_all(stdin.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ))
+    }
+}
+
+impl KeychainAdapter for OsKeychain {
+    fn get(&self, account: &str) -> LairResult<Option<Vec<u8>>> {
+        let cmd = if cfg!(target_os = "macos") {
+            let mut cmd = std::process::Command::new("security");
+            cmd.args([
+                "find-generic-password",
+                "-w",
+                "-s",
+                &self.service,
+                "-a",
+                account,
+            ]);
+            cmd
+        } else {
+            let mut cmd = std::process::Command::new("secret-tool");
+            cmd.args(["lookup", "service", &self.service, "account", account]);
+            cmd
+        };
+        match self.run(cmd, None)? {
+            Some(value) if !value.is_empty() => {
+                Ok(Some(STANDARD.decode(value).map_err(one_err::OneErr::new)?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn set(&self, account: &str, value: &[u8]) -> LairResult<()> {
+        let value = STANDARD.encode(value);
+        let stored = if cfg!(target_os = "macos") {
+            // `security` only takes the password as an argument, so run it
+            // interactively and pass the whole command on stdin.
+            let mut cmd = std::process::Command::new("security");
+            cmd.arg("-i");
+            let command = format!(
+                "add-generic-password -U -s {} -a {} -w {}\n",
+                security_quote(&self.service)?,
+                security_quote(account)?,
+                value,
+            );
+            let stored = self.run(cmd, Some(&command))?;
+            // Interactive mode exits successfully even if the command failed,
+            // so check that the value can be read back.
+            let read_back = self
+                .get(account)?
+                .map(|read_back| STANDARD.encode(read_back));
+            stored.filter(|_| read_back.as_deref() == Some(value.as_str()))
+        } else {
+            let mut cmd = std::process::Command::new("secret-tool");
+            cmd.args([
+                "store",
+                &format!("--label={}/{}", self.service, account),
+                "service",
+                &self.service,
+                "account",
+                account,
+            ]);
+            self.run(cmd, Some(&value))?
+        };
+        stored
+            .map(|_| ())
+            .ok_or_else(|| format!("failed to write keychain item {}", account).into())
+    }
+}
+
+/// Quote an argument of a command read by `security -i`, which splits
+/// commands on whitespace outside of double quotes.
+fn security_quote(arg: &str) -> LairResult<String> {
+    if arg.contains(['"', '\\', '\n']) {
+        return Err(format!("{:?} can't be used as a keychain item name", arg).into());
+    }
+    Ok(format!("\"{}\"", arg))
+}
+
+struct KeychainStoreFactory(Arc<dyn KeychainAdapter>);
+
+impl AsLairStoreFactory for KeychainStoreFactory {
+    fn connect_to_store(
+        &self,
+        unlock_secret: sodoken::BufReadSized<32>,
+    ) -> BoxFuture<'static, LairResult<LairStore>> {
+        let adapter = self.0.clone();
+        async move {
+            // load all existing entries up front, the keychain is only
+            // written to after this point
+            let inner = tokio::task::spawn_blocking({
+                let adapter = adapter.clone();
+                move || -> LairResult<KeychainStoreInner> {
+                    let mut inner = KeychainStoreInner {
+                        // like the mem store, use the unlock_secret directly
+                        // as our bidi context key
+                        bidi_key: unlock_secret,
+                        entry_by_tag: HashMap::new(),
+                        ed_pk_to_tag: HashMap::new(),
+                        x_pk_to_tag: HashMap::new(),
+                    };
+                    let tags: Vec<String> = match adapter.get(INDEX_ACCOUNT)? {
+                        Some(index) => holochain_serialized_bytes::decode(&index)
+                            .map_err(one_err::OneErr::new)?,
+                        None => Vec::new(),
+                    };
+                    for tag in tags {
+                        let bytes = adapter
+                            .get(&tag)?
+                            .ok_or_else(|| format!("keychain item {} is missing", tag))?;
+                        inner.insert(Arc::new(LairEntryInner::decode(&bytes)?))?;
+                    }
+                    Ok(inner)
+                }
+            })
+            .await
+            .map_err(one_err::OneErr::new)??;
+
+            Ok(LairStore(Arc::new(KeychainStore {
+                adapter,
+                inner: Arc::new(RwLock::new(inner)),
+                write_lock: Arc::new(tokio::sync::Mutex::new(())),
+            })))
+        }
+        .boxed()
+    }
+}
+
+struct KeychainStoreInner {
+    /// key for encryption / decryption of secrets
+    bidi_key: sodoken::BufReadSized<32>,
+    /// the entries, keyed by tag
+    entry_by_tag: HashMap<Arc<str>, LairEntry>,
+    /// index for signature pub key to tag
+    ed_pk_to_tag: HashMap<Ed25519PubKey, Arc<str>>,
+    /// index for encryption pub key to tag
+    x_pk_to_tag: HashMap<X25519PubKey, Arc<str>>,
+}
+
+impl KeychainStoreInner {
+    /// Check that an entry can be added without overwriting anything.
+    fn check(&self, entry: &LairEntry) -> LairResult<()> {
+        // refuse to overwrite entries
+        if self.entry_by_tag.contains_key(&entry.tag()) {
+            return Err("tag already registered".into());
+        }
+        if let Some(seed_info) = seed_info(entry) {
+            if self.ed_pk_to_tag.contains_key(&seed_info.ed25519_pub_key)
+                || self.x_pk_to_tag.contains_key(&seed_info.x25519_pub_key)
+            {
+                return Err("pub key already registered".into());
+            }
+        }
+        Ok(())
+    }
+
+    fn insert(&mut self, entry: LairEntry) -> LairResult<()> {
+        self.check(&entry)?;
+        let tag = entry.tag();
+        if let Some(seed_info) = seed_info(&entry) {
+            self.ed_pk_to_tag
+                .insert(seed_info.ed25519_pub_key.clone(), tag.clone());
+            self.x_pk_to_tag
+                .insert(seed_info.x25519_pub_key.clone(), tag.clone());
+        }
+        self.entry_by_tag.insert(tag, entry);
+        Ok(())
+    }
+}
+
+fn seed_info(entry: &LairEntry) -> Option<&SeedInfo> {
+    match &**entry {
+        LairEntryInner::Seed { seed_info, .. }
+        | LairEntryInner::DeepLockedSeed { seed_info, .. } => Some(seed_info),
+        _ => None,
+    }
+}
+
+struct KeychainStore {
+    adapter: Arc<dyn KeychainAdapter>,
+    inner: Arc<RwLock<KeychainStoreInner>>,
+    /// Serializes writes so the stored index never misses a tag.
+    write_lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl KeychainStore {
+    fn get_by_tag(&self, tag: Option<Arc<str>>) -> LairResult<LairEntry> {
+        tag.and_then(|tag| self.inner.read().entry_by_tag.get(&tag).cloned())
+            .ok_or_else(|| "tag not found".into())
+    }
+}
+
+impl AsLairStore for KeychainStore {
+    fn get_bidi_ctx_key(&self) -> sodoken::BufReadSized<32> {
+        self.inner.read().bidi_key.clone()
+    }
+
+    fn list_entries(&self) -> BoxFuture<'static, LairResult<Vec<LairEntryInfo>>> {
+        let list = self
+            .inner
+            .read()
+            .entry_by_tag
+            .values()
+            .filter_map(|e| match &**e {
+                LairEntryInner::Seed { tag, seed_info, .. } => Some(LairEntryInfo::Seed {
+                    tag: tag.clone(),
+                    seed_info: seed_info.clone(),
+                }),
+                LairEntryInner::DeepLockedSeed { tag, seed_info, .. } => {
+                    Some(LairEntryInfo::DeepLockedSeed {
+                        tag: tag.clone(),
+                        seed_info: seed_info.clone(),
+                    })
+                }
+                LairEntryInner::WkaTlsCert { tag, cert_info, .. } => {
+                    Some(LairEntryInfo::WkaTlsCert {
+                        tag: tag.clone(),
+                        cert_info: cert_info.clone(),
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+        async move { Ok(list) }.boxed()
+    }
+
+    fn write_entry(&self, entry: LairEntry) -> BoxFuture<'static, LairResult<()>> {
+        let adapter = self.adapter.clone();
+        let inner = self.inner.clone();
+        let write_lock = self.write_lock.clone();
+        async move {
+            let _guard = write_lock.lock().await;
+
+            // only update the in-memory indexes once the keychain
+            // write has succeeded
+            let index = {
+                let lock = inner.read();
+                lock.check(&entry)?;
+                let mut tags: Vec<String> =
+                    lock.entry_by_tag.keys().map(|t| t.to_string()).collect();
+                tags.push(entry.tag().to_string());
+                holochain_serialized_bytes::encode(&tags).map_err(one_err::OneErr::new)?
+            };
+            let encoded = entry.encode()?;
+            let tag = entry.tag();
+
+            tokio::task::spawn_blocking(move || -> LairResult<()> {
+                adapter.set(&tag, &encoded)?;
+                adapter.set(INDEX_ACCOUNT, &index)
+            })
+            .await
+            .map_err(one_err::OneErr::new)??;
+
+            inner.write().insert(entry)
+        }
+        .boxed()
+    }
+
+    fn get_entry_by_tag(&self, tag: Arc<str>) -> BoxFuture<'static, LairResult<LairEntry>> {
+        let res = self.get_by_tag(Some(tag));
+        async move { res }.boxed()
+    }
+
+    fn get_entry_by_ed25519_pub_key(
+        &self,
+        ed25519_pub_key: Ed25519PubKey,
+    ) -> BoxFuture<'static, LairResult<LairEntry>> {
+        let tag = self
+            .inner
+            .read()
+            .ed_pk_to_tag
+            .get(&ed25519_pub_key)
+            .cloned();
+        let res = self.get_by_tag(tag);
+        async move { res }.boxed()
+    }
+
+    fn get_entry_by_x25519_pub_key(
+        &self,
+        x25519_pub_key: X25519PubKey,
+    ) -> BoxFuture<'static, LairResult<LairEntry>> {
+        let tag = self.inner.read().x_pk_to_tag.get(&x25519_pub_key).cloned();
+        let res = self.get_by_tag(tag);
+        async move { res }.boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct MemKeychain(parking_lot::Mutex<HashMap<String, Vec<u8>>>);
+
+    impl KeychainAdapter for MemKeychain {
+        fn get(&self, account: &str) -> LairResult<Option<Vec<u8>>> {
+            Ok(self.0.lock().get(account).cloned())
+        }
+
+        fn set(&self, account: &str, value: &[u8]) -> LairResult<()> {
+            self.0.lock().insert(account.to_string(), value.to_vec());
+            Ok(())
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn keychain_store_persists_entries() {
+        let keychain: Arc<dyn KeychainAdapter> = Arc::new(MemKeychain::default());
+        let factory = create_keychain_store_factory(keychain.clone());
+        let unlock_secret = sodoken::BufReadSized::from([0xdb; 32]);
+
+        let store = factory
+            .connect_to_store(unlock_secret.clone())
+            .await
+            .unwrap();
+        let seed_info = store.new_seed("test-seed".into(), false).await.unwrap();
+        assert!(store.new_seed("test-seed".into(), false).await.is_err());
+
+        // a new connection to the same keychain sees the entry
+        let store = factory.connect_to_store(unlock_secret).await.unwrap();
+        assert_eq!(1, store.list_entries().await.unwrap().len());
+        let entry = store
+            .get_entry_by_ed25519_pub_key(seed_info.ed25519_pub_key.clone())
+            .await
+            .unwrap();
+        assert_eq!("test-seed", &*entry.tag());
+        store
+            .get_entry_by_x25519_pub_key(seed_info.x25519_pub_key)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,159 @@
+//! The [`Keystore`] abstraction over the available keystore backends.
+//!
+//! Whichever backend holds the secrets, the conductor always talks to it
+//! through a [`MetaLairClient`]. Lair is the default backend, the others
+//! exist for deployments which can't run a lair server.
+
+use crate::keychain_store::*;
+use crate::lair_keystore::*;
+use crate::*;
+use futures::future::{BoxFuture, FutureExt};
+use kitsune_p2p_types::dependencies::{lair_keystore_api, url2};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// A backend which can hold the conductor's secrets.
+pub trait Keystore: 'static + Send + Sync {
+    /// Whether a passphrase must be provided to [`Keystore::spawn`].
+    fn requires_passphrase(&self) -> bool {
+        true
+    }
+
+    /// Start (or connect to) the keystore, returning a client for it.
+    fn spawn(
+        &self,
+        passphrase: Option<sodoken::BufRead>,
+    ) -> BoxFuture<'static, LairResult<MetaLairClient>>;
+}
+
+fn require_passphrase(passphrase: Option<sodoken::BufRead>) -> LairResult<sodoken::BufRead> {
+    passphrase.ok_or_else(|| "passphrase required for lair keystore api".into())
+}
+
+/// DANGER! A keystore pre-populated with publicly known test keys.
+/// DO NOT USE THIS IN PRODUCTION!
+pub struct DangerTestKeystore;
+
+impl Keystore for DangerTestKeystore {
+    fn requires_passphrase(&self) -> bool {
+        false
+    }
+
+    fn spawn(
+        &self,
+        _passphrase: Option<sodoken::BufRead>,
+    ) -> BoxFuture<'static, LairResult<MetaLairClient>> {
+        spawn_test_keystore().boxed()
+    }
+}
+
+/// An external lair-keystore server process.
+pub struct LairServerKeystore {
+    /// The connection url of the lair server.
+    pub connection_url: url2::Url2,
+}
+
+impl Keystore for LairServerKeystore {
+    fn spawn(
+        &self,
+        passphrase: Option<sodoken::BufRead>,
+    ) -> BoxFuture<'static, LairResult<MetaLairClient>> {
+        let connection_url = self.connection_url.clone();
+        async move { spawn_lair_keystore(connection_url, require_passphrase(passphrase)?).await }
+            .boxed()
+    }
+}
+
+/// A lair-keystore server run within this process.
+pub struct LairInProcKeystore {
+    /// The path to the lair config file.
+    pub config_path: PathBuf,
+}
+
+impl Keystore for LairInProcKeystore {
+    fn spawn(
+        &self,
+        passphrase: Option<sodoken::BufRead>,
+    ) -> BoxFuture<'static, LairResult<MetaLairClient>> {
+        let config_path = self.config_path.clone();
+        async move {
+            spawn_lair_keystore_in_proc(&config_path, require_passphrase(passphrase)?).await
+        }
+        .boxed()
+    }
+}
+
+/// Where a [`SoftwareKeystore`] persists its entries.
+#[derive(Clone)]
+pub enum SoftwareKeystoreStore {
+    /// An encrypted sqlite file, at the location given in the lair config.
+    Sqlite,
+    /// A keychain, see [`os_keychain`].
+    Keychain(Arc<dyn KeychainAdapter>),
+}
+
+/// A keystore served directly within this process, without a lair server.
+///
+/// There is no IPC socket or pid file involved, which makes this usable
+/// on platforms where a lair server can't run. The lair config file is
+/// still used to derive the store encryption key from the passphrase.
+pub struct SoftwareKeystore {
+    /// The path to the lair config file, created if missing.
+    pub config_path: PathBuf,
+    /// Where entries are persisted.
+    pub store: SoftwareKeystoreStore,
+}
+
+impl Keystore for SoftwareKeystore {
+    fn spawn(
+        &self,
+        passphrase: Option<sodoken::BufRead>,
+    ) -> BoxFuture<'static, LairResult<MetaLairClient>> {
+        let config_path = self.config_path.clone();
+        let store = self.store.clone();
+        async move {
+            let passphrase = require_passphrase(passphrase)?;
+            let config = get_config(&config_path, passphrase.clone()).await?;
+            let store_factory = match store {
+                SoftwareKeystoreStore::Sqlite => {
+                    ::lair_keystore::create_sql_pool_factory(&config.store_file)
+                }
+                SoftwareKeystoreStore::Keychain(adapter) => create_keychain_store_factory(adapter),
+            };
+            let keystore = lair_keystore_api::in_proc_keystore::InProcKeystore::new(
+                config,
+                store_factory,
+                passphrase,
+            )
+            .await?;
+            let client = keystore.new_client().await?;
+            let (s, _) = tokio::sync::mpsc::unbounded_channel();
+            Ok(MetaLairClient(Arc::new(parking_lot::Mutex::new(client)), s))
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn software_keystore_persists_keys() {
+        let dir = tempdir::TempDir::new("software_keystore").unwrap();
+        let keystore = SoftwareKeystore {
+            config_path: dir.path().join("lair-keystore-config.yaml"),
+            store: SoftwareKeystoreStore::Sqlite,
+        };
+        let passphrase = || sodoken::BufRead::new_no_lock(b"passphrase");
+
+        assert!(keystore.spawn(None).await.is_err());
+
+        let client = keystore.spawn(Some(passphrase())).await.unwrap();
+        let agent = client.new_sign_keypair_random().await.unwrap();
+        client.shutdown().await.unwrap();
+
+        let client = keystore.spawn(Some(passphrase())).await.unwrap();
+        assert!(client.list_public_keys().await.unwrap().contains(&agent));
+    }
+}
@@ -38,7 +38,7 @@ pub async fn spawn_lair_keystore_in_proc(
     spawn_lair_keystore(connection_url.into(), passphrase).await
 }
 
-async fn get_config(
+pub(crate) async fn get_config(
     config_path: &PathBuf,
     passphrase: sodoken::BufRead,
 ) -> LairResult<LairServerConfig> {
@@ -42,6 +42,11 @@ pub use agent_pubkey_ext::*;
 
 pub mod lair_keystore;
 
+pub mod keychain_store;
+
+mod keystore;
+pub use keystore::*;
+
 pub mod paths;
 
 mod test_keystore;
@@ -62,6 +62,22 @@ pub trait HolochainP2pDnaT: Send + Sync + 'static {
         expires_at: Timestamp,
     ) -> actor::HolochainP2pResult<SerializedBytes>;
 
+    /// Invoke a read-only zome function on a remote node (if you have been granted
+    /// the capability to query it).
+    #[allow(clippy::too_many_arguments)]
+    async fn remote_query(
+        &self,
+        from_agent: AgentPubKey,
+        from_signature: Signature,
+        to_agent: AgentPubKey,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap_secret: Option<CapSecret>,
+        payload: ExternIO,
+        nonce: Nonce256Bits,
+        expires_at: Timestamp,
+    ) -> actor::HolochainP2pResult<SerializedBytes>;
+
     /// Invoke a zome function on a remote node (if you have been granted the capability).
     /// This is a fire-and-forget operation, a best effort will be made
     /// to forward the signal, but if the conductor network is overworked
@@ -141,6 +157,21 @@ pub trait HolochainP2pDnaT: Send + Sync + 'static {
         filter: holochain_zome_types::chain::ChainFilter,
     ) -> actor::HolochainP2pResult<Vec<MustGetAgentActivityResponse>>;
 
+    /// Get the actions at one position of an agent's chain from the DHT.
+    async fn get_action_by_seq(
+        &self,
+        author: AgentPubKey,
+        action_seq: u32,
+    ) -> actor::HolochainP2pResult<Vec<ActionBySeqResponse>>;
+
+    /// Get a page of the public entries of one type created by an agent from the DHT.
+    async fn get_entries_by_author(
+        &self,
+        author: AgentPubKey,
+        entry_type: EntryType,
+        page: EntriesPage,
+    ) -> actor::HolochainP2pResult<Vec<EntriesByAuthorResponse>>;
+
     /// Send a validation receipt to a remote node.
     async fn send_validation_receipts(
         &self,
@@ -154,6 +185,14 @@ pub trait HolochainP2pDnaT: Send + Sync + 'static {
         basis: holo_hash::OpBasis,
     ) -> actor::HolochainP2pResult<bool>;
 
+    /// Queue ops which aren't held locally to be fetched from the
+    /// authorities for their basis.
+    async fn fetch_from_authorities(
+        &self,
+        basis: holo_hash::OpBasis,
+        op_hash_list: Vec<OpHashSized>,
+    ) -> actor::HolochainP2pResult<()>;
+
     /// Messages between agents driving a countersigning session.
     async fn countersigning_session_negotiation(
         &self,
@@ -164,6 +203,17 @@ pub trait HolochainP2pDnaT: Send + Sync + 'static {
     /// New data has been integrated and is ready for gossiping.
     async fn new_integrated_data(&self) -> actor::HolochainP2pResult<()>;
 
+    /// Estimate how far the clocks of the peers we have recently talked to
+    /// are from our clock.
+    async fn network_time_offset(&self) -> actor::HolochainP2pResult<Option<NetworkTimeOffset>>;
+
+    /// Summarize the DHT arc a local agent is currently storing data for.
+    /// `None` if the agent hasn't published its agent info yet.
+    async fn storage_arc(
+        &self,
+        agent: AgentPubKey,
+    ) -> actor::HolochainP2pResult<Option<StorageArcSummary>>;
+
     /// Access to the specified CHC
     fn chc(&self) -> Option<ChcImpl>;
 }
@@ -174,9 +224,18 @@ pub trait HolochainP2pDnaT: Send + Sync + 'static {
 pub struct HolochainP2pDna {
     sender: ghost_actor::GhostSender<actor::HolochainP2p>,
     dna_hash: Arc<DnaHash>,
+    /// The agent of the cell this sender is bound to, if any.
+    agent: Option<Arc<AgentPubKey>>,
     chc: Option<ChcImpl>,
 }
 
+impl HolochainP2pDna {
+    /// The agent of the cell whose requests are sent, if this sender is bound to a cell.
+    fn from_agent(&self) -> Option<AgentPubKey> {
+        self.agent.as_deref().cloned()
+    }
+}
+
 impl From<HolochainP2pDna> for GenericNetwork {
     fn from(value: HolochainP2pDna) -> Self {
         Arc::new(value)
@@ -244,6 +303,36 @@ impl HolochainP2pDnaT for HolochainP2pDna {
             .await
     }
 
+    /// Invoke a read-only zome function on a remote node (if you have been granted
+    /// the capability to query it).
+    async fn remote_query(
+        &self,
+        from_agent: AgentPubKey,
+        from_signature: Signature,
+        to_agent: AgentPubKey,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap_secret: Option<CapSecret>,
+        payload: ExternIO,
+        nonce: Nonce256Bits,
+        expires_at: Timestamp,
+    ) -> actor::HolochainP2pResult<SerializedBytes> {
+        self.sender
+            .remote_query(
+                (*self.dna_hash).clone(),
+                from_agent,
+                from_signature,
+                to_agent,
+                zome_name,
+                fn_name,
+                cap_secret,
+                payload,
+                nonce,
+                expires_at,
+            )
+            .await
+    }
+
     /// Invoke a zome function on a remote node (if you have been granted the capability).
     /// This is a fire-and-forget operation, a best effort will be made
     /// to forward the signal, but if the conductor network is overworked
@@ -307,7 +396,13 @@ impl HolochainP2pDnaT for HolochainP2pDna {
         op: DhtOp,
     ) -> actor::HolochainP2pResult<()> {
         self.sender
-            .publish_countersign((*self.dna_hash).clone(), flag, basis_hash, op)
+            .publish_countersign(
+                (*self.dna_hash).clone(),
+                self.from_agent(),
+                flag,
+                basis_hash,
+                op,
+            )
             .await
     }
 
@@ -318,7 +413,12 @@ impl HolochainP2pDnaT for HolochainP2pDna {
         options: actor::GetOptions,
     ) -> actor::HolochainP2pResult<Vec<WireOps>> {
         self.sender
-            .get((*self.dna_hash).clone(), dht_hash, options)
+            .get(
+                (*self.dna_hash).clone(),
+                self.from_agent(),
+                dht_hash,
+                options,
+            )
             .instrument(tracing::debug_span!("HolochainP2p::get"))
             .await
     }
@@ -330,7 +430,12 @@ impl HolochainP2pDnaT for HolochainP2pDna {
         options: actor::GetMetaOptions,
     ) -> actor::HolochainP2pResult<Vec<MetadataSet>> {
         self.sender
-            .get_meta((*self.dna_hash).clone(), dht_hash, options)
+            .get_meta(
+                (*self.dna_hash).clone(),
+                self.from_agent(),
+                dht_hash,
+                options,
+            )
             .await
     }
 
@@ -341,7 +446,12 @@ impl HolochainP2pDnaT for HolochainP2pDna {
         options: actor::GetLinksOptions,
     ) -> actor::HolochainP2pResult<Vec<WireLinkOps>> {
         self.sender
-            .get_links((*self.dna_hash).clone(), link_key, options)
+            .get_links(
+                (*self.dna_hash).clone(),
+                self.from_agent(),
+                link_key,
+                options,
+            )
             .await
     }
 
@@ -351,7 +461,7 @@ impl HolochainP2pDnaT for HolochainP2pDna {
         query: WireLinkQuery,
     ) -> actor::HolochainP2pResult<CountLinksResponse> {
         self.sender
-            .count_links((*self.dna_hash).clone(), query)
+            .count_links((*self.dna_hash).clone(), self.from_agent(), query)
             .await
     }
 
@@ -363,7 +473,13 @@ impl HolochainP2pDnaT for HolochainP2pDna {
         options: actor::GetActivityOptions,
     ) -> actor::HolochainP2pResult<Vec<AgentActivityResponse<ActionHash>>> {
         self.sender
-            .get_agent_activity((*self.dna_hash).clone(), agent, query, options)
+            .get_agent_activity(
+                (*self.dna_hash).clone(),
+                self.from_agent(),
+                agent,
+                query,
+                options,
+            )
             .await
     }
 
@@ -373,7 +489,39 @@ impl HolochainP2pDnaT for HolochainP2pDna {
         filter: holochain_zome_types::chain::ChainFilter,
     ) -> actor::HolochainP2pResult<Vec<MustGetAgentActivityResponse>> {
         self.sender
-            .must_get_agent_activity((*self.dna_hash).clone(), author, filter)
+            .must_get_agent_activity((*self.dna_hash).clone(), self.from_agent(), author, filter)
+            .await
+    }
+
+    async fn get_action_by_seq(
+        &self,
+        author: AgentPubKey,
+        action_seq: u32,
+    ) -> actor::HolochainP2pResult<Vec<ActionBySeqResponse>> {
+        self.sender
+            .get_action_by_seq(
+                (*self.dna_hash).clone(),
+                self.from_agent(),
+                author,
+                action_seq,
+            )
+            .await
+    }
+
+    async fn get_entries_by_author(
+        &self,
+        author: AgentPubKey,
+        entry_type: EntryType,
+        page: EntriesPage,
+    ) -> actor::HolochainP2pResult<Vec<EntriesByAuthorResponse>> {
+        self.sender
+            .get_entries_by_author(
+                (*self.dna_hash).clone(),
+                self.from_agent(),
+                author,
+                entry_type,
+                page,
+            )
             .await
     }
 
@@ -384,7 +532,12 @@ impl HolochainP2pDnaT for HolochainP2pDna {
         receipts: ValidationReceiptBundle,
     ) -> actor::HolochainP2pResult<()> {
         self.sender
-            .send_validation_receipts((*self.dna_hash).clone(), to_agent, receipts)
+            .send_validation_receipts(
+                (*self.dna_hash).clone(),
+                self.from_agent(),
+                to_agent,
+                receipts,
+            )
             .await
     }
 
@@ -398,13 +551,28 @@ impl HolochainP2pDnaT for HolochainP2pDna {
             .await
     }
 
+    async fn fetch_from_authorities(
+        &self,
+        basis: holo_hash::OpBasis,
+        op_hash_list: Vec<OpHashSized>,
+    ) -> actor::HolochainP2pResult<()> {
+        self.sender
+            .fetch_from_authorities((*self.dna_hash).clone(), basis, op_hash_list)
+            .await
+    }
+
     async fn countersigning_session_negotiation(
         &self,
         agents: Vec<AgentPubKey>,
         message: event::CountersigningSessionNegotiationMessage,
     ) -> actor::HolochainP2pResult<()> {
         self.sender
-            .countersigning_session_negotiation((*self.dna_hash).clone(), agents, message)
+            .countersigning_session_negotiation(
+                (*self.dna_hash).clone(),
+                self.from_agent(),
+                agents,
+                message,
+            )
             .await
     }
 
@@ -414,6 +582,35 @@ impl HolochainP2pDnaT for HolochainP2pDna {
             .await
     }
 
+    async fn network_time_offset(&self) -> actor::HolochainP2pResult<Option<NetworkTimeOffset>> {
+        let diagnostics = self
+            .sender
+            .get_diagnostics((*self.dna_hash).clone())
+            .await?;
+        let estimate = diagnostics.metrics.read().clock_offset_estimate();
+        Ok(estimate.map(|estimate| NetworkTimeOffset {
+            offset_micros: estimate.offset_micros,
+            uncertainty_micros: estimate.uncertainty_micros,
+            peer_count: estimate.peer_count as u32,
+        }))
+    }
+
+    async fn storage_arc(
+        &self,
+        agent: AgentPubKey,
+    ) -> actor::HolochainP2pResult<Option<StorageArcSummary>> {
+        let arc = self
+            .sender
+            .storage_arc((*self.dna_hash).clone(), agent)
+            .await?;
+        Ok(arc.map(|arc| StorageArcSummary {
+            bounds: arc
+                .to_bounds_grouped()
+                .map(|(start, end)| (start.as_u32(), end.as_u32())),
+            coverage: arc.coverage(),
+        }))
+    }
+
     fn chc(&self) -> Option<ChcImpl> {
         self.chc.clone()
     }
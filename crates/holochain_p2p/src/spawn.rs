@@ -3,8 +3,11 @@ use crate::event::*;
 
 mod actor;
 use actor::*;
+mod remote_signal_queue;
 use holo_hash::DnaHash;
 
+use crate::WireProtocol;
+
 /// Spawn a new HolochainP2p actor.
 /// Conductor will call this on initialization.
 pub async fn spawn_holochain_p2p(
@@ -36,4 +39,8 @@ pub async fn spawn_holochain_p2p(
 pub struct NetworkCompatParams {
     /// The hash of the installed DPKI service
     pub dpki_hash: Option<DnaHash>,
+    /// The wire protocol this conductor speaks. Conductors from before the
+    /// wire protocol was versioned don't send one.
+    #[serde(default = "WireProtocol::legacy")]
+    pub wire_protocol: WireProtocol,
 }
@@ -9,6 +9,7 @@ use kitsune_p2p::dependencies::kitsune_p2p_fetch;
 use kitsune_p2p::dht::Arq;
 use kitsune_p2p::event::*;
 use kitsune_p2p::gossip::sharded_gossip::KitsuneDiagnostics;
+use kitsune_p2p::metrics::ProtocolViolation;
 use kitsune_p2p::KOp;
 use kitsune_p2p::KitsuneOpData;
 use kitsune_p2p::PreflightUserData;
@@ -19,6 +20,8 @@ use crate::types::AgentPubKeyExt;
 use ghost_actor::dependencies::tracing;
 use ghost_actor::dependencies::tracing_futures::Instrument;
 
+use super::remote_signal_queue::{self, QueueEvent};
+use holochain_nonce::fresh_nonce;
 use holochain_trace::tracing::warn;
 use holochain_zome_types::zome::FunctionName;
 use kitsune_p2p::actor::KitsuneP2pSender;
@@ -152,6 +155,30 @@ impl WrapEvtSender {
         )
     }
 
+    fn remote_query(
+        &self,
+        dna_hash: DnaHash,
+        from: AgentPubKey,
+        signature: Signature,
+        to_agent: AgentPubKey,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap_secret: Option<CapSecret>,
+        payload: ExternIO,
+        nonce: Nonce256Bits,
+        expires_at: Timestamp,
+    ) -> impl Future<Output = HolochainP2pResult<SerializedBytes>> + 'static + Send {
+        timing_trace!(
+            {
+                self.0.remote_query(
+                    dna_hash, from, signature, to_agent, zome_name, fn_name, cap_secret, payload,
+                    nonce, expires_at,
+                )
+            },
+            "(hp2p:handle) remote_query",
+        )
+    }
+
     fn publish(
         &self,
         dna_hash: DnaHash,
@@ -216,6 +243,18 @@ impl WrapEvtSender {
         )
     }
 
+    fn probe_basis(
+        &self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        basis: OpBasis,
+    ) -> impl Future<Output = HolochainP2pResult<ProbeBasisResponse>> + 'static + Send {
+        timing_trace!(
+            { self.0.probe_basis(dna_hash, to_agent, basis) },
+            "(hp2p:handle) probe_basis"
+        )
+    }
+
     fn get_agent_activity(
         &self,
         dna_hash: DnaHash,
@@ -251,6 +290,39 @@ impl WrapEvtSender {
         )
     }
 
+    fn get_action_by_seq(
+        &self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        author: AgentPubKey,
+        action_seq: u32,
+    ) -> impl Future<Output = HolochainP2pResult<ActionBySeqResponse>> + 'static + Send {
+        timing_trace!(
+            {
+                self.0
+                    .get_action_by_seq(dna_hash, to_agent, author, action_seq)
+            },
+            "(hp2p:handle) get_action_by_seq",
+        )
+    }
+
+    fn get_entries_by_author(
+        &self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        author: AgentPubKey,
+        entry_type: EntryType,
+        page: EntriesPage,
+    ) -> impl Future<Output = HolochainP2pResult<EntriesByAuthorResponse>> + 'static + Send {
+        timing_trace!(
+            {
+                self.0
+                    .get_entries_by_author(dna_hash, to_agent, author, entry_type, page)
+            },
+            "(hp2p:handle) get_entries_by_author",
+        )
+    }
+
     fn validation_receipts_received(
         &self,
         dna_hash: DnaHash,
@@ -330,6 +402,50 @@ impl WrapEvtSender {
             "(hp2p:handle) countersigning_session_negotiation"
         )
     }
+
+    fn egress_blocked(
+        &self,
+        dna_hash: DnaHash,
+        from_agent: AgentPubKey,
+        to_agent: AgentPubKey,
+        request: String,
+    ) -> impl Future<Output = HolochainP2pResult<()>> + 'static + Send {
+        timing_trace!(
+            {
+                self.0
+                    .egress_blocked(dna_hash, from_agent, to_agent, request)
+            },
+            "(hp2p:handle) egress_blocked"
+        )
+    }
+
+    fn queue_remote_signal(
+        &self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        signal: QueuedRemoteSignal,
+        max_len: u32,
+    ) -> impl Future<Output = HolochainP2pResult<(bool, usize)>> + 'static + Send {
+        timing_trace!(
+            {
+                self.0
+                    .queue_remote_signal(dna_hash, to_agent, signal, max_len)
+            },
+            "(hp2p:handle) queue_remote_signal"
+        )
+    }
+
+    fn take_queued_remote_signals(
+        &self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+    ) -> impl Future<Output = HolochainP2pResult<(Vec<QueuedRemoteSignal>, usize)>> + 'static + Send
+    {
+        timing_trace!(
+            { self.0.take_queued_remote_signals(dna_hash, to_agent) },
+            "(hp2p:handle) take_queued_remote_signals"
+        )
+    }
 }
 
 pub(crate) struct HolochainP2pActor {
@@ -337,6 +453,152 @@ pub(crate) struct HolochainP2pActor {
     evt_sender: WrapEvtSender,
     kitsune_p2p: ghost_actor::GhostSender<kitsune_p2p::actor::KitsuneP2p>,
     host: kitsune_p2p::HostApi,
+    /// The egress policy of each cell which has one, which must allow a
+    /// request the cell makes of a peer for it to be sent.
+    egress_policies: HashMap<CellId, Arc<EgressPolicy>>,
+    /// When each blocked request was last reported.
+    egress_reports: EgressReports,
+    /// The wire protocol negotiated with each peer in the preflight handshake.
+    peer_protocols: PeerProtocols,
+}
+
+/// The wire protocol negotiated with each peer, by the transport URL it
+/// connected from.
+type PeerProtocols = Arc<parking_lot::RwLock<HashMap<String, WireProtocol>>>;
+
+/// The capabilities negotiated with the peer at any of these transport URLs,
+/// or `None` if the peer hasn't been connected to yet.
+fn peer_capabilities(
+    peer_protocols: &PeerProtocols,
+    urls: &[impl AsRef<str>],
+) -> Option<WireCapabilities> {
+    let peer_protocols = peer_protocols.read();
+    urls.iter()
+        .filter_map(|url| peer_protocols.get(url.as_ref()))
+        .map(|protocol| protocol.capabilities)
+        .reduce(WireCapabilities::union)
+}
+
+/// How long a request blocked by an egress policy isn't reported again for,
+/// when the same cell makes the same kind of request of the same peer.
+const EGRESS_REPORT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// When a kind of request a cell made of a peer was last reported as blocked.
+type EgressReports =
+    Arc<parking_lot::Mutex<HashMap<(CellId, AgentPubKey, &'static str), std::time::Instant>>>;
+
+/// Log a request which was blocked by an egress policy, and report it to the
+/// conductor so that it's recorded in the event journal.
+///
+/// Requests are blocked each time they are retried and each time a peer is
+/// considered for a get, so a request is only reported again once
+/// [`EGRESS_REPORT_INTERVAL`] has passed.
+fn report_egress_blocked(
+    evt_sender: WrapEvtSender,
+    egress_reports: &EgressReports,
+    cell_id: CellId,
+    to_agent: AgentPubKey,
+    request: &'static str,
+) {
+    let now = std::time::Instant::now();
+    {
+        let mut egress_reports = egress_reports.lock();
+        egress_reports.retain(|_, at| now.duration_since(*at) < EGRESS_REPORT_INTERVAL);
+        match egress_reports.entry((cell_id.clone(), to_agent.clone(), request)) {
+            std::collections::hash_map::Entry::Occupied(_) => {
+                tracing::debug!(
+                    ?cell_id,
+                    ?to_agent,
+                    request,
+                    "Egress policy blocked a request again"
+                );
+                return;
+            }
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(now);
+            }
+        }
+    }
+    tracing::warn!(
+        ?cell_id,
+        ?to_agent,
+        request,
+        "Egress policy blocked a request"
+    );
+    let (dna_hash, from_agent) = cell_id.into_dna_and_agent();
+    tokio::task::spawn(async move {
+        if let Err(err) = evt_sender
+            .egress_blocked(dna_hash, from_agent, to_agent, request.to_string())
+            .await
+        {
+            tracing::warn!(
+                ?err,
+                "Failed to report a request blocked by an egress policy"
+            );
+        }
+    });
+}
+
+/// Take the signals which were held for an agent while it was offline, and
+/// send them, each signed again with a fresh nonce because the original may
+/// have expired.
+async fn deliver_queued_signals(
+    evt_sender: WrapEvtSender,
+    kitsune_p2p: ghost_actor::GhostSender<kitsune_p2p::actor::KitsuneP2p>,
+    timeout: kitsune_p2p_types::KitsuneTimeout,
+    dna_hash: DnaHash,
+    to_agent: AgentPubKey,
+) -> HolochainP2pResult<()> {
+    let (signals, expired) = evt_sender
+        .take_queued_remote_signals(dna_hash.clone(), to_agent.clone())
+        .await?;
+    remote_signal_queue::record(QueueEvent::Expired, expired);
+    remote_signal_queue::record(QueueEvent::Delivered, signals.len());
+
+    let space = dna_hash.clone().into_kitsune();
+    for signal in signals {
+        let (nonce, expires_at) =
+            fresh_nonce(Timestamp::now()).map_err(HolochainP2pError::other)?;
+        let zome_call_unsigned = ZomeCallUnsigned {
+            provenance: signal.from_agent.clone(),
+            cell_id: CellId::new(dna_hash.clone(), to_agent.clone()),
+            zome_name: signal.zome_name.clone(),
+            fn_name: signal.fn_name.clone(),
+            cap_secret: signal.cap,
+            payload: signal.payload.clone(),
+            nonce,
+            expires_at,
+            wait_for_publish: None,
+        };
+        let signature = evt_sender
+            .sign_network_data(
+                dna_hash.clone(),
+                signal.from_agent.clone(),
+                zome_call_unsigned.data_to_sign()?.to_vec(),
+            )
+            .await?;
+        let req = crate::wire::WireMessage::call_remote_multi(
+            signal.zome_name,
+            signal.fn_name,
+            signal.from_agent,
+            vec![(signature, to_agent.clone())],
+            signal.cap,
+            signal.payload,
+            nonce,
+            expires_at,
+        )
+        .encode()?;
+        kitsune_p2p
+            .targeted_broadcast(
+                space.clone(),
+                vec![to_agent.clone().into_kitsune()],
+                timeout,
+                req,
+                false,
+            )
+            .await?;
+    }
+    Ok(())
 }
 
 impl ghost_actor::GhostControlHandler for HolochainP2pActor {
@@ -366,26 +628,35 @@ impl HolochainP2pActor {
         kitsune_p2p_types::codec::rmp_encode(&mut bytes, &compat)
             .map_err(HolochainP2pError::other)?;
 
+        let peer_protocols = PeerProtocols::default();
         let preflight_user_data = PreflightUserData {
-            bytes: bytes.clone(),
-            comparator: Box::new(move |url, mut recvd_bytes| {
-                if bytes.as_slice() != recvd_bytes {
+            bytes,
+            comparator: Box::new({
+                let peer_protocols = peer_protocols.clone();
+                move |url, mut recvd_bytes| {
                     let common = "Cannot complete preflight handshake with peer because network compatibility params don't match";
-                    Err(
-                        match kitsune_p2p_types::codec::rmp_decode::<_, NetworkCompatParams>(
-                            &mut recvd_bytes,
-                        ) {
-                            Ok(theirs) => {
-                                format!("{common}. ours={compat:?}, theirs={theirs:?}, url={url}")
-                            }
-                            Err(err) => {
-                                format!(
-                                "{common}. (Can't decode peer's sent hash.) url={url}, err={err}"
-                            )
-                            }
-                        },
+                    let theirs = kitsune_p2p_types::codec::rmp_decode::<_, NetworkCompatParams>(
+                        &mut recvd_bytes,
                     )
-                } else {
+                    .map_err(|err| {
+                        format!("{common}. (Can't decode peer's sent hash.) url={url}, err={err}")
+                    })?;
+                    if theirs.dpki_hash != compat.dpki_hash {
+                        return Err(format!(
+                            "{common}. ours={compat:?}, theirs={theirs:?}, url={url}"
+                        ));
+                    }
+                    // Peers speaking an older version are still spoken to,
+                    // with only the features both support.
+                    let protocol = compat
+                        .wire_protocol
+                        .negotiate(&theirs.wire_protocol)
+                        .ok_or_else(|| {
+                            format!(
+                                "{common}. There is no wire protocol version both speak. ours={compat:?}, theirs={theirs:?}, url={url}"
+                            )
+                        })?;
+                    peer_protocols.write().insert(url.to_string(), protocol);
                     Ok(())
                 }
             }),
@@ -406,6 +677,9 @@ impl HolochainP2pActor {
             evt_sender: WrapEvtSender(evt_sender),
             kitsune_p2p,
             host,
+            egress_policies: HashMap::new(),
+            egress_reports: Default::default(),
+            peer_protocols,
         })
     }
 
@@ -447,6 +721,44 @@ impl HolochainP2pActor {
         .into())
     }
 
+    /// receiving an incoming read-only query from a remote node
+    #[allow(clippy::too_many_arguments)]
+    fn handle_incoming_remote_query(
+        &mut self,
+        dna_hash: DnaHash,
+        from: AgentPubKey,
+        signature: Signature,
+        to_agent: AgentPubKey,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap_secret: Option<CapSecret>,
+        data: Vec<u8>,
+        nonce: Nonce256Bits,
+        expires_at: Timestamp,
+    ) -> kitsune_p2p::actor::KitsuneP2pHandlerResult<Vec<u8>> {
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            let res = evt_sender
+                .remote_query(
+                    dna_hash,
+                    from,
+                    signature,
+                    to_agent,
+                    zome_name,
+                    fn_name,
+                    cap_secret,
+                    ExternIO::from(data),
+                    nonce,
+                    expires_at,
+                )
+                .await;
+            res.map_err(kitsune_p2p::KitsuneP2pError::from)
+                .map(|res| UnsafeBytes::from(res).into())
+        }
+        .boxed()
+        .into())
+    }
+
     /// receiving an incoming get request from a remote node
     #[tracing::instrument(skip(self, dna_hash, to_agent, dht_hash, options), level = "trace")]
     fn handle_incoming_get(
@@ -527,6 +839,23 @@ impl HolochainP2pActor {
         .into())
     }
 
+    fn handle_incoming_probe_basis(
+        &mut self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        basis: OpBasis,
+    ) -> kitsune_p2p::actor::KitsuneP2pHandlerResult<Vec<u8>> {
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            let res = evt_sender.probe_basis(dna_hash, to_agent, basis).await;
+            res.and_then(|r| Ok(SerializedBytes::try_from(r)?))
+                .map_err(kitsune_p2p::KitsuneP2pError::from)
+                .map(|res| UnsafeBytes::from(res).into())
+        }
+        .boxed()
+        .into())
+    }
+
     /// receiving an incoming get_links request from a remote node
     fn handle_incoming_get_agent_activity(
         &mut self,
@@ -570,6 +899,49 @@ impl HolochainP2pActor {
         .into())
     }
 
+    /// receiving an incoming get_action_by_seq request from a remote node
+    fn handle_incoming_get_action_by_seq(
+        &mut self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        agent: AgentPubKey,
+        action_seq: u32,
+    ) -> kitsune_p2p::actor::KitsuneP2pHandlerResult<Vec<u8>> {
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            let res = evt_sender
+                .get_action_by_seq(dna_hash, to_agent, agent, action_seq)
+                .await;
+            res.and_then(|r| Ok(SerializedBytes::try_from(r)?))
+                .map_err(kitsune_p2p::KitsuneP2pError::from)
+                .map(|res| UnsafeBytes::from(res).into())
+        }
+        .boxed()
+        .into())
+    }
+
+    /// receiving an incoming get_entries_by_author request from a remote node
+    fn handle_incoming_get_entries_by_author(
+        &mut self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        author: AgentPubKey,
+        entry_type: EntryType,
+        page: EntriesPage,
+    ) -> kitsune_p2p::actor::KitsuneP2pHandlerResult<Vec<u8>> {
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            let res = evt_sender
+                .get_entries_by_author(dna_hash, to_agent, author, entry_type, page)
+                .await;
+            res.and_then(|r| Ok(SerializedBytes::try_from(r)?))
+                .map_err(kitsune_p2p::KitsuneP2pError::from)
+                .map(|res| UnsafeBytes::from(res).into())
+        }
+        .boxed()
+        .into())
+    }
+
     /// receiving an incoming publish from a remote node
     fn handle_incoming_publish(
         &mut self,
@@ -630,6 +1002,160 @@ impl HolochainP2pActor {
         .boxed()
         .into())
     }
+
+    /// The implicit network timeout, used as the budget for requests
+    /// which don't specify a timeout of their own.
+    fn implicit_budget(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.config.tuning_params.tx2_implicit_timeout_ms as u64)
+    }
+
+    /// The egress policy of the cell a request is made for, if it has one.
+    /// Requests which aren't made for a cell aren't restricted.
+    fn egress_policy(
+        &self,
+        dna_hash: &DnaHash,
+        from_agent: Option<&AgentPubKey>,
+    ) -> Option<(CellId, Arc<EgressPolicy>)> {
+        let cell_id = CellId::new(dna_hash.clone(), from_agent?.clone());
+        let policy = self.egress_policies.get(&cell_id)?.clone();
+        Some((cell_id, policy))
+    }
+
+    /// Check that the egress policy of the cell a request is made for allows
+    /// it to be sent to a peer. A request which isn't allowed is reported,
+    /// and fails with [`HolochainP2pError::EgressBlocked`].
+    fn check_egress(
+        &self,
+        dna_hash: &DnaHash,
+        from_agent: Option<&AgentPubKey>,
+        to_agent: &AgentPubKey,
+        request: &'static str,
+    ) -> impl Future<Output = HolochainP2pResult<()>> + 'static + Send {
+        let policy = self.egress_policy(dna_hash, from_agent);
+        let host = self.host.clone();
+        let evt_sender = self.evt_sender.clone();
+        let egress_reports = self.egress_reports.clone();
+        let to_agent = to_agent.clone();
+        async move {
+            let Some((cell_id, policy)) = policy else {
+                return Ok(());
+            };
+            // A peer without agent info has no known URLs, so it's only
+            // allowed if no rule needs them.
+            let urls: Vec<String> = if policy.has_network_id_rules() {
+                host.get_agent_info_signed(GetAgentInfoSignedEvt {
+                    space: cell_id.dna_hash().clone().into_kitsune(),
+                    agent: to_agent.clone().into_kitsune(),
+                })
+                .await
+                .map_err(HolochainP2pError::other)?
+                .map(|info| info.url_list.iter().map(ToString::to_string).collect())
+                .unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            if policy.allows(&to_agent, &urls) {
+                return Ok(());
+            }
+            report_egress_blocked(
+                evt_sender,
+                &egress_reports,
+                cell_id,
+                to_agent.clone(),
+                request,
+            );
+            Err(HolochainP2pError::EgressBlocked {
+                request: request.to_string(),
+                to_agent,
+            })
+        }
+    }
+
+    /// Check that a peer supports the wire protocol features a request needs.
+    /// A peer which hasn't been connected to yet is assumed to support them,
+    /// since connecting to it will negotiate them. A request the peer is known
+    /// not to support fails with [`HolochainP2pError::UnsupportedByPeer`].
+    fn check_capabilities(
+        &self,
+        dna_hash: &DnaHash,
+        to_agent: &AgentPubKey,
+        required: WireCapabilities,
+        request: &'static str,
+    ) -> impl Future<Output = HolochainP2pResult<()>> + 'static + Send {
+        let peer_protocols = self.peer_protocols.clone();
+        let host = self.host.clone();
+        let space = dna_hash.clone().into_kitsune();
+        let to_agent = to_agent.clone();
+        async move {
+            let urls: Vec<String> = host
+                .get_agent_info_signed(GetAgentInfoSignedEvt {
+                    space,
+                    agent: to_agent.clone().into_kitsune(),
+                })
+                .await
+                .map_err(HolochainP2pError::other)?
+                .map(|info| info.url_list.iter().map(ToString::to_string).collect())
+                .unwrap_or_default();
+            match peer_capabilities(&peer_protocols, &urls) {
+                Some(capabilities) if !capabilities.contains(required) => {
+                    Err(HolochainP2pError::UnsupportedByPeer {
+                        request: request.to_string(),
+                        to_agent,
+                    })
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// A filter which keeps the peers chosen to serve a request to those the
+    /// egress policy of the cell it's made for allows, reporting the peers it
+    /// skips, and to those which support the wire protocol features the
+    /// request needs.
+    fn peer_filter(
+        &self,
+        dna_hash: &DnaHash,
+        from_agent: Option<&AgentPubKey>,
+        request: &'static str,
+        required: WireCapabilities,
+    ) -> Option<kitsune_p2p::actor::PeerFilter> {
+        let policy = self.egress_policy(dna_hash, from_agent);
+        if policy.is_none() && required == WireCapabilities::NONE {
+            return None;
+        }
+        let peer_protocols = self.peer_protocols.clone();
+        let evt_sender = self.evt_sender.clone();
+        let egress_reports = self.egress_reports.clone();
+        Some(kitsune_p2p::actor::PeerFilter::new(move |info| {
+            let to_agent = AgentPubKey::from_kitsune(&info.agent);
+            let urls: Vec<String> = info.url_list.iter().map(ToString::to_string).collect();
+            if peer_capabilities(&peer_protocols, &urls)
+                .is_some_and(|capabilities| !capabilities.contains(required))
+            {
+                return false;
+            }
+            let Some((cell_id, policy)) = &policy else {
+                return true;
+            };
+            if policy.allows(&to_agent, &urls) {
+                return true;
+            }
+            report_egress_blocked(
+                evt_sender.clone(),
+                &egress_reports,
+                cell_id.clone(),
+                to_agent,
+                request,
+            );
+            false
+        }))
+    }
+
+    /// The deadline for an outgoing request, which is the caller's deadline if
+    /// one was given, otherwise a new deadline for the request's `timeout_ms`.
+    fn request_deadline(&self, deadline: Option<Deadline>, timeout_ms: Option<u64>) -> Deadline {
+        deadline.unwrap_or_else(|| Deadline::from_timeout_ms(timeout_ms, self.implicit_budget()))
+    }
 }
 
 impl ghost_actor::GhostHandler<kitsune_p2p::event::KitsuneP2pEvent> for HolochainP2pActor {}
@@ -654,9 +1180,27 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
                 },
             );
 
+        // Agents with active agent info are online, so any signals held
+        // for them can be delivered once their agent info is stored.
+        let online_agents: Vec<_> = if remote_signal_queue::is_enabled(&self.config.tuning_params) {
+            put_requests
+                .iter()
+                .flat_map(|(dna, agents)| {
+                    agents
+                        .iter()
+                        .filter(|info| info.is_active())
+                        .map(|info| (dna.clone(), AgentPubKey::from_kitsune(&info.agent)))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
         let evt_sender = self.evt_sender.clone();
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        let timeout = self.config.tuning_params.implicit_timeout();
         Ok(async move {
-            Ok(futures::future::join_all(
+            let puts = futures::future::join_all(
                 iter::repeat_with(|| evt_sender.clone())
                     .zip(put_requests.into_iter())
                     .map(|(evt_sender, (dna, agents))| async move {
@@ -668,7 +1212,27 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             .collect::<HolochainP2pResult<Vec<Vec<AgentInfoPut>>>>()?
             .into_iter()
             .flatten()
-            .collect())
+            .collect();
+
+            if !online_agents.is_empty() {
+                tokio::task::spawn(async move {
+                    for (dna_hash, to_agent) in online_agents {
+                        if let Err(e) = deliver_queued_signals(
+                            evt_sender.clone(),
+                            kitsune_p2p.clone(),
+                            timeout,
+                            dna_hash,
+                            to_agent,
+                        )
+                        .await
+                        {
+                            tracing::info!("Failed to deliver queued remote signals: {:?}", e);
+                        }
+                    }
+                });
+            }
+
+            Ok(puts)
         }
         .boxed()
         .into())
@@ -811,6 +1375,20 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
                     None => Err(HolochainP2pError::RoutingAgentError(to_agent).into()),
                 }
             }
+            crate::wire::WireMessage::RemoteQuery {
+                zome_name,
+                fn_name,
+                cap_secret,
+                data,
+                from_agent,
+                signature,
+                to_agent,
+                nonce,
+                expires_at,
+            } => self.handle_incoming_remote_query(
+                space, from_agent, signature, to_agent, zome_name, fn_name, cap_secret, data,
+                *nonce, expires_at,
+            ),
             crate::wire::WireMessage::Get { dht_hash, options } => {
                 self.handle_incoming_get(space, to_agent, dht_hash, options)
             }
@@ -823,6 +1401,9 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             WireMessage::CountLinks { query } => {
                 self.handle_incoming_count_links(space, to_agent, query)
             }
+            WireMessage::ProbeBasis { basis } => {
+                self.handle_incoming_probe_basis(space, to_agent, basis)
+            }
             crate::wire::WireMessage::GetAgentActivity {
                 agent,
                 query,
@@ -831,6 +1412,16 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             crate::wire::WireMessage::MustGetAgentActivity { agent, filter } => {
                 self.handle_incoming_must_get_agent_activity(space, to_agent, agent, filter)
             }
+            crate::wire::WireMessage::GetActionBySeq { agent, action_seq } => {
+                self.handle_incoming_get_action_by_seq(space, to_agent, agent, action_seq)
+            }
+            crate::wire::WireMessage::GetEntriesByAuthor {
+                author,
+                entry_type,
+                page,
+            } => self.handle_incoming_get_entries_by_author(
+                space, to_agent, author, entry_type, page,
+            ),
             crate::wire::WireMessage::ValidationReceipts { .. } => {
                 Err(HolochainP2pError::invalid_p2p_message(
                     "invalid: validation receipts are now notifications rather than requests, please upgrade".to_string(),
@@ -868,8 +1459,12 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             | crate::wire::WireMessage::GetMeta { .. }
             | crate::wire::WireMessage::GetLinks { .. }
             | crate::wire::WireMessage::CountLinks { .. }
+            | crate::wire::WireMessage::ProbeBasis { .. }
+            | crate::wire::WireMessage::RemoteQuery { .. }
             | crate::wire::WireMessage::GetAgentActivity { .. }
-            | crate::wire::WireMessage::MustGetAgentActivity { .. } => {
+            | crate::wire::WireMessage::MustGetAgentActivity { .. }
+            | crate::wire::WireMessage::GetActionBySeq { .. }
+            | crate::wire::WireMessage::GetEntriesByAuthor { .. } => {
                 Err(HolochainP2pError::invalid_p2p_message(
                     "invalid call type message in a notify".to_string(),
                 )
@@ -956,7 +1551,10 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             .into_iter()
             .map(|op_data| {
                 let op = crate::wire::WireDhtOpData::decode(op_data.0.clone())
-                    .map_err(HolochainP2pError::from)?
+                    .map_err(|err| {
+                        tracing::warn!(?err, "Failed to decode a received op");
+                        HolochainP2pError::ProtocolViolation(ProtocolViolation::MalformedOp)
+                    })?
                     .op_data;
 
                 aitia::trace!(&hc_sleuth::Event::Fetched {
@@ -1094,6 +1692,36 @@ impl HolochainP2pHandler for HolochainP2pActor {
             .into())
     }
 
+    fn handle_set_agent_gossip(
+        &mut self,
+        dna_hash: DnaHash,
+        agent_pub_key: AgentPubKey,
+        enabled: bool,
+    ) -> HolochainP2pHandlerResult<()> {
+        let space = dna_hash.into_kitsune();
+        let agent = agent_pub_key.into_kitsune();
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(async move {
+            Ok(kitsune_p2p
+                .set_local_agent_gossip(space, agent, enabled)
+                .await?)
+        }
+        .boxed()
+        .into())
+    }
+
+    fn handle_set_egress_policies(
+        &mut self,
+        policies: EgressPolicyMap,
+    ) -> HolochainP2pHandlerResult<()> {
+        self.egress_policies = policies
+            .into_iter()
+            .map(|(cell_id, policy)| (cell_id, Arc::new(policy)))
+            .collect();
+        Ok(async move { Ok(()) }.boxed().into())
+    }
+
     /// Dispatch an outgoing remote call.
     #[tracing::instrument(skip(self), level = "trace")]
     fn handle_call_remote(
@@ -1109,9 +1737,14 @@ impl HolochainP2pHandler for HolochainP2pActor {
         nonce: Nonce256Bits,
         expires_at: Timestamp,
     ) -> HolochainP2pHandlerResult<SerializedBytes> {
-        let space = dna_hash.into_kitsune();
+        let space = dna_hash.clone().into_kitsune();
         let to_agent_kitsune = to_agent.clone().into_kitsune();
 
+        // The remote call can't succeed once it has expired, so don't wait for it any longer.
+        let deadline = Deadline::until(expires_at, self.implicit_budget());
+        deadline.check("call_remote")?;
+        let egress = self.check_egress(&dna_hash, Some(&from_agent), &to_agent, "call_remote");
+
         let req = crate::wire::WireMessage::call_remote(
             zome_name, fn_name, from_agent, signature, to_agent, cap_secret, payload, nonce,
             expires_at,
@@ -1120,8 +1753,57 @@ impl HolochainP2pHandler for HolochainP2pActor {
 
         let kitsune_p2p = self.kitsune_p2p.clone();
         Ok(async move {
+            egress.await?;
+            let result: Vec<u8> = kitsune_p2p
+                .rpc_single(space, to_agent_kitsune, req, Some(deadline.remaining_ms()))
+                .await?;
+            Ok(UnsafeBytes::from(result).into())
+        }
+        .boxed()
+        .into())
+    }
+
+    /// Dispatch an outgoing read-only remote query.
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_remote_query(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: AgentPubKey,
+        signature: Signature,
+        to_agent: AgentPubKey,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap_secret: Option<CapSecret>,
+        payload: ExternIO,
+        nonce: Nonce256Bits,
+        expires_at: Timestamp,
+    ) -> HolochainP2pHandlerResult<SerializedBytes> {
+        let space = dna_hash.clone().into_kitsune();
+        let to_agent_kitsune = to_agent.clone().into_kitsune();
+
+        // The remote call can't succeed once it has expired, so don't wait for it any longer.
+        let deadline = Deadline::until(expires_at, self.implicit_budget());
+        deadline.check("remote_query")?;
+        let egress = self.check_egress(&dna_hash, Some(&from_agent), &to_agent, "remote_query");
+        let supported = self.check_capabilities(
+            &dna_hash,
+            &to_agent,
+            WireCapabilities::REMOTE_QUERY,
+            "remote_query",
+        );
+
+        let req = crate::wire::WireMessage::remote_query(
+            zome_name, fn_name, from_agent, signature, to_agent, cap_secret, payload, nonce,
+            expires_at,
+        )
+        .encode()?;
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(async move {
+            egress.await?;
+            supported.await?;
             let result: Vec<u8> = kitsune_p2p
-                .rpc_single(space, to_agent_kitsune, req, None)
+                .rpc_single(space, to_agent_kitsune, req, Some(deadline.remaining_ms()))
                 .await?;
             Ok(UnsafeBytes::from(result).into())
         }
@@ -1143,28 +1825,101 @@ impl HolochainP2pHandler for HolochainP2pActor {
         nonce: Nonce256Bits,
         expires_at: Timestamp,
     ) -> HolochainP2pHandlerResult<()> {
-        let space = dna_hash.into_kitsune();
-        let to_agents = to_agent_list
+        let space = dna_hash.clone().into_kitsune();
+        let timeout = self.config.tuning_params.implicit_timeout();
+        let egress: Vec<_> = to_agent_list
             .iter()
-            .map(|(_signature, agent)| agent.clone().into_kitsune())
+            .map(|(_, agent)| {
+                self.check_egress(&dna_hash, Some(&from_agent), agent, "send_remote_signal")
+            })
             .collect();
 
-        let req = crate::wire::WireMessage::call_remote_multi(
-            zome_name,
-            fn_name,
-            from_agent,
-            to_agent_list,
-            cap,
-            payload,
-            nonce,
-            expires_at,
-        )
-        .encode()?;
-
-        let timeout = self.config.tuning_params.implicit_timeout();
-
         let kitsune_p2p = self.kitsune_p2p.clone();
+        let host = self.host.clone();
+        let evt_sender = self.evt_sender.clone();
+        let queue_enabled = remote_signal_queue::is_enabled(&self.config.tuning_params);
+        let queue_ttl = self.config.tuning_params.remote_signal_queue_ttl();
+        let queue_max_len = self.config.tuning_params.remote_signal_queue_max_len;
         Ok(async move {
+            // Signals to agents which the egress policies don't allow are dropped,
+            // rather than failing the signal to everyone else.
+            let mut allowed = Vec::with_capacity(to_agent_list.len());
+            for ((signature, agent), egress) in to_agent_list.into_iter().zip(egress) {
+                match egress.await {
+                    Ok(()) => allowed.push((signature, agent)),
+                    Err(HolochainP2pError::EgressBlocked { .. }) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            let to_agent_list = allowed;
+
+            // Rather than sending signals to agents which are offline, where they
+            // would be dropped, hold them until the agents come online.
+            let to_agent_list = if queue_enabled {
+                let now = Timestamp::now();
+                let now_ms = now.as_millis() as u64;
+                let mut online = Vec::with_capacity(to_agent_list.len());
+                for (signature, agent) in to_agent_list {
+                    let info = host
+                        .get_agent_info_signed(GetAgentInfoSignedEvt {
+                            space: space.clone(),
+                            agent: agent.clone().into_kitsune(),
+                        })
+                        .await
+                        .map_err(HolochainP2pError::other)?;
+                    if info.is_some_and(|info| info.is_active() && info.expires_at_ms > now_ms) {
+                        online.push((signature, agent));
+                    } else {
+                        let signal = QueuedRemoteSignal {
+                            from_agent: from_agent.clone(),
+                            zome_name: zome_name.clone(),
+                            fn_name: fn_name.clone(),
+                            cap,
+                            payload: payload.clone(),
+                            expires_at: now.saturating_add(&queue_ttl),
+                        };
+                        let (queued, expired) = evt_sender
+                            .queue_remote_signal(
+                                dna_hash.clone(),
+                                agent.clone(),
+                                signal,
+                                queue_max_len,
+                            )
+                            .await?;
+                        remote_signal_queue::record(QueueEvent::Expired, expired);
+                        if queued {
+                            remote_signal_queue::record(QueueEvent::Queued, 1);
+                        } else {
+                            tracing::debug!(?agent, "Remote signal queue is full, dropping signal");
+                            remote_signal_queue::record(QueueEvent::Dropped, 1);
+                        }
+                    }
+                }
+                online
+            } else {
+                to_agent_list
+            };
+            if to_agent_list.is_empty() {
+                return Ok(());
+            }
+
+            let to_agents = to_agent_list
+                .iter()
+                .map(|(_signature, agent)| agent.clone().into_kitsune())
+                .collect();
+
+            let req = crate::wire::WireMessage::call_remote_multi(
+                zome_name,
+                fn_name,
+                from_agent,
+                to_agent_list,
+                cap,
+                payload,
+                nonce,
+                expires_at,
+            )
+            .encode()?;
+
             kitsune_p2p
                 .targeted_broadcast(space, to_agents, timeout, req, true)
                 .await?;
@@ -1188,6 +1943,8 @@ impl HolochainP2pHandler for HolochainP2pActor {
     ) -> HolochainP2pHandlerResult<()> {
         use kitsune_p2p_types::KitsuneTimeout;
 
+        let peer_filter =
+            self.peer_filter(&dna_hash, Some(&source), "publish", WireCapabilities::NONE);
         let source = source.into_kitsune();
         let space = dna_hash.clone().into_kitsune();
         let basis = basis_hash.to_kitsune();
@@ -1238,6 +1995,7 @@ impl HolochainP2pHandler for HolochainP2pActor {
                         op_hash_list,
                         context: fetch_context,
                     },
+                    peer_filter,
                 )
                 .await?;
             Ok(())
@@ -1250,10 +2008,17 @@ impl HolochainP2pHandler for HolochainP2pActor {
     fn handle_publish_countersign(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         flag: bool,
         basis_hash: holo_hash::OpBasis,
         op: DhtOp,
     ) -> HolochainP2pHandlerResult<()> {
+        let peer_filter = self.peer_filter(
+            &dna_hash,
+            from_agent.as_ref(),
+            "publish_countersign",
+            WireCapabilities::NONE,
+        );
         let space = dna_hash.into_kitsune();
         let basis = basis_hash.to_kitsune();
         let timeout = self.config.tuning_params.implicit_timeout();
@@ -1263,7 +2028,13 @@ impl HolochainP2pHandler for HolochainP2pActor {
             let payload = crate::wire::WireMessage::publish_countersign(flag, op).encode()?;
 
             kitsune_p2p
-                .broadcast(space, basis, timeout, BroadcastData::User(payload))
+                .broadcast(
+                    space,
+                    basis,
+                    timeout,
+                    BroadcastData::User(payload),
+                    peer_filter,
+                )
                 .await?;
             Ok(())
         }
@@ -1275,19 +2046,35 @@ impl HolochainP2pHandler for HolochainP2pActor {
     fn handle_get(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         dht_hash: holo_hash::AnyDhtHash,
         options: actor::GetOptions,
     ) -> HolochainP2pHandlerResult<Vec<WireOps>> {
-        let space = dna_hash.into_kitsune();
+        let space = dna_hash.clone().into_kitsune();
         let basis = dht_hash.to_kitsune();
         let r_options: event::GetOptions = (&options).into();
+        let deadline = self.request_deadline(options.deadline, options.timeout_ms);
+        deadline.check("get")?;
 
         let payload = crate::wire::WireMessage::get(dht_hash, r_options).encode()?;
 
         let kitsune_p2p = self.kitsune_p2p.clone();
         let tuning_params = self.config.tuning_params.clone();
+        let peer_filter = self.peer_filter(
+            &dna_hash,
+            from_agent.as_ref(),
+            "get",
+            WireCapabilities::NONE,
+        );
+        let hedge_delay_ms = options.hedge_delay_ms;
         Ok(async move {
-            let input = kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
+            let mut input =
+                kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
+            input.max_timeout = deadline.to_kitsune_timeout();
+            input.peer_filter = peer_filter;
+            if let Some(hedge_delay_ms) = hedge_delay_ms {
+                input.hedge_delay_ms = hedge_delay_ms;
+            }
             let result = kitsune_p2p
                 .rpc_multi(input)
                 .instrument(tracing::debug_span!("rpc_multi"))
@@ -1309,19 +2096,31 @@ impl HolochainP2pHandler for HolochainP2pActor {
     fn handle_get_meta(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         dht_hash: holo_hash::AnyDhtHash,
         options: actor::GetMetaOptions,
     ) -> HolochainP2pHandlerResult<Vec<MetadataSet>> {
-        let space = dna_hash.into_kitsune();
+        let space = dna_hash.clone().into_kitsune();
         let basis = dht_hash.to_kitsune();
         let r_options: event::GetMetaOptions = (&options).into();
+        let deadline = self.request_deadline(options.deadline, options.timeout_ms);
+        deadline.check("get_meta")?;
 
         let payload = crate::wire::WireMessage::get_meta(dht_hash, r_options).encode()?;
 
         let kitsune_p2p = self.kitsune_p2p.clone();
         let tuning_params = self.config.tuning_params.clone();
+        let peer_filter = self.peer_filter(
+            &dna_hash,
+            from_agent.as_ref(),
+            "get_meta",
+            WireCapabilities::NONE,
+        );
         Ok(async move {
-            let input = kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
+            let mut input =
+                kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
+            input.max_timeout = deadline.to_kitsune_timeout();
+            input.peer_filter = peer_filter;
             let result = kitsune_p2p.rpc_multi(input).await?;
 
             let mut out = Vec::new();
@@ -1340,17 +2139,26 @@ impl HolochainP2pHandler for HolochainP2pActor {
     fn handle_get_links(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         link_key: WireLinkKey,
         options: actor::GetLinksOptions,
     ) -> HolochainP2pHandlerResult<Vec<WireLinkOps>> {
-        let space = dna_hash.into_kitsune();
+        let space = dna_hash.clone().into_kitsune();
         let basis = link_key.base.to_kitsune();
         let r_options: event::GetLinksOptions = (&options).into();
+        let deadline = self.request_deadline(options.deadline, options.timeout_ms);
+        deadline.check("get_links")?;
 
         let payload = crate::wire::WireMessage::get_links(link_key, r_options).encode()?;
 
         let kitsune_p2p = self.kitsune_p2p.clone();
         let tuning_params = self.config.tuning_params.clone();
+        let peer_filter = self.peer_filter(
+            &dna_hash,
+            from_agent.as_ref(),
+            "get_links",
+            WireCapabilities::NONE,
+        );
         Ok(async move {
             let mut input =
                 kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
@@ -1358,6 +2166,8 @@ impl HolochainP2pHandler for HolochainP2pActor {
             //        without doing any pagination / etc...
             //        Setting up RpcMulti to act like RpcSingle
             input.max_remote_agent_count = 1;
+            input.max_timeout = deadline.to_kitsune_timeout();
+            input.peer_filter = peer_filter;
             let result = kitsune_p2p.rpc_multi(input).await?;
 
             let mut out = Vec::new();
@@ -1375,19 +2185,27 @@ impl HolochainP2pHandler for HolochainP2pActor {
     fn handle_count_links(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         query: WireLinkQuery,
     ) -> HolochainP2pHandlerResult<CountLinksResponse> {
-        let space = dna_hash.into_kitsune();
+        let space = dna_hash.clone().into_kitsune();
         let basis = query.base.to_kitsune();
 
         let payload = WireMessage::count_links(query).encode()?;
 
         let kitsune_p2p = self.kitsune_p2p.clone();
         let tuning_params = self.config.tuning_params.clone();
+        let peer_filter = self.peer_filter(
+            &dna_hash,
+            from_agent.as_ref(),
+            "count_links",
+            WireCapabilities::NONE,
+        );
         Ok(async move {
             let mut input =
                 kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
             input.max_remote_agent_count = 1;
+            input.peer_filter = peer_filter;
             let result = kitsune_p2p.rpc_multi(input).await?;
 
             if let Some(result) = result.into_iter().next() {
@@ -1403,26 +2221,69 @@ impl HolochainP2pHandler for HolochainP2pActor {
         .into())
     }
 
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_probe_basis(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
+        to_agent: AgentPubKey,
+        basis: OpBasis,
+        timeout_ms: Option<u64>,
+    ) -> HolochainP2pHandlerResult<ProbeBasisResponse> {
+        let space = dna_hash.clone().into_kitsune();
+        let egress = self.check_egress(&dna_hash, from_agent.as_ref(), &to_agent, "probe_basis");
+        let supported = self.check_capabilities(
+            &dna_hash,
+            &to_agent,
+            WireCapabilities::PROBE_BASIS,
+            "probe_basis",
+        );
+        let to_agent = to_agent.into_kitsune();
+
+        let payload = WireMessage::probe_basis(basis).encode()?;
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(async move {
+            egress.await?;
+            supported.await?;
+            let response = kitsune_p2p
+                .rpc_single(space, to_agent, payload, timeout_ms)
+                .await?;
+            Ok(SerializedBytes::from(UnsafeBytes::from(response)).try_into()?)
+        }
+        .boxed()
+        .into())
+    }
+
     #[tracing::instrument(skip(self), level = "trace")]
     fn handle_get_agent_activity(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         agent: AgentPubKey,
         query: ChainQueryFilter,
         options: actor::GetActivityOptions,
     ) -> HolochainP2pHandlerResult<Vec<AgentActivityResponse<ActionHash>>> {
-        let space = dna_hash.into_kitsune();
+        let space = dna_hash.clone().into_kitsune();
         // Convert the agent key to an any dht hash so it can be used
         // as the basis for sending this request
         let agent_hash: AnyDhtHash = agent.clone().into();
         let basis = agent_hash.to_kitsune();
         let r_options: event::GetActivityOptions = (&options).into();
+        let deadline = self.request_deadline(options.deadline, options.timeout_ms);
+        deadline.check("get_agent_activity")?;
 
         let payload =
             crate::wire::WireMessage::get_agent_activity(agent, query, r_options).encode()?;
 
         let kitsune_p2p = self.kitsune_p2p.clone();
         let tuning_params = self.config.tuning_params.clone();
+        let peer_filter = self.peer_filter(
+            &dna_hash,
+            from_agent.as_ref(),
+            "get_agent_activity",
+            WireCapabilities::NONE,
+        );
         Ok(async move {
             let mut input =
                 kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
@@ -1430,6 +2291,8 @@ impl HolochainP2pHandler for HolochainP2pActor {
             //        without doing any pagination / etc...
             //        Setting up RpcMulti to act like RpcSingle
             input.max_remote_agent_count = 1;
+            input.max_timeout = deadline.to_kitsune_timeout();
+            input.peer_filter = peer_filter;
             let result = kitsune_p2p.rpc_multi(input).await?;
 
             let mut out = Vec::new();
@@ -1448,10 +2311,11 @@ impl HolochainP2pHandler for HolochainP2pActor {
     fn handle_must_get_agent_activity(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         agent: AgentPubKey,
         filter: holochain_zome_types::chain::ChainFilter,
     ) -> HolochainP2pHandlerResult<Vec<MustGetAgentActivityResponse>> {
-        let space = dna_hash.into_kitsune();
+        let space = dna_hash.clone().into_kitsune();
         // Convert the agent key to an any dht hash so it can be used
         // as the basis for sending this request
         let agent_hash: AnyDhtHash = agent.clone().into();
@@ -1461,6 +2325,12 @@ impl HolochainP2pHandler for HolochainP2pActor {
 
         let kitsune_p2p = self.kitsune_p2p.clone();
         let tuning_params = self.config.tuning_params.clone();
+        let peer_filter = self.peer_filter(
+            &dna_hash,
+            from_agent.as_ref(),
+            "must_get_agent_activity",
+            WireCapabilities::NONE,
+        );
         Ok(async move {
             let mut input =
                 kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
@@ -1468,6 +2338,100 @@ impl HolochainP2pHandler for HolochainP2pActor {
             //        without doing any pagination / etc...
             //        Setting up RpcMulti to act like RpcSingle
             input.max_remote_agent_count = 1;
+            input.peer_filter = peer_filter;
+            let result = kitsune_p2p.rpc_multi(input).await?;
+
+            let mut out = Vec::new();
+            for item in result {
+                let kitsune_p2p::actor::RpcMultiResponse { response, .. } = item;
+                out.push(SerializedBytes::from(UnsafeBytes::from(response)).try_into()?);
+            }
+
+            Ok(out)
+        }
+        .boxed()
+        .into())
+    }
+
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_get_action_by_seq(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
+        author: AgentPubKey,
+        action_seq: u32,
+    ) -> HolochainP2pHandlerResult<Vec<ActionBySeqResponse>> {
+        let space = dna_hash.clone().into_kitsune();
+        // Convert the agent key to an any dht hash so it can be used
+        // as the basis for sending this request
+        let agent_hash: AnyDhtHash = author.clone().into();
+        let basis = agent_hash.to_kitsune();
+
+        let payload = crate::wire::WireMessage::get_action_by_seq(author, action_seq).encode()?;
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        let tuning_params = self.config.tuning_params.clone();
+        let peer_filter = self.peer_filter(
+            &dna_hash,
+            from_agent.as_ref(),
+            "get_action_by_seq",
+            WireCapabilities::GET_ACTION_BY_SEQ,
+        );
+        Ok(async move {
+            let mut input =
+                kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
+            // TODO - We're just targeting a single remote node for now
+            //        without doing any pagination / etc...
+            //        Setting up RpcMulti to act like RpcSingle
+            input.max_remote_agent_count = 1;
+            input.peer_filter = peer_filter;
+            let result = kitsune_p2p.rpc_multi(input).await?;
+
+            let mut out = Vec::new();
+            for item in result {
+                let kitsune_p2p::actor::RpcMultiResponse { response, .. } = item;
+                out.push(SerializedBytes::from(UnsafeBytes::from(response)).try_into()?);
+            }
+
+            Ok(out)
+        }
+        .boxed()
+        .into())
+    }
+
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_get_entries_by_author(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
+        author: AgentPubKey,
+        entry_type: EntryType,
+        page: EntriesPage,
+    ) -> HolochainP2pHandlerResult<Vec<EntriesByAuthorResponse>> {
+        let space = dna_hash.clone().into_kitsune();
+        // The agent activity authorities of the author are asked, since they
+        // hold all of its actions. The entries are fetched from their own
+        // authorities by the cascade.
+        let agent_hash: AnyDhtHash = author.clone().into();
+        let basis = agent_hash.to_kitsune();
+
+        let payload =
+            crate::wire::WireMessage::get_entries_by_author(author, entry_type, page).encode()?;
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        let tuning_params = self.config.tuning_params.clone();
+        let peer_filter = self.peer_filter(
+            &dna_hash,
+            from_agent.as_ref(),
+            "get_entries_by_author",
+            WireCapabilities::GET_ENTRIES_BY_AUTHOR,
+        );
+        Ok(async move {
+            // Each authority only lists the actions it has integrated, so the
+            // responses of several are merged by the cascade.
+            let mut input =
+                kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
+            input.peer_filter = peer_filter;
             let result = kitsune_p2p.rpc_multi(input).await?;
 
             let mut out = Vec::new();
@@ -1486,9 +2450,16 @@ impl HolochainP2pHandler for HolochainP2pActor {
     fn handle_send_validation_receipts(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         to_agent: AgentPubKey,
         receipts: ValidationReceiptBundle,
     ) -> HolochainP2pHandlerResult<()> {
+        let egress = self.check_egress(
+            &dna_hash,
+            from_agent.as_ref(),
+            &to_agent,
+            "send_validation_receipts",
+        );
         let space = dna_hash.into_kitsune();
         let to_agent = to_agent.into_kitsune();
 
@@ -1498,6 +2469,7 @@ impl HolochainP2pHandler for HolochainP2pActor {
 
         let kitsune_p2p = self.kitsune_p2p.clone();
         Ok(async move {
+            egress.await?;
             kitsune_p2p
                 .targeted_broadcast(space, vec![to_agent], timeout, req, false)
                 .await?;
@@ -1536,13 +2508,47 @@ impl HolochainP2pHandler for HolochainP2pActor {
         )
     }
 
+    #[tracing::instrument(skip(self), level = "trace")]
+    fn handle_fetch_from_authorities(
+        &mut self,
+        dna_hash: DnaHash,
+        basis_hash: OpBasis,
+        op_hash_list: Vec<OpHashSized>,
+    ) -> HolochainP2pHandlerResult<()> {
+        let space = dna_hash.into_kitsune();
+        let basis = basis_hash.to_kitsune();
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(async move {
+            Ok(kitsune_p2p
+                .fetch_from_authorities(space, basis, op_hash_list)
+                .await?)
+        }
+        .boxed()
+        .into())
+    }
+
     #[tracing::instrument(skip(self), level = "trace")]
     fn handle_countersigning_session_negotiation(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         agents: Vec<AgentPubKey>,
         message: CountersigningSessionNegotiationMessage,
     ) -> HolochainP2pHandlerResult<()> {
+        // The session can't go ahead without every agent, so any agent which
+        // the egress policy doesn't allow fails the whole negotiation.
+        let egress: Vec<_> = agents
+            .iter()
+            .map(|agent| {
+                self.check_egress(
+                    &dna_hash,
+                    from_agent.as_ref(),
+                    agent,
+                    "countersigning_session_negotiation",
+                )
+            })
+            .collect();
         let space = dna_hash.into_kitsune();
         let agents = agents.into_iter().map(|a| a.into_kitsune()).collect();
 
@@ -1553,6 +2559,7 @@ impl HolochainP2pHandler for HolochainP2pActor {
 
         let kitsune_p2p = self.kitsune_p2p.clone();
         Ok(async move {
+            futures::future::try_join_all(egress).await?;
             kitsune_p2p
                 .targeted_broadcast(space, agents, timeout, payload, false)
                 .await?;
@@ -1601,4 +2608,24 @@ impl HolochainP2pHandler for HolochainP2pActor {
         .boxed()
         .into())
     }
+
+    fn handle_storage_arc(
+        &mut self,
+        dna_hash: DnaHash,
+        agent_pub_key: AgentPubKey,
+    ) -> HolochainP2pHandlerResult<Option<kitsune_p2p_types::dht_arc::DhtArc>> {
+        let space = dna_hash.into_kitsune();
+        let agent = agent_pub_key.into_kitsune();
+
+        let host = self.host.clone();
+        Ok(async move {
+            let info = host
+                .get_agent_info_signed(GetAgentInfoSignedEvt { space, agent })
+                .await
+                .map_err(HolochainP2pError::other)?;
+            Ok(info.map(|info| info.storage_arc()))
+        }
+        .boxed()
+        .into())
+    }
 }
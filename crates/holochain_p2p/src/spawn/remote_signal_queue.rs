@@ -0,0 +1,78 @@
+//! Store-and-forward of remote signals to agents which are offline.
+//!
+//! A signal to an agent which has no active, unexpired agent info is held by
+//! the conductor instead of being sent into the void, and is delivered once an
+//! active agent info for that agent is put, which is when kitsune next sees it
+//! online. The conductor persists the held signals, so they survive a restart.
+
+use kitsune_p2p_types::config::tuning_params_struct::KitsuneP2pTuningParams;
+use opentelemetry_api::{global::meter_with_version, metrics::*, KeyValue};
+use std::sync::OnceLock;
+
+static QUEUE_METRIC: OnceLock<Counter<u64>> = OnceLock::new();
+
+/// What happened to a signal which went through the queue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum QueueEvent {
+    Queued,
+    /// Sent on because the recipient came online.
+    Delivered,
+    Expired,
+    Dropped,
+}
+
+impl QueueEvent {
+    fn as_str(&self) -> &'static str {
+        match self {
+            QueueEvent::Queued => "queued",
+            QueueEvent::Delivered => "delivered",
+            QueueEvent::Expired => "expired",
+            QueueEvent::Dropped => "dropped",
+        }
+    }
+}
+
+pub(crate) fn record(event: QueueEvent, count: usize) {
+    if count == 0 {
+        return;
+    }
+    QUEUE_METRIC
+        .get_or_init(|| {
+            meter_with_version(
+                "hc.holochain_p2p",
+                None::<&'static str>,
+                None::<&'static str>,
+                Some(vec![]),
+            )
+            .u64_counter("hc.holochain_p2p.remote_signal.queue")
+            .with_description(
+                "The number of remote signals to offline agents, by what happened to them",
+            )
+            .init()
+        })
+        .add(count as u64, &[KeyValue::new("event", event.as_str())]);
+}
+
+/// Whether signals to offline agents are held at all, which they aren't when
+/// either the time to live or the length of the queue is zero.
+pub(crate) fn is_enabled(tuning_params: &KitsuneP2pTuningParams) -> bool {
+    tuning_params.remote_signal_queue_ttl_ms > 0 && tuning_params.remote_signal_queue_max_len > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_ttl_or_length_disables_the_queue() {
+        let tuning_params = |ttl_ms, max_len| {
+            let mut tuning_params = KitsuneP2pTuningParams::default();
+            tuning_params.remote_signal_queue_ttl_ms = ttl_ms;
+            tuning_params.remote_signal_queue_max_len = max_len;
+            tuning_params
+        };
+        assert!(is_enabled(&KitsuneP2pTuningParams::default()));
+        assert!(!is_enabled(&tuning_params(0, 10)));
+        assert!(!is_enabled(&tuning_params(1000, 0)));
+    }
+}
@@ -33,6 +33,22 @@ impl HolochainP2pHandler for StubNetwork {
         Err("stub".into())
     }
 
+    fn handle_set_agent_gossip(
+        &mut self,
+        dna_hash: DnaHash,
+        agent_pub_key: AgentPubKey,
+        enabled: bool,
+    ) -> HolochainP2pHandlerResult<()> {
+        Err("stub".into())
+    }
+
+    fn handle_set_egress_policies(
+        &mut self,
+        policies: EgressPolicyMap,
+    ) -> HolochainP2pHandlerResult<()> {
+        Err("stub".into())
+    }
+
     fn handle_call_remote(
         &mut self,
         dna_hash: DnaHash,
@@ -49,6 +65,22 @@ impl HolochainP2pHandler for StubNetwork {
         Err("stub".into())
     }
 
+    fn handle_remote_query(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: AgentPubKey,
+        signature: Signature,
+        to_agent: AgentPubKey,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap_secret: Option<CapSecret>,
+        payload: ExternIO,
+        nonce: Nonce256Bits,
+        expires_at: Timestamp,
+    ) -> HolochainP2pHandlerResult<SerializedBytes> {
+        Err("stub".into())
+    }
+
     fn handle_send_remote_signal(
         &mut self,
         dna_hash: DnaHash,
@@ -81,6 +113,7 @@ impl HolochainP2pHandler for StubNetwork {
     fn handle_publish_countersign(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         flag: bool,
         basis_hash: holo_hash::OpBasis,
         op: DhtOp,
@@ -91,6 +124,7 @@ impl HolochainP2pHandler for StubNetwork {
     fn handle_get(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         dht_hash: holo_hash::AnyDhtHash,
         options: actor::GetOptions,
     ) -> HolochainP2pHandlerResult<Vec<WireOps>> {
@@ -100,6 +134,7 @@ impl HolochainP2pHandler for StubNetwork {
     fn handle_get_meta(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         dht_hash: holo_hash::AnyDhtHash,
         options: actor::GetMetaOptions,
     ) -> HolochainP2pHandlerResult<Vec<MetadataSet>> {
@@ -109,6 +144,7 @@ impl HolochainP2pHandler for StubNetwork {
     fn handle_get_links(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         link_key: WireLinkKey,
         options: actor::GetLinksOptions,
     ) -> HolochainP2pHandlerResult<Vec<WireLinkOps>> {
@@ -118,14 +154,27 @@ impl HolochainP2pHandler for StubNetwork {
     fn handle_count_links(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         query: WireLinkQuery,
     ) -> HolochainP2pHandlerResult<CountLinksResponse> {
         Err("stub".into())
     }
 
+    fn handle_probe_basis(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
+        to_agent: AgentPubKey,
+        basis: OpBasis,
+        timeout_ms: Option<u64>,
+    ) -> HolochainP2pHandlerResult<ProbeBasisResponse> {
+        Err("stub".into())
+    }
+
     fn handle_get_agent_activity(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         agent: AgentPubKey,
         query: ChainQueryFilter,
         options: actor::GetActivityOptions,
@@ -136,15 +185,38 @@ impl HolochainP2pHandler for StubNetwork {
     fn handle_must_get_agent_activity(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         agent: AgentPubKey,
         filter: holochain_zome_types::chain::ChainFilter,
     ) -> HolochainP2pHandlerResult<Vec<MustGetAgentActivityResponse>> {
         Err("stub".into())
     }
 
+    fn handle_get_action_by_seq(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
+        author: AgentPubKey,
+        action_seq: u32,
+    ) -> HolochainP2pHandlerResult<Vec<ActionBySeqResponse>> {
+        Err("stub".into())
+    }
+
+    fn handle_get_entries_by_author(
+        &mut self,
+        dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
+        author: AgentPubKey,
+        entry_type: EntryType,
+        page: EntriesPage,
+    ) -> HolochainP2pHandlerResult<Vec<EntriesByAuthorResponse>> {
+        Err("stub".into())
+    }
+
     fn handle_send_validation_receipts(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         to_agent: AgentPubKey,
         receipts: ValidationReceiptBundle,
     ) -> HolochainP2pHandlerResult<()> {
@@ -162,9 +234,19 @@ impl HolochainP2pHandler for StubNetwork {
     ) -> HolochainP2pHandlerResult<bool> {
         Err("stub".into())
     }
+
+    fn handle_fetch_from_authorities(
+        &mut self,
+        dna_hash: DnaHash,
+        basis_hash: OpBasis,
+        op_hash_list: Vec<OpHashSized>,
+    ) -> HolochainP2pHandlerResult<()> {
+        Err("stub".into())
+    }
     fn handle_countersigning_session_negotiation(
         &mut self,
         dna_hash: DnaHash,
+        from_agent: Option<AgentPubKey>,
         agents: Vec<AgentPubKey>,
         message: event::CountersigningSessionNegotiationMessage,
     ) -> HolochainP2pHandlerResult<()> {
@@ -188,6 +270,14 @@ impl HolochainP2pHandler for StubNetwork {
     ) -> HolochainP2pHandlerResult<kitsune_p2p::gossip::sharded_gossip::KitsuneDiagnostics> {
         Err("stub".into())
     }
+
+    fn handle_storage_arc(
+        &mut self,
+        dna_hash: DnaHash,
+        agent_pub_key: AgentPubKey,
+    ) -> HolochainP2pHandlerResult<Option<kitsune_p2p_types::dht_arc::DhtArc>> {
+        Err("stub".into())
+    }
 }
 
 /// Spawn a stub network that doesn't respond to any messages.
@@ -327,6 +417,7 @@ mod tests {
                     payload: payload.clone(),
                     nonce,
                     expires_at,
+                    wait_for_publish: None,
                 }
                 .data_to_sign()
                 .unwrap(),
@@ -399,7 +490,7 @@ mod tests {
             },
             validators_signatures: vec![],
         }];
-        p2p.send_validation_receipts(dna, a1, receipts.into())
+        p2p.send_validation_receipts(dna, None, a1, receipts.into())
             .await
             .unwrap();
 
@@ -596,7 +687,12 @@ mod tests {
 
         tracing::info!("test - get");
         let res = p2p
-            .get(dna, hash, crate::actor::GetOptions::default())
+            .get(
+                dna.clone(),
+                None,
+                hash.clone(),
+                crate::actor::GetOptions::default(),
+            )
             .await
             .unwrap();
 
@@ -607,6 +703,14 @@ mod tests {
             assert!(r == test_1 || r == test_2);
         }
 
+        tracing::info!("test - get with an expired deadline");
+        let options = crate::actor::GetOptions {
+            deadline: Some(Deadline::new(std::time::Duration::ZERO)),
+            ..Default::default()
+        };
+        let err = p2p.get(dna, None, hash, options).await.unwrap_err();
+        assert!(matches!(err, HolochainP2pError::DeadlineExceeded { .. }));
+
         tracing::info!("test - end of test shutdown p2p");
         p2p.ghost_actor_shutdown().await.unwrap();
         tracing::info!("test - end of test await task end");
@@ -683,13 +787,21 @@ mod tests {
             base: hash.into(),
             type_query: LinkTypeFilter::single_dep(0.into()),
             tag: None,
+            tag_range: None,
+            tag_fields: Vec::new(),
             after: None,
             before: None,
             author: None,
+            since: None,
         };
 
         let res = p2p
-            .get_links(dna, link_key, crate::actor::GetLinksOptions::default())
+            .get_links(
+                dna,
+                None,
+                link_key,
+                crate::actor::GetLinksOptions::default(),
+            )
             .await
             .unwrap();
 
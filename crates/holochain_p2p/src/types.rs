@@ -36,6 +36,44 @@ pub enum HolochainP2pError {
     /// Chain Head Coordination error
     #[error(transparent)]
     ChcError(#[from] holochain_types::chc::ChcError),
+
+    /// A request could not be made because its deadline had already passed
+    #[error("The deadline for {request} expired before the request could be made, it had a budget of {budget_ms}ms")]
+    DeadlineExceeded {
+        /// The request which was not made
+        request: String,
+        /// The total time the deadline allowed
+        budget_ms: u64,
+    },
+
+    /// A request was not sent because the egress policy of the space it was made in doesn't allow it
+    #[error("The egress policy of this app doesn't allow {request} to be sent to {to_agent}")]
+    EgressBlocked {
+        /// The request which was not sent
+        request: String,
+        /// The peer the request would have been sent to
+        to_agent: holo_hash::AgentPubKey,
+    },
+
+    /// A request was not sent because the peer's wire protocol doesn't support it
+    #[error("{to_agent} doesn't support {request}, its conductor must be upgraded")]
+    UnsupportedByPeer {
+        /// The request which was not sent
+        request: String,
+        /// The peer the request would have been sent to
+        to_agent: holo_hash::AgentPubKey,
+    },
+
+    /// A request was refused because the conductor is too busy to handle it, it may be retried later
+    #[error("The conductor is too busy to handle {request}, try again later")]
+    Busy {
+        /// The request which was refused
+        request: String,
+    },
+
+    /// Data sent by a remote peer was rejected as a breach of the protocol
+    #[error("Protocol violation: {0:?}")]
+    ProtocolViolation(kitsune_p2p::metrics::ProtocolViolation),
 }
 
 impl HolochainP2pError {
@@ -61,6 +99,7 @@ impl From<kitsune_p2p::KitsuneP2pError> for HolochainP2pError {
             RoutingAgentError(agent) => {
                 Self::RoutingAgentError(holo_hash::AgentPubKey::from_kitsune(&agent))
             }
+            ProtocolViolation(violation) => Self::ProtocolViolation(violation),
             _ => Self::OtherKitsuneP2pError(e),
         }
     }
@@ -73,6 +112,7 @@ impl From<HolochainP2pError> for kitsune_p2p::KitsuneP2pError {
             RoutingDnaError(dna) => Self::RoutingSpaceError(dna.to_kitsune()),
             RoutingAgentError(agent) => Self::RoutingAgentError(agent.to_kitsune()),
             OtherKitsuneP2pError(e) => e,
+            ProtocolViolation(violation) => Self::ProtocolViolation(violation),
             _ => Self::other(e),
         }
     }
@@ -108,7 +148,44 @@ pub fn space_holo_to_kit(d: holo_hash::DnaHash) -> kitsune_p2p::KitsuneSpace {
     d.into_kitsune_raw()
 }
 
+/// Turn a [`AgentInfoSigned`](kitsune_p2p_types::agent_info::AgentInfoSigned)
+/// into an [`AgentInfoOp`](holochain_types::prelude::AgentInfoOp) which
+/// publishes it to the authorities for its agent.
+pub fn agent_info_to_op(
+    info: &kitsune_p2p_types::agent_info::AgentInfoSigned,
+) -> actor::HolochainP2pResult<holochain_types::prelude::AgentInfoOp> {
+    use holochain_types::prelude::Timestamp;
+    let signature = holochain_types::prelude::Signature::try_from(&info.signature[..])
+        .map_err(HolochainP2pError::other)?;
+    Ok(holochain_types::prelude::AgentInfoOp {
+        agent: holo_hash::AgentPubKey::from_kitsune(&info.agent),
+        agent_info: info.encoded_bytes.to_vec(),
+        signature,
+        signed_at: Timestamp::from_micros(info.signed_at_ms as i64 * 1000),
+        expires_at: Timestamp::from_micros(info.expires_at_ms as i64 * 1000),
+    })
+}
+
+/// Turn an [`AgentInfoOp`](holochain_types::prelude::AgentInfoOp) back into
+/// the [`AgentInfoSigned`](kitsune_p2p_types::agent_info::AgentInfoSigned) it
+/// was made from. This only decodes the info, the signature must be checked
+/// separately.
+pub fn agent_info_from_op(
+    op: &holochain_types::prelude::AgentInfoOp,
+) -> actor::HolochainP2pResult<kitsune_p2p_types::agent_info::AgentInfoSigned> {
+    use kitsune_p2p_types::agent_info::agent_info_helper::AgentInfoSignedEncode;
+    let encode = AgentInfoSignedEncode {
+        agent: op.agent.to_kitsune(),
+        signature: Arc::new(op.signature.0.to_vec().into()),
+        agent_info: op.agent_info.clone().into_boxed_slice(),
+    };
+    let mut buf = Vec::new();
+    kitsune_p2p_types::codec::rmp_encode(&mut buf, &encode).map_err(HolochainP2pError::other)?;
+    kitsune_p2p_types::agent_info::AgentInfoSigned::decode(&buf).map_err(HolochainP2pError::other)
+}
+
 pub mod actor;
+mod deadline;
 pub mod event;
 
 #[cfg(feature = "mock_network")]
@@ -118,8 +195,14 @@ pub(crate) mod wire;
 
 use std::sync::Arc;
 
+pub use deadline::Deadline;
+pub use wire::ProbeBasisResponse;
+pub use wire::WireCapabilities;
 pub use wire::WireDhtOpData;
 pub use wire::WireMessage;
+pub use wire::WireProtocol;
+pub use wire::MIN_WIRE_PROTOCOL_VERSION;
+pub use wire::WIRE_PROTOCOL_VERSION;
 
 use crate::HolochainP2pDnaT;
 
@@ -10,6 +10,9 @@ use kitsune_p2p::dependencies::kitsune_p2p_fetch::OpHashSized;
 use kitsune_p2p::gossip::sharded_gossip::KitsuneDiagnostics;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
 
+/// The egress policy of each cell which has one.
+pub type EgressPolicyMap = std::collections::HashMap<CellId, EgressPolicy>;
+
 /// Holochain-specific FetchContext extension trait.
 pub trait FetchContextExt {
     /// Applies the "request_validation_receipt" flag *if* the param is true
@@ -74,6 +77,11 @@ pub struct GetOptions {
     /// not a timeout error.
     pub timeout_ms: Option<u64>,
 
+    /// ```[Network]```
+    /// A deadline shared with other requests made for the same caller.
+    /// When set this takes precedence over `timeout_ms`.
+    pub deadline: Option<Deadline>,
+
     /// ```[Network]```
     /// We are interested in speed. If `true` and we have any results
     /// when `race_timeout_ms` is expired, those results will be returned.
@@ -86,6 +94,18 @@ pub struct GetOptions {
     /// Set to `None` for a default "best-effort" race.
     pub race_timeout_ms: Option<u64>,
 
+    /// ```[Network]```
+    /// If an authority hasn't responded within this many milliseconds,
+    /// ask another authority as well and use the first response.
+    /// Set to `Some(0)` to only ask one authority at a time, or to `None`
+    /// for the `default_rpc_multi_hedge_delay_ms` tuning param.
+    ///
+    /// Only gets can override the hedge delay. Other requests which are made
+    /// of several authorities, such as `get_links` or `get_agent_activity`,
+    /// always use the tuning param. Zomes can't set it either, so gets made
+    /// through the HDK use the tuning param too.
+    pub hedge_delay_ms: Option<u64>,
+
     /// ```[Remote]```
     /// Whether the remote-end should follow redirects or just return the
     /// requested entry.
@@ -106,8 +126,10 @@ impl Default for GetOptions {
         Self {
             remote_agent_count: None,
             timeout_ms: None,
+            deadline: None,
             as_race: true,
             race_timeout_ms: None,
+            hedge_delay_ms: None,
             follow_redirects: true,
             all_live_actions_with_metadata: false,
             request_type: Default::default(),
@@ -122,8 +144,10 @@ impl GetOptions {
         Self {
             remote_agent_count: None,
             timeout_ms: None,
+            deadline: None,
             as_race: true,
             race_timeout_ms: None,
+            hedge_delay_ms: None,
             // Never redirect as the returned value must always match the hash.
             follow_redirects: false,
             all_live_actions_with_metadata: false,
@@ -157,6 +181,11 @@ pub struct GetMetaOptions {
     /// not a timeout error.
     pub timeout_ms: Option<u64>,
 
+    /// ```[Network]```
+    /// A deadline shared with other requests made for the same caller.
+    /// When set this takes precedence over `timeout_ms`.
+    pub deadline: Option<Deadline>,
+
     /// ```[Network]```
     /// We are interested in speed. If `true` and we have any results
     /// when `race_timeout_ms` is expired, those results will be returned.
@@ -179,6 +208,7 @@ impl Default for GetMetaOptions {
         Self {
             remote_agent_count: None,
             timeout_ms: None,
+            deadline: None,
             as_race: true,
             race_timeout_ms: None,
             metadata_request: MetadataRequest::default(),
@@ -198,6 +228,11 @@ pub struct GetLinksOptions {
     /// Note - if all requests time-out you will receive an empty result,
     /// not a timeout error.
     pub timeout_ms: Option<u64>,
+
+    /// ```[Network]```
+    /// A deadline shared with other requests made for the same caller.
+    /// When set this takes precedence over `timeout_ms`.
+    pub deadline: Option<Deadline>,
     /// Whether to fetch links from the network or return only
     /// locally available links. Defaults to fetching links from network.
     pub get_options: holochain_zome_types::entry::GetOptions,
@@ -215,6 +250,11 @@ pub struct GetActivityOptions {
     /// Note - if all requests time-out you will receive an empty result,
     /// not a timeout error.
     pub timeout_ms: Option<u64>,
+
+    /// ```[Network]```
+    /// A deadline shared with other requests made for the same caller.
+    /// When set this takes precedence over `timeout_ms`.
+    pub deadline: Option<Deadline>,
     /// Number of times to retry getting records in parallel.
     /// For a small dht a large parallel get can overwhelm a single
     /// agent and it can be worth retrying the records that didn't
@@ -232,16 +272,21 @@ pub struct GetActivityOptions {
     /// Include the full signed actions and hashes in the response
     /// instead of just the hashes.
     pub include_full_actions: bool,
+    /// Where the activity is read from. This is only used locally
+    /// and is not forwarded to the remote agent.
+    pub read_source: holochain_zome_types::entry::ReadSource,
 }
 
 impl Default for GetActivityOptions {
     fn default() -> Self {
         Self {
             timeout_ms: None,
+            deadline: None,
             retry_gets: 0,
             include_valid_activity: true,
             include_rejected_activity: false,
             include_full_actions: false,
+            read_source: Default::default(),
         }
     }
 }
@@ -258,6 +303,15 @@ ghost_actor::ghost_chan! {
         /// If a cell is disabled, we'll need to \"leave\" the network module as well.
         fn leave(dna_hash: DnaHash, agent_pub_key: AgentPubKey) -> ();
 
+        /// Stop or resume gossip for a joined agent, while it stays in the network
+        /// and can still make and answer requests.
+        fn set_agent_gossip(dna_hash: DnaHash, agent_pub_key: AgentPubKey, enabled: bool) -> ();
+
+        /// Restrict the peers the requests of each cell may be sent to, to those which
+        /// its egress policy allows. Cells which aren't listed, and requests which aren't
+        /// made for a cell, are not restricted.
+        fn set_egress_policies(policies: EgressPolicyMap) -> ();
+
         /// Invoke a zome function on a remote node (if you have been granted the capability).
         fn call_remote(
             dna_hash: DnaHash,
@@ -272,6 +326,21 @@ ghost_actor::ghost_chan! {
             expires_at: Timestamp,
         ) -> SerializedBytes;
 
+        /// Invoke a read-only zome function on a remote node (if you have been granted
+        /// the capability to query it).
+        fn remote_query(
+            dna_hash: DnaHash,
+            from_agent: AgentPubKey,
+            signature: Signature,
+            to_agent: AgentPubKey,
+            zome_name: ZomeName,
+            fn_name: FunctionName,
+            cap_secret: Option<CapSecret>,
+            payload: ExternIO,
+            nonce: Nonce256Bits,
+            expires_at: Timestamp,
+        ) -> SerializedBytes;
+
         /// Invoke a zome function on a remote node (if you have been granted the capability).
         /// This is a fire-and-forget operation, a best effort will be made
         /// to forward the signal, but if the conductor network is overworked
@@ -303,6 +372,7 @@ ghost_actor::ghost_chan! {
         /// Publish a countersigning op.
         fn publish_countersign(
             dna_hash: DnaHash,
+            from_agent: Option<AgentPubKey>,
             flag: bool,
             basis_hash: holo_hash::OpBasis,
             op: DhtOp,
@@ -311,6 +381,7 @@ ghost_actor::ghost_chan! {
         /// Get an entry from the DHT.
         fn get(
             dna_hash: DnaHash,
+            from_agent: Option<AgentPubKey>,
             dht_hash: holo_hash::AnyDhtHash,
             options: GetOptions,
         ) -> Vec<WireOps>;
@@ -318,6 +389,7 @@ ghost_actor::ghost_chan! {
         /// Get metadata from the DHT.
         fn get_meta(
             dna_hash: DnaHash,
+            from_agent: Option<AgentPubKey>,
             dht_hash: holo_hash::AnyDhtHash,
             options: GetMetaOptions,
         ) -> Vec<MetadataSet>;
@@ -325,6 +397,7 @@ ghost_actor::ghost_chan! {
         /// Get links from the DHT.
         fn get_links(
             dna_hash: DnaHash,
+            from_agent: Option<AgentPubKey>,
             link_key: WireLinkKey,
             options: GetLinksOptions,
         ) -> Vec<WireLinkOps>;
@@ -332,12 +405,23 @@ ghost_actor::ghost_chan! {
         /// Get a count of links from the DHT.
         fn count_links(
             dna_hash: DnaHash,
+            from_agent: Option<AgentPubKey>,
             query: WireLinkQuery,
         ) -> CountLinksResponse;
 
+        /// Ask a single authority how many ops it holds for a basis.
+        fn probe_basis(
+            dna_hash: DnaHash,
+            from_agent: Option<AgentPubKey>,
+            to_agent: AgentPubKey,
+            basis: OpBasis,
+            timeout_ms: Option<u64>,
+        ) -> ProbeBasisResponse;
+
         /// Get agent activity from the DHT.
         fn get_agent_activity(
             dna_hash: DnaHash,
+            from_agent: Option<AgentPubKey>,
             agent: AgentPubKey,
             query: ChainQueryFilter,
             options: GetActivityOptions,
@@ -346,12 +430,30 @@ ghost_actor::ghost_chan! {
         /// A remote node is requesting agent activity from us.
         fn must_get_agent_activity(
             dna_hash: DnaHash,
+            from_agent: Option<AgentPubKey>,
             author: AgentPubKey,
             filter: holochain_zome_types::chain::ChainFilter,
         ) -> Vec<MustGetAgentActivityResponse>;
 
+        /// Get the actions at one position of an agent's chain from the DHT.
+        fn get_action_by_seq(
+            dna_hash: DnaHash,
+            from_agent: Option<AgentPubKey>,
+            author: AgentPubKey,
+            action_seq: u32,
+        ) -> Vec<ActionBySeqResponse>;
+
+        /// Get a page of the public entries of one type created by an agent from the DHT.
+        fn get_entries_by_author(
+            dna_hash: DnaHash,
+            from_agent: Option<AgentPubKey>,
+            author: AgentPubKey,
+            entry_type: EntryType,
+            page: EntriesPage,
+        ) -> Vec<EntriesByAuthorResponse>;
+
         /// Send a validation receipt to a remote node.
-        fn send_validation_receipts(dna_hash: DnaHash, to_agent: AgentPubKey, receipts: ValidationReceiptBundle) -> ();
+        fn send_validation_receipts(dna_hash: DnaHash, from_agent: Option<AgentPubKey>, to_agent: AgentPubKey, receipts: ValidationReceiptBundle) -> ();
 
         /// New data has been integrated and is ready for gossiping.
         fn new_integrated_data(dna_hash: DnaHash) -> ();
@@ -359,9 +461,14 @@ ghost_actor::ghost_chan! {
         /// Check if any local agent in this space is an authority for a hash.
         fn authority_for_hash(dna_hash: DnaHash, basis: OpBasis) -> bool;
 
+        /// Queue ops which aren't held locally to be fetched from the
+        /// authorities for their basis.
+        fn fetch_from_authorities(dna_hash: DnaHash, basis: OpBasis, op_hash_list: Vec<OpHashSized>) -> ();
+
         /// Messages between agents negotiation a countersigning session.
         fn countersigning_session_negotiation(
             dna_hash: DnaHash,
+            from_agent: Option<AgentPubKey>,
             agents: Vec<AgentPubKey>,
             message: event::CountersigningSessionNegotiationMessage,
         ) -> ();
@@ -376,6 +483,10 @@ ghost_actor::ghost_chan! {
 
         /// Get struct for diagnostic data
         fn get_diagnostics(dna_hash: DnaHash) -> KitsuneDiagnostics;
+
+        /// Get the DHT arc a local agent is currently storing data for,
+        /// as advertised in its latest agent info.
+        fn storage_arc(dna_hash: DnaHash, agent_pub_key: AgentPubKey) -> Option<kitsune_p2p_types::dht_arc::DhtArc>;
     }
 }
 
@@ -384,13 +495,21 @@ pub type HolochainP2pRef = ghost_actor::GhostSender<HolochainP2p>;
 
 /// Extension trait for converting `GhostSender<HolochainP2p>` into HolochainP2pDna
 pub trait HolochainP2pRefToDna {
-    /// Partially apply dna_hash && agent_pub_key to this sender,
+    /// Partially apply dna_hash to this sender,
     /// binding it to a specific dna context.
     fn into_dna(self, dna_hash: DnaHash, chc: Option<ChcImpl>) -> crate::HolochainP2pDna;
 
-    /// Clone and partially apply dna_hash && agent_pub_key to this sender,
+    /// Clone and partially apply dna_hash to this sender,
     /// binding it to a specific dna context.
     fn to_dna(&self, dna_hash: DnaHash, chc: Option<ChcImpl>) -> crate::HolochainP2pDna;
+
+    /// Partially apply dna_hash && agent_pub_key to this sender,
+    /// binding it to a specific cell, whose egress policy its requests follow.
+    fn into_cell(self, cell_id: CellId, chc: Option<ChcImpl>) -> crate::HolochainP2pDna;
+
+    /// Clone and partially apply dna_hash && agent_pub_key to this sender,
+    /// binding it to a specific cell, whose egress policy its requests follow.
+    fn to_cell(&self, cell_id: CellId, chc: Option<ChcImpl>) -> crate::HolochainP2pDna;
 }
 
 impl HolochainP2pRefToDna for HolochainP2pRef {
@@ -398,6 +517,7 @@ impl HolochainP2pRefToDna for HolochainP2pRef {
         crate::HolochainP2pDna {
             sender: self,
             dna_hash: Arc::new(dna_hash),
+            agent: None,
             chc,
         }
     }
@@ -405,4 +525,18 @@ impl HolochainP2pRefToDna for HolochainP2pRef {
     fn to_dna(&self, dna_hash: DnaHash, chc: Option<ChcImpl>) -> crate::HolochainP2pDna {
         self.clone().into_dna(dna_hash, chc)
     }
+
+    fn into_cell(self, cell_id: CellId, chc: Option<ChcImpl>) -> crate::HolochainP2pDna {
+        let (dna_hash, agent) = cell_id.into_dna_and_agent();
+        crate::HolochainP2pDna {
+            sender: self,
+            dna_hash: Arc::new(dna_hash),
+            agent: Some(Arc::new(agent)),
+            chc,
+        }
+    }
+
+    fn to_cell(&self, cell_id: CellId, chc: Option<ChcImpl>) -> crate::HolochainP2pDna {
+        self.clone().into_cell(cell_id, chc)
+    }
 }
@@ -0,0 +1,122 @@
+//! A time budget for an outgoing network request.
+
+use crate::actor::HolochainP2pResult;
+use crate::HolochainP2pError;
+use holochain_types::prelude::Timestamp;
+use std::time::Duration;
+
+/// The point in time by which an outgoing network request must complete.
+///
+/// A deadline is created once by the caller and set in the options of each
+/// request it should bound. The p2p actor and kitsune use the time that is
+/// remaining rather than starting a fresh timeout of their own, so several
+/// requests made with the same deadline share one budget. The cascade doesn't
+/// set one, so gets made through it are bounded by their `timeout_ms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deadline {
+    expires_at: tokio::time::Instant,
+    budget: Duration,
+}
+
+impl Deadline {
+    /// A deadline which expires once `budget` has elapsed from now.
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            expires_at: tokio::time::Instant::now() + budget,
+            budget,
+        }
+    }
+
+    /// A deadline for a `timeout_ms` request option, falling back
+    /// to `default_budget` when no timeout was given.
+    pub fn from_timeout_ms(timeout_ms: Option<u64>, default_budget: Duration) -> Self {
+        Self::new(timeout_ms.map_or(default_budget, Duration::from_millis))
+    }
+
+    /// A deadline which expires at `expires_at`, but never allows more than
+    /// `max_budget`. Used for requests, such as remote zome calls, which
+    /// already carry an expiry of their own.
+    pub fn until(expires_at: Timestamp, max_budget: Duration) -> Self {
+        let until_expiry = (expires_at.as_micros() - Timestamp::now().as_micros()).max(0);
+        Self::new(max_budget.min(Duration::from_micros(until_expiry as u64)))
+    }
+
+    /// The total time this deadline allowed when it was created.
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// The time left before this deadline expires.
+    pub fn remaining(&self) -> Duration {
+        self.expires_at
+            .saturating_duration_since(tokio::time::Instant::now())
+    }
+
+    /// Has this deadline expired?
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= tokio::time::Instant::now()
+    }
+
+    /// `Ok(())` if there is time left to make the `request`,
+    /// otherwise [`HolochainP2pError::DeadlineExceeded`].
+    pub fn check(&self, request: &str) -> HolochainP2pResult<()> {
+        if self.is_expired() {
+            Err(HolochainP2pError::DeadlineExceeded {
+                request: request.to_string(),
+                budget_ms: self.budget.as_millis() as u64,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The remaining time as a kitsune timeout.
+    pub fn to_kitsune_timeout(&self) -> kitsune_p2p_types::KitsuneTimeout {
+        kitsune_p2p_types::KitsuneTimeout::new(self.remaining())
+    }
+
+    /// The remaining time in milliseconds, as used by `timeout_ms` parameters.
+    pub fn remaining_ms(&self) -> u64 {
+        self.remaining().as_millis() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn deadline_expires_after_budget() {
+        let deadline = Deadline::from_timeout_ms(Some(10), Duration::from_secs(60));
+        assert_eq!(Duration::from_millis(10), deadline.budget());
+        assert!(deadline.remaining() <= deadline.budget());
+        assert!(deadline.check("get").is_ok());
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(deadline.is_expired());
+        assert_eq!(0, deadline.remaining_ms());
+        assert!(matches!(
+            deadline.check("get"),
+            Err(HolochainP2pError::DeadlineExceeded { budget_ms: 10, .. })
+        ));
+    }
+
+    #[test]
+    fn deadline_from_timeout_ms_uses_default() {
+        let deadline = Deadline::from_timeout_ms(None, Duration::from_secs(60));
+        assert_eq!(Duration::from_secs(60), deadline.budget());
+    }
+
+    #[tokio::test]
+    async fn deadline_until_is_capped_by_expiry() {
+        let max = Duration::from_secs(60);
+        let soon = (Timestamp::now() + Duration::from_secs(5)).unwrap();
+        assert!(Deadline::until(soon, max).budget() <= Duration::from_secs(5));
+
+        let later = (Timestamp::now() + Duration::from_secs(600)).unwrap();
+        assert_eq!(max, Deadline::until(later, max).budget());
+
+        let past = Timestamp::from_micros(0);
+        assert!(Deadline::until(past, max).is_expired());
+    }
+}
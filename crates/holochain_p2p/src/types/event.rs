@@ -107,6 +107,27 @@ pub enum CountersigningSessionNegotiationMessage {
     EnzymePush(Box<ChainOp>),
 }
 
+/// A remote signal to an agent which was offline when it was sent, held until
+/// the agent comes online.
+///
+/// The signal is signed again with a fresh nonce when it's delivered, because
+/// the nonce it was sent with may have expired by then.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct QueuedRemoteSignal {
+    /// The agent which sent the signal.
+    pub from_agent: AgentPubKey,
+    /// The zome the signal is delivered to.
+    pub zome_name: ZomeName,
+    /// The function the signal is delivered to.
+    pub fn_name: FunctionName,
+    /// The capability secret the signal is delivered with.
+    pub cap: Option<CapSecret>,
+    /// The signal.
+    pub payload: ExternIO,
+    /// When the signal stops being held.
+    pub expires_at: Timestamp,
+}
+
 /// Multiple ways to fetch op data
 #[derive(Debug, derive_more::From)]
 pub enum FetchOpDataQuery {
@@ -184,6 +205,20 @@ ghost_actor::ghost_chan! {
             expires_at: Timestamp,
         ) -> SerializedBytes;
 
+        /// A remote node is attempting to make a read-only query on us.
+        fn remote_query(
+            dna_hash: DnaHash,
+            from_agent: AgentPubKey,
+            signature: Signature,
+            to_agent: AgentPubKey,
+            zome_name: ZomeName,
+            fn_name: FunctionName,
+            cap_secret: Option<CapSecret>,
+            payload: ExternIO,
+            nonce: Nonce256Bits,
+            expires_at: Timestamp,
+        ) -> SerializedBytes;
+
         /// A remote node is publishing data in a range we claim to be holding.
         fn publish(
             dna_hash: DnaHash,
@@ -223,6 +258,13 @@ ghost_actor::ghost_chan! {
             query: WireLinkQuery,
         ) -> CountLinksResponse;
 
+        /// A remote node is asking how many ops we hold for a basis.
+        fn probe_basis(
+            dna_hash: DnaHash,
+            to_agent: AgentPubKey,
+            basis: OpBasis,
+        ) -> ProbeBasisResponse;
+
         /// A remote node is requesting agent activity from us.
         fn get_agent_activity(
             dna_hash: DnaHash,
@@ -240,6 +282,23 @@ ghost_actor::ghost_chan! {
             filter: holochain_zome_types::chain::ChainFilter,
         ) -> MustGetAgentActivityResponse;
 
+        /// A remote node is requesting the actions at one position of an agent's chain.
+        fn get_action_by_seq(
+            dna_hash: DnaHash,
+            to_agent: AgentPubKey,
+            author: AgentPubKey,
+            action_seq: u32,
+        ) -> ActionBySeqResponse;
+
+        /// A remote node is requesting a page of the entries of one type created by an agent.
+        fn get_entries_by_author(
+            dna_hash: DnaHash,
+            to_agent: AgentPubKey,
+            author: AgentPubKey,
+            entry_type: EntryType,
+            page: EntriesPage,
+        ) -> EntriesByAuthorResponse;
+
         /// A remote node has sent us a validation receipt.
         fn validation_receipts_received(
             dna_hash: DnaHash,
@@ -281,6 +340,35 @@ ghost_actor::ghost_chan! {
             to_agent: AgentPubKey,
             message: CountersigningSessionNegotiationMessage,
         ) -> ();
+
+        /// A request a cell made was not sent to a peer because the cell's egress
+        /// policy doesn't allow it, and should be recorded for auditing.
+        fn egress_blocked(
+            dna_hash: DnaHash,
+            from_agent: AgentPubKey,
+            to_agent: AgentPubKey,
+            request: String,
+        ) -> ();
+
+        /// A remote signal to an agent which is offline should be held until the
+        /// agent comes online. At most `max_len` signals are held, and expired
+        /// signals are removed to make room for more once that many are held.
+        /// Returns whether the signal was queued rather than dropped, and how
+        /// many expired signals were removed.
+        fn queue_remote_signal(
+            dna_hash: DnaHash,
+            to_agent: AgentPubKey,
+            signal: QueuedRemoteSignal,
+            max_len: u32,
+        ) -> (bool, usize);
+
+        /// An agent has come online, so the remote signals held for it should be
+        /// taken to be delivered. Returns the unexpired signals, oldest first,
+        /// and how many of the signals held for the agent had expired.
+        fn take_queued_remote_signals(
+            dna_hash: DnaHash,
+            to_agent: AgentPubKey,
+        ) -> (Vec<QueuedRemoteSignal>, usize);
     }
 }
 
@@ -289,12 +377,16 @@ macro_rules! match_p2p_evt {
     ($h:ident => |$i:ident| { $($t:tt)* }, { $($t2:tt)* }) => {
         match $h {
             HolochainP2pEvent::CallRemote { $i, .. } => { $($t)* }
+            HolochainP2pEvent::RemoteQuery { $i, .. } => { $($t)* }
             HolochainP2pEvent::Get { $i, .. } => { $($t)* }
             HolochainP2pEvent::GetMeta { $i, .. } => { $($t)* }
             HolochainP2pEvent::GetLinks { $i, .. } => { $($t)* }
             HolochainP2pEvent::CountLinks { $i, .. } => { $($t)* }
+            HolochainP2pEvent::ProbeBasis { $i, .. } => { $($t)* }
             HolochainP2pEvent::GetAgentActivity { $i, .. } => { $($t)* }
             HolochainP2pEvent::MustGetAgentActivity { $i, .. } => { $($t)* }
+            HolochainP2pEvent::GetActionBySeq { $i, .. } => { $($t)* }
+            HolochainP2pEvent::GetEntriesByAuthor { $i, .. } => { $($t)* }
             HolochainP2pEvent::ValidationReceiptsReceived { $i, .. } => { $($t)* }
             HolochainP2pEvent::SignNetworkData { $i, .. } => { $($t)* }
             HolochainP2pEvent::CountersigningSessionNegotiation { $i, .. } => { $($t)* }
@@ -315,6 +407,9 @@ impl HolochainP2pEvent {
             HolochainP2pEvent::QueryGossipAgents { dna_hash, .. } => { dna_hash }
             HolochainP2pEvent::PutAgentInfoSigned { dna_hash, .. } => { dna_hash }
             HolochainP2pEvent::QueryPeerDensity { dna_hash, .. } => { dna_hash }
+            HolochainP2pEvent::EgressBlocked { dna_hash, .. } => { dna_hash }
+            HolochainP2pEvent::QueueRemoteSignal { dna_hash, .. } => { dna_hash }
+            HolochainP2pEvent::TakeQueuedRemoteSignals { dna_hash, .. } => { dna_hash }
         })
     }
 
@@ -329,6 +424,9 @@ impl HolochainP2pEvent {
             HolochainP2pEvent::QueryGossipAgents { .. } => { unimplemented!("There is no single agent target for QueryGossipAgents") },
             HolochainP2pEvent::PutAgentInfoSigned { .. } => { unimplemented!("There is no single agent target for PutAgentInfoSigned") },
             HolochainP2pEvent::QueryPeerDensity { .. } => { unimplemented!() },
+            HolochainP2pEvent::EgressBlocked { .. } => { unimplemented!("There is no local agent target for EgressBlocked") },
+            HolochainP2pEvent::QueueRemoteSignal { .. } => { unimplemented!("There is no local agent target for QueueRemoteSignal") },
+            HolochainP2pEvent::TakeQueuedRemoteSignals { .. } => { unimplemented!("There is no local agent target for TakeQueuedRemoteSignals") },
         })
     }
 }
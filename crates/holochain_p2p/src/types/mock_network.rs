@@ -342,14 +342,18 @@ impl HolochainP2pMockMsg {
             HolochainP2pMockMsg::Wire { msg, .. } => match &msg {
                 crate::wire::WireMessage::CallRemote { .. }
                 | crate::wire::WireMessage::CallRemoteMulti { .. }
+                | crate::wire::WireMessage::RemoteQuery { .. }
                 | crate::wire::WireMessage::ValidationReceipts { .. }
                 | crate::wire::WireMessage::Get { .. }
                 | crate::wire::WireMessage::GetMeta { .. }
                 | crate::wire::WireMessage::GetLinks { .. }
                 | crate::wire::WireMessage::CountLinks { .. }
+                | crate::wire::WireMessage::ProbeBasis { .. }
                 | crate::wire::WireMessage::GetAgentActivity { .. }
                 | crate::wire::WireMessage::PublishCountersign { .. }
-                | crate::wire::WireMessage::MustGetAgentActivity { .. } => next_msg_id().as_req(),
+                | crate::wire::WireMessage::MustGetAgentActivity { .. }
+                | crate::wire::WireMessage::GetActionBySeq { .. }
+                | crate::wire::WireMessage::GetEntriesByAuthor { .. } => next_msg_id().as_req(),
 
                 crate::wire::WireMessage::CountersigningSessionNegotiation { .. } => {
                     MsgId::new_notify()
@@ -372,13 +376,17 @@ impl HolochainP2pMockMsg {
                 let call = match &msg {
                     crate::wire::WireMessage::CallRemote { .. }
                     | crate::wire::WireMessage::CallRemoteMulti { .. }
+                    | crate::wire::WireMessage::RemoteQuery { .. }
                     | crate::wire::WireMessage::ValidationReceipts { .. }
                     | crate::wire::WireMessage::Get { .. }
                     | crate::wire::WireMessage::GetMeta { .. }
                     | crate::wire::WireMessage::GetLinks { .. }
                     | crate::wire::WireMessage::CountLinks { .. }
+                    | crate::wire::WireMessage::ProbeBasis { .. }
                     | crate::wire::WireMessage::GetAgentActivity { .. }
-                    | crate::wire::WireMessage::MustGetAgentActivity { .. } => true,
+                    | crate::wire::WireMessage::MustGetAgentActivity { .. }
+                    | crate::wire::WireMessage::GetActionBySeq { .. }
+                    | crate::wire::WireMessage::GetEntriesByAuthor { .. } => true,
                     crate::wire::WireMessage::PublishCountersign { .. }
                     | crate::wire::WireMessage::CountersigningSessionNegotiation { .. } => false,
                 };
@@ -21,6 +21,134 @@ impl WireDhtOpData {
     }
 }
 
+/// An authority's answer to a [`WireMessage::ProbeBasis`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct ProbeBasisResponse {
+    /// The number of integrated ops the authority holds for the basis.
+    pub op_count: u32,
+}
+
+/// The version of the wire protocol this conductor speaks. It must be bumped
+/// whenever [`WireMessage`] changes in a way that conductors speaking the
+/// previous version can't read.
+pub const WIRE_PROTOCOL_VERSION: u16 = 1;
+
+/// The oldest version of the wire protocol this conductor can still speak.
+/// Version 0 is spoken by conductors from before the handshake, which send
+/// messages without an envelope and support no optional features.
+pub const MIN_WIRE_PROTOCOL_VERSION: u16 = 0;
+
+/// A bitfield of the optional features of the wire protocol which a conductor
+/// supports. Peers exchange their capabilities when they connect, and requests
+/// which need a capability a peer lacks are not sent to it.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(transparent)]
+pub struct WireCapabilities(pub u32);
+
+impl WireCapabilities {
+    /// No optional features.
+    pub const NONE: Self = Self(0);
+    /// Read-only remote queries, with [`WireMessage::RemoteQuery`].
+    pub const REMOTE_QUERY: Self = Self(1 << 0);
+    /// Basis probes, with [`WireMessage::ProbeBasis`].
+    pub const PROBE_BASIS: Self = Self(1 << 1);
+    /// Lookups of actions by author and sequence number, with
+    /// [`WireMessage::GetActionBySeq`].
+    pub const GET_ACTION_BY_SEQ: Self = Self(1 << 2);
+    /// Listings of the entries of one type created by an author, with
+    /// [`WireMessage::GetEntriesByAuthor`].
+    pub const GET_ENTRIES_BY_AUTHOR: Self = Self(1 << 3);
+    /// Every optional feature this conductor supports.
+    pub const ALL: Self = Self::REMOTE_QUERY
+        .union(Self::PROBE_BASIS)
+        .union(Self::GET_ACTION_BY_SEQ)
+        .union(Self::GET_ENTRIES_BY_AUTHOR);
+
+    /// The features in either set.
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// The features in both sets.
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Whether every feature of `other` is in this set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// The wire protocol a conductor speaks, which it sends to each peer in the
+/// preflight handshake as part of its [`NetworkCompatParams`](crate::NetworkCompatParams).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WireProtocol {
+    /// The newest version of the protocol the conductor speaks.
+    pub version: u16,
+    /// The oldest version of the protocol the conductor speaks.
+    pub min_version: u16,
+    /// The optional features the conductor supports.
+    pub capabilities: WireCapabilities,
+}
+
+impl Default for WireProtocol {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+impl WireProtocol {
+    /// The wire protocol this conductor speaks.
+    pub const fn current() -> Self {
+        Self {
+            version: WIRE_PROTOCOL_VERSION,
+            min_version: MIN_WIRE_PROTOCOL_VERSION,
+            capabilities: WireCapabilities::ALL,
+        }
+    }
+
+    /// The wire protocol of a conductor from before the handshake, which
+    /// doesn't say which protocol it speaks.
+    pub const fn legacy() -> Self {
+        Self {
+            version: 0,
+            min_version: 0,
+            capabilities: WireCapabilities::NONE,
+        }
+    }
+
+    /// Agree on the protocol to speak with a peer, which is the newest version
+    /// both speak with the features both support. Returns `None` if there is
+    /// no version both speak.
+    pub fn negotiate(&self, theirs: &Self) -> Option<Self> {
+        let version = self.version.min(theirs.version);
+        let min_version = self.min_version.max(theirs.min_version);
+        (version >= min_version).then(|| Self {
+            version,
+            min_version,
+            capabilities: self.capabilities.intersection(theirs.capabilities),
+        })
+    }
+}
+
+/// A [`WireMessage`] as it is sent, tagged with the version of the wire
+/// protocol it was encoded with.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct WireEnvelope<M> {
+    version: u16,
+    message: M,
+}
+
+/// Just the version of a [`WireEnvelope`], which can be read even when the
+/// message can't.
+#[derive(Debug, serde::Deserialize)]
+struct WireEnvelopeVersion {
+    version: u16,
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
 #[serde(tag = "type", content = "content")]
 #[allow(missing_docs)]
@@ -48,6 +176,19 @@ pub enum WireMessage {
         nonce: Box<Nonce256Bits>,
         expires_at: Timestamp,
     },
+    /// A read-only call, which may only target functions granted for queries.
+    RemoteQuery {
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        from_agent: holo_hash::AgentPubKey,
+        signature: Signature,
+        to_agent: AgentPubKey,
+        cap_secret: Option<CapSecret>,
+        #[serde(with = "serde_bytes")]
+        data: Vec<u8>,
+        nonce: Box<Nonce256Bits>,
+        expires_at: Timestamp,
+    },
     ValidationReceipts {
         receipts: ValidationReceiptBundle,
     },
@@ -66,6 +207,9 @@ pub enum WireMessage {
     CountLinks {
         query: WireLinkQuery,
     },
+    ProbeBasis {
+        basis: OpBasis,
+    },
     GetAgentActivity {
         agent: AgentPubKey,
         query: ChainQueryFilter,
@@ -75,6 +219,15 @@ pub enum WireMessage {
         agent: AgentPubKey,
         filter: holochain_zome_types::chain::ChainFilter,
     },
+    GetActionBySeq {
+        agent: AgentPubKey,
+        action_seq: u32,
+    },
+    GetEntriesByAuthor {
+        author: AgentPubKey,
+        entry_type: EntryType,
+        page: EntriesPage,
+    },
     CountersigningSessionNegotiation {
         message: event::CountersigningSessionNegotiationMessage,
     },
@@ -86,12 +239,53 @@ pub enum WireMessage {
 
 #[allow(missing_docs)]
 impl WireMessage {
+    /// Encode for sending.
+    ///
+    /// Messages which need an optional feature are only sent to peers which
+    /// support it, so they go in an envelope tagged with the wire protocol
+    /// version. The rest are sent bare, as conductors speaking version 0
+    /// expect them, so that every peer can read them.
     pub fn encode(&self) -> Result<Vec<u8>, SerializedBytesError> {
-        holochain_serialized_bytes::encode(&self)
+        if self.required_capabilities() == WireCapabilities::NONE {
+            holochain_serialized_bytes::encode(self)
+        } else {
+            holochain_serialized_bytes::encode(&WireEnvelope {
+                version: WIRE_PROTOCOL_VERSION,
+                message: self,
+            })
+        }
     }
 
+    /// Decode a received message, from its envelope if it has one, or bare
+    /// as sent by conductors speaking version 0.
     pub fn decode(data: &[u8]) -> Result<Self, SerializedBytesError> {
-        holochain_serialized_bytes::decode(&data)
+        match holochain_serialized_bytes::decode::<_, WireEnvelope<Self>>(&data) {
+            Ok(envelope) => Ok(envelope.message),
+            Err(err) => match holochain_serialized_bytes::decode(&data) {
+                Ok(message) => Ok(message),
+                Err(_) => match holochain_serialized_bytes::decode(&data) {
+                    Ok(WireEnvelopeVersion { version }) if version > WIRE_PROTOCOL_VERSION => {
+                        Err(SerializedBytesError::Deserialize(format!(
+                            "Can't read a message from wire protocol version {version}, \
+                        this conductor speaks up to version {WIRE_PROTOCOL_VERSION}: {err}"
+                        )))
+                    }
+                    _ => Err(err),
+                },
+            },
+        }
+    }
+
+    /// The optional features of the wire protocol a peer must support to
+    /// handle this message.
+    pub fn required_capabilities(&self) -> WireCapabilities {
+        match self {
+            Self::RemoteQuery { .. } => WireCapabilities::REMOTE_QUERY,
+            Self::ProbeBasis { .. } => WireCapabilities::PROBE_BASIS,
+            Self::GetActionBySeq { .. } => WireCapabilities::GET_ACTION_BY_SEQ,
+            Self::GetEntriesByAuthor { .. } => WireCapabilities::GET_ENTRIES_BY_AUTHOR,
+            _ => WireCapabilities::NONE,
+        }
     }
 
     pub fn publish_countersign(flag: bool, op: DhtOp) -> WireMessage {
@@ -124,6 +318,32 @@ impl WireMessage {
         }
     }
 
+    /// For an outgoing read-only remote query.
+    #[allow(clippy::too_many_arguments)]
+    pub fn remote_query(
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        from_agent: holo_hash::AgentPubKey,
+        signature: Signature,
+        to_agent: holo_hash::AgentPubKey,
+        cap_secret: Option<CapSecret>,
+        payload: ExternIO,
+        nonce: Nonce256Bits,
+        expires_at: Timestamp,
+    ) -> WireMessage {
+        Self::RemoteQuery {
+            zome_name,
+            fn_name,
+            from_agent,
+            to_agent,
+            signature,
+            cap_secret,
+            data: payload.into_vec(),
+            nonce: Box::new(nonce),
+            expires_at,
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn call_remote_multi(
         zome_name: ZomeName,
@@ -170,6 +390,10 @@ impl WireMessage {
         Self::CountLinks { query }
     }
 
+    pub fn probe_basis(basis: OpBasis) -> WireMessage {
+        Self::ProbeBasis { basis }
+    }
+
     pub fn get_agent_activity(
         agent: AgentPubKey,
         query: ChainQueryFilter,
@@ -189,9 +413,121 @@ impl WireMessage {
         Self::MustGetAgentActivity { agent, filter }
     }
 
+    pub fn get_action_by_seq(agent: AgentPubKey, action_seq: u32) -> WireMessage {
+        Self::GetActionBySeq { agent, action_seq }
+    }
+
+    pub fn get_entries_by_author(
+        author: AgentPubKey,
+        entry_type: EntryType,
+        page: EntriesPage,
+    ) -> WireMessage {
+        Self::GetEntriesByAuthor {
+            author,
+            entry_type,
+            page,
+        }
+    }
+
     pub fn countersigning_session_negotiation(
         message: event::CountersigningSessionNegotiationMessage,
     ) -> WireMessage {
         Self::CountersigningSessionNegotiation { message }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiates_newest_common_version_and_shared_capabilities() {
+        let ours = WireProtocol {
+            version: 3,
+            min_version: 2,
+            capabilities: WireCapabilities::REMOTE_QUERY.union(WireCapabilities::PROBE_BASIS),
+        };
+        let theirs = WireProtocol {
+            version: 2,
+            min_version: 1,
+            capabilities: WireCapabilities::PROBE_BASIS,
+        };
+        let agreed = ours.negotiate(&theirs).unwrap();
+        assert_eq!(agreed, theirs.negotiate(&ours).unwrap());
+        assert_eq!(2, agreed.version);
+        assert!(agreed.capabilities.contains(WireCapabilities::PROBE_BASIS));
+        assert!(!agreed.capabilities.contains(WireCapabilities::REMOTE_QUERY));
+
+        assert_eq!(
+            Some(WireProtocol::legacy()),
+            WireProtocol::current().negotiate(&WireProtocol::legacy())
+        );
+    }
+
+    #[test]
+    fn legacy_peers_can_talk_to_upgraded_peers() {
+        let dht_hash: holo_hash::AnyDhtHash = holo_hash::EntryHash::from_raw_36(vec![1; 36]).into();
+        let options = || event::GetOptions {
+            follow_redirects: false,
+            all_live_actions_with_metadata: false,
+            request_type: event::GetRequest::All,
+        };
+
+        // A conductor from before the handshake sends and expects bare messages.
+        let legacy_bytes =
+            holochain_serialized_bytes::encode(&WireMessage::get(dht_hash.clone(), options()))
+                .unwrap();
+        assert!(matches!(
+            WireMessage::decode(&legacy_bytes).unwrap(),
+            WireMessage::Get { dht_hash: hash, .. } if hash == dht_hash
+        ));
+
+        let bytes = WireMessage::get(dht_hash.clone(), options())
+            .encode()
+            .unwrap();
+        assert!(matches!(
+            holochain_serialized_bytes::decode::<_, WireMessage>(&bytes).unwrap(),
+            WireMessage::Get { dht_hash: hash, .. } if hash == dht_hash
+        ));
+
+        // Messages which need a feature a legacy peer lacks aren't sent to it.
+        let protocol = WireProtocol::current()
+            .negotiate(&WireProtocol::legacy())
+            .unwrap();
+        let msg = WireMessage::probe_basis(holo_hash::EntryHash::from_raw_36(vec![2; 36]).into());
+        assert!(!protocol.capabilities.contains(msg.required_capabilities()));
+    }
+
+    #[test]
+    fn messages_round_trip_in_an_envelope() {
+        let msg = WireMessage::get_action_by_seq(AgentPubKey::from_raw_36(vec![0; 36]), 7);
+        let bytes = msg.encode().unwrap();
+        let envelope: WireEnvelope<WireMessage> =
+            holochain_serialized_bytes::decode(&bytes).unwrap();
+        assert_eq!(WIRE_PROTOCOL_VERSION, envelope.version);
+        assert!(matches!(
+            WireMessage::decode(&bytes).unwrap(),
+            WireMessage::GetActionBySeq { action_seq: 7, .. }
+        ));
+    }
+
+    #[test]
+    fn messages_from_newer_versions_name_the_version() {
+        #[derive(Debug, serde::Serialize)]
+        #[serde(tag = "type", content = "content")]
+        enum NewerMessage {
+            NewOp { value: u32 },
+        }
+
+        let bytes = holochain_serialized_bytes::encode(&WireEnvelope {
+            version: WIRE_PROTOCOL_VERSION + 1,
+            message: NewerMessage::NewOp { value: 1 },
+        })
+        .unwrap();
+        let err = WireMessage::decode(&bytes).unwrap_err();
+        assert!(err.to_string().contains(&format!(
+            "wire protocol version {}",
+            WIRE_PROTOCOL_VERSION + 1
+        )));
+    }
+}
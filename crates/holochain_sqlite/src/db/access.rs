@@ -3,11 +3,12 @@ use crate::db::databases::DATABASE_HANDLES;
 use crate::db::guard::{PConnGuard, PTxnGuard};
 use crate::db::kind::{DbKind, DbKindT};
 use crate::db::pool::{
-    initialize_connection, new_connection_pool, num_read_threads, ConnectionPool, DbSyncLevel,
+    initialize_connection, new_connection_pool, ConnectionPool, DbPoolSize, DbSyncLevel,
 };
 use crate::error::{DatabaseError, DatabaseResult};
 use derive_more::Into;
 use holochain_util::log_elapsed;
+use opentelemetry_api::KeyValue;
 use parking_lot::Mutex;
 use rusqlite::*;
 use shrinkwraprs::Shrinkwrap;
@@ -19,7 +20,10 @@ use std::{path::PathBuf, sync::atomic::AtomicUsize};
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tracing::Instrument;
 
-use super::metrics::{create_connection_use_time_metric, create_pool_usage_metric, UseTimeMetric};
+use super::metrics::{
+    create_connection_use_time_metric, create_pool_saturation_metric, create_pool_usage_metric,
+    SaturationMetric, UseTimeMetric,
+};
 
 static ACQUIRE_TIMEOUT_MS: AtomicU64 = AtomicU64::new(10_000);
 static THREAD_ACQUIRE_TIMEOUT_MS: AtomicU64 = AtomicU64::new(30_000);
@@ -81,13 +85,16 @@ pub struct DbRead<Kind: DbKindT> {
     kind: Kind,
     path: PathBuf,
     connection_pool: ConnectionPool,
+    query_pool: ConnectionPool,
     write_semaphore: Arc<Semaphore>,
     read_semaphore: Arc<Semaphore>,
     long_read_semaphore: Arc<Semaphore>,
+    query_semaphore: Arc<Semaphore>,
     statement_trace_fn: Option<fn(&str)>,
     max_readers: usize,
     num_readers: Arc<AtomicUsize>,
     use_time_metric: UseTimeMetric,
+    saturation_metric: SaturationMetric,
 }
 
 impl<Kind: DbKindT> std::fmt::Debug for DbRead<Kind> {
@@ -124,10 +131,38 @@ impl<Kind: DbKindT> DbRead<Kind> {
         F: FnOnce(Transaction) -> Result<R, E> + Send + 'static,
         R: Send + 'static,
     {
-        let mut conn = self
-            .checkout_connection(self.read_semaphore.clone())
+        let conn = self
+            .checkout_connection(self.read_semaphore.clone(), &self.connection_pool, "read")
+            .await?;
+
+        Self::execute_read(conn, f).await
+    }
+
+    /// Execute a read closure on one of the database's dedicated read-only connections.
+    ///
+    /// These connections are reserved for query-heavy workflows, such as fetching the ops
+    /// which are waiting for validation, so that those queries don't contend with the
+    /// connections used for other reads and writes.
+    #[tracing::instrument(skip_all)]
+    pub async fn query_async<E, R, F>(&self, f: F) -> Result<R, E>
+    where
+        E: From<DatabaseError> + Send + 'static,
+        F: FnOnce(Transaction) -> Result<R, E> + Send + 'static,
+        R: Send + 'static,
+    {
+        let conn = self
+            .checkout_connection(self.query_semaphore.clone(), &self.query_pool, "query")
             .await?;
 
+        Self::execute_read(conn, f).await
+    }
+
+    async fn execute_read<E, R, F>(mut conn: PConnGuard, f: F) -> Result<R, E>
+    where
+        E: From<DatabaseError> + Send + 'static,
+        F: FnOnce(Transaction) -> Result<R, E> + Send + 'static,
+        R: Send + 'static,
+    {
         let start = tokio::time::Instant::now();
         let span = tracing::info_span!("spawn_blocking");
 
@@ -147,6 +182,18 @@ impl<Kind: DbKindT> DbRead<Kind> {
             })?.map_err(DatabaseError::from)?
     }
 
+    /// Copy a consistent snapshot of the database to a file with the SQLite
+    /// online backup API. Reads and writes carry on while the copy is taken.
+    pub async fn backup_to(&self, path: &Path) -> DatabaseResult<()> {
+        let conn = self
+            .checkout_connection(self.read_semaphore.clone(), &self.connection_pool, "read")
+            .await?;
+        let path = path.to_owned();
+        tokio::task::spawn_blocking(move || super::snapshot::backup_connection(&conn, &path))
+            .in_current_span()
+            .await?
+    }
+
     /// Intended to be used for transactions that need to be kept open for a longer period of time than just running a
     /// sequence of reads using `read_async`. You should default to `read_async` and only call this if you have a good
     /// reason.
@@ -155,13 +202,22 @@ impl<Kind: DbKindT> DbRead<Kind> {
     #[tracing::instrument(skip_all)]
     pub async fn get_read_txn(&self) -> DatabaseResult<PTxnGuard> {
         let conn = self
-            .checkout_connection(self.long_read_semaphore.clone())
+            .checkout_connection(
+                self.long_read_semaphore.clone(),
+                &self.connection_pool,
+                "long_read",
+            )
             .await?;
         Ok(conn.into())
     }
 
-    #[tracing::instrument(skip(self))]
-    async fn checkout_connection(&self, semaphore: Arc<Semaphore>) -> DatabaseResult<PConnGuard> {
+    #[tracing::instrument(skip(self, connection_pool))]
+    async fn checkout_connection(
+        &self,
+        semaphore: Arc<Semaphore>,
+        connection_pool: &ConnectionPool,
+        pool_name: &'static str,
+    ) -> DatabaseResult<PConnGuard> {
         // TODO: use semaphore for this message
         let waiting = self.num_readers.fetch_add(1, Ordering::Relaxed);
         if waiting > self.max_readers {
@@ -176,11 +232,11 @@ impl<Kind: DbKindT> DbRead<Kind> {
             tracing::trace!("checkout_connection ready to acquire semaphore");
         }
 
-        let permit = acquire_semaphore_permit(semaphore).await?;
+        let permit = self.acquire_permit(semaphore, pool_name).await?;
 
         self.num_readers.fetch_sub(1, Ordering::Relaxed);
 
-        let mut conn = self.get_connection_from_pool()?;
+        let mut conn = self.get_connection_from_pool(connection_pool)?;
         if self.statement_trace_fn.is_some() {
             conn.trace(self.statement_trace_fn);
         }
@@ -188,12 +244,26 @@ impl<Kind: DbKindT> DbRead<Kind> {
         Ok(PConnGuard::new(conn, permit, self.use_time_metric.clone()))
     }
 
+    /// Acquire a permit to use a connection from one of the pools,
+    /// counting the times the pool was saturated.
+    async fn acquire_permit(
+        &self,
+        semaphore: Arc<Semaphore>,
+        pool_name: &'static str,
+    ) -> DatabaseResult<OwnedSemaphorePermit> {
+        if semaphore.available_permits() == 0 {
+            self.saturation_metric
+                .add(1, &[KeyValue::new("pool", pool_name)]);
+        }
+        acquire_semaphore_permit(semaphore).await
+    }
+
     /// Get a connection from the pool.
     /// TODO: We should eventually swap this for an async solution.
     #[tracing::instrument(skip_all)]
-    fn get_connection_from_pool(&self) -> DatabaseResult<PConn> {
+    fn get_connection_from_pool(&self, connection_pool: &ConnectionPool) -> DatabaseResult<PConn> {
         let now = Instant::now();
-        let r = Ok(PConn::new(self.connection_pool.get()?));
+        let r = Ok(PConn::new(connection_pool.get()?));
         let el = now.elapsed();
         if el.as_millis() > 20 {
             // TODO Convert to a metric
@@ -234,9 +304,20 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
         path_prefix: &Path,
         kind: Kind,
         sync_level: DbSyncLevel,
+    ) -> DatabaseResult<Self> {
+        Self::open_with_pool_size(path_prefix, kind, sync_level, DbPoolSize::default())
+    }
+
+    /// Create or open an existing database reference with the given connection pool sizes.
+    /// The pool sizes only apply when the database is opened for the first time.
+    pub fn open_with_pool_size(
+        path_prefix: &Path,
+        kind: Kind,
+        sync_level: DbSyncLevel,
+        pool_size: DbPoolSize,
     ) -> DatabaseResult<Self> {
         DATABASE_HANDLES.get_or_insert(&kind, path_prefix, |kind| {
-            Self::new(Some(path_prefix), kind, sync_level, None)
+            Self::new(Some(path_prefix), kind, sync_level, pool_size, None)
         })
     }
 
@@ -244,6 +325,7 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
         path_prefix: Option<&Path>,
         kind: Kind,
         sync_level: DbSyncLevel,
+        pool_size: DbPoolSize,
         statement_trace_fn: Option<fn(&str)>,
     ) -> DatabaseResult<Self> {
         let path = match path_prefix {
@@ -306,26 +388,77 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
             None => None,
         };
 
+        /*
+         * We want
+         * - read_connections for standard read limit
+         * - long_read_connections for use in long running read transactions, to allow the normal pool to continue to be used
+         * - 1 connection for writing
+         * - query_connections in a separate read-only pool for query-heavy workflows
+         */
+        let read_connections = pool_size.read_connections();
+        let long_read_connections = pool_size.long_read_connections();
+        let query_connections = pool_size.query_connections();
+        let max_cons = read_connections + long_read_connections + 1;
+
         // Now we know the database file is valid we can open a connection pool.
-        let pool = new_connection_pool(path.as_ref().map(|p| p.as_ref()), sync_level);
+        let (pool, query_pool) = match &path {
+            Some(path) => (
+                new_connection_pool(Some(path), sync_level, max_cons, false),
+                new_connection_pool(Some(path), sync_level, query_connections, true),
+            ),
+            // A separate pool would open a separate in-memory database,
+            // so the query connections come from the main pool instead.
+            None => {
+                let pool =
+                    new_connection_pool(None, sync_level, max_cons + query_connections, false);
+                (pool.clone(), pool)
+            }
+        };
         let mut conn = pool.get()?;
         // set to faster write-ahead-log mode
         conn.pragma_update(None, "journal_mode", "WAL".to_string())?;
-        crate::table::initialize_database(&mut conn, kind.kind())?;
+        let snapshot = match &path {
+            Some(path) => super::migration::snapshot_before_migration(&conn, path, &kind.kind())?,
+            None => None,
+        };
+        if let Err(e) = crate::table::initialize_database(&mut conn, kind.kind()) {
+            if let (Some(snapshot), Some(path)) = (snapshot, &path) {
+                // Close every connection before putting the snapshot back.
+                drop(conn);
+                drop(pool);
+                super::migration::restore_snapshot(&snapshot, path)?;
+                tracing::error!(
+                    ?snapshot,
+                    ?e,
+                    "database {} failed to migrate and was restored from its snapshot",
+                    kind.kind()
+                );
+            }
+            return Err(e.into());
+        }
+        if let (Some(_), Some(path)) = (&snapshot, &path) {
+            if let Err(err) = super::migration::prune_snapshots(path) {
+                tracing::warn!(?err, "failed to remove old pre-migration snapshots");
+            }
+        }
 
         let use_time_metric = create_connection_use_time_metric(kind.kind());
+        let saturation_metric = create_pool_saturation_metric(kind.kind());
 
         let db_read = DbRead {
             write_semaphore: Self::get_write_semaphore(kind.kind()),
-            read_semaphore: Self::get_read_semaphore(kind.kind()),
-            long_read_semaphore: Self::get_long_read_semaphore(kind.kind()),
-            max_readers: num_read_threads() * 2,
+            read_semaphore: Self::get_read_semaphore(kind.kind(), read_connections),
+            long_read_semaphore: Self::get_long_read_semaphore(kind.kind(), long_read_connections),
+            query_semaphore: Self::get_query_semaphore(kind.kind(), query_connections),
+            max_readers: read_connections + long_read_connections,
             num_readers: Arc::new(AtomicUsize::new(0)),
             kind: kind.clone(),
             path: path.unwrap_or_default(),
             connection_pool: pool,
+            query_pool,
             statement_trace_fn,
             use_time_metric,
+            saturation_metric,
         };
 
         create_pool_usage_metric(
@@ -334,6 +467,7 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
                 db_read.write_semaphore.clone(),
                 db_read.read_semaphore.clone(),
                 db_read.long_read_semaphore.clone(),
+                db_read.query_semaphore.clone(),
             ],
         );
 
@@ -347,9 +481,11 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
         F: FnOnce(&mut Transaction) -> Result<R, E> + Send + 'static,
         R: Send + 'static,
     {
-        let _permit = acquire_semaphore_permit(self.0.write_semaphore.clone()).await?;
+        let _permit = self
+            .acquire_permit(self.0.write_semaphore.clone(), "write")
+            .await?;
 
-        let mut conn = self.get_connection_from_pool()?;
+        let mut conn = self.get_connection_from_pool(&self.connection_pool)?;
 
         let start = tokio::time::Instant::now();
         let span = tracing::info_span!("spawn_blocking");
@@ -378,6 +514,10 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
         self.read_semaphore.available_permits()
     }
 
+    pub fn available_query_reader_count(&self) -> usize {
+        self.query_semaphore.available_permits()
+    }
+
     fn get_write_semaphore(kind: DbKind) -> Arc<Semaphore> {
         static MAP: once_cell::sync::Lazy<Mutex<HashMap<DbKind, Arc<Semaphore>>>> =
             once_cell::sync::Lazy::new(|| Mutex::new(HashMap::new()));
@@ -387,21 +527,30 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
             .clone()
     }
 
-    fn get_read_semaphore(kind: DbKind) -> Arc<Semaphore> {
+    fn get_read_semaphore(kind: DbKind, permits: usize) -> Arc<Semaphore> {
+        static MAP: once_cell::sync::Lazy<Mutex<HashMap<DbKind, Arc<Semaphore>>>> =
+            once_cell::sync::Lazy::new(|| Mutex::new(HashMap::new()));
+        MAP.lock()
+            .entry(kind)
+            .or_insert_with(|| Arc::new(Semaphore::new(permits)))
+            .clone()
+    }
+
+    fn get_long_read_semaphore(kind: DbKind, permits: usize) -> Arc<Semaphore> {
         static MAP: once_cell::sync::Lazy<Mutex<HashMap<DbKind, Arc<Semaphore>>>> =
             once_cell::sync::Lazy::new(|| Mutex::new(HashMap::new()));
         MAP.lock()
             .entry(kind)
-            .or_insert_with(|| Arc::new(Semaphore::new(num_read_threads())))
+            .or_insert_with(|| Arc::new(Semaphore::new(permits)))
             .clone()
     }
 
-    fn get_long_read_semaphore(kind: DbKind) -> Arc<Semaphore> {
+    fn get_query_semaphore(kind: DbKind, permits: usize) -> Arc<Semaphore> {
         static MAP: once_cell::sync::Lazy<Mutex<HashMap<DbKind, Arc<Semaphore>>>> =
             once_cell::sync::Lazy::new(|| Mutex::new(HashMap::new()));
         MAP.lock()
             .entry(kind)
-            .or_insert_with(|| Arc::new(Semaphore::new(num_read_threads())))
+            .or_insert_with(|| Arc::new(Semaphore::new(permits)))
             .clone()
     }
 
@@ -422,12 +571,24 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
     /// connection pool, useful for testing.
     #[cfg(any(test, feature = "test_utils"))]
     pub fn test(path: &Path, kind: Kind) -> DatabaseResult<Self> {
-        Self::new(Some(path), kind, DbSyncLevel::default(), None)
+        Self::new(
+            Some(path),
+            kind,
+            DbSyncLevel::default(),
+            DbPoolSize::default(),
+            None,
+        )
     }
 
     #[cfg(any(test, feature = "test_utils"))]
     pub fn test_in_mem(kind: Kind) -> DatabaseResult<Self> {
-        Self::new(None, kind, DbSyncLevel::default(), None)
+        Self::new(
+            None,
+            kind,
+            DbSyncLevel::default(),
+            DbPoolSize::default(),
+            None,
+        )
     }
 
     #[cfg(all(any(test, feature = "test_utils"), not(loom)))]
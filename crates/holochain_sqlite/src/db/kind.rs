@@ -1,3 +1,4 @@
+use holo_hash::encode::holo_hash_encode;
 use holo_hash::DnaHash;
 use holochain_zome_types::cell::CellId;
 use kitsune_p2p_bin_data::KitsuneSpace;
@@ -87,7 +88,11 @@ impl DbKindT for DbKindAuthored {
     fn filename_inner(&self) -> PathBuf {
         [
             "authored",
-            &format!("authored-{}-{}", self.0.dna_hash(), self.0.agent_pubkey()),
+            &format!(
+                "authored-{}-{}",
+                holo_hash_encode(self.0.dna_hash().get_raw_39()),
+                holo_hash_encode(self.0.agent_pubkey().get_raw_39())
+            ),
         ]
         .iter()
         .collect()
@@ -112,7 +117,12 @@ impl DbKindT for DbKindDht {
     }
 
     fn filename_inner(&self) -> PathBuf {
-        ["dht", &format!("dht-{}", self.0)].iter().collect()
+        [
+            "dht",
+            &format!("dht-{}", holo_hash_encode(self.0.get_raw_39())),
+        ]
+        .iter()
+        .collect()
     }
 
     fn if_corrupt_wipe(&self) -> bool {
@@ -137,7 +147,12 @@ impl DbKindT for DbKindCache {
     }
 
     fn filename_inner(&self) -> PathBuf {
-        ["cache", &format!("cache-{}", self.0)].iter().collect()
+        [
+            "cache",
+            &format!("cache-{}", holo_hash_encode(self.0.get_raw_39())),
+        ]
+        .iter()
+        .collect()
     }
 
     fn if_corrupt_wipe(&self) -> bool {
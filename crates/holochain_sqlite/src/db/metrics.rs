@@ -4,6 +4,7 @@ use std::sync::Arc;
 use tokio::sync::Semaphore;
 
 pub type UseTimeMetric = Histogram<f64>;
+pub type SaturationMetric = Counter<u64>;
 
 pub fn create_pool_usage_metric(kind: DbKind, db_semaphores: Vec<Arc<Semaphore>>) {
     let meter = meter_with_version(
@@ -55,6 +56,23 @@ pub fn create_connection_use_time_metric(kind: DbKind) -> UseTimeMetric {
     .init()
 }
 
+pub fn create_pool_saturation_metric(kind: DbKind) -> SaturationMetric {
+    meter_with_version(
+        "hc.db",
+        None::<&'static str>,
+        None::<&'static str>,
+        Some(vec![
+            KeyValue::new("kind", db_kind_name(kind.clone())),
+            KeyValue::new("id", format!("{}", kind)),
+        ]),
+    )
+    .u64_counter("hc.db.pool.saturation")
+    .with_description(
+        "The number of times a connection was requested while every connection of the pool was in use",
+    )
+    .init()
+}
+
 fn db_kind_name(kind: DbKind) -> String {
     match kind {
         DbKind::Authored(_) => "authored",
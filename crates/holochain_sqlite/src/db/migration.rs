@@ -0,0 +1,227 @@
+//! Snapshots and dry-run reports for schema migrations.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use rusqlite::Connection;
+use rusqlite::OpenFlags;
+
+use super::pool::initialize_connection;
+use super::DbKind;
+use super::DbSyncLevel;
+use crate::error::DatabaseResult;
+use crate::schema::PendingMigrations;
+
+/// The number of pre-migration snapshots of each database which are kept.
+/// Every snapshot is a full copy of its database, and only the most recent is
+/// needed to go back to the version of Holochain which ran before the migration.
+const SNAPSHOTS_KEPT: usize = 1;
+
+/// The file a database is copied to before migrating it from `from_version`.
+fn snapshot_path(path: &Path, from_version: usize) -> PathBuf {
+    let mut snapshot = path.as_os_str().to_owned();
+    snapshot.push(format!(".pre-migration-v{}", from_version));
+    snapshot.into()
+}
+
+/// If the database at `path` has migrations to run, copy it to a snapshot
+/// file next to it and return the snapshot's path.
+/// New databases have nothing worth keeping, so they aren't snapshotted.
+pub(super) fn snapshot_before_migration(
+    conn: &Connection,
+    path: &Path,
+    db_kind: &DbKind,
+) -> DatabaseResult<Option<PathBuf>> {
+    let Some(schema) = crate::table::schema_for_kind(db_kind) else {
+        return Ok(None);
+    };
+    let pending = schema.pending(conn)?;
+    if pending.is_empty() || pending.current_version == 0 {
+        return Ok(None);
+    }
+
+    // Move everything from the write-ahead log into the database file
+    // so that copying the file captures all of the data.
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+    let snapshot = snapshot_path(path, pending.current_version);
+    std::fs::copy(path, &snapshot)?;
+    tracing::info!(
+        ?snapshot,
+        "database {} snapshotted before migrating from {} to {}",
+        db_kind,
+        pending.current_version,
+        pending.latest_version,
+    );
+    Ok(Some(snapshot))
+}
+
+/// Delete all but the [`SNAPSHOTS_KEPT`] most recent pre-migration snapshots
+/// of the database at `path`, so that snapshots don't pile up over upgrades.
+pub(super) fn prune_snapshots(path: &Path) -> DatabaseResult<()> {
+    let (Some(dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return Ok(());
+    };
+    let prefix = format!("{}.pre-migration-v", file_name.to_string_lossy());
+    let mut snapshots = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if let Some(version) = name
+            .strip_prefix(&prefix)
+            .and_then(|version| version.parse::<usize>().ok())
+        {
+            snapshots.push((version, entry.path()));
+        }
+    }
+    snapshots.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+    for (_, snapshot) in snapshots.into_iter().skip(SNAPSHOTS_KEPT) {
+        std::fs::remove_file(&snapshot)?;
+        tracing::info!(?snapshot, "removed an old pre-migration snapshot");
+    }
+    Ok(())
+}
+
+/// Put a snapshot taken by [`snapshot_before_migration`] back in place.
+/// All connections to the database must be closed.
+pub(super) fn restore_snapshot(snapshot: &Path, path: &Path) -> DatabaseResult<()> {
+    for suffix in ["-wal", "-shm"] {
+        let mut file = path.as_os_str().to_owned();
+        file.push(suffix);
+        match std::fs::remove_file(PathBuf::from(file)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => (),
+        }
+    }
+    std::fs::copy(snapshot, path)?;
+    Ok(())
+}
+
+/// Find every database under the databases root which has schema
+/// migrations waiting to run the next time it is opened.
+/// No database is changed.
+pub fn pending_migrations_in(root: &Path) -> DatabaseResult<Vec<(PathBuf, PendingMigrations)>> {
+    let mut out = Vec::new();
+    if !root.is_dir() {
+        return Ok(out);
+    }
+    for dir in std::fs::read_dir(root)? {
+        let dir = dir?;
+        if !dir.file_type()?.is_dir() {
+            continue;
+        }
+        let dir_name = dir.file_name().to_string_lossy().into_owned();
+        for file in std::fs::read_dir(dir.path())? {
+            let path = file?.path();
+            if path.extension() != Some(std::ffi::OsStr::new("sqlite3")) {
+                continue;
+            }
+            let file_name = path
+                .file_name()
+                .map(|f| f.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let Some(schema) = crate::table::schema_for_file_name(&dir_name, &file_name) else {
+                continue;
+            };
+            let pending = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+                .and_then(|mut conn| {
+                    initialize_connection(&mut conn, DbSyncLevel::default())?;
+                    schema.pending(&conn)
+                });
+            match pending {
+                Ok(pending) if !pending.is_empty() => out.push((path, pending)),
+                Ok(_) => (),
+                Err(err) => {
+                    tracing::warn!(?path, ?err, "Could not check database for migrations")
+                }
+            }
+        }
+    }
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::DbKindConductor;
+    use crate::db::DbKindT;
+    use crate::db::DbKindWasm;
+    use crate::db::DbWrite;
+
+    #[test]
+    fn snapshot_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("db.sqlite3");
+        let mut conn = Connection::open(&path).unwrap();
+        conn.pragma_update(None, "journal_mode", "WAL").unwrap();
+        crate::schema::SCHEMA_CONDUCTOR
+            .initialize(&mut conn, None)
+            .unwrap();
+        conn.pragma_update(None, "user_version", 1).unwrap();
+
+        let snapshot = snapshot_before_migration(&conn, &path, &DbKind::Conductor)
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot_path(&path, 1), snapshot);
+
+        conn.pragma_update(None, "user_version", 2).unwrap();
+        drop(conn);
+        restore_snapshot(&snapshot, &path).unwrap();
+
+        let conn = Connection::open(&path).unwrap();
+        let user_version: u16 = conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(1, user_version);
+    }
+
+    #[test]
+    fn only_the_latest_snapshot_is_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("db.sqlite3");
+        let other = dir.path().join("other.sqlite3");
+        for version in [1, 3, 12] {
+            std::fs::write(snapshot_path(&path, version), []).unwrap();
+        }
+        std::fs::write(snapshot_path(&other, 1), []).unwrap();
+
+        prune_snapshots(&path).unwrap();
+
+        assert!(snapshot_path(&path, 12).exists());
+        assert!(!snapshot_path(&path, 3).exists());
+        assert!(!snapshot_path(&path, 1).exists());
+        // Snapshots of other databases are left alone.
+        assert!(snapshot_path(&other, 1).exists());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn report_pending_migrations() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(pending_migrations_in(&dir.path().join("missing"))
+            .unwrap()
+            .is_empty());
+
+        DbWrite::test(dir.path(), DbKindConductor).unwrap();
+        DbWrite::test(dir.path(), DbKindWasm).unwrap();
+        assert!(pending_migrations_in(dir.path()).unwrap().is_empty());
+
+        // Make the conductor database look like it was made by an older version.
+        let path = dir.path().join(DbKindConductor.filename());
+        Connection::open(&path)
+            .unwrap()
+            .pragma_update(None, "user_version", 1)
+            .unwrap();
+
+        let pending = pending_migrations_in(dir.path()).unwrap();
+        assert_eq!(
+            vec![(
+                path,
+                PendingMigrations {
+                    current_version: 1,
+                    latest_version: 5,
+                }
+            )],
+            pending
+        );
+    }
+}
@@ -6,7 +6,9 @@ mod databases;
 mod guard;
 mod kind;
 mod metrics;
+mod migration;
 mod pool;
+mod snapshot;
 
 #[cfg(all(test, not(loom)))]
 mod tests;
@@ -17,7 +19,9 @@ pub use kind::{
     DbKind, DbKindAuthored, DbKindCache, DbKindConductor, DbKindDht, DbKindOp, DbKindP2pAgents,
     DbKindP2pMetrics, DbKindT, DbKindWasm,
 };
-pub use pool::{DbSyncLevel, DbSyncStrategy};
+pub use migration::pending_migrations_in;
+pub use pool::{DbPoolSize, DbPoolSizes, DbSyncLevel, DbSyncStrategy};
+pub use snapshot::restore_databases_from_snapshot;
 
 #[cfg(feature = "test_utils")]
 pub use access::set_acquire_timeout;
@@ -1,3 +1,4 @@
+use crate::db::kind::DbKind;
 use crate::functions::add_custom_functions;
 use holochain_serialized_bytes::prelude::*;
 use once_cell::sync::Lazy;
@@ -52,27 +53,101 @@ pub enum DbSyncStrategy {
     Resilient,
 }
 
+/// The number of connections in the pools of a database.
+/// Any size which isn't set uses its default.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct DbPoolSize {
+    /// The number of connections for short read transactions.
+    /// Defaults to [`num_read_threads`].
+    pub read_connections: Option<usize>,
+    /// The number of connections for read transactions which are held open
+    /// for a longer time, such as cascade queries across databases.
+    /// Defaults to [`num_read_threads`].
+    pub long_read_connections: Option<usize>,
+    /// The number of dedicated read-only connections for query-heavy workflows
+    /// such as validation, so they don't contend with the other reads and writes.
+    /// Defaults to half of [`num_read_threads`].
+    pub query_connections: Option<usize>,
+}
+
+impl DbPoolSize {
+    /// The number of connections for short read transactions.
+    pub fn read_connections(&self) -> usize {
+        self.read_connections
+            .unwrap_or_else(num_read_threads)
+            .max(1)
+    }
+
+    /// The number of connections for long read transactions.
+    pub fn long_read_connections(&self) -> usize {
+        self.long_read_connections
+            .unwrap_or_else(num_read_threads)
+            .max(1)
+    }
+
+    /// The number of dedicated read-only connections for query-heavy workflows.
+    pub fn query_connections(&self) -> usize {
+        self.query_connections
+            .unwrap_or_else(|| num_read_threads() / 2)
+            .max(1)
+    }
+}
+
+/// The sizes of the connection pools for each kind of database.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct DbPoolSizes {
+    /// Pool sizes for the databases of the chains authored by local agents.
+    pub authored: DbPoolSize,
+    /// Pool sizes for the DHT databases.
+    pub dht: DbPoolSize,
+    /// Pool sizes for the cache databases.
+    pub cache: DbPoolSize,
+    /// Pool sizes for the conductor database.
+    pub conductor: DbPoolSize,
+    /// Pool sizes for the wasm database.
+    pub wasm: DbPoolSize,
+    /// Pool sizes for the peer store databases.
+    pub p2p_agent_store: DbPoolSize,
+    /// Pool sizes for the network metrics databases.
+    pub p2p_metrics: DbPoolSize,
+}
+
+impl DbPoolSizes {
+    /// Get the pool sizes configured for a kind of database.
+    pub fn for_kind(&self, kind: &DbKind) -> DbPoolSize {
+        match kind {
+            DbKind::Authored(_) => self.authored,
+            DbKind::Dht(_) => self.dht,
+            DbKind::Cache(_) => self.cache,
+            DbKind::Conductor => self.conductor,
+            DbKind::Wasm => self.wasm,
+            DbKind::P2pAgentStore(_) => self.p2p_agent_store,
+            DbKind::P2pMetrics(_) => self.p2p_metrics,
+            #[cfg(feature = "test_utils")]
+            DbKind::Test(_) => DbPoolSize::default(),
+        }
+    }
+}
+
 pub(super) fn new_connection_pool(
     path: Option<&Path>,
     synchronous_level: DbSyncLevel,
+    max_cons: usize,
+    query_only: bool,
 ) -> ConnectionPool {
     use r2d2_sqlite::SqliteConnectionManager;
     let manager = match path {
         Some(path) => SqliteConnectionManager::file(path),
         None => SqliteConnectionManager::memory(),
     };
-    let customizer = Box::new(ConnCustomizer { synchronous_level });
-
-    /*
-     * We want
-     * - num_read_threads connections for standard read limit
-     * - num_read_threads for use in long running read transactions, to allow the normal pool to continue to be used
-     * - 1 connection for writing
-     */
-    let max_cons = num_read_threads() * 2 + 1;
+    let customizer = Box::new(ConnCustomizer {
+        synchronous_level,
+        query_only,
+    });
 
     r2d2::Pool::builder()
-        // Only up to 20 connections at a time
         .max_size(max_cons as u32)
         // Never maintain idle connections
         .min_idle(Some(0))
@@ -90,11 +165,16 @@ pub(super) fn new_connection_pool(
 #[derive(Debug)]
 struct ConnCustomizer {
     synchronous_level: DbSyncLevel,
+    query_only: bool,
 }
 
 impl r2d2::CustomizeConnection<Connection, rusqlite::Error> for ConnCustomizer {
     fn on_acquire(&self, conn: &mut Connection) -> Result<(), rusqlite::Error> {
         initialize_connection(conn, self.synchronous_level)?;
+        if self.query_only {
+            // Refuse any changes to the database on this connection.
+            conn.pragma_update(None, "query_only", true)?;
+        }
         Ok(())
     }
 }
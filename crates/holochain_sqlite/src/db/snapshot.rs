@@ -0,0 +1,143 @@
+//! Hot snapshots of the databases of a running conductor,
+//! and restoring the databases from a snapshot before startup.
+//!
+//! A snapshot directory has the same layout as the databases root,
+//! so the file for each database is found under [`DbKindT::filename`](super::DbKindT::filename).
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use rusqlite::backup::Backup;
+use rusqlite::Connection;
+
+use super::migration::restore_snapshot;
+use super::pool::initialize_connection;
+use super::DbSyncLevel;
+use crate::error::DatabaseError;
+use crate::error::DatabaseResult;
+
+/// Copy a consistent snapshot of the database behind `conn` to `path`
+/// with the SQLite online backup API.
+pub(super) fn backup_connection(conn: &Connection, path: &Path) -> DatabaseResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    // Write to a temporary file first so that a snapshot which fails part
+    // way through never leaves a partial database behind.
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    let mut dest = Connection::open(&partial)?;
+    initialize_connection(&mut dest, DbSyncLevel::default())?;
+    {
+        // Copy every page in a single step, so the snapshot is read in one
+        // transaction. The databases use write-ahead logging, so writers
+        // carry on while the copy is taken.
+        let backup = Backup::new(conn, &mut dest)?;
+        backup.run_to_completion(-1, Duration::ZERO, None)?;
+    }
+    drop(dest);
+    std::fs::rename(&partial, path)?;
+    Ok(())
+}
+
+/// Copy every database in a snapshot directory into the databases root,
+/// replacing the databases which are already there.
+/// This must be done before any database is opened.
+///
+/// Returns the paths of the databases which were restored.
+pub fn restore_databases_from_snapshot(
+    snapshot_dir: &Path,
+    root: &Path,
+) -> DatabaseResult<Vec<PathBuf>> {
+    if !snapshot_dir.is_dir() {
+        return Err(DatabaseError::DatabaseMissing(snapshot_dir.to_owned()));
+    }
+    let mut restored = Vec::new();
+    for dir in std::fs::read_dir(snapshot_dir)? {
+        let dir = dir?;
+        if !dir.file_type()?.is_dir() {
+            continue;
+        }
+        for file in std::fs::read_dir(dir.path())? {
+            let snapshot = file?.path();
+            if snapshot.extension() != Some(std::ffi::OsStr::new("sqlite3")) {
+                continue;
+            }
+            let Some(file_name) = snapshot.file_name() else {
+                continue;
+            };
+            let target_dir = root.join(dir.file_name());
+            std::fs::create_dir_all(&target_dir)?;
+            let path = target_dir.join(file_name);
+            restore_snapshot(&snapshot, &path)?;
+            restored.push(path);
+        }
+    }
+    restored.sort();
+    tracing::info!(
+        ?snapshot_dir,
+        "restored {} databases from snapshot",
+        restored.len()
+    );
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::DbKindConductor;
+    use crate::db::DbKindT;
+    use crate::db::DbWrite;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn snapshot_and_restore_databases() {
+        let root = tempfile::tempdir().unwrap();
+        let snapshot_dir = tempfile::tempdir().unwrap();
+        let restore_root = tempfile::tempdir().unwrap();
+
+        let db = DbWrite::test(root.path(), DbKindConductor).unwrap();
+        db.write_async(|txn| -> DatabaseResult<()> {
+            txn.execute(
+                "INSERT INTO ConductorState (id, blob) VALUES (1, x'01')",
+                [],
+            )?;
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        let path = snapshot_dir.path().join(DbKindConductor.filename());
+        db.backup_to(&path).await.unwrap();
+
+        // Writes after the snapshot are not in it.
+        db.write_async(|txn| -> DatabaseResult<()> {
+            txn.execute("UPDATE ConductorState SET blob = x'02' WHERE id = 1", [])?;
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        let restored =
+            restore_databases_from_snapshot(snapshot_dir.path(), restore_root.path()).unwrap();
+        let restored_path = restore_root.path().join(DbKindConductor.filename());
+        assert_eq!(vec![restored_path.clone()], restored);
+
+        let mut conn = Connection::open(&restored_path).unwrap();
+        initialize_connection(&mut conn, DbSyncLevel::default()).unwrap();
+        let blob: Vec<u8> = conn
+            .query_row("SELECT blob FROM ConductorState WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(vec![1], blob);
+
+        assert!(restore_databases_from_snapshot(
+            &snapshot_dir.path().join("missing"),
+            restore_root.path()
+        )
+        .is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use tempfile::TempDir;
 
-use crate::prelude::{DatabaseResult, DbKindWasm};
+use crate::prelude::{DatabaseError, DatabaseResult, DbKindWasm};
 
 use super::pool::num_read_threads;
 use super::DbWrite;
@@ -135,3 +135,42 @@ async fn db_connection_doesnt_timeout() {
     // without taking permits.
     assert!(result.is_err());
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn query_connections_are_read_only() {
+    let td = TempDir::new().unwrap();
+    let db = DbWrite::test(td.path(), DbKindWasm).unwrap();
+
+    db.write_async(|txn| {
+        txn.execute(
+            "INSERT INTO Wasm (hash, blob) VALUES(?, ?)",
+            [vec![0], vec![0]],
+        )?;
+        DatabaseResult::Ok(())
+    })
+    .await
+    .unwrap();
+
+    // The query connections see what was written through the write connection.
+    let count: usize = db
+        .query_async(|txn| {
+            DatabaseResult::Ok(
+                txn.query_row("SELECT COUNT(rowid) FROM Wasm", [], |row| row.get(0))?,
+            )
+        })
+        .await
+        .unwrap();
+    assert_eq!(1, count);
+
+    // But they refuse to make any changes.
+    let result = db
+        .query_async(|txn| {
+            txn.execute(
+                "INSERT INTO Wasm (hash, blob) VALUES(?, ?)",
+                [vec![1], vec![1]],
+            )?;
+            DatabaseResult::Ok(())
+        })
+        .await;
+    assert!(matches!(result, Err(DatabaseError::SqliteError(_))));
+}
@@ -24,3 +24,18 @@ pub fn nonce_already_seen(
         .optional()?
         .is_some())
 }
+
+/// The latest nonce witnessed for each agent which hasn't expired by `now`,
+/// as the agent and the time the nonce expires, soonest expiry first.
+pub fn nonce_windows(
+    txn: &Transaction<'_>,
+    now: Timestamp,
+) -> DatabaseResult<Vec<(AgentPubKey, Timestamp)>> {
+    let mut statement = txn.prepare(sql_conductor::SELECT_NONCE_WINDOWS)?;
+    let windows = statement
+        .query_map(named_params! {":now": now}, |row| {
+            Ok((row.get("agent")?, row.get("expires")?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(windows)
+}
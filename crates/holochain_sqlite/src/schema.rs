@@ -12,6 +12,11 @@
 //! Note that there is code in `build.rs` which fails the build if any schema or migration
 //! file has a change according to `git diff`. This will hopefully help prevent accidental
 //! modification of schemas, which should never be committed.
+//!
+//! Every migration which is run is recorded in the `MigrationHistory` table of the
+//! database it was run on. Before an existing database file is migrated, a snapshot
+//! of it is taken next to it, which is restored if the migration fails.
+//! [`Schema::pending`] reports the migrations that would run without running them.
 
 use once_cell::sync::Lazy;
 use rusqlite::{Connection, Transaction};
@@ -29,6 +34,66 @@ pub static SCHEMA_CELL: Lazy<Schema> = Lazy::new(|| Schema {
             forward: include_str!("sql/cell/schema/2-up.sql").into(),
             _schema: include_str!("sql/cell/schema/2.sql").into(),
         },
+        M {
+            forward: include_str!("sql/cell/schema/3-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/3.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/4-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/4.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/5-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/5.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/6-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/6.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/7-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/7.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/8-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/8.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/9-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/9.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/10-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/10.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/11-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/11.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/12-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/12.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/13-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/13.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/14-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/14.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/15-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/15.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/16-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/16.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/17-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/17.sql").into(),
+        },
     ],
 });
 
@@ -39,6 +104,22 @@ pub static SCHEMA_CONDUCTOR: Lazy<Schema> = Lazy::new(|| Schema {
             forward: include_str!("sql/conductor/schema/1.sql").into(),
             _schema: "".into(),
         },
+        M {
+            forward: include_str!("sql/conductor/schema/2.sql").into(),
+            _schema: "".into(),
+        },
+        M {
+            forward: include_str!("sql/conductor/schema/3.sql").into(),
+            _schema: "".into(),
+        },
+        M {
+            forward: include_str!("sql/conductor/schema/4.sql").into(),
+            _schema: "".into(),
+        },
+        M {
+            forward: include_str!("sql/conductor/schema/5.sql").into(),
+            _schema: "".into(),
+        },
     ],
 });
 
@@ -54,11 +135,43 @@ pub static SCHEMA_P2P_METRICS: Lazy<Schema> = Lazy::new(|| Schema {
     migrations: vec![M::initial(include_str!("sql/p2p_metrics/schema/0.sql"))],
 });
 
+/// Records each migration as it is applied.
+const MIGRATION_HISTORY: &str = "CREATE TABLE IF NOT EXISTS MigrationHistory (
+    version INTEGER PRIMARY KEY,
+    applied_at INTEGER NOT NULL
+);";
+
 pub struct Schema {
     migrations: Vec<Migration>,
 }
 
+/// The migrations a database needs to be brought up to date with its [`Schema`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PendingMigrations {
+    /// The number of migrations which have already been applied.
+    pub current_version: usize,
+    /// The number of migrations in the schema.
+    pub latest_version: usize,
+}
+
+impl PendingMigrations {
+    /// Whether the database is already up to date.
+    pub fn is_empty(&self) -> bool {
+        self.current_version >= self.latest_version
+    }
+}
+
 impl Schema {
+    /// Report which migrations [`Schema::initialize`] would run,
+    /// without changing the database.
+    pub fn pending(&self, conn: &Connection) -> rusqlite::Result<PendingMigrations> {
+        let user_version: u16 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        Ok(PendingMigrations {
+            current_version: user_version as usize,
+            latest_version: self.migrations.len(),
+        })
+    }
+
     /// Determine if any database migrations need to run, and run them if so.
     /// The decision is based on the difference between this Schema's
     /// current_index and the user_version pragma value in the database itself.
@@ -79,6 +192,11 @@ impl Schema {
         match migrations_applied.cmp(&(num_migrations)) {
             std::cmp::Ordering::Less => {
                 let mut txn = conn.transaction()?;
+                txn.execute_batch(MIGRATION_HISTORY)?;
+                let applied_at = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_millis() as i64)
+                    .unwrap_or_default();
 
                 // run forward migrations
                 for v in migrations_applied..num_migrations {
@@ -86,6 +204,10 @@ impl Schema {
                     // set the DB user_version so that next time we don't run
                     // the same migration
                     txn.pragma_update(None, "user_version", v + 1)?;
+                    txn.execute(
+                        "INSERT OR REPLACE INTO MigrationHistory (version, applied_at) VALUES (?, ?)",
+                        (v + 1, applied_at),
+                    )?;
                 }
                 txn.commit()?;
                 tracing::info!(
@@ -205,4 +327,137 @@ mod tests {
             1
         );
     }
+
+    #[test]
+    fn test_migrations_pending_and_history() {
+        let mut schema = Schema {
+            migrations: vec![M::initial("CREATE TABLE Numbers (num INTEGER);")],
+        };
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        assert_eq!(
+            PendingMigrations {
+                current_version: 0,
+                latest_version: 1
+            },
+            schema.pending(&conn).unwrap()
+        );
+
+        schema.initialize(&mut conn, None).unwrap();
+        assert!(schema.pending(&conn).unwrap().is_empty());
+
+        schema.migrations.push(M {
+            forward: "CREATE TABLE Names (name TEXT);".into(),
+            _schema: "n/a".into(),
+        });
+        let pending = schema.pending(&conn).unwrap();
+        assert_eq!(1, pending.current_version);
+        assert_eq!(2, pending.latest_version);
+        // Reporting doesn't run anything.
+        assert!(conn
+            .execute("INSERT INTO Names (name) VALUES ('Mike')", ())
+            .is_err());
+
+        schema.initialize(&mut conn, None).unwrap();
+        let versions = conn
+            .prepare("SELECT version FROM MigrationHistory ORDER BY version")
+            .unwrap()
+            .query_map([], |row| row.get::<_, usize>(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(vec![1, 2], versions);
+    }
+
+    #[test]
+    fn test_failed_migration_is_rolled_back() {
+        let mut schema = Schema {
+            migrations: vec![M::initial("CREATE TABLE Numbers (num INTEGER);")],
+        };
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        schema.initialize(&mut conn, None).unwrap();
+
+        schema.migrations.push(M {
+            forward: "CREATE TABLE Names (name TEXT); This is not sql;".into(),
+            _schema: "n/a".into(),
+        });
+        assert!(schema.initialize(&mut conn, None).is_err());
+
+        assert_eq!(1, schema.pending(&conn).unwrap().current_version);
+        assert!(conn
+            .execute("INSERT INTO Names (name) VALUES ('Mike')", ())
+            .is_err());
+    }
+
+    #[test]
+    fn test_cell_migration_stores_each_validation_provenance_once() {
+        let mut schema = Schema {
+            migrations: SCHEMA_CELL.migrations[..15].to_vec(),
+        };
+        let mut conn = Connection::open_in_memory().unwrap();
+        schema.initialize(&mut conn, None).unwrap();
+        for (hash, provenance) in [(1, Some(7)), (2, Some(7)), (3, None)] {
+            conn.execute(
+                "INSERT INTO DhtOp (hash, type, basis_hash, action_hash, require_receipt,
+                storage_center_loc, authored_timestamp, op_order, validation_provenance)
+                VALUES (?, 'StoreRecord', x'00', x'00', 0, 0, 0, '', ?)",
+                (vec![hash as u8], provenance.map(|p: u8| vec![p])),
+            )
+            .unwrap();
+        }
+
+        schema.migrations = SCHEMA_CELL.migrations.clone();
+        schema.initialize(&mut conn, None).unwrap();
+
+        let provenances: usize = conn
+            .query_row("SELECT COUNT(*) FROM ValidationProvenance", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(1, provenances);
+        let blobs = conn
+            .prepare(
+                "SELECT ValidationProvenance.blob FROM DhtOp
+                LEFT JOIN ValidationProvenance ON DhtOp.validation_provenance_id = ValidationProvenance.id
+                ORDER BY DhtOp.hash",
+            )
+            .unwrap()
+            .query_map([], |row| row.get::<_, Option<Vec<u8>>>(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(vec![Some(vec![7]), Some(vec![7]), None], blobs);
+    }
+
+    #[test]
+    fn test_cell_schema_indexes_the_deleted_entry_actions_query() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        SCHEMA_CELL.initialize(&mut conn, None).unwrap();
+        let plan = conn
+            .prepare(&format!(
+                "EXPLAIN QUERY PLAN {}",
+                crate::sql::sql_cell::DELETED_ENTRY_ACTIONS
+            ))
+            .unwrap()
+            .query_map(
+                rusqlite::named_params! {
+                    ":deleted_entry_action": "RegisterDeletedEntryAction",
+                    ":status": 0,
+                },
+                |row| row.get::<_, String>(3),
+            )
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        // Every table is searched through an index rather than scanned.
+        assert!(
+            plan.iter().all(|step| !step.starts_with("SCAN")),
+            "{:#?}",
+            plan
+        );
+        assert!(plan
+            .iter()
+            .any(|step| step.contains("DhtOp_action_hash_idx")));
+    }
 }
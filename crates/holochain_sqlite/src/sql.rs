@@ -15,6 +15,7 @@ pub mod sql_cell {
         include_str!("sql/cell/update_dep_store_entry_basis.sql");
     pub const UPDATE_INTEGRATE_DEP_CREATE_LINK: &str =
         include_str!("sql/cell/update_dep_create_link.sql");
+    pub const INTEGRATED_OPS: &str = include_str!("sql/cell/integrated_ops.sql");
 
     pub const FETCH_OP_HASHES_P1: &str =
         include_str!("sql/cell/fetch_hashes/fetch_op_hashes_p1.sql");
@@ -24,9 +25,12 @@ pub mod sql_cell {
     pub const FETCH_OP_REGION: &str = include_str!("sql/cell/fetch_op_region.sql");
     pub const FETCH_OPS_BY_REGION: &str = include_str!("sql/cell/fetch_ops_by_region.sql");
     pub const FETCH_REGION_OP_HASHES: &str = include_str!("sql/cell/fetch_region_op_hashes.sql");
+    pub const ARC_STORAGE_SIZE: &str = include_str!("sql/cell/arc_storage_size.sql");
 
     pub const FETCH_PUBLISHABLE_OP: &str = include_str!("sql/cell/fetch_publishable_op.sql");
 
+    pub const DELETED_ENTRY_ACTIONS: &str = include_str!("sql/cell/deleted_entry_actions.sql");
+
     pub const SUM_OF_RECEIVED_BYTES_SINCE_TIMESTAMP: &str =
         include_str!("sql/cell/sum_of_received_bytes_since_timestamp.sql");
 
@@ -54,15 +58,36 @@ pub mod sql_cell {
         pub const DHT_OPS_IN_VALIDATION_LIMBO: &str =
             include_str!("sql/cell/state_dump/dht_ops_in_validation_limbo.sql");
         pub const DHT_OPS_ROW_ID: &str = include_str!("sql/cell/state_dump/dht_ops_row_id.sql");
+        pub const DHT_OPS_PAGE: &str = include_str!("sql/cell/state_dump/dht_ops_page.sql");
+    }
+    pub mod queue_stats {
+        pub const SYS_VALIDATION: &str = include_str!("sql/cell/queue_stats/sys_validation.sql");
+        pub const APP_VALIDATION: &str = include_str!("sql/cell/queue_stats/app_validation.sql");
+        pub const INTEGRATION: &str = include_str!("sql/cell/queue_stats/integration.sql");
+        pub const PUBLISH: &str = include_str!("sql/cell/queue_stats/publish.sql");
     }
 }
 
 pub mod sql_conductor {
     pub(crate) const SELECT_NONCE: &str = include_str!("sql/conductor/nonce_already_seen.sql");
     pub const DELETE_EXPIRED_NONCE: &str = include_str!("sql/conductor/delete_expired_nonce.sql");
+    pub const DELETE_ALL_NONCES: &str = include_str!("sql/conductor/delete_all_nonces.sql");
+    pub const SELECT_NONCE_WINDOWS: &str = include_str!("sql/conductor/select_nonce_windows.sql");
+    pub const DELETE_EVICTED_MISSED_SIGNALS: &str =
+        include_str!("sql/conductor/delete_evicted_missed_signals.sql");
     pub const FROM_BLOCK_SPAN_WHERE_OVERLAPPING: &str =
         include_str!("sql/conductor/from_block_span_where_overlapping.sql");
     pub const IS_BLOCKED: &str = include_str!("sql/conductor/is_blocked.sql");
+    pub const SELECT_DUE_DURABLE_CALLS: &str =
+        include_str!("sql/conductor/select_due_durable_calls.sql");
+    pub const SELECT_EVENT_JOURNAL_SINCE: &str =
+        include_str!("sql/conductor/select_event_journal_since.sql");
+    pub const SELECT_MISSED_SIGNAL_BOUNDS: &str =
+        include_str!("sql/conductor/select_missed_signal_bounds.sql");
+    pub const SELECT_MISSED_SIGNALS_SINCE: &str =
+        include_str!("sql/conductor/select_missed_signals_since.sql");
+    pub const SELECT_QUEUED_REMOTE_SIGNALS: &str =
+        include_str!("sql/conductor/select_queued_remote_signals.sql");
     pub const SELECT_VALID_CAP_GRANT_FOR_CAP_SECRET: &str =
         include_str!("sql/conductor/select_valid_cap_grant_for_cap_secret.sql");
     pub const SELECT_VALID_UNRESTRICTED_CAP_GRANT: &str =
@@ -72,6 +97,7 @@ pub mod sql_conductor {
 pub(crate) mod sql_p2p_agent_store {
     pub(crate) const INSERT: &str = include_str!("sql/p2p_agent_store/insert.sql");
     pub(crate) const SELECT_ALL: &str = include_str!("sql/p2p_agent_store/select_all.sql");
+    pub(crate) const SELECT_PAGE: &str = include_str!("sql/p2p_agent_store/select_page.sql");
     pub(crate) const DELETE: &str = include_str!("sql/p2p_agent_store/delete.sql");
     pub(crate) const EXTRAPOLATED_COVERAGE: &str =
         include_str!("sql/p2p_agent_store/extrapolated_coverage.sql");
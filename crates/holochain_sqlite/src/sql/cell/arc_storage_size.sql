@@ -0,0 +1,23 @@
+-- The running totals of the buckets the arc touches, so the arc is rounded out
+-- to whole buckets of 1/4096th of the location space.
+SELECT
+  TOTAL(bytes) AS total_size
+FROM
+  StorageBucket
+WHERE
+  (
+    -- non-wrapping case: everything within the given range
+    :storage_start_loc <= :storage_end_loc
+    AND (
+      bucket >= :storage_start_loc >> 20
+      AND bucket <= :storage_end_loc >> 20
+    )
+  )
+  OR (
+    -- wrapping case: everything *outside* the given range
+    :storage_start_loc > :storage_end_loc
+    AND (
+      bucket <= :storage_end_loc >> 20
+      OR bucket >= :storage_start_loc >> 20
+    )
+  )
@@ -0,0 +1,32 @@
+-- Every action which created an entry that has been deleted,
+-- along with when the first valid delete of that action was integrated.
+-- The delete time is NULL if the action hasn't been deleted.
+SELECT
+  Entry.hash AS entry_hash,
+  Action.blob AS action_blob,
+  (
+    SELECT
+      MIN(DhtOp.when_integrated)
+    FROM
+      Action AS DeleteAction
+      JOIN DhtOp ON DhtOp.action_hash = DeleteAction.hash
+    WHERE
+      DeleteAction.deletes_action_hash = Action.hash
+      -- The unary plus keeps sqlite from looking the ops up by type, which
+      -- would read every delete op, rather than by action hash.
+      AND +DhtOp.type = :deleted_entry_action
+      AND +DhtOp.validation_status = :status
+      AND +DhtOp.when_integrated IS NOT NULL
+  ) AS deleted_at
+FROM
+  Entry
+  JOIN Action ON Action.entry_hash = Entry.hash
+WHERE
+  Entry.hash IN (
+    SELECT
+      deletes_entry_hash
+    FROM
+      Action
+    WHERE
+      deletes_entry_hash IS NOT NULL
+  )
@@ -3,11 +3,15 @@ SELECT
   DhtOp.type,
   Action.blob AS action_blob,
   Action.author AS author,
-  Entry.blob AS entry_blob
+  Entry.blob AS entry_blob,
+  AgentInfo.blob AS agent_info_blob,
+  BlobChunk.blob AS blob_chunk_blob
 FROM
   DhtOp
   LEFT JOIN Action ON DhtOp.action_hash = Action.hash
   LEFT JOIN Entry ON Action.entry_hash = Entry.hash
+  LEFT JOIN AgentInfo ON DhtOp.hash = AgentInfo.hash
+  LEFT JOIN BlobChunk ON DhtOp.hash = BlobChunk.hash
 WHERE
   DhtOp.hash = :hash
   AND DhtOp.withhold_publish IS NULL
\ No newline at end of file
@@ -0,0 +1,17 @@
+-- no-sql-format --
+SELECT
+  Action.blob as action_blob,
+  Action.author as author,
+  Entry.blob as entry_blob,
+  DhtOp.type as dht_type,
+  DhtOp.hash as dht_hash,
+  DhtOp.basis_hash as basis_hash,
+  DhtOp.authored_timestamp as authored_timestamp
+FROM
+  DhtOp
+  JOIN Action ON DhtOp.action_hash = Action.hash
+  LEFT JOIN Entry ON Action.entry_hash = Entry.hash
+WHERE
+  DhtOp.when_integrated = :when_integrated
+ORDER BY
+  DhtOp.op_order ASC
@@ -0,0 +1,8 @@
+SELECT
+  COUNT(hash) AS depth,
+  MIN(COALESCE(when_received, authored_timestamp)) AS oldest
+FROM
+  DhtOp
+WHERE
+  when_integrated IS NULL
+  AND validation_stage = 3
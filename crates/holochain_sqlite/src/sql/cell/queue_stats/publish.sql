@@ -0,0 +1,13 @@
+SELECT
+  COUNT(DhtOp.hash) AS depth,
+  MIN(DhtOp.authored_timestamp) AS oldest
+FROM
+  DhtOp
+  JOIN Action ON DhtOp.action_hash = Action.hash
+WHERE
+  (
+    DhtOp.type != 'StoreEntry'
+    OR Action.private_entry = 0
+  )
+  AND DhtOp.withhold_publish IS NULL
+  AND DhtOp.receipts_complete IS NULL
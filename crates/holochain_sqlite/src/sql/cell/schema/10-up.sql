@@ -0,0 +1,13 @@
+-- no-sql-format --
+
+-- The agent infos held as ops, so the infos published by agents can be found
+-- without a bootstrap service.
+CREATE TABLE IF NOT EXISTS AgentInfo (
+    hash             BLOB           PRIMARY KEY ON CONFLICT IGNORE,
+    agent            BLOB           NOT NULL,
+    signed_at        INTEGER        NOT NULL,
+    expires_at       INTEGER        NOT NULL,
+    blob             BLOB           NOT NULL,
+    FOREIGN KEY(hash) REFERENCES DhtOp(hash) ON DELETE CASCADE
+);
+CREATE INDEX AgentInfo_agent_idx ON AgentInfo ( agent, signed_at );
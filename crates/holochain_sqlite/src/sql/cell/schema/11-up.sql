@@ -0,0 +1,21 @@
+-- no-sql-format --
+
+-- The chunks of blobs held as ops, so large payloads are stored and gossiped
+-- apart from the source chain entries which refer to them.
+CREATE TABLE IF NOT EXISTS BlobChunk (
+    hash             BLOB           PRIMARY KEY ON CONFLICT IGNORE,
+    chunk_hash       BLOB           NOT NULL,
+    author           BLOB           NOT NULL,
+    blob             BLOB           NOT NULL,
+    FOREIGN KEY(hash) REFERENCES DhtOp(hash) ON DELETE CASCADE
+);
+CREATE INDEX BlobChunk_chunk_hash_idx ON BlobChunk ( chunk_hash );
+
+-- The chunks each blob manifest on this node keeps, so a chunk can be dropped
+-- once no manifest refers to it.
+CREATE TABLE IF NOT EXISTS BlobChunkHold (
+    manifest         BLOB           NOT NULL,
+    chunk_hash       BLOB           NOT NULL,
+    PRIMARY KEY (manifest, chunk_hash) ON CONFLICT IGNORE
+);
+CREATE INDEX BlobChunkHold_chunk_hash_idx ON BlobChunkHold ( chunk_hash );
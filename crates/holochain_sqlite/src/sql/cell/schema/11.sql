@@ -0,0 +1,232 @@
+-- no-sql-format --
+
+-- Initial Holochain Cell schema
+
+CREATE TABLE IF NOT EXISTS Entry (
+    hash             BLOB           PRIMARY KEY ON CONFLICT IGNORE,
+    -- might not need this index, let's avoid for now
+    -- type             VARCHAR(64)    NOT NULL,
+
+    blob             BLOB           NOT NULL,
+
+    -- CapClaim / CapGrant
+    tag              TEXT           NULL,
+
+    -- CapClaim
+    grantor          BLOB           NULL,
+    cap_secret       BLOB           NULL,
+
+    -- CapGrant
+    functions        BLOB           NULL,
+    access_type      TEXT           NULL,
+    access_secret    BLOB           NULL,
+    access_assignees BLOB           NULL
+);
+-- CREATE INDEX Entry_type_idx ON Entry ( type );
+
+
+-- TODO: some of the NULL fields can be collapsed,
+--       like between Update and Delete
+CREATE TABLE IF NOT EXISTS Action (
+    hash             BLOB           PRIMARY KEY ON CONFLICT IGNORE,
+    type             TEXT           NOT NULL,
+    author           BLOB           NOT NULL,
+
+    blob             BLOB           NOT NULL,
+    prev_hash        BLOB           NULL,
+
+    -- Actions only
+    seq              INTEGER        NULL,
+
+    -- Create / Update
+    entry_hash       BLOB           NULL,
+    entry_type       TEXT           NULL,  -- The opaque EntryType
+    private_entry    INTEGER        NULL,  -- BOOLEAN
+
+    -- Update
+    original_entry_hash   BLOB      NULL,
+    original_action_hash  BLOB      NULL,
+
+    -- Delete
+    deletes_entry_hash    BLOB      NULL,
+    deletes_action_hash   BLOB      NULL,
+
+    -- CreateLink
+    -- NB: basis_hash can't be foreign key, since it could map to either
+    --     Entry or Action
+    base_hash        BLOB           NULL,
+    zome_index       INTEGER        NULL,
+    link_type        INTEGER        NULL,
+    tag              BLOB           NULL,
+
+    -- DeleteLink
+    create_link_hash    BLOB           NULL,
+
+    -- AgentValidationPkg
+    membrane_proof   BLOB           NULL,
+
+    -- OpenChain / CloseChain
+    prev_dna_hash    BLOB           NULL
+
+    -- We can't have any of these constraint because
+    -- the record authority doesn't get the create link for a remove link. @freesig
+    -- FOREIGN KEY(entry_hash) REFERENCES Entry(hash)
+    -- FOREIGN KEY(original_entry_hash) REFERENCES Entry(hash),
+    -- FOREIGN KEY(original_action_hash) REFERENCES Action(hash),
+    -- FOREIGN KEY(deletes_entry_hash) REFERENCES Entry(hash)
+    -- FOREIGN KEY(deletes_action_hash) REFERENCES Action(hash),
+    -- FOREIGN KEY(create_link_hash) REFERENCES Action(hash)
+);
+CREATE INDEX IF NOT EXISTS Action_type_idx ON Action ( type );
+CREATE INDEX IF NOT EXISTS Action_author ON Action ( author );
+CREATE INDEX IF NOT EXISTS Action_seq_idx ON Action ( seq );
+CREATE INDEX IF NOT EXISTS Action_author_seq_idx ON Action ( author, seq );
+CREATE INDEX IF NOT EXISTS Action_base_type_tag_idx ON Action ( base_hash, zome_index, link_type, tag );
+CREATE INDEX IF NOT EXISTS Action_author_entry_type_idx ON Action ( author, entry_type, seq );
+CREATE INDEX IF NOT EXISTS Action_author_entry_hash_idx ON Action ( author, entry_hash );
+CREATE INDEX IF NOT EXISTS Action_deletes_action_hash_idx ON Action ( deletes_action_hash );
+
+
+-- NB: basis_hash, action_hash, and entry_hash, in general, will have
+--     duplication of data. Could rethink these a bit.
+CREATE TABLE IF NOT EXISTS DhtOp (
+    hash             BLOB           PRIMARY KEY ON CONFLICT IGNORE,
+    type             TEXT           NOT NULL,
+    basis_hash       BLOB           NOT NULL,
+    action_hash      BLOB           NOT NULL,
+    require_receipt  INTEGER        NOT NULL,      -- BOOLEAN
+
+    storage_center_loc          INTEGER   NOT NULL,
+    authored_timestamp       INTEGER   NOT NULL,
+
+    -- This is the order that process ops should result
+    -- in dependencies before dependants.
+    -- See OpOrder.
+    op_order        TEXT           NOT NULL,
+
+    -- If this is null then validation is still in progress.
+    validation_status INTEGER       NULL,
+
+    when_integrated   INTEGER       NULL,          -- DATETIME
+
+    -- Used to withhold ops from publishing for things
+    -- like countersigning.
+    withhold_publish    INTEGER     NULL, -- BOOLEAN
+
+    -- The op has received enough validation receipts.
+    -- This is required as a field because different ops have different EntryTypes,
+    -- which have different numbers of required validation receipts.
+    receipts_complete   INTEGER     NULL,     -- BOOLEAN
+
+    last_publish_time   INTEGER     NULL,   -- UNIX TIMESTAMP SECONDS
+
+    -- 0: Awaiting System Validation Dependencies.
+    -- 1: Successfully System Validated (And ready for app validation).
+    -- 2: Awaiting App Validation Dependencies.
+    -- 3: Awaiting integration.
+    -- Don't need the other stages (pending, awaiting integration) because:
+    -- - pending = validation_stage null && validation_status null.
+    -- We could make this an enum and use a Blob so we can capture which
+    -- deps are being awaited for debugging.
+    validation_stage            INTEGER     NULL,
+    num_validation_attempts     INTEGER     NULL,
+    last_validation_attempt     INTEGER     NULL,
+
+    -- The integration dependency if there is one.
+    dependency          BLOB           NULL,
+
+    -- The zomes and conductor version which validated the op, if it has been validated.
+    -- See ValidationProvenance.
+    validation_provenance   BLOB       NULL,
+
+    -- The hashes the op is waiting for, if it is awaiting sys or app validation dependencies.
+    -- See ValidationStage.
+    missing_dependencies    BLOB       NULL,
+
+
+    FOREIGN KEY(action_hash) REFERENCES Action(hash) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS DhtOp_type_dep_idx ON DhtOp ( type, dependency );
+CREATE INDEX IF NOT EXISTS DhtOp_type_when_int_idx ON DhtOp ( type, when_integrated );
+CREATE INDEX IF NOT EXISTS DhtOp_validation_stage_idx ON DhtOp ( validation_stage, type, dependency );
+CREATE INDEX IF NOT EXISTS DhtOp_stage_type_status_idx ON DhtOp ( validation_stage, type, validation_status);
+CREATE INDEX IF NOT EXISTS DhtOp_validation_status_idx ON DhtOp ( validation_status );
+CREATE INDEX IF NOT EXISTS DhtOp_authored_timestamp_idx ON DhtOp ( authored_timestamp );
+CREATE INDEX IF NOT EXISTS DhtOp_storage_center_loc_idx ON DhtOp ( storage_center_loc );
+CREATE INDEX IF NOT EXISTS DhtOp_action_hash_idx ON DhtOp ( action_hash );
+CREATE INDEX IF NOT EXISTS DhtOp_basis_hash_idx ON DhtOp ( basis_hash );
+
+-- The warrants held as ops, so that the warrants against an agent can be looked up
+CREATE TABLE IF NOT EXISTS Warrant (
+    hash             BLOB           PRIMARY KEY ON CONFLICT IGNORE,
+    author           BLOB           NOT NULL,
+    warrantee        BLOB           NOT NULL,
+    timestamp        INTEGER        NOT NULL,
+    blob             BLOB           NOT NULL,
+    FOREIGN KEY(hash) REFERENCES DhtOp(hash) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS Warrant_warrantee_idx ON Warrant ( warrantee );
+
+-- The ops of warrants held before the Warrant table existed, which were stored
+-- without their warrant. They are kept as evidence but never read as ops.
+CREATE TABLE IF NOT EXISTS QuarantinedWarrantOp (
+    hash               BLOB           PRIMARY KEY ON CONFLICT IGNORE,
+    basis_hash         BLOB           NOT NULL,
+    action_hash        BLOB           NOT NULL,
+    authored_timestamp INTEGER        NOT NULL,
+    validation_status  INTEGER        NULL,
+    when_integrated    INTEGER        NULL
+);
+
+-- The agent infos held as ops, so the infos published by agents can be found
+-- without a bootstrap service.
+CREATE TABLE IF NOT EXISTS AgentInfo (
+    hash             BLOB           PRIMARY KEY ON CONFLICT IGNORE,
+    agent            BLOB           NOT NULL,
+    signed_at        INTEGER        NOT NULL,
+    expires_at       INTEGER        NOT NULL,
+    blob             BLOB           NOT NULL,
+    FOREIGN KEY(hash) REFERENCES DhtOp(hash) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS AgentInfo_agent_idx ON AgentInfo ( agent, signed_at );
+
+CREATE TABLE IF NOT EXISTS BlobChunk (
+    hash             BLOB           PRIMARY KEY ON CONFLICT IGNORE,
+    chunk_hash       BLOB           NOT NULL,
+    author           BLOB           NOT NULL,
+    blob             BLOB           NOT NULL,
+    FOREIGN KEY(hash) REFERENCES DhtOp(hash) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS BlobChunk_chunk_hash_idx ON BlobChunk ( chunk_hash );
+
+CREATE TABLE IF NOT EXISTS BlobChunkHold (
+    manifest         BLOB           NOT NULL,
+    chunk_hash       BLOB           NOT NULL,
+    PRIMARY KEY (manifest, chunk_hash) ON CONFLICT IGNORE
+);
+CREATE INDEX IF NOT EXISTS BlobChunkHold_chunk_hash_idx ON BlobChunkHold ( chunk_hash );
+
+CREATE TABLE IF NOT EXISTS ValidationReceipt (
+    hash            BLOB           PRIMARY KEY ON CONFLICT IGNORE,
+    op_hash         BLOB           NOT NULL,
+    blob            BLOB           NOT NULL,
+    FOREIGN KEY(op_hash) REFERENCES DhtOp(hash) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS ChainLock (
+    lock BLOB PRIMARY KEY ON CONFLICT ROLLBACK,
+    author BLOB NOT NULL,
+    -- The expiration time of the lock as a Timestamp (microseconds)
+    expires_at_timestamp INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS ScheduledFunctions (
+    author BLOB NOT NULL,
+    zome_name TEXT NOT NULL,
+    scheduled_fn TEXT NOT NULL,
+    maybe_schedule BLOB NOT NULL,
+    start INTEGER NOT NULL,
+    end INTEGER NOT NULL,
+    ephemeral BOOLEAN NOT NULL,
+    PRIMARY KEY (zome_name, scheduled_fn, author) ON CONFLICT ROLLBACK
+);
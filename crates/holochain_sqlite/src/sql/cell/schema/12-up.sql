@@ -0,0 +1,16 @@
+-- no-sql-format --
+
+-- The fields of the payloads carried in link tags, so get_links can filter
+-- links on them without decoding every tag. Links integrated before this
+-- migration aren't indexed, which only affects links created by development
+-- builds, since payload tags are new with this migration.
+CREATE TABLE IF NOT EXISTS LinkTagField (
+    action_hash      BLOB           NOT NULL,
+    name             TEXT           NOT NULL,
+    kind             INTEGER        NOT NULL,
+    -- BLOB affinity, so values are compared as they were stored.
+    value            BLOB           NOT NULL,
+    PRIMARY KEY (action_hash, name) ON CONFLICT IGNORE,
+    FOREIGN KEY(action_hash) REFERENCES Action(hash) ON DELETE CASCADE
+);
+CREATE INDEX LinkTagField_name_idx ON LinkTagField ( name, kind, value );
@@ -0,0 +1,7 @@
+-- no-sql-format --
+
+-- The authors whose source chains are frozen for investigation, which can't
+-- be written to until they are unfrozen.
+CREATE TABLE IF NOT EXISTS ChainFreeze (
+    author BLOB PRIMARY KEY ON CONFLICT IGNORE
+);
@@ -0,0 +1,6 @@
+-- no-sql-format --
+
+-- When each op was stored, so the age of the ops waiting in the workflow
+-- queues can be measured from when they arrived rather than when they were
+-- authored. Ops stored before this migration have no time.
+ALTER TABLE DhtOp ADD COLUMN when_received INTEGER NULL;
@@ -0,0 +1,22 @@
+-- no-sql-format --
+
+-- Each distinct provenance is stored once and ops refer to it, rather than
+-- every validated op holding its own copy of the same blob.
+CREATE TABLE IF NOT EXISTS ValidationProvenance (
+    id      INTEGER     PRIMARY KEY,
+    -- The msgpack encoded ValidationProvenance.
+    blob    BLOB        NOT NULL UNIQUE ON CONFLICT IGNORE
+);
+
+ALTER TABLE DhtOp ADD COLUMN validation_provenance_id INTEGER NULL REFERENCES ValidationProvenance(id);
+
+INSERT INTO ValidationProvenance (blob)
+SELECT DISTINCT validation_provenance FROM DhtOp WHERE validation_provenance IS NOT NULL;
+
+UPDATE DhtOp
+SET validation_provenance_id = (
+    SELECT id FROM ValidationProvenance WHERE blob = DhtOp.validation_provenance
+)
+WHERE validation_provenance IS NOT NULL;
+
+ALTER TABLE DhtOp DROP COLUMN validation_provenance;
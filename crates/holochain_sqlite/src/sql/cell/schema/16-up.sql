@@ -0,0 +1,52 @@
+-- no-sql-format --
+
+-- The bytes of op data each op holds, as counted for storage arcs: its action,
+-- and its entry for ops which hold the entry itself.
+ALTER TABLE DhtOp ADD COLUMN stored_bytes INTEGER NULL;
+
+-- A running total of the bytes of op data stored in each 1/4096th of the
+-- location space, by the storage center of the ops, so the data stored within
+-- an arc can be summed without reading every op.
+CREATE TABLE IF NOT EXISTS StorageBucket (
+    bucket  INTEGER     PRIMARY KEY,
+    bytes   INTEGER     NOT NULL
+);
+
+UPDATE DhtOp
+SET stored_bytes = COALESCE((SELECT LENGTH(blob) FROM Action WHERE hash = DhtOp.action_hash), 0)
+    + CASE
+        WHEN DhtOp.type IN ('StoreEntry', 'StoreRecord') THEN COALESCE((
+            SELECT LENGTH(Entry.blob) FROM Action JOIN Entry ON Action.entry_hash = Entry.hash
+            WHERE Action.hash = DhtOp.action_hash
+        ), 0)
+        ELSE 0
+    END;
+
+INSERT INTO StorageBucket (bucket, bytes)
+SELECT storage_center_loc >> 20, SUM(stored_bytes) FROM DhtOp GROUP BY storage_center_loc >> 20;
+
+-- Ops are inserted after their action and entry.
+CREATE TRIGGER IF NOT EXISTS DhtOp_stored_bytes_insert AFTER INSERT ON DhtOp
+BEGIN
+    UPDATE DhtOp
+    SET stored_bytes = COALESCE((SELECT LENGTH(blob) FROM Action WHERE hash = NEW.action_hash), 0)
+        + CASE
+            WHEN NEW.type IN ('StoreEntry', 'StoreRecord') THEN COALESCE((
+                SELECT LENGTH(Entry.blob) FROM Action JOIN Entry ON Action.entry_hash = Entry.hash
+                WHERE Action.hash = NEW.action_hash
+            ), 0)
+            ELSE 0
+        END
+    WHERE hash = NEW.hash;
+
+    INSERT INTO StorageBucket (bucket, bytes)
+    VALUES (NEW.storage_center_loc >> 20, (SELECT stored_bytes FROM DhtOp WHERE hash = NEW.hash))
+    ON CONFLICT (bucket) DO UPDATE SET bytes = bytes + excluded.bytes;
+END;
+
+CREATE TRIGGER IF NOT EXISTS DhtOp_stored_bytes_delete AFTER DELETE ON DhtOp
+BEGIN
+    UPDATE StorageBucket
+    SET bytes = bytes - COALESCE(OLD.stored_bytes, 0)
+    WHERE bucket = OLD.storage_center_loc >> 20;
+END;
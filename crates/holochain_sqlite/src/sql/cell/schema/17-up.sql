@@ -0,0 +1,6 @@
+-- no-sql-format --
+
+-- The entry purge job looks up the actions which created deleted entries, so
+-- index the deleted entries and the actions by the entry they create.
+CREATE INDEX IF NOT EXISTS Action_deletes_entry_hash_idx ON Action ( deletes_entry_hash ) WHERE deletes_entry_hash IS NOT NULL;
+CREATE INDEX IF NOT EXISTS Action_entry_hash_idx ON Action ( entry_hash );
@@ -0,0 +1 @@
+CREATE INDEX Action_author_seq_idx ON Action (author, seq);
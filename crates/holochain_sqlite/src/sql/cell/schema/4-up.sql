@@ -0,0 +1,62 @@
+CREATE TABLE IF NOT EXISTS Warrant (
+  hash BLOB PRIMARY KEY ON CONFLICT IGNORE,
+  author BLOB NOT NULL,
+  warrantee BLOB NOT NULL,
+  timestamp INTEGER NOT NULL,
+  blob BLOB NOT NULL,
+  FOREIGN KEY(hash) REFERENCES DhtOp(hash) ON DELETE CASCADE
+);
+CREATE INDEX Warrant_warrantee_idx ON Warrant (warrantee);
+-- Warrant ops held before this migration can't be backfilled into the Warrant
+-- table. Only their DhtOp row was stored: the warrant, its author and its
+-- signature were never written to any table, so there is nothing to copy from.
+-- Without an Action or Warrant row they can't be read back or served to a peer.
+--
+-- Their DhtOp rows are moved to QuarantinedWarrantOp rather than deleted, so the
+-- evidence that this node held a warrant against an agent is kept. Removing them
+-- from DhtOp lets this node fetch the warrants again from any peer which holds
+-- them in full.
+CREATE TABLE IF NOT EXISTS QuarantinedWarrantOp (
+  hash BLOB PRIMARY KEY ON CONFLICT IGNORE,
+  basis_hash BLOB NOT NULL,
+  action_hash BLOB NOT NULL,
+  authored_timestamp INTEGER NOT NULL,
+  validation_status INTEGER NULL,
+  when_integrated INTEGER NULL
+);
+INSERT INTO
+  QuarantinedWarrantOp (
+    hash,
+    basis_hash,
+    action_hash,
+    authored_timestamp,
+    validation_status,
+    when_integrated
+  )
+SELECT
+  hash,
+  basis_hash,
+  action_hash,
+  authored_timestamp,
+  validation_status,
+  when_integrated
+FROM
+  DhtOp
+WHERE
+  type = 'ChainIntegrityWarrant'
+  AND hash NOT IN (
+    SELECT
+      hash
+    FROM
+      Warrant
+  );
+DELETE FROM
+  DhtOp
+WHERE
+  type = 'ChainIntegrityWarrant'
+  AND hash NOT IN (
+    SELECT
+      hash
+    FROM
+      Warrant
+  );
@@ -0,0 +1,4 @@
+-- no-sql-format --
+
+-- The zomes and conductor version which validated the op, as a msgpack blob.
+ALTER TABLE DhtOp ADD COLUMN validation_provenance BLOB NULL;
@@ -0,0 +1,5 @@
+-- no-sql-format --
+
+-- Supports get links queries filtered by a tag range. Tag prefix filters
+-- compare the hex of the tag, so they only use the base and type columns.
+CREATE INDEX Action_base_type_tag_idx ON Action ( base_hash, zome_index, link_type, tag );
@@ -0,0 +1,5 @@
+-- no-sql-format --
+
+-- Supports finding an author's live creates of an entry without reading their whole chain.
+CREATE INDEX Action_author_entry_hash_idx ON Action ( author, entry_hash );
+CREATE INDEX Action_deletes_action_hash_idx ON Action ( deletes_action_hash );
@@ -0,0 +1,4 @@
+-- no-sql-format --
+
+-- Supports listing the entries of one type created by an author.
+CREATE INDEX Action_author_entry_type_idx ON Action ( author, entry_type, seq );
@@ -0,0 +1,4 @@
+-- no-sql-format --
+
+-- The hashes an op in validation limbo is waiting for, as a msgpack blob.
+ALTER TABLE DhtOp ADD COLUMN missing_dependencies BLOB NULL;
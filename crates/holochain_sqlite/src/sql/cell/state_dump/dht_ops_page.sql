@@ -0,0 +1,22 @@
+-- no-sql-format --
+SELECT
+  Action.blob as action_blob,
+  Entry.blob as entry_blob,
+  DhtOp.type as dht_type,
+  DhtOp.hash as dht_hash,
+  DhtOp.validation_status as validation_status,
+  DhtOp.validation_stage as validation_stage,
+  DhtOp.when_integrated as when_integrated,
+  ValidationProvenance.blob as validation_provenance,
+  DhtOp.rowid as rowid
+FROM
+  DhtOp
+  JOIN Action ON DhtOp.action_hash = Action.hash
+  LEFT JOIN Entry ON Action.entry_hash = Entry.hash
+  LEFT JOIN ValidationProvenance ON DhtOp.validation_provenance_id = ValidationProvenance.id
+WHERE
+  DhtOp.rowid > :cursor
+ORDER BY
+  DhtOp.rowid ASC
+LIMIT
+  :limit
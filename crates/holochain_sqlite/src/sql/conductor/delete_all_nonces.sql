@@ -0,0 +1,2 @@
+DELETE FROM
+  nonce
\ No newline at end of file
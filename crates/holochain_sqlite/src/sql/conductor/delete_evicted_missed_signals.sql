@@ -0,0 +1,5 @@
+DELETE FROM
+  MissedSignal
+WHERE
+  app_id = :app_id
+  AND seq <= :evict_through
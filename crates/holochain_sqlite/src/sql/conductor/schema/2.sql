@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS EventJournal (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  -- literal integer from Timestamp in rust
+  timestamp INTEGER NOT NULL,
+  blob BLOB NOT NULL
+);
+CREATE INDEX IF NOT EXISTS event_journal_timestamp_idx ON EventJournal(timestamp);
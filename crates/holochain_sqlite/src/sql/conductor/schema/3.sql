@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS MissedSignal (
+  app_id TEXT NOT NULL,
+  -- position of the signal among the signals persisted for the app
+  seq INTEGER NOT NULL,
+  blob BLOB NOT NULL,
+  PRIMARY KEY (app_id, seq)
+);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS DurableCallOutbox (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  -- literal integers from Timestamp in rust
+  expires_at INTEGER NOT NULL,
+  next_attempt_at INTEGER NOT NULL,
+  attempts INTEGER NOT NULL,
+  blob BLOB NOT NULL
+);
+CREATE INDEX IF NOT EXISTS durable_call_outbox_next_attempt_idx ON DurableCallOutbox(next_attempt_at);
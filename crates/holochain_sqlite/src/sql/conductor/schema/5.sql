@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS RemoteSignalQueue (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  dna_hash BLOB NOT NULL,
+  to_agent BLOB NOT NULL,
+  -- literal integers from Timestamp in rust
+  expires_at INTEGER NOT NULL,
+  blob BLOB NOT NULL
+);
+CREATE INDEX IF NOT EXISTS remote_signal_queue_to_agent_idx ON RemoteSignalQueue(dna_hash, to_agent);
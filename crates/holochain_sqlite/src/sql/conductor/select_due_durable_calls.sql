@@ -0,0 +1,12 @@
+SELECT
+  id,
+  expires_at,
+  attempts,
+  blob
+FROM
+  DurableCallOutbox
+WHERE
+  next_attempt_at <= :now
+ORDER BY
+  next_attempt_at,
+  id
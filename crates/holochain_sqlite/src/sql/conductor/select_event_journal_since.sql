@@ -0,0 +1,10 @@
+SELECT
+  id,
+  timestamp,
+  blob
+FROM
+  EventJournal
+WHERE
+  timestamp >= :since
+ORDER BY
+  id
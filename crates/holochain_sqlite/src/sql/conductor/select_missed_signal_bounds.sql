@@ -0,0 +1,7 @@
+SELECT
+  MIN(seq) AS oldest,
+  MAX(seq) AS latest
+FROM
+  MissedSignal
+WHERE
+  app_id = :app_id
@@ -0,0 +1,10 @@
+SELECT
+  seq,
+  blob
+FROM
+  MissedSignal
+WHERE
+  app_id = :app_id
+  AND seq > :since
+ORDER BY
+  seq
@@ -0,0 +1,10 @@
+-- the latest nonce witnessed for each agent, while it hasn't expired
+SELECT
+  agent,
+  expires
+FROM
+  nonce
+WHERE
+  expires > :now
+ORDER BY
+  expires
\ No newline at end of file
@@ -0,0 +1,9 @@
+SELECT
+  blob
+FROM
+  RemoteSignalQueue
+WHERE
+  dna_hash = :dna_hash
+  AND to_agent = :to_agent
+ORDER BY
+  id
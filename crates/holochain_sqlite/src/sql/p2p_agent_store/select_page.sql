@@ -0,0 +1,12 @@
+-- select one page of the table, in insertion order
+SELECT
+  rowid,
+  encoded
+FROM
+  p2p_agent_store
+WHERE
+  rowid > :cursor
+ORDER BY
+  rowid ASC
+LIMIT
+  :limit
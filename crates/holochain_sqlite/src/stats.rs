@@ -6,6 +6,13 @@ pub fn get_size_on_disk(txn: Transaction) -> Result<usize, DatabaseError> {
         .map_err(DatabaseError::SqliteError)
 }
 
+/// The size of the database file, without scanning its pages.
+pub fn get_file_size(txn: Transaction) -> Result<usize, DatabaseError> {
+    let page_count: usize = txn.pragma_query_value(None, "page_count", |r| r.get(0))?;
+    let page_size: usize = txn.pragma_query_value(None, "page_size", |r| r.get(0))?;
+    Ok(page_count * page_size)
+}
+
 pub fn get_used_size(txn: Transaction) -> Result<usize, DatabaseError> {
     txn.query_row("select sum(pgsize - unused) from dbstat", (), |r| r.get(0))
         .map_err(DatabaseError::SqliteError)
@@ -2,7 +2,7 @@ mod p2p_agent_store;
 mod p2p_metrics;
 
 pub use p2p_agent_store::{
-    p2p_prune, p2p_put, p2p_put_all, p2p_put_single, AsP2pStateReadExt, AsP2pStateTxExt,
-    AsP2pStateWriteExt,
+    p2p_prune, p2p_prune_stale, p2p_put, p2p_put_all, p2p_put_single, AsP2pStateReadExt,
+    AsP2pStateTxExt, AsP2pStateWriteExt,
 };
 pub use p2p_metrics::AsP2pMetricStoreTxExt;
@@ -69,6 +69,19 @@ pub trait AsP2pStateReadExt {
     /// List all AgentInfoSigned records within a space in the p2p_agent_store
     async fn p2p_list_agents(&self) -> DatabaseResult<Vec<AgentInfoSigned>>;
 
+    /// List all AgentInfoSigned records within a space in the p2p_agent_store,
+    /// including inactive ones which advertise no urls.
+    async fn p2p_list_all_agents(&self) -> DatabaseResult<Vec<AgentInfoSigned>>;
+
+    /// List up to `limit` AgentInfoSigned records stored after the `cursor` rowid,
+    /// along with their rowids, in the order they were stored.
+    /// Replacing a record moves it to the end.
+    async fn p2p_list_agents_page(
+        &self,
+        cursor: u64,
+        limit: u32,
+    ) -> DatabaseResult<Vec<(u64, AgentInfoSigned)>>;
+
     /// Count agent records within a space in the p2p_agent_store
     async fn p2p_count_agents(&self) -> DatabaseResult<u32>;
 
@@ -198,6 +211,10 @@ impl AgentStore {
             .collect())
     }
 
+    fn get_all_with_inactive(&self) -> DatabaseResult<Vec<AgentInfoSigned>> {
+        Ok(self.0.lock().values().cloned().collect())
+    }
+
     fn count(&self) -> DatabaseResult<u32> {
         Ok(self.0.lock().len() as u32)
     }
@@ -463,6 +480,41 @@ pub async fn p2p_prune(
     Ok(())
 }
 
+/// Remove AgentInfoSigned records from the p2p_store which had expired by
+/// `expired_by_ms`, or by now if that is earlier, so unexpired records are
+/// never removed. Records which advertise no urls are the tombstones of agents
+/// which left the space, and are kept until they expire like any other record
+/// so that the agent's older infos aren't gossiped back in.
+///
+/// Returns the agents which were removed.
+#[tracing::instrument(skip_all)]
+pub async fn p2p_prune_stale(
+    db: &DbWrite<DbKindP2pAgents>,
+    expired_by_ms: u64,
+) -> DatabaseResult<Vec<Arc<KitsuneAgent>>> {
+    let space = db.kind().0.clone();
+    db.write_async(move |txn| {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let expired_by_ms = expired_by_ms.min(now);
+
+        let stale: Vec<_> = cache_get(space.clone(), &*txn)?
+            .get_all_with_inactive()?
+            .into_iter()
+            .filter(|info| info.expires_at_ms <= expired_by_ms)
+            .map(|info| info.agent.clone())
+            .collect();
+        for agent in &stale {
+            txn.p2p_remove_agent(space.clone(), agent)?;
+        }
+
+        DatabaseResult::Ok(stale)
+    })
+    .await
+}
+
 #[async_trait::async_trait]
 impl AsP2pStateReadExt for DbRead<DbKindP2pAgents> {
     async fn p2p_get_agent(&self, agent: &KitsuneAgent) -> DatabaseResult<Option<AgentInfoSigned>> {
@@ -473,6 +525,32 @@ impl AsP2pStateReadExt for DbRead<DbKindP2pAgents> {
         cache_get_async(self).await?.get_all()
     }
 
+    async fn p2p_list_all_agents(&self) -> DatabaseResult<Vec<AgentInfoSigned>> {
+        cache_get_async(self).await?.get_all_with_inactive()
+    }
+
+    async fn p2p_list_agents_page(
+        &self,
+        cursor: u64,
+        limit: u32,
+    ) -> DatabaseResult<Vec<(u64, AgentInfoSigned)>> {
+        self.read_async(move |txn| {
+            let mut stmt = txn
+                .prepare(sql_p2p_agent_store::SELECT_PAGE)
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
+            let page = stmt
+                .query_map(named_params! { ":cursor": cursor, ":limit": limit }, |r| {
+                    let rowid: u64 = r.get(0)?;
+                    let signed = AgentInfoSigned::decode(r.get_ref(1)?.as_blob()?)
+                        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
+                    Ok((rowid, signed))
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            DatabaseResult::Ok(page)
+        })
+        .await
+    }
+
     async fn p2p_count_agents(&self) -> DatabaseResult<u32> {
         cache_get_async(self).await?.count()
     }
@@ -260,3 +260,97 @@ async fn test_p2p_agent_store_gossip_query_sanity() {
     // clean up temp dir
     tmp_dir.close().unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_p2p_agent_store_list_page() {
+    let tmp_dir = tempfile::Builder::new()
+        .prefix("p2p_agent_store_list_page")
+        .tempdir()
+        .unwrap();
+
+    let space = rand_space();
+
+    let db = DbWrite::test(tmp_dir.path(), DbKindP2pAgents(space.clone())).unwrap();
+
+    for _ in 0..7 {
+        rand_insert(&db, &space, &rand_agent(), true).await;
+    }
+
+    // page through the store three at a time
+    let mut cursor = 0;
+    let mut pages = Vec::new();
+    loop {
+        let page = db.p2p_list_agents_page(cursor, 3).await.unwrap();
+        if page.is_empty() {
+            break;
+        }
+        assert!(page.iter().all(|(rowid, _)| *rowid > cursor));
+        cursor = page.last().unwrap().0;
+        pages.push(page.len());
+    }
+    assert_eq!(vec![3, 3, 1], pages);
+
+    // clean up temp dir
+    tmp_dir.close().unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_p2p_agent_store_prune_stale() {
+    let tmp_dir = tempfile::Builder::new()
+        .prefix("p2p_agent_store_prune_stale")
+        .tempdir()
+        .unwrap();
+
+    let space = rand_space();
+
+    let db = DbWrite::test(tmp_dir.path(), DbKindP2pAgents(space.clone())).unwrap();
+
+    let live = rand_agent();
+    let expired = rand_agent();
+    rand_insert(&db, &space, &live, true).await;
+    rand_insert(&db, &space, &expired, false).await;
+
+    // an agent which left the space advertises no urls until its info expires
+    let left = rand_agent();
+    let signed_at_ms = rand_signed_at_ms();
+    let tombstone = AgentInfoSigned::sign(
+        space.clone(),
+        left.clone(),
+        Arq::new_full_max(
+            &kitsune_p2p_dht::prelude::Topology::standard_epoch_full(),
+            &ArqStrat::default(),
+            left.get_loc(),
+        ),
+        vec![],
+        signed_at_ms,
+        signed_at_ms + 20000,
+        |_| async { Ok(Arc::new(KitsuneSignature(vec![0; 64]))) },
+    )
+    .await
+    .unwrap();
+    p2p_put(&db, &tombstone).await.unwrap();
+    assert_eq!(3, db.p2p_list_all_agents().await.unwrap().len());
+
+    // nothing had expired long ago
+    let pruned = p2p_prune_stale(&db, 0).await.unwrap();
+    assert!(pruned.is_empty());
+
+    // only the expired agent is removed, the tombstone is kept until it expires
+    let pruned = p2p_prune_stale(&db, u64::MAX).await.unwrap();
+    assert_eq!(vec![expired.clone()], pruned);
+    let mut remaining = db
+        .p2p_list_all_agents()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|info| info.agent.clone())
+        .collect::<Vec<_>>();
+    remaining.sort();
+    let mut expected = vec![live, left];
+    expected.sort();
+    assert_eq!(expected, remaining);
+    assert!(db.p2p_get_agent(&expired).await.unwrap().is_none());
+
+    // clean up temp dir
+    tmp_dir.close().unwrap();
+}
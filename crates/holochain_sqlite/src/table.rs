@@ -3,35 +3,42 @@
 use rusqlite::Connection;
 
 use crate::db::DbKind;
+use crate::schema::Schema;
 
-/// Enumeration of all databases needed by Holochain
-pub(crate) fn initialize_database(conn: &mut Connection, db_kind: DbKind) -> rusqlite::Result<()> {
+/// The schema used by each kind of database.
+pub(crate) fn schema_for_kind(db_kind: &DbKind) -> Option<&'static Schema> {
     match db_kind {
-        DbKind::Dht(_) => {
-            crate::schema::SCHEMA_CELL.initialize(conn, Some(db_kind))?;
-        }
-        DbKind::Authored(_) => {
-            crate::schema::SCHEMA_CELL.initialize(conn, Some(db_kind))?;
-        }
-        DbKind::Conductor => {
-            crate::schema::SCHEMA_CONDUCTOR.initialize(conn, Some(db_kind))?;
-        }
-        DbKind::Wasm => {
-            crate::schema::SCHEMA_WASM.initialize(conn, Some(db_kind))?;
-        }
-        DbKind::P2pAgentStore(_) => {
-            crate::schema::SCHEMA_P2P_STATE.initialize(conn, Some(db_kind))?;
-        }
-        DbKind::P2pMetrics(_) => {
-            crate::schema::SCHEMA_P2P_METRICS.initialize(conn, Some(db_kind))?;
-        }
-        DbKind::Cache(_) => {
-            crate::schema::SCHEMA_CELL.initialize(conn, Some(db_kind))?;
+        DbKind::Dht(_) | DbKind::Authored(_) | DbKind::Cache(_) => {
+            Some(&crate::schema::SCHEMA_CELL)
         }
+        DbKind::Conductor => Some(&crate::schema::SCHEMA_CONDUCTOR),
+        DbKind::Wasm => Some(&crate::schema::SCHEMA_WASM),
+        DbKind::P2pAgentStore(_) => Some(&crate::schema::SCHEMA_P2P_STATE),
+        DbKind::P2pMetrics(_) => Some(&crate::schema::SCHEMA_P2P_METRICS),
         #[cfg(feature = "test_utils")]
-        DbKind::Test(_) => {
-            // Nothing to do
-        }
+        DbKind::Test(_) => None,
+    }
+}
+
+/// The schema used by a database file found under the databases root,
+/// based on the file names given by [`DbKindT::filename`](crate::db::DbKindT::filename).
+pub(crate) fn schema_for_file_name(dir: &str, file_name: &str) -> Option<&'static Schema> {
+    match dir {
+        "authored" | "dht" | "cache" => Some(&crate::schema::SCHEMA_CELL),
+        "conductor" => Some(&crate::schema::SCHEMA_CONDUCTOR),
+        "wasm" => Some(&crate::schema::SCHEMA_WASM),
+        "p2p" if file_name.starts_with("p2p_agent_store-") => {
+            Some(&crate::schema::SCHEMA_P2P_STATE)
+        }
+        "p2p" if file_name.starts_with("p2p_metrics-") => Some(&crate::schema::SCHEMA_P2P_METRICS),
+        _ => None,
+    }
+}
+
+/// Enumeration of all databases needed by Holochain
+pub(crate) fn initialize_database(conn: &mut Connection, db_kind: DbKind) -> rusqlite::Result<()> {
+    if let Some(schema) = schema_for_kind(&db_kind) {
+        schema.initialize(conn, Some(db_kind))?;
     }
     Ok(())
 }
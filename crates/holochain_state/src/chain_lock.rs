@@ -66,3 +66,18 @@ pub fn is_lock_expired(
     // If there's no lock then it's expired.
     Ok(r.unwrap_or(true))
 }
+
+/// True if the author's chain has been frozen with [`crate::mutations::freeze_chain`].
+/// Nothing can be written to a frozen chain until it is unfrozen.
+pub fn is_chain_frozen(txn: &Transaction, author: &AgentPubKey) -> StateMutationResult<bool> {
+    Ok(txn
+        .query_row(
+            "SELECT 1 FROM ChainFreeze WHERE author = :author",
+            named_params! {
+                ":author": author,
+            },
+            |row| row.get::<_, u32>(0),
+        )
+        .optional()?
+        .is_some())
+}
@@ -0,0 +1,171 @@
+//! Persistence for the durable remote calls queued by cells, which the
+//! conductor keeps retrying until they are delivered or expire.
+
+use holochain_sqlite::prelude::DbWrite;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_sqlite::sql::sql_conductor;
+use holochain_types::prelude::*;
+
+use crate::mutations;
+use crate::prelude::from_blob;
+use crate::prelude::StateMutationResult;
+use crate::prelude::StateQueryResult;
+
+/// A durable remote call waiting in the outbox.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DurableCall {
+    /// The id the call was queued with.
+    pub id: DurableCallId,
+    /// The cell which queued the call, and whose agent makes it.
+    pub cell_id: CellId,
+    /// The call.
+    pub call: DurableCallRemote,
+    /// When the call stops being retried.
+    pub expires_at: Timestamp,
+    /// How many times the call has been attempted.
+    pub attempts: u32,
+}
+
+/// Queue the calls a cell has made, under the ids they were given, due to be
+/// attempted straight away and each expiring when its time to live from `now`
+/// runs out.
+pub async fn queue(
+    db: &DbWrite<DbKindConductor>,
+    cell_id: CellId,
+    calls: Vec<(DurableCallId, DurableCallRemote)>,
+    now: Timestamp,
+) -> StateMutationResult<()> {
+    db.write_async(move |txn| {
+        for (id, call) in &calls {
+            let expires_at = now.saturating_add(&call.ttl);
+            mutations::insert_durable_call(txn, *id, &cell_id, call, expires_at, now)?;
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// Get the calls which are due to be attempted at `now`, the longest overdue first.
+#[allow(clippy::let_and_return)] // required to drop temporary
+pub fn due(txn: &Transaction<'_>, now: Timestamp) -> StateQueryResult<Vec<DurableCall>> {
+    let mut stmt = txn.prepare(sql_conductor::SELECT_DUE_DURABLE_CALLS)?;
+    let calls = stmt
+        .query_and_then(named_params! { ":now": now }, |row| {
+            let id: i64 = row.get("id")?;
+            let attempts: i64 = row.get("attempts")?;
+            let (cell_id, call) = from_blob(row.get("blob")?)?;
+            StateQueryResult::Ok(DurableCall {
+                id: DurableCallId(id as u64),
+                cell_id,
+                call,
+                expires_at: row.get("expires_at")?,
+                attempts: attempts as u32,
+            })
+        })?
+        .collect::<StateQueryResult<Vec<_>>>();
+
+    calls
+}
+
+/// Record a failed attempt of a call, and when to attempt it next.
+pub fn reschedule(
+    txn: &Transaction<'_>,
+    id: DurableCallId,
+    attempts: u32,
+    next_attempt_at: Timestamp,
+) -> StateMutationResult<()> {
+    txn.execute(
+        "UPDATE DurableCallOutbox SET attempts = :attempts, next_attempt_at = :next_attempt_at WHERE id = :id",
+        named_params! {
+            ":id": id.0 as i64,
+            ":attempts": attempts,
+            ":next_attempt_at": next_attempt_at,
+        },
+    )?;
+    Ok(())
+}
+
+/// Remove a call which has been delivered or given up on.
+pub fn remove(txn: &Transaction<'_>, id: DurableCallId) -> StateMutationResult<()> {
+    txn.execute("DELETE FROM DurableCallOutbox WHERE id = ?", [id.0 as i64])?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_conductor_db;
+    use std::time::Duration;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn calls_are_due_until_removed() {
+        let db = test_conductor_db();
+        let cell_id = CellId::new(
+            DnaHash::from_raw_36(vec![0; 36]),
+            AgentPubKey::from_raw_36(vec![1; 36]),
+        );
+        let call = DurableCallRemote::new(
+            AgentPubKey::from_raw_36(vec![2; 36]),
+            "zome".into(),
+            "fn".into(),
+            None,
+            ExternIO::encode(()).unwrap(),
+            Duration::from_secs(60),
+        );
+        let now = Timestamp::from_micros(1_000_000);
+        let (first, second) = (DurableCallId(2), DurableCallId(1));
+        queue(
+            &db,
+            cell_id.clone(),
+            vec![(first, call.clone()), (second, call.clone())],
+            now,
+        )
+        .await
+        .unwrap();
+
+        let due_at = |at| db.read_async(move |txn| due(&txn, at));
+        let calls = due_at(now).await.unwrap();
+        assert_eq!(
+            calls.iter().map(|c| c.id).collect::<Vec<_>>(),
+            vec![second, first]
+        );
+        assert_eq!(calls[0].cell_id, cell_id);
+        assert_eq!(calls[0].call, call);
+        assert_eq!(calls[0].attempts, 0);
+        assert_eq!(calls[0].expires_at, Timestamp::from_micros(61_000_000));
+
+        // A rescheduled call isn't due until its next attempt.
+        let later = Timestamp::from_micros(5_000_000);
+        db.write_async(move |txn| reschedule(txn, first, 1, later))
+            .await
+            .unwrap();
+        assert_eq!(
+            due_at(now)
+                .await
+                .unwrap()
+                .iter()
+                .map(|c| c.id)
+                .collect::<Vec<_>>(),
+            vec![second]
+        );
+        let calls = due_at(later).await.unwrap();
+        assert_eq!(
+            calls.iter().map(|c| (c.id, c.attempts)).collect::<Vec<_>>(),
+            vec![(second, 0), (first, 1)]
+        );
+
+        db.write_async(move |txn| remove(txn, second))
+            .await
+            .unwrap();
+        assert_eq!(
+            due_at(later)
+                .await
+                .unwrap()
+                .iter()
+                .map(|c| c.id)
+                .collect::<Vec<_>>(),
+            vec![first]
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Persistence for the conductor's event journal.
+
+use holochain_sqlite::prelude::DbWrite;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_sqlite::sql::sql_conductor;
+use holochain_types::prelude::*;
+
+use crate::mutations;
+use crate::prelude::from_blob;
+use crate::prelude::StateMutationResult;
+use crate::prelude::StateQueryResult;
+
+/// Append an event to the journal, timestamped now.
+pub async fn record(
+    db: &DbWrite<DbKindConductor>,
+    event: ConductorEvent,
+) -> StateMutationResult<()> {
+    db.write_async(move |txn| mutations::insert_event_journal_entry(txn, Timestamp::now(), &event))
+        .await
+}
+
+/// Get every event recorded at or after `since`, oldest first.
+#[allow(clippy::let_and_return)] // required to drop temporary
+pub fn query_since(
+    txn: &Transaction<'_>,
+    since: Timestamp,
+) -> StateQueryResult<Vec<EventJournalEntry>> {
+    let mut stmt = txn.prepare(sql_conductor::SELECT_EVENT_JOURNAL_SINCE)?;
+    let entries = stmt
+        .query_and_then(named_params! { ":since": since }, |row| {
+            let id: i64 = row.get("id")?;
+            let timestamp = row.get("timestamp")?;
+            let event = from_blob(row.get("blob")?)?;
+            StateQueryResult::Ok(EventJournalEntry {
+                id: id as u64,
+                timestamp,
+                event,
+            })
+        })?
+        .collect::<StateQueryResult<Vec<_>>>();
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_conductor_db;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn journal_entries_are_returned_in_order_since_a_time() {
+        let db = test_conductor_db();
+        let installed_app_id: InstalledAppId = "app".into();
+
+        record(
+            &db,
+            ConductorEvent::AppInstalled {
+                installed_app_id: installed_app_id.clone(),
+            },
+        )
+        .await
+        .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        let between = Timestamp::now();
+        tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        record(
+            &db,
+            ConductorEvent::AppEnabled {
+                installed_app_id: installed_app_id.clone(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let all = db
+            .read_async(|txn| query_since(&txn, Timestamp::from_micros(0)))
+            .await
+            .unwrap();
+        assert_eq!(
+            all.iter().map(|e| e.event.clone()).collect::<Vec<_>>(),
+            vec![
+                ConductorEvent::AppInstalled {
+                    installed_app_id: installed_app_id.clone(),
+                },
+                ConductorEvent::AppEnabled {
+                    installed_app_id: installed_app_id.clone(),
+                },
+            ]
+        );
+        assert!(all[0].id < all[1].id);
+
+        let recent = db
+            .read_async(move |txn| query_since(&txn, between))
+            .await
+            .unwrap();
+        assert_eq!(
+            recent.into_iter().map(|e| e.event).collect::<Vec<_>>(),
+            vec![ConductorEvent::AppEnabled { installed_app_id }]
+        );
+    }
+}
@@ -0,0 +1,29 @@
+//! Writing generated [`FixtureData`] into databases.
+
+use holo_hash::HasHash;
+use holochain_sqlite::prelude::DbKindDht;
+use holochain_sqlite::prelude::DbWrite;
+use holochain_types::fixture_data::FixtureData;
+use holochain_types::fixture_data::FIXTURE_EPOCH;
+use holochain_types::prelude::*;
+
+use crate::mutations;
+use crate::prelude::StateMutationResult;
+
+/// Insert every op of the fixture data into a DHT database, as valid and
+/// integrated at [`FIXTURE_EPOCH`]. Returns the number of ops inserted.
+pub async fn insert_fixture_data(
+    db: &DbWrite<DbKindDht>,
+    data: &FixtureData,
+) -> StateMutationResult<usize> {
+    let ops = data.ops();
+    db.write_async(move |txn| {
+        for op in &ops {
+            mutations::insert_op(txn, op)?;
+            mutations::set_validation_status(txn, op.as_hash(), ValidationStatus::Valid)?;
+            mutations::set_when_integrated(txn, op.as_hash(), FIXTURE_EPOCH)?;
+        }
+        Ok(ops.len())
+    })
+    .await
+}
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use holo_hash::AgentPubKey;
 use holochain_keystore::MetaLairClient;
 
+use crate::negative_cache::NegativeCache;
 use crate::prelude::*;
 
 #[derive(Clone)]
@@ -20,6 +21,9 @@ pub struct HostFnWorkspace<
     /// This is needed so that we don't run init recursively inside
     /// init calls.
     init_is_root: bool,
+    /// The misses recorded for the space, if cascades created from this
+    /// workspace should skip the network for them.
+    negative_cache: Option<NegativeCache>,
 }
 
 #[derive(Clone, shrinkwraprs::Shrinkwrap)]
@@ -34,6 +38,7 @@ pub struct HostFnStores {
     pub dht: DbRead<DbKindDht>,
     pub cache: DbWrite<DbKindCache>,
     pub scratch: Option<SyncScratch>,
+    pub negative_cache: Option<NegativeCache>,
 }
 
 pub type HostFnWorkspaceRead = HostFnWorkspace<DbRead<DbKindAuthored>, DbRead<DbKindDht>>;
@@ -127,11 +132,19 @@ impl SourceChainWorkspace {
                 dna_def,
                 cache,
                 init_is_root,
+                negative_cache: None,
             },
             source_chain,
         })
     }
 
+    /// Share the misses recorded for the space with the cascades
+    /// created from this workspace.
+    pub fn with_negative_cache(mut self, negative_cache: NegativeCache) -> Self {
+        self.inner.negative_cache = Some(negative_cache);
+        self
+    }
+
     /// Did this zome call chain originate from within
     /// an init callback.
     pub fn called_from_init(&self) -> bool {
@@ -173,9 +186,17 @@ where
             cache,
             dna_def,
             init_is_root: false,
+            negative_cache: None,
         })
     }
 
+    /// Share the misses recorded for the space with the cascades
+    /// created from this workspace.
+    pub fn with_negative_cache(mut self, negative_cache: NegativeCache) -> Self {
+        self.negative_cache = Some(negative_cache);
+        self
+    }
+
     pub fn source_chain(&self) -> &Option<SourceChain<SourceChainDb, SourceChainDht>> {
         &self.source_chain
     }
@@ -190,6 +211,7 @@ where
             dht: self.dht.clone(),
             cache: self.cache.clone(),
             scratch: self.source_chain.as_ref().map(|sc| sc.scratch()),
+            negative_cache: self.negative_cache.clone(),
         }
     }
 
@@ -219,6 +241,7 @@ impl From<HostFnWorkspace> for HostFnWorkspaceRead {
             cache: workspace.cache,
             dna_def: workspace.dna_def,
             init_is_root: workspace.init_is_root,
+            negative_cache: workspace.negative_cache,
         }
     }
 }
@@ -238,6 +261,7 @@ impl From<SourceChainWorkspace> for HostFnWorkspaceRead {
             cache: workspace.inner.cache,
             dna_def: workspace.inner.dna_def,
             init_is_root: workspace.inner.init_is_root,
+            negative_cache: workspace.inner.negative_cache,
         }
     }
 }
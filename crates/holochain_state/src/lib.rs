@@ -32,14 +32,21 @@ pub mod block;
 pub mod chain_lock;
 #[allow(missing_docs)]
 pub mod dna_def;
+pub mod durable_call_outbox;
 pub mod entry_def;
+pub mod event_journal;
+#[cfg(feature = "fixture_data")]
+pub mod fixture_data;
 pub mod host_fn_workspace;
 pub mod integrate;
+pub mod missed_signals;
 pub mod mutations;
+pub mod negative_cache;
 pub mod nonce;
 #[allow(missing_docs)]
 pub mod prelude;
 pub mod query;
+pub mod remote_signal_queue;
 pub mod schedule;
 pub mod scratch;
 #[allow(missing_docs)]
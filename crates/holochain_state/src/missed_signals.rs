@@ -0,0 +1,175 @@
+//! Persistence for the signals emitted by apps, so that clients which were
+//! disconnected when a signal was emitted can fetch it later.
+//!
+//! The signals of each app are kept in a ring buffer of a configurable size.
+
+use holochain_sqlite::prelude::DbWrite;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::OptionalExtension;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_sqlite::sql::sql_conductor;
+use holochain_types::prelude::*;
+
+use crate::mutations;
+use crate::prelude::from_blob;
+use crate::prelude::StateMutationResult;
+use crate::prelude::StateQueryResult;
+
+/// Persist a signal emitted by an app, evicting the app's oldest signals so
+/// that at most `buffer_size` remain. Returns the token of the new signal.
+pub async fn record(
+    db: &DbWrite<DbKindConductor>,
+    installed_app_id: InstalledAppId,
+    signal: Signal,
+    buffer_size: u32,
+) -> StateMutationResult<u64> {
+    db.write_async(move |txn| {
+        let (_, latest) = bounds(txn, &installed_app_id)?;
+        let token = latest.unwrap_or(0) + 1;
+        mutations::insert_missed_signal(txn, &installed_app_id, token, &signal)?;
+        txn.execute(
+            sql_conductor::DELETE_EVICTED_MISSED_SIGNALS,
+            named_params! {
+                ":app_id": installed_app_id,
+                ":evict_through": token.saturating_sub(buffer_size as u64) as i64,
+            },
+        )?;
+        StateMutationResult::Ok(token)
+    })
+    .await
+}
+
+/// Remove every signal persisted for an app.
+pub async fn clear(
+    db: &DbWrite<DbKindConductor>,
+    installed_app_id: InstalledAppId,
+) -> StateMutationResult<()> {
+    db.write_async(move |txn| {
+        txn.execute(
+            "DELETE FROM MissedSignal WHERE app_id = ?",
+            [installed_app_id],
+        )?;
+        StateMutationResult::Ok(())
+    })
+    .await
+}
+
+/// Get the signals persisted for an app after `since_token`, oldest first.
+/// Every persisted signal is returned if no token is given.
+pub fn query_since(
+    txn: &Transaction<'_>,
+    installed_app_id: &InstalledAppId,
+    since_token: Option<u64>,
+) -> StateQueryResult<MissedSignals> {
+    let (oldest, latest_token) = bounds(txn, installed_app_id)?;
+    // Signals after the token are missing if the oldest one still
+    // persisted isn't the one directly after the token.
+    let truncated = match (since_token, oldest) {
+        (Some(since), Some(oldest)) => oldest > since + 1,
+        _ => false,
+    };
+
+    let mut stmt = txn.prepare(sql_conductor::SELECT_MISSED_SIGNALS_SINCE)?;
+    let signals = stmt
+        .query_and_then(
+            named_params! {
+                ":app_id": installed_app_id,
+                ":since": since_token.unwrap_or(0) as i64,
+            },
+            |row| {
+                let token: i64 = row.get("seq")?;
+                let signal = from_blob(row.get("blob")?)?;
+                StateQueryResult::Ok(MissedSignal {
+                    token: token as u64,
+                    signal,
+                })
+            },
+        )?
+        .collect::<StateQueryResult<Vec<_>>>()?;
+
+    Ok(MissedSignals {
+        signals,
+        latest_token,
+        truncated,
+    })
+}
+
+/// The tokens of the oldest and latest signals persisted for an app.
+fn bounds(
+    txn: &Transaction<'_>,
+    installed_app_id: &InstalledAppId,
+) -> StateQueryResult<(Option<u64>, Option<u64>)> {
+    let bounds = txn
+        .query_row(
+            sql_conductor::SELECT_MISSED_SIGNAL_BOUNDS,
+            named_params! { ":app_id": installed_app_id },
+            |row| {
+                let oldest: Option<i64> = row.get("oldest")?;
+                let latest: Option<i64> = row.get("latest")?;
+                Ok((oldest.map(|s| s as u64), latest.map(|s| s as u64)))
+            },
+        )
+        .optional()?
+        .unwrap_or_default();
+    Ok(bounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_conductor_db;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn signals_are_kept_in_a_ring_buffer_per_app() {
+        let db = test_conductor_db();
+        let app: InstalledAppId = "app".into();
+        let other_app: InstalledAppId = "other".into();
+
+        for i in 0..5 {
+            record(&db, app.clone(), test_signal(&i.to_string()), 3)
+                .await
+                .unwrap();
+        }
+        record(&db, other_app.clone(), test_signal("other"), 3)
+            .await
+            .unwrap();
+
+        let query = |since| {
+            let app = app.clone();
+            db.read_async(move |txn| query_since(&txn, &app, since))
+        };
+
+        // Only the 3 latest signals are kept.
+        let all = query(None).await.unwrap();
+        assert_eq!(
+            all.signals.iter().map(|s| s.token).collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+        assert_eq!(all.signals[0].signal, test_signal("2"));
+        assert_eq!(all.latest_token, Some(5));
+        assert!(!all.truncated);
+
+        let recent = query(Some(3)).await.unwrap();
+        assert_eq!(
+            recent.signals.iter().map(|s| s.token).collect::<Vec<_>>(),
+            vec![4, 5]
+        );
+        assert!(!recent.truncated);
+
+        // Signal 2 has been evicted.
+        let behind = query(Some(1)).await.unwrap();
+        assert_eq!(behind.signals.len(), 3);
+        assert!(behind.truncated);
+
+        clear(&db, app.clone()).await.unwrap();
+        let cleared = query(None).await.unwrap();
+        assert!(cleared.signals.is_empty());
+        assert_eq!(cleared.latest_token, None);
+
+        let other = db
+            .read_async(move |txn| query_since(&txn, &other_app, None))
+            .await
+            .unwrap();
+        assert_eq!(other.signals.len(), 1);
+    }
+}
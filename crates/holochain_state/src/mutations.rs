@@ -1,5 +1,6 @@
 use crate::entry_def::EntryDefStoreKey;
 use crate::query::from_blob;
+use crate::query::link::link_tag_value_to_sql;
 use crate::query::to_blob;
 use crate::schedule::fn_is_scheduled;
 use crate::scratch::Scratch;
@@ -10,22 +11,32 @@ use holochain_nonce::Nonce256Bits;
 use holochain_sqlite::prelude::DatabaseResult;
 use holochain_sqlite::rusqlite::named_params;
 use holochain_sqlite::rusqlite::types::Null;
+use holochain_sqlite::rusqlite::OptionalExtension;
 use holochain_sqlite::rusqlite::Transaction;
 use holochain_sqlite::sql::sql_conductor;
+use holochain_types::agent_info_op::AgentInfoOp;
+use holochain_types::blob_chunk_op::BlobChunkOp;
 use holochain_types::dht_op::ChainOpHashed;
+use holochain_types::dht_op::DhtOp;
 use holochain_types::dht_op::DhtOpHashed;
 use holochain_types::dht_op::DhtOpLite;
 use holochain_types::dht_op::OpOrder;
+use holochain_types::dht_op::ValidationProvenance;
+use holochain_types::prelude::ConductorEvent;
 use holochain_types::prelude::DnaDefHashed;
 use holochain_types::prelude::DnaWasmHashed;
+use holochain_types::prelude::InstalledAppId;
+use holochain_types::prelude::Signal;
 use holochain_types::prelude::SysValDep;
 use holochain_types::prelude::{DhtOpError, SignedValidationReceipt};
 use holochain_types::sql::AsSql;
+use holochain_types::warrant::WarrantOp;
 use holochain_zome_types::block::Block;
 use holochain_zome_types::block::BlockTargetId;
 use holochain_zome_types::block::BlockTargetReason;
 use holochain_zome_types::entry::EntryHashed;
 use holochain_zome_types::prelude::*;
+use std::collections::HashSet;
 use std::str::FromStr;
 
 pub use error::*;
@@ -127,9 +138,163 @@ pub fn insert_op(txn: &mut Transaction, op: &DhtOpHashed) -> StateMutationResult
     let dependency = op.sys_validation_dependency();
     insert_op_lite(txn, &op_lite, hash, &op_order, &timestamp)?;
     set_dependency(txn, hash, dependency)?;
+    match op {
+        DhtOp::WarrantOp(op) => insert_warrant(txn, hash, op)?,
+        DhtOp::AgentInfoOp(op) => insert_agent_info(txn, hash, op)?,
+        DhtOp::BlobChunkOp(op) => insert_blob_chunk(txn, hash, op)?,
+        DhtOp::ChainOp(_) => (),
+    }
+    Ok(())
+}
+
+/// Insert the [`SignedWarrant`] held by a [`WarrantOp`] into the database,
+/// so the warrants against an agent can be looked up.
+pub fn insert_warrant(
+    txn: &mut Transaction,
+    op_hash: &DhtOpHash,
+    op: &WarrantOp,
+) -> StateMutationResult<()> {
+    let warrantee = op.warrant.dht_basis();
+    let signed_warrant = op.clone().into_signed_warrant();
+    sql_insert!(txn, Warrant, {
+        "hash": op_hash,
+        "author": op.author,
+        "warrantee": warrantee,
+        "timestamp": op.timestamp,
+        "blob": to_blob(&signed_warrant)?,
+    })?;
+    Ok(())
+}
+
+/// Insert the [`AgentInfoOp`] into the database,
+/// so the infos published for an agent can be looked up.
+pub fn insert_agent_info(
+    txn: &mut Transaction,
+    op_hash: &DhtOpHash,
+    op: &AgentInfoOp,
+) -> StateMutationResult<()> {
+    sql_insert!(txn, AgentInfo, {
+        "hash": op_hash,
+        "agent": op.agent,
+        "signed_at": op.signed_at,
+        "expires_at": op.expires_at,
+        "blob": to_blob(op)?,
+    })?;
+    Ok(())
+}
+
+/// Insert the [`BlobChunkOp`] into the database,
+/// so the chunk can be looked up by the hash of its bytes.
+pub fn insert_blob_chunk(
+    txn: &mut Transaction,
+    op_hash: &DhtOpHash,
+    op: &BlobChunkOp,
+) -> StateMutationResult<()> {
+    sql_insert!(txn, BlobChunk, {
+        "hash": op_hash,
+        "chunk_hash": op.chunk_hash,
+        "author": op.author,
+        "blob": to_blob(op)?,
+    })?;
+    Ok(())
+}
+
+/// Record that the blob manifest created by the action `manifest` keeps
+/// these chunks on this node.
+pub fn insert_blob_chunk_holds(
+    txn: &mut Transaction,
+    manifest: &ActionHash,
+    chunks: &[EntryHash],
+) -> StateMutationResult<()> {
+    for chunk_hash in chunks {
+        sql_insert!(txn, BlobChunkHold, {
+            "manifest": manifest,
+            "chunk_hash": chunk_hash,
+        })?;
+    }
+    Ok(())
+}
+
+/// Remove the holds of the blob manifest created by the action `manifest`,
+/// returning the hashes of its chunks which no other manifest holds.
+pub fn delete_blob_chunk_holds(
+    txn: &mut Transaction,
+    manifest: &ActionHash,
+) -> StateMutationResult<Vec<EntryHash>> {
+    let released = txn
+        .prepare_cached(
+            "DELETE FROM BlobChunkHold WHERE manifest = :manifest RETURNING chunk_hash",
+        )?
+        .query_map(named_params! { ":manifest": manifest }, |row| row.get(0))?
+        .collect::<Result<Vec<EntryHash>, _>>()?;
+    let mut unheld = Vec::with_capacity(released.len());
+    for chunk_hash in released {
+        let held: bool = txn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM BlobChunkHold WHERE chunk_hash = :chunk_hash)",
+            named_params! { ":chunk_hash": chunk_hash },
+            |row| row.get(0),
+        )?;
+        if !held {
+            unheld.push(chunk_hash);
+        }
+    }
+    Ok(unheld)
+}
+
+/// Delete the ops storing the chunks with these hashes.
+///
+/// The rows of the `BlobChunk` table go with their ops.
+pub fn delete_blob_chunks(txn: &mut Transaction, chunks: &[EntryHash]) -> StateMutationResult<()> {
+    for chunk_hash in chunks {
+        txn.execute(
+            "DELETE FROM DhtOp WHERE hash IN (SELECT hash FROM BlobChunk WHERE chunk_hash = :chunk_hash)",
+            named_params! { ":chunk_hash": chunk_hash },
+        )?;
+    }
     Ok(())
 }
 
+/// Evict the ops which were received before `before` from a cache database,
+/// along with the actions and entries which no remaining op refers to.
+///
+/// Returns the number of evicted ops.
+pub fn evict_cached_ops(txn: &mut Transaction, before: Timestamp) -> StateMutationResult<usize> {
+    let action_hashes = txn
+        .prepare_cached(
+            "DELETE FROM DhtOp
+            WHERE COALESCE(when_received, when_integrated, authored_timestamp) < :before
+            RETURNING action_hash",
+        )?
+        .query_map(named_params! { ":before": before }, |row| row.get(0))?
+        .collect::<Result<HashSet<ActionHash>, _>>()?;
+    let evicted = action_hashes.len();
+    let mut entry_hashes = HashSet::new();
+    for action_hash in action_hashes {
+        let entry_hash: Option<Option<EntryHash>> = txn
+            .query_row(
+                "DELETE FROM Action
+                WHERE hash = :hash
+                AND NOT EXISTS(SELECT 1 FROM DhtOp WHERE action_hash = :hash)
+                RETURNING entry_hash",
+                named_params! { ":hash": action_hash },
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(Some(entry_hash)) = entry_hash {
+            entry_hashes.insert(entry_hash);
+        }
+    }
+    for entry_hash in entry_hashes {
+        txn.execute(
+            "DELETE FROM Entry
+            WHERE hash = :hash
+            AND NOT EXISTS(SELECT 1 FROM Action WHERE entry_hash = :hash)",
+            named_params! { ":hash": entry_hash },
+        )?;
+    }
+    Ok(evicted)
+}
+
 /// Insert a [`DhtOpLite`] into an authored database.
 /// This sets the sql fields so the authored database
 /// can be used in queries with other databases.
@@ -168,6 +333,7 @@ pub fn insert_op_lite(
         "action_hash": action_hash,
         "require_receipt": 0,
         "op_order": order,
+        "when_received": Timestamp::now(),
     })?;
     Ok(())
 }
@@ -237,6 +403,71 @@ pub fn insert_conductor_state(
     Ok(())
 }
 
+/// Append an event to the conductor's event journal.
+pub fn insert_event_journal_entry(
+    txn: &Transaction<'_>,
+    timestamp: Timestamp,
+    event: &ConductorEvent,
+) -> StateMutationResult<()> {
+    sql_insert!(txn, EventJournal, {
+        "timestamp": timestamp,
+        "blob": to_blob(event)?,
+    })?;
+    Ok(())
+}
+
+/// Persist a durable remote call queued by a cell, due to be attempted at
+/// `next_attempt_at`.
+pub fn insert_durable_call(
+    txn: &Transaction<'_>,
+    id: DurableCallId,
+    cell_id: &CellId,
+    call: &DurableCallRemote,
+    expires_at: Timestamp,
+    next_attempt_at: Timestamp,
+) -> StateMutationResult<()> {
+    sql_insert!(txn, DurableCallOutbox, {
+        "id": id.0 as i64,
+        "expires_at": expires_at,
+        "next_attempt_at": next_attempt_at,
+        "attempts": 0,
+        "blob": to_blob(&(cell_id, call))?,
+    })?;
+    Ok(())
+}
+
+/// Persist a signal emitted by an app, at the given position among the
+/// signals persisted for the app.
+pub fn insert_missed_signal(
+    txn: &Transaction<'_>,
+    installed_app_id: &InstalledAppId,
+    seq: u64,
+    signal: &Signal,
+) -> StateMutationResult<()> {
+    sql_insert!(txn, MissedSignal, {
+        "app_id": installed_app_id,
+        "seq": seq as i64,
+        "blob": to_blob(signal)?,
+    })?;
+    Ok(())
+}
+
+/// Persist a remote signal held until the agent it's sent to comes online.
+pub fn insert_queued_remote_signal(
+    txn: &Transaction<'_>,
+    dna_hash: &DnaHash,
+    to_agent: &AgentPubKey,
+    signal: &holochain_p2p::event::QueuedRemoteSignal,
+) -> StateMutationResult<()> {
+    sql_insert!(txn, RemoteSignalQueue, {
+        "dna_hash": dna_hash,
+        "to_agent": to_agent,
+        "expires_at": signal.expires_at,
+        "blob": to_blob(signal)?,
+    })?;
+    Ok(())
+}
+
 pub fn insert_nonce(
     txn: &Transaction<'_>,
     agent: &AgentPubKey,
@@ -397,6 +628,30 @@ pub fn set_dependency(
     Ok(())
 }
 
+/// Set what a [`DhtOp`](holochain_types::dht_op::DhtOp) was validated with in the database.
+///
+/// Each distinct provenance is stored once, in the `ValidationProvenance` table.
+pub fn set_validation_provenance(
+    txn: &mut Transaction,
+    hash: &DhtOpHash,
+    provenance: &ValidationProvenance,
+) -> StateMutationResult<()> {
+    let blob = to_blob(provenance)?;
+    txn.execute(
+        "INSERT INTO ValidationProvenance (blob) VALUES (:blob)",
+        named_params! { ":blob": blob },
+    )?;
+    let id: i64 = txn.query_row(
+        "SELECT id FROM ValidationProvenance WHERE blob = :blob",
+        named_params! { ":blob": blob },
+        |row| row.get(0),
+    )?;
+    dht_op_update!(txn, hash, {
+        "validation_provenance_id": id,
+    })?;
+    Ok(())
+}
+
 /// Set the whether or not a receipt is required of a [`DhtOp`](holochain_types::dht_op::DhtOp) in the database.
 pub fn set_require_receipt(
     txn: &mut Transaction,
@@ -416,6 +671,11 @@ pub fn set_validation_stage(
     stage: ValidationStage,
 ) -> StateMutationResult<()> {
     let now = holochain_zome_types::prelude::Timestamp::now();
+    let missing_dependencies = match &stage {
+        ValidationStage::AwaitingSysDeps(dep) => Some(to_blob(&vec![dep.clone()])?),
+        ValidationStage::AwaitingAppDeps(deps) => Some(to_blob(deps)?),
+        _ => None,
+    };
     // TODO num_validation_attempts is incremented every time this is called but never reset between sys and app validation
     // which means that if an op takes a few tries to pass sys validation then it will be 'deprioritised' in the app validation
     // query rather than sorted by OpOrder. Check for/add a test that checks app validation is resilient to this and isn't relying on
@@ -426,13 +686,15 @@ pub fn set_validation_stage(
         SET
         num_validation_attempts = IFNULL(num_validation_attempts, 0) + 1,
         last_validation_attempt = :last_validation_attempt,
-        validation_stage = :validation_stage
+        validation_stage = :validation_stage,
+        missing_dependencies = :missing_dependencies
         WHERE
         DhtOp.hash = :hash
         ",
         named_params! {
             ":last_validation_attempt": now,
             ":validation_stage": stage,
+            ":missing_dependencies": missing_dependencies,
             ":hash": hash,
         },
     )?;
@@ -451,6 +713,29 @@ pub fn set_when_integrated(
     Ok(())
 }
 
+/// Set when a [`DhtOp`](holochain_types::dht_op::DhtOp) was integrated,
+/// unless it already has been, so an op which is held again keeps the time it
+/// first arrived at.
+pub fn set_when_integrated_if_unset(
+    txn: &mut Transaction,
+    hash: &DhtOpHash,
+    time: Timestamp,
+) -> StateMutationResult<()> {
+    txn.execute(
+        "
+        UPDATE DhtOp
+        SET when_integrated = :when_integrated
+        WHERE DhtOp.hash = :hash
+        AND DhtOp.when_integrated IS NULL
+        ",
+        named_params! {
+            ":when_integrated": time,
+            ":hash": hash,
+        },
+    )?;
+    Ok(())
+}
+
 /// Set when a [`DhtOp`](holochain_types::dht_op::DhtOp) was last publish time
 pub fn set_last_publish_time(
     txn: &mut Transaction,
@@ -516,7 +801,7 @@ pub fn insert_action(
     let prev_hash = action.prev_action().cloned();
     let private = match action.entry_type().map(|et| et.visibility()) {
         Some(EntryVisibility::Private) => true,
-        Some(EntryVisibility::Public) => false,
+        Some(EntryVisibility::Public | EntryVisibility::Unlisted) => false,
         None => false,
     };
     match action {
@@ -533,6 +818,17 @@ pub fn insert_action(
                 "tag": create_link.tag.as_sql(),
                 "blob": to_blob(&signed_action)?,
             })?;
+            if let Some(payload) = create_link.tag.payload() {
+                for (name, value) in payload.iter() {
+                    let (kind, value) = link_tag_value_to_sql(value);
+                    sql_insert!(txn, LinkTagField, {
+                        "action_hash": hash,
+                        "name": name,
+                        "kind": kind,
+                        "value": value,
+                    })?;
+                }
+            }
         }
         Action::DeleteLink(delete_link) => {
             sql_insert!(txn, Action, {
@@ -589,7 +885,8 @@ pub fn insert_action(
         | Action::Dna(_)
         | Action::AgentValidationPkg(_)
         | Action::OpenChain(_)
-        | Action::CloseChain(_) => {
+        | Action::CloseChain(_)
+        | Action::RenewMembraneProof(_) => {
             sql_insert!(txn, Action, {
                 "hash": hash,
                 "type": action_type,
@@ -689,6 +986,21 @@ pub fn unlock_chain(txn: &mut Transaction, author: &AgentPubKey) -> StateMutatio
     Ok(())
 }
 
+/// Freeze the author's chain, so nothing can be written to it until it is
+/// unfrozen. The freeze is kept across restarts.
+pub fn freeze_chain(txn: &mut Transaction, author: &AgentPubKey) -> StateMutationResult<()> {
+    sql_insert!(txn, ChainFreeze, {
+        "author": author,
+    })?;
+    Ok(())
+}
+
+/// Unfreeze the author's chain.
+pub fn unfreeze_chain(txn: &mut Transaction, author: &AgentPubKey) -> StateMutationResult<()> {
+    txn.execute("DELETE FROM ChainFreeze WHERE author = ?", [author])?;
+    Ok(())
+}
+
 pub fn delete_all_ephemeral_scheduled_fns(txn: &mut Transaction) -> StateMutationResult<()> {
     txn.execute(
         holochain_sqlite::sql::sql_cell::schedule::DELETE_ALL_EPHEMERAL,
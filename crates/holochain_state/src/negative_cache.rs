@@ -0,0 +1,99 @@
+//! An in-memory cache of the hashes which authorities reported they hold no data for.
+//!
+//! The cascade records a miss when every authority it asked for a hash responded
+//! without any data, and skips the network for gets of that hash until the miss
+//! expires. A miss is forgotten as soon as an op for its hash arrives at this
+//! node by publish or gossip, so data this node is an authority for is never
+//! hidden for longer than it takes to arrive. Other misses last until they expire.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use holo_hash::AnyDhtHash;
+
+/// The misses recorded for a space, shared by every cascade in the space.
+#[derive(Clone, Debug)]
+pub struct NegativeCache {
+    ttl: Duration,
+    misses: Arc<parking_lot::Mutex<HashMap<AnyDhtHash, Instant>>>,
+}
+
+impl NegativeCache {
+    /// Create a cache which remembers misses for `ttl`.
+    /// A zero `ttl` disables the cache.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            misses: Default::default(),
+        }
+    }
+
+    /// Record that the authorities for this hash hold no data for it.
+    pub fn insert(&self, hash: AnyDhtHash) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let now = Instant::now();
+        let mut misses = self.misses.lock();
+        misses.retain(|_, expires| *expires > now);
+        misses.insert(hash, now + self.ttl);
+    }
+
+    /// Whether a miss for this hash was recorded and hasn't expired.
+    pub fn contains(&self, hash: &AnyDhtHash) -> bool {
+        let mut misses = self.misses.lock();
+        match misses.get(hash) {
+            Some(expires) if *expires > Instant::now() => true,
+            Some(_) => {
+                misses.remove(hash);
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Forget the misses for these hashes because data for them has arrived.
+    pub fn invalidate(&self, hashes: impl IntoIterator<Item = AnyDhtHash>) {
+        let mut misses = self.misses.lock();
+        for hash in hashes {
+            misses.remove(&hash);
+        }
+    }
+
+    /// Whether no misses are recorded, so there is nothing to invalidate.
+    pub fn is_empty(&self) -> bool {
+        self.misses.lock().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holo_hash::ActionHash;
+
+    #[test]
+    fn misses_expire_and_are_invalidated() {
+        let hash: AnyDhtHash = ActionHash::from_raw_36(vec![1; 36]).into();
+        let other: AnyDhtHash = ActionHash::from_raw_36(vec![2; 36]).into();
+
+        let cache = NegativeCache::new(Duration::from_secs(60));
+        cache.insert(hash.clone());
+        cache.insert(other.clone());
+        assert!(cache.contains(&hash));
+        cache.invalidate([hash.clone()]);
+        assert!(!cache.contains(&hash));
+        assert!(cache.contains(&other));
+
+        let cache = NegativeCache::new(Duration::from_millis(1));
+        cache.insert(hash.clone());
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(!cache.contains(&hash));
+        assert!(cache.is_empty());
+
+        let disabled = NegativeCache::new(Duration::ZERO);
+        disabled.insert(hash.clone());
+        assert!(!disabled.contains(&hash));
+    }
+}
@@ -50,6 +50,29 @@ pub async fn witness_nonce(
     }
 }
 
+/// The latest nonce witnessed for each agent which is still within its window,
+/// as the agent and the time the nonce expires.
+///
+/// A zome call from an agent which reuses its latest nonce before it expires
+/// is rejected as a replay.
+pub async fn nonce_windows(
+    db: &DbWrite<DbKindConductor>,
+    now: Timestamp,
+) -> DatabaseResult<Vec<(AgentPubKey, Timestamp)>> {
+    db.read_async(move |txn| holochain_sqlite::nonce::nonce_windows(&txn, now))
+        .await
+}
+
+/// Forget every nonce witnessed so far, for conductors which are configured
+/// not to keep their nonce windows across restarts.
+pub async fn forget_nonces(db: &DbWrite<DbKindConductor>) -> DatabaseResult<()> {
+    db.write_async(|txn| {
+        txn.execute(sql_conductor::DELETE_ALL_NONCES, [])?;
+        Ok(())
+    })
+    .await
+}
+
 #[cfg(test)]
 pub mod test {
     use ::fixt::prelude::*;
@@ -146,4 +169,44 @@ pub mod test {
                 .unwrap()
         );
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn nonce_windows_list_unexpired_nonces_until_forgotten() {
+        let db = test_conductor_db();
+        let now = Timestamp::now();
+        let agent_0 = fixt!(AgentPubKey, Predictable, 0);
+        let agent_1 = fixt!(AgentPubKey, Predictable, 1);
+        let (nonce_0, expires_0) = fresh_nonce(now).unwrap();
+        let (nonce_1, expires_1) = fresh_nonce(now).unwrap();
+
+        super::witness_nonce(&db, agent_0.clone(), nonce_0, now, expires_0)
+            .await
+            .unwrap();
+        super::witness_nonce(&db, agent_1.clone(), nonce_1, now, expires_1)
+            .await
+            .unwrap();
+
+        let mut windows = super::nonce_windows(&db, now).await.unwrap();
+        windows.sort();
+        let mut expected = vec![(agent_0.clone(), expires_0), (agent_1, expires_1)];
+        expected.sort();
+        assert_eq!(windows, expected);
+
+        // Windows which have expired aren't listed.
+        assert!(super::nonce_windows(&db, expires_0)
+            .await
+            .unwrap()
+            .is_empty());
+
+        super::forget_nonces(&db).await.unwrap();
+        assert!(super::nonce_windows(&db, now).await.unwrap().is_empty());
+
+        // A forgotten nonce is no longer recognised as a replay.
+        assert_eq!(
+            WitnessNonceResult::Fresh,
+            super::witness_nonce(&db, agent_0, nonce_0, now, expires_0)
+                .await
+                .unwrap()
+        );
+    }
 }
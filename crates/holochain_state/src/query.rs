@@ -31,6 +31,7 @@ pub mod entry_details;
 pub mod error;
 pub mod link;
 pub mod link_count;
+pub mod link_delta;
 pub mod link_details;
 pub mod live_entry;
 pub mod live_record;
@@ -38,7 +39,11 @@ pub mod record_details;
 
 pub mod prelude {
     pub use super::from_blob;
+    pub use super::get_agent_info_from_db;
+    pub use super::get_agent_infos_from_db;
+    pub use super::get_blob_chunk_from_db;
     pub use super::get_entry_from_db;
+    pub use super::get_warrants_against_from_db;
     pub use super::to_blob;
     pub use super::Params;
     pub use super::Query;
@@ -960,6 +965,118 @@ pub fn get_public_op_from_db(
     }
 }
 
+/// Get the valid warrants which have been issued against an agent.
+pub fn get_warrants_against_from_db(
+    txn: &Transaction,
+    warrantee: &AgentPubKey,
+) -> StateQueryResult<Vec<SignedWarrant>> {
+    let mut stmt = txn.prepare_cached(
+        "
+        SELECT Warrant.blob AS warrant_blob FROM Warrant
+        JOIN DhtOp ON DhtOp.hash = Warrant.hash
+        WHERE Warrant.warrantee = :warrantee
+        AND DhtOp.validation_status = :status
+        ",
+    )?;
+    let warrants = stmt
+        .query_and_then(
+            named_params! {
+                ":warrantee": warrantee,
+                ":status": ValidationStatus::Valid,
+            },
+            |row| from_blob::<SignedWarrant>(row.get("warrant_blob")?),
+        )?
+        .collect::<StateQueryResult<Vec<_>>>()?;
+    Ok(warrants)
+}
+
+/// Get the latest valid agent info held for an agent, if it hasn't expired.
+pub fn get_agent_info_from_db(
+    txn: &Transaction,
+    agent: &AgentPubKey,
+    now: Timestamp,
+) -> StateQueryResult<Option<AgentInfoOp>> {
+    let result = txn.query_row_and_then(
+        "
+        SELECT AgentInfo.blob AS agent_info_blob FROM AgentInfo
+        JOIN DhtOp ON DhtOp.hash = AgentInfo.hash
+        WHERE AgentInfo.agent = :agent
+        AND AgentInfo.expires_at > :now
+        AND DhtOp.validation_status = :status
+        ORDER BY AgentInfo.signed_at DESC
+        LIMIT 1
+        ",
+        named_params! {
+            ":agent": agent,
+            ":now": now,
+            ":status": ValidationStatus::Valid,
+        },
+        |row| from_blob::<AgentInfoOp>(row.get("agent_info_blob")?),
+    );
+    match result {
+        Err(StateQueryError::Sql(holochain_sqlite::rusqlite::Error::QueryReturnedNoRows)) => {
+            Ok(None)
+        }
+        Err(e) => Err(e),
+        Ok(result) => Ok(Some(result)),
+    }
+}
+
+/// Get the valid chunk of a blob with this hash, if it's held.
+pub fn get_blob_chunk_from_db(
+    txn: &Transaction,
+    chunk_hash: &EntryHash,
+) -> StateQueryResult<Option<BlobChunkOp>> {
+    let result = txn.query_row_and_then(
+        "
+        SELECT BlobChunk.blob AS blob_chunk_blob FROM BlobChunk
+        JOIN DhtOp ON DhtOp.hash = BlobChunk.hash
+        WHERE BlobChunk.chunk_hash = :chunk_hash
+        AND DhtOp.validation_status = :status
+        LIMIT 1
+        ",
+        named_params! {
+            ":chunk_hash": chunk_hash,
+            ":status": ValidationStatus::Valid,
+        },
+        |row| from_blob::<BlobChunkOp>(row.get("blob_chunk_blob")?),
+    );
+    match result {
+        Err(StateQueryError::Sql(holochain_sqlite::rusqlite::Error::QueryReturnedNoRows)) => {
+            Ok(None)
+        }
+        Err(e) => Err(e),
+        Ok(result) => Ok(Some(result)),
+    }
+}
+
+/// Get the latest valid agent info held for every agent, leaving out
+/// the infos which have expired.
+pub fn get_agent_infos_from_db(
+    txn: &Transaction,
+    now: Timestamp,
+) -> StateQueryResult<Vec<AgentInfoOp>> {
+    let mut stmt = txn.prepare_cached(
+        "
+        SELECT AgentInfo.blob AS agent_info_blob, MAX(AgentInfo.signed_at) FROM AgentInfo
+        JOIN DhtOp ON DhtOp.hash = AgentInfo.hash
+        WHERE AgentInfo.expires_at > :now
+        AND DhtOp.validation_status = :status
+        GROUP BY AgentInfo.agent
+        ",
+    )?;
+    let infos = stmt
+        .query_and_then(
+            named_params! {
+                ":now": now,
+                ":status": ValidationStatus::Valid,
+            },
+            |row| from_blob::<AgentInfoOp>(row.get("agent_info_blob")?),
+        )?
+        .collect::<StateQueryResult<Vec<_>>>()?;
+    Ok(infos)
+}
+
 pub fn map_sql_dht_op(
     include_private_entries: bool,
     type_fieldname: &str,
@@ -989,7 +1106,7 @@ pub fn map_sql_dht_op_common(
             let mut entry: Option<Entry> = None;
             if action
                 .entry_type()
-                .filter(|et| include_private_entries || *et.visibility() == EntryVisibility::Public)
+                .filter(|et| include_private_entries || et.visibility().is_published())
                 .is_some()
             {
                 let e: Option<Vec<u8>> = row.get("entry_blob")?;
@@ -999,6 +1116,12 @@ pub fn map_sql_dht_op_common(
                 };
             }
 
+            // The entry may have been purged after it was deleted,
+            // in which case a store entry op can't be sent anymore.
+            if !return_private_entry_ops && entry.is_none() && op_type == ChainOpType::StoreEntry {
+                return Ok(None);
+            }
+
             Ok(Some(ChainOp::from_type(op_type, action, entry)?.into()))
         }
         DhtOpType::Warrant(_) => {
@@ -1009,5 +1132,13 @@ pub fn map_sql_dht_op_common(
                 WarrantOp::new(warrant, author, signature, timestamp).into(),
             ))
         }
+        DhtOpType::AgentInfo => {
+            let op = from_blob::<AgentInfoOp>(row.get("agent_info_blob")?)?;
+            Ok(Some(op.into()))
+        }
+        DhtOpType::BlobChunk => {
+            let op = from_blob::<BlobChunkOp>(row.get("blob_chunk_blob")?)?;
+            Ok(Some(op.into()))
+        }
     }
 }
@@ -1,5 +1,7 @@
 use holo_hash::*;
 use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::types::Value;
+use holochain_sqlite::rusqlite::ToSql;
 use holochain_types::dht_op::ChainOpType;
 use holochain_types::sql::ToSqlStatement;
 use holochain_zome_types::prelude::*;
@@ -17,6 +19,17 @@ pub struct GetLinksFilter {
     pub after: Option<Timestamp>,
     pub before: Option<Timestamp>,
     pub author: Option<AgentPubKey>,
+    /// Only links with a tag from the start tag inclusive to the end tag exclusive.
+    pub tag_range: Option<(LinkTag, LinkTag)>,
+    /// Only links whose tag carries a payload matching all of these filters.
+    /// Applied in sql with the `LinkTagField` index.
+    pub tag_fields: Vec<LinkTagFieldFilter>,
+    /// Only read the link ops which were integrated, or added to the cache,
+    /// at or after this time.
+    pub integrated_since: Option<Timestamp>,
+    /// Only read the link ops which were integrated, or added to the cache,
+    /// before this time.
+    pub integrated_before: Option<Timestamp>,
 }
 
 #[derive(Debug, Clone)]
@@ -25,9 +38,103 @@ pub struct LinksQuery {
     pub type_query: LinkTypeFilter,
     pub tag: Option<String>,
     filter: GetLinksFilter,
+    tag_start: Option<Vec<u8>>,
+    tag_end: Option<Vec<u8>>,
+    tag_fields: LinkTagFieldsSql,
     query: String,
 }
 
+/// The sql conditions which only match links whose tag carries a [`LinkTagPayload`]
+/// matching every field filter, using the `LinkTagField` index, with their parameters.
+#[derive(Debug, Clone, Default)]
+pub struct LinkTagFieldsSql {
+    conditions: String,
+    params: Vec<(String, Value)>,
+}
+
+impl LinkTagFieldsSql {
+    pub fn new(filters: &[LinkTagFieldFilter]) -> Self {
+        let mut sql = Self::default();
+        for (i, filter) in filters.iter().enumerate() {
+            let (name, value_condition) = match filter {
+                LinkTagFieldFilter::Eq(name, value) => {
+                    let (kind, value) = link_tag_value_to_sql(value);
+                    sql.push_param(format!(":tag_field_{i}_kind"), kind);
+                    sql.push_param(format!(":tag_field_{i}_value"), value);
+                    (
+                        name,
+                        format!(
+                            "LinkTagField.kind = :tag_field_{i}_kind
+                            AND LinkTagField.value = :tag_field_{i}_value"
+                        ),
+                    )
+                }
+                LinkTagFieldFilter::Range(name, start, end) => {
+                    let (kind, start) = link_tag_value_to_sql(start);
+                    let (end_kind, end) = link_tag_value_to_sql(end);
+                    if kind != end_kind {
+                        // Values of different kinds don't compare, so nothing is in the range.
+                        sql.conditions.push_str(" AND 0");
+                        continue;
+                    }
+                    sql.push_param(format!(":tag_field_{i}_kind"), kind);
+                    sql.push_param(format!(":tag_field_{i}_start"), start);
+                    sql.push_param(format!(":tag_field_{i}_end"), end);
+                    (
+                        name,
+                        format!(
+                            "LinkTagField.kind = :tag_field_{i}_kind
+                            AND LinkTagField.value >= :tag_field_{i}_start
+                            AND LinkTagField.value < :tag_field_{i}_end"
+                        ),
+                    )
+                }
+            };
+            sql.push_param(format!(":tag_field_{i}_name"), Value::Text(name.clone()));
+            sql.conditions.push_str(&format!(
+                "
+                AND EXISTS (
+                    SELECT 1 FROM LinkTagField
+                    WHERE LinkTagField.action_hash = Action.hash
+                    AND LinkTagField.name = :tag_field_{i}_name
+                    AND {value_condition}
+                )"
+            ));
+        }
+        sql
+    }
+
+    /// The conditions, each starting with `AND`, on the `Action` of a create link.
+    pub fn conditions(&self) -> &str {
+        &self.conditions
+    }
+
+    pub fn params(&self) -> impl Iterator<Item = Params<'_>> {
+        self.params
+            .iter()
+            .map(|(name, value)| (name.as_str(), value as &dyn ToSql))
+    }
+
+    fn push_param(&mut self, name: String, value: Value) {
+        self.params.push((name, value));
+    }
+}
+
+/// The kind and value a field of a [`LinkTagPayload`] is indexed with.
+///
+/// Values are only compared with values of the same kind, and sqlite compares
+/// integers numerically, text by its bytes and blobs byte by byte, which is how
+/// [`LinkTagValue`]s of each kind compare.
+pub(crate) fn link_tag_value_to_sql(value: &LinkTagValue) -> (Value, Value) {
+    let (kind, value) = match value {
+        LinkTagValue::Bool(v) => (0, Value::Integer(*v as i64)),
+        LinkTagValue::Int(v) => (1, Value::Integer(*v)),
+        LinkTagValue::Text(v) => (2, Value::Text(v.clone())),
+        LinkTagValue::Bytes(v) => (3, Value::Blob(v.clone())),
+    };
+    (Value::Integer(kind), value)
+}
+
 impl LinksQuery {
     pub fn new(
         base: AnyLinkableHash,
@@ -37,12 +144,20 @@ impl LinksQuery {
     ) -> Self {
         let tag = tag.map(|tag| Self::tag_to_hex(&tag));
         let create_string = Self::create_query_string(&type_query, tag.clone(), &filter);
-        let delete_string = Self::delete_query_string(&type_query, tag.clone());
+        let delete_string = Self::delete_query_string(&type_query, tag.clone(), &filter);
+        let (tag_start, tag_end) = filter
+            .tag_range
+            .clone()
+            .map_or((None, None), |(start, end)| (Some(start.0), Some(end.0)));
+        let tag_fields = LinkTagFieldsSql::new(&filter.tag_fields);
         Self {
             base: Arc::new(base),
             type_query,
             tag,
             filter,
+            tag_start,
+            tag_end,
+            tag_fields,
             query: Self::create_query(create_string, delete_string),
         }
     }
@@ -73,6 +188,10 @@ impl LinksQuery {
         self.query.clone()
     }
 
+    pub fn filter(&self) -> &GetLinksFilter {
+        &self.filter
+    }
+
     fn common_query_string() -> &'static str {
         "
             JOIN Action On DhtOp.action_hash = Action.hash
@@ -99,9 +218,13 @@ impl LinksQuery {
         );
         s = Self::add_type_query(s, type_query);
         s = Self::add_tag(s, tag);
+        s = Self::add_tag_range(s, filter.tag_range.as_ref());
+        s.push_str(LinkTagFieldsSql::new(&filter.tag_fields).conditions());
         s = Self::add_after(s, filter.after);
         s = Self::add_before(s, filter.before);
         s = Self::add_author(s, filter.author.as_ref());
+        s = Self::add_integrated_since(s, filter.integrated_since);
+        s = Self::add_integrated_before(s, filter.integrated_before);
 
         s
     }
@@ -124,6 +247,17 @@ impl LinksQuery {
         }
     }
 
+    // Tags are blobs, which sqlite compares byte by byte.
+    fn add_tag_range(q: String, tag_range: Option<&(LinkTag, LinkTag)>) -> String {
+        match tag_range {
+            Some(_) => format!(
+                "{} AND Action.tag >= :tag_start AND Action.tag < :tag_end",
+                q
+            ),
+            None => format!("{} AND :tag_start IS NULL AND :tag_end IS NULL", q),
+        }
+    }
+
     fn add_after(q: String, after: Option<Timestamp>) -> String {
         match after {
             Some(_) => format!("{} AND DhtOp.authored_timestamp >= :after", q),
@@ -145,7 +279,44 @@ impl LinksQuery {
         }
     }
 
-    fn delete_query_string(type_query: &LinkTypeFilter, tag: Option<String>) -> String {
+    // A create which arrived since then may have been deleted before it, so
+    // creates with a delete are left out even when the delete is older. Deletes
+    // which arrive after the upper bound are left for a later read.
+    fn add_integrated_since(q: String, integrated_since: Option<Timestamp>) -> String {
+        match integrated_since {
+            Some(_) => format!(
+                "{}
+                AND DhtOp.when_integrated >= :integrated_since
+                AND NOT EXISTS (
+                    SELECT 1 FROM DhtOp AS DeleteOp
+                    JOIN Action AS DeleteAction ON DeleteOp.action_hash = DeleteAction.hash
+                    WHERE DeleteOp.type = :delete
+                    AND DeleteAction.create_link_hash = Action.hash
+                    AND DeleteOp.validation_status = :status
+                    AND DeleteOp.when_integrated IS NOT NULL
+                    AND (
+                        :integrated_before IS NULL
+                        OR DeleteOp.when_integrated < :integrated_before
+                    )
+                )",
+                q
+            ),
+            None => format!("{} AND :integrated_since IS NULL", q),
+        }
+    }
+
+    fn add_integrated_before(q: String, integrated_before: Option<Timestamp>) -> String {
+        match integrated_before {
+            Some(_) => format!("{} AND DhtOp.when_integrated < :integrated_before", q),
+            None => format!("{} AND :integrated_before IS NULL", q),
+        }
+    }
+
+    fn delete_query_string(
+        type_query: &LinkTypeFilter,
+        tag: Option<String>,
+        filter: &GetLinksFilter,
+    ) -> String {
         let mut sub_create_query = format!(
             "
             SELECT Action.hash FROM DhtOp
@@ -169,11 +340,19 @@ impl LinksQuery {
             ",
             sub_create_query
         );
-        delete_query
+        // The deletes since then can be of links created at any time.
+        let delete_query = match filter.integrated_since {
+            Some(_) => format!(
+                "{} AND DhtOp.when_integrated >= :integrated_since",
+                delete_query
+            ),
+            None => format!("{} AND :integrated_since IS NULL", delete_query),
+        };
+        Self::add_integrated_before(delete_query, filter.integrated_before)
     }
 
     pub fn params(&self) -> Vec<Params> {
-        {
+        let mut params = {
             named_params! {
                 ":create": ChainOpType::RegisterAddLink,
                 ":delete": ChainOpType::RegisterRemoveLink,
@@ -182,9 +361,15 @@ impl LinksQuery {
                 ":after": self.filter.after,
                 ":before": self.filter.before,
                 ":author": self.filter.author,
+                ":tag_start": self.tag_start,
+                ":tag_end": self.tag_end,
+                ":integrated_since": self.filter.integrated_since,
+                ":integrated_before": self.filter.integrated_before,
             }
         }
-        .to_vec()
+        .to_vec();
+        params.extend(self.tag_fields.params());
+        params
     }
 }
 
@@ -234,6 +419,8 @@ impl Query for GetLinksQuery {
         let base_filter = query.base.clone();
         let type_query_filter = query.type_query.clone();
         let tag_filter = query.tag.clone();
+        let tag_range_filter = query.filter.tag_range.clone();
+        let tag_fields_filter = query.filter.tag_fields.clone();
         let f = move |action: &QueryData<Self>| match action.action() {
             Action::CreateLink(CreateLink {
                 base_address,
@@ -247,6 +434,10 @@ impl Query for GetLinksQuery {
                     && tag_filter
                         .as_ref()
                         .map_or(true, |t| LinksQuery::tag_to_hex(tag).starts_with(&(**t)))
+                    && tag_range_filter
+                        .as_ref()
+                        .map_or(true, |(start, end)| start.0 <= tag.0 && tag.0 < end.0)
+                    && tag.matches_fields(&tag_fields_filter)
             }
             Action::DeleteLink(DeleteLink { base_address, .. }) => *base_address == *base_filter,
             _ => false,
@@ -280,12 +471,12 @@ impl Query for GetLinksQuery {
         S: Store,
     {
         let mut links: Self::Output = state.creates.into_values().collect();
-        links.sort_by_key(|l| l.timestamp);
+        LinkOrder::Ascending.sort(&mut links);
         Ok(links)
     }
 }
 
-fn link_from_action(action: Action) -> StateQueryResult<Link> {
+pub(crate) fn link_from_action(action: Action) -> StateQueryResult<Link> {
     let hash = ActionHash::with_data_sync(&action);
     match action {
         Action::CreateLink(action) => Ok(Link {
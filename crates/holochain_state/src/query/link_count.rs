@@ -9,6 +9,10 @@ impl From<WireLinkQuery> for GetLinksFilter {
             before: value.before,
             after: value.after,
             author: value.author,
+            tag_range: value.tag_range,
+            tag_fields: Vec::new(),
+            integrated_since: None,
+            integrated_before: None,
         }
     }
 }
@@ -0,0 +1,146 @@
+use crate::query::link::{link_from_action, GetLinksFilter};
+use holo_hash::*;
+use holochain_zome_types::prelude::*;
+use std::fmt::Debug;
+
+use super::link::LinksQuery;
+use super::*;
+
+/// Query the links created and deleted since the watermark set as the
+/// `integrated_since` of the filter.
+///
+/// The watermark is compared with the time each link op was integrated, or
+/// added to the cache, rather than with the time it was authored, so ops which
+/// were authored earlier but only arrived since are included. Only ops which
+/// arrived before this delta's own watermark are read, so each op is in
+/// exactly one of a series of deltas.
+#[derive(Debug, Clone)]
+pub struct GetLinksDeltaQuery {
+    query: LinksQuery,
+    /// When the delta started to be read, which is its watermark.
+    read_at: Timestamp,
+}
+
+impl GetLinksDeltaQuery {
+    pub fn new(
+        base: AnyLinkableHash,
+        type_query: LinkTypeFilter,
+        tag: Option<LinkTag>,
+        mut filter: GetLinksFilter,
+    ) -> Self {
+        // Taken before anything is read, so ops which arrive during the read
+        // are included in the next delta.
+        let read_at = Timestamp::now();
+        filter.integrated_before = Some(read_at);
+        Self {
+            query: LinksQuery::new(base, type_query, tag, filter),
+            read_at,
+        }
+    }
+
+    /// The watermark of the delta, for the `since` of the next one.
+    pub fn watermark(&self) -> Timestamp {
+        self.read_at
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct LinksDeltaState {
+    creates: HashMap<ActionHash, Link>,
+    /// The create link hashes of the links deleted since the watermark.
+    deletes: HashSet<ActionHash>,
+}
+
+impl Query for GetLinksDeltaQuery {
+    type Item = Judged<SignedActionHashed>;
+    type State = LinksDeltaState;
+    type Output = LinksDelta;
+    fn query(&self) -> String {
+        self.query.query()
+    }
+
+    fn params(&self) -> Vec<Params> {
+        self.query.params()
+    }
+
+    fn init_fold(&self) -> StateQueryResult<Self::State> {
+        Ok(LinksDeltaState::default())
+    }
+
+    fn as_map(&self) -> Arc<dyn Fn(&Row) -> StateQueryResult<Self::Item>> {
+        let f = row_blob_to_action("action_blob");
+        // Data is valid because it is filtered in the sql query.
+        Arc::new(move |row| Ok(Judged::valid(f(row)?)))
+    }
+
+    // Actions in the scratch haven't been integrated yet, so they are all
+    // newer than the watermark.
+    fn as_filter(&self) -> Box<dyn Fn(&QueryData<Self>) -> bool> {
+        let query = &self.query;
+        let base_filter = query.base.clone();
+        let type_query_filter = query.type_query.clone();
+        let tag_filter = query.tag.clone();
+        let tag_range_filter = query.filter().tag_range.clone();
+        let tag_fields_filter = query.filter().tag_fields.clone();
+        let f = move |action: &QueryData<Self>| match action.action() {
+            Action::CreateLink(CreateLink {
+                base_address,
+                tag,
+                zome_index,
+                link_type,
+                ..
+            }) => {
+                *base_address == *base_filter
+                    && type_query_filter.contains(zome_index, link_type)
+                    && tag_filter
+                        .as_ref()
+                        .map_or(true, |t| LinksQuery::tag_to_hex(tag).starts_with(&(**t)))
+                    && tag_range_filter
+                        .as_ref()
+                        .map_or(true, |(start, end)| start.0 <= tag.0 && tag.0 < end.0)
+                    && tag.matches_fields(&tag_fields_filter)
+            }
+            Action::DeleteLink(DeleteLink { base_address, .. }) => *base_address == *base_filter,
+            _ => false,
+        };
+        Box::new(f)
+    }
+
+    fn fold(&self, mut state: Self::State, data: Self::Item) -> StateQueryResult<Self::State> {
+        let (action, _) = data.data.into_inner();
+        let (action, hash) = action.into_inner();
+        match action {
+            Action::CreateLink(create_link) => {
+                state
+                    .creates
+                    .insert(hash, link_from_action(Action::CreateLink(create_link))?);
+            }
+            Action::DeleteLink(delete_link) => {
+                state.deletes.insert(delete_link.link_add_address);
+            }
+            _ => return Err(StateQueryError::UnexpectedAction(action.action_type())),
+        }
+        Ok(state)
+    }
+
+    fn render<S>(&self, state: Self::State, _stores: S) -> StateQueryResult<Self::Output>
+    where
+        S: Store,
+    {
+        let LinksDeltaState { creates, deletes } = state;
+        let mut created: Vec<Link> = creates
+            .into_values()
+            .filter(|link| !deletes.contains(&link.create_link_hash))
+            .collect();
+        LinkOrder::Ascending.sort(&mut created);
+
+        let mut deleted: Vec<ActionHash> = deletes.into_iter().collect();
+        deleted.sort();
+
+        Ok(LinksDelta {
+            created,
+            deleted,
+            watermark: self.read_at,
+        })
+    }
+}
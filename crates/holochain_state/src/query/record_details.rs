@@ -167,6 +167,7 @@ impl Query for GetRecordDetailsQuery {
             validation_status,
             deletes: deletes.into_iter().collect(),
             updates: updates.into_iter().collect(),
+            warrants: Vec::new(),
         };
         Ok(Some(details))
     }
@@ -257,6 +257,45 @@ async fn insert_op_equivalence() {
     assert_eq!(ops1, ops2);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn get_agent_infos() {
+    holochain_trace::test_run();
+    let mut conn = Connection::open_in_memory().unwrap();
+    SCHEMA_CELL.initialize(&mut conn, None).unwrap();
+    let mut txn = conn
+        .transaction_with_behavior(TransactionBehavior::Exclusive)
+        .unwrap();
+
+    let now = Timestamp::now();
+    let hour = std::time::Duration::from_secs(60 * 60);
+    let agent_info = |agent: &AgentPubKey, signed_at: Timestamp| AgentInfoOp {
+        agent: agent.clone(),
+        agent_info: vec![],
+        signature: fixt!(Signature),
+        signed_at,
+        expires_at: (signed_at + hour).unwrap(),
+    };
+
+    let alice = fixt!(AgentPubKey);
+    let bob = fixt!(AgentPubKey);
+    let alice_old = agent_info(&alice, (now - hour / 2).unwrap());
+    let alice_new = agent_info(&alice, now);
+    // Bob's info has expired by now
+    let bob_expired = agent_info(&bob, (now - hour * 2).unwrap());
+
+    for op in [&alice_old, &alice_new, &bob_expired] {
+        let op = DhtOpHashed::from_content_sync(DhtOp::AgentInfoOp(op.clone()));
+        insert_valid_integrated_op(&mut txn, &op).unwrap();
+    }
+
+    assert_eq!(
+        get_agent_info_from_db(&txn, &alice, now).unwrap(),
+        Some(alice_new.clone())
+    );
+    assert_eq!(get_agent_info_from_db(&txn, &bob, now).unwrap(), None);
+    assert_eq!(get_agent_infos_from_db(&txn, now).unwrap(), vec![alice_new]);
+}
+
 fn get_link_query<'a, 'b: 'a>(
     txns: &[&'a Transaction<'b>],
     scratch: Option<&Scratch>,
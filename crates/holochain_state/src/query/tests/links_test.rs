@@ -1074,3 +1074,85 @@ async fn link_type_ranges() {
     )
     .await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn links_filtered_by_tag_fields() {
+    holochain_trace::test_run();
+    let test_db = test_dht_db();
+    let mut td = fixtures(test_db.to_db(), 4);
+    let base_hash = td[0].base_hash.clone();
+    for (i, d) in td.iter_mut().enumerate() {
+        let kind = if i % 2 == 0 { "up" } else { "down" };
+        let tag = LinkTag::try_from(
+            &LinkTagPayload::new()
+                .with("weight", i as i64)
+                .with("kind", kind),
+        )
+        .unwrap();
+        d.base_hash = base_hash.clone();
+        d.link_add.base_address = base_hash.clone().into();
+        d.link_add.tag = tag;
+        d.add_link().await;
+    }
+    let hash = |i: usize| ActionHash::with_data_sync(&Action::CreateLink(td[i].link_add.clone()));
+
+    // Every field of every payload is indexed.
+    let indexed: usize = td[0]
+        .env
+        .read_async(|txn| -> DatabaseResult<usize> {
+            Ok(txn.query_row("SELECT COUNT(*) FROM LinkTagField", [], |row| row.get(0))?)
+        })
+        .await
+        .unwrap();
+    assert_eq!(8, indexed);
+
+    let get_links = |tag_fields: Vec<LinkTagFieldFilter>| {
+        let query = GetLinksQuery::new(
+            base_hash.clone().into(),
+            LinkTypeFilter::Dependencies(td.iter().map(|d| d.zome_index).collect()),
+            None,
+            GetLinksFilter {
+                tag_fields,
+                ..Default::default()
+            },
+        );
+        td[0]
+            .env
+            .read_async(move |txn| -> DatabaseResult<HashSet<ActionHash>> {
+                Ok(query
+                    .run(DbScratch::new(&[&txn], &Scratch::new()))
+                    .unwrap()
+                    .into_iter()
+                    .map(|link| link.create_link_hash)
+                    .collect())
+            })
+    };
+    let weight =
+        |start: i64, end: i64| LinkTagFieldFilter::Range("weight".into(), start.into(), end.into());
+    let kind = |kind: &str| LinkTagFieldFilter::Eq("kind".into(), kind.into());
+
+    assert_eq!(
+        [hash(1), hash(2)].into_iter().collect::<HashSet<_>>(),
+        get_links(vec![weight(1, 3)]).await.unwrap()
+    );
+    assert_eq!(
+        [hash(2)].into_iter().collect::<HashSet<_>>(),
+        get_links(vec![kind("up"), weight(1, 4)]).await.unwrap()
+    );
+    // Values of a different kind never match.
+    assert!(
+        get_links(vec![LinkTagFieldFilter::Eq("weight".into(), "1".into())])
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(get_links(vec![LinkTagFieldFilter::Range(
+        "weight".into(),
+        0i64.into(),
+        "9".into()
+    )])
+    .await
+    .unwrap()
+    .is_empty());
+    assert_eq!(4, get_links(vec![]).await.unwrap().len());
+}
@@ -0,0 +1,186 @@
+//! Persistence for the remote signals to agents which were offline when the
+//! signals were sent, which are held until the agents come online.
+//!
+//! The queue is shared by every space of the conductor and bounded in length.
+//! Each signal expires when its time to live runs out.
+
+use holochain_p2p::event::QueuedRemoteSignal;
+use holochain_sqlite::prelude::DbWrite;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_sqlite::sql::sql_conductor;
+use holochain_types::prelude::*;
+
+use crate::mutations;
+use crate::prelude::from_blob;
+use crate::prelude::StateMutationResult;
+use crate::prelude::StateQueryResult;
+
+/// Hold a signal until `to_agent` comes online. Once `max_len` signals are held,
+/// the signals which have expired by `now` are removed to make room, and the
+/// signal is dropped if there still isn't any.
+/// Returns whether the signal was queued, and how many expired signals were removed.
+pub async fn push(
+    db: &DbWrite<DbKindConductor>,
+    dna_hash: DnaHash,
+    to_agent: AgentPubKey,
+    signal: QueuedRemoteSignal,
+    max_len: u32,
+    now: Timestamp,
+) -> StateMutationResult<(bool, usize)> {
+    db.write_async(move |txn| {
+        let mut expired = 0;
+        if len(txn)? >= max_len as u64 {
+            expired = txn.execute(
+                "DELETE FROM RemoteSignalQueue WHERE expires_at <= :now",
+                named_params! { ":now": now },
+            )?;
+        }
+        if len(txn)? >= max_len as u64 {
+            return StateMutationResult::Ok((false, expired));
+        }
+        mutations::insert_queued_remote_signal(txn, &dna_hash, &to_agent, &signal)?;
+        StateMutationResult::Ok((true, expired))
+    })
+    .await
+}
+
+/// Take the signals held for `to_agent`, which has come online. Returns the
+/// signals which haven't expired by `now`, oldest first, and how many had.
+pub async fn take(
+    db: &DbWrite<DbKindConductor>,
+    dna_hash: DnaHash,
+    to_agent: AgentPubKey,
+    now: Timestamp,
+) -> StateMutationResult<(Vec<QueuedRemoteSignal>, usize)> {
+    // Agents are seen online far more often than signals are held for them,
+    // so only take the write lock when there are signals to take.
+    let held = db
+        .read_async({
+            let dna_hash = dna_hash.clone();
+            let to_agent = to_agent.clone();
+            move |txn| query(&txn, &dna_hash, &to_agent)
+        })
+        .await?;
+    if held.is_empty() {
+        return Ok((Vec::new(), 0));
+    }
+
+    db.write_async(move |txn| {
+        let held = query(txn, &dna_hash, &to_agent)?;
+        txn.execute(
+            "DELETE FROM RemoteSignalQueue WHERE dna_hash = :dna_hash AND to_agent = :to_agent",
+            named_params! {
+                ":dna_hash": dna_hash,
+                ":to_agent": to_agent,
+            },
+        )?;
+        let count = held.len();
+        let signals: Vec<_> = held
+            .into_iter()
+            .filter(|signal| signal.expires_at > now)
+            .collect();
+        let expired = count - signals.len();
+        StateMutationResult::Ok((signals, expired))
+    })
+    .await
+}
+
+/// Get the signals held for an agent, oldest first.
+#[allow(clippy::let_and_return)] // required to drop temporary
+fn query(
+    txn: &Transaction<'_>,
+    dna_hash: &DnaHash,
+    to_agent: &AgentPubKey,
+) -> StateQueryResult<Vec<QueuedRemoteSignal>> {
+    let mut stmt = txn.prepare(sql_conductor::SELECT_QUEUED_REMOTE_SIGNALS)?;
+    let signals = stmt
+        .query_and_then(
+            named_params! {
+                ":dna_hash": dna_hash,
+                ":to_agent": to_agent,
+            },
+            |row| from_blob(row.get("blob")?),
+        )?
+        .collect::<StateQueryResult<Vec<_>>>();
+
+    signals
+}
+
+/// The number of signals held across all spaces.
+fn len(txn: &Transaction<'_>) -> StateQueryResult<u64> {
+    let len: i64 = txn.query_row("SELECT COUNT(*) FROM RemoteSignalQueue", [], |row| {
+        row.get(0)
+    })?;
+    Ok(len as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_conductor_db;
+
+    fn signal(payload: u32, expires_at: Timestamp) -> QueuedRemoteSignal {
+        QueuedRemoteSignal {
+            from_agent: AgentPubKey::from_raw_36(vec![2; 36]),
+            zome_name: "zome".into(),
+            fn_name: "recv_remote_signal".into(),
+            cap: None,
+            payload: ExternIO::encode(payload).unwrap(),
+            expires_at,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn signals_are_held_until_taken() {
+        let db = test_conductor_db();
+        let dna_hash = DnaHash::from_raw_36(vec![0; 36]);
+        let to_agent = AgentPubKey::from_raw_36(vec![1; 36]);
+        let now = Timestamp::from_micros(1_000_000);
+        let later = Timestamp::from_micros(2_000_000);
+
+        let queue = |signal| push(&db, dna_hash.clone(), to_agent.clone(), signal, 10, now);
+        assert_eq!((true, 0), queue(signal(1, later)).await.unwrap());
+        assert_eq!((true, 0), queue(signal(2, now)).await.unwrap());
+
+        let other_agent = AgentPubKey::from_raw_36(vec![3; 36]);
+        assert_eq!(
+            (Vec::new(), 0),
+            take(&db, dna_hash.clone(), other_agent, now).await.unwrap()
+        );
+        // The second signal expired at `now`.
+        assert_eq!(
+            (vec![signal(1, later)], 1),
+            take(&db, dna_hash.clone(), to_agent.clone(), now)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            (Vec::new(), 0),
+            take(&db, dna_hash, to_agent, now).await.unwrap()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn signals_are_dropped_until_expired_signals_make_room() {
+        let db = test_conductor_db();
+        let dna_hash = DnaHash::from_raw_36(vec![0; 36]);
+        let to_agent = AgentPubKey::from_raw_36(vec![1; 36]);
+        let now = Timestamp::from_micros(1_000_000);
+        let later = Timestamp::from_micros(2_000_000);
+        let much_later = Timestamp::from_micros(3_000_000);
+
+        let queue = |signal, now| push(&db, dna_hash.clone(), to_agent.clone(), signal, 1, now);
+        assert_eq!((true, 0), queue(signal(1, later), now).await.unwrap());
+        assert_eq!((false, 0), queue(signal(2, later), now).await.unwrap());
+        assert_eq!(
+            (true, 1),
+            queue(signal(3, much_later), later).await.unwrap()
+        );
+
+        assert_eq!(
+            (vec![signal(3, much_later)], 0),
+            take(&db, dna_hash, to_agent, later).await.unwrap()
+        );
+    }
+}
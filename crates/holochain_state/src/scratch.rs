@@ -26,6 +26,27 @@ pub struct Scratch {
     chain_top_ordering: ChainTopOrdering,
     scheduled_fns: Vec<ScheduledFn>,
     chain_head: Option<(u32, usize)>,
+    blob_chunks: BlobChunkWrites,
+    durable_calls: Vec<(DurableCallId, DurableCallRemote)>,
+}
+
+/// The blob chunks staged by a zome call, and the holds and releases of the
+/// blob manifests which account for them, written when the chain is flushed.
+#[derive(Debug, Clone, Default)]
+pub struct BlobChunkWrites {
+    /// The bytes of the staged chunks, by chunk hash.
+    pub chunks: HashMap<EntryHash, Arc<Vec<u8>>>,
+    /// The chunks each manifest keeps, by the action which created the manifest.
+    pub holds: Vec<(ActionHash, Vec<EntryHash>)>,
+    /// The manifests whose chunks are released.
+    pub releases: Vec<ActionHash>,
+}
+
+impl BlobChunkWrites {
+    /// Whether nothing is staged.
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty() && self.holds.is_empty() && self.releases.is_empty()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -52,6 +73,12 @@ impl Scratch {
         self.scheduled_fns.push(scheduled_fn)
     }
 
+    /// Stage a durable remote call, to be handed to the conductor's outbox once
+    /// the chain has been flushed.
+    pub fn add_durable_call(&mut self, id: DurableCallId, call: DurableCallRemote) {
+        self.durable_calls.push((id, call))
+    }
+
     pub fn chain_top_ordering(&self) -> ChainTopOrdering {
         self.chain_top_ordering
     }
@@ -107,7 +134,10 @@ impl Scratch {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.actions.is_empty() && self.scheduled_fns.is_empty()
+        self.actions.is_empty()
+            && self.scheduled_fns.is_empty()
+            && self.blob_chunks.is_empty()
+            && self.durable_calls.is_empty()
     }
 
     pub fn actions(&self) -> impl Iterator<Item = &SignedActionHashed> {
@@ -160,10 +190,43 @@ impl Scratch {
         Ok(r)
     }
 
+    /// Stage a blob chunk, to be stored when the chain is flushed.
+    pub fn add_blob_chunk(&mut self, chunk_hash: EntryHash, chunk: Vec<u8>) {
+        self.blob_chunks.chunks.insert(chunk_hash, Arc::new(chunk));
+    }
+
+    /// The bytes of a blob chunk staged in this scratch.
+    pub fn blob_chunk(&self, chunk_hash: &EntryHash) -> Option<Arc<Vec<u8>>> {
+        self.blob_chunks.chunks.get(chunk_hash).cloned()
+    }
+
+    /// Stage holds on these chunks for the manifest created by the action `manifest`.
+    pub fn add_blob_chunk_holds(&mut self, manifest: ActionHash, chunks: Vec<EntryHash>) {
+        self.blob_chunks.holds.push((manifest, chunks));
+    }
+
+    /// Stage the release of the chunks held for the manifest created by the
+    /// action `manifest`.
+    pub fn add_blob_chunk_release(&mut self, manifest: ActionHash) {
+        self.blob_chunks.releases.push(manifest);
+    }
+
+    /// Take the staged blob chunks, holds and releases.
+    pub fn take_blob_chunks(&mut self) -> BlobChunkWrites {
+        std::mem::take(&mut self.blob_chunks)
+    }
+
     pub fn drain_scheduled_fns(&mut self) -> impl Iterator<Item = ScheduledFn> + '_ {
         self.scheduled_fns.drain(..)
     }
 
+    /// Drain out the staged durable remote calls.
+    pub fn drain_durable_calls(
+        &mut self,
+    ) -> impl Iterator<Item = (DurableCallId, DurableCallRemote)> + '_ {
+        self.durable_calls.drain(..)
+    }
+
     /// Drain out all the actions.
     pub fn drain_actions(&mut self) -> impl Iterator<Item = SignedActionHashed> + '_ {
         self.chain_head = None;
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+use crate::chain_lock::is_chain_frozen;
 use crate::chain_lock::is_chain_locked;
 use crate::chain_lock::is_lock_expired;
 use crate::integrate::authored_ops_to_dht_db;
@@ -15,6 +18,7 @@ use holo_hash::AgentPubKey;
 use holo_hash::DhtOpHash;
 use holo_hash::DnaHash;
 use holo_hash::HasHash;
+use holochain_keystore::AgentPubKeyExt;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::ChcImpl;
 use holochain_p2p::HolochainP2pDnaT;
@@ -99,6 +103,9 @@ impl SourceChain {
         let countersigning_agent_state = self
             .vault
             .write_async(move |txn| {
+                if is_chain_frozen(txn, author.as_ref())? {
+                    return Err(SourceChainError::ChainFrozen);
+                }
                 if is_chain_locked(txn, &hashed_preflight_request, author.as_ref())? {
                     return Err(SourceChainError::ChainLocked);
                 }
@@ -233,6 +240,204 @@ impl SourceChain {
         .await
     }
 
+    /// Put several new records at the end of the source chain in one pass,
+    /// using ActionBuilders for an action type which has no weight data.
+    ///
+    /// All the actions are built on top of each other before any are signed,
+    /// then they are signed together and added to the scratch at once.
+    /// Returns the hashes of the new actions, in the order of the builders.
+    pub async fn put_weightless_many<
+        W: Default,
+        U: ActionUnweighed<Weight = W>,
+        B: ActionBuilder<U>,
+    >(
+        &self,
+        action_builders: Vec<B>,
+        chain_top_ordering: ChainTopOrdering,
+    ) -> SourceChainResult<Vec<ActionHash>> {
+        if action_builders.is_empty() {
+            return Ok(Vec::new());
+        }
+        let HeadInfo {
+            action: mut prev_action,
+            seq: mut action_seq,
+            mut timestamp,
+        } = self.chain_head_nonempty()?;
+
+        // Build every action on top of the one before it.
+        let mut actions = Vec::with_capacity(action_builders.len());
+        for action_builder in action_builders {
+            action_seq += 1;
+            // See `put_weighed` for why the timestamp is bumped.
+            timestamp = std::cmp::max(
+                Timestamp::now(),
+                (timestamp + std::time::Duration::from_micros(1))?,
+            );
+            let common = ActionBuilderCommon {
+                author: (*self.author).clone(),
+                timestamp,
+                action_seq,
+                prev_action,
+            };
+            let action: Action = action_builder
+                .build(common)
+                .weighed(Default::default())
+                .into();
+            let action = ActionHashed::from_content_sync(action);
+            prev_action = action.as_hash().clone();
+            actions.push(action);
+        }
+
+        let hashes = actions.iter().map(|a| a.as_hash().clone()).collect();
+        let actions = futures::future::try_join_all(
+            actions
+                .into_iter()
+                .map(|action| SignedActionHashed::sign(&self.keystore, action)),
+        )
+        .await?;
+        self.scratch.apply(|scratch| {
+            for action in actions {
+                insert_record_scratch(scratch, Record::new(action, None), chain_top_ordering);
+            }
+        })?;
+        Ok(hashes)
+    }
+
+    /// Put a [`RenewMembraneProof`] at the end of the source chain, which
+    /// supersedes the agent's current membrane proof with a new one.
+    ///
+    /// The current proof is held by the most recent [`RenewMembraneProof`],
+    /// or by the [`AgentValidationPkg`] if the proof was never renewed.
+    pub async fn put_membrane_proof_renewal(
+        &self,
+        membrane_proof: MembraneProof,
+        chain_top_ordering: ChainTopOrdering,
+    ) -> SourceChainResult<ActionHash> {
+        let supersedes = self
+            .query(
+                ChainQueryFilter::new()
+                    .action_type(ActionType::AgentValidationPkg)
+                    .action_type(ActionType::RenewMembraneProof),
+            )
+            .await?
+            .last()
+            .map(|record| record.action_address().clone())
+            .ok_or(SourceChainError::InvalidStructure(
+                ChainInvalidReason::GenesisDataMissing,
+            ))?;
+        self.put_weightless(
+            builder::RenewMembraneProof::new(supersedes, membrane_proof),
+            None,
+            chain_top_ordering,
+        )
+        .await
+    }
+
+    /// Stage one chunk of a blob, returning the hash of its bytes.
+    ///
+    /// The chunk is stored as a [`BlobChunkOp`] when the chain is flushed, in
+    /// the same transaction as the actions of the call, and only if a blob
+    /// manifest created in the call holds it. Staging a chunk which is already
+    /// staged is a no-op.
+    pub fn put_blob_chunk(&self, chunk: Vec<u8>) -> SourceChainResult<EntryHash> {
+        let chunk_hash = BlobChunkOp::chunk_hash_of(&chunk);
+        self.scratch.apply(|scratch| {
+            scratch.add_blob_chunk(chunk_hash.clone(), chunk);
+        })?;
+        Ok(chunk_hash)
+    }
+
+    /// Keep the chunks listed by the blob manifest created by the action
+    /// `manifest` on this node until the manifest is released or deleted.
+    ///
+    /// The holds are written when the chain is flushed.
+    pub fn hold_blob_chunks(
+        &self,
+        manifest: ActionHash,
+        chunks: Vec<EntryHash>,
+    ) -> SourceChainResult<()> {
+        self.scratch
+            .apply(|scratch| scratch.add_blob_chunk_holds(manifest, chunks))?;
+        Ok(())
+    }
+
+    /// Release the chunks held for the blob manifest created by the action
+    /// `manifest` when the chain is flushed.
+    ///
+    /// Deleting the manifest releases its chunks too. Only the holds of this
+    /// manifest are read, so releasing a blob doesn't depend on the length of
+    /// the chain.
+    pub fn release_blob_chunks(&self, manifest: ActionHash) -> SourceChainResult<()> {
+        self.scratch
+            .apply(|scratch| scratch.add_blob_chunk_release(manifest))?;
+        Ok(())
+    }
+
+    /// Sign the chunks staged in this call as [`BlobChunkOp`]s, each accounted
+    /// for by a manifest which holds it.
+    async fn blob_chunk_ops(
+        &self,
+        blob_chunks: &BlobChunkWrites,
+    ) -> SourceChainResult<Vec<DhtOpHashed>> {
+        let mut ops = Vec::with_capacity(blob_chunks.chunks.len());
+        for (chunk_hash, chunk) in &blob_chunks.chunks {
+            let manifest = blob_chunks
+                .holds
+                .iter()
+                .find(|(_, chunks)| chunks.contains(chunk_hash))
+                .map(|(manifest, _)| manifest.clone())
+                .ok_or_else(|| SourceChainError::BlobChunkNotHeld(chunk_hash.clone()))?;
+            let signature = self
+                .author
+                .sign_raw(
+                    &self.keystore,
+                    BlobChunkOp::signed_data(chunk_hash, &manifest).into(),
+                )
+                .await
+                .map_err(holochain_keystore::KeystoreError::LairError)?;
+            ops.push(DhtOpHashed::from_content_sync(DhtOp::from(BlobChunkOp {
+                author: (*self.author).clone(),
+                chunk_hash: chunk_hash.clone(),
+                manifest,
+                chunk: (**chunk).clone(),
+                signature,
+                timestamp: Timestamp::now(),
+            })));
+        }
+        Ok(ops)
+    }
+
+    /// Delete the chunks which no manifest holds any more from this node,
+    /// unless it is an authority for them.
+    async fn purge_blob_chunks(
+        &self,
+        network: &(dyn HolochainP2pDnaT + Send + Sync),
+        unheld: Vec<EntryHash>,
+    ) -> SourceChainResult<()> {
+        let mut chunks = Vec::with_capacity(unheld.len());
+        for chunk_hash in unheld {
+            if !network
+                .authority_for_hash(chunk_hash.clone().into())
+                .await?
+            {
+                chunks.push(chunk_hash);
+            }
+        }
+        if chunks.is_empty() {
+            return Ok(());
+        }
+        self.dht_db
+            .write_async({
+                let chunks = chunks.clone();
+                move |txn| delete_blob_chunks(txn, &chunks)
+            })
+            .await?;
+        self.vault
+            .write_async(move |txn| delete_blob_chunks(txn, &chunks))
+            .await?;
+        Ok(())
+    }
+
     #[async_recursion]
     #[tracing::instrument(skip(self, network))]
     pub async fn flush(
@@ -244,7 +449,7 @@ impl SourceChain {
         if self.scratch.apply(|s| s.is_empty())? {
             return Ok(Vec::new());
         }
-        let (scheduled_fns, actions, ops, entries, records) =
+        let (scheduled_fns, actions, ops, entries, records, mut blob_chunks) =
             self.scratch.apply_and_then(|scratch| {
                 let records: Vec<Record> = scratch.records().collect();
 
@@ -254,11 +459,27 @@ impl SourceChain {
                 // Drain out any entries.
                 let entries = scratch.drain_entries().collect::<Vec<_>>();
                 let scheduled_fns = scratch.drain_scheduled_fns().collect::<Vec<_>>();
-                SourceChainResult::Ok((scheduled_fns, actions, ops, entries, records))
+                let blob_chunks = scratch.take_blob_chunks();
+                SourceChainResult::Ok((scheduled_fns, actions, ops, entries, records, blob_chunks))
             })?;
 
+        // Deleting a blob manifest releases the chunks it holds.
+        blob_chunks
+            .releases
+            .extend(actions.iter().filter_map(|shh| match shh.action() {
+                Action::Delete(delete) => Some(delete.deletes_address.clone()),
+                _ => None,
+            }));
+        let blob_chunk_ops = self.blob_chunk_ops(&blob_chunks).await?;
+        let blob_chunk_holds = blob_chunks.holds.clone();
+        let blob_chunk_releases = blob_chunks.releases.clone();
+
         // Sync with CHC, if CHC is present
         if let Some(chc) = network.chc() {
+            // Don't let the CHC record writes which a frozen chain would refuse.
+            if self.is_chain_frozen().await? {
+                return Err(SourceChainError::ChainFrozen);
+            }
             let payload = AddRecordPayload::from_records(
                 self.keystore.clone(),
                 (*self.author).clone(),
@@ -292,6 +513,11 @@ impl SourceChain {
         let ops_to_integrate = ops
             .iter()
             .map(|op| (op.1.clone(), op.0.dht_basis()))
+            .chain(
+                blob_chunk_ops
+                    .iter()
+                    .map(|op| (op.as_hash().clone(), op.dht_basis())),
+            )
             .collect::<Vec<_>>();
 
         // Write the entries, actions and ops to the database in one transaction.
@@ -301,6 +527,11 @@ impl SourceChain {
         let chain_flush_result = self
             .vault
             .write_async(move |txn: &mut Transaction| {
+                // Nothing, including blob chunks and scheduled functions, is
+                // written to a frozen chain.
+                if is_chain_frozen(txn, author.as_ref())? {
+                    return Err(SourceChainError::ChainFrozen);
+                }
                 let now = Timestamp::now();
                 // TODO: if the chain is locked, functions can still be scheduled.
                 //       Do we want that?
@@ -308,9 +539,24 @@ impl SourceChain {
                     schedule_fn(txn, author.as_ref(), scheduled_fn, None, now)?;
                 }
 
+                // The blob chunks are written with the actions which account for
+                // them, and roll back with them if the chain can't be written.
+                for op in &blob_chunk_ops {
+                    insert_op(txn, op)?;
+                    set_validation_status(txn, op.as_hash(), ValidationStatus::Valid)?;
+                    set_when_integrated(txn, op.as_hash(), now)?;
+                }
+                for (manifest, chunks) in &blob_chunk_holds {
+                    insert_blob_chunk_holds(txn, manifest, chunks)?;
+                }
+                let mut unheld_blob_chunks = Vec::new();
+                for manifest in &blob_chunk_releases {
+                    unheld_blob_chunks.extend(delete_blob_chunk_holds(txn, manifest)?);
+                }
+
                 if actions.last().is_none() {
-                    // Nothing to write
-                    return Ok(Vec::new());
+                    // Nothing else to write
+                    return Ok((Vec::new(), unheld_blob_chunks));
                 }
 
                 // As at check.
@@ -358,7 +604,7 @@ impl SourceChain {
                         op: hc_sleuth::OpInfo::new(op.clone(), op_hash.clone(), dep.clone()),
                     });
                 }
-                SourceChainResult::Ok(actions)
+                SourceChainResult::Ok((actions, unheld_blob_chunks))
             })
             .await;
 
@@ -381,8 +627,21 @@ impl SourceChain {
                         (*self.author).clone(),
                     )
                     .await?;
+                    let mut old_hashes: Vec<_> = actions
+                        .iter()
+                        .map(|shh| (shh.action().action_seq(), shh.as_hash().clone()))
+                        .collect();
+                    old_hashes.sort_by_key(|(seq, _)| *seq);
                     let rebased_actions =
                         rebase_actions_on(&keystore, actions, new_head_info).await?;
+                    // Manifests created in this call have new hashes once rebased.
+                    let rebased_hashes: HashMap<ActionHash, ActionHash> = old_hashes
+                        .into_iter()
+                        .map(|(_, hash)| hash)
+                        .zip(rebased_actions.iter().map(|shh| shh.as_hash().clone()))
+                        .collect();
+                    let rebase =
+                        |hash: ActionHash| rebased_hashes.get(&hash).cloned().unwrap_or(hash);
                     child_chain.scratch.apply(move |scratch| {
                         for action in rebased_actions {
                             scratch.add_action(action, ChainTopOrdering::Relaxed);
@@ -390,6 +649,18 @@ impl SourceChain {
                         for entry in entries {
                             scratch.add_entry(entry, ChainTopOrdering::Relaxed);
                         }
+                        for (chunk_hash, chunk) in blob_chunks.chunks {
+                            scratch.add_blob_chunk(
+                                chunk_hash,
+                                Arc::try_unwrap(chunk).unwrap_or_else(|chunk| (*chunk).clone()),
+                            );
+                        }
+                        for (manifest, chunks) in blob_chunks.holds {
+                            scratch.add_blob_chunk_holds(rebase(manifest), chunks);
+                        }
+                        for manifest in blob_chunks.releases {
+                            scratch.add_blob_chunk_release(rebase(manifest));
+                        }
                     })?;
                     child_chain.flush(network).await
                 } else {
@@ -401,7 +672,7 @@ impl SourceChain {
                     ))
                 }
             }
-            Ok(actions) => {
+            Ok((actions, unheld_blob_chunks)) => {
                 authored_ops_to_dht_db(
                     network,
                     ops_to_integrate,
@@ -410,9 +681,10 @@ impl SourceChain {
                     &self.dht_db_cache,
                 )
                 .await?;
+                self.purge_blob_chunks(network, unheld_blob_chunks).await?;
                 SourceChainResult::Ok(actions)
             }
-            result => result,
+            Err(e) => Err(e),
         }
     }
 }
@@ -452,6 +724,14 @@ where
         })
     }
 
+    /// The bytes of a blob chunk staged in this call, which isn't stored yet.
+    pub fn staged_blob_chunk(&self, chunk_hash: &EntryHash) -> SourceChainResult<Option<Vec<u8>>> {
+        Ok(self
+            .scratch
+            .apply(|scratch| scratch.blob_chunk(chunk_hash))?
+            .map(|chunk| (*chunk).clone()))
+    }
+
     /// Create a source chain with a blank chain head.
     /// You probably don't want this.
     /// This type is only useful for when a source chain
@@ -591,8 +871,46 @@ where
         check_agent: AgentPubKey,
         check_secret: Option<CapSecret>,
     ) -> SourceChainResult<Option<CapGrant>> {
+        self.find_valid_cap_grant(check_function, check_agent, check_secret, false)
+            .await
+    }
+
+    /// Find a grant allowing the agent to run the function as a read-only
+    /// remote query. Only query grants and the chain author are considered.
+    pub async fn valid_query_cap_grant(
+        &self,
+        check_function: GrantedFunction,
+        check_agent: AgentPubKey,
+        check_secret: Option<CapSecret>,
+    ) -> SourceChainResult<Option<CapGrant>> {
+        self.find_valid_cap_grant(check_function, check_agent, check_secret, true)
+            .await
+    }
+
+    async fn find_valid_cap_grant(
+        &self,
+        check_function: GrantedFunction,
+        check_agent: AgentPubKey,
+        check_secret: Option<CapSecret>,
+        query: bool,
+    ) -> SourceChainResult<Option<CapGrant>> {
+        let is_valid = move |grant: &CapGrant,
+                             function: &GrantedFunction,
+                             agent: &AgentPubKey,
+                             secret: Option<&CapSecret>| {
+            if query {
+                grant.is_valid_query(function, agent, secret)
+            } else {
+                grant.is_valid(function, agent, secret)
+            }
+        };
         let author_grant = CapGrant::from(self.agent_pubkey().clone());
-        if author_grant.is_valid(&check_function, &check_agent, check_secret.as_ref()) {
+        if is_valid(
+            &author_grant,
+            &check_function,
+            &check_agent,
+            check_secret.as_ref(),
+        ) {
             // caller is source chain author
             return Ok(Some(author_grant));
         }
@@ -650,8 +968,12 @@ where
                     // loop over all found cap grants and check if one of them
                     // is valid for assignee and function
                     for cap_grant in cap_grants {
-                        if cap_grant.is_valid(&check_function, &check_agent, check_secret.as_ref())
-                        {
+                        if is_valid(
+                            &cap_grant,
+                            &check_function,
+                            &check_agent,
+                            check_secret.as_ref(),
+                        ) {
                             return Ok(Some(cap_grant));
                         }
                     }
@@ -856,6 +1178,120 @@ where
         Ok(query.filter_records(records))
     }
 
+    /// Find the most recent `Create` of this entry on the chain, including the scratch
+    /// space, which hasn't since been deleted on the chain.
+    ///
+    /// The authored database is searched by entry hash, so this doesn't read the whole chain.
+    pub async fn find_live_create(
+        &self,
+        entry_type: EntryType,
+        entry_hash: EntryHash,
+    ) -> SourceChainResult<Option<ActionHash>> {
+        let author = self.author.clone();
+        let mut creates: Vec<ActionHash> = self
+            .vault
+            .read_async({
+                let entry_type = entry_type.clone();
+                let entry_hash = entry_hash.clone();
+                move |txn| {
+                    let mut stmt = txn.prepare_cached(
+                        "
+                        SELECT Action.hash FROM Action
+                        WHERE Action.author = :author
+                        AND Action.entry_hash = :entry_hash
+                        AND Action.entry_type = :entry_type
+                        AND Action.type = :create
+                        AND NOT EXISTS (
+                            SELECT 1 FROM Action AS Deleted
+                            WHERE Deleted.deletes_action_hash = Action.hash
+                            AND Deleted.author = :author
+                        )
+                        ORDER BY Action.seq
+                        ",
+                    )?;
+                    let creates = stmt
+                        .query_map(
+                            named_params! {
+                                ":author": author,
+                                ":entry_hash": entry_hash,
+                                ":entry_type": entry_type.as_sql(),
+                                ":create": ActionType::Create.as_sql(),
+                            },
+                            |row| row.get(0),
+                        )?
+                        .collect::<Result<Vec<ActionHash>, _>>()?;
+                    StateQueryResult::Ok(creates)
+                }
+            })
+            .await?;
+        let deleted = self.scratch.apply(|scratch| {
+            creates.extend(
+                scratch
+                    .actions()
+                    .filter(|shh| match shh.action() {
+                        Action::Create(create) => {
+                            create.entry_type == entry_type && create.entry_hash == entry_hash
+                        }
+                        _ => false,
+                    })
+                    .map(|shh| shh.action_address().clone()),
+            );
+            scratch
+                .actions()
+                .filter_map(|shh| match shh.action() {
+                    Action::Delete(delete) => Some(delete.deletes_address.clone()),
+                    _ => None,
+                })
+                .collect::<HashSet<_>>()
+        })?;
+        Ok(creates
+            .into_iter()
+            .rev()
+            .find(|hash| !deleted.contains(hash)))
+    }
+
+    /// Find the most recent `CreateLink` with these fields on the chain, including the
+    /// scratch space, which hasn't since been deleted on the chain.
+    pub async fn find_live_create_link(
+        &self,
+        base_address: &AnyLinkableHash,
+        target_address: &AnyLinkableHash,
+        zome_index: ZomeIndex,
+        link_type: LinkType,
+        tag: &LinkTag,
+    ) -> SourceChainResult<Option<ActionHash>> {
+        let records = self
+            .query(
+                ChainQueryFilter::new()
+                    .action_type(ActionType::CreateLink)
+                    .action_type(ActionType::DeleteLink),
+            )
+            .await?;
+        let deleted: HashSet<&ActionHash> = records
+            .iter()
+            .filter_map(|record| match record.action() {
+                Action::DeleteLink(delete) => Some(&delete.link_add_address),
+                _ => None,
+            })
+            .collect();
+        Ok(records
+            .iter()
+            .rev()
+            .filter(|record| match record.action() {
+                Action::CreateLink(create) => {
+                    create.base_address == *base_address
+                        && create.target_address == *target_address
+                        && create.zome_index == zome_index
+                        && create.link_type == link_type
+                        && create.tag == *tag
+                }
+                _ => false,
+            })
+            .map(|record| record.action_address())
+            .find(|hash| !deleted.contains(hash))
+            .cloned())
+    }
+
     pub async fn is_chain_locked(&self, lock: Vec<u8>) -> SourceChainResult<bool> {
         let author = self.author.clone();
         Ok(self
@@ -864,6 +1300,34 @@ where
             .await?)
     }
 
+    /// Whether this chain is frozen, so that nothing can be written to it.
+    pub async fn is_chain_frozen(&self) -> SourceChainResult<bool> {
+        let author = self.author.clone();
+        Ok(self
+            .vault
+            .read_async(move |txn| is_chain_frozen(&txn, author.as_ref()))
+            .await?)
+    }
+
+    /// Freeze this chain, so that nothing can be written to it until it is
+    /// unfrozen with [`SourceChain::unfreeze_chain`].
+    pub async fn freeze_chain(&self) -> SourceChainResult<()> {
+        let author = self.author.clone();
+        self.vault
+            .write_async(move |txn| freeze_chain(txn, author.as_ref()))
+            .await?;
+        Ok(())
+    }
+
+    /// Unfreeze this chain.
+    pub async fn unfreeze_chain(&self) -> SourceChainResult<()> {
+        let author = self.author.clone();
+        self.vault
+            .write_async(move |txn| unfreeze_chain(txn, author.as_ref()))
+            .await?;
+        Ok(())
+    }
+
     /// If there is a countersigning session get the
     /// StoreEntry op to send to the entry authorities.
     pub fn countersigning_op(&self) -> SourceChainResult<Option<ChainOp>> {
@@ -1308,6 +1772,60 @@ pub async fn dump_state(
         .await?)
 }
 
+/// Dump up to `limit` records of the source chain, starting after the action
+/// sequence number `after_seq`, or from the start of the chain if it's `None`.
+#[tracing::instrument(skip_all)]
+pub async fn dump_state_page(
+    vault: DbRead<DbKindAuthored>,
+    author: AgentPubKey,
+    after_seq: Option<u32>,
+    limit: u32,
+) -> Result<Vec<SourceChainDumpRecord>, SourceChainError> {
+    Ok(vault
+        .read_async(move |txn| {
+            txn.prepare(
+                "
+                SELECT
+                Action.blob AS action_blob, Entry.blob AS entry_blob,
+                Action.hash AS action_hash
+                FROM Action
+                LEFT JOIN Entry ON Action.entry_hash = Entry.hash
+                WHERE
+                Action.author = :author
+                AND
+                Action.seq > :after_seq
+                ORDER BY Action.seq ASC
+                LIMIT :limit
+                ",
+            )?
+            .query_and_then(
+                named_params! {
+                    ":author": author,
+                    ":after_seq": after_seq.map_or(-1, i64::from),
+                    ":limit": limit,
+                },
+                |row| {
+                    let action: SignedAction = from_blob(row.get("action_blob")?)?;
+                    let (action, signature) = action.into();
+                    let action_address = row.get("action_hash")?;
+                    let entry: Option<Vec<u8>> = row.get("entry_blob")?;
+                    let entry: Option<Entry> = match entry {
+                        Some(entry) => Some(from_blob(entry)?),
+                        None => None,
+                    };
+                    StateQueryResult::Ok(SourceChainDumpRecord {
+                        signature,
+                        action_address,
+                        action,
+                        entry,
+                    })
+                },
+            )?
+            .collect::<StateQueryResult<Vec<_>>>()
+        })
+        .await?)
+}
+
 impl From<SourceChain> for SourceChainRead {
     fn from(chain: SourceChain) -> Self {
         SourceChainRead {
@@ -1569,6 +2087,125 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_find_live_create() -> SourceChainResult<()> {
+        let test_db = test_authored_db();
+        let dht_db = test_dht_db();
+        let keystore = test_keystore();
+        let db = test_db.to_db();
+        let alice = fixt!(AgentPubKey, Predictable, 0);
+
+        let mut mock = MockHolochainP2pDnaT::new();
+        mock.expect_authority_for_hash().returning(|_| Ok(false));
+        mock.expect_chc().return_const(None);
+        let dht_db_cache = DhtDbQueryCache::new(dht_db.to_db().into());
+
+        source_chain::genesis(
+            db.clone(),
+            dht_db.to_db(),
+            &dht_db_cache,
+            keystore.clone(),
+            fake_dna_hash(1),
+            alice.clone(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let chain = SourceChain::new(
+            db.clone(),
+            dht_db.to_db(),
+            dht_db_cache.clone(),
+            keystore.clone(),
+            alice.clone(),
+        )
+        .await?;
+
+        let entry = Entry::App(fixt!(AppEntryBytes));
+        let entry_hash = EntryHash::with_data_sync(&entry);
+        let entry_type = EntryType::App(fixt!(AppEntryDef));
+        assert_eq!(
+            chain
+                .find_live_create(entry_type.clone(), entry_hash.clone())
+                .await?,
+            None
+        );
+
+        // A create in the scratch space is found.
+        let create = builder::Create {
+            entry_type: entry_type.clone(),
+            entry_hash: entry_hash.clone(),
+        };
+        let created = chain
+            .put_weightless(create, Some(entry.clone()), ChainTopOrdering::Strict)
+            .await?;
+        assert_eq!(
+            chain
+                .find_live_create(entry_type.clone(), entry_hash.clone())
+                .await?,
+            Some(created.clone())
+        );
+
+        // Once flushed it is found in the database.
+        chain.flush(&mock).await?;
+        let chain = SourceChain::new(
+            db.clone(),
+            dht_db.to_db(),
+            dht_db_cache.clone(),
+            keystore.clone(),
+            alice.clone(),
+        )
+        .await?;
+        assert_eq!(
+            chain
+                .find_live_create(entry_type.clone(), entry_hash.clone())
+                .await?,
+            Some(created.clone())
+        );
+
+        // The same entry with a different type isn't a match.
+        let other_type = EntryType::App(AppEntryDef::new(
+            EntryDefIndex(u8::MAX),
+            0.into(),
+            EntryVisibility::Public,
+        ));
+        assert_eq!(
+            chain
+                .find_live_create(other_type, entry_hash.clone())
+                .await?,
+            None
+        );
+
+        // A delete in the scratch space hides the create.
+        let delete = builder::Delete {
+            deletes_address: created.clone(),
+            deletes_entry_address: entry_hash.clone(),
+        };
+        chain
+            .put_weightless(delete, None, ChainTopOrdering::Strict)
+            .await?;
+        assert_eq!(
+            chain
+                .find_live_create(entry_type.clone(), entry_hash.clone())
+                .await?,
+            None
+        );
+
+        // And so does the delete once it is in the database.
+        chain.flush(&mock).await?;
+        let chain = SourceChain::new(
+            db.clone(),
+            dht_db.to_db(),
+            dht_db_cache.clone(),
+            keystore.clone(),
+            alice.clone(),
+        )
+        .await?;
+        assert_eq!(chain.find_live_create(entry_type, entry_hash).await?, None);
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_get_cap_grant() -> SourceChainResult<()> {
         let test_db = test_authored_db();
@@ -2223,6 +2860,42 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn source_chain_dump_state_page() -> SourceChainResult<()> {
+        let test_db = test_authored_db();
+        let dht_db = test_dht_db();
+        let dht_db_cache = DhtDbQueryCache::new(dht_db.to_db().into());
+        let keystore = test_keystore();
+        let vault = test_db.to_db();
+        let author = keystore.new_sign_keypair_random().await.unwrap();
+        genesis(
+            vault.clone(),
+            dht_db.to_db(),
+            &dht_db_cache,
+            keystore.clone(),
+            fixt!(DnaHash),
+            author.clone(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let first = dump_state_page(vault.clone().into(), author.clone(), None, 2).await?;
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].action.action_seq(), 0);
+        assert_eq!(first[1].action.action_seq(), 1);
+
+        let rest = dump_state_page(vault.clone().into(), author.clone(), Some(1), 2).await?;
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].action.action_seq(), 2);
+
+        let all = dump_state(vault.clone().into(), author.clone()).await?;
+        assert_eq!(all.records, [first, rest].concat());
+
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn source_chain_query() {
         let test_db = test_authored_db();
@@ -2459,4 +3132,229 @@ mod tests {
         let zomes_initialized = chain.zomes_initialized().await.unwrap();
         assert!(zomes_initialized);
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn frozen_chain_refuses_writes() {
+        let test_db = test_authored_db();
+        let dht_db = test_dht_db();
+        let dht_db_cache = DhtDbQueryCache::new(dht_db.to_db().into());
+        let keystore = test_keystore();
+        let vault = test_db.to_db();
+        let alice = keystore.new_sign_keypair_random().await.unwrap();
+
+        genesis(
+            vault.clone(),
+            dht_db.to_db(),
+            &dht_db_cache,
+            keystore.clone(),
+            fixt!(DnaHash),
+            alice.clone(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let chain = SourceChain::new(vault, dht_db.to_db(), dht_db_cache, keystore, alice.clone())
+            .await
+            .unwrap();
+        let head = chain.chain_head_nonempty().unwrap();
+        let mut mock = MockHolochainP2pDnaT::new();
+        mock.expect_authority_for_hash().returning(|_| Ok(false));
+        mock.expect_chc().return_const(None);
+
+        chain.freeze_chain().await.unwrap();
+        assert!(chain.is_chain_frozen().await.unwrap());
+        chain
+            .put(
+                builder::InitZomesComplete {},
+                None,
+                ChainTopOrdering::Strict,
+            )
+            .await
+            .unwrap();
+        assert_matches!(chain.flush(&mock).await, Err(SourceChainError::ChainFrozen));
+        let persisted = chain
+            .author_db()
+            .read_async({
+                let alice = Arc::new(alice.clone());
+                move |txn| chain_head_db_nonempty(&txn, alice)
+            })
+            .await
+            .unwrap();
+        assert_eq!(persisted, head);
+
+        chain.unfreeze_chain().await.unwrap();
+        assert!(!chain.is_chain_frozen().await.unwrap());
+        chain
+            .put(
+                builder::InitZomesComplete {},
+                None,
+                ChainTopOrdering::Strict,
+            )
+            .await
+            .unwrap();
+        assert_eq!(chain.flush(&mock).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn membrane_proof_renewal_supersedes_current_proof() {
+        let test_db = test_authored_db();
+        let dht_db = test_dht_db();
+        let dht_db_cache = DhtDbQueryCache::new(dht_db.to_db().into());
+        let keystore = test_keystore();
+        let vault = test_db.to_db();
+        let alice = keystore.new_sign_keypair_random().await.unwrap();
+        let dna_hash = fixt!(DnaHash);
+
+        genesis(
+            vault.clone(),
+            dht_db.to_db(),
+            &dht_db_cache,
+            keystore.clone(),
+            dna_hash.clone(),
+            alice.clone(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let chain = SourceChain::new(vault, dht_db.to_db(), dht_db_cache, keystore, alice.clone())
+            .await
+            .unwrap();
+        let pkg_hash = chain
+            .query(ChainQueryFilter::new().action_type(ActionType::AgentValidationPkg))
+            .await
+            .unwrap()[0]
+            .action_address()
+            .clone();
+
+        let proof = || Arc::new(SerializedBytes::from(UnsafeBytes::from(vec![1, 2, 3])));
+        let first = chain
+            .put_membrane_proof_renewal(proof(), ChainTopOrdering::Strict)
+            .await
+            .unwrap();
+        chain
+            .put_membrane_proof_renewal(proof(), ChainTopOrdering::Strict)
+            .await
+            .unwrap();
+
+        // The first renewal supersedes the proof from genesis,
+        // and later renewals supersede the previous renewal.
+        let superseded: Vec<_> = chain
+            .query(ChainQueryFilter::new().action_type(ActionType::RenewMembraneProof))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|record| match record.action() {
+                Action::RenewMembraneProof(renewal) => renewal.supersedes.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(superseded, vec![pkg_hash, first]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn blob_chunks_are_purged_once_no_manifest_holds_them() -> SourceChainResult<()> {
+        let test_db = test_authored_db();
+        let dht_db = test_dht_db();
+        let dht_db_cache = DhtDbQueryCache::new(dht_db.to_db().into());
+        let keystore = test_keystore();
+        let db = test_db.to_db();
+        let alice = fixt!(AgentPubKey, Predictable, 0);
+
+        let mut mock = MockHolochainP2pDnaT::new();
+        mock.expect_authority_for_hash().returning(|_| Ok(false));
+        mock.expect_chc().return_const(None);
+
+        source_chain::genesis(
+            db.clone(),
+            dht_db.to_db(),
+            None,
+            &dht_db_cache,
+            keystore.clone(),
+            fake_dna_hash(1),
+            alice.clone(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let new_chain = || {
+            SourceChain::new(
+                db.clone(),
+                dht_db.to_db(),
+                dht_db_cache.clone(),
+                keystore.clone(),
+                alice.clone(),
+            )
+        };
+        let stored = |chunk_hash: EntryHash| {
+            db.read_async(move |txn| get_blob_chunk_from_db(&txn, &chunk_hash))
+        };
+
+        let chain = new_chain().await?;
+        let shared = chain.put_blob_chunk(vec![1; 16])?;
+        let own = chain.put_blob_chunk(vec![2; 16])?;
+        assert_eq!(shared, BlobChunkOp::chunk_hash_of(&[1; 16]));
+        // A staged chunk can be read back within the call.
+        assert_eq!(chain.staged_blob_chunk(&own)?, Some(vec![2; 16]));
+
+        let entry_type = EntryType::App(fixt!(AppEntryDef));
+        let mut manifests = Vec::new();
+        for _ in 0..2 {
+            let entry = Entry::App(fixt!(AppEntryBytes));
+            let entry_hash = EntryHash::with_data_sync(&entry);
+            let create = builder::Create {
+                entry_type: entry_type.clone(),
+                entry_hash: entry_hash.clone(),
+            };
+            let action_hash = chain
+                .put_weightless(create, Some(entry), ChainTopOrdering::Strict)
+                .await?;
+            manifests.push((action_hash, entry_hash));
+        }
+        let (first, first_entry) = manifests[0].clone();
+        let (second, _) = manifests[1].clone();
+        chain.hold_blob_chunks(first.clone(), vec![shared.clone(), own.clone()])?;
+        chain.hold_blob_chunks(second.clone(), vec![shared.clone()])?;
+
+        // Nothing is stored until the chain is flushed.
+        assert!(stored(own.clone()).await?.is_none());
+        chain.flush(&mock).await?;
+        let op = stored(own.clone()).await?.unwrap();
+        assert_eq!(op.chunk, vec![2; 16]);
+        assert!(op.manifest == first || op.manifest == second);
+
+        // Deleting the first manifest releases its chunks, but the shared
+        // chunk is still held by the second manifest.
+        let chain = new_chain().await?;
+        let delete = builder::Delete {
+            deletes_address: first,
+            deletes_entry_address: first_entry,
+        };
+        chain
+            .put_weightless(delete, None, ChainTopOrdering::Strict)
+            .await?;
+        chain.flush(&mock).await?;
+        assert!(stored(own).await?.is_none());
+        assert_eq!(stored(shared.clone()).await?.unwrap().chunk, vec![1; 16]);
+
+        let chain = new_chain().await?;
+        chain.release_blob_chunks(second)?;
+        chain.flush(&mock).await?;
+        assert!(stored(shared).await?.is_none());
+
+        // A chunk which no manifest holds is never stored.
+        let chain = new_chain().await?;
+        let orphan = chain.put_blob_chunk(vec![3; 16])?;
+        assert_matches!(
+            chain.flush(&mock).await,
+            Err(SourceChainError::BlobChunkNotHeld(chunk_hash)) if chunk_hash == orphan
+        );
+        assert!(stored(orphan).await?.is_none());
+
+        Ok(())
+    }
 }
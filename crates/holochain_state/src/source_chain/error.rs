@@ -45,6 +45,9 @@ pub enum SourceChainError {
     #[error("Attempted to write anything other than the countersigning session entry while the chain was locked for a countersigning session.")]
     ChainLocked,
 
+    #[error("Attempted to write to a source chain which is frozen for investigation.")]
+    ChainFrozen,
+
     #[error("Attempted to write a countersigning session that has already expired")]
     LockExpired,
 
@@ -124,6 +127,10 @@ pub enum SourceChainError {
              In the future, all filters will be compatible with each other and this will not be an error.")]
     UnsupportedQuery(ChainQueryFilter),
 
+    /// A blob chunk was created without a blob manifest to hold it.
+    #[error("The blob chunk {0} was created but no blob manifest holds it")]
+    BlobChunkNotHeld(EntryHash),
+
     /// Other
     #[error("Other: {0}")]
     Other(Box<dyn std::error::Error + Send + Sync>),
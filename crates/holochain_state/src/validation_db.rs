@@ -1,8 +1,24 @@
 //! # Validation Database Types
 
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use holo_hash::ActionHash;
 use holo_hash::AnyDhtHash;
+use holo_hash::DhtOpHash;
 use holochain_serialized_bytes::prelude::*;
+use holochain_sqlite::rusqlite::named_params;
 use holochain_sqlite::rusqlite::ToSql;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_types::dht_op::{
+    op_dependency_id, ChainOpType, DhtOpType, OpDependencyEdge, OpDependencyGraph,
+    OpDependencyKind, OpDependencyNode, ValidationLimboOp, ValidationLimboStage,
+};
+use holochain_types::prelude::{Timestamp, ValidationStatus};
+
+use crate::mutations::StateMutationResult;
+use crate::prelude::from_blob;
+use crate::prelude::StateQueryResult;
 
 /// The status of a [`DhtOp`](holochain_types::dht_op::DhtOp) in limbo
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -35,3 +51,357 @@ impl ToSql for ValidationStage {
         ))
     }
 }
+
+/// List the ops which haven't been integrated yet, those with the most
+/// validation attempts first.
+pub fn list_validation_limbo(txn: &Transaction) -> StateQueryResult<Vec<ValidationLimboOp>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT
+        hash, type, validation_stage, missing_dependencies,
+        num_validation_attempts, last_validation_attempt
+        FROM DhtOp
+        WHERE when_integrated IS NULL
+        ORDER BY num_validation_attempts DESC, authored_timestamp ASC
+        ",
+    )?;
+    let iter = stmt.query_and_then([], |row| {
+        let stage = limbo_stage(row.get("validation_stage")?);
+        let missing_dependencies = match row.get("missing_dependencies")? {
+            Some(blob) => from_blob(blob)?,
+            None => Vec::new(),
+        };
+        StateQueryResult::Ok(ValidationLimboOp {
+            op_hash: row.get("hash")?,
+            op_type: row.get("type")?,
+            stage,
+            missing_dependencies,
+            num_validation_attempts: row
+                .get::<_, Option<u32>>("num_validation_attempts")?
+                .unwrap_or(0),
+            last_validation_attempt: row.get("last_validation_attempt")?,
+        })
+    })?;
+    iter.collect()
+}
+
+fn limbo_stage(validation_stage: Option<u8>) -> ValidationLimboStage {
+    match validation_stage {
+        None => ValidationLimboStage::Pending,
+        Some(0) => ValidationLimboStage::AwaitingSysDeps,
+        Some(1) => ValidationLimboStage::SysValidated,
+        Some(2) => ValidationLimboStage::AwaitingAppDeps,
+        Some(_) => ValidationLimboStage::AwaitingIntegration,
+    }
+}
+
+/// Build the dependency graph of the given ops from what the validation workflows
+/// recorded them as waiting for.
+///
+/// The ops holding each dependency are followed in turn, so the graph covers
+/// everything the given ops are transitively waiting on, up to `max_ops` ops.
+/// Integrated ops end the graph, since they no longer wait on anything.
+pub fn op_dependency_graph(
+    txn: &Transaction,
+    op_hashes: &[DhtOpHash],
+    max_ops: usize,
+) -> StateQueryResult<OpDependencyGraph> {
+    let mut op_stmt = txn.prepare(
+        "
+        SELECT
+        type, when_integrated, validation_stage, validation_status,
+        missing_dependencies, dependency
+        FROM DhtOp
+        WHERE hash = :hash
+        ",
+    )?;
+    let mut held_by_stmt = txn.prepare(
+        "
+        SELECT hash
+        FROM DhtOp
+        WHERE action_hash = :hash
+        OR (basis_hash = :hash AND type = :store_entry)
+        ",
+    )?;
+
+    let mut graph = OpDependencyGraph::default();
+    let mut seen_ops = HashSet::new();
+    let mut seen_deps = HashSet::new();
+    let mut queue: VecDeque<DhtOpHash> = op_hashes.iter().cloned().collect();
+    while let Some(op_hash) = queue.pop_front() {
+        if seen_ops.contains(&op_hash) {
+            continue;
+        }
+        if seen_ops.len() >= max_ops {
+            graph.truncated = true;
+            break;
+        }
+        seen_ops.insert(op_hash.clone());
+
+        let row = op_stmt
+            .query_and_then(named_params! { ":hash": op_hash }, |row| {
+                let integrated = row
+                    .get::<_, Option<Timestamp>>("when_integrated")?
+                    .is_some();
+                let stage = limbo_stage(row.get("validation_stage")?);
+                let missing_dependencies: Vec<AnyDhtHash> = match row.get("missing_dependencies")? {
+                    Some(blob) => from_blob(blob)?,
+                    None => Vec::new(),
+                };
+                StateQueryResult::Ok((
+                    row.get::<_, DhtOpType>("type")?,
+                    integrated,
+                    stage,
+                    row.get::<_, Option<ValidationStatus>>("validation_status")?,
+                    missing_dependencies,
+                    row.get::<_, Option<ActionHash>>("dependency")?,
+                ))
+            })?
+            .next()
+            .transpose()?;
+        let Some((op_type, integrated, stage, validation_status, missing, dependency)) = row else {
+            graph.nodes.push(OpDependencyNode::MissingOp { op_hash });
+            continue;
+        };
+
+        let id = op_dependency_id(&op_hash);
+        graph.nodes.push(OpDependencyNode::Op {
+            op_hash,
+            op_type,
+            stage: (!integrated).then_some(stage),
+            validation_status,
+        });
+        if integrated {
+            continue;
+        }
+
+        let missing_kind = match stage {
+            ValidationLimboStage::AwaitingSysDeps => Some(OpDependencyKind::SysValidation),
+            ValidationLimboStage::AwaitingAppDeps => Some(OpDependencyKind::AppValidation),
+            _ => None,
+        };
+        let deps = missing_kind
+            .into_iter()
+            .flat_map(|kind| missing.iter().map(move |dep| (dep.clone(), kind)))
+            .chain(dependency.map(|dep| (dep.into(), OpDependencyKind::Integration)));
+        for (dep, kind) in deps {
+            graph.edges.push(OpDependencyEdge {
+                from: id.clone(),
+                to: op_dependency_id(&dep),
+                kind,
+            });
+            if !seen_deps.insert(dep.clone()) {
+                continue;
+            }
+            let held_by = held_by_stmt
+                .query_map(
+                    named_params! {
+                        ":hash": dep,
+                        ":store_entry": ChainOpType::StoreEntry,
+                    },
+                    |row| row.get::<_, DhtOpHash>(0),
+                )?
+                .collect::<Result<Vec<_>, _>>()?;
+            for held_by in held_by.iter() {
+                graph.edges.push(OpDependencyEdge {
+                    from: op_dependency_id(&dep),
+                    to: op_dependency_id(&held_by),
+                    kind: OpDependencyKind::HeldBy,
+                });
+            }
+            graph.nodes.push(OpDependencyNode::Dependency {
+                hash: dep,
+                held: !held_by.is_empty(),
+            });
+            queue.extend(held_by);
+        }
+    }
+    Ok(graph)
+}
+
+/// Reset the validation attempts of those of the given ops which are still awaiting
+/// sys or app validation, so that the next validation workflow runs try them first.
+///
+/// Returns the hashes of the ops which were reset.
+pub fn reset_validation_attempts(
+    txn: &mut Transaction,
+    op_hashes: &[DhtOpHash],
+) -> StateMutationResult<Vec<DhtOpHash>> {
+    let mut reset = Vec::new();
+    for op_hash in op_hashes {
+        let updated = txn.execute(
+            "
+            UPDATE DhtOp
+            SET num_validation_attempts = 0
+            WHERE hash = :hash
+            AND when_integrated IS NULL
+            AND validation_status IS NULL
+            ",
+            named_params! {
+                ":hash": op_hash,
+            },
+        )?;
+        if updated > 0 {
+            reset.push(op_hash.clone());
+        }
+    }
+    Ok(reset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutations::{insert_op, set_validation_stage, set_when_integrated};
+    use ::fixt::prelude::*;
+    use holo_hash::HasHash;
+    use holochain_types::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn limbo_lists_missing_dependencies_and_retries_reset_attempts() {
+        let db = crate::test_utils::test_dht_db().to_db();
+
+        let op = |action| {
+            DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(fixt!(Signature), action))
+        };
+        let awaiting = op(fixt!(Action));
+        let integrated = op(fixt!(Action));
+        let awaiting_hash = awaiting.as_hash().clone();
+        let integrated_hash = integrated.as_hash().clone();
+        let dep: AnyDhtHash = fixt!(ActionHash).into();
+
+        db.write_async({
+            let awaiting_hash = awaiting_hash.clone();
+            let integrated_hash = integrated_hash.clone();
+            let dep = dep.clone();
+            move |txn| -> StateMutationResult<()> {
+                insert_op(txn, &awaiting)?;
+                insert_op(txn, &integrated)?;
+                set_validation_stage(txn, &awaiting_hash, ValidationStage::Pending)?;
+                set_validation_stage(
+                    txn,
+                    &awaiting_hash,
+                    ValidationStage::AwaitingAppDeps(vec![dep]),
+                )?;
+                set_when_integrated(txn, &integrated_hash, Timestamp::now())?;
+                Ok(())
+            }
+        })
+        .await
+        .unwrap();
+
+        let limbo = db
+            .read_async(|txn| list_validation_limbo(&txn))
+            .await
+            .unwrap();
+        assert_eq!(limbo.len(), 1);
+        assert_eq!(limbo[0].op_hash, awaiting_hash);
+        assert_eq!(limbo[0].stage, ValidationLimboStage::AwaitingAppDeps);
+        assert_eq!(limbo[0].missing_dependencies, vec![dep]);
+        assert_eq!(limbo[0].num_validation_attempts, 2);
+        assert!(limbo[0].last_validation_attempt.is_some());
+
+        let reset = db
+            .write_async({
+                let op_hashes = vec![awaiting_hash.clone(), integrated_hash];
+                move |txn| reset_validation_attempts(txn, &op_hashes)
+            })
+            .await
+            .unwrap();
+        assert_eq!(reset, vec![awaiting_hash]);
+
+        let limbo = db
+            .read_async(|txn| list_validation_limbo(&txn))
+            .await
+            .unwrap();
+        assert_eq!(limbo[0].num_validation_attempts, 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn dependency_graph_follows_ops_holding_dependencies() {
+        let db = crate::test_utils::test_dht_db().to_db();
+
+        let op = |action| {
+            DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(fixt!(Signature), action))
+        };
+        // The stalled op waits for an action held by another op, which in turn
+        // waits for an action nobody holds.
+        let holder_action = fixt!(Action);
+        let held: AnyDhtHash = ActionHash::with_data_sync(&holder_action).into();
+        let unheld: AnyDhtHash = fixt!(ActionHash).into();
+        let stalled = op(fixt!(Action));
+        let holder = op(holder_action);
+        let stalled_hash = stalled.as_hash().clone();
+        let holder_hash = holder.as_hash().clone();
+        let missing_hash = fixt!(DhtOpHash);
+
+        db.write_async({
+            let stalled_hash = stalled_hash.clone();
+            let holder_hash = holder_hash.clone();
+            let held = held.clone();
+            let unheld = unheld.clone();
+            move |txn| -> StateMutationResult<()> {
+                insert_op(txn, &stalled)?;
+                insert_op(txn, &holder)?;
+                set_validation_stage(
+                    txn,
+                    &stalled_hash,
+                    ValidationStage::AwaitingAppDeps(vec![held]),
+                )?;
+                set_validation_stage(txn, &holder_hash, ValidationStage::AwaitingSysDeps(unheld))?;
+                Ok(())
+            }
+        })
+        .await
+        .unwrap();
+
+        let graph = db
+            .read_async({
+                let op_hashes = vec![stalled_hash.clone(), missing_hash.clone()];
+                move |txn| op_dependency_graph(&txn, &op_hashes, 100)
+            })
+            .await
+            .unwrap();
+        assert!(!graph.truncated);
+        let edge = |from: String, to: String, kind| OpDependencyEdge { from, to, kind };
+        for expected in [
+            edge(
+                op_dependency_id(&stalled_hash),
+                op_dependency_id(&held),
+                OpDependencyKind::AppValidation,
+            ),
+            edge(
+                op_dependency_id(&held),
+                op_dependency_id(&holder_hash),
+                OpDependencyKind::HeldBy,
+            ),
+            edge(
+                op_dependency_id(&holder_hash),
+                op_dependency_id(&unheld),
+                OpDependencyKind::SysValidation,
+            ),
+        ] {
+            assert!(graph.edges.contains(&expected), "missing {:?}", expected);
+        }
+        for expected in [
+            OpDependencyNode::Dependency {
+                hash: held,
+                held: true,
+            },
+            OpDependencyNode::Dependency {
+                hash: unheld,
+                held: false,
+            },
+            OpDependencyNode::MissingOp {
+                op_hash: missing_hash,
+            },
+        ] {
+            assert!(graph.nodes.contains(&expected), "missing {:?}", expected);
+        }
+
+        let graph = db
+            .read_async(move |txn| op_dependency_graph(&txn, &[stalled_hash], 1))
+            .await
+            .unwrap();
+        assert!(graph.truncated);
+    }
+}
@@ -1,12 +1,13 @@
 //! Module for items related to aggregating validation_receipts
 
+use holo_hash::ActionHash;
 use holo_hash::AgentPubKey;
 use holo_hash::DhtOpHash;
 use holochain_sqlite::prelude::*;
 use holochain_sqlite::rusqlite::named_params;
 use holochain_sqlite::rusqlite::OptionalExtension;
 use holochain_sqlite::rusqlite::Transaction;
-use holochain_types::prelude::{SignedValidationReceipt, ValidationReceipt};
+use holochain_types::prelude::{DhtOpType, SignedValidationReceipt, ValidationReceipt};
 use mutations::StateMutationResult;
 
 use crate::mutations;
@@ -45,6 +46,32 @@ pub fn count_valid(txn: &Transaction, op_hash: &DhtOpHash) -> DatabaseResult<usi
     Ok(count)
 }
 
+/// Count the validation receipts held for any of the given ops.
+pub fn count_valid_for_ops(txn: &Transaction, op_hashes: &[DhtOpHash]) -> DatabaseResult<usize> {
+    op_hashes
+        .iter()
+        .try_fold(0, |total, op_hash| Ok(total + count_valid(txn, op_hash)?))
+}
+
+/// List the hash and type of each op produced by an action.
+pub fn list_action_ops(
+    txn: &Transaction,
+    action_hash: &ActionHash,
+) -> StateQueryResult<Vec<(DhtOpHash, DhtOpType)>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT hash, type FROM DhtOp WHERE action_hash = :action_hash
+        ",
+    )?;
+    let iter = stmt.query_and_then(
+        named_params! {
+            ":action_hash": action_hash
+        },
+        |row| Ok((row.get("hash")?, row.get("type")?)),
+    )?;
+    iter.collect()
+}
+
 pub fn add_if_unique(
     txn: &mut Transaction,
     receipt: SignedValidationReceipt,
@@ -205,6 +232,51 @@ mod tests {
         assert!(pending.is_empty());
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn count_receipts_across_ops() {
+        holochain_trace::test_run();
+
+        let env = crate::test_utils::test_dht_db().to_db();
+        let keystore = test_keystore();
+
+        let op_1 = DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+            fixt!(Signature),
+            fixt!(Action),
+        ));
+        let op_2 = DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+            fixt!(Signature),
+            fixt!(Action),
+        ));
+        let op_hash_1 = op_1.as_hash().clone();
+        let op_hash_2 = op_2.as_hash().clone();
+        let vr1 = fake_vr(&op_hash_1, &keystore).await;
+        let vr2 = fake_vr(&op_hash_2, &keystore).await;
+
+        env.write_async(move |txn| -> StateMutationResult<()> {
+            mutations::insert_op(txn, &op_1)?;
+            mutations::insert_op(txn, &op_2)?;
+            add_if_unique(txn, vr1)?;
+            add_if_unique(txn, vr2)
+        })
+        .await
+        .unwrap();
+
+        let (none, one, both) = env
+            .read_async(move |txn| -> DatabaseResult<_> {
+                Ok((
+                    count_valid_for_ops(&txn, &[])?,
+                    count_valid_for_ops(&txn, &[op_hash_1.clone()])?,
+                    count_valid_for_ops(&txn, &[op_hash_1, op_hash_2])?,
+                ))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(0, none);
+        assert_eq!(1, one);
+        assert_eq!(2, both);
+    }
+
     async fn create_modified_op(
         vault: DbWrite<DbKindDht>,
         modifier: fn(txn: &mut Transaction, op_hash: HoloHashOf<DhtOp>) -> StateMutationResult<()>,
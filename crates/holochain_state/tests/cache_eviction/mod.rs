@@ -0,0 +1,84 @@
+use arbitrary::Arbitrary;
+use arbitrary::Unstructured;
+use holo_hash::*;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_state::mutations;
+use holochain_state::prelude::*;
+use std::time::Duration;
+
+fn dna_op(u: &mut Unstructured) -> (SignedActionHashed, DhtOpHash) {
+    let action = SignedActionHashed::with_presigned(
+        ActionHashed::from_content_sync(Action::Dna(Dna::arbitrary(u).unwrap())),
+        Signature::arbitrary(u).unwrap(),
+    );
+    (action, DhtOpHash::arbitrary(u).unwrap())
+}
+
+fn insert_op(txn: &mut Transaction, action: &SignedActionHashed, op_hash: &DhtOpHash) {
+    let timestamp = Timestamp::now();
+    let action_hash = action.as_hash().clone();
+    mutations::insert_action(txn, action).unwrap();
+    mutations::insert_op_lite(
+        txn,
+        &ChainOpLite::StoreRecord(action_hash.clone(), None, action_hash.into()).into(),
+        op_hash,
+        &OpOrder::new(ChainOpType::StoreRecord, timestamp),
+        &timestamp,
+    )
+    .unwrap();
+}
+
+fn count(txn: &Transaction, table: &str) -> usize {
+    txn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+        row.get(0)
+    })
+    .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn evicts_ops_received_before_the_cutoff_and_their_actions() {
+    let mut u = Unstructured::new(&NOISE);
+    let db = test_cache_db();
+    let (old_action, old_op) = dna_op(&mut u);
+    let (new_action, new_op) = dna_op(&mut u);
+    db.test_write({
+        let old_action = old_action.clone();
+        let old_op = old_op.clone();
+        let new_action = new_action.clone();
+        move |txn| {
+            insert_op(txn, &old_action, &old_op);
+            insert_op(txn, &new_action, &new_op);
+        }
+    });
+
+    let an_hour_ago = Timestamp::now().saturating_sub(&Duration::from_secs(60 * 60));
+    db.test_write(move |txn| {
+        txn.execute(
+            "UPDATE DhtOp SET when_received = :when_received WHERE hash = :hash",
+            named_params! {
+                ":when_received": an_hour_ago.saturating_sub(&Duration::from_secs(1)),
+                ":hash": old_op,
+            },
+        )
+        .unwrap();
+    });
+
+    let evicted = db.test_write(move |txn| mutations::evict_cached_ops(txn, an_hour_ago).unwrap());
+    assert_eq!(evicted, 1);
+
+    db.test_read(move |txn| {
+        assert_eq!(count(&txn, "DhtOp"), 1);
+        assert_eq!(count(&txn, "Action"), 1);
+        let exists = |hash: &ActionHash| -> bool {
+            txn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM Action WHERE hash = :hash)",
+                named_params! { ":hash": hash },
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert!(!exists(old_action.as_hash()));
+        assert!(exists(new_action.as_hash()));
+    });
+}
@@ -1,2 +1,3 @@
+pub mod cache_eviction;
 pub mod cache_tests;
 pub mod corrupt_db;
@@ -6,7 +6,7 @@ use holo_hash::AgentPubKey;
 use holochain_util::tokio_helper::block_on;
 use kitsune_p2p_bin_data::KitsuneAgent;
 use kitsune_p2p_bin_data::{KitsuneBinType, KitsuneSpace};
-use kitsune_p2p_bootstrap_client::{random, BootstrapNet};
+use kitsune_p2p_bootstrap_client::{random, BootstrapClient, BootstrapNet};
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
 use kitsune_p2p_types::bootstrap::{RandomLimit, RandomQuery};
 use ratatui::{prelude::*, widgets::*};
@@ -125,6 +125,7 @@ pub fn render_bootstrap_widget(
             async {
                 let network_type = { *get_network_type().read().unwrap() };
                 random(
+                    &BootstrapClient::default(),
                     Some(bootstrap_url.into()),
                     RandomQuery {
                         // TODO This conversion is defined but it's in holochain_p2p which shouldn't be a dep of this crate.
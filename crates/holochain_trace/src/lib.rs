@@ -82,11 +82,13 @@ use tracing_subscriber::{
     },
     layer::SubscriberExt,
     registry::LookupSpan,
+    reload,
     util::SubscriberInitExt,
     Layer, Registry,
 };
 
 use derive_more::Display;
+use once_cell::sync::OnceCell;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
@@ -136,6 +138,9 @@ pub enum Output {
 /// ParseError is a String
 pub type ParseError = String;
 
+/// Handle to swap the filter of the subscriber set up by [`init_fmt`].
+static FILTER_HANDLE: OnceCell<reload::Handle<EnvFilter, Registry>> = OnceCell::new();
+
 impl FromStr for Output {
     type Err = ParseError;
     fn from_str(day: &str) -> Result<Self, Self::Err> {
@@ -328,7 +333,8 @@ fn init_fmt_with_opts<W>(output: Output, writer: W) -> Result<(), errors::Tracin
 where
     W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
 {
-    let filter = standard_filter()?;
+    let (filter, filter_handle) = reload::Layer::new(standard_filter()?);
+    let reloadable = !matches!(output, Output::None | Output::OpenTel);
 
     println!(
         "Initialising log output formatting with option {:?}",
@@ -357,7 +363,9 @@ where
             )
             .init(),
 
-        Output::Log => Registry::default().with(standard_layer(writer)?).init(),
+        Output::Log => Registry::default()
+            .with(standard_layer_unfiltered(writer)?.with_filter(filter))
+            .init(),
 
         Output::LogTimed => Registry::default()
             .with(
@@ -420,9 +428,32 @@ where
         }
         Output::None => (),
     };
+    if reloadable {
+        FILTER_HANDLE.set(filter_handle).ok();
+    }
+    Ok(())
+}
+
+/// Replace the filter of the subscriber set up by [`init_fmt`] while it is running.
+/// The directives use the same format as `RUST_LOG` and replace the whole filter.
+/// Nothing is changed if any directive is invalid.
+pub fn set_filter(directives: &str) -> Result<(), errors::TracingError> {
+    let filter = EnvFilter::try_new(directives)?;
+    FILTER_HANDLE
+        .get()
+        .ok_or(errors::TracingError::FilterNotReloadable)?
+        .reload(filter)?;
     Ok(())
 }
 
+/// The directives of the filter currently used by the subscriber set up by [`init_fmt`],
+/// or `None` if logging wasn't set up with a filter that can be changed.
+pub fn current_filter() -> Option<String> {
+    FILTER_HANDLE
+        .get()
+        .and_then(|handle| handle.with_current(|filter| filter.to_string()).ok())
+}
+
 pub mod errors {
     //! Error in the tracing/logging framework
 
@@ -438,5 +469,9 @@ pub mod errors {
         TracingFlame,
         #[error(transparent)]
         BadDirective(#[from] tracing_subscriber::filter::ParseError),
+        #[error("Logging was not set up with a filter that can be changed")]
+        FilterNotReloadable,
+        #[error(transparent)]
+        Reload(#[from] tracing_subscriber::reload::Error),
     }
 }
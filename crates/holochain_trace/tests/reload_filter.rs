@@ -0,0 +1,23 @@
+use holochain_trace::errors::TracingError;
+use holochain_trace::*;
+
+#[test]
+fn set_filter_at_runtime() {
+    assert!(matches!(
+        set_filter("debug"),
+        Err(TracingError::FilterNotReloadable)
+    ));
+    assert_eq!(None, current_filter());
+
+    init_fmt(Output::Log).unwrap();
+
+    set_filter("holochain=debug").unwrap();
+    assert_eq!(Some("holochain=debug".to_string()), current_filter());
+
+    // An invalid directive leaves the filter as it was.
+    assert!(matches!(
+        set_filter("holochain=loud"),
+        Err(TracingError::BadDirective(_))
+    ));
+    assert_eq!(Some("holochain=debug".to_string()), current_filter());
+}
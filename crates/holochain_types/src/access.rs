@@ -1,4 +1,9 @@
-//! Defines HostFnAccess and Permission
+//! Defines HostFnAccess and Permission, and the host function classes
+//! which an app manifest can allow each zome to use.
+
+use holochain_zome_types::prelude::ZomeName;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 
 /// Access a call has to host functions
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -94,3 +99,227 @@ impl HostFnAccess {
         }
     }
 }
+
+/// A class of host functions which an app manifest can allow a zome to use.
+///
+/// Every host function belongs to exactly one class. The [`HostFnClass::Unrestricted`]
+/// ones, such as querying the source chain or getting zome info, can always be used.
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub enum HostFnClass {
+    /// Sending signals, queries and calls to other zomes, cells or agents,
+    /// or fetching blob chunks from them.
+    Network,
+    /// Writing actions to the source chain, or locking it for countersigning.
+    ChainWrites,
+    /// Signing, encryption and other use of the conductor's keystore.
+    Crypto,
+    /// Creating, enabling, disabling and deleting clone cells.
+    CloneManagement,
+    /// Getting records, links and agent activity from the DHT, which may fetch
+    /// them from other agents.
+    DhtReads,
+    /// Host functions every zome may use, which don't need to be allowed.
+    /// This includes `must_get_*`, so that validation can always read the ops
+    /// it depends on.
+    Unrestricted,
+}
+
+impl HostFnClass {
+    /// The class of a host function, by the name the wasm imports it as,
+    /// or `None` if it isn't a known host function.
+    pub fn of_host_fn(host_fn_name: &str) -> Option<Self> {
+        let name = host_fn_name.strip_prefix("__hc__").unwrap_or(host_fn_name);
+        // Drop the version suffix, e.g. `_1`.
+        let name = name
+            .rsplit_once('_')
+            .filter(|(_, version)| version.chars().all(|c| c.is_ascii_digit()))
+            .map_or(name, |(name, _)| name);
+        Some(match name {
+            "send_remote_signal"
+            | "remote_query"
+            | "call"
+            | "call_remote_durable"
+            | "get_blob_chunk"
+            | "block_agent"
+            | "unblock_agent" => Self::Network,
+            "create"
+            | "update"
+            | "delete"
+            | "create_link"
+            | "create_links"
+            | "delete_link"
+            | "delete_links"
+            | "ensure_entry"
+            | "ensure_link"
+            | "close_chain"
+            | "open_chain"
+            | "create_blob_chunk"
+            | "hold_blob_chunks"
+            | "release_blob_chunks"
+            | "renew_membrane_proof"
+            | "schedule"
+            | "accept_countersigning_preflight_request" => Self::ChainWrites,
+            "sign"
+            | "sign_ephemeral"
+            | "create_x25519_keypair"
+            | "x_salsa20_poly1305_shared_secret_create_random"
+            | "x_salsa20_poly1305_shared_secret_export"
+            | "x_salsa20_poly1305_shared_secret_ingest"
+            | "x_salsa20_poly1305_encrypt"
+            | "x_salsa20_poly1305_decrypt"
+            | "x_25519_x_salsa20_poly1305_encrypt"
+            | "x_25519_x_salsa20_poly1305_decrypt"
+            | "ed_25519_x_salsa20_poly1305_encrypt"
+            | "ed_25519_x_salsa20_poly1305_decrypt" => Self::Crypto,
+            "create_clone_cell" | "disable_clone_cell" | "enable_clone_cell"
+            | "delete_clone_cell" => Self::CloneManagement,
+            "get"
+            | "get_details"
+            | "get_dual_sign_acceptance"
+            | "get_links"
+            | "get_link_details"
+            | "get_links_delta"
+            | "count_links"
+            | "get_agent_activity"
+            | "get_action_by_seq"
+            | "get_entries_by_author" => Self::DhtReads,
+            "must_get_entry"
+            | "must_get_action"
+            | "must_get_valid_record"
+            | "must_get_agent_activity"
+            | "query"
+            | "agent_info"
+            | "dna_info"
+            | "zome_info"
+            | "call_info"
+            | "capability_claims"
+            | "capability_grants"
+            | "capability_info"
+            | "emit_signal"
+            | "entry_type_metrics"
+            | "hash"
+            | "network_time"
+            | "random_bytes"
+            | "sleep"
+            | "sys_time"
+            | "trace"
+            | "verify_signature"
+            | "version" => Self::Unrestricted,
+            _ => return None,
+        })
+    }
+}
+
+/// The classes of host functions which each zome of a DNA may use,
+/// as declared in an app manifest.
+///
+/// A zome which isn't listed may use no class of host functions.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub struct HostFnAllowlist(pub BTreeMap<ZomeName, BTreeSet<HostFnClass>>);
+
+impl HostFnAllowlist {
+    /// Whether the zome may use host functions of this class.
+    pub fn allows(&self, zome_name: &ZomeName, class: HostFnClass) -> bool {
+        class == HostFnClass::Unrestricted
+            || self
+                .0
+                .get(zome_name)
+                .map_or(false, |classes| classes.contains(&class))
+    }
+
+    /// Combine two allowlists for the same DNA, so that a zome may only use
+    /// the classes which both allow it.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self(
+            self.0
+                .iter()
+                .filter_map(|(zome_name, classes)| {
+                    let other = other.0.get(zome_name)?;
+                    Some((
+                        zome_name.clone(),
+                        classes.intersection(other).copied().collect(),
+                    ))
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_fn_classes() {
+        assert_eq!(
+            HostFnClass::of_host_fn("__hc__send_remote_signal_1"),
+            Some(HostFnClass::Network)
+        );
+        assert_eq!(
+            HostFnClass::of_host_fn("__hc__get_blob_chunk_1"),
+            Some(HostFnClass::Network)
+        );
+        assert_eq!(
+            HostFnClass::of_host_fn("__hc__create_link_1"),
+            Some(HostFnClass::ChainWrites)
+        );
+        assert_eq!(
+            HostFnClass::of_host_fn("__hc__x_salsa20_poly1305_encrypt_1"),
+            Some(HostFnClass::Crypto)
+        );
+        assert_eq!(
+            HostFnClass::of_host_fn("__hc__delete_clone_cell_1"),
+            Some(HostFnClass::CloneManagement)
+        );
+        assert_eq!(
+            HostFnClass::of_host_fn("__hc__get_links_1"),
+            Some(HostFnClass::DhtReads)
+        );
+        assert_eq!(
+            HostFnClass::of_host_fn("__hc__must_get_valid_record_1"),
+            Some(HostFnClass::Unrestricted)
+        );
+        assert_eq!(
+            HostFnClass::of_host_fn("__hc__dna_info_2"),
+            Some(HostFnClass::Unrestricted)
+        );
+        assert_eq!(HostFnClass::of_host_fn("__hc__not_a_host_fn_1"), None);
+    }
+
+    #[test]
+    fn allowlist_intersection() {
+        let zome = ZomeName::from("zome");
+        let a = HostFnAllowlist(
+            [(
+                zome.clone(),
+                [HostFnClass::Network, HostFnClass::Crypto].into(),
+            )]
+            .into(),
+        );
+        let b = HostFnAllowlist(
+            [(
+                zome.clone(),
+                [HostFnClass::Crypto, HostFnClass::ChainWrites].into(),
+            )]
+            .into(),
+        );
+        let both = a.intersection(&b);
+        assert!(both.allows(&zome, HostFnClass::Crypto));
+        assert!(!both.allows(&zome, HostFnClass::Network));
+        assert!(!both.allows(&zome, HostFnClass::ChainWrites));
+        assert!(!both.allows(&ZomeName::from("other"), HostFnClass::Crypto));
+        assert!(both.allows(&ZomeName::from("other"), HostFnClass::Unrestricted));
+    }
+}
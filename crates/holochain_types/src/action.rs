@@ -251,6 +251,12 @@ impl NewEntryActionRef<'_> {
             | NewEntryActionRef::Update(Update { entry_hash, .. }) => entry_hash,
         }
     }
+    pub fn author(&self) -> &AgentPubKey {
+        match self {
+            NewEntryActionRef::Create(Create { author, .. })
+            | NewEntryActionRef::Update(Update { author, .. }) => author,
+        }
+    }
     pub fn to_new_entry_action(&self) -> NewEntryAction {
         match self {
             NewEntryActionRef::Create(create) => NewEntryAction::Create((*create).to_owned()),
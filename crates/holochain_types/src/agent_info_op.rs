@@ -0,0 +1,32 @@
+//! Defines the AgentInfo variant of DhtOp
+
+use holochain_zome_types::prelude::*;
+
+/// An AgentInfo DhtOp, which publishes the signed network info of an agent to
+/// the authorities for the agent's key, so the agent can be found through the
+/// DHT when no bootstrap service can be reached.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub struct AgentInfoOp {
+    /// The agent the info is for, which is also the agent who signed it
+    pub agent: AgentPubKey,
+    /// The encoded agent info, exactly as it was signed by the agent
+    #[serde(with = "serde_bytes")]
+    pub agent_info: Vec<u8>,
+    /// signature of the encoded agent info by the agent
+    pub signature: Signature,
+    /// time when the agent info was signed, according to the agent's clock
+    pub signed_at: Timestamp,
+    /// time when the agent info expires, according to the agent's clock
+    pub expires_at: Timestamp,
+}
+
+impl AgentInfoOp {
+    /// Whether the agent info has expired at the given time.
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        self.expires_at <= now
+    }
+}
@@ -74,6 +74,15 @@ pub struct NetworkInfoRequestPayload {
     pub last_time_queried: Option<Timestamp>,
 }
 
+/// The instructions on how to request a [`PublicationProof`]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PublicationProofRequestPayload {
+    /// The cell which authored the action
+    pub cell_id: CellId,
+    /// The action to get the receipts for
+    pub action_hash: ActionHash,
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 /// The instructions on how to update coordinators for a dna file.
 pub struct UpdateCoordinatorsPayload {
@@ -142,6 +151,36 @@ pub struct InstallAppPayload {
     /// all Cells have DNAs with the same overridden DNA.
     pub network_seed: Option<NetworkSeed>,
 
+    /// Optional: the order in which the validation queues of each DNA should
+    /// process ops, keyed by the RoleName specified in the app bundle manifest.
+    /// DNAs which aren't listed keep the order they already have, which is
+    /// [`ValidationQueueOrder::OpType`] unless another app has set it.
+    #[serde(default)]
+    pub validation_queue_orders: HashMap<RoleName, ValidationQueueOrder>,
+
+    /// Optional: persist up to this many of the app's most recent signals, so
+    /// that clients can fetch the signals they missed while disconnected with
+    /// `AppRequest::FetchMissedSignals`. Signals are not persisted if not set.
+    #[serde(default)]
+    pub missed_signals_buffer_size: Option<u32>,
+
+    /// Optional: cancel zome calls to this app which run for longer than
+    /// this many milliseconds. A call can set its own timeout, which takes
+    /// precedence. Calls are not timed out if neither is set.
+    #[serde(default)]
+    pub zome_call_timeout_ms: Option<u64>,
+
+    /// Optional: what gets from this app do with data authored by agents
+    /// with outstanding valid warrants against them, when a get doesn't set
+    /// [`GetOptions::warranted_authors`] itself. Such data is included if not set.
+    #[serde(default)]
+    pub warranted_authors: Option<WarrantedAuthors>,
+
+    /// Optional: restrict which peers the cells of this app may send requests
+    /// to. Requests are not restricted if not set.
+    #[serde(default)]
+    pub egress_policy: Option<EgressPolicy>,
+
     /// Optional: If app installation fails due to genesis failure, normally the app will be
     /// immediately uninstalled. When this flag is set, the app is left installed with empty cells intact.
     /// This can be useful for using `graft_records_onto_source_chain`, or for diagnostics.
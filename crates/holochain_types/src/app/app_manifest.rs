@@ -42,10 +42,12 @@ use std::path::PathBuf;
 
 pub(crate) mod app_manifest_v1;
 pub mod app_manifest_validated;
+mod builder;
 mod current;
 mod error;
 
-pub use app_manifest_v1::{AppRoleDnaManifest, CellProvisioning};
+pub use app_manifest_v1::{AppRoleDnaManifest, CellProvisioning, CloneArchivalPolicy};
+pub use builder::AppManifestBuilder;
 pub use current::*;
 pub use error::*;
 
@@ -128,6 +130,8 @@ impl AppManifest {
                         modifiers: Default::default(),
                         installed_hash: Some(cell_id.dna_hash().clone().into()),
                         clone_limit: 256,
+                        host_fns: None,
+                        clone_archival: None,
                     },
                 }
             })
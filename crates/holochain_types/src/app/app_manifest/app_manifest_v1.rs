@@ -14,7 +14,7 @@ use super::{
     app_manifest_validated::{AppManifestValidated, AppRoleManifestValidated},
     error::{AppManifestError, AppManifestResult},
 };
-use crate::prelude::{RoleName, YamlProperties};
+use crate::prelude::{HostFnAllowlist, RoleName, YamlProperties};
 use holo_hash::DnaHashB64;
 use holochain_zome_types::prelude::*;
 use std::collections::HashMap;
@@ -72,6 +72,40 @@ impl AppRoleManifest {
             dna: AppRoleDnaManifest::sample(),
         }
     }
+
+    /// A role whose cells are created from the DNA bundled at `path`.
+    pub fn bundled(name: impl Into<RoleName>, path: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            provisioning: Some(CellProvisioning::default()),
+            dna: AppRoleDnaManifest {
+                location: Some(mr_bundle::Location::Bundled(path.into())),
+                modifiers: DnaModifiersOpt::none(),
+                installed_hash: None,
+                clone_limit: 0,
+                host_fns: None,
+                clone_archival: None,
+            },
+        }
+    }
+
+    /// Set how the cells of this role are provisioned.
+    pub fn with_provisioning(mut self, provisioning: CellProvisioning) -> Self {
+        self.provisioning = Some(provisioning);
+        self
+    }
+
+    /// Set how many clones of this role's cell may be created.
+    pub fn with_clone_limit(mut self, clone_limit: u32) -> Self {
+        self.dna.clone_limit = clone_limit;
+        self
+    }
+
+    /// Set the modifiers applied to this role's DNA.
+    pub fn with_modifiers(mut self, modifiers: DnaModifiersOpt<YamlProperties>) -> Self {
+        self.dna.modifiers = modifiers;
+        self
+    }
 }
 
 /// The DNA portion of an app role
@@ -113,6 +147,20 @@ pub struct AppRoleDnaManifest {
     /// Default: 0
     #[serde(default)]
     pub clone_limit: u32,
+
+    /// The classes of host functions which each zome of this DNA may use.
+    ///
+    /// If specified, a zome may only call host functions of the classes listed
+    /// for it, and a zome which isn't listed may use no class at all.
+    /// Default: every zome may use every host function.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_fns: Option<HostFnAllowlist>,
+
+    /// Automatically archive (disable) the clone cells of this role which
+    /// are no longer in use.
+    /// Default: clone cells are never archived automatically.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clone_archival: Option<CloneArchivalPolicy>,
 }
 
 impl AppRoleDnaManifest {
@@ -125,10 +173,39 @@ impl AppRoleDnaManifest {
             modifiers: DnaModifiersOpt::none(),
             installed_hash: None,
             clone_limit: 0,
+            host_fns: None,
+            clone_archival: None,
         }
     }
 }
 
+/// A policy for archiving the clone cells of a role which are no longer in use,
+/// to keep the resources used by apps which create many short-lived clones bounded.
+///
+/// Archived clone cells are disabled, and can be enabled again like any other
+/// disabled clone cell.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub struct CloneArchivalPolicy {
+    /// Archive a clone cell once nothing has been written to its source chain
+    /// for this many seconds.
+    pub archive_after_inactive_secs: u64,
+}
+
+impl CloneArchivalPolicy {
+    /// Whether a clone cell whose source chain was last written to at
+    /// `last_write` should be archived at `now`.
+    pub fn should_archive(&self, last_write: Timestamp, now: Timestamp) -> bool {
+        let inactive_micros = now.as_micros().saturating_sub(last_write.as_micros());
+        inactive_micros >= 0
+            && inactive_micros as u64 / 1_000_000 >= self.archive_after_inactive_secs
+    }
+}
+
 /// Specifies remote, local, or bundled location of DNA
 pub type DnaLocation = mr_bundle::Location;
 
@@ -208,6 +285,8 @@ impl AppManifestV1 {
                         installed_hash,
                         clone_limit,
                         modifiers,
+                        host_fns: _,
+                        clone_archival: _,
                     } = dna;
                     let modifiers = modifiers.serialized()?;
                     // Go from "flexible" enum into proper DnaVersionSpec.
@@ -298,6 +377,8 @@ pub mod tests {
                 modifiers,
                 installed_hash: Some(installed_hash.into()),
                 clone_limit: 50,
+                host_fns: None,
+                clone_archival: None,
             },
             provisioning: Some(CellProvisioning::Create { deferred: false }),
         }];
@@ -367,6 +448,70 @@ roles:
         }
     }
 
+    #[test]
+    fn manifest_v1_host_fns() {
+        let yaml = r#"---
+manifest_version: "1"
+name: "Test app"
+roles:
+  - name: "role_name"
+    dna:
+      path: /tmp/test.dna
+      host_fns:
+        posts: [chain_writes, network]
+        ui_helpers: []
+"#;
+        let manifest: AppManifest = serde_yaml::from_str(yaml).unwrap();
+        let AppManifest::V1(manifest) = manifest;
+        let host_fns = manifest.roles[0].dna.host_fns.clone().unwrap();
+        let posts = ZomeName::from("posts");
+        assert!(host_fns.allows(&posts, HostFnClass::ChainWrites));
+        assert!(host_fns.allows(&posts, HostFnClass::Network));
+        assert!(!host_fns.allows(&posts, HostFnClass::Crypto));
+        assert!(!host_fns.allows(&ZomeName::from("ui_helpers"), HostFnClass::Network));
+        assert!(!host_fns.allows(&ZomeName::from("unlisted"), HostFnClass::Network));
+    }
+
+    #[test]
+    fn manifest_v1_clone_archival() {
+        let yaml = r#"---
+manifest_version: "1"
+name: "Test app"
+roles:
+  - name: "role_name"
+    provisioning:
+      strategy: "clone_only"
+    dna:
+      path: /tmp/test.dna
+      clone_limit: 100
+      clone_archival:
+        archive_after_inactive_secs: 7776000
+"#;
+        let manifest: AppManifest = serde_yaml::from_str(yaml).unwrap();
+        let AppManifest::V1(manifest) = manifest;
+        let policy = manifest.roles[0].dna.clone_archival.clone().unwrap();
+        assert_eq!(
+            CloneArchivalPolicy {
+                archive_after_inactive_secs: 90 * 24 * 60 * 60
+            },
+            policy
+        );
+
+        let last_write = Timestamp::from_micros(1_000_000);
+        let day = 24 * 60 * 60 * 1_000_000;
+        assert!(!policy.should_archive(last_write, last_write));
+        assert!(!policy.should_archive(
+            last_write,
+            Timestamp::from_micros(last_write.as_micros() + 89 * day)
+        ));
+        assert!(policy.should_archive(
+            last_write,
+            Timestamp::from_micros(last_write.as_micros() + 90 * day)
+        ));
+        // A chain written to "in the future" is not inactive.
+        assert!(!policy.should_archive(last_write, Timestamp::from_micros(0)));
+    }
+
     #[tokio::test]
     async fn manifest_v1_set_network_seed() {
         let mut u = arbitrary::Unstructured::new(&[0]);
@@ -0,0 +1,127 @@
+//! A builder for app manifests, so that tools which package hApps can
+//! construct their manifests in Rust rather than templating YAML.
+
+use super::*;
+use std::collections::HashSet;
+
+/// Builds a manifest of the current version for an app, and checks that it's
+/// valid before returning it.
+///
+/// ```
+/// # use holochain_types::prelude::*;
+/// let manifest = AppManifestBuilder::new("forum")
+///     .description("A place to talk")
+///     .role(AppRoleManifest::bundled("forum", "forum.dna").with_clone_limit(10))
+///     .build()
+///     .unwrap();
+/// let yaml = serde_yaml::to_string(&manifest).unwrap();
+/// assert_eq!(manifest, serde_yaml::from_str(&yaml).unwrap());
+/// ```
+#[derive(Clone, Debug)]
+pub struct AppManifestBuilder {
+    name: String,
+    description: Option<String>,
+    roles: Vec<AppRoleManifest>,
+}
+
+impl AppManifestBuilder {
+    /// Start building the manifest of an app with this name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            roles: Vec::new(),
+        }
+    }
+
+    /// Set the description of the app.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a role to the app.
+    pub fn role(mut self, role: AppRoleManifest) -> Self {
+        self.roles.push(role);
+        self
+    }
+
+    /// Build the manifest.
+    ///
+    /// Fails if the app has no name, if two roles have the same name, or if
+    /// the manifest couldn't be installed, for example because a role which
+    /// creates its cells has no DNA location.
+    pub fn build(self) -> AppManifestResult<AppManifest> {
+        if self.name.trim().is_empty() {
+            return Err(AppManifestError::MissingField("name".to_string()));
+        }
+        let mut role_names = HashSet::new();
+        if let Some(role) = self
+            .roles
+            .iter()
+            .find(|role| !role_names.insert(&role.name))
+        {
+            return Err(AppManifestError::DuplicateRoleName(role.name.clone()));
+        }
+        let manifest: AppManifest = AppManifestCurrent {
+            name: self.name,
+            description: self.description,
+            roles: self.roles,
+        }
+        .into();
+        manifest.clone().validate()?;
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use matches::assert_matches;
+
+    #[test]
+    fn built_manifest_round_trips_through_yaml() {
+        let manifest = AppManifestBuilder::new("test_app")
+            .description("An app")
+            .role(AppRoleManifest::bundled("role1", "dna-1.dna"))
+            .role(
+                AppRoleManifest::bundled("role2", "dna-2.dna")
+                    .with_provisioning(CellProvisioning::CloneOnly)
+                    .with_clone_limit(5),
+            )
+            .build()
+            .unwrap();
+        let yaml = serde_yaml::to_string(&manifest).unwrap();
+        let parsed: AppManifest = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(manifest, parsed);
+
+        assert_eq!(manifest.app_name(), "test_app");
+        let roles = manifest.app_roles();
+        assert_eq!(roles.len(), 2);
+        assert_eq!(roles[1].dna.clone_limit, 5);
+    }
+
+    #[test]
+    fn invalid_manifests_are_not_built() {
+        assert_matches!(
+            AppManifestBuilder::new("").build(),
+            Err(AppManifestError::MissingField(_))
+        );
+        assert_matches!(
+            AppManifestBuilder::new("test_app")
+                .role(AppRoleManifest::bundled("role1", "dna-1.dna"))
+                .role(AppRoleManifest::bundled("role1", "dna-2.dna"))
+                .build(),
+            Err(AppManifestError::DuplicateRoleName(name)) if name == "role1"
+        );
+        assert_matches!(
+            AppManifestBuilder::new("test_app")
+                .role(
+                    AppRoleManifest::bundled("role1", "dna-1.dna")
+                        .with_provisioning(CellProvisioning::CloneOnly)
+                )
+                .build(),
+            Err(AppManifestError::InvalidStrategyCloneOnly(_))
+        );
+    }
+}
@@ -12,6 +12,9 @@ pub enum AppManifestError {
     #[error("Invalid manifest for app role '{0}': Using strategy 'clone-only' with clone_limit == 0 is pointless")]
     InvalidStrategyCloneOnly(RoleName),
 
+    #[error("Invalid app manifest: more than one role is named '{0}'")]
+    DuplicateRoleName(RoleName),
+
     #[error(transparent)]
     SerializationError(#[from] SerializedBytesError),
 }
@@ -25,6 +25,9 @@ pub enum AppError {
     #[error("Tried to access missing role name: '{0}'")]
     RoleNameMissing(RoleName),
 
+    #[error("Tried to access role '{0}', which has not been provisioned")]
+    RoleNotProvisioned(RoleName),
+
     #[error("Tried to install app '{0}' which contains duplicate role names. The following role names have duplicates: {1:?}")]
     DuplicateRoleNames(InstalledAppId, Vec<RoleName>),
 }
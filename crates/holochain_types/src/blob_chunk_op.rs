@@ -0,0 +1,54 @@
+//! Defines the BlobChunk variant of DhtOp
+
+use crate::dht_op::DhtOpUniqueForm;
+use holochain_zome_types::prelude::*;
+
+/// A BlobChunk DhtOp, which stores one chunk of a blob with the authorities
+/// for the hash of its bytes.
+///
+/// Chunks are content addressed: the op hash only depends on the chunk hash,
+/// so every author of the same bytes publishes the same op, and a reader
+/// which knows the chunk hash from a blob manifest can fetch the op by hash.
+///
+/// Every chunk is accounted for on its author's chain by the action which
+/// created the blob manifest listing it, which the authorities check the op
+/// against.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub struct BlobChunkOp {
+    /// The agent who published the chunk
+    pub author: AgentPubKey,
+    /// The hash of the bytes of the chunk
+    pub chunk_hash: EntryHash,
+    /// The action on the author's chain which created the blob manifest
+    /// listing the chunk
+    pub manifest: ActionHash,
+    /// The bytes of the chunk
+    #[serde(with = "serde_bytes")]
+    pub chunk: Vec<u8>,
+    /// signature of the chunk hash and the manifest action hash by the author
+    pub signature: Signature,
+    /// time when the chunk was published, according to the author's clock
+    pub timestamp: Timestamp,
+}
+
+impl BlobChunkOp {
+    /// The hash which addresses a chunk with these bytes.
+    pub fn chunk_hash_of(chunk: &[u8]) -> EntryHash {
+        EntryHash::from_raw_32(holo_hash::encode::blake2b_256(chunk))
+    }
+
+    /// The data the author signs for a chunk listed by the manifest created
+    /// by the action `manifest`.
+    pub fn signed_data(chunk_hash: &EntryHash, manifest: &ActionHash) -> Vec<u8> {
+        [chunk_hash.get_raw_39(), manifest.get_raw_39()].concat()
+    }
+
+    /// The hash of the op which stores the chunk with this hash.
+    pub fn op_hash(chunk_hash: &EntryHash) -> DhtOpHash {
+        DhtOpHash::with_data_sync(&DhtOpUniqueForm::BlobChunk(chunk_hash))
+    }
+}
@@ -54,6 +54,9 @@ pub trait ChainItem: Clone + PartialEq + Eq + std::fmt::Debug + Send + Sync {
     /// The hash of the previous item
     fn prev_hash(&self) -> Option<&Self::Hash>;
 
+    /// When the item was authored
+    fn timestamp(&self) -> Timestamp;
+
     /// A display representation of the item
     fn to_display(&self) -> String;
 }
@@ -76,6 +79,10 @@ impl ChainItem for ActionHashed {
         self.prev_action()
     }
 
+    fn timestamp(&self) -> Timestamp {
+        self.content.timestamp()
+    }
+
     fn to_display(&self) -> String {
         format!("{}", self.content)
     }
@@ -96,6 +103,10 @@ impl ChainItem for SignedActionHashed {
         self.hashed.prev_hash()
     }
 
+    fn timestamp(&self) -> Timestamp {
+        ChainItem::timestamp(&self.hashed)
+    }
+
     fn to_display(&self) -> String {
         format!("{}", self.hashed.content)
     }
@@ -109,13 +120,17 @@ impl ChainItem for SignedActionHashed {
 /// - Stop at the first gap in the chain.
 /// - Take no **more** then the [`take`]. It may return less.
 /// - Stop at (including) the [`ActionHash`] in [`until`]. But not if this hash is not in the chain.
+/// - Stop at (excluding) the first action authored before [`until_timestamp`].
 ///
 /// [`take`]: ChainFilter::take
 /// [`until`]: ChainFilter::until
+/// [`until_timestamp`]: ChainFilter::until_timestamp
 pub struct ChainFilterIter<I: AsRef<A>, A: ChainItem = SignedActionHashed> {
     filter: ChainFilter<A::Hash>,
     iter: Peekable<std::vec::IntoIter<I>>,
     end: bool,
+    /// The walk found the action before the until timestamp.
+    reached_until_timestamp: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -170,6 +185,23 @@ pub enum MustGetAgentActivityResponse {
     EmptyRange,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, SerializedBytes, Serialize, Deserialize)]
+/// Response to a `get_action_by_seq` call.
+pub struct ActionBySeqResponse {
+    /// The valid actions the authority holds at the requested position.
+    /// There is more than one if the author has forked their chain.
+    pub actions: Vec<SignedActionHashed>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, SerializedBytes, Serialize, Deserialize)]
+/// Response to a `get_entries_by_author` call.
+pub struct EntriesByAuthorResponse {
+    /// The valid actions which created the entries of the requested page, in
+    /// the order they were authored. The entries are held by their own
+    /// authorities rather than the author's agent activity authorities.
+    pub actions: Vec<SignedActionHashed>,
+}
+
 /// Identical structure to [`MustGetAgentActivityResponse`] except it includes
 /// the [`ChainFilterRange`] that was used to produce the response. Doesn't need
 /// to be serialized because it is only used internally.
@@ -266,6 +298,7 @@ impl<I: AsRef<A>, A: ChainItem> ChainFilterIter<I, A> {
             filter,
             iter,
             end: false,
+            reached_until_timestamp: false,
         }
     }
 }
@@ -336,6 +369,17 @@ impl<I: AsRef<A>, A: ChainItem> Iterator for ChainFilterIter<I, A> {
             }
         };
 
+        // Check if this action is before the until timestamp.
+        // It's the parent of the last action returned, so the walk is
+        // known to include everything from the until timestamp onwards.
+        if let Some(until_timestamp) = self.filter.until_timestamp {
+            if op.as_ref().timestamp() < until_timestamp {
+                self.end = true;
+                self.reached_until_timestamp = true;
+                return None;
+            }
+        }
+
         match &mut self.filter.filters {
             // Check if there is any left to take.
             ChainFilters::Take(n) => *n = n.checked_sub(1)?,
@@ -461,10 +505,21 @@ impl ChainFilterRange {
         let until_hashes = self.filter.get_until().cloned();
 
         // Create the filter iterator and collect the filtered actions.
-        let out: Vec<_> = ChainFilterIter::new(self.filter, chain).collect();
+        let mut iter = ChainFilterIter::new(self.filter, chain);
+        let out: Vec<_> = iter.by_ref().collect();
 
         // Check the invariants hold.
         match out.last().zip(out.first()) {
+            // The walk ended at the action before the until timestamp, which is
+            // within the range, so only the top of the range must match.
+            Some((_, highest))
+                if iter.reached_until_timestamp
+                    && highest.action.action().action_seq() == *self.range.end() =>
+            {
+                MustGetAgentActivityResponse::Activity(out)
+            }
+            // Even the chain top is before the until timestamp.
+            None if iter.reached_until_timestamp => MustGetAgentActivityResponse::EmptyRange,
             // The actual results after the filter must match the range.
             Some((lowest, highest))
                 if (lowest.action.action().action_seq()..=highest.action.action().action_seq())
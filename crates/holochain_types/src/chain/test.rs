@@ -49,8 +49,21 @@ fn can_until_hash(len: u32, chain_top: u32, until: TestHash) -> Vec<TestChainIte
     build_chain(chain(0..len), filter)
 }
 
+#[test_case(10, 9, Timestamp(0) => chain(0..10))]
+#[test_case(10, 9, Timestamp(5) => chain(5..10))]
+#[test_case(10, 5, Timestamp(5) => chain(5..6))]
+#[test_case(10, 4, Timestamp(5) => chain(0..0))]
+/// Check taking until some timestamp works.
+fn can_until_timestamp(len: u32, chain_top: u32, until: Timestamp) -> Vec<TestChainItem> {
+    let filter = TestFilter::new(hash(chain_top)).until_timestamp(until);
+    build_chain(chain(0..len), filter)
+}
+
 #[test_case(10, TestFilter::new(hash(9)).take(10).until(hash(4)) => chain(4..10))]
 #[test_case(10, TestFilter::new(hash(9)).take(2).until(hash(4)) => chain(8..10))]
+#[test_case(10, TestFilter::new(hash(9)).take(10).until_timestamp(Timestamp(6)).until(hash(4)) => chain(6..10))]
+#[test_case(10, TestFilter::new(hash(9)).take(2).until_timestamp(Timestamp(6)) => chain(8..10))]
+#[test_case(10, TestFilter::new(hash(9)).until_any([hash(2), hash(7)]) => chain(7..10))]
 #[test_case(10, TestFilter::new(hash(9)).take(20).take(2).until(hash(4)) => chain(8..10))]
 #[test_case(10, TestFilter::new(hash(9)).take(20).take(2).until(hash(4)).until(hash(9)) => chain(9..10))]
 /// Check take and until can be combined and the first to be
@@ -117,6 +130,21 @@ fn matches_chain(a: &Vec<RegisterAgentActivity>, seq: &[u32]) -> bool {
 #[test_case(
     forked_chain(&[4..6, 3..8]), ChainFilter::new(action_hash(&[5, 0])).until(action_hash(&[4, 1])), |h| if *h == action_hash(&[5, 0]) { Some(5) } else { Some(4) }
     => matches MustGetAgentActivityResponse::IncompleteChain ; "chain_top (5,0) until (4,1) chain (0,0) to (5,0) and (3,1) to (7,1)")]
+#[test_case(
+    chain(0..10), ChainFilter::new(action_hash(&[9])).until_timestamp(Timestamp(5)), hash_to_seq(&[9])
+    => matches MustGetAgentActivityResponse::Activity(a) if matches_chain(&a, &[9, 8, 7, 6, 5]) ; "chain_top 9 until timestamp 5 chain 0 to 9")]
+#[test_case(
+    chain(0..10), ChainFilter::new(action_hash(&[9])).until_timestamp(Timestamp(5)).take(2), hash_to_seq(&[9])
+    => matches MustGetAgentActivityResponse::Activity(a) if matches_chain(&a, &[9, 8]) ; "chain_top 9 until timestamp 5 take 2 chain 0 to 9")]
+#[test_case(
+    chain(0..10), ChainFilter::new(action_hash(&[3])).until_timestamp(Timestamp(5)), hash_to_seq(&[3])
+    => matches MustGetAgentActivityResponse::EmptyRange ; "chain_top 3 until timestamp 5 chain 0 to 9")]
+#[test_case(
+    gap_chain(&[0..4, 5..10]), ChainFilter::new(action_hash(&[9])).until_timestamp(Timestamp(6)), hash_to_seq(&[9])
+    => matches MustGetAgentActivityResponse::Activity(a) if matches_chain(&a, &[9, 8, 7, 6]) ; "chain_top 9 until timestamp 6 chain 0 to 3 then 5 to 10")]
+#[test_case(
+    gap_chain(&[0..4, 5..10]), ChainFilter::new(action_hash(&[9])).until_timestamp(Timestamp(3)), hash_to_seq(&[9])
+    => matches MustGetAgentActivityResponse::IncompleteChain ; "chain_top 9 until timestamp 3 chain 0 to 3 then 5 to 10")]
 fn test_filter_then_check(
     chain: Vec<TestChainItem>,
     filter: ChainFilter,
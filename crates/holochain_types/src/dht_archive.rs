@@ -0,0 +1,470 @@
+//! A portable archive of the ops held for a DNA, for exchanging DHT data with
+//! external archival systems and for seeding new nodes offline.
+//!
+//! The layout follows content-addressed archive formats such as CAR: a header,
+//! then a sequence of blocks which each hold one op, then an index from the hash
+//! of each op to its block.
+//!
+//! ```text
+//! magic | header len (u32 LE) | header | (block len (u32 LE) | block)* | index | index offset (u64 LE)
+//! ```
+//!
+//! The header, the blocks and the index are each encoded as msgpack. Blocks are
+//! checked against their hash whenever they are read, so an archive which has been
+//! corrupted or tampered with is rejected rather than loaded.
+
+use crate::prelude::*;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
+
+/// The bytes every DHT archive starts with.
+pub const DHT_ARCHIVE_MAGIC: &[u8; 8] = b"HCDHTAR\0";
+
+/// The version of the archive layout written by [`DhtArchiveWriter`].
+pub const DHT_ARCHIVE_VERSION: u32 = 1;
+
+/// The most bytes of an archive sent in one chunk when it is transferred over the admin API.
+pub const DHT_ARCHIVE_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Errors reading or writing a DHT archive.
+#[derive(Debug, thiserror::Error)]
+pub enum DhtArchiveError {
+    /// The bytes don't start with [`DHT_ARCHIVE_MAGIC`].
+    #[error("Not a DHT archive")]
+    NotAnArchive,
+
+    /// The archive was written with a layout this version can't read.
+    #[error("Unsupported DHT archive version {0}")]
+    UnsupportedVersion(u32),
+
+    /// The archive ends before a header, block or index which it refers to.
+    #[error("The DHT archive is truncated")]
+    Truncated,
+
+    /// A block doesn't hash to the hash it is indexed by.
+    #[error("The block for op {0} in the DHT archive doesn't match its hash")]
+    HashMismatch(DhtOpHash),
+
+    /// The archive holds ops for a different DNA than expected.
+    #[error("The DHT archive holds ops for DNA {found}, not {expected}")]
+    DnaMismatch {
+        /// The DNA the archive was expected to hold ops for.
+        expected: DnaHash,
+        /// The DNA the archive holds ops for.
+        found: DnaHash,
+    },
+
+    /// A header, block or index couldn't be encoded or decoded.
+    #[error(transparent)]
+    Serialization(#[from] SerializedBytesError),
+
+    /// A header, block or index is too large to be written into an archive.
+    #[error("A DHT archive can't hold a block of {0} bytes")]
+    TooLarge(usize),
+
+    /// A chunk of an archive being transferred was sent or asked for out of order.
+    #[error("Expected the DHT archive chunk at offset {expected}, not {found}")]
+    UnexpectedOffset {
+        /// The offset of the next chunk of the transfer.
+        expected: u64,
+        /// The offset of the chunk which was sent or asked for.
+        found: u64,
+    },
+
+    /// The archive couldn't be read or written.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Result type for DHT archives.
+pub type DhtArchiveResult<T> = Result<T, DhtArchiveError>;
+
+/// The header at the start of a DHT archive.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DhtArchiveHeader {
+    /// The layout version of the archive.
+    pub version: u32,
+    /// The DNA whose ops the archive holds.
+    pub dna_hash: DnaHash,
+    /// When the archive was written.
+    pub created_at: Timestamp,
+}
+
+/// Writes the ops held for a DNA into a DHT archive.
+///
+/// Each op is written out as soon as it is pushed, so only the index is held in
+/// memory while an archive is written into a file or other [`std::io::Write`].
+pub struct DhtArchiveWriter<W: std::io::Write = Vec<u8>> {
+    out: W,
+    offset: u64,
+    index: BTreeMap<DhtOpHash, (u64, u32)>,
+}
+
+impl DhtArchiveWriter {
+    /// Start an archive of the ops held for a DNA in memory.
+    pub fn new(dna_hash: DnaHash) -> DhtArchiveResult<Self> {
+        Self::new_at(dna_hash, Timestamp::now())
+    }
+
+    /// Start an archive of the ops held for a DNA in memory, with a given creation time.
+    /// Archives of the same ops created at the same time have the same bytes.
+    pub fn new_at(dna_hash: DnaHash, created_at: Timestamp) -> DhtArchiveResult<Self> {
+        Self::with_writer(Vec::new(), dna_hash, created_at)
+    }
+}
+
+impl<W: std::io::Write> DhtArchiveWriter<W> {
+    /// Start an archive of the ops held for a DNA, writing it into `out`.
+    pub fn with_writer(out: W, dna_hash: DnaHash, created_at: Timestamp) -> DhtArchiveResult<Self> {
+        let header = holochain_serialized_bytes::encode(&DhtArchiveHeader {
+            version: DHT_ARCHIVE_VERSION,
+            dna_hash,
+            created_at,
+        })?;
+        let mut writer = Self {
+            out,
+            offset: 0,
+            index: BTreeMap::new(),
+        };
+        writer.write(DHT_ARCHIVE_MAGIC)?;
+        writer.write(&len_u32(header.len())?.to_le_bytes())?;
+        writer.write(&header)?;
+        Ok(writer)
+    }
+
+    /// Add an op to the archive. An op which was already added is skipped.
+    pub fn push(&mut self, op: &DhtOpHashed) -> DhtArchiveResult<()> {
+        if self.index.contains_key(op.as_hash()) {
+            return Ok(());
+        }
+        let block = holochain_serialized_bytes::encode(op.as_content())?;
+        let block_len = len_u32(block.len())?;
+        self.write(&block_len.to_le_bytes())?;
+        self.index
+            .insert(op.as_hash().clone(), (self.offset, block_len));
+        self.write(&block)?;
+        Ok(())
+    }
+
+    /// Write the index and return the output the archive was written into.
+    pub fn finish(mut self) -> DhtArchiveResult<W> {
+        let index_offset = self.offset;
+        let index: Vec<(DhtOpHash, u64, u32)> = std::mem::take(&mut self.index)
+            .into_iter()
+            .map(|(hash, (offset, len))| (hash, offset, len))
+            .collect();
+        self.write(&holochain_serialized_bytes::encode(&index)?)?;
+        self.write(&index_offset.to_le_bytes())?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> DhtArchiveResult<()> {
+        self.out.write_all(bytes)?;
+        self.offset += bytes.len() as u64;
+        Ok(())
+    }
+}
+
+/// A DHT archive which has been opened for reading.
+///
+/// Opening an archive only reads its header and index. Each block is read,
+/// and checked against its hash, when the op it holds is asked for, so an
+/// archive opened from a file is never read into memory as a whole.
+pub struct DhtArchive {
+    source: DhtArchiveSource,
+    header: DhtArchiveHeader,
+    index: BTreeMap<DhtOpHash, Range<u64>>,
+}
+
+/// Where the bytes of an open archive are read from.
+enum DhtArchiveSource {
+    Bytes(Vec<u8>),
+    File {
+        file: std::sync::Mutex<std::fs::File>,
+        len: u64,
+    },
+}
+
+impl DhtArchiveSource {
+    fn len(&self) -> u64 {
+        match self {
+            Self::Bytes(bytes) => bytes.len() as u64,
+            Self::File { len, .. } => *len,
+        }
+    }
+
+    fn read(&self, range: Range<u64>) -> DhtArchiveResult<Cow<'_, [u8]>> {
+        if range.start > range.end || range.end > self.len() {
+            return Err(DhtArchiveError::Truncated);
+        }
+        match self {
+            Self::Bytes(bytes) => Ok(Cow::Borrowed(
+                &bytes[range.start as usize..range.end as usize],
+            )),
+            Self::File { file, .. } => {
+                let len = usize::try_from(range.end - range.start)
+                    .map_err(|_| DhtArchiveError::Truncated)?;
+                let mut buf = vec![0; len];
+                let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+                file.seek(SeekFrom::Start(range.start))?;
+                file.read_exact(&mut buf)?;
+                Ok(Cow::Owned(buf))
+            }
+        }
+    }
+
+    fn read_u32(&self, start: u64) -> DhtArchiveResult<u32> {
+        Ok(u32::from_le_bytes(
+            self.read(range(start, 4)?)?
+                .as_ref()
+                .try_into()
+                .map_err(|_| DhtArchiveError::Truncated)?,
+        ))
+    }
+
+    fn read_u64(&self, start: u64) -> DhtArchiveResult<u64> {
+        Ok(u64::from_le_bytes(
+            self.read(range(start, 8)?)?
+                .as_ref()
+                .try_into()
+                .map_err(|_| DhtArchiveError::Truncated)?,
+        ))
+    }
+}
+
+impl DhtArchive {
+    /// Open an archive held in memory, reading its header and index.
+    pub fn open(bytes: Vec<u8>) -> DhtArchiveResult<Self> {
+        Self::open_source(DhtArchiveSource::Bytes(bytes))
+    }
+
+    /// Open an archive in a file, reading its header and index.
+    /// Blocks are read from the file as the ops they hold are asked for.
+    pub fn open_file(file: std::fs::File) -> DhtArchiveResult<Self> {
+        let len = file.metadata()?.len();
+        Self::open_source(DhtArchiveSource::File {
+            file: std::sync::Mutex::new(file),
+            len,
+        })
+    }
+
+    fn open_source(source: DhtArchiveSource) -> DhtArchiveResult<Self> {
+        let magic_len = DHT_ARCHIVE_MAGIC.len() as u64;
+        if source.len() < magic_len || *source.read(0..magic_len)? != DHT_ARCHIVE_MAGIC[..] {
+            return Err(DhtArchiveError::NotAnArchive);
+        }
+        let header_len = source.read_u32(magic_len)?;
+        let header_range = range(magic_len + 4, header_len as u64)?;
+        let header_end = header_range.end;
+        let header: DhtArchiveHeader =
+            holochain_serialized_bytes::decode(&source.read(header_range)?)?;
+        if header.version != DHT_ARCHIVE_VERSION {
+            return Err(DhtArchiveError::UnsupportedVersion(header.version));
+        }
+
+        let trailer_start = source
+            .len()
+            .checked_sub(8)
+            .filter(|start| *start >= header_end)
+            .ok_or(DhtArchiveError::Truncated)?;
+        let index_offset = source.read_u64(trailer_start)?;
+        if index_offset < header_end || index_offset > trailer_start {
+            return Err(DhtArchiveError::Truncated);
+        }
+        let index: Vec<(DhtOpHash, u64, u32)> =
+            holochain_serialized_bytes::decode(&source.read(index_offset..trailer_start)?)?;
+        let index = index
+            .into_iter()
+            .map(|(hash, offset, len)| {
+                let block = range(offset, len as u64)?;
+                if block.start < header_end || block.end > index_offset {
+                    return Err(DhtArchiveError::Truncated);
+                }
+                Ok((hash, block))
+            })
+            .collect::<DhtArchiveResult<_>>()?;
+
+        Ok(Self {
+            source,
+            header,
+            index,
+        })
+    }
+
+    /// The header of the archive.
+    pub fn header(&self) -> &DhtArchiveHeader {
+        &self.header
+    }
+
+    /// The number of ops in the archive.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Whether the archive holds no ops.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// The hashes of the ops in the archive, in order.
+    pub fn hashes(&self) -> impl Iterator<Item = &DhtOpHash> {
+        self.index.keys()
+    }
+
+    /// Read an op from the archive by its hash.
+    pub fn get(&self, hash: &DhtOpHash) -> DhtArchiveResult<Option<DhtOpHashed>> {
+        self.index
+            .get(hash)
+            .map(|range| self.read_block(hash, range.clone()))
+            .transpose()
+    }
+
+    /// Read every op in the archive, failing if any of them doesn't match its hash.
+    pub fn ops(&self) -> DhtArchiveResult<Vec<DhtOpHashed>> {
+        self.iter().collect()
+    }
+
+    /// Read the ops in the archive one at a time, in the order of their hashes.
+    /// Each op is only decoded, and checked against its hash, when it is reached.
+    pub fn iter(&self) -> impl Iterator<Item = DhtArchiveResult<DhtOpHashed>> + '_ {
+        self.index
+            .iter()
+            .map(|(hash, range)| self.read_block(hash, range.clone()))
+    }
+
+    fn read_block(&self, hash: &DhtOpHash, range: Range<u64>) -> DhtArchiveResult<DhtOpHashed> {
+        let op: DhtOp = holochain_serialized_bytes::decode(&self.source.read(range)?)?;
+        let op = DhtOpHashed::from_content_sync(op);
+        if op.as_hash() != hash {
+            return Err(DhtArchiveError::HashMismatch(hash.clone()));
+        }
+        Ok(op)
+    }
+}
+
+fn range(start: u64, len: u64) -> DhtArchiveResult<Range<u64>> {
+    let end = start.checked_add(len).ok_or(DhtArchiveError::Truncated)?;
+    Ok(start..end)
+}
+
+fn len_u32(len: usize) -> DhtArchiveResult<u32> {
+    u32::try_from(len).map_err(|_| DhtArchiveError::TooLarge(len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use holo_hash::fixt::*;
+
+    fn op() -> DhtOpHashed {
+        DhtOpHashed::from_content_sync(DhtOp::from(ChainOp::RegisterAgentActivity(
+            fixt!(Signature),
+            Action::Dna(fixt!(Dna)),
+        )))
+    }
+
+    #[test]
+    fn archive_round_trip() {
+        let dna_hash = fixt!(DnaHash);
+        let ops = vec![op(), op(), op()];
+
+        let mut writer = DhtArchiveWriter::new(dna_hash.clone()).unwrap();
+        for op in &ops {
+            writer.push(op).unwrap();
+        }
+        // Duplicates are only written once.
+        writer.push(&ops[0]).unwrap();
+        let archive = DhtArchive::open(writer.finish().unwrap()).unwrap();
+
+        assert_eq!(archive.header().dna_hash, dna_hash);
+        assert_eq!(archive.len(), 3);
+        for op in &ops {
+            assert_eq!(archive.get(op.as_hash()).unwrap().as_ref(), Some(op));
+        }
+        assert_eq!(archive.get(&fixt!(DhtOpHash)).unwrap(), None);
+        assert_eq!(archive.ops().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn archive_round_trip_through_a_file() {
+        let dna_hash = fixt!(DnaHash);
+        let ops = vec![op(), op(), op()];
+
+        let file = tempfile::tempfile().unwrap();
+        let mut writer =
+            DhtArchiveWriter::with_writer(file, dna_hash.clone(), Timestamp::now()).unwrap();
+        for op in &ops {
+            writer.push(op).unwrap();
+        }
+        let file = writer.finish().unwrap();
+        let archive = DhtArchive::open_file(file).unwrap();
+
+        assert_eq!(archive.header().dna_hash, dna_hash);
+        assert_eq!(archive.len(), 3);
+        for op in &ops {
+            assert_eq!(archive.get(op.as_hash()).unwrap().as_ref(), Some(op));
+        }
+        assert_eq!(archive.ops().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn archive_rejects_corrupt_blocks() {
+        let op = op();
+        let mut writer = DhtArchiveWriter::new(fixt!(DnaHash)).unwrap();
+        writer.push(&op).unwrap();
+        let mut bytes = writer.finish().unwrap();
+
+        // Flip the last byte of the only block.
+        let archive = DhtArchive::open(bytes.clone()).unwrap();
+        let block_end = archive.index.values().next().unwrap().end as usize;
+        bytes[block_end - 1] ^= 0xff;
+
+        let archive = DhtArchive::open(bytes).unwrap();
+        assert!(archive.ops().is_err());
+    }
+
+    #[test]
+    fn archive_rejects_other_bytes() {
+        assert!(matches!(
+            DhtArchive::open(b"not an archive".to_vec()),
+            Err(DhtArchiveError::NotAnArchive)
+        ));
+
+        let mut bytes = DhtArchiveWriter::new(fixt!(DnaHash))
+            .unwrap()
+            .finish()
+            .unwrap();
+        bytes.truncate(bytes.len() - 4);
+        assert!(matches!(
+            DhtArchive::open(bytes),
+            Err(DhtArchiveError::Truncated)
+        ));
+    }
+
+    #[test]
+    fn archive_rejects_out_of_range_index() {
+        let bytes = DhtArchiveWriter::new(fixt!(DnaHash))
+            .unwrap()
+            .finish()
+            .unwrap();
+        let empty_index =
+            holochain_serialized_bytes::encode(&Vec::<(DhtOpHash, u64, u32)>::new()).unwrap();
+        let header_end = bytes.len() - 8 - empty_index.len();
+
+        // An index entry whose block would end past the end of the address space.
+        let mut bytes = bytes[..header_end].to_vec();
+        bytes.extend_from_slice(
+            &holochain_serialized_bytes::encode(&vec![(fixt!(DhtOpHash), u64::MAX, u32::MAX)])
+                .unwrap(),
+        );
+        bytes.extend_from_slice(&(header_end as u64).to_le_bytes());
+        assert!(matches!(
+            DhtArchive::open(bytes),
+            Err(DhtArchiveError::Truncated)
+        ));
+    }
+}
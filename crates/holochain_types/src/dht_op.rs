@@ -7,6 +7,8 @@
 use std::str::FromStr;
 
 use crate::action::NewEntryAction;
+use crate::agent_info_op::AgentInfoOp;
+use crate::blob_chunk_op::BlobChunkOp;
 use crate::prelude::*;
 use crate::record::RecordGroup;
 use crate::warrant::WarrantOp;
@@ -40,6 +42,10 @@ pub enum DhtOp {
     ChainOp(Box<ChainOp>),
     /// TODO, new type of op
     WarrantOp(Box<WarrantOp>),
+    /// An op publishing the signed network info of an agent.
+    AgentInfoOp(Box<AgentInfoOp>),
+    /// An op storing one chunk of a blob.
+    BlobChunkOp(Box<BlobChunkOp>),
 }
 
 /// A unit of DHT gossip concerning source chain data.
@@ -139,6 +145,18 @@ impl From<WarrantOp> for DhtOp {
     }
 }
 
+impl From<AgentInfoOp> for DhtOp {
+    fn from(op: AgentInfoOp) -> Self {
+        DhtOp::AgentInfoOp(Box::new(op))
+    }
+}
+
+impl From<BlobChunkOp> for DhtOp {
+    fn from(op: BlobChunkOp) -> Self {
+        DhtOp::BlobChunkOp(Box::new(op))
+    }
+}
+
 impl kitsune_p2p_dht::prelude::OpRegion for DhtOp {
     fn loc(&self) -> Loc {
         self.dht_basis().get_loc()
@@ -169,6 +187,10 @@ pub enum DhtOpLite {
     Chain(Box<ChainOpLite>),
     /// Note: WarrantOps are already "lite", as they only contain hashes
     Warrant(Box<WarrantOp>),
+    /// Note: AgentInfoOps are small enough to be kept whole
+    AgentInfo(Box<AgentInfoOp>),
+    /// The hash of the chunk stored by a BlobChunkOp, without its bytes
+    BlobChunk(EntryHash),
 }
 
 /// A type for storing in databases that doesn't need the actual
@@ -208,6 +230,18 @@ impl From<WarrantOp> for DhtOpLite {
     }
 }
 
+impl From<AgentInfoOp> for DhtOpLite {
+    fn from(op: AgentInfoOp) -> Self {
+        DhtOpLite::AgentInfo(Box::new(op))
+    }
+}
+
+impl From<BlobChunkOp> for DhtOpLite {
+    fn from(op: BlobChunkOp) -> Self {
+        DhtOpLite::BlobChunk(op.chunk_hash)
+    }
+}
+
 impl PartialEq for ChainOpLite {
     fn eq(&self, other: &Self) -> bool {
         // The ops are the same if they are the same type on the same action hash.
@@ -232,6 +266,17 @@ impl std::hash::Hash for ChainOpLite {
 pub enum DhtOpType {
     Chain(ChainOpType),
     Warrant(WarrantOpType),
+    #[from(ignore)]
+    AgentInfo,
+    #[from(ignore)]
+    BlobChunk,
+}
+
+impl DhtOpType {
+    /// The name of the agent info op type, as stored in the database.
+    pub const AGENT_INFO: &'static str = "AgentInfo";
+    /// The name of the blob chunk op type, as stored in the database.
+    pub const BLOB_CHUNK: &'static str = "BlobChunk";
 }
 
 impl ToSql for DhtOpType {
@@ -241,6 +286,12 @@ impl ToSql for DhtOpType {
         match self {
             DhtOpType::Chain(op) => op.to_sql(),
             DhtOpType::Warrant(op) => op.to_sql(),
+            DhtOpType::AgentInfo => Ok(holochain_sqlite::rusqlite::types::ToSqlOutput::Borrowed(
+                Self::AGENT_INFO.into(),
+            )),
+            DhtOpType::BlobChunk => Ok(holochain_sqlite::rusqlite::types::ToSqlOutput::Borrowed(
+                Self::BLOB_CHUNK.into(),
+            )),
         }
     }
 }
@@ -249,12 +300,18 @@ impl FromSql for DhtOpType {
     fn column_result(
         value: holochain_sqlite::rusqlite::types::ValueRef<'_>,
     ) -> holochain_sqlite::rusqlite::types::FromSqlResult<Self> {
-        String::column_result(value)
-            .and_then(|string| {
-                ChainOpType::from_str(&string)
-                    .map_err(|_| holochain_sqlite::rusqlite::types::FromSqlError::InvalidType)
-            })
-            .map(Into::into)
+        String::column_result(value).and_then(|string| {
+            if string == Self::AGENT_INFO {
+                return Ok(DhtOpType::AgentInfo);
+            }
+            if string == Self::BLOB_CHUNK {
+                return Ok(DhtOpType::BlobChunk);
+            }
+            ChainOpType::from_str(&string)
+                .map(Into::into)
+                .or_else(|_| WarrantOpType::from_str(&string).map(Into::into))
+                .map_err(|_| holochain_sqlite::rusqlite::types::FromSqlError::InvalidType)
+        })
     }
 }
 
@@ -352,6 +409,8 @@ impl DhtOp {
         match self {
             Self::ChainOp(op) => op.as_unique_form(),
             Self::WarrantOp(op) => DhtOpUniqueForm::Warrant(&op.warrant, &op.author, op.timestamp),
+            Self::AgentInfoOp(op) => DhtOpUniqueForm::AgentInfo(&op.agent, &op.agent_info),
+            Self::BlobChunkOp(op) => DhtOpUniqueForm::BlobChunk(&op.chunk_hash),
         }
     }
 
@@ -368,6 +427,8 @@ impl DhtOp {
         match self {
             Self::ChainOp(op) => DhtOpType::Chain(op.get_type()),
             Self::WarrantOp(op) => DhtOpType::Warrant(op.get_type()),
+            Self::AgentInfoOp(_) => DhtOpType::AgentInfo,
+            Self::BlobChunkOp(_) => DhtOpType::BlobChunk,
         }
     }
 
@@ -381,6 +442,8 @@ impl DhtOp {
         match self {
             Self::ChainOp(op) => op.signature(),
             Self::WarrantOp(op) => &op.signature,
+            Self::AgentInfoOp(op) => &op.signature,
+            Self::BlobChunkOp(op) => &op.signature,
         }
     }
 
@@ -388,6 +451,8 @@ impl DhtOp {
         match self {
             Self::ChainOp(op) => OpOrder::new(op.get_type(), op.timestamp()),
             Self::WarrantOp(op) => OpOrder::new(op.get_type(), op.timestamp),
+            Self::AgentInfoOp(op) => OpOrder::new(DhtOpType::AgentInfo, op.signed_at),
+            Self::BlobChunkOp(op) => OpOrder::new(DhtOpType::BlobChunk, op.timestamp),
         }
     }
 
@@ -396,6 +461,8 @@ impl DhtOp {
         match self {
             Self::ChainOp(op) => op.action().author().clone(),
             Self::WarrantOp(op) => op.author.clone(),
+            Self::AgentInfoOp(op) => op.agent.clone(),
+            Self::BlobChunkOp(op) => op.author.clone(),
         }
     }
 
@@ -404,6 +471,8 @@ impl DhtOp {
         match self {
             Self::ChainOp(op) => op.timestamp(),
             Self::WarrantOp(op) => op.timestamp,
+            Self::AgentInfoOp(op) => op.signed_at,
+            Self::BlobChunkOp(op) => op.timestamp,
         }
     }
 
@@ -412,6 +481,8 @@ impl DhtOp {
         match self {
             Self::ChainOp(op) => DhtOpLite::Chain(op.to_lite().into()),
             Self::WarrantOp(op) => DhtOpLite::Warrant(op.clone()),
+            Self::AgentInfoOp(op) => DhtOpLite::AgentInfo(op.clone()),
+            Self::BlobChunkOp(op) => DhtOpLite::BlobChunk(op.chunk_hash.clone()),
         }
     }
 
@@ -431,6 +502,7 @@ impl DhtOp {
                     }
                 },
             },
+            Self::AgentInfoOp(_) | Self::BlobChunkOp(_) => None,
         }
     }
 }
@@ -668,6 +740,8 @@ impl DhtOpLite {
         match self {
             Self::Chain(op) => op.dht_basis().clone(),
             Self::Warrant(op) => op.warrant.dht_basis(),
+            Self::AgentInfo(op) => op.agent.clone().into(),
+            Self::BlobChunk(chunk_hash) => chunk_hash.clone().into(),
         }
     }
 
@@ -684,6 +758,8 @@ impl DhtOpLite {
         match self {
             Self::Chain(op) => op.get_type().into(),
             Self::Warrant(op) => op.get_type().into(),
+            Self::AgentInfo(_) => DhtOpType::AgentInfo,
+            Self::BlobChunk(_) => DhtOpType::BlobChunk,
         }
     }
 
@@ -714,6 +790,8 @@ impl DhtOpLite {
                     }
                 },
             },
+            Self::AgentInfo(op) => op.agent.clone().into(),
+            Self::BlobChunk(chunk_hash) => chunk_hash.clone().into(),
         }
     }
 }
@@ -856,6 +934,10 @@ pub enum DhtOpUniqueForm<'a> {
     RegisterRemoveLink(&'a action::DeleteLink),
 
     Warrant(&'a Warrant, &'a AgentPubKey, Timestamp),
+
+    AgentInfo(&'a AgentPubKey, &'a [u8]),
+
+    BlobChunk(&'a EntryHash),
 }
 
 impl<'a> DhtOpUniqueForm<'a> {
@@ -878,6 +960,10 @@ impl<'a> DhtOpUniqueForm<'a> {
             DhtOpUniqueForm::RegisterRemoveLink(action) => action.base_address.clone(),
 
             DhtOpUniqueForm::Warrant(warrant, _, _) => warrant.dht_basis(),
+
+            DhtOpUniqueForm::AgentInfo(agent, _) => (*agent).clone().into(),
+
+            DhtOpUniqueForm::BlobChunk(chunk_hash) => (*chunk_hash).clone().into(),
         }
     }
 
@@ -1124,6 +1210,7 @@ pub fn action_to_op_types(action: &Action) -> Vec<ChainOpType> {
         | Action::OpenChain(_)
         | Action::CloseChain(_)
         | Action::AgentValidationPkg(_)
+        | Action::RenewMembraneProof(_)
         | Action::InitZomesComplete(_) => {
             vec![ChainOpType::StoreRecord, ChainOpType::RegisterAgentActivity]
         }
@@ -1244,6 +1331,14 @@ impl WireOps {
             WireOps::Record(o) => o.render(),
         }
     }
+
+    /// Whether the authority responded without any data for the hash.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            WireOps::Entry(o) => o.creates.is_empty() && o.entry.is_none(),
+            WireOps::Record(o) => o.action.is_none(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -1363,6 +1458,9 @@ impl OpOrder {
             DhtOpType::Warrant(WarrantOpType::ChainIntegrityWarrant) => {
                 OpNumericalOrder::ChainIntegrityWarrant
             }
+            // Agent info and blob chunk ops have no dependencies. They share the
+            // first slot, as the rendered order only has room for ten kinds of op.
+            DhtOpType::AgentInfo | DhtOpType::BlobChunk => OpNumericalOrder::RegisterAgentActivity,
         };
         Self { order, timestamp }
     }
@@ -1377,3 +1475,156 @@ impl holochain_sqlite::rusqlite::ToSql for OpOrder {
         ))
     }
 }
+
+/// The order in which a DNA's validation queues hand out ops to validate.
+///
+/// Ops which have been attempted fewer times always come first, whichever
+/// order is chosen, so that ops which keep failing can't hold up the rest.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationQueueOrder {
+    /// Validate ops by type and then oldest first (see [`OpOrder`]),
+    /// which gives the most likely ordering where dependencies come first.
+    #[default]
+    OpType,
+    /// Validate the oldest ops first, regardless of their type.
+    OldestFirst,
+    /// Validate the newest ops first, regardless of their type.
+    /// Useful for apps which care most about recent data being available.
+    NewestFirst,
+}
+
+/// What an op was validated with, so that ops which were validated under
+/// older validation rules can be found after a DNA's zomes change.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationProvenance {
+    /// The wasm hashes of the integrity zomes whose validation callbacks
+    /// were run for the op. Zomes which aren't wasm, such as inline zomes,
+    /// are left out.
+    pub integrity_wasm_hashes: Vec<WasmHash>,
+    /// The version of the conductor which ran the validation.
+    pub conductor_version: String,
+}
+
+/// How far an op which hasn't been integrated yet has got through validation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationLimboStage {
+    /// Waiting to be system validated.
+    Pending,
+    /// Waiting for dependencies before it can be system validated.
+    AwaitingSysDeps,
+    /// Waiting to be app validated.
+    SysValidated,
+    /// Waiting for dependencies before it can be app validated.
+    AwaitingAppDeps,
+    /// Validated and waiting to be integrated.
+    AwaitingIntegration,
+}
+
+/// An op in validation limbo, with what is known about why it hasn't been integrated yet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationLimboOp {
+    /// The hash of the op.
+    pub op_hash: DhtOpHash,
+    /// The type of the op.
+    pub op_type: DhtOpType,
+    /// The stage of validation the op has reached.
+    pub stage: ValidationLimboStage,
+    /// The hashes the op was waiting for the last time it was validated.
+    /// Empty unless the op is awaiting sys or app validation dependencies.
+    pub missing_dependencies: Vec<AnyDhtHash>,
+    /// The number of times the op has been through a validation workflow.
+    pub num_validation_attempts: u32,
+    /// When the op was last through a validation workflow, if it has been.
+    pub last_validation_attempt: Option<Timestamp>,
+}
+
+/// The dependencies of a set of ops, as a graph which can be rendered to debug
+/// validation stalls.
+///
+/// Ops point at the actions and entries they are waiting for, and those in turn
+/// point at the ops which hold them, so a chain of stalled ops can be followed
+/// back to whatever is holding it up.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpDependencyGraph {
+    /// The ops and dependencies in the graph.
+    pub nodes: Vec<OpDependencyNode>,
+    /// What each node is waiting for or held by.
+    pub edges: Vec<OpDependencyEdge>,
+    /// Whether the graph was cut short because it grew too large.
+    pub truncated: bool,
+}
+
+/// A node of an [`OpDependencyGraph`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OpDependencyNode {
+    /// An op held in the DHT database.
+    Op {
+        /// The hash of the op.
+        op_hash: DhtOpHash,
+        /// The type of the op.
+        op_type: DhtOpType,
+        /// The stage of validation the op has reached, or `None` if it has been integrated.
+        stage: Option<ValidationLimboStage>,
+        /// The outcome of validating the op, if it has been validated.
+        validation_status: Option<ValidationStatus>,
+    },
+    /// An op which was asked for but isn't held in the DHT database.
+    MissingOp {
+        /// The hash of the op.
+        op_hash: DhtOpHash,
+    },
+    /// An action or entry which ops are waiting for.
+    Dependency {
+        /// The hash of the action or entry.
+        hash: AnyDhtHash,
+        /// Whether any op in the DHT database holds the action or entry.
+        held: bool,
+    },
+}
+
+impl OpDependencyNode {
+    /// The id of the node, which edges refer to it by.
+    pub fn id(&self) -> String {
+        match self {
+            Self::Op { op_hash, .. } | Self::MissingOp { op_hash } => op_dependency_id(op_hash),
+            Self::Dependency { hash, .. } => op_dependency_id(hash),
+        }
+    }
+}
+
+/// The id of the [`OpDependencyNode`] for a hash.
+///
+/// This is the full base64 encoding of the hash, so ids stay unique however
+/// hashes are formatted for display.
+pub fn op_dependency_id<T: holo_hash::HashType>(hash: &holo_hash::HoloHash<T>) -> String {
+    holo_hash::holo_hash_encode(hash.get_raw_39())
+}
+
+/// An edge of an [`OpDependencyGraph`], between the ids of two nodes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpDependencyEdge {
+    /// The id of the node the edge starts at.
+    pub from: String,
+    /// The id of the node the edge ends at.
+    pub to: String,
+    /// What the edge means.
+    pub kind: OpDependencyKind,
+}
+
+/// What an [`OpDependencyEdge`] means.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpDependencyKind {
+    /// The op is waiting for the dependency before it can be system validated.
+    SysValidation,
+    /// The op is waiting for the dependency before it can be app validated.
+    AppValidation,
+    /// The op is waiting for the ops of the dependency, an action, to be integrated
+    /// before it can be integrated.
+    Integration,
+    /// The dependency is held by the op.
+    HeldBy,
+}
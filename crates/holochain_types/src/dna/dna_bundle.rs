@@ -143,7 +143,12 @@ impl DnaBundle {
         let manifest = Self::manifest_from_dna_def(dna.into_content())?;
         let resources = code
             .into_iter()
-            .map(|(hash, wasm)| (PathBuf::from(hash.to_string()), wasm.code.to_vec().into()))
+            .map(|(hash, wasm)| {
+                (
+                    PathBuf::from(holo_hash_encode(hash.get_raw_39())),
+                    wasm.code.to_vec().into(),
+                )
+            })
             .collect();
         DnaBundle::new(manifest.try_into()?, resources, PathBuf::from("."))
     }
@@ -162,8 +167,8 @@ impl DnaBundle {
                     .map(|name| ZomeDependency { name })
                     .collect();
                 zome.wasm_hash(&name).ok().map(|hash| {
+                    let filename = holo_hash_encode(hash.get_raw_39());
                     let hash = WasmHashB64::from(hash);
-                    let filename = format!("{}", hash);
                     ZomeManifest {
                         name,
                         hash: Some(hash),
@@ -186,8 +191,8 @@ impl DnaBundle {
                     .map(|name| ZomeDependency { name })
                     .collect();
                 zome.wasm_hash(&name).ok().map(|hash| {
+                    let filename = holo_hash_encode(hash.get_raw_39());
                     let hash = WasmHashB64::from(hash);
-                    let filename = format!("{}", hash);
                     ZomeManifest {
                         name,
                         hash: Some(hash),
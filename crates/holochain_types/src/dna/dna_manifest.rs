@@ -1,10 +1,13 @@
 use crate::prelude::*;
 use std::{collections::HashSet, path::PathBuf};
+mod builder;
 mod dna_manifest_v1;
 
 #[cfg(test)]
 mod test;
 
+pub use builder::DnaManifestBuilder;
+
 /// Re-export the current version. When creating a new version, just re-export
 /// the new version, and update the code accordingly.
 pub use dna_manifest_v1::{
@@ -0,0 +1,157 @@
+//! A builder for DNA manifests, so that tools which package DNAs can
+//! construct their manifests in Rust rather than templating YAML.
+
+use super::*;
+
+/// Builds a manifest of the current version for a DNA, and checks that it's
+/// valid before returning it.
+///
+/// ```
+/// # use holochain_types::prelude::*;
+/// let manifest = DnaManifestBuilder::new("forum")
+///     .network_seed("my-network")
+///     .integrity_zome(ZomeManifest::bundled("posts_integrity", "posts_integrity.wasm"))
+///     .coordinator_zome(
+///         ZomeManifest::bundled("posts", "posts.wasm").with_dependency("posts_integrity"),
+///     )
+///     .build()
+///     .unwrap();
+/// let yaml = serde_yaml::to_string(&manifest).unwrap();
+/// assert_eq!(manifest, serde_yaml::from_str(&yaml).unwrap());
+/// ```
+#[derive(Clone, Debug)]
+pub struct DnaManifestBuilder {
+    name: String,
+    network_seed: Option<String>,
+    properties: Option<YamlProperties>,
+    origin_time: Option<HumanTimestamp>,
+    integrity_zomes: Vec<ZomeManifest>,
+    coordinator_zomes: Vec<ZomeManifest>,
+}
+
+impl DnaManifestBuilder {
+    /// Start building the manifest of a DNA with this name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            network_seed: None,
+            properties: None,
+            origin_time: None,
+            integrity_zomes: Vec::new(),
+            coordinator_zomes: Vec::new(),
+        }
+    }
+
+    /// Set the network seed of the DNA.
+    pub fn network_seed(mut self, network_seed: impl Into<String>) -> Self {
+        self.network_seed = Some(network_seed.into());
+        self
+    }
+
+    /// Set the properties of the DNA.
+    pub fn properties(mut self, properties: YamlProperties) -> Self {
+        self.properties = Some(properties);
+        self
+    }
+
+    /// Set the origin time of the DNA's network.
+    ///
+    /// Defaults to the time the manifest is built, which changes the DNA hash
+    /// each time, so set it for builds which have to be reproducible.
+    pub fn origin_time(mut self, origin_time: impl Into<HumanTimestamp>) -> Self {
+        self.origin_time = Some(origin_time.into());
+        self
+    }
+
+    /// Add an integrity zome. Zomes are initialized in the order they're added.
+    pub fn integrity_zome(mut self, zome: ZomeManifest) -> Self {
+        self.integrity_zomes.push(zome);
+        self
+    }
+
+    /// Add a coordinator zome.
+    pub fn coordinator_zome(mut self, zome: ZomeManifest) -> Self {
+        self.coordinator_zomes.push(zome);
+        self
+    }
+
+    /// Build the manifest.
+    ///
+    /// Fails if the DNA has no name, if two zomes have the same name, or if a
+    /// zome depends on a zome which isn't one of the integrity zomes.
+    pub fn build(self) -> DnaResult<ValidatedDnaManifest> {
+        if self.name.trim().is_empty() {
+            return Err(DnaError::Invalid(
+                "the DNA manifest has no name".to_string(),
+            ));
+        }
+        DnaManifest::current(
+            self.name,
+            self.network_seed,
+            self.properties,
+            self.origin_time.unwrap_or_else(|| Timestamp::now().into()),
+            self.integrity_zomes,
+            self.coordinator_zomes,
+        )
+        .try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use matches::assert_matches;
+
+    fn builder() -> DnaManifestBuilder {
+        DnaManifestBuilder::new("test_dna")
+            .network_seed("seed")
+            .origin_time(Timestamp::from_micros(1_644_620_940_789_576))
+            .integrity_zome(ZomeManifest::bundled("zome1", "zome-1.wasm"))
+            .integrity_zome(
+                ZomeManifest::bundled("zome2", "nested/zome-2.wasm").with_dependency("zome1"),
+            )
+            .coordinator_zome(
+                ZomeManifest::bundled("zome3", "zome-3.wasm").with_dependency("zome2"),
+            )
+    }
+
+    #[test]
+    fn built_manifest_round_trips_through_yaml() {
+        let manifest = builder().build().unwrap();
+        let yaml = serde_yaml::to_string(&manifest).unwrap();
+        let parsed: ValidatedDnaManifest = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(manifest, parsed);
+
+        let DnaManifest::V1(m) = &manifest.0;
+        assert_eq!(m.name, "test_dna");
+        assert_eq!(m.integrity.network_seed.as_deref(), Some("seed"));
+        assert_eq!(
+            m.all_zomes()
+                .map(|z| z.name.to_string())
+                .collect::<Vec<_>>(),
+            vec!["zome1", "zome2", "zome3"]
+        );
+    }
+
+    #[test]
+    fn invalid_manifests_are_not_built() {
+        assert_matches!(
+            DnaManifestBuilder::new(" ").build(),
+            Err(DnaError::Invalid(_))
+        );
+        assert_matches!(
+            builder()
+                .coordinator_zome(ZomeManifest::bundled("zome1", "other.wasm"))
+                .build(),
+            Err(DnaError::DuplicateZomeNames(name)) if name == "zome1"
+        );
+        assert_matches!(
+            builder()
+                .coordinator_zome(
+                    ZomeManifest::bundled("zome4", "zome-4.wasm").with_dependency("zome3")
+                )
+                .build(),
+            Err(DnaError::DanglingZomeDependency(dep, zome)) if dep == "zome3" && zome == "zome4"
+        );
+    }
+}
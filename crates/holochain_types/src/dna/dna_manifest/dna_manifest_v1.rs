@@ -58,6 +58,34 @@ use serde_with::serde_as;
 ///     - name: zome4
 ///       bundled: ../dna2/zomes/zome2.wasm
 /// ```
+///
+/// An integrity zome can import the entry and link types of other integrity zomes
+/// by declaring them as dependencies, to share common types between integrity zomes.
+/// The imported types come after the zome's own types, in the order they are declared.
+/// Imported types are re-exported, so zomes which depend on the importing zome can
+/// use them too. They come after all of those zomes' declared dependencies.
+///
+/// ```yaml
+/// manifest_version: "1"
+/// name: composed integrity dna
+/// integrity:
+///   network_seed: 00000000-0000-0000-0000-000000000000
+///   properties: ~
+///   origin_time: 2022-02-11T23:05:19.470323Z
+///   zomes:
+///     - name: profiles
+///       bundled: ../dna1/zomes/profiles.wasm
+///     - name: posts
+///       bundled: ../dna1/zomes/posts.wasm
+///       dependencies:
+///         - name: profiles
+/// coordinator:
+///   zomes:
+///     - name: posts_api
+///       bundled: ../dna1/zomes/posts_api.wasm
+///       dependencies:
+///         - name: posts
+/// ```
 
 #[serde_as]
 #[derive(
@@ -151,7 +179,8 @@ pub struct ZomeManifest {
     #[serde(flatten)]
     pub location: ZomeLocation,
 
-    /// The integrity zomes this zome depends on.
+    /// The integrity zomes this zome depends on, or for an integrity
+    /// zome, the integrity zomes whose types it imports.
     /// The order of these must match the order the types
     /// are used in the zome.
     pub dependencies: Option<Vec<ZomeDependency>>,
@@ -175,6 +204,25 @@ pub struct ZomeDependency {
 pub type ZomeLocation = mr_bundle::Location;
 
 impl ZomeManifest {
+    /// A zome whose wasm is bundled at `path`, without any dependencies.
+    pub fn bundled(name: impl Into<ZomeName>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            hash: None,
+            location: ZomeLocation::Bundled(path.into()),
+            dependencies: None,
+            dylib: None,
+        }
+    }
+
+    /// Add an integrity zome which this zome depends on.
+    pub fn with_dependency(mut self, name: impl Into<ZomeName>) -> Self {
+        self.dependencies
+            .get_or_insert_with(Vec::new)
+            .push(ZomeDependency { name: name.into() });
+        self
+    }
+
     /// Accessor
     pub fn location(&self) -> &ZomeLocation {
         &self.location
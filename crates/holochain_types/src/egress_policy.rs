@@ -0,0 +1,114 @@
+//! Types for restricting which peers the cells of an app may send requests to.
+
+use crate::prelude::*;
+
+/// A peer, or set of peers, named by a rule of an [`EgressPolicy`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EgressPeer {
+    /// The peer with this agent key.
+    Agent(AgentPubKey),
+    /// The peers reachable at this network identity, which is matched against
+    /// the transport URLs in their agent info. A URL matches if it is equal to
+    /// the identity or if the identity is its last path segment, so either a
+    /// full URL or the bare id of a node can be given.
+    NetworkId(String),
+}
+
+impl EgressPeer {
+    /// Whether the peer with this agent key and these transport URLs is named.
+    pub fn matches(&self, agent: &AgentPubKey, urls: &[impl AsRef<str>]) -> bool {
+        match self {
+            EgressPeer::Agent(a) => a == agent,
+            EgressPeer::NetworkId(id) => urls.iter().any(|url| {
+                let url = url.as_ref().trim_end_matches('/');
+                url == id.as_str() || url.rsplit('/').next() == Some(id.as_str())
+            }),
+        }
+    }
+}
+
+/// Rules restricting which peers the cells of an app may send requests to,
+/// for deployments which must control where their data goes.
+///
+/// The rules apply to the requests a cell makes of specific peers, such as
+/// remote calls and signals, and to the peers chosen to serve gets. A request
+/// to a peer which isn't allowed is not sent, and is recorded in the
+/// conductor's event journal.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EgressPolicy {
+    /// If not empty, requests may only be sent to the peers named here.
+    #[serde(default)]
+    pub allow: Vec<EgressPeer>,
+    /// Requests may never be sent to the peers named here, even if they are
+    /// also allowed.
+    #[serde(default)]
+    pub deny: Vec<EgressPeer>,
+}
+
+impl EgressPolicy {
+    /// Whether any rule names peers by network identity, in which case the
+    /// transport URLs of a peer are needed to check a request to it.
+    pub fn has_network_id_rules(&self) -> bool {
+        self.allow
+            .iter()
+            .chain(self.deny.iter())
+            .any(|peer| matches!(peer, EgressPeer::NetworkId(_)))
+    }
+
+    /// Whether a request may be sent to the peer with this agent key and
+    /// these transport URLs.
+    pub fn allows(&self, agent: &AgentPubKey, urls: &[impl AsRef<str>]) -> bool {
+        let named = |peer: &EgressPeer| peer.matches(agent, urls);
+        !self.deny.iter().any(named) && (self.allow.is_empty() || self.allow.iter().any(named))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+
+    #[test]
+    fn deny_takes_precedence_over_allow() {
+        let alice = fixt!(AgentPubKey);
+        let bob = fixt!(AgentPubKey);
+        let no_urls: &[&str] = &[];
+
+        assert!(EgressPolicy::default().allows(&alice, no_urls));
+
+        let policy = EgressPolicy {
+            allow: vec![EgressPeer::Agent(alice.clone())],
+            deny: vec![],
+        };
+        assert!(policy.allows(&alice, no_urls));
+        assert!(!policy.allows(&bob, no_urls));
+
+        let policy = EgressPolicy {
+            allow: vec![EgressPeer::Agent(alice.clone())],
+            deny: vec![EgressPeer::Agent(alice.clone())],
+        };
+        assert!(!policy.allows(&alice, no_urls));
+    }
+
+    #[test]
+    fn network_id_matches_url_or_last_segment() {
+        let agent = fixt!(AgentPubKey);
+        let urls = ["wss://signal.example.com/abc123"];
+
+        let policy = EgressPolicy {
+            allow: vec![],
+            deny: vec![EgressPeer::NetworkId("abc123".into())],
+        };
+        assert!(policy.has_network_id_rules());
+        assert!(!policy.allows(&agent, &urls));
+        assert!(policy.allows(&agent, &["wss://signal.example.com/xyz789"]));
+
+        let policy = EgressPolicy {
+            allow: vec![EgressPeer::NetworkId(urls[0].into())],
+            deny: vec![],
+        };
+        assert!(policy.allows(&agent, &urls));
+        assert!(!policy.allows(&agent, &["wss://signal.example.com/abc1234"]));
+    }
+}
@@ -0,0 +1,107 @@
+//! Types for the conductor's event journal, a persistent record of the
+//! changes made to a conductor's configuration over its lifetime.
+
+use crate::prelude::*;
+use std::path::PathBuf;
+
+/// Something which happened to the conductor and was recorded in its event journal.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum ConductorEvent {
+    /// An app was installed.
+    AppInstalled {
+        /// The app which was installed.
+        installed_app_id: InstalledAppId,
+    },
+    /// An app was enabled.
+    AppEnabled {
+        /// The app which was enabled.
+        installed_app_id: InstalledAppId,
+    },
+    /// An app was disabled.
+    AppDisabled {
+        /// The app which was disabled.
+        installed_app_id: InstalledAppId,
+        /// Why the app was disabled.
+        reason: DisabledAppReason,
+    },
+    /// An app was uninstalled.
+    AppUninstalled {
+        /// The app which was uninstalled.
+        installed_app_id: InstalledAppId,
+    },
+    /// A cell was created, either while installing an app or by cloning.
+    CellCreated {
+        /// The cell which was created.
+        cell_id: CellId,
+    },
+    /// An app interface was attached.
+    AppInterfaceAttached {
+        /// The port the interface is listening on.
+        port: u16,
+        /// The app the interface is restricted to, if any.
+        installed_app_id: Option<InstalledAppId>,
+    },
+    /// Schema migrations were run on a database.
+    DbMigrated {
+        /// The path of the database which was migrated.
+        path: PathBuf,
+        /// The schema version before the migrations ran.
+        from_version: usize,
+        /// The schema version after the migrations ran.
+        to_version: usize,
+    },
+    /// A request a cell made was not sent to a peer because the cell's egress
+    /// policy doesn't allow it to contact that peer. Repeats of the same
+    /// request are only recorded once a minute.
+    EgressBlocked {
+        /// The cell which made the request.
+        cell_id: CellId,
+        /// The peer the request would have been sent to.
+        to_agent: AgentPubKey,
+        /// The kind of request, such as `call_remote` or `get`.
+        request: String,
+    },
+    /// A zome call was refused because its nonce could have been a replay.
+    ZomeCallNonceRejected {
+        /// The agent the zome call was made as.
+        agent: AgentPubKey,
+        /// Why the nonce was rejected.
+        reason: NonceRejection,
+        /// How many of the agent's nonces were rejected for this reason since
+        /// the previous event for them. Repeated rejections are only recorded
+        /// once a minute.
+        count: u32,
+    },
+    /// The conductor shut down because of an unrecoverable error.
+    Crashed {
+        /// The error which caused the shutdown.
+        error: String,
+        /// The task in which the error occurred.
+        context: String,
+    },
+}
+
+/// Why the nonce of a zome call was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NonceRejection {
+    /// The agent already made a call with this nonce, which hasn't expired yet.
+    Duplicate,
+    /// The nonce had already expired.
+    Expired,
+    /// The nonce expires too far in the future for the conductor to keep it
+    /// in its window.
+    Future,
+}
+
+/// An entry in the conductor's event journal.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct EventJournalEntry {
+    /// The position of this entry in the journal.
+    pub id: u64,
+    /// When the event happened.
+    pub timestamp: Timestamp,
+    /// What happened.
+    pub event: ConductorEvent,
+}
@@ -0,0 +1,384 @@
+//! Deterministic fixture data for tests.
+//!
+//! [`FixtureData::generate`] builds the source chains of a set of agents from a
+//! [`FixtureConfig`]. The same config always generates the same agents, actions,
+//! entries and links, so tests in different crates can share a dataset by sharing
+//! a seed rather than checking in databases. The data can be turned into ops,
+//! written to a [DHT archive](crate::dht_archive) or inserted into a database with
+//! `holochain_state`.
+//!
+//! Every chain starts with the genesis actions, followed by app entries which each
+//! have links from them, followed by entries countersigned by pairs of agents.
+//! Signatures are random bytes rather than real signatures, so the data is for
+//! tests which read and query stored data, not for tests of validation.
+
+use crate::dht_op::produce_ops_from_record;
+use crate::prelude::*;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+
+/// The timestamp of the first action of every generated chain.
+pub const FIXTURE_EPOCH: Timestamp = Timestamp(1_700_000_000_000_000);
+
+/// The time between consecutive actions of a generated chain, in microseconds.
+const FIXTURE_ACTION_INTERVAL_MICROS: i64 = 1_000_000;
+
+/// What to generate.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FixtureConfig {
+    /// The seed every hash, entry and link is derived from.
+    pub seed: u64,
+    /// The number of agents, each with their own chain.
+    pub agents: usize,
+    /// The number of app entries on each chain after genesis.
+    pub entries_per_agent: usize,
+    /// The number of links created from each app entry.
+    pub links_per_entry: usize,
+    /// The number of countersigned entries. Each is countersigned by two
+    /// agents in turn, so this needs at least two agents.
+    pub countersigned_entries: usize,
+    /// The size of the payload of each app entry, in bytes.
+    pub entry_size: usize,
+}
+
+impl Default for FixtureConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            agents: 3,
+            entries_per_agent: 10,
+            links_per_entry: 1,
+            countersigned_entries: 1,
+            entry_size: 64,
+        }
+    }
+}
+
+/// The chains generated from a [`FixtureConfig`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct FixtureData {
+    /// The DNA the chains are in.
+    pub dna_hash: DnaHash,
+    /// The agents, in the order their chains were generated.
+    pub agents: Vec<AgentPubKey>,
+    /// The chain of each agent, in the same order as [`FixtureData::agents`].
+    pub chains: Vec<Vec<Record>>,
+}
+
+impl FixtureData {
+    /// Generate the chains for a config.
+    ///
+    /// Panics if countersigned entries are asked for with fewer than two agents.
+    pub fn generate(config: &FixtureConfig) -> Self {
+        assert!(
+            config.countersigned_entries == 0 || config.agents >= 2,
+            "countersigned entries need at least two agents"
+        );
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let dna_hash = DnaHash::from_raw_32(rng.gen::<[u8; 32]>().to_vec());
+        let agents: Vec<AgentPubKey> = (0..config.agents)
+            .map(|_| AgentPubKey::from_raw_32(rng.gen::<[u8; 32]>().to_vec()))
+            .collect();
+        let mut chains: Vec<ChainBuilder> = agents
+            .iter()
+            .map(|agent| ChainBuilder::genesis(&mut rng, &dna_hash, agent.clone()))
+            .collect();
+
+        for chain in chains.iter_mut() {
+            for _ in 0..config.entries_per_agent {
+                let entry = Entry::App(random_app_entry(&mut rng, config.entry_size));
+                let entry_hash = EntryHash::with_data_sync(&entry);
+                chain.push(
+                    &mut rng,
+                    |author, timestamp, action_seq, prev_action| {
+                        Action::Create(Create {
+                            author,
+                            timestamp,
+                            action_seq,
+                            prev_action,
+                            entry_type: fixture_entry_type(),
+                            entry_hash: entry_hash.clone(),
+                            weight: Default::default(),
+                        })
+                    },
+                    Some(entry),
+                );
+                for _ in 0..config.links_per_entry {
+                    let target = EntryHash::from_raw_32(rng.gen::<[u8; 32]>().to_vec());
+                    let tag = LinkTag::new(rng.gen::<[u8; 8]>().to_vec());
+                    chain.push(
+                        &mut rng,
+                        |author, timestamp, action_seq, prev_action| {
+                            Action::CreateLink(CreateLink {
+                                author,
+                                timestamp,
+                                action_seq,
+                                prev_action,
+                                base_address: entry_hash.clone().into(),
+                                target_address: target.into(),
+                                zome_index: 0.into(),
+                                link_type: 0.into(),
+                                tag,
+                                weight: Default::default(),
+                            })
+                        },
+                        None,
+                    );
+                }
+            }
+        }
+
+        for i in 0..config.countersigned_entries {
+            let first = i % chains.len();
+            let second = (i + 1) % chains.len();
+            countersign(&mut rng, &mut chains, [first, second], config.entry_size);
+        }
+
+        Self {
+            dna_hash,
+            agents,
+            chains: chains.into_iter().map(|chain| chain.records).collect(),
+        }
+    }
+
+    /// Every record of every chain.
+    pub fn records(&self) -> impl Iterator<Item = &Record> {
+        self.chains.iter().flatten()
+    }
+
+    /// The ops produced by every record, in chain order.
+    pub fn ops(&self) -> Vec<DhtOpHashed> {
+        self.records()
+            .flat_map(|record| {
+                produce_ops_from_record(record).expect("Generated records are well formed")
+            })
+            .map(|op| DhtOpHashed::from_content_sync(DhtOp::from(op)))
+            .collect()
+    }
+
+    /// A DHT archive of every op, created at [`FIXTURE_EPOCH`] so that the
+    /// archive is the same every time.
+    pub fn to_dht_archive(&self) -> DhtArchiveResult<Vec<u8>> {
+        let mut writer = DhtArchiveWriter::new_at(self.dna_hash.clone(), FIXTURE_EPOCH)?;
+        for op in self.ops() {
+            writer.push(&op)?;
+        }
+        writer.finish()
+    }
+}
+
+struct ChainBuilder {
+    author: AgentPubKey,
+    records: Vec<Record>,
+}
+
+impl ChainBuilder {
+    fn genesis(rng: &mut StdRng, dna_hash: &DnaHash, author: AgentPubKey) -> Self {
+        let dna = Action::Dna(Dna {
+            author: author.clone(),
+            timestamp: FIXTURE_EPOCH,
+            hash: dna_hash.clone(),
+        });
+        let mut chain = Self {
+            author: author.clone(),
+            records: vec![random_signed_record(rng, dna, None)],
+        };
+        chain.push(
+            rng,
+            |author, timestamp, action_seq, prev_action| {
+                Action::AgentValidationPkg(AgentValidationPkg {
+                    author,
+                    timestamp,
+                    action_seq,
+                    prev_action,
+                    membrane_proof: None,
+                })
+            },
+            None,
+        );
+        chain.push(
+            rng,
+            |author, timestamp, action_seq, prev_action| {
+                Action::Create(Create {
+                    entry_hash: author.clone().into(),
+                    author,
+                    timestamp,
+                    action_seq,
+                    prev_action,
+                    entry_type: EntryType::AgentPubKey,
+                    weight: Default::default(),
+                })
+            },
+            Some(Entry::Agent(author)),
+        );
+        chain
+    }
+
+    fn top(&self) -> &Record {
+        self.records.last().expect("Chains start with genesis")
+    }
+
+    fn next_timestamp(&self) -> Timestamp {
+        Timestamp::from_micros(
+            self.top().action().timestamp().as_micros() + FIXTURE_ACTION_INTERVAL_MICROS,
+        )
+    }
+
+    fn push(
+        &mut self,
+        rng: &mut StdRng,
+        action: impl FnOnce(AgentPubKey, Timestamp, u32, ActionHash) -> Action,
+        entry: Option<Entry>,
+    ) {
+        let action = action(
+            self.author.clone(),
+            self.next_timestamp(),
+            self.top().action().action_seq() + 1,
+            self.top().action_address().clone(),
+        );
+        self.records.push(random_signed_record(rng, action, entry));
+    }
+}
+
+/// Add an entry countersigned by two agents to both of their chains.
+fn countersign(rng: &mut StdRng, chains: &mut [ChainBuilder], signers: [usize; 2], size: usize) {
+    let app_entry = random_app_entry(rng, size);
+    let start = signers
+        .iter()
+        .map(|i| chains[*i].next_timestamp())
+        .max()
+        .expect("There are two signers");
+    let session_times = CounterSigningSessionTimes::try_new(
+        start,
+        Timestamp::from_micros(start.as_micros() + 60 * FIXTURE_ACTION_INTERVAL_MICROS),
+    )
+    .expect("The session ends after it starts");
+    let preflight_request = PreflightRequest::try_new(
+        EntryHash::with_data_sync(&Entry::App(app_entry.clone())),
+        signers
+            .iter()
+            .map(|i| (chains[*i].author.clone(), vec![]))
+            .collect(),
+        vec![],
+        0,
+        false,
+        session_times,
+        ActionBase::Create(CreateBase::new(fixture_entry_type())),
+        PreflightBytes(vec![]),
+    )
+    .expect("The preflight request is well formed");
+    let responses = signers
+        .iter()
+        .enumerate()
+        .map(|(agent_index, i)| {
+            let top = chains[*i].top();
+            (
+                CounterSigningAgentState::new(
+                    agent_index as u8,
+                    top.action_address().clone(),
+                    top.action().action_seq(),
+                ),
+                random_signature(rng),
+            )
+        })
+        .collect();
+    let session_data = CounterSigningSessionData::try_new(preflight_request, responses, vec![])
+        .expect("The responses are in agent order");
+    let entry = Entry::CounterSign(Box::new(session_data.clone()), app_entry);
+    let actions = session_data
+        .build_action_set(EntryHash::with_data_sync(&entry), Default::default())
+        .expect("Every signer has a response");
+    for (i, action) in signers.into_iter().zip(actions) {
+        let record = random_signed_record(rng, action, Some(entry.clone()));
+        chains[i].records.push(record);
+    }
+}
+
+fn fixture_entry_type() -> EntryType {
+    EntryType::App(AppEntryDef::new(
+        0.into(),
+        0.into(),
+        EntryVisibility::Public,
+    ))
+}
+
+fn random_app_entry(rng: &mut StdRng, size: usize) -> AppEntryBytes {
+    let payload: Vec<u8> = (0..size).map(|_| rng.gen()).collect();
+    AppEntryBytes(SerializedBytes::from(UnsafeBytes::from(
+        holochain_serialized_bytes::encode(&serde_bytes::ByteBuf::from(payload))
+            .expect("Bytes can always be encoded"),
+    )))
+}
+
+fn random_signature(rng: &mut StdRng) -> Signature {
+    let mut signature = [0; SIGNATURE_BYTES];
+    rng.fill(&mut signature[..]);
+    Signature(signature)
+}
+
+fn random_signed_record(rng: &mut StdRng, action: Action, entry: Option<Entry>) -> Record {
+    Record::new(
+        SignedActionHashed::with_presigned(
+            ActionHashed::from_content_sync(action),
+            random_signature(rng),
+        ),
+        entry,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixture_data_is_deterministic() {
+        let config = FixtureConfig::default();
+        let data = FixtureData::generate(&config);
+        assert_eq!(data, FixtureData::generate(&config));
+        assert_eq!(
+            data.to_dht_archive().unwrap(),
+            FixtureData::generate(&config).to_dht_archive().unwrap()
+        );
+        assert_ne!(
+            data,
+            FixtureData::generate(&FixtureConfig {
+                seed: 1,
+                ..config.clone()
+            })
+        );
+    }
+
+    #[test]
+    fn fixture_chains_are_well_formed() {
+        let config = FixtureConfig {
+            agents: 2,
+            entries_per_agent: 3,
+            links_per_entry: 2,
+            countersigned_entries: 2,
+            ..Default::default()
+        };
+        let data = FixtureData::generate(&config);
+        for chain in &data.chains {
+            // Genesis, entries and their links, then both countersigned entries.
+            assert_eq!(3 + 3 * 3 + 2, chain.len());
+            for (seq, pair) in chain.windows(2).enumerate() {
+                assert_eq!(seq as u32 + 1, pair[1].action().action_seq());
+                assert_eq!(
+                    Some(pair[0].action_address()),
+                    pair[1].action().prev_action()
+                );
+                assert!(pair[0].action().timestamp() < pair[1].action().timestamp());
+            }
+            assert!(matches!(
+                chain.last().unwrap().entry().as_option(),
+                Some(Entry::CounterSign(..))
+            ));
+        }
+
+        let archive = DhtArchive::open(data.to_dht_archive().unwrap()).unwrap();
+        assert_eq!(data.dna_hash, archive.header().dna_hash);
+        assert_eq!(FIXTURE_EPOCH, archive.header().created_at);
+        assert!(!archive.is_empty());
+    }
+}
@@ -198,7 +198,14 @@ impl InlineZomeSet {
         (
             self.integrity_zomes
                 .into_iter()
-                .map(|(n, z)| IntegrityZome::new((*n).into(), z.into()))
+                .map(|(n, z)| {
+                    let mut z = IntegrityZome::new((*n).into(), z.into());
+                    let dep = self.dependencies.remove(z.zome_name());
+                    if let Some(dep) = dep {
+                        z.set_dependency(dep);
+                    }
+                    z
+                })
                 .collect(),
             self.coordinator_zomes
                 .into_iter()
@@ -214,10 +221,11 @@ impl InlineZomeSet {
         )
     }
 
-    /// Add a integrity dependency for a coordinator zome
+    /// Add a integrity dependency for a coordinator zome, or import the types
+    /// of an integrity zome into another integrity zome.
     pub fn with_dependency(mut self, from: &'static str, to: &'static str) -> Self {
         assert!(
-            self.coordinator_zomes.contains_key(from),
+            self.coordinator_zomes.contains_key(from) || self.integrity_zomes.contains_key(from),
             "{} -> {}",
             to,
             from
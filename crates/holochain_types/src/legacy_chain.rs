@@ -0,0 +1,821 @@
+//! Source chains written by the LMDB-era conductor, for migrating them into the
+//! current sqlite databases.
+//!
+//! Before the move to sqlite, each cell kept its source chain in an LMDB
+//! environment: every signed header was stored in a content-addressed table under
+//! its hash, and every entry in a public or private table under its hash. The
+//! types here mirror the msgpack encoding of those headers and entries, so that
+//! a legacy chain can be checked against its own hashes and signatures before it
+//! is converted.
+//!
+//! Converting a legacy chain produces a chain of current actions which reference
+//! each other by their new hashes. The new actions have to be signed again by the
+//! author, because the bytes that were signed have changed.
+
+use crate::prelude::*;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+/// Errors checking or converting a legacy source chain.
+#[derive(Debug, thiserror::Error)]
+pub enum LegacyChainError {
+    /// The chain has no headers.
+    #[error("The legacy source chain is empty")]
+    Empty,
+
+    /// The chain doesn't start with a `Dna` header.
+    #[error("The legacy source chain doesn't start with a Dna header")]
+    MissingDna,
+
+    /// A header doesn't follow the header before it.
+    #[error("Legacy header {0} at position {1} doesn't follow the previous header")]
+    BrokenChain(ActionHash, u32),
+
+    /// A header was authored by a different agent than the chain.
+    #[error("Legacy header {0} was authored by {1}, not the chain's author")]
+    WrongAuthor(ActionHash, AgentPubKey),
+
+    /// A header's signature doesn't verify against its author.
+    #[error("Legacy header {0} has an invalid signature")]
+    InvalidSignature(ActionHash),
+
+    /// A header refers to an entry which isn't in the chain.
+    #[error("Legacy header {0} refers to entry {1}, which is missing")]
+    MissingEntry(ActionHash, EntryHash),
+
+    /// A link was created in a zome which has no link type to convert it to.
+    #[error("Legacy link {0} was created in zome {1:?}, which has no link type to convert it to")]
+    MissingLinkType(ActionHash, ZomeIndex),
+
+    /// A signature couldn't be checked.
+    #[error(transparent)]
+    Keystore(#[from] holochain_keystore::KeystoreError),
+}
+
+/// Result type for legacy source chains.
+pub type LegacyChainResult<T> = Result<T, LegacyChainError>;
+
+/// A timestamp as it was encoded by the LMDB-era conductor: whole seconds and
+/// nanoseconds since the UNIX epoch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct LegacyTimestamp(pub i64, pub u32);
+
+impl From<LegacyTimestamp> for Timestamp {
+    fn from(LegacyTimestamp(secs, nanos): LegacyTimestamp) -> Self {
+        Timestamp::from_micros(secs * 1_000_000 + (nanos / 1_000) as i64)
+    }
+}
+
+/// The entry type of a legacy app entry.
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct LegacyAppEntryType {
+    pub id: EntryDefIndex,
+    pub zome_id: ZomeIndex,
+    pub visibility: EntryVisibility,
+}
+
+/// The type of an entry referred to by a legacy header.
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum LegacyEntryType {
+    AgentPubKey,
+    App(LegacyAppEntryType),
+    CapClaim,
+    CapGrant,
+}
+
+impl From<LegacyEntryType> for EntryType {
+    fn from(entry_type: LegacyEntryType) -> Self {
+        match entry_type {
+            LegacyEntryType::AgentPubKey => EntryType::AgentPubKey,
+            LegacyEntryType::App(LegacyAppEntryType {
+                id,
+                zome_id,
+                visibility,
+            }) => EntryType::App(AppEntryDef::new(id, zome_id, visibility)),
+            LegacyEntryType::CapClaim => EntryType::CapClaim,
+            LegacyEntryType::CapGrant => EntryType::CapGrant,
+        }
+    }
+}
+
+/// A legacy capability grant, which could only list the functions it grants.
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LegacyCapGrant {
+    pub tag: String,
+    pub access: CapAccess,
+    pub functions: BTreeSet<(ZomeName, FunctionName)>,
+}
+
+/// An entry in a legacy source chain.
+#[allow(missing_docs)]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+#[serde(tag = "entry_type", content = "entry")]
+pub enum LegacyEntry {
+    Agent(AgentPubKey),
+    App(AppEntryBytes),
+    CapClaim(CapClaim),
+    CapGrant(LegacyCapGrant),
+}
+
+impl HashableContent for LegacyEntry {
+    type HashType = holo_hash::hash_type::Entry;
+
+    fn hash_type(&self) -> Self::HashType {
+        holo_hash::hash_type::Entry
+    }
+
+    fn hashable_content(&self) -> HashableContentBytes {
+        match self {
+            LegacyEntry::Agent(agent_pubkey) => HashableContentBytes::Prehashed39(
+                EntryHash::from(agent_pubkey.clone()).into_inner(),
+            ),
+            entry => HashableContentBytes::Content(
+                entry
+                    .try_into()
+                    .expect("Could not serialize HashableContent"),
+            ),
+        }
+    }
+}
+
+impl From<LegacyEntry> for Entry {
+    fn from(entry: LegacyEntry) -> Self {
+        match entry {
+            LegacyEntry::Agent(agent) => Entry::Agent(agent),
+            LegacyEntry::App(bytes) => Entry::App(bytes),
+            LegacyEntry::CapClaim(claim) => Entry::CapClaim(claim),
+            LegacyEntry::CapGrant(LegacyCapGrant {
+                tag,
+                access,
+                functions,
+            }) => Entry::CapGrant(ZomeCallCapGrant::new(
+                tag,
+                access,
+                GrantedFunctions::Listed(functions),
+            )),
+        }
+    }
+}
+
+/// A header in a legacy source chain.
+///
+/// Legacy headers referred to each other by header hashes, which share their
+/// hash type with [`ActionHash`], and could only link entries.
+#[allow(missing_docs)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, SerializedBytes,
+)]
+#[serde(tag = "type")]
+pub enum LegacyHeader {
+    Dna {
+        author: AgentPubKey,
+        timestamp: LegacyTimestamp,
+        hash: DnaHash,
+    },
+    AgentValidationPkg {
+        author: AgentPubKey,
+        timestamp: LegacyTimestamp,
+        header_seq: u32,
+        prev_header: ActionHash,
+        membrane_proof: Option<MembraneProof>,
+    },
+    InitZomesComplete {
+        author: AgentPubKey,
+        timestamp: LegacyTimestamp,
+        header_seq: u32,
+        prev_header: ActionHash,
+    },
+    CreateLink {
+        author: AgentPubKey,
+        timestamp: LegacyTimestamp,
+        header_seq: u32,
+        prev_header: ActionHash,
+        base_address: EntryHash,
+        target_address: EntryHash,
+        zome_id: ZomeIndex,
+        tag: LinkTag,
+    },
+    DeleteLink {
+        author: AgentPubKey,
+        timestamp: LegacyTimestamp,
+        header_seq: u32,
+        prev_header: ActionHash,
+        base_address: EntryHash,
+        link_add_address: ActionHash,
+    },
+    OpenChain {
+        author: AgentPubKey,
+        timestamp: LegacyTimestamp,
+        header_seq: u32,
+        prev_header: ActionHash,
+        prev_dna_hash: DnaHash,
+    },
+    CloseChain {
+        author: AgentPubKey,
+        timestamp: LegacyTimestamp,
+        header_seq: u32,
+        prev_header: ActionHash,
+        new_dna_hash: DnaHash,
+    },
+    Create {
+        author: AgentPubKey,
+        timestamp: LegacyTimestamp,
+        header_seq: u32,
+        prev_header: ActionHash,
+        entry_type: LegacyEntryType,
+        entry_hash: EntryHash,
+    },
+    Update {
+        author: AgentPubKey,
+        timestamp: LegacyTimestamp,
+        header_seq: u32,
+        prev_header: ActionHash,
+        original_header_address: ActionHash,
+        original_entry_address: EntryHash,
+        entry_type: LegacyEntryType,
+        entry_hash: EntryHash,
+    },
+    Delete {
+        author: AgentPubKey,
+        timestamp: LegacyTimestamp,
+        header_seq: u32,
+        prev_header: ActionHash,
+        deletes_address: ActionHash,
+        deletes_entry_address: EntryHash,
+    },
+}
+
+holo_hash::impl_hashable_content!(LegacyHeader, Action);
+
+impl LegacyHeader {
+    /// The agent who authored the header.
+    pub fn author(&self) -> &AgentPubKey {
+        match self {
+            LegacyHeader::Dna { author, .. }
+            | LegacyHeader::AgentValidationPkg { author, .. }
+            | LegacyHeader::InitZomesComplete { author, .. }
+            | LegacyHeader::CreateLink { author, .. }
+            | LegacyHeader::DeleteLink { author, .. }
+            | LegacyHeader::OpenChain { author, .. }
+            | LegacyHeader::CloseChain { author, .. }
+            | LegacyHeader::Create { author, .. }
+            | LegacyHeader::Update { author, .. }
+            | LegacyHeader::Delete { author, .. } => author,
+        }
+    }
+
+    /// The position of the header in its chain.
+    pub fn header_seq(&self) -> u32 {
+        match self {
+            LegacyHeader::Dna { .. } => 0,
+            LegacyHeader::AgentValidationPkg { header_seq, .. }
+            | LegacyHeader::InitZomesComplete { header_seq, .. }
+            | LegacyHeader::CreateLink { header_seq, .. }
+            | LegacyHeader::DeleteLink { header_seq, .. }
+            | LegacyHeader::OpenChain { header_seq, .. }
+            | LegacyHeader::CloseChain { header_seq, .. }
+            | LegacyHeader::Create { header_seq, .. }
+            | LegacyHeader::Update { header_seq, .. }
+            | LegacyHeader::Delete { header_seq, .. } => *header_seq,
+        }
+    }
+
+    /// The hash of the previous header in the chain, if this isn't the first.
+    pub fn prev_header(&self) -> Option<&ActionHash> {
+        match self {
+            LegacyHeader::Dna { .. } => None,
+            LegacyHeader::AgentValidationPkg { prev_header, .. }
+            | LegacyHeader::InitZomesComplete { prev_header, .. }
+            | LegacyHeader::CreateLink { prev_header, .. }
+            | LegacyHeader::DeleteLink { prev_header, .. }
+            | LegacyHeader::OpenChain { prev_header, .. }
+            | LegacyHeader::CloseChain { prev_header, .. }
+            | LegacyHeader::Create { prev_header, .. }
+            | LegacyHeader::Update { prev_header, .. }
+            | LegacyHeader::Delete { prev_header, .. } => Some(prev_header),
+        }
+    }
+
+    /// The entry the header creates, if any.
+    pub fn entry_hash(&self) -> Option<&EntryHash> {
+        match self {
+            LegacyHeader::Create { entry_hash, .. } | LegacyHeader::Update { entry_hash, .. } => {
+                Some(entry_hash)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A legacy header with the signature of its author.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct LegacySignedHeader(pub LegacyHeader, pub Signature);
+
+/// A source chain read from the LMDB-era databases of a cell.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct LegacyChain {
+    /// The signed headers of the chain, in any order.
+    pub headers: Vec<LegacySignedHeader>,
+    /// The entries created by the headers, public and private, in any order.
+    pub entries: Vec<LegacyEntry>,
+}
+
+impl LegacyChain {
+    /// The agent who started the chain, from its `Dna` header.
+    pub fn author(&self) -> Option<&AgentPubKey> {
+        self.headers
+            .iter()
+            .find(|LegacySignedHeader(header, _)| matches!(header, LegacyHeader::Dna { .. }))
+            .map(|LegacySignedHeader(header, _)| header.author())
+    }
+
+    /// Check the chain against its own hashes and signatures.
+    ///
+    /// The headers must form a single chain, starting with a `Dna` header, in which
+    /// each header follows the one before it. Every header must be authored and
+    /// signed by `author`, and every entry a header creates must be present.
+    pub async fn verify(&self, author: &AgentPubKey) -> LegacyChainResult<()> {
+        let entries = self.entry_map();
+        let mut prev: Option<ActionHash> = None;
+        for (seq, LegacySignedHeader(header, signature)) in self.sorted_headers().enumerate() {
+            let hash = ActionHash::with_data_sync(header);
+            if seq == 0 && !matches!(header, LegacyHeader::Dna { .. }) {
+                return Err(LegacyChainError::MissingDna);
+            }
+            if header.header_seq() != seq as u32 || header.prev_header() != prev.as_ref() {
+                return Err(LegacyChainError::BrokenChain(hash, seq as u32));
+            }
+            if header.author() != author {
+                return Err(LegacyChainError::WrongAuthor(hash, header.author().clone()));
+            }
+            if !author.verify_signature(signature, header.clone()).await? {
+                return Err(LegacyChainError::InvalidSignature(hash));
+            }
+            if let Some(entry_hash) = header.entry_hash() {
+                if !entries.contains_key(entry_hash) {
+                    return Err(LegacyChainError::MissingEntry(hash, entry_hash.clone()));
+                }
+            }
+            prev = Some(hash);
+        }
+        if prev.is_none() {
+            return Err(LegacyChainError::Empty);
+        }
+        Ok(())
+    }
+
+    /// Convert the chain into current actions, each with the entry it creates.
+    ///
+    /// The chain should have been checked with [`LegacyChain::verify`] first.
+    /// The `Dna` action is given `dna_hash`, the DNA of the cell the chain is
+    /// being imported into. References from one header of the chain to another, or
+    /// to an entry of the chain, are rewritten to the new hashes. References to
+    /// data outside the chain are kept as they are. All actions are given the
+    /// default rate weight.
+    ///
+    /// Legacy links had no type, so each link is given the type `link_types` maps
+    /// its zome to. A link in a zome without a mapping is an error rather than being
+    /// given a type the zome may not mean.
+    pub fn into_actions(
+        self,
+        dna_hash: DnaHash,
+        link_types: &HashMap<ZomeIndex, LinkType>,
+    ) -> LegacyChainResult<Vec<(Action, Option<Entry>)>> {
+        let entries = self.entry_map();
+        let mut headers: Vec<LegacySignedHeader> = self.headers;
+        headers.sort_by_key(|LegacySignedHeader(header, _)| header.header_seq());
+
+        let mut action_hashes: HashMap<ActionHash, ActionHash> = HashMap::new();
+        let mut entry_hashes: HashMap<EntryHash, EntryHash> = HashMap::new();
+        let mut prev_action: Option<ActionHash> = None;
+        let mut out = Vec::with_capacity(headers.len());
+
+        for LegacySignedHeader(header, _) in headers {
+            let legacy_hash = ActionHash::with_data_sync(&header);
+            let entry = header
+                .entry_hash()
+                .map(|legacy_entry_hash| {
+                    // The same entry may be created by more than one header, so it is
+                    // cloned rather than taken.
+                    let entry: Entry = entries
+                        .get(legacy_entry_hash)
+                        .cloned()
+                        .ok_or_else(|| {
+                            LegacyChainError::MissingEntry(
+                                legacy_hash.clone(),
+                                legacy_entry_hash.clone(),
+                            )
+                        })?
+                        .into();
+                    entry_hashes
+                        .insert(legacy_entry_hash.clone(), EntryHash::with_data_sync(&entry));
+                    LegacyChainResult::Ok(entry)
+                })
+                .transpose()?;
+
+            let action = convert_header(
+                header,
+                &legacy_hash,
+                &dna_hash,
+                prev_action.take(),
+                &action_hashes,
+                &entry_hashes,
+                link_types,
+            )?;
+            let action_hash = ActionHash::with_data_sync(&action);
+            action_hashes.insert(legacy_hash, action_hash.clone());
+            prev_action = Some(action_hash);
+            out.push((action, entry));
+        }
+        Ok(out)
+    }
+
+    fn sorted_headers(&self) -> impl Iterator<Item = &LegacySignedHeader> {
+        let mut headers: Vec<_> = self.headers.iter().collect();
+        headers.sort_by_key(|LegacySignedHeader(header, _)| header.header_seq());
+        headers.into_iter()
+    }
+
+    fn entry_map(&self) -> HashMap<EntryHash, LegacyEntry> {
+        self.entries
+            .iter()
+            .map(|entry| (EntryHash::with_data_sync(entry), entry.clone()))
+            .collect()
+    }
+}
+
+fn convert_header(
+    header: LegacyHeader,
+    legacy_hash: &ActionHash,
+    dna_hash: &DnaHash,
+    prev_action: Option<ActionHash>,
+    action_hashes: &HashMap<ActionHash, ActionHash>,
+    entry_hashes: &HashMap<EntryHash, EntryHash>,
+    link_types: &HashMap<ZomeIndex, LinkType>,
+) -> LegacyChainResult<Action> {
+    let action_hash = |hash: ActionHash| action_hashes.get(&hash).cloned().unwrap_or(hash);
+    let entry_hash = |hash: EntryHash| entry_hashes.get(&hash).cloned().unwrap_or(hash);
+    // Only the Dna header has no previous header, and it doesn't use this.
+    let prev = || {
+        prev_action
+            .clone()
+            .expect("a chain which has been verified")
+    };
+
+    Ok(match header {
+        LegacyHeader::Dna {
+            author, timestamp, ..
+        } => Action::Dna(Dna {
+            author,
+            timestamp: timestamp.into(),
+            hash: dna_hash.clone(),
+        }),
+        LegacyHeader::AgentValidationPkg {
+            author,
+            timestamp,
+            header_seq,
+            membrane_proof,
+            ..
+        } => Action::AgentValidationPkg(AgentValidationPkg {
+            author,
+            timestamp: timestamp.into(),
+            action_seq: header_seq,
+            prev_action: prev(),
+            membrane_proof,
+        }),
+        LegacyHeader::InitZomesComplete {
+            author,
+            timestamp,
+            header_seq,
+            ..
+        } => Action::InitZomesComplete(InitZomesComplete {
+            author,
+            timestamp: timestamp.into(),
+            action_seq: header_seq,
+            prev_action: prev(),
+        }),
+        LegacyHeader::CreateLink {
+            author,
+            timestamp,
+            header_seq,
+            base_address,
+            target_address,
+            zome_id,
+            tag,
+            ..
+        } => Action::CreateLink(CreateLink {
+            author,
+            timestamp: timestamp.into(),
+            action_seq: header_seq,
+            prev_action: prev(),
+            base_address: entry_hash(base_address).into(),
+            target_address: entry_hash(target_address).into(),
+            zome_index: zome_id,
+            link_type: *link_types
+                .get(&zome_id)
+                .ok_or_else(|| LegacyChainError::MissingLinkType(legacy_hash.clone(), zome_id))?,
+            tag,
+            weight: RateWeight::default(),
+        }),
+        LegacyHeader::DeleteLink {
+            author,
+            timestamp,
+            header_seq,
+            base_address,
+            link_add_address,
+            ..
+        } => Action::DeleteLink(DeleteLink {
+            author,
+            timestamp: timestamp.into(),
+            action_seq: header_seq,
+            prev_action: prev(),
+            base_address: entry_hash(base_address).into(),
+            link_add_address: action_hash(link_add_address),
+        }),
+        LegacyHeader::OpenChain {
+            author,
+            timestamp,
+            header_seq,
+            prev_dna_hash,
+            ..
+        } => Action::OpenChain(OpenChain {
+            author,
+            timestamp: timestamp.into(),
+            action_seq: header_seq,
+            prev_action: prev(),
+            prev_dna_hash,
+        }),
+        LegacyHeader::CloseChain {
+            author,
+            timestamp,
+            header_seq,
+            new_dna_hash,
+            ..
+        } => Action::CloseChain(CloseChain {
+            author,
+            timestamp: timestamp.into(),
+            action_seq: header_seq,
+            prev_action: prev(),
+            new_dna_hash,
+        }),
+        LegacyHeader::Create {
+            author,
+            timestamp,
+            header_seq,
+            entry_type,
+            entry_hash: hash,
+            ..
+        } => Action::Create(Create {
+            author,
+            timestamp: timestamp.into(),
+            action_seq: header_seq,
+            prev_action: prev(),
+            entry_type: entry_type.into(),
+            entry_hash: entry_hash(hash),
+            weight: EntryRateWeight::default(),
+        }),
+        LegacyHeader::Update {
+            author,
+            timestamp,
+            header_seq,
+            original_header_address,
+            original_entry_address,
+            entry_type,
+            entry_hash: hash,
+            ..
+        } => Action::Update(Update {
+            author,
+            timestamp: timestamp.into(),
+            action_seq: header_seq,
+            prev_action: prev(),
+            original_action_address: action_hash(original_header_address),
+            original_entry_address: entry_hash(original_entry_address),
+            entry_type: entry_type.into(),
+            entry_hash: entry_hash(hash),
+            weight: EntryRateWeight::default(),
+        }),
+        LegacyHeader::Delete {
+            author,
+            timestamp,
+            header_seq,
+            deletes_address,
+            deletes_entry_address,
+            ..
+        } => Action::Delete(Delete {
+            author,
+            timestamp: timestamp.into(),
+            action_seq: header_seq,
+            prev_action: prev(),
+            deletes_address: action_hash(deletes_address),
+            deletes_entry_address: entry_hash(deletes_entry_address),
+            weight: RateWeight::default(),
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixt::*;
+    use ::fixt::prelude::*;
+    use holo_hash::fixt::*;
+
+    struct Builder {
+        keystore: MetaLairClient,
+        author: AgentPubKey,
+        headers: Vec<LegacySignedHeader>,
+        entries: Vec<LegacyEntry>,
+    }
+
+    impl Builder {
+        async fn new() -> Self {
+            let keystore = holochain_keystore::spawn_test_keystore().await.unwrap();
+            let author = keystore.new_sign_keypair_random().await.unwrap();
+            let mut builder = Self {
+                keystore,
+                author: author.clone(),
+                headers: Vec::new(),
+                entries: Vec::new(),
+            };
+            builder
+                .push(LegacyHeader::Dna {
+                    author,
+                    timestamp: LegacyTimestamp(1, 0),
+                    hash: fixt!(DnaHash),
+                })
+                .await;
+            builder
+        }
+
+        fn top(&self) -> (u32, ActionHash) {
+            let LegacySignedHeader(header, _) = self.headers.last().unwrap();
+            (header.header_seq() + 1, ActionHash::with_data_sync(header))
+        }
+
+        async fn push(&mut self, header: LegacyHeader) -> ActionHash {
+            let signature = self.author.sign(&self.keystore, &header).await.unwrap();
+            let hash = ActionHash::with_data_sync(&header);
+            self.headers.push(LegacySignedHeader(header, signature));
+            hash
+        }
+
+        async fn create(&mut self, entry: LegacyEntry) -> (ActionHash, EntryHash) {
+            let (header_seq, prev_header) = self.top();
+            let entry_hash = EntryHash::with_data_sync(&entry);
+            let entry_type = match &entry {
+                LegacyEntry::Agent(_) => LegacyEntryType::AgentPubKey,
+                LegacyEntry::App(_) => LegacyEntryType::App(LegacyAppEntryType {
+                    id: 0.into(),
+                    zome_id: 0.into(),
+                    visibility: EntryVisibility::Public,
+                }),
+                LegacyEntry::CapClaim(_) => LegacyEntryType::CapClaim,
+                LegacyEntry::CapGrant(_) => LegacyEntryType::CapGrant,
+            };
+            self.entries.push(entry);
+            let header = LegacyHeader::Create {
+                author: self.author.clone(),
+                timestamp: LegacyTimestamp(2, 500_000),
+                header_seq,
+                prev_header,
+                entry_type,
+                entry_hash: entry_hash.clone(),
+            };
+            (self.push(header).await, entry_hash)
+        }
+
+        fn chain(&self) -> LegacyChain {
+            LegacyChain {
+                headers: self.headers.iter().rev().cloned().collect(),
+                entries: self.entries.clone(),
+            }
+        }
+    }
+
+    async fn chain_with_link() -> Builder {
+        let mut builder = Builder::new().await;
+        let (_, base) = builder.create(LegacyEntry::App(fixt!(AppEntryBytes))).await;
+        let grant = LegacyEntry::CapGrant(LegacyCapGrant {
+            tag: "legacy".into(),
+            access: CapAccess::Unrestricted,
+            functions: [("zome".into(), "fn".into())].into_iter().collect(),
+        });
+        let (_, target) = builder.create(grant).await;
+        let (header_seq, prev_header) = builder.top();
+        let link = LegacyHeader::CreateLink {
+            author: builder.author.clone(),
+            timestamp: LegacyTimestamp(3, 0),
+            header_seq,
+            prev_header,
+            base_address: base,
+            target_address: target,
+            zome_id: 0.into(),
+            tag: LinkTag::new("tag"),
+        };
+        builder.push(link).await;
+        builder
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn verify_legacy_chain() {
+        let builder = chain_with_link().await;
+        builder.chain().verify(&builder.author).await.unwrap();
+
+        let other = fixt!(AgentPubKey);
+        assert!(matches!(
+            builder.chain().verify(&other).await,
+            Err(LegacyChainError::WrongAuthor(_, _))
+        ));
+
+        let mut missing_entry = builder.chain();
+        missing_entry.entries.pop();
+        assert!(matches!(
+            missing_entry.verify(&builder.author).await,
+            Err(LegacyChainError::MissingEntry(_, _))
+        ));
+
+        let mut broken = builder.chain();
+        broken.headers.remove(1);
+        assert!(matches!(
+            broken.verify(&builder.author).await,
+            Err(LegacyChainError::BrokenChain(_, _))
+        ));
+
+        let mut forged = builder.chain();
+        forged.headers[0].1 = fixt!(Signature);
+        assert!(matches!(
+            forged.verify(&builder.author).await,
+            Err(LegacyChainError::InvalidSignature(_))
+        ));
+
+        let empty = LegacyChain {
+            headers: Vec::new(),
+            entries: Vec::new(),
+        };
+        assert!(matches!(
+            empty.verify(&builder.author).await,
+            Err(LegacyChainError::Empty)
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn convert_legacy_chain() {
+        let builder = chain_with_link().await;
+        let dna_hash = fixt!(DnaHash);
+        let link_types = [(0.into(), LinkType(3))].into_iter().collect();
+        let actions = builder
+            .chain()
+            .into_actions(dna_hash.clone(), &link_types)
+            .unwrap();
+        assert_eq!(actions.len(), 4);
+
+        let (dna, _) = &actions[0];
+        assert_eq!(dna.action_type(), ActionType::Dna);
+        assert_eq!(dna.timestamp(), Timestamp::from_micros(1_000_000));
+        assert!(matches!(dna, Action::Dna(Dna { hash, .. }) if *hash == dna_hash));
+
+        // Every action follows the converted action before it.
+        for pair in actions.windows(2) {
+            assert_eq!(
+                pair[1].0.prev_action(),
+                Some(&ActionHash::with_data_sync(&pair[0].0))
+            );
+        }
+
+        // Entries keep their hash, except those whose shape has changed, and the
+        // link follows the new hashes.
+        let (create, app_entry) = &actions[1];
+        let app_entry = app_entry.clone().unwrap();
+        assert_eq!(
+            create.entry_hash(),
+            Some(&EntryHash::with_data_sync(&app_entry))
+        );
+        assert_eq!(create.entry_hash(), builder.headers[1].0.entry_hash());
+        let (grant, grant_entry) = &actions[2];
+        assert!(matches!(grant_entry, Some(Entry::CapGrant(_))));
+        assert_ne!(grant.entry_hash(), builder.headers[2].0.entry_hash());
+        match &actions[3].0 {
+            Action::CreateLink(link) => {
+                assert_eq!(
+                    link.base_address,
+                    create.entry_hash().unwrap().clone().into()
+                );
+                assert_eq!(
+                    link.target_address,
+                    grant.entry_hash().unwrap().clone().into()
+                );
+                assert_eq!(link.link_type, LinkType(3));
+            }
+            action => panic!("expected a link, got {action:?}"),
+        }
+
+        // A link in a zome without a link type isn't given one.
+        assert!(matches!(
+            builder.chain().into_actions(dna_hash, &HashMap::new()),
+            Err(LegacyChainError::MissingLinkType(_, zome)) if zome == 0.into()
+        ));
+    }
+}
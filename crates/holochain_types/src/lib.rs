@@ -15,16 +15,22 @@
 pub mod access;
 pub mod action;
 pub mod activity;
+pub mod agent_info_op;
 pub mod app;
 pub mod autonomic;
+pub mod blob_chunk_op;
 pub mod chain;
 pub mod chc;
 pub mod combinators;
 pub mod db;
 pub mod db_cache;
+pub mod dht_archive;
 pub mod dht_op;
 pub mod dna;
+pub mod egress_policy;
 pub mod entry;
+pub mod event_journal;
+pub mod legacy_chain;
 pub mod link;
 mod macros;
 pub mod metadata;
@@ -46,6 +52,9 @@ pub mod fixt;
 #[cfg(feature = "fuzzing")]
 pub mod facts;
 
+#[cfg(feature = "fixture_data")]
+pub mod fixture_data;
+
 #[cfg(feature = "test_utils")]
 pub mod inline_zome;
 #[cfg(feature = "test_utils")]
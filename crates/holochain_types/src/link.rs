@@ -22,12 +22,23 @@ pub struct WireLinkKey {
     pub type_query: LinkTypeFilter,
     /// Optionally specify a tag for more specific queries.
     pub tag: Option<LinkTag>,
+    /// Optionally specify a range of tags, from the start tag inclusive
+    /// to the end tag exclusive.
+    #[serde(default)]
+    pub tag_range: Option<(LinkTag, LinkTag)>,
+    /// Only get links whose tag carries a payload matching all of these filters.
+    #[serde(default)]
+    pub tag_fields: Vec<LinkTagFieldFilter>,
     /// Specify a minimum action timestamp to filter results.
     pub after: Option<Timestamp>,
     /// Specify a maximum action timestamp to filter results.
     pub before: Option<Timestamp>,
     /// Only get links created by this author.
     pub author: Option<AgentPubKey>,
+    /// Only get link ops which the authority integrated at or after this
+    /// timestamp. Unlike `after`, this applies to deletes as well as creates.
+    #[serde(default)]
+    pub since: Option<Timestamp>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes, Default)]
@@ -246,6 +257,10 @@ pub struct WireLinkQuery {
     /// Filter by tag prefix.
     pub tag_prefix: Option<LinkTag>,
 
+    /// Filter by a range of tags, from the start tag inclusive to the end tag exclusive.
+    #[serde(default)]
+    pub tag_range: Option<(LinkTag, LinkTag)>,
+
     /// Only include links created before this time.
     pub before: Option<Timestamp>,
 
@@ -10,17 +10,23 @@ pub use std::convert::TryInto;
 pub use crate::access::*;
 pub use crate::action::*;
 pub use crate::activity::*;
+pub use crate::agent_info_op::*;
 pub use crate::app::*;
 pub use crate::autonomic::*;
+pub use crate::blob_chunk_op::*;
 pub use crate::chain::*;
 pub use crate::chc::*;
 pub use crate::combinators::*;
 pub use crate::db::*;
 pub use crate::db_cache::*;
+pub use crate::dht_archive::*;
 pub use crate::dht_op::*;
 pub use crate::dna::wasm::*;
 pub use crate::dna::*;
+pub use crate::egress_policy::*;
 pub use crate::entry::*;
+pub use crate::event_journal::*;
+pub use crate::legacy_chain::*;
 pub use crate::link::*;
 pub use crate::metadata::*;
 pub use crate::record::*;
@@ -16,6 +16,9 @@ use std::collections::BTreeSet;
 mod error;
 pub use error::*;
 
+mod bundle;
+pub use bundle::*;
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SerializedBytes, Default)]
 /// A condensed version of get record request.
 /// This saves bandwidth by removing duplicated and implied data.
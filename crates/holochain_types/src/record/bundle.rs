@@ -0,0 +1,302 @@
+//! Offline verification of an exported set of records, so that a third party
+//! can audit data without running a conductor.
+
+use crate::prelude::*;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+/// Something [`verify_record_bundle`] found wrong with a record.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecordBundleProblem {
+    /// The hash the record was exported with isn't the hash of its action.
+    ActionHashMismatch {
+        /// The hash of the action.
+        computed: ActionHash,
+    },
+    /// The signature isn't the author's signature of the action.
+    InvalidSignature,
+    /// The entry doesn't hash to the entry hash of the action.
+    EntryHashMismatch {
+        /// The entry hash of the action.
+        expected: EntryHash,
+        /// The hash of the entry.
+        computed: EntryHash,
+    },
+    /// The record holds an entry although its action doesn't create one.
+    UnexpectedEntry,
+    /// The previous action is in the bundle, but it isn't the action before this
+    /// one in the author's chain.
+    BrokenPrevAction {
+        /// The previous action of this action.
+        prev_action: ActionHash,
+    },
+    /// The previous action isn't in the bundle, although earlier actions of the
+    /// author's chain are, so the chain can't be followed through this action.
+    MissingPrevAction {
+        /// The previous action of this action.
+        prev_action: ActionHash,
+    },
+    /// Another action in the bundle has the same author and sequence number,
+    /// so the author's chain has forked.
+    ChainFork {
+        /// The other action at the same position in the chain.
+        other: ActionHash,
+    },
+}
+
+/// A problem with one of the records of a bundle.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordBundleIssue {
+    /// The hash the record was exported with.
+    pub action_hash: ActionHash,
+    /// What is wrong with the record.
+    pub problem: RecordBundleProblem,
+}
+
+/// The outcome of [`verify_record_bundle`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordBundleReport {
+    /// The number of records in the bundle.
+    pub records: usize,
+    /// The number of authors whose records are in the bundle.
+    pub authors: usize,
+    /// Every problem found, in the order of the records they were found in.
+    pub issues: Vec<RecordBundleIssue>,
+}
+
+impl RecordBundleReport {
+    /// Whether no problem was found with the bundle.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Verify the signatures, hashes and chain linkage of a set of records, which may
+/// come from the chains of any number of authors, in any order.
+///
+/// A bundle may hold only part of a chain, so an author's chain may start at any
+/// action, but from there on every previous action must be in the bundle.
+/// Private entries which weren't exported can't be checked against their hashes.
+pub async fn verify_record_bundle(records: &[Record]) -> RecordBundleReport {
+    let computed: Vec<ActionHash> = records
+        .iter()
+        .map(|record| ActionHash::with_data_sync(record.action()))
+        .collect();
+
+    let mut positions: HashMap<ActionHash, (&AgentPubKey, u32)> = HashMap::new();
+    let mut chain_starts: HashMap<&AgentPubKey, u32> = HashMap::new();
+    for (record, hash) in records.iter().zip(&computed) {
+        let action = record.action();
+        positions.insert(hash.clone(), (action.author(), action.action_seq()));
+        let start = chain_starts
+            .entry(action.author())
+            .or_insert(action.action_seq());
+        *start = (*start).min(action.action_seq());
+    }
+
+    let mut issues = Vec::new();
+    let mut seen: HashMap<(&AgentPubKey, u32), &ActionHash> = HashMap::new();
+    let mut seen_hashes = HashSet::new();
+    for (record, hash) in records.iter().zip(&computed) {
+        if !seen_hashes.insert(hash) {
+            // The same action exported twice is only verified once.
+            continue;
+        }
+        let action = record.action();
+        let mut issue = |problem| {
+            issues.push(RecordBundleIssue {
+                action_hash: record.action_address().clone(),
+                problem,
+            })
+        };
+
+        if record.action_address() != hash {
+            issue(RecordBundleProblem::ActionHashMismatch {
+                computed: hash.clone(),
+            });
+        }
+
+        if !matches!(
+            action
+                .author()
+                .verify_signature(record.signature(), action)
+                .await,
+            Ok(true)
+        ) {
+            issue(RecordBundleProblem::InvalidSignature);
+        }
+
+        if let Some(entry) = record.entry().as_option() {
+            match action.entry_hash() {
+                Some(expected) => {
+                    let computed = EntryHash::with_data_sync(entry);
+                    if *expected != computed {
+                        issue(RecordBundleProblem::EntryHashMismatch {
+                            expected: expected.clone(),
+                            computed,
+                        });
+                    }
+                }
+                None => issue(RecordBundleProblem::UnexpectedEntry),
+            }
+        }
+
+        if let Some(prev_action) = action.prev_action() {
+            match positions.get(prev_action) {
+                Some((author, seq))
+                    if *author == action.author() && seq + 1 == action.action_seq() => {}
+                Some(_) => issue(RecordBundleProblem::BrokenPrevAction {
+                    prev_action: prev_action.clone(),
+                }),
+                None if chain_starts[action.author()] < action.action_seq() => {
+                    issue(RecordBundleProblem::MissingPrevAction {
+                        prev_action: prev_action.clone(),
+                    })
+                }
+                None => {}
+            }
+        }
+
+        if let Some(other) = seen.insert((action.author(), action.action_seq()), hash) {
+            issue(RecordBundleProblem::ChainFork {
+                other: other.clone(),
+            });
+        }
+    }
+
+    RecordBundleReport {
+        records: records.len(),
+        authors: chain_starts.len(),
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_keystore::MetaLairClient;
+
+    async fn record(keystore: &MetaLairClient, action: Action, entry: Option<Entry>) -> Record {
+        Record::new(
+            SignedActionHashed::sign(keystore, action.into_hashed())
+                .await
+                .unwrap(),
+            entry,
+        )
+    }
+
+    /// A chain of a dna action and `creates` create actions.
+    async fn chain(keystore: &MetaLairClient, creates: u32) -> Vec<Record> {
+        let author = AgentPubKey::new_random(keystore).await.unwrap();
+        let dna = Action::Dna(Dna {
+            author: author.clone(),
+            timestamp: Timestamp::from_micros(0),
+            hash: DnaHash::from_raw_32(vec![0; 32]),
+        });
+        let mut prev_action = ActionHash::with_data_sync(&dna);
+        let mut records = vec![record(keystore, dna, None).await];
+        for seq in 1..=creates {
+            let entry = Entry::App(AppEntryBytes(UnsafeBytes::from(vec![seq as u8]).into()));
+            let create = Action::Create(Create {
+                author: author.clone(),
+                timestamp: Timestamp::from_micros(seq as i64),
+                action_seq: seq,
+                prev_action,
+                entry_type: EntryType::App(AppEntryDef::new(
+                    0.into(),
+                    0.into(),
+                    EntryVisibility::Public,
+                )),
+                entry_hash: EntryHash::with_data_sync(&entry),
+                weight: Default::default(),
+            });
+            prev_action = ActionHash::with_data_sync(&create);
+            records.push(record(keystore, create, Some(entry)).await);
+        }
+        records
+    }
+
+    fn problems(report: &RecordBundleReport) -> Vec<RecordBundleProblem> {
+        report.issues.iter().map(|i| i.problem.clone()).collect()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn valid_chains_and_partial_chains_verify() {
+        let keystore = holochain_keystore::spawn_test_keystore().await.unwrap();
+        let mut records = chain(&keystore, 3).await;
+        records.extend(chain(&keystore, 2).await);
+        records.reverse();
+
+        let report = verify_record_bundle(&records).await;
+        assert!(report.is_valid(), "{:?}", report);
+        assert_eq!(report.records, 7);
+        assert_eq!(report.authors, 2);
+
+        // A bundle may start part way through a chain.
+        let report = verify_record_bundle(&records[..2]).await;
+        assert!(report.is_valid(), "{:?}", report);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn tampered_records_are_reported() {
+        let keystore = holochain_keystore::spawn_test_keystore().await.unwrap();
+        let records = chain(&keystore, 3).await;
+
+        // An entry which doesn't match its action.
+        let mut tampered = records.clone();
+        *tampered[1].as_entry_mut() =
+            RecordEntry::Present(records[2].entry().clone().into_option().unwrap());
+        let report = verify_record_bundle(&tampered).await;
+        assert!(matches!(
+            problems(&report)[..],
+            [RecordBundleProblem::EntryHashMismatch { .. }]
+        ));
+
+        // An action which was changed after it was signed.
+        let mut tampered = records.clone();
+        if let Action::Create(create) = tampered[3].as_action_mut() {
+            create.timestamp = Timestamp::from_micros(100);
+        }
+        let report = verify_record_bundle(&tampered).await;
+        assert!(matches!(
+            problems(&report)[..],
+            [
+                RecordBundleProblem::ActionHashMismatch { .. },
+                RecordBundleProblem::InvalidSignature
+            ]
+        ));
+
+        // A gap in the chain.
+        let gap = vec![records[0].clone(), records[2].clone(), records[3].clone()];
+        let report = verify_record_bundle(&gap).await;
+        assert_eq!(
+            problems(&report),
+            vec![RecordBundleProblem::MissingPrevAction {
+                prev_action: records[1].action_address().clone()
+            }]
+        );
+
+        // A fork, signed by the author, which doesn't follow from the previous action.
+        let mut forked = records[2].action().clone();
+        if let Action::Create(create) = &mut forked {
+            create.timestamp = Timestamp::from_micros(200);
+            create.prev_action = records[0].action_address().clone();
+        }
+        let forked = record(&keystore, forked, records[2].entry().clone().into_option()).await;
+        let mut bundle = records.clone();
+        bundle.push(forked);
+        let report = verify_record_bundle(&bundle).await;
+        assert_eq!(
+            problems(&report),
+            vec![
+                RecordBundleProblem::BrokenPrevAction {
+                    prev_action: records[0].action_address().clone()
+                },
+                RecordBundleProblem::ChainFork {
+                    other: records[2].action_address().clone()
+                },
+            ]
+        );
+    }
+}
@@ -43,6 +43,92 @@ pub enum SystemSignal {
     Test(String),
     /// A countersigning session has successfully completed.
     SuccessfulCountersigning(holo_hash::EntryHash),
+    /// A clone cell was archived (disabled) because nothing had been written
+    /// to its source chain for longer than the clone archival policy of its
+    /// role allows.
+    CloneCellArchived {
+        /// The archived clone cell.
+        cell_id: CellId,
+        /// The clone id of the archived cell.
+        clone_id: CloneId,
+        /// When the source chain of the cell was last written to.
+        last_write: Timestamp,
+    },
+    /// A durable remote call queued by a cell has reached the remote agent,
+    /// which responded.
+    DurableCallRemoteDelivered {
+        /// The cell that queued the call.
+        cell_id: CellId,
+        /// The id the call was queued with.
+        call_id: DurableCallId,
+        /// The response of the remote agent.
+        response: ZomeCallResponse,
+    },
+    /// A durable remote call queued by a cell couldn't reach the remote agent
+    /// before its time to live ran out, and has been given up on.
+    DurableCallRemoteExpired {
+        /// The cell that queued the call.
+        cell_id: CellId,
+        /// The id the call was queued with.
+        call_id: DurableCallId,
+        /// How many times the call was attempted.
+        attempts: u32,
+    },
+    /// A durable remote call queued by a cell isn't allowed by the cell's
+    /// egress policy, and has been given up on without being attempted again.
+    DurableCallRemoteBlocked {
+        /// The cell that queued the call.
+        cell_id: CellId,
+        /// The id the call was queued with.
+        call_id: DurableCallId,
+        /// The remote agent the call was to.
+        to_agent: AgentPubKey,
+    },
+}
+
+/// The ops for an action authored by a cell have been accepted by at least
+/// one authority, which has sent back a validation receipt.
+///
+/// This is emitted for the first receipt received for an action, as the
+/// [`AppSignal`] of a [`Signal::App`] from each coordinator zome which depends
+/// on the integrity zome that defines the entry or link type of the action, so
+/// it reaches the zome which committed it. A delete is signalled like the
+/// action it deletes. Actions which belong to no zome, such as genesis actions,
+/// aren't signalled.
+///
+/// It is encoded with a `type` field of `PublishAccepted`, so clients can
+/// subscribe to it with that signal pattern.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub struct PublishAccepted {
+    /// The action whose ops were accepted.
+    pub action_hash: holo_hash::ActionHash,
+    /// The validators which signed the first receipt.
+    pub validators: Vec<holo_hash::AgentPubKey>,
+}
+
+/// A signal which was persisted for an app, so that clients which were
+/// disconnected when it was emitted can still fetch it.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
+pub struct MissedSignal {
+    /// The position of this signal among the signals persisted for the app.
+    pub token: u64,
+    /// The signal.
+    pub signal: Signal,
+}
+
+/// The signals persisted for an app after a given token.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
+pub struct MissedSignals {
+    /// The persisted signals, oldest first.
+    pub signals: Vec<MissedSignal>,
+    /// The token of the latest signal persisted for the app, to pass as
+    /// `since_token` on the next fetch.
+    pub latest_token: Option<u64>,
+    /// Whether some signals after the requested token have already been
+    /// evicted from the buffer, in which case the client can't fully catch up
+    /// from the signals alone.
+    pub truncated: bool,
 }
 
 /// Create a test signal
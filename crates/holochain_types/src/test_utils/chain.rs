@@ -109,6 +109,11 @@ impl ChainItem for TestChainItem {
         self.prev.as_ref()
     }
 
+    /// Test items are authored one microsecond apart, at their sequence number.
+    fn timestamp(&self) -> Timestamp {
+        Timestamp(self.seq as i64)
+    }
+
     fn to_display(&self) -> String {
         String::from("test chain item")
     }
@@ -226,7 +231,8 @@ pub fn chain_item_to_action(u: &mut Unstructured, i: &impl ChainItem) -> SignedA
     let mut action = SignedActionHashed::arbitrary(u).unwrap();
     match (action_seq, prev_action) {
         (_, None) => {
-            let dna = Dna::arbitrary(u).unwrap();
+            let mut dna = Dna::arbitrary(u).unwrap();
+            dna.timestamp = i.timestamp();
             action.hashed.content = Action::Dna(dna);
             action.hashed.hash = hash;
         }
@@ -234,6 +240,7 @@ pub fn chain_item_to_action(u: &mut Unstructured, i: &impl ChainItem) -> SignedA
             let mut create = Create::arbitrary(u).unwrap();
             create.action_seq = action_seq;
             create.prev_action = prev_action;
+            create.timestamp = i.timestamp();
             action.hashed.content = Action::Create(create);
             action.hashed.hash = hash;
         }
@@ -1,11 +1,12 @@
 //! Types for validation receipts and signed validation receipts to be sent between peers.
 
-use crate::prelude::{Signature, Timestamp};
+use crate::prelude::{DhtOpType, Signature, Timestamp};
 use futures::{Stream, StreamExt, TryStreamExt};
-use holo_hash::{AgentPubKey, DhtOpHash};
+use holo_hash::{ActionHash, AgentPubKey, DhtOpHash};
 use holochain_keystore::{AgentPubKeyExt, MetaLairClient};
 use holochain_serialized_bytes::prelude::*;
 use holochain_zome_types::prelude::*;
+use std::collections::BTreeSet;
 use std::vec::IntoIter;
 
 /// Validation receipt content - to be signed.
@@ -121,9 +122,97 @@ impl IntoIterator for ValidationReceiptBundle {
     }
 }
 
+/// Proof that the ops produced by an action have been published to,
+/// and validated by, other agents, for display to the action's author.
+///
+/// Built from the validation receipts held by the author's conductor.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct PublicationProof {
+    /// The action the ops were produced from.
+    pub action_hash: ActionHash,
+
+    /// The receipts for each op produced by the action.
+    pub ops: Vec<OpPublicationProof>,
+}
+
+impl PublicationProof {
+    /// All agents which have sent a receipt for any op of the action.
+    pub fn validators(&self) -> BTreeSet<&AgentPubKey> {
+        self.ops
+            .iter()
+            .flat_map(|op| op.receipts.iter())
+            .flat_map(|receipt| receipt.validators.iter())
+            .collect()
+    }
+
+    /// The number of distinct agents which have found any op of the action valid.
+    pub fn valid_count(&self) -> usize {
+        self.ops
+            .iter()
+            .flat_map(|op| op.receipts.iter())
+            .filter(|receipt| receipt.validation_status == ValidationStatus::Valid)
+            .flat_map(|receipt| receipt.validators.iter())
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+}
+
+/// The validation receipts received for one op of a [PublicationProof].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OpPublicationProof {
+    /// The op the receipts are for.
+    pub op_hash: DhtOpHash,
+
+    /// The type of the op.
+    pub op_type: DhtOpType,
+
+    /// The receipts received for this op.
+    pub receipts: Vec<PublicationReceipt>,
+}
+
+/// A [SignedValidationReceipt] without the op hash, which is held
+/// by the enclosing [OpPublicationProof].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PublicationReceipt {
+    /// The remote validators which signed the receipt.
+    pub validators: Vec<AgentPubKey>,
+
+    /// The signatures of the validators, in the same order.
+    pub validators_signatures: Vec<Signature>,
+
+    /// The result of validation.
+    pub validation_status: ValidationStatus,
+
+    /// Time when the validators integrated the op.
+    pub when_integrated: Timestamp,
+}
+
+impl From<SignedValidationReceipt> for PublicationReceipt {
+    fn from(signed: SignedValidationReceipt) -> Self {
+        let SignedValidationReceipt {
+            receipt:
+                ValidationReceipt {
+                    validation_status,
+                    validators,
+                    when_integrated,
+                    ..
+                },
+            validators_signatures,
+        } = signed;
+        Self {
+            validators,
+            validators_signatures,
+            validation_status,
+            when_integrated,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::prelude::*;
     use crate::validation_receipt::try_stream_of_results;
+    use ::fixt::prelude::*;
 
     #[tokio::test]
     async fn test_try_stream_of_results() {
@@ -150,4 +239,39 @@ mod tests {
         let stream = futures::stream::iter(iter);
         assert_eq!(Err("test".to_string()), try_stream_of_results(stream).await);
     }
+
+    #[test]
+    fn publication_proof_counts_distinct_valid_validators() {
+        let alice = fixt!(AgentPubKey);
+        let bob = fixt!(AgentPubKey);
+        let carol = fixt!(AgentPubKey);
+        let receipt = |validator: &AgentPubKey, validation_status| PublicationReceipt {
+            validators: vec![validator.clone()],
+            validators_signatures: vec![fixt!(Signature)],
+            validation_status,
+            when_integrated: Timestamp::now(),
+        };
+        let op = |receipts| OpPublicationProof {
+            op_hash: fixt!(DhtOpHash),
+            op_type: ChainOpType::StoreRecord.into(),
+            receipts,
+        };
+
+        let proof = PublicationProof {
+            action_hash: fixt!(ActionHash),
+            ops: vec![
+                op(vec![
+                    receipt(&alice, ValidationStatus::Valid),
+                    receipt(&bob, ValidationStatus::Valid),
+                ]),
+                op(vec![
+                    receipt(&alice, ValidationStatus::Valid),
+                    receipt(&carol, ValidationStatus::Rejected),
+                ]),
+            ],
+        };
+
+        assert_eq!(3, proof.validators().len());
+        assert_eq!(2, proof.valid_count());
+    }
 }
@@ -66,6 +66,70 @@ impl GlobalZomeTypes {
     }
 }
 
+/// Resolve the integrity zomes whose types are in scope for each zome of a DNA,
+/// in the order their types are indexed by the zome.
+///
+/// An integrity zome's own types come first, followed by the zomes it depends on in
+/// the order they are declared. An integrity zome re-exports the types it imports, so
+/// the zomes which depend on it can use them too. Re-exported types come after all of
+/// a zome's declared dependencies, so importing types into an integrity zome doesn't
+/// move the types of the zomes which already depend on it.
+///
+/// When there's only one integrity zome it's the only zome in scope for every zome.
+pub fn resolve_zome_dependencies(
+    dna_def: &DnaDef,
+) -> ZomeTypesResult<HashMap<ZomeName, Vec<ZomeIndex>>> {
+    // Create a map of integrity zome names to ZomeIndexes.
+    let integrity_zomes: HashMap<_, _> = dna_def
+        .integrity_zomes
+        .iter()
+        .enumerate()
+        .map(|(i, (n, _))| Some((n.clone(), ZomeIndex(i.try_into().ok()?))))
+        .collect::<Option<_>>()
+        .ok_or(ZomeTypesError::ZomeIndexOverflow)?;
+    let index_of = |zome_name: &ZomeName, dependency: &ZomeName| {
+        integrity_zomes
+            .get(dependency)
+            .copied()
+            .ok_or_else(|| ZomeTypesError::MissingDependenciesForZome(zome_name.clone()))
+    };
+
+    dna_def
+        .all_zomes()
+        .map(|(zome_name, def)| {
+            let mut dependencies = Vec::new();
+
+            if integrity_zomes.len() == 1 {
+                // If there's only one integrity zome we add it to this zome and are done.
+                dependencies.push(ZomeIndex(0));
+            } else {
+                // Integrity zomes need to have themselves as a dependency.
+                if dna_def.is_integrity_zome(zome_name) {
+                    dependencies.push(index_of(zome_name, zome_name)?);
+                }
+                for name in def.dependencies() {
+                    dependencies.push(index_of(zome_name, name)?);
+                }
+                // Add the types re-exported by the integrity zomes in scope, and in turn
+                // the types re-exported by those, skipping any which are already in scope.
+                let mut i = 0;
+                while let Some(zome_index) = dependencies.get(i) {
+                    let (name, def) = &dna_def.integrity_zomes[zome_index.0 as usize];
+                    for import in def.as_any_zome_def().dependencies() {
+                        let import = index_of(name, import)?;
+                        if !dependencies.contains(&import) {
+                            dependencies.push(import);
+                        }
+                    }
+                    i += 1;
+                }
+            }
+
+            Ok((zome_name.clone(), dependencies))
+        })
+        .collect()
+}
+
 fn new_scope<T>(iter: impl Iterator<Item = (ZomeIndex, NumZomeTypes)>) -> ScopedZomeTypes<T>
 where
     T: From<u8>,
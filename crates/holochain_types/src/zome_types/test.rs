@@ -100,3 +100,84 @@ fn test_in_scope_subset(set: GlobalZomeTypes, zomes: &[u8]) -> ScopedZomeTypesSe
     let zomes = zomes.iter().map(|z| ZomeIndex(*z)).collect::<Vec<_>>();
     set.in_scope_subset(&zomes[..])
 }
+
+fn make_dna_def(integrity: &[(&str, &[&str])], coordinators: &[(&str, &[&str])]) -> DnaDef {
+    let zome_def = |dependencies: &[&str]| {
+        ZomeDef::Wasm(WasmZome {
+            wasm_hash: holo_hash::WasmHash::from_raw_36(vec![0; 36]),
+            dependencies: dependencies.iter().map(|&d| d.into()).collect(),
+            preserialized_path: None,
+        })
+    };
+    let mut dna_modifiers = DnaModifiersBuilder::default();
+    dna_modifiers.network_seed("00000000-0000-0000-0000-000000000000".into());
+    DnaDefBuilder::default()
+        .integrity_zomes(
+            integrity
+                .iter()
+                .map(|(name, deps)| ((*name).into(), zome_def(deps).into()))
+                .collect(),
+        )
+        .coordinator_zomes(
+            coordinators
+                .iter()
+                .map(|(name, deps)| ((*name).into(), zome_def(deps).into()))
+                .collect(),
+        )
+        .modifiers(dna_modifiers.build().unwrap())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_resolve_zome_dependencies_re_exports_imported_types() {
+    // "profiles" imports the types of "common", which imports the types of "base".
+    let dna_def = make_dna_def(
+        &[
+            ("base", &[]),
+            ("common", &["base"]),
+            ("profiles", &["common"]),
+            ("posts", &[]),
+        ],
+        &[
+            ("profiles_api", &["profiles"]),
+            ("posts_api", &["posts", "profiles"]),
+            ("base_api", &["base"]),
+        ],
+    );
+    let deps = resolve_zome_dependencies(&dna_def).unwrap();
+    let scope = |zome_name: &str| {
+        deps[&ZomeName::from(zome_name)]
+            .iter()
+            .map(|z| z.0)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(scope("base"), [0]);
+    assert_eq!(scope("common"), [1, 0]);
+    assert_eq!(scope("profiles"), [2, 1, 0]);
+    assert_eq!(scope("posts"), [3]);
+    assert_eq!(scope("profiles_api"), [2, 1, 0]);
+    // Re-exported types come after the declared dependencies, so their positions don't move.
+    assert_eq!(scope("posts_api"), [3, 2, 1, 0]);
+    assert_eq!(scope("base_api"), [0]);
+}
+
+#[test]
+fn test_resolve_zome_dependencies_tolerates_import_cycles() {
+    let dna_def = make_dna_def(&[("a", &["b"]), ("b", &["a"])], &[("c", &["b"])]);
+    let deps = resolve_zome_dependencies(&dna_def).unwrap();
+
+    assert_eq!(deps[&ZomeName::from("a")], [ZomeIndex(0), ZomeIndex(1)]);
+    assert_eq!(deps[&ZomeName::from("b")], [ZomeIndex(1), ZomeIndex(0)]);
+    assert_eq!(deps[&ZomeName::from("c")], [ZomeIndex(1), ZomeIndex(0)]);
+}
+
+#[test]
+fn test_resolve_zome_dependencies_missing_import() {
+    let dna_def = make_dna_def(&[("a", &["missing"]), ("b", &[])], &[]);
+    assert!(matches!(
+        resolve_zome_dependencies(&dna_def),
+        Err(ZomeTypesError::MissingDependenciesForZome(zome_name)) if zome_name == ZomeName::from("a")
+    ));
+}
@@ -1,35 +1,66 @@
 //! Commandline passphrase capture utilities.
 
 use once_cell::sync::Lazy;
-use std::io::Result;
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
 
-static PIPED: Lazy<std::sync::Mutex<bool>> = Lazy::new(|| std::sync::Mutex::new(false));
+/// Where the passphrase is read from by [pw_get].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum PwSource {
+    /// Prompt for the passphrase on the tty.
+    #[default]
+    Interactive,
+    /// Read the passphrase from stdin, up to the first newline.
+    Piped,
+    /// Read the passphrase from a file which only its owner can read,
+    /// up to the first newline.
+    Keyfile(PathBuf),
+    /// Read the passphrase from the systemd credential with this name, as set
+    /// up by `LoadCredential=` or `LoadCredentialEncrypted=` in the unit file.
+    SystemdCredential(String),
+}
+
+static SOURCE: Lazy<std::sync::Mutex<PwSource>> = Lazy::new(Default::default);
 
 /// Set the "piped" flag. If the user would prefer to send the passphrase
 /// over stdin (rather than tty capture). This must be set before the first
 /// call to [pw_get] or the passphrase will already be captured.
 pub fn pw_set_piped(piped: bool) {
-    *PIPED.lock().unwrap() = piped;
+    pw_set_source(if piped {
+        PwSource::Piped
+    } else {
+        PwSource::Interactive
+    });
 }
 
-fn get_piped() -> bool {
-    *PIPED.lock().unwrap()
+/// Set where the passphrase is read from. Like [pw_set_piped], this must be
+/// set before the first call to [pw_get].
+pub fn pw_set_source(source: PwSource) {
+    *SOURCE.lock().unwrap() = source;
 }
 
-static PASSPHRASE: Lazy<std::result::Result<sodoken::BufRead, String>> = Lazy::new(|| {
-    if get_piped() {
-        read_piped_passphrase().map_err(|e| e.to_string())
-    } else {
-        read_interactive_passphrase("# passphrase> ").map_err(|e| e.to_string())
-    }
-});
+fn get_source() -> PwSource {
+    SOURCE.lock().unwrap().clone()
+}
+
+static PASSPHRASE: Lazy<std::result::Result<sodoken::BufRead, String>> =
+    Lazy::new(|| match get_source() {
+        PwSource::Interactive => {
+            read_interactive_passphrase("# passphrase> ").map_err(|e| e.to_string())
+        }
+        PwSource::Piped => read_piped_passphrase().map_err(|e| e.to_string()),
+        PwSource::Keyfile(path) => read_keyfile_passphrase(&path).map_err(|e| e.to_string()),
+        PwSource::SystemdCredential(name) => {
+            read_systemd_credential_passphrase(&name).map_err(|e| e.to_string())
+        }
+    });
 
-/// Capture a passphrase from the user. Either captures from tty, or
-/// reads stdin if [pw_set_piped] was called with `true`.
+/// Capture a passphrase from the user, from the source set with
+/// [pw_set_source] or [pw_set_piped]. Captures from tty by default.
 pub fn pw_get() -> Result<sodoken::BufRead> {
     PASSPHRASE
         .clone()
-        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+        .map_err(|e| Error::new(ErrorKind::Other, e))
 }
 
 fn vec_to_locked(mut pass_tmp: Vec<u8>) -> Result<sodoken::BufRead> {
@@ -92,3 +123,106 @@ fn read_piped_passphrase() -> Result<sodoken::BufRead> {
         }
     }
 }
+
+fn read_keyfile_passphrase(path: &Path) -> Result<sodoken::BufRead> {
+    let fail = |reason: String| {
+        Error::new(
+            ErrorKind::Other,
+            format!(
+                "Could not read passphrase keyfile {}: {}",
+                path.display(),
+                reason
+            ),
+        )
+    };
+
+    let metadata = std::fs::metadata(path).map_err(|e| fail(e.to_string()))?;
+    if !metadata.is_file() {
+        return Err(fail("not a file".to_string()));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = metadata.permissions().mode() & 0o777;
+        if mode & 0o077 != 0 {
+            return Err(fail(format!(
+                "it can be accessed by users other than its owner (mode {:o}), \
+                 restrict it with `chmod 600`",
+                mode
+            )));
+        }
+    }
+
+    let mut pass_tmp = std::fs::read(path).map_err(|e| fail(e.to_string()))?;
+    // Only the first line is used, like a piped passphrase. Anything after it
+    // is zeroed before the buffer is dropped.
+    let mut end = pass_tmp
+        .iter()
+        .position(|b| *b == b'\n')
+        .unwrap_or(pass_tmp.len());
+    if end > 0 && pass_tmp[end - 1] == b'\r' {
+        end -= 1;
+    }
+    pass_tmp[end..].fill(0);
+    pass_tmp.truncate(end);
+    if pass_tmp.is_empty() {
+        return Err(fail("the passphrase is empty".to_string()));
+    }
+    vec_to_locked(pass_tmp)
+}
+
+fn read_systemd_credential_passphrase(name: &str) -> Result<sodoken::BufRead> {
+    let dir = std::env::var_os("CREDENTIALS_DIRECTORY").ok_or_else(|| {
+        Error::new(
+            ErrorKind::Other,
+            format!(
+                "Could not read passphrase credential {}: CREDENTIALS_DIRECTORY is not set, \
+                 are we running in a systemd unit with `LoadCredential={}:<path>`?",
+                name, name
+            ),
+        )
+    })?;
+    read_keyfile_passphrase(&Path::new(&dir).join(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_keyfile(name: &str, contents: &[u8], mode: u32) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{}-{}", name, std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+        }
+        #[cfg(not(unix))]
+        let _ = mode;
+        path
+    }
+
+    #[test]
+    fn keyfile_passphrase_stops_at_newline() {
+        let path = write_keyfile("pw-keyfile", b"pass phrase\r\nignored", 0o600);
+        let passphrase = read_keyfile_passphrase(&path).unwrap();
+        assert_eq!(b"pass phrase", &passphrase.read_lock()[..]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn keyfile_passphrase_must_not_be_empty() {
+        let path = write_keyfile("pw-keyfile-empty", b"\n", 0o600);
+        assert!(read_keyfile_passphrase(&path).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn keyfile_readable_by_others_is_rejected() {
+        let path = write_keyfile("pw-keyfile-open", b"pass", 0o644);
+        let err = read_keyfile_passphrase(&path).unwrap_err();
+        assert!(err.to_string().contains("chmod 600"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -16,6 +16,9 @@ use tokio_tungstenite::tungstenite::handshake::server::{Callback, ErrorResponse,
 use tokio_tungstenite::tungstenite::http::{HeaderMap, HeaderValue, StatusCode};
 use tokio_tungstenite::tungstenite::protocol::Message;
 
+mod reconnect;
+pub use reconnect::*;
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
 #[serde(rename_all = "snake_case", tag = "type")]
 /// The messages actually sent over the wire by this library.
@@ -71,9 +74,12 @@ impl WireMessage {
         SerializedBytes: TryFrom<S, Error = SerializedBytesError>,
     {
         let s1 = SerializedBytes::try_from(s).map_err(Error::other)?;
-        let s2 = Self::Authenticate {
-            data: UnsafeBytes::from(s1).into(),
-        };
+        Self::authenticate_encoded(UnsafeBytes::from(s1).into())
+    }
+
+    /// Create a new authenticate message from already serialized data.
+    fn authenticate_encoded(data: Vec<u8>) -> Result<Message> {
+        let s2 = Self::Authenticate { data };
         let s3: SerializedBytes = s2.try_into().map_err(Error::other)?;
         Ok(Message::Binary(UnsafeBytes::from(s3).into()))
     }
@@ -491,11 +497,25 @@ impl WebsocketSender {
         S: std::fmt::Debug,
         SerializedBytes: TryFrom<S, Error = SerializedBytesError>,
     {
+        self.send_timeout(WireMessage::authenticate(s)?, timeout)
+            .await
+    }
+
+    /// Authenticate with the remote, with data which is already serialized.
+    pub(crate) async fn authenticate_encoded_timeout(
+        &self,
+        data: Vec<u8>,
+        timeout: std::time::Duration,
+    ) -> Result<()> {
+        self.send_timeout(WireMessage::authenticate_encoded(data)?, timeout)
+            .await
+    }
+
+    async fn send_timeout(&self, s: Message, timeout: std::time::Duration) -> Result<()> {
         use futures::sink::SinkExt;
         self.0
             .exec(move |_, core| async move {
                 tokio::time::timeout(timeout, async {
-                    let s = WireMessage::authenticate(s)?;
                     core.send.lock().await.send(s).await.map_err(Error::other)?;
                     Ok(())
                 })
@@ -657,6 +677,7 @@ pub async fn connect(
 }
 
 /// A request to connect to a websocket server.
+#[derive(Clone)]
 pub struct ConnectRequest {
     addr: std::net::SocketAddr,
     headers: HeaderMap<HeaderValue>,
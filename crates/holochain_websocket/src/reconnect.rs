@@ -0,0 +1,319 @@
+//! A client which reconnects to the remote when its connection drops.
+
+use crate::*;
+use std::time::Duration;
+
+/// Reconnect configuration for a [ReconnectingWebsocketSender].
+#[derive(Clone, Debug)]
+pub struct ReconnectConfig {
+    /// Delay before retrying after a failed connection attempt. Doubles with
+    /// each attempt that fails in a row. [default = 100 milliseconds]
+    pub initial_backoff: Duration,
+
+    /// The longest delay between connection attempts. A connection must
+    /// also stay open this long before the delay is reset back to the
+    /// initial backoff. [default = 30 seconds]
+    pub max_backoff: Duration,
+
+    /// Give up after this many failed connection attempts in a row,
+    /// or keep trying forever if `None`. [default = None]
+    pub max_attempts: Option<u32>,
+}
+
+impl ReconnectConfig {
+    /// The default ReconnectConfig.
+    pub const DEFAULT: ReconnectConfig = ReconnectConfig {
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_secs(30),
+        max_attempts: None,
+    };
+
+    /// The delay before the next attempt, given the current one.
+    fn next_backoff(&self, backoff: Duration) -> Duration {
+        std::cmp::min(backoff.saturating_mul(2), self.max_backoff)
+    }
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Clients don't expect requests from the remote, so their content is ignored.
+#[derive(Debug)]
+struct UnexpectedRequest;
+
+impl TryFrom<SerializedBytes> for UnexpectedRequest {
+    type Error = SerializedBytesError;
+
+    fn try_from(_: SerializedBytes) -> std::result::Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+#[derive(Clone)]
+enum ConnectionState {
+    Connecting,
+    Connected(WebsocketSender),
+    Closed,
+}
+
+struct Shared {
+    config: Arc<WebsocketConfig>,
+    reconnect_config: ReconnectConfig,
+    request: ConnectRequest,
+    authentication: Option<Vec<u8>>,
+    signal_send: tokio::sync::broadcast::Sender<Vec<u8>>,
+}
+
+impl Shared {
+    /// Connect, and authenticate if required, retrying with backoff.
+    /// Returns the last error once `max_attempts` attempts have failed.
+    async fn connect_with_backoff(&self) -> Result<(WebsocketSender, WebsocketReceiver)> {
+        let mut backoff = self.reconnect_config.initial_backoff;
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            match self.try_connect().await {
+                Ok(r) => return Ok(r),
+                Err(err) => {
+                    if self
+                        .reconnect_config
+                        .max_attempts
+                        .is_some_and(|max| attempts >= max)
+                    {
+                        return Err(err);
+                    }
+                    tracing::debug!(?err, ?backoff, %attempts, "Websocket connect failed, retrying");
+                    tokio::time::sleep(backoff).await;
+                    backoff = self.reconnect_config.next_backoff(backoff);
+                }
+            }
+        }
+    }
+
+    async fn try_connect(&self) -> Result<(WebsocketSender, WebsocketReceiver)> {
+        let (send, recv) = connect(self.config.clone(), self.request.clone()).await?;
+        if let Some(data) = &self.authentication {
+            send.authenticate_encoded_timeout(data.clone(), self.config.default_request_timeout)
+                .await?;
+        }
+        Ok((send, recv))
+    }
+
+    /// Receive on each connection until it drops, then reconnect.
+    async fn run(
+        self: Arc<Self>,
+        mut recv: WebsocketReceiver,
+        state: tokio::sync::watch::Sender<ConnectionState>,
+    ) {
+        let mut backoff = self.reconnect_config.initial_backoff;
+        loop {
+            let connected_at = tokio::time::Instant::now();
+            loop {
+                match recv.recv::<UnexpectedRequest>().await {
+                    Ok(ReceiveMessage::Signal(data)) => {
+                        // No subscribers is not an error, the signal is just dropped.
+                        let _ = self.signal_send.send(data);
+                    }
+                    Ok(msg) => {
+                        tracing::warn!(?msg, "Ignoring unexpected message from remote");
+                    }
+                    Err(err) => {
+                        tracing::info!(?err, "Websocket connection dropped, reconnecting");
+                        break;
+                    }
+                }
+            }
+            state.send_replace(ConnectionState::Connecting);
+            drop(recv);
+
+            // A connection which drops straight away, for example because
+            // authentication was rejected, must not cause a tight reconnect loop.
+            if connected_at.elapsed() < self.reconnect_config.max_backoff {
+                tokio::time::sleep(backoff).await;
+                backoff = self.reconnect_config.next_backoff(backoff);
+            } else {
+                backoff = self.reconnect_config.initial_backoff;
+            }
+
+            match self.connect_with_backoff().await {
+                Ok((send, new_recv)) => {
+                    tracing::info!(addr = ?new_recv.peer_addr(), "Websocket reconnected");
+                    recv = new_recv;
+                    state.send_replace(ConnectionState::Connected(send));
+                }
+                Err(err) => {
+                    tracing::warn!(?err, "Websocket reconnect failed, giving up");
+                    state.send_replace(ConnectionState::Closed);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+struct RunTask(tokio::task::JoinHandle<()>);
+
+impl Drop for RunTask {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// A websocket client which reconnects to the remote when its connection
+/// drops, backing off exponentially between attempts. After reconnecting,
+/// it authenticates again with the same data it was created with, and
+/// signals are delivered to the existing subscribers.
+///
+/// Requests made while disconnected wait for the connection to be
+/// re-established, within their timeout. Requests which were in flight
+/// when the connection dropped fail, and are not retried.
+///
+/// The connection is closed when all clones of this sender are dropped.
+#[derive(Clone)]
+pub struct ReconnectingWebsocketSender {
+    shared: Arc<Shared>,
+    state: tokio::sync::watch::Receiver<ConnectionState>,
+    _task: Arc<RunTask>,
+}
+
+impl ReconnectingWebsocketSender {
+    /// Establish a new outgoing websocket connection to remote.
+    pub async fn connect(
+        config: Arc<WebsocketConfig>,
+        reconnect_config: ReconnectConfig,
+        request: impl Into<ConnectRequest>,
+    ) -> Result<Self> {
+        Self::connect_inner(config, reconnect_config, request.into(), None).await
+    }
+
+    /// Establish a new outgoing websocket connection to remote, and
+    /// authenticate with `s` each time the connection is established.
+    ///
+    /// The remote must accept the same authentication more than once,
+    /// so for an app interface this needs a token which isn't single use.
+    pub async fn connect_authenticated<S>(
+        config: Arc<WebsocketConfig>,
+        reconnect_config: ReconnectConfig,
+        request: impl Into<ConnectRequest>,
+        s: S,
+    ) -> Result<Self>
+    where
+        S: std::fmt::Debug,
+        SerializedBytes: TryFrom<S, Error = SerializedBytesError>,
+    {
+        let data = SerializedBytes::try_from(s).map_err(Error::other)?;
+        Self::connect_inner(
+            config,
+            reconnect_config,
+            request.into(),
+            Some(UnsafeBytes::from(data).into()),
+        )
+        .await
+    }
+
+    async fn connect_inner(
+        config: Arc<WebsocketConfig>,
+        reconnect_config: ReconnectConfig,
+        request: ConnectRequest,
+        authentication: Option<Vec<u8>>,
+    ) -> Result<Self> {
+        let (signal_send, _) = tokio::sync::broadcast::channel(1024);
+        let shared = Arc::new(Shared {
+            config,
+            reconnect_config,
+            request,
+            authentication,
+            signal_send,
+        });
+
+        let (send, recv) = shared.connect_with_backoff().await?;
+        let (state_send, state) = tokio::sync::watch::channel(ConnectionState::Connected(send));
+        let task = tokio::task::spawn(shared.clone().run(recv, state_send));
+
+        Ok(Self {
+            shared,
+            state,
+            _task: Arc::new(RunTask(task)),
+        })
+    }
+
+    /// Subscribe to signals from the remote. The subscription carries on
+    /// across reconnects, though signals sent by the remote while the
+    /// connection was down are not received.
+    pub fn subscribe_signals(&self) -> tokio::sync::broadcast::Receiver<Vec<u8>> {
+        self.shared.signal_send.subscribe()
+    }
+
+    /// Whether the connection is currently established.
+    pub fn is_connected(&self) -> bool {
+        matches!(*self.state.borrow(), ConnectionState::Connected(_))
+    }
+
+    /// Wait for a connection, until `timeout_at`.
+    async fn connected(&self, timeout_at: tokio::time::Instant) -> Result<WebsocketSender> {
+        let mut state = self.state.clone();
+        let state = tokio::time::timeout_at(
+            timeout_at,
+            state.wait_for(|s| !matches!(s, ConnectionState::Connecting)),
+        )
+        .await
+        .map_err(Error::other)?
+        .map_err(|_| Error::other("WebsocketClosed"))?
+        .clone();
+        match state {
+            ConnectionState::Connected(send) => Ok(send),
+            _ => Err(Error::other("WebsocketClosed")),
+        }
+    }
+
+    /// Make a request of the remote using the default configured timeout.
+    pub async fn request<S, R>(&self, s: S) -> Result<R>
+    where
+        S: std::fmt::Debug,
+        SerializedBytes: TryFrom<S, Error = SerializedBytesError>,
+        R: serde::de::DeserializeOwned + std::fmt::Debug,
+    {
+        self.request_timeout(s, self.shared.config.default_request_timeout)
+            .await
+    }
+
+    /// Make a request of the remote. The timeout includes any time spent
+    /// waiting for the connection to be re-established.
+    pub async fn request_timeout<S, R>(&self, s: S, timeout: Duration) -> Result<R>
+    where
+        S: std::fmt::Debug,
+        SerializedBytes: TryFrom<S, Error = SerializedBytesError>,
+        R: serde::de::DeserializeOwned + std::fmt::Debug,
+    {
+        let timeout_at = tokio::time::Instant::now() + timeout;
+        let send = self.connected(timeout_at).await?;
+        send.request_timeout(s, timeout_at - tokio::time::Instant::now())
+            .await
+    }
+
+    /// Send a signal to the remote using the default configured timeout.
+    pub async fn signal<S>(&self, s: S) -> Result<()>
+    where
+        S: std::fmt::Debug,
+        SerializedBytes: TryFrom<S, Error = SerializedBytesError>,
+    {
+        self.signal_timeout(s, self.shared.config.default_request_timeout)
+            .await
+    }
+
+    /// Send a signal to the remote. The timeout includes any time spent
+    /// waiting for the connection to be re-established.
+    pub async fn signal_timeout<S>(&self, s: S, timeout: Duration) -> Result<()>
+    where
+        S: std::fmt::Debug,
+        SerializedBytes: TryFrom<S, Error = SerializedBytesError>,
+    {
+        let timeout_at = tokio::time::Instant::now() + timeout;
+        let send = self.connected(timeout_at).await?;
+        send.signal_timeout(s, timeout_at - tokio::time::Instant::now())
+            .await
+    }
+}
@@ -397,3 +397,82 @@ async fn handle_client_close() {
         .expect("Error joining the signal sender task")
         .expect("Other error than WebsocketClosed while sending signals");
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reconnecting_sender_resumes_after_disconnect() {
+    holochain_trace::test_run();
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, PartialEq)]
+    enum TestMsg {
+        Auth,
+        Hello,
+        Signal(u8),
+    }
+
+    let l = WebsocketListener::bind(Arc::new(WebsocketConfig::LISTENER_DEFAULT), "localhost:0")
+        .await
+        .unwrap();
+    let addr = l.local_addrs().unwrap()[0];
+
+    let l_task = tokio::task::spawn(async move {
+        // Each connection authenticates, then gets a signal and a response to a request.
+        // The signal is only sent once the client has made its request, by which time it
+        // has subscribed to signals. The first connection is then dropped.
+        for n in 0..2 {
+            let (send, mut recv) = l.accept().await.unwrap();
+            match recv.recv::<TestMsg>().await.unwrap() {
+                ReceiveMessage::Authenticate(data) => {
+                    assert_eq!(encode(&TestMsg::Auth).unwrap(), data);
+                }
+                oth => panic!("unexpected: {oth:?}"),
+            }
+            match recv.recv::<TestMsg>().await.unwrap() {
+                ReceiveMessage::Request(data, res) => {
+                    assert_eq!(TestMsg::Hello, data);
+                    send.signal(TestMsg::Signal(n)).await.unwrap();
+                    res.respond(TestMsg::Hello).await.unwrap();
+                }
+                oth => panic!("unexpected: {oth:?}"),
+            }
+        }
+    });
+
+    let mut reconnect_config = ReconnectConfig::DEFAULT;
+    reconnect_config.initial_backoff = std::time::Duration::from_millis(10);
+    let send = ReconnectingWebsocketSender::connect_authenticated(
+        Arc::new(WebsocketConfig::CLIENT_DEFAULT),
+        reconnect_config,
+        addr,
+        TestMsg::Auth,
+    )
+    .await
+    .unwrap();
+    let mut signals = send.subscribe_signals();
+
+    for n in 0..2 {
+        // A request made after the first connection was dropped, but before the client
+        // noticed, fails with the connection, so it's retried.
+        let res: TestMsg = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                match send
+                    .request_timeout(TestMsg::Hello, std::time::Duration::from_secs(5))
+                    .await
+                {
+                    Ok(res) => break res,
+                    Err(err) => tracing::debug!(?err, "request failed, retrying"),
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(TestMsg::Hello, res);
+
+        let signal = tokio::time::timeout(std::time::Duration::from_secs(5), signals.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(encode(&TestMsg::Signal(n)).unwrap(), signal);
+    }
+
+    l_task.await.unwrap();
+}
@@ -130,6 +130,12 @@ impl ActionExt for Action {
                 action_seq,
                 prev_action,
                 ..
+            })
+            | Self::RenewMembraneProof(RenewMembraneProof {
+                timestamp,
+                action_seq,
+                prev_action,
+                ..
             }) => {
                 *timestamp = new_timestamp;
                 *action_seq = new_seq;
@@ -7,6 +7,9 @@ pub struct GetAgentActivityInput {
     pub agent_pubkey: holo_hash::AgentPubKey,
     pub chain_query_filter: crate::query::ChainQueryFilter,
     pub activity_request: crate::query::ActivityRequest,
+    /// Where the activity is read from.
+    #[serde(default)]
+    pub read_source: crate::entry::ReadSource,
 }
 
 impl GetAgentActivityInput {
@@ -20,8 +23,44 @@ impl GetAgentActivityInput {
             agent_pubkey,
             chain_query_filter,
             activity_request,
+            read_source: Default::default(),
         }
     }
+
+    /// Set where the activity is read from.
+    pub fn with_read_source(mut self, read_source: crate::entry::ReadSource) -> Self {
+        self.read_source = read_source;
+        self
+    }
+}
+
+/// Input to fetch the action at a single position of an agent's chain.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GetActionBySeqInput {
+    /// The author of the chain.
+    pub author: holo_hash::AgentPubKey,
+    /// The sequence number of the action on the chain.
+    pub action_seq: u32,
+    /// Where the action is read from.
+    #[serde(default)]
+    pub read_source: crate::entry::ReadSource,
+}
+
+impl GetActionBySeqInput {
+    /// Constructor.
+    pub fn new(author: holo_hash::AgentPubKey, action_seq: u32) -> Self {
+        Self {
+            author,
+            action_seq,
+            read_source: Default::default(),
+        }
+    }
+
+    /// Set where the action is read from.
+    pub fn with_read_source(mut self, read_source: crate::entry::ReadSource) -> Self {
+        self.read_source = read_source;
+        self
+    }
 }
 
 /// Query arguments for the deterministic version of GetAgentActivity
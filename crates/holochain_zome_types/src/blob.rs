@@ -0,0 +1,25 @@
+//! Types for the host functions which store and fetch the chunks of blobs.
+//!
+//! Each chunk of a blob is published to the DHT as its own op, addressed by
+//! the hash of its bytes, rather than as a source chain entry. Authorities for
+//! a chunk only fetch it when they are sent it, and readers only fetch the
+//! chunks they read.
+
+use holo_hash::ActionHash;
+use holo_hash::EntryHash;
+use holochain_serialized_bytes::prelude::*;
+
+/// The bytes of one chunk of a blob, at most
+/// [`BLOB_CHUNK_SIZE`](holochain_integrity_types::blob::BLOB_CHUNK_SIZE) long.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
+pub struct BlobChunk(#[serde(with = "serde_bytes")] pub Vec<u8>);
+
+/// Input to `hold_blob_chunks`, which keeps the chunks of a blob on this node
+/// until the manifest which lists them is released.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
+pub struct HoldBlobChunksInput {
+    /// The action which created the manifest of the blob.
+    pub manifest: ActionHash,
+    /// The hashes of the chunks the manifest lists.
+    pub chunks: Vec<EntryHash>,
+}
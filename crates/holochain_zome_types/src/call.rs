@@ -9,6 +9,8 @@ pub type RoleName = String;
 pub enum CallTargetCell {
     OtherCell(CellId),
     OtherRole(RoleName),
+    /// A clone cell in the same app as the calling cell, by its clone id.
+    OtherClone(CloneId),
     Local,
 }
 
@@ -65,6 +67,84 @@ impl Call {
     }
 }
 
+/// A read-only call to a function on another agent's source chain.
+///
+/// The remote agent must have granted the function with
+/// [`GrantedFunctions::Queries`], and the function is not allowed to write to
+/// the remote agent's source chain or the network.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RemoteQuery {
+    pub agent: AgentPubKey,
+    pub zome_name: ZomeName,
+    pub fn_name: FunctionName,
+    pub cap_secret: Option<CapSecret>,
+    pub payload: ExternIO,
+}
+
+impl RemoteQuery {
+    pub fn new(
+        agent: AgentPubKey,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap_secret: Option<CapSecret>,
+        payload: ExternIO,
+    ) -> Self {
+        Self {
+            agent,
+            zome_name,
+            fn_name,
+            cap_secret,
+            payload,
+        }
+    }
+}
+
+/// A call to a function on another agent's source chain which the conductor
+/// keeps retrying, while the agent can't be reached, until its time to live
+/// runs out.
+///
+/// The call is persisted, so retries carry on across conductor restarts. The
+/// response is delivered to the calling cell's app as a system signal.
+///
+/// Delivery is at least once: a call whose response is lost is made again.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DurableCallRemote {
+    pub agent: AgentPubKey,
+    pub zome_name: ZomeName,
+    pub fn_name: FunctionName,
+    pub cap_secret: Option<CapSecret>,
+    pub payload: ExternIO,
+    /// How long to keep retrying the call for.
+    pub ttl: core::time::Duration,
+}
+
+impl DurableCallRemote {
+    pub fn new(
+        agent: AgentPubKey,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap_secret: Option<CapSecret>,
+        payload: ExternIO,
+        ttl: core::time::Duration,
+    ) -> Self {
+        Self {
+            agent,
+            zome_name,
+            fn_name,
+            cap_secret,
+            payload,
+            ttl,
+        }
+    }
+}
+
+/// Identifies a [`DurableCallRemote`] queued by the conductor, so that its
+/// response can be matched up with the call.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub struct DurableCallId(pub u64);
+
 #[allow(missing_docs)]
 pub trait CallbackResult: Sized {
     /// if a callback result is definitive we should halt any further iterations over remaining
@@ -0,0 +1,88 @@
+//! Types for reading the time with an estimate of how the network's clocks compare.
+
+use holochain_integrity_types::Timestamp;
+
+/// The local system time of the host, along with an estimate of how far
+/// the clocks of the peers it has recently talked to are from it.
+///
+/// Useful when the local clock alone isn't enough, for example to choose
+/// countersigning session times that other agents won't see as being in the future.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct NetworkTime {
+    /// The local system time, as [`sys_time`] would return it.
+    ///
+    /// [`sys_time`]: https://docs.rs/hdk/latest/hdk/time/fn.sys_time.html
+    pub local: Timestamp,
+    /// The estimated offset of the network's clocks from the local clock.
+    ///
+    /// This is `None` until the host has measured the clocks of some peers.
+    pub offset: Option<NetworkTimeOffset>,
+}
+
+/// An estimate of how far the network's clocks are from the local clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct NetworkTimeOffset {
+    /// How far ahead of the local clock the network is, in microseconds.
+    /// Negative if the network is behind the local clock.
+    pub offset_micros: i64,
+    /// How far off the offset may be, in microseconds, in either direction.
+    pub uncertainty_micros: i64,
+    /// How many peers the estimate is based on.
+    pub peer_count: u32,
+}
+
+impl NetworkTime {
+    /// The local time corrected by the estimated network offset,
+    /// or the local time if there is no estimate.
+    pub fn network_now(&self) -> Timestamp {
+        match self.offset {
+            Some(offset) => {
+                Timestamp::from_micros(self.local.as_micros().saturating_add(offset.offset_micros))
+            }
+            None => self.local,
+        }
+    }
+
+    /// The earliest and latest time it may currently be on the network,
+    /// given the uncertainty of the estimate.
+    ///
+    /// Both are the local time if there is no estimate.
+    pub fn network_now_bounds(&self) -> (Timestamp, Timestamp) {
+        let now = self.network_now().as_micros();
+        let uncertainty = self.offset.map_or(0, |o| o.uncertainty_micros);
+        (
+            Timestamp::from_micros(now.saturating_sub(uncertainty)),
+            Timestamp::from_micros(now.saturating_add(uncertainty)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_now_applies_the_offset() {
+        let local = Timestamp::from_micros(10_000);
+        let no_estimate = NetworkTime {
+            local,
+            offset: None,
+        };
+        assert_eq!(local, no_estimate.network_now());
+        assert_eq!((local, local), no_estimate.network_now_bounds());
+
+        let behind = NetworkTime {
+            local,
+            offset: Some(NetworkTimeOffset {
+                offset_micros: -2_000,
+                uncertainty_micros: 500,
+                peer_count: 3,
+            }),
+        };
+        assert_eq!(Timestamp::from_micros(8_000), behind.network_now());
+        assert_eq!(
+            (Timestamp::from_micros(7_500), Timestamp::from_micros(8_500)),
+            behind.network_now_bounds()
+        );
+    }
+}
@@ -54,6 +54,14 @@ pub struct GetOptions {
     /// Configure whether data should be fetched from the network or only from the local
     /// databases.
     pub strategy: GetStrategy,
+    /// Configure where data is read from. Takes precedence over the `strategy`
+    /// if set.
+    #[serde(default)]
+    pub read_source: Option<ReadSource>,
+    /// Configure what happens to data authored by agents with outstanding
+    /// valid warrants against them. The app's default is used if not set.
+    #[serde(default)]
+    pub warranted_authors: Option<WarrantedAuthors>,
 }
 
 impl GetOptions {
@@ -65,6 +73,8 @@ impl GetOptions {
     pub fn network() -> Self {
         Self {
             strategy: GetStrategy::Network,
+            read_source: None,
+            warranted_authors: None,
         }
     }
     /// Gets the action/entry and its metadata from local databases only.
@@ -72,6 +82,40 @@ impl GetOptions {
     pub fn local() -> Self {
         Self {
             strategy: GetStrategy::Local,
+            read_source: None,
+            warranted_authors: None,
+        }
+    }
+    /// Gets the action/entry and its metadata from local databases, and only
+    /// goes to the network if they don't hold it.
+    pub fn cache_then_network() -> Self {
+        ReadSource::CacheThenNetwork.into()
+    }
+
+    /// Where data is read from, as set by the `read_source` or else
+    /// the `strategy`.
+    pub fn read_source(&self) -> ReadSource {
+        self.read_source.unwrap_or_else(|| self.strategy.into())
+    }
+
+    /// Set what happens to data authored by agents with outstanding valid
+    /// warrants against them.
+    pub fn with_warranted_authors(mut self, warranted_authors: WarrantedAuthors) -> Self {
+        self.warranted_authors = Some(warranted_authors);
+        self
+    }
+}
+
+impl From<ReadSource> for GetOptions {
+    fn from(read_source: ReadSource) -> Self {
+        let strategy = match read_source {
+            ReadSource::LocalOnly => GetStrategy::Local,
+            ReadSource::CacheThenNetwork | ReadSource::NetworkOnly => GetStrategy::Network,
+        };
+        Self {
+            strategy,
+            read_source: Some(read_source),
+            warranted_authors: None,
         }
     }
 }
@@ -97,6 +141,52 @@ pub enum GetStrategy {
     Local,
 }
 
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+/// Where data is read from by the read host functions.
+pub enum ReadSource {
+    /// Read from the local databases only. No network call is made, so the
+    /// read never waits on the network.
+    LocalOnly,
+    /// Read from the local databases, and only go to the network if they
+    /// don't hold the data.
+    CacheThenNetwork,
+    /// Fetch the latest data from the network before reading, even if the
+    /// local databases already hold it. This is the behaviour of [`GetStrategy::Network`].
+    ///
+    /// The read is still answered from the local databases, which the fetched
+    /// data is merged into, so locally held data is returned along with
+    /// anything newer the network had. If the current agent is authoring the
+    /// data or is an authority for it, nothing is fetched from the network.
+    #[default]
+    NetworkOnly,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+/// What the get host functions do with data authored by agents with
+/// outstanding valid warrants against them.
+///
+/// Only the warrants held by this conductor are known, which are those it
+/// holds as an agent activity authority and those it has issued itself.
+pub enum WarrantedAuthors {
+    /// Return the data as if its author had no warrants.
+    #[default]
+    Include,
+    /// Return the data, along with the warrants against its author where
+    /// the result has room for them, i.e. in [`RecordDetails`](crate::metadata::RecordDetails).
+    Annotate,
+    /// Leave out data authored by warranted agents.
+    Exclude,
+}
+
+impl From<GetStrategy> for ReadSource {
+    fn from(strategy: GetStrategy) -> Self {
+        match strategy {
+            GetStrategy::Network => Self::NetworkOnly,
+            GetStrategy::Local => Self::LocalOnly,
+        }
+    }
+}
+
 /// Zome input to create an entry.
 #[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
 pub struct CreateInput {
@@ -162,6 +252,80 @@ impl GetInput {
     }
 }
 
+/// Zome input to get the public entries of one type created by an author.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct GetEntriesByAuthorInput {
+    /// The author of the entries.
+    pub author: holo_hash::AgentPubKey,
+    /// The type of the entries.
+    pub entry_type: EntryType,
+    /// Which of the entries to get.
+    #[serde(default)]
+    pub page: EntriesPage,
+    /// Where the entries are read from.
+    #[serde(default)]
+    pub read_source: ReadSource,
+}
+
+impl GetEntriesByAuthorInput {
+    /// Constructor.
+    pub fn new(author: holo_hash::AgentPubKey, entry_type: EntryType, page: EntriesPage) -> Self {
+        Self {
+            author,
+            entry_type,
+            page,
+            read_source: Default::default(),
+        }
+    }
+
+    /// Set where the entries are read from.
+    pub fn with_read_source(mut self, read_source: ReadSource) -> Self {
+        self.read_source = read_source;
+        self
+    }
+}
+
+/// A page of the entries created by an author, which are listed in the order
+/// they were created.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub struct EntriesPage {
+    /// Only list entries created after the action at this position of the
+    /// author's chain. The next page starts after the last record of a page.
+    pub after_seq: Option<u32>,
+    /// The most entries to list, which authorities cap at [`EntriesPage::MAX_LIMIT`].
+    pub limit: u32,
+}
+
+impl EntriesPage {
+    /// The number of entries listed when no limit is given.
+    pub const DEFAULT_LIMIT: u32 = 100;
+
+    /// The most entries an authority lists in one response.
+    pub const MAX_LIMIT: u32 = 1000;
+
+    /// The first page of entries.
+    pub fn first(limit: u32) -> Self {
+        Self {
+            after_seq: None,
+            limit,
+        }
+    }
+
+    /// The page of entries created after the action at `after_seq`.
+    pub fn after(after_seq: u32, limit: u32) -> Self {
+        Self {
+            after_seq: Some(after_seq),
+            limit,
+        }
+    }
+}
+
+impl Default for EntriesPage {
+    fn default() -> Self {
+        Self::first(Self::DEFAULT_LIMIT)
+    }
+}
+
 /// Zome input type for all update operations.
 #[derive(PartialEq, Debug, Deserialize, Serialize, Clone)]
 pub struct UpdateInput {
@@ -240,6 +404,7 @@ pub fn entry_type_matches(entry_type: &EntryType, entry: &Entry) -> bool {
         (EntryType::AgentPubKey, Entry::Agent(_)) => true,
         (EntryType::App(_), Entry::App(_)) => true,
         (EntryType::App(_), Entry::CounterSign(_, _)) => true,
+        (EntryType::App(_), Entry::DualSign(_, _)) => true,
         (EntryType::CapClaim, Entry::CapClaim(_)) => true,
         (EntryType::CapGrant, Entry::CapGrant(_)) => true,
         _ => false,
@@ -141,7 +141,8 @@ impl ActionRefMut for Action {
             | Self::CloseChain(CloseChain { ref mut author, .. })
             | Self::OpenChain(OpenChain { ref mut author, .. })
             | Self::Create(Create { ref mut author, .. })
-            | Self::Update(Update { ref mut author, .. }) => author,
+            | Self::Update(Update { ref mut author, .. })
+            | Self::RenewMembraneProof(RenewMembraneProof { ref mut author, .. }) => author,
         }
     }
 
@@ -177,6 +178,9 @@ impl ActionRefMut for Action {
             })
             | Self::Update(Update {
                 ref mut timestamp, ..
+            })
+            | Self::RenewMembraneProof(RenewMembraneProof {
+                ref mut timestamp, ..
             }) => timestamp,
         }
     }
@@ -212,6 +216,9 @@ impl ActionRefMut for Action {
             })
             | Self::Update(Update {
                 ref mut action_seq, ..
+            })
+            | Self::RenewMembraneProof(RenewMembraneProof {
+                ref mut action_seq, ..
             }) => Some(action_seq),
         }
     }
@@ -256,6 +263,10 @@ impl ActionRefMut for Action {
                 ref mut prev_action,
                 ..
             }) => Some(prev_action),
+            Self::RenewMembraneProof(RenewMembraneProof {
+                ref mut prev_action,
+                ..
+            }) => Some(prev_action),
         }
     }
 
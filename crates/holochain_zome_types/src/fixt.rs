@@ -72,6 +72,9 @@ impl Iterator for AppEntryDefFixturator<EntryVisibility> {
 /// Alias
 pub type MaybeMembraneProof = Option<Arc<SerializedBytes>>;
 
+/// Alias
+pub type MaybePurgePolicy = Option<PurgePolicy>;
+
 fixturator!(
     ActionBuilderCommon;
     constructor fn new(AgentPubKey, Timestamp, u32, ActionHash);
@@ -182,22 +185,53 @@ fixturator!(
     constructor fn new(ZomeName, ZomeIndex, SerializedBytes, EntryDefs, FunctionNameVec, ScopedZomeTypesSet);
 );
 
+fixturator!(
+    AgentInfoV1;
+    curve Empty AgentInfoV1 {
+        agent_initial_pubkey: fixt!(AgentPubKey, Empty),
+        agent_latest_pubkey: fixt!(AgentPubKey, Empty),
+        chain_head: (fixt!(ActionHash, Empty), fixt!(u32, Empty), fixt!(Timestamp, Empty)),
+    };
+    curve Unpredictable AgentInfoV1 {
+        agent_initial_pubkey: fixt!(AgentPubKey, Unpredictable),
+        agent_latest_pubkey: fixt!(AgentPubKey, Unpredictable),
+        chain_head: (fixt!(ActionHash, Unpredictable), fixt!(u32, Unpredictable), fixt!(Timestamp, Unpredictable)),
+    };
+    curve Predictable AgentInfoV1 {
+        agent_initial_pubkey: fixt!(AgentPubKey, Predictable),
+        agent_latest_pubkey: fixt!(AgentPubKey, Predictable),
+        chain_head: (fixt!(ActionHash, Predictable), fixt!(u32, Predictable), fixt!(Timestamp, Predictable)),
+    };
+);
+
 fixturator!(
     AgentInfo;
     curve Empty AgentInfo {
         agent_initial_pubkey: fixt!(AgentPubKey, Empty),
         agent_latest_pubkey: fixt!(AgentPubKey, Empty),
         chain_head: (fixt!(ActionHash, Empty), fixt!(u32, Empty), fixt!(Timestamp, Empty)),
+        chain_length: fixt!(u32, Empty),
+        storage_arc: None,
     };
     curve Unpredictable AgentInfo {
         agent_initial_pubkey: fixt!(AgentPubKey, Unpredictable),
         agent_latest_pubkey: fixt!(AgentPubKey, Unpredictable),
         chain_head: (fixt!(ActionHash, Unpredictable), fixt!(u32, Unpredictable), fixt!(Timestamp, Unpredictable)),
+        chain_length: fixt!(u32, Unpredictable),
+        storage_arc: Some(StorageArcSummary {
+            bounds: Some((fixt!(u32, Unpredictable), fixt!(u32, Unpredictable))),
+            coverage: 0.5,
+        }),
     };
     curve Predictable AgentInfo {
         agent_initial_pubkey: fixt!(AgentPubKey, Predictable),
         agent_latest_pubkey: fixt!(AgentPubKey, Predictable),
         chain_head: (fixt!(ActionHash, Predictable), fixt!(u32, Predictable), fixt!(Timestamp, Predictable)),
+        chain_length: fixt!(u32, Predictable),
+        storage_arc: Some(StorageArcSummary {
+            bounds: None,
+            coverage: 0.0,
+        }),
     };
 );
 
@@ -472,9 +506,29 @@ fixturator!(
     from u8;
 );
 
+fixturator!(
+    PurgePolicy;
+    constructor fn after_secs(u64);
+);
+
+fixturator!(
+    EntryDefPolicies;
+    curve Empty EntryDefPolicies::default();
+    curve Unpredictable EntryDefPolicies {
+        required_validations: fixt!(RequiredValidations),
+        max_size: None,
+        purge: fixt!(MaybePurgePolicy),
+    };
+    curve Predictable EntryDefPolicies {
+        required_validations: RequiredValidationsFixturator::new_indexed(Predictable, get_fixt_index!()).next().unwrap(),
+        max_size: None,
+        purge: MaybePurgePolicyFixturator::new_indexed(Predictable, get_fixt_index!()).next().unwrap(),
+    };
+);
+
 fixturator!(
     EntryDef;
-    constructor fn new(EntryDefId, EntryVisibility, RequiredValidations, bool);
+    constructor fn new(EntryDefId, EntryVisibility, bool, EntryDefPolicies);
 );
 
 fixturator!(
@@ -520,6 +574,27 @@ fixturator! {
     };
 }
 
+fixturator!(
+    MembraneProof;
+    curve Empty Arc::new(SerializedBytesFixturator::new(Empty).next().unwrap());
+    curve Unpredictable Arc::new(fixt!(SerializedBytes));
+    curve Predictable Arc::new(SerializedBytesFixturator::new_indexed(Predictable, get_fixt_index!()).next().unwrap());
+);
+
+fixturator! {
+    MaybePurgePolicy;
+    enum [ Some None ];
+    curve Empty MaybePurgePolicy::None;
+    curve Unpredictable match MaybePurgePolicyVariant::random() {
+        MaybePurgePolicyVariant::None => MaybePurgePolicy::None,
+        MaybePurgePolicyVariant::Some => MaybePurgePolicy::Some(fixt!(PurgePolicy)),
+    };
+    curve Predictable match MaybePurgePolicyVariant::nth(get_fixt_index!()) {
+        MaybePurgePolicyVariant::None => MaybePurgePolicy::None,
+        MaybePurgePolicyVariant::Some => MaybePurgePolicy::Some(PurgePolicyFixturator::new_indexed(Predictable, get_fixt_index!()).next().unwrap()),
+    };
+}
+
 fixturator! {
     EntryType;
     enum [ AgentPubKey App CapClaim CapGrant ];
@@ -547,6 +622,11 @@ fixturator!(
     constructor fn from_builder(ActionBuilderCommon, MaybeMembraneProof);
 );
 
+fixturator!(
+    RenewMembraneProof;
+    constructor fn from_builder(ActionBuilderCommon, ActionHash, MembraneProof);
+);
+
 fixturator!(
     InitZomesComplete;
     constructor fn from_builder(ActionBuilderCommon);
@@ -578,7 +658,7 @@ fixturator!(
     };
     curve Entry {
         let et = match get_fixt_curve!() {
-            Entry::App(_) | Entry::CounterSign(_, _) => EntryType::App(AppEntryDefFixturator::new_indexed(Unpredictable, get_fixt_index!()).next().unwrap()),
+            Entry::App(_) | Entry::CounterSign(_, _) | Entry::DualSign(_, _) => EntryType::App(AppEntryDefFixturator::new_indexed(Unpredictable, get_fixt_index!()).next().unwrap()),
             Entry::Agent(_) => EntryType::AgentPubKey,
             Entry::CapClaim(_) => EntryType::CapClaim,
             Entry::CapGrant(_) => EntryType::CapGrant,
@@ -614,7 +694,7 @@ fixturator!(
 
     curve Entry {
         let et = match get_fixt_curve!() {
-            Entry::App(_) | Entry::CounterSign(_, _) => EntryType::App(AppEntryDefFixturator::new_indexed(Unpredictable, get_fixt_index!()).next().unwrap()),
+            Entry::App(_) | Entry::CounterSign(_, _) | Entry::DualSign(_, _) => EntryType::App(AppEntryDefFixturator::new_indexed(Unpredictable, get_fixt_index!()).next().unwrap()),
             Entry::Agent(_) => EntryType::AgentPubKey,
             Entry::CapClaim(_) => EntryType::CapClaim,
             Entry::CapGrant(_) => EntryType::CapGrant,
@@ -642,6 +722,7 @@ fixturator!(
         Create(Create)
         Update(Update)
         Delete(Delete)
+        RenewMembraneProof(RenewMembraneProof)
     ];
 
     curve PublicCurve {
@@ -925,3 +1006,93 @@ fixturator!(
             .unwrap()],
     };
 );
+
+fixturator!(
+    Role;
+    constructor fn new(U8);
+);
+
+fixturator!(
+    PreflightBytes;
+    curve Empty PreflightBytes(Vec::new());
+    curve Unpredictable PreflightBytes(
+        BytesFixturator::new_indexed(Unpredictable, get_fixt_index!()).next().unwrap()
+    );
+    curve Predictable PreflightBytes(
+        BytesFixturator::new_indexed(Predictable, get_fixt_index!()).next().unwrap()
+    );
+);
+
+// Session times always start now so that preflight requests built from them
+// can be accepted by a conductor.
+fixturator!(
+    CounterSigningSessionTimes;
+    curve Empty {
+        let start = Timestamp::now();
+        CounterSigningSessionTimes::try_new(start, (start + Duration::from_secs(30)).unwrap())
+            .unwrap()
+    };
+    curve Unpredictable {
+        let start = Timestamp::now();
+        let secs = rng().gen_range(10..120);
+        CounterSigningSessionTimes::try_new(start, (start + Duration::from_secs(secs)).unwrap())
+            .unwrap()
+    };
+    curve Predictable {
+        let start = Timestamp::now();
+        let secs = 10 + get_fixt_index!() as u64 % 110;
+        CounterSigningSessionTimes::try_new(start, (start + Duration::from_secs(secs)).unwrap())
+            .unwrap()
+    };
+);
+
+fixturator!(
+    PreflightRequest;
+    curve Empty {
+        PreflightRequestFixturator::new_indexed(
+            vec![(fixt!(AgentPubKey), vec![]), (fixt!(AgentPubKey), vec![])],
+            get_fixt_index!(),
+        )
+        .next()
+        .unwrap()
+    };
+    curve Unpredictable {
+        PreflightRequestFixturator::new_indexed(
+            vec![
+                (fixt!(AgentPubKey), vec![fixt!(Role)]),
+                (fixt!(AgentPubKey), vec![fixt!(Role)]),
+            ],
+            get_fixt_index!(),
+        )
+        .next()
+        .unwrap()
+    };
+    curve Predictable {
+        PreflightRequestFixturator::new_indexed(
+            vec![
+                (fixt!(AgentPubKey), vec![Role(0)]),
+                (fixt!(AgentPubKey), vec![Role(1)]),
+            ],
+            get_fixt_index!(),
+        )
+        .next()
+        .unwrap()
+    };
+    // A valid, non-enzymatic request for a public app entry signed by the given agents.
+    curve CounterSigningAgents {
+        let app_entry_def = AppEntryDefFixturator::new_indexed(EntryVisibility::Public, get_fixt_index!())
+            .next()
+            .unwrap();
+        PreflightRequest::try_new(
+            EntryHashFixturator::new_indexed(Unpredictable, get_fixt_index!()).next().unwrap(),
+            get_fixt_curve!(),
+            vec![],
+            0,
+            false,
+            CounterSigningSessionTimesFixturator::new_indexed(Empty, get_fixt_index!()).next().unwrap(),
+            ActionBase::Create(CreateBase::new(EntryType::App(app_entry_def))),
+            PreflightBytes(vec![]),
+        )
+        .unwrap()
+    };
+);
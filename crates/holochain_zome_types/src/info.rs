@@ -8,7 +8,7 @@ pub use holochain_integrity_types::info::*;
 /// The struct containing all information about the executing agent's identity.
 #[allow(missing_docs)]
 #[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq)]
-pub struct AgentInfo {
+pub struct AgentInfoV1 {
     /// The current agent's pubkey at genesis.
     /// Always found at index 2 in the source chain.
     pub agent_initial_pubkey: AgentPubKey,
@@ -19,7 +19,7 @@ pub struct AgentInfo {
     pub chain_head: (ActionHash, u32, Timestamp),
 }
 
-impl AgentInfo {
+impl AgentInfoV1 {
     pub fn new(
         agent_initial_pubkey: AgentPubKey,
         agent_latest_pubkey: AgentPubKey,
@@ -33,6 +33,51 @@ impl AgentInfo {
     }
 }
 
+/// The struct containing all information about the executing agent's identity,
+/// its source chain and its current storage responsibilities on the DHT.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq)]
+pub struct AgentInfoV2 {
+    /// The current agent's pubkey at genesis.
+    /// Always found at index 2 in the source chain.
+    pub agent_initial_pubkey: AgentPubKey,
+    /// The current agent's current pubkey.
+    /// Same as the initial pubkey if it has never been changed.
+    /// The agent can revoke an old key and replace it with a new one, the latest appears here.
+    pub agent_latest_pubkey: AgentPubKey,
+    /// The hash, sequence number and timestamp of the action at the head of the chain.
+    pub chain_head: (ActionHash, u32, Timestamp),
+    /// The number of actions on the source chain, including the head.
+    pub chain_length: u32,
+    /// The DHT arc the cell is currently storing data for.
+    /// `None` if the cell hasn't published its agent info to the network yet.
+    pub storage_arc: Option<StorageArcSummary>,
+}
+
+/// Convenience alias to the latest `AgentInfoN`.
+pub type AgentInfo = AgentInfoV2;
+
+/// A summary of the DHT arc a cell is storing data for.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StorageArcSummary {
+    /// The inclusive start and end DHT locations of the arc,
+    /// or `None` if the arc is empty.
+    /// The arc wraps around the DHT if the start is after the end.
+    pub bounds: Option<(u32, u32)>,
+    /// The fraction of the DHT covered by the arc, between 0.0 and 1.0.
+    pub coverage: f64,
+}
+
+impl StorageArcSummary {
+    /// Check whether the arc covers a DHT location.
+    pub fn contains(&self, location: u32) -> bool {
+        match self.bounds {
+            None => false,
+            Some((start, end)) if start <= end => start <= location && location <= end,
+            Some((start, end)) => start <= location || location <= end,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CallInfo {
     /// The provenance identifies the agent who made the call.
@@ -13,6 +13,7 @@
 pub mod action;
 #[allow(missing_docs)]
 pub mod agent_activity;
+pub mod blob;
 #[allow(missing_docs)]
 pub mod block;
 pub mod bytes;
@@ -22,6 +23,7 @@ pub mod capability;
 pub mod cell;
 #[allow(missing_docs)]
 pub mod chain;
+pub mod clock;
 pub mod clone;
 pub mod countersigning;
 #[allow(missing_docs)]
@@ -41,6 +43,7 @@ pub mod judged;
 #[allow(missing_docs)]
 pub mod link;
 pub mod metadata;
+pub mod metrics;
 #[allow(missing_docs)]
 pub mod migrate_agent;
 #[allow(missing_docs)]
@@ -100,6 +100,17 @@ pub struct GetLinksInput {
     /// The tag prefix to filter by.
     pub tag_prefix: Option<LinkTag>,
 
+    /// Only include links with a tag in this range, from the start tag inclusive
+    /// to the end tag exclusive. Tags are compared byte by byte, so tags which
+    /// should sort numerically need to be encoded big-endian.
+    #[serde(default)]
+    pub tag_range: Option<(LinkTag, LinkTag)>,
+
+    /// Only include links whose tag carries a [`LinkTagPayload`]
+    /// matching all of these filters.
+    #[serde(default)]
+    pub tag_fields: Vec<LinkTagFieldFilter>,
+
     /// Only include links created after this time.
     pub after: Option<Timestamp>,
 
@@ -108,6 +119,65 @@ pub struct GetLinksInput {
 
     /// Only include links created by this author.
     pub author: Option<AgentPubKey>,
+
+    /// The order to return the links in. Defaults to oldest first.
+    #[serde(default)]
+    pub order: LinkOrder,
+
+    /// Only include links created or deleted at or after this watermark.
+    ///
+    /// Unlike `after`, this is compared with when the link ops were
+    /// integrated by each node rather than when they were authored, so ops
+    /// which were authored earlier but only arrived since are included.
+    /// Pass the [`LinksDelta::watermark`] of the previous `get_links_delta`
+    /// to fetch only what changed since then.
+    #[serde(default)]
+    pub since: Option<Timestamp>,
+}
+
+/// The order in which links are returned by `get_links`.
+///
+/// Links are ordered canonically by the timestamp of their create link action,
+/// then by the hash of that action, so every agent which sees the same links
+/// gets them in the same order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinkOrder {
+    /// Oldest first.
+    #[default]
+    Ascending,
+    /// Newest first, the exact reverse of [`LinkOrder::Ascending`].
+    Descending,
+}
+
+impl LinkOrder {
+    /// Sort links into this order.
+    pub fn sort(self, links: &mut [Link]) {
+        links.sort_by(|a, b| {
+            (a.timestamp, &a.create_link_hash).cmp(&(b.timestamp, &b.create_link_hash))
+        });
+        if self == LinkOrder::Descending {
+            links.reverse();
+        }
+    }
+}
+
+/// The changes to a set of links since a watermark, returned by `get_links_delta`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, SerializedBytes)]
+pub struct LinksDelta {
+    /// Links created since the watermark which haven't been deleted, oldest first.
+    pub created: Vec<Link>,
+    /// The create link action hashes of links deleted since the watermark.
+    ///
+    /// This can include links which were also created since the watermark,
+    /// or which the other filters of the query would have excluded.
+    pub deleted: Vec<ActionHash>,
+    /// When this delta started to be read, to pass as the `since` of the next
+    /// query.
+    ///
+    /// Link ops which arrive while a delta is read can be returned again by the
+    /// next one. Authorities compare the watermark with when they integrated
+    /// each op, so it relies on the clocks of nodes being roughly in sync.
+    pub watermark: Timestamp,
 }
 
 type CreateLinkWithDeleteLinks = Vec<(SignedActionHashed, Vec<SignedActionHashed>)>;
@@ -133,3 +203,40 @@ impl LinkDetails {
         self.into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(timestamp: i64, hash_byte: u8) -> Link {
+        Link {
+            author: AgentPubKey::from_raw_32(vec![0; 32]),
+            base: ActionHash::from_raw_32(vec![0; 32]).into(),
+            target: ActionHash::from_raw_32(vec![0; 32]).into(),
+            timestamp: Timestamp(timestamp),
+            zome_index: 0.into(),
+            link_type: 0.into(),
+            tag: LinkTag::new(vec![]),
+            create_link_hash: ActionHash::from_raw_32(vec![hash_byte; 32]),
+        }
+    }
+
+    #[test]
+    fn link_order_is_timestamp_then_hash() {
+        let mut links = vec![link(2, 1), link(1, 3), link(1, 2), link(0, 9)];
+
+        LinkOrder::Ascending.sort(&mut links);
+        let ascending = links
+            .iter()
+            .map(|l| (l.timestamp.0, l.create_link_hash.get_raw_32()[0]))
+            .collect::<Vec<_>>();
+        assert_eq!(ascending, vec![(0, 9), (1, 2), (1, 3), (2, 1)]);
+
+        LinkOrder::Descending.sort(&mut links);
+        let descending = links
+            .iter()
+            .map(|l| (l.timestamp.0, l.create_link_hash.get_raw_32()[0]))
+            .collect::<Vec<_>>();
+        assert_eq!(descending, vec![(2, 1), (1, 3), (1, 2), (0, 9)]);
+    }
+}
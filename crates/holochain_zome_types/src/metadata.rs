@@ -2,7 +2,9 @@
 use crate::record::Record;
 use crate::record::SignedActionHashed;
 use crate::validate::ValidationStatus;
+use crate::warrant::SignedWarrant;
 use crate::Entry;
+use holochain_integrity_types::dual_signing::DualSignData;
 use holochain_serialized_bytes::prelude::*;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, SerializedBytes)]
@@ -30,6 +32,33 @@ pub struct RecordDetails {
     pub deletes: Vec<SignedActionHashed>,
     /// Any [`Update`](crate::action::Update) on this record.
     pub updates: Vec<SignedActionHashed>,
+    /// The valid warrants held against the author of this record. Only
+    /// filled in for gets with [`WarrantedAuthors::Annotate`](crate::entry::WarrantedAuthors::Annotate).
+    #[serde(default)]
+    pub warrants: Vec<SignedWarrant>,
+}
+
+impl RecordDetails {
+    /// If this record proposes a dual signed entry, the updates of it made by the
+    /// counterparty, oldest first. Any valid one of these is an acceptance of the proposal.
+    ///
+    /// See [`dual_signing`](holochain_integrity_types::dual_signing).
+    pub fn dual_sign_acceptance_candidates(&self) -> Vec<&SignedActionHashed> {
+        let counterparty = match self.record.entry().as_option() {
+            Some(Entry::DualSign(data, _)) => match data.as_ref() {
+                DualSignData::Proposal { counterparty } => counterparty,
+                DualSignData::Acceptance { .. } => return Vec::new(),
+            },
+            _ => return Vec::new(),
+        };
+        let mut candidates: Vec<_> = self
+            .updates
+            .iter()
+            .filter(|update| update.action().author() == counterparty)
+            .collect();
+        candidates.sort_by_key(|update| update.action().timestamp());
+        candidates
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, SerializedBytes)]
@@ -0,0 +1,23 @@
+//! Types for the metrics the conductor keeps about a DNA.
+
+use holochain_integrity_types::EntryDefIndex;
+use holochain_integrity_types::ZomeIndex;
+use holochain_serialized_bytes::prelude::*;
+
+/// Counts of the actions on the app entries of one entry type which passed or
+/// failed app validation on this conductor since it started.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
+pub struct EntryTypeMetrics {
+    /// The index of the integrity zome which defines the entry type.
+    pub zome_index: ZomeIndex,
+    /// The index of the entry type within its zome.
+    pub entry_index: EntryDefIndex,
+    /// The number of valid create actions.
+    pub creates: u64,
+    /// The number of valid update actions.
+    pub updates: u64,
+    /// The number of valid delete actions.
+    pub deletes: u64,
+    /// The number of actions which were rejected by app validation.
+    pub validation_failures: u64,
+}
@@ -2,12 +2,14 @@
 
 pub use crate::action::*;
 pub use crate::agent_activity::*;
+pub use crate::blob::*;
 pub use crate::block::*;
 pub use crate::bytes::*;
 pub use crate::call::*;
 pub use crate::capability::*;
 pub use crate::cell::*;
 pub use crate::chain::*;
+pub use crate::clock::*;
 pub use crate::clone::*;
 pub use crate::countersigning::*;
 pub use crate::crdt::*;
@@ -21,6 +23,7 @@ pub use crate::init::*;
 pub use crate::judged::*;
 pub use crate::link::*;
 pub use crate::metadata::*;
+pub use crate::metrics::*;
 pub use crate::migrate_agent::*;
 pub use crate::op::*;
 #[cfg(feature = "properties")]
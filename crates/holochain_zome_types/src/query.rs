@@ -96,6 +96,10 @@ pub struct LinkQuery {
     /// Filter by tag prefix.
     pub tag_prefix: Option<LinkTag>,
 
+    /// Filter by a range of tags, from the start tag inclusive to the end tag exclusive.
+    #[serde(default)]
+    pub tag_range: Option<(LinkTag, LinkTag)>,
+
     /// Only include links created before this time.
     pub before: Option<Timestamp>,
 
@@ -373,6 +377,7 @@ impl LinkQuery {
             base: base.into(),
             link_type,
             tag_prefix: None,
+            tag_range: None,
             before: None,
             after: None,
             author: None,
@@ -385,6 +390,12 @@ impl LinkQuery {
         self
     }
 
+    /// Filter for links with a tag from `start` inclusive to `end` exclusive.
+    pub fn tag_range(mut self, start: LinkTag, end: LinkTag) -> Self {
+        self.tag_range = Some((start, end));
+        self
+    }
+
     /// Filter for links created before `before`.
     pub fn before(mut self, before: Timestamp) -> Self {
         self.before = Some(before);
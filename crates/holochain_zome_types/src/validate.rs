@@ -46,6 +46,29 @@ impl CallbackResult for ValidateCallbackResult {
     }
 }
 
+impl CallbackResult for DependencyHintsCallbackResult {
+    fn is_definitive(&self) -> bool {
+        false
+    }
+    fn try_from_wasm_error(wasm_error: WasmError) -> Result<Self, WasmError> {
+        match wasm_error.error {
+            // Hints are only an optimisation, so a failing callback just
+            // doesn't give any.
+            WasmErrorInner::Guest(_)
+            | WasmErrorInner::Serialize(_)
+            | WasmErrorInner::Deserialize(_) => Ok(DependencyHintsCallbackResult::default()),
+            WasmErrorInner::Host(_)
+            | WasmErrorInner::HostShortCircuit(_)
+            | WasmErrorInner::Compile(_)
+            | WasmErrorInner::CallError(_)
+            | WasmErrorInner::PointerMap
+            | WasmErrorInner::ErrorWhileError
+            | WasmErrorInner::Memory
+            | WasmErrorInner::UninitializedSerializedModuleCache => Err(wasm_error),
+        }
+    }
+}
+
 #[cfg(feature = "full")]
 impl rusqlite::ToSql for ValidationStatus {
     fn to_sql(&self) -> rusqlite::Result<rusqlite::types::ToSqlOutput> {
@@ -72,6 +72,11 @@ impl IntegrityZome {
             def: self.def.erase_type(),
         }
     }
+
+    /// Import the types of another integrity zome into this zome.
+    pub fn set_dependency(&mut self, zome_name: impl Into<ZomeName>) {
+        self.def.set_dependency(zome_name);
+    }
 }
 
 impl CoordinatorZome {
@@ -210,6 +215,16 @@ impl IntegrityZomeDef {
     pub fn as_any_zome_def(&self) -> &ZomeDef {
         &self.0
     }
+
+    /// Import the types of another integrity zome into this zome.
+    pub fn set_dependency(&mut self, zome_name: impl Into<ZomeName>) {
+        match &mut self.0 {
+            ZomeDef::Wasm(WasmZome { dependencies, .. }) => dependencies.push(zome_name.into()),
+
+            #[cfg(feature = "full-dna-def")]
+            ZomeDef::Inline { dependencies, .. } => dependencies.push(zome_name.into()),
+        }
+    }
 }
 
 impl CoordinatorZomeDef {
@@ -39,7 +39,8 @@ wasm_io_types! {
     fn accept_countersigning_preflight_request(zt::countersigning::PreflightRequest) -> zt::countersigning::PreflightRequestAcceptance;
 
     // Info about the calling agent.
-    fn agent_info (()) -> zt::info::AgentInfo;
+    fn agent_info_1 (()) -> zt::info::AgentInfoV1;
+    fn agent_info_2 (()) -> zt::info::AgentInfoV2;
 
     // Block some agent on the same DNA.
     fn block_agent (zt::block::BlockAgentInput) -> ();
@@ -62,12 +63,18 @@ wasm_io_types! {
     // @todo Get the capability for the current zome call.
     fn capability_info (()) -> ();
 
+    // Store one chunk of a blob and publish it, returning the hash of its bytes.
+    fn create_blob_chunk (zt::blob::BlobChunk) -> holo_hash::EntryHash;
+
     // Returns ActionHash of the newly created record.
     fn create (zt::entry::CreateInput) -> holo_hash::ActionHash;
 
     // Create a link between two entries.
     fn create_link (zt::link::CreateLinkInput) -> holo_hash::ActionHash;
 
+    // Create many links between entries in one pass.
+    fn create_links (Vec<zt::link::CreateLinkInput>) -> Vec<holo_hash::ActionHash>;
+
     fn create_x25519_keypair(()) -> zt::x_salsa20_poly1305::x25519::X25519PubKey;
 
     // The debug host import takes a TraceMsg to output wherever the host wants to display it.
@@ -77,6 +84,15 @@ wasm_io_types! {
     // Action hash of the CreateLink record.
     fn delete_link (zt::link::DeleteLinkInput) -> holo_hash::ActionHash;
 
+    // Action hashes of the CreateLink records.
+    fn delete_links (Vec<zt::link::DeleteLinkInput>) -> Vec<holo_hash::ActionHash>;
+
+    // Create an entry unless an identical live one is already on the chain.
+    fn ensure_entry (zt::entry::CreateInput) -> holo_hash::ActionHash;
+
+    // Create a link unless an identical live one is already on the chain.
+    fn ensure_link (zt::link::CreateLinkInput) -> holo_hash::ActionHash;
+
     // Delete a record.
     fn delete (zt::entry::DeleteInput) -> holo_hash::ActionHash;
 
@@ -84,12 +100,27 @@ wasm_io_types! {
     // Emit a Signal::App to subscribers on the interface
     fn emit_signal (zt::signal::AppSignal) -> ();
 
+    // Get one chunk of a blob, fetching it from its authorities if it isn't held locally.
+    fn get_blob_chunk (holo_hash::EntryHash) -> Option<zt::blob::BlobChunk>;
+
     fn get_agent_activity (zt::agent_activity::GetAgentActivityInput) -> zt::query::AgentActivity;
 
+    // Get the action at a single position of an agent's chain.
+    fn get_action_by_seq (zt::agent_activity::GetActionBySeqInput) -> Option<zt::prelude::SignedActionHashed>;
+
     fn get_details (Vec<zt::entry::GetInput>) -> Vec<Option<zt::metadata::Details>>;
 
+    // Get the counterparty's acceptance of a proposed dual signed entry.
+    fn get_dual_sign_acceptance (zt::entry::GetInput) -> Option<zt::prelude::Record>;
+
+    // Get a page of the public entries of one type created by an author.
+    fn get_entries_by_author (zt::entry::GetEntriesByAuthorInput) -> Vec<zt::prelude::Record>;
+
     fn get_link_details (Vec<zt::link::GetLinksInput>) -> Vec<zt::link::LinkDetails>;
 
+    // Get the links created and deleted since a watermark.
+    fn get_links_delta (Vec<zt::link::GetLinksInput>) -> Vec<zt::link::LinksDelta>;
+
     // Get links by entry hash from the cascade.
     fn get_links (Vec<zt::link::GetLinksInput>) -> Vec<Vec<zt::link::Link>>;
 
@@ -98,6 +129,9 @@ wasm_io_types! {
     // Attempt to get a live entry from the cascade.
     fn get (Vec<zt::entry::GetInput>) -> Vec<Option<zt::record::Record>>;
 
+    // Keep the chunks listed by a blob manifest on this node.
+    fn hold_blob_chunks (zt::blob::HoldBlobChunksInput) -> ();
+
     // Hash data on the host.
     fn hash (zt::hash::HashInput) -> zt::hash::HashOutput;
 
@@ -112,15 +146,30 @@ wasm_io_types! {
 
     fn must_get_agent_activity (zt::chain::MustGetAgentActivityInput) -> Vec<zt::op::RegisterAgentActivity>;
 
+    // Counts of the actions on each app entry type of the calling DNA.
+    fn entry_type_metrics (()) -> Vec<zt::metrics::EntryTypeMetrics>;
+
+    // Current system time along with an estimate of the network's clock offset.
+    fn network_time (()) -> zt::clock::NetworkTime;
+
     // Query the source chain for data.
     fn query (zt::query::ChainQueryFilter) -> Vec<crate::prelude::Record>;
 
+    // Stop keeping the chunks of a blob manifest, dropping those nothing else holds.
+    fn release_blob_chunks (holo_hash::ActionHash) -> ();
+
     // the length of random bytes to create
     fn random_bytes (u32) -> zt::bytes::Bytes;
 
     // Remotely signal many agents without waiting for responses
     fn send_remote_signal (zt::signal::RemoteSignal) -> ();
 
+    // Call a read-only function on a remote agent's chain.
+    fn remote_query (zt::call::RemoteQuery) -> zt::prelude::ZomeCallResponse;
+
+    // Queue a call to a remote agent which is retried until it is delivered or expires.
+    fn call_remote_durable (zt::call::DurableCallRemote) -> zt::call::DurableCallId;
+
     // // @todo
     // fn send (()) -> ();
 
@@ -204,6 +253,9 @@ wasm_io_types! {
 
     // Open your chain, pointing to the previous DNA
     fn open_chain(zt::chain::OpenChainInput) -> holo_hash::ActionHash;
+
+    // Renew your membrane proof, superseding your current one
+    fn renew_membrane_proof(zt::chain::RenewMembraneProofInput) -> holo_hash::ActionHash;
 }
 
 /// Anything that can go wrong while calling a HostFnApi method
@@ -213,7 +265,7 @@ pub enum HostFnApiError {
     RibosomeError(Box<dyn std::error::Error + Send + Sync>),
 }
 
-#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Eq, Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum ZomeCallAuthorization {
     Authorized,
     BadSignature,
@@ -235,7 +287,7 @@ impl ZomeCallAuthorization {
 }
 
 /// Response to a zome call.
-#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, SerializedBytes, PartialEq)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, SerializedBytes, PartialEq, Eq)]
 pub enum ZomeCallResponse {
     /// Arbitrary response from zome fns to the outside world.
     /// Something like a 200 http response.
@@ -275,6 +327,13 @@ pub struct ZomeCallUnsigned {
     pub nonce: Nonce256Bits,
     /// Time after which this zome call MUST NOT be accepted.
     pub expires_at: Timestamp,
+    /// How many milliseconds to wait for the data committed by the call to be
+    /// published, if the caller opted into waiting.
+    ///
+    /// It is left out of the signed bytes when it is `None`, so calls which
+    /// don't wait are signed the same as before the field was added.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_for_publish: Option<u64>,
 }
 
 impl ZomeCallUnsigned {
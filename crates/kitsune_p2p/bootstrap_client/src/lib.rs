@@ -2,15 +2,21 @@ use kitsune_p2p_bootstrap::error::BootstrapClientError;
 use kitsune_p2p_bootstrap::error::BootstrapClientResult;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
 use kitsune_p2p_types::bootstrap::RandomQuery;
+use kitsune_p2p_types::config::ServiceClientConfig;
 use std::convert::TryFrom;
 use std::convert::TryInto;
 use std::sync::OnceLock;
 use url2::Url2;
 
+#[cfg(feature = "tx5")]
+mod signal_relay;
+#[cfg(feature = "tx5")]
+pub use signal_relay::SignalRelay;
+
 pub mod prelude {
     pub use kitsune_p2p_bootstrap::error::*;
 
-    pub use super::{now, now_once, proxy_list, put, random, BootstrapNet};
+    pub use super::{now, now_once, proxy_list, put, random, BootstrapClient, BootstrapNet};
 }
 
 /// The "net" flag / bucket to use when talking to the bootstrap server.
@@ -29,8 +35,39 @@ impl BootstrapNet {
     }
 }
 
-/// Reuse a single reqwest Client for efficiency as we likely need several connections.
-static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+/// The HTTP client used to talk to bootstrap services.
+///
+/// Each kitsune instance builds its own from the `service_client` of its
+/// network config, so conductors in the same process can use different proxies
+/// and root certificates. It's cheap to clone, clones share their connections.
+#[derive(Clone, Debug, Default)]
+pub struct BootstrapClient(reqwest::Client);
+
+impl BootstrapClient {
+    /// Build a client which connects through the proxy and trusts the extra
+    /// root certificates of a [`ServiceClientConfig`].
+    pub fn new(config: &ServiceClientConfig) -> BootstrapClientResult<Self> {
+        Ok(Self(client_builder(config)?.build()?))
+    }
+}
+
+/// Start building an HTTP client which connects through the proxy and trusts
+/// the extra root certificates of a [`ServiceClientConfig`].
+fn client_builder(config: &ServiceClientConfig) -> BootstrapClientResult<reqwest::ClientBuilder> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(proxy_url) = &config.proxy_url {
+        builder = builder.proxy(reqwest::Proxy::all(proxy_url.as_str())?);
+    }
+    for path in &config.extra_root_certs {
+        let pem = std::fs::read(path).map_err(|err| {
+            BootstrapClientError::Bootstrap(
+                format!("could not read root certificate {}: {err}", path.display()).into(),
+            )
+        })?;
+        builder = builder.add_root_certificate(reqwest::Certificate::from_pem(&pem)?);
+    }
+    Ok(builder)
+}
 
 /// A cell to hold our local offset for calculating a 'now' that is compatible with the remote
 /// service. This is much less precise and comprehensive than NTP style calculations.
@@ -52,6 +89,7 @@ const OP_PROXY_LIST: &str = "proxy_list";
 
 /// Standard interface to the remote bootstrap service.
 ///
+/// - client: the client to make the request with
 /// - url: the url of the bootstrap service or None to short circuit and not send a request
 /// - op: the header op for the remote service
 /// - input: op-specific struct that will be messagepack encoded and sent as binary data in the
@@ -59,6 +97,7 @@ const OP_PROXY_LIST: &str = "proxy_list";
 ///
 /// Output type O is op specific and needs to be messagepack decodeable.
 async fn do_api<I: serde::Serialize, O: serde::de::DeserializeOwned>(
+    client: &BootstrapClient,
     url: Option<Url2>,
     op: &str,
     input: I,
@@ -70,8 +109,8 @@ async fn do_api<I: serde::Serialize, O: serde::de::DeserializeOwned>(
         Some(url) => {
             let url = format!("{}?net={}", url.as_str(), net.value());
 
-            let res = CLIENT
-                .get_or_init(reqwest::Client::new)
+            let res = client
+                .0
                 .post(url.as_str())
                 .body(body_data)
                 .header(OP_HEADER, op)
@@ -97,11 +136,12 @@ async fn do_api<I: serde::Serialize, O: serde::de::DeserializeOwned>(
 /// Input must be an AgentInfoSigned with a valid signature otherwise the remote service will not
 /// accept the data.
 pub async fn put(
+    client: &BootstrapClient,
     url: Option<Url2>,
     agent_info_signed: AgentInfoSigned,
     net: BootstrapNet,
 ) -> BootstrapClientResult<()> {
-    match do_api(url, OP_PUT, agent_info_signed, net).await {
+    match do_api(client, url, OP_PUT, agent_info_signed, net).await {
         Ok(Some(())) => Ok(()),
         Ok(None) => Ok(()),
         Err(e) => Err(e),
@@ -120,8 +160,12 @@ fn local_now() -> BootstrapClientResult<u64> {
 ///
 /// There is no input to the `now` endpoint, just `()` to be encoded as nil in messagepack.
 #[allow(dead_code)]
-pub async fn now(url: Option<Url2>, net: BootstrapNet) -> BootstrapClientResult<u64> {
-    match do_api(url, OP_NOW, (), net).await {
+pub async fn now(
+    client: &BootstrapClient,
+    url: Option<Url2>,
+    net: BootstrapNet,
+) -> BootstrapClientResult<u64> {
+    match do_api(client, url, OP_NOW, (), net).await {
         // If the server gives us something useful we use it.
         Ok(Some(v)) => Ok(v),
         // If we don't have a server url we should trust ourselves.
@@ -136,11 +180,15 @@ pub async fn now(url: Option<Url2>, net: BootstrapNet) -> BootstrapClientResult<
 ///
 /// Calculates the offset on the first call and caches it in the cell above.
 /// Only calls `now` once then keeps the offset for the static lifetime.
-pub async fn now_once(url: Option<Url2>, net: BootstrapNet) -> BootstrapClientResult<u64> {
+pub async fn now_once(
+    client: &BootstrapClient,
+    url: Option<Url2>,
+    net: BootstrapNet,
+) -> BootstrapClientResult<u64> {
     match NOW_OFFSET_MILLIS.get() {
         Some(offset) => Ok(u64::try_from(i64::try_from(local_now()?)? + offset)?),
         None => {
-            let offset: i64 = match now(url.clone(), net).await {
+            let offset: i64 = match now(client, url.clone(), net).await {
                 Ok(v) => {
                     let offset = v as i64 - local_now()? as i64;
                     match NOW_OFFSET_MILLIS.set(offset) {
@@ -176,15 +224,17 @@ pub async fn now_once(url: Option<Url2>, net: BootstrapNet) -> BootstrapClientRe
 /// service to mitigate eclipse attacks by having a strong randomness implementation.
 #[allow(dead_code)]
 pub async fn random(
+    client: &BootstrapClient,
     url: Option<Url2>,
     query: RandomQuery,
     net: BootstrapNet,
 ) -> BootstrapClientResult<Vec<AgentInfoSigned>> {
-    let outer_vec: Vec<serde_bytes::ByteBuf> = match do_api(url, OP_RANDOM, query, net).await {
-        Ok(Some(v)) => v,
-        Ok(None) => Vec::new(),
-        Err(e) => return Err(e),
-    };
+    let outer_vec: Vec<serde_bytes::ByteBuf> =
+        match do_api(client, url, OP_RANDOM, query, net).await {
+            Ok(Some(v)) => v,
+            Ok(None) => Vec::new(),
+            Err(e) => return Err(e),
+        };
     let ret: Result<Vec<AgentInfoSigned>, _> = outer_vec
         .into_iter()
         .map(|bytes| kitsune_p2p_types::codec::rmp_decode(&mut AsRef::<[u8]>::as_ref(&bytes)))
@@ -196,13 +246,19 @@ pub async fn random(
 ///
 /// Fetches the list of proxy servers currently stored in the bootstrap service.
 #[allow(dead_code)]
-pub async fn proxy_list(url: Url2, net: BootstrapNet) -> BootstrapClientResult<Vec<Url2>> {
-    Ok(do_api::<_, Vec<String>>(Some(url), OP_PROXY_LIST, (), net)
-        .await?
-        .unwrap_or_default()
-        .into_iter()
-        .flat_map(|s| Url2::try_parse(s).ok())
-        .collect())
+pub async fn proxy_list(
+    client: &BootstrapClient,
+    url: Url2,
+    net: BootstrapNet,
+) -> BootstrapClientResult<Vec<Url2>> {
+    Ok(
+        do_api::<_, Vec<String>>(client, Some(url), OP_PROXY_LIST, (), net)
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|s| Url2::try_parse(s).ok())
+            .collect(),
+    )
 }
 
 #[cfg(test)]
@@ -260,6 +316,7 @@ mod tests {
 
         // Simply hitting the endpoint should be OK.
         put(
+            &BootstrapClient::default(),
             Some(url2::url2!("http://{:?}", addr)),
             agent_info_signed,
             BootstrapNet::Tx5,
@@ -273,6 +330,7 @@ mod tests {
         bad.signature = Arc::new(vec![].into());
         let bad = AgentInfoSigned(Arc::new(bad));
         let res = put(
+            &BootstrapClient::default(),
             Some(url2::url2!("http://{:?}", addr)),
             bad,
             BootstrapNet::Tx5,
@@ -283,6 +341,38 @@ mod tests {
         abort_handle.abort();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_client_config() {
+        let (addr, abort_handle) = start_bootstrap().await;
+        let url = url2::url2!("http://{:?}", addr);
+
+        let direct = BootstrapClient::new(&ServiceClientConfig::default()).unwrap();
+        now(&direct, Some(url.clone()), BootstrapNet::Tx5)
+            .await
+            .unwrap();
+
+        // Requests go through the proxy, which isn't listening.
+        for proxy_url in ["http://127.0.0.1:1", "socks5h://127.0.0.1:1"] {
+            let proxied = BootstrapClient::new(&ServiceClientConfig {
+                proxy_url: Some(url2::url2!("{}", proxy_url)),
+                ..Default::default()
+            })
+            .unwrap();
+            assert!(now(&proxied, Some(url.clone()), BootstrapNet::Tx5)
+                .await
+                .is_err());
+        }
+
+        // A root certificate which can't be read is an error.
+        let res = BootstrapClient::new(&ServiceClientConfig {
+            extra_root_certs: vec!["/no/such/ca.pem".into()],
+            ..Default::default()
+        });
+        assert!(matches!(res, Err(BootstrapClientError::Bootstrap(_))));
+
+        abort_handle.abort();
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_now() {
         let (addr, abort_handle) = start_bootstrap().await;
@@ -296,18 +386,26 @@ mod tests {
             .unwrap();
 
         // We should be able to get a milliseconds timestamp back.
-        let remote_now: u64 = now(Some(url2::url2!("http://{:?}", addr)), BootstrapNet::Tx5)
-            .await
-            .unwrap();
+        let remote_now: u64 = now(
+            &BootstrapClient::default(),
+            Some(url2::url2!("http://{:?}", addr)),
+            BootstrapNet::Tx5,
+        )
+        .await
+        .unwrap();
         let threshold = 5000;
 
         assert!((remote_now - local_millis) < threshold);
 
         // Now once should return some number and the remote server offset should be set in the
         // NOW_OFFSET_MILLIS once cell.
-        let _: u64 = now_once(Some(url2::url2!("http://{:?}", addr)), BootstrapNet::Tx5)
-            .await
-            .unwrap();
+        let _: u64 = now_once(
+            &BootstrapClient::default(),
+            Some(url2::url2!("http://{:?}", addr)),
+            BootstrapNet::Tx5,
+        )
+        .await
+        .unwrap();
         assert!(NOW_OFFSET_MILLIS.get().is_some());
 
         abort_handle.abort();
@@ -321,9 +419,13 @@ mod tests {
         let mut u = arbitrary::Unstructured::new(&[0; 1024]);
 
         let space = fixt!(KitsuneSpace, Unpredictable);
-        let now = now(Some(url2::url2!("http://{:?}", addr)), BootstrapNet::Tx5)
-            .await
-            .unwrap();
+        let now = now(
+            &BootstrapClient::default(),
+            Some(url2::url2!("http://{:?}", addr)),
+            BootstrapNet::Tx5,
+        )
+        .await
+        .unwrap();
 
         let alice = create_test_keypair();
         let bob = create_test_keypair();
@@ -353,6 +455,7 @@ mod tests {
             .unwrap();
 
             put(
+                &BootstrapClient::default(),
                 Some(url2::url2!("http://{:?}", addr)),
                 agent_info_signed.clone(),
                 BootstrapNet::Tx5,
@@ -364,6 +467,7 @@ mod tests {
         }
 
         let mut random = super::random(
+            &BootstrapClient::default(),
             Some(url2::url2!("http://{:?}", addr)),
             RandomQuery {
                 space: Arc::new(space.clone()),
@@ -381,6 +485,7 @@ mod tests {
         assert!(random == expected);
 
         let random_single = super::random(
+            &BootstrapClient::default(),
             Some(url2::url2!("http://{:?}", addr)),
             RandomQuery {
                 space: Arc::new(space.clone()),
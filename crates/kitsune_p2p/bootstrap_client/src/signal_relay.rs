@@ -0,0 +1,208 @@
+//! A local relay for the connection to a tx5 signal server.
+//!
+//! tx5 connects to its signal server directly, so it can't go through a proxy
+//! or trust extra root certificates. When a [`ServiceClientConfig`] asks for
+//! either, tx5 connects to this relay on a loopback port instead. The relay
+//! makes the websocket connection to the signal server with an HTTP client
+//! built from the config and forwards the bytes both ways.
+
+use crate::client_builder;
+use kitsune_p2p_bootstrap::error::BootstrapClientError;
+use kitsune_p2p_bootstrap::error::BootstrapClientResult;
+use kitsune_p2p_types::config::ServiceClientConfig;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use url2::Url2;
+
+/// The largest websocket upgrade request which is relayed.
+const MAX_REQUEST_HEAD_BYTES: usize = 16 * 1024;
+
+/// A relay on a loopback port to a tx5 signal server.
+///
+/// Peer urls contain the url of the signal server the peer listens at, so urls
+/// of the signal server must be translated to the relay with [`Self::to_local`]
+/// before they're given to tx5, and back with [`Self::to_public`] before
+/// they're given to peers. Urls of other signal servers are left as they are,
+/// and tx5 connects to them directly.
+///
+/// The relay stops when this is dropped.
+#[derive(Debug)]
+pub struct SignalRelay {
+    signal_url: Url2,
+    local_url: Url2,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl SignalRelay {
+    /// Start relaying to the signal server at `signal_url` through the proxy
+    /// and with the extra root certificates of `config`.
+    pub async fn spawn(
+        config: &ServiceClientConfig,
+        signal_url: &str,
+    ) -> BootstrapClientResult<Self> {
+        let signal_url = Url2::try_parse(signal_url).map_err(|err| {
+            BootstrapClientError::Bootstrap(
+                format!("invalid signal url {signal_url}: {err}").into(),
+            )
+        })?;
+        let upstream = match signal_url.scheme() {
+            "ws" => origin(&signal_url, "http"),
+            "wss" => origin(&signal_url, "https"),
+            scheme => {
+                return Err(BootstrapClientError::Bootstrap(
+                    format!("unsupported signal url scheme {scheme}").into(),
+                ))
+            }
+        };
+        // Websocket upgrades need HTTP/1.1.
+        let client = client_builder(config)?.http1_only().build()?;
+
+        let listener = tokio::net::TcpListener::bind((std::net::Ipv4Addr::LOCALHOST, 0)).await?;
+        let local_url = url2::url2!("ws://{}", listener.local_addr()?);
+
+        let task = tokio::task::spawn(async move {
+            loop {
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(err) => {
+                        tracing::warn!(?err, "signal relay failed to accept a connection");
+                        continue;
+                    }
+                };
+                let client = client.clone();
+                let upstream = upstream.clone();
+                tokio::task::spawn(async move {
+                    if let Err(err) = relay(&client, &upstream, stream).await {
+                        tracing::warn!(?err, %upstream, "signal relay connection failed");
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            signal_url,
+            local_url,
+            task,
+        })
+    }
+
+    /// The url of the relay, for tx5 to listen at in place of the signal server.
+    pub fn local_url(&self) -> String {
+        origin(&self.local_url, "ws")
+    }
+
+    /// Translate a url of the signal server to the relay.
+    pub fn to_local(&self, url: &str) -> String {
+        translate(url, &self.signal_url, &self.local_url)
+    }
+
+    /// Translate a url of the relay to the signal server.
+    pub fn to_public(&self, url: &str) -> String {
+        translate(url, &self.local_url, &self.signal_url)
+    }
+}
+
+impl Drop for SignalRelay {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// The scheme, host and port of a url, with another scheme.
+fn origin(url: &Url2, scheme: &str) -> String {
+    let mut origin = format!("{scheme}://{}", url.host_str().unwrap_or_default());
+    if let Some(port) = url.port() {
+        origin.push_str(&format!(":{port}"));
+    }
+    origin
+}
+
+/// Replace the scheme, host and port of `url` with those of `to`, if they are
+/// those of `from`.
+fn translate(url: &str, from: &Url2, to: &Url2) -> String {
+    let parsed = match Url2::try_parse(url) {
+        Ok(parsed) => parsed,
+        Err(_) => return url.to_string(),
+    };
+    if parsed.scheme() != from.scheme()
+        || parsed.host_str() != from.host_str()
+        || parsed.port_or_known_default() != from.port_or_known_default()
+    {
+        return url.to_string();
+    }
+    let mut translated = origin(to, to.scheme());
+    translated.push_str(parsed.path());
+    if let Some(query) = parsed.query() {
+        translated.push('?');
+        translated.push_str(query);
+    }
+    translated
+}
+
+/// Relay one websocket connection from tx5 to the signal server.
+async fn relay(
+    client: &reqwest::Client,
+    upstream: &str,
+    mut local: tokio::net::TcpStream,
+) -> BootstrapClientResult<()> {
+    let bad_request = |reason: &str| BootstrapClientError::Bootstrap(reason.into());
+
+    // tx5 sends nothing else until the upgrade is answered.
+    let mut head = Vec::new();
+    let mut buf = [0; 1024];
+    let head_len = loop {
+        let read = local.read(&mut buf).await?;
+        if read == 0 {
+            return Err(bad_request("connection closed before the upgrade request"));
+        }
+        head.extend_from_slice(&buf[..read]);
+        if let Some(end) = head.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end + 4;
+        }
+        if head.len() > MAX_REQUEST_HEAD_BYTES {
+            return Err(bad_request("upgrade request too large"));
+        }
+    };
+    let rest = head.split_off(head_len);
+    let head = std::str::from_utf8(&head).map_err(|_| bad_request("upgrade request not utf8"))?;
+
+    let mut lines = head.split("\r\n");
+    let path = lines
+        .next()
+        .and_then(|line| line.split(' ').nth(1))
+        .ok_or_else(|| bad_request("malformed upgrade request"))?;
+    let mut request = client
+        .get(format!("{upstream}{path}"))
+        .version(reqwest::Version::HTTP_11);
+    for line in lines.filter(|line| !line.is_empty()) {
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| bad_request("malformed upgrade request header"))?;
+        // The client sets the host of the signal server.
+        if name.trim().eq_ignore_ascii_case("host") {
+            continue;
+        }
+        request = request.header(name.trim(), value.trim());
+    }
+
+    let response = request.send().await?;
+    let mut reply = format!("HTTP/1.1 {}\r\n", response.status()).into_bytes();
+    for (name, value) in response.headers() {
+        reply.extend_from_slice(name.as_str().as_bytes());
+        reply.extend_from_slice(b": ");
+        reply.extend_from_slice(value.as_bytes());
+        reply.extend_from_slice(b"\r\n");
+    }
+    reply.extend_from_slice(b"\r\n");
+    local.write_all(&reply).await?;
+    if response.status() != reqwest::StatusCode::SWITCHING_PROTOCOLS {
+        return Err(BootstrapClientError::Bootstrap(
+            format!("signal server refused the upgrade: {}", response.status()).into(),
+        ));
+    }
+
+    let mut remote = response.upgrade().await?;
+    remote.write_all(&rest).await?;
+    tokio::io::copy_bidirectional(&mut local, &mut remote).await?;
+    Ok(())
+}
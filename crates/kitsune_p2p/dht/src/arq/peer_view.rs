@@ -26,6 +26,28 @@ impl PeerView {
             Self::Quantized(v) => v.update_arq(arq),
         }
     }
+
+    /// Shrink the arq to bring the data stored for it within a storage budget,
+    /// given the number of bytes which are stored.
+    pub fn shrink_arq_to_storage_budget(&self, arq: &mut Arq, used: u64, budget: u64) -> bool {
+        match self {
+            Self::Quantized(v) => v.shrink_arq_to_storage_budget(arq, used, budget),
+        }
+    }
+
+    /// The coverage of the whole DHT, extrapolated from the arqs within the filter.
+    pub fn extrapolated_coverage(&self, filter: &Arq) -> f64 {
+        match self {
+            Self::Quantized(v) => v.extrapolated_coverage(filter),
+        }
+    }
+
+    /// The clamping which the arq is set to instead of being resized, if any.
+    pub fn arc_clamping(&self) -> Option<ArqClamping> {
+        match self {
+            Self::Quantized(v) => v.strat.local_storage.arc_clamping.clone(),
+        }
+    }
 }
 
 /// The Quantized PeerView
@@ -130,6 +152,29 @@ impl PeerViewQ {
         }
     }
 
+    /// Shrink the arq to bring the data stored for it within a storage budget,
+    /// given the number of bytes which are stored.
+    ///
+    /// The arq is shrunk in proportion to how far over budget the stored data is. It isn't shrunk if the arc is clamped, or if
+    /// the extrapolated coverage is already at or below the minimum coverage, since
+    /// shrinking would leave the network under-covered.
+    ///
+    /// Returns true if the arq was shrunk.
+    pub fn shrink_arq_to_storage_budget(&self, arq: &mut Arq, used: u64, budget: u64) -> bool {
+        if used <= budget || self.strat.local_storage.arc_clamping.is_some() {
+            return false;
+        }
+        if self.extrapolated_coverage(arq) <= self.strat.min_coverage {
+            return false;
+        }
+        let count = (arq.count() as u128 * budget as u128 / used as u128) as u32;
+        if count >= arq.count() {
+            return false;
+        }
+        *arq.count_mut() = count;
+        true
+    }
+
     fn is_slacking(&self, cov: f64, num_peers: usize) -> bool {
         num_peers as f64 <= cov * self.strat.slacker_ratio
     }
@@ -492,4 +537,33 @@ mod tests {
             (2.0, 1)
         );
     }
+
+    #[test]
+    fn test_shrink_arq_to_storage_budget() {
+        let topo = Topology::unit_zero();
+        let pow = 24;
+        let arqs: Vec<_> = (0..0x100)
+            .step_by(0x10)
+            .map(|x| make_arq(&topo, pow, x, x + 0x20))
+            .collect();
+
+        // The coverage of 2 is above the minimum, so the arq can shrink.
+        let strat = ArqStrat {
+            min_coverage: 1.0,
+            ..ArqStrat::default()
+        };
+        let view = PeerViewQ::new(topo.clone(), strat, arqs.clone());
+        let mut arq = make_arq(&topo, pow, 0, 0x20);
+        let count = arq.count();
+        assert!(!view.shrink_arq_to_storage_budget(&mut arq, 100, 100));
+        assert_eq!(arq.count(), count);
+        assert!(view.shrink_arq_to_storage_budget(&mut arq, 200, 100));
+        assert_eq!(arq.count(), count / 2);
+
+        // The coverage of 2 is below the minimum, so the arq is left alone.
+        let view = PeerViewQ::new(topo.clone(), ArqStrat::default(), arqs);
+        let mut arq = make_arq(&topo, pow, 0, 0x20);
+        assert!(!view.shrink_arq_to_storage_budget(&mut arq, 200, 100));
+        assert_eq!(arq.count(), count);
+    }
 }
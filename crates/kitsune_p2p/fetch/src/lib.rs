@@ -74,6 +74,9 @@ pub enum TransferMethod {
     Publish,
     /// Transfer by gossiping
     Gossip,
+    /// Transfer by fetching from authorities on request,
+    /// for ops which are needed locally but aren't held
+    Fetch,
 }
 
 /// Usage agnostic context data.
@@ -194,6 +194,15 @@ impl FetchPool {
         self.state.share_ref(|s| s.queue.is_empty())
     }
 
+    /// Stop fetching from a source for the given duration, for example because it has been
+    /// sending invalid data. Items are fetched from their other sources in the meantime.
+    pub fn ban_source(&self, source: FetchSource, duration: Duration) {
+        self.state.share_mut(|s| {
+            tracing::debug!(?source, ?duration, "FetchPool source banned");
+            s.ban_source(source, Instant::now() + duration);
+        });
+    }
+
     /// Check the state of all sources and remove any that have expired. See the docs on State::check_sources for details.
     pub fn check_sources(&self) {
         self.state.share_mut(|s| {
@@ -336,6 +345,11 @@ impl State {
         }
     }
 
+    /// Stop using a source until the given time.
+    pub fn ban_source(&mut self, source: FetchSource, until: Instant) {
+        self.sources.entry(source).or_default().ban_until(until);
+    }
+
     /// Check for sources which have expired and remove them from the list of sources.
     /// Any ops which don't have any sources left will be removed from the queue.
     pub fn check_sources(&mut self, config: FetchConfig) {
@@ -640,6 +654,35 @@ mod tests {
         assert_eq!(num_items * num_items, seen_sources.len());
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn banned_sources_are_not_used() {
+        let cfg = Arc::new(TestFetchConfig(1, 10));
+        let mut q = State::default();
+        q.push(&*cfg, test_req_op(1, None, test_source(1)));
+        q.push(&*cfg, test_req_op(1, None, test_source(2)));
+
+        q.ban_source(test_source(1), Instant::now() + Duration::from_secs(60));
+
+        // Only the source which isn't banned is used
+        for _ in 0..5 {
+            let batch = q.get_batch(cfg.clone());
+            assert_eq!(1, batch.len());
+            assert_eq!(test_source(2), batch[0].2);
+
+            // Move time forwards so the item will be ready to retry
+            tokio::time::advance(Duration::from_secs(11)).await;
+        }
+
+        // Banning the remaining source leaves nothing to fetch from
+        q.ban_source(test_source(2), Instant::now() + Duration::from_secs(60));
+        assert!(q.get_batch(cfg.clone()).is_empty());
+
+        // Once the bans end the sources are used again
+        tokio::time::advance(Duration::from_secs(60)).await;
+        q.check_sources(cfg.clone());
+        assert_eq!(1, q.get_batch(cfg.clone()).len());
+    }
+
     #[tokio::test(start_paused = true)]
     async fn remove_fetch_item() {
         holochain_trace::test_run();
@@ -1,6 +1,7 @@
 use indexmap::IndexSet;
 use kitsune_p2p_types::KAgent;
 use std::{default, ops::Deref};
+use tokio::time::Instant;
 
 use crate::{backoff::FetchBackoff, FetchConfig};
 
@@ -76,6 +77,9 @@ impl Sources {
 pub(crate) struct SourceState {
     /// The current state of the source
     current_state: SourceCurrentState,
+    /// If the source is banned, when the ban ends. A banned source is not used but is kept
+    /// so that it can be used again once the ban ends.
+    banned_until: Option<Instant>,
 }
 
 impl SourceState {
@@ -84,6 +88,9 @@ impl SourceState {
     /// a fixed number of times to probe the source before going back into a backoff state. If any fetches from the
     /// probe attempts succeed then the source will be considered available again.
     pub fn should_use(&mut self) -> bool {
+        if self.is_banned() {
+            return false;
+        }
         match &mut self.current_state {
             SourceCurrentState::Available(_) => true,
             SourceCurrentState::Backoff(backoff) => backoff.is_ready(),
@@ -108,6 +115,20 @@ impl SourceState {
         }
     }
 
+    /// Stop using this source until the given time.
+    pub fn ban_until(&mut self, until: Instant) {
+        self.banned_until = Some(
+            self.banned_until
+                .map_or(until, |current| current.max(until)),
+        );
+    }
+
+    /// Check whether this source is currently banned.
+    pub fn is_banned(&self) -> bool {
+        self.banned_until
+            .map_or(false, |until| Instant::now() < until)
+    }
+
     /// Notify the state that a request to this source has timed out.
     pub fn record_timeout(&mut self) {
         if let SourceCurrentState::Available(num_timeouts) = &mut self.current_state {
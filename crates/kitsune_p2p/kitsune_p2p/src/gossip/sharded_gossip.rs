@@ -39,7 +39,10 @@ use crate::metrics::MetricsSync;
 
 use super::{HowToConnect, MetaOpKey};
 
+pub use bandwidth::gossip_bytes_total;
 pub use bandwidth::BandwidthThrottles;
+pub use bandwidth::SpaceBandwidthBudget;
+pub use bandwidth::TrafficPriority;
 
 /// How quickly to run a gossip iteration which attempts to initiate
 /// with a new target.
@@ -117,6 +120,8 @@ pub struct ShardedGossip {
     pub(crate) state: Share<ShardedGossipState>,
     /// Bandwidth for incoming and outgoing gossip.
     bandwidth: Arc<BandwidthThrottle>,
+    /// The outbound bandwidth budget shared with the rest of the space.
+    space_budget: Arc<SpaceBandwidthBudget>,
 }
 
 impl std::fmt::Debug for ShardedGossip {
@@ -166,6 +171,7 @@ impl ShardedGossip {
         host_api: HostApiLegacy,
         gossip_type: GossipType,
         bandwidth: Arc<BandwidthThrottle>,
+        space_budget: Arc<SpaceBandwidthBudget>,
         metrics: MetricsSync,
         fetch_pool: FetchPool,
         #[cfg(feature = "test")] enable_history: bool,
@@ -195,6 +201,7 @@ impl ShardedGossip {
                 fetch_pool,
             },
             bandwidth,
+            space_budget,
         });
 
         let mut refresh_agent_list_timer = std::time::Instant::now();
@@ -286,6 +293,11 @@ impl ShardedGossip {
         let timeout = self.gossip.tuning_params.implicit_timeout();
 
         self.bandwidth.outgoing_bytes(bytes).await;
+        let priority = match self.gossip.gossip_type {
+            GossipType::Recent => TrafficPriority::Publish,
+            GossipType::Historical => TrafficPriority::Backfill,
+        };
+        self.space_budget.outgoing_bytes(priority, bytes).await;
 
         let con = match how.clone() {
             HowToConnect::Con(con, remote_url) => {
@@ -1395,11 +1407,15 @@ impl AsGossipModule for ShardedGossip {
 
 struct ShardedRecentGossipFactory {
     bandwidth: Arc<BandwidthThrottle>,
+    space_budget: Arc<SpaceBandwidthBudget>,
 }
 
 impl ShardedRecentGossipFactory {
-    fn new(bandwidth: Arc<BandwidthThrottle>) -> Self {
-        Self { bandwidth }
+    fn new(bandwidth: Arc<BandwidthThrottle>, space_budget: Arc<SpaceBandwidthBudget>) -> Self {
+        Self {
+            bandwidth,
+            space_budget,
+        }
     }
 }
 
@@ -1420,6 +1436,7 @@ impl AsGossipModuleFactory for ShardedRecentGossipFactory {
             host,
             GossipType::Recent,
             self.bandwidth.clone(),
+            self.space_budget.clone(),
             metrics,
             fetch_pool,
         ))
@@ -1428,11 +1445,15 @@ impl AsGossipModuleFactory for ShardedRecentGossipFactory {
 
 struct ShardedHistoricalGossipFactory {
     bandwidth: Arc<BandwidthThrottle>,
+    space_budget: Arc<SpaceBandwidthBudget>,
 }
 
 impl ShardedHistoricalGossipFactory {
-    fn new(bandwidth: Arc<BandwidthThrottle>) -> Self {
-        Self { bandwidth }
+    fn new(bandwidth: Arc<BandwidthThrottle>, space_budget: Arc<SpaceBandwidthBudget>) -> Self {
+        Self {
+            bandwidth,
+            space_budget,
+        }
     }
 }
 
@@ -1453,6 +1474,7 @@ impl AsGossipModuleFactory for ShardedHistoricalGossipFactory {
             host,
             GossipType::Historical,
             self.bandwidth.clone(),
+            self.space_budget.clone(),
             metrics,
             fetch_pool,
         ))
@@ -1460,13 +1482,25 @@ impl AsGossipModuleFactory for ShardedHistoricalGossipFactory {
 }
 
 /// Create a recent `GossipModuleFactory`
-pub fn recent_factory(bandwidth: Arc<BandwidthThrottle>) -> GossipModuleFactory {
-    GossipModuleFactory(Arc::new(ShardedRecentGossipFactory::new(bandwidth)))
+pub fn recent_factory(
+    bandwidth: Arc<BandwidthThrottle>,
+    space_budget: Arc<SpaceBandwidthBudget>,
+) -> GossipModuleFactory {
+    GossipModuleFactory(Arc::new(ShardedRecentGossipFactory::new(
+        bandwidth,
+        space_budget,
+    )))
 }
 
 /// Create a historical `GossipModuleFactory`
-pub fn historical_factory(bandwidth: Arc<BandwidthThrottle>) -> GossipModuleFactory {
-    GossipModuleFactory(Arc::new(ShardedHistoricalGossipFactory::new(bandwidth)))
+pub fn historical_factory(
+    bandwidth: Arc<BandwidthThrottle>,
+    space_budget: Arc<SpaceBandwidthBudget>,
+) -> GossipModuleFactory {
+    GossipModuleFactory(Arc::new(ShardedHistoricalGossipFactory::new(
+        bandwidth,
+        space_budget,
+    )))
 }
 
 #[allow(dead_code)]
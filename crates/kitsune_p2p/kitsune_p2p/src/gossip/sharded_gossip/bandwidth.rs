@@ -7,6 +7,21 @@ use governor::{clock::Clock, Quota};
 
 use super::*;
 
+/// Total gossip bytes received across all throttles in this process.
+static TOTAL_BYTES_INBOUND: AtomicU64 = AtomicU64::new(0);
+
+/// Total gossip bytes sent across all throttles in this process.
+static TOTAL_BYTES_OUTBOUND: AtomicU64 = AtomicU64::new(0);
+
+/// The total number of gossip bytes (inbound, outbound) which have passed
+/// through any bandwidth throttle in this process.
+pub fn gossip_bytes_total() -> (u64, u64) {
+    (
+        TOTAL_BYTES_INBOUND.load(std::sync::atomic::Ordering::Relaxed),
+        TOTAL_BYTES_OUTBOUND.load(std::sync::atomic::Ordering::Relaxed),
+    )
+}
+
 #[derive(Clone)]
 /// Set of bandwidth throttles for all gossip loops.
 pub struct BandwidthThrottles {
@@ -172,6 +187,7 @@ where
             if let Some(outbound) = &self.outbound {
                 self.try_throttle("send", outbound, bytes, bits).await;
             }
+            TOTAL_BYTES_OUTBOUND.fetch_add(bytes as u64, std::sync::atomic::Ordering::Relaxed);
             let el = self.start_time.elapsed();
             let last_s = self
                 .last_outbound_time
@@ -209,6 +225,7 @@ where
             if let Some(inbound) = &self.inbound {
                 self.try_throttle("receive", inbound, bytes, bits).await;
             }
+            TOTAL_BYTES_INBOUND.fetch_add(bytes as u64, std::sync::atomic::Ordering::Relaxed);
             let el = self.start_time.elapsed();
             let last_s = self
                 .last_inbound_time
@@ -241,6 +258,126 @@ where
     }
 }
 
+/// The priority class of outgoing traffic within a space.
+/// When a space is over its bandwidth budget, traffic of a higher class is
+/// always sent before traffic of a lower class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TrafficPriority {
+    /// Historical gossip, which backfills old data.
+    Backfill = 0,
+    /// Publishes, broadcasts and recent gossip.
+    Publish = 1,
+    /// Remote calls, which someone is waiting on.
+    Call = 2,
+}
+
+/// The outbound bandwidth budget for a single space, shared by all of
+/// its traffic. Senders wait for enough budget to become available,
+/// and no sender starts while a sender of a higher [`TrafficPriority`] is
+/// waiting, so historical sync can't starve interactive traffic.
+pub struct SpaceBandwidthBudget<C = DefaultClock>
+where
+    C: Clock,
+{
+    clock: C,
+    limiter: Option<RateLimiter<NotKeyed, InMemoryState, C>>,
+    waiting: [AtomicUsize; 3],
+    released: tokio::sync::Notify,
+}
+
+impl SpaceBandwidthBudget {
+    /// Create a budget from the space tuning parameters.
+    /// A budget of 0 mbps never waits.
+    pub fn new(tuning_params: &KitsuneP2pTuningParams) -> Self {
+        Self::new_inner(
+            tuning_params.space_outbound_budget_mbps,
+            tuning_params.space_outbound_budget_burst_ratio,
+            governor::clock::DefaultClock::default(),
+        )
+    }
+}
+
+#[cfg(test)]
+impl SpaceBandwidthBudget<governor::clock::FakeRelativeClock> {
+    fn test(mbps: f64, burst_ratio: f64, clock: governor::clock::FakeRelativeClock) -> Self {
+        Self::new_inner(mbps, burst_ratio, clock)
+    }
+}
+
+impl<C> SpaceBandwidthBudget<C>
+where
+    C: Clock,
+{
+    fn new_inner(mbps: f64, burst_ratio: f64, clock: C) -> Self {
+        // Convert to bits per second.
+        let bps = mbps * 1000.0 * 1000.0;
+        let limiter = NonZeroU32::new(bps as u32).map(|per_second| {
+            let burst =
+                NonZeroU32::new((bps * burst_ratio) as u32).expect("burst_ratio cannot be 0");
+            RateLimiter::direct_with_clock(Quota::per_second(per_second).allow_burst(burst), &clock)
+        });
+        Self {
+            clock,
+            limiter,
+            waiting: Default::default(),
+            released: tokio::sync::Notify::new(),
+        }
+    }
+
+    /// Does this budget ever make senders wait?
+    pub fn is_limited(&self) -> bool {
+        self.limiter.is_some()
+    }
+
+    /// Is a sender of a higher priority than `priority` waiting?
+    fn higher_waiting(&self, priority: TrafficPriority) -> bool {
+        self.waiting[priority as usize + 1..]
+            .iter()
+            .any(|w| w.load(std::sync::atomic::Ordering::Acquire) > 0)
+    }
+
+    /// Wait until there's enough budget to send this many bytes
+    /// and no traffic of a higher priority is waiting.
+    pub async fn outgoing_bytes(&self, priority: TrafficPriority, bytes: usize) {
+        let limiter = match &self.limiter {
+            Some(limiter) => limiter,
+            None => return,
+        };
+        let bits = match NonZeroU32::new(bytes as u32 * 8) {
+            Some(bits) => bits,
+            None => return,
+        };
+        self.waiting[priority as usize].fetch_add(1, std::sync::atomic::Ordering::AcqRel);
+        loop {
+            if self.higher_waiting(priority) {
+                // Check again periodically in case the wake up is missed.
+                tokio::time::timeout(Duration::from_millis(10), self.released.notified())
+                    .await
+                    .ok();
+                continue;
+            }
+            match limiter.check_n(bits) {
+                Ok(()) => break,
+                Err(governor::NegativeMultiDecision::BatchNonConforming(_, n)) => {
+                    let dur = n.wait_time_from(governor::clock::Clock::now(&self.clock));
+                    tokio::time::sleep(dur).await;
+                }
+                Err(governor::NegativeMultiDecision::InsufficientCapacity(cap)) => {
+                    tracing::warn!(
+                        ?priority,
+                        "Tried to send {} bits, which is larger than the space budget's burst of {} bits. Allowing it through anyway!",
+                        bits,
+                        cap
+                    );
+                    break;
+                }
+            }
+        }
+        self.waiting[priority as usize].fetch_sub(1, std::sync::atomic::Ordering::AcqRel);
+        self.released.notify_waiters();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
@@ -291,4 +428,63 @@ mod tests {
         // Allow for small rounding error.
         assert!(mbps < 0.11);
     }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_space_budget_priority() {
+        holochain_trace::test_run();
+        let clock = governor::clock::FakeRelativeClock::default();
+        // 1 mbps with a burst of 1 second.
+        let budget = Arc::new(SpaceBandwidthBudget::test(1.0, 1.0, clock.clone()));
+        let one_second = 125_000;
+
+        // Use up the burst.
+        budget
+            .outgoing_bytes(TrafficPriority::Backfill, one_second)
+            .await;
+
+        let sent = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let send = |priority| {
+            let budget = budget.clone();
+            let sent = sent.clone();
+            tokio::spawn(async move {
+                budget.outgoing_bytes(priority, one_second).await;
+                sent.lock().push(priority);
+            })
+        };
+
+        // The backfill starts waiting before the call.
+        let backfill = send(TrafficPriority::Backfill);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let call = send(TrafficPriority::Call);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(sent.lock().is_empty());
+
+        // Only one message fits in the refilled budget and the call goes first.
+        clock.advance(Duration::from_secs(1));
+        tokio::time::timeout(Duration::from_secs(10), call)
+            .await
+            .expect("the call should be sent before the backfill")
+            .unwrap();
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert_eq!(vec![TrafficPriority::Call], *sent.lock());
+
+        clock.advance(Duration::from_secs(1));
+        backfill.await.unwrap();
+        assert_eq!(
+            vec![TrafficPriority::Call, TrafficPriority::Backfill],
+            *sent.lock()
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_space_budget_disabled() {
+        let clock = governor::clock::FakeRelativeClock::default();
+        let budget = SpaceBandwidthBudget::test(0.0, 1.0, clock);
+        assert!(!budget.is_limited());
+        for _ in 0..10 {
+            budget
+                .outgoing_bytes(TrafficPriority::Backfill, MAX_SEND_BUF_BYTES)
+                .await;
+        }
+    }
 }
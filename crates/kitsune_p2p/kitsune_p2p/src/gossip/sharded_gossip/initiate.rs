@@ -104,6 +104,15 @@ impl ShardedGossipLocal {
             return Ok(vec![ShardedGossipWire::already_in_progress()]);
         }
 
+        // Don't gossip with peers that are banned for protocol violations.
+        // The remote will time out the round.
+        if self
+            .inner
+            .share_mut(|i, _| Ok(i.metrics.read().is_banned(&remote_agent_list)))?
+        {
+            return Ok(Vec::with_capacity(0));
+        }
+
         // If this is the same connection as our current target then we need to decide who proceeds.
         if let Some(our_id) = same_as_target {
             // If we have a lower id then we proceed
@@ -128,10 +128,16 @@ fn next_remote_node(
         }
     });
 
+    // Move nodes with a low score behind the rest, keeping the order within each group.
+    remote_nodes
+        .sort_by_key(|n| metrics.read().peer_score(&n.agent_info_list) < DEPRIORITIZED_PEER_SCORE);
+
     let forced_initiate = metrics.read().forced_initiate();
 
     remote_nodes
         .into_iter()
+        // Don't initiate with nodes that are banned for protocol violations.
+        .filter(|n| !metrics.read().is_banned(&n.agent_info_list))
         // Don't initiate with nodes we are currently gossiping with.
         .filter(|n| !metrics.read().is_current_round(&n.agent_info_list))
         .find(|n| {
@@ -405,6 +411,66 @@ mod tests {
         assert_eq!(r, remote_nodes.last().cloned());
     }
 
+    #[test]
+    /// Test we never choose a node that is banned for protocol violations.
+    fn dont_choose_banned_nodes() {
+        // - Create 10 remote nodes.
+        let remote_nodes = create_remote_nodes(10);
+
+        let metrics = MetricsSync::default();
+
+        // - Record enough bad signatures from every node to get them banned.
+        for node in remote_nodes.iter() {
+            for _ in 0..2 {
+                metrics.write().record_protocol_violation(
+                    ProtocolViolation::BadSignature,
+                    &node.agent_info_list,
+                );
+            }
+            assert!(metrics.read().is_banned(&node.agent_info_list));
+        }
+
+        let r = next_remote_node(remote_nodes, &metrics, tuning_params_no_delay());
+
+        // - Expect no nodes to be chosen.
+        assert!(r.is_none());
+    }
+
+    #[test]
+    /// Test we choose nodes with a low score only after all others.
+    fn low_score_nodes_are_deprioritized() {
+        // - Create 2 remote nodes that have never been gossiped with.
+        let remote_nodes = create_remote_nodes(2);
+
+        let metrics = MetricsSync::default();
+
+        // - Record enough spam from the first node to lower its score without banning it.
+        for _ in 0..6 {
+            metrics.write().record_protocol_violation(
+                ProtocolViolation::Spam,
+                &remote_nodes[0].agent_info_list,
+            );
+        }
+        assert!(
+            metrics.read().peer_score(&remote_nodes[0].agent_info_list) < DEPRIORITIZED_PEER_SCORE
+        );
+        assert!(!metrics.read().is_banned(&remote_nodes[0].agent_info_list));
+
+        // - Expect the second node to always win the tie.
+        for _ in 0..100 {
+            let r = next_remote_node(remote_nodes.clone(), &metrics, tuning_params_no_delay());
+            assert_eq!(r, remote_nodes.get(1).cloned());
+        }
+
+        // - Expect the first node to still be chosen when it's the only one.
+        let r = next_remote_node(
+            remote_nodes[..1].to_vec(),
+            &metrics,
+            tuning_params_no_delay(),
+        );
+        assert_eq!(r, remote_nodes.first().cloned());
+    }
+
     #[test]
     /// Test we don't choose nodes we've seen too recently.
     fn dont_choose_very_recent_rounds() {
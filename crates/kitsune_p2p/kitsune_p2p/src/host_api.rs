@@ -5,14 +5,15 @@ use must_future::MustBoxFuture;
 use std::sync::Arc;
 
 use kitsune_p2p_types::{
-    bin_types::KitsuneSpace,
+    bin_types::{KitsuneAgent, KitsuneSpace},
+    config::KitsuneP2pTuningParams,
     dependencies::lair_keystore_api,
     dht::{
         region::{Region, RegionCoords},
         region_set::RegionSetLtcs,
         spacetime::Topology,
     },
-    dht_arc::DhtArcSet,
+    dht_arc::{DhtArcSet, DhtLocation},
     KOpData, KOpHash,
 };
 
@@ -47,6 +48,37 @@ pub trait KitsuneHost: 'static + Send + Sync + std::fmt::Debug {
     /// Remove an agent info from storage
     fn remove_agent_info_signed(&self, input: GetAgentInfoSignedEvt) -> KitsuneHostResult<bool>;
 
+    /// Get the unexpired agent infos which the host holds as DHT data for a
+    /// space, so agents can be found without a bootstrap service. The infos
+    /// nearest to `basis_loc` come first. A host which doesn't publish agent
+    /// infos to the DHT reports none.
+    fn query_dht_agent_infos(
+        &self,
+        space: Arc<KitsuneSpace>,
+        basis_loc: DhtLocation,
+    ) -> KitsuneHostResult<Vec<crate::types::agent_store::AgentInfoSigned>> {
+        let _space = space;
+        let _basis_loc = basis_loc;
+        futures::FutureExt::boxed(async move { Ok(Vec::new()) }).into()
+    }
+
+    /// Remove the agent infos stored for a space which had expired by
+    /// `expired_by_ms`, or by now if that is earlier. Agent infos which
+    /// advertise no urls are kept until they expire like any other.
+    /// Kitsune calls this each time it updates the infos of its local agents.
+    ///
+    /// Returns the agents whose infos were removed. A host which doesn't
+    /// support pruning removes nothing.
+    fn prune_agent_info_signed(
+        &self,
+        space: Arc<KitsuneSpace>,
+        expired_by_ms: u64,
+    ) -> KitsuneHostResult<Vec<Arc<KitsuneAgent>>> {
+        let _space = space;
+        let _expired_by_ms = expired_by_ms;
+        futures::FutureExt::boxed(async move { Ok(Vec::new()) }).into()
+    }
+
     /// Extrapolated Peer Coverage.
     fn peer_extrapolated_coverage(
         &self,
@@ -71,6 +103,14 @@ pub trait KitsuneHost: 'static + Send + Sync + std::fmt::Debug {
         regions: Vec<Region>,
     ) -> KitsuneHostResult<Vec<Region>>;
 
+    /// The number of bytes the data of a space takes up on disk.
+    /// Used to keep arcs within a storage budget. A host which doesn't
+    /// account for storage reports 0.
+    fn storage_disk_usage(&self, space: Arc<KitsuneSpace>) -> KitsuneHostResult<u64> {
+        let _space = space;
+        futures::FutureExt::boxed(async move { Ok(0) }).into()
+    }
+
     /// Get all op hashes within a region
     fn query_op_hashes_by_region(
         &self,
@@ -127,6 +167,16 @@ pub trait KitsuneHost: 'static + Send + Sync + std::fmt::Debug {
     ) {
     }
 
+    /// Get the tuning params to use for a space, given the tuning params of the network.
+    /// This lets the host run spaces in different roles, for example with a different arc clamping.
+    fn space_tuning_params(
+        &self,
+        _space: Arc<KitsuneSpace>,
+        tuning_params: KitsuneP2pTuningParams,
+    ) -> KitsuneP2pTuningParams {
+        tuning_params
+    }
+
     /// Get the lair "tag" identifying the id seed to use for crypto signing.
     /// (this is currently only used in tx5/WebRTC if that feature is enabled.)
     fn lair_tag(&self) -> Option<Arc<str>> {
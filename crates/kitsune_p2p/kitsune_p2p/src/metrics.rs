@@ -15,6 +15,7 @@ use crate::gossip::sharded_gossip::RoundState;
 use crate::types::*;
 use kitsune_p2p_timestamp::Timestamp;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
+use kitsune_p2p_types::dht::Arq;
 
 use num_traits::*;
 
@@ -39,10 +40,41 @@ pub(crate) static METRIC_MSG_OUT_TIME: Lazy<opentelemetry_api::metrics::Histogra
             .init()
     });
 
+pub(crate) static METRIC_BOOTSTRAP_PUT: Lazy<opentelemetry_api::metrics::Counter<u64>> =
+    Lazy::new(|| {
+        opentelemetry_api::global::meter("kitsune")
+            .u64_counter("kitsune.bootstrap.put.count")
+            .with_description("Agent info publishes to the bootstrap service")
+            .init()
+    });
+
 /// how long historical metric records should be kept
 /// (currently set to 1 week)
 const HISTORICAL_RECORD_EXPIRE_DURATION_MICROS: i64 = 1000 * 1000 * 60 * 60 * 24 * 7;
 
+/// how long a clock offset sample from a remote node is used
+/// for the network clock estimate
+/// (currently set to 10 minutes)
+const CLOCK_SAMPLE_EXPIRE_DURATION: Duration = Duration::from_secs(60 * 10);
+
+/// how many of the most recent arq transitions are kept for each space
+const MAX_ARQ_TRANSITIONS: usize = 100;
+
+/// The score of a peer with no recorded protocol violations.
+pub const MAX_PEER_SCORE: f32 = 100.0;
+
+/// Peers scoring below this are only chosen for gossip
+/// after all the better scoring peers.
+pub const DEPRIORITIZED_PEER_SCORE: f32 = 50.0;
+
+/// How long it takes for half of a peer's violation penalty to decay
+/// (currently set to 10 minutes)
+const PEER_PENALTY_HALF_LIFE: Duration = Duration::from_secs(60 * 10);
+
+/// How long a peer is banned for when its score drops to zero
+/// (currently set to 30 minutes)
+pub const PEER_BAN_DURATION: Duration = Duration::from_secs(60 * 30);
+
 /// Running average that prioritizes memory and cpu efficiency
 /// over strict accuracy.
 /// For metrics where we can't afford the memory of tracking samples
@@ -149,6 +181,90 @@ pub struct PeerAgentHistory {
     pub errors: VecDeque<RoundMetric>,
     /// Is this node currently in an active round?
     pub current_rounds: HashSet<GossipType>,
+    /// Score based on the protocol violations of this agent.
+    pub score: PeerScore,
+}
+
+/// A breach of the protocol by a remote peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProtocolViolation {
+    /// The peer sent data with a signature that doesn't verify.
+    BadSignature,
+    /// The peer sent an op that couldn't be decoded or hashed.
+    MalformedOp,
+    /// The peer sent unsolicited or excessive data.
+    Spam,
+}
+
+impl ProtocolViolation {
+    /// How much this violation lowers a peer's score.
+    pub fn penalty(&self) -> f32 {
+        match self {
+            // Bad signatures can't happen by accident.
+            Self::BadSignature => 50.0,
+            Self::MalformedOp => 20.0,
+            Self::Spam => 10.0,
+        }
+    }
+}
+
+/// The score of a remote peer, lowered by protocol violations.
+/// The penalty decays over time so peers recover from occasional violations,
+/// but a peer whose score reaches zero is banned for a while.
+#[derive(Debug, Clone, Default)]
+pub struct PeerScore {
+    /// Penalty at the time it was last updated.
+    penalty: f32,
+    /// When the penalty was last updated.
+    updated_at: Option<Instant>,
+    /// If banned, when the ban ends.
+    banned_until: Option<Instant>,
+    /// Number of violations recorded of each kind.
+    violations: HashMap<ProtocolViolation, u32>,
+}
+
+impl PeerScore {
+    /// The current score, between 0 and [`MAX_PEER_SCORE`].
+    pub fn score(&self) -> f32 {
+        (MAX_PEER_SCORE - self.penalty_at(Instant::now())).max(0.0)
+    }
+
+    /// Is this peer currently banned?
+    pub fn is_banned(&self) -> bool {
+        self.banned_until
+            .map_or(false, |until| Instant::now() < until)
+    }
+
+    /// Number of violations recorded of each kind.
+    pub fn violations(&self) -> &HashMap<ProtocolViolation, u32> {
+        &self.violations
+    }
+
+    /// Record a violation, banning the peer if its score drops to zero.
+    /// Returns true if this violation got the peer banned.
+    pub fn record_violation(&mut self, violation: ProtocolViolation) -> bool {
+        let now = Instant::now();
+        self.penalty = self.penalty_at(now) + violation.penalty();
+        self.updated_at = Some(now);
+        *self.violations.entry(violation).or_default() += 1;
+        if self.penalty >= MAX_PEER_SCORE && !self.is_banned() {
+            self.banned_until = Some(now + PEER_BAN_DURATION);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn penalty_at(&self, now: Instant) -> f32 {
+        match self.updated_at {
+            Some(updated_at) => {
+                let half_lives = now.saturating_duration_since(updated_at).as_secs_f32()
+                    / PEER_PENALTY_HALF_LIFE.as_secs_f32();
+                self.penalty * 0.5_f32.powf(half_lives)
+            }
+            None => 0.0,
+        }
+    }
 }
 
 /// Detailed info about the history of gossip with this node
@@ -303,11 +419,38 @@ pub struct Metrics {
     /// Aggregate Extrapolated Dht Coverage
     agg_extrap_cov: RunAvg,
 
+    /// Latest clock offset sample from each remote node, by url.
+    clock_samples: HashMap<String, ClockSample>,
+
+    /// The most recent changes to the arqs of local agents, oldest first.
+    arq_transitions: VecDeque<ArqTransition>,
+
     // Number of times we need to force initiate
     // the next round.
     pub(crate) force_initiates: u8,
 }
 
+/// A single measurement of the offset between a remote node's clock and ours.
+#[derive(Debug, Clone, Copy)]
+struct ClockSample {
+    offset_micros: i64,
+    uncertainty_micros: i64,
+    recorded_at: Instant,
+}
+
+/// An estimate of how far the clocks of the remote nodes
+/// we talk to are from our own clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockOffsetEstimate {
+    /// How far ahead of our clock the network is, in microseconds.
+    /// Negative if the network is behind us.
+    pub offset_micros: i64,
+    /// How far off the offset may be, in microseconds, in either direction.
+    pub uncertainty_micros: i64,
+    /// How many remote nodes the estimate is based on.
+    pub peer_count: usize,
+}
+
 /// Outcome of a gossip round.
 #[derive(Debug, PartialOrd, Ord, PartialEq, Eq)]
 pub enum RoundOutcome {
@@ -317,6 +460,136 @@ pub enum RoundOutcome {
     Error(RoundMetric),
 }
 
+/// How a local agent's arq changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArqTransitionKind {
+    /// The arq covers more of the DHT.
+    Grew,
+    /// The arq covers less of the DHT.
+    Shrank,
+    /// The arq covers the same length of the DHT with a different power.
+    Requantized,
+}
+
+impl ArqTransitionKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Grew => "grew",
+            Self::Shrank => "shrank",
+            Self::Requantized => "requantized",
+        }
+    }
+}
+
+/// What caused a local agent's arq to change.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArqTransitionTrigger {
+    /// The arq was resized towards the target coverage,
+    /// given the coverage extrapolated from its peer view.
+    PeerDensity {
+        /// The extrapolated coverage before the arq was resized.
+        extrapolated_coverage: f64,
+    },
+    /// The arq was set to the clamped size.
+    Clamping,
+    /// The arq was shrunk to fit the storage budget.
+    StorageBudget {
+        /// The bytes the space took up on disk when the arq was shrunk.
+        used_bytes: u64,
+        /// The storage budget.
+        budget_bytes: u64,
+    },
+}
+
+impl ArqTransitionTrigger {
+    fn to_json(self) -> serde_json::Value {
+        match self {
+            Self::PeerDensity {
+                extrapolated_coverage,
+            } => serde_json::json!({
+                "trigger": "peer_density",
+                "extrapolatedCoverage": extrapolated_coverage,
+            }),
+            Self::Clamping => serde_json::json!({ "trigger": "clamping" }),
+            Self::StorageBudget {
+                used_bytes,
+                budget_bytes,
+            } => serde_json::json!({
+                "trigger": "storage_budget",
+                "usedBytes": used_bytes,
+                "budgetBytes": budget_bytes,
+            }),
+        }
+    }
+}
+
+/// A change to the arq of a local agent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArqTransition {
+    /// The agent whose arq changed.
+    pub agent: Arc<KitsuneAgent>,
+    /// How the arq changed.
+    pub kind: ArqTransitionKind,
+    /// The power of the arq before the change.
+    pub old_power: u8,
+    /// The chunk count of the arq before the change.
+    pub old_count: u32,
+    /// The power of the arq after the change.
+    pub new_power: u8,
+    /// The chunk count of the arq after the change.
+    pub new_count: u32,
+    /// What caused the change.
+    pub trigger: ArqTransitionTrigger,
+    /// When the change was made.
+    pub at: Timestamp,
+}
+
+impl ArqTransition {
+    /// Describe the change from one arq to another,
+    /// or None if the arq has the same power and count.
+    pub fn new(
+        agent: Arc<KitsuneAgent>,
+        old: &Arq,
+        new: &Arq,
+        trigger: ArqTransitionTrigger,
+    ) -> Option<Self> {
+        let (old_power, old_count) = (old.power(), old.count());
+        let (new_power, new_count) = (new.power(), new.count());
+        // Both lengths are in units of the smallest possible chunk.
+        let old_len = (old_count as u64) << old_power;
+        let new_len = (new_count as u64) << new_power;
+        let kind = match new_len.cmp(&old_len) {
+            std::cmp::Ordering::Greater => ArqTransitionKind::Grew,
+            std::cmp::Ordering::Less => ArqTransitionKind::Shrank,
+            std::cmp::Ordering::Equal if new_power != old_power => ArqTransitionKind::Requantized,
+            std::cmp::Ordering::Equal => return None,
+        };
+        Some(Self {
+            agent,
+            kind,
+            old_power,
+            old_count,
+            new_power,
+            new_count,
+            trigger,
+            at: Timestamp::now(),
+        })
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "agent": self.agent.to_string(),
+            "kind": self.kind.as_str(),
+            "oldPower": self.old_power,
+            "oldCount": self.old_count,
+            "newPower": self.new_power,
+            "newCount": self.new_count,
+            "trigger": self.trigger.to_json(),
+            "at": self.at.to_string(),
+        })
+    }
+}
+
 /// Accept differing key types
 pub enum AgentLike<'lt> {
     /// An agent info
@@ -397,18 +670,50 @@ impl Metrics {
                     serde_json::json!({
                         "reachability_quotient": *i.reachability_quotient,
                         "latency_micros": *i.latency_micros,
+                        "score": i.score.score(),
+                        "banned": i.score.is_banned(),
                     }),
                 )
             })
             .collect::<serde_json::map::Map<String, serde_json::Value>>()
             .into();
 
+        let arq_transitions: Vec<serde_json::Value> = self
+            .arq_transitions
+            .iter()
+            .map(ArqTransition::to_json)
+            .collect();
+
         serde_json::json!({
             "aggExtrapCov": *self.agg_extrap_cov,
             "agents": agents,
+            "arqTransitions": arq_transitions,
         })
     }
 
+    /// Record a change to the arq of a local agent, and emit it as a tracing event.
+    pub fn record_arq_transition(&mut self, transition: ArqTransition) {
+        tracing::info!(
+            agent = ?transition.agent,
+            kind = transition.kind.as_str(),
+            old_power = transition.old_power,
+            old_count = transition.old_count,
+            new_power = transition.new_power,
+            new_count = transition.new_count,
+            trigger = ?transition.trigger,
+            "Arq transition"
+        );
+        if self.arq_transitions.len() >= MAX_ARQ_TRANSITIONS {
+            self.arq_transitions.pop_front();
+        }
+        self.arq_transitions.push_back(transition);
+    }
+
+    /// The most recent changes to the arqs of local agents, oldest first.
+    pub fn arq_transitions(&self) -> impl Iterator<Item = &ArqTransition> {
+        self.arq_transitions.iter()
+    }
+
     /// Record an individual extrapolated coverage event
     /// (either from us or a remote)
     /// and add it to our running aggregate extrapolated coverage metric.
@@ -416,6 +721,73 @@ impl Metrics {
         self.agg_extrap_cov.push(extrap_cov);
     }
 
+    /// Record the offset of a remote node's clock from ours,
+    /// replacing any previous sample from that node.
+    pub fn record_clock_sample(
+        &mut self,
+        remote_url: String,
+        offset_micros: i64,
+        uncertainty_micros: i64,
+    ) {
+        let now = Instant::now();
+        self.clock_samples.retain(|_, s| {
+            now.saturating_duration_since(s.recorded_at) < CLOCK_SAMPLE_EXPIRE_DURATION
+        });
+        self.clock_samples.insert(
+            remote_url,
+            ClockSample {
+                offset_micros,
+                uncertainty_micros,
+                recorded_at: now,
+            },
+        );
+    }
+
+    /// Estimate the offset of the network's clocks from ours,
+    /// using the recent samples from remote nodes.
+    ///
+    /// The offset is the median of the sampled offsets, so a minority of nodes
+    /// with badly skewed clocks can't move it far. The uncertainty is the median
+    /// uncertainty of the samples plus how far they spread around the median.
+    /// Returns None if there are no recent samples.
+    pub fn clock_offset_estimate(&self) -> Option<ClockOffsetEstimate> {
+        let now = Instant::now();
+        let samples: Vec<ClockSample> = self
+            .clock_samples
+            .values()
+            .filter(|s| now.saturating_duration_since(s.recorded_at) < CLOCK_SAMPLE_EXPIRE_DURATION)
+            .copied()
+            .collect();
+        if samples.is_empty() {
+            return None;
+        }
+
+        fn median(mut values: Vec<i64>) -> i64 {
+            values.sort_unstable();
+            let mid = values.len() / 2;
+            if values.len() % 2 == 0 {
+                values[mid - 1] + (values[mid] - values[mid - 1]) / 2
+            } else {
+                values[mid]
+            }
+        }
+
+        let offset_micros = median(samples.iter().map(|s| s.offset_micros).collect());
+        let spread = median(
+            samples
+                .iter()
+                .map(|s| (s.offset_micros - offset_micros).saturating_abs())
+                .collect(),
+        );
+        let uncertainty = median(samples.iter().map(|s| s.uncertainty_micros).collect());
+
+        Some(ClockOffsetEstimate {
+            offset_micros,
+            uncertainty_micros: uncertainty.saturating_add(spread),
+            peer_count: samples.len(),
+        })
+    }
+
     /// Sucessful and unsuccessful messages from the remote
     /// can be combined to estimate a "reachability quotient"
     /// between 1 (or 0 if empty) and 100. Errors are weighted
@@ -687,6 +1059,56 @@ impl Metrics {
         }
     }
 
+    /// Record a protocol violation by the supplied remote agents.
+    /// Returns the agents that got banned because of it.
+    pub fn record_protocol_violation<'a, T, I>(
+        &mut self,
+        violation: ProtocolViolation,
+        remote_agent_list: I,
+    ) -> Vec<Arc<KitsuneAgent>>
+    where
+        T: Into<AgentLike<'a>>,
+        I: IntoIterator<Item = T>,
+    {
+        let mut banned = Vec::new();
+        for agent_info in remote_agent_list {
+            let agent = agent_info.into().agent().clone();
+            let history = self.agent_history.entry(agent.clone()).or_default();
+            if history.score.record_violation(violation) {
+                tracing::warn!(?agent, ?violation, "Banning peer for protocol violations");
+                banned.push(agent);
+            }
+        }
+        banned
+    }
+
+    /// Return the lowest score of the supplied remote agents,
+    /// or [`MAX_PEER_SCORE`] if none of them have a history.
+    pub fn peer_score<'a, T, I>(&self, remote_agent_list: I) -> f32
+    where
+        T: Into<AgentLike<'a>>,
+        I: IntoIterator<Item = T>,
+    {
+        remote_agent_list
+            .into_iter()
+            .filter_map(|agent_info| self.agent_history.get(agent_info.into().agent()))
+            .map(|info| info.score.score())
+            .fold(MAX_PEER_SCORE, f32::min)
+    }
+
+    /// Is any of the supplied remote agents currently banned?
+    pub fn is_banned<'a, T, I>(&self, remote_agent_list: I) -> bool
+    where
+        T: Into<AgentLike<'a>>,
+        I: IntoIterator<Item = T>,
+    {
+        remote_agent_list.into_iter().any(|agent_info| {
+            self.agent_history
+                .get(agent_info.into().agent())
+                .map_or(false, |info| info.score.is_banned())
+        })
+    }
+
     /// Return the average (mean) latency microseconds for the
     /// supplied remote agents.
     pub fn latency_micros<'a, T, I>(&self, remote_agent_list: I) -> f32
@@ -796,6 +1218,13 @@ impl std::fmt::Display for Metrics {
                     completion_frequency
                 )?;
                 write!(f, "\n\t\tCurrent Rounds: {:?}", info.current_rounds)?;
+                write!(
+                    f,
+                    "\n\t\tScore: {}, Banned: {}, Violations: {:?}",
+                    info.score.score(),
+                    info.score.is_banned(),
+                    info.score.violations()
+                )?;
             }
         }
         write!(
@@ -910,4 +1339,97 @@ mod tests {
         a5.push_n(1, 255);
         assert_eq!(1.0, *a5);
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_peer_score() {
+        let mut score = PeerScore::default();
+        assert_eq!(MAX_PEER_SCORE, score.score());
+
+        assert!(!score.record_violation(ProtocolViolation::MalformedOp));
+        assert_eq!(80.0, score.score());
+
+        // Half the penalty decays after a half life.
+        tokio::time::advance(PEER_PENALTY_HALF_LIFE).await;
+        assert_eq!(90.0, score.score());
+
+        // The peer is banned when its score reaches zero.
+        assert!(!score.record_violation(ProtocolViolation::BadSignature));
+        assert!(score.record_violation(ProtocolViolation::BadSignature));
+        assert_eq!(0.0, score.score());
+        assert!(score.is_banned());
+        assert_eq!(
+            Some(&2),
+            score.violations().get(&ProtocolViolation::BadSignature)
+        );
+
+        // The ban ends after a while.
+        tokio::time::advance(PEER_BAN_DURATION).await;
+        assert!(!score.is_banned());
+        assert!(score.score() > 0.0);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_clock_offset_estimate() {
+        let mut metrics = Metrics::default();
+        assert_eq!(None, metrics.clock_offset_estimate());
+
+        metrics.record_clock_sample("a".to_string(), 1_000, 100);
+        metrics.record_clock_sample("b".to_string(), 2_000, 300);
+        // A node with a badly skewed clock doesn't move the estimate far.
+        metrics.record_clock_sample("c".to_string(), 60_000_000, 200);
+        assert_eq!(
+            Some(ClockOffsetEstimate {
+                offset_micros: 2_000,
+                uncertainty_micros: 200 + 1_000,
+                peer_count: 3,
+            }),
+            metrics.clock_offset_estimate()
+        );
+
+        // A new sample from a node replaces its previous one.
+        metrics.record_clock_sample("c".to_string(), 1_500, 200);
+        assert_eq!(
+            Some(ClockOffsetEstimate {
+                offset_micros: 1_500,
+                uncertainty_micros: 200 + 500,
+                peer_count: 3,
+            }),
+            metrics.clock_offset_estimate()
+        );
+    }
+
+    #[test]
+    fn test_arq_transitions() {
+        use kitsune_p2p_types::dht::spacetime::SpaceOffset;
+
+        let agent = Arc::new(KitsuneAgent::new(vec![1; 36]));
+        let arq = |power, count| Arq::new(power, 0.into(), SpaceOffset(count));
+        let trigger = ArqTransitionTrigger::Clamping;
+        let kind = |old: Arq, new: Arq| {
+            ArqTransition::new(agent.clone(), &old, &new, trigger).map(|t| t.kind)
+        };
+
+        assert_eq!(Some(ArqTransitionKind::Grew), kind(arq(4, 8), arq(4, 9)));
+        assert_eq!(Some(ArqTransitionKind::Shrank), kind(arq(4, 8), arq(3, 15)));
+        assert_eq!(
+            Some(ArqTransitionKind::Requantized),
+            kind(arq(4, 8), arq(3, 16))
+        );
+        assert_eq!(None, kind(arq(4, 8), arq(4, 8)));
+
+        let mut metrics = Metrics::default();
+        for count in 0..MAX_ARQ_TRANSITIONS as u32 + 1 {
+            let transition =
+                ArqTransition::new(agent.clone(), &arq(4, count), &arq(4, count + 1), trigger)
+                    .unwrap();
+            metrics.record_arq_transition(transition);
+        }
+        // Only the most recent transitions are kept.
+        assert_eq!(MAX_ARQ_TRANSITIONS, metrics.arq_transitions().count());
+        assert_eq!(1, metrics.arq_transitions().next().unwrap().old_count);
+        assert_eq!(
+            MAX_ARQ_TRANSITIONS,
+            metrics.dump()["arqTransitions"].as_array().unwrap().len()
+        );
+    }
 }
@@ -42,10 +42,15 @@ pub async fn spawn_kitsune_p2p(
         self_host_api
     };
 
+    // The client for the bootstrap service, shared by the network and every space.
+    let bootstrap_client =
+        kitsune_p2p_bootstrap_client::BootstrapClient::new(&config.service_client)?;
+
     // Create the network. Any events it sends will have to wait to be processed until Kitsune has finished initialising
     // but everything that is needed to construct the network is available now.
     let (ep_hnd, ep_evt, bootstrap_net) = create_meta_net(
         &config,
+        bootstrap_client.clone(),
         tls_config,
         internal_sender.clone(),
         self_host_api.clone(),
@@ -64,6 +69,7 @@ pub async fn spawn_kitsune_p2p(
                 ep_hnd,
                 ep_evt,
                 bootstrap_net,
+                bootstrap_client,
             )
             .await?,
         ),
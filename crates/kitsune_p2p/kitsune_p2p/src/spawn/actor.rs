@@ -5,12 +5,14 @@ use crate::actor::*;
 use crate::event::*;
 use crate::gossip::sharded_gossip::BandwidthThrottles;
 use crate::gossip::sharded_gossip::KitsuneDiagnostics;
+use crate::metrics::ProtocolViolation;
 use crate::types::gossip::GossipModuleType;
 use crate::types::metrics::KitsuneMetrics;
 use crate::wire::MetricExchangeMsg;
 use crate::*;
 use futures::future::FutureExt;
 use futures::stream::StreamExt;
+use kitsune_p2p_bootstrap_client::BootstrapClient;
 use kitsune_p2p_bootstrap_client::BootstrapNet;
 use kitsune_p2p_fetch::*;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
@@ -91,7 +93,7 @@ ghost_actor::ghost_chan! {
         fn incoming_gossip(space: KSpace, con: MetaNetCon, remote_url: String, data: Payload, module_type: crate::types::gossip::GossipModuleType) -> ();
 
         /// Incoming Metric Exchange
-        fn incoming_metric_exchange(space: KSpace, msgs: VecMXM) -> ();
+        fn incoming_metric_exchange(space: KSpace, con: MetaNetCon, remote_url: String, msgs: VecMXM) -> ();
 
         /// New Con
         fn new_con(url: String, con: MetaNetCon) -> ();
@@ -104,6 +106,9 @@ ghost_actor::ghost_chan! {
 
         /// Get all local joined agent infos across all spaces.
         fn get_all_local_joined_agent_infos() -> Vec<AgentInfoSigned>;
+
+        /// A remote agent sent us data that breaks the protocol.
+        fn incoming_protocol_violation(space: KSpace, agent: KAgent, violation: ProtocolViolation) -> ();
     }
 }
 
@@ -122,6 +127,7 @@ pub(crate) struct KitsuneP2pActor {
     >,
     config: Arc<KitsuneP2pConfig>,
     bootstrap_net: BootstrapNet,
+    bootstrap_client: BootstrapClient,
     bandwidth_throttles: BandwidthThrottles,
     parallel_notify_permit: Arc<tokio::sync::Semaphore>,
     fetch_pool: FetchPool,
@@ -138,6 +144,7 @@ impl KitsuneP2pActor {
         ep_hnd: MetaNet,
         ep_evt: MetaNetEvtRecv,
         bootstrap_net: BootstrapNet,
+        bootstrap_client: BootstrapClient,
     ) -> KitsuneP2pResult<Self> {
         crate::types::metrics::init();
 
@@ -180,6 +187,7 @@ impl KitsuneP2pActor {
             spaces: HashMap::new(),
             config: Arc::new(config),
             bootstrap_net,
+            bootstrap_client,
             bandwidth_throttles,
             parallel_notify_permit,
             fetch_pool,
@@ -189,6 +197,7 @@ impl KitsuneP2pActor {
 
 pub(super) async fn create_meta_net(
     config: &KitsuneP2pConfig,
+    bootstrap_client: BootstrapClient,
     tls_config: tls::TlsConfig,
     internal_sender: ghost_actor::GhostSender<Internal>,
     host: HostApiLegacy,
@@ -198,6 +207,11 @@ pub(super) async fn create_meta_net(
     let mut ep_evt = None;
     let mut bootstrap_net = None;
 
+    #[cfg(all(feature = "tx2", feature = "tx5", unix))]
+    let local_config = local_transport_for_loopback_signal(config);
+    #[cfg(all(feature = "tx2", feature = "tx5", unix))]
+    let config = local_config.as_ref().unwrap_or(config);
+
     #[cfg(feature = "tx2")]
     if ep_hnd.is_none() && config.is_tx2() {
         let metrics = Tx2ApiMetrics::default().set_write_len(|d, l| {
@@ -218,7 +232,14 @@ pub(super) async fn create_meta_net(
         });
 
         tracing::trace!("tx2");
-        let (h, e) = MetaNet::new_tx2(host.clone(), config.clone(), tls_config, metrics).await?;
+        let (h, e) = MetaNet::new_tx2(
+            host.clone(),
+            config.clone(),
+            tls_config,
+            metrics,
+            bootstrap_client,
+        )
+        .await?;
         ep_hnd = Some(h);
         ep_evt = Some(e);
         bootstrap_net = Some(BootstrapNet::Tx2);
@@ -236,6 +257,7 @@ pub(super) async fn create_meta_net(
             host.clone(),
             internal_sender.clone(),
             signal_url,
+            &config.service_client,
             preflight_user_data,
         )
         .await?;
@@ -250,6 +272,38 @@ pub(super) async fn create_meta_net(
     }
 }
 
+/// The config to connect to peers with over the local transport instead of
+/// tx5, if the tuning params ask for that when the signal server is on a
+/// loopback address, and it is.
+#[cfg(all(feature = "tx2", feature = "tx5", unix))]
+fn local_transport_for_loopback_signal(config: &KitsuneP2pConfig) -> Option<KitsuneP2pConfig> {
+    if !config.tuning_params.tx5_local_transport_for_loopback_signal {
+        return None;
+    }
+    let signal_url = match config.transport_pool.first() {
+        Some(TransportConfig::WebRTC { signal_url }) => signal_url,
+        _ => return None,
+    };
+    let signal_url2 = url2::Url2::try_parse(signal_url).ok()?;
+    let host = signal_url2.host_str()?;
+    let is_loopback = host == "localhost"
+        || host
+            .trim_matches(&['[', ']'][..])
+            .parse::<std::net::IpAddr>()
+            .map_or(false, |ip| ip.is_loopback());
+    if !is_loopback {
+        return None;
+    }
+
+    tracing::info!(
+        %signal_url,
+        "the signal server is on this host, so all peers are, connecting to them over the local transport"
+    );
+    let mut config = config.clone();
+    config.transport_pool = vec![TransportConfig::Local { socket_dir: None }];
+    Some(config)
+}
+
 use crate::spawn::actor::fetch::{FetchResponseConfig, FetchTask};
 use crate::spawn::actor::meta_net_task::MetaNetTask;
 use ghost_actor::dependencies::must_future::MustBoxFuture;
@@ -403,6 +457,8 @@ impl InternalHandler for KitsuneP2pActor {
     fn handle_incoming_metric_exchange(
         &mut self,
         space: Arc<KitsuneSpace>,
+        con: MetaNetCon,
+        remote_url: String,
         msgs: Vec<MetricExchangeMsg>,
     ) -> InternalHandlerResult<()> {
         let space_sender = match self.spaces.get_mut(&space) {
@@ -413,7 +469,9 @@ impl InternalHandler for KitsuneP2pActor {
         };
         Ok(async move {
             let (_, space_inner) = space_sender.await;
-            space_inner.incoming_metric_exchange(space, msgs).await
+            space_inner
+                .incoming_metric_exchange(space, con, remote_url, msgs)
+                .await
         }
         .boxed()
         .into())
@@ -499,6 +557,15 @@ impl InternalHandler for KitsuneP2pActor {
         .boxed()
         .into())
     }
+
+    fn handle_incoming_protocol_violation(
+        &mut self,
+        space: KSpace,
+        agent: KAgent,
+        violation: ProtocolViolation,
+    ) -> InternalHandlerResult<()> {
+        actor::KitsuneP2pHandler::handle_report_protocol_violation(self, space, agent, violation)
+    }
 }
 
 impl ghost_actor::GhostHandler<KitsuneP2pEvent> for KitsuneP2pActor {}
@@ -614,6 +681,7 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         let host = self.host_api.clone().api;
         let config = Arc::clone(&self.config);
         let bootstrap_net = self.bootstrap_net;
+        let bootstrap_client = self.bootstrap_client.clone();
         let bandwidth_throttles = self.bandwidth_throttles.clone();
         let parallel_notify_permit = self.parallel_notify_permit.clone();
         let fetch_pool = self.fetch_pool.clone();
@@ -627,6 +695,7 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
                     host,
                     config,
                     bootstrap_net,
+                    bootstrap_client,
                     bandwidth_throttles,
                     parallel_notify_permit,
                     fetch_pool,
@@ -669,6 +738,27 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         .into())
     }
 
+    fn handle_set_local_agent_gossip(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+        agent: Arc<KitsuneAgent>,
+        enabled: bool,
+    ) -> KitsuneP2pHandlerResult<()> {
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(space)),
+            Some(space) => space.get(),
+        };
+        Ok(async move {
+            let (space_sender, _) = space_sender.await;
+            space_sender
+                .set_local_agent_gossip(space, agent, enabled)
+                .await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     fn handle_rpc_single(
         &mut self,
         space: Arc<KitsuneSpace>,
@@ -713,6 +803,7 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         basis: Arc<KitsuneBasis>,
         timeout: KitsuneTimeout,
         data: BroadcastData,
+        peer_filter: Option<PeerFilter>,
     ) -> KitsuneP2pHandlerResult<()> {
         let space_sender = match self.spaces.get_mut(&space) {
             None => return Err(KitsuneP2pError::RoutingSpaceError(space)),
@@ -720,7 +811,9 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         };
         Ok(async move {
             let (space_sender, _) = space_sender.await;
-            space_sender.broadcast(space, basis, timeout, data).await
+            space_sender
+                .broadcast(space, basis, timeout, data, peer_filter)
+                .await
         }
         .boxed()
         .into())
@@ -765,6 +858,48 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         .into())
     }
 
+    fn handle_report_protocol_violation(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+        agent: Arc<KitsuneAgent>,
+        violation: ProtocolViolation,
+    ) -> KitsuneP2pHandlerResult<()> {
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return unit_ok_fut(),
+            Some(space) => space.get(),
+        };
+        Ok(async move {
+            let (space_sender, _) = space_sender.await;
+            space_sender
+                .report_protocol_violation(space, agent, violation)
+                .await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
+    fn handle_fetch_from_authorities(
+        &mut self,
+        space: Arc<KitsuneSpace>,
+        basis: Arc<KitsuneBasis>,
+        op_hash_list: Vec<OpHashSized>,
+    ) -> KitsuneP2pHandlerResult<()> {
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return unit_ok_fut(),
+            Some(space) => space.get(),
+        };
+        Ok(async move {
+            let (space_sender, _) = space_sender.await;
+            space_sender
+                .fetch_from_authorities(space, basis, op_hash_list)
+                .await?;
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     fn handle_authority_for_hash(
         &mut self,
         space: Arc<KitsuneSpace>,
@@ -998,6 +1133,7 @@ mod tests {
     use crate::HostStub;
     use crate::KitsuneP2pResult;
     use ghost_actor::actor_builder::GhostActorBuilder;
+    use kitsune_p2p_bootstrap_client::BootstrapClient;
     use kitsune_p2p_bootstrap_client::BootstrapNet;
     use kitsune_p2p_types::config::{KitsuneP2pConfig, NetworkType, TransportConfig};
     use kitsune_p2p_types::tls::TlsConfig;
@@ -1058,6 +1194,71 @@ mod tests {
         meta_net.close(0, "test").await;
     }
 
+    #[cfg(all(feature = "tx2", feature = "tx5", unix))]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn create_local_meta_net_for_loopback_signal() {
+        let mut config = KitsuneP2pConfig::default();
+        config.transport_pool = vec![TransportConfig::WebRTC {
+            signal_url: "ws://127.0.0.1:1".to_string(),
+        }];
+        config.bootstrap_service = None;
+        config.network_type = NetworkType::QuicMdns;
+        config = config.tune(|mut tp| {
+            tp.tx5_local_transport_for_loopback_signal = true;
+            tp
+        });
+
+        // The signal server is never connected to.
+        let (meta_net, _, bootstrap_net) = test_create_meta_net(config).await.unwrap();
+
+        assert_eq!(BootstrapNet::Tx2, bootstrap_net);
+        // The local url is wrapped in a proxy url.
+        let addr = meta_net.local_addr().unwrap();
+        assert!(
+            addr.contains(&format!("/{}/", kitsune_p2p_types::tx2::LOCAL_SCHEME)),
+            "{}",
+            addr
+        );
+
+        meta_net.close(0, "test").await;
+    }
+
+    #[cfg(all(feature = "tx2", feature = "tx5", unix))]
+    #[test]
+    fn local_transport_only_for_loopback_signal() {
+        use super::local_transport_for_loopback_signal;
+
+        let config_for = |signal_url: &str, enabled: bool| {
+            let mut config = KitsuneP2pConfig::default();
+            config.transport_pool = vec![TransportConfig::WebRTC {
+                signal_url: signal_url.to_string(),
+            }];
+            config.tune(|mut tp| {
+                tp.tx5_local_transport_for_loopback_signal = enabled;
+                tp
+            })
+        };
+
+        for signal_url in [
+            "ws://127.0.0.1:8080",
+            "ws://localhost:8080",
+            "ws://[::1]:8080",
+        ] {
+            let config = local_transport_for_loopback_signal(&config_for(signal_url, true));
+            assert!(
+                matches!(
+                    config.unwrap().transport_pool.as_slice(),
+                    [TransportConfig::Local { socket_dir: None }]
+                ),
+                "{signal_url}"
+            );
+            assert!(local_transport_for_loopback_signal(&config_for(signal_url, false)).is_none());
+        }
+        for signal_url in ["wss://signal.holo.host", "ws://192.168.1.2:8080"] {
+            assert!(local_transport_for_loopback_signal(&config_for(signal_url, true)).is_none());
+        }
+    }
+
     async fn test_create_meta_net(
         config: KitsuneP2pConfig,
     ) -> KitsuneP2pResult<(MetaNet, MetaNetEvtRecv, BootstrapNet)> {
@@ -1075,6 +1276,7 @@ mod tests {
 
         create_meta_net(
             &config,
+            BootstrapClient::new(&config.service_client).unwrap(),
             TlsConfig::new_ephemeral().await.unwrap(),
             internal_sender,
             HostStub::new().legacy(sender),
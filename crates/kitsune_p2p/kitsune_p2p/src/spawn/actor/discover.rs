@@ -414,6 +414,69 @@ pub(crate) fn search_remotes_covering_basis(
     }
 }
 
+/// find the agent infos published to the DHT near basis_loc, without storing
+/// them. Agents publish their infos to the nodes covering their own location,
+/// so the infos the host holds as an authority come first, which finds agents
+/// even when no peers are known. The known remote nodes nearest basis_loc are
+/// then asked for the agent infos they hold near it.
+pub(crate) fn query_dht_agent_infos(
+    inner: Arc<SpaceReadOnlyInner>,
+    basis_loc: DhtLocation,
+    timeout: KitsuneTimeout,
+) -> impl Future<Output = KitsuneP2pResult<Vec<AgentInfoSigned>>> + 'static + Send {
+    const QUERY_NODE_COUNT: usize = 3;
+
+    async move {
+        // keep only the most recently signed info for each agent
+        let mut found: HashMap<Arc<KitsuneAgent>, AgentInfoSigned> = HashMap::new();
+        let mut add = |info: AgentInfoSigned| match found.entry(info.agent.clone()) {
+            Entry::Occupied(mut entry) => {
+                if info.signed_at_ms > entry.get().signed_at_ms {
+                    entry.insert(info);
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(info);
+            }
+        };
+
+        match inner
+            .host_api
+            .query_dht_agent_infos(inner.space.clone(), basis_loc)
+            .await
+        {
+            Ok(list) => list.into_iter().for_each(&mut add),
+            Err(err) => {
+                tracing::debug!(?err, "query_dht_agent_infos: host query failed");
+            }
+        }
+
+        let nodes = get_cached_remotes_near_basis(inner.clone(), basis_loc, timeout)
+            .await
+            .unwrap_or_else(|_| Vec::new());
+        for node in nodes.into_iter().take(QUERY_NODE_COUNT) {
+            let con_hnd = match peer_connect(inner.clone(), &node, timeout).await {
+                PeerDiscoverResult::OkRemote { con_hnd, .. } => con_hnd,
+                _ => continue,
+            };
+            let payload = wire::Wire::peer_query(inner.space.clone(), basis_loc);
+            match con_hnd.request(&payload, timeout).await {
+                Ok(wire::Wire::PeerQueryResp(wire::PeerQueryResp { peer_list })) => {
+                    peer_list.into_iter().for_each(&mut add);
+                }
+                peer_resp => {
+                    tracing::debug!(
+                        ?peer_resp,
+                        "query_dht_agent_infos: unexpected peer response"
+                    );
+                }
+            }
+        }
+
+        Ok(found.into_values().collect())
+    }
+}
+
 pub(crate) trait GetCachedRemotesNearBasisSpace: 'static + Send + Sync {
     fn space(&self) -> Arc<KitsuneSpace>;
 
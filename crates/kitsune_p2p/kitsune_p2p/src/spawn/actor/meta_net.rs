@@ -36,11 +36,15 @@ use crate::spawn::KitsuneP2pEvent;
 use crate::spawn::PutAgentInfoSignedEvt;
 use crate::types::event::KitsuneP2pEventSender;
 use kitsune_p2p_block::BlockTargetId;
+#[cfg(feature = "tx5")]
+use kitsune_p2p_bootstrap_client::SignalRelay;
 use kitsune_p2p_timestamp::Timestamp;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
 use kitsune_p2p_types::codec::Codec;
 use kitsune_p2p_types::config::KitsuneP2pConfig;
 use kitsune_p2p_types::config::KitsuneP2pTuningParams;
+#[cfg(feature = "tx5")]
+use kitsune_p2p_types::config::ServiceClientConfig;
 use kitsune_p2p_types::*;
 use opentelemetry_api::metrics::Histogram;
 use parking_lot::Mutex;
@@ -635,9 +639,31 @@ pub enum MetaNet {
         url: tx5::PeerUrl,
         res: ResStore,
         tun: KitsuneP2pTuningParams,
+        /// Relays the signal connection when tx5 can't connect to the signal
+        /// server itself, in which case tx5's urls are those of the relay.
+        relay: Option<Arc<SignalRelay>>,
     },
 }
 
+/// A tx5 url as kitsune and other peers know it, which is the url of the
+/// signal server rather than the relay when the signal connection is relayed.
+#[cfg(feature = "tx5")]
+fn tx5_public_url(relay: &Option<Arc<SignalRelay>>, url: &PeerUrl) -> String {
+    match relay {
+        Some(relay) => relay.to_public(&url.to_string()),
+        None => url.to_string(),
+    }
+}
+
+/// A url which kitsune or other peers know as a tx5 url, as tx5 knows it.
+#[cfg(feature = "tx5")]
+fn tx5_local_url(relay: &Option<Arc<SignalRelay>>, url: &str) -> String {
+    match relay {
+        Some(relay) => relay.to_local(url),
+        None => url.to_string(),
+    }
+}
+
 impl MetaNet {
     /// Construct abstraction with tx2 backend.
     #[cfg(feature = "tx2")]
@@ -646,6 +672,7 @@ impl MetaNet {
         config: KitsuneP2pConfig,
         tls_config: kitsune_p2p_types::tls::TlsConfig,
         metrics: Tx2ApiMetrics,
+        bootstrap_client: kitsune_p2p_bootstrap_client::BootstrapClient,
     ) -> KitsuneP2pResult<(Self, MetaNetEvtRecv)> {
         use kitsune_p2p_types::tx2::tx2_utils::TxUrl;
 
@@ -670,6 +697,19 @@ impl MetaNet {
                     TxUrl::from_str_panicking("none:"),
                 )
             }
+            #[cfg(unix)]
+            KitsuneP2pTx2Backend::Local { socket_dir } => {
+                let mut conf = LocalConfig::default();
+                conf.tls = Some(tls_config.clone());
+                conf.tuning_params = Some(config.tuning_params.clone());
+                conf.socket_dir = socket_dir;
+                (
+                    tx2_local_adapter(conf)
+                        .await
+                        .map_err(KitsuneP2pError::other)?,
+                    TxUrl::from_str_panicking("none:"),
+                )
+            }
             /*
             KitsuneP2pTx2Backend::Quic { bind_to } => {
                 let mut conf = QuicConfig::default();
@@ -712,9 +752,11 @@ impl MetaNet {
                         bootstrap_url,
                         fallback_proxy_url,
                     };
-                    conf.proxy_from_bootstrap_cb = Arc::new(|bootstrap_url| {
+                    conf.proxy_from_bootstrap_cb = Arc::new(move |bootstrap_url| {
+                        let bootstrap_client = bootstrap_client.clone();
                         Box::pin(async move {
                             match kitsune_p2p_bootstrap_client::proxy_list(
+                                &bootstrap_client,
                                 bootstrap_url.into(),
                                 kitsune_p2p_bootstrap_client::BootstrapNet::Tx2,
                             )
@@ -850,6 +892,7 @@ impl MetaNet {
         host: HostApiLegacy,
         kitsune_internal_sender: ghost_actor::GhostSender<crate::spawn::Internal>,
         signal_url: String,
+        service_client: &ServiceClientConfig,
         preflight_user_data: PreflightUserData,
     ) -> KitsuneP2pResult<(Self, MetaNetEvtRecv)> {
         use kitsune_p2p_types::codec::{rmp_decode, rmp_encode};
@@ -958,8 +1001,21 @@ impl MetaNet {
         let (ep_hnd, mut ep_evt) = tx5::Ep3::new(Arc::new(tx5_config)).await;
         let ep_hnd = Arc::new(ep_hnd);
 
-        let cli_url = ep_hnd.listen(tx5::Tx5Url::new(&signal_url)?).await?;
-        tracing::info!(%cli_url, "tx5 listening at url");
+        // tx5 can't connect through a proxy or trust extra root certificates,
+        // so it connects to the signal server through a relay which can.
+        let relay = if *service_client != ServiceClientConfig::default() {
+            Some(Arc::new(
+                SignalRelay::spawn(service_client, &signal_url).await?,
+            ))
+        } else {
+            None
+        };
+        let listen_url = match &relay {
+            Some(relay) => relay.local_url(),
+            None => signal_url,
+        };
+        let cli_url = ep_hnd.listen(tx5::Tx5Url::new(&listen_url)?).await?;
+        tracing::info!(%cli_url, relayed = relay.is_some(), "tx5 listening at url");
 
         let res_store = Arc::new(Mutex::new(HashMap::new()));
 
@@ -967,6 +1023,7 @@ impl MetaNet {
         let res_store2 = res_store.clone();
         let tuning_params2 = tuning_params.clone();
         let spawn_host = host.clone();
+        let relay2 = relay.clone();
         tokio::task::spawn(async move {
             while let Some(evt) = ep_evt.recv().await {
                 match evt {
@@ -976,7 +1033,7 @@ impl MetaNet {
                     tx5::Ep3Event::Connected { peer_url } => {
                         if evt_send
                             .send(MetaNetEvt::Connected {
-                                remote_url: peer_url.to_string(),
+                                remote_url: tx5_public_url(&relay2, &peer_url),
                                 con: MetaNetCon::Tx5 {
                                     host: spawn_host.clone(),
                                     ep: ep_hnd2.clone(),
@@ -994,7 +1051,7 @@ impl MetaNet {
                     tx5::Ep3Event::Disconnected { peer_url } => {
                         if evt_send
                             .send(MetaNetEvt::Disconnected {
-                                remote_url: peer_url.to_string(),
+                                remote_url: tx5_public_url(&relay2, &peer_url),
                                 con: MetaNetCon::Tx5 {
                                     host: spawn_host.clone(),
                                     ep: ep_hnd2.clone(),
@@ -1020,7 +1077,7 @@ impl MetaNet {
                                         tracing::trace!(%msg_id, ?data, "received notify");
                                         if evt_send
                                             .send(MetaNetEvt::Notify {
-                                                remote_url: peer_url.to_string(),
+                                                remote_url: tx5_public_url(&relay2, &peer_url),
                                                 con: MetaNetCon::Tx5 {
                                                     host: spawn_host.clone(),
                                                     ep: ep_hnd2.clone(),
@@ -1069,7 +1126,7 @@ impl MetaNet {
                                         });
                                         if evt_send
                                             .send(MetaNetEvt::Request {
-                                                remote_url: peer_url.to_string(),
+                                                remote_url: tx5_public_url(&relay2, &peer_url),
                                                 con: MetaNetCon::Tx5 {
                                                     host: spawn_host.clone(),
                                                     ep: ep_hnd2.clone(),
@@ -1131,6 +1188,7 @@ impl MetaNet {
                 url: cli_url,
                 res: res_store,
                 tun: tuning_params,
+                relay,
             },
             evt_recv,
         ))
@@ -1146,8 +1204,8 @@ impl MetaNet {
 
         #[cfg(feature = "tx5")]
         {
-            if let MetaNet::Tx5 { url, .. } = self {
-                return Ok(url.to_string());
+            if let MetaNet::Tx5 { url, relay, .. } = self {
+                return Ok(tx5_public_url(relay, url));
             }
         }
 
@@ -1223,9 +1281,9 @@ impl MetaNet {
         {
             // Even if tx5 is enabled, check that the peer_url is a ws or wss url to the signal server
             if peer_url.scheme() == "ws" || peer_url.scheme() == "wss" {
-                if let MetaNet::Tx5 { ep, .. } = self {
-                    let peer_url =
-                        PeerUrl::new(peer_url.to_string()).map_err(KitsuneError::other)?;
+                if let MetaNet::Tx5 { ep, relay, .. } = self {
+                    let peer_url = PeerUrl::new(tx5_local_url(relay, &peer_url.to_string()))
+                        .map_err(KitsuneError::other)?;
                     ep.close(peer_url).map_err(KitsuneError::other)?;
                 }
             }
@@ -1250,13 +1308,19 @@ impl MetaNet {
         #[cfg(feature = "tx5")]
         {
             if let MetaNet::Tx5 {
-                host, ep, res, tun, ..
+                host,
+                ep,
+                res,
+                tun,
+                relay,
+                ..
             } = self
             {
                 return Ok(MetaNetCon::Tx5 {
                     host: host.clone(),
                     ep: ep.clone(),
-                    rem_url: tx5::Tx5Url::new(remote_url).map_err(KitsuneError::other)?,
+                    rem_url: tx5::Tx5Url::new(tx5_local_url(relay, &remote_url))
+                        .map_err(KitsuneError::other)?,
                     res: res.clone(),
                     tun: tun.clone(),
                 });
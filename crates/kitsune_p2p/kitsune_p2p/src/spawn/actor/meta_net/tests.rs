@@ -257,6 +257,8 @@ write_test_struct! {
         fn handle_incoming_metric_exchange(
             &mut Self,
             _space: Arc<KitsuneSpace>,
+            _con: MetaNetCon,
+            _remote_url: String,
             _msgs: Vec<MetricExchangeMsg>,
         ) -> InternalHandlerResult<()>, InternalHandlerResult<()> {
             Ok(futures::future::FutureExt::boxed(async move {
@@ -403,6 +405,45 @@ async fn start_signal_srv() -> (std::net::SocketAddr, tx5_signal_srv::SrvHnd) {
     (*addr_list.first().unwrap(), sig_hnd)
 }
 
+/// Start a SOCKS5 proxy which counts the connections it makes.
+async fn start_socks5_proxy() -> (std::net::SocketAddr, Arc<atomic::AtomicUsize>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let connections = Arc::new(atomic::AtomicUsize::new(0));
+    let connections2 = connections.clone();
+    tokio::task::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let connections = connections2.clone();
+            tokio::task::spawn(async move {
+                // Accept the greeting without authentication.
+                let mut greeting = [0; 2];
+                stream.read_exact(&mut greeting).await.unwrap();
+                let mut methods = vec![0; greeting[1] as usize];
+                stream.read_exact(&mut methods).await.unwrap();
+                stream.write_all(&[5, 0]).await.unwrap();
+
+                // Connect to the requested IPv4 address.
+                let mut request = [0; 10];
+                stream.read_exact(&mut request).await.unwrap();
+                assert_eq!([5, 1, 0, 1], request[..4]);
+                let ip = std::net::Ipv4Addr::new(request[4], request[5], request[6], request[7]);
+                let port = u16::from_be_bytes([request[8], request[9]]);
+                let mut upstream = tokio::net::TcpStream::connect((ip, port)).await.unwrap();
+                connections.fetch_add(1, atomic::Ordering::SeqCst);
+                stream
+                    .write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0])
+                    .await
+                    .unwrap();
+
+                let _ = tokio::io::copy_bidirectional(&mut stream, &mut upstream).await;
+            });
+        }
+    });
+    (addr, connections)
+}
+
 struct Setup2Nodes {
     tuning_params: KitsuneP2pTuningParams,
     _sig_hnd: tx5_signal_srv::SrvHnd,
@@ -426,6 +467,22 @@ impl Setup2Nodes {
         test: Test,
         user_data_a: PreflightUserData,
         user_data_b: PreflightUserData,
+    ) -> Self {
+        Self::new_with(
+            test,
+            user_data_a,
+            user_data_b,
+            ServiceClientConfig::default(),
+        )
+        .await
+    }
+
+    /// Set up the nodes, with the first connecting to the signal server with `service_client_a`.
+    pub async fn new_with(
+        test: Test,
+        user_data_a: PreflightUserData,
+        user_data_b: PreflightUserData,
+        service_client_a: ServiceClientConfig,
     ) -> Self {
         let mut tuning_params = config::tuning_params_struct::KitsuneP2pTuningParams::default();
         tuning_params.tx2_implicit_timeout_ms = 500;
@@ -442,6 +499,7 @@ impl Setup2Nodes {
             },
             i_s.clone(),
             format!("ws://{sig_addr}"),
+            &service_client_a,
             user_data_a,
         )
         .await
@@ -457,6 +515,7 @@ impl Setup2Nodes {
             },
             i_s.clone(),
             format!("ws://{sig_addr}"),
+            &ServiceClientConfig::default(),
             user_data_b,
         )
         .await
@@ -540,6 +599,73 @@ async fn basic_connected() {
     nodes.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn notify_over_proxied_signal_connection() {
+    let remote_urls = Arc::new(Mutex::new(Vec::new()));
+
+    let mut test = Test::default();
+
+    {
+        let remote_urls = remote_urls.clone();
+        test.recv = Arc::new(move |evt| {
+            if let MetaNetEvt::Notify { remote_url, .. } = evt {
+                remote_urls.lock().push(remote_url);
+            }
+        });
+    }
+
+    let (proxy_addr, proxy_connections) = start_socks5_proxy().await;
+    let service_client = ServiceClientConfig {
+        proxy_url: Some(url2::url2!("socks5://{proxy_addr}")),
+        ..Default::default()
+    };
+    let nodes = Setup2Nodes::new_with(
+        test,
+        PreflightUserData::default(),
+        PreflightUserData::default(),
+        service_client,
+    )
+    .await;
+
+    // The first node reaches the signal server through the proxy, but its url
+    // is on the signal server like the second node's, not on its relay.
+    assert!(proxy_connections.load(atomic::Ordering::SeqCst) > 0);
+    let signal_server = |addr: &str| addr.rsplit_once('/').unwrap().0.to_string();
+    assert_eq!(signal_server(&nodes.addr1), signal_server(&nodes.addr2));
+
+    for (send, addr) in [
+        (&nodes.send1, nodes.addr2.clone()),
+        (&nodes.send2, nodes.addr1.clone()),
+    ] {
+        let con = send
+            .get_connection(addr, nodes.tuning_params.implicit_timeout())
+            .await
+            .unwrap();
+        con.notify(
+            &wire::Wire::failure("Hello World!".into()),
+            nodes.tuning_params.implicit_timeout(),
+        )
+        .await
+        .unwrap();
+    }
+
+    // Each node knows the other by its url on the signal server.
+    tokio::time::timeout(std::time::Duration::from_secs(10), async {
+        while remote_urls.lock().len() < 2 {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    let mut remote_urls = remote_urls.lock().clone();
+    remote_urls.sort();
+    let mut addrs = vec![nodes.addr1.clone(), nodes.addr2.clone()];
+    addrs.sort();
+    assert_eq!(addrs, remote_urls);
+
+    nodes.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn close_and_disconnected() {
     let (recvc_not, recvc_wait) = notify_pair();
@@ -3,6 +3,7 @@ use crate::event::{
     FetchOpDataEvt, FetchOpDataEvtQuery, GetAgentInfoSignedEvt, KitsuneP2pEventSender,
     PutAgentInfoSignedEvt, QueryAgentsEvt,
 };
+use crate::metrics::ProtocolViolation;
 use crate::spawn::actor::fetch::FetchResponseConfig;
 use crate::spawn::actor::{
     Internal, InternalSender, UNAUTHORIZED_DISCONNECT_CODE, UNAUTHORIZED_DISCONNECT_REASON,
@@ -209,11 +210,27 @@ impl MetaNetTask {
                 // this *does* go over the network...
                 // so we don't want it to be too many
                 const LIMIT: u32 = 8;
-                let query = QueryAgentsEvt::new(space)
+                let query = QueryAgentsEvt::new(space.clone())
                     .near_basis(basis_loc)
                     .limit(LIMIT);
                 let resp = match self.host.legacy.query_agents(query).await {
-                    Ok(list) => wire::Wire::peer_query_resp(list),
+                    Ok(mut list) => {
+                        // Also answer with the agent infos held as DHT data near the
+                        // location, which covers agents that aren't in the peer store.
+                        match self.host.query_dht_agent_infos(space, basis_loc).await {
+                            Ok(dht_list) => {
+                                for info in dht_list.into_iter().take(LIMIT as usize) {
+                                    if !list.iter().any(|i| i.agent == info.agent) {
+                                        list.push(info);
+                                    }
+                                }
+                            }
+                            Err(err) => {
+                                tracing::debug!(?err, "Error querying DHT agent infos");
+                            }
+                        }
+                        wire::Wire::peer_query_resp(list)
+                    }
                     Err(err) => wire::Wire::failure(format!("Error querying agents: {:?}", err,)),
                 };
                 respond(resp).await;
@@ -470,6 +487,12 @@ impl MetaNetTask {
                                     "Dropping incoming op because the host failed to hash it {:?}",
                                     op
                                 );
+                                self.report_protocol_violation(
+                                    space.clone(),
+                                    &url,
+                                    ProtocolViolation::MalformedOp,
+                                )
+                                .await?;
                                 continue;
                             }
                         };
@@ -497,6 +520,11 @@ impl MetaNetTask {
                                 KitsuneP2pError::GhostError(GhostError::Disconnected) => {
                                     return Err(MetaNetTaskError::RequiredChannelClosed)
                                 }
+                                KitsuneP2pError::ProtocolViolation(violation) => {
+                                    tracing::warn!(?violation, "Host rejected incoming op");
+                                    self.report_protocol_violation(space.clone(), &url, violation)
+                                        .await?;
+                                }
                                 err => {
                                     tracing::error!(?err, "Failed to receive op");
                                 }
@@ -535,7 +563,11 @@ impl MetaNetTask {
                 Ok(())
             }
             wire::Wire::MetricExchange(wire::MetricExchange { space, msgs }) => {
-                if let Err(err) = self.i_s.incoming_metric_exchange(space, msgs).await {
+                if let Err(err) = self
+                    .i_s
+                    .incoming_metric_exchange(space, con, url, msgs)
+                    .await
+                {
                     tracing::error!(?err, "Metric exchange failed to send");
                     Err(err.into())
                 } else {
@@ -577,19 +609,67 @@ impl MetaNetTask {
             }
         }
     }
+
+    /// Report a protocol violation against every agent in the space that
+    /// advertises the remote url the offending data came from.
+    async fn report_protocol_violation(
+        &self,
+        space: Arc<KitsuneSpace>,
+        url: &str,
+        violation: ProtocolViolation,
+    ) -> MetaNetTaskResult<()> {
+        let agents = match self
+            .host
+            .legacy
+            .query_agents(QueryAgentsEvt::new(space.clone()))
+            .await
+        {
+            Ok(agents) => agents,
+            Err(KitsuneP2pError::GhostError(GhostError::Disconnected)) => {
+                return Err(MetaNetTaskError::RequiredChannelClosed)
+            }
+            Err(err) => {
+                tracing::warn!(?err, "Failed to look up the agents of a misbehaving peer");
+                return Ok(());
+            }
+        };
+
+        for info in agents
+            .into_iter()
+            .filter(|info| info.url_list.iter().any(|u| u.as_str() == url))
+        {
+            if let Err(err) = self
+                .i_s
+                .incoming_protocol_violation(space.clone(), info.agent.clone(), violation)
+                .await
+            {
+                match err {
+                    KitsuneP2pError::GhostError(GhostError::Disconnected) => {
+                        return Err(MetaNetTaskError::RequiredChannelClosed)
+                    }
+                    err => {
+                        tracing::error!(?err, "Failed to report protocol violation");
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::actor::BroadcastData;
     use crate::dht_arc::DhtLocation;
+    use crate::metrics::ProtocolViolation;
     use crate::spawn::actor::fetch::FetchResponseConfig;
     use crate::spawn::actor::meta_net_task::MetaNetTask;
     use crate::spawn::actor::test_util::InternalStub;
     use crate::spawn::actor::test_util::LegacyHostStub as HostReceiverStub;
     use crate::spawn::actor::Internal;
     use crate::spawn::meta_net::{MetaNetCon, MetaNetConTest, MetaNetEvt};
-    use crate::test_util::data::mk_agent_info;
+    use crate::test_util::data::{mk_agent_info, mk_agent_info_with_url};
     use crate::types::wire;
     use crate::wire::PushOpItem;
     use crate::{
@@ -1957,6 +2037,105 @@ mod tests {
         assert_eq!(1, host_receiver_stub.receive_ops_calls.read().len());
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn send_notify_push_op_data_reports_op_that_fails_to_hash() {
+        let (mut ep_evt_send, internal_stub, _, host_receiver_stub, host_stub, _, _, _) =
+            setup().await;
+
+        let remote_url = "ws://localhost:1/remote";
+        *host_receiver_stub.space_agents.write() = vec![
+            mk_agent_info_with_url(1, remote_url).await,
+            mk_agent_info_with_url(2, "ws://localhost:2/other").await,
+        ];
+
+        host_stub.fail_next_request();
+
+        ep_evt_send
+            .send(MetaNetEvt::Notify {
+                remote_url: remote_url.to_string(),
+                con: mk_test_con(),
+                data: wire::Wire::PushOpData(wire::PushOpData {
+                    op_data_list: vec![(
+                        test_space(1),
+                        vec![PushOpItem {
+                            op_data: KitsuneOpData::new(vec![0, 4, 10]),
+                            region: None,
+                        }],
+                    )],
+                }),
+            })
+            .await
+            .unwrap();
+
+        wait_for_condition(|| {
+            !internal_stub
+                .incoming_protocol_violation_calls
+                .read()
+                .is_empty()
+        })
+        .await
+        .expect("Timed out waiting for a protocol violation");
+
+        let calls = internal_stub
+            .incoming_protocol_violation_calls
+            .read()
+            .clone();
+        assert_eq!(1, calls.len());
+        assert_eq!(Arc::new(KitsuneAgent::new(vec![1; 32])), calls[0].1);
+        assert_eq!(ProtocolViolation::MalformedOp, calls[0].2);
+        assert!(host_receiver_stub.receive_ops_calls.read().is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn send_notify_push_op_data_reports_op_rejected_by_host() {
+        let (mut ep_evt_send, internal_stub, _, host_receiver_stub, _, _, fetch_pool, _) =
+            setup().await;
+
+        let remote_url = "ws://localhost:1/remote";
+        *host_receiver_stub.space_agents.write() =
+            vec![mk_agent_info_with_url(1, remote_url).await];
+        *host_receiver_stub.reject_ops_with.lock() = Some(ProtocolViolation::BadSignature);
+
+        fetch_pool.push(test_req_op(1, None, test_source(2)));
+
+        ep_evt_send
+            .send(MetaNetEvt::Notify {
+                remote_url: remote_url.to_string(),
+                con: mk_test_con(),
+                data: wire::Wire::PushOpData(wire::PushOpData {
+                    op_data_list: vec![(
+                        test_space(1),
+                        vec![PushOpItem {
+                            op_data: KitsuneOpData::new(vec![1, 4, 10]),
+                            region: None,
+                        }],
+                    )],
+                }),
+            })
+            .await
+            .unwrap();
+
+        wait_for_condition(|| {
+            !internal_stub
+                .incoming_protocol_violation_calls
+                .read()
+                .is_empty()
+        })
+        .await
+        .expect("Timed out waiting for a protocol violation");
+
+        let calls = internal_stub
+            .incoming_protocol_violation_calls
+            .read()
+            .clone();
+        assert_eq!(1, calls.len());
+        assert_eq!(Arc::new(KitsuneAgent::new(vec![1; 32])), calls[0].1);
+        assert_eq!(ProtocolViolation::BadSignature, calls[0].2);
+
+        // The op can still be fetched from another source.
+        assert_eq!(1, fetch_pool.len());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn send_notify_push_op_data_handles_shutdown_on_receive_ops() {
         let (mut ep_evt_send, _, _, host_receiver_stub, _, _, fetch_pool, meta_net_task_finished) =
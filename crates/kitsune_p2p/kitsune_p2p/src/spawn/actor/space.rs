@@ -1,20 +1,22 @@
 use super::*;
+use crate::gossip::sharded_gossip::{SpaceBandwidthBudget, TrafficPriority};
 use crate::metrics::*;
 use crate::types::gossip::GossipModule;
 use base64::Engine;
 use ghost_actor::dependencies::tracing;
+use kitsune_p2p_bootstrap_client::BootstrapClient;
 use kitsune_p2p_bootstrap_client::BootstrapNet;
 use kitsune_p2p_fetch::FetchPool;
 use kitsune_p2p_mdns::*;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
-use kitsune_p2p_types::codec::{rmp_decode, rmp_encode};
+use kitsune_p2p_types::codec::{rmp_decode, rmp_encode, Codec};
 use kitsune_p2p_types::config::KitsuneP2pConfig;
 use kitsune_p2p_types::config::NetworkType;
 use kitsune_p2p_types::dht::arq::ArqSize;
 use kitsune_p2p_types::dht::prelude::ArqClamping;
 use kitsune_p2p_types::dht::spacetime::SpaceDimension;
 use kitsune_p2p_types::dht::Arq;
-use kitsune_p2p_types::dht_arc::{DhtArcRange, DhtArcSet};
+use kitsune_p2p_types::dht_arc::{DhtArcRange, DhtArcSet, DhtLocation};
 use kitsune_p2p_types::tx2::tx2_utils::TxUrl;
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::AtomicBool;
@@ -63,6 +65,10 @@ ghost_actor::ghost_chan! {
         /// see if an agent is locally joined
         fn is_agent_local(agent: KAgent) -> bool;
 
+        /// Find the agent infos published to the DHT near a location, held by
+        /// the host or by remote peers, which finds peers without the bootstrap service.
+        fn query_dht_agent_infos(basis_loc: DhtLocation) -> Vec<AgentInfoSigned>;
+
         /// Update the arc of a local agent.
         fn update_agent_arc(agent: KAgent, arq: Arq) -> ();
 
@@ -107,7 +113,7 @@ ghost_actor::ghost_chan! {
         fn incoming_gossip(space: KSpace, con: MetaNetCon, remote_url: String, data: Payload, module_type: crate::types::gossip::GossipModuleType) -> ();
 
         /// Incoming Metric Exchange
-        fn incoming_metric_exchange(space: KSpace, msgs: VecMXM) -> ();
+        fn incoming_metric_exchange(space: KSpace, con: MetaNetCon, remote_url: String, msgs: VecMXM) -> ();
 
         /// New Con
         fn new_con(url: String, con: WireConHnd) -> ();
@@ -124,6 +130,7 @@ pub(crate) async fn spawn_space(
     host: HostApi,
     config: Arc<KitsuneP2pConfig>,
     bootstrap_net: BootstrapNet,
+    bootstrap_client: BootstrapClient,
     bandwidth_throttles: BandwidthThrottles,
     parallel_notify_permit: Arc<tokio::sync::Semaphore>,
     fetch_pool: FetchPool,
@@ -146,6 +153,11 @@ pub(crate) async fn spawn_space(
         .create_channel::<KitsuneP2p>()
         .await?;
 
+    let mut space_config = (*config).clone();
+    space_config.tuning_params =
+        host.space_tuning_params(space.clone(), config.tuning_params.clone());
+    let config = Arc::new(space_config);
+
     let host = HostApiLegacy::new(host, evt_send);
 
     tokio::task::spawn(builder.spawn(Space::new(
@@ -155,6 +167,7 @@ pub(crate) async fn spawn_space(
         ep_hnd,
         config,
         bootstrap_net,
+        bootstrap_client,
         bandwidth_throttles,
         parallel_notify_permit,
         fetch_pool,
@@ -192,7 +205,7 @@ impl SpaceInternalHandler for Space {
 
     fn handle_update_agent_info(&mut self) -> SpaceInternalHandlerResult<()> {
         let space = self.space.clone();
-        let mut mdns_handles = self.mdns_handles.clone();
+        let mdns_handles = self.mdns_handles.clone();
         let network_type = self.config.network_type.clone();
         let mut agent_list = Vec::with_capacity(self.local_joined_agents.len());
         for agent in self.local_joined_agents.keys().cloned() {
@@ -200,12 +213,17 @@ impl SpaceInternalHandler for Space {
             agent_list.push((agent, arq));
         }
         let bootstrap_net = self.ro_inner.bootstrap_net;
+        let bootstrap_client = self.ro_inner.bootstrap_client.clone();
         let ep_hnd = self.ro_inner.ep_hnd.clone();
         let evt_sender = self.host_api.legacy.clone();
         let bootstrap_service = self.config.bootstrap_service.clone();
         let expires_after = self.config.tuning_params.agent_info_expires_after_ms as u64;
         let dynamic_arcs = self.config.tuning_params.gossip_dynamic_arcs;
+        let arc_storage_budget = self.config.tuning_params.arc_storage_budget();
+        let storage_budget_shrinks = self.storage_budget_shrinks.clone();
+        let host_api = self.host_api.clone();
         let internal_sender = self.i_s.clone();
+        let metrics = self.ro_inner.metrics.clone();
         Ok(async move {
             let urls = vec![TxUrl::try_from(ep_hnd.local_addr()?)?];
             let mut peer_data = Vec::with_capacity(agent_list.len());
@@ -215,20 +233,37 @@ impl SpaceInternalHandler for Space {
                     space: space.clone(),
                     agent,
                     bootstrap_net,
+                    bootstrap_client: &bootstrap_client,
                     arq,
                     urls: &urls,
                     evt_sender: &evt_sender,
                     internal_sender: &internal_sender,
                     network_type: network_type.clone(),
-                    mdns_handles: &mut mdns_handles,
+                    mdns_handles: &mdns_handles,
                     bootstrap_service: &bootstrap_service,
                     dynamic_arcs,
+                    host_api: &host_api,
+                    arc_storage_budget,
+                    storage_budget_shrinks: &storage_budget_shrinks,
+                    metrics: &metrics,
                 };
                 peer_data.push(update_single_agent_info(input).await?);
             }
             internal_sender
                 .publish_agent_info_signed(PutAgentInfoSignedEvt { peer_data })
                 .await?;
+
+            // Drop the infos of peers which have expired since the last update.
+            match host_api
+                .prune_agent_info_signed(space.clone(), u64::MAX)
+                .await
+            {
+                Ok(pruned) if !pruned.is_empty() => {
+                    tracing::debug!(?space, pruned = pruned.len(), "Pruned expired agent infos")
+                }
+                Ok(_) => (),
+                Err(err) => tracing::warn!(?space, ?err, "Failed to prune expired agent infos"),
+            }
             Ok(())
         }
         .boxed()
@@ -241,7 +276,8 @@ impl SpaceInternalHandler for Space {
     ) -> SpaceInternalHandlerResult<()> {
         let space = self.space.clone();
         let bootstrap_net = self.ro_inner.bootstrap_net;
-        let mut mdns_handles = self.mdns_handles.clone();
+        let bootstrap_client = self.ro_inner.bootstrap_client.clone();
+        let mdns_handles = self.mdns_handles.clone();
         let network_type = self.config.network_type.clone();
         let ep_hnd = self.ro_inner.ep_hnd.clone();
         let evt_sender = self.host_api.legacy.clone();
@@ -249,6 +285,10 @@ impl SpaceInternalHandler for Space {
         let bootstrap_service = self.config.bootstrap_service.clone();
         let expires_after = self.config.tuning_params.agent_info_expires_after_ms as u64;
         let dynamic_arcs = self.config.tuning_params.gossip_dynamic_arcs;
+        let arc_storage_budget = self.config.tuning_params.arc_storage_budget();
+        let storage_budget_shrinks = self.storage_budget_shrinks.clone();
+        let host_api = self.host_api.clone();
+        let metrics = self.ro_inner.metrics.clone();
         let arc = self.get_agent_arq(&agent);
 
         Ok(async move {
@@ -258,14 +298,19 @@ impl SpaceInternalHandler for Space {
                 space: space.clone(),
                 agent,
                 bootstrap_net,
+                bootstrap_client: &bootstrap_client,
                 arq: arc,
                 urls: &urls,
                 evt_sender: &evt_sender,
                 internal_sender: &internal_sender,
                 network_type: network_type.clone(),
-                mdns_handles: &mut mdns_handles,
+                mdns_handles: &mdns_handles,
                 bootstrap_service: &bootstrap_service,
                 dynamic_arcs,
+                host_api: &host_api,
+                arc_storage_budget,
+                storage_budget_shrinks: &storage_budget_shrinks,
+                metrics: &metrics,
             };
             let peer_data = vec![update_single_agent_info(input).await?];
             internal_sender
@@ -291,6 +336,7 @@ impl SpaceInternalHandler for Space {
                     Arc::new(KitsuneBasis::new(agent_info.agent.0.clone())),
                     timeout,
                     BroadcastData::AgentInfo(agent_info.clone()),
+                    None,
                 )
             })
             .collect();
@@ -334,6 +380,18 @@ impl SpaceInternalHandler for Space {
         Ok(async move { Ok(res) }.boxed().into())
     }
 
+    fn handle_query_dht_agent_infos(
+        &mut self,
+        basis_loc: DhtLocation,
+    ) -> SpaceInternalHandlerResult<Vec<AgentInfoSigned>> {
+        let timeout = self.config.tuning_params.implicit_timeout();
+        Ok(
+            discover::query_dht_agent_infos(self.ro_inner.clone(), basis_loc, timeout)
+                .boxed()
+                .into(),
+        )
+    }
+
     fn handle_update_agent_arc(
         &mut self,
         agent: Arc<KitsuneAgent>,
@@ -435,19 +493,23 @@ impl SpaceInternalHandler for Space {
                     use discover::PeerDiscoverResult;
 
                     // attempt to establish a connection
-                    let con_hnd = match discover::peer_connect(ro_inner, &info, timeout).await {
-                        PeerDiscoverResult::OkShortcut => return,
-                        PeerDiscoverResult::OkRemote { con_hnd, .. } => con_hnd,
-                        PeerDiscoverResult::Err(err) => {
-                            tracing::warn!(?err, "broadcast error");
-                            return;
-                        }
-                    };
+                    let con_hnd =
+                        match discover::peer_connect(ro_inner.clone(), &info, timeout).await {
+                            PeerDiscoverResult::OkShortcut => return,
+                            PeerDiscoverResult::OkRemote { con_hnd, .. } => con_hnd,
+                            PeerDiscoverResult::Err(err) => {
+                                tracing::warn!(?err, "broadcast error");
+                                return;
+                            }
+                        };
 
                     // generate our broadcast payload
                     let payload = wire::Wire::broadcast(space, info.agent.clone(), data);
 
                     // forward the data
+                    ro_inner
+                        .outgoing_wire(TrafficPriority::Publish, &payload)
+                        .await;
                     if let Err(err) = con_hnd.notify(&payload, timeout).await {
                         tracing::warn!(?err, "broadcast error");
                     }
@@ -616,9 +678,14 @@ impl SpaceInternalHandler for Space {
     fn handle_incoming_metric_exchange(
         &mut self,
         _space: Arc<KitsuneSpace>,
+        con: MetaNetCon,
+        remote_url: String,
         msgs: Vec<MetricExchangeMsg>,
     ) -> InternalHandlerResult<()> {
-        self.ro_inner.metric_exchange.write().ingest_msgs(msgs);
+        self.ro_inner
+            .metric_exchange
+            .write()
+            .ingest_msgs(con, remote_url, msgs);
         unit_ok_fut()
     }
 
@@ -633,24 +700,38 @@ impl SpaceInternalHandler for Space {
     }
 }
 
+/// The mdns broadcasts of local agents, by space and agent.
+type MdnsHandles = Arc<parking_lot::Mutex<HashMap<Vec<u8>, Arc<AtomicBool>>>>;
+
+/// The disk usage of the space when the arc of each local agent was last
+/// shrunk to fit the storage budget.
+type StorageBudgetShrinks = Arc<parking_lot::Mutex<HashMap<Arc<KitsuneAgent>, u64>>>;
+
 struct UpdateAgentInfoInput<'borrow> {
     expires_after: u64,
     space: Arc<KitsuneSpace>,
     agent: Arc<KitsuneAgent>,
     bootstrap_net: BootstrapNet,
+    bootstrap_client: &'borrow BootstrapClient,
     arq: Arq,
     urls: &'borrow Vec<TxUrl>,
     evt_sender: &'borrow futures::channel::mpsc::Sender<KitsuneP2pEvent>,
     internal_sender: &'borrow ghost_actor::GhostSender<SpaceInternal>,
     network_type: NetworkType,
-    mdns_handles: &'borrow mut HashMap<Vec<u8>, Arc<AtomicBool>>,
+    mdns_handles: &'borrow MdnsHandles,
     bootstrap_service: &'borrow Option<Url2>,
     dynamic_arcs: bool,
+    host_api: &'borrow HostApiLegacy,
+    arc_storage_budget: Option<u64>,
+    storage_budget_shrinks: &'borrow StorageBudgetShrinks,
+    metrics: &'borrow MetricsSync,
 }
 
 async fn update_arc_length(
     evt_sender: &futures::channel::mpsc::Sender<KitsuneP2pEvent>,
+    metrics: &MetricsSync,
     space: Arc<KitsuneSpace>,
+    agent: Arc<KitsuneAgent>,
     arq: &mut Arq,
 ) -> KitsuneP2pResult<()> {
     let dim = SpaceDimension::standard();
@@ -663,7 +744,18 @@ async fn update_arc_length(
     #[cfg(feature = "test_utils")]
     tracing::trace!("Before: {:2.1}% |{}|", cov_before, arq.to_ascii(dim, 64));
 
+    let trigger = if view.arc_clamping().is_some() {
+        ArqTransitionTrigger::Clamping
+    } else {
+        ArqTransitionTrigger::PeerDensity {
+            extrapolated_coverage: view.extrapolated_coverage(arq),
+        }
+    };
+    let old_arq = *arq;
     view.update_arq(arq);
+    if let Some(transition) = ArqTransition::new(agent, &old_arq, arq, trigger) {
+        metrics.write().record_arq_transition(transition);
+    }
 
     let cov_after = arq.coverage(dim) * 100.0;
 
@@ -675,6 +767,76 @@ async fn update_arc_length(
     Ok(())
 }
 
+/// Keep an arc within the storage budget once the space's data on disk has
+/// grown past it.
+///
+/// Data which is already stored isn't removed when the arc shrinks, so disk
+/// usage doesn't fall back under the budget. While it is over, the arc isn't
+/// allowed to grow back past `old_arq`, its size before this update, and it
+/// is only shrunk again if more data has been stored since it was last shrunk.
+#[allow(clippy::too_many_arguments)]
+async fn clamp_arc_to_storage_budget(
+    host_api: &HostApiLegacy,
+    evt_sender: &futures::channel::mpsc::Sender<KitsuneP2pEvent>,
+    metrics: &MetricsSync,
+    storage_budget_shrinks: &StorageBudgetShrinks,
+    space: Arc<KitsuneSpace>,
+    agent: Arc<KitsuneAgent>,
+    old_arq: Arq,
+    arq: &mut Arq,
+    budget: u64,
+) -> KitsuneP2pResult<()> {
+    let used = host_api
+        .storage_disk_usage(space.clone())
+        .await
+        .map_err(KitsuneP2pError::other)?;
+    if used <= budget {
+        storage_budget_shrinks.lock().remove(&agent);
+        return Ok(());
+    }
+
+    let dim = SpaceDimension::standard();
+    if arq.coverage(dim) > old_arq.coverage(dim) {
+        *arq = old_arq;
+    }
+    let shrunk_at = storage_budget_shrinks.lock().get(&agent).copied();
+    if shrunk_at.is_some_and(|shrunk_at| used <= shrunk_at) {
+        return Ok(());
+    }
+
+    let view = evt_sender
+        .query_peer_density(space.clone(), arq.to_dht_arc(dim))
+        .await?;
+    let cov_before = arq.coverage(dim) * 100.0;
+    let before_shrink = *arq;
+    if view.shrink_arq_to_storage_budget(arq, used, budget) {
+        storage_budget_shrinks.lock().insert(agent.clone(), used);
+        let trigger = ArqTransitionTrigger::StorageBudget {
+            used_bytes: used,
+            budget_bytes: budget,
+        };
+        if let Some(transition) = ArqTransition::new(agent, &before_shrink, arq, trigger) {
+            metrics.write().record_arq_transition(transition);
+        }
+        tracing::info!(
+            ?space,
+            used,
+            budget,
+            "Shrank arc from {:2.1}% to {:2.1}% to fit the storage budget",
+            cov_before,
+            arq.coverage(dim) * 100.0
+        );
+    } else {
+        tracing::warn!(
+            ?space,
+            used,
+            budget,
+            "Space is over the storage budget but its arc can't shrink without dropping below minimum coverage"
+        );
+    }
+    Ok(())
+}
+
 // TODO document me
 async fn update_single_agent_info(
     input: UpdateAgentInfoInput<'_>,
@@ -684,6 +846,7 @@ async fn update_single_agent_info(
         space,
         agent,
         bootstrap_net,
+        bootstrap_client,
         mut arq,
         urls,
         evt_sender,
@@ -692,16 +855,36 @@ async fn update_single_agent_info(
         mdns_handles,
         bootstrap_service,
         dynamic_arcs,
+        host_api,
+        arc_storage_budget,
+        storage_budget_shrinks,
+        metrics,
     } = input;
 
     if dynamic_arcs {
-        update_arc_length(evt_sender, space.clone(), &mut arq).await?;
+        let old_arq = arq;
+        update_arc_length(evt_sender, metrics, space.clone(), agent.clone(), &mut arq).await?;
+        if let Some(budget) = arc_storage_budget {
+            clamp_arc_to_storage_budget(
+                host_api,
+                evt_sender,
+                metrics,
+                storage_budget_shrinks,
+                space.clone(),
+                agent.clone(),
+                old_arq,
+                &mut arq,
+                budget,
+            )
+            .await?;
+        }
     }
 
     // Update the agents arc through the internal sender.
     internal_sender.update_agent_arc(agent.clone(), arq).await?;
 
-    let signed_at_ms = kitsune_p2p_bootstrap_client::now_once(None, bootstrap_net).await?;
+    let signed_at_ms =
+        kitsune_p2p_bootstrap_client::now_once(bootstrap_client, None, bootstrap_net).await?;
     let expires_at_ms = signed_at_ms + expires_after;
 
     let agent_info_signed = AgentInfoSigned::sign(
@@ -743,8 +926,8 @@ async fn update_single_agent_info(
             if !urls.is_empty() {
                 // Kill previous broadcast for this space + agent
                 let key = [space.get_bytes(), agent.get_bytes()].concat();
-                if let Some(current_handle) = mdns_handles.get(&key) {
-                    mdns_kill_thread(current_handle.to_owned());
+                if let Some(current_handle) = mdns_handles.lock().remove(&key) {
+                    mdns_kill_thread(current_handle);
                 }
                 // Broadcast by using Space as service type and Agent as service name
                 let space_b64 = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(&space[..]);
@@ -757,21 +940,74 @@ async fn update_single_agent_info(
                 tracing::trace!(?space_b64, ?agent_b64);
                 let handle = mdns_create_broadcast_thread(space_b64, agent_b64, &buffer);
                 // store handle in self
-                mdns_handles.insert(key, handle);
+                mdns_handles.lock().insert(key, handle);
             }
         }
         NetworkType::QuicBootstrap => {
-            kitsune_p2p_bootstrap_client::put(
+            // The agent info is also published to the DHT by the host, so it can
+            // still be found while the bootstrap service is down. Retrying happens
+            // in the background so a slow bootstrap service doesn't hold up the update.
+            tokio::task::spawn(put_bootstrap_agent_info(
+                bootstrap_client.clone(),
                 bootstrap_service.clone(),
                 agent_info_signed.clone(),
                 bootstrap_net,
-            )
-            .await?;
+            ));
         }
     }
     Ok(agent_info_signed)
 }
 
+/// How many times publishing an agent info to the bootstrap service is attempted.
+const BOOTSTRAP_PUT_ATTEMPTS: u32 = 3;
+
+/// How long to wait before the first retry of a failed bootstrap publish,
+/// doubled after every further failure.
+const BOOTSTRAP_PUT_BACKOFF: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Publish an agent info to the bootstrap service, retrying with backoff,
+/// and record whether it made it in the bootstrap put metric.
+async fn put_bootstrap_agent_info(
+    bootstrap_client: BootstrapClient,
+    bootstrap_service: Option<Url2>,
+    agent_info_signed: AgentInfoSigned,
+    bootstrap_net: BootstrapNet,
+) {
+    let mut backoff = BOOTSTRAP_PUT_BACKOFF;
+    for attempt in 1..=BOOTSTRAP_PUT_ATTEMPTS {
+        match kitsune_p2p_bootstrap_client::put(
+            &bootstrap_client,
+            bootstrap_service.clone(),
+            agent_info_signed.clone(),
+            bootstrap_net,
+        )
+        .await
+        {
+            Ok(()) => {
+                METRIC_BOOTSTRAP_PUT.add(1, &[opentelemetry_api::KeyValue::new("is_error", false)]);
+                return;
+            }
+            Err(err) if attempt < BOOTSTRAP_PUT_ATTEMPTS => {
+                tracing::debug!(
+                    ?err,
+                    attempt,
+                    "Failed to publish agent info to the bootstrap service, retrying"
+                );
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            Err(err) => {
+                METRIC_BOOTSTRAP_PUT.add(1, &[opentelemetry_api::KeyValue::new("is_error", true)]);
+                tracing::warn!(
+                    ?err,
+                    attempts = BOOTSTRAP_PUT_ATTEMPTS,
+                    "Failed to publish agent info to the bootstrap service"
+                );
+            }
+        }
+    }
+}
+
 use crate::spawn::actor::space::agent_info_update::AgentInfoUpdateTask;
 use crate::spawn::actor::space::bootstrap_task::BootstrapTask;
 use ghost_actor::dependencies::must_future::MustBoxFuture;
@@ -790,6 +1026,12 @@ impl ghost_actor::GhostControlHandler for Space {
             for module in self.gossip_mod.values_mut() {
                 module.close();
             }
+            for (_, handle) in self.mdns_handles.lock().drain() {
+                mdns_kill_thread(handle);
+            }
+            for (_, listener) in self.mdns_listeners.drain() {
+                listener.abort();
+            }
         }
         .boxed()
         .into()
@@ -818,8 +1060,10 @@ impl KitsuneP2pHandler for Space {
         }
         self.local_joined_agents
             .insert(agent.clone(), maybe_agent_info);
-        for module in self.gossip_mod.values() {
-            module.local_agent_join(agent.clone());
+        if !self.gossip_disabled_agents.contains(&agent) {
+            for module in self.gossip_mod.values() {
+                module.local_agent_join(agent.clone());
+            }
         }
         let fut = self.i_s.update_single_agent_info(agent);
         let evt_sender = self.host_api.legacy.clone();
@@ -827,40 +1071,50 @@ impl KitsuneP2pHandler for Space {
             NetworkType::QuicMdns => {
                 // Listen to MDNS service that has that space as service type
                 let space_b64 = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(&space[..]);
-                if !self.mdns_listened_spaces.contains(&space_b64) {
-                    self.mdns_listened_spaces.insert(space_b64.clone());
-                    tokio::task::spawn(async move {
-                        let stream = mdns_listen(space_b64);
-                        tokio::pin!(stream);
-                        while let Some(maybe_response) = stream.next().await {
-                            match maybe_response {
-                                Ok(response) => {
-                                    tracing::trace!(msg = "Peer found via MDNS", ?response);
-                                    // Decode response
-                                    let maybe_agent_info_signed =
-                                        rmp_decode(&mut &*response.buffer);
-                                    if let Err(e) = maybe_agent_info_signed {
-                                        tracing::error!(msg = "Failed to decode MDNS peer", ?e);
-                                        continue;
-                                    }
-                                    if let Ok(remote_agent_info_signed) = maybe_agent_info_signed {
-                                        // Add to local storage
-                                        if let Err(e) = evt_sender
-                                            .put_agent_info_signed(PutAgentInfoSignedEvt {
-                                                peer_data: vec![remote_agent_info_signed],
-                                            })
-                                            .await
+                if let std::collections::hash_map::Entry::Vacant(entry) =
+                    self.mdns_listeners.entry(space_b64.clone())
+                {
+                    let listener = tokio::task::spawn({
+                        let space_b64 = space_b64.clone();
+                        async move {
+                            let stream = mdns_listen(space_b64);
+                            tokio::pin!(stream);
+                            while let Some(maybe_response) = stream.next().await {
+                                match maybe_response {
+                                    Ok(response) => {
+                                        tracing::trace!(msg = "Peer found via MDNS", ?response);
+                                        // Decode response
+                                        let maybe_agent_info_signed =
+                                            rmp_decode(&mut &*response.buffer);
+                                        if let Err(e) = maybe_agent_info_signed {
+                                            tracing::error!(msg = "Failed to decode MDNS peer", ?e);
+                                            continue;
+                                        }
+                                        if let Ok(remote_agent_info_signed) =
+                                            maybe_agent_info_signed
                                         {
-                                            tracing::error!(msg = "Failed to store MDNS peer", ?e);
+                                            // Add to local storage
+                                            if let Err(e) = evt_sender
+                                                .put_agent_info_signed(PutAgentInfoSignedEvt {
+                                                    peer_data: vec![remote_agent_info_signed],
+                                                })
+                                                .await
+                                            {
+                                                tracing::error!(
+                                                    msg = "Failed to store MDNS peer",
+                                                    ?e
+                                                );
+                                            }
                                         }
                                     }
-                                }
-                                Err(e) => {
-                                    tracing::error!(msg = "Failed to get peers from MDNS", ?e);
+                                    Err(e) => {
+                                        tracing::error!(msg = "Failed to get peers from MDNS", ?e);
+                                    }
                                 }
                             }
                         }
                     });
+                    entry.insert(listener);
                 }
             }
             NetworkType::QuicBootstrap => {
@@ -886,6 +1140,29 @@ impl KitsuneP2pHandler for Space {
         self.publish_leave_agent_info(agent)
     }
 
+    fn handle_set_local_agent_gossip(
+        &mut self,
+        _space: Arc<KitsuneSpace>,
+        agent: Arc<KitsuneAgent>,
+        enabled: bool,
+    ) -> KitsuneP2pHandlerResult<()> {
+        let changed = if enabled {
+            self.gossip_disabled_agents.remove(&agent)
+        } else {
+            self.gossip_disabled_agents.insert(agent.clone())
+        };
+        if changed && self.local_joined_agents.contains_key(&agent) {
+            for module in self.gossip_mod.values() {
+                if enabled {
+                    module.local_agent_join(agent.clone());
+                } else {
+                    module.local_agent_leave(agent.clone());
+                }
+            }
+        }
+        unit_ok_fut()
+    }
+
     fn handle_rpc_single(
         &mut self,
         space: Arc<KitsuneSpace>,
@@ -909,6 +1186,7 @@ impl KitsuneP2pHandler for Space {
             timeout,
         );
 
+        let ro_inner = self.ro_inner.clone();
         let metrics = self.ro_inner.metrics.clone();
 
         Ok(async move {
@@ -919,6 +1197,9 @@ impl KitsuneP2pHandler for Space {
                 }
                 discover::PeerDiscoverResult::OkRemote { con_hnd, .. } => {
                     let payload = wire::Wire::call(space.clone(), to_agent.clone(), payload.into());
+                    ro_inner
+                        .outgoing_wire(TrafficPriority::Call, &payload)
+                        .await;
                     let res = con_hnd.request(&payload, timeout).await?;
                     match res {
                         wire::Wire::Failure(wire::Failure { reason }) => {
@@ -971,6 +1252,7 @@ impl KitsuneP2pHandler for Space {
         basis: Arc<KitsuneBasis>,
         timeout: KitsuneTimeout,
         data: BroadcastData,
+        peer_filter: Option<PeerFilter>,
     ) -> KitsuneP2pHandlerResult<()> {
         // first, forward this data to all connected local agents.
         let mut local_notify_events = Vec::new();
@@ -1039,10 +1321,16 @@ impl KitsuneP2pHandler for Space {
                 .await
                 .ok();
             tokio::task::spawn(async move {
-                let cover_nodes = discover_fut.await?;
+                let mut cover_nodes = discover_fut.await?;
                 if cover_nodes.is_empty() {
                     return Err("failed to discover neighboring peers".into());
                 }
+                if let Some(peer_filter) = &peer_filter {
+                    cover_nodes.retain(|info| peer_filter.allows(info));
+                    if cover_nodes.is_empty() {
+                        return Err("no neighboring peers may be broadcast to".into());
+                    }
+                }
 
                 let mut all = Vec::new();
 
@@ -1102,7 +1390,11 @@ impl KitsuneP2pHandler for Space {
                     );
 
                     // notify the remote node
+                    let ro_inner = ro_inner.clone();
                     all.push(async move {
+                        ro_inner
+                            .outgoing_wire(TrafficPriority::Publish, &data)
+                            .await;
                         if let Err(err) = con_hnd.notify(&data, timeout).await {
                             tracing::warn!(?err, "delegate broadcast error");
                         }
@@ -1182,6 +1474,9 @@ impl KitsuneP2pHandler for Space {
                         discover::PeerDiscoverResult::OkRemote { con_hnd, .. } => {
                             let payload =
                                 wire::Wire::broadcast(space, agent, BroadcastData::User(payload));
+                            ro_inner
+                                .outgoing_wire(TrafficPriority::Publish, &payload)
+                                .await;
                             con_hnd
                                 .notify(&payload, timeout)
                                 .map(|r| {
@@ -1222,6 +1517,60 @@ impl KitsuneP2pHandler for Space {
         unit_ok_fut()
     }
 
+    fn handle_report_protocol_violation(
+        &mut self,
+        _space: KSpace,
+        agent: KAgent,
+        violation: ProtocolViolation,
+    ) -> KitsuneP2pHandlerResult<()> {
+        let banned = self
+            .ro_inner
+            .metrics
+            .write()
+            .record_protocol_violation(violation, [&agent]);
+        for agent in banned {
+            self.ro_inner
+                .fetch_pool
+                .ban_source(FetchSource::Agent(agent), PEER_BAN_DURATION);
+        }
+        unit_ok_fut()
+    }
+
+    fn handle_fetch_from_authorities(
+        &mut self,
+        space: KSpace,
+        basis: KBasis,
+        op_hash_list: Vec<OpHashSized>,
+    ) -> KitsuneP2pHandlerResult<()> {
+        let ro_inner = self.ro_inner.clone();
+        let timeout = ro_inner.config.tuning_params.implicit_timeout();
+        let discover_fut =
+            discover::search_remotes_covering_basis(ro_inner.clone(), basis.get_loc(), timeout);
+        Ok(async move {
+            let cover_nodes = discover_fut.await?;
+            if cover_nodes.is_empty() {
+                return Err("failed to discover neighboring peers".into());
+            }
+            // Every authority is a source for every op, so the fetch pool
+            // moves on to the next authority if one doesn't have an op.
+            for op_hash in op_hash_list {
+                for info in &cover_nodes {
+                    ro_inner.fetch_pool.push(FetchPoolPush {
+                        key: FetchKey::Op(op_hash.data()),
+                        space: space.clone(),
+                        source: FetchSource::Agent(info.agent()),
+                        size: op_hash.maybe_size(),
+                        context: None,
+                        transfer_method: TransferMethod::Fetch,
+                    });
+                }
+            }
+            Ok(())
+        }
+        .boxed()
+        .into())
+    }
+
     fn handle_authority_for_hash(
         &mut self,
         _space: Arc<KitsuneSpace>,
@@ -1286,15 +1635,26 @@ pub(crate) struct SpaceReadOnlyInner {
     #[allow(dead_code)]
     pub(crate) config: Arc<KitsuneP2pConfig>,
     pub(crate) bootstrap_net: BootstrapNet,
+    pub(crate) bootstrap_client: BootstrapClient,
     pub(crate) parallel_notify_permit: Arc<tokio::sync::Semaphore>,
     pub(crate) metrics: MetricsSync,
     pub(crate) metric_exchange: MetricExchangeSync,
     pub(crate) publish_pending_delegates: parking_lot::Mutex<HashMap<KOpHash, PendingDelegate>>,
     #[allow(dead_code)]
     pub(crate) fetch_pool: FetchPool,
+    pub(crate) space_budget: Arc<SpaceBandwidthBudget>,
 }
 
 impl SpaceReadOnlyInner {
+    /// Wait until the space's outbound bandwidth budget allows sending this message.
+    pub(crate) async fn outgoing_wire(&self, priority: TrafficPriority, wire: &wire::Wire) {
+        if !self.space_budget.is_limited() {
+            return;
+        }
+        let bytes = wire.encode_vec().map(|b| b.len()).unwrap_or_default();
+        self.space_budget.outgoing_bytes(priority, bytes).await;
+    }
+
     pub(crate) fn publish_pending_delegate(
         self: Arc<Self>,
         op_hash: KOpHash,
@@ -1350,9 +1710,12 @@ pub(crate) struct Space {
     pub(crate) host_api: HostApiLegacy,
     pub(crate) local_joined_agents: HashMap<Arc<KitsuneAgent>, Option<AgentInfoSigned>>,
     pub(crate) agent_arqs: HashMap<Arc<KitsuneAgent>, Arq>,
+    /// Local agents which have had gossip turned off while staying in the space.
+    gossip_disabled_agents: HashSet<Arc<KitsuneAgent>>,
     pub(crate) config: Arc<KitsuneP2pConfig>,
-    mdns_handles: HashMap<Vec<u8>, Arc<AtomicBool>>,
-    mdns_listened_spaces: HashSet<String>,
+    mdns_handles: MdnsHandles,
+    storage_budget_shrinks: StorageBudgetShrinks,
+    mdns_listeners: HashMap<String, tokio::task::JoinHandle<()>>,
     gossip_mod: HashMap<GossipModuleType, GossipModule>,
 }
 
@@ -1366,11 +1729,13 @@ impl Space {
         ep_hnd: MetaNet,
         config: Arc<KitsuneP2pConfig>,
         bootstrap_net: BootstrapNet,
+        bootstrap_client: BootstrapClient,
         bandwidth_throttles: BandwidthThrottles,
         parallel_notify_permit: Arc<tokio::sync::Semaphore>,
         fetch_pool: FetchPool,
     ) -> Self {
         let metrics = MetricsSync::default();
+        let space_budget = Arc::new(SpaceBandwidthBudget::new(&config.tuning_params));
 
         {
             let space = space.clone();
@@ -1415,6 +1780,7 @@ impl Space {
                                 GossipModuleType::ShardedRecent,
                                 crate::gossip::sharded_gossip::recent_factory(
                                     bandwidth_throttles.recent(),
+                                    space_budget.clone(),
                                 ),
                             ));
                         }
@@ -1423,6 +1789,7 @@ impl Space {
                                 GossipModuleType::ShardedHistorical,
                                 crate::gossip::sharded_gossip::historical_factory(
                                     bandwidth_throttles.historical(),
+                                    space_budget.clone(),
                                 ),
                             ));
                         }
@@ -1464,6 +1831,7 @@ impl Space {
                 space.clone(),
                 config.bootstrap_service.clone(),
                 bootstrap_net,
+                bootstrap_client.clone(),
                 config
                     .tuning_params
                     .bootstrap_check_delay_backoff_multiplier,
@@ -1478,11 +1846,13 @@ impl Space {
             ep_hnd,
             config: config.clone(),
             bootstrap_net,
+            bootstrap_client: bootstrap_client.clone(),
             parallel_notify_permit,
             metrics,
             metric_exchange,
             publish_pending_delegates: parking_lot::Mutex::new(HashMap::new()),
             fetch_pool,
+            space_budget,
         });
 
         Self {
@@ -1492,9 +1862,11 @@ impl Space {
             host_api,
             local_joined_agents: HashMap::new(),
             agent_arqs: HashMap::new(),
+            gossip_disabled_agents: HashSet::new(),
             config,
-            mdns_handles: HashMap::new(),
-            mdns_listened_spaces: HashSet::new(),
+            mdns_handles: MdnsHandles::default(),
+            storage_budget_shrinks: StorageBudgetShrinks::default(),
+            mdns_listeners: HashMap::new(),
             gossip_mod,
         }
     }
@@ -1515,13 +1887,17 @@ impl Space {
         let space = self.space.clone();
         let network_type = self.config.network_type.clone();
         let bootstrap_net = self.ro_inner.bootstrap_net;
+        let bootstrap_client = self.ro_inner.bootstrap_client.clone();
         let evt_sender = self.host_api.legacy.clone();
         let bootstrap_service = self.config.bootstrap_service.clone();
         let expires_after = self.config.tuning_params.agent_info_expires_after_ms as u64;
         let host = self.host_api.clone();
+        let mdns_handles = self.mdns_handles.clone();
 
         Ok(async move {
-            let signed_at_ms = kitsune_p2p_bootstrap_client::now_once(None, bootstrap_net).await?;
+            let signed_at_ms =
+                kitsune_p2p_bootstrap_client::now_once(&bootstrap_client, None, bootstrap_net)
+                    .await?;
             let expires_at_ms = signed_at_ms + expires_after;
             let agent_info_signed = AgentInfoSigned::sign(
                 space.clone(),
@@ -1550,6 +1926,8 @@ impl Space {
 
             tracing::debug!(?agent_info_signed);
 
+            let mdns_key = [space.get_bytes(), agent.get_bytes()].concat();
+
             // TODO: at some point, we should not remove agents who have left, but rather
             // there should be a flag indicating they have left. The removed agent may just
             // get re-gossiped to another local agent in the same space, defeating the purpose.
@@ -1559,9 +1937,16 @@ impl Space {
 
             // Push to the network as well
             match network_type {
-                NetworkType::QuicMdns => tracing::warn!("NOT publishing leaves to mdns"),
+                NetworkType::QuicMdns => {
+                    // There is no way to publish a leave over mdns, so just
+                    // stop advertising the agent and let peers' copies expire.
+                    if let Some(handle) = mdns_handles.lock().remove(&mdns_key) {
+                        mdns_kill_thread(handle);
+                    }
+                }
                 NetworkType::QuicBootstrap => {
                     match kitsune_p2p_bootstrap_client::put(
+                        &bootstrap_client,
                         bootstrap_service.clone(),
                         agent_info_signed,
                         bootstrap_net,
@@ -5,9 +5,11 @@ use futures::channel::mpsc::Sender;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use ghost_actor::{GhostControlSender, GhostError, GhostSender};
+use kitsune_p2p_bootstrap_client::BootstrapClient;
 use kitsune_p2p_bootstrap_client::BootstrapNet;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
 use kitsune_p2p_types::bootstrap::RandomQuery;
+use kitsune_p2p_types::dht_arc::DhtLocation;
 use parking_lot::RwLock;
 use std::sync::Arc;
 use std::time::Duration;
@@ -27,6 +29,7 @@ trait BootstrapService: Send {
 }
 
 struct DefaultBootstrapService {
+    client: BootstrapClient,
     url: Option<Url2>,
     net: BootstrapNet,
 }
@@ -34,8 +37,15 @@ struct DefaultBootstrapService {
 impl BootstrapService for DefaultBootstrapService {
     fn random(&self, query: RandomQuery) -> BoxFuture<KitsuneP2pResult<Vec<AgentInfoSigned>>> {
         async move {
-            Ok(kitsune_p2p_bootstrap_client::random(self.url.clone(), query, self.net).await?)
-        }.boxed()
+            Ok(kitsune_p2p_bootstrap_client::random(
+                &self.client,
+                self.url.clone(),
+                query,
+                self.net,
+            )
+            .await?)
+        }
+        .boxed()
     }
 }
 
@@ -46,6 +56,7 @@ impl BootstrapTask {
         space: Arc<KitsuneSpace>,
         bootstrap_service: Option<Url2>,
         bootstrap_net: BootstrapNet,
+        bootstrap_client: BootstrapClient,
         bootstrap_check_delay_backoff_multiplier: u32,
         mut bootstrap_max_delay_s: u32,
     ) -> Arc<RwLock<Self>> {
@@ -60,6 +71,7 @@ impl BootstrapTask {
         }));
 
         let bootstrap_query = DefaultBootstrapService {
+            client: bootstrap_client,
             url: bootstrap_service,
             net: bootstrap_net,
         };
@@ -109,7 +121,7 @@ impl BootstrapTask {
                         std::cmp::min(current_delay * backoff_multiplier, max_delay);
                 }
 
-                match bootstrap_query
+                let list = match bootstrap_query
                     .random(RandomQuery {
                         space: space.clone(),
                         limit: MAX_AGENTS_PER_QUERY.into(),
@@ -118,42 +130,60 @@ impl BootstrapTask {
                 {
                     Err(e) => {
                         tracing::error!(msg = "Failed to get peers from bootstrap", ?e);
+
+                        // Fall back to asking the peers we already know for the agent
+                        // infos published near a random location of the DHT.
+                        match internal_sender
+                            .query_dht_agent_infos(DhtLocation::new(rand::random()))
+                            .await
+                        {
+                            Ok(list) if !list.is_empty() => list,
+                            Ok(_) => continue,
+                            Err(err) => {
+                                tracing::debug!(?err, "Failed to get peers from the DHT");
+                                continue;
+                            }
+                        }
                     }
                     Ok(list) => {
                         if list.len() > MAX_AGENTS_PER_QUERY as usize {
                             tracing::warn!("Expected no more than {} agents from the bootstrap server but got {}", MAX_AGENTS_PER_QUERY, list.len());
                             continue;
                         }
+                        list
+                    }
+                };
 
-                        if !internal_sender.ghost_actor_is_active() {
-                            break;
-                        }
-                        let mut peer_data = Vec::with_capacity(list.len());
-                        for item in list {
-                            match internal_sender.is_agent_local(item.agent.clone()).await {
-                                Err(err) => tracing::error!(?err),
-                                Ok(is_local) => {
-                                    if !is_local {
-                                        // we got a result - let's add it to our store for the future
-                                        peer_data.push(item);
-                                    }
-                                }
+                if !internal_sender.ghost_actor_is_active() {
+                    break;
+                }
+                let mut peer_data = Vec::with_capacity(list.len());
+                for item in list {
+                    match internal_sender.is_agent_local(item.agent.clone()).await {
+                        Err(err) => tracing::error!(?err),
+                        Ok(is_local) => {
+                            if !is_local {
+                                // we got a result - let's add it to our store for the future
+                                peer_data.push(item);
                             }
                         }
+                    }
+                }
 
-                        if let Err(err) = host_sender
-                            .put_agent_info_signed(PutAgentInfoSignedEvt { peer_data })
-                            .await
-                        {
-                            match err {
-                                KitsuneP2pError::GhostError(GhostError::Disconnected) => {
-                                    tracing::error!(?err, "Bootstrap task cannot communicate with the host, shutting down");
-                                    break;
-                                }
-                                _ => {
-                                    tracing::error!(?err, "error storing bootstrap agent_info");
-                                }
-                            }
+                if let Err(err) = host_sender
+                    .put_agent_info_signed(PutAgentInfoSignedEvt { peer_data })
+                    .await
+                {
+                    match err {
+                        KitsuneP2pError::GhostError(GhostError::Disconnected) => {
+                            tracing::error!(
+                                ?err,
+                                "Bootstrap task cannot communicate with the host, shutting down"
+                            );
+                            break;
+                        }
+                        _ => {
+                            tracing::error!(?err, "error storing bootstrap agent_info");
                         }
                     }
                 }
@@ -193,6 +223,7 @@ mod tests {
     use kitsune_p2p_types::agent_info::AgentInfoSigned;
     use kitsune_p2p_types::bootstrap::RandomQuery;
     use kitsune_p2p_types::dht::Arq;
+    use kitsune_p2p_types::dht_arc::DhtLocation;
     use kitsune_p2p_types::fixt::AgentInfoSignedFixturator;
     use kitsune_p2p_types::KOpHash;
     use parking_lot::RwLock;
@@ -277,6 +308,29 @@ mod tests {
         test_sender.ghost_actor_shutdown_immediate().await.unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn bootstrap_task_falls_back_to_dht_agent_infos_when_bootstrap_fails() {
+        let agents = vec![fixt!(AgentInfoSigned)];
+        let dht_agents = std::iter::repeat_with(|| fixt!(AgentInfoSigned))
+            .take(2)
+            .collect::<Vec<_>>();
+        let (test_sender, mut host_stub, _) = setup(
+            DummySpaceInternalImpl::new(HashSet::new()).with_dht_agents(dht_agents),
+            agents,
+            2,
+            true,
+        )
+        .await;
+
+        // The first query succeeds and the second fails, falling back to the DHT.
+        let evt = host_stub.next_event(Duration::from_secs(5)).await;
+        assert_eq!(1, evt.peer_data.len());
+        let evt = host_stub.next_event(Duration::from_secs(5)).await;
+        assert_eq!(2, evt.peer_data.len());
+
+        test_sender.ghost_actor_shutdown_immediate().await.unwrap();
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn bootstrap_task_query_delay_increases_exponentially() {
         let agents = vec![fixt!(AgentInfoSigned)];
@@ -543,14 +597,21 @@ mod tests {
 
     struct DummySpaceInternalImpl {
         local_agents: HashSet<KAgent>,
+        dht_agents: Vec<AgentInfoSigned>,
     }
 
     impl DummySpaceInternalImpl {
         fn new(local_agents: HashSet<AgentInfoSigned>) -> Self {
             DummySpaceInternalImpl {
                 local_agents: local_agents.into_iter().map(|a| a.agent.clone()).collect(),
+                dht_agents: Vec::new(),
             }
         }
+
+        fn with_dht_agents(mut self, dht_agents: Vec<AgentInfoSigned>) -> Self {
+            self.dht_agents = dht_agents;
+            self
+        }
     }
 
     impl GhostControlHandler for DummySpaceInternalImpl {}
@@ -595,6 +656,15 @@ mod tests {
             Ok(async move { Ok(is_local) }.boxed().into())
         }
 
+        fn handle_query_dht_agent_infos(
+            &mut self,
+            _basis_loc: DhtLocation,
+        ) -> SpaceInternalHandlerResult<Vec<AgentInfoSigned>> {
+            let dht_agents = self.dht_agents.clone();
+
+            Ok(async move { Ok(dht_agents) }.boxed().into())
+        }
+
         fn handle_update_agent_arc(
             &mut self,
             _agent: KAgent,
@@ -657,6 +727,8 @@ mod tests {
         fn handle_incoming_metric_exchange(
             &mut self,
             _space: KSpace,
+            _con: MetaNetCon,
+            _remote_url: String,
             _msgs: VecMXM,
         ) -> SpaceInternalHandlerResult<()> {
             unreachable!()
@@ -1,5 +1,6 @@
 use super::*;
 use crate::wire::MetricExchangeMsg;
+use kitsune_p2p_timestamp::Timestamp;
 use kitsune_p2p_types::config::KitsuneP2pTuningParams;
 use kitsune_p2p_types::dht_arc::DhtArcSet;
 use tokio::time::{Duration, Instant};
@@ -41,6 +42,8 @@ const METRIC_EXCHANGE_FREQ: Duration = Duration::from_millis(1000 * 60);
 struct RemoteRef {
     con: MetaNetCon,
     last_sync: ShouldTrigger,
+    /// When we sent the time request we are waiting for a reply to, if any.
+    pending_time_request: Option<i64>,
 }
 
 pub(crate) struct MetricExchange {
@@ -82,12 +85,17 @@ impl MetricExchange {
                 let timeout = self.tuning_params.implicit_timeout();
                 let con = r.con.clone();
                 let extrap_cov = self.extrap_cov;
+                let sent_at_micros = Timestamp::now().as_micros();
+                r.pending_time_request = Some(sent_at_micros);
                 tokio::task::spawn(async move {
                     let payload = wire::Wire::metric_exchange(
                         space,
-                        vec![MetricExchangeMsg::V1UniBlast {
-                            extrap_cov_f32_le: extrap_cov.to_le_bytes().to_vec().into(),
-                        }],
+                        vec![
+                            MetricExchangeMsg::V1UniBlast {
+                                extrap_cov_f32_le: extrap_cov.to_le_bytes().to_vec().into(),
+                            },
+                            MetricExchangeMsg::V1TimeRequest { sent_at_micros },
+                        ],
                     );
                     let _ = con.notify(&payload, timeout).await;
                 });
@@ -107,6 +115,7 @@ impl MetricExchange {
                 e.insert(RemoteRef {
                     con,
                     last_sync: ShouldTrigger::new(METRIC_EXCHANGE_FREQ),
+                    pending_time_request: None,
                 });
             }
             Occupied(mut e) => {
@@ -122,7 +131,7 @@ impl MetricExchange {
         self.remote_refs.remove(&url);
     }
 
-    pub fn ingest_msgs(&mut self, msgs: Vec<MetricExchangeMsg>) {
+    pub fn ingest_msgs(&mut self, con: MetaNetCon, url: String, msgs: Vec<MetricExchangeMsg>) {
         for msg in msgs {
             match msg {
                 MetricExchangeMsg::V1UniBlast { extrap_cov_f32_le } => {
@@ -134,12 +143,66 @@ impl MetricExchange {
                     let extrap_cov = f32::from_le_bytes(tmp);
                     self.metrics.write().record_extrap_cov_event(extrap_cov);
                 }
+                MetricExchangeMsg::V1TimeRequest { sent_at_micros } => {
+                    let space = self.space.clone();
+                    let timeout = self.tuning_params.implicit_timeout();
+                    let con = con.clone();
+                    let received_at_micros = Timestamp::now().as_micros();
+                    tokio::task::spawn(async move {
+                        let payload = wire::Wire::metric_exchange(
+                            space,
+                            vec![MetricExchangeMsg::V1TimeResponse {
+                                request_sent_at_micros: sent_at_micros,
+                                received_at_micros,
+                            }],
+                        );
+                        let _ = con.notify(&payload, timeout).await;
+                    });
+                }
+                MetricExchangeMsg::V1TimeResponse {
+                    request_sent_at_micros,
+                    received_at_micros,
+                } => {
+                    // Only accept a reply to the request we are waiting for,
+                    // so the remote can't make up a short round trip.
+                    let pending = self
+                        .remote_refs
+                        .get_mut(&url)
+                        .and_then(|r| r.pending_time_request.take());
+                    if pending != Some(request_sent_at_micros) {
+                        continue;
+                    }
+                    if let Some((offset_micros, uncertainty_micros)) = clock_offset(
+                        request_sent_at_micros,
+                        received_at_micros,
+                        Timestamp::now().as_micros(),
+                    ) {
+                        self.metrics.write().record_clock_sample(
+                            url.clone(),
+                            offset_micros,
+                            uncertainty_micros,
+                        );
+                    }
+                }
                 MetricExchangeMsg::UnknownMessage => (),
             }
         }
     }
 }
 
+/// Estimate the offset of a remote clock from ours, and how far off that may be,
+/// from when we sent a request, when the remote received it and when we got the reply.
+///
+/// The remote is assumed to have received the request half way through the round trip,
+/// so the uncertainty is half the round trip time.
+fn clock_offset(sent_at: i64, remote_received_at: i64, replied_at: i64) -> Option<(i64, i64)> {
+    let round_trip = replied_at.checked_sub(sent_at).filter(|rtt| *rtt >= 0)?;
+    let offset = remote_received_at
+        .saturating_sub(sent_at)
+        .saturating_sub(round_trip / 2);
+    Some((offset, round_trip - round_trip / 2))
+}
+
 #[derive(Clone)]
 pub(crate) struct MetricExchangeSync(Arc<parking_lot::RwLock<MetricExchange>>);
 
@@ -199,3 +262,21 @@ impl MetricExchangeSync {
         out
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_offset_assumes_symmetric_latency() {
+        // The remote clock is 1s ahead and each direction takes 10ms.
+        assert_eq!(
+            Some((1_000_000, 10_000)),
+            clock_offset(0, 1_010_000, 20_000)
+        );
+        // The remote clock is behind.
+        assert_eq!(Some((-500, 1)), clock_offset(1_000, 500, 1_001));
+        // Our clock went backwards during the round trip.
+        assert_eq!(None, clock_offset(1_000, 500, 999));
+    }
+}
@@ -1,5 +1,8 @@
 use super::*;
 use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use std::future::Future;
+use std::time::Duration;
 
 pub(crate) async fn handle_rpc_multi(
     input: actor::RpcMulti,
@@ -19,8 +22,11 @@ pub(crate) async fn handle_rpc_multi_as_single(
         basis,
         payload,
         max_timeout,
+        peer_filter,
+        hedge_delay_ms,
         ..
     } = input;
+    let hedge_delay = (hedge_delay_ms > 0).then(|| Duration::from_millis(hedge_delay_ms));
 
     let ro_inner = &ro_inner;
     let space = &space;
@@ -68,6 +74,28 @@ pub(crate) async fn handle_rpc_multi_as_single(
         .boxed()
     };
 
+    let make_req = &make_req;
+    let attempt = |info: AgentInfoSigned| async move {
+        use discover::PeerDiscoverResult;
+
+        match discover::peer_connect(ro_inner.clone(), &info, max_timeout).await {
+            PeerDiscoverResult::OkShortcut => {
+                tracing::warn!("remote peer is local");
+                Err("remote peer is local".into())
+            }
+            PeerDiscoverResult::Err(err) => {
+                tracing::warn!(?err, "peer discovery error");
+                Err(err)
+            }
+            PeerDiscoverResult::OkRemote { con_hnd, .. } => {
+                make_req(con_hnd, info.agent.clone()).await.map_err(|err| {
+                    tracing::warn!(?err, "remote call error");
+                    err
+                })
+            }
+        }
+    };
+
     max_timeout
         .mix("rpc_multi", async move {
             let mut errs = vec![];
@@ -103,38 +131,13 @@ pub(crate) async fn handle_rpc_multi_as_single(
                 }
 
                 if let Some(mut infos) = infos {
+                    if let Some(peer_filter) = &peer_filter {
+                        infos.retain(|info| peer_filter.allows(info));
+                    }
                     rand::seq::SliceRandom::shuffle(infos.as_mut_slice(), &mut rand::thread_rng());
 
-                    for info in infos {
-                        use discover::PeerDiscoverResult;
-
-                        let con_hnd = match discover::peer_connect(
-                            ro_inner.clone(),
-                            &info,
-                            max_timeout,
-                        )
-                        .await
-                        {
-                            PeerDiscoverResult::OkShortcut => {
-                                tracing::warn!("remote peer is local");
-                                continue;
-                            }
-                            PeerDiscoverResult::Err(err) => {
-                                tracing::warn!(?err, "peer discovery error");
-                                errs.push(err);
-                                continue;
-                            }
-                            PeerDiscoverResult::OkRemote { con_hnd, .. } => con_hnd,
-                        };
-
-                        match make_req(con_hnd, info.agent.clone()).await {
-                            Ok(res) => return Ok(res),
-                            Err(err) => {
-                                tracing::warn!(?err, "remote call error");
-                                errs.push(err);
-                                continue;
-                            }
-                        }
+                    if let Some(res) = hedged(infos, hedge_delay, &mut errs, attempt).await {
+                        return Ok(res);
                     }
                 }
             }
@@ -171,3 +174,129 @@ pub(crate) async fn handle_rpc_multi_as_single(
         .await
         .map_err(|err| err.into())
 }
+
+/// The most requests [`hedged`] has in flight at once.
+const MAX_HEDGED_REQUESTS: usize = 2;
+
+/// Make a request of each candidate in turn until one of them succeeds.
+///
+/// If a request hasn't completed within `hedge_delay`, the next candidate is asked
+/// too, without waiting for the first, and the first response wins. The requests
+/// still in flight are then dropped, which cancels them. This cuts the latency of
+/// requests to slow authorities, while only authorities which are slow to respond
+/// are asked twice. When a request fails the next candidate is asked straight away.
+///
+/// Without a `hedge_delay` only one request is in flight at a time.
+/// The errors of failed requests are pushed to `errs`.
+pub(crate) async fn hedged<C, T, E, F, Fut>(
+    candidates: impl IntoIterator<Item = C>,
+    hedge_delay: Option<Duration>,
+    errs: &mut Vec<E>,
+    attempt: F,
+) -> Option<T>
+where
+    F: Fn(C) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut candidates = candidates.into_iter().peekable();
+    let mut in_flight = FuturesUnordered::new();
+    loop {
+        if in_flight.is_empty() {
+            in_flight.push(attempt(candidates.next()?));
+        }
+        let can_hedge = in_flight.len() < MAX_HEDGED_REQUESTS && candidates.peek().is_some();
+        let next = match hedge_delay {
+            Some(delay) if can_hedge => match tokio::time::timeout(delay, in_flight.next()).await {
+                Ok(next) => next,
+                Err(_) => {
+                    if let Some(candidate) = candidates.next() {
+                        in_flight.push(attempt(candidate));
+                    }
+                    continue;
+                }
+            },
+            _ => in_flight.next().await,
+        };
+        match next {
+            Some(Ok(res)) => return Some(res),
+            Some(Err(err)) => {
+                errs.push(err);
+                if let Some(candidate) = candidates.next() {
+                    in_flight.push(attempt(candidate));
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Candidates which respond after the given number of millis, or fail if negative.
+    async fn run(
+        candidates: Vec<i64>,
+        hedge_delay_ms: Option<u64>,
+    ) -> (Option<usize>, Vec<usize>, Vec<usize>) {
+        let asked = Mutex::new(Vec::new());
+        let mut errs = Vec::new();
+        let res = hedged(
+            candidates.into_iter().enumerate(),
+            hedge_delay_ms.map(Duration::from_millis),
+            &mut errs,
+            |(i, ms)| {
+                asked.lock().unwrap().push(i);
+                async move {
+                    tokio::time::sleep(Duration::from_millis(ms.unsigned_abs())).await;
+                    if ms < 0 {
+                        Err(i)
+                    } else {
+                        Ok(i)
+                    }
+                }
+            },
+        )
+        .await;
+        (res, asked.into_inner().unwrap(), errs)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn fast_authority_is_not_hedged() {
+        assert_eq!(
+            run(vec![10, 10, 10], Some(100)).await,
+            (Some(0), vec![0], vec![])
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_authority_is_hedged_and_loses() {
+        let start = tokio::time::Instant::now();
+        assert_eq!(
+            run(vec![1000, 10, 10], Some(100)).await,
+            (Some(1), vec![0, 1], vec![])
+        );
+        assert_eq!(start.elapsed(), Duration::from_millis(110));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn at_most_two_requests_are_in_flight() {
+        assert_eq!(
+            run(vec![1000, 1000, 10], Some(100)).await,
+            (Some(0), vec![0, 1], vec![])
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn failed_requests_are_replaced() {
+        assert_eq!(
+            run(vec![-10, 1000, 10], Some(100)).await,
+            (Some(2), vec![0, 1, 2], vec![0])
+        );
+        assert_eq!(
+            run(vec![-10, -10], None).await,
+            (None, vec![0, 1], vec![0, 1])
+        );
+    }
+}
@@ -265,6 +265,8 @@ async fn test_rpc_multi_logic_mocked() {
             max_remote_agent_count: 3,
             max_timeout: KitsuneTimeout::from_millis(30000),
             remote_request_grace_ms: 3000,
+            peer_filter: None,
+            hedge_delay_ms: 0,
         },
         ro_inner,
         HashSet::new(),
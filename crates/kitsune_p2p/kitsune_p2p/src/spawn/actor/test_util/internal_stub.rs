@@ -1,6 +1,7 @@
 use crate::actor::BroadcastData;
+use crate::metrics::ProtocolViolation;
 use crate::spawn::actor::{
-    EvtRcv, InternalHandlerResult, KSpace, MaybeDelegate, OpHashList, VecMXM,
+    EvtRcv, InternalHandlerResult, KAgent, KSpace, MaybeDelegate, OpHashList, VecMXM,
 };
 use crate::spawn::meta_net::MetaNetCon;
 use crate::spawn::{Internal, InternalHandler};
@@ -54,6 +55,7 @@ pub struct InternalStub {
             )>,
         >,
     >,
+    pub incoming_protocol_violation_calls: Arc<RwLock<Vec<(KSpace, KAgent, ProtocolViolation)>>>,
     pub connections: Arc<RwLock<HashMap<String, MetaNetCon>>>,
     pub respond_with_error_count: Arc<AtomicUsize>,
     pub respond_with_error: Arc<AtomicBool>,
@@ -66,6 +68,7 @@ impl InternalStub {
             incoming_publish_calls: Arc::new(RwLock::new(vec![])),
             incoming_delegate_broadcast_calls: Arc::new(RwLock::new(vec![])),
             incoming_gossip_calls: Arc::new(RwLock::new(vec![])),
+            incoming_protocol_violation_calls: Arc::new(RwLock::new(vec![])),
             connections: Arc::new(parking_lot::RwLock::new(HashMap::new())),
             respond_with_error_count: Arc::new(AtomicUsize::new(0)),
             respond_with_error: Arc::new(AtomicBool::new(false)),
@@ -169,6 +172,8 @@ impl InternalHandler for InternalStub {
     fn handle_incoming_metric_exchange(
         &mut self,
         _space: crate::spawn::actor::KSpace,
+        _con: MetaNetCon,
+        _remote_url: String,
         _msgs: VecMXM,
     ) -> InternalHandlerResult<()> {
         todo!()
@@ -201,6 +206,19 @@ impl InternalHandler for InternalStub {
     ) -> InternalHandlerResult<Vec<AgentInfoSigned>> {
         todo!()
     }
+
+    fn handle_incoming_protocol_violation(
+        &mut self,
+        space: KSpace,
+        agent: KAgent,
+        violation: ProtocolViolation,
+    ) -> InternalHandlerResult<()> {
+        self.incoming_protocol_violation_calls
+            .write()
+            .push((space, agent, violation));
+
+        Ok(async move { Ok(()) }.boxed().into())
+    }
 }
 
 ghost_actor::ghost_chan! {
@@ -1,6 +1,7 @@
 use crate::event::{
     FetchOpDataEvtQuery, KitsuneP2pEvent, KitsuneP2pEventHandlerResult, PutAgentInfoSignedEvt,
 };
+use crate::metrics::ProtocolViolation;
 use crate::spawn::actor::{KAgent, KSpace};
 use crate::test_util::data::mk_agent_info;
 use crate::types::event::Payload;
@@ -8,6 +9,7 @@ use crate::{KOp, KitsuneP2pError};
 use futures::channel::mpsc::{channel, Receiver};
 use futures::{FutureExt, SinkExt, StreamExt};
 use ghost_actor::GhostRespond;
+use kitsune_p2p_types::agent_info::AgentInfoSigned;
 use kitsune_p2p_types::bin_types::KitsuneOpData;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -23,6 +25,10 @@ pub struct LegacyHostStub {
     pub notify_calls: Arc<parking_lot::RwLock<Vec<(KSpace, KAgent, Payload)>>>,
     pub receive_ops_calls:
         Arc<parking_lot::RwLock<Vec<(KSpace, Vec<KOp>, Option<kitsune_p2p_fetch::FetchContext>)>>>,
+    /// The agents returned when querying all the agents of a space.
+    pub space_agents: Arc<parking_lot::RwLock<Vec<AgentInfoSigned>>>,
+    /// If set, the next received ops are rejected as this violation.
+    pub reject_ops_with: Arc<parking_lot::Mutex<Option<ProtocolViolation>>>,
 
     put_events: Receiver<PutAgentInfoSignedEvt>,
     abort_handle: AbortHandle,
@@ -35,6 +41,8 @@ impl LegacyHostStub {
         let put_agent_info_signed_calls = Arc::new(parking_lot::RwLock::new(Vec::new()));
         let notify_calls = Arc::new(parking_lot::RwLock::new(Vec::new()));
         let receive_ops_calls = Arc::new(parking_lot::RwLock::new(Vec::new()));
+        let space_agents = Arc::new(parking_lot::RwLock::new(Vec::new()));
+        let reject_ops_with = Arc::new(parking_lot::Mutex::new(None));
 
         let respond_with_error = Arc::new(AtomicBool::new(false));
         let respond_with_error_count = Arc::new(AtomicUsize::new(0));
@@ -46,6 +54,8 @@ impl LegacyHostStub {
             let task_put_agent_info_signed_calls = put_agent_info_signed_calls.clone();
             let task_notify_calls = notify_calls.clone();
             let task_receive_ops_calls = receive_ops_calls.clone();
+            let task_space_agents = space_agents.clone();
+            let task_reject_ops_with = reject_ops_with.clone();
 
             async move {
                 while let Some(evt) = host_receiver.next().await {
@@ -94,7 +104,16 @@ impl LegacyHostStub {
                                 continue;
                             }
 
-                            let len = input.limit.unwrap();
+                            let len = match input.limit {
+                                Some(len) => len,
+                                None => {
+                                    let agents = task_space_agents.read().clone();
+                                    respond
+                                        .unwrap()
+                                        .respond(Ok(async move { Ok(agents) }.boxed().into()));
+                                    continue;
+                                }
+                            };
 
                             respond.unwrap().respond(Ok(async move {
                                 let mut agents = vec![];
@@ -178,6 +197,15 @@ impl LegacyHostStub {
                                 continue;
                             }
 
+                            if let Some(violation) = task_reject_ops_with.lock().take() {
+                                respond.unwrap().respond(Ok(async move {
+                                    Err(KitsuneP2pError::ProtocolViolation(violation))
+                                }
+                                .boxed()
+                                .into()));
+                                continue;
+                            }
+
                             task_receive_ops_calls.write().push((space, ops, context));
 
                             respond
@@ -196,6 +224,8 @@ impl LegacyHostStub {
             put_agent_info_signed_calls,
             notify_calls,
             receive_ops_calls,
+            space_agents,
+            reject_ops_with,
             put_events: receiver,
             abort_handle: handle.abort_handle(),
         }
@@ -14,6 +14,7 @@ use ghost_actor::{GhostControlHandler, GhostHandler};
 use kitsune_p2p_fetch::FetchContext;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
 use kitsune_p2p_types::dht::Arq;
+use kitsune_p2p_types::dht_arc::DhtLocation;
 use kitsune_p2p_types::{KAgent, KBasis, KOpHash, KSpace};
 use std::collections::HashSet;
 
@@ -83,6 +84,13 @@ impl SpaceInternalHandler for SpaceInternalStub {
         unreachable!()
     }
 
+    fn handle_query_dht_agent_infos(
+        &mut self,
+        _basis_loc: DhtLocation,
+    ) -> SpaceInternalHandlerResult<Vec<AgentInfoSigned>> {
+        unreachable!()
+    }
+
     fn handle_update_agent_arc(
         &mut self,
         _agent: KAgent,
@@ -141,6 +149,8 @@ impl SpaceInternalHandler for SpaceInternalStub {
     fn handle_incoming_metric_exchange(
         &mut self,
         _space: KSpace,
+        _con: MetaNetCon,
+        _remote_url: String,
         _msgs: VecMXM,
     ) -> SpaceInternalHandlerResult<()> {
         unreachable!()
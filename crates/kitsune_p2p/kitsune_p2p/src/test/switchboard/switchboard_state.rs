@@ -1,6 +1,8 @@
 //! An in-memory network for sharded kitsune tests.
 
-use crate::gossip::sharded_gossip::{BandwidthThrottle, GossipType, ShardedGossip};
+use crate::gossip::sharded_gossip::{
+    BandwidthThrottle, GossipType, ShardedGossip, SpaceBandwidthBudget,
+};
 use crate::test_util::spawn_handler;
 use crate::types::gossip::*;
 use crate::types::wire;
@@ -133,6 +135,7 @@ impl Switchboard {
         let host_api = HostApiLegacy::new(host_api, evt_sender);
 
         let bandwidth = Arc::new(BandwidthThrottle::new(1000.0, 1000.0, 10.0));
+        let space_budget = Arc::new(SpaceBandwidthBudget::new(&tuning_params));
 
         let gossip = ShardedGossip::new(
             tuning_params,
@@ -141,6 +144,7 @@ impl Switchboard {
             host_api.clone(),
             self.gossip_type,
             bandwidth,
+            space_budget,
             Default::default(),
             kitsune_p2p_fetch::FetchPool::new_bitwise_or(),
         );
@@ -1,5 +1,5 @@
 use crate::{KitsuneAgent, KitsuneBinType, KitsuneSignature, KitsuneSpace};
-use kitsune_p2p_types::{agent_info::AgentInfoSigned, dht::arq::ArqSize};
+use kitsune_p2p_types::{agent_info::AgentInfoSigned, dht::arq::ArqSize, tx2::tx2_utils::TxUrl};
 use std::sync::Arc;
 
 pub async fn mk_agent_info(u: u8) -> AgentInfoSigned {
@@ -15,3 +15,17 @@ pub async fn mk_agent_info(u: u8) -> AgentInfoSigned {
     .await
     .unwrap()
 }
+
+pub async fn mk_agent_info_with_url(u: u8, url: &str) -> AgentInfoSigned {
+    AgentInfoSigned::sign(
+        Arc::new(KitsuneSpace::new(vec![0x11; 32])),
+        Arc::new(KitsuneAgent::new(vec![u; 32])),
+        ArqSize::empty(),
+        vec![TxUrl::from_str_panicking(url)],
+        0,
+        0,
+        |_| async move { Ok(Arc::new(KitsuneSignature(vec![0; 64]))) },
+    )
+    .await
+    .unwrap()
+}
@@ -24,6 +24,10 @@ pub enum KitsuneP2pError {
     #[error("Decoding Error: {0}")]
     DecodingError(Box<str>),
 
+    /// The host rejected data sent by a remote peer as a breach of the protocol.
+    #[error("Protocol violation: {0:?}")]
+    ProtocolViolation(crate::metrics::ProtocolViolation),
+
     /// std::io::Error
     #[error(transparent)]
     StdIoError(#[from] std::io::Error),
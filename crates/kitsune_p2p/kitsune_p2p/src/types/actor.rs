@@ -6,6 +6,7 @@ use std::sync::Arc;
 use url2::Url2;
 
 use crate::gossip::sharded_gossip::KitsuneDiagnostics;
+use crate::metrics::ProtocolViolation;
 
 /// Make a request to multiple destination agents - awaiting/aggregating the responses.
 /// The remote sides will see these messages as "RequestEvt" events.
@@ -31,6 +32,18 @@ pub struct RpcMulti {
     /// but made any additional outgoing remote requests,
     /// we'll wait at least this long for additional responses.
     pub remote_request_grace_ms: u64,
+
+    /// If set, remote requests are only made to the peers this filter allows.
+    pub peer_filter: Option<PeerFilter>,
+
+    /// Hedge remote requests: if a peer hasn't responded within this many
+    /// milliseconds, make the same request of another peer as well, and use
+    /// whichever response arrives first. Zero disables hedging, so peers are
+    /// only asked one at a time.
+    ///
+    /// [`RpcMulti::new`] sets this from the `default_rpc_multi_hedge_delay_ms`
+    /// tuning param.
+    pub hedge_delay_ms: u64,
 }
 
 impl RpcMulti {
@@ -49,10 +62,39 @@ impl RpcMulti {
             max_remote_agent_count: tuning_params.default_rpc_multi_remote_agent_count,
             max_timeout: tuning_params.implicit_timeout(),
             remote_request_grace_ms: tuning_params.default_rpc_multi_remote_request_grace_ms,
+            peer_filter: None,
+            hedge_delay_ms: tuning_params.default_rpc_multi_hedge_delay_ms,
         }
     }
 }
 
+/// Decides which peers a request may be sent to, given their agent info.
+#[derive(Clone)]
+pub struct PeerFilter(
+    Arc<dyn Fn(&kitsune_p2p_types::agent_info::AgentInfoSigned) -> bool + Send + Sync>,
+);
+
+impl PeerFilter {
+    /// Construct a filter from a predicate which returns true for the
+    /// peers which are allowed.
+    pub fn new(
+        f: impl Fn(&kitsune_p2p_types::agent_info::AgentInfoSigned) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(f))
+    }
+
+    /// Whether a request may be sent to this peer.
+    pub fn allows(&self, info: &kitsune_p2p_types::agent_info::AgentInfoSigned) -> bool {
+        (self.0)(info)
+    }
+}
+
+impl std::fmt::Debug for PeerFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PeerFilter").finish_non_exhaustive()
+    }
+}
+
 /// A response type helps indicate what agent gave what response.
 #[derive(Clone, Debug)]
 pub struct RpcMultiResponse {
@@ -110,6 +152,11 @@ ghost_actor::ghost_chan! {
         /// Withdraw this space/agent pair from this network.
         fn leave(space: KSpace, agent: KAgent) -> ();
 
+        /// Stop or resume gossip for a local agent without leaving the space.
+        /// While gossip is off the agent can still make and answer requests.
+        /// The setting is kept if the agent leaves and joins the space again.
+        fn set_local_agent_gossip(space: KSpace, agent: KAgent, enabled: bool) -> ();
+
         /// Make a request of a single remote agent, expecting a response.
         /// The remote side will receive a "Call" event.
         fn rpc_single(space: KSpace, to_agent: KAgent, payload: Payload, timeout_ms: OptU64) -> Vec<u8>;
@@ -126,11 +173,14 @@ ghost_actor::ghost_chan! {
         /// An Ok(()) result only means that we were able to establish at
         /// least one connection with a node in the target neighborhood.
         /// The remote sides will see these messages as "Notify" events.
+        /// If a peer filter is given, the data is only sent directly to the
+        /// peers it allows, though those peers may still delegate it to others.
         fn broadcast(
             space: KSpace,
             basis: KBasis,
             timeout: KitsuneTimeout,
             data: BroadcastData,
+            peer_filter: Option<PeerFilter>,
         ) -> ();
 
         /// Broadcast data to a specific set of agents without
@@ -153,6 +203,19 @@ ghost_actor::ghost_chan! {
         /// New data has been integrated and is ready for gossiping.
         fn new_integrated_data(space: KSpace) -> ();
 
+        /// Report a protocol violation by a remote agent, such as data with a bad signature.
+        /// This lowers the agent's score, and agents with a low enough score are
+        /// temporarily banned from gossip and from being fetched from.
+        fn report_protocol_violation(space: KSpace, agent: KAgent, violation: ProtocolViolation) -> ();
+
+        /// Fetch ops which aren't held locally from the agents covering their basis.
+        /// The ops are queued in the fetch pool, so this returns before they arrive.
+        fn fetch_from_authorities(
+            space: KSpace,
+            basis: KBasis,
+            op_hash_list: Vec<kitsune_p2p_fetch::OpHashSized>,
+        ) -> ();
+
         /// Check if an agent is an authority for a hash.
         fn authority_for_hash(
             space: KSpace,
@@ -45,6 +45,23 @@ pub enum MetricExchangeMsg {
         extrap_cov_f32_le: WireData,
     },
 
+    /// Ask the remote node for its time, so we can estimate
+    /// the offset between its clock and ours.
+    V1TimeRequest {
+        /// Our time when sending this request,
+        /// in microseconds since the unix epoch.
+        sent_at_micros: i64,
+    },
+
+    /// Reply to a [`MetricExchangeMsg::V1TimeRequest`].
+    V1TimeResponse {
+        /// The `sent_at_micros` of the request being replied to.
+        request_sent_at_micros: i64,
+        /// Our time when receiving the request,
+        /// in microseconds since the unix epoch.
+        received_at_micros: i64,
+    },
+
     /// Future proof by having an unknown message catch-all variant
     /// that we can ignore for any future variants that are added
     #[serde(other)]
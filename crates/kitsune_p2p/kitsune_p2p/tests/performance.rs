@@ -106,6 +106,7 @@ async fn minimise_p2p_agent_store_host_calls() {
                             op_hash_list: vec![test_data.into()],
                             context: FetchContext::default(),
                         },
+                        None,
                     )
                     .await
                     .unwrap();
@@ -125,6 +126,7 @@ async fn minimise_p2p_agent_store_host_calls() {
                             op_hash_list: vec![test_data.into()],
                             context: FetchContext::default(),
                         },
+                        None,
                     )
                     .await
                     .unwrap();
@@ -148,6 +148,7 @@ async fn two_nodes_publish_and_fetch() {
                 op_hash_list: vec![test_data.into()],
                 context: FetchContext::default(),
             },
+            None,
         )
         .await
         .unwrap();
@@ -260,6 +261,7 @@ async fn two_nodes_publish_and_fetch_large_number_of_ops() {
                     .collect(),
                 context: FetchContext::default(),
             },
+            None,
         )
         .await
         .unwrap();
@@ -813,6 +815,7 @@ async fn publish_only_fetches_ops_once() {
                 op_hash_list: vec![test_data.clone().into()],
                 context: FetchContext::default(),
             },
+            None,
         )
         .await
         .unwrap();
@@ -858,6 +861,7 @@ async fn publish_only_fetches_ops_once() {
                 op_hash_list: vec![test_data.into()],
                 context: FetchContext::default(),
             },
+            None,
         )
         .await
         .unwrap();
@@ -1009,6 +1013,7 @@ async fn delegate_publish() {
                 op_hash_list: vec![test_data.clone().into()],
                 context: FetchContext::default(),
             },
+            None,
         )
         .await
         .unwrap();
@@ -102,9 +102,16 @@ impl Timestamp {
     ///
     /// This is behind a feature because we need Timestamp to be WASM compatible, and
     /// chrono doesn't have a now() implementation for WASM.
+    ///
+    /// With the `mock_clock` feature, any offset set with
+    /// [`mock_clock::advance`](crate::mock_clock::advance) is added.
     #[cfg(feature = "now")]
     pub fn now() -> Timestamp {
-        Timestamp::from(chrono::offset::Utc::now())
+        #[cfg(feature = "mock_clock")]
+        let offset_micros = crate::mock_clock::offset_micros();
+        #[cfg(not(feature = "mock_clock"))]
+        let offset_micros = 0;
+        Timestamp(Timestamp::from(chrono::offset::Utc::now()).0 + offset_micros)
     }
     /// Compute signed difference between two Timestamp, returning `None` if overflow occurred, or
     /// Some(chrono::Duration).  Produces Duration for differences of up to +/- i64::MIN/MAX
@@ -24,6 +24,9 @@ mod chrono_ext;
 #[cfg(feature = "fuzzing")]
 pub mod noise;
 
+#[cfg(feature = "mock_clock")]
+pub mod mock_clock;
+
 /// One million
 pub const MM: i64 = 1_000_000;
 
@@ -0,0 +1,45 @@
+//! A process wide offset applied to [`Timestamp::now`](crate::Timestamp::now).
+//!
+//! Tests which depend on time windows can move the clock forward instantly
+//! instead of sleeping. The offset is shared by everything in the process,
+//! so tests which use it should each run in a process of their own, as they
+//! do under `cargo nextest`. This must never be enabled outside of tests.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
+
+static OFFSET_MICROS: AtomicI64 = AtomicI64::new(0);
+
+/// Move the clock forward by `duration`.
+pub fn advance(duration: Duration) {
+    OFFSET_MICROS.fetch_add(duration.as_micros() as i64, Ordering::SeqCst);
+}
+
+/// How far the clock has been moved forward from the system time.
+pub fn offset() -> Duration {
+    Duration::from_micros(offset_micros() as u64)
+}
+
+/// Move the clock back to the system time.
+pub fn reset() {
+    OFFSET_MICROS.store(0, Ordering::SeqCst);
+}
+
+pub(crate) fn offset_micros() -> i64 {
+    OFFSET_MICROS.load(Ordering::SeqCst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Timestamp;
+
+    #[test]
+    fn advance_moves_now_forward() {
+        let hour = Duration::from_secs(60 * 60);
+        let before = Timestamp::now();
+        advance(hour);
+        assert!(offset() >= hour);
+        assert!(Timestamp::now() >= (before + hour).unwrap());
+    }
+}
@@ -28,10 +28,18 @@ struct Test {
     src_ep: Tx2EpHnd<TestData>,
 }
 
+/// The tx2 backend the endpoints are bound with.
+#[derive(Clone, Copy)]
+enum Backend {
+    Mem,
+    #[cfg(unix)]
+    Local,
+}
+
 impl Test {
-    pub async fn new() -> Self {
-        let (dst_url, dst_ep) = mk_dst().await;
-        let src_ep = mk_src().await;
+    pub async fn new(backend: Backend) -> Self {
+        let (dst_url, dst_ep) = mk_dst(backend).await;
+        let src_ep = mk_src(backend).await;
 
         Self {
             dst_ep,
@@ -66,10 +74,14 @@ impl Test {
     }
 }
 
-async fn mk_core() -> (TxUrl, Tx2Ep<TestData>, Tx2EpHnd<TestData>) {
+async fn mk_core(backend: Backend) -> (TxUrl, Tx2Ep<TestData>, Tx2EpHnd<TestData>) {
     let t = KitsuneTimeout::from_millis(5000);
 
-    let f = tx2_mem_adapter(MemConfig::default()).await.unwrap();
+    let f = match backend {
+        Backend::Mem => tx2_mem_adapter(MemConfig::default()).await.unwrap(),
+        #[cfg(unix)]
+        Backend::Local => tx2_local_adapter(LocalConfig::default()).await.unwrap(),
+    };
     let f = tx2_pool_promote(f, Default::default());
     let f = tx2_api(f, Default::default());
 
@@ -80,8 +92,8 @@ async fn mk_core() -> (TxUrl, Tx2Ep<TestData>, Tx2EpHnd<TestData>) {
     (addr, ep, ep_hnd)
 }
 
-async fn mk_dst() -> (TxUrl, Tx2EpHnd<TestData>) {
-    let (url, mut ep, ep_hnd) = mk_core().await;
+async fn mk_dst(backend: Backend) -> (TxUrl, Tx2EpHnd<TestData>) {
+    let (url, mut ep, ep_hnd) = mk_core(backend).await;
 
     tokio::task::spawn(async move {
         while let Some(evt) = ep.next().await {
@@ -105,8 +117,8 @@ async fn mk_dst() -> (TxUrl, Tx2EpHnd<TestData>) {
     (url, ep_hnd)
 }
 
-async fn mk_src() -> Tx2EpHnd<TestData> {
-    let (_url, mut ep, ep_hnd) = mk_core().await;
+async fn mk_src(backend: Backend) -> Tx2EpHnd<TestData> {
+    let (_url, mut ep, ep_hnd) = mk_core(backend).await;
 
     tokio::task::spawn(async move { while let Some(_evt) = ep.next().await {} });
 
@@ -135,9 +147,16 @@ fn criterion_benchmark(c: &mut Criterion) {
         .build()
         .unwrap();
 
-    let t = rt.block_on(async { Share::new(Some(Test::new().await)) });
-
+    let t = rt.block_on(async { Share::new(Some(Test::new(Backend::Mem).await)) });
     c.bench_function("api-thru-mem", |b| b.iter(|| api_thru(&rt, &t)));
+
+    // The latency of a request between conductors on the same host over
+    // the unix domain socket transport.
+    #[cfg(unix)]
+    {
+        let t = rt.block_on(async { Share::new(Some(Test::new(Backend::Local).await)) });
+        c.bench_function("api-thru-local", |b| b.iter(|| api_thru(&rt, &t)));
+    }
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -3,6 +3,11 @@
 mod framed;
 pub use framed::*;
 
+#[cfg(unix)]
+mod local;
+#[cfg(unix)]
+pub use local::*;
+
 mod mem;
 pub use mem::*;
 
@@ -0,0 +1,926 @@
+#![allow(clippy::new_ret_no_self)]
+//! Unix domain socket backend for kitsune tx2.
+//!
+//! Conductors running on the same host can use this instead of a network
+//! transport, avoiding the latency and cpu cost of loopback networking.
+//! Every endpoint listens on a socket in a directory private to the current
+//! user, so any local endpoints of that user configured with the same
+//! directory can reach each other.
+//!
+//! A connection is established over tls on its first stream, so each side
+//! proves it holds the certificate it is known by, and the certificate
+//! digest in an endpoint's url is checked when connecting to it. Each tx2
+//! channel is then carried on its own unix stream, which is only accepted
+//! with a secret exchanged over the tls stream of its connection.
+
+use crate::config::*;
+use crate::tls::*;
+use crate::tx2::tx2_adapter::*;
+use crate::tx2::tx2_utils::*;
+use crate::tx2::*;
+use crate::*;
+use base64::Engine;
+use futures::{
+    future::{BoxFuture, FutureExt},
+    stream::{BoxStream, StreamExt},
+};
+use lair_keystore_api::dependencies::sodoken;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+use tokio_rustls::{rustls, TlsAcceptor, TlsConnector, TlsStream};
+use tokio_util::compat::TokioAsyncReadCompatExt;
+
+/// The url scheme of local endpoint addresses.
+pub const LOCAL_SCHEME: &str = "kitsune-local";
+
+/// Configuration for LocalBackendAdapt
+#[non_exhaustive]
+#[derive(Default)]
+pub struct LocalConfig {
+    /// Tls config
+    /// Default: None = ephemeral.
+    pub tls: Option<TlsConfig>,
+
+    /// Tuning Params
+    /// Default: None = default.
+    pub tuning_params: Option<KitsuneP2pTuningParams>,
+
+    /// The directory endpoint sockets are created in.
+    /// It must only be accessible by the current user.
+    /// Default: None = [`default_local_socket_dir`].
+    pub socket_dir: Option<PathBuf>,
+}
+
+impl LocalConfig {
+    /// into inner contents with default application
+    pub async fn split(self) -> KitsuneResult<(TlsConfig, KitsuneP2pTuningParams, PathBuf)> {
+        let LocalConfig {
+            tls,
+            tuning_params,
+            socket_dir,
+        } = self;
+
+        let tls = match tls {
+            None => TlsConfig::new_ephemeral().await?,
+            Some(tls) => tls,
+        };
+
+        let tuning_params = tuning_params.unwrap_or_default();
+
+        let socket_dir = socket_dir.unwrap_or_else(default_local_socket_dir);
+
+        Ok((tls, tuning_params, socket_dir))
+    }
+}
+
+/// The socket directory used when none is configured, which is private to
+/// the current user. This is `kitsune-local` in `$XDG_RUNTIME_DIR` if it is
+/// set, or `kitsune-local-<uid>` in the system temp directory otherwise.
+pub fn default_local_socket_dir() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir).join("kitsune-local"),
+        _ => std::env::temp_dir().join(format!("kitsune-local-{}", current_uid())),
+    }
+}
+
+/// Construct a new unix domain socket endpoint adapter for kitsune tx2.
+pub async fn tx2_local_adapter(config: LocalConfig) -> KitsuneResult<AdapterFactory> {
+    LocalBackendAdapt::new(config).await
+}
+
+// -- private -- //
+
+/// Tls ALPN identifier for kitsune local handshaking
+const ALPN_KITSUNE_LOCAL_0: &[u8] = b"kitsune-local/0";
+
+static NEXT_LOCAL_ID: atomic::AtomicU64 = atomic::AtomicU64::new(1);
+
+/// The first stream of a connection, which is upgraded to tls.
+/// The connection is open for as long as this stream is.
+const KIND_CON: u8 = 0;
+
+/// A new channel on an established connection.
+const KIND_CHAN: u8 = 1;
+
+/// Sent back to the opener of a channel once it is accepted.
+const CHAN_ACCEPTED: u8 = 1;
+
+/// A connection is identified by the socket path of the endpoint
+/// which established it, and an id chosen by that endpoint.
+type ConKey = (PathBuf, u64);
+
+/// A secret an endpoint issues for each of its connections,
+/// which the peer presents to open channels to it.
+type ChanToken = [u8; 32];
+
+fn local_err(e: std::io::Error) -> KitsuneError {
+    KitsuneError::other(e)
+}
+
+fn current_uid() -> u32 {
+    nix::unistd::getuid().as_raw()
+}
+
+/// Create the socket directory, only accessible by the current user,
+/// or check an existing one is. Anyone else who could write to it
+/// could stand in for the endpoints listening there.
+fn prepare_socket_dir(dir: &Path) -> KitsuneResult<()> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+
+    if let Some(parent) = dir.parent() {
+        std::fs::create_dir_all(parent).map_err(local_err)?;
+    }
+    match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => (),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => (),
+        Err(e) => return Err(local_err(e)),
+    }
+
+    let meta = std::fs::symlink_metadata(dir).map_err(local_err)?;
+    if !meta.is_dir() {
+        return Err(format!("socket dir {} is not a directory", dir.display()).into());
+    }
+    if meta.uid() != current_uid() {
+        return Err(format!("socket dir {} is owned by another user", dir.display()).into());
+    }
+    if meta.mode() & 0o077 != 0 {
+        return Err(format!(
+            "socket dir {} must only be accessible by its owner",
+            dir.display()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Only endpoints of the same user can reach each other.
+fn check_peer_user(stream: &UnixStream) -> KitsuneResult<()> {
+    let cred = stream.peer_cred().map_err(local_err)?;
+    if cred.uid() != current_uid() {
+        return Err("local peer belongs to another user".into());
+    }
+    Ok(())
+}
+
+async fn random_token() -> KitsuneResult<ChanToken> {
+    let token = sodoken::BufWriteSized::new_no_lock();
+    sodoken::random::bytes_buf(token.clone())
+        .await
+        .map_err(KitsuneError::other)?;
+    let token = *token.read_lock_sized();
+    Ok(token)
+}
+
+/// The digest of the certificate the peer proved it holds during the
+/// tls handshake.
+async fn peer_cert_digest(tls: &TlsStream<UnixStream>) -> KitsuneResult<Tx2Cert> {
+    let (_, session) = tls.get_ref();
+    let cert = match session.peer_certificates().and_then(|chain| chain.first()) {
+        None => return Err("invalid peer certificate (none)".into()),
+        Some(cert) => cert.0.clone(),
+    };
+    let digest = sodoken::BufWriteSized::<32>::new_no_lock();
+    sodoken::hash::blake2b::hash(digest.clone(), cert)
+        .await
+        .map_err(KitsuneError::other)?;
+    let digest = digest.read_lock_sized().to_vec();
+    Ok(digest.into())
+}
+
+fn path_to_url(path: &Path, cert: &Tx2Cert) -> KitsuneResult<TxUrl> {
+    let path = path
+        .to_str()
+        .ok_or_else(|| KitsuneError::from("socket path is not valid utf8"))?;
+    let mut url = url2::Url2::try_parse(format!("{}://localhost", LOCAL_SCHEME))
+        .map_err(KitsuneError::other)?;
+    url.set_path(path);
+    url.query_pairs_mut().append_pair("cert", cert.as_str());
+    Ok(url.into())
+}
+
+/// The socket path of a local endpoint url, and the digest of the
+/// certificate the endpoint must hold.
+fn url_to_path(url: &TxUrl) -> KitsuneResult<(PathBuf, Tx2Cert)> {
+    if url.scheme() != LOCAL_SCHEME {
+        return Err(format!("invalid url {} : scheme must be {}", url, LOCAL_SCHEME).into());
+    }
+    let path = url.to_file_path().map_err(|_| {
+        KitsuneError::from(format!("invalid url {} : not a local socket path", url))
+    })?;
+    let cert = url
+        .query_pairs()
+        .find(|(name, _)| name == "cert")
+        .and_then(|(_, cert)| base64::prelude::BASE64_URL_SAFE_NO_PAD.decode(&*cert).ok())
+        .filter(|cert| cert.len() == 32)
+        .ok_or_else(|| KitsuneError::from(format!("invalid url {} : no valid cert", url)))?;
+    Ok((path, cert.into()))
+}
+
+async fn write_bytes<S: AsyncWrite + Unpin>(stream: &mut S, bytes: &[u8]) -> KitsuneResult<()> {
+    let len =
+        u16::try_from(bytes.len()).map_err(|_| KitsuneError::from("header field too long"))?;
+    stream.write_u16(len).await.map_err(local_err)?;
+    stream.write_all(bytes).await.map_err(local_err)
+}
+
+async fn read_bytes<S: AsyncRead + Unpin>(stream: &mut S) -> KitsuneResult<Vec<u8>> {
+    let len = stream.read_u16().await.map_err(local_err)?;
+    let mut bytes = vec![0; len as usize];
+    stream.read_exact(&mut bytes).await.map_err(local_err)?;
+    Ok(bytes)
+}
+
+/// Sent by the opener of a connection over its tls stream, and by the opener
+/// of a channel after the channel kind.
+struct Header {
+    con_id: u64,
+    initiator: PathBuf,
+    /// For a connection, the token its opener issued. For a channel, the
+    /// token its receiver issued for the connection.
+    token: ChanToken,
+}
+
+impl Header {
+    async fn write<S: AsyncWrite + Unpin>(&self, stream: &mut S) -> KitsuneResult<()> {
+        let initiator = self
+            .initiator
+            .to_str()
+            .ok_or_else(|| KitsuneError::from("socket path is not valid utf8"))?;
+        stream.write_u64(self.con_id).await.map_err(local_err)?;
+        write_bytes(stream, initiator.as_bytes()).await?;
+        stream.write_all(&self.token).await.map_err(local_err)?;
+        stream.flush().await.map_err(local_err)
+    }
+
+    async fn read<S: AsyncRead + Unpin>(stream: &mut S) -> KitsuneResult<Self> {
+        let con_id = stream.read_u64().await.map_err(local_err)?;
+        let initiator = String::from_utf8(read_bytes(stream).await?)
+            .map_err(|_| KitsuneError::from("socket path is not valid utf8"))?
+            .into();
+        let mut token = [0; 32];
+        stream.read_exact(&mut token).await.map_err(local_err)?;
+        Ok(Self {
+            con_id,
+            initiator,
+            token,
+        })
+    }
+}
+
+struct LocalInChanRecvAdapt(BoxStream<'static, InChanFut>);
+
+impl LocalInChanRecvAdapt {
+    pub fn new(recv: TReceiver<InChan>, active: Active) -> Self {
+        Self(
+            futures::stream::unfold((recv, active), move |(mut recv, active)| async move {
+                let fut = active.fut(async move {
+                    let item = recv
+                        .next()
+                        .await
+                        .ok_or_else(|| KitsuneError::from(KitsuneErrorKind::Closed))?;
+                    Ok((item, recv))
+                });
+                match fut.await {
+                    Err(_) => None,
+                    Ok((item, recv)) => Some((async move { Ok(item) }.boxed(), (recv, active))),
+                }
+            })
+            .boxed(),
+        )
+    }
+}
+
+impl futures::stream::Stream for LocalInChanRecvAdapt {
+    type Item = InChanFut;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let inner = &mut self.0;
+        tokio::pin!(inner);
+        futures::stream::Stream::poll_next(inner, cx)
+    }
+}
+
+impl InChanRecvAdapt for LocalInChanRecvAdapt {}
+
+struct LocalConAdapt {
+    uniq: Uniq,
+    dir: Tx2ConDir,
+    key: ConKey,
+    peer_path: PathBuf,
+    peer_addr: TxUrl,
+    peer_cert: Tx2Cert,
+    /// The token the peer issued for this connection.
+    peer_token: ChanToken,
+    con_active: Active,
+    mix_active: Active,
+}
+
+impl ConAdapt for LocalConAdapt {
+    fn uniq(&self) -> Uniq {
+        self.uniq
+    }
+
+    fn dir(&self) -> Tx2ConDir {
+        self.dir
+    }
+
+    fn peer_addr(&self) -> KitsuneResult<TxUrl> {
+        Ok(self.peer_addr.clone())
+    }
+
+    fn peer_cert(&self) -> Tx2Cert {
+        self.peer_cert.clone()
+    }
+
+    fn out_chan(&self, timeout: KitsuneTimeout) -> OutChanFut {
+        let header = Header {
+            con_id: self.key.1,
+            initiator: self.key.0.clone(),
+            token: self.peer_token,
+        };
+        let peer_path = self.peer_path.clone();
+        let mix_active = self.mix_active.clone();
+        async move {
+            if !mix_active.is_active() {
+                return Err(KitsuneErrorKind::Closed.into());
+            }
+            timeout
+                .mix("LocalConAdapt::out_chan", async move {
+                    let mut stream = UnixStream::connect(&peer_path).await.map_err(local_err)?;
+                    check_peer_user(&stream)?;
+                    stream.write_u8(KIND_CHAN).await.map_err(local_err)?;
+                    header.write(&mut stream).await?;
+                    // Nothing is written until the peer has checked the token,
+                    // so a channel can't be delivered to anyone else listening
+                    // at the peer's path.
+                    if stream.read_u8().await.map_err(local_err)? != CHAN_ACCEPTED {
+                        return Err("channel rejected".into());
+                    }
+                    let send: OutChan = Box::new(FramedWriter::new(Box::new(stream.compat())));
+                    Ok(send)
+                })
+                .await
+        }
+        .boxed()
+    }
+
+    fn is_closed(&self) -> bool {
+        !self.mix_active.is_active()
+    }
+
+    fn close(&self, _code: u32, _reason: &str) -> BoxFuture<'static, ()> {
+        self.con_active.kill();
+        async move {}.boxed()
+    }
+}
+
+struct LocalConRecvAdapt(BoxStream<'static, ConFut>);
+
+impl LocalConRecvAdapt {
+    pub fn new(recv: TReceiver<Con>, active: Active) -> Self {
+        Self(
+            futures::stream::unfold((recv, active), move |(mut recv, active)| async move {
+                let fut = active.fut(async move {
+                    let item = recv
+                        .next()
+                        .await
+                        .ok_or_else(|| KitsuneError::from(KitsuneErrorKind::Closed))?;
+                    Ok((item, recv))
+                });
+                match fut.await {
+                    Err(_) => None,
+                    Ok((item, recv)) => Some((async move { Ok(item) }.boxed(), (recv, active))),
+                }
+            })
+            .boxed(),
+        )
+    }
+}
+
+impl futures::stream::Stream for LocalConRecvAdapt {
+    type Item = ConFut;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let inner = &mut self.0;
+        tokio::pin!(inner);
+        futures::stream::Stream::poll_next(inner, cx)
+    }
+}
+
+impl ConRecvAdapt for LocalConRecvAdapt {}
+
+/// A connection as the endpoint's listener sees it.
+struct LocalConEntry {
+    chan_send: TSender<InChan>,
+    /// The token this endpoint issued for the connection.
+    token: ChanToken,
+}
+
+/// State shared between an endpoint and its listener task.
+struct LocalEndpointShared {
+    path: PathBuf,
+    url: TxUrl,
+    local_cert: Tx2Cert,
+    tls_acceptor: TlsAcceptor,
+    tls_connector: TlsConnector,
+    timeout: std::time::Duration,
+    ep_active: Active,
+    cons: Mutex<HashMap<ConKey, LocalConEntry>>,
+}
+
+impl LocalEndpointShared {
+    /// Register a connection established on `con_stream`.
+    #[allow(clippy::too_many_arguments)]
+    fn new_con(
+        self: &Arc<Self>,
+        dir: Tx2ConDir,
+        key: ConKey,
+        peer_path: PathBuf,
+        peer_cert: Tx2Cert,
+        peer_token: ChanToken,
+        token: ChanToken,
+        mut con_stream: TlsStream<UnixStream>,
+    ) -> KitsuneResult<Con> {
+        let peer_addr = path_to_url(&peer_path, &peer_cert)?;
+        let con_active = Active::new();
+        let mix_active = con_active.mix(&self.ep_active);
+        let (chan_send, chan_recv) = t_chan(32);
+        {
+            let mut cons = self.cons.lock();
+            if cons.contains_key(&key) {
+                return Err("duplicate connection".into());
+            }
+            cons.insert(key.clone(), LocalConEntry { chan_send, token });
+        }
+
+        // Nothing is sent on the connection stream after the handshake,
+        // so it only completes when either side goes away.
+        {
+            let this = self.clone();
+            let key = key.clone();
+            let con_active = con_active.clone();
+            let mix_active = mix_active.clone();
+            tokio::task::spawn(async move {
+                let _ = mix_active
+                    .fut(async move {
+                        let mut buf = [0; 1];
+                        let _ = con_stream.read(&mut buf).await;
+                        Ok(())
+                    })
+                    .await;
+                con_active.kill();
+                if let Some(entry) = this.cons.lock().remove(&key) {
+                    entry.chan_send.close_channel();
+                }
+            });
+        }
+
+        let con: Arc<dyn ConAdapt> = Arc::new(LocalConAdapt {
+            uniq: Uniq::default(),
+            dir,
+            key,
+            peer_path,
+            peer_addr,
+            peer_cert,
+            peer_token,
+            con_active,
+            mix_active: mix_active.clone(),
+        });
+        let chan_recv: Box<dyn InChanRecvAdapt> =
+            Box::new(LocalInChanRecvAdapt::new(chan_recv, mix_active));
+        Ok((con, chan_recv))
+    }
+
+    async fn handle_incoming(
+        self: Arc<Self>,
+        mut stream: UnixStream,
+        c_send: TSender<Con>,
+    ) -> KitsuneResult<()> {
+        check_peer_user(&stream)?;
+        match stream.read_u8().await.map_err(local_err)? {
+            KIND_CON => {
+                let mut tls: TlsStream<UnixStream> = self
+                    .tls_acceptor
+                    .accept(stream)
+                    .await
+                    .map_err(local_err)?
+                    .into();
+                let peer_cert = peer_cert_digest(&tls).await?;
+                let header = Header::read(&mut tls).await?;
+                let token = random_token().await?;
+                tls.write_all(&token).await.map_err(local_err)?;
+                tls.flush().await.map_err(local_err)?;
+                let con = self.new_con(
+                    Tx2ConDir::Incoming,
+                    (header.initiator.clone(), header.con_id),
+                    header.initiator,
+                    peer_cert,
+                    header.token,
+                    token,
+                    tls,
+                )?;
+                tracing::debug!(url = %self.url, "incoming connection (local)");
+                c_send
+                    .send(con)
+                    .await
+                    .map_err(|_| KitsuneError::from(KitsuneErrorKind::Closed))
+            }
+            KIND_CHAN => {
+                let header = Header::read(&mut stream).await?;
+                let key = (header.initiator, header.con_id);
+                let chan_send = {
+                    let cons = self.cons.lock();
+                    let entry = cons
+                        .get(&key)
+                        .ok_or_else(|| KitsuneError::from("channel for unknown connection"))?;
+                    if entry.token != header.token {
+                        return Err("channel with invalid token".into());
+                    }
+                    entry.chan_send.clone()
+                };
+                stream.write_u8(CHAN_ACCEPTED).await.map_err(local_err)?;
+                let chan: InChan = Box::new(FramedReader::new(Box::new(stream.compat())));
+                chan_send
+                    .send(chan)
+                    .await
+                    .map_err(|_| KitsuneError::from(KitsuneErrorKind::Closed))
+            }
+            kind => Err(format!("invalid stream kind {}", kind).into()),
+        }
+    }
+}
+
+impl Drop for LocalEndpointShared {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+struct LocalEndpointAdapt(Arc<LocalEndpointShared>, TSender<Con>, Uniq);
+
+impl EndpointAdapt for LocalEndpointAdapt {
+    fn debug(&self) -> serde_json::Value {
+        if self.0.ep_active.is_active() {
+            serde_json::json!({
+                "type": "tx2_local",
+                "state": "open",
+                "addr": &self.0.url,
+                "connections": self.0.cons.lock().len(),
+            })
+        } else {
+            serde_json::json!({
+                "type": "tx2_local",
+                "state": "closed",
+            })
+        }
+    }
+
+    fn uniq(&self) -> Uniq {
+        self.2
+    }
+
+    fn local_addr(&self) -> KitsuneResult<TxUrl> {
+        if !self.0.ep_active.is_active() {
+            return Err(KitsuneErrorKind::Closed.into());
+        }
+        Ok(self.0.url.clone())
+    }
+
+    fn local_cert(&self) -> Tx2Cert {
+        self.0.local_cert.clone()
+    }
+
+    fn connect(&self, url: TxUrl, timeout: KitsuneTimeout) -> ConFut {
+        let shared = self.0.clone();
+        async move {
+            if !shared.ep_active.is_active() {
+                return Err(KitsuneErrorKind::Closed.into());
+            }
+            let (peer_path, expected_cert) = url_to_path(&url)?;
+            let con_id = NEXT_LOCAL_ID.fetch_add(1, atomic::Ordering::Relaxed);
+            let token = random_token().await?;
+            let header = Header {
+                con_id,
+                initiator: shared.path.clone(),
+                token,
+            };
+            let (tls, peer_cert, peer_token) = timeout
+                .mix("LocalEndpointAdapt::connect", async {
+                    let mut stream = UnixStream::connect(&peer_path).await.map_err(|e| {
+                        KitsuneError::from(format!("remote not found: {} : {:?}", url, e))
+                    })?;
+                    check_peer_user(&stream)?;
+                    stream.write_u8(KIND_CON).await.map_err(local_err)?;
+                    let server_name = rustls::ServerName::try_from("stub.stub").unwrap();
+                    let mut tls: TlsStream<UnixStream> = shared
+                        .tls_connector
+                        .connect(server_name, stream)
+                        .await
+                        .map_err(local_err)?
+                        .into();
+                    let peer_cert = peer_cert_digest(&tls).await?;
+                    if peer_cert != expected_cert {
+                        return Err(format!(
+                            "remote {} holds certificate {:?}, not {:?}",
+                            url, peer_cert, expected_cert
+                        )
+                        .into());
+                    }
+                    header.write(&mut tls).await?;
+                    let mut peer_token = [0; 32];
+                    tls.read_exact(&mut peer_token).await.map_err(local_err)?;
+                    Ok((tls, peer_cert, peer_token))
+                })
+                .await?;
+
+            let key = (shared.path.clone(), con_id);
+            let con = shared.new_con(
+                Tx2ConDir::Outgoing,
+                key,
+                peer_path,
+                peer_cert,
+                peer_token,
+                token,
+                tls,
+            )?;
+            tracing::debug!(%url, "outgoing connection (local)");
+            Ok(con)
+        }
+        .boxed()
+    }
+
+    fn is_closed(&self) -> bool {
+        !self.0.ep_active.is_active()
+    }
+
+    fn close(&self, _code: u32, _reason: &str) -> BoxFuture<'static, ()> {
+        self.0.ep_active.kill();
+        self.1.close_channel();
+        let _ = std::fs::remove_file(&self.0.path);
+        async move {}.boxed()
+    }
+}
+
+/// Unix domain socket endpoint adapter for kitsune tx2.
+struct LocalBackendAdapt {
+    local_cert: Tx2Cert,
+    socket_dir: PathBuf,
+    tls_acceptor: TlsAcceptor,
+    tls_connector: TlsConnector,
+    timeout: std::time::Duration,
+}
+
+impl LocalBackendAdapt {
+    /// Construct a new unix domain socket endpoint adapter for kitsune tx2.
+    pub async fn new(config: LocalConfig) -> KitsuneResult<AdapterFactory> {
+        let (tls, tuning_params, socket_dir) = config.split().await?;
+        let (tls_srv, tls_cli) =
+            gen_tls_configs(ALPN_KITSUNE_LOCAL_0, &tls, tuning_params.clone())?;
+        let out: AdapterFactory = Arc::new(Self {
+            local_cert: tls.cert_digest.into(),
+            socket_dir,
+            tls_acceptor: tls_srv.into(),
+            tls_connector: tls_cli.into(),
+            timeout: tuning_params.implicit_timeout().time_remaining(),
+        });
+        Ok(out)
+    }
+}
+
+impl BindAdapt for LocalBackendAdapt {
+    fn bind(&self, _url: TxUrl, timeout: KitsuneTimeout) -> EndpointFut {
+        let local_cert = self.local_cert.clone();
+        let socket_dir = self.socket_dir.clone();
+        let tls_acceptor = self.tls_acceptor.clone();
+        let tls_connector = self.tls_connector.clone();
+        let stream_timeout = self.timeout;
+        timeout
+            .mix("LocalBackendAdapt::bind", async move {
+                let path = tokio::task::spawn_blocking(move || {
+                    prepare_socket_dir(&socket_dir)?;
+                    let id = NEXT_LOCAL_ID.fetch_add(1, atomic::Ordering::SeqCst);
+                    let path = socket_dir.join(format!("{}-{}.sock", std::process::id(), id));
+                    // A socket left behind by an earlier process with the same pid.
+                    let _ = std::fs::remove_file(&path);
+                    KitsuneResult::Ok(path)
+                })
+                .await
+                .map_err(KitsuneError::other)??;
+                let listener = UnixListener::bind(&path).map_err(local_err)?;
+
+                let shared = Arc::new(LocalEndpointShared {
+                    url: path_to_url(&path, &local_cert)?,
+                    path,
+                    local_cert,
+                    tls_acceptor,
+                    tls_connector,
+                    timeout: stream_timeout,
+                    ep_active: Active::new(),
+                    cons: Mutex::new(HashMap::new()),
+                });
+                let (c_send, c_recv) = t_chan(32);
+
+                {
+                    let shared = shared.clone();
+                    let c_send = c_send.clone();
+                    let ep_active = shared.ep_active.clone();
+                    tokio::task::spawn(ep_active.fut(async move {
+                        while let Ok((stream, _)) = listener.accept().await {
+                            let shared = shared.clone();
+                            let c_send = c_send.clone();
+                            tokio::task::spawn(async move {
+                                let timeout = shared.timeout;
+                                match tokio::time::timeout(
+                                    timeout,
+                                    shared.handle_incoming(stream, c_send),
+                                )
+                                .await
+                                {
+                                    Ok(Ok(())) => (),
+                                    Ok(Err(err)) => {
+                                        tracing::debug!(?err, "failed to accept local stream")
+                                    }
+                                    Err(_) => tracing::debug!("timed out accepting local stream"),
+                                }
+                            });
+                        }
+                        KitsuneResult::Ok(())
+                    }));
+                }
+
+                tracing::info!(url = %shared.url, "bound local endpoint (local)");
+                let rc: Box<dyn ConRecvAdapt> =
+                    Box::new(LocalConRecvAdapt::new(c_recv, shared.ep_active.clone()));
+                let ep: Arc<dyn EndpointAdapt> =
+                    Arc::new(LocalEndpointAdapt(shared, c_send, Uniq::default()));
+                Ok((ep, rc))
+            })
+            .boxed()
+    }
+
+    fn local_cert(&self) -> Tx2Cert {
+        self.local_cert.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A socket dir private to the test, in a temp dir which may not be.
+    fn test_socket_dir() -> (tempfile::TempDir, PathBuf) {
+        let tmp = tempfile::tempdir().unwrap();
+        let socket_dir = tmp.path().join("sockets");
+        (tmp, socket_dir)
+    }
+
+    async fn bind_pair(
+        socket_dir: &Path,
+    ) -> (
+        (Arc<dyn EndpointAdapt>, Box<dyn ConRecvAdapt>),
+        (Arc<dyn EndpointAdapt>, Box<dyn ConRecvAdapt>),
+    ) {
+        let t = KitsuneTimeout::from_millis(5000);
+        let mut out = Vec::new();
+        for _ in 0..2 {
+            let config = LocalConfig {
+                socket_dir: Some(socket_dir.to_owned()),
+                ..Default::default()
+            };
+            let back = LocalBackendAdapt::new(config).await.unwrap();
+            out.push(back.bind("none:".try_into().unwrap(), t).await.unwrap());
+        }
+        let ep2 = out.pop().unwrap();
+        let ep1 = out.pop().unwrap();
+        (ep1, ep2)
+    }
+
+    #[test]
+    fn local_url_round_trip() {
+        let path = PathBuf::from("/tmp/kitsune local/123-4.sock");
+        let cert: Tx2Cert = vec![0xdb; 32].into();
+        let url = path_to_url(&path, &cert).unwrap();
+        assert_eq!(LOCAL_SCHEME, url.scheme());
+        assert_eq!((path, cert), url_to_path(&url).unwrap());
+
+        let mem = TxUrl::from_str_panicking("kitsune-mem://1");
+        assert!(url_to_path(&mem).is_err());
+
+        let no_cert = TxUrl::from_str_panicking("kitsune-local://localhost/tmp/1-1.sock");
+        assert!(url_to_path(&no_cert).is_err());
+    }
+
+    #[test]
+    fn socket_dir_must_be_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (_tmp, dir) = test_socket_dir();
+        prepare_socket_dir(&dir).unwrap();
+        let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+        assert_eq!(0, mode & 0o077);
+        // An existing private directory is used as it is.
+        prepare_socket_dir(&dir).unwrap();
+
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(prepare_socket_dir(&dir).is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_tx2_local() {
+        let t = KitsuneTimeout::from_millis(5000);
+
+        let (_tmp, socket_dir) = test_socket_dir();
+        let ((ep1, _con_recv1), (ep2, mut con_recv2)) = bind_pair(&socket_dir).await;
+        let ep1_cert = ep1.local_cert();
+
+        let rt = metric_task(async move {
+            let (con2, mut chan_recv2) = con_recv2.next().await.unwrap().await.unwrap();
+            assert!(matches!(con2.dir(), Tx2ConDir::Incoming));
+            assert_eq!(ep1_cert, con2.peer_cert());
+            let mut in_chan = chan_recv2.next().await.unwrap().await.unwrap();
+            let (_, mut buf) = in_chan.read(t).await.unwrap();
+            assert_eq!(b"hello", &buf[..]);
+            buf.clear();
+            buf.extend_from_slice(b"world");
+            let mut out_chan = con2.out_chan(t).await.unwrap();
+            out_chan.write(0.into(), buf, t).await.unwrap();
+            KitsuneResult::Ok(())
+        });
+
+        let addr2 = ep2.local_addr().unwrap();
+        let (con1, mut chan_recv1) = ep1.connect(addr2.clone(), t).await.unwrap();
+        assert_eq!(addr2, con1.peer_addr().unwrap());
+        assert_eq!(ep2.local_cert(), con1.peer_cert());
+
+        let mut out_chan = con1.out_chan(t).await.unwrap();
+        let mut buf = PoolBuf::new();
+        buf.extend_from_slice(b"hello");
+        out_chan.write(0.into(), buf, t).await.unwrap();
+
+        let mut in_chan = chan_recv1.next().await.unwrap().await.unwrap();
+        let (_, buf) = in_chan.read(t).await.unwrap();
+        assert_eq!(b"world", &buf[..]);
+
+        rt.await.unwrap().unwrap();
+
+        ep1.close(0, "").await;
+        ep2.close(0, "").await;
+        assert!(con1.is_closed());
+        assert_eq!(0, std::fs::read_dir(&socket_dir).unwrap().count());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn local_connect_checks_the_peer_cert() {
+        let t = KitsuneTimeout::from_millis(5000);
+
+        let (_tmp, socket_dir) = test_socket_dir();
+        let ((ep1, _con_recv1), (ep2, _con_recv2)) = bind_pair(&socket_dir).await;
+
+        // The url of the second endpoint's socket, but with the first
+        // endpoint's cert.
+        let (path2, _) = url_to_path(&ep2.local_addr().unwrap()).unwrap();
+        let url = path_to_url(&path2, &ep1.local_cert()).unwrap();
+        assert!(ep1.connect(url, t).await.is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn local_channel_needs_the_con_token() {
+        let t = KitsuneTimeout::from_millis(5000);
+
+        let (_tmp, socket_dir) = test_socket_dir();
+        let ((ep1, _con_recv1), (ep2, mut con_recv2)) = bind_pair(&socket_dir).await;
+
+        let (_con1, _chan_recv1) = ep1.connect(ep2.local_addr().unwrap(), t).await.unwrap();
+        let (con2, _chan_recv2) = con_recv2.next().await.unwrap().await.unwrap();
+
+        // Try opening a channel without the token for every connection id
+        // handed out so far, which includes the id of this connection.
+        let (path1, _) = url_to_path(&ep1.local_addr().unwrap()).unwrap();
+        let (path2, _) = url_to_path(&ep2.local_addr().unwrap()).unwrap();
+        for con_id in 0..NEXT_LOCAL_ID.load(atomic::Ordering::SeqCst) {
+            let mut stream = UnixStream::connect(&path2).await.unwrap();
+            stream.write_u8(KIND_CHAN).await.unwrap();
+            Header {
+                con_id,
+                initiator: path1.clone(),
+                token: [0; 32],
+            }
+            .write(&mut stream)
+            .await
+            .unwrap();
+            // The stream is closed instead of accepted.
+            assert!(stream.read_u8().await.is_err());
+        }
+        assert!(!con2.is_closed());
+    }
+}
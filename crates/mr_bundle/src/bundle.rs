@@ -269,4 +269,23 @@ mod tests {
             Err(MrBundleError::BundleError(BundleError::BundledPathNotInManifest(path))) if path == PathBuf::from("3.thing")
         );
     }
+
+    #[test]
+    fn encoding_is_deterministic() {
+        let manifest = TestManifest(vec![
+            Location::Bundled("1.thing".into()),
+            Location::Bundled("2.thing".into()),
+        ]);
+        let one = ("1.thing".into(), vec![1].into());
+        let two = ("2.thing".into(), vec![2].into());
+
+        let bundle =
+            Bundle::new_unchecked(manifest.clone(), vec![one.clone(), two.clone()]).unwrap();
+        let reordered = Bundle::new_unchecked(manifest, vec![two, one]).unwrap();
+        let encoded = bundle.encode().unwrap();
+        assert_eq!(encoded, reordered.encode().unwrap());
+
+        // The gzip header has no modification time.
+        assert_eq!(encoded[4..8], [0, 0, 0, 0]);
+    }
 }
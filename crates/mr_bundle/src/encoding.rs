@@ -4,10 +4,23 @@ use super::error::MrBundleResult;
 use std::io::Read;
 use std::io::Write;
 
+/// The compression level used for encoding. This is fixed rather than left to
+/// the default of the compression library, so that the encoding is stable.
+const COMPRESSION_LEVEL: u32 = 6;
+
+/// The gzip header value for an unknown operating system.
+const GZIP_OS_UNKNOWN: u8 = 255;
+
 /// Get compressed bytes from some serializable data
+///
+/// The encoding is deterministic: the gzip header carries no timestamp or
+/// operating system, so encoding the same data always yields the same bytes.
 pub fn encode<T: serde::ser::Serialize>(data: &T) -> MrBundleResult<Vec<u8>> {
     let bytes = rmp_serde::to_vec_named(data)?;
-    let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    let mut enc = flate2::GzBuilder::new()
+        .mtime(0)
+        .operating_system(GZIP_OS_UNKNOWN)
+        .write(Vec::new(), flate2::Compression::new(COMPRESSION_LEVEL));
     enc.write_all(&bytes)?;
     Ok(enc.finish()?)
 }
@@ -67,11 +67,53 @@ fn create_post(post: Post) -> ExternResult<ActionHash> {
     ))
 }
 
+#[hdk_extern]
+fn ensure_post(post: Post) -> ExternResult<ActionHash> {
+    hdk::prelude::ensure_entry(&EntryZomes::IntegrityCreateEntry(
+        crate::integrity::EntryTypes::Post(post),
+    ))
+}
+
 #[hdk_extern]
 fn delete_post(post_hash: ActionHash) -> ExternResult<ActionHash> {
     hdk::prelude::delete_entry(post_hash)
 }
 
+#[hdk_extern]
+fn create_blob(bytes: Vec<u8>) -> ExternResult<ActionHash> {
+    hdk::prelude::create_blob(&bytes, EntryTypes::BlobManifest)
+}
+
+#[hdk_extern]
+fn read_blob(manifest: ActionHash) -> ExternResult<Option<Vec<u8>>> {
+    match get(manifest, GetOptions::local())?
+        .as_ref()
+        .and_then(|record| record.action().entry_hash())
+    {
+        Some(entry_hash) => hdk::prelude::read_blob(entry_hash.clone()),
+        None => Ok(None),
+    }
+}
+
+#[hdk_extern]
+fn delete_blob(manifest: ActionHash) -> ExternResult<ActionHash> {
+    hdk::prelude::delete_blob(manifest)
+}
+
+#[hdk_extern]
+fn get_blob_chunk(chunk: EntryHash) -> ExternResult<Option<Vec<u8>>> {
+    hdk::prelude::get_blob_chunk(chunk)
+}
+
+#[hdk_extern]
+fn get_posts_by_author(author: AgentPubKey) -> ExternResult<Vec<Record>> {
+    hdk::prelude::get_entries_by_author(
+        author,
+        EntryTypesUnit::Post.try_into()?,
+        EntriesPage::default(),
+    )
+}
+
 #[hdk_extern]
 fn get_entry(_: ()) -> ExternResult<Option<Record>> {
     get(hash_entry(&post())?, GetOptions::local())
@@ -18,6 +18,7 @@ pub enum EntryTypes {
     Msg(Msg),
     #[entry_type(required_validations = 5, visibility = "private")]
     PrivMsg(PrivMsg),
+    BlobManifest(BlobManifest),
 }
 
 #[hdk_link_types]
@@ -57,6 +58,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     Some(EntryTypes::Post(post)) if post.0 == "Banana" => {
                         Ok(ValidateCallbackResult::Invalid("No Bananas!".to_string()))
                     }
+                    Some(EntryTypes::BlobManifest(manifest)) => validate_blob_manifest(&manifest),
                     _ => Ok(ValidateCallbackResult::Valid),
                 },
             )
@@ -73,6 +73,12 @@ fn create_tagged_link(tag: String) -> ExternResult<ActionHash> {
     )
 }
 
+#[hdk_extern]
+fn create_payload_link(payload: LinkTagPayload) -> ExternResult<ActionHash> {
+    let tag = LinkTag::try_from(&payload).map_err(|e| wasm_error!(e))?;
+    hdk::prelude::create_link(base()?, target()?, LinkTypes::SomeLinks, tag)
+}
+
 #[hdk_extern]
 fn delete_link(input: ActionHash) -> ExternResult<ActionHash> {
     hdk::prelude::delete_link(input)
@@ -217,6 +223,37 @@ fn delete_all_links(_: ()) -> ExternResult<()> {
     Ok(())
 }
 
+#[hdk_extern]
+fn create_many_links(count: u32) -> ExternResult<Vec<ActionHash>> {
+    let base = path("many")?;
+    let target = target()?;
+    hdk::prelude::create_links((0..count).map(|i| {
+        (
+            base.clone(),
+            target.clone(),
+            LinkTypes::SomeLinks,
+            i.to_le_bytes().to_vec(),
+        )
+    }))
+}
+
+#[hdk_extern]
+fn get_many_links(_: ()) -> ExternResult<Vec<Link>> {
+    hdk::prelude::get_links(
+        GetLinksInputBuilder::try_new(path("many")?, LinkTypes::SomeLinks)?.build(),
+    )
+}
+
+#[hdk_extern]
+fn delete_many_links(input: Vec<ActionHash>) -> ExternResult<Vec<ActionHash>> {
+    hdk::prelude::delete_links(input)
+}
+
+#[hdk_extern]
+fn ensure_link(_: ()) -> ExternResult<ActionHash> {
+    hdk::prelude::ensure_link(base()?, target()?, LinkTypes::SomeLinks, ())
+}
+
 /// Same as path.ensure() but doesn't check for
 /// exists. This can happen when ensuring paths
 /// in partitions so this test just shows that it's safe to do so.
@@ -264,6 +301,11 @@ fn get_links_with_query(input: GetLinksInput) -> ExternResult<Vec<Link>> {
     Ok(hdk::prelude::get_links(input)?)
 }
 
+#[hdk_extern]
+fn get_links_delta_with_query(input: GetLinksInput) -> ExternResult<LinksDelta> {
+    hdk::prelude::get_links_delta(input)
+}
+
 #[hdk_extern]
 fn get_time(_: ()) -> ExternResult<Timestamp> {
     sys_time()
@@ -5,6 +5,11 @@ fn sys_time(_: ()) -> ExternResult<Timestamp> {
     hdk::prelude::sys_time()
 }
 
+#[hdk_extern]
+fn network_time(_: ()) -> ExternResult<NetworkTime> {
+    hdk::prelude::network_time()
+}
+
 #[cfg(all(test, feature = "mock"))]
 pub mod test {
     use hdk::prelude::*;
@@ -88,6 +88,22 @@ fn who_are_they_role(role_name: RoleName) -> ExternResult<AgentInfo> {
     }
 }
 
+#[hdk_extern]
+fn who_are_they_clone(clone_id: CloneId) -> ExternResult<AgentInfo> {
+    let zome_call_response: ZomeCallResponse = call(
+        CallTargetCell::OtherClone(clone_id),
+        zome_info()?.name,
+        "whoami".to_string().into(),
+        None,
+        &(),
+    )?;
+    match zome_call_response {
+        ZomeCallResponse::Ok(v) => Ok(v.decode().map_err(|e| wasm_error!(e))?),
+        // This should be handled in real code.
+        _ => unreachable!(),
+    }
+}
+
 /// Call the create entry zome from this zome.
 /// The cell id must point to a cell which includes
 /// the "create_entry" zome.
@@ -106,3 +122,46 @@ fn call_create_entry(cell_id: CellId) -> ExternResult<ActionHash> {
         _ => unreachable!(),
     }
 }
+
+/// Allow "whoami" and "set_access" to be queried, but not called.
+#[hdk_extern]
+fn set_query_access(_: ()) -> ExternResult<()> {
+    let mut fns = BTreeSet::new();
+    fns.insert((zome_info()?.name, "whoami".into()));
+    fns.insert((zome_info()?.name, "set_access".into()));
+    create_cap_grant(CapGrantEntry {
+        tag: "".into(),
+        access: ().into(),
+        functions: GrantedFunctions::Queries(fns),
+    })?;
+
+    Ok(())
+}
+
+#[hdk_extern]
+fn query_whoami(agent_pubkey: AgentPubKey) -> ExternResult<ZomeCallResponse> {
+    remote_query(
+        agent_pubkey,
+        zome_info()?.name,
+        "whoami".to_string().into(),
+        None,
+        &(),
+    )
+}
+
+/// Attempt a write on the remote chain through a query, which must fail.
+#[hdk_extern]
+fn query_set_access(agent_pubkey: AgentPubKey) -> ExternResult<ZomeCallResponse> {
+    remote_query(
+        agent_pubkey,
+        zome_info()?.name,
+        "set_access".to_string().into(),
+        None,
+        &(),
+    )
+}
+
+#[hdk_extern]
+fn query_own_chain(_: ()) -> ExternResult<Vec<Record>> {
+    query(ChainQueryFilter::new())
+}